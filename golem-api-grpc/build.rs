@@ -33,6 +33,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/golem/common/plugin_installation_id.proto",
                 "proto/golem/common/resource_limits.proto",
                 "proto/golem/common/uuid.proto",
+                "proto/golem/common/watch_event_type.proto",
                 "proto/golem/component/producer_field.proto",
                 "proto/golem/component/producers.proto",
                 "proto/golem/component/export.proto",
@@ -64,6 +65,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "proto/golem/worker/worker_filter.proto",
                 "proto/golem/worker/worker_status.proto",
                 "proto/golem/worker/v1/worker_service.proto",
+                "proto/golem/worker/v1/worker_schedule_service.proto",
                 "proto/golem/worker/v1/worker_execution_error.proto",
                 "proto/golem/worker/v1/worker_error.proto",
                 "proto/golem/workerexecutor/v1/worker_executor.proto",
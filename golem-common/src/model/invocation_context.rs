@@ -22,6 +22,7 @@ use serde::de::Error;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::num::{NonZeroU128, NonZeroU64};
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
@@ -94,6 +95,11 @@ pub enum AttributeValue {
     String(String),
 }
 
+/// The attribute of a span holding the deadline of the invocation as an ISO 8601 timestamp.
+/// Long-running host calls of the invoked worker, and the invocations it makes through RPC,
+/// do not last longer than the earliest deadline of the spans of the invocation.
+pub const DEADLINE_ATTRIBUTE: &str = "golem.deadline";
+
 #[derive(Debug)]
 pub enum InvocationContextSpan {
     Local {
@@ -160,7 +166,13 @@ impl InvocationContextSpan {
     }
 
     pub fn start_span(self: &Arc<Self>, span_id: Option<SpanId>) -> Arc<Self> {
-        Self::new(span_id)
+        let span_id = span_id.unwrap_or(SpanId::generate());
+        Arc::new(Self::Local {
+            span_id,
+            parent: Some(self.clone()),
+            start: Timestamp::now_utc(),
+            attributes: RwLock::new(HashMap::new()),
+        })
     }
 
     pub fn get_attribute(&self, key: &str, inherit: bool) -> Option<AttributeValue> {
@@ -259,6 +271,12 @@ impl InvocationContextSpan {
         }
     }
 
+    pub fn deadline(&self) -> Option<Timestamp> {
+        match self.get_attribute(DEADLINE_ATTRIBUTE, false)? {
+            AttributeValue::String(value) => Timestamp::from_str(&value).ok(),
+        }
+    }
+
     pub fn set_attribute(&self, key: String, value: AttributeValue) {
         match self {
             Self::Local { attributes, .. } => {
@@ -409,8 +427,27 @@ impl InvocationContextStack {
         }
     }
 
+    /// Pushes a span on the stack, making it the current span. The spans of the stack are
+    /// ordered from the current one to the root one.
     pub fn push(&mut self, span: Arc<InvocationContextSpan>) {
-        self.spans.push(span);
+        let mut spans = NEVec::new(span);
+        for span in self.spans.iter() {
+            spans.push(span.clone());
+        }
+        self.spans = spans;
+    }
+
+    pub fn current_span_id(&self) -> &SpanId {
+        self.spans.first().span_id()
+    }
+
+    pub fn parent_span_id(&self) -> Option<&SpanId> {
+        self.spans.iter().nth(1).map(|span| span.span_id())
+    }
+
+    /// The earliest deadline of the spans of the stack
+    pub fn deadline(&self) -> Option<Timestamp> {
+        self.spans.iter().filter_map(|span| span.deadline()).min()
     }
 }
 
@@ -0,0 +1,222 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::invocation_context::{SpanId, TraceId};
+use crate::model::{IdempotencyKey, Timestamp, WorkerId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Enum))]
+pub enum TracedInvocationStatus {
+    /// The invocation has not completed yet
+    Running,
+    Completed,
+    /// The last attempt of the invocation failed, it may still be retried
+    Failed,
+}
+
+// An invocation of a worker recorded in its oplog as part of a trace, with the spans of its
+// invocation context from the nearest to the root one
+#[derive(Clone, Debug, PartialEq)]
+pub struct TracedInvocation {
+    pub worker_id: WorkerId,
+    pub idempotency_key: IdempotencyKey,
+    pub function_name: String,
+    pub span_id: SpanId,
+    pub ancestor_span_ids: Vec<SpanId>,
+    pub started_at: Timestamp,
+    pub completed_at: Option<Timestamp>,
+    pub status: TracedInvocationStatus,
+    pub error: Option<String>,
+}
+
+/// An invocation of a worker belonging to a trace
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "poem", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct InvocationTreeNode {
+    pub worker_id: WorkerId,
+    pub idempotency_key: IdempotencyKey,
+    pub function_name: String,
+    pub span_id: String,
+    /// The span of the invocation which made this one through worker-to-worker RPC, missing for
+    /// the invocations made by the gateway or the API
+    pub parent_span_id: Option<String>,
+    /// The number of invocations between this one and the root of its tree
+    pub depth: u32,
+    pub started_at: Timestamp,
+    pub completed_at: Option<Timestamp>,
+    pub status: TracedInvocationStatus,
+    pub error: Option<String>,
+}
+
+/// The invocations of the workers belonging to a trace, in depth-first order. The invocations
+/// made by the gateway or the API are the roots of the tree, ordered by their start, and are
+/// followed by the invocations they made, recursively.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "poem", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct InvocationTree {
+    pub trace_id: String,
+    pub invocations: Vec<InvocationTreeNode>,
+}
+
+impl InvocationTree {
+    pub fn new(trace_id: &TraceId, invocations: Vec<TracedInvocation>) -> Self {
+        let by_span_id: HashMap<&SpanId, usize> = invocations
+            .iter()
+            .enumerate()
+            .map(|(index, invocation)| (&invocation.span_id, index))
+            .collect();
+
+        // The parent of an invocation is the nearest invocation among the spans of its context,
+        // the spans in between being the ones started by the parent before calling the worker
+        let parents: Vec<Option<usize>> = invocations
+            .iter()
+            .enumerate()
+            .map(|(index, invocation)| {
+                invocation
+                    .ancestor_span_ids
+                    .iter()
+                    .filter_map(|span_id| by_span_id.get(span_id).copied())
+                    .find(|parent| *parent != index)
+            })
+            .collect();
+
+        let mut children: HashMap<Option<usize>, Vec<usize>> = HashMap::new();
+        for (index, parent) in parents.iter().enumerate() {
+            children.entry(*parent).or_default().push(index);
+        }
+        for siblings in children.values_mut() {
+            siblings.sort_by_key(|index| invocations[*index].started_at);
+        }
+
+        let mut ordered = Vec::with_capacity(invocations.len());
+        let mut visited = HashSet::new();
+        let mut stack: Vec<(usize, u32)> = children
+            .get(&None)
+            .map(|roots| roots.iter().rev().map(|root| (*root, 0)).collect())
+            .unwrap_or_default();
+
+        while let Some((index, depth)) = stack.pop() {
+            if !visited.insert(index) {
+                continue;
+            }
+            ordered.push((index, depth));
+            if let Some(siblings) = children.get(&Some(index)) {
+                stack.extend(siblings.iter().rev().map(|child| (*child, depth + 1)));
+            }
+        }
+
+        let nodes = ordered
+            .into_iter()
+            .map(|(index, depth)| {
+                let invocation = &invocations[index];
+                InvocationTreeNode {
+                    worker_id: invocation.worker_id.clone(),
+                    idempotency_key: invocation.idempotency_key.clone(),
+                    function_name: invocation.function_name.clone(),
+                    span_id: invocation.span_id.to_string(),
+                    parent_span_id: parents[index]
+                        .map(|parent| invocations[parent].span_id.to_string()),
+                    depth,
+                    started_at: invocation.started_at,
+                    completed_at: invocation.completed_at,
+                    status: invocation.status,
+                    error: invocation.error.clone(),
+                }
+            })
+            .collect();
+
+        Self {
+            trace_id: trace_id.to_string(),
+            invocations: nodes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ComponentId;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn invocation(
+        worker_name: &str,
+        span_id: &SpanId,
+        ancestor_span_ids: Vec<SpanId>,
+        started_at: u64,
+    ) -> TracedInvocation {
+        TracedInvocation {
+            worker_id: WorkerId {
+                component_id: ComponentId(Uuid::nil()),
+                worker_name: worker_name.to_string(),
+            },
+            idempotency_key: IdempotencyKey::fresh(),
+            function_name: "golem:it/api.{call}".to_string(),
+            span_id: span_id.clone(),
+            ancestor_span_ids,
+            started_at: Timestamp::from(started_at),
+            completed_at: None,
+            status: TracedInvocationStatus::Completed,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn invocations_are_ordered_depth_first() {
+        let request = SpanId::generate();
+        let [api, cart, user_span, inventory, pricing, retry] =
+            std::array::from_fn(|_| SpanId::generate());
+
+        // The gateway invokes api, which calls cart, which calls inventory from a custom span
+        // and pricing, and the gateway retries api later
+        let invocations = vec![
+            invocation("pricing", &pricing, vec![cart.clone(), api.clone()], 4),
+            invocation("api", &api, vec![request.clone()], 1),
+            invocation(
+                "inventory",
+                &inventory,
+                vec![user_span, cart.clone(), api.clone()],
+                3,
+            ),
+            invocation("api", &retry, vec![request.clone()], 5),
+            invocation("cart", &cart, vec![api.clone(), request], 2),
+        ];
+
+        let tree = InvocationTree::new(&TraceId::generate(), invocations);
+        let nodes = tree
+            .invocations
+            .iter()
+            .map(|node| (node.worker_id.worker_name.as_str(), node.depth))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            nodes,
+            vec![
+                ("api", 0),
+                ("cart", 1),
+                ("inventory", 2),
+                ("pricing", 2),
+                ("api", 0)
+            ]
+        );
+        assert_eq!(tree.invocations[0].parent_span_id, None);
+        assert_eq!(tree.invocations[2].parent_span_id, Some(cart.to_string()));
+        assert_eq!(tree.invocations[4].span_id, retry.to_string());
+    }
+}
@@ -29,7 +29,7 @@ use rand::prelude::IteratorRandom;
 use serde::de::Unexpected;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::ops::Add;
 use std::str::FromStr;
@@ -45,6 +45,7 @@ pub mod component_constraint;
 pub mod component_metadata;
 pub mod exports;
 pub mod invocation_context;
+pub mod invocation_tree;
 pub mod lucene;
 pub mod oplog;
 pub mod plugin;
@@ -496,6 +497,7 @@ pub struct WorkerMetadata {
     pub worker_id: WorkerId,
     pub args: Vec<String>,
     pub env: Vec<(String, String)>,
+    pub wasi_config_vars: BTreeMap<String, String>,
     pub account_id: AccountId,
     pub created_at: Timestamp,
     pub parent: Option<WorkerId>,
@@ -508,6 +510,7 @@ impl WorkerMetadata {
             worker_id,
             args: vec![],
             env: vec![],
+            wasi_config_vars: BTreeMap::new(),
             account_id,
             created_at: Timestamp::now_utc(),
             parent: None,
@@ -594,6 +597,75 @@ pub enum WorkerStatusRecordExtensions {
         active_plugins: HashSet<PluginInstallationId>,
         deleted_regions: DeletedRegions,
     },
+    Extension3 {
+        active_plugins: HashSet<PluginInstallationId>,
+        deleted_regions: DeletedRegions,
+        labels: BTreeMap<String, String>,
+    },
+    Extension4 {
+        active_plugins: HashSet<PluginInstallationId>,
+        deleted_regions: DeletedRegions,
+        labels: BTreeMap<String, String>,
+        compaction: OplogCompactionStatus,
+    },
+    Extension5 {
+        active_plugins: HashSet<PluginInstallationId>,
+        deleted_regions: DeletedRegions,
+        labels: BTreeMap<String, String>,
+        compaction: OplogCompactionStatus,
+        fork: Option<WorkerForkOrigin>,
+    },
+}
+
+/// Keeps track of the snapshots taken of a worker to compact its oplog.
+///
+/// When a snapshot succeeds, the whole history before it is skipped during recovery, and the
+/// entries of this history (except the first one, creating the worker) may be dropped from the
+/// oplog.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub struct OplogCompactionStatus {
+    /// Index of the pending update entry holding the last successfully loaded snapshot
+    pub last_snapshot_index: Option<OplogIndex>,
+    pub last_snapshot_timestamp: Option<Timestamp>,
+    /// Index of the entry recording the last failed attempt to snapshot or update the worker
+    pub last_failed_attempt_index: Option<OplogIndex>,
+    /// Serialized size of the entries added since the last snapshot or failed attempt, counted
+    /// by the oplog when the entries are written
+    pub bytes_since_last_attempt: u64,
+    /// The entries between the first one and this index (exclusive) were dropped from the oplog
+    pub truncated_until: Option<OplogIndex>,
+}
+
+impl OplogCompactionStatus {
+    pub fn last_attempt_index(&self) -> OplogIndex {
+        self.last_snapshot_index
+            .max(self.last_failed_attempt_index)
+            .unwrap_or(OplogIndex::NONE)
+    }
+
+    // The number of entries added since the last snapshot or failed attempt
+    pub fn entries_since_last_attempt(&self, current: OplogIndex) -> u64 {
+        u64::from(current).saturating_sub(u64::from(self.last_attempt_index()))
+    }
+}
+
+/// The point of the history of another worker a worker was forked from.
+///
+/// The oplog of the fork starts as a copy of the oplog of the source worker up to the fork point,
+/// and the fork diverges from the source by the entries added to its oplog after it.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct WorkerForkOrigin {
+    pub source_worker_id: WorkerId,
+    /// Index of the last entry copied from the oplog of the source worker
+    pub forked_at: OplogIndex,
+    pub timestamp: Timestamp,
+}
+
+impl WorkerForkOrigin {
+    // The number of entries added to the oplog of the fork since it was forked
+    pub fn diverged_entries(&self, current: OplogIndex) -> u64 {
+        u64::from(current).saturating_sub(u64::from(self.forked_at))
+    }
 }
 
 impl ::bincode::Decode for WorkerStatusRecord {
@@ -646,6 +718,9 @@ impl WorkerStatusRecord {
         match &self.extensions {
             WorkerStatusRecordExtensions::Extension1 { active_plugins } => active_plugins,
             WorkerStatusRecordExtensions::Extension2 { active_plugins, .. } => active_plugins,
+            WorkerStatusRecordExtensions::Extension3 { active_plugins, .. } => active_plugins,
+            WorkerStatusRecordExtensions::Extension4 { active_plugins, .. } => active_plugins,
+            WorkerStatusRecordExtensions::Extension5 { active_plugins, .. } => active_plugins,
         }
     }
 
@@ -653,6 +728,9 @@ impl WorkerStatusRecord {
         match &mut self.extensions {
             WorkerStatusRecordExtensions::Extension1 { active_plugins } => active_plugins,
             WorkerStatusRecordExtensions::Extension2 { active_plugins, .. } => active_plugins,
+            WorkerStatusRecordExtensions::Extension3 { active_plugins, .. } => active_plugins,
+            WorkerStatusRecordExtensions::Extension4 { active_plugins, .. } => active_plugins,
+            WorkerStatusRecordExtensions::Extension5 { active_plugins, .. } => active_plugins,
         }
     }
 
@@ -662,6 +740,15 @@ impl WorkerStatusRecord {
             WorkerStatusRecordExtensions::Extension2 {
                 deleted_regions, ..
             } => deleted_regions,
+            WorkerStatusRecordExtensions::Extension3 {
+                deleted_regions, ..
+            } => deleted_regions,
+            WorkerStatusRecordExtensions::Extension4 {
+                deleted_regions, ..
+            } => deleted_regions,
+            WorkerStatusRecordExtensions::Extension5 {
+                deleted_regions, ..
+            } => deleted_regions,
         }
     }
 
@@ -671,6 +758,75 @@ impl WorkerStatusRecord {
             WorkerStatusRecordExtensions::Extension2 {
                 deleted_regions, ..
             } => deleted_regions,
+            WorkerStatusRecordExtensions::Extension3 {
+                deleted_regions, ..
+            } => deleted_regions,
+            WorkerStatusRecordExtensions::Extension4 {
+                deleted_regions, ..
+            } => deleted_regions,
+            WorkerStatusRecordExtensions::Extension5 {
+                deleted_regions, ..
+            } => deleted_regions,
+        }
+    }
+
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        match &self.extensions {
+            WorkerStatusRecordExtensions::Extension1 { .. }
+            | WorkerStatusRecordExtensions::Extension2 { .. } => unreachable!(),
+            WorkerStatusRecordExtensions::Extension3 { labels, .. } => labels,
+            WorkerStatusRecordExtensions::Extension4 { labels, .. } => labels,
+            WorkerStatusRecordExtensions::Extension5 { labels, .. } => labels,
+        }
+    }
+
+    pub fn labels_mut(&mut self) -> &mut BTreeMap<String, String> {
+        match &mut self.extensions {
+            WorkerStatusRecordExtensions::Extension1 { .. }
+            | WorkerStatusRecordExtensions::Extension2 { .. } => unreachable!(),
+            WorkerStatusRecordExtensions::Extension3 { labels, .. } => labels,
+            WorkerStatusRecordExtensions::Extension4 { labels, .. } => labels,
+            WorkerStatusRecordExtensions::Extension5 { labels, .. } => labels,
+        }
+    }
+
+    pub fn compaction(&self) -> &OplogCompactionStatus {
+        match &self.extensions {
+            WorkerStatusRecordExtensions::Extension1 { .. }
+            | WorkerStatusRecordExtensions::Extension2 { .. }
+            | WorkerStatusRecordExtensions::Extension3 { .. } => unreachable!(),
+            WorkerStatusRecordExtensions::Extension4 { compaction, .. } => compaction,
+            WorkerStatusRecordExtensions::Extension5 { compaction, .. } => compaction,
+        }
+    }
+
+    pub fn compaction_mut(&mut self) -> &mut OplogCompactionStatus {
+        match &mut self.extensions {
+            WorkerStatusRecordExtensions::Extension1 { .. }
+            | WorkerStatusRecordExtensions::Extension2 { .. }
+            | WorkerStatusRecordExtensions::Extension3 { .. } => unreachable!(),
+            WorkerStatusRecordExtensions::Extension4 { compaction, .. } => compaction,
+            WorkerStatusRecordExtensions::Extension5 { compaction, .. } => compaction,
+        }
+    }
+
+    pub fn fork(&self) -> Option<&WorkerForkOrigin> {
+        match &self.extensions {
+            WorkerStatusRecordExtensions::Extension1 { .. }
+            | WorkerStatusRecordExtensions::Extension2 { .. }
+            | WorkerStatusRecordExtensions::Extension3 { .. }
+            | WorkerStatusRecordExtensions::Extension4 { .. } => unreachable!(),
+            WorkerStatusRecordExtensions::Extension5 { fork, .. } => fork.as_ref(),
+        }
+    }
+
+    pub fn fork_mut(&mut self) -> &mut Option<WorkerForkOrigin> {
+        match &mut self.extensions {
+            WorkerStatusRecordExtensions::Extension1 { .. }
+            | WorkerStatusRecordExtensions::Extension2 { .. }
+            | WorkerStatusRecordExtensions::Extension3 { .. }
+            | WorkerStatusRecordExtensions::Extension4 { .. } => unreachable!(),
+            WorkerStatusRecordExtensions::Extension5 { fork, .. } => fork,
         }
     }
 
@@ -679,16 +835,55 @@ impl WorkerStatusRecord {
     ) -> Result<WorkerStatusRecordExtensions, DecodeError> {
         match result {
             Ok(WorkerStatusRecordExtensions::Extension1 { active_plugins }) => {
-                Ok(WorkerStatusRecordExtensions::Extension2 {
+                Ok(WorkerStatusRecordExtensions::Extension5 {
                     active_plugins,
                     deleted_regions: DeletedRegions::new(),
+                    labels: BTreeMap::new(),
+                    compaction: OplogCompactionStatus::default(),
+                    fork: None,
                 })
             }
-            Ok(ex @ WorkerStatusRecordExtensions::Extension2 { .. }) => Ok(ex),
+            Ok(WorkerStatusRecordExtensions::Extension2 {
+                active_plugins,
+                deleted_regions,
+            }) => Ok(WorkerStatusRecordExtensions::Extension5 {
+                active_plugins,
+                deleted_regions,
+                labels: BTreeMap::new(),
+                compaction: OplogCompactionStatus::default(),
+                fork: None,
+            }),
+            Ok(WorkerStatusRecordExtensions::Extension3 {
+                active_plugins,
+                deleted_regions,
+                labels,
+            }) => Ok(WorkerStatusRecordExtensions::Extension5 {
+                active_plugins,
+                deleted_regions,
+                labels,
+                compaction: OplogCompactionStatus::default(),
+                fork: None,
+            }),
+            Ok(WorkerStatusRecordExtensions::Extension4 {
+                active_plugins,
+                deleted_regions,
+                labels,
+                compaction,
+            }) => Ok(WorkerStatusRecordExtensions::Extension5 {
+                active_plugins,
+                deleted_regions,
+                labels,
+                compaction,
+                fork: None,
+            }),
+            Ok(ex @ WorkerStatusRecordExtensions::Extension5 { .. }) => Ok(ex),
             Err(DecodeError::UnexpectedEnd { .. }) => {
-                Ok(WorkerStatusRecordExtensions::Extension2 {
+                Ok(WorkerStatusRecordExtensions::Extension5 {
                     active_plugins: HashSet::new(),
                     deleted_regions: DeletedRegions::new(),
+                    labels: BTreeMap::new(),
+                    compaction: OplogCompactionStatus::default(),
+                    fork: None,
                 })
             }
             Err(err) => Err(err),
@@ -713,9 +908,12 @@ impl Default for WorkerStatusRecord {
             total_linear_memory_size: 0,
             owned_resources: HashMap::new(),
             oplog_idx: OplogIndex::default(),
-            extensions: WorkerStatusRecordExtensions::Extension2 {
+            extensions: WorkerStatusRecordExtensions::Extension5 {
                 active_plugins: HashSet::new(),
                 deleted_regions: DeletedRegions::new(),
+                labels: BTreeMap::new(),
+                compaction: OplogCompactionStatus::default(),
+                fork: None,
             },
         }
     }
@@ -859,6 +1057,97 @@ impl IntoValue for WorkerStatus {
     }
 }
 
+impl WorkerStatus {
+    pub const ALL: [WorkerStatus; 7] = [
+        WorkerStatus::Running,
+        WorkerStatus::Idle,
+        WorkerStatus::Suspended,
+        WorkerStatus::Interrupted,
+        WorkerStatus::Retrying,
+        WorkerStatus::Failed,
+        WorkerStatus::Exited,
+    ];
+}
+
+/// The component version the workers of a component were most recently created with or
+/// updated to
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "poem", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDeployment {
+    pub component_version: ComponentVersion,
+    pub timestamp: Timestamp,
+}
+
+/// Aggregated status of all the workers of a component
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "poem", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerStatusSummary {
+    pub running: u64,
+    pub idle: u64,
+    pub suspended: u64,
+    pub interrupted: u64,
+    pub retrying: u64,
+    pub failed: u64,
+    pub exited: u64,
+    pub total: u64,
+    /// Ratio of failed workers among all the workers of the component
+    pub failure_rate: f64,
+    pub last_deployment: Option<ComponentDeployment>,
+}
+
+impl WorkerStatusSummary {
+    pub fn new(
+        counts: impl IntoIterator<Item = (WorkerStatus, u64)>,
+        last_deployment: Option<ComponentDeployment>,
+    ) -> Self {
+        let mut summary = Self {
+            last_deployment,
+            ..Self::default()
+        };
+        for (status, count) in counts {
+            *summary.count_mut(&status) += count;
+        }
+        summary.total = WorkerStatus::ALL
+            .iter()
+            .map(|status| summary.count(status))
+            .sum();
+        summary.failure_rate = if summary.total == 0 {
+            0.0
+        } else {
+            summary.failed as f64 / summary.total as f64
+        };
+        summary
+    }
+
+    pub fn count(&self, status: &WorkerStatus) -> u64 {
+        match status {
+            WorkerStatus::Running => self.running,
+            WorkerStatus::Idle => self.idle,
+            WorkerStatus::Suspended => self.suspended,
+            WorkerStatus::Interrupted => self.interrupted,
+            WorkerStatus::Retrying => self.retrying,
+            WorkerStatus::Failed => self.failed,
+            WorkerStatus::Exited => self.exited,
+        }
+    }
+
+    fn count_mut(&mut self, status: &WorkerStatus) -> &mut u64 {
+        match status {
+            WorkerStatus::Running => &mut self.running,
+            WorkerStatus::Idle => &mut self.idle,
+            WorkerStatus::Suspended => &mut self.suspended,
+            WorkerStatus::Interrupted => &mut self.interrupted,
+            WorkerStatus::Retrying => &mut self.retrying,
+            WorkerStatus::Failed => &mut self.failed,
+            WorkerStatus::Exited => &mut self.exited,
+        }
+    }
+}
+
 /// Internal representation of `WorkerInvocation` to support backward compatibility
 /// in its binary format.
 #[derive(Clone, Debug, PartialEq, Encode, Decode)]
@@ -1171,6 +1460,53 @@ impl Display for WorkerEnvFilter {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "poem", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerLastErrorFilter {
+    pub comparator: StringFilterComparator,
+    pub value: String,
+}
+
+impl WorkerLastErrorFilter {
+    pub fn new(comparator: StringFilterComparator, value: String) -> Self {
+        Self { comparator, value }
+    }
+}
+
+impl Display for WorkerLastErrorFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "last_error {} {}", self.comparator, self.value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
+#[cfg_attr(feature = "poem", oai(rename_all = "camelCase"))]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerLabelFilter {
+    pub name: String,
+    pub comparator: StringFilterComparator,
+    pub value: String,
+}
+
+impl WorkerLabelFilter {
+    pub fn new(name: String, comparator: StringFilterComparator, value: String) -> Self {
+        Self {
+            name,
+            comparator,
+            value,
+        }
+    }
+}
+
+impl Display for WorkerLabelFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "label.{} {} {}", self.name, self.comparator, self.value)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
 #[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
 #[cfg_attr(feature = "poem", oai(rename_all = "camelCase"))]
@@ -1262,6 +1598,8 @@ pub enum WorkerFilter {
     And(WorkerAndFilter),
     Or(WorkerOrFilter),
     Not(WorkerNotFilter),
+    LastError(WorkerLastErrorFilter),
+    Label(WorkerLabelFilter),
 }
 
 impl WorkerFilter {
@@ -1288,6 +1626,17 @@ impl WorkerFilter {
     }
 
     pub fn matches(&self, metadata: &WorkerMetadata) -> bool {
+        self.matches_with_last_error(metadata, None)
+    }
+
+    // The last error of a worker is not part of its metadata, so it has to be looked up
+    // separately for the filters which need it. Workers without an error are matched
+    // as if their last error was empty.
+    pub fn matches_with_last_error(
+        &self,
+        metadata: &WorkerMetadata,
+        last_error: Option<&str>,
+    ) -> bool {
         match self.clone() {
             WorkerFilter::Name(WorkerNameFilter { comparator, value }) => {
                 comparator.matches(&metadata.worker_id.worker_name, &value)
@@ -1318,11 +1667,24 @@ impl WorkerFilter {
             WorkerFilter::Status(WorkerStatusFilter { comparator, value }) => {
                 comparator.matches(&metadata.last_known_status.status, &value)
             }
-            WorkerFilter::Not(WorkerNotFilter { filter }) => !filter.matches(metadata),
+            WorkerFilter::LastError(WorkerLastErrorFilter { comparator, value }) => {
+                comparator.matches(&last_error.unwrap_or_default().to_string(), &value)
+            }
+            WorkerFilter::Label(WorkerLabelFilter {
+                name,
+                comparator,
+                value,
+            }) => match metadata.last_known_status.labels().get(&name) {
+                Some(label_value) => comparator.matches(label_value, &value),
+                None => false,
+            },
+            WorkerFilter::Not(WorkerNotFilter { filter }) => {
+                !filter.matches_with_last_error(metadata, last_error)
+            }
             WorkerFilter::And(WorkerAndFilter { filters }) => {
                 let mut result = true;
                 for filter in filters {
-                    if !filter.matches(metadata) {
+                    if !filter.matches_with_last_error(metadata, last_error) {
                         result = false;
                         break;
                     }
@@ -1334,7 +1696,7 @@ impl WorkerFilter {
                 if !filters.is_empty() {
                     result = false;
                     for filter in filters {
-                        if filter.matches(metadata) {
+                        if filter.matches_with_last_error(metadata, last_error) {
                             result = true;
                             break;
                         }
@@ -1345,6 +1707,34 @@ impl WorkerFilter {
         }
     }
 
+    pub fn needs_last_error(&self) -> bool {
+        match self {
+            WorkerFilter::LastError(_) => true,
+            WorkerFilter::Not(WorkerNotFilter { filter }) => filter.needs_last_error(),
+            WorkerFilter::And(WorkerAndFilter { filters })
+            | WorkerFilter::Or(WorkerOrFilter { filters }) => {
+                filters.iter().any(|filter| filter.needs_last_error())
+            }
+            _ => false,
+        }
+    }
+
+    // A prefix all the names of the matching workers start with, if the filter implies one,
+    // so the workers can be looked up by their keys instead of scanning all of them
+    pub fn name_prefix(&self) -> Option<String> {
+        match self {
+            WorkerFilter::Name(WorkerNameFilter {
+                comparator: StringFilterComparator::Equal | StringFilterComparator::StartsWith,
+                value,
+            }) => Some(value.clone()),
+            WorkerFilter::And(WorkerAndFilter { filters }) => filters
+                .iter()
+                .filter_map(|filter| filter.name_prefix())
+                .max_by_key(|prefix| prefix.len()),
+            _ => None,
+        }
+    }
+
     pub fn new_and(filters: Vec<WorkerFilter>) -> Self {
         WorkerFilter::And(WorkerAndFilter::new(filters))
     }
@@ -1377,6 +1767,14 @@ impl WorkerFilter {
         WorkerFilter::CreatedAt(WorkerCreatedAtFilter::new(comparator, value))
     }
 
+    pub fn new_last_error(comparator: StringFilterComparator, value: String) -> Self {
+        WorkerFilter::LastError(WorkerLastErrorFilter::new(comparator, value))
+    }
+
+    pub fn new_label(name: String, comparator: StringFilterComparator, value: String) -> Self {
+        WorkerFilter::Label(WorkerLabelFilter::new(name, comparator, value))
+    }
+
     pub fn from(filters: Vec<String>) -> Result<WorkerFilter, String> {
         let mut fs = Vec::new();
         for f in filters {
@@ -1404,6 +1802,12 @@ impl Display for WorkerFilter {
             WorkerFilter::Env(filter) => {
                 write!(f, "{}", filter)
             }
+            WorkerFilter::LastError(filter) => {
+                write!(f, "{}", filter)
+            }
+            WorkerFilter::Label(filter) => {
+                write!(f, "{}", filter)
+            }
             WorkerFilter::Not(filter) => {
                 write!(f, "{}", filter)
             }
@@ -1417,51 +1821,110 @@ impl Display for WorkerFilter {
     }
 }
 
+// Besides the `<property> <comparator> <value>` form, the following predicates are supported:
+//   status in [Idle, Running]
+//   version between 1 and 3
+//   created_at after 2024-01-01T00:00:00Z
+//   name startswith worker-
+//   last_error like out of memory
+//   label.tenant == acme
 impl FromStr for WorkerFilter {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let elements = s.split_whitespace().collect::<Vec<&str>>();
 
-        if elements.len() == 3 {
-            let arg = elements[0];
-            let comparator = elements[1];
-            let value = elements[2];
-            match arg {
-                "name" => Ok(WorkerFilter::new_name(
-                    comparator.parse()?,
-                    value.to_string(),
-                )),
-                "version" => Ok(WorkerFilter::new_version(
-                    comparator.parse()?,
-                    value
-                        .parse()
-                        .map_err(|e| format!("Invalid filter value: {}", e))?,
-                )),
-                "status" => Ok(WorkerFilter::new_status(
-                    comparator.parse()?,
-                    value.parse()?,
-                )),
-                "created_at" | "createdAt" => Ok(WorkerFilter::new_created_at(
-                    comparator.parse()?,
-                    value.parse()?,
-                )),
-                _ if arg.starts_with("env.") => {
-                    let name = &arg[4..];
-                    Ok(WorkerFilter::new_env(
-                        name.to_string(),
-                        comparator.parse()?,
-                        value.to_string(),
-                    ))
+        if elements.len() < 3 {
+            return Err(format!("Invalid filter: {}", s));
+        }
+
+        let arg = elements[0];
+        let comparator = elements[1];
+        let values = &elements[2..];
+        let single_value = || match values {
+            [value] => Ok(*value),
+            _ => Err(format!("Invalid filter: {}", s)),
+        };
+
+        match arg {
+            "name" => Ok(WorkerFilter::new_name(
+                comparator.parse()?,
+                single_value()?.to_string(),
+            )),
+            "version" if comparator.eq_ignore_ascii_case("between") => match values {
+                [from, and, to] if and.eq_ignore_ascii_case("and") => {
+                    Ok(WorkerFilter::new_and(vec![
+                        WorkerFilter::new_version(
+                            FilterComparator::GreaterEqual,
+                            parse_version(from)?,
+                        ),
+                        WorkerFilter::new_version(FilterComparator::LessEqual, parse_version(to)?),
+                    ]))
                 }
                 _ => Err(format!("Invalid filter: {}", s)),
+            },
+            "version" => Ok(WorkerFilter::new_version(
+                comparator.parse()?,
+                parse_version(single_value()?)?,
+            )),
+            "status" if comparator.eq_ignore_ascii_case("in") => {
+                let statuses = values
+                    .join(" ")
+                    .trim()
+                    .strip_prefix('[')
+                    .and_then(|statuses| statuses.strip_suffix(']'))
+                    .ok_or_else(|| format!("Invalid filter: {}", s))?
+                    .split(',')
+                    .map(|status| status.trim())
+                    .filter(|status| !status.is_empty())
+                    .map(|status| {
+                        Ok(WorkerFilter::new_status(
+                            FilterComparator::Equal,
+                            status.parse()?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                Ok(WorkerFilter::new_or(statuses))
             }
-        } else {
-            Err(format!("Invalid filter: {}", s))
+            "status" => Ok(WorkerFilter::new_status(
+                comparator.parse()?,
+                single_value()?.parse()?,
+            )),
+            "created_at" | "createdAt" => Ok(WorkerFilter::new_created_at(
+                comparator.parse()?,
+                single_value()?.parse()?,
+            )),
+            "last_error" | "lastError" => Ok(WorkerFilter::new_last_error(
+                comparator.parse()?,
+                values.join(" "),
+            )),
+            _ if arg.starts_with("env.") => {
+                let name = &arg[4..];
+                Ok(WorkerFilter::new_env(
+                    name.to_string(),
+                    comparator.parse()?,
+                    single_value()?.to_string(),
+                ))
+            }
+            _ if arg.starts_with("label.") => {
+                let name = &arg[6..];
+                Ok(WorkerFilter::new_label(
+                    name.to_string(),
+                    comparator.parse()?,
+                    single_value()?.to_string(),
+                ))
+            }
+            _ => Err(format!("Invalid filter: {}", s)),
         }
     }
 }
 
+fn parse_version(value: &str) -> Result<ComponentVersion, String> {
+    value
+        .parse()
+        .map_err(|e| format!("Invalid filter value: {}", e))
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
 #[cfg_attr(feature = "poem", derive(poem_openapi::Enum))]
 pub enum StringFilterComparator {
@@ -1469,6 +1932,7 @@ pub enum StringFilterComparator {
     NotEqual,
     Like,
     NotLike,
+    StartsWith,
 }
 
 impl StringFilterComparator {
@@ -1482,6 +1946,9 @@ impl StringFilterComparator {
             StringFilterComparator::NotLike => {
                 !value1.to_string().contains(value2.to_string().as_str())
             }
+            StringFilterComparator::StartsWith => {
+                value1.to_string().starts_with(value2.to_string().as_str())
+            }
         }
     }
 }
@@ -1495,6 +1962,7 @@ impl FromStr for StringFilterComparator {
             "!=" | "notequal" | "ne" => Ok(StringFilterComparator::NotEqual),
             "like" => Ok(StringFilterComparator::Like),
             "notlike" => Ok(StringFilterComparator::NotLike),
+            "startswith" | "prefix" => Ok(StringFilterComparator::StartsWith),
             _ => Err(format!("Unknown String Filter Comparator: {}", s)),
         }
     }
@@ -1509,6 +1977,7 @@ impl TryFrom<i32> for StringFilterComparator {
             1 => Ok(StringFilterComparator::NotEqual),
             2 => Ok(StringFilterComparator::Like),
             3 => Ok(StringFilterComparator::NotLike),
+            4 => Ok(StringFilterComparator::StartsWith),
             _ => Err(format!("Unknown String Filter Comparator: {}", value)),
         }
     }
@@ -1521,6 +1990,7 @@ impl From<StringFilterComparator> for i32 {
             StringFilterComparator::NotEqual => 1,
             StringFilterComparator::Like => 2,
             StringFilterComparator::NotLike => 3,
+            StringFilterComparator::StartsWith => 4,
         }
     }
 }
@@ -1532,6 +2002,7 @@ impl Display for StringFilterComparator {
             StringFilterComparator::NotEqual => "!=",
             StringFilterComparator::Like => "like",
             StringFilterComparator::NotLike => "notlike",
+            StringFilterComparator::StartsWith => "startswith",
         };
         write!(f, "{}", s)
     }
@@ -1582,9 +2053,9 @@ impl FromStr for FilterComparator {
             "==" | "=" | "equal" | "eq" => Ok(FilterComparator::Equal),
             "!=" | "notequal" | "ne" => Ok(FilterComparator::NotEqual),
             ">=" | "greaterequal" | "ge" => Ok(FilterComparator::GreaterEqual),
-            ">" | "greater" | "gt" => Ok(FilterComparator::Greater),
+            ">" | "greater" | "gt" | "after" => Ok(FilterComparator::Greater),
             "<=" | "lessequal" | "le" => Ok(FilterComparator::LessEqual),
-            "<" | "less" | "lt" => Ok(FilterComparator::Less),
+            "<" | "less" | "lt" | "before" => Ok(FilterComparator::Less),
             _ => Err(format!("Unknown Filter Comparator: {}", s)),
         }
     }
@@ -2073,6 +2544,11 @@ pub enum GatewayBindingType {
     FileServer,
     HttpHandler,
     CorsPreflight,
+    Grpc,
+    WebSocket,
+    ServerSentEvents,
+    HttpProxy,
+    StatusPage,
 }
 
 // To keep backward compatibility as we documented wit-worker to be default
@@ -2098,6 +2574,11 @@ impl<'de> Deserialize<'de> for GatewayBindingType {
                     "default" | "wit-worker" => Ok(GatewayBindingType::Default),
                     "file-server" => Ok(GatewayBindingType::FileServer),
                     "cors-preflight" => Ok(GatewayBindingType::CorsPreflight),
+                    "grpc" => Ok(GatewayBindingType::Grpc),
+                    "web-socket" => Ok(GatewayBindingType::WebSocket),
+                    "server-sent-events" => Ok(GatewayBindingType::ServerSentEvents),
+                    "http-proxy" => Ok(GatewayBindingType::HttpProxy),
+                    "status-page" => Ok(GatewayBindingType::StatusPage),
                     _ => Err(de::Error::invalid_value(Unexpected::Str(value), &self)),
                 }
             }
@@ -2123,7 +2604,7 @@ impl TryFrom<String> for GatewayBindingType {
 mod tests {
     use test_r::test;
 
-    use std::collections::HashSet;
+    use std::collections::{BTreeMap, HashSet};
     use std::str::FromStr;
     use std::time::SystemTime;
     use std::vec;
@@ -2131,9 +2612,10 @@ mod tests {
     use crate::model::oplog::OplogIndex;
 
     use crate::model::{
-        AccountId, ComponentFilePath, ComponentId, FilterComparator, IdempotencyKey, ShardId,
-        StringFilterComparator, TargetWorkerId, Timestamp, WorkerFilter, WorkerId, WorkerMetadata,
-        WorkerStatus, WorkerStatusRecord,
+        AccountId, ComponentDeployment, ComponentFilePath, ComponentId, FilterComparator,
+        IdempotencyKey, ShardId, StringFilterComparator, TargetWorkerId, Timestamp, WorkerFilter,
+        WorkerForkOrigin, WorkerId, WorkerMetadata, WorkerStatus, WorkerStatusRecord,
+        WorkerStatusSummary,
     };
     use bincode::{Decode, Encode};
 
@@ -2207,6 +2689,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn worker_filter_parse_rich_predicates() {
+        assert_eq!(
+            WorkerFilter::from_str("status in [Idle, Running]").unwrap(),
+            WorkerFilter::new_or(vec![
+                WorkerFilter::new_status(FilterComparator::Equal, WorkerStatus::Idle),
+                WorkerFilter::new_status(FilterComparator::Equal, WorkerStatus::Running),
+            ])
+        );
+
+        assert_eq!(
+            WorkerFilter::from_str("version between 1 and 3").unwrap(),
+            WorkerFilter::new_version(FilterComparator::GreaterEqual, 1)
+                .and(WorkerFilter::new_version(FilterComparator::LessEqual, 3))
+        );
+
+        assert_eq!(
+            WorkerFilter::from_str("created_at after 2024-04-01T12:10:00Z").unwrap(),
+            WorkerFilter::new_created_at(
+                FilterComparator::Greater,
+                Timestamp::from_str("2024-04-01T12:10:00Z").unwrap(),
+            )
+        );
+
+        assert_eq!(
+            WorkerFilter::from_str("last_error like out of memory").unwrap(),
+            WorkerFilter::new_last_error(StringFilterComparator::Like, "out of memory".to_string())
+        );
+
+        let by_prefix = WorkerFilter::from_str("name startswith cart-").unwrap();
+        assert_eq!(by_prefix.name_prefix(), Some("cart-".to_string()));
+        assert_eq!(
+            by_prefix
+                .and(WorkerFilter::new_name(
+                    StringFilterComparator::Equal,
+                    "cart-12".to_string(),
+                ))
+                .name_prefix(),
+            Some("cart-12".to_string())
+        );
+        assert_eq!(
+            by_prefix
+                .or(WorkerFilter::new_version(FilterComparator::Equal, 1))
+                .name_prefix(),
+            None
+        );
+
+        assert!(WorkerFilter::from_str("name = cart 1").is_err());
+        assert!(WorkerFilter::from_str("status in Idle").is_err());
+        assert!(WorkerFilter::from_str("version between 1 3").is_err());
+    }
+
     #[test]
     fn worker_filter_combination() {
         assert_eq!(
@@ -2311,6 +2845,7 @@ mod tests {
                 ("env1".to_string(), "value1".to_string()),
                 ("env2".to_string(), "value2".to_string()),
             ],
+            wasi_config_vars: BTreeMap::new(),
             account_id: AccountId {
                 value: "account-1".to_string(),
             },
@@ -2374,6 +2909,57 @@ mod tests {
                 "worker-2".to_string(),
             ))
             .matches(&worker_metadata));
+
+        let last_error =
+            WorkerFilter::new_last_error(StringFilterComparator::Like, "out of memory".to_string());
+        assert!(last_error.needs_last_error());
+        assert!(last_error
+            .matches_with_last_error(&worker_metadata, Some("Runtime error: out of memory")));
+        assert!(!last_error.matches(&worker_metadata));
+        assert!(last_error.not().matches(&worker_metadata));
+        assert!(
+            WorkerFilter::new_name(StringFilterComparator::StartsWith, "worker-".to_string())
+                .matches(&worker_metadata)
+        );
+    }
+
+    #[test]
+    fn worker_filter_labels() {
+        let mut worker_metadata = WorkerMetadata::default(
+            WorkerId {
+                worker_name: "worker-1".to_string(),
+                component_id: ComponentId::new_v4(),
+            },
+            AccountId {
+                value: "account-1".to_string(),
+            },
+        );
+        worker_metadata
+            .last_known_status
+            .labels_mut()
+            .insert("tenant".to_string(), "acme".to_string());
+
+        let filter = WorkerFilter::from_str("label.tenant == acme").unwrap();
+        assert_eq!(
+            filter,
+            WorkerFilter::new_label(
+                "tenant".to_string(),
+                StringFilterComparator::Equal,
+                "acme".to_string()
+            )
+        );
+        assert_eq!(filter.to_string(), "label.tenant == acme");
+        assert!(filter.matches(&worker_metadata));
+
+        assert!(!WorkerFilter::from_str("label.tenant == globex")
+            .unwrap()
+            .matches(&worker_metadata));
+        assert!(!WorkerFilter::from_str("label.region == eu")
+            .unwrap()
+            .matches(&worker_metadata));
+        assert!(WorkerFilter::from_str("label.tenant startswith ac")
+            .unwrap()
+            .matches(&worker_metadata));
     }
 
     #[test]
@@ -2467,4 +3053,47 @@ mod tests {
         let path = ComponentFilePath::from_abs_str("a/b/c");
         assert!(path.is_err());
     }
+
+    #[test]
+    fn worker_status_summary_totals() {
+        let summary = WorkerStatusSummary::new(
+            [
+                (WorkerStatus::Idle, 5),
+                (WorkerStatus::Running, 2),
+                (WorkerStatus::Failed, 3),
+            ],
+            Some(ComponentDeployment {
+                component_version: 3,
+                timestamp: Timestamp::from(1_700_000_000_000),
+            }),
+        );
+
+        assert_eq!(summary.total, 10);
+        assert_eq!(summary.failure_rate, 0.3);
+        assert_eq!(summary.count(&WorkerStatus::Exited), 0);
+        assert_eq!(WorkerStatusSummary::new([], None).failure_rate, 0.0);
+    }
+    #[test]
+    fn fork_origin_is_kept_in_status_record() {
+        let source_worker_id = WorkerId {
+            component_id: ComponentId::new_v4(),
+            worker_name: "source".to_string(),
+        };
+        let mut status = WorkerStatusRecord::default();
+        assert_eq!(status.fork(), None);
+
+        *status.fork_mut() = Some(WorkerForkOrigin {
+            source_worker_id: source_worker_id.clone(),
+            forked_at: OplogIndex::from_u64(12),
+            timestamp: Timestamp::from(1_700_000_000_000),
+        });
+
+        let decoded: WorkerStatusRecord =
+            crate::serialization::deserialize(&crate::serialization::serialize(&status).unwrap())
+                .unwrap();
+        let fork = decoded.fork().unwrap();
+        assert_eq!(fork.source_worker_id, source_worker_id);
+        assert_eq!(fork.diverged_entries(OplogIndex::from_u64(12)), 0);
+        assert_eq!(fork.diverged_entries(OplogIndex::from_u64(20)), 8);
+    }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::model::invocation_context::InvocationContextStack;
 use crate::model::regions::OplogRegion;
 use crate::model::RetryConfig;
 use crate::model::{
@@ -28,7 +29,7 @@ use golem_wasm_ast::analysis::analysed_type::{r#enum, u64};
 use golem_wasm_ast::analysis::AnalysedType;
 use golem_wasm_rpc::{IntoValue, Value};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
@@ -255,8 +256,8 @@ pub enum OplogEntry {
         response: OplogPayload,
         wrapped_function_type: DurableFunctionType, // TODO: rename in Golem 2.0
     },
-    /// The worker has been invoked
-    ExportedFunctionInvoked {
+    /// The worker has been invoked (original 1.0 version, without the invocation context)
+    ExportedFunctionInvokedV1 {
         timestamp: Timestamp,
         function_name: String,
         request: OplogPayload,
@@ -375,8 +376,8 @@ pub enum OplogEntry {
         response: OplogPayload,
         wrapped_function_type: DurableFunctionType, // TODO: rename in Golem 2.0
     },
-    /// The current version of the Create entry (previous is CreateV1)
-    Create {
+    /// The second version of the Create entry, without WASI config vars
+    CreateV2 {
         timestamp: Timestamp,
         worker_id: WorkerId,
         component_version: ComponentVersion,
@@ -415,6 +416,35 @@ pub enum OplogEntry {
         timestamp: Timestamp,
         idempotency_key: IdempotencyKey,
     },
+    /// The current version of the Create entry (previous versions are CreateV1 and CreateV2)
+    Create {
+        timestamp: Timestamp,
+        worker_id: WorkerId,
+        component_version: ComponentVersion,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        wasi_config_vars: BTreeMap<String, String>,
+        account_id: AccountId,
+        parent: Option<WorkerId>,
+        component_size: u64,
+        initial_total_linear_memory_size: u64,
+        initial_active_plugins: HashSet<PluginInstallationId>,
+    },
+    /// Replaces the labels of the worker
+    ChangeLabels {
+        timestamp: Timestamp,
+        labels: BTreeMap<String, String>,
+    },
+    /// The worker has been invoked (previous version is ExportedFunctionInvokedV1). The invocation
+    /// context carries the trace id and the spans of the invocation, including the invocation's own
+    /// span as the current one.
+    ExportedFunctionInvoked {
+        timestamp: Timestamp,
+        function_name: String,
+        request: OplogPayload,
+        idempotency_key: IdempotencyKey,
+        invocation_context: InvocationContextStack,
+    },
 }
 
 impl OplogEntry {
@@ -423,6 +453,7 @@ impl OplogEntry {
         component_version: ComponentVersion,
         args: Vec<String>,
         env: Vec<(String, String)>,
+        wasi_config_vars: BTreeMap<String, String>,
         account_id: AccountId,
         parent: Option<WorkerId>,
         component_size: u64,
@@ -435,6 +466,7 @@ impl OplogEntry {
             component_version,
             args,
             env,
+            wasi_config_vars,
             account_id,
             parent,
             component_size,
@@ -624,6 +656,13 @@ impl OplogEntry {
         }
     }
 
+    pub fn change_labels(labels: BTreeMap<String, String>) -> OplogEntry {
+        OplogEntry::ChangeLabels {
+            timestamp: Timestamp::now_utc(),
+            labels,
+        }
+    }
+
     pub fn is_end_atomic_region(&self, idx: OplogIndex) -> bool {
         matches!(self, OplogEntry::EndAtomicRegion { begin_index, .. } if *begin_index == idx)
     }
@@ -678,6 +717,7 @@ impl OplogEntry {
                 | OplogEntry::DeactivatePlugin { .. }
                 | OplogEntry::Revert { .. }
                 | OplogEntry::CancelPendingInvocation { .. }
+                | OplogEntry::ChangeLabels { .. }
         )
     }
 
@@ -685,6 +725,7 @@ impl OplogEntry {
         match self {
             OplogEntry::Create { timestamp, .. }
             | OplogEntry::ImportedFunctionInvokedV1 { timestamp, .. }
+            | OplogEntry::ExportedFunctionInvokedV1 { timestamp, .. }
             | OplogEntry::ExportedFunctionInvoked { timestamp, .. }
             | OplogEntry::ExportedFunctionCompleted { timestamp, .. }
             | OplogEntry::Suspend { timestamp }
@@ -710,11 +751,13 @@ impl OplogEntry {
             | OplogEntry::Restart { timestamp }
             | OplogEntry::ImportedFunctionInvoked { timestamp, .. }
             | OplogEntry::CreateV1 { timestamp, .. }
+            | OplogEntry::CreateV2 { timestamp, .. }
             | OplogEntry::SuccessfulUpdateV1 { timestamp, .. }
             | OplogEntry::ActivatePlugin { timestamp, .. }
             | OplogEntry::DeactivatePlugin { timestamp, .. }
             | OplogEntry::Revert { timestamp, .. }
-            | OplogEntry::CancelPendingInvocation { timestamp, .. } => *timestamp,
+            | OplogEntry::CancelPendingInvocation { timestamp, .. }
+            | OplogEntry::ChangeLabels { timestamp, .. } => *timestamp,
         }
     }
 
@@ -726,6 +769,9 @@ impl OplogEntry {
             OplogEntry::CreateV1 {
                 component_version, ..
             } => Some(*component_version),
+            OplogEntry::CreateV2 {
+                component_version, ..
+            } => Some(*component_version),
             OplogEntry::SuccessfulUpdate { target_version, .. } => Some(*target_version),
             OplogEntry::SuccessfulUpdateV1 { target_version, .. } => Some(*target_version),
             _ => None,
@@ -755,11 +801,35 @@ impl OplogEntry {
                 component_size: *component_size,
                 initial_total_linear_memory_size: *initial_total_linear_memory_size,
             }),
+            OplogEntry::CreateV2 {
+                timestamp,
+                component_version,
+                args,
+                env,
+                account_id,
+                parent,
+                component_size,
+                initial_total_linear_memory_size,
+                initial_active_plugins,
+                worker_id: _,
+            } => Some(OplogEntry::CreateV2 {
+                timestamp: *timestamp,
+                worker_id: worker_id.clone(),
+                component_version: *component_version,
+                args: args.clone(),
+                env: env.clone(),
+                account_id: account_id.clone(),
+                parent: parent.clone(),
+                component_size: *component_size,
+                initial_total_linear_memory_size: *initial_total_linear_memory_size,
+                initial_active_plugins: initial_active_plugins.clone(),
+            }),
             OplogEntry::Create {
                 timestamp,
                 component_version,
                 args,
                 env,
+                wasi_config_vars,
                 account_id,
                 parent,
                 component_size,
@@ -772,6 +842,7 @@ impl OplogEntry {
                 component_version: *component_version,
                 args: args.clone(),
                 env: env.clone(),
+                wasi_config_vars: wasi_config_vars.clone(),
                 account_id: account_id.clone(),
                 parent: parent.clone(),
                 component_size: *component_size,
@@ -12,15 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::model::invocation_tree::{InvocationTree, InvocationTreeNode, TracedInvocationStatus};
 use crate::model::oplog::OplogIndex;
 use crate::model::{
-    AccountId, ComponentFilePath, ComponentFilePermissions, ComponentFileSystemNode,
-    ComponentFileSystemNodeDetails, ComponentType, FilterComparator, GatewayBindingType,
-    IdempotencyKey, InitialComponentFile, InitialComponentFileKey, LogLevel, NumberOfShards, Pod,
-    PromiseId, RoutingTable, RoutingTableEntry, ScanCursor, ShardId, StringFilterComparator,
-    TargetWorkerId, Timestamp, WorkerCreatedAtFilter, WorkerEnvFilter, WorkerEvent, WorkerFilter,
-    WorkerId, WorkerNameFilter, WorkerNotFilter, WorkerStatus, WorkerStatusFilter,
-    WorkerVersionFilter,
+    AccountId, ComponentDeployment, ComponentFilePath, ComponentFilePermissions,
+    ComponentFileSystemNode, ComponentFileSystemNodeDetails, ComponentType, FilterComparator,
+    GatewayBindingType, IdempotencyKey, InitialComponentFile, InitialComponentFileKey, LogLevel,
+    NumberOfShards, OplogCompactionStatus, Pod, PromiseId, RoutingTable, RoutingTableEntry,
+    ScanCursor, ShardId, StringFilterComparator, TargetWorkerId, Timestamp, WorkerCreatedAtFilter,
+    WorkerEnvFilter, WorkerEvent, WorkerFilter, WorkerForkOrigin, WorkerId, WorkerLabelFilter,
+    WorkerLastErrorFilter, WorkerNameFilter, WorkerNotFilter, WorkerStatus, WorkerStatusFilter,
+    WorkerStatusSummary, WorkerVersionFilter,
 };
 use golem_api_grpc::proto::golem;
 use golem_api_grpc::proto::golem::shardmanager::{
@@ -194,6 +196,201 @@ impl From<WorkerStatus> for golem_api_grpc::proto::golem::worker::WorkerStatus {
     }
 }
 
+impl From<WorkerStatusSummary> for golem::workerexecutor::v1::WorkerStatusSummary {
+    fn from(value: WorkerStatusSummary) -> Self {
+        Self {
+            counts: WorkerStatus::ALL
+                .iter()
+                .map(|status| golem::workerexecutor::v1::WorkerStatusCount {
+                    status: status.clone().into(),
+                    count: value.count(status),
+                })
+                .collect(),
+            last_deployment: value.last_deployment.map(|deployment| {
+                golem::workerexecutor::v1::ComponentDeployment {
+                    component_version: deployment.component_version,
+                    timestamp: Some(deployment.timestamp.into()),
+                }
+            }),
+        }
+    }
+}
+
+impl TryFrom<golem::workerexecutor::v1::WorkerStatusSummary> for WorkerStatusSummary {
+    type Error = String;
+
+    fn try_from(
+        value: golem::workerexecutor::v1::WorkerStatusSummary,
+    ) -> Result<Self, Self::Error> {
+        let counts = value
+            .counts
+            .into_iter()
+            .map(|count| Ok((WorkerStatus::try_from(count.status)?, count.count)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let last_deployment = value
+            .last_deployment
+            .map(|deployment| {
+                Ok::<_, String>(ComponentDeployment {
+                    component_version: deployment.component_version,
+                    timestamp: deployment
+                        .timestamp
+                        .ok_or("Missing deployment timestamp")?
+                        .into(),
+                })
+            })
+            .transpose()?;
+
+        Ok(WorkerStatusSummary::new(counts, last_deployment))
+    }
+}
+
+impl From<TracedInvocationStatus> for golem::workerexecutor::v1::TracedInvocationStatus {
+    fn from(value: TracedInvocationStatus) -> Self {
+        match value {
+            TracedInvocationStatus::Running => Self::Running,
+            TracedInvocationStatus::Completed => Self::Completed,
+            TracedInvocationStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<golem::workerexecutor::v1::TracedInvocationStatus> for TracedInvocationStatus {
+    fn from(value: golem::workerexecutor::v1::TracedInvocationStatus) -> Self {
+        match value {
+            golem::workerexecutor::v1::TracedInvocationStatus::Running => Self::Running,
+            golem::workerexecutor::v1::TracedInvocationStatus::Completed => Self::Completed,
+            golem::workerexecutor::v1::TracedInvocationStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<InvocationTree> for golem::workerexecutor::v1::InvocationTree {
+    fn from(value: InvocationTree) -> Self {
+        Self {
+            trace_id: value.trace_id,
+            invocations: value
+                .invocations
+                .into_iter()
+                .map(|node| golem::workerexecutor::v1::InvocationTreeNode {
+                    worker_id: Some(node.worker_id.into()),
+                    idempotency_key: Some(node.idempotency_key.into()),
+                    function_name: node.function_name,
+                    span_id: node.span_id,
+                    parent_span_id: node.parent_span_id,
+                    depth: node.depth,
+                    started_at: Some(node.started_at.into()),
+                    completed_at: node.completed_at.map(|timestamp| timestamp.into()),
+                    status: golem::workerexecutor::v1::TracedInvocationStatus::from(node.status)
+                        .into(),
+                    error: node.error,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<golem::workerexecutor::v1::InvocationTree> for InvocationTree {
+    type Error = String;
+
+    fn try_from(value: golem::workerexecutor::v1::InvocationTree) -> Result<Self, Self::Error> {
+        let invocations = value
+            .invocations
+            .into_iter()
+            .map(|node| {
+                Ok(InvocationTreeNode {
+                    worker_id: node
+                        .worker_id
+                        .ok_or("Missing worker_id field")?
+                        .try_into()?,
+                    idempotency_key: node
+                        .idempotency_key
+                        .ok_or("Missing idempotency_key field")?
+                        .into(),
+                    function_name: node.function_name,
+                    span_id: node.span_id,
+                    parent_span_id: node.parent_span_id,
+                    depth: node.depth,
+                    started_at: node.started_at.ok_or("Missing started_at field")?.into(),
+                    completed_at: node.completed_at.map(|timestamp| timestamp.into()),
+                    status: golem::workerexecutor::v1::TracedInvocationStatus::try_from(
+                        node.status,
+                    )
+                    .map_err(|_| format!("Invalid invocation status {}", node.status))?
+                    .into(),
+                    error: node.error,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(InvocationTree {
+            trace_id: value.trace_id,
+            invocations,
+        })
+    }
+}
+
+impl OplogCompactionStatus {
+    // The compaction status as reported in the worker metadata, at the given oplog index
+    pub fn to_protobuf(
+        &self,
+        current: OplogIndex,
+    ) -> golem_api_grpc::proto::golem::worker::OplogCompactionStatus {
+        golem_api_grpc::proto::golem::worker::OplogCompactionStatus {
+            last_snapshot_index: self.last_snapshot_index.map(|idx| idx.into()),
+            last_snapshot_timestamp: self.last_snapshot_timestamp.map(|t| t.into()),
+            last_failed_attempt_index: self.last_failed_attempt_index.map(|idx| idx.into()),
+            entries_since_last_attempt: self.entries_since_last_attempt(current),
+            bytes_since_last_attempt: self.bytes_since_last_attempt,
+            truncated_until: self.truncated_until.map(|idx| idx.into()),
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::worker::OplogCompactionStatus> for OplogCompactionStatus {
+    fn from(value: golem_api_grpc::proto::golem::worker::OplogCompactionStatus) -> Self {
+        Self {
+            last_snapshot_index: value.last_snapshot_index.map(OplogIndex::from_u64),
+            last_snapshot_timestamp: value.last_snapshot_timestamp.map(|t| t.into()),
+            last_failed_attempt_index: value.last_failed_attempt_index.map(OplogIndex::from_u64),
+            bytes_since_last_attempt: value.bytes_since_last_attempt,
+            truncated_until: value.truncated_until.map(OplogIndex::from_u64),
+        }
+    }
+}
+
+impl WorkerForkOrigin {
+    // The fork origin as reported in the worker metadata, at the given oplog index of the fork
+    pub fn to_protobuf(
+        &self,
+        current: OplogIndex,
+    ) -> golem_api_grpc::proto::golem::worker::WorkerForkOrigin {
+        golem_api_grpc::proto::golem::worker::WorkerForkOrigin {
+            source_worker_id: Some(self.source_worker_id.clone().into()),
+            forked_at: self.forked_at.into(),
+            timestamp: Some(self.timestamp.into()),
+            diverged_entries: self.diverged_entries(current),
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerForkOrigin> for WorkerForkOrigin {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::WorkerForkOrigin,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            source_worker_id: value
+                .source_worker_id
+                .ok_or("Missing source_worker_id")?
+                .try_into()?,
+            forked_at: OplogIndex::from_u64(value.forked_at),
+            timestamp: value.timestamp.ok_or("Missing timestamp")?.into(),
+        })
+    }
+}
+
 impl From<golem_api_grpc::proto::golem::common::AccountId> for AccountId {
     fn from(proto: golem_api_grpc::proto::golem::common::AccountId) -> Self {
         Self { value: proto.name }
@@ -239,6 +436,19 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerFilter> for WorkerFilte
                 golem_api_grpc::proto::golem::worker::worker_filter::Filter::Env(filter) => Ok(
                     WorkerFilter::new_env(filter.name, filter.comparator.try_into()?, filter.value),
                 ),
+                golem_api_grpc::proto::golem::worker::worker_filter::Filter::LastError(filter) => {
+                    Ok(WorkerFilter::new_last_error(
+                        filter.comparator.try_into()?,
+                        filter.value,
+                    ))
+                }
+                golem_api_grpc::proto::golem::worker::worker_filter::Filter::Label(filter) => {
+                    Ok(WorkerFilter::new_label(
+                        filter.name,
+                        filter.comparator.try_into()?,
+                        filter.value,
+                    ))
+                }
                 golem_api_grpc::proto::golem::worker::worker_filter::Filter::Not(filter) => {
                     let filter = *filter.filter.ok_or_else(|| "Missing filter".to_string())?;
                     Ok(WorkerFilter::new_not(filter.try_into()?))
@@ -301,6 +511,25 @@ impl From<WorkerFilter> for golem_api_grpc::proto::golem::worker::WorkerFilter {
                     value,
                 },
             ),
+            WorkerFilter::LastError(WorkerLastErrorFilter { comparator, value }) => {
+                golem_api_grpc::proto::golem::worker::worker_filter::Filter::LastError(
+                    golem_api_grpc::proto::golem::worker::WorkerLastErrorFilter {
+                        comparator: comparator.into(),
+                        value,
+                    },
+                )
+            }
+            WorkerFilter::Label(WorkerLabelFilter {
+                name,
+                comparator,
+                value,
+            }) => golem_api_grpc::proto::golem::worker::worker_filter::Filter::Label(
+                golem_api_grpc::proto::golem::worker::WorkerLabelFilter {
+                    name,
+                    comparator: comparator.into(),
+                    value,
+                },
+            ),
             WorkerFilter::Status(WorkerStatusFilter { comparator, value }) => {
                 golem_api_grpc::proto::golem::worker::worker_filter::Filter::Status(
                     golem_api_grpc::proto::golem::worker::WorkerStatusFilter {
@@ -362,6 +591,9 @@ impl From<StringFilterComparator> for golem_api_grpc::proto::golem::common::Stri
             StringFilterComparator::NotLike => {
                 golem_api_grpc::proto::golem::common::StringFilterComparator::StringNotLike
             }
+            StringFilterComparator::StartsWith => {
+                golem_api_grpc::proto::golem::common::StringFilterComparator::StringStartsWith
+            }
         }
     }
 }
@@ -745,6 +977,21 @@ impl From<golem_api_grpc::proto::golem::apidefinition::GatewayBindingType> for G
             golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::AuthCallBack => {
                 GatewayBindingType::CorsPreflight
             }
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::Grpc => {
+                GatewayBindingType::Grpc
+            }
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::WebSocket => {
+                GatewayBindingType::WebSocket
+            }
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::ServerSentEvents => {
+                GatewayBindingType::ServerSentEvents
+            }
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::HttpProxy => {
+                GatewayBindingType::HttpProxy
+            }
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::StatusPage => {
+                GatewayBindingType::StatusPage
+            }
         }
     }
 }
@@ -764,6 +1011,21 @@ impl From<GatewayBindingType> for golem_api_grpc::proto::golem::apidefinition::G
             GatewayBindingType::CorsPreflight => {
                 golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::CorsPreflight
             }
+            GatewayBindingType::Grpc => {
+                golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::Grpc
+            }
+            GatewayBindingType::WebSocket => {
+                golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::WebSocket
+            }
+            GatewayBindingType::ServerSentEvents => {
+                golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::ServerSentEvents
+            }
+            GatewayBindingType::HttpProxy => {
+                golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::HttpProxy
+            }
+            GatewayBindingType::StatusPage => {
+                golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::StatusPage
+            }
         }
     }
 }
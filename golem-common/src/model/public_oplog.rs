@@ -418,6 +418,13 @@ pub struct ExportedFunctionInvokedParameters {
     pub function_name: String,
     pub request: Vec<ValueAndType>,
     pub idempotency_key: IdempotencyKey,
+    /// The trace the invocation belongs to, missing for invocations recorded without their
+    /// invocation context
+    pub trace_id: Option<String>,
+    pub span_id: Option<String>,
+    /// The span of the caller, which is the span of the invoking worker for worker-to-worker
+    /// invocations
+    pub parent_span_id: Option<String>,
 }
 
 impl IntoValue for ExportedFunctionInvokedParameters {
@@ -867,6 +874,34 @@ impl IntoValue for CancelInvocationParameters {
     }
 }
 
+#[derive(Clone, Debug, Serialize, PartialEq, Deserialize)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Object))]
+pub struct ChangeLabelsParameters {
+    pub timestamp: Timestamp,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl IntoValue for ChangeLabelsParameters {
+    fn into_value(self) -> Value {
+        Value::Record(vec![
+            self.timestamp.into_value(),
+            Value::List(
+                self.labels
+                    .into_iter()
+                    .map(|(k, v)| Value::Tuple(vec![k.into_value(), v.into_value()]))
+                    .collect::<Vec<Value>>(),
+            ),
+        ])
+    }
+
+    fn get_type() -> AnalysedType {
+        record(vec![
+            field("timestamp", Timestamp::get_type()),
+            field("labels", list(tuple(vec![str(), str()]))),
+        ])
+    }
+}
+
 /// A mirror of the core `OplogEntry` type, without the undefined arbitrary payloads.
 ///
 /// Instead, it encodes all payloads with wasm-rpc `Value` types. This makes this the base type
@@ -947,9 +982,46 @@ pub enum PublicOplogEntry {
     Revert(RevertParameters),
     /// Cancel a pending invocation
     CancelInvocation(CancelInvocationParameters),
+    /// Replaces the labels of the worker
+    ChangeLabels(ChangeLabelsParameters),
 }
 
 impl PublicOplogEntry {
+    pub fn timestamp(&self) -> Timestamp {
+        match self {
+            PublicOplogEntry::Create(params) => params.timestamp,
+            PublicOplogEntry::ImportedFunctionInvoked(params) => params.timestamp,
+            PublicOplogEntry::ExportedFunctionInvoked(params) => params.timestamp,
+            PublicOplogEntry::ExportedFunctionCompleted(params) => params.timestamp,
+            PublicOplogEntry::Suspend(params) => params.timestamp,
+            PublicOplogEntry::Error(params) => params.timestamp,
+            PublicOplogEntry::NoOp(params) => params.timestamp,
+            PublicOplogEntry::Jump(params) => params.timestamp,
+            PublicOplogEntry::Interrupted(params) => params.timestamp,
+            PublicOplogEntry::Exited(params) => params.timestamp,
+            PublicOplogEntry::ChangeRetryPolicy(params) => params.timestamp,
+            PublicOplogEntry::BeginAtomicRegion(params) => params.timestamp,
+            PublicOplogEntry::EndAtomicRegion(params) => params.timestamp,
+            PublicOplogEntry::BeginRemoteWrite(params) => params.timestamp,
+            PublicOplogEntry::EndRemoteWrite(params) => params.timestamp,
+            PublicOplogEntry::PendingWorkerInvocation(params) => params.timestamp,
+            PublicOplogEntry::PendingUpdate(params) => params.timestamp,
+            PublicOplogEntry::SuccessfulUpdate(params) => params.timestamp,
+            PublicOplogEntry::FailedUpdate(params) => params.timestamp,
+            PublicOplogEntry::GrowMemory(params) => params.timestamp,
+            PublicOplogEntry::CreateResource(params) => params.timestamp,
+            PublicOplogEntry::DropResource(params) => params.timestamp,
+            PublicOplogEntry::DescribeResource(params) => params.timestamp,
+            PublicOplogEntry::Log(params) => params.timestamp,
+            PublicOplogEntry::Restart(params) => params.timestamp,
+            PublicOplogEntry::ActivatePlugin(params) => params.timestamp,
+            PublicOplogEntry::DeactivatePlugin(params) => params.timestamp,
+            PublicOplogEntry::Revert(params) => params.timestamp,
+            PublicOplogEntry::CancelInvocation(params) => params.timestamp,
+            PublicOplogEntry::ChangeLabels(params) => params.timestamp,
+        }
+    }
+
     pub fn matches(&self, query: &Query) -> bool {
         fn matches_impl(entry: &PublicOplogEntry, query: &Query, field_stack: &[String]) -> bool {
             match query {
@@ -1176,6 +1248,14 @@ impl PublicOplogEntry {
                     || Self::string_match("cancel-invocation", &[], query_path, query)
                     || Self::string_match(&params.idempotency_key.value, &[], query_path, query)
             }
+            PublicOplogEntry::ChangeLabels(params) => {
+                Self::string_match("changelabels", &[], query_path, query)
+                    || Self::string_match("change-labels", &[], query_path, query)
+                    || params.labels.iter().any(|(key, value)| {
+                        Self::string_match(key, &[], query_path, query)
+                            || Self::string_match(value, &[], query_path, query)
+                    })
+            }
         }
     }
 
@@ -1474,6 +1554,10 @@ impl IntoValue for PublicOplogEntry {
                 case_idx: 28,
                 case_value: Some(Box::new(params.into_value())),
             },
+            PublicOplogEntry::ChangeLabels(params) => Value::Variant {
+                case_idx: 29,
+                case_value: Some(Box::new(params.into_value())),
+            },
         }
     }
 
@@ -1523,6 +1607,7 @@ impl IntoValue for PublicOplogEntry {
             case("deactivate-plugin", DeactivatePluginParameters::get_type()),
             case("revert", RevertParameters::get_type()),
             case("cancel-invocation", CancelInvocationParameters::get_type()),
+            case("change-labels", ChangeLabelsParameters::get_type()),
         ])
     }
 }
@@ -1564,18 +1649,83 @@ impl Display for OplogCursor {
     }
 }
 
+/// The kinds of oplog entries an oplog query can be restricted to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "poem", derive(poem_openapi::Enum))]
+#[cfg_attr(feature = "poem", oai(rename_all = "kebab-case"))]
+#[serde(rename_all = "kebab-case")]
+pub enum PublicOplogEntryKind {
+    /// Invocations of exported functions, and their completion
+    ExportedFunction,
+    /// Calls to host functions
+    HostCall,
+    /// Failures of the worker
+    Error,
+}
+
+impl PublicOplogEntryKind {
+    pub fn matches(&self, entry: &PublicOplogEntry) -> bool {
+        matches!(
+            (self, entry),
+            (
+                PublicOplogEntryKind::ExportedFunction,
+                PublicOplogEntry::ExportedFunctionInvoked(_)
+                    | PublicOplogEntry::ExportedFunctionCompleted(_)
+            ) | (
+                PublicOplogEntryKind::HostCall,
+                PublicOplogEntry::ImportedFunctionInvoked(_)
+            ) | (PublicOplogEntryKind::Error, PublicOplogEntry::Error(_))
+        )
+    }
+}
+
+impl Display for PublicOplogEntryKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PublicOplogEntryKind::ExportedFunction => write!(f, "exported-function"),
+            PublicOplogEntryKind::HostCall => write!(f, "host-call"),
+            PublicOplogEntryKind::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Restricts an oplog query to some kinds of entries, added in a time range.
+///
+/// An entry matches if it is of any of the `kinds` (or there are no `kinds`), and was added after
+/// `after` and before `before` (both inclusive) when they are set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PublicOplogEntryFilter {
+    pub kinds: Vec<PublicOplogEntryKind>,
+    pub after: Option<Timestamp>,
+    pub before: Option<Timestamp>,
+}
+
+impl PublicOplogEntryFilter {
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.after.is_none() && self.before.is_none()
+    }
+
+    pub fn matches(&self, entry: &PublicOplogEntry) -> bool {
+        let timestamp = entry.timestamp();
+        (self.kinds.is_empty() || self.kinds.iter().any(|kind| kind.matches(entry)))
+            && self.after.is_none_or(|after| timestamp >= after)
+            && self.before.is_none_or(|before| timestamp <= before)
+    }
+}
+
 #[cfg(feature = "protobuf")]
 mod protobuf {
     use crate::model::oplog::{LogLevel, OplogIndex, WorkerResourceId};
     use crate::model::public_oplog::{
-        ActivatePluginParameters, CancelInvocationParameters, ChangeRetryPolicyParameters,
-        CreateParameters, DeactivatePluginParameters, DescribeResourceParameters,
-        EndRegionParameters, ErrorParameters, ExportedFunctionCompletedParameters,
-        ExportedFunctionInvokedParameters, ExportedFunctionParameters, FailedUpdateParameters,
-        GrowMemoryParameters, ImportedFunctionInvokedParameters, JumpParameters, LogParameters,
-        ManualUpdateParameters, OplogCursor, PendingUpdateParameters,
-        PendingWorkerInvocationParameters, PluginInstallationDescription,
-        PublicDurableFunctionType, PublicOplogEntry, PublicRetryConfig, PublicUpdateDescription,
+        ActivatePluginParameters, CancelInvocationParameters, ChangeLabelsParameters,
+        ChangeRetryPolicyParameters, CreateParameters, DeactivatePluginParameters,
+        DescribeResourceParameters, EndRegionParameters, ErrorParameters,
+        ExportedFunctionCompletedParameters, ExportedFunctionInvokedParameters,
+        ExportedFunctionParameters, FailedUpdateParameters, GrowMemoryParameters,
+        ImportedFunctionInvokedParameters, JumpParameters, LogParameters, ManualUpdateParameters,
+        OplogCursor, PendingUpdateParameters, PendingWorkerInvocationParameters,
+        PluginInstallationDescription, PublicDurableFunctionType, PublicOplogEntry,
+        PublicOplogEntryFilter, PublicOplogEntryKind, PublicRetryConfig, PublicUpdateDescription,
         PublicWorkerInvocation, ResourceParameters, RevertParameters,
         SnapshotBasedUpdateParameters, SuccessfulUpdateParameters, TimestampParameter,
         WriteRemoteBatchedParameters,
@@ -1709,6 +1859,9 @@ mod protobuf {
                             .idempotency_key
                             .ok_or("Missing idempotency_key field")?
                             .into(),
+                        trace_id: exported_function_invoked.trace_id,
+                        span_id: exported_function_invoked.span_id,
+                        parent_span_id: exported_function_invoked.parent_span_id,
                     }),
                 ),
                 oplog_entry::Entry::ExportedFunctionCompleted(exported_function_completed) => {
@@ -1945,6 +2098,12 @@ mod protobuf {
                             .into(),
                     }),
                 ),
+                oplog_entry::Entry::ChangeLabels(change) => {
+                    Ok(PublicOplogEntry::ChangeLabels(ChangeLabelsParameters {
+                        timestamp: change.timestamp.ok_or("Missing timestamp field")?.into(),
+                        labels: change.labels.into_iter().collect(),
+                    }))
+                }
             }
         }
     }
@@ -2013,6 +2172,9 @@ mod protobuf {
                                     })
                                     .collect::<Result<Vec<_>, _>>()?,
                                 idempotency_key: Some(exported_function_invoked.idempotency_key.into()),
+                                trace_id: exported_function_invoked.trace_id,
+                                span_id: exported_function_invoked.span_id,
+                                parent_span_id: exported_function_invoked.parent_span_id,
                             },
                         )),
                     }
@@ -2291,6 +2453,16 @@ mod protobuf {
                         )),
                     }
                 }
+                PublicOplogEntry::ChangeLabels(change) => {
+                    golem_api_grpc::proto::golem::worker::OplogEntry {
+                        entry: Some(oplog_entry::Entry::ChangeLabels(
+                            golem_api_grpc::proto::golem::worker::ChangeLabelsParameters {
+                                timestamp: Some(change.timestamp.into()),
+                                labels: change.labels.into_iter().collect(),
+                            },
+                        )),
+                    }
+                }
             })
         }
     }
@@ -2432,6 +2604,34 @@ mod protobuf {
         }
     }
 
+    impl TryFrom<golem_api_grpc::proto::golem::worker::LogParameters> for LogParameters {
+        type Error = String;
+
+        fn try_from(
+            value: golem_api_grpc::proto::golem::worker::LogParameters,
+        ) -> Result<Self, Self::Error> {
+            Ok(LogParameters {
+                level: value.level().into(),
+                timestamp: value.timestamp.ok_or("Missing timestamp field")?.into(),
+                context: value.context,
+                message: value.message,
+            })
+        }
+    }
+
+    impl From<LogParameters> for golem_api_grpc::proto::golem::worker::LogParameters {
+        fn from(value: LogParameters) -> Self {
+            golem_api_grpc::proto::golem::worker::LogParameters {
+                timestamp: Some(value.timestamp.into()),
+                level: Into::<golem_api_grpc::proto::golem::worker::OplogLogLevel>::into(
+                    value.level,
+                ) as i32,
+                context: value.context,
+                message: value.message,
+            }
+        }
+    }
+
     impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerInvocation> for PublicWorkerInvocation {
         type Error = String;
 
@@ -2547,6 +2747,64 @@ mod protobuf {
             }
         }
     }
+
+    impl From<golem_api_grpc::proto::golem::worker::OplogEntryKind> for PublicOplogEntryKind {
+        fn from(value: golem_api_grpc::proto::golem::worker::OplogEntryKind) -> Self {
+            match value {
+                golem_api_grpc::proto::golem::worker::OplogEntryKind::ExportedFunction => {
+                    PublicOplogEntryKind::ExportedFunction
+                }
+                golem_api_grpc::proto::golem::worker::OplogEntryKind::HostCall => {
+                    PublicOplogEntryKind::HostCall
+                }
+                golem_api_grpc::proto::golem::worker::OplogEntryKind::Error => {
+                    PublicOplogEntryKind::Error
+                }
+            }
+        }
+    }
+
+    impl From<PublicOplogEntryKind> for golem_api_grpc::proto::golem::worker::OplogEntryKind {
+        fn from(value: PublicOplogEntryKind) -> Self {
+            match value {
+                PublicOplogEntryKind::ExportedFunction => {
+                    golem_api_grpc::proto::golem::worker::OplogEntryKind::ExportedFunction
+                }
+                PublicOplogEntryKind::HostCall => {
+                    golem_api_grpc::proto::golem::worker::OplogEntryKind::HostCall
+                }
+                PublicOplogEntryKind::Error => {
+                    golem_api_grpc::proto::golem::worker::OplogEntryKind::Error
+                }
+            }
+        }
+    }
+
+    impl From<golem_api_grpc::proto::golem::worker::OplogEntryFilter> for PublicOplogEntryFilter {
+        fn from(value: golem_api_grpc::proto::golem::worker::OplogEntryFilter) -> Self {
+            Self {
+                kinds: value.kinds().map(|kind| kind.into()).collect(),
+                after: value.after.map(|after| after.into()),
+                before: value.before.map(|before| before.into()),
+            }
+        }
+    }
+
+    impl From<PublicOplogEntryFilter> for golem_api_grpc::proto::golem::worker::OplogEntryFilter {
+        fn from(value: PublicOplogEntryFilter) -> Self {
+            Self {
+                kinds: value
+                    .kinds
+                    .into_iter()
+                    .map(|kind| {
+                        golem_api_grpc::proto::golem::worker::OplogEntryKind::from(kind) as i32
+                    })
+                    .collect(),
+                after: value.after.map(|after| after.into()),
+                before: value.before.map(|before| before.into()),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -2559,9 +2817,9 @@ mod tests {
         ExportedFunctionInvokedParameters, ExportedFunctionParameters, FailedUpdateParameters,
         GrowMemoryParameters, ImportedFunctionInvokedParameters, JumpParameters, LogParameters,
         PendingUpdateParameters, PendingWorkerInvocationParameters, PluginInstallationDescription,
-        PublicDurableFunctionType, PublicOplogEntry, PublicRetryConfig, PublicUpdateDescription,
-        PublicWorkerInvocation, ResourceParameters, SnapshotBasedUpdateParameters,
-        SuccessfulUpdateParameters, TimestampParameter,
+        PublicDurableFunctionType, PublicOplogEntry, PublicOplogEntryFilter, PublicOplogEntryKind,
+        PublicRetryConfig, PublicUpdateDescription, PublicWorkerInvocation, ResourceParameters,
+        SnapshotBasedUpdateParameters, SuccessfulUpdateParameters, TimestampParameter,
     };
     use crate::model::{
         AccountId, ComponentId, Empty, IdempotencyKey, PluginInstallationId, Timestamp, WorkerId,
@@ -2659,6 +2917,9 @@ mod tests {
                 },
             ],
             idempotency_key: IdempotencyKey::new("idempotency_key".to_string()),
+            trace_id: Some("4bf92f3577b34da6a3ce929d0e0e4736".to_string()),
+            span_id: Some("00f067aa0ba902b7".to_string()),
+            parent_span_id: None,
         });
         let serialized = entry.to_json_string();
         let deserialized: PublicOplogEntry = serde_json::from_str(&serialized).unwrap();
@@ -3003,4 +3264,45 @@ mod tests {
         let deserialized: PublicOplogEntry = serde_json::from_str(&serialized).unwrap();
         assert_eq!(entry, deserialized);
     }
+
+    #[test]
+    fn entries_are_filtered_by_kind_and_time() {
+        let error = PublicOplogEntry::Error(ErrorParameters {
+            timestamp: Timestamp::from(2000),
+            error: "test".to_string(),
+        });
+        let completed =
+            PublicOplogEntry::ExportedFunctionCompleted(ExportedFunctionCompletedParameters {
+                timestamp: Timestamp::from(3000),
+                response: ValueAndType {
+                    value: Value::String("test".to_string()),
+                    typ: str(),
+                },
+                consumed_fuel: 0,
+            });
+        let suspend = PublicOplogEntry::Suspend(TimestampParameter {
+            timestamp: Timestamp::from(1000),
+        });
+
+        let all = PublicOplogEntryFilter::default();
+        assert!(all.is_empty());
+        assert!(all.matches(&error) && all.matches(&completed) && all.matches(&suspend));
+
+        let by_kind = PublicOplogEntryFilter {
+            kinds: vec![PublicOplogEntryKind::Error, PublicOplogEntryKind::HostCall],
+            ..Default::default()
+        };
+        assert!(by_kind.matches(&error));
+        assert!(!by_kind.matches(&completed));
+        assert!(!by_kind.matches(&suspend));
+
+        let by_time = PublicOplogEntryFilter {
+            kinds: vec![],
+            after: Some(Timestamp::from(1500)),
+            before: Some(Timestamp::from(3000)),
+        };
+        assert!(by_time.matches(&error));
+        assert!(by_time.matches(&completed));
+        assert!(!by_time.matches(&suspend));
+    }
 }
@@ -23,8 +23,8 @@ use fred::clients::Transaction;
 use fred::cmd;
 use fred::prelude::{RedisPool as FredRedisPool, *};
 use fred::types::{
-    InfoKind, Limit, MultipleKeys, MultipleOrderedPairs, MultipleValues, MultipleZaddValues,
-    Ordering, RedisKey, RedisMap, XCap, ZRange, ZSort, XID,
+    InfoKind, Limit, MultipleKeys, MultipleOrderedPairs, MultipleStrings, MultipleValues,
+    MultipleZaddValues, Ordering, RedisKey, RedisMap, XCap, ZRange, ZSort, XID,
 };
 use tracing::{debug, Level};
 
@@ -170,6 +170,30 @@ impl RedisLabelledApi<'_> {
         )
     }
 
+    pub async fn eval<R, K, V>(&self, script: &str, keys: Vec<K>, args: V) -> RedisResult<R>
+    where
+        R: FromRedis,
+        K: AsRef<str>,
+        V: TryInto<MultipleValues> + Send,
+        V::Error: Into<RedisError> + Send,
+    {
+        self.ensure_connected().await?;
+        let start = Instant::now();
+        self.record(
+            start,
+            "EVAL",
+            self.pool
+                .eval(
+                    script,
+                    keys.iter()
+                        .map(|k| self.prefixed_key(k))
+                        .collect::<Vec<_>>(),
+                    args,
+                )
+                .await,
+        )
+    }
+
     pub async fn get<R, K>(&self, key: K) -> RedisResult<R>
     where
         R: FromRedis,
@@ -566,6 +590,21 @@ impl RedisLabelledApi<'_> {
         )
     }
 
+    pub async fn xdel<R, K, S>(&self, key: K, ids: S) -> RedisResult<R>
+    where
+        R: FromRedis,
+        K: AsRef<str>,
+        S: Into<MultipleStrings> + Send,
+    {
+        self.ensure_connected().await?;
+        let start = Instant::now();
+        self.record(
+            start,
+            "XDEL",
+            self.pool.xdel(self.prefixed_key(key), ids).await,
+        )
+    }
+
     pub async fn zadd<R, K, V>(
         &self,
         key: K,
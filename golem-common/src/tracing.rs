@@ -17,6 +17,7 @@ use std::fs::OpenOptions;
 use std::io::stdout;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use figment::providers::Serialized;
 use figment::Figment;
@@ -30,12 +31,15 @@ use tracing_subscriber::Layer;
 use tracing_subscriber::Registry;
 
 use crate::config::env_config_provider;
+#[cfg(feature = "model")]
+use crate::model::invocation_context::InvocationContextStack;
 use crate::tracing::format::JsonFlattenSpanFormatter;
 
 pub enum Output {
     Stdout,
     File,
     TracingConsole,
+    Otlp,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -133,6 +137,37 @@ impl OutputConfig {
     }
 }
 
+/// Export of the spans to an OpenTelemetry collector through OTLP over gRPC
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OtlpConfig {
+    pub enabled: bool,
+    pub endpoint: String,
+    pub service_name: String,
+    /// The ratio of the traces exported, decided by the trace id, so every service exports
+    /// the same traces when they are configured with the same ratio
+    pub sampling_ratio: f64,
+    #[serde(with = "humantime_serde")]
+    pub export_timeout: Duration,
+}
+
+impl OtlpConfig {
+    pub fn disabled(service_name: &str) -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4317".to_string(),
+            service_name: service_name.to_string(),
+            sampling_ratio: 1.0,
+            export_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self::disabled("golem")
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TracingConfig {
     pub stdout: OutputConfig,
@@ -142,6 +177,7 @@ pub struct TracingConfig {
     pub file_truncate: bool,
     pub console: bool,
     pub dtor_friendly: bool,
+    pub otlp: OtlpConfig,
 }
 
 impl TracingConfig {
@@ -157,6 +193,7 @@ impl TracingConfig {
             file_truncate: true,
             console: false,
             dtor_friendly: false,
+            otlp: OtlpConfig::disabled(name),
         }
     }
 
@@ -214,6 +251,7 @@ impl Default for TracingConfig {
             file_truncate: true,
             console: false,
             dtor_friendly: false,
+            otlp: OtlpConfig::default(),
         }
     }
 }
@@ -394,6 +432,14 @@ where
         );
     }
 
+    if config.otlp.enabled {
+        layers.push(
+            otlp::make_layer(&config.otlp)
+                .with_filter(make_filter(Output::Otlp))
+                .boxed(),
+        );
+    }
+
     tracing_subscriber::registry().with(layers).init();
 
     std::panic::set_hook({
@@ -419,6 +465,26 @@ pub fn init_tracing_with_default_debug_env_filter(config: &TracingConfig) {
     init_tracing(config, filter::for_all_outputs::default_debug_env());
 }
 
+/// Makes the span a child of the span of an incoming request with a `traceparent` header, when
+/// the spans are exported through OTLP
+pub fn set_parent_from_headers(span: &tracing::Span, headers: &http::HeaderMap) {
+    otlp::set_parent_from_headers(span, headers)
+}
+
+/// Makes the span a child of the current span of an invocation context, joining the trace of
+/// the request which invoked the worker, when the spans are exported through OTLP
+#[cfg(feature = "model")]
+pub fn set_parent_from_invocation_context(
+    span: &tracing::Span,
+    invocation_context: &InvocationContextStack,
+) {
+    otlp::set_parent(
+        span,
+        invocation_context.trace_id.0.get(),
+        invocation_context.current_span_id().0.get(),
+    )
+}
+
 #[allow(clippy::collapsible_else_if)]
 fn make_layer<W>(
     config: &OutputConfig,
@@ -486,6 +552,101 @@ where
     }
 }
 
+mod otlp {
+    use std::sync::OnceLock;
+
+    use opentelemetry::propagation::Extractor;
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider as _,
+    };
+    use opentelemetry::{global, Context, KeyValue};
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{Sampler, Tracer, TracerProvider};
+    use opentelemetry_sdk::{runtime, Resource};
+    use tokio::runtime::Runtime;
+    use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+    use tracing_subscriber::Registry;
+
+    use super::OtlpConfig;
+
+    // Tracing is initialized before the runtimes of the services are created, so the exporter
+    // runs on a runtime of its own for the lifetime of the process
+    static EXPORTER_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+    pub fn make_layer(config: &OtlpConfig) -> OpenTelemetryLayer<Registry, Tracer> {
+        let runtime = EXPORTER_RUNTIME.get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("otlp-exporter")
+                .enable_all()
+                .build()
+                .expect("cannot create the runtime of the OTLP exporter")
+        });
+        let _guard = runtime.enter();
+
+        let exporter = SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(config.endpoint.clone())
+            .with_timeout(config.export_timeout)
+            .build()
+            .unwrap_or_else(|err| {
+                panic!(
+                    "cannot create OTLP exporter for {}, error: {}",
+                    config.endpoint, err
+                )
+            });
+
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_sampler(Sampler::TraceIdRatioBased(config.sampling_ratio))
+            .with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                config.service_name.clone(),
+            )]))
+            .build();
+
+        let tracer = provider.tracer(config.service_name.clone());
+
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(provider);
+
+        tracing_opentelemetry::layer().with_tracer(tracer)
+    }
+
+    pub fn set_parent_from_headers(span: &tracing::Span, headers: &http::HeaderMap) {
+        let context = global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(headers))
+        });
+        if context.span().span_context().is_valid() {
+            span.set_parent(context);
+        }
+    }
+
+    pub fn set_parent(span: &tracing::Span, trace_id: u128, span_id: u64) {
+        let span_context = SpanContext::new(
+            TraceId::from_bytes(trace_id.to_be_bytes()),
+            SpanId::from_bytes(span_id.to_be_bytes()),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        span.set_parent(Context::new().with_remote_span_context(span_context));
+    }
+
+    struct HeaderExtractor<'a>(&'a http::HeaderMap);
+
+    impl Extractor for HeaderExtractor<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|value| value.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|key| key.as_str()).collect()
+        }
+    }
+}
+
 pub(crate) mod format {
     use std::collections::BTreeSet;
     use std::{fmt, io};
@@ -652,4 +813,37 @@ mod test {
             assert_eq!(logs.matches("\"value_4\"").count(), 1);
         }
     }
+
+    #[cfg(feature = "model")]
+    mod otlp {
+        use test_r::test;
+
+        use opentelemetry::trace::{TraceContextExt, TraceId, TracerProvider as _};
+        use opentelemetry_sdk::trace::TracerProvider;
+        use tracing::info_span;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        use tracing_subscriber::layer::SubscriberExt;
+        use tracing_subscriber::Registry;
+
+        use crate::model::invocation_context::InvocationContextStack;
+        use crate::tracing::set_parent_from_invocation_context;
+
+        #[test]
+        fn spans_join_the_trace_of_the_invocation_context() {
+            let provider = TracerProvider::builder().build();
+            let subscriber = Registry::default()
+                .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+            tracing::subscriber::with_default(subscriber, || {
+                let invocation_context = InvocationContextStack::fresh();
+                let span = info_span!("invocation");
+                set_parent_from_invocation_context(&span, &invocation_context);
+
+                assert_eq!(
+                    span.context().span().span_context().trace_id(),
+                    TraceId::from_bytes(invocation_context.trace_id.0.get().to_be_bytes())
+                );
+            });
+        }
+    }
 }
@@ -16,6 +16,7 @@ use crate::service::compile_service::ComponentCompilationServiceImpl;
 use config::ServerConfig;
 use golem_api_grpc::proto::golem::componentcompilation::v1::component_compilation_service_server::ComponentCompilationServiceServer;
 use golem_service_base::config::BlobStorageConfig;
+use golem_service_base::storage::blob::azure::AzureBlobStorage;
 use golem_service_base::storage::blob::s3::S3BlobStorage;
 use golem_service_base::storage::blob::sqlite::SqliteBlobStorage;
 use golem_service_base::storage::blob::BlobStorage;
@@ -60,6 +61,10 @@ pub async fn run(
             info!("Using S3 for blob storage");
             Arc::new(S3BlobStorage::new(config.clone()).await)
         }
+        BlobStorageConfig::AzureBlob(config) => {
+            info!("Using Azure Blob Storage for blob storage");
+            Arc::new(AzureBlobStorage::new(config.clone()).map_err(|err| anyhow!(err))?)
+        }
         BlobStorageConfig::LocalFileSystem(config) => {
             info!(
                 "Using local file system for blob storage at {:?}",
@@ -76,3 +76,37 @@ impl ComponentCompilationEnabledConfig {
             .expect("Failed to build ComponentCompilationService URI")
     }
 }
+
+// The worker service asked for the workers of components before deleting them
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "config")]
+pub enum WorkerServiceConfig {
+    Enabled(WorkerServiceEnabledConfig),
+    Disabled(Empty),
+}
+
+impl Default for WorkerServiceConfig {
+    fn default() -> Self {
+        Self::Enabled(WorkerServiceEnabledConfig {
+            host: "localhost".to_string(),
+            port: 9007,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerServiceEnabledConfig {
+    pub host: String,
+    pub port: u16,
+}
+
+impl WorkerServiceEnabledConfig {
+    pub fn uri(&self) -> Uri {
+        Uri::builder()
+            .scheme("http")
+            .authority(format!("{}:{}", self.host, self.port).as_str())
+            .path_and_query("/")
+            .build()
+            .expect("Failed to build WorkerService URI")
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// Whether a new version of a component is compatible with the API definitions using it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentCompatibility {
+    pub compatible: bool,
+    /// Functions called by API definitions which are no longer exported
+    pub missing_functions: Vec<String>,
+    /// Functions called by API definitions which are exported with different types
+    pub conflicting_functions: Vec<FunctionTypeConflict>,
+}
+
+/// The types of a function as called by the API definitions and as exported by the new version,
+/// in Rib syntax
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct FunctionTypeConflict {
+    pub function: String,
+    pub existing_parameter_types: String,
+    pub new_parameter_types: String,
+    pub existing_result_types: String,
+    pub new_result_types: String,
+}
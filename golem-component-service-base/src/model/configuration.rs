@@ -0,0 +1,210 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_service_base::model::ResourceLimits;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Configuration of the workers of components. Settings missing from the configuration of a
+/// component are inherited from the defaults of its project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentConfiguration {
+    /// Environment variables of the workers, merged by name with the inherited ones
+    pub env: Option<BTreeMap<String, String>>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub resource_limits: Option<ResourceLimits>,
+    /// The hosts the workers are allowed to reach, replacing the inherited rules
+    pub egress_rules: Option<Vec<EgressRule>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub min_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub multiplier: f64,
+    pub max_jitter_factor: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct EgressRule {
+    /// The host name, or a pattern of host names starting with `*.`
+    pub host: String,
+    /// Any port is allowed if missing
+    pub port: Option<u16>,
+}
+
+/// The configuration of a component after applying its settings over the project defaults
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct EffectiveComponentConfiguration {
+    pub project_defaults: ComponentConfiguration,
+    pub component_overrides: ComponentConfiguration,
+    pub effective: ComponentConfiguration,
+}
+
+impl ComponentConfiguration {
+    // Applies the settings of this configuration over the inherited ones
+    pub fn inherit(&self, defaults: &ComponentConfiguration) -> ComponentConfiguration {
+        let env = match (&defaults.env, &self.env) {
+            (Some(inherited), Some(own)) => {
+                let mut env = inherited.clone();
+                env.extend(own.clone());
+                Some(env)
+            }
+            (inherited, own) => own.clone().or(inherited.clone()),
+        };
+
+        ComponentConfiguration {
+            env,
+            retry_policy: self.retry_policy.clone().or(defaults.retry_policy.clone()),
+            resource_limits: self
+                .resource_limits
+                .clone()
+                .or(defaults.resource_limits.clone()),
+            egress_rules: self.egress_rules.clone().or(defaults.egress_rules.clone()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(env) = &self.env {
+            if let Some(name) = env
+                .keys()
+                .find(|name| name.is_empty() || name.contains('=') || name.contains('\0'))
+            {
+                return Err(format!("Invalid environment variable name: {name:?}"));
+            }
+        }
+
+        if let Some(retry_policy) = &self.retry_policy {
+            if retry_policy.min_delay_ms > retry_policy.max_delay_ms {
+                return Err(
+                    "The minimum delay of the retry policy is larger than its maximum delay"
+                        .to_string(),
+                );
+            }
+            if retry_policy.multiplier.is_nan() || retry_policy.multiplier < 1.0 {
+                return Err("The multiplier of the retry policy must be at least 1".to_string());
+            }
+            if retry_policy
+                .max_jitter_factor
+                .is_some_and(|factor| !(0.0..=1.0).contains(&factor))
+            {
+                return Err(
+                    "The maximum jitter factor of the retry policy must be between 0 and 1"
+                        .to_string(),
+                );
+            }
+        }
+
+        if let Some(resource_limits) = &self.resource_limits {
+            if resource_limits.available_fuel < 0 || resource_limits.max_memory_per_worker < 0 {
+                return Err("Resource limits cannot be negative".to_string());
+            }
+        }
+
+        if let Some(egress_rules) = &self.egress_rules {
+            for rule in egress_rules {
+                let host = rule.host.strip_prefix("*.").unwrap_or(&rule.host);
+                if host.is_empty() || host.contains(['*', '/', ':', ' ']) {
+                    return Err(format!("Invalid egress rule host: {:?}", rule.host));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> Option<BTreeMap<String, String>> {
+        Some(
+            vars.iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn component_settings_override_project_defaults() {
+        let defaults = ComponentConfiguration {
+            env: env(&[("REGION", "eu"), ("LOG_LEVEL", "info")]),
+            retry_policy: Some(RetryPolicy {
+                max_attempts: 3,
+                min_delay_ms: 100,
+                max_delay_ms: 1000,
+                multiplier: 2.0,
+                max_jitter_factor: None,
+            }),
+            resource_limits: None,
+            egress_rules: Some(vec![EgressRule {
+                host: "*.example.com".to_string(),
+                port: None,
+            }]),
+        };
+        let overrides = ComponentConfiguration {
+            env: env(&[("LOG_LEVEL", "debug")]),
+            retry_policy: None,
+            resource_limits: Some(ResourceLimits {
+                available_fuel: 1000,
+                max_memory_per_worker: 4096,
+            }),
+            egress_rules: Some(vec![]),
+        };
+
+        let effective = overrides.inherit(&defaults);
+
+        assert_eq!(
+            effective,
+            ComponentConfiguration {
+                env: env(&[("REGION", "eu"), ("LOG_LEVEL", "debug")]),
+                retry_policy: defaults.retry_policy.clone(),
+                resource_limits: overrides.resource_limits.clone(),
+                egress_rules: Some(vec![]),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_configurations_are_rejected() {
+        let invalid_env = ComponentConfiguration {
+            env: env(&[("A=B", "c")]),
+            ..Default::default()
+        };
+        let invalid_host = ComponentConfiguration {
+            egress_rules: Some(vec![EgressRule {
+                host: "example.*".to_string(),
+                port: Some(443),
+            }]),
+            ..Default::default()
+        };
+
+        assert!(invalid_env.validate().is_err());
+        assert!(invalid_host.validate().is_err());
+        assert!(ComponentConfiguration::default().validate().is_ok());
+    }
+}
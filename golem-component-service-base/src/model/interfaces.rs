@@ -0,0 +1,167 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{ComponentWit, WitWorldItem};
+use golem_common::model::ComponentVersion;
+use golem_wasm_ast::analysis::{
+    AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// The interfaces a component version imports and exports, with the exported functions in the
+/// form they can be called from Rib
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentInterfaces {
+    pub version: ComponentVersion,
+    /// Fully qualified names of the imported interfaces
+    pub imports: Vec<String>,
+    pub exports: Vec<ExportedInterface>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ExportedInterface {
+    /// Fully qualified name of the interface, or empty for the functions exported by the world
+    pub name: String,
+    pub functions: Vec<ExportedFunction>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ExportedFunction {
+    pub name: String,
+    /// The name to call the function by from Rib, such as `golem:it/api.{add-item}`
+    pub rib_name: String,
+    pub parameters: Vec<AnalysedFunctionParameter>,
+    pub results: Vec<AnalysedFunctionResult>,
+}
+
+impl ComponentInterfaces {
+    pub fn new(version: ComponentVersion, wit: &ComponentWit, exports: &[AnalysedExport]) -> Self {
+        let imports = wit
+            .imports
+            .iter()
+            .filter_map(|item| match item {
+                WitWorldItem::Interface(interface) => Some(interface.name.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let mut root = ExportedInterface {
+            name: String::new(),
+            functions: vec![],
+        };
+        let mut interfaces = vec![];
+        for export in exports {
+            match export {
+                AnalysedExport::Function(function) => {
+                    root.functions.push(exported_function(None, function))
+                }
+                AnalysedExport::Instance(instance) => interfaces.push(ExportedInterface {
+                    name: instance.name.clone(),
+                    functions: instance
+                        .functions
+                        .iter()
+                        .map(|function| exported_function(Some(&instance.name), function))
+                        .collect(),
+                }),
+            }
+        }
+        if !root.functions.is_empty() {
+            interfaces.insert(0, root);
+        }
+
+        ComponentInterfaces {
+            version,
+            imports,
+            exports: interfaces,
+        }
+    }
+}
+
+fn exported_function(interface: Option<&str>, function: &AnalysedFunction) -> ExportedFunction {
+    ExportedFunction {
+        name: function.name.clone(),
+        rib_name: match interface {
+            Some(interface) => format!("{interface}.{{{}}}", function.name),
+            None => function.name.clone(),
+        },
+        parameters: function.parameters.clone(),
+        results: function.results.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use crate::model::WitInterface;
+    use golem_wasm_ast::analysis::analysed_type::{str, u32};
+    use golem_wasm_ast::analysis::AnalysedInstance;
+
+    #[test]
+    fn exported_functions_get_their_rib_names() {
+        let wit = ComponentWit {
+            name: "golem:it/shopping-cart".to_string(),
+            docs: None,
+            imports: vec![WitWorldItem::Interface(WitInterface {
+                name: "wasi:io/streams@0.2.0".to_string(),
+                docs: None,
+                types: vec![],
+                functions: vec![],
+            })],
+            exports: vec![],
+        };
+        let add_item = AnalysedFunction {
+            name: "add-item".to_string(),
+            parameters: vec![AnalysedFunctionParameter {
+                name: "name".to_string(),
+                typ: str(),
+            }],
+            results: vec![AnalysedFunctionResult {
+                name: None,
+                typ: u32(),
+            }],
+        };
+        let exports = vec![
+            AnalysedExport::Instance(AnalysedInstance {
+                name: "golem:it/api".to_string(),
+                functions: vec![add_item.clone()],
+            }),
+            AnalysedExport::Function(add_item),
+        ];
+
+        let interfaces = ComponentInterfaces::new(3, &wit, &exports);
+
+        assert_eq!(
+            interfaces.imports,
+            vec!["wasi:io/streams@0.2.0".to_string()]
+        );
+        assert_eq!(interfaces.exports.len(), 2);
+        assert_eq!(interfaces.exports[0].name, "");
+        assert_eq!(interfaces.exports[0].functions[0].rib_name, "add-item");
+        assert_eq!(interfaces.exports[1].name, "golem:it/api");
+        assert_eq!(
+            interfaces.exports[1].functions[0].rib_name,
+            "golem:it/api.{add-item}"
+        );
+        assert_eq!(interfaces.exports[1].functions[0].results[0].typ, u32());
+    }
+}
@@ -12,17 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod compatibility;
 mod component;
+mod configuration;
+mod interfaces;
+mod upload;
+mod wit;
 
 use bincode::{Decode, Encode};
+pub use compatibility::*;
 pub use component::*;
+pub use configuration::*;
 use golem_common::model::component_metadata::DynamicLinkedInstance;
 use golem_common::model::{ComponentFilePathWithPermissionsList, ComponentType};
 use golem_service_base::poem::TempFileUpload;
+pub use interfaces::*;
 use poem_openapi::types::multipart::{JsonField, Upload};
 use poem_openapi::{Multipart, Object};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+pub use upload::*;
+pub use wit::*;
 
 #[derive(Multipart)]
 #[oai(rename_all = "camelCase")]
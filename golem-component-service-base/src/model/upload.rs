@@ -0,0 +1,113 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Utc};
+use golem_common::model::{ComponentId, ComponentType};
+use golem_service_base::model::ComponentName;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Starts a resumable upload of a new component when its name is given, or of a new version of
+/// an existing component when its id is given
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentUploadRequest {
+    pub component_name: Option<ComponentName>,
+    pub component_id: Option<ComponentId>,
+    /// The type of the component, by default `Durable` for new components, and the type of the
+    /// previous version for new versions
+    pub component_type: Option<ComponentType>,
+}
+
+/// An uploaded part of a resumable component upload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentUploadPart {
+    pub part_number: u32,
+    pub size: u64,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// A resumable upload of a component. Its parts can be uploaded in any order and retried, and
+/// the component is created from them once the upload is completed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentUpload {
+    pub upload_id: Uuid,
+    /// The id of the component created or updated by the upload
+    pub component_id: ComponentId,
+    /// The name of the created component, missing when uploading a new version
+    pub component_name: Option<ComponentName>,
+    pub component_type: Option<ComponentType>,
+    /// The uploaded parts, ordered by their number
+    pub parts: Vec<ComponentUploadPart>,
+    /// The total size of the uploaded parts
+    pub size: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ComponentUpload {
+    pub fn is_new_component(&self) -> bool {
+        self.component_name.is_some()
+    }
+
+    // The parts have to be numbered contiguously from 1 to be completed
+    pub fn missing_parts(&self) -> Vec<u32> {
+        let last = self.parts.last().map_or(0, |part| part.part_number);
+        (1..=last)
+            .filter(|number| {
+                self.parts
+                    .binary_search_by_key(number, |part| part.part_number)
+                    .is_err()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+
+    fn upload(part_numbers: &[u32]) -> ComponentUpload {
+        ComponentUpload {
+            upload_id: Uuid::new_v4(),
+            component_id: ComponentId::new_v4(),
+            component_name: Some(ComponentName("component".to_string())),
+            component_type: None,
+            parts: part_numbers
+                .iter()
+                .map(|part_number| ComponentUploadPart {
+                    part_number: *part_number,
+                    size: 10,
+                    uploaded_at: Utc::now(),
+                })
+                .collect(),
+            size: 10 * part_numbers.len() as u64,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn missing_parts_are_the_gaps_before_the_last_part() {
+        assert!(upload(&[]).missing_parts().is_empty());
+        assert!(upload(&[1, 2, 3]).missing_parts().is_empty());
+        assert_eq!(upload(&[2, 5]).missing_parts(), vec![1, 3, 4]);
+    }
+}
@@ -0,0 +1,476 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::{Enum, Object, Union};
+use serde::{Deserialize, Serialize};
+use wit_parser::decoding::DecodedWasm;
+use wit_parser::{
+    Docs, Function, FunctionKind, Handle, Interface, Resolve, Results, Type, TypeDefKind, TypeId,
+    WorldId, WorldItem, WorldKey,
+};
+
+/// The WIT world of a component, with the documentation of its items
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentWit {
+    /// Fully qualified name of the world
+    pub name: String,
+    pub docs: Option<String>,
+    pub imports: Vec<WitWorldItem>,
+    pub exports: Vec<WitWorldItem>,
+}
+
+/// An interface, function or type imported or exported by a world
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[oai(discriminator_name = "type", one_of = true)]
+#[serde(tag = "type")]
+pub enum WitWorldItem {
+    Interface(WitInterface),
+    Function(WitFunction),
+    TypeDefinition(WitTypeDefinition),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WitInterface {
+    /// Fully qualified name of the interface, or its name in the world for inline interfaces
+    pub name: String,
+    pub docs: Option<String>,
+    pub types: Vec<WitTypeDefinition>,
+    pub functions: Vec<WitFunction>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum WitFunctionKind {
+    Freestanding,
+    Method,
+    Static,
+    Constructor,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WitFunction {
+    /// Name of the function, in the `[method]resource.name` form for resource functions
+    pub name: String,
+    pub docs: Option<String>,
+    pub kind: WitFunctionKind,
+    /// The resource of methods, static functions and constructors
+    pub resource: Option<String>,
+    pub params: Vec<WitParameter>,
+    /// Results of the function, only named if the function has named results
+    pub results: Vec<WitResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WitParameter {
+    pub name: String,
+    /// The type in WIT syntax
+    #[serde(rename = "type")]
+    #[oai(rename = "type")]
+    pub typ: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WitResult {
+    pub name: Option<String>,
+    /// The type in WIT syntax
+    #[serde(rename = "type")]
+    #[oai(rename = "type")]
+    pub typ: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum WitTypeKind {
+    Record,
+    Variant,
+    Enum,
+    Flags,
+    Resource,
+    Alias,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WitTypeDefinition {
+    pub name: String,
+    pub docs: Option<String>,
+    pub kind: WitTypeKind,
+    /// The aliased type in WIT syntax, for type aliases
+    pub alias_of: Option<String>,
+    /// Fields of records, cases of variants and enums, and flags
+    pub members: Vec<WitTypeMember>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WitTypeMember {
+    pub name: String,
+    pub docs: Option<String>,
+    /// The type of record fields and variant cases with a payload, in WIT syntax
+    #[serde(rename = "type")]
+    #[oai(rename = "type")]
+    pub typ: Option<String>,
+}
+
+impl ComponentWit {
+    /// Decodes the WIT world of a component binary, including the docs embedded in it
+    pub fn decode(data: &[u8]) -> Result<ComponentWit, String> {
+        match wit_parser::decoding::decode(data)
+            .map_err(|err| format!("Failed to decode the WIT of the component: {err}"))?
+        {
+            DecodedWasm::Component(resolve, world) => Ok(Self::from_resolve(&resolve, world)),
+            DecodedWasm::WitPackage(_, _) => {
+                Err("Expected a component, got a WIT package".to_string())
+            }
+        }
+    }
+
+    pub fn from_resolve(resolve: &Resolve, world_id: WorldId) -> ComponentWit {
+        let world = &resolve.worlds[world_id];
+        let name = match world.package {
+            Some(package) => resolve.id_of_name(package, &world.name),
+            None => world.name.clone(),
+        };
+
+        ComponentWit {
+            name,
+            docs: docs(&world.docs),
+            imports: world
+                .imports
+                .iter()
+                .map(|(key, item)| world_item(resolve, key, item))
+                .collect(),
+            exports: world
+                .exports
+                .iter()
+                .map(|(key, item)| world_item(resolve, key, item))
+                .collect(),
+        }
+    }
+}
+
+fn world_item(resolve: &Resolve, key: &WorldKey, item: &WorldItem) -> WitWorldItem {
+    match item {
+        WorldItem::Interface { id, .. } => {
+            let name = match key {
+                WorldKey::Name(name) => name.clone(),
+                WorldKey::Interface(id) => resolve
+                    .id_of(*id)
+                    .unwrap_or_else(|| resolve.name_world_key(key)),
+            };
+            WitWorldItem::Interface(interface(resolve, name, &resolve.interfaces[*id]))
+        }
+        WorldItem::Function(f) => WitWorldItem::Function(function(resolve, f)),
+        WorldItem::Type(id) => WitWorldItem::TypeDefinition(type_definition(resolve, *id)),
+    }
+}
+
+fn interface(resolve: &Resolve, name: String, interface: &Interface) -> WitInterface {
+    WitInterface {
+        name,
+        docs: docs(&interface.docs),
+        types: interface
+            .types
+            .values()
+            .map(|id| type_definition(resolve, *id))
+            .collect(),
+        functions: interface
+            .functions
+            .values()
+            .map(|f| function(resolve, f))
+            .collect(),
+    }
+}
+
+fn function(resolve: &Resolve, function: &Function) -> WitFunction {
+    let (kind, resource) = match &function.kind {
+        FunctionKind::Freestanding => (WitFunctionKind::Freestanding, None),
+        FunctionKind::Method(id) => (WitFunctionKind::Method, Some(*id)),
+        FunctionKind::Static(id) => (WitFunctionKind::Static, Some(*id)),
+        FunctionKind::Constructor(id) => (WitFunctionKind::Constructor, Some(*id)),
+    };
+
+    WitFunction {
+        name: function.name.clone(),
+        docs: docs(&function.docs),
+        kind,
+        resource: resource.map(|id| type_name(resolve, &Type::Id(id))),
+        params: function
+            .params
+            .iter()
+            .map(|(name, typ)| WitParameter {
+                name: name.clone(),
+                typ: type_name(resolve, typ),
+            })
+            .collect(),
+        results: match &function.results {
+            Results::Named(results) => results
+                .iter()
+                .map(|(name, typ)| WitResult {
+                    name: Some(name.clone()),
+                    typ: type_name(resolve, typ),
+                })
+                .collect(),
+            Results::Anon(typ) => vec![WitResult {
+                name: None,
+                typ: type_name(resolve, typ),
+            }],
+        },
+    }
+}
+
+fn type_definition(resolve: &Resolve, id: TypeId) -> WitTypeDefinition {
+    let typedef = &resolve.types[id];
+    let member = |name: &String, typ: Option<&Type>, member_docs: &Docs| WitTypeMember {
+        name: name.clone(),
+        docs: docs(member_docs),
+        typ: typ.map(|typ| type_name(resolve, typ)),
+    };
+
+    let (kind, alias_of, members) = match &typedef.kind {
+        TypeDefKind::Record(record) => (
+            WitTypeKind::Record,
+            None,
+            record
+                .fields
+                .iter()
+                .map(|field| member(&field.name, Some(&field.ty), &field.docs))
+                .collect(),
+        ),
+        TypeDefKind::Variant(variant) => (
+            WitTypeKind::Variant,
+            None,
+            variant
+                .cases
+                .iter()
+                .map(|case| member(&case.name, case.ty.as_ref(), &case.docs))
+                .collect(),
+        ),
+        TypeDefKind::Enum(enum_) => (
+            WitTypeKind::Enum,
+            None,
+            enum_
+                .cases
+                .iter()
+                .map(|case| member(&case.name, None, &case.docs))
+                .collect(),
+        ),
+        TypeDefKind::Flags(flags) => (
+            WitTypeKind::Flags,
+            None,
+            flags
+                .flags
+                .iter()
+                .map(|flag| member(&flag.name, None, &flag.docs))
+                .collect(),
+        ),
+        TypeDefKind::Resource => (WitTypeKind::Resource, None, vec![]),
+        TypeDefKind::Type(typ) => (WitTypeKind::Alias, Some(type_name(resolve, typ)), vec![]),
+        _ => (
+            WitTypeKind::Alias,
+            Some(anonymous_type_name(resolve, &typedef.kind)),
+            vec![],
+        ),
+    };
+
+    WitTypeDefinition {
+        name: typedef.name.clone().unwrap_or_default(),
+        docs: docs(&typedef.docs),
+        kind,
+        alias_of,
+        members,
+    }
+}
+
+fn type_name(resolve: &Resolve, typ: &Type) -> String {
+    match typ {
+        Type::Bool => "bool".to_string(),
+        Type::U8 => "u8".to_string(),
+        Type::U16 => "u16".to_string(),
+        Type::U32 => "u32".to_string(),
+        Type::U64 => "u64".to_string(),
+        Type::S8 => "s8".to_string(),
+        Type::S16 => "s16".to_string(),
+        Type::S32 => "s32".to_string(),
+        Type::S64 => "s64".to_string(),
+        Type::F32 => "f32".to_string(),
+        Type::F64 => "f64".to_string(),
+        Type::Char => "char".to_string(),
+        Type::String => "string".to_string(),
+        Type::Id(id) => {
+            let typedef = &resolve.types[*id];
+            match &typedef.name {
+                Some(name) => name.clone(),
+                None => anonymous_type_name(resolve, &typedef.kind),
+            }
+        }
+    }
+}
+
+fn anonymous_type_name(resolve: &Resolve, kind: &TypeDefKind) -> String {
+    match kind {
+        TypeDefKind::Option(typ) => format!("option<{}>", type_name(resolve, typ)),
+        TypeDefKind::Result(result) => match (&result.ok, &result.err) {
+            (None, None) => "result".to_string(),
+            (Some(ok), None) => format!("result<{}>", type_name(resolve, ok)),
+            (None, Some(err)) => format!("result<_, {}>", type_name(resolve, err)),
+            (Some(ok), Some(err)) => format!(
+                "result<{}, {}>",
+                type_name(resolve, ok),
+                type_name(resolve, err)
+            ),
+        },
+        TypeDefKind::List(typ) => format!("list<{}>", type_name(resolve, typ)),
+        TypeDefKind::Tuple(tuple) => format!(
+            "tuple<{}>",
+            tuple
+                .types
+                .iter()
+                .map(|typ| type_name(resolve, typ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TypeDefKind::Handle(Handle::Own(id)) => type_name(resolve, &Type::Id(*id)),
+        TypeDefKind::Handle(Handle::Borrow(id)) => {
+            format!("borrow<{}>", type_name(resolve, &Type::Id(*id)))
+        }
+        TypeDefKind::Future(None) => "future".to_string(),
+        TypeDefKind::Future(Some(typ)) => format!("future<{}>", type_name(resolve, typ)),
+        TypeDefKind::Stream(stream) => match &stream.element {
+            Some(typ) => format!("stream<{}>", type_name(resolve, typ)),
+            None => "stream".to_string(),
+        },
+        TypeDefKind::Type(typ) => type_name(resolve, typ),
+        other => other.as_str().to_string(),
+    }
+}
+
+fn docs(docs: &Docs) -> Option<String> {
+    docs.contents.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+
+    #[test]
+    fn world_items_are_described_with_docs_and_wit_types() {
+        let mut resolve = Resolve::new();
+        let package = resolve
+            .push_str(
+                "cart.wit",
+                r#"
+                package golem:it;
+
+                /// Shopping cart operations
+                interface api {
+                  /// A product in the cart
+                  record product-item {
+                    product-id: string,
+                    /// Number of items
+                    quantity: u32,
+                  }
+
+                  resource cart {
+                    constructor(user-id: string);
+                    add-item: func(item: product-item);
+                  }
+
+                  /// Checks out the cart
+                  checkout: func() -> result<string, string>;
+                  get-cart-contents: func() -> list<product-item>;
+                }
+
+                world shopping-cart {
+                  import wasi:clocks/wall-clock@0.2.0;
+                  export api;
+                }
+
+                package wasi:clocks@0.2.0 {
+                  interface wall-clock {
+                    record datetime { seconds: u64, nanoseconds: u32 }
+                    now: func() -> datetime;
+                  }
+                }
+                "#,
+            )
+            .unwrap();
+        let world = resolve
+            .select_world(package, Some("shopping-cart"))
+            .unwrap();
+
+        let wit = ComponentWit::from_resolve(&resolve, world);
+
+        assert_eq!(wit.name, "golem:it/shopping-cart");
+        let WitWorldItem::Interface(import) = &wit.imports[0] else {
+            panic!("expected an imported interface")
+        };
+        assert_eq!(import.name, "wasi:clocks/wall-clock@0.2.0");
+
+        let WitWorldItem::Interface(api) = &wit.exports[0] else {
+            panic!("expected an exported interface")
+        };
+        assert_eq!(api.name, "golem:it/api");
+        assert_eq!(api.docs, Some("Shopping cart operations".to_string()));
+
+        let product_item = &api.types[0];
+        assert_eq!(product_item.kind, WitTypeKind::Record);
+        assert_eq!(product_item.docs, Some("A product in the cart".to_string()));
+        assert_eq!(
+            product_item.members[1],
+            WitTypeMember {
+                name: "quantity".to_string(),
+                docs: Some("Number of items".to_string()),
+                typ: Some("u32".to_string()),
+            }
+        );
+        assert_eq!(api.types[1].kind, WitTypeKind::Resource);
+
+        let checkout = api.functions.iter().find(|f| f.name == "checkout").unwrap();
+        assert_eq!(checkout.docs, Some("Checks out the cart".to_string()));
+        assert_eq!(checkout.results[0].typ, "result<string, string>");
+
+        let add_item = api
+            .functions
+            .iter()
+            .find(|f| f.name == "[method]cart.add-item")
+            .unwrap();
+        assert_eq!(add_item.kind, WitFunctionKind::Method);
+        assert_eq!(add_item.resource, Some("cart".to_string()));
+        assert_eq!(
+            add_item
+                .params
+                .iter()
+                .map(|param| param.typ.as_str())
+                .collect::<Vec<_>>(),
+            vec!["borrow<cart>", "product-item"]
+        );
+    }
+}
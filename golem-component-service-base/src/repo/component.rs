@@ -155,6 +155,15 @@ impl<Owner: ComponentOwner> TryFrom<ComponentRecord<Owner>> for Component<Owner>
     }
 }
 
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct DeletedComponentRecord {
+    pub namespace: String,
+    pub component_id: Uuid,
+    pub name: String,
+    pub deleted_at: DateTime<Utc>,
+    pub deleted_by: String,
+}
+
 #[derive(sqlx::FromRow, Debug, Clone)]
 pub struct ComponentConstraintsRecord {
     pub namespace: String,
@@ -245,6 +254,9 @@ pub trait ComponentRepo<Owner: ComponentOwner>: Debug {
     /// Creates a new component version (ignores component.version) and copies the plugin
     /// installations from the previous latest version.
     ///
+    /// When `expected_version` is set, the new version is only created if it is still the latest
+    /// one (including versions not activated yet), otherwise nothing changes and `None` is returned.
+    ///
     /// Returns the updated component.
     async fn update(
         &self,
@@ -255,7 +267,8 @@ pub trait ComponentRepo<Owner: ComponentOwner>: Debug {
         metadata: Vec<u8>,
         component_type: Option<i32>,
         files: Option<Vec<FileRecord>>,
-    ) -> Result<ComponentRecord<Owner>, RepoError>;
+        expected_version: Option<i64>,
+    ) -> Result<Option<ComponentRecord<Owner>>, RepoError>;
 
     /// Activates a component version previously created with `update`.
     ///
@@ -304,6 +317,29 @@ pub trait ComponentRepo<Owner: ComponentOwner>: Debug {
 
     async fn delete(&self, namespace: &str, component_id: &Uuid) -> Result<(), RepoError>;
 
+    /// Moves a component to the trash, hiding it from listings and name based lookups.
+    ///
+    /// Returns false if the component does not exist or is already in the trash.
+    async fn soft_delete(
+        &self,
+        namespace: &str,
+        component_id: &Uuid,
+        deleted_by: &str,
+    ) -> Result<bool, RepoError>;
+
+    /// Restores a component from the trash.
+    ///
+    /// Returns false if the component is not in the trash.
+    async fn restore(&self, namespace: &str, component_id: &Uuid) -> Result<bool, RepoError>;
+
+    async fn get_deleted(&self, namespace: &str) -> Result<Vec<DeletedComponentRecord>, RepoError>;
+
+    /// Gets the components of all namespaces that were moved to the trash before the given time
+    async fn get_deleted_before(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<DeletedComponentRecord>, RepoError>;
+
     async fn create_or_update_constraint(
         &self,
         component_constraint_record: &ComponentConstraintsRecord,
@@ -409,7 +445,8 @@ impl<Owner: ComponentOwner, Repo: ComponentRepo<Owner> + Send + Sync> ComponentR
         metadata: Vec<u8>,
         component_type: Option<i32>,
         files: Option<Vec<FileRecord>>,
-    ) -> Result<ComponentRecord<Owner>, RepoError> {
+        expected_version: Option<i64>,
+    ) -> Result<Option<ComponentRecord<Owner>>, RepoError> {
         let result = self
             .repo
             .update(
@@ -420,6 +457,7 @@ impl<Owner: ComponentOwner, Repo: ComponentRepo<Owner> + Send + Sync> ComponentR
                 metadata,
                 component_type,
                 files,
+                expected_version,
             )
             .await;
         Self::logged_with_id("update", component_id, result)
@@ -508,6 +546,37 @@ impl<Owner: ComponentOwner, Repo: ComponentRepo<Owner> + Send + Sync> ComponentR
         Self::logged_with_id("delete", component_id, result)
     }
 
+    async fn soft_delete(
+        &self,
+        namespace: &str,
+        component_id: &Uuid,
+        deleted_by: &str,
+    ) -> Result<bool, RepoError> {
+        let result = self
+            .repo
+            .soft_delete(namespace, component_id, deleted_by)
+            .await;
+        Self::logged_with_id("soft_delete", component_id, result)
+    }
+
+    async fn restore(&self, namespace: &str, component_id: &Uuid) -> Result<bool, RepoError> {
+        let result = self.repo.restore(namespace, component_id).await;
+        Self::logged_with_id("restore", component_id, result)
+    }
+
+    async fn get_deleted(&self, namespace: &str) -> Result<Vec<DeletedComponentRecord>, RepoError> {
+        let result = self.repo.get_deleted(namespace).await;
+        Self::logged("get_deleted", result)
+    }
+
+    async fn get_deleted_before(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<DeletedComponentRecord>, RepoError> {
+        let result = self.repo.get_deleted_before(deleted_before).await;
+        Self::logged("get_deleted_before", result)
+    }
+
     async fn create_or_update_constraint(
         &self,
         component_constraint_record: &ComponentConstraintsRecord,
@@ -802,7 +871,8 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
         metadata: Vec<u8>,
         component_type: Option<i32>,
         files: Option<Vec<FileRecord>>,
-    ) -> Result<ComponentRecord<Owner>, RepoError> {
+        expected_version: Option<i64>,
+    ) -> Result<Option<ComponentRecord<Owner>>, RepoError> {
         let mut transaction = self.db_pool.begin().await?;
 
         let result = sqlx::query("SELECT namespace FROM components WHERE component_id = $1")
@@ -819,7 +889,8 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                 ))
             } else {
                 let now = Utc::now();
-                let new_version = if let Some(component_type) = component_type {
+                // With an expected version, the new version is only inserted on top of it
+                let inserted = if let Some(component_type) = component_type {
                     sqlx::query(
                         r#"
                               WITH prev AS (SELECT component_id, version, object_store_key, transformed_object_store_key
@@ -828,6 +899,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                                    LIMIT 1)
                               INSERT INTO component_versions
                               SELECT prev.component_id, prev.version + 1, $2, $3, $4, $5, FALSE, prev.object_store_key, prev.transformed_object_store_key FROM prev
+                              WHERE $6 IS NULL OR prev.version = $6
                               RETURNING *
                               "#,
                     )
@@ -836,9 +908,9 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                         .bind(now)
                         .bind(metadata)
                         .bind(component_type)
-                        .fetch_one(&mut *transaction)
-                        .await?
-                        .get("version")
+                        .bind(expected_version)
+                        .fetch_optional(&mut *transaction)
+                        .await
                 } else {
                     sqlx::query(
                         r#"
@@ -848,6 +920,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                                    LIMIT 1)
                               INSERT INTO component_versions
                               SELECT prev.component_id, prev.version + 1, $2, $3, $4, prev.component_type, FALSE, prev.object_store_key, prev.transformed_object_store_key FROM prev
+                              WHERE $5 IS NULL OR prev.version = $5
                               RETURNING *
                               "#,
                     )
@@ -855,9 +928,25 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                         .bind(data.len() as i32)
                         .bind(now)
                         .bind(metadata)
-                        .fetch_one(&mut *transaction)
-                        .await?
-                        .get("version")
+                        .bind(expected_version)
+                        .fetch_optional(&mut *transaction)
+                        .await
+                };
+
+                let new_version: i64 = match inserted.map_err(RepoError::from) {
+                    Ok(Some(row)) => row.get("version"),
+                    // a concurrent update inserted the same version first
+                    Ok(None) | Err(RepoError::UniqueViolation(_)) if expected_version.is_some() => {
+                        transaction.rollback().await?;
+                        return Ok(None);
+                    }
+                    Ok(None) => {
+                        transaction.rollback().await?;
+                        return Err(RepoError::Internal(
+                            "Component has no version to update".to_string(),
+                        ));
+                    }
+                    Err(error) => return Err(error),
                 };
 
                 debug!("update created new component version {new_version}");
@@ -953,7 +1042,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                     .get_by_version(namespace, component_id, new_version as u64)
                     .await?;
 
-                component.ok_or(RepoError::Internal(
+                component.map(Some).ok_or(RepoError::Internal(
                     "Could not re-get newly created component version".to_string(),
                 ))
             }
@@ -1086,7 +1175,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                     cv.transformed_object_store_key AS transformed_object_store_key
                 FROM components c
                     JOIN component_versions cv ON c.component_id = cv.component_id
-                WHERE c.namespace = $1
+                WHERE c.namespace = $1 AND c.deleted_at IS NULL
                 ORDER BY cv.component_id, cv.version
                 "#,
         )
@@ -1120,7 +1209,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                     cv.transformed_object_store_key AS transformed_object_store_key
                 FROM components c
                     JOIN component_versions cv ON c.component_id = cv.component_id
-                WHERE c.namespace = $1
+                WHERE c.namespace = $1 AND c.deleted_at IS NULL
                 ORDER BY cv.component_id, cv.version
                 "#,
         )
@@ -1155,7 +1244,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                     cv.transformed_object_store_key AS transformed_object_store_key
                 FROM components c
                     JOIN component_versions cv ON c.component_id = cv.component_id
-                WHERE c.component_id = $1 AND c.namespace = $2 AND cv.available = TRUE
+                WHERE c.component_id = $1 AND c.namespace = $2 AND cv.available = TRUE AND c.deleted_at IS NULL
                 ORDER BY cv.version DESC
                 LIMIT 1
                 "#,
@@ -1194,7 +1283,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                     cv.transformed_object_store_key AS transformed_object_store_key
                 FROM components c
                     JOIN component_versions cv ON c.component_id = cv.component_id
-                WHERE c.component_id = $1 AND c.namespace = $2 AND cv.available = TRUE
+                WHERE c.component_id = $1 AND c.namespace = $2 AND cv.available = TRUE AND c.deleted_at IS NULL
                 ORDER BY cv.version
                 DESC LIMIT 1
                 "#,
@@ -1311,7 +1400,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                     cv.transformed_object_store_key AS transformed_object_store_key
                 FROM components c
                     JOIN component_versions cv ON c.component_id = cv.component_id
-                WHERE c.namespace = $1 AND c.name = $2
+                WHERE c.namespace = $1 AND c.name = $2 AND c.deleted_at IS NULL
                 ORDER BY cv.version
                 "#,
         )
@@ -1347,7 +1436,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
                     cv.transformed_object_store_key AS transformed_object_store_key
                 FROM components c
                     JOIN component_versions cv ON c.component_id = cv.component_id
-                WHERE c.namespace = $1 AND c.name = $2
+                WHERE c.namespace = $1 AND c.name = $2 AND c.deleted_at IS NULL
                 ORDER BY cv.version
                 "#,
         )
@@ -1363,7 +1452,7 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
 
     async fn get_id_by_name(&self, namespace: &str, name: &str) -> Result<Option<Uuid>, RepoError> {
         let result =
-            sqlx::query("SELECT component_id FROM components WHERE namespace = $1 AND name = $2")
+            sqlx::query("SELECT component_id FROM components WHERE namespace = $1 AND name = $2 AND deleted_at IS NULL")
                 .bind(namespace)
                 .bind(name)
                 .fetch_optional(self.db_pool.deref())
@@ -1417,6 +1506,121 @@ impl<Owner: ComponentOwner> ComponentRepo<Owner> for DbComponentRepo<sqlx::Postg
         Ok(())
     }
 
+    async fn soft_delete(
+        &self,
+        namespace: &str,
+        component_id: &Uuid,
+        deleted_by: &str,
+    ) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+                UPDATE components
+                SET deleted_at = $3, deleted_by = $4
+                WHERE namespace = $1 AND component_id = $2 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(namespace)
+        .bind(component_id)
+        .bind(Utc::now())
+        .bind(deleted_by)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn restore(&self, namespace: &str, component_id: &Uuid) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+                UPDATE components
+                SET deleted_at = NULL, deleted_by = NULL
+                WHERE namespace = $1 AND component_id = $2 AND deleted_at IS NOT NULL
+            "#,
+        )
+        .bind(namespace)
+        .bind(component_id)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[when(sqlx::Postgres -> get_deleted)]
+    async fn get_deleted_postgres(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<DeletedComponentRecord>, RepoError> {
+        sqlx::query_as::<_, DeletedComponentRecord>(
+            r#"
+                SELECT namespace, component_id, name, deleted_at::timestamptz AS deleted_at, deleted_by
+                FROM components
+                WHERE namespace = $1 AND deleted_at IS NOT NULL
+                ORDER BY deleted_at
+            "#,
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_deleted)]
+    async fn get_deleted_sqlite(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<DeletedComponentRecord>, RepoError> {
+        sqlx::query_as::<_, DeletedComponentRecord>(
+            r#"
+                SELECT namespace, component_id, name, deleted_at, deleted_by
+                FROM components
+                WHERE namespace = $1 AND deleted_at IS NOT NULL
+                ORDER BY deleted_at
+            "#,
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Postgres -> get_deleted_before)]
+    async fn get_deleted_before_postgres(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<DeletedComponentRecord>, RepoError> {
+        sqlx::query_as::<_, DeletedComponentRecord>(
+            r#"
+                SELECT namespace, component_id, name, deleted_at::timestamptz AS deleted_at, deleted_by
+                FROM components
+                WHERE deleted_at IS NOT NULL AND deleted_at < $1
+                ORDER BY deleted_at
+            "#,
+        )
+        .bind(deleted_before)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_deleted_before)]
+    async fn get_deleted_before_sqlite(
+        &self,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<DeletedComponentRecord>, RepoError> {
+        sqlx::query_as::<_, DeletedComponentRecord>(
+            r#"
+                SELECT namespace, component_id, name, deleted_at, deleted_by
+                FROM components
+                WHERE deleted_at IS NOT NULL AND deleted_at < $1
+                ORDER BY deleted_at
+            "#,
+        )
+        .bind(deleted_before)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
     async fn create_or_update_constraint(
         &self,
         component_constraint_record: &ComponentConstraintsRecord,
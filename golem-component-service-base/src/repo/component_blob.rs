@@ -0,0 +1,298 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use futures::future::BoxFuture;
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool, Transaction};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+// A content-addressed object of the component store, along with the number of component
+// versions referencing it
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct ComponentBlobRecord {
+    pub object_key: String,
+    pub size: i64,
+    pub ref_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+// An upload or a deletion of the object in the component object store, run by the repo while
+// holding the lock of the object, and failing with an error message
+pub type ObjectStoreOperation<'a> = BoxFuture<'a, Result<(), String>>;
+
+#[async_trait]
+pub trait ComponentBlobRepo {
+    async fn get(&self, object_key: &str) -> Result<Option<ComponentBlobRecord>, RepoError>;
+
+    // Adds a reference to an object which is already stored, returning the number of references,
+    // or None if the object is not stored
+    async fn acquire_stored(&self, object_key: &str) -> Result<Option<i64>, RepoError>;
+
+    // Adds a reference to the object, returning the number of references. The object is uploaded
+    // first if it is not stored yet, in the same transaction, so it cannot be deleted in between.
+    async fn acquire(
+        &self,
+        object_key: &str,
+        size: i64,
+        upload: ObjectStoreOperation<'_>,
+    ) -> Result<i64, RepoError>;
+
+    // Removes a reference from the object, returning the number of remaining references, or None
+    // if the object was not referenced. The object is deleted when the last reference is removed,
+    // in the same transaction, so it cannot be referenced again in between.
+    async fn release(
+        &self,
+        object_key: &str,
+        delete: ObjectStoreOperation<'_>,
+    ) -> Result<Option<i64>, RepoError>;
+}
+
+pub struct DbComponentBlobRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbComponentBlobRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedComponentBlobRepo<Repo: ComponentBlobRepo> {
+    repo: Repo,
+}
+
+impl<Repo: ComponentBlobRepo> LoggedComponentBlobRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        object_key: &str,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(object_key = object_key, "{}", message),
+            Err(error) => error!(
+                object_key = object_key,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: ComponentBlobRepo + Send + Sync> ComponentBlobRepo for LoggedComponentBlobRepo<Repo> {
+    async fn get(&self, object_key: &str) -> Result<Option<ComponentBlobRecord>, RepoError> {
+        let result = self.repo.get(object_key).await;
+        Self::logged_with_id("get", object_key, result)
+    }
+
+    async fn acquire_stored(&self, object_key: &str) -> Result<Option<i64>, RepoError> {
+        let result = self.repo.acquire_stored(object_key).await;
+        Self::logged_with_id("acquire_stored", object_key, result)
+    }
+
+    async fn acquire(
+        &self,
+        object_key: &str,
+        size: i64,
+        upload: ObjectStoreOperation<'_>,
+    ) -> Result<i64, RepoError> {
+        let result = self.repo.acquire(object_key, size, upload).await;
+        Self::logged_with_id("acquire", object_key, result)
+    }
+
+    async fn release(
+        &self,
+        object_key: &str,
+        delete: ObjectStoreOperation<'_>,
+    ) -> Result<Option<i64>, RepoError> {
+        let result = self.repo.release(object_key, delete).await;
+        Self::logged_with_id("release", object_key, result)
+    }
+}
+
+impl DbComponentBlobRepo<sqlx::Postgres> {
+    // Locks the object until the end of the transaction, even if it has no record yet
+    async fn lock(
+        transaction: &mut Transaction<'_, sqlx::Postgres>,
+        object_key: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtextextended($1, 0))")
+            .bind(object_key)
+            .execute(&mut **transaction)
+            .await?;
+        Ok(())
+    }
+}
+
+impl DbComponentBlobRepo<sqlx::Sqlite> {
+    // SQLite has no row locks, so the transaction takes the write lock of the database by
+    // starting with a write, which lasts until the end of the transaction
+    async fn lock(
+        transaction: &mut Transaction<'_, sqlx::Sqlite>,
+        object_key: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query("UPDATE component_blobs SET ref_count = ref_count WHERE object_key = $1")
+            .bind(object_key)
+            .execute(&mut **transaction)
+            .await?;
+        Ok(())
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl ComponentBlobRepo for DbComponentBlobRepo<sqlx::Postgres> {
+    #[when(sqlx::Postgres -> get)]
+    async fn get_postgres(
+        &self,
+        object_key: &str,
+    ) -> Result<Option<ComponentBlobRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentBlobRecord>(
+            r#"
+              SELECT object_key, size, ref_count, created_at::timestamptz
+              FROM component_blobs
+              WHERE object_key = $1
+               "#,
+        )
+        .bind(object_key)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get)]
+    async fn get_sqlite(&self, object_key: &str) -> Result<Option<ComponentBlobRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentBlobRecord>(
+            r#"
+              SELECT object_key, size, ref_count, created_at
+              FROM component_blobs
+              WHERE object_key = $1
+               "#,
+        )
+        .bind(object_key)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn acquire_stored(&self, object_key: &str) -> Result<Option<i64>, RepoError> {
+        let result = sqlx::query_as::<_, (i64,)>(
+            r#"
+              UPDATE component_blobs
+              SET ref_count = ref_count + 1
+              WHERE object_key = $1 AND ref_count > 0
+              RETURNING ref_count
+            "#,
+        )
+        .bind(object_key)
+        .fetch_optional(self.db_pool.deref())
+        .await?;
+
+        Ok(result.map(|(ref_count,)| ref_count))
+    }
+
+    async fn acquire(
+        &self,
+        object_key: &str,
+        size: i64,
+        upload: ObjectStoreOperation<'_>,
+    ) -> Result<i64, RepoError> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        Self::lock(&mut transaction, object_key).await?;
+
+        let stored = sqlx::query_as::<_, (i64,)>(
+            "SELECT ref_count FROM component_blobs WHERE object_key = $1",
+        )
+        .bind(object_key)
+        .fetch_optional(&mut *transaction)
+        .await?
+        .is_some_and(|(ref_count,)| ref_count > 0);
+
+        if !stored {
+            if let Err(error) = upload.await {
+                transaction.rollback().await?;
+                return Err(RepoError::Internal(error));
+            }
+        }
+
+        let (ref_count,) = sqlx::query_as::<_, (i64,)>(
+            r#"
+              INSERT INTO component_blobs (object_key, size, ref_count, created_at)
+              VALUES ($1, $2, 1, $3)
+              ON CONFLICT (object_key) DO UPDATE
+              SET ref_count = component_blobs.ref_count + 1
+              RETURNING ref_count
+            "#,
+        )
+        .bind(object_key)
+        .bind(size)
+        .bind(Utc::now())
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+
+        Ok(ref_count)
+    }
+
+    async fn release(
+        &self,
+        object_key: &str,
+        delete: ObjectStoreOperation<'_>,
+    ) -> Result<Option<i64>, RepoError> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        Self::lock(&mut transaction, object_key).await?;
+
+        let result = sqlx::query_as::<_, (i64,)>(
+            r#"
+              UPDATE component_blobs
+              SET ref_count = ref_count - 1
+              WHERE object_key = $1 AND ref_count > 0
+              RETURNING ref_count
+            "#,
+        )
+        .bind(object_key)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        if let Some((0,)) = result {
+            sqlx::query("DELETE FROM component_blobs WHERE object_key = $1")
+                .bind(object_key)
+                .execute(&mut *transaction)
+                .await?;
+
+            // If the deletion fails, the reference is kept
+            if let Err(error) = delete.await {
+                transaction.rollback().await?;
+                return Err(RepoError::Internal(error));
+            }
+        }
+
+        transaction.commit().await?;
+
+        Ok(result.map(|(ref_count,)| ref_count))
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use conditional_trait_gen::trait_gen;
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool, Row};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+// Stores the configurations as JSON, the ones of components are deleted along with them
+#[async_trait]
+pub trait ComponentConfigurationRepo {
+    async fn get_project_defaults(&self, namespace: &str) -> Result<Option<String>, RepoError>;
+
+    async fn put_project_defaults(
+        &self,
+        namespace: &str,
+        configuration: &str,
+    ) -> Result<(), RepoError>;
+
+    async fn get_component_configuration(
+        &self,
+        component_id: &Uuid,
+    ) -> Result<Option<String>, RepoError>;
+
+    async fn put_component_configuration(
+        &self,
+        component_id: &Uuid,
+        configuration: &str,
+    ) -> Result<(), RepoError>;
+}
+
+pub struct DbComponentConfigurationRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbComponentConfigurationRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedComponentConfigurationRepo<Repo: ComponentConfigurationRepo> {
+    repo: Repo,
+}
+
+impl<Repo: ComponentConfigurationRepo> LoggedComponentConfigurationRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_namespace<R>(
+        message: &'static str,
+        namespace: &str,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(namespace = namespace, "{}", message),
+            Err(error) => error!(
+                namespace = namespace,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        component_id: &Uuid,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(component_id = component_id.to_string(), "{}", message),
+            Err(error) => error!(
+                component_id = component_id.to_string(),
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: ComponentConfigurationRepo + Send + Sync> ComponentConfigurationRepo
+    for LoggedComponentConfigurationRepo<Repo>
+{
+    async fn get_project_defaults(&self, namespace: &str) -> Result<Option<String>, RepoError> {
+        let result = self.repo.get_project_defaults(namespace).await;
+        Self::logged_with_namespace("get_project_defaults", namespace, result)
+    }
+
+    async fn put_project_defaults(
+        &self,
+        namespace: &str,
+        configuration: &str,
+    ) -> Result<(), RepoError> {
+        let result = self
+            .repo
+            .put_project_defaults(namespace, configuration)
+            .await;
+        Self::logged_with_namespace("put_project_defaults", namespace, result)
+    }
+
+    async fn get_component_configuration(
+        &self,
+        component_id: &Uuid,
+    ) -> Result<Option<String>, RepoError> {
+        let result = self.repo.get_component_configuration(component_id).await;
+        Self::logged_with_id("get_component_configuration", component_id, result)
+    }
+
+    async fn put_component_configuration(
+        &self,
+        component_id: &Uuid,
+        configuration: &str,
+    ) -> Result<(), RepoError> {
+        let result = self
+            .repo
+            .put_component_configuration(component_id, configuration)
+            .await;
+        Self::logged_with_id("put_component_configuration", component_id, result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl ComponentConfigurationRepo for DbComponentConfigurationRepo<sqlx::Postgres> {
+    async fn get_project_defaults(&self, namespace: &str) -> Result<Option<String>, RepoError> {
+        let result = sqlx::query(
+            "SELECT configuration FROM project_default_configurations WHERE namespace = $1",
+        )
+        .bind(namespace)
+        .fetch_optional(self.db_pool.deref())
+        .await?;
+
+        Ok(result.map(|x| x.get("configuration")))
+    }
+
+    async fn put_project_defaults(
+        &self,
+        namespace: &str,
+        configuration: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO project_default_configurations (namespace, configuration, updated_at)
+              VALUES ($1, $2, CURRENT_TIMESTAMP)
+              ON CONFLICT (namespace) DO UPDATE
+              SET configuration = $2, updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(namespace)
+        .bind(configuration)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_component_configuration(
+        &self,
+        component_id: &Uuid,
+    ) -> Result<Option<String>, RepoError> {
+        let result = sqlx::query(
+            "SELECT configuration FROM component_configurations WHERE component_id = $1",
+        )
+        .bind(component_id)
+        .fetch_optional(self.db_pool.deref())
+        .await?;
+
+        Ok(result.map(|x| x.get("configuration")))
+    }
+
+    async fn put_component_configuration(
+        &self,
+        component_id: &Uuid,
+        configuration: &str,
+    ) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO component_configurations (component_id, configuration, updated_at)
+              VALUES ($1, $2, CURRENT_TIMESTAMP)
+              ON CONFLICT (component_id) DO UPDATE
+              SET configuration = $2, updated_at = CURRENT_TIMESTAMP
+            "#,
+        )
+        .bind(component_id)
+        .bind(configuration)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+}
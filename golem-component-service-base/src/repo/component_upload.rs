@@ -0,0 +1,344 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+// A resumable upload of a component. Uploads creating a new component have its name, the
+// others upload a new version of an existing component.
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct ComponentUploadRecord {
+    pub upload_id: Uuid,
+    pub namespace: String,
+    pub component_id: Uuid,
+    pub component_name: Option<String>,
+    pub component_type: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct ComponentUploadPartRecord {
+    pub upload_id: Uuid,
+    pub part_number: i32,
+    pub size: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait ComponentUploadRepo {
+    async fn create(&self, upload: &ComponentUploadRecord) -> Result<(), RepoError>;
+
+    async fn get(
+        &self,
+        namespace: &str,
+        upload_id: &Uuid,
+    ) -> Result<Option<ComponentUploadRecord>, RepoError>;
+
+    // Uploading a part again replaces it
+    async fn put_part(&self, part: &ComponentUploadPartRecord) -> Result<(), RepoError>;
+
+    // The parts of the upload, ordered by their number
+    async fn get_parts(
+        &self,
+        upload_id: &Uuid,
+    ) -> Result<Vec<ComponentUploadPartRecord>, RepoError>;
+
+    // Deletes the upload along with its parts
+    async fn delete(&self, upload_id: &Uuid) -> Result<(), RepoError>;
+
+    // The uploads of all namespaces which have neither been initiated nor had a part uploaded
+    // since the given time
+    async fn get_inactive_since(
+        &self,
+        inactive_since: DateTime<Utc>,
+    ) -> Result<Vec<ComponentUploadRecord>, RepoError>;
+}
+
+pub struct DbComponentUploadRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbComponentUploadRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedComponentUploadRepo<Repo: ComponentUploadRepo> {
+    repo: Repo,
+}
+
+impl<Repo: ComponentUploadRepo> LoggedComponentUploadRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        upload_id: &Uuid,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(upload_id = upload_id.to_string(), "{}", message),
+            Err(error) => error!(
+                upload_id = upload_id.to_string(),
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: ComponentUploadRepo + Send + Sync> ComponentUploadRepo
+    for LoggedComponentUploadRepo<Repo>
+{
+    async fn create(&self, upload: &ComponentUploadRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(upload).await;
+        Self::logged_with_id("create", &upload.upload_id, result)
+    }
+
+    async fn get(
+        &self,
+        namespace: &str,
+        upload_id: &Uuid,
+    ) -> Result<Option<ComponentUploadRecord>, RepoError> {
+        let result = self.repo.get(namespace, upload_id).await;
+        Self::logged_with_id("get", upload_id, result)
+    }
+
+    async fn put_part(&self, part: &ComponentUploadPartRecord) -> Result<(), RepoError> {
+        let result = self.repo.put_part(part).await;
+        Self::logged_with_id("put_part", &part.upload_id, result)
+    }
+
+    async fn get_parts(
+        &self,
+        upload_id: &Uuid,
+    ) -> Result<Vec<ComponentUploadPartRecord>, RepoError> {
+        let result = self.repo.get_parts(upload_id).await;
+        Self::logged_with_id("get_parts", upload_id, result)
+    }
+
+    async fn delete(&self, upload_id: &Uuid) -> Result<(), RepoError> {
+        let result = self.repo.delete(upload_id).await;
+        Self::logged_with_id("delete", upload_id, result)
+    }
+
+    async fn get_inactive_since(
+        &self,
+        inactive_since: DateTime<Utc>,
+    ) -> Result<Vec<ComponentUploadRecord>, RepoError> {
+        let result = self.repo.get_inactive_since(inactive_since).await;
+        match &result {
+            Ok(_) => debug!("get_inactive_since"),
+            Err(error) => error!(error = error.to_string(), "get_inactive_since"),
+        }
+        result
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl ComponentUploadRepo for DbComponentUploadRepo<sqlx::Postgres> {
+    async fn create(&self, upload: &ComponentUploadRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO component_uploads
+                (upload_id, namespace, component_id, component_name, component_type, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5, $6)
+               "#,
+        )
+        .bind(upload.upload_id)
+        .bind(upload.namespace.clone())
+        .bind(upload.component_id)
+        .bind(upload.component_name.clone())
+        .bind(upload.component_type)
+        .bind(upload.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get)]
+    async fn get_postgres(
+        &self,
+        namespace: &str,
+        upload_id: &Uuid,
+    ) -> Result<Option<ComponentUploadRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentUploadRecord>(
+            r#"
+              SELECT upload_id, namespace, component_id, component_name, component_type, created_at::timestamptz
+              FROM component_uploads
+              WHERE namespace = $1 AND upload_id = $2
+               "#,
+        )
+        .bind(namespace)
+        .bind(upload_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get)]
+    async fn get_sqlite(
+        &self,
+        namespace: &str,
+        upload_id: &Uuid,
+    ) -> Result<Option<ComponentUploadRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentUploadRecord>(
+            r#"
+              SELECT upload_id, namespace, component_id, component_name, component_type, created_at
+              FROM component_uploads
+              WHERE namespace = $1 AND upload_id = $2
+               "#,
+        )
+        .bind(namespace)
+        .bind(upload_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn put_part(&self, part: &ComponentUploadPartRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO component_upload_parts (upload_id, part_number, size, created_at)
+              VALUES ($1, $2, $3, $4)
+              ON CONFLICT (upload_id, part_number) DO UPDATE
+              SET size = $3, created_at = $4
+            "#,
+        )
+        .bind(part.upload_id)
+        .bind(part.part_number)
+        .bind(part.size)
+        .bind(part.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get_parts)]
+    async fn get_parts_postgres(
+        &self,
+        upload_id: &Uuid,
+    ) -> Result<Vec<ComponentUploadPartRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentUploadPartRecord>(
+            r#"
+              SELECT upload_id, part_number, size, created_at::timestamptz
+              FROM component_upload_parts
+              WHERE upload_id = $1
+              ORDER BY part_number
+               "#,
+        )
+        .bind(upload_id)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_parts)]
+    async fn get_parts_sqlite(
+        &self,
+        upload_id: &Uuid,
+    ) -> Result<Vec<ComponentUploadPartRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentUploadPartRecord>(
+            r#"
+              SELECT upload_id, part_number, size, created_at
+              FROM component_upload_parts
+              WHERE upload_id = $1
+              ORDER BY part_number
+               "#,
+        )
+        .bind(upload_id)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn delete(&self, upload_id: &Uuid) -> Result<(), RepoError> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        sqlx::query("DELETE FROM component_upload_parts WHERE upload_id = $1")
+            .bind(upload_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        sqlx::query("DELETE FROM component_uploads WHERE upload_id = $1")
+            .bind(upload_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get_inactive_since)]
+    async fn get_inactive_since_postgres(
+        &self,
+        inactive_since: DateTime<Utc>,
+    ) -> Result<Vec<ComponentUploadRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentUploadRecord>(
+            r#"
+              SELECT u.upload_id, u.namespace, u.component_id, u.component_name, u.component_type, u.created_at::timestamptz
+              FROM component_uploads u
+              WHERE u.created_at < $1
+                AND NOT EXISTS (
+                  SELECT 1 FROM component_upload_parts p
+                  WHERE p.upload_id = u.upload_id AND p.created_at >= $1
+                )
+              ORDER BY u.created_at
+               "#,
+        )
+        .bind(inactive_since)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_inactive_since)]
+    async fn get_inactive_since_sqlite(
+        &self,
+        inactive_since: DateTime<Utc>,
+    ) -> Result<Vec<ComponentUploadRecord>, RepoError> {
+        sqlx::query_as::<_, ComponentUploadRecord>(
+            r#"
+              SELECT u.upload_id, u.namespace, u.component_id, u.component_name, u.component_type, u.created_at
+              FROM component_uploads u
+              WHERE u.created_at < $1
+                AND NOT EXISTS (
+                  SELECT 1 FROM component_upload_parts p
+                  WHERE p.upload_id = u.upload_id AND p.created_at >= $1
+                )
+              ORDER BY u.created_at
+               "#,
+        )
+        .bind(inactive_since)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+}
@@ -13,4 +13,7 @@
 // limitations under the License.
 
 pub mod component;
+pub mod component_blob;
+pub mod component_configuration;
+pub mod component_upload;
 pub mod plugin;
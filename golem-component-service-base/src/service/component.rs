@@ -13,16 +13,25 @@
 // limitations under the License.
 
 use crate::model::InitialComponentFilesArchiveAndPermissions;
-use crate::model::{Component, ComponentConstraints};
+use crate::model::{Component, ComponentCompatibility, ComponentConstraints, FunctionTypeConflict};
 use crate::repo::component::{record_metadata_serde, ComponentRecord, FileRecord};
 use crate::repo::component::{ComponentConstraintsRecord, ComponentRepo};
+use crate::service::component_archive::{
+    read_tar_entries, ComponentFilesArchiveFormat, TarEntryStream,
+};
+use crate::service::component_blob::{
+    content_addressed_key, is_content_addressed_key, ComponentBlobService,
+};
 use crate::service::component_compilation::ComponentCompilationService;
 use crate::service::component_object_store::ComponentObjectStore;
+use crate::service::component_validation::validate_component;
+use crate::service::component_workers::ComponentWorkersService;
 use crate::service::plugin::{PluginError, PluginService};
 use async_trait::async_trait;
 use async_zip::tokio::read::seek::ZipFileReader;
 use async_zip::ZipEntry;
 use bytes::Bytes;
+use chrono::Utc;
 use futures::TryStreamExt;
 use golem_api_grpc::proto::golem::common::{ErrorBody, ErrorsBody};
 use golem_api_grpc::proto::golem::component::v1::component_error;
@@ -44,10 +53,13 @@ use golem_common::model::{
 };
 use golem_common::retries::with_retries;
 use golem_common::SafeDisplay;
-use golem_service_base::model::{ComponentName, VersionedComponentId};
+use golem_service_base::model::{ComponentName, DeletedComponent, VersionedComponentId};
 use golem_service_base::repo::plugin_installation::PluginInstallationRecord;
 use golem_service_base::repo::RepoError;
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
+use golem_service_base::service::namespace_quota::{
+    ensure_within_limit, NamespaceQuotaError, NamespaceQuotaService, QuotaExceeded, QuotaResource,
+};
 use golem_service_base::storage::blob::ReplayableStream;
 use golem_wasm_ast::analysis::AnalysedType;
 use http::StatusCode;
@@ -57,6 +69,7 @@ use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use std::vec;
 use tap::TapFallible;
 use tempfile::NamedTempFile;
@@ -64,7 +77,7 @@ use tokio::io::BufReader;
 use tokio_stream::Stream;
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 use tokio_util::io::ReaderStream;
-use tracing::{debug, error, info, info_span};
+use tracing::{debug, error, info, info_span, warn};
 use tracing_futures::Instrument;
 
 #[derive(Debug, thiserror::Error)]
@@ -97,6 +110,11 @@ pub enum ComponentError {
     InitialComponentFileUploadError { message: String, error: String },
     #[error("Provided component file not found: {path} (key: {key})")]
     InitialComponentFileNotFound { path: String, key: String },
+    #[error("Unknown file {path} in component {component_id}")]
+    UnknownComponentFile {
+        component_id: VersionedComponentId,
+        path: String,
+    },
     #[error("Component transformation plugin was not found ({plugin_name}:{plugin_version})")]
     TransformationPluginNotFound {
         plugin_name: String,
@@ -106,6 +124,20 @@ pub enum ComponentError {
     InternalPluginError(#[from] Box<PluginError>),
     #[error("Component transformation failed: {0}")]
     TransformationFailed(TransformationFailedReason),
+    #[error("Invalid component configuration: {0}")]
+    InvalidConfiguration(String),
+    #[error("Invalid component: {}", .0.join(", "))]
+    InvalidComponent(Vec<String>),
+    #[error("Component {0} was modified concurrently, If-Match precondition failed")]
+    PreconditionFailed(ComponentId),
+    #[error("{}", .0.error)]
+    QuotaExceeded(QuotaExceeded),
+    #[error("Internal quota error: {0}")]
+    InternalQuotaError(String),
+    #[error("Component {0} still has workers, delete them first")]
+    ComponentHasWorkers(ComponentId),
+    #[error("Failed to look up the workers of the component: {0}")]
+    WorkerLookupError(String),
 }
 
 impl ComponentError {
@@ -176,9 +208,17 @@ impl SafeDisplay for ComponentError {
             ComponentError::MalformedComponentArchiveError { .. } => self.to_string(),
             ComponentError::InitialComponentFileUploadError { .. } => self.to_string(),
             ComponentError::InitialComponentFileNotFound { .. } => self.to_string(),
+            ComponentError::UnknownComponentFile { .. } => self.to_string(),
             ComponentError::TransformationPluginNotFound { .. } => self.to_string(),
             ComponentError::InternalPluginError(_) => self.to_string(),
             ComponentError::TransformationFailed(_) => self.to_string(),
+            ComponentError::InvalidConfiguration(_) => self.to_string(),
+            ComponentError::InvalidComponent(_) => self.to_string(),
+            ComponentError::PreconditionFailed(_) => self.to_string(),
+            ComponentError::QuotaExceeded(_) => self.to_string(),
+            ComponentError::InternalQuotaError(_) => self.to_string(),
+            ComponentError::ComponentHasWorkers(_) => self.to_string(),
+            ComponentError::WorkerLookupError(_) => self.to_string(),
         }
     }
 }
@@ -189,6 +229,17 @@ impl From<RepoError> for ComponentError {
     }
 }
 
+impl From<NamespaceQuotaError> for ComponentError {
+    fn from(error: NamespaceQuotaError) -> Self {
+        match error {
+            NamespaceQuotaError::Exceeded(exceeded) => ComponentError::QuotaExceeded(exceeded),
+            NamespaceQuotaError::UnsupportedResource(_) | NamespaceQuotaError::InternalError(_) => {
+                ComponentError::InternalQuotaError(error.to_safe_string())
+            }
+        }
+    }
+}
+
 impl From<ComponentError> for golem_api_grpc::proto::golem::component::v1::ComponentError {
     fn from(value: ComponentError) -> Self {
         let error = match value {
@@ -241,7 +292,8 @@ impl From<ComponentError> for golem_api_grpc::proto::golem::component::v1::Compo
                     errors: vec![value.to_safe_string()],
                 })
             }
-            ComponentError::InitialComponentFileNotFound { .. } => {
+            ComponentError::InitialComponentFileNotFound { .. }
+            | ComponentError::UnknownComponentFile { .. } => {
                 component_error::Error::NotFound(ErrorBody {
                     error: value.to_safe_string(),
                 })
@@ -261,6 +313,37 @@ impl From<ComponentError> for golem_api_grpc::proto::golem::component::v1::Compo
                     error: value.to_safe_string(),
                 })
             }
+            ComponentError::InvalidConfiguration(_) => {
+                component_error::Error::BadRequest(ErrorsBody {
+                    errors: vec![value.to_safe_string()],
+                })
+            }
+            ComponentError::InvalidComponent(errors) => {
+                component_error::Error::BadRequest(ErrorsBody { errors })
+            }
+            ComponentError::PreconditionFailed(_) => {
+                component_error::Error::AlreadyExists(ErrorBody {
+                    error: value.to_safe_string(),
+                })
+            }
+            ComponentError::QuotaExceeded(_) => component_error::Error::LimitExceeded(ErrorBody {
+                error: value.to_safe_string(),
+            }),
+            ComponentError::InternalQuotaError(_) => {
+                component_error::Error::InternalError(ErrorBody {
+                    error: value.to_safe_string(),
+                })
+            }
+            ComponentError::ComponentHasWorkers(_) => {
+                component_error::Error::AlreadyExists(ErrorBody {
+                    error: value.to_safe_string(),
+                })
+            }
+            ComponentError::WorkerLookupError(_) => {
+                component_error::Error::InternalError(ErrorBody {
+                    error: value.to_safe_string(),
+                })
+            }
         };
         Self { error: Some(error) }
     }
@@ -323,6 +406,8 @@ pub trait ComponentService<Owner: ComponentOwner>: Debug {
         owner: &Owner,
     ) -> Result<Component<Owner>, ComponentError>;
 
+    /// Creates a new version of the component. When `expected_version` is set, it fails with
+    /// `PreconditionFailed` unless that version is still the latest one.
     async fn update(
         &self,
         component_id: &ComponentId,
@@ -330,6 +415,7 @@ pub trait ComponentService<Owner: ComponentOwner>: Debug {
         component_type: Option<ComponentType>,
         files: Option<InitialComponentFilesArchiveAndPermissions>,
         dynamic_linking: HashMap<String, DynamicLinkedInstance>,
+        expected_version: Option<ComponentVersion>,
         owner: &Owner,
     ) -> Result<Component<Owner>, ComponentError>;
 
@@ -362,6 +448,14 @@ pub trait ComponentService<Owner: ComponentOwner>: Debug {
         ComponentError,
     >;
 
+    /// Streams the content of one of the initial files of a component version
+    async fn get_file_contents(
+        &self,
+        component_id: &VersionedComponentId,
+        path: &ComponentFilePath,
+        owner: &Owner,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>>, ComponentError>;
+
     async fn find_by_name(
         &self,
         component_name: Option<ComponentName>,
@@ -394,8 +488,32 @@ pub trait ComponentService<Owner: ComponentOwner>: Debug {
 
     async fn get_owner(&self, component_id: &ComponentId) -> Result<Option<Owner>, ComponentError>;
 
-    async fn delete(&self, component_id: &ComponentId, owner: &Owner)
-        -> Result<(), ComponentError>;
+    /// Moves the component to the trash.
+    ///
+    /// Fails with `ComponentHasWorkers` while the component has workers which have not exited.
+    /// The stored component binaries are kept until the component is purged.
+    async fn delete(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
+        deleted_by: &str,
+    ) -> Result<(), ComponentError>;
+
+    async fn restore(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
+    ) -> Result<(), ComponentError>;
+
+    async fn get_deleted(&self, owner: &Owner) -> Result<Vec<DeletedComponent>, ComponentError>;
+
+    /// Permanently deletes a component and all of its versions, including the stored binaries.
+    /// Fails with `ComponentHasWorkers` while the component has workers which have not exited.
+    async fn purge(&self, component_id: &ComponentId, owner: &Owner) -> Result<(), ComponentError>;
+
+    /// Purges all components which have been in the trash for longer than `retention`,
+    /// except for the ones which still have workers
+    async fn purge_expired(&self, retention: Duration) -> Result<usize, ComponentError>;
 
     async fn create_or_update_constraint(
         &self,
@@ -408,6 +526,15 @@ pub trait ComponentService<Owner: ComponentOwner>: Debug {
         owner: &Owner,
     ) -> Result<Option<FunctionConstraintCollection>, ComponentError>;
 
+    /// Checks a new version of a component against the API definitions using it, without
+    /// storing it, and returns the functions they call which it would break
+    async fn check_compatibility(
+        &self,
+        component_id: &ComponentId,
+        data: &[u8],
+        owner: &Owner,
+    ) -> Result<ConflictReport, ComponentError>;
+
     /// Gets the list of installed plugins for a given component version belonging to `owner`
     async fn get_plugin_installations_for_component(
         &self,
@@ -442,9 +569,13 @@ pub trait ComponentService<Owner: ComponentOwner>: Debug {
 pub struct ComponentServiceDefault<Owner: ComponentOwner, Scope: PluginScope> {
     component_repo: Arc<dyn ComponentRepo<Owner> + Sync + Send>,
     object_store: Arc<dyn ComponentObjectStore + Sync + Send>,
+    component_blob_service: Arc<dyn ComponentBlobService + Sync + Send>,
     component_compilation: Arc<dyn ComponentCompilationService + Sync + Send>,
     initial_component_files_service: Arc<InitialComponentFilesService>,
     plugin_service: Arc<dyn PluginService<Owner::PluginOwner, Scope> + Sync + Send>,
+    namespace_quota_service: Arc<dyn NamespaceQuotaService<Owner> + Sync + Send>,
+    component_workers: Arc<dyn ComponentWorkersService + Sync + Send>,
+    block_incompatible_updates: bool,
 }
 
 impl<Owner: ComponentOwner, Scope: PluginScope> Debug for ComponentServiceDefault<Owner, Scope> {
@@ -457,16 +588,24 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
     pub fn new(
         component_repo: Arc<dyn ComponentRepo<Owner> + Sync + Send>,
         object_store: Arc<dyn ComponentObjectStore + Sync + Send>,
+        component_blob_service: Arc<dyn ComponentBlobService + Sync + Send>,
         component_compilation: Arc<dyn ComponentCompilationService + Sync + Send>,
         initial_component_files_service: Arc<InitialComponentFilesService>,
         plugin_service: Arc<dyn PluginService<Owner::PluginOwner, Scope> + Sync + Send>,
+        namespace_quota_service: Arc<dyn NamespaceQuotaService<Owner> + Sync + Send>,
+        component_workers: Arc<dyn ComponentWorkersService + Sync + Send>,
+        block_incompatible_updates: bool,
     ) -> Self {
         ComponentServiceDefault {
             component_repo,
             object_store,
+            component_blob_service,
             component_compilation,
             initial_component_files_service,
             plugin_service,
+            namespace_quota_service,
+            component_workers,
+            block_incompatible_updates,
         }
     }
 
@@ -517,6 +656,29 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         }
     }
 
+    // Checks the exports of a new component version against the function calls of the API
+    // definitions using the component, which are registered as its constraints
+    async fn find_constraint_conflicts(
+        &self,
+        component_id: &ComponentId,
+        metadata: &ComponentMetadata,
+        owner: &Owner,
+    ) -> Result<ConflictReport, ComponentError> {
+        let constraints = self
+            .component_repo
+            .get_constraint(&owner.to_string(), &component_id.0)
+            .await?;
+
+        let new_type_registry = FunctionTypeRegistry::from_export_metadata(&metadata.exports);
+
+        Ok(match constraints {
+            Some(constraints) => {
+                Self::find_component_metadata_conflicts(&constraints, &new_type_registry)
+            }
+            None => ConflictReport::default(),
+        })
+    }
+
     async fn upload_component_files(
         &self,
         account_id: &AccountId,
@@ -576,10 +738,91 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         &self,
         path_permissions: &HashMap<ComponentFilePath, ComponentFilePermissions>,
         payload: InitialComponentFilesArchiveAndPermissions,
-    ) -> Result<Vec<(ComponentFilePath, ComponentFilePermissions, ZipEntryStream)>, ComponentError>
-    {
+    ) -> Result<
+        Vec<(
+            ComponentFilePath,
+            ComponentFilePermissions,
+            ArchiveEntryStream,
+        )>,
+        ComponentError,
+    > {
         let files_file = Arc::new(payload.archive);
 
+        let format = ComponentFilesArchiveFormat::detect_file(&files_file).map_err(|e| {
+            ComponentError::initial_component_file_upload_error(
+                "Failed to open provided component files",
+                e.to_string(),
+            )
+        })?;
+
+        match format {
+            Some(ComponentFilesArchiveFormat::Zip) => {
+                Self::prepare_zip_files_for_upload(path_permissions, files_file).await
+            }
+            Some(ComponentFilesArchiveFormat::Tar) => {
+                Self::prepare_tar_files_for_upload(path_permissions, files_file).await
+            }
+            None => Err(ComponentError::malformed_component_archive_from_message(
+                "Provided component files must be a zip or an uncompressed tar archive",
+            )),
+        }
+    }
+
+    async fn prepare_tar_files_for_upload(
+        path_permissions: &HashMap<ComponentFilePath, ComponentFilePermissions>,
+        files_file: Arc<NamedTempFile>,
+    ) -> Result<
+        Vec<(
+            ComponentFilePath,
+            ComponentFilePermissions,
+            ArchiveEntryStream,
+        )>,
+        ComponentError,
+    > {
+        let entries = {
+            let files_file = files_file.clone();
+            tokio::task::spawn_blocking(move || read_tar_entries(&files_file))
+                .await
+                .map_err(|e| {
+                    ComponentError::malformed_component_archive_from_error(
+                        "Failed to unpack provided component files",
+                        e.into(),
+                    )
+                })?
+                .map_err(|e| {
+                    ComponentError::malformed_component_archive_from_error(
+                        "Failed to unpack provided component files",
+                        e.into(),
+                    )
+                })?
+        };
+
+        entries
+            .iter()
+            .map(|entry| {
+                let path = initial_component_file_path(&entry.path)?;
+                let permissions = path_permissions
+                    .get(&path)
+                    .cloned()
+                    .unwrap_or(ComponentFilePermissions::ReadOnly);
+                let stream =
+                    ArchiveEntryStream::Tar(TarEntryStream::new(files_file.clone(), entry));
+                Ok((path, permissions, stream))
+            })
+            .collect()
+    }
+
+    async fn prepare_zip_files_for_upload(
+        path_permissions: &HashMap<ComponentFilePath, ComponentFilePermissions>,
+        files_file: Arc<NamedTempFile>,
+    ) -> Result<
+        Vec<(
+            ComponentFilePath,
+            ComponentFilePermissions,
+            ArchiveEntryStream,
+        )>,
+        ComponentError,
+    > {
         let tokio_file = tokio::fs::File::from_std(files_file.reopen().map_err(|e| {
             ComponentError::initial_component_file_upload_error(
                 "Failed to open provided component files",
@@ -628,7 +871,10 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
                 .cloned()
                 .unwrap_or(ComponentFilePermissions::ReadOnly);
 
-            let stream = ZipEntryStream::from_zip_file_and_index(files_file.clone(), i);
+            let stream = ArchiveEntryStream::Zip(ZipEntryStream::from_zip_file_and_index(
+                files_file.clone(),
+                i,
+            ));
 
             result.push((path, permissions, stream));
         }
@@ -636,6 +882,70 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         Ok(result)
     }
 
+    // Workers keep running the stored versions of their component, so a component is only
+    // deleted once all of its workers have exited or have been deleted
+    async fn ensure_no_live_workers(
+        &self,
+        component_id: &ComponentId,
+    ) -> Result<(), ComponentError> {
+        if self
+            .component_workers
+            .has_live_workers(component_id)
+            .await
+            .map_err(ComponentError::WorkerLookupError)?
+        {
+            Err(ComponentError::ComponentHasWorkers(component_id.clone()))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Checks the size of a new component version, and the number of components when creating
+    // one, against the quotas of the owner. Every way of creating or updating a component ends
+    // up here, so the quotas cannot be bypassed by any of the APIs.
+    async fn check_quotas(
+        &self,
+        owner: &Owner,
+        component_size: usize,
+        new_component: bool,
+    ) -> Result<(), ComponentError> {
+        let size_limit = self
+            .namespace_quota_service
+            .limit(owner, QuotaResource::ComponentSize)
+            .await?;
+        ensure_within_limit(
+            QuotaResource::ComponentSize,
+            size_limit,
+            component_size as u64,
+        )?;
+
+        if new_component {
+            let count_limit = self
+                .namespace_quota_service
+                .limit(owner, QuotaResource::Components)
+                .await?;
+
+            if count_limit.is_some() {
+                let components = self
+                    .component_repo
+                    .get_all(&owner.to_string())
+                    .await?
+                    .into_iter()
+                    .map(|record| record.component_id)
+                    .collect::<HashSet<_>>()
+                    .len();
+
+                ensure_within_limit(
+                    QuotaResource::Components,
+                    count_limit,
+                    components as u64 + 1,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     // All files must be confirmed to be in the blob store before calling this method
     async fn create_unchecked(
         &self,
@@ -648,7 +958,10 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         dynamic_linking: HashMap<String, DynamicLinkedInstance>,
         owner: &Owner,
     ) -> Result<Component<Owner>, ComponentError> {
-        let component = Component::new(
+        self.check_quotas(owner, data.len(), true).await?;
+        validate_component(&data, &dynamic_linking).map_err(ComponentError::InvalidComponent)?;
+
+        let mut component = Component::new(
             component_id.clone(),
             component_name.clone(),
             component_type,
@@ -671,6 +984,9 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
             .map_err(ComponentError::ComponentProcessingError)?;
         transformed_metadata.dynamic_linking = component.metadata.dynamic_linking.clone();
 
+        component.object_store_key = Some(content_addressed_key(&data));
+        component.transformed_object_store_key = Some(content_addressed_key(&transformed_data));
+
         tokio::try_join!(
             self.upload_user_component(&component, data),
             self.upload_protected_component(&component, transformed_data)
@@ -684,6 +1000,7 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
 
         let result = self.component_repo.create(&record).await;
         if let Err(RepoError::UniqueViolation(_)) = result {
+            self.release_component_data(&component).await?;
             Err(ComponentError::AlreadyExists(component_id.clone()))?;
         }
 
@@ -701,25 +1018,28 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         component_type: Option<ComponentType>,
         files: Option<Vec<InitialComponentFile>>,
         dynamic_linking: HashMap<String, DynamicLinkedInstance>,
+        expected_version: Option<ComponentVersion>,
         owner: &Owner,
     ) -> Result<Component<Owner>, ComponentError> {
+        self.check_quotas(owner, data.len(), false).await?;
+        validate_component(&data, &dynamic_linking).map_err(ComponentError::InvalidComponent)?;
+
         let mut metadata = ComponentMetadata::analyse_component(&data)
             .map_err(ComponentError::ComponentProcessingError)?;
         metadata.dynamic_linking = dynamic_linking;
 
-        let constraints = self
-            .component_repo
-            .get_constraint(&owner.to_string(), &component_id.0)
+        let conflicts = self
+            .find_constraint_conflicts(component_id, &metadata, owner)
             .await?;
-
-        let new_type_registry = FunctionTypeRegistry::from_export_metadata(&metadata.exports);
-
-        if let Some(constraints) = constraints {
-            let conflicts =
-                Self::find_component_metadata_conflicts(&constraints, &new_type_registry);
-            if !conflicts.is_empty() {
+        if !conflicts.is_empty() {
+            if self.block_incompatible_updates {
                 return Err(ComponentError::ComponentConstraintConflictError(conflicts));
             }
+            warn!(
+                owner = %owner,
+                component_id = %component_id,
+                "Updating component breaking the API definitions using it:\n{conflicts}",
+            );
         }
 
         info!(
@@ -751,15 +1071,16 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
                     .to_vec(),
                 component_type.map(|ct| ct as i32),
                 files,
+                expected_version.map(|version| version as i64),
             )
-            .await?;
+            .await?
+            .ok_or_else(|| ComponentError::PreconditionFailed(component_id.clone()))?;
         let mut component: Component<Owner> = component_record
             .clone()
             .try_into()
             .map_err(|e| ComponentError::conversion_error("record", e))?;
-        let object_store_key = component.versioned_component_id.to_string();
+        let object_store_key = content_addressed_key(&data);
         component.object_store_key = Some(object_store_key.clone());
-        component.transformed_object_store_key = Some(object_store_key.clone());
 
         debug!("Result component: {component:?}");
 
@@ -768,6 +1089,9 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
             .map_err(ComponentError::ComponentProcessingError)?;
         transformed_metadata.dynamic_linking = metadata.dynamic_linking.clone();
 
+        let transformed_object_store_key = content_addressed_key(&transformed_data);
+        component.transformed_object_store_key = Some(transformed_object_store_key.clone());
+
         tokio::try_join!(
             self.upload_user_component(&component, data),
             self.upload_protected_component(&component, transformed_data)
@@ -783,7 +1107,7 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
                 &component_id.0,
                 component.versioned_component_id.version as i64,
                 &object_store_key,
-                &object_store_key,
+                &transformed_object_store_key,
                 record_metadata_serde::serialize(&transformed_metadata)
                     .map_err(|err| ComponentError::conversion_error("metadata", err))?
                     .to_vec(),
@@ -798,12 +1122,9 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         component: &Component<Owner>,
         data: Vec<u8>,
     ) -> Result<(), ComponentError> {
-        self.object_store
-            .put(&component.user_object_store_key(), data)
+        self.component_blob_service
+            .store(&component.user_object_store_key(), data)
             .await
-            .map_err(|e| {
-                ComponentError::component_store_error("Failed to upload user component", e)
-            })
     }
 
     async fn upload_protected_component(
@@ -811,12 +1132,31 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         component: &Component<Owner>,
         data: Vec<u8>,
     ) -> Result<(), ComponentError> {
-        self.object_store
-            .put(&component.protected_object_store_key(), data)
+        self.component_blob_service
+            .store(&component.protected_object_store_key(), data)
             .await
-            .map_err(|e| {
-                ComponentError::component_store_error("Failed to upload protected component", e)
-            })
+    }
+
+    // Removes the references of a component version to its content-addressed data. Data stored
+    // before deduplication is only deleted along with the version owning it.
+    async fn release_component_data(
+        &self,
+        component: &Component<Owner>,
+    ) -> Result<(), ComponentError> {
+        for object_key in [
+            component.protected_object_store_key(),
+            component.user_object_store_key(),
+        ] {
+            if is_content_addressed_key(&object_key) {
+                self.component_blob_service.release(&object_key).await?;
+            } else if component.owns_stored_object() {
+                self.object_store.delete(&object_key).await.map_err(|e| {
+                    ComponentError::component_store_error("Failed to delete component data", e)
+                })?;
+            }
+        }
+
+        Ok(())
     }
 
     async fn apply_transformations(
@@ -971,7 +1311,7 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
     async fn retransform(
         &self,
         namespace: &str,
-        new_component: Component<Owner>,
+        mut new_component: Component<Owner>,
     ) -> Result<(), PluginError> {
         let data = self
             .object_store
@@ -985,15 +1325,19 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
         let transformed_metadata = ComponentMetadata::analyse_component(&transformed_data)
             .map_err(ComponentError::ComponentProcessingError)?;
 
-        self.object_store
-            .put(
-                &new_component.protected_object_store_key(),
-                transformed_data,
-            )
-            .await
-            .map_err(|e| {
-                ComponentError::component_store_error("Failed to upload protected component", e)
-            })?;
+        // The new version shares the user component of the previous one
+        let user_object_store_key = new_component.user_object_store_key();
+        if is_content_addressed_key(&user_object_store_key) {
+            self.component_blob_service
+                .acquire(&user_object_store_key)
+                .await?;
+        }
+
+        let transformed_object_store_key = content_addressed_key(&transformed_data);
+        new_component.transformed_object_store_key = Some(transformed_object_store_key.clone());
+
+        self.upload_protected_component(&new_component, transformed_data)
+            .await?;
 
         self.component_repo
             .activate(
@@ -1003,7 +1347,7 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentServiceDefault<Owner, S
                 &new_component
                     .object_store_key
                     .unwrap_or(new_component.versioned_component_id.to_string()),
-                &new_component.versioned_component_id.to_string(),
+                &transformed_object_store_key,
                 record_metadata_serde::serialize(&transformed_metadata)
                     .map_err(|err| ComponentError::conversion_error("metadata", err))?
                     .to_vec(),
@@ -1112,6 +1456,7 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentService<Owner>
         component_type: Option<ComponentType>,
         files: Option<InitialComponentFilesArchiveAndPermissions>,
         dynamic_linking: HashMap<String, DynamicLinkedInstance>,
+        expected_version: Option<ComponentVersion>,
         owner: &Owner,
     ) -> Result<Component<Owner>, ComponentError> {
         info!(owner = %owner, "Update component");
@@ -1130,6 +1475,7 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentService<Owner>
             component_type,
             uploaded_files,
             dynamic_linking,
+            expected_version,
             owner,
         )
         .await
@@ -1171,6 +1517,7 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentService<Owner>
             component_type,
             files,
             dynamic_linking,
+            None,
             owner,
         )
         .await
@@ -1248,6 +1595,40 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentService<Owner>
         }
     }
 
+    async fn get_file_contents(
+        &self,
+        component_id: &VersionedComponentId,
+        path: &ComponentFilePath,
+        owner: &Owner,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>>, ComponentError> {
+        let component = self
+            .get_by_version(component_id, owner)
+            .await?
+            .ok_or_else(|| ComponentError::UnknownVersionedComponentId(component_id.clone()))?;
+
+        let file = component
+            .files
+            .iter()
+            .find(|file| &file.path == path)
+            .ok_or_else(|| ComponentError::UnknownComponentFile {
+                component_id: component_id.clone(),
+                path: path.to_string(),
+            })?;
+
+        info!(owner = %owner, component_id = %component_id, path = %path, "Get component file contents");
+
+        self.initial_component_files_service
+            .get(&owner.account_id(), &file.key)
+            .await
+            .map_err(|e| {
+                ComponentError::component_store_error(
+                    "Failed to get initial component file",
+                    anyhow::Error::msg(e),
+                )
+            })?
+            .ok_or_else(|| ComponentError::initial_component_file_not_found(path, &file.key))
+    }
+
     async fn find_by_name(
         &self,
         component_name: Option<ComponentName>,
@@ -1372,8 +1753,78 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentService<Owner>
         &self,
         component_id: &ComponentId,
         owner: &Owner,
+        deleted_by: &str,
+    ) -> Result<(), ComponentError> {
+        info!(owner = %owner, component_id = %component_id, deleted_by = deleted_by, "Delete component");
+
+        self.ensure_no_live_workers(component_id).await?;
+
+        let deleted = self
+            .component_repo
+            .soft_delete(&owner.to_string(), &component_id.0, deleted_by)
+            .await?;
+
+        if deleted {
+            Ok(())
+        } else {
+            Err(ComponentError::UnknownComponentId(component_id.clone()))
+        }
+    }
+
+    async fn restore(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
     ) -> Result<(), ComponentError> {
-        info!(owner = %owner, component_id = %component_id, "Delete component");
+        info!(owner = %owner, component_id = %component_id, "Restore component");
+
+        let namespace = owner.to_string();
+        let records = self.component_repo.get(&namespace, &component_id.0).await?;
+        let name = match records.first() {
+            Some(record) => record.name.clone(),
+            None => return Err(ComponentError::UnknownComponentId(component_id.clone())),
+        };
+
+        if let Some(existing) = self
+            .component_repo
+            .get_id_by_name(&namespace, &name)
+            .await?
+        {
+            if existing != component_id.0 {
+                return Err(ComponentError::AlreadyExists(ComponentId(existing)));
+            }
+        }
+
+        let restored = self
+            .component_repo
+            .restore(&namespace, &component_id.0)
+            .await?;
+
+        if restored {
+            Ok(())
+        } else {
+            Err(ComponentError::UnknownComponentId(component_id.clone()))
+        }
+    }
+
+    async fn get_deleted(&self, owner: &Owner) -> Result<Vec<DeletedComponent>, ComponentError> {
+        info!(owner = %owner, "Get deleted components");
+
+        let records = self.component_repo.get_deleted(&owner.to_string()).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| DeletedComponent {
+                component_id: ComponentId(record.component_id),
+                component_name: ComponentName(record.name),
+                deleted_at: record.deleted_at,
+                deleted_by: record.deleted_by,
+            })
+            .collect())
+    }
+
+    async fn purge(&self, component_id: &ComponentId, owner: &Owner) -> Result<(), ComponentError> {
+        info!(owner = %owner, component_id = %component_id, "Purge component");
 
         let records = self
             .component_repo
@@ -1385,28 +1836,55 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentService<Owner>
             .collect::<Result<Vec<Component<Owner>>, _>>()
             .map_err(|e| ComponentError::conversion_error("record", e))?;
 
-        let mut object_store_keys = Vec::new();
+        if components.is_empty() {
+            return Err(ComponentError::UnknownComponentId(component_id.clone()));
+        }
 
-        for component in components {
-            if component.owns_stored_object() {
-                object_store_keys.push(component.protected_object_store_key());
-                object_store_keys.push(component.user_object_store_key());
-            }
+        self.ensure_no_live_workers(component_id).await?;
+
+        // The versions are deleted first, so that no remaining version references released data
+        self.component_repo
+            .delete(&owner.to_string(), &component_id.0)
+            .await?;
+
+        for component in &components {
+            self.release_component_data(component).await?;
         }
 
-        if !object_store_keys.is_empty() {
-            for key in object_store_keys {
-                self.object_store.delete(&key).await.map_err(|e| {
-                    ComponentError::component_store_error("Failed to delete component data", e)
-                })?;
+        Ok(())
+    }
+
+    async fn purge_expired(&self, retention: Duration) -> Result<usize, ComponentError> {
+        let deleted_before = Utc::now()
+            - chrono::Duration::from_std(retention)
+                .map_err(|e| ComponentError::conversion_error("retention", e.to_string()))?;
+
+        let records = self
+            .component_repo
+            .get_deleted_before(deleted_before)
+            .await?;
+
+        let mut purged = 0;
+        for record in records {
+            let owner: Owner = record
+                .namespace
+                .parse()
+                .map_err(|e| ComponentError::conversion_error("namespace", e))?;
+            let component_id = ComponentId(record.component_id);
+
+            match self.purge(&component_id, &owner).await {
+                Ok(()) => purged += 1,
+                // Kept in the trash until its workers are deleted, so it can still be restored
+                Err(ComponentError::ComponentHasWorkers(_)) => {
+                    info!(owner = %owner, component_id = %component_id, "Not purging component which still has workers")
+                }
+                Err(error) => {
+                    error!(owner = %owner, component_id = %component_id, "Failed to purge component: {error}")
+                }
             }
-            self.component_repo
-                .delete(&owner.to_string(), &component_id.0)
-                .await?;
-            Ok(())
-        } else {
-            Err(ComponentError::UnknownComponentId(component_id.clone()))
         }
+
+        Ok(purged)
     }
 
     async fn create_or_update_constraint(
@@ -1456,6 +1934,29 @@ impl<Owner: ComponentOwner, Scope: PluginScope> ComponentService<Owner>
         Ok(result)
     }
 
+    async fn check_compatibility(
+        &self,
+        component_id: &ComponentId,
+        data: &[u8],
+        owner: &Owner,
+    ) -> Result<ConflictReport, ComponentError> {
+        info!(owner = %owner, component_id = %component_id, "Check component compatibility");
+
+        if self
+            .get_latest_version(component_id, owner)
+            .await?
+            .is_none()
+        {
+            return Err(ComponentError::UnknownComponentId(component_id.clone()));
+        }
+
+        let metadata = ComponentMetadata::analyse_component(data)
+            .map_err(ComponentError::ComponentProcessingError)?;
+
+        self.find_constraint_conflicts(component_id, &metadata, owner)
+            .await
+    }
+
     async fn get_plugin_installations_for_component(
         &self,
         owner: &Owner,
@@ -1676,7 +2177,7 @@ impl Display for ConflictingFunction {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ConflictReport {
     pub missing_functions: Vec<RegistryKey>,
     pub conflicting_functions: Vec<ConflictingFunction>,
@@ -1714,6 +2215,36 @@ impl ConflictReport {
     }
 }
 
+impl From<ConflictReport> for ComponentCompatibility {
+    fn from(value: ConflictReport) -> Self {
+        ComponentCompatibility {
+            compatible: value.is_empty(),
+            missing_functions: value
+                .missing_functions
+                .iter()
+                .map(internal::function_name)
+                .collect(),
+            conflicting_functions: value
+                .conflicting_functions
+                .into_iter()
+                .map(|conflict| FunctionTypeConflict {
+                    function: internal::function_name(&conflict.function),
+                    existing_parameter_types: internal::convert_to_pretty_types(
+                        &conflict.existing_parameter_types,
+                    ),
+                    new_parameter_types: internal::convert_to_pretty_types(
+                        &conflict.new_parameter_types,
+                    ),
+                    existing_result_types: internal::convert_to_pretty_types(
+                        &conflict.existing_result_types,
+                    ),
+                    new_result_types: internal::convert_to_pretty_types(&conflict.new_result_types),
+                })
+                .collect(),
+        }
+    }
+}
+
 fn initial_component_file_path_from_zip_entry(
     entry: &ZipEntry,
 ) -> Result<ComponentFilePath, ComponentError> {
@@ -1724,6 +2255,10 @@ fn initial_component_file_path_from_zip_entry(
         ))
     })?;
 
+    initial_component_file_path(file_path)
+}
+
+fn initial_component_file_path(file_path: &str) -> Result<ComponentFilePath, ComponentError> {
     // convert windows path separators to unix and sanitize the path
     let file_path: String = file_path
         .replace('\\', "/")
@@ -1740,6 +2275,33 @@ fn initial_component_file_path_from_zip_entry(
     })
 }
 
+// The content of a file in an uploaded archive of initial component files
+enum ArchiveEntryStream {
+    Zip(ZipEntryStream),
+    Tar(TarEntryStream),
+}
+
+#[async_trait]
+impl ReplayableStream for ArchiveEntryStream {
+    type Item = Result<Bytes, String>;
+
+    async fn make_stream(
+        &self,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Item> + Send + Sync>>, String> {
+        match self {
+            ArchiveEntryStream::Zip(stream) => stream.make_stream().await,
+            ArchiveEntryStream::Tar(stream) => stream.make_stream().await,
+        }
+    }
+
+    async fn length(&self) -> Result<u64, String> {
+        match self {
+            ArchiveEntryStream::Zip(stream) => stream.length().await,
+            ArchiveEntryStream::Tar(stream) => stream.length().await,
+        }
+    }
+}
+
 struct ZipEntryStream {
     file: Arc<NamedTempFile>,
     index: usize,
@@ -1798,6 +2360,19 @@ impl ReplayableStream for ZipEntryStream {
 
 mod internal {
     use golem_wasm_ast::analysis::AnalysedType;
+    use rib::RegistryKey;
+
+    // The name of the function as called from Rib
+    pub(crate) fn function_name(key: &RegistryKey) -> String {
+        match key {
+            RegistryKey::FunctionName(function_name) => function_name.clone(),
+            RegistryKey::FunctionNameWithInterface {
+                interface_name,
+                function_name,
+            } => format!("{interface_name}.{{{function_name}}}"),
+        }
+    }
+
     pub(crate) fn convert_to_pretty_types(analysed_types: &[AnalysedType]) -> String {
         let type_names = analysed_types
             .iter()
@@ -1814,9 +2389,12 @@ mod internal {
 mod tests {
     use test_r::test;
 
-    use crate::service::component::ComponentError;
+    use crate::model::ComponentCompatibility;
+    use crate::service::component::{ComponentError, ConflictReport, ConflictingFunction};
     use golem_common::SafeDisplay;
     use golem_service_base::repo::RepoError;
+    use golem_wasm_ast::analysis::analysed_type::{str, u32};
+    use rib::RegistryKey;
 
     #[test]
     pub fn test_repo_error_to_service_error() {
@@ -1827,4 +2405,38 @@ mod tests {
             "Internal repository error".to_string()
         );
     }
+
+    #[test]
+    pub fn test_conflict_report_to_compatibility() {
+        let report = ConflictReport {
+            missing_functions: vec![RegistryKey::FunctionName("get-cart".to_string())],
+            conflicting_functions: vec![ConflictingFunction {
+                function: RegistryKey::FunctionNameWithInterface {
+                    interface_name: "golem:it/api".to_string(),
+                    function_name: "add-item".to_string(),
+                },
+                existing_parameter_types: vec![str()],
+                new_parameter_types: vec![str(), u32()],
+                existing_result_types: vec![],
+                new_result_types: vec![],
+            }],
+        };
+
+        let compatibility = ComponentCompatibility::from(report);
+
+        assert!(!compatibility.compatible);
+        assert_eq!(
+            compatibility.missing_functions,
+            vec!["get-cart".to_string()]
+        );
+        assert_eq!(
+            compatibility.conflicting_functions[0].function,
+            "golem:it/api.{add-item}"
+        );
+        assert_eq!(
+            compatibility.conflicting_functions[0].new_parameter_types,
+            "string, u32"
+        );
+        assert!(ComponentCompatibility::from(ConflictReport::default()).compatible);
+    }
 }
@@ -0,0 +1,178 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::TryStreamExt;
+use golem_service_base::storage::blob::ReplayableStream;
+use std::io::{Read, SeekFrom};
+use std::pin::Pin;
+use std::sync::Arc;
+use tempfile::NamedTempFile;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_stream::Stream;
+use tokio_util::io::ReaderStream;
+
+/// The formats of the archives the initial files of a component can be uploaded in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentFilesArchiveFormat {
+    Zip,
+    /// Uncompressed tar archive
+    Tar,
+}
+
+impl ComponentFilesArchiveFormat {
+    pub const HEADER_SIZE: usize = 512;
+
+    // Detected from the content, as the uploaded archives have no reliable file name
+    pub fn detect(header: &[u8]) -> Option<Self> {
+        if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+            Some(ComponentFilesArchiveFormat::Zip)
+        } else if header.get(257..262) == Some(b"ustar") {
+            Some(ComponentFilesArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
+
+    pub fn detect_file(file: &NamedTempFile) -> Result<Option<Self>, std::io::Error> {
+        let mut header = Vec::with_capacity(Self::HEADER_SIZE);
+        file.reopen()?
+            .take(Self::HEADER_SIZE as u64)
+            .read_to_end(&mut header)?;
+        Ok(Self::detect(&header))
+    }
+}
+
+/// A regular file in a tar archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarEntry {
+    pub path: String,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// Lists the regular files of a tar archive, with the position of their content in the archive
+pub fn read_tar_entries(file: &NamedTempFile) -> Result<Vec<TarEntry>, std::io::Error> {
+    let mut archive = tar::Archive::new(file.reopen()?);
+    let mut result = vec![];
+
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_file() {
+            result.push(TarEntry {
+                path: entry.path()?.to_string_lossy().to_string(),
+                offset: entry.raw_file_position(),
+                size: entry.size(),
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+// Entries of uncompressed tar archives are stored as they are, so they are streamed directly
+// from their position in the uploaded archive
+pub struct TarEntryStream {
+    file: Arc<NamedTempFile>,
+    offset: u64,
+    size: u64,
+}
+
+impl TarEntryStream {
+    pub fn new(file: Arc<NamedTempFile>, entry: &TarEntry) -> Self {
+        Self {
+            file,
+            offset: entry.offset,
+            size: entry.size,
+        }
+    }
+}
+
+#[async_trait]
+impl ReplayableStream for TarEntryStream {
+    type Item = Result<Bytes, String>;
+
+    async fn make_stream(
+        &self,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Item> + Send + Sync>>, String> {
+        let reopened = self
+            .file
+            .reopen()
+            .map_err(|e| format!("Failed to reopen file: {e}"))?;
+        let mut file = tokio::fs::File::from_std(reopened);
+        file.seek(SeekFrom::Start(self.offset))
+            .await
+            .map_err(|e| format!("Failed to seek to entry: {e}"))?;
+        let stream = ReaderStream::new(file.take(self.size));
+        let mapped_stream = stream.map_err(|e| format!("Error reading entry: {e}"));
+        Ok(Box::pin(mapped_stream))
+    }
+
+    async fn length(&self) -> Result<u64, String> {
+        Ok(self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    async fn tar_entries_are_streamed_from_the_archive() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, content) in [
+            ("static/index.html", "<html></html>"),
+            ("config.json", "{}"),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, content.as_bytes())
+                .unwrap();
+        }
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&builder.into_inner().unwrap()).unwrap();
+
+        assert_eq!(
+            ComponentFilesArchiveFormat::detect_file(&file).unwrap(),
+            Some(ComponentFilesArchiveFormat::Tar)
+        );
+        assert_eq!(
+            ComponentFilesArchiveFormat::detect(b"PK\x03\x04rest"),
+            Some(ComponentFilesArchiveFormat::Zip)
+        );
+
+        let entries = read_tar_entries(&file).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "static/index.html");
+
+        let stream = TarEntryStream::new(Arc::new(file), &entries[0]);
+        assert_eq!(stream.length().await.unwrap(), 13);
+        let content = stream
+            .make_stream()
+            .await
+            .unwrap()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap()
+            .concat();
+        assert_eq!(content, b"<html></html>");
+    }
+}
@@ -0,0 +1,159 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::repo::component_blob::ComponentBlobRepo;
+use crate::service::component::ComponentError;
+use crate::service::component_object_store::ComponentObjectStore;
+use async_trait::async_trait;
+use futures::FutureExt;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+const CONTENT_ADDRESSED_KEY_PREFIX: &str = "sha256-";
+
+// The object store key of component data addressed by its content, shared by every component
+// version with the same data, in any namespace
+pub fn content_addressed_key(data: &[u8]) -> String {
+    format!(
+        "{}{}",
+        CONTENT_ADDRESSED_KEY_PREFIX,
+        hex::encode(Sha256::digest(data))
+    )
+}
+
+// Components stored before deduplication are keyed by their versioned id, and are not
+// reference counted
+pub fn is_content_addressed_key(object_key: &str) -> bool {
+    object_key.starts_with(CONTENT_ADDRESSED_KEY_PREFIX)
+}
+
+/// Stores content-addressed component data, counting the component versions referencing it.
+/// The data is uploaded only once, and deleted when the last version referencing it is purged.
+#[async_trait]
+pub trait ComponentBlobService {
+    // Adds a reference to the object, uploading the data only if it is not stored yet
+    async fn store(&self, object_key: &str, data: Vec<u8>) -> Result<(), ComponentError>;
+
+    // Adds a reference to an object which is already stored
+    async fn acquire(&self, object_key: &str) -> Result<(), ComponentError>;
+
+    // Removes a reference from the object, deleting it once it is no longer referenced
+    async fn release(&self, object_key: &str) -> Result<(), ComponentError>;
+}
+
+pub struct ComponentBlobServiceDefault {
+    component_blob_repo: Arc<dyn ComponentBlobRepo + Sync + Send>,
+    object_store: Arc<dyn ComponentObjectStore + Sync + Send>,
+}
+
+impl ComponentBlobServiceDefault {
+    pub fn new(
+        component_blob_repo: Arc<dyn ComponentBlobRepo + Sync + Send>,
+        object_store: Arc<dyn ComponentObjectStore + Sync + Send>,
+    ) -> Self {
+        Self {
+            component_blob_repo,
+            object_store,
+        }
+    }
+}
+
+#[async_trait]
+impl ComponentBlobService for ComponentBlobServiceDefault {
+    async fn store(&self, object_key: &str, data: Vec<u8>) -> Result<(), ComponentError> {
+        // Stored data is only deleted along with its last reference, so referencing it again
+        // does not need to lock it
+        if self
+            .component_blob_repo
+            .acquire_stored(object_key)
+            .await?
+            .is_some()
+        {
+            debug!(object_key = object_key, "Component data already stored");
+            return Ok(());
+        }
+
+        let size = data.len() as i64;
+        let object_store = self.object_store.clone();
+        let upload = async move {
+            object_store
+                .put(object_key, data)
+                .await
+                .map_err(|e| format!("Failed to upload component data: {e}"))
+        };
+
+        self.component_blob_repo
+            .acquire(object_key, size, upload.boxed())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn acquire(&self, object_key: &str) -> Result<(), ComponentError> {
+        self.component_blob_repo
+            .acquire_stored(object_key)
+            .await?
+            .ok_or(ComponentError::ComponentStoreError {
+                message: "Failed to reference component data".to_string(),
+                error: format!("Object {object_key} not found"),
+            })?;
+
+        Ok(())
+    }
+
+    async fn release(&self, object_key: &str) -> Result<(), ComponentError> {
+        let object_store = self.object_store.clone();
+        let delete = async move {
+            object_store
+                .delete(object_key)
+                .await
+                .map_err(|e| format!("Failed to delete component data: {e}"))
+        };
+
+        let remaining = self
+            .component_blob_repo
+            .release(object_key, delete.boxed())
+            .await?;
+
+        if remaining == Some(0) {
+            info!(
+                object_key = object_key,
+                "Unreferenced component data deleted"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+
+    #[test]
+    fn content_addressed_keys_depend_only_on_the_data() {
+        let key = content_addressed_key(b"component");
+
+        assert!(is_content_addressed_key(&key));
+        assert_eq!(key, content_addressed_key(b"component"));
+        assert_ne!(key, content_addressed_key(b"other component"));
+        assert_eq!(key.len(), CONTENT_ADDRESSED_KEY_PREFIX.len() + 64);
+        assert!(!is_content_addressed_key(
+            "8e9a6e2c-2b86-4b32-8c4a-25cf1d7d3f3c#0"
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{ComponentConfiguration, EffectiveComponentConfiguration};
+use crate::repo::component_configuration::ComponentConfigurationRepo;
+use crate::service::component::{ComponentError, ComponentService};
+use async_trait::async_trait;
+use golem_common::model::component::ComponentOwner;
+use golem_common::model::ComponentId;
+use std::sync::Arc;
+use tracing::info;
+
+/// Manages the project defaults of the configuration of components, and the settings of the
+/// components overriding them. The settings of a component apply to all of its versions.
+#[async_trait]
+pub trait ComponentConfigurationService<Owner: ComponentOwner> {
+    async fn get_project_defaults(
+        &self,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError>;
+
+    async fn update_project_defaults(
+        &self,
+        configuration: ComponentConfiguration,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError>;
+
+    async fn get_component_configuration(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError>;
+
+    async fn update_component_configuration(
+        &self,
+        component_id: &ComponentId,
+        configuration: ComponentConfiguration,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError>;
+
+    async fn get_effective_configuration(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
+    ) -> Result<EffectiveComponentConfiguration, ComponentError>;
+}
+
+pub struct ComponentConfigurationServiceDefault<Owner: ComponentOwner> {
+    repo: Arc<dyn ComponentConfigurationRepo + Sync + Send>,
+    component_service: Arc<dyn ComponentService<Owner> + Sync + Send>,
+}
+
+impl<Owner: ComponentOwner> ComponentConfigurationServiceDefault<Owner> {
+    pub fn new(
+        repo: Arc<dyn ComponentConfigurationRepo + Sync + Send>,
+        component_service: Arc<dyn ComponentService<Owner> + Sync + Send>,
+    ) -> Self {
+        Self {
+            repo,
+            component_service,
+        }
+    }
+
+    async fn ensure_component_exists(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
+    ) -> Result<(), ComponentError> {
+        match self
+            .component_service
+            .get_latest_version(component_id, owner)
+            .await?
+        {
+            Some(_) => Ok(()),
+            None => Err(ComponentError::UnknownComponentId(component_id.clone())),
+        }
+    }
+
+    fn parse(configuration: Option<String>) -> Result<ComponentConfiguration, ComponentError> {
+        match configuration {
+            Some(configuration) => serde_json::from_str(&configuration).map_err(|err| {
+                ComponentError::conversion_error("component configuration", err.to_string())
+            }),
+            None => Ok(ComponentConfiguration::default()),
+        }
+    }
+
+    fn serialize(configuration: &ComponentConfiguration) -> Result<String, ComponentError> {
+        configuration
+            .validate()
+            .map_err(ComponentError::InvalidConfiguration)?;
+        serde_json::to_string(configuration).map_err(|err| {
+            ComponentError::conversion_error("component configuration", err.to_string())
+        })
+    }
+}
+
+#[async_trait]
+impl<Owner: ComponentOwner> ComponentConfigurationService<Owner>
+    for ComponentConfigurationServiceDefault<Owner>
+{
+    async fn get_project_defaults(
+        &self,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError> {
+        let configuration = self.repo.get_project_defaults(&owner.to_string()).await?;
+        Self::parse(configuration)
+    }
+
+    async fn update_project_defaults(
+        &self,
+        configuration: ComponentConfiguration,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError> {
+        let serialized = Self::serialize(&configuration)?;
+        self.repo
+            .put_project_defaults(&owner.to_string(), &serialized)
+            .await?;
+
+        info!(owner = %owner, "Updated project default configuration");
+
+        Ok(configuration)
+    }
+
+    async fn get_component_configuration(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError> {
+        self.ensure_component_exists(component_id, owner).await?;
+
+        let configuration = self
+            .repo
+            .get_component_configuration(&component_id.0)
+            .await?;
+        Self::parse(configuration)
+    }
+
+    async fn update_component_configuration(
+        &self,
+        component_id: &ComponentId,
+        configuration: ComponentConfiguration,
+        owner: &Owner,
+    ) -> Result<ComponentConfiguration, ComponentError> {
+        self.ensure_component_exists(component_id, owner).await?;
+
+        let serialized = Self::serialize(&configuration)?;
+        self.repo
+            .put_component_configuration(&component_id.0, &serialized)
+            .await?;
+
+        info!(
+            owner = %owner,
+            component_id = %component_id,
+            "Updated component configuration"
+        );
+
+        Ok(configuration)
+    }
+
+    async fn get_effective_configuration(
+        &self,
+        component_id: &ComponentId,
+        owner: &Owner,
+    ) -> Result<EffectiveComponentConfiguration, ComponentError> {
+        let component_overrides = self
+            .get_component_configuration(component_id, owner)
+            .await?;
+        let project_defaults = self.get_project_defaults(owner).await?;
+        let effective = component_overrides.inherit(&project_defaults);
+
+        Ok(EffectiveComponentConfiguration {
+            project_defaults,
+            component_overrides,
+            effective,
+        })
+    }
+}
@@ -0,0 +1,468 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{Component, ComponentUpload, ComponentUploadPart, ComponentUploadRequest};
+use crate::repo::component_upload::{
+    ComponentUploadPartRecord, ComponentUploadRecord, ComponentUploadRepo,
+};
+use crate::service::component::{ComponentError, ComponentService};
+use crate::service::component_object_store::ComponentObjectStore;
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::StreamExt;
+use golem_common::model::component::ComponentOwner;
+use golem_common::model::{ComponentId, ComponentType};
+use golem_common::SafeDisplay;
+use golem_service_base::model::ComponentName;
+use golem_service_base::repo::RepoError;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+use uuid::Uuid;
+
+// Parts are numbered the same way as in S3 multipart uploads
+pub const MAX_UPLOAD_PART_NUMBER: u32 = 10000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ComponentUploadError {
+    #[error("Unknown component upload: {0}")]
+    UnknownUpload(Uuid),
+    #[error("Invalid component upload: {0}")]
+    InvalidRequest(String),
+    #[error("Component upload {upload_id} is incomplete, missing parts: {missing_parts:?}")]
+    Incomplete {
+        upload_id: Uuid,
+        missing_parts: Vec<u32>,
+    },
+    #[error(transparent)]
+    Component(#[from] ComponentError),
+    #[error("Internal repository error: {0}")]
+    InternalRepoError(#[from] RepoError),
+    #[error("Internal component store error: {message}: {error}")]
+    ComponentStoreError { message: String, error: String },
+}
+
+impl ComponentUploadError {
+    fn component_store_error(message: impl AsRef<str>, error: anyhow::Error) -> Self {
+        Self::ComponentStoreError {
+            message: message.as_ref().to_string(),
+            error: format!("{error}"),
+        }
+    }
+}
+
+impl SafeDisplay for ComponentUploadError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            Self::UnknownUpload(_) => self.to_string(),
+            Self::InvalidRequest(_) => self.to_string(),
+            Self::Incomplete { .. } => self.to_string(),
+            Self::Component(inner) => inner.to_safe_string(),
+            Self::InternalRepoError(inner) => inner.to_safe_string(),
+            Self::ComponentStoreError { .. } => self.to_string(),
+        }
+    }
+}
+
+/// Uploads components in parts, so large components can be uploaded over unreliable connections
+/// by retrying only the failed parts. The parts are kept in the component object store until the
+/// upload is completed or aborted, or until it expires after being abandoned.
+#[async_trait]
+pub trait ComponentUploadService<Owner: ComponentOwner>: Debug {
+    async fn initiate(
+        &self,
+        request: ComponentUploadRequest,
+        owner: &Owner,
+    ) -> Result<ComponentUpload, ComponentUploadError>;
+
+    async fn get(
+        &self,
+        upload_id: &Uuid,
+        owner: &Owner,
+    ) -> Result<ComponentUpload, ComponentUploadError>;
+
+    // Uploading a part again replaces it
+    async fn upload_part(
+        &self,
+        upload_id: &Uuid,
+        part_number: u32,
+        data: Vec<u8>,
+        owner: &Owner,
+    ) -> Result<ComponentUploadPart, ComponentUploadError>;
+
+    // Assembles the parts into the component, creating it or a new version of it.
+    // The parts are streamed from the object store into the single copy of the component which
+    // gets analysed and stored, as analysing the component needs all of it.
+    async fn complete(
+        &self,
+        upload_id: &Uuid,
+        owner: &Owner,
+    ) -> Result<Component<Owner>, ComponentUploadError>;
+
+    async fn abort(&self, upload_id: &Uuid, owner: &Owner) -> Result<(), ComponentUploadError>;
+
+    // Aborts the uploads of all owners which have had no part uploaded for longer than the ttl,
+    // returning how many of them were aborted
+    async fn abort_expired(&self, ttl: Duration) -> Result<usize, ComponentUploadError>;
+}
+
+pub struct ComponentUploadServiceDefault<Owner: ComponentOwner> {
+    repo: Arc<dyn ComponentUploadRepo + Sync + Send>,
+    object_store: Arc<dyn ComponentObjectStore + Sync + Send>,
+    component_service: Arc<dyn ComponentService<Owner> + Sync + Send>,
+}
+
+impl<Owner: ComponentOwner> ComponentUploadServiceDefault<Owner> {
+    pub fn new(
+        repo: Arc<dyn ComponentUploadRepo + Sync + Send>,
+        object_store: Arc<dyn ComponentObjectStore + Sync + Send>,
+        component_service: Arc<dyn ComponentService<Owner> + Sync + Send>,
+    ) -> Self {
+        Self {
+            repo,
+            object_store,
+            component_service,
+        }
+    }
+
+    // The object store keys are flat, as the local store does not create directories
+    fn part_key(upload_id: &Uuid, part_number: u32) -> String {
+        format!("upload-{upload_id}-{part_number}")
+    }
+
+    async fn get_record(
+        &self,
+        upload_id: &Uuid,
+        owner: &Owner,
+    ) -> Result<ComponentUploadRecord, ComponentUploadError> {
+        self.repo
+            .get(&owner.to_string(), upload_id)
+            .await?
+            .ok_or(ComponentUploadError::UnknownUpload(*upload_id))
+    }
+
+    // Streams the parts after each other, checking that they are as large as when uploaded
+    async fn read_parts(&self, upload: &ComponentUpload) -> Result<Vec<u8>, ComponentUploadError> {
+        let mut data = Vec::with_capacity(upload.size as usize);
+        for part in &upload.parts {
+            let start = data.len();
+            let mut stream = self
+                .object_store
+                .get_stream(&Self::part_key(&upload.upload_id, part.part_number))
+                .await;
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.map_err(|err| {
+                    ComponentUploadError::component_store_error(
+                        "Failed to download component upload part",
+                        err,
+                    )
+                })?;
+                data.extend_from_slice(&chunk);
+            }
+
+            let size = (data.len() - start) as u64;
+            if size != part.size {
+                return Err(ComponentUploadError::ComponentStoreError {
+                    message: format!(
+                        "Component upload part {} has changed in the store",
+                        part.part_number
+                    ),
+                    error: format!("expected {} bytes, got {size}", part.size),
+                });
+            }
+        }
+        Ok(data)
+    }
+
+    async fn delete_upload(&self, upload: &ComponentUpload) -> Result<(), ComponentUploadError> {
+        for part in &upload.parts {
+            self.object_store
+                .delete(&Self::part_key(&upload.upload_id, part.part_number))
+                .await
+                .map_err(|err| {
+                    ComponentUploadError::component_store_error(
+                        "Failed to delete component upload part",
+                        err,
+                    )
+                })?;
+        }
+
+        self.repo.delete(&upload.upload_id).await?;
+
+        Ok(())
+    }
+}
+
+impl<Owner: ComponentOwner> Debug for ComponentUploadServiceDefault<Owner> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComponentUploadServiceDefault").finish()
+    }
+}
+
+fn to_upload(
+    record: ComponentUploadRecord,
+    parts: Vec<ComponentUploadPartRecord>,
+) -> Result<ComponentUpload, ComponentUploadError> {
+    let component_type = record
+        .component_type
+        .map(ComponentType::try_from)
+        .transpose()
+        .map_err(|err| {
+            ComponentUploadError::Component(ComponentError::conversion_error("component type", err))
+        })?;
+
+    let parts: Vec<ComponentUploadPart> = parts
+        .into_iter()
+        .map(|part| ComponentUploadPart {
+            part_number: part.part_number as u32,
+            size: part.size as u64,
+            uploaded_at: part.created_at,
+        })
+        .collect();
+
+    Ok(ComponentUpload {
+        upload_id: record.upload_id,
+        component_id: ComponentId(record.component_id),
+        component_name: record.component_name.map(ComponentName),
+        component_type,
+        size: parts.iter().map(|part| part.size).sum(),
+        parts,
+        created_at: record.created_at,
+    })
+}
+
+#[async_trait]
+impl<Owner: ComponentOwner> ComponentUploadService<Owner> for ComponentUploadServiceDefault<Owner> {
+    async fn initiate(
+        &self,
+        request: ComponentUploadRequest,
+        owner: &Owner,
+    ) -> Result<ComponentUpload, ComponentUploadError> {
+        let component_id = match (&request.component_name, &request.component_id) {
+            (Some(component_name), None) => {
+                if let Some(component_id) = self
+                    .component_service
+                    .find_id_by_name(component_name, owner)
+                    .await?
+                {
+                    return Err(ComponentError::AlreadyExists(component_id).into());
+                }
+                ComponentId::new_v4()
+            }
+            (None, Some(component_id)) => {
+                self.component_service
+                    .get_latest_version(component_id, owner)
+                    .await?
+                    .ok_or(ComponentError::UnknownComponentId(component_id.clone()))?;
+                component_id.clone()
+            }
+            _ => {
+                return Err(ComponentUploadError::InvalidRequest(
+                    "exactly one of the component name and id must be given".to_string(),
+                ))
+            }
+        };
+
+        let record = ComponentUploadRecord {
+            upload_id: Uuid::new_v4(),
+            namespace: owner.to_string(),
+            component_id: component_id.0,
+            component_name: request.component_name.map(|name| name.0),
+            component_type: request.component_type.map(|ty| ty as i32),
+            created_at: Utc::now(),
+        };
+
+        self.repo.create(&record).await?;
+
+        info!(
+            owner = %owner,
+            component_id = %component_id,
+            upload_id = %record.upload_id,
+            "Component upload initiated"
+        );
+
+        to_upload(record, vec![])
+    }
+
+    async fn get(
+        &self,
+        upload_id: &Uuid,
+        owner: &Owner,
+    ) -> Result<ComponentUpload, ComponentUploadError> {
+        let record = self.get_record(upload_id, owner).await?;
+        let parts = self.repo.get_parts(upload_id).await?;
+        to_upload(record, parts)
+    }
+
+    async fn upload_part(
+        &self,
+        upload_id: &Uuid,
+        part_number: u32,
+        data: Vec<u8>,
+        owner: &Owner,
+    ) -> Result<ComponentUploadPart, ComponentUploadError> {
+        if !(1..=MAX_UPLOAD_PART_NUMBER).contains(&part_number) {
+            return Err(ComponentUploadError::InvalidRequest(format!(
+                "part number must be between 1 and {MAX_UPLOAD_PART_NUMBER}"
+            )));
+        }
+        if data.is_empty() {
+            return Err(ComponentUploadError::InvalidRequest(
+                "parts must not be empty".to_string(),
+            ));
+        }
+
+        self.get_record(upload_id, owner).await?;
+
+        let size = data.len() as u64;
+
+        self.object_store
+            .put(&Self::part_key(upload_id, part_number), data)
+            .await
+            .map_err(|err| {
+                ComponentUploadError::component_store_error(
+                    "Failed to upload component upload part",
+                    err,
+                )
+            })?;
+
+        let record = ComponentUploadPartRecord {
+            upload_id: *upload_id,
+            part_number: part_number as i32,
+            size: size as i64,
+            created_at: Utc::now(),
+        };
+
+        self.repo.put_part(&record).await?;
+
+        Ok(ComponentUploadPart {
+            part_number,
+            size,
+            uploaded_at: record.created_at,
+        })
+    }
+
+    async fn complete(
+        &self,
+        upload_id: &Uuid,
+        owner: &Owner,
+    ) -> Result<Component<Owner>, ComponentUploadError> {
+        let upload = self.get(upload_id, owner).await?;
+
+        let missing_parts = upload.missing_parts();
+        if upload.parts.is_empty() || !missing_parts.is_empty() {
+            return Err(ComponentUploadError::Incomplete {
+                upload_id: *upload_id,
+                missing_parts,
+            });
+        }
+
+        let data = self.read_parts(&upload).await?;
+
+        let component = match &upload.component_name {
+            Some(component_name) => {
+                self.component_service
+                    .create(
+                        &upload.component_id,
+                        component_name,
+                        upload.component_type.unwrap_or(ComponentType::Durable),
+                        data,
+                        None,
+                        vec![],
+                        HashMap::new(),
+                        owner,
+                    )
+                    .await?
+            }
+            None => {
+                self.component_service
+                    .update(
+                        &upload.component_id,
+                        data,
+                        upload.component_type,
+                        None,
+                        HashMap::new(),
+                        None,
+                        owner,
+                    )
+                    .await?
+            }
+        };
+
+        self.delete_upload(&upload).await?;
+
+        info!(
+            owner = %owner,
+            component_id = %upload.component_id,
+            upload_id = %upload_id,
+            "Component upload completed"
+        );
+
+        Ok(component)
+    }
+
+    async fn abort(&self, upload_id: &Uuid, owner: &Owner) -> Result<(), ComponentUploadError> {
+        let upload = self.get(upload_id, owner).await?;
+
+        self.delete_upload(&upload).await?;
+
+        info!(
+            owner = %owner,
+            component_id = %upload.component_id,
+            upload_id = %upload_id,
+            "Component upload aborted"
+        );
+
+        Ok(())
+    }
+
+    async fn abort_expired(&self, ttl: Duration) -> Result<usize, ComponentUploadError> {
+        let inactive_since = Utc::now()
+            - chrono::Duration::from_std(ttl).map_err(|e| {
+                ComponentUploadError::Component(ComponentError::conversion_error(
+                    "ttl",
+                    e.to_string(),
+                ))
+            })?;
+
+        let records = self.repo.get_inactive_since(inactive_since).await?;
+
+        let mut aborted = 0;
+        for record in records {
+            let upload_id = record.upload_id;
+            let namespace = record.namespace.clone();
+            let parts = self.repo.get_parts(&upload_id).await?;
+            let upload = to_upload(record, parts)?;
+
+            match self.delete_upload(&upload).await {
+                Ok(()) => {
+                    info!(
+                        owner = %namespace,
+                        component_id = %upload.component_id,
+                        upload_id = %upload_id,
+                        "Expired component upload aborted"
+                    );
+                    aborted += 1
+                }
+                Err(error) => {
+                    error!(owner = %namespace, upload_id = %upload_id, "Failed to abort expired component upload: {error}")
+                }
+            }
+        }
+
+        Ok(aborted)
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{ComponentWit, WitWorldItem};
+use golem_common::model::component_metadata::DynamicLinkedInstance;
+use std::collections::HashMap;
+use wasmparser::{Validator, WasmFeatures};
+
+// Interfaces provided by the worker executor to every component
+const SUPPORTED_IMPORT_PACKAGES: [&str; 2] = ["wasi:", "golem:"];
+
+// Validates an uploaded component binary against the component model, and checks that
+// everything it imports can be provided to it when running as a worker: the WASI and Golem host
+// interfaces, and the dynamically linked interfaces. Returns every problem found, so they can be
+// reported together.
+pub fn validate_component(
+    data: &[u8],
+    dynamic_linking: &HashMap<String, DynamicLinkedInstance>,
+) -> Result<(), Vec<String>> {
+    Validator::new_with_features(WasmFeatures::all())
+        .validate_all(data)
+        .map_err(|err| vec![format!("Invalid component: {err}")])?;
+
+    let wit = ComponentWit::decode(data).map_err(|err| vec![err])?;
+
+    let errors = wit
+        .imports
+        .iter()
+        .filter_map(|item| match item {
+            WitWorldItem::Interface(interface) => {
+                (!is_supported_import(&interface.name, dynamic_linking)).then(|| {
+                    format!(
+                        "Unsupported import: interface {} is neither provided by Golem nor dynamically linked",
+                        interface.name
+                    )
+                })
+            }
+            WitWorldItem::Function(function) => Some(format!(
+                "Unsupported import: function {} is imported directly by the world",
+                function.name
+            )),
+            WitWorldItem::TypeDefinition(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn is_supported_import(
+    name: &str,
+    dynamic_linking: &HashMap<String, DynamicLinkedInstance>,
+) -> bool {
+    let unversioned = |name: &str| name.split('@').next().unwrap_or(name).to_string();
+
+    SUPPORTED_IMPORT_PACKAGES
+        .iter()
+        .any(|package| name.starts_with(package))
+        || dynamic_linking
+            .keys()
+            .any(|linked| unversioned(linked) == unversioned(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use golem_common::model::component_metadata::DynamicLinkedWasmRpc;
+
+    #[test]
+    fn imports_must_be_provided_by_golem_or_linked() {
+        let dynamic_linking = HashMap::from([(
+            "rpc:counters-client/counters-client".to_string(),
+            DynamicLinkedInstance::WasmRpc(DynamicLinkedWasmRpc {
+                target_interface_name: HashMap::new(),
+            }),
+        )]);
+
+        assert!(is_supported_import(
+            "wasi:io/streams@0.2.0",
+            &dynamic_linking
+        ));
+        assert!(is_supported_import(
+            "golem:api/host@1.1.0",
+            &dynamic_linking
+        ));
+        assert!(is_supported_import(
+            "rpc:counters-client/counters-client@0.0.1",
+            &dynamic_linking
+        ));
+        assert!(!is_supported_import(
+            "acme:payments/gateway",
+            &dynamic_linking
+        ));
+
+        let errors = validate_component(b"not a component", &dynamic_linking).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Invalid component"));
+    }
+}
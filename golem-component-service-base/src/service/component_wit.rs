@@ -0,0 +1,118 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::ComponentWit;
+use crate::service::component::{ComponentError, ComponentService};
+use async_trait::async_trait;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
+use golem_common::model::component::ComponentOwner;
+use golem_common::model::component_metadata::ComponentProcessingError;
+use golem_common::SafeDisplay;
+use golem_service_base::model::VersionedComponentId;
+use std::sync::Arc;
+
+/// Serves the parsed WIT worlds of component versions
+#[async_trait]
+pub trait ComponentWitService<Owner: ComponentOwner> {
+    async fn get(
+        &self,
+        component_id: &VersionedComponentId,
+        owner: &Owner,
+    ) -> Result<Arc<ComponentWit>, ComponentError>;
+}
+
+#[derive(Debug, Clone)]
+enum ComponentWitLoadError {
+    Download(String),
+    Decode(String),
+}
+
+impl From<ComponentWitLoadError> for ComponentError {
+    fn from(value: ComponentWitLoadError) -> Self {
+        match value {
+            ComponentWitLoadError::Download(error) => ComponentError::ComponentStoreError {
+                message: "Failed to download component".to_string(),
+                error,
+            },
+            ComponentWitLoadError::Decode(error) => {
+                ComponentError::ComponentProcessingError(ComponentProcessingError::Parsing(error))
+            }
+        }
+    }
+}
+
+// Component versions are immutable, so their decoded WIT never needs to be invalidated. It is
+// cached by the versioned id, which is only looked up after checking the owner
+pub struct ComponentWitServiceDefault<Owner: ComponentOwner> {
+    component_service: Arc<dyn ComponentService<Owner> + Sync + Send>,
+    cache: Cache<VersionedComponentId, (), Arc<ComponentWit>, ComponentWitLoadError>,
+}
+
+impl<Owner: ComponentOwner> ComponentWitServiceDefault<Owner> {
+    pub fn new(component_service: Arc<dyn ComponentService<Owner> + Sync + Send>) -> Self {
+        Self {
+            component_service,
+            cache: Cache::new(
+                Some(1024),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::None,
+                "component_wit",
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl<Owner: ComponentOwner> ComponentWitService<Owner> for ComponentWitServiceDefault<Owner> {
+    async fn get(
+        &self,
+        component_id: &VersionedComponentId,
+        owner: &Owner,
+    ) -> Result<Arc<ComponentWit>, ComponentError> {
+        if self
+            .component_service
+            .get_by_version(component_id, owner)
+            .await?
+            .is_none()
+        {
+            return Err(ComponentError::UnknownVersionedComponentId(
+                component_id.clone(),
+            ));
+        }
+
+        let result = self
+            .cache
+            .get_or_insert_simple(component_id, || {
+                let component_service = self.component_service.clone();
+                let component_id = component_id.clone();
+                let owner = owner.clone();
+                Box::pin(async move {
+                    let data = component_service
+                        .download(
+                            &component_id.component_id,
+                            Some(component_id.version),
+                            &owner,
+                        )
+                        .await
+                        .map_err(|err| ComponentWitLoadError::Download(err.to_safe_string()))?;
+                    ComponentWit::decode(&data)
+                        .map(Arc::new)
+                        .map_err(ComponentWitLoadError::Decode)
+                })
+            })
+            .await?;
+
+        Ok(result)
+    }
+}
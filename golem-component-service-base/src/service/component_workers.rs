@@ -0,0 +1,105 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use golem_api_grpc::proto::golem::worker::v1::worker_service_client::WorkerServiceClient;
+use golem_api_grpc::proto::golem::worker::v1::{
+    get_workers_metadata_response, GetWorkersMetadataRequest,
+};
+use golem_common::client::{GrpcClient, GrpcClientConfig};
+use golem_common::model::{ComponentId, FilterComparator, ScanCursor, WorkerFilter, WorkerStatus};
+use http::Uri;
+use tonic::codec::CompressionEncoding;
+use tonic::transport::Channel;
+
+// Looks up whether a component still has workers, so that it is not deleted from under them
+#[async_trait]
+pub trait ComponentWorkersService {
+    // Whether the component has workers which have not exited yet
+    async fn has_live_workers(&self, component_id: &ComponentId) -> Result<bool, String>;
+}
+
+pub struct ComponentWorkersServiceDefault {
+    client: GrpcClient<WorkerServiceClient<Channel>>,
+}
+
+impl ComponentWorkersServiceDefault {
+    pub fn new(uri: Uri) -> Self {
+        let client = GrpcClient::new(
+            "worker-service",
+            |channel| {
+                WorkerServiceClient::new(channel)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .accept_compressed(CompressionEncoding::Gzip)
+            },
+            uri,
+            GrpcClientConfig::default(),
+        );
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl ComponentWorkersService for ComponentWorkersServiceDefault {
+    async fn has_live_workers(&self, component_id: &ComponentId) -> Result<bool, String> {
+        let filter = WorkerFilter::new_status(FilterComparator::NotEqual, WorkerStatus::Exited);
+        let mut cursor = Some(ScanCursor::default());
+
+        // A page of the scan may be empty even though later ones are not
+        while let Some(current_cursor) = cursor {
+            let request = GetWorkersMetadataRequest {
+                component_id: Some(component_id.clone().into()),
+                filter: Some(filter.clone().into()),
+                cursor: Some(current_cursor.into()),
+                count: 1,
+                precise: false,
+            };
+
+            let response = self
+                .client
+                .call("get-workers-metadata", move |client| {
+                    let request = request.clone();
+                    Box::pin(async move { client.get_workers_metadata(request).await })
+                })
+                .await
+                .map_err(|status| format!("Failed to get the workers: {status}"))?
+                .into_inner();
+
+            match response.result {
+                Some(get_workers_metadata_response::Result::Success(success)) => {
+                    if !success.workers.is_empty() {
+                        return Ok(true);
+                    }
+                    cursor = success.cursor.map(|cursor| cursor.into());
+                }
+                Some(get_workers_metadata_response::Result::Error(error)) => {
+                    return Err(format!("Failed to get the workers: {error:?}"));
+                }
+                None => return Err("Failed to get the workers: empty response".to_string()),
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+// Used when the component service is not connected to a worker service, such as in tests
+pub struct ComponentWorkersServiceDisabled;
+
+#[async_trait]
+impl ComponentWorkersService for ComponentWorkersServiceDisabled {
+    async fn has_live_workers(&self, _: &ComponentId) -> Result<bool, String> {
+        Ok(false)
+    }
+}
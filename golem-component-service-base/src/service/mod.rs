@@ -13,6 +13,13 @@
 // limitations under the License.
 
 pub mod component;
+pub mod component_archive;
+pub mod component_blob;
 pub mod component_compilation;
+pub mod component_configuration;
 pub mod component_object_store;
+pub mod component_upload;
+pub mod component_validation;
+pub mod component_wit;
+pub mod component_workers;
 pub mod plugin;
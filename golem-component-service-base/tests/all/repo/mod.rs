@@ -33,8 +33,8 @@ use golem_component_service_base::repo::plugin::PluginRepo;
 use golem_service_base::model::{ComponentName, VersionedComponentId};
 use golem_service_base::repo::plugin_installation::PluginInstallationRecord;
 use golem_service_base::repo::RepoError;
-use poem_openapi::NewType;
 use poem_openapi::__private::serde_json;
+use poem_openapi::NewType;
 use serde::{Deserialize, Serialize};
 use sqlx::query_builder::Separated;
 use sqlx::{Database, Encode, QueryBuilder};
@@ -309,6 +309,152 @@ async fn test_repo_component_delete(
     assert!(result4.unwrap().is_empty());
 }
 
+async fn test_repo_component_soft_delete(
+    component_repo: Arc<dyn ComponentRepo<DefaultComponentOwner> + Sync + Send>,
+) {
+    let component_name1 = ComponentName("shopping-cart1-component-soft-delete".to_string());
+    let data = get_component_data("shopping-cart");
+    let namespace = DefaultComponentOwner.to_string();
+
+    let component1 = Component::new(
+        ComponentId::new_v4(),
+        component_name1.clone(),
+        ComponentType::Durable,
+        &data,
+        vec![],
+        vec![],
+        HashMap::new(),
+        DefaultComponentOwner,
+    )
+    .unwrap();
+    let component_id1 = component1.versioned_component_id.component_id.0;
+
+    component_repo
+        .create(&ComponentRecord::try_from_model(component1.clone(), true).unwrap())
+        .await
+        .unwrap();
+
+    let deleted = component_repo
+        .soft_delete(&namespace, &component_id1, "test-user")
+        .await
+        .unwrap();
+    assert!(deleted);
+
+    let deleted_again = component_repo
+        .soft_delete(&namespace, &component_id1, "test-user")
+        .await
+        .unwrap();
+    assert!(!deleted_again);
+
+    let by_name = component_repo
+        .get_id_by_name(&namespace, &component_name1.0)
+        .await
+        .unwrap();
+    assert_eq!(by_name, None);
+
+    let versions = component_repo
+        .get(&namespace, &component_id1)
+        .await
+        .unwrap();
+    assert_eq!(versions.len(), 1);
+
+    let trash = component_repo.get_deleted(&namespace).await.unwrap();
+    let trashed = trash
+        .iter()
+        .find(|r| r.component_id == component_id1)
+        .unwrap();
+    assert_eq!(trashed.deleted_by, "test-user");
+
+    let restored = component_repo
+        .restore(&namespace, &component_id1)
+        .await
+        .unwrap();
+    assert!(restored);
+
+    let by_name = component_repo
+        .get_id_by_name(&namespace, &component_name1.0)
+        .await
+        .unwrap();
+    assert_eq!(by_name, Some(component_id1));
+}
+
+async fn test_repo_component_update_with_expected_version(
+    component_repo: Arc<dyn ComponentRepo<DefaultComponentOwner> + Sync + Send>,
+) {
+    let component_name1 = ComponentName("shopping-cart1-component-expected-version".to_string());
+    let data = get_component_data("shopping-cart");
+    let namespace = DefaultComponentOwner.to_string();
+    let owner_row: DefaultComponentOwnerRow = DefaultComponentOwner.into();
+
+    let component1 = Component::new(
+        ComponentId::new_v4(),
+        component_name1,
+        ComponentType::Durable,
+        &data,
+        vec![],
+        vec![],
+        HashMap::new(),
+        DefaultComponentOwner,
+    )
+    .unwrap();
+    let component_id1 = component1.versioned_component_id.component_id.0;
+    let record = ComponentRecord::try_from_model(component1.clone(), true).unwrap();
+
+    component_repo.create(&record).await.unwrap();
+
+    let updated = component_repo
+        .update(
+            &owner_row,
+            &namespace,
+            &component_id1,
+            data.clone(),
+            record.metadata.clone(),
+            None,
+            None,
+            Some(0),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.map(|record| record.version), Some(1));
+
+    // version 0 is not the latest one anymore
+    let stale = component_repo
+        .update(
+            &owner_row,
+            &namespace,
+            &component_id1,
+            data.clone(),
+            record.metadata.clone(),
+            None,
+            None,
+            Some(0),
+        )
+        .await
+        .unwrap();
+    assert!(stale.is_none());
+
+    let versions = component_repo
+        .get(&namespace, &component_id1)
+        .await
+        .unwrap();
+    assert_eq!(versions.len(), 2);
+
+    let unconditional = component_repo
+        .update(
+            &owner_row,
+            &namespace,
+            &component_id1,
+            data,
+            record.metadata.clone(),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(unconditional.map(|record| record.version), Some(2));
+}
+
 async fn test_repo_component_constraints(
     component_repo: Arc<dyn ComponentRepo<UuidOwner> + Sync + Send>,
 ) {
@@ -97,6 +97,23 @@ mod tests {
         crate::all::repo::test_repo_component_delete(component_repo.clone()).await
     }
 
+    #[test]
+    #[tracing::instrument]
+    async fn repo_component_soft_delete(
+        component_repo: &Arc<dyn ComponentRepo<DefaultComponentOwner> + Sync + Send>,
+    ) {
+        crate::all::repo::test_repo_component_soft_delete(component_repo.clone()).await
+    }
+
+    #[test]
+    #[tracing::instrument]
+    async fn repo_component_update_with_expected_version(
+        component_repo: &Arc<dyn ComponentRepo<DefaultComponentOwner> + Sync + Send>,
+    ) {
+        crate::all::repo::test_repo_component_update_with_expected_version(component_repo.clone())
+            .await
+    }
+
     #[test]
     #[tracing::instrument]
     async fn repo_component_constraints(
@@ -12,11 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use async_trait::async_trait;
 use test_r::{inherit_test_dep, test, test_dep};
+use tokio::sync::Notify;
 
 use crate::all::repo::sqlite::SqliteDb;
 use crate::all::repo::{constraint_data, get_component_data};
 use crate::Tracing;
+use futures::Stream;
 use golem_common::model::component::DefaultComponentOwner;
 use golem_common::model::plugin::{DefaultPluginOwner, DefaultPluginScope};
 use golem_common::model::{
@@ -25,29 +28,53 @@ use golem_common::model::{
 };
 use golem_common::SafeDisplay;
 use golem_component_service_base::config::ComponentStoreLocalConfig;
-use golem_component_service_base::model::InitialComponentFilesArchiveAndPermissions;
+use golem_component_service_base::model::{
+    ComponentUploadRequest, InitialComponentFilesArchiveAndPermissions,
+};
 use golem_component_service_base::repo::component::{
     ComponentRepo, DbComponentRepo, LoggedComponentRepo,
 };
+use golem_component_service_base::repo::component_blob::{
+    ComponentBlobRepo, DbComponentBlobRepo, LoggedComponentBlobRepo,
+};
+use golem_component_service_base::repo::component_upload::{
+    DbComponentUploadRepo, LoggedComponentUploadRepo,
+};
 use golem_component_service_base::repo::plugin::{DbPluginRepo, LoggedPluginRepo, PluginRepo};
 use golem_component_service_base::service::component::{
     ComponentError, ComponentService, ComponentServiceDefault, ConflictReport, ConflictingFunction,
 };
+use golem_component_service_base::service::component_blob::{
+    content_addressed_key, ComponentBlobService, ComponentBlobServiceDefault,
+};
 use golem_component_service_base::service::component_compilation::{
     ComponentCompilationService, ComponentCompilationServiceDisabled,
 };
 use golem_component_service_base::service::component_object_store;
 use golem_component_service_base::service::component_object_store::ComponentObjectStore;
+use golem_component_service_base::service::component_upload::{
+    ComponentUploadError, ComponentUploadService, ComponentUploadServiceDefault,
+};
+use golem_component_service_base::service::component_workers::{
+    ComponentWorkersService, ComponentWorkersServiceDisabled,
+};
 use golem_component_service_base::service::plugin::{PluginService, PluginServiceDefault};
 use golem_service_base::model::ComponentName;
+use golem_service_base::repo::namespace_quota::{DbNamespaceQuotaRepo, LoggedNamespaceQuotaRepo};
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
+use golem_service_base::service::namespace_quota::{
+    DefaultNamespaceQuotaService, NamespaceQuota, NamespaceQuotaError, NamespaceQuotaService,
+    QuotaResource,
+};
 use golem_service_base::storage::blob::fs::FileSystemBlobStorage;
 use golem_service_base::storage::blob::BlobStorage;
 use golem_wasm_ast::analysis::analysed_type::{str, u64};
 use rib::RegistryKey;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 inherit_test_dep!(Tracing);
@@ -84,6 +111,24 @@ fn object_store() -> Arc<dyn ComponentObjectStore + Send + Sync> {
     )
 }
 
+#[test_dep]
+fn sqlite_component_blob_repo(db: &SqliteDb) -> Arc<dyn ComponentBlobRepo + Send + Sync> {
+    Arc::new(LoggedComponentBlobRepo::new(DbComponentBlobRepo::new(
+        db.pool.clone(),
+    )))
+}
+
+#[test_dep]
+fn component_blob_service(
+    component_blob_repo: &Arc<dyn ComponentBlobRepo + Send + Sync>,
+    object_store: &Arc<dyn ComponentObjectStore + Send + Sync>,
+) -> Arc<dyn ComponentBlobService + Send + Sync> {
+    Arc::new(ComponentBlobServiceDefault::new(
+        component_blob_repo.clone(),
+        object_store.clone(),
+    ))
+}
+
 #[test_dep]
 fn component_compilation_service() -> Arc<dyn ComponentCompilationService + Send + Sync> {
     Arc::new(ComponentCompilationServiceDisabled)
@@ -112,21 +157,39 @@ fn plugin_service(
     Arc::new(PluginServiceDefault::new(plugin_repo.clone()))
 }
 
+#[test_dep]
+fn namespace_quota_service(
+    db: &SqliteDb,
+) -> Arc<dyn NamespaceQuotaService<DefaultComponentOwner> + Send + Sync> {
+    Arc::new(DefaultNamespaceQuotaService::new(
+        Arc::new(LoggedNamespaceQuotaRepo::new(DbNamespaceQuotaRepo::new(
+            db.pool.clone(),
+        ))),
+        vec![QuotaResource::Components, QuotaResource::ComponentSize],
+    ))
+}
+
 #[test_dep]
 fn component_service(
     component_repo: &Arc<dyn ComponentRepo<DefaultComponentOwner> + Send + Sync>,
     object_store: &Arc<dyn ComponentObjectStore + Send + Sync>,
+    component_blob_service: &Arc<dyn ComponentBlobService + Send + Sync>,
     component_compilation_service: &Arc<dyn ComponentCompilationService + Send + Sync>,
     initial_component_files_service: &Arc<InitialComponentFilesService>,
     plugin_service: &Arc<dyn PluginService<DefaultPluginOwner, DefaultPluginScope> + Send + Sync>,
+    namespace_quota_service: &Arc<dyn NamespaceQuotaService<DefaultComponentOwner> + Send + Sync>,
     _tracing: &Tracing,
 ) -> Arc<dyn ComponentService<DefaultComponentOwner> + Send + Sync> {
     Arc::new(ComponentServiceDefault::new(
         component_repo.clone(),
         object_store.clone(),
+        component_blob_service.clone(),
         component_compilation_service.clone(),
         initial_component_files_service.clone(),
         plugin_service.clone(),
+        namespace_quota_service.clone(),
+        Arc::new(ComponentWorkersServiceDisabled),
+        true,
     ))
 }
 
@@ -249,11 +312,28 @@ async fn test_services(
             None,
             None,
             HashMap::new(),
+            None,
             &DefaultComponentOwner,
         )
         .await
         .unwrap();
 
+    let stale_update = component_service
+        .update(
+            &component1.versioned_component_id.component_id,
+            get_component_data("shopping-cart"),
+            None,
+            None,
+            HashMap::new(),
+            Some(component1.versioned_component_id.version),
+            &DefaultComponentOwner,
+        )
+        .await;
+    assert!(matches!(
+        stale_update,
+        Err(ComponentError::PreconditionFailed(_))
+    ));
+
     let component1_result = component_service
         .get_latest_version(
             &component1.versioned_component_id.component_id,
@@ -386,6 +466,58 @@ async fn test_services(
         .delete(
             &component1v2.versioned_component_id.component_id,
             &DefaultComponentOwner,
+            "test-user",
+        )
+        .await
+        .unwrap();
+
+    let component1_result = component_service
+        .find_id_by_name(&component1.component_name, &DefaultComponentOwner)
+        .await
+        .unwrap();
+    assert_eq!(component1_result, None);
+
+    let component1_result = component_service
+        .download(
+            &component1v2.versioned_component_id.component_id,
+            Some(component1v2.versioned_component_id.version),
+            &DefaultComponentOwner,
+        )
+        .await;
+    assert!(component1_result.is_ok());
+
+    let deleted = component_service
+        .get_deleted(&DefaultComponentOwner)
+        .await
+        .unwrap();
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(
+        deleted[0].component_id,
+        component1.versioned_component_id.component_id
+    );
+    assert_eq!(deleted[0].deleted_by, "test-user");
+
+    component_service
+        .restore(
+            &component1.versioned_component_id.component_id,
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+
+    let component1_result = component_service
+        .find_id_by_name(&component1.component_name, &DefaultComponentOwner)
+        .await
+        .unwrap();
+    assert_eq!(
+        component1_result,
+        Some(component1.versioned_component_id.component_id.clone())
+    );
+
+    component_service
+        .purge(
+            &component1v2.versioned_component_id.component_id,
+            &DefaultComponentOwner,
         )
         .await
         .unwrap();
@@ -512,6 +644,7 @@ async fn test_initial_component_file_data_sharing(
                 }],
             }),
             HashMap::new(),
+            None,
             &DefaultComponentOwner,
         )
         .await
@@ -534,6 +667,439 @@ async fn test_initial_component_file_data_sharing(
     assert_eq!(component1_keys, component2_keys);
 }
 
+#[test]
+#[tracing::instrument]
+async fn test_component_data_deduplication(
+    component_service: &Arc<dyn ComponentService<DefaultComponentOwner> + Send + Sync>,
+) {
+    let data = get_component_data("rust-echo");
+
+    let component1 = component_service
+        .create(
+            &ComponentId::new_v4(),
+            &ComponentName("rust-echo-deduplication-1".to_string()),
+            ComponentType::Durable,
+            data.clone(),
+            None,
+            vec![],
+            HashMap::new(),
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+
+    let component2 = component_service
+        .create(
+            &ComponentId::new_v4(),
+            &ComponentName("rust-echo-deduplication-2".to_string()),
+            ComponentType::Durable,
+            data.clone(),
+            None,
+            vec![],
+            HashMap::new(),
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+
+    // the components have the same data, so they share the stored objects
+    assert_eq!(component1.object_store_key, component2.object_store_key);
+    assert_eq!(
+        component1.transformed_object_store_key,
+        component2.transformed_object_store_key
+    );
+
+    component_service
+        .purge(
+            &component1.versioned_component_id.component_id,
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+
+    // the data is still referenced by the other component
+    let downloaded = component_service
+        .download(
+            &component2.versioned_component_id.component_id,
+            Some(component2.versioned_component_id.version),
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+    assert_eq!(downloaded, data);
+}
+
+// Pauses the deletions after notifying their start, so that other operations can interleave
+struct SlowDeleteObjectStore {
+    store: Arc<dyn ComponentObjectStore + Send + Sync>,
+    deleting: Arc<Notify>,
+}
+
+#[async_trait]
+impl ComponentObjectStore for SlowDeleteObjectStore {
+    async fn get(&self, object_key: &str) -> Result<Vec<u8>, anyhow::Error> {
+        self.store.get(object_key).await
+    }
+
+    async fn get_stream(
+        &self,
+        object_key: &str,
+    ) -> Pin<Box<dyn Stream<Item = Result<Vec<u8>, anyhow::Error>> + Send + Sync>> {
+        self.store.get_stream(object_key).await
+    }
+
+    async fn put(&self, object_key: &str, data: Vec<u8>) -> Result<(), anyhow::Error> {
+        self.store.put(object_key, data).await
+    }
+
+    async fn delete(&self, object_key: &str) -> Result<(), anyhow::Error> {
+        self.deleting.notify_one();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        self.store.delete(object_key).await
+    }
+}
+
+#[test]
+#[tracing::instrument]
+async fn test_component_data_referenced_while_being_deleted(
+    component_blob_repo: &Arc<dyn ComponentBlobRepo + Send + Sync>,
+    object_store: &Arc<dyn ComponentObjectStore + Send + Sync>,
+) {
+    let deleting = Arc::new(Notify::new());
+    let object_store: Arc<dyn ComponentObjectStore + Send + Sync> =
+        Arc::new(SlowDeleteObjectStore {
+            store: object_store.clone(),
+            deleting: deleting.clone(),
+        });
+    let component_blob_service = Arc::new(ComponentBlobServiceDefault::new(
+        component_blob_repo.clone(),
+        object_store.clone(),
+    ));
+
+    let data = get_component_data("rust-echo");
+    let object_key = content_addressed_key(&data);
+
+    component_blob_service
+        .store(&object_key, data.clone())
+        .await
+        .unwrap();
+
+    // The last reference is released, and the data is referenced again while being deleted
+    let release = tokio::spawn({
+        let component_blob_service = component_blob_service.clone();
+        let object_key = object_key.clone();
+        async move { component_blob_service.release(&object_key).await }
+    });
+    deleting.notified().await;
+
+    let acquire_result = component_blob_service.acquire(&object_key).await;
+    let store_result = component_blob_service
+        .store(&object_key, data.clone())
+        .await;
+    release.await.unwrap().unwrap();
+
+    // The deleted data cannot be referenced, and storing it again uploads it after the deletion
+    assert!(acquire_result.is_err());
+    assert!(store_result.is_ok());
+    assert_eq!(
+        component_blob_repo
+            .get(&object_key)
+            .await
+            .unwrap()
+            .map(|blob| blob.ref_count),
+        Some(1)
+    );
+    assert_eq!(object_store.get(&object_key).await.unwrap(), data);
+}
+
+// Fixed limits, not shared with the other tests through the database
+#[derive(Debug)]
+struct FixedQuotas(HashMap<QuotaResource, u64>);
+
+#[async_trait]
+impl NamespaceQuotaService<DefaultComponentOwner> for FixedQuotas {
+    async fn get_all(
+        &self,
+        _namespace: &DefaultComponentOwner,
+    ) -> Result<Vec<NamespaceQuota>, NamespaceQuotaError> {
+        Ok(vec![])
+    }
+
+    async fn set(
+        &self,
+        _namespace: &DefaultComponentOwner,
+        resource: QuotaResource,
+        _limit: u64,
+    ) -> Result<NamespaceQuota, NamespaceQuotaError> {
+        Err(NamespaceQuotaError::UnsupportedResource(resource))
+    }
+
+    async fn delete(
+        &self,
+        _namespace: &DefaultComponentOwner,
+        resource: QuotaResource,
+    ) -> Result<(), NamespaceQuotaError> {
+        Err(NamespaceQuotaError::UnsupportedResource(resource))
+    }
+
+    async fn limit(
+        &self,
+        _namespace: &DefaultComponentOwner,
+        resource: QuotaResource,
+    ) -> Result<Option<u64>, NamespaceQuotaError> {
+        Ok(self.0.get(&resource).copied())
+    }
+
+    async fn acquire_invocation(
+        &self,
+        _namespace: &DefaultComponentOwner,
+    ) -> Result<(), NamespaceQuotaError> {
+        Ok(())
+    }
+}
+
+async fn create_rust_echo(
+    component_service: &(dyn ComponentService<DefaultComponentOwner> + Send + Sync),
+) -> Result<ComponentId, ComponentError> {
+    component_service
+        .create(
+            &ComponentId::new_v4(),
+            &ComponentName(format!("rust-echo-quotas-{}", Uuid::new_v4())),
+            ComponentType::Durable,
+            get_component_data("rust-echo"),
+            None,
+            vec![],
+            HashMap::new(),
+            &DefaultComponentOwner,
+        )
+        .await
+        .map(|component| component.versioned_component_id.component_id)
+}
+
+#[test]
+#[tracing::instrument]
+async fn test_component_quotas(
+    component_service: &Arc<dyn ComponentService<DefaultComponentOwner> + Send + Sync>,
+    component_repo: &Arc<dyn ComponentRepo<DefaultComponentOwner> + Send + Sync>,
+    object_store: &Arc<dyn ComponentObjectStore + Send + Sync>,
+    component_blob_service: &Arc<dyn ComponentBlobService + Send + Sync>,
+    component_compilation_service: &Arc<dyn ComponentCompilationService + Send + Sync>,
+    initial_component_files_service: &Arc<InitialComponentFilesService>,
+    plugin_service: &Arc<dyn PluginService<DefaultPluginOwner, DefaultPluginScope> + Send + Sync>,
+) {
+    let limited_component_service = |quotas: FixedQuotas| {
+        ComponentServiceDefault::new(
+            component_repo.clone(),
+            object_store.clone(),
+            component_blob_service.clone(),
+            component_compilation_service.clone(),
+            initial_component_files_service.clone(),
+            plugin_service.clone(),
+            Arc::new(quotas),
+            Arc::new(ComponentWorkersServiceDisabled),
+            true,
+        )
+    };
+    let size_limited = limited_component_service(FixedQuotas(HashMap::from([(
+        QuotaResource::ComponentSize,
+        1024,
+    )])));
+    let count_limited =
+        limited_component_service(FixedQuotas(HashMap::from([(QuotaResource::Components, 0)])));
+
+    let too_large = create_rust_echo(&size_limited).await;
+    let too_many = create_rust_echo(&count_limited).await;
+
+    // Updates are checked by the service too, whichever API they come from
+    let component_id = create_rust_echo(component_service.as_ref()).await.unwrap();
+    let too_large_update = size_limited
+        .update(
+            &component_id,
+            get_component_data("rust-echo"),
+            None,
+            None,
+            HashMap::new(),
+            None,
+            &DefaultComponentOwner,
+        )
+        .await;
+
+    let exceeded_resource = |result: Result<_, ComponentError>| match result {
+        Err(ComponentError::QuotaExceeded(exceeded)) => Some(exceeded.resource),
+        _ => None,
+    };
+
+    assert_eq!(
+        exceeded_resource(too_large.map(|_| ())),
+        Some(QuotaResource::ComponentSize)
+    );
+    assert_eq!(
+        exceeded_resource(too_many.map(|_| ())),
+        Some(QuotaResource::Components)
+    );
+    assert_eq!(
+        exceeded_resource(too_large_update.map(|_| ())),
+        Some(QuotaResource::ComponentSize)
+    );
+}
+
+// Pretends that every component has workers
+struct AlwaysWorkers;
+
+#[async_trait]
+impl ComponentWorkersService for AlwaysWorkers {
+    async fn has_live_workers(&self, _component_id: &ComponentId) -> Result<bool, String> {
+        Ok(true)
+    }
+}
+
+#[test]
+#[tracing::instrument]
+async fn test_component_delete_with_workers(
+    component_service: &Arc<dyn ComponentService<DefaultComponentOwner> + Send + Sync>,
+    component_repo: &Arc<dyn ComponentRepo<DefaultComponentOwner> + Send + Sync>,
+    object_store: &Arc<dyn ComponentObjectStore + Send + Sync>,
+    component_blob_service: &Arc<dyn ComponentBlobService + Send + Sync>,
+    component_compilation_service: &Arc<dyn ComponentCompilationService + Send + Sync>,
+    initial_component_files_service: &Arc<InitialComponentFilesService>,
+    plugin_service: &Arc<dyn PluginService<DefaultPluginOwner, DefaultPluginScope> + Send + Sync>,
+) {
+    let with_workers: ComponentServiceDefault<DefaultComponentOwner, DefaultPluginScope> =
+        ComponentServiceDefault::new(
+            component_repo.clone(),
+            object_store.clone(),
+            component_blob_service.clone(),
+            component_compilation_service.clone(),
+            initial_component_files_service.clone(),
+            plugin_service.clone(),
+            Arc::new(FixedQuotas(HashMap::new())),
+            Arc::new(AlwaysWorkers),
+            true,
+        );
+
+    let component_id = create_rust_echo(component_service.as_ref()).await.unwrap();
+
+    let delete_with_workers = with_workers
+        .delete(&component_id, &DefaultComponentOwner, "test")
+        .await;
+
+    // Deleted while there were no workers, but they showed up before purging it
+    component_service
+        .delete(&component_id, &DefaultComponentOwner, "test")
+        .await
+        .unwrap();
+
+    let purge_with_workers = with_workers
+        .purge(&component_id, &DefaultComponentOwner)
+        .await;
+    let purged_expired = with_workers.purge_expired(Duration::ZERO).await.unwrap();
+
+    let still_deleted = component_service
+        .get_deleted(&DefaultComponentOwner)
+        .await
+        .unwrap()
+        .iter()
+        .any(|deleted| deleted.component_id == component_id);
+
+    component_service
+        .purge(&component_id, &DefaultComponentOwner)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        delete_with_workers,
+        Err(ComponentError::ComponentHasWorkers(id)) if id == component_id
+    ));
+    assert!(matches!(
+        purge_with_workers,
+        Err(ComponentError::ComponentHasWorkers(id)) if id == component_id
+    ));
+    assert_eq!(purged_expired, 0);
+    assert!(still_deleted);
+}
+
+#[test]
+#[tracing::instrument]
+async fn test_component_upload(
+    db: &SqliteDb,
+    component_service: &Arc<dyn ComponentService<DefaultComponentOwner> + Send + Sync>,
+    object_store: &Arc<dyn ComponentObjectStore + Send + Sync>,
+) {
+    let upload_service = ComponentUploadServiceDefault::new(
+        Arc::new(LoggedComponentUploadRepo::new(DbComponentUploadRepo::new(
+            db.pool.clone(),
+        ))),
+        object_store.clone(),
+        component_service.clone(),
+    );
+    let component_name = ComponentName("rust-echo-upload".to_string());
+    let data = get_component_data("rust-echo");
+    let (first, second) = data.split_at(data.len() / 2);
+
+    let upload = upload_service
+        .initiate(
+            ComponentUploadRequest {
+                component_name: Some(component_name.clone()),
+                component_id: None,
+                component_type: None,
+            },
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+    // Uploaded out of order, the parts are assembled by their numbers
+    upload_service
+        .upload_part(
+            &upload.upload_id,
+            2,
+            second.to_vec(),
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+    upload_service
+        .upload_part(&upload.upload_id, 1, first.to_vec(), &DefaultComponentOwner)
+        .await
+        .unwrap();
+    let component = upload_service
+        .complete(&upload.upload_id, &DefaultComponentOwner)
+        .await
+        .unwrap();
+
+    let abandoned = upload_service
+        .initiate(
+            ComponentUploadRequest {
+                component_name: None,
+                component_id: Some(component.versioned_component_id.component_id.clone()),
+                component_type: None,
+            },
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+    upload_service
+        .upload_part(
+            &abandoned.upload_id,
+            1,
+            first.to_vec(),
+            &DefaultComponentOwner,
+        )
+        .await
+        .unwrap();
+    let aborted = upload_service.abort_expired(Duration::ZERO).await.unwrap();
+    let abandoned_after_expiry = upload_service
+        .get(&abandoned.upload_id, &DefaultComponentOwner)
+        .await;
+
+    assert_eq!(component.component_name, component_name);
+    assert_eq!(component.component_size, data.len() as u64);
+    assert!(aborted >= 1);
+    assert!(matches!(
+        abandoned_after_expiry,
+        Err(ComponentUploadError::UnknownUpload(id)) if id == abandoned.upload_id
+    ));
+}
+
 #[test]
 #[tracing::instrument]
 async fn test_component_constraint_incompatible_updates(
@@ -584,6 +1150,7 @@ async fn test_component_constraint_incompatible_updates(
             None,
             None,
             HashMap::new(),
+            None,
             &DefaultComponentOwner,
         )
         .await
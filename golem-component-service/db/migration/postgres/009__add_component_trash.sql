@@ -0,0 +1,7 @@
+ALTER TABLE components
+    ADD COLUMN IF NOT EXISTS deleted_at timestamp without time zone;
+ALTER TABLE components
+    ADD COLUMN IF NOT EXISTS deleted_by text;
+
+DROP INDEX components_namespace_name_idx;
+CREATE UNIQUE INDEX components_namespace_name_idx ON components (namespace, name) WHERE deleted_at IS NULL;
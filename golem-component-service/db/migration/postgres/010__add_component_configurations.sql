@@ -0,0 +1,13 @@
+CREATE TABLE project_default_configurations
+(
+    namespace     text NOT NULL PRIMARY KEY,
+    configuration text NOT NULL,
+    updated_at    timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE component_configurations
+(
+    component_id  uuid NOT NULL PRIMARY KEY REFERENCES components (component_id) ON DELETE CASCADE,
+    configuration text NOT NULL,
+    updated_at    timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,14 @@
+CREATE TABLE audit_log
+(
+    namespace     text NOT NULL,
+    entry_id      text NOT NULL,
+    actor         text NOT NULL,
+    operation     text NOT NULL,
+    resource_id   text NOT NULL,
+    before_digest text,
+    after_digest  text,
+    created_at    timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (entry_id)
+);
+
+CREATE INDEX audit_log_created_at_idx ON audit_log (namespace, created_at);
@@ -0,0 +1,8 @@
+CREATE TABLE namespace_quotas
+(
+    namespace   text   NOT NULL,
+    resource    text   NOT NULL,
+    quota_limit bigint NOT NULL,
+    updated_at  timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (namespace, resource)
+);
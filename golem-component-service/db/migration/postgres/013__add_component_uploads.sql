@@ -0,0 +1,18 @@
+CREATE TABLE component_uploads
+(
+    upload_id      uuid    NOT NULL PRIMARY KEY,
+    namespace      text    NOT NULL,
+    component_id   uuid    NOT NULL,
+    component_name text,
+    component_type integer,
+    created_at     timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE component_upload_parts
+(
+    upload_id   uuid    NOT NULL REFERENCES component_uploads (upload_id) ON DELETE CASCADE,
+    part_number integer NOT NULL,
+    size        bigint  NOT NULL,
+    created_at  timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (upload_id, part_number)
+);
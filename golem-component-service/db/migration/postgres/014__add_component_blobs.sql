@@ -0,0 +1,7 @@
+CREATE TABLE component_blobs
+(
+    object_key text   NOT NULL PRIMARY KEY,
+    size       bigint NOT NULL,
+    ref_count  bigint NOT NULL,
+    created_at timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
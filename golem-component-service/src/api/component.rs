@@ -13,40 +13,163 @@
 // limitations under the License.
 
 use crate::api::{ComponentError, Result};
-use futures_util::TryStreamExt;
+use crate::service::ComponentWatch;
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
+use futures_util::{StreamExt, TryStreamExt};
 use golem_common::model::component::DefaultComponentOwner;
 use golem_common::model::plugin::{
     DefaultPluginOwner, DefaultPluginScope, PluginInstallation, PluginInstallationCreation,
     PluginInstallationUpdate,
 };
-use golem_common::model::ComponentFilePathWithPermissionsList;
-use golem_common::model::{ComponentId, ComponentType, Empty, PluginInstallationId};
+use golem_common::model::{
+    ComponentFilePath, ComponentFilePathWithPermissionsList, InitialComponentFile,
+};
+use golem_common::model::{
+    ComponentId, ComponentType, ComponentVersion, Empty, PluginInstallationId,
+};
 use golem_common::recorded_http_api_request;
 use golem_component_service_base::model::{
-    DynamicLinking, InitialComponentFilesArchiveAndPermissions, UpdatePayload,
+    ComponentCompatibility, ComponentConfiguration, ComponentInterfaces, ComponentUpload,
+    ComponentUploadPart, ComponentUploadRequest, ComponentWit, DynamicLinking,
+    EffectiveComponentConfiguration, InitialComponentFilesArchiveAndPermissions, UpdatePayload,
 };
 use golem_component_service_base::service::component::ComponentService;
+use golem_component_service_base::service::component_configuration::ComponentConfigurationService;
+use golem_component_service_base::service::component_upload::ComponentUploadService;
+use golem_component_service_base::service::component_wit::ComponentWitService;
 use golem_component_service_base::service::plugin::{PluginError, PluginService};
 use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::EmptyAuthCtx;
+use golem_service_base::headers::{with_etag, IfMatch};
 use golem_service_base::model::*;
 use golem_service_base::poem::TempFileUpload;
+use golem_service_base::service::audit_log::{
+    AuditLogEntry, AuditLogFilter, AuditLogService, AuditOperation, AuditedOperation,
+};
+use golem_service_base::service::namespace_quota::{
+    NamespaceQuota, NamespaceQuotaService, NamespaceQuotaUpdate,
+};
 use poem::Body;
-use poem_openapi::param::{Path, Query};
-use poem_openapi::payload::{Binary, Json};
+use poem_openapi::param::{Header, Path, Query};
+use poem_openapi::payload::{Binary, EventStream, Json, Response};
 use poem_openapi::types::multipart::{JsonField, Upload};
 use poem_openapi::*;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
+use tap::TapFallible;
 use tracing::Instrument;
+use uuid::Uuid;
+
+const WATCH_KEEP_ALIVE: Duration = Duration::from_secs(15);
+const DEFAULT_AUDIT_LOG_LIMIT: u32 = 100;
 
 pub struct ComponentApi {
     pub component_service: Arc<dyn ComponentService<DefaultComponentOwner> + Sync + Send>,
+    pub component_wit_service: Arc<dyn ComponentWitService<DefaultComponentOwner> + Sync + Send>,
+    pub component_configuration_service:
+        Arc<dyn ComponentConfigurationService<DefaultComponentOwner> + Sync + Send>,
+    pub audit_log_service: Arc<dyn AuditLogService<DefaultComponentOwner> + Sync + Send>,
+    pub namespace_quota_service:
+        Arc<dyn NamespaceQuotaService<DefaultComponentOwner> + Sync + Send>,
+    pub component_upload_service:
+        Arc<dyn ComponentUploadService<DefaultComponentOwner> + Sync + Send>,
     pub plugin_service:
         Arc<dyn PluginService<DefaultPluginOwner, DefaultPluginScope> + Sync + Send>,
+    pub component_watch: Arc<ComponentWatch>,
 }
 
 #[OpenApi(prefix_path = "/v1/components", tag = ApiTags::Component)]
 impl ComponentApi {
+    /// Checks an `If-Match` precondition against the latest version of the component, returning
+    /// the version the update has to be based on, if any
+    async fn check_if_match(
+        &self,
+        component_id: &ComponentId,
+        if_match: Option<&str>,
+    ) -> Result<Option<ComponentVersion>> {
+        let Some(if_match) = if_match.map(IfMatch::parse) else {
+            return Ok(None);
+        };
+
+        let current_version = self
+            .component_service
+            .get_latest_version(component_id, &DefaultComponentOwner)
+            .await?
+            .map(|component| component.versioned_component_id.version);
+
+        if if_match.matches(
+            current_version
+                .map(|version| version.to_string())
+                .as_deref(),
+        ) {
+            Ok(current_version)
+        } else {
+            Err(ComponentError::PreconditionFailed(Json(ErrorBody {
+                error: "Component was modified concurrently, If-Match precondition failed"
+                    .to_string(),
+            })))
+        }
+    }
+
+    /// The latest version of a component as served by the API, for the digests of the audit log
+    async fn latest_component(&self, component_id: &ComponentId) -> Option<Component> {
+        self.component_service
+            .get_latest_version(component_id, &DefaultComponentOwner)
+            .await
+            .ok()
+            .flatten()
+            .map(Component::from)
+    }
+
+    async fn get_component_interfaces_internal(
+        &self,
+        component_id: &ComponentId,
+        version: Option<u64>,
+    ) -> Result<Json<ComponentInterfaces>> {
+        let component = match version {
+            Some(version) => {
+                self.component_service
+                    .get_by_version(
+                        &VersionedComponentId {
+                            component_id: component_id.clone(),
+                            version,
+                        },
+                        &DefaultComponentOwner,
+                    )
+                    .await?
+            }
+            None => {
+                self.component_service
+                    .get_latest_version(component_id, &DefaultComponentOwner)
+                    .await?
+            }
+        }
+        .ok_or_else(|| {
+            ComponentError::NotFound(Json(ErrorBody {
+                error: "Component not found".to_string(),
+            }))
+        })?;
+
+        let wit = self
+            .component_wit_service
+            .get(&component.versioned_component_id, &DefaultComponentOwner)
+            .await?;
+
+        Ok(Json(ComponentInterfaces::new(
+            component.versioned_component_id.version,
+            &wit,
+            &component.metadata.exports,
+        )))
+    }
+
+    async fn audit(&self, operation: AuditedOperation) {
+        self.audit_log_service
+            .record_or_log(&DefaultComponentOwner, &EmptyAuthCtx::default(), operation)
+            .await
+    }
+
     /// Create a new component
     ///
     /// The request body is encoded as multipart/form-data containing metadata and the WASM binary.
@@ -93,10 +216,57 @@ impl ComponentApi {
                 .map_err(|e| e.into())
                 .map(|response| Json(response.into()))
         };
+
+        if let Ok(Json(component)) = &response {
+            self.audit(
+                AuditedOperation::new(AuditOperation::ComponentCreate, &component_id)
+                    .after(Some(component)),
+            )
+            .instrument(record.span.clone())
+            .await;
+        }
+
+        record.result(response)
+    }
+
+    /// Check the compatibility of a new component version
+    ///
+    /// Checks a new WASM for a component against the API definitions using the component, without
+    /// updating it. Returns the functions called by the API definitions which the new version no
+    /// longer exports, or exports with different types.
+    #[oai(
+        path = "/:component_id/compatibility",
+        method = "post",
+        operation_id = "check_component_compatibility"
+    )]
+    async fn check_component_compatibility(
+        &self,
+        component_id: Path<ComponentId>,
+        wasm: Binary<Body>,
+    ) -> Result<Json<ComponentCompatibility>> {
+        let record = recorded_http_api_request!(
+            "check_component_compatibility",
+            component_id = component_id.0.to_string()
+        );
+
+        let response = {
+            let data = wasm.0.into_vec().await?;
+            self.component_service
+                .check_compatibility(&component_id.0, &data, &DefaultComponentOwner)
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into())
+                .map(|report| Json(report.into()))
+        };
+
         record.result(response)
     }
 
     /// Update a component
+    ///
+    /// If the `If-Match` header is set, the update is only applied when it matches
+    /// the latest version of the component, otherwise it fails with 412 Precondition Failed.
+    /// The `ETag` header of the response is the new version of the component.
     #[oai(
         path = "/:component_id/upload",
         method = "put",
@@ -105,17 +275,28 @@ impl ComponentApi {
     async fn upload_component(
         &self,
         component_id: Path<ComponentId>,
+        #[oai(name = "If-Match")] if_match: Header<Option<String>>,
         wasm: Binary<Body>,
         /// Type of the new version of the component - if not specified, the type of the previous version
         /// is used.
         component_type: Query<Option<ComponentType>>,
-    ) -> Result<Json<Component>> {
+    ) -> Result<Response<Json<Component>>> {
         let record = recorded_http_api_request!(
             "upload_component",
             component_id = component_id.0.to_string()
         );
 
+        let before = self
+            .latest_component(&component_id.0)
+            .instrument(record.span.clone())
+            .await;
+
         let response = {
+            let expected_version = self
+                .check_if_match(&component_id.0, if_match.0.as_deref())
+                .instrument(record.span.clone())
+                .await?;
+
             let data = wasm.0.into_vec().await?;
             self.component_service
                 .update(
@@ -124,17 +305,38 @@ impl ComponentApi {
                     component_type.0,
                     None,
                     HashMap::new(),
+                    expected_version,
                     &DefaultComponentOwner,
                 )
                 .instrument(record.span.clone())
                 .await
                 .map_err(|e| e.into())
-                .map(|response| Json(response.into()))
+                .map(Component::from)
         };
+
+        if let Ok(component) = &response {
+            self.audit(
+                AuditedOperation::new(AuditOperation::ComponentUpdate, &component_id.0)
+                    .before(before.as_ref())
+                    .after(Some(component)),
+            )
+            .instrument(record.span.clone())
+            .await;
+        }
+
+        let response = response.map(|component: Component| {
+            let version = component.versioned_component_id.version.to_string();
+            with_etag(Json(component), &version)
+        });
+
         record.result(response)
     }
 
     /// Update a component
+    ///
+    /// If the `If-Match` header is set, the update is only applied when it matches
+    /// the latest version of the component, otherwise it fails with 412 Precondition Failed.
+    /// The `ETag` header of the response is the new version of the component.
     #[oai(
         path = "/:component_id/updates",
         method = "post",
@@ -143,13 +345,24 @@ impl ComponentApi {
     async fn update_component(
         &self,
         component_id: Path<ComponentId>,
+        #[oai(name = "If-Match")] if_match: Header<Option<String>>,
         payload: UpdatePayload,
-    ) -> Result<Json<Component>> {
+    ) -> Result<Response<Json<Component>>> {
         let record = recorded_http_api_request!(
             "update_component",
             component_id = component_id.0.to_string()
         );
+        let before = self
+            .latest_component(&component_id.0)
+            .instrument(record.span.clone())
+            .await;
+
         let response = {
+            let expected_version = self
+                .check_if_match(&component_id.0, if_match.0.as_deref())
+                .instrument(record.span.clone())
+                .await?;
+
             let data = payload.component.into_vec().await?;
             let files_file = payload.files.map(|f| f.into_file());
 
@@ -173,13 +386,206 @@ impl ComponentApi {
                         .unwrap_or_default()
                         .0
                         .dynamic_linking,
+                    expected_version,
                     &DefaultComponentOwner,
                 )
                 .instrument(record.span.clone())
                 .await
                 .map_err(|e| e.into())
-                .map(|response| Json(response.into()))
+                .map(Component::from)
+        };
+
+        if let Ok(component) = &response {
+            self.audit(
+                AuditedOperation::new(AuditOperation::ComponentUpdate, &component_id.0)
+                    .before(before.as_ref())
+                    .after(Some(component)),
+            )
+            .instrument(record.span.clone())
+            .await;
+        }
+
+        let response = response.map(|component: Component| {
+            let version = component.versioned_component_id.version.to_string();
+            with_etag(Json(component), &version)
+        });
+
+        record.result(response)
+    }
+
+    /// Start a resumable component upload
+    ///
+    /// Starts uploading a new component when `componentName` is given, or a new version of an
+    /// existing component when `componentId` is given. The WASM binary is then uploaded in
+    /// parts, which can be retried independently, and the upload is completed to create the
+    /// component. Uploads which are neither completed nor aborted are aborted once no part has
+    /// been uploaded to them for the configured time, a day by default.
+    #[oai(
+        path = "/uploads",
+        method = "post",
+        operation_id = "initiate_component_upload"
+    )]
+    async fn initiate_component_upload(
+        &self,
+        request: Json<ComponentUploadRequest>,
+    ) -> Result<Json<ComponentUpload>> {
+        let record = recorded_http_api_request!(
+            "initiate_component_upload",
+            component_name = request.0.component_name.as_ref().map(|name| name.0.clone()),
+            component_id = request.0.component_id.as_ref().map(|id| id.to_string())
+        );
+
+        let response = self
+            .component_upload_service
+            .initiate(request.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Get a resumable component upload
+    ///
+    /// Lists the parts uploaded so far, so an interrupted upload can be resumed with the
+    /// missing ones.
+    #[oai(
+        path = "/uploads/:upload_id",
+        method = "get",
+        operation_id = "get_component_upload"
+    )]
+    async fn get_component_upload(&self, upload_id: Path<Uuid>) -> Result<Json<ComponentUpload>> {
+        let record =
+            recorded_http_api_request!("get_component_upload", upload_id = upload_id.0.to_string());
+
+        let response = self
+            .component_upload_service
+            .get(&upload_id.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Upload a part of a component
+    ///
+    /// Parts are numbered from 1, and are assembled in the order of their numbers. Uploading
+    /// a part again replaces it.
+    #[oai(
+        path = "/uploads/:upload_id/parts/:part_number",
+        method = "put",
+        operation_id = "upload_component_part"
+    )]
+    async fn upload_component_part(
+        &self,
+        upload_id: Path<Uuid>,
+        part_number: Path<u32>,
+        data: Binary<Body>,
+    ) -> Result<Json<ComponentUploadPart>> {
+        let record = recorded_http_api_request!(
+            "upload_component_part",
+            upload_id = upload_id.0.to_string(),
+            part_number = part_number.0
+        );
+
+        let response = {
+            let data = data.0.into_vec().await?;
+
+            self.component_upload_service
+                .upload_part(&upload_id.0, part_number.0, data, &DefaultComponentOwner)
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into())
+                .map(Json)
         };
+
+        record.result(response)
+    }
+
+    /// Complete a resumable component upload
+    ///
+    /// Assembles the uploaded parts into the component, and creates the component or its new
+    /// version. Fails with `400` if any part is missing.
+    #[oai(
+        path = "/uploads/:upload_id/complete",
+        method = "post",
+        operation_id = "complete_component_upload"
+    )]
+    async fn complete_component_upload(&self, upload_id: Path<Uuid>) -> Result<Json<Component>> {
+        let record = recorded_http_api_request!(
+            "complete_component_upload",
+            upload_id = upload_id.0.to_string()
+        );
+
+        let upload = match self
+            .component_upload_service
+            .get(&upload_id.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(upload) => upload,
+            Err(error) => return record.result(Err(error.into())),
+        };
+
+        let before = if upload.is_new_component() {
+            None
+        } else {
+            self.latest_component(&upload.component_id)
+                .instrument(record.span.clone())
+                .await
+        };
+
+        let response = self
+            .component_upload_service
+            .complete(&upload_id.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(|response| Json(response.into()));
+
+        if let Ok(Json(component)) = &response {
+            let operation = if upload.is_new_component() {
+                AuditOperation::ComponentCreate
+            } else {
+                AuditOperation::ComponentUpdate
+            };
+            self.audit(
+                AuditedOperation::new(operation, &upload.component_id)
+                    .before(before.as_ref())
+                    .after(Some(component)),
+            )
+            .instrument(record.span.clone())
+            .await;
+        }
+
+        record.result(response)
+    }
+
+    /// Abort a resumable component upload
+    ///
+    /// Deletes the upload along with its uploaded parts.
+    #[oai(
+        path = "/uploads/:upload_id",
+        method = "delete",
+        operation_id = "abort_component_upload"
+    )]
+    async fn abort_component_upload(&self, upload_id: Path<Uuid>) -> Result<Json<Empty>> {
+        let record = recorded_http_api_request!(
+            "abort_component_upload",
+            upload_id = upload_id.0.to_string()
+        );
+
+        let response = self
+            .component_upload_service
+            .abort(&upload_id.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(|_| Json(Empty {}));
+
         record.result(response)
     }
 
@@ -294,9 +700,172 @@ impl ComponentApi {
         record.result(response)
     }
 
+    /// Get the WIT of a given component version
+    ///
+    /// Gets the WIT world of a component version, with the interfaces, functions and types it
+    /// imports and exports, along with their documentation. Types are rendered in WIT syntax.
+    #[oai(
+        path = "/:component_id/versions/:version/wit",
+        method = "get",
+        operation_id = "get_component_wit"
+    )]
+    async fn get_component_wit(
+        &self,
+        component_id: Path<ComponentId>,
+        version: Path<String>,
+    ) -> Result<Json<ComponentWit>> {
+        let record = recorded_http_api_request!(
+            "get_component_wit",
+            component_id = component_id.0.to_string(),
+            version = version.0,
+        );
+
+        let response = {
+            let version_int = Self::parse_version_path_segment(&version.0)?;
+
+            let versioned_component_id = VersionedComponentId {
+                component_id: component_id.0,
+                version: version_int,
+            };
+
+            self.component_wit_service
+                .get(&versioned_component_id, &DefaultComponentOwner)
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into())
+                .map(|wit| Json(wit.as_ref().clone()))
+        };
+
+        record.result(response)
+    }
+
+    /// Get the initial files of a given component version
+    ///
+    /// Lists the files the workers of a component version start with, with their paths and
+    /// permissions.
+    #[oai(
+        path = "/:component_id/versions/:version/files",
+        method = "get",
+        operation_id = "get_component_files"
+    )]
+    async fn get_component_files(
+        &self,
+        component_id: Path<ComponentId>,
+        version: Path<String>,
+    ) -> Result<Json<Vec<InitialComponentFile>>> {
+        let record = recorded_http_api_request!(
+            "get_component_files",
+            component_id = component_id.0.to_string(),
+            version = version.0,
+        );
+
+        let response = {
+            let version_int = Self::parse_version_path_segment(&version.0)?;
+
+            let versioned_component_id = VersionedComponentId {
+                component_id: component_id.0,
+                version: version_int,
+            };
+
+            self.component_service
+                .get_by_version(&versioned_component_id, &DefaultComponentOwner)
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into())
+                .and_then(|response| match response {
+                    Some(component) => Ok(Json(component.files)),
+                    None => Err(ComponentError::NotFound(Json(ErrorBody {
+                        error: "Component not found".to_string(),
+                    }))),
+                })
+        };
+
+        record.result(response)
+    }
+
+    /// Download an initial file of a given component version
+    ///
+    /// Downloads the content of one of the files the workers of a component version start with,
+    /// identified by its absolute path.
+    #[oai(
+        path = "/:component_id/versions/:version/files/content",
+        method = "get",
+        operation_id = "download_component_file"
+    )]
+    async fn download_component_file(
+        &self,
+        component_id: Path<ComponentId>,
+        version: Path<String>,
+        path: Query<String>,
+    ) -> Result<Binary<Body>> {
+        let record = recorded_http_api_request!(
+            "download_component_file",
+            component_id = component_id.0.to_string(),
+            version = version.0,
+            path = path.0,
+        );
+
+        let response = {
+            let version_int = Self::parse_version_path_segment(&version.0)?;
+            let file_path = ComponentFilePath::from_abs_str(&path.0).map_err(|error| {
+                ComponentError::BadRequest(Json(ErrorsBody {
+                    errors: vec![format!("Invalid file path: {error}")],
+                }))
+            })?;
+
+            let versioned_component_id = VersionedComponentId {
+                component_id: component_id.0,
+                version: version_int,
+            };
+
+            self.component_service
+                .get_file_contents(&versioned_component_id, &file_path, &DefaultComponentOwner)
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into())
+                .map(|bytes| {
+                    Binary(Body::from_bytes_stream(bytes.map_err(|e| {
+                        std::io::Error::new(std::io::ErrorKind::Other, e)
+                    })))
+                })
+        };
+
+        record.result(response)
+    }
+
+    /// Get the interfaces of a given component
+    ///
+    /// Gets the interfaces a component version imports and exports, with the parameter and result
+    /// types of the exported functions and the names to call them by from Rib. Defaults to the
+    /// latest version of the component.
+    #[oai(
+        path = "/:component_id/interfaces",
+        method = "get",
+        operation_id = "get_component_interfaces"
+    )]
+    async fn get_component_interfaces(
+        &self,
+        component_id: Path<ComponentId>,
+        version: Query<Option<u64>>,
+    ) -> Result<Json<ComponentInterfaces>> {
+        let record = recorded_http_api_request!(
+            "get_component_interfaces",
+            component_id = component_id.0.to_string(),
+            version = version.0.map(|v| v.to_string())
+        );
+
+        let response = self
+            .get_component_interfaces_internal(&component_id.0, version.0)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+
     /// Get the latest version of a given component
     ///
-    /// Gets the latest version of a component.
+    /// Gets the latest version of a component. The `ETag` header of the response is the version,
+    /// usable in the `If-Match` header of updates.
     #[oai(
         path = "/:component_id/latest",
         method = "get",
@@ -305,7 +874,7 @@ impl ComponentApi {
     async fn get_latest_component_metadata(
         &self,
         component_id: Path<ComponentId>,
-    ) -> Result<Json<Component>> {
+    ) -> Result<Response<Json<Component>>> {
         let record = recorded_http_api_request!(
             "get_latest_component_metadata",
             component_id = component_id.0.to_string()
@@ -318,7 +887,10 @@ impl ComponentApi {
             .await
             .map_err(|e| e.into())
             .and_then(|response| match response {
-                Some(component) => Ok(Json(component.into())),
+                Some(component) => {
+                    let version = component.versioned_component_id.version.to_string();
+                    Ok(with_etag(Json(component.into()), &version))
+                }
                 None => Err(ComponentError::NotFound(Json(ErrorBody {
                     error: "Component not found".to_string(),
                 }))),
@@ -351,6 +923,369 @@ impl ComponentApi {
         record.result(response)
     }
 
+    /// Watch components
+    ///
+    /// Streams all component versions, optionally filtered by component name, as server-sent events.
+    /// The current state is sent first as `Added` events, followed by `Added`, `Modified` and `Deleted`
+    /// events whenever the components change. Every batch of events ends with a `Bookmark` event
+    /// carrying a cursor; passing it as `cursor` when reconnecting avoids resending unchanged state.
+    #[oai(path = "/watch", method = "get", operation_id = "watch_components")]
+    async fn watch_components(
+        &self,
+        #[oai(name = "component-name")] component_name: Query<Option<ComponentName>>,
+        cursor: Query<Option<String>>,
+    ) -> Result<EventStream<BoxStream<'static, ComponentWatchEvent>>> {
+        let record = recorded_http_api_request!(
+            "watch_components",
+            component_name = component_name.0.as_ref().map(|n| n.0.clone())
+        );
+
+        let events = self
+            .component_watch
+            .watch(component_name.0, cursor.0)
+            .filter_map(|event| async move {
+                event
+                    .tap_err(|error| tracing::error!("Watching components failed: {error}"))
+                    .ok()
+                    .map(ComponentWatchEvent::from)
+            })
+            .boxed();
+
+        record.result(Ok(EventStream::new(events).keep_alive(WATCH_KEEP_ALIVE)))
+    }
+
+    /// Delete a component
+    ///
+    /// Moves the component to the trash. It no longer shows up in listings and its name can be
+    /// reused. Fails with `409` while the component has workers which have not exited, which have
+    /// to be deleted first. The component can be restored until it is permanently purged after
+    /// the configured retention period.
+    #[oai(
+        path = "/:component_id",
+        method = "delete",
+        operation_id = "delete_component"
+    )]
+    async fn delete_component(&self, component_id: Path<ComponentId>) -> Result<Json<Empty>> {
+        let record = recorded_http_api_request!(
+            "delete_component",
+            component_id = component_id.0.to_string()
+        );
+
+        let before = self
+            .latest_component(&component_id.0)
+            .instrument(record.span.clone())
+            .await;
+
+        let response = self
+            .component_service
+            .delete(
+                &component_id.0,
+                &DefaultComponentOwner,
+                &DefaultComponentOwner.to_string(),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(|_| Json(Empty {}));
+
+        if response.is_ok() {
+            self.audit(
+                AuditedOperation::new(AuditOperation::ComponentDelete, &component_id.0)
+                    .before(before.as_ref()),
+            )
+            .instrument(record.span.clone())
+            .await;
+        }
+
+        record.result(response)
+    }
+
+    /// Restore a deleted component
+    ///
+    /// Restores a component from the trash. Fails if another component with the same name has been
+    /// created in the meantime.
+    #[oai(
+        path = "/:component_id/restore",
+        method = "post",
+        operation_id = "restore_component"
+    )]
+    async fn restore_component(&self, component_id: Path<ComponentId>) -> Result<Json<Empty>> {
+        let record = recorded_http_api_request!(
+            "restore_component",
+            component_id = component_id.0.to_string()
+        );
+
+        let response = self
+            .component_service
+            .restore(&component_id.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(|_| Json(Empty {}));
+
+        record.result(response)
+    }
+
+    /// Get deleted components
+    ///
+    /// Gets the components currently in the trash.
+    #[oai(
+        path = "/trash",
+        method = "get",
+        operation_id = "get_deleted_components"
+    )]
+    async fn get_deleted_components(&self) -> Result<Json<Vec<DeletedComponent>>> {
+        let record = recorded_http_api_request!("get_deleted_components",);
+
+        let response = self
+            .component_service
+            .get_deleted(&DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Get the audit log of components
+    ///
+    /// Lists the recorded component creations, updates and deletions, the newest first, with the
+    /// digests of the component before and after each of them. The entries can be filtered by
+    /// their actor, operation, component and time range.
+    #[oai(
+        path = "/audit-log",
+        method = "get",
+        operation_id = "get_component_audit_log"
+    )]
+    async fn get_audit_log(
+        &self,
+        actor: Query<Option<String>>,
+        operation: Query<Option<AuditOperation>>,
+        #[oai(name = "component-id")] component_id: Query<Option<ComponentId>>,
+        since: Query<Option<DateTime<Utc>>>,
+        until: Query<Option<DateTime<Utc>>>,
+        /// The maximum number of entries to return, 100 by default
+        limit: Query<Option<u32>>,
+    ) -> Result<Json<Vec<AuditLogEntry>>> {
+        let record = recorded_http_api_request!("get_component_audit_log",);
+
+        let filter = AuditLogFilter {
+            actor: actor.0,
+            operation: operation.0,
+            resource_id: component_id.0.map(|component_id| component_id.to_string()),
+            since: since.0,
+            until: until.0,
+        };
+
+        let response = self
+            .audit_log_service
+            .get_all(
+                &DefaultComponentOwner,
+                filter,
+                limit.0.unwrap_or(DEFAULT_AUDIT_LOG_LIMIT),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Get the component quotas
+    ///
+    /// Lists the limits of the number of components and of the size of a component in the
+    /// project. Resources without a limit are not listed.
+    #[oai(
+        path = "/quotas",
+        method = "get",
+        operation_id = "get_component_quotas"
+    )]
+    async fn get_quotas(&self) -> Result<Json<Vec<NamespaceQuota>>> {
+        let record = recorded_http_api_request!("get_component_quotas",);
+
+        let response = self
+            .namespace_quota_service
+            .get_all(&DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Update a component quota
+    ///
+    /// Sets or removes the limit of the number of components or of the size of a component in
+    /// the project. Creating components over the limit fails with `429`, and uploading
+    /// components larger than the limit fails with `413`.
+    #[oai(
+        path = "/quotas",
+        method = "put",
+        operation_id = "update_component_quota"
+    )]
+    async fn update_quota(
+        &self,
+        update: Json<NamespaceQuotaUpdate>,
+    ) -> Result<Json<Vec<NamespaceQuota>>> {
+        let record = recorded_http_api_request!(
+            "update_component_quota",
+            resource = update.0.resource.to_string()
+        );
+
+        let response = self
+            .namespace_quota_service
+            .update(&DefaultComponentOwner, update.0)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Get the project default configuration
+    ///
+    /// Gets the configuration inherited by all components of the project, unless overridden by
+    /// the configuration of a component.
+    #[oai(
+        path = "/defaults/configuration",
+        method = "get",
+        operation_id = "get_project_default_configuration"
+    )]
+    async fn get_project_default_configuration(&self) -> Result<Json<ComponentConfiguration>> {
+        let record = recorded_http_api_request!("get_project_default_configuration",);
+
+        let response = self
+            .component_configuration_service
+            .get_project_defaults(&DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Update the project default configuration
+    ///
+    /// Replaces the configuration inherited by all components of the project.
+    #[oai(
+        path = "/defaults/configuration",
+        method = "put",
+        operation_id = "update_project_default_configuration"
+    )]
+    async fn update_project_default_configuration(
+        &self,
+        configuration: Json<ComponentConfiguration>,
+    ) -> Result<Json<ComponentConfiguration>> {
+        let record = recorded_http_api_request!("update_project_default_configuration",);
+
+        let response = self
+            .component_configuration_service
+            .update_project_defaults(configuration.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Get the configuration of a component
+    ///
+    /// Gets the settings of the component overriding the project defaults, applying to all of
+    /// its versions.
+    #[oai(
+        path = "/:component_id/configuration",
+        method = "get",
+        operation_id = "get_component_configuration"
+    )]
+    async fn get_component_configuration(
+        &self,
+        component_id: Path<ComponentId>,
+    ) -> Result<Json<ComponentConfiguration>> {
+        let record = recorded_http_api_request!(
+            "get_component_configuration",
+            component_id = component_id.0.to_string()
+        );
+
+        let response = self
+            .component_configuration_service
+            .get_component_configuration(&component_id.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Update the configuration of a component
+    ///
+    /// Replaces the settings of the component overriding the project defaults. Settings left
+    /// empty are inherited from the project defaults.
+    #[oai(
+        path = "/:component_id/configuration",
+        method = "put",
+        operation_id = "update_component_configuration"
+    )]
+    async fn update_component_configuration(
+        &self,
+        component_id: Path<ComponentId>,
+        configuration: Json<ComponentConfiguration>,
+    ) -> Result<Json<ComponentConfiguration>> {
+        let record = recorded_http_api_request!(
+            "update_component_configuration",
+            component_id = component_id.0.to_string()
+        );
+
+        let response = self
+            .component_configuration_service
+            .update_component_configuration(
+                &component_id.0,
+                configuration.0,
+                &DefaultComponentOwner,
+            )
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
+    /// Get the effective configuration of a component
+    ///
+    /// Gets the result of merging the settings of the component over the project defaults,
+    /// along with both of them.
+    #[oai(
+        path = "/:component_id/configuration/effective",
+        method = "get",
+        operation_id = "get_effective_component_configuration"
+    )]
+    async fn get_effective_component_configuration(
+        &self,
+        component_id: Path<ComponentId>,
+    ) -> Result<Json<EffectiveComponentConfiguration>> {
+        let record = recorded_http_api_request!(
+            "get_effective_component_configuration",
+            component_id = component_id.0.to_string()
+        );
+
+        let response = self
+            .component_configuration_service
+            .get_effective_configuration(&component_id.0, &DefaultComponentOwner)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
     /// Gets the list of plugins installed for the given component version
     #[oai(
         path = "/:component_id/versions/:version/plugins/installs",
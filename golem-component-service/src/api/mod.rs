@@ -16,8 +16,13 @@ use crate::service::Services;
 use golem_common::metrics::api::TraceErrorKind;
 use golem_common::SafeDisplay;
 use golem_component_service_base::service::component::ComponentError as ComponentServiceError;
+use golem_component_service_base::service::component_upload::ComponentUploadError;
 use golem_component_service_base::service::plugin::PluginError;
 use golem_service_base::model::{ErrorBody, ErrorsBody};
+use golem_service_base::service::audit_log::AuditLogError;
+use golem_service_base::service::namespace_quota::{
+    NamespaceQuotaError, QuotaExceeded, QuotaResource,
+};
 use poem::endpoint::PrometheusExporter;
 use poem::error::ReadBodyError;
 use poem::Route;
@@ -54,7 +59,13 @@ pub fn make_open_api_service(services: &Services) -> OpenApiService<ApiServices,
         (
             component::ComponentApi {
                 component_service: services.component_service.clone(),
+                component_wit_service: services.component_wit_service.clone(),
+                component_configuration_service: services.component_configuration_service.clone(),
+                audit_log_service: services.audit_log_service.clone(),
+                namespace_quota_service: services.namespace_quota_service.clone(),
+                component_upload_service: services.component_upload_service.clone(),
                 plugin_service: services.plugin_service.clone(),
+                component_watch: services.component_watch.clone(),
             },
             healthcheck::HealthcheckApi,
             plugin::PluginApi {
@@ -78,6 +89,12 @@ pub enum ComponentError {
     NotFound(Json<ErrorBody>),
     #[oai(status = 409)]
     AlreadyExists(Json<ErrorBody>),
+    #[oai(status = 412)]
+    PreconditionFailed(Json<ErrorBody>),
+    #[oai(status = 413)]
+    PayloadTooLarge(Json<QuotaExceeded>),
+    #[oai(status = 429)]
+    TooManyRequests(Json<QuotaExceeded>),
     #[oai(status = 500)]
     InternalError(Json<ErrorBody>),
 }
@@ -88,7 +105,10 @@ impl TraceErrorKind for ComponentError {
             ComponentError::BadRequest(_) => "BadRequest",
             ComponentError::NotFound(_) => "NotFound",
             ComponentError::AlreadyExists(_) => "AlreadyExists",
+            ComponentError::PreconditionFailed(_) => "PreconditionFailed",
             ComponentError::LimitExceeded(_) => "LimitExceeded",
+            ComponentError::PayloadTooLarge(_) => "PayloadTooLarge",
+            ComponentError::TooManyRequests(_) => "TooManyRequests",
             ComponentError::Unauthorized(_) => "Unauthorized",
             ComponentError::InternalError(_) => "InternalError",
         }
@@ -151,7 +171,8 @@ impl From<ComponentServiceError> for ComponentError {
                     error: error.to_safe_string(),
                 }))
             }
-            ComponentServiceError::InitialComponentFileNotFound { .. } => {
+            ComponentServiceError::InitialComponentFileNotFound { .. }
+            | ComponentServiceError::UnknownComponentFile { .. } => {
                 ComponentError::NotFound(Json(ErrorBody {
                     error: error.to_safe_string(),
                 }))
@@ -171,6 +192,37 @@ impl From<ComponentServiceError> for ComponentError {
                     error: error.to_safe_string(),
                 }))
             }
+            ComponentServiceError::InvalidConfiguration(_) => {
+                ComponentError::BadRequest(Json(ErrorsBody {
+                    errors: vec![error.to_safe_string()],
+                }))
+            }
+            ComponentServiceError::InvalidComponent(errors) => {
+                ComponentError::BadRequest(Json(ErrorsBody { errors }))
+            }
+            ComponentServiceError::PreconditionFailed(_) => {
+                ComponentError::PreconditionFailed(Json(ErrorBody {
+                    error: error.to_safe_string(),
+                }))
+            }
+            ComponentServiceError::QuotaExceeded(exceeded) => {
+                NamespaceQuotaError::Exceeded(exceeded).into()
+            }
+            ComponentServiceError::InternalQuotaError(_) => {
+                ComponentError::InternalError(Json(ErrorBody {
+                    error: error.to_safe_string(),
+                }))
+            }
+            ComponentServiceError::ComponentHasWorkers(_) => {
+                ComponentError::AlreadyExists(Json(ErrorBody {
+                    error: error.to_safe_string(),
+                }))
+            }
+            ComponentServiceError::WorkerLookupError(_) => {
+                ComponentError::InternalError(Json(ErrorBody {
+                    error: error.to_safe_string(),
+                }))
+            }
         }
     }
 }
@@ -209,6 +261,63 @@ impl From<PluginError> for ComponentError {
     }
 }
 
+impl From<ComponentUploadError> for ComponentError {
+    fn from(value: ComponentUploadError) -> Self {
+        match value {
+            ComponentUploadError::Component(error) => error.into(),
+            ComponentUploadError::UnknownUpload(_) => ComponentError::NotFound(Json(ErrorBody {
+                error: value.to_safe_string(),
+            })),
+            ComponentUploadError::InvalidRequest(_) | ComponentUploadError::Incomplete { .. } => {
+                ComponentError::BadRequest(Json(ErrorsBody {
+                    errors: vec![value.to_safe_string()],
+                }))
+            }
+            ComponentUploadError::InternalRepoError(_)
+            | ComponentUploadError::ComponentStoreError { .. } => {
+                ComponentError::InternalError(Json(ErrorBody {
+                    error: value.to_safe_string(),
+                }))
+            }
+        }
+    }
+}
+
+impl From<AuditLogError> for ComponentError {
+    fn from(value: AuditLogError) -> Self {
+        match value {
+            AuditLogError::InternalError(_) => ComponentError::InternalError(Json(ErrorBody {
+                error: value.to_safe_string(),
+            })),
+        }
+    }
+}
+
+impl From<NamespaceQuotaError> for ComponentError {
+    fn from(value: NamespaceQuotaError) -> Self {
+        match value {
+            NamespaceQuotaError::Exceeded(exceeded)
+                if exceeded.resource == QuotaResource::ComponentSize =>
+            {
+                ComponentError::PayloadTooLarge(Json(exceeded))
+            }
+            NamespaceQuotaError::Exceeded(exceeded) => {
+                ComponentError::TooManyRequests(Json(exceeded))
+            }
+            NamespaceQuotaError::UnsupportedResource(_) => {
+                ComponentError::BadRequest(Json(ErrorsBody {
+                    errors: vec![value.to_safe_string()],
+                }))
+            }
+            NamespaceQuotaError::InternalError(_) => {
+                ComponentError::InternalError(Json(ErrorBody {
+                    error: value.to_safe_string(),
+                }))
+            }
+        }
+    }
+}
+
 impl From<ReadBodyError> for ComponentError {
     fn from(value: ReadBodyError) -> Self {
         ComponentError::InternalError(Json(ErrorBody {
@@ -12,9 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use golem_service_base::config::BlobStorageConfig;
+use golem_service_base::config::{BlobStorageConfig, TrashConfig, WatchConfig};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use std::time::Duration;
 
 use golem_common::config::{
     ConfigExample, ConfigLoader, DbConfig, DbSqliteConfig, HasConfigExamples,
@@ -23,7 +24,7 @@ use golem_common::model::Empty;
 use golem_common::tracing::TracingConfig;
 use golem_component_service_base::config::ComponentCompilationConfig;
 use golem_component_service_base::config::{
-    ComponentStoreConfig, ComponentStoreLocalConfig, ComponentStoreS3Config,
+    ComponentStoreConfig, ComponentStoreLocalConfig, ComponentStoreS3Config, WorkerServiceConfig,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -34,7 +35,14 @@ pub struct ComponentServiceConfig {
     pub db: DbConfig,
     pub component_store: ComponentStoreConfig,
     pub compilation: ComponentCompilationConfig,
+    pub worker_service: WorkerServiceConfig,
     pub blob_storage: BlobStorageConfig,
+    pub trash: TrashConfig,
+    pub upload: ComponentUploadConfig,
+    pub watch: WatchConfig,
+    // Whether to reject new component versions breaking the API definitions using the
+    // component, or to only log a warning about them
+    pub block_incompatible_updates: bool,
 }
 
 impl Default for ComponentServiceConfig {
@@ -52,7 +60,31 @@ impl Default for ComponentServiceConfig {
                 object_prefix: "".to_string(),
             }),
             compilation: ComponentCompilationConfig::default(),
+            worker_service: WorkerServiceConfig::default(),
             blob_storage: BlobStorageConfig::default(),
+            trash: TrashConfig::default(),
+            upload: ComponentUploadConfig::default(),
+            watch: WatchConfig::default(),
+            block_incompatible_updates: true,
+        }
+    }
+}
+
+/// How long the parts of an upload are kept after its last part was uploaded, if it is neither
+/// completed nor aborted
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComponentUploadConfig {
+    #[serde(with = "humantime_serde")]
+    pub ttl: Duration,
+    #[serde(with = "humantime_serde")]
+    pub sweep_interval: Duration,
+}
+
+impl Default for ComponentUploadConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(60 * 60 * 24),
+            sweep_interval: Duration::from_secs(60 * 60),
         }
     }
 }
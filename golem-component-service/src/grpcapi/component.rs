@@ -18,6 +18,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use tracing::Instrument;
 
+use crate::service::ComponentWatch;
 use futures_util::stream::BoxStream;
 use futures_util::StreamExt;
 use futures_util::TryStreamExt;
@@ -29,10 +30,11 @@ use golem_api_grpc::proto::golem::component::v1::{
     get_component_metadata_all_versions_response, get_component_metadata_response,
     get_components_response, get_installed_plugins_response, install_plugin_response,
     uninstall_plugin_response, update_component_request, update_component_response,
-    update_installed_plugin_response, ComponentError, CreateComponentConstraintsRequest,
-    CreateComponentConstraintsResponse, CreateComponentConstraintsSuccessResponse,
-    CreateComponentRequest, CreateComponentRequestHeader, CreateComponentResponse,
-    DownloadComponentRequest, DownloadComponentResponse, GetComponentMetadataAllVersionsResponse,
+    update_installed_plugin_response, watch_components_response, ComponentError,
+    CreateComponentConstraintsRequest, CreateComponentConstraintsResponse,
+    CreateComponentConstraintsSuccessResponse, CreateComponentRequest,
+    CreateComponentRequestHeader, CreateComponentResponse, DownloadComponentRequest,
+    DownloadComponentResponse, GetComponentMetadataAllVersionsResponse,
     GetComponentMetadataResponse, GetComponentMetadataSuccessResponse, GetComponentRequest,
     GetComponentSuccessResponse, GetComponentsRequest, GetComponentsResponse,
     GetComponentsSuccessResponse, GetInstalledPluginsRequest, GetInstalledPluginsResponse,
@@ -40,7 +42,7 @@ use golem_api_grpc::proto::golem::component::v1::{
     InstallPluginRequest, InstallPluginResponse, InstallPluginSuccessResponse,
     UninstallPluginRequest, UninstallPluginResponse, UpdateComponentRequest,
     UpdateComponentRequestHeader, UpdateComponentResponse, UpdateInstalledPluginRequest,
-    UpdateInstalledPluginResponse,
+    UpdateInstalledPluginResponse, WatchComponentsRequest, WatchComponentsResponse,
 };
 use golem_api_grpc::proto::golem::component::ComponentConstraints as ComponentConstraintsProto;
 use golem_api_grpc::proto::golem::component::FunctionConstraintCollection as FunctionConstraintCollectionProto;
@@ -82,6 +84,7 @@ pub struct ComponentGrpcApi {
         Arc<dyn component::ComponentService<DefaultComponentOwner> + Sync + Send>,
     pub plugin_service:
         Arc<dyn PluginService<DefaultPluginOwner, DefaultPluginScope> + Sync + Send>,
+    pub component_watch: Arc<ComponentWatch>,
 }
 
 impl ComponentGrpcApi {
@@ -428,6 +431,37 @@ impl ComponentService for ComponentGrpcApi {
         }))
     }
 
+    type WatchComponentsStream = BoxStream<'static, Result<WatchComponentsResponse, Status>>;
+
+    async fn watch_components(
+        &self,
+        request: Request<WatchComponentsRequest>,
+    ) -> Result<Response<Self::WatchComponentsStream>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!("watch_components",);
+
+        let stream = self
+            .component_watch
+            .watch(
+                request
+                    .component_name
+                    .map(golem_service_base::model::ComponentName),
+                request.cursor,
+            )
+            .map(|event| {
+                let result = match event {
+                    Ok(event) => watch_components_response::Result::Success(event.into()),
+                    Err(error) => watch_components_response::Result::Error(internal_error(&error)),
+                };
+                Ok(WatchComponentsResponse {
+                    result: Some(result),
+                })
+            })
+            .boxed();
+
+        Ok(Response::new(record.succeed(stream)))
+    }
+
     async fn create_component(
         &self,
         request: Request<Streaming<CreateComponentRequest>>,
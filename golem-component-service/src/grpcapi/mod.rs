@@ -57,6 +57,7 @@ pub async fn start_grpc_server(
                     ComponentServiceServer::new(ComponentGrpcApi {
                         component_service: services.component_service.clone(),
                         plugin_service: services.plugin_service.clone(),
+                        component_watch: services.component_watch.clone(),
                     })
                     .accept_compressed(CompressionEncoding::Gzip)
                     .send_compressed(CompressionEncoding::Gzip),
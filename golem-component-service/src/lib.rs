@@ -30,7 +30,7 @@ use poem_openapi::OpenApiService;
 use prometheus::Registry;
 use std::net::{Ipv4Addr, SocketAddrV4};
 use tokio::task::JoinSet;
-use tracing::{info, Instrument};
+use tracing::{error, info, Instrument};
 
 pub mod api;
 pub mod config;
@@ -103,6 +103,8 @@ impl ComponentService {
     ) -> Result<RunDetails, anyhow::Error> {
         let grpc_port = self.start_grpc_server(join_set).await?;
         let http_port = self.start_standalone_http_server(join_set).await?;
+        self.start_trash_purge(join_set);
+        self.start_upload_sweep(join_set);
         Ok(RunDetails {
             http_port,
             grpc_port,
@@ -116,6 +118,8 @@ impl ComponentService {
     ) -> Result<TrafficReadyEndpoints, anyhow::Error> {
         let grpc_port = self.start_grpc_server(join_set).await?;
         let endpoint = self.main_endpoint();
+        self.start_trash_purge(join_set);
+        self.start_upload_sweep(join_set);
         Ok(TrafficReadyEndpoints {
             grpc_port,
             endpoint,
@@ -143,6 +147,48 @@ impl ComponentService {
         .map_err(|err| anyhow!(err).context("gRPC server failed"))
     }
 
+    /// Periodically purges the components which have been in the trash longer than the retention period
+    fn start_trash_purge(&self, join_set: &mut JoinSet<Result<(), anyhow::Error>>) {
+        let component_service = self.services.component_service.clone();
+        let trash = self.config.trash.clone();
+
+        join_set.spawn(
+            async move {
+                let mut interval = tokio::time::interval(trash.purge_interval);
+                loop {
+                    interval.tick().await;
+                    match component_service.purge_expired(trash.retention).await {
+                        Ok(0) => {}
+                        Ok(purged) => info!("Purged {purged} components from the trash"),
+                        Err(err) => error!("Failed to purge components from the trash: {err}"),
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Periodically aborts the component uploads which have been abandoned for longer than their ttl
+    fn start_upload_sweep(&self, join_set: &mut JoinSet<Result<(), anyhow::Error>>) {
+        let component_upload_service = self.services.component_upload_service.clone();
+        let upload = self.config.upload.clone();
+
+        join_set.spawn(
+            async move {
+                let mut interval = tokio::time::interval(upload.sweep_interval);
+                loop {
+                    interval.tick().await;
+                    match component_upload_service.abort_expired(upload.ttl).await {
+                        Ok(0) => {}
+                        Ok(aborted) => info!("Aborted {aborted} expired component uploads"),
+                        Err(err) => error!("Failed to abort expired component uploads: {err}"),
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
     fn main_endpoint(&self) -> BoxEndpoint<'static> {
         api::make_open_api_service(&self.services)
             .into_endpoint()
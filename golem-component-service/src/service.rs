@@ -13,44 +13,97 @@
 // limitations under the License.
 
 use crate::config::ComponentServiceConfig;
+use futures_util::Stream;
 use golem_common::config::DbConfig;
 use golem_common::model::component::DefaultComponentOwner;
 use golem_common::model::plugin::{DefaultPluginOwner, DefaultPluginScope};
 use golem_component_service_base::config::ComponentCompilationConfig;
 use golem_component_service_base::config::ComponentStoreConfig;
+use golem_component_service_base::config::WorkerServiceConfig;
 use golem_component_service_base::repo::component::{
     ComponentRepo, DbComponentRepo, LoggedComponentRepo,
 };
+use golem_component_service_base::repo::component_blob::{
+    ComponentBlobRepo, DbComponentBlobRepo, LoggedComponentBlobRepo,
+};
+use golem_component_service_base::repo::component_configuration::{
+    ComponentConfigurationRepo, DbComponentConfigurationRepo, LoggedComponentConfigurationRepo,
+};
+use golem_component_service_base::repo::component_upload::{
+    ComponentUploadRepo, DbComponentUploadRepo, LoggedComponentUploadRepo,
+};
 use golem_component_service_base::repo::plugin::{DbPluginRepo, LoggedPluginRepo, PluginRepo};
 use golem_component_service_base::service::component::{ComponentService, ComponentServiceDefault};
+use golem_component_service_base::service::component_blob::{
+    ComponentBlobService, ComponentBlobServiceDefault,
+};
 use golem_component_service_base::service::component_compilation::{
     ComponentCompilationService, ComponentCompilationServiceDefault,
     ComponentCompilationServiceDisabled,
 };
+use golem_component_service_base::service::component_configuration::{
+    ComponentConfigurationService, ComponentConfigurationServiceDefault,
+};
 use golem_component_service_base::service::component_object_store;
 use golem_component_service_base::service::component_object_store::{
     ComponentObjectStore, LoggedComponentObjectStore,
 };
+use golem_component_service_base::service::component_upload::{
+    ComponentUploadService, ComponentUploadServiceDefault,
+};
+use golem_component_service_base::service::component_wit::{
+    ComponentWitService, ComponentWitServiceDefault,
+};
+use golem_component_service_base::service::component_workers::{
+    ComponentWorkersService, ComponentWorkersServiceDefault, ComponentWorkersServiceDisabled,
+};
 use golem_component_service_base::service::plugin::{PluginService, PluginServiceDefault};
-use golem_service_base::config::BlobStorageConfig;
+use golem_service_base::config::{BlobStorageConfig, WatchConfig};
 use golem_service_base::db;
+use golem_service_base::model::{Component, ComponentName};
+use golem_service_base::repo::audit_log::{AuditLogRepo, DbAuditLogRepo, LoggedAuditLogRepo};
+use golem_service_base::repo::namespace_quota::{
+    DbNamespaceQuotaRepo, LoggedNamespaceQuotaRepo, NamespaceQuotaRepo,
+};
+use golem_service_base::service::audit_log::{AuditLogService, DefaultAuditLogService};
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
+use golem_service_base::service::namespace_quota::{
+    DefaultNamespaceQuotaService, NamespaceQuotaService, QuotaResource,
+};
 use golem_service_base::storage::blob::sqlite::SqliteBlobStorage;
 use golem_service_base::storage::blob::BlobStorage;
 use golem_service_base::storage::sqlite::SqlitePool;
+use golem_service_base::watch::{WatchEvent, WatchHub};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct Services {
     pub component_service: Arc<dyn ComponentService<DefaultComponentOwner> + Sync + Send>,
     pub compilation_service: Arc<dyn ComponentCompilationService + Sync + Send>,
+    pub component_wit_service: Arc<dyn ComponentWitService<DefaultComponentOwner> + Sync + Send>,
+    pub component_configuration_service:
+        Arc<dyn ComponentConfigurationService<DefaultComponentOwner> + Sync + Send>,
+    pub audit_log_service: Arc<dyn AuditLogService<DefaultComponentOwner> + Sync + Send>,
+    pub namespace_quota_service:
+        Arc<dyn NamespaceQuotaService<DefaultComponentOwner> + Sync + Send>,
+    pub component_upload_service:
+        Arc<dyn ComponentUploadService<DefaultComponentOwner> + Sync + Send>,
     pub plugin_service:
         Arc<dyn PluginService<DefaultPluginOwner, DefaultPluginScope> + Send + Sync>,
+    pub component_watch: Arc<ComponentWatch>,
 }
 
 impl Services {
     pub async fn new(config: &ComponentServiceConfig) -> Result<Services, String> {
-        let (component_repo, plugin_repo) = match &config.db {
+        let (
+            component_repo,
+            plugin_repo,
+            component_configuration_repo,
+            audit_log_repo,
+            namespace_quota_repo,
+            component_upload_repo,
+            component_blob_repo,
+        ) = match &config.db {
             DbConfig::Postgres(db_config) => {
                 let db_pool = db::create_postgres_pool(db_config)
                     .await
@@ -65,7 +118,34 @@ impl Services {
                 > = Arc::new(LoggedPluginRepo::new(DbPluginRepo::new(
                     db_pool.clone().into(),
                 )));
-                (component_repo, plugin_repo)
+                let component_configuration_repo: Arc<
+                    dyn ComponentConfigurationRepo + Sync + Send,
+                > = Arc::new(LoggedComponentConfigurationRepo::new(
+                    DbComponentConfigurationRepo::new(db_pool.clone().into()),
+                ));
+                let audit_log_repo: Arc<dyn AuditLogRepo + Sync + Send> = Arc::new(
+                    LoggedAuditLogRepo::new(DbAuditLogRepo::new(db_pool.clone().into())),
+                );
+                let namespace_quota_repo: Arc<dyn NamespaceQuotaRepo + Sync + Send> =
+                    Arc::new(LoggedNamespaceQuotaRepo::new(DbNamespaceQuotaRepo::new(
+                        db_pool.clone().into(),
+                    )));
+                let component_upload_repo: Arc<dyn ComponentUploadRepo + Sync + Send> =
+                    Arc::new(LoggedComponentUploadRepo::new(DbComponentUploadRepo::new(
+                        db_pool.clone().into(),
+                    )));
+                let component_blob_repo: Arc<dyn ComponentBlobRepo + Sync + Send> = Arc::new(
+                    LoggedComponentBlobRepo::new(DbComponentBlobRepo::new(db_pool.clone().into())),
+                );
+                (
+                    component_repo,
+                    plugin_repo,
+                    component_configuration_repo,
+                    audit_log_repo,
+                    namespace_quota_repo,
+                    component_upload_repo,
+                    component_blob_repo,
+                )
             }
             DbConfig::Sqlite(db_config) => {
                 let db_pool = db::create_sqlite_pool(db_config)
@@ -80,7 +160,34 @@ impl Services {
                 > = Arc::new(LoggedPluginRepo::new(DbPluginRepo::new(
                     db_pool.clone().into(),
                 )));
-                (component_repo, plugin_repo)
+                let component_configuration_repo: Arc<
+                    dyn ComponentConfigurationRepo + Sync + Send,
+                > = Arc::new(LoggedComponentConfigurationRepo::new(
+                    DbComponentConfigurationRepo::new(db_pool.clone().into()),
+                ));
+                let audit_log_repo: Arc<dyn AuditLogRepo + Sync + Send> = Arc::new(
+                    LoggedAuditLogRepo::new(DbAuditLogRepo::new(db_pool.clone().into())),
+                );
+                let namespace_quota_repo: Arc<dyn NamespaceQuotaRepo + Sync + Send> =
+                    Arc::new(LoggedNamespaceQuotaRepo::new(DbNamespaceQuotaRepo::new(
+                        db_pool.clone().into(),
+                    )));
+                let component_upload_repo: Arc<dyn ComponentUploadRepo + Sync + Send> =
+                    Arc::new(LoggedComponentUploadRepo::new(DbComponentUploadRepo::new(
+                        db_pool.clone().into(),
+                    )));
+                let component_blob_repo: Arc<dyn ComponentBlobRepo + Sync + Send> = Arc::new(
+                    LoggedComponentBlobRepo::new(DbComponentBlobRepo::new(db_pool.clone().into())),
+                );
+                (
+                    component_repo,
+                    plugin_repo,
+                    component_configuration_repo,
+                    audit_log_repo,
+                    namespace_quota_repo,
+                    component_upload_repo,
+                    component_blob_repo,
+                )
             }
         };
 
@@ -88,6 +195,9 @@ impl Services {
             BlobStorageConfig::S3(config) => Arc::new(
                 golem_service_base::storage::blob::s3::S3BlobStorage::new(config.clone()).await,
             ),
+            BlobStorageConfig::AzureBlob(config) => Arc::new(
+                golem_service_base::storage::blob::azure::AzureBlobStorage::new(config.clone())?,
+            ),
             BlobStorageConfig::LocalFileSystem(config) => Arc::new(
                 golem_service_base::storage::blob::fs::FileSystemBlobStorage::new(&config.root)
                     .await?,
@@ -127,6 +237,10 @@ impl Services {
                 }
             };
 
+        let component_blob_service: Arc<dyn ComponentBlobService + Sync + Send> = Arc::new(
+            ComponentBlobServiceDefault::new(component_blob_repo, object_store.clone()),
+        );
+
         let compilation_service: Arc<dyn ComponentCompilationService + Sync + Send> =
             match config.compilation.clone() {
                 ComponentCompilationConfig::Enabled(config) => {
@@ -137,23 +251,126 @@ impl Services {
                 }
             };
 
+        let component_workers: Arc<dyn ComponentWorkersService + Sync + Send> =
+            match config.worker_service.clone() {
+                WorkerServiceConfig::Enabled(config) => {
+                    Arc::new(ComponentWorkersServiceDefault::new(config.uri()))
+                }
+                WorkerServiceConfig::Disabled(_) => Arc::new(ComponentWorkersServiceDisabled),
+            };
+
         let plugin_service: Arc<
             dyn PluginService<DefaultPluginOwner, DefaultPluginScope> + Sync + Send,
         > = Arc::new(PluginServiceDefault::new(plugin_repo));
 
+        let namespace_quota_service: Arc<
+            dyn NamespaceQuotaService<DefaultComponentOwner> + Sync + Send,
+        > = Arc::new(DefaultNamespaceQuotaService::new(
+            namespace_quota_repo,
+            vec![QuotaResource::Components, QuotaResource::ComponentSize],
+        ));
+
         let component_service: Arc<dyn ComponentService<DefaultComponentOwner> + Sync + Send> =
             Arc::new(ComponentServiceDefault::new(
                 component_repo.clone(),
                 object_store.clone(),
+                component_blob_service,
                 compilation_service.clone(),
                 initial_component_files_service.clone(),
                 plugin_service.clone(),
+                namespace_quota_service.clone(),
+                component_workers,
+                config.block_incompatible_updates,
             ));
 
+        let component_wit_service: Arc<
+            dyn ComponentWitService<DefaultComponentOwner> + Sync + Send,
+        > = Arc::new(ComponentWitServiceDefault::new(component_service.clone()));
+
+        let component_configuration_service: Arc<
+            dyn ComponentConfigurationService<DefaultComponentOwner> + Sync + Send,
+        > = Arc::new(ComponentConfigurationServiceDefault::new(
+            component_configuration_repo,
+            component_service.clone(),
+        ));
+
+        let audit_log_service: Arc<dyn AuditLogService<DefaultComponentOwner> + Sync + Send> =
+            Arc::new(DefaultAuditLogService::new(audit_log_repo));
+
+        let component_upload_service: Arc<
+            dyn ComponentUploadService<DefaultComponentOwner> + Sync + Send,
+        > = Arc::new(ComponentUploadServiceDefault::new(
+            component_upload_repo,
+            object_store.clone(),
+            component_service.clone(),
+        ));
+
+        let component_watch = Arc::new(ComponentWatch::new(
+            component_service.clone(),
+            config.watch.clone(),
+        ));
+
         Ok(Services {
             component_service,
             compilation_service,
+            component_wit_service,
+            component_configuration_service,
+            audit_log_service,
+            namespace_quota_service,
+            component_upload_service,
             plugin_service,
+            component_watch,
         })
     }
 }
+
+/// Watch streams of the components, shared by the HTTP and gRPC APIs so that all the watchers of
+/// the same components are served by a single poller
+pub struct ComponentWatch {
+    component_service: Arc<dyn ComponentService<DefaultComponentOwner> + Sync + Send>,
+    hub: WatchHub<Component>,
+}
+
+impl ComponentWatch {
+    pub fn new(
+        component_service: Arc<dyn ComponentService<DefaultComponentOwner> + Sync + Send>,
+        config: WatchConfig,
+    ) -> Self {
+        Self {
+            component_service,
+            hub: WatchHub::new(config),
+        }
+    }
+
+    /// Watches all the components, or the versions of the component with the given name
+    pub fn watch(
+        &self,
+        component_name: Option<ComponentName>,
+        resume_from: Option<String>,
+    ) -> impl Stream<Item = Result<WatchEvent<Component>, String>> + Send + 'static {
+        let key = format!("{:?}", component_name.as_ref().map(|name| &name.0));
+        let component_service = self.component_service.clone();
+
+        let list = move || {
+            let component_service = component_service.clone();
+            let component_name = component_name.clone();
+            async move {
+                let components = component_service
+                    .find_by_name(component_name, &DefaultComponentOwner)
+                    .await?;
+
+                Ok::<_, golem_component_service_base::service::component::ComponentError>(
+                    components
+                        .into_iter()
+                        .map(|c| {
+                            let component: Component = c.into();
+                            (component.versioned_component_id.to_string(), component)
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        self.hub.watch(key, list, resume_from)
+    }
+}
@@ -22,10 +22,13 @@ pub enum CallType {
     Function {
         worker: Option<Box<Expr>>,
         function_name: DynamicParsedFunctionName,
+        idempotency_key: Option<Box<Expr>>,
     },
     VariantConstructor(String),
     EnumConstructor(String),
     InstanceCreation(InstanceCreationType),
+    // `emit(event-name, payload)`, publishing an event instead of calling a worker function
+    EventEmission,
 }
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Ord, PartialOrd)]
@@ -66,10 +69,30 @@ impl CallType {
             _ => None,
         }
     }
+
+    pub fn idempotency_key_expr(&self) -> Option<&Expr> {
+        match self {
+            CallType::Function {
+                idempotency_key, ..
+            } => idempotency_key.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn idempotency_key_expr_mut(&mut self) -> Option<&mut Box<Expr>> {
+        match self {
+            CallType::Function {
+                idempotency_key, ..
+            } => idempotency_key.as_mut(),
+            _ => None,
+        }
+    }
+
     pub fn function_without_worker(function: DynamicParsedFunctionName) -> CallType {
         CallType::Function {
             worker: None,
             function_name: function,
+            idempotency_key: None,
         }
     }
     pub fn is_resource_method(&self) -> bool {
@@ -98,6 +121,7 @@ impl Display for CallType {
                     write!(f, "{}", resource_name.resource_name)
                 }
             },
+            CallType::EventEmission => write!(f, "emit"),
         }
     }
 }
@@ -182,11 +206,17 @@ mod protobuf {
                 .map(|w| Expr::try_from(*w))
                 .transpose()?
                 .map(Box::new);
+            let idempotency_key = value
+                .idempotency_key
+                .map(|k| Expr::try_from(*k))
+                .transpose()?
+                .map(Box::new);
             match invocation {
                 golem_api_grpc::proto::golem::rib::call_type::Name::Parsed(name) => {
                     Ok(CallType::Function {
                         function_name: DynamicParsedFunctionName::try_from(name)?,
                         worker,
+                        idempotency_key,
                     })
                 }
                 golem_api_grpc::proto::golem::rib::call_type::Name::VariantConstructor(name) => {
@@ -202,6 +232,9 @@ mod protobuf {
                     let instance_creation = InstanceCreationType::try_from(*instance_creation)?;
                     Ok(CallType::InstanceCreation(instance_creation))
                 }
+                golem_api_grpc::proto::golem::rib::call_type::Name::EventEmission(_) => {
+                    Ok(CallType::EventEmission)
+                }
             }
         }
     }
@@ -212,14 +245,17 @@ mod protobuf {
                 CallType::Function {
                     worker,
                     function_name,
+                    idempotency_key,
                 } => golem_api_grpc::proto::golem::rib::CallType {
                     worker_name: worker.map(|w| Box::new(golem_api_grpc::proto::golem::rib::Expr::from(*w))),
+                    idempotency_key: idempotency_key.map(|k| Box::new(golem_api_grpc::proto::golem::rib::Expr::from(*k))),
                     name: Some(golem_api_grpc::proto::golem::rib::call_type::Name::Parsed(
                         function_name.into(),
                     )),
                 },
                 CallType::VariantConstructor(name) => golem_api_grpc::proto::golem::rib::CallType {
                     worker_name: None,
+                    idempotency_key: None,
                     name: Some(
                         golem_api_grpc::proto::golem::rib::call_type::Name::VariantConstructor(
                             name,
@@ -228,15 +264,26 @@ mod protobuf {
                 },
                 CallType::EnumConstructor(name) => golem_api_grpc::proto::golem::rib::CallType {
                     worker_name: None,
+                    idempotency_key: None,
                     name: Some(
                         golem_api_grpc::proto::golem::rib::call_type::Name::EnumConstructor(name),
                     ),
                 },
+                CallType::EventEmission => golem_api_grpc::proto::golem::rib::CallType {
+                    worker_name: None,
+                    idempotency_key: None,
+                    name: Some(
+                        golem_api_grpc::proto::golem::rib::call_type::Name::EventEmission(
+                            golem_api_grpc::proto::golem::rib::EventEmission {},
+                        ),
+                    ),
+                },
                 CallType::InstanceCreation(instance_creation) => {
                     match instance_creation {
                         InstanceCreationType::Worker { worker_name } => {
                             golem_api_grpc::proto::golem::rib::CallType {
                                 worker_name: worker_name.clone().map(|w| Box::new(golem_api_grpc::proto::golem::rib::Expr::from(*w))),
+                                idempotency_key: None,
                                 name:  Some(golem_api_grpc::proto::golem::rib::call_type::Name::InstanceCreation(
                                     Box::new(golem_api_grpc::proto::golem::rib::InstanceCreationType {
                                         kind: Some(golem_api_grpc::proto::golem::rib::instance_creation_type::Kind::Worker(Box::new(WorkerInstance {
@@ -249,6 +296,7 @@ mod protobuf {
                         InstanceCreationType::Resource { worker_name, resource_name } => {
                             golem_api_grpc::proto::golem::rib::CallType {
                                 worker_name: worker_name.clone().map(|w| Box::new(golem_api_grpc::proto::golem::rib::Expr::from(*w))),
+                                idempotency_key: None,
                                 name:  Some(golem_api_grpc::proto::golem::rib::call_type::Name::InstanceCreation(
                                     Box::new(golem_api_grpc::proto::golem::rib::InstanceCreationType {
                                         kind: Some(golem_api_grpc::proto::golem::rib::instance_creation_type::Kind::Resource(Box::new(golem_api_grpc::proto::golem::rib::ResourceInstanceWithWorkerName {
@@ -282,6 +330,7 @@ mod protobuf {
                         function_name: DynamicParsedFunctionName::parse(
                             ParsedFunctionName::try_from(name)?.to_string(),
                         )?,
+                        idempotency_key: None,
                     })
                 }
                 golem_api_grpc::proto::golem::rib::invocation_name::Name::VariantConstructor(
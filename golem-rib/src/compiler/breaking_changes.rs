@@ -0,0 +1,398 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::CallType;
+use crate::rib_compilation_error::RibCompilationError;
+use crate::{
+    Expr, FunctionTypeRegistry, GlobalVariableTypeSpec, InferredExpr, RegistryKey, RegistryValue,
+    WorkerFunctionType, WorkerFunctionsInRib,
+};
+use golem_wasm_ast::analysis::{AnalysedExport, AnalysedType};
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+// A worker function call of a compiled script that would fail to type check
+// against a new version of the component
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakingChange {
+    pub function: RegistryKey,
+    pub kind: BreakingChangeKind,
+    // The expressions of the script failing to type check because of the change, which are the
+    // calls of a missing function, or the expressions a changed type is no longer valid for
+    pub exprs: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakingChangeKind {
+    MissingFunction,
+    ChangedParameterTypes {
+        existing: Vec<AnalysedType>,
+        new: Vec<AnalysedType>,
+        cause: String,
+    },
+    ChangedReturnTypes {
+        existing: Vec<AnalysedType>,
+        new: Vec<AnalysedType>,
+        cause: String,
+    },
+}
+
+impl Display for BreakingChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let function = match self.function.get_interface_name() {
+            Some(interface) => format!("{}.{{{}}}", interface, self.function.get_function_name()),
+            None => self.function.get_function_name(),
+        };
+
+        match &self.kind {
+            BreakingChangeKind::MissingFunction => {
+                write!(f, "function {} no longer exists", function)?
+            }
+            BreakingChangeKind::ChangedParameterTypes { cause, .. } => write!(
+                f,
+                "parameter types of function {} changed: {}",
+                function, cause
+            )?,
+            BreakingChangeKind::ChangedReturnTypes { cause, .. } => write!(
+                f,
+                "return types of function {} changed: {}",
+                function, cause
+            )?,
+        }
+
+        for expr in &self.exprs {
+            let span = expr.source_span();
+            write!(
+                f,
+                "\n  at line {}, column {}: `{}`",
+                span.start_line(),
+                span.start_column(),
+                expr
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Finds the worker function calls of a script, compiled into `worker_functions`, that would fail
+// to type check against the exports of a new version of the component, along with the
+// expressions of `expr` (the script as parsed, before compilation) that fail.
+// The types of a function may change without breaking the script, such as when a case is added to
+// an enum or variant it takes, so the script is type checked again against each changed function.
+// Such a script has to be changed before its component constraints can be updated to the new version.
+pub fn find_breaking_changes(
+    expr: &Expr,
+    worker_functions: &WorkerFunctionsInRib,
+    new_export_metadata: &Vec<AnalysedExport>,
+    global_variable_type_spec: &Vec<GlobalVariableTypeSpec>,
+) -> Vec<BreakingChange> {
+    let new_type_registry = FunctionTypeRegistry::from_export_metadata(new_export_metadata);
+
+    let mut breaking_changes = vec![];
+    let mut changed_functions = vec![];
+
+    for function in &worker_functions.function_calls {
+        match new_type_registry.lookup(&function.function_key) {
+            Some(RegistryValue::Function {
+                parameter_types,
+                return_types,
+            }) => {
+                if parameter_types != function.parameter_types
+                    || return_types != function.return_types
+                {
+                    changed_functions.push((function, parameter_types, return_types));
+                }
+            }
+            _ => breaking_changes.push(BreakingChange {
+                function: function.function_key.clone(),
+                kind: BreakingChangeKind::MissingFunction,
+                exprs: internal::calls_of(expr, function),
+            }),
+        }
+    }
+
+    let changed = changed_functions
+        .iter()
+        .map(|(function, _, _)| *function)
+        .collect::<Vec<_>>();
+
+    if let Err(error) = internal::infer_with_new_types(
+        expr,
+        worker_functions,
+        &changed,
+        &new_type_registry,
+        global_variable_type_spec,
+    ) {
+        // Each change is checked on its own to find the ones breaking the script, and if none
+        // does on its own, they break it together
+        let mut errors = changed_functions
+            .iter()
+            .filter_map(|(function, parameter_types, return_types)| {
+                internal::infer_with_new_types(
+                    expr,
+                    worker_functions,
+                    &[*function],
+                    &new_type_registry,
+                    global_variable_type_spec,
+                )
+                .err()
+                .map(|error| (*function, parameter_types, return_types, error))
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            errors = changed_functions
+                .iter()
+                .map(|(function, parameter_types, return_types)| {
+                    (*function, parameter_types, return_types, error.clone())
+                })
+                .collect();
+        }
+
+        for (function, parameter_types, return_types, error) in errors {
+            let kind = if *parameter_types != function.parameter_types {
+                BreakingChangeKind::ChangedParameterTypes {
+                    existing: function.parameter_types.clone(),
+                    new: parameter_types.clone(),
+                    cause: error.cause,
+                }
+            } else {
+                BreakingChangeKind::ChangedReturnTypes {
+                    existing: function.return_types.clone(),
+                    new: return_types.clone(),
+                    cause: error.cause,
+                }
+            };
+
+            breaking_changes.push(BreakingChange {
+                function: function.function_key.clone(),
+                kind,
+                exprs: vec![error.expr],
+            });
+        }
+    }
+
+    breaking_changes.sort_by(|a, b| a.function.cmp(&b.function));
+    breaking_changes
+}
+
+mod internal {
+    use super::*;
+
+    // Infers the types of the script with the new types of the `changed` functions, keeping the
+    // existing types of the other worker functions, so that a failure is caused by these changes.
+    // The enums and variants are those of the new version, which the existing calls may refer to.
+    pub(crate) fn infer_with_new_types(
+        expr: &Expr,
+        worker_functions: &WorkerFunctionsInRib,
+        changed: &[&WorkerFunctionType],
+        new_type_registry: &FunctionTypeRegistry,
+        global_variable_type_spec: &Vec<GlobalVariableTypeSpec>,
+    ) -> Result<InferredExpr, RibCompilationError> {
+        let mut type_registry = new_type_registry.clone();
+
+        for function in &worker_functions.function_calls {
+            if !changed.contains(&function) {
+                type_registry.types.insert(
+                    function.function_key.clone(),
+                    RegistryValue::Function {
+                        parameter_types: function.parameter_types.clone(),
+                        return_types: function.return_types.clone(),
+                    },
+                );
+            }
+        }
+
+        InferredExpr::from_expr(expr, &type_registry, global_variable_type_spec)
+    }
+
+    // The calls of a function in a script as parsed, where methods invoked on a worker
+    // (Example: `worker.add-item(x)`) are not yet resolved to a function of the component,
+    // so they are matched by name
+    pub(crate) fn calls_of(expr: &Expr, function: &WorkerFunctionType) -> Vec<Expr> {
+        let mut calls = vec![];
+        let mut queue = VecDeque::new();
+        queue.push_back(expr);
+
+        while let Some(expr) = queue.pop_front() {
+            match expr {
+                Expr::Call {
+                    call_type: CallType::Function { function_name, .. },
+                    ..
+                } if RegistryKey::registry_keys_of_function(function_name)
+                    .contains(&function.function_key) =>
+                {
+                    calls.push(expr.clone())
+                }
+                Expr::InvokeMethodLazy { method, .. }
+                    if simple_name(method)
+                        == simple_name(&function.function_key.get_function_name()) =>
+                {
+                    calls.push(expr.clone())
+                }
+                _ => {}
+            }
+
+            expr.visit_children_bottom_up(&mut queue);
+        }
+
+        calls
+    }
+
+    // The name of a function without its interface, and without the resource it belongs to
+    // (Example: `add-item` for `golem:it/api.{[method]cart.add-item}`)
+    fn simple_name(name: &str) -> &str {
+        let name = name.rsplit_once(".{").map_or(name, |(_, name)| name);
+        let name = name.trim_end_matches('}');
+        let name = name
+            .strip_prefix("[constructor]")
+            .or_else(|| name.strip_prefix("[method]"))
+            .or_else(|| name.strip_prefix("[static]"))
+            .unwrap_or(name);
+        name.rsplit_once('.').map_or(name, |(_, name)| name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{case, r#enum, str, u64, unit_case, variant};
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedInstance,
+    };
+    use test_r::test;
+
+    fn metadata(functions: Vec<(&str, AnalysedType)>) -> Vec<AnalysedExport> {
+        metadata_with_results(
+            functions
+                .into_iter()
+                .map(|(name, parameter_type)| (name, parameter_type, str()))
+                .collect(),
+        )
+    }
+
+    fn metadata_with_results(
+        functions: Vec<(&str, AnalysedType, AnalysedType)>,
+    ) -> Vec<AnalysedExport> {
+        vec![AnalysedExport::Instance(AnalysedInstance {
+            name: "golem:it/api".to_string(),
+            functions: functions
+                .into_iter()
+                .map(|(name, parameter_type, result_type)| AnalysedFunction {
+                    name: name.to_string(),
+                    parameters: vec![AnalysedFunctionParameter {
+                        name: "arg".to_string(),
+                        typ: parameter_type,
+                    }],
+                    results: vec![AnalysedFunctionResult {
+                        name: None,
+                        typ: result_type,
+                    }],
+                })
+                .collect(),
+        })]
+    }
+
+    #[test]
+    fn missing_and_changed_functions_are_reported_with_their_calls() {
+        let expr = Expr::from_text(
+            r#"
+              let worker = instance("cart");
+              let a = worker.get-item("a");
+              let b = worker.remove-item("b");
+              let c = worker.add-item("c");
+              "${a}-${b}-${c}"
+            "#,
+        )
+        .unwrap();
+
+        let existing = metadata(vec![
+            ("get-item", str()),
+            ("remove-item", str()),
+            ("add-item", str()),
+        ]);
+        let compiled = crate::compile(&expr, &existing).unwrap();
+        let worker_functions = compiled.worker_invoke_calls.unwrap();
+
+        assert!(find_breaking_changes(&expr, &worker_functions, &existing, &vec![]).is_empty());
+
+        let new = metadata(vec![("get-item", str()), ("add-item", u64())]);
+        let breaking_changes = find_breaking_changes(&expr, &worker_functions, &new, &vec![]);
+
+        let summary = breaking_changes
+            .iter()
+            .map(|change| {
+                (
+                    change.function.get_function_name(),
+                    matches!(change.kind, BreakingChangeKind::MissingFunction),
+                    change.exprs.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("add-item".to_string(), false, 1),
+                ("remove-item".to_string(), true, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn new_enum_and_variant_cases_are_not_reported() {
+        let expr = Expr::from_text(
+            r#"
+              let worker = instance("cart");
+              let state = worker.get-state(red);
+              let result = worker.set-state(state);
+              "${result}"
+            "#,
+        )
+        .unwrap();
+
+        let existing = metadata_with_results(vec![
+            (
+                "get-state",
+                r#enum(&["red", "green"]),
+                variant(vec![unit_case("idle"), case("busy", u64())]),
+            ),
+            (
+                "set-state",
+                variant(vec![unit_case("idle"), case("busy", u64())]),
+                str(),
+            ),
+        ]);
+        let compiled = crate::compile(&expr, &existing).unwrap();
+        let worker_functions = compiled.worker_invoke_calls.unwrap();
+
+        let new_state = variant(vec![
+            unit_case("idle"),
+            case("busy", u64()),
+            case("failed", str()),
+        ]);
+        let new = metadata_with_results(vec![
+            (
+                "get-state",
+                r#enum(&["red", "green", "blue"]),
+                new_state.clone(),
+            ),
+            ("set-state", new_state, str()),
+        ]);
+
+        assert!(find_breaking_changes(&expr, &worker_functions, &new, &vec![]).is_empty());
+    }
+}
@@ -14,8 +14,10 @@
 
 use crate::compiler::byte_code::internal::ExprState;
 use crate::compiler::ir::RibIR;
+use crate::rib_source_span::SourceSpan;
 use crate::{Expr, InferredExpr, InstructionId};
 use bincode::{Decode, Encode};
+use std::collections::VecDeque;
 
 #[derive(Debug, Clone, PartialEq, Encode, Decode)]
 pub struct RibByteCode {
@@ -25,8 +27,17 @@ pub struct RibByteCode {
 impl RibByteCode {
     // Convert expression to bytecode instructions
     pub fn from_expr(inferred_expr: &InferredExpr) -> Result<RibByteCode, String> {
+        Self::from_expr_with_source_map(inferred_expr).map(|(byte_code, _)| byte_code)
+    }
+
+    // Convert expression to bytecode instructions, along with the source spans
+    // the instructions were compiled from
+    pub fn from_expr_with_source_map(
+        inferred_expr: &InferredExpr,
+    ) -> Result<(RibByteCode, RibSourceMap), String> {
         let expr: &Expr = inferred_expr.get_expr();
         let mut instructions = Vec::new();
+        let mut instruction_spans = Vec::new();
         let mut stack: Vec<ExprState> = Vec::new();
         let mut instruction_id = InstructionId::init();
         stack.push(ExprState::from_expr(expr));
@@ -34,24 +45,91 @@ impl RibByteCode {
         while let Some(remaining) = stack.pop() {
             match remaining {
                 ExprState::Expr(expr) => {
+                    let processed = instructions.len();
+
                     internal::process_expr(
                         &expr,
                         &mut stack,
                         &mut instructions,
                         &mut instruction_id,
                     )?;
+
+                    let source_span = Some(expr.source_span()).filter(|span| !span.is_empty());
+                    instruction_spans
+                        .extend((processed..instructions.len()).map(|_| source_span.clone()));
                 }
 
                 ExprState::Instruction(instruction) => {
                     instructions.push(instruction);
+                    instruction_spans.push(None);
                 }
             }
         }
 
         // Use VecDeque to avoid reversal, but ok as well since this is compilation
-        Ok(RibByteCode {
-            instructions: instructions.into_iter().rev().collect(),
-        })
+        Ok((
+            RibByteCode {
+                instructions: instructions.into_iter().rev().collect(),
+            },
+            RibSourceMap {
+                instruction_spans: instruction_spans.into_iter().rev().collect(),
+                branches: RibBranch::from_expr(expr),
+            },
+        ))
+    }
+}
+
+// The source spans of the instructions of a byte code (none for the instructions
+// the compiler adds, such as jumps), and the branches of the script, which are the arms
+// of pattern matches and the branches of conditions.
+// Used to report the coverage of a script by its executions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibSourceMap {
+    pub instruction_spans: Vec<Option<SourceSpan>>,
+    pub branches: Vec<RibBranch>,
+}
+
+// A branch of a pattern match or a condition, where `block` identifies the
+// pattern match or the condition, and `branch` is the index of the arm within it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibBranch {
+    pub block: usize,
+    pub branch: usize,
+    pub source_span: SourceSpan,
+}
+
+impl RibBranch {
+    fn from_expr(expr: &Expr) -> Vec<RibBranch> {
+        let mut branches = vec![];
+        let mut block = 0;
+        let mut queue = VecDeque::new();
+        queue.push_back(expr);
+
+        while let Some(expr) = queue.pop_back() {
+            let arms = match expr {
+                Expr::PatternMatch { match_arms, .. } => match_arms
+                    .iter()
+                    .map(|match_arm| match_arm.arm_resolution_expr.source_span())
+                    .collect(),
+                Expr::Cond { lhs, rhs, .. } => vec![lhs.source_span(), rhs.source_span()],
+                _ => vec![],
+            };
+
+            if !arms.is_empty() {
+                for (branch, source_span) in arms.into_iter().enumerate() {
+                    branches.push(RibBranch {
+                        block,
+                        branch,
+                        source_span,
+                    });
+                }
+                block += 1;
+            }
+
+            expr.visit_children_bottom_up(&mut queue);
+        }
+
+        branches
     }
 }
 
@@ -93,7 +171,7 @@ mod internal {
     use crate::compiler::desugar::desugar_pattern_match;
     use crate::{
         AnalysedTypeWithUnit, DynamicParsedFunctionReference, Expr, FunctionReferenceType,
-        InferredType, InstructionId, RibIR, VariableId, WorkerNamePresence,
+        IdempotencyKeyPresence, InferredType, InstructionId, RibIR, VariableId, WorkerNamePresence,
     };
     use golem_wasm_ast::analysis::{AnalysedType, TypeFlags};
     use std::collections::HashSet;
@@ -353,6 +431,7 @@ mod internal {
                     CallType::Function {
                         function_name,
                         worker,
+                        idempotency_key,
                     } => {
                         let function_result_type = if inferred_type.is_unit() {
                             AnalysedTypeWithUnit::Unit
@@ -369,12 +448,24 @@ mod internal {
                             None => WorkerNamePresence::Present,
                         };
 
+                        let idempotency_key_presence = match idempotency_key {
+                            Some(_) => IdempotencyKeyPresence::Present,
+                            None => IdempotencyKeyPresence::Absent,
+                        };
+
                         instructions.push(RibIR::InvokeFunction(
                             worker_name,
+                            idempotency_key_presence,
                             args.len(),
                             function_result_type,
                         ));
 
+                        // To be pushed to interpreter stack after the arguments,
+                        // and popped right after the worker name
+                        if let Some(idempotency_key_expr) = idempotency_key {
+                            stack.push(ExprState::from_expr(idempotency_key_expr));
+                        }
+
                         if let Some(worker_expr) = worker {
                             stack.push(ExprState::from_expr(worker_expr));
                         }
@@ -518,6 +609,11 @@ mod internal {
                             convert_to_analysed_type(expr, inferred_type)?,
                         ));
                     }
+
+                    // Consumes the event name and the payload, and pushes nothing
+                    CallType::EventEmission => {
+                        instructions.push(RibIR::EmitEvent);
+                    }
                 }
             }
 
@@ -48,7 +48,12 @@ pub enum RibIR {
     Label(InstructionId),
     Deconstruct,
     CreateFunctionName(ParsedFunctionSite, FunctionReferenceType),
-    InvokeFunction(WorkerNamePresence, usize, AnalysedTypeWithUnit),
+    InvokeFunction(
+        WorkerNamePresence,
+        IdempotencyKeyPresence,
+        usize,
+        AnalysedTypeWithUnit,
+    ),
     PushVariant(String, AnalysedType), // There is no arg size since the type of each variant case is only 1 from beginning
     PushEnum(String, AnalysedType),
     Throw(String),
@@ -64,6 +69,7 @@ pub enum RibIR {
     AdvanceIterator,
     PushToSink,
     SinkToList,
+    EmitEvent,
 }
 
 #[derive(Debug, Clone, PartialEq, Encode, Decode)]
@@ -98,6 +104,39 @@ impl From<WorkerNamePresence> for golem_api_grpc::proto::golem::rib::WorkerNameP
     }
 }
 
+// Whether an explicit idempotency key (`with idempotency-key(..)`) is on the stack
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub enum IdempotencyKeyPresence {
+    Present,
+    Absent,
+}
+
+impl From<golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence> for IdempotencyKeyPresence {
+    fn from(value: golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence) -> Self {
+        match value {
+            golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence::IdempotencyKeyPresent => {
+                IdempotencyKeyPresence::Present
+            }
+            golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence::IdempotencyKeyAbsent => {
+                IdempotencyKeyPresence::Absent
+            }
+        }
+    }
+}
+
+impl From<IdempotencyKeyPresence> for golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence {
+    fn from(value: IdempotencyKeyPresence) -> Self {
+        match value {
+            IdempotencyKeyPresence::Present => {
+                golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence::IdempotencyKeyPresent
+            }
+            IdempotencyKeyPresence::Absent => {
+                golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence::IdempotencyKeyAbsent
+            }
+        }
+    }
+}
+
 impl RibIR {
     pub fn get_instruction_id(&self) -> Option<InstructionId> {
         match self {
@@ -180,15 +219,15 @@ impl InstructionId {
 #[cfg(feature = "protobuf")]
 mod protobuf {
     use crate::{
-        AnalysedTypeWithUnit, FunctionReferenceType, InstructionId, ParsedFunctionSite, RibIR,
-        WorkerNamePresence,
+        AnalysedTypeWithUnit, FunctionReferenceType, IdempotencyKeyPresence, InstructionId,
+        ParsedFunctionSite, RibIR, WorkerNamePresence,
     };
     use golem_api_grpc::proto::golem::rib::rib_ir::Instruction;
     use golem_api_grpc::proto::golem::rib::{
-        And, CallInstruction, ConcatInstruction, CreateFunctionNameInstruction, EqualTo, GetTag,
-        GreaterThan, GreaterThanOrEqualTo, IsEmpty, JumpInstruction, LessThan, LessThanOrEqualTo,
-        Negate, Or, PushListInstruction, PushNoneInstruction, PushTupleInstruction,
-        RibIr as ProtoRibIR,
+        And, CallInstruction, ConcatInstruction, CreateFunctionNameInstruction, EmitEvent, EqualTo,
+        GetTag, GreaterThan, GreaterThanOrEqualTo, IsEmpty, JumpInstruction, LessThan,
+        LessThanOrEqualTo, Negate, Or, PushListInstruction, PushNoneInstruction,
+        PushTupleInstruction, RibIr as ProtoRibIR,
     };
     use golem_wasm_ast::analysis::{AnalysedType, TypeStr};
     use golem_wasm_rpc::ValueAndType;
@@ -436,8 +475,22 @@ mod protobuf {
                         .map(|x| x.into())
                         .unwrap_or(WorkerNamePresence::Absent);
 
+                    let idempotency_key_presence = call_instruction
+                        .idempotency_key_presence
+                        .map(|x| {
+                            golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence::try_from(x)
+                                .map_err(|err| err.to_string())
+                        })
+                        .transpose()?;
+
+                    // Default is absent because old rib scripts don't have idempotency keys in it
+                    let idempotency_key_presence = idempotency_key_presence
+                        .map(|x| x.into())
+                        .unwrap_or(IdempotencyKeyPresence::Absent);
+
                     Ok(RibIR::InvokeFunction(
                         worker_name_presence,
+                        idempotency_key_presence,
                         call_instruction.argument_count as usize,
                         return_type,
                     ))
@@ -522,6 +575,7 @@ mod protobuf {
                 }
                 Instruction::AdvanceIterator(_) => Ok(RibIR::AdvanceIterator),
                 Instruction::SinkToList(_) => Ok(RibIR::SinkToList),
+                Instruction::EmitEvent(_) => Ok(RibIR::EmitEvent),
                 Instruction::PushToSink(_) => Ok(RibIR::PushToSink),
             }
         }
@@ -588,7 +642,12 @@ mod protobuf {
                 RibIR::Deconstruct => {
                     Instruction::Deconstruct((&AnalysedType::Str(TypeStr)).into())
                 } //TODO; remove type in deconstruct from protobuf
-                RibIR::InvokeFunction(worker_name_presence, arg_count, return_type) => {
+                RibIR::InvokeFunction(
+                    worker_name_presence,
+                    idempotency_key_presence,
+                    arg_count,
+                    return_type,
+                ) => {
                     let typ = match return_type {
                         AnalysedTypeWithUnit::Unit => None,
                         AnalysedTypeWithUnit::Type(analysed_type) => {
@@ -601,10 +660,14 @@ mod protobuf {
                     let worker_name_presence: golem_api_grpc::proto::golem::rib::WorkerNamePresence =
                         worker_name_presence.into();
 
+                    let idempotency_key_presence: golem_api_grpc::proto::golem::rib::IdempotencyKeyPresence =
+                        idempotency_key_presence.into();
+
                     Instruction::Call(CallInstruction {
                         argument_count: arg_count as u64,
                         return_type: typ,
                         worker_name_presence: Some(worker_name_presence.into()),
+                        idempotency_key_presence: Some(idempotency_key_presence.into()),
                     })
                 }
                 RibIR::PushVariant(name, return_type) => {
@@ -673,6 +736,7 @@ mod protobuf {
                 RibIR::SinkToList => {
                     Instruction::SinkToList(golem_api_grpc::proto::golem::rib::SinkToList {})
                 }
+                RibIR::EmitEvent => Instruction::EmitEvent(EmitEvent {}),
             };
 
             Ok(ProtoRibIR {
@@ -12,22 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use breaking_changes::*;
 pub use byte_code::*;
 pub use compiler_output::*;
 use golem_wasm_ast::analysis::AnalysedExport;
 pub use ir::*;
 use std::fmt::Display;
+pub use taint_tracking::*;
 pub use type_with_unit::*;
 pub use worker_functions_in_rib::*;
 
 use crate::rib_compilation_error::RibCompilationError;
+use crate::type_checker::check_static_worker_name;
 use crate::type_registry::FunctionTypeRegistry;
 use crate::{Expr, GlobalVariableTypeSpec, InferredExpr, RibInputTypeInfo, RibOutputTypeInfo};
 
+mod breaking_changes;
 mod byte_code;
 mod compiler_output;
 mod desugar;
 mod ir;
+mod taint_tracking;
 mod type_with_unit;
 mod worker_functions_in_rib;
 
@@ -38,6 +43,28 @@ pub fn compile(
     compile_with_restricted_global_variables(expr, export_metadata, None, &vec![])
 }
 
+// Compiles a Rib script against the global variables that the host registered up front
+// along with their types (Example: `request`, `env`, `auth`). Using any other global variable
+// fails the compilation, and the usages of the registered global variables are type checked
+// against their registered types.
+pub fn compile_with_global_input_types(
+    expr: &Expr,
+    export_metadata: &Vec<AnalysedExport>,
+    rib_input_types: &RibInputTypeInfo,
+) -> Result<CompilerOutput, RibError> {
+    let mut expr = expr.clone();
+    expr.bind_global_input_types(rib_input_types);
+
+    let allowed_global_variables = rib_input_types.types.keys().cloned().collect();
+
+    compile_with_restricted_global_variables(
+        &expr,
+        export_metadata,
+        Some(allowed_global_variables),
+        &vec![],
+    )
+}
+
 // Rib allows global input variables, however, we can choose to fail compilation
 // if they don't fall under a pre-defined set of global variables. If nothing is specified,
 // then it implies, any names can be a global variable in Rib. Example: `foo`.
@@ -51,6 +78,81 @@ pub fn compile_with_restricted_global_variables(
     allowed_global_variables: Option<Vec<String>>,
     global_variable_type_spec: &Vec<GlobalVariableTypeSpec>,
 ) -> Result<CompilerOutput, RibError> {
+    compile_with_worker_name_typing(
+        expr,
+        export_metadata,
+        allowed_global_variables,
+        global_variable_type_spec,
+        WorkerNameTyping::Inferred,
+    )
+}
+
+// How the worker names given to `instance()` are type checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WorkerNameTyping {
+    // Any expression inferred to be a string is a valid worker name.
+    // Example: `instance(request.path.user-id)`
+    #[default]
+    Inferred,
+    // Only worker names known at compile time are valid, that is string literals, interpolations
+    // of them, and variables bound to them. This rejects the scripts computing the worker to call
+    // from their inputs, such as the untrusted fields of a request.
+    // Example: `instance("cart-${region}")` where `let region = "eu";`
+    Strict,
+}
+
+// Same as `compile_with_restricted_global_variables`, with the typing of worker names chosen
+// by the host, such as strict typing for the routes where dynamic worker targeting is a concern
+pub fn compile_with_worker_name_typing(
+    expr: &Expr,
+    export_metadata: &Vec<AnalysedExport>,
+    allowed_global_variables: Option<Vec<String>>,
+    global_variable_type_spec: &Vec<GlobalVariableTypeSpec>,
+    worker_name_typing: WorkerNameTyping,
+) -> Result<CompilerOutput, RibError> {
+    for info in global_variable_type_spec {
+        if !info.variable_id.is_global() {
+            return Err(RibError::InternalError(format!(
+                "variable {} in the type spec is not a global variable",
+                info.variable_id
+            )));
+        }
+    }
+
+    let type_registry = FunctionTypeRegistry::from_export_metadata(export_metadata);
+    let inferred_expr = InferredExpr::from_expr(expr, &type_registry, global_variable_type_spec)?;
+
+    if worker_name_typing == WorkerNameTyping::Strict {
+        check_static_worker_name(inferred_expr.get_expr())
+            .map_err(|err| RibError::RibCompilationError(err.into()))?;
+    }
+
+    compile_inferred_expr(&inferred_expr, &type_registry, allowed_global_variables)
+        .map(|(compiler_output, _)| compiler_output)
+}
+
+// Same as `compile`, along with the source map of the byte code, to record the coverage
+// of the script when interpreting it with `interpret_with_coverage`
+pub fn compile_with_source_map(
+    expr: &Expr,
+    export_metadata: &Vec<AnalysedExport>,
+) -> Result<(CompilerOutput, RibSourceMap), RibError> {
+    let type_registry = FunctionTypeRegistry::from_export_metadata(export_metadata);
+    let inferred_expr = InferredExpr::from_expr(expr, &type_registry, &vec![])?;
+
+    compile_inferred_expr(&inferred_expr, &type_registry, None)
+}
+
+// Same as `compile_with_restricted_global_variables`, tracking the values derived from the
+// untrusted sources of the script into the sinks of `taint_tracking`. The tainted sinks are
+// returned along with the output, or fail the compilation if the policy is `TaintPolicy::Error`.
+pub fn compile_with_taint_tracking(
+    expr: &Expr,
+    export_metadata: &Vec<AnalysedExport>,
+    allowed_global_variables: Option<Vec<String>>,
+    global_variable_type_spec: &Vec<GlobalVariableTypeSpec>,
+    taint_tracking: &TaintTracking,
+) -> Result<(CompilerOutput, Vec<TaintedSink>), RibError> {
     for info in global_variable_type_spec {
         if !info.variable_id.is_global() {
             return Err(RibError::InternalError(format!(
@@ -63,12 +165,31 @@ pub fn compile_with_restricted_global_variables(
     let type_registry = FunctionTypeRegistry::from_export_metadata(export_metadata);
     let inferred_expr = InferredExpr::from_expr(expr, &type_registry, global_variable_type_spec)?;
 
+    let mut tainted_sinks = find_tainted_sinks(inferred_expr.get_expr(), taint_tracking);
+
+    if taint_tracking.policy == TaintPolicy::Error && !tainted_sinks.is_empty() {
+        return Err(RibError::RibCompilationError(
+            tainted_sinks.remove(0).into(),
+        ));
+    }
+
+    let (compiler_output, _) =
+        compile_inferred_expr(&inferred_expr, &type_registry, allowed_global_variables)?;
+
+    Ok((compiler_output, tainted_sinks))
+}
+
+fn compile_inferred_expr(
+    inferred_expr: &InferredExpr,
+    type_registry: &FunctionTypeRegistry,
+    allowed_global_variables: Option<Vec<String>>,
+) -> Result<(CompilerOutput, RibSourceMap), RibError> {
     let function_calls_identified =
-        WorkerFunctionsInRib::from_inferred_expr(&inferred_expr, &type_registry)?;
+        WorkerFunctionsInRib::from_inferred_expr(inferred_expr, type_registry)?;
 
-    let global_input_type_info = RibInputTypeInfo::from_expr(&inferred_expr)?;
+    let global_input_type_info = RibInputTypeInfo::from_expr(inferred_expr)?;
 
-    let output_type_info = RibOutputTypeInfo::from_expr(&inferred_expr)?;
+    let output_type_info = RibOutputTypeInfo::from_expr(inferred_expr)?;
 
     if let Some(allowed_global_variables) = &allowed_global_variables {
         let mut un_allowed_variables = vec![];
@@ -88,19 +209,22 @@ pub fn compile_with_restricted_global_variables(
         }
     }
 
-    let byte_code = RibByteCode::from_expr(&inferred_expr).map_err(|e| {
-        RibError::InternalError(format!(
-            "failed to convert inferred expression to byte code: {}",
-            e
-        ))
-    })?;
+    let (byte_code, source_map) =
+        RibByteCode::from_expr_with_source_map(inferred_expr).map_err(|e| {
+            RibError::InternalError(format!(
+                "failed to convert inferred expression to byte code: {}",
+                e
+            ))
+        })?;
 
-    Ok(CompilerOutput {
+    let compiler_output = CompilerOutput {
         worker_invoke_calls: function_calls_identified,
         byte_code,
         rib_input_type_info: global_input_type_info,
         rib_output_type_info: Some(output_type_info),
-    })
+    };
+
+    Ok((compiler_output, source_map))
 }
 
 #[derive(Debug, Clone)]
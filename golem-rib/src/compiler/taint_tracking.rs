@@ -0,0 +1,523 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::{CallType, InstanceCreationType};
+use crate::rib_compilation_error::RibCompilationError;
+use crate::{Expr, VariableId};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+
+// Taint tracking marks the values derived from untrusted inputs (the `sources`, which are global
+// variables such as `request`) and reports where they flow into sensitive `sinks`.
+// Example: `let user = request.headers.x-user; { headers: { x-user: user } }` echoes a
+// request header back, which is reported if the `headers` field of the output is a sink.
+// The `trusted` parts of the sources (Example: `request.path.user-id`) are validated before the
+// script runs, so the values derived from them are not tainted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaintTracking {
+    pub sources: Vec<String>,
+    pub sinks: Vec<TaintSink>,
+    pub policy: TaintPolicy,
+    pub trusted: Vec<String>,
+}
+
+impl TaintTracking {
+    pub fn new(sources: Vec<String>, sinks: Vec<TaintSink>, policy: TaintPolicy) -> Self {
+        TaintTracking {
+            sources,
+            sinks,
+            policy,
+            trusted: vec![],
+        }
+    }
+
+    pub fn with_trusted(mut self, trusted: Vec<String>) -> Self {
+        self.trusted = trusted;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TaintSink {
+    // The worker names of `instance()` and of the worker function calls
+    WorkerName,
+    // The value the script evaluates to, such as a worker name or a URL computed by the script
+    Output,
+    // A field of the record the script evaluates to, such as the `headers` of a response
+    OutputField(String),
+}
+
+impl Display for TaintSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaintSink::WorkerName => write!(f, "a worker name"),
+            TaintSink::Output => write!(f, "the output"),
+            TaintSink::OutputField(field) => write!(f, "the `{}` of the output", field),
+        }
+    }
+}
+
+// Whether the compilation only reports the tainted sinks, or fails on them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaintPolicy {
+    #[default]
+    Warn,
+    Error,
+}
+
+// A value derived from `source` (Example: `request.path.user-id`) flowing into `sink`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaintedSink {
+    pub sink: TaintSink,
+    pub source: String,
+    pub expr: Expr,
+}
+
+impl Display for TaintedSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let span = self.expr.source_span();
+
+        write!(
+            f,
+            "value derived from `{}` flows into {} at line {}, column {}: `{}`",
+            self.source,
+            self.sink,
+            span.start_line(),
+            span.start_column(),
+            self.expr
+        )
+    }
+}
+
+impl From<TaintedSink> for RibCompilationError {
+    fn from(value: TaintedSink) -> Self {
+        RibCompilationError {
+            cause: format!("untrusted value flows into {}", value.sink),
+            expr: value.expr,
+            immediate_parent: None,
+            additional_error_details: vec![format!("the value is derived from `{}`", value.source)],
+            help_messages: vec![
+                "derive the value from trusted inputs, or pass it to a worker function validating it".to_string(),
+            ],
+        }
+    }
+}
+
+// Finds the sinks of an inferred expression that untrusted values flow into.
+// Values derived through comparisons and arithmetic are not tainted, as booleans and numbers
+// cannot carry an injection, while any other expression is tainted if one of its parts is.
+pub fn find_tainted_sinks(expr: &Expr, taint_tracking: &TaintTracking) -> Vec<TaintedSink> {
+    let mut analysis =
+        internal::TaintAnalysis::new(expr, &taint_tracking.sources, &taint_tracking.trusted);
+    let mut tainted_sinks = vec![];
+
+    for sink in &taint_tracking.sinks {
+        let sink_exprs = match sink {
+            TaintSink::WorkerName => internal::collect_worker_names(expr),
+            TaintSink::Output => vec![expr],
+            TaintSink::OutputField(field) => analysis
+                .output_exprs(expr)
+                .into_iter()
+                .filter_map(|output| match output {
+                    Expr::Record { exprs, .. } => exprs
+                        .iter()
+                        .find(|(name, _)| name == field)
+                        .map(|(_, expr)| expr.as_ref()),
+                    _ => None,
+                })
+                .collect(),
+        };
+
+        for sink_expr in sink_exprs {
+            if let Some(source) = analysis.taint_of(sink_expr) {
+                let tainted_sink = TaintedSink {
+                    sink: sink.clone(),
+                    source,
+                    expr: sink_expr.clone(),
+                };
+
+                // The same worker name can be both in an instance and in its function calls
+                if !tainted_sinks.contains(&tainted_sink) {
+                    tainted_sinks.push(tainted_sink);
+                }
+            }
+        }
+    }
+
+    tainted_sinks
+}
+
+mod internal {
+    use super::*;
+
+    pub(crate) struct TaintAnalysis<'a> {
+        sources: &'a [String],
+        trusted: &'a [String],
+        // The expressions a local variable can be bound to
+        bindings: HashMap<&'a VariableId, Vec<&'a Expr>>,
+        taint_of_variables: HashMap<&'a VariableId, Option<String>>,
+        visiting: HashSet<&'a VariableId>,
+    }
+
+    impl<'a> TaintAnalysis<'a> {
+        pub(crate) fn new(expr: &'a Expr, sources: &'a [String], trusted: &'a [String]) -> Self {
+            TaintAnalysis {
+                sources,
+                trusted,
+                bindings: collect_bindings(expr),
+                taint_of_variables: HashMap::new(),
+                visiting: HashSet::new(),
+            }
+        }
+
+        // The source of the first untrusted value found in the expression
+        pub(crate) fn taint_of(&mut self, expr: &'a Expr) -> Option<String> {
+            match expr {
+                Expr::Identifier { variable_id, .. } => self.taint_of_variable(variable_id),
+                Expr::SelectField { expr, field, .. } => self
+                    .taint_of(expr)
+                    .map(|source| format!("{}.{}", source, field))
+                    .filter(|source| !self.trusted.contains(source)),
+                Expr::SelectIndex { expr, index, .. } => self
+                    .taint_of(expr)
+                    .map(|source| format!("{}[{}]", source, index)),
+                Expr::Literal { .. }
+                | Expr::Number { .. }
+                | Expr::Boolean { .. }
+                | Expr::Flags { .. }
+                | Expr::Throw { .. }
+                | Expr::Not { .. }
+                | Expr::GreaterThan { .. }
+                | Expr::GreaterThanOrEqualTo { .. }
+                | Expr::LessThan { .. }
+                | Expr::LessThanOrEqualTo { .. }
+                | Expr::EqualTo { .. }
+                | Expr::And { .. }
+                | Expr::Or { .. }
+                | Expr::Plus { .. }
+                | Expr::Minus { .. }
+                | Expr::Multiply { .. }
+                | Expr::Divide { .. } => None,
+                Expr::Let { .. } => None,
+                Expr::Call {
+                    call_type: CallType::InstanceCreation(_),
+                    ..
+                } => None,
+                Expr::Call { args, .. } | Expr::InvokeMethodLazy { args, .. } => {
+                    self.first_taint(args.iter())
+                }
+                Expr::Sequence { exprs, .. }
+                | Expr::Tuple { exprs, .. }
+                | Expr::Concat { exprs, .. } => self.first_taint(exprs.iter()),
+                Expr::Record { exprs, .. } => {
+                    self.first_taint(exprs.iter().map(|(_, expr)| expr.as_ref()))
+                }
+                Expr::ExprBlock { exprs, .. } => exprs.last().and_then(|expr| self.taint_of(expr)),
+                Expr::Cond { lhs, rhs, .. } => {
+                    self.first_taint([lhs.as_ref(), rhs.as_ref()].into_iter())
+                }
+                Expr::PatternMatch { match_arms, .. } => self.first_taint(
+                    match_arms
+                        .iter()
+                        .map(|match_arm| match_arm.arm_resolution_expr.as_ref()),
+                ),
+                Expr::Option { expr, .. } => expr.as_ref().and_then(|expr| self.taint_of(expr)),
+                Expr::Result { expr, .. } => match expr {
+                    Ok(expr) | Err(expr) => self.taint_of(expr),
+                },
+                Expr::Unwrap { expr, .. } | Expr::GetTag { expr, .. } => self.taint_of(expr),
+                Expr::ListComprehension { yield_expr, .. } => self.taint_of(yield_expr),
+                Expr::ListReduce {
+                    yield_expr,
+                    init_value_expr,
+                    ..
+                } => self.first_taint([yield_expr.as_ref(), init_value_expr.as_ref()].into_iter()),
+            }
+        }
+
+        // The expressions a script can evaluate to, following the variables and branches
+        pub(crate) fn output_exprs(&self, expr: &'a Expr) -> Vec<&'a Expr> {
+            let mut outputs = vec![];
+            let mut visited = HashSet::new();
+            let mut queue = VecDeque::new();
+            queue.push_back(expr);
+
+            while let Some(expr) = queue.pop_front() {
+                match expr {
+                    Expr::ExprBlock { exprs, .. } => queue.extend(exprs.last()),
+                    Expr::Cond { lhs, rhs, .. } => {
+                        queue.push_back(lhs.as_ref());
+                        queue.push_back(rhs.as_ref());
+                    }
+                    Expr::PatternMatch { match_arms, .. } => queue.extend(
+                        match_arms
+                            .iter()
+                            .map(|match_arm| match_arm.arm_resolution_expr.as_ref()),
+                    ),
+                    Expr::Identifier { variable_id, .. } if !variable_id.is_global() => {
+                        if visited.insert(variable_id) {
+                            if let Some(bound_exprs) = self.bindings.get(variable_id) {
+                                queue.extend(bound_exprs.iter().copied());
+                            }
+                        }
+                    }
+                    expr => outputs.push(expr),
+                }
+            }
+
+            outputs
+        }
+
+        fn taint_of_variable(&mut self, variable_id: &'a VariableId) -> Option<String> {
+            if variable_id.is_global() {
+                let name = variable_id.name();
+                return self.sources.contains(&name).then_some(name);
+            }
+
+            if let Some(taint) = self.taint_of_variables.get(variable_id) {
+                return taint.clone();
+            }
+
+            if !self.visiting.insert(variable_id) {
+                return None;
+            }
+
+            let bound_exprs = self.bindings.get(variable_id).cloned().unwrap_or_default();
+            let taint = self.first_taint(bound_exprs.into_iter());
+
+            self.visiting.remove(variable_id);
+            self.taint_of_variables.insert(variable_id, taint.clone());
+            taint
+        }
+
+        fn first_taint(&mut self, exprs: impl Iterator<Item = &'a Expr>) -> Option<String> {
+            for expr in exprs {
+                if let Some(taint) = self.taint_of(expr) {
+                    return Some(taint);
+                }
+            }
+            None
+        }
+    }
+
+    fn collect_bindings(expr: &Expr) -> HashMap<&VariableId, Vec<&Expr>> {
+        let mut bindings: HashMap<&VariableId, Vec<&Expr>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(expr);
+
+        while let Some(expr) = queue.pop_back() {
+            match expr {
+                Expr::Let {
+                    variable_id, expr, ..
+                } => bindings.entry(variable_id).or_default().push(expr.as_ref()),
+                Expr::ListComprehension {
+                    iterated_variable,
+                    iterable_expr,
+                    ..
+                } => bindings
+                    .entry(iterated_variable)
+                    .or_default()
+                    .push(iterable_expr.as_ref()),
+                Expr::ListReduce {
+                    reduce_variable,
+                    iterated_variable,
+                    iterable_expr,
+                    init_value_expr,
+                    yield_expr,
+                    ..
+                } => {
+                    bindings
+                        .entry(iterated_variable)
+                        .or_default()
+                        .push(iterable_expr.as_ref());
+                    let reduced = bindings.entry(reduce_variable).or_default();
+                    reduced.push(init_value_expr.as_ref());
+                    reduced.push(yield_expr.as_ref());
+                }
+                // The variables of a match arm are bound to parts of the matched value
+                Expr::PatternMatch {
+                    predicate,
+                    match_arms,
+                    ..
+                } => {
+                    for match_arm in match_arms {
+                        for literal in match_arm.arm_pattern.get_expr_literals() {
+                            if let Expr::Identifier { variable_id, .. } = literal {
+                                bindings
+                                    .entry(variable_id)
+                                    .or_default()
+                                    .push(predicate.as_ref());
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            expr.visit_children_bottom_up(&mut queue);
+        }
+
+        bindings
+    }
+
+    pub(crate) fn collect_worker_names(expr: &Expr) -> Vec<&Expr> {
+        let mut worker_names = vec![];
+        let mut queue = VecDeque::new();
+        queue.push_back(expr);
+
+        while let Some(expr) = queue.pop_back() {
+            if let Expr::Call { call_type, .. } = expr {
+                match call_type {
+                    CallType::InstanceCreation(InstanceCreationType::Worker { worker_name })
+                    | CallType::InstanceCreation(InstanceCreationType::Resource {
+                        worker_name,
+                        ..
+                    })
+                    | CallType::Function {
+                        worker: worker_name,
+                        ..
+                    } => worker_names.extend(worker_name.as_deref()),
+                    CallType::VariantConstructor(_) => {}
+                    CallType::EnumConstructor(_) => {}
+                    CallType::EventEmission => {}
+                }
+            }
+
+            expr.visit_children_bottom_up(&mut queue);
+        }
+
+        worker_names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compile_with_taint_tracking, Expr, TaintPolicy, TaintSink, TaintTracking, TaintedSink,
+    };
+    use golem_wasm_ast::analysis::analysed_type::str;
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+    };
+    use test_r::test;
+
+    fn compile(rib: &str, policy: TaintPolicy) -> Result<Vec<TaintedSink>, String> {
+        compile_trusting(rib, policy, vec![])
+    }
+
+    fn compile_trusting(
+        rib: &str,
+        policy: TaintPolicy,
+        trusted: Vec<String>,
+    ) -> Result<Vec<TaintedSink>, String> {
+        let metadata = vec![AnalysedExport::Function(AnalysedFunction {
+            name: "foo".to_string(),
+            parameters: vec![AnalysedFunctionParameter {
+                name: "arg1".to_string(),
+                typ: str(),
+            }],
+            results: vec![AnalysedFunctionResult {
+                name: None,
+                typ: str(),
+            }],
+        })];
+
+        let expr = Expr::from_text(rib).unwrap();
+
+        let taint_tracking = TaintTracking::new(
+            vec!["request".to_string()],
+            vec![
+                TaintSink::WorkerName,
+                TaintSink::OutputField("headers".to_string()),
+            ],
+            policy,
+        )
+        .with_trusted(trusted);
+
+        compile_with_taint_tracking(&expr, &metadata, None, &vec![], &taint_tracking)
+            .map(|(_, tainted_sinks)| tainted_sinks)
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn test_taint_tracking() {
+        let untainted = r#"
+          let region = "eu";
+          let worker = instance("cart-${region}");
+          let result = worker.foo(request.body.name);
+          let is-admin = request.headers.role == "admin";
+          { status: 200, body: result, headers: { x-admin: is-admin } }
+        "#;
+
+        assert_eq!(compile(untainted, TaintPolicy::Error), Ok(vec![]));
+
+        let tainted = r#"
+          let user-id: string = request.path.user-id;
+          let worker = instance("cart-${user-id}");
+          let result = worker.foo("bar");
+          let origin: string = request.headers.origin;
+          let response = { status: 200, body: result, headers: { x-origin: origin } };
+          response
+        "#;
+
+        let tainted_sinks = compile(tainted, TaintPolicy::Warn).unwrap();
+        let sinks = tainted_sinks
+            .iter()
+            .map(|tainted_sink| (tainted_sink.sink.clone(), tainted_sink.source.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sinks,
+            vec![
+                (TaintSink::WorkerName, "request.path.user-id".to_string()),
+                (
+                    TaintSink::OutputField("headers".to_string()),
+                    "request.headers.origin".to_string()
+                )
+            ]
+        );
+
+        let error = compile(tainted, TaintPolicy::Error).unwrap_err();
+
+        assert!(error.contains("untrusted value flows into a worker name"));
+        assert!(error.contains("the value is derived from `request.path.user-id`"));
+    }
+
+    #[test]
+    fn test_taint_tracking_trusted_sources() {
+        let rib = r#"
+          let user-id: string = request.path.user-id;
+          let worker = instance("cart-${user-id}");
+          let result = worker.foo(request.path.order-id);
+          let order-id: string = request.path.order-id;
+          { status: 200, body: result, headers: { x-order-id: order-id } }
+        "#;
+
+        let trusted = vec!["request.path.user-id".to_string()];
+
+        let tainted_sinks = compile_trusting(rib, TaintPolicy::Warn, trusted).unwrap();
+        let sinks = tainted_sinks
+            .iter()
+            .map(|tainted_sink| (tainted_sink.sink.clone(), tainted_sink.source.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sinks,
+            vec![(
+                TaintSink::OutputField("headers".to_string()),
+                "request.path.order-id".to_string()
+            )]
+        );
+    }
+}
@@ -21,7 +21,7 @@ use crate::rib_source_span::SourceSpan;
 use crate::type_registry::FunctionTypeRegistry;
 use crate::{
     from_string, text, type_checker, type_inference, DynamicParsedFunctionName,
-    GlobalVariableTypeSpec, InferredType, ParsedFunctionName, VariableId,
+    GlobalVariableTypeSpec, InferredType, ParsedFunctionName, RibInputTypeInfo, VariableId,
 };
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
 use combine::parser::char::spaces;
@@ -231,6 +231,7 @@ pub enum Expr {
         method: String,
         generic_type_parameter: Option<GenericTypeParameter>,
         args: Vec<Expr>,
+        idempotency_key: Option<Box<Expr>>,
         inferred_type: InferredType,
         source_span: SourceSpan,
     },
@@ -500,6 +501,7 @@ impl Expr {
             call_type: CallType::Function {
                 function_name: dynamic_parsed_fn_name,
                 worker: worker_name.map(Box::new),
+                idempotency_key: None,
             },
             generic_type_parameter,
             args,
@@ -533,11 +535,60 @@ impl Expr {
             method: function_name,
             generic_type_parameter,
             args,
+            idempotency_key: None,
             inferred_type: InferredType::Unknown,
             source_span: SourceSpan::default(),
         }
     }
 
+    // Attaches an explicit idempotency key to a worker function invocation,
+    // as in `worker.f(x) with idempotency-key(request.headers.x-request-id)`.
+    // Expressions that are not worker function invocations are returned unchanged.
+    pub fn with_idempotency_key(self, key: Expr) -> Self {
+        match self {
+            Expr::InvokeMethodLazy {
+                lhs,
+                method,
+                generic_type_parameter,
+                args,
+                inferred_type,
+                source_span,
+                ..
+            } => Expr::InvokeMethodLazy {
+                lhs,
+                method,
+                generic_type_parameter,
+                args,
+                idempotency_key: Some(Box::new(key)),
+                inferred_type,
+                source_span,
+            },
+            Expr::Call {
+                call_type:
+                    CallType::Function {
+                        worker,
+                        function_name,
+                        ..
+                    },
+                generic_type_parameter,
+                args,
+                inferred_type,
+                source_span,
+            } => Expr::Call {
+                call_type: CallType::Function {
+                    worker,
+                    function_name,
+                    idempotency_key: Some(Box::new(key)),
+                },
+                generic_type_parameter,
+                args,
+                inferred_type,
+                source_span,
+            },
+            expr => expr,
+        }
+    }
+
     pub fn concat(expressions: Vec<Expr>) -> Self {
         Expr::Concat {
             exprs: expressions,
@@ -756,6 +807,10 @@ impl Expr {
         type_inference::bind_global_variable_types(self, type_spec)
     }
 
+    pub fn bind_global_input_types(&mut self, rib_input_types: &RibInputTypeInfo) {
+        type_inference::bind_global_input_types(self, rib_input_types)
+    }
+
     pub fn bind_instance_types(&mut self) {
         type_inference::bind_instance_types(self)
     }
@@ -1027,6 +1082,7 @@ impl Expr {
         type_spec: &Vec<GlobalVariableTypeSpec>,
     ) -> Result<(), RibCompilationError> {
         self.identify_instance_creation(function_type_registry)?;
+        self.identify_event_emission();
         *self = self.bind_global_variable_types(type_spec)?;
         self.bind_type_annotations();
         self.bind_variables_of_list_comprehension();
@@ -1090,6 +1146,10 @@ impl Expr {
         type_inference::identify_instance_creation(self, function_type_registry)
     }
 
+    pub fn identify_event_emission(&mut self) {
+        type_inference::identify_event_emission(self)
+    }
+
     pub fn infer_function_call_types(
         &mut self,
         function_type_registry: &FunctionTypeRegistry,
@@ -1954,6 +2014,11 @@ impl TryFrom<golem_api_grpc::proto::golem::rib::Expr> for Expr {
                     .into_iter()
                     .map(Expr::try_from)
                     .collect::<Result<Vec<_>, _>>()?;
+                let idempotency_key = lazy_invoke
+                    .idempotency_key
+                    .map(|key| Expr::try_from(*key))
+                    .transpose()?
+                    .map(Box::new);
 
                 Expr::InvokeMethodLazy {
                     lhs,
@@ -1961,6 +2026,7 @@ impl TryFrom<golem_api_grpc::proto::golem::rib::Expr> for Expr {
                     generic_type_parameter: generic_type_parameter
                         .map(|value| GenericTypeParameter { value }),
                     args,
+                    idempotency_key,
                     inferred_type: InferredType::Unknown,
                     source_span: SourceSpan::default(),
                 }
@@ -2373,6 +2439,7 @@ mod protobuf {
                     method,
                     generic_type_parameter,
                     args,
+                    idempotency_key,
                     ..
                 } => Some(
                     golem_api_grpc::proto::golem::rib::expr::Expr::LazyInvokeMethod(Box::new(
@@ -2381,6 +2448,7 @@ mod protobuf {
                             method,
                             generic_type_parameter: generic_type_parameter.map(|t| t.value),
                             args: args.into_iter().map(|expr| expr.into()).collect(),
+                            idempotency_key: idempotency_key.map(|key| Box::new((*key).into())),
                         },
                     )),
                 ),
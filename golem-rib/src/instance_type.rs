@@ -2,8 +2,8 @@ use crate::parser::{PackageName, TypeParameter};
 use crate::rib_compilation_error::RibCompilationError;
 use crate::type_parameter::InterfaceName;
 use crate::{
-    DynamicParsedFunctionName, Expr, FunctionCallError, FunctionTypeRegistry, InferredType,
-    RegistryKey, RegistryValue,
+    DynamicParsedFunctionName, DynamicParsedFunctionReference, Expr, FunctionCallError,
+    FunctionTypeRegistry, InferredType, ParsedFunctionSite, RegistryKey, RegistryValue, SemVer,
 };
 use golem_api_grpc::proto::golem::rib::instance_type::Instance;
 use golem_api_grpc::proto::golem::rib::{
@@ -197,147 +197,71 @@ impl InstanceType {
         type_parameter: Option<TypeParameter>,
     ) -> Result<Function, FunctionCallError> {
         match type_parameter {
-            Some(tp) => match tp {
-                TypeParameter::Interface(iface) => {
-                    let interfaces = self
-                        .function_dict()
-                        .map
-                        .into_iter()
-                        .filter(|(f, _)| f.interface_name() == Some(iface.clone()))
-                        .collect::<Vec<_>>();
-
-                    if interfaces.is_empty() {
-                        return Err(FunctionCallError::InvalidFunctionCall {
-                            function_name: method_name.to_string(),
-                            expr,
-                            message: format!("Interface '{}' not found", iface),
-                        });
-                    }
+            Some(tp) => {
+                let functions_in_scope = self
+                    .function_dict()
+                    .map
+                    .into_iter()
+                    .filter(|(f, _)| is_in_scope_of_type_parameter(f, &tp))
+                    .collect::<Vec<_>>();
 
-                    let functions = interfaces
-                        .into_iter()
-                        .filter(|(f, _)| f.name() == method_name)
-                        .collect::<Vec<_>>();
-
-                    if functions.is_empty() {
-                        return Err(FunctionCallError::InvalidFunctionCall {
-                            function_name: method_name.to_string(),
-                            expr,
-                            message: format!(
-                                "Function '{}' not found in interface '{}'",
-                                method_name, iface
-                            ),
-                        });
-                    }
+                if functions_in_scope.is_empty() {
+                    let message = match &tp {
+                        TypeParameter::Interface(iface) => {
+                            format!("Interface '{}' not found", iface)
+                        }
+                        TypeParameter::PackageName(pkg) => format!("package '{}' not found", pkg),
+                        TypeParameter::FullyQualifiedInterface(fq_iface) => format!(
+                            "function '{}' not found in interface '{}'",
+                            method_name, fq_iface
+                        ),
+                    };
 
-                    // There is only 1 interface, and there cannot exist any more conflicts
-                    // with an interface
-                    if functions.len() == 1 {
-                        let (fqfn, ftype) = &functions[0];
-                        Ok(Function {
-                            function_name: fqfn.clone(),
-                            function_type: ftype.clone(),
-                        })
-                    } else {
-                        search_function_in_instance(self, method_name).map_err(|err| {
-                            FunctionCallError::InvalidFunctionCall {
-                                function_name: method_name.to_string(),
-                                expr,
-                                message: err,
-                            }
-                        })
-                    }
+                    return Err(FunctionCallError::InvalidFunctionCall {
+                        function_name: method_name.to_string(),
+                        expr,
+                        message,
+                    });
                 }
 
-                TypeParameter::PackageName(pkg) => {
-                    let packages = self
-                        .function_dict()
-                        .map
-                        .into_iter()
-                        .filter(|(f, _)| f.package_name() == Some(pkg.clone()))
-                        .collect::<Vec<_>>();
-
-                    if packages.is_empty() {
-                        return Err(FunctionCallError::InvalidFunctionCall {
-                            function_name: method_name.to_string(),
-                            expr,
-                            message: format!("package '{}' not found", pkg),
-                        });
-                    }
+                let functions = functions_in_scope
+                    .into_iter()
+                    .filter(|(f, _)| f.name() == method_name)
+                    .collect::<Vec<_>>();
 
-                    let functions = packages
-                        .into_iter()
-                        .filter(|(f, _)| f.name() == method_name)
-                        .collect::<Vec<_>>();
-
-                    if functions.is_empty() {
-                        return Err(FunctionCallError::InvalidFunctionCall {
-                            function_name: method_name.to_string(),
-                            expr,
-                            message: format!(
-                                "function '{}' not found in package '{}'",
-                                method_name, pkg
-                            ),
-                        });
-                    }
+                if functions.is_empty() {
+                    let message = match &tp {
+                        TypeParameter::Interface(iface) => format!(
+                            "Function '{}' not found in interface '{}'",
+                            method_name, iface
+                        ),
+                        TypeParameter::PackageName(pkg) => {
+                            format!("function '{}' not found in package '{}'", method_name, pkg)
+                        }
+                        TypeParameter::FullyQualifiedInterface(fq_iface) => format!(
+                            "function '{}' not found in interface '{}'",
+                            method_name, fq_iface
+                        ),
+                    };
 
-                    if functions.len() == 1 {
-                        let (fqfn, ftype) = &functions[0];
-                        Ok(Function {
-                            function_name: fqfn.clone(),
-                            function_type: ftype.clone(),
-                        })
-                    } else {
-                        search_function_in_instance(self, method_name).map_err(|err| {
-                            FunctionCallError::InvalidFunctionCall {
-                                function_name: method_name.to_string(),
-                                expr,
-                                message: err,
-                            }
-                        })
-                    }
+                    return Err(FunctionCallError::InvalidFunctionCall {
+                        function_name: method_name.to_string(),
+                        expr,
+                        message,
+                    });
                 }
 
-                TypeParameter::FullyQualifiedInterface(fq_iface) => {
-                    let functions = self
-                        .function_dict()
-                        .map
-                        .into_iter()
-                        .filter(|(f, _)| {
-                            f.package_name() == Some(fq_iface.package_name.clone())
-                                && f.interface_name() == Some(fq_iface.interface_name.clone())
-                                && f.name() == method_name
-                        })
-                        .collect::<Vec<_>>();
-
-                    if functions.is_empty() {
-                        return Err(FunctionCallError::InvalidFunctionCall {
-                            function_name: method_name.to_string(),
-                            expr,
-                            message: format!(
-                                "function '{}' not found in interface '{}'",
-                                method_name, fq_iface
-                            ),
-                        });
-                    }
-
-                    if functions.len() == 1 {
-                        let (fqfn, ftype) = &functions[0];
-                        Ok(Function {
-                            function_name: fqfn.clone(),
-                            function_type: ftype.clone(),
-                        })
-                    } else {
-                        search_function_in_instance(self, method_name).map_err(|err| {
-                            FunctionCallError::InvalidFunctionCall {
-                                function_name: method_name.to_string(),
-                                expr,
-                                message: err,
-                            }
-                        })
+                // The type parameter may still leave more than one candidate, such as
+                // when only the interface name is given while multiple versions of it exist.
+                // The candidates are then reported, but only the ones within the type parameter
+                search_function(functions, method_name).map_err(|err| {
+                    FunctionCallError::InvalidFunctionCall {
+                        function_name: method_name.to_string(),
+                        expr,
+                        message: err,
                     }
-                }
-            },
+                })
+            }
             None => search_function_in_instance(self, method_name).map_err(|err| {
                 FunctionCallError::InvalidFunctionCall {
                     function_name: method_name.to_string(),
@@ -386,14 +310,14 @@ impl InstanceType {
                 worker_name: worker_name.map(Box::new),
                 functions_global: function_dict,
             }),
-            Some(type_parameter) => match type_parameter {
+            Some(type_parameter) => match type_parameter.clone() {
                 TypeParameter::Interface(interface_name) => {
                     let function_dict = FunctionDictionary {
                         map: function_dict
                             .map
                             .into_iter()
-                            .filter(|(f, _)| f.interface_name() == Some(interface_name.clone()))
-                            .collect::<Vec<_>>(),
+                            .filter(|(f, _)| is_in_scope_of_type_parameter(f, &type_parameter))
+                            .collect(),
                     };
 
                     Ok(InstanceType::Interface {
@@ -407,7 +331,7 @@ impl InstanceType {
                         map: function_dict
                             .map
                             .into_iter()
-                            .filter(|(f, _)| f.package_name() == Some(package_name.clone()))
+                            .filter(|(f, _)| is_in_scope_of_type_parameter(f, &type_parameter))
                             .collect(),
                     };
 
@@ -422,11 +346,7 @@ impl InstanceType {
                         map: function_dict
                             .map
                             .into_iter()
-                            .filter(|(f, _)| {
-                                f.package_name() == Some(fq_interface.package_name.clone())
-                                    && f.interface_name()
-                                        == Some(fq_interface.interface_name.clone())
-                            })
+                            .filter(|(f, _)| is_in_scope_of_type_parameter(f, &type_parameter))
                             .collect(),
                     };
 
@@ -565,26 +485,49 @@ fn resolve_function_name(
                 resource_name,
             },
         )),
-        None => match get_resource_method_name(function_name) {
-            Ok(Some((constructor, method))) => {
-                Ok(FunctionName::ResourceMethod(FullyQualifiedResourceMethod {
+        None => match get_resource_static_method_name(function_name) {
+            Ok(Some((resource, method))) => Ok(FunctionName::ResourceStaticMethod(
+                FullyQualifiedResourceMethod {
                     package_name,
                     interface_name,
-                    resource_name: constructor,
+                    resource_name: resource,
                     method_name: method,
-                }))
-            }
-            Ok(None) => Ok(FunctionName::Function(FullyQualifiedFunctionName {
+                },
+            )),
+            Ok(None) => resolve_function_or_resource_method_name(
                 package_name,
                 interface_name,
-                function_name: function_name.to_string(),
-            })),
-
+                function_name,
+            ),
             Err(e) => Err(format!("Invalid function call. {}", e)),
         },
     }
 }
 
+fn resolve_function_or_resource_method_name(
+    package_name: Option<PackageName>,
+    interface_name: Option<InterfaceName>,
+    function_name: &str,
+) -> Result<FunctionName, String> {
+    match get_resource_method_name(function_name) {
+        Ok(Some((constructor, method))) => {
+            Ok(FunctionName::ResourceMethod(FullyQualifiedResourceMethod {
+                package_name,
+                interface_name,
+                resource_name: constructor,
+                method_name: method,
+            }))
+        }
+        Ok(None) => Ok(FunctionName::Function(FullyQualifiedFunctionName {
+            package_name,
+            interface_name,
+            function_name: function_name.to_string(),
+        })),
+
+        Err(e) => Err(format!("Invalid function call. {}", e)),
+    }
+}
+
 fn get_resource_name(function_name: &str) -> Option<String> {
     if function_name.starts_with("[constructor]") {
         Some(
@@ -597,6 +540,24 @@ fn get_resource_name(function_name: &str) -> Option<String> {
     }
 }
 
+// Static functions of a resource, such as `[static]cart.create`
+fn get_resource_static_method_name(
+    function_name: &str,
+) -> Result<Option<(String, String)>, String> {
+    match function_name.strip_prefix("[static]") {
+        Some(resource_and_method) => match resource_and_method.split_once('.') {
+            Some((resource, method)) if !resource.is_empty() && !method.is_empty() => {
+                Ok(Some((resource.to_string(), method.to_string())))
+            }
+            _ => Err(format!(
+                "Invalid resource static method name: {}",
+                function_name
+            )),
+        },
+        None => Ok(None),
+    }
+}
+
 fn get_resource_method_name(function_name: &str) -> Result<Option<(String, String)>, String> {
     if function_name.starts_with("[method]") {
         let constructor_and_method = function_name.trim_start_matches("[method]").to_string();
@@ -623,6 +584,7 @@ pub enum FunctionName {
     Function(FullyQualifiedFunctionName),
     ResourceConstructor(FullyQualifiedResourceConstructor),
     ResourceMethod(FullyQualifiedResourceMethod),
+    ResourceStaticMethod(FullyQualifiedResourceMethod),
 }
 
 impl FunctionName {
@@ -631,6 +593,9 @@ impl FunctionName {
             FunctionName::Function(fqfn) => fqfn.interface_name.clone(),
             FunctionName::ResourceConstructor(fqfn) => fqfn.interface_name.clone(),
             FunctionName::ResourceMethod(resource_method) => resource_method.interface_name.clone(),
+            FunctionName::ResourceStaticMethod(static_method) => {
+                static_method.interface_name.clone()
+            }
         }
     }
 
@@ -639,14 +604,27 @@ impl FunctionName {
             FunctionName::Function(fqfn) => fqfn.package_name.clone(),
             FunctionName::ResourceConstructor(fqfn) => fqfn.package_name.clone(),
             FunctionName::ResourceMethod(fqfn) => fqfn.package_name.clone(),
+            FunctionName::ResourceStaticMethod(fqfn) => fqfn.package_name.clone(),
         }
     }
 
+    // The version of the interface the function belongs to,
+    // which may be part of either the interface or the package name
+    pub fn version(&self) -> Option<String> {
+        self.interface_name()
+            .and_then(|iface| iface.version)
+            .or(self.package_name().and_then(|pkg| pkg.version))
+    }
+
     pub fn name(&self) -> String {
         match self {
             FunctionName::Function(fqfn) => fqfn.function_name.to_string(),
             FunctionName::ResourceConstructor(fqfn) => fqfn.resource_name.to_string(),
             FunctionName::ResourceMethod(fqfn) => fqfn.method_name.to_string(),
+            // Static functions are invoked through the resource, as in `worker.cart.create()`
+            FunctionName::ResourceStaticMethod(fqfn) => {
+                format!("{}.{}", fqfn.resource_name, fqfn.method_name)
+            }
         }
     }
 }
@@ -717,6 +695,44 @@ impl FullyQualifiedResourceMethod {
         DynamicParsedFunctionName::parse(dynamic_parsed_str)
     }
 
+    // Static functions don't need an instance of the resource, and are therefore
+    // addressed directly by the function site rather than through a resource constructor
+    pub fn dynamic_parsed_static_function_name(&self) -> Result<DynamicParsedFunctionName, String> {
+        let site = match (&self.package_name, &self.interface_name) {
+            (Some(package_name), Some(interface_name)) => {
+                let version = interface_name
+                    .version
+                    .as_ref()
+                    .or(package_name.version.as_ref())
+                    .map(|version| {
+                        semver::Version::parse(version)
+                            .map(SemVer)
+                            .map_err(|err| format!("Invalid version {}: {}", version, err))
+                    })
+                    .transpose()?;
+
+                ParsedFunctionSite::PackagedInterface {
+                    namespace: package_name.namespace.clone(),
+                    package: package_name.package_name.clone(),
+                    interface: interface_name.name.clone(),
+                    version,
+                }
+            }
+            (None, Some(interface_name)) => ParsedFunctionSite::Interface {
+                name: interface_name.name.clone(),
+            },
+            (_, None) => ParsedFunctionSite::Global,
+        };
+
+        Ok(DynamicParsedFunctionName {
+            site,
+            function: DynamicParsedFunctionReference::RawResourceStaticMethod {
+                resource: self.resource_name.clone(),
+                method: self.method_name.clone(),
+            },
+        })
+    }
+
     pub fn method_name(&self) -> &String {
         &self.method_name
     }
@@ -753,6 +769,15 @@ fn search_function_in_instance(
         .filter(|(f, _)| f.name() == *function_name)
         .collect();
 
+    search_function(functions, function_name)
+}
+
+// Picks the function among the candidates having the same name,
+// or reports the type parameters that would disambiguate them
+fn search_function(
+    functions: Vec<(FunctionName, FunctionType)>,
+    function_name: &str,
+) -> Result<Function, String> {
     if functions.is_empty() {
         return Err(format!("function '{}' not found", function_name));
     }
@@ -776,6 +801,43 @@ fn search_function_in_instance(
     }
 }
 
+// A type parameter without a version refers to any version of the package or interface.
+// Component metadata usually carries the version of an interface on the interface name
+// (`golem:it/api@1.0.0`), while it can be given on the package in a type parameter
+// (`golem:it@1.0.0/api`), hence only the effective version of both is compared
+fn is_in_scope_of_type_parameter(
+    function_name: &FunctionName,
+    type_parameter: &TypeParameter,
+) -> bool {
+    let package_matches = match type_parameter.get_package_name() {
+        Some(expected) => function_name.package_name().is_some_and(|actual| {
+            actual.namespace == expected.namespace && actual.package_name == expected.package_name
+        }),
+        None => true,
+    };
+
+    let interface_matches = match type_parameter.get_interface_name() {
+        Some(expected) => function_name
+            .interface_name()
+            .is_some_and(|actual| actual.name == expected.name),
+        None => true,
+    };
+
+    let expected_version = type_parameter
+        .get_interface_name()
+        .and_then(|iface| iface.version)
+        .or(type_parameter
+            .get_package_name()
+            .and_then(|pkg| pkg.version));
+
+    let version_matches = match expected_version {
+        Some(expected) => function_name.version() == Some(expected),
+        None => true,
+    };
+
+    package_matches && interface_matches && version_matches
+}
+
 fn search_function_in_single_package(
     interfaces: HashSet<Option<InterfaceName>>,
     functions: Vec<(FunctionName, FunctionType)>,
@@ -790,7 +852,7 @@ fn search_function_in_single_package(
     } else {
         let mut interfaces = interfaces
             .into_iter()
-            .filter_map(|iface| iface.map(|i| i.name))
+            .filter_map(|iface| iface.map(|i| i.to_string()))
             .collect::<Vec<_>>();
 
         interfaces.sort();
@@ -820,7 +882,7 @@ fn search_function_in_multiple_packages(
             pkg.map(|p| {
                 let mut interface_list = interfaces
                     .into_iter()
-                    .filter_map(|iface| iface.map(|i| i.name))
+                    .filter_map(|iface| iface.map(|i| i.to_string()))
                     .collect::<Vec<_>>();
 
                 interface_list.sort();
@@ -975,6 +1037,9 @@ impl TryFrom<ProtoFunctionName> for FunctionName {
             function_name_type::FunctionName::ResourceMethod(fqfn) => {
                 Ok(FunctionName::ResourceMethod(TryFrom::try_from(fqfn)?))
             }
+            function_name_type::FunctionName::ResourceStaticMethod(fqfn) => {
+                Ok(FunctionName::ResourceStaticMethod(TryFrom::try_from(fqfn)?))
+            }
         }
     }
 }
@@ -1120,3 +1185,113 @@ impl From<FullyQualifiedResourceConstructor> for ProtoFullyQualifiedResourceCons
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn test_resolve_resource_static_function_name() {
+        let package_name = PackageName {
+            namespace: "golem".to_string(),
+            package_name: "it".to_string(),
+            version: None,
+        };
+
+        let interface_name = InterfaceName {
+            name: "api".to_string(),
+            version: None,
+        };
+
+        let function_name = resolve_function_name(
+            Some(package_name),
+            Some(interface_name),
+            "[static]cart.create",
+        )
+        .unwrap();
+
+        assert_eq!(function_name.name(), "cart.create");
+
+        let FunctionName::ResourceStaticMethod(static_method) = function_name else {
+            panic!(
+                "Expected a resource static method, found {:?}",
+                function_name
+            )
+        };
+
+        assert_eq!(
+            static_method.dynamic_parsed_static_function_name(),
+            DynamicParsedFunctionName::parse("golem:it/api.{[static]cart.create}")
+        );
+
+        assert!(resolve_function_name(None, None, "[static]cart").is_err());
+    }
+
+    #[test]
+    fn test_type_parameter_disambiguates_function_versions() {
+        let function_type = FunctionType {
+            parameter_types: vec![],
+            return_type: vec![],
+        };
+
+        let function = |interface: &str| {
+            let type_parameter = TypeParameter::from_str(interface).unwrap();
+            let function_name = resolve_function_name(
+                type_parameter.get_package_name(),
+                type_parameter.get_interface_name(),
+                "get-cart",
+            )
+            .unwrap();
+
+            (function_name, function_type.clone())
+        };
+
+        let instance_type = InstanceType::Global {
+            worker_name: None,
+            functions_global: FunctionDictionary {
+                map: vec![
+                    function("golem:it/api@1.0.0"),
+                    function("golem:it/api@2.0.0"),
+                    function("golem:other/api"),
+                ],
+            },
+        };
+
+        let get_function = |type_parameter: Option<&str>| {
+            instance_type
+                .get_function(
+                    Expr::identifier_global("worker", None),
+                    "get-cart",
+                    type_parameter.map(|tp| TypeParameter::from_str(tp).unwrap()),
+                )
+                .map(|function| function.function_name.version())
+                .map_err(|err| match err {
+                    FunctionCallError::InvalidFunctionCall { message, .. } => message,
+                    _ => panic!("Unexpected function call error"),
+                })
+        };
+
+        assert_eq!(
+            get_function(Some("golem:it/api@2.0.0")),
+            Ok(Some("2.0.0".to_string()))
+        );
+        assert_eq!(
+            get_function(Some("golem:it@1.0.0/api")),
+            Ok(Some("1.0.0".to_string()))
+        );
+        assert_eq!(get_function(Some("golem:other")), Ok(None));
+        assert_eq!(
+            get_function(Some("golem:it/api")),
+            Err("multiple interfaces contain function 'get-cart'. specify an interface name as type parameter from: api@1.0.0, api@2.0.0".to_string())
+        );
+        assert_eq!(
+            get_function(Some("api")),
+            Err("function 'get-cart' exists in multiple packages. specify a package name as type parameter from: golem:it (interfaces: api@1.0.0, api@2.0.0), golem:other (interfaces: api)".to_string())
+        );
+        assert_eq!(
+            get_function(Some("golem:it/api@3.0.0")),
+            Err("function 'get-cart' not found in interface 'golem:it/api@3.0.0'".to_string())
+        );
+    }
+}
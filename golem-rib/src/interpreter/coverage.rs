@@ -0,0 +1,197 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RibSourceMap;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+// The executions of the instructions of a byte code, accumulated over the runs of the
+// interpreter, and reported against the source spans of the script they were compiled from.
+// Example: compile a script with `compile_with_source_map`, run it with `interpret_with_coverage`
+// for every test input, and write `coverage.report().to_lcov("cart.rib")` as `lcov.info`.
+#[derive(Debug, Clone)]
+pub struct RibCoverage {
+    source_map: RibSourceMap,
+    hits: Vec<u64>,
+}
+
+impl RibCoverage {
+    pub fn new(source_map: RibSourceMap) -> Self {
+        let hits = vec![0; source_map.instruction_spans.len()];
+        RibCoverage { source_map, hits }
+    }
+
+    pub(crate) fn record(&mut self, instruction_index: usize) {
+        if let Some(hits) = self.hits.get_mut(instruction_index) {
+            *hits += 1;
+        }
+    }
+
+    pub fn report(&self) -> RibCoverageReport {
+        let executed = self
+            .source_map
+            .instruction_spans
+            .iter()
+            .zip(self.hits.iter())
+            .filter_map(|(span, hits)| span.as_ref().map(|span| (span, *hits)))
+            .collect::<Vec<_>>();
+
+        // The hits of a line are the hits of the most executed instruction on the line
+        let mut lines = BTreeMap::new();
+        for (span, hits) in &executed {
+            let line_hits = lines.entry(span.start_line() as u32).or_insert(0);
+            *line_hits = (*line_hits).max(*hits);
+        }
+
+        // A branch is taken if any of the instructions within the branch are executed
+        let branches = self
+            .source_map
+            .branches
+            .iter()
+            .map(|branch| RibBranchCoverage {
+                line: branch.source_span.start_line() as u32,
+                block: branch.block,
+                branch: branch.branch,
+                hits: executed
+                    .iter()
+                    .filter(|(span, _)| branch.source_span.contains(span))
+                    .map(|(_, hits)| *hits)
+                    .max()
+                    .unwrap_or(0),
+            })
+            .collect();
+
+        RibCoverageReport { lines, branches }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibCoverageReport {
+    // Hits per line, for the lines with instructions
+    pub lines: BTreeMap<u32, u64>,
+    pub branches: Vec<RibBranchCoverage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibBranchCoverage {
+    pub line: u32,
+    pub block: usize,
+    pub branch: usize,
+    pub hits: u64,
+}
+
+impl RibCoverageReport {
+    // Coverage report in the lcov tracefile format, for the script at `source_file`
+    pub fn to_lcov(&self, source_file: &str) -> String {
+        let mut lcov = String::new();
+
+        let _ = writeln!(lcov, "TN:");
+        let _ = writeln!(lcov, "SF:{}", source_file);
+
+        for branch in &self.branches {
+            // `-` marks the branches of a pattern match or a condition that is never evaluated
+            let block_evaluated = self
+                .branches
+                .iter()
+                .any(|other| other.block == branch.block && other.hits > 0);
+
+            let taken = if block_evaluated {
+                branch.hits.to_string()
+            } else {
+                "-".to_string()
+            };
+
+            let _ = writeln!(
+                lcov,
+                "BRDA:{},{},{},{}",
+                branch.line, branch.block, branch.branch, taken
+            );
+        }
+
+        let _ = writeln!(lcov, "BRF:{}", self.branches.len());
+        let _ = writeln!(
+            lcov,
+            "BRH:{}",
+            self.branches
+                .iter()
+                .filter(|branch| branch.hits > 0)
+                .count()
+        );
+
+        for (line, hits) in &self.lines {
+            let _ = writeln!(lcov, "DA:{},{}", line, hits);
+        }
+
+        let _ = writeln!(lcov, "LF:{}", self.lines.len());
+        let _ = writeln!(
+            lcov,
+            "LH:{}",
+            self.lines.values().filter(|hits| **hits > 0).count()
+        );
+        let _ = writeln!(lcov, "end_of_record");
+
+        lcov
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interpreter::rib_interpreter::Interpreter;
+    use crate::{compile_with_source_map, Expr, RibCoverage};
+    use test_r::test;
+
+    #[test]
+    async fn test_coverage_of_match_arms_and_conditions() {
+        let expr = r#"
+          let x: option<u64> = some(1);
+          let y = match x {
+            some(v) => "found",
+            none => "not found"
+          };
+          if y == "found" then "yes" else "no"
+        "#;
+
+        let expr = Expr::from_text(expr).unwrap();
+        let (compiled, source_map) = compile_with_source_map(&expr, &vec![]).unwrap();
+
+        let mut coverage = RibCoverage::new(source_map);
+        let mut interpreter = Interpreter::default();
+
+        interpreter
+            .run_with_coverage(compiled.byte_code.clone(), &mut coverage)
+            .await
+            .unwrap();
+
+        let report = coverage.report();
+
+        let mut branches = report
+            .branches
+            .iter()
+            .map(|branch| (branch.line, branch.hits))
+            .collect::<Vec<_>>();
+        branches.sort();
+
+        assert_eq!(branches, vec![(4, 1), (5, 0), (7, 0), (7, 1)]);
+        assert_eq!(report.lines.get(&2), Some(&1));
+        assert_eq!(report.lines.get(&5), Some(&0));
+
+        let lcov = report.to_lcov("test.rib");
+
+        assert!(lcov.starts_with("TN:\nSF:test.rib\n"));
+        assert!(lcov.contains("BRF:4\nBRH:2\n"));
+        assert!(lcov.contains("DA:4,1\n"));
+        assert!(lcov.contains("DA:5,0\n"));
+        assert!(lcov.ends_with("end_of_record\n"));
+    }
+}
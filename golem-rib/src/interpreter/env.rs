@@ -14,7 +14,8 @@
 
 use crate::interpreter::interpreter_stack_value::RibInterpreterStackValue;
 use crate::{
-    EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName, RibFunctionInvoke, RibInput, VariableId,
+    EvaluatedEventName, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey,
+    EvaluatedWorkerName, RibFunctionInvoke, RibInput, VariableId,
 };
 use golem_wasm_rpc::ValueAndType;
 use std::collections::HashMap;
@@ -49,16 +50,28 @@ impl InterpreterEnv {
         worker_name: Option<String>,
         function_name: String,
         args: Vec<ValueAndType>,
+        idempotency_key: Option<String>,
     ) -> Result<ValueAndType, String> {
         self.call_worker_function_async
             .invoke(
                 worker_name.map(EvaluatedWorkerName),
                 EvaluatedFqFn(function_name),
                 EvaluatedFnArgs(args),
+                idempotency_key.map(EvaluatedIdempotencyKey),
             )
             .await
     }
 
+    pub async fn emit_event_async(
+        &self,
+        event_name: String,
+        payload: ValueAndType,
+    ) -> Result<(), String> {
+        self.call_worker_function_async
+            .emit(EvaluatedEventName(event_name), payload)
+            .await
+    }
+
     pub fn from_input(env: &RibInput) -> Self {
         let env = env
             .input
@@ -115,7 +128,7 @@ impl EnvironmentKey {
 
 mod internal {
     use crate::interpreter::env::RibFunctionInvoke;
-    use crate::{EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName};
+    use crate::{EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey, EvaluatedWorkerName};
     use async_trait::async_trait;
     use golem_wasm_ast::analysis::analysed_type::tuple;
     use golem_wasm_rpc::{Value, ValueAndType};
@@ -129,6 +142,7 @@ mod internal {
             _worker_name: Option<EvaluatedWorkerName>,
             _function_name: EvaluatedFqFn,
             _args: EvaluatedFnArgs,
+            _idempotency_key: Option<EvaluatedIdempotencyKey>,
         ) -> Result<ValueAndType, String> {
             Ok(ValueAndType::new(Value::Tuple(vec![]), tuple(vec![])))
         }
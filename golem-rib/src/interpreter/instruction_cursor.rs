@@ -37,6 +37,11 @@ impl RibByteCodeCursor {
         }
     }
 
+    // The position of the next instruction
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
     pub fn move_to(&mut self, move_to: &InstructionId) -> Option<()> {
         for (index, current_instruction) in self.byte_code.instructions.iter().enumerate() {
             if let Some(label_id) = current_instruction.get_instruction_id() {
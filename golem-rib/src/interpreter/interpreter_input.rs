@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::RibInputTypeInfo;
 use golem_wasm_rpc::ValueAndType;
 use std::collections::HashMap;
+use std::fmt::Display;
 
 // Acts as the structure to hold the global input values
 #[derive(Debug, Default, Clone)]
@@ -26,9 +28,285 @@ impl RibInput {
         RibInput { input }
     }
 
+    // A builder that validates the values of global variables against
+    // the types registered up front (Example: `compiler_output.rib_input_type_info`)
+    pub fn builder(rib_input_types: &RibInputTypeInfo) -> RibInputBuilder {
+        RibInputBuilder {
+            rib_input_types: rib_input_types.clone(),
+            input: HashMap::new(),
+        }
+    }
+
     pub fn merge(&self, other: RibInput) -> RibInput {
         let mut cloned = self.clone();
         cloned.input.extend(other.input);
         cloned
     }
 }
+
+pub struct RibInputBuilder {
+    rib_input_types: RibInputTypeInfo,
+    input: HashMap<String, ValueAndType>,
+}
+
+impl RibInputBuilder {
+    pub fn with_global(
+        mut self,
+        name: impl Into<String>,
+        value: ValueAndType,
+    ) -> Result<Self, RibInputError> {
+        let name = name.into();
+
+        let expected = self
+            .rib_input_types
+            .get(&name)
+            .ok_or(RibInputError::UnknownGlobalVariable(name.clone()))?;
+
+        internal::check_type(&name, expected, &value.typ)?;
+
+        self.input.insert(name, value);
+        Ok(self)
+    }
+
+    // Fails if any of the registered global variables is not given a value
+    pub fn build(self) -> Result<RibInput, RibInputError> {
+        let mut missing = self
+            .rib_input_types
+            .types
+            .keys()
+            .filter(|name| !self.input.contains_key(*name))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(RibInputError::MissingGlobalVariables(missing));
+        }
+
+        Ok(RibInput { input: self.input })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RibInputError {
+    UnknownGlobalVariable(String),
+    MissingGlobalVariables(Vec<String>),
+    TypeMismatch {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+    MissingField {
+        path: String,
+        field: String,
+    },
+}
+
+impl Display for RibInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RibInputError::UnknownGlobalVariable(name) => {
+                write!(f, "unknown global variable `{}`", name)
+            }
+            RibInputError::MissingGlobalVariables(names) => {
+                write!(f, "missing global variables: {}", names.join(", "))
+            }
+            RibInputError::TypeMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "type mismatch at `{}`. expected {}, found {}",
+                path, expected, actual
+            ),
+            RibInputError::MissingField { path, field } => {
+                write!(f, "missing field `{}` in `{}`", field, path)
+            }
+        }
+    }
+}
+
+mod internal {
+    use crate::interpreter::interpreter_input::RibInputError;
+    use crate::TypeName;
+    use golem_wasm_ast::analysis::AnalysedType;
+
+    // Compares the expected and actual types structurally,
+    // to report the exact path where they differ
+    pub(crate) fn check_type(
+        path: &str,
+        expected: &AnalysedType,
+        actual: &AnalysedType,
+    ) -> Result<(), RibInputError> {
+        match (expected, actual) {
+            (AnalysedType::Record(expected_record), AnalysedType::Record(actual_record)) => {
+                for expected_field in &expected_record.fields {
+                    let actual_field = actual_record
+                        .fields
+                        .iter()
+                        .find(|field| field.name == expected_field.name)
+                        .ok_or(RibInputError::MissingField {
+                            path: path.to_string(),
+                            field: expected_field.name.clone(),
+                        })?;
+
+                    check_type(
+                        &format!("{}.{}", path, expected_field.name),
+                        &expected_field.typ,
+                        &actual_field.typ,
+                    )?;
+                }
+
+                if actual_record.fields.len() != expected_record.fields.len() {
+                    return Err(type_mismatch(path, expected, actual));
+                }
+
+                Ok(())
+            }
+            (AnalysedType::List(expected_list), AnalysedType::List(actual_list)) => check_type(
+                &format!("{}[]", path),
+                &expected_list.inner,
+                &actual_list.inner,
+            ),
+            (AnalysedType::Option(expected_option), AnalysedType::Option(actual_option)) => {
+                check_type(path, &expected_option.inner, &actual_option.inner)
+            }
+            (AnalysedType::Tuple(expected_tuple), AnalysedType::Tuple(actual_tuple))
+                if expected_tuple.items.len() == actual_tuple.items.len() =>
+            {
+                for (index, (expected_item, actual_item)) in expected_tuple
+                    .items
+                    .iter()
+                    .zip(actual_tuple.items.iter())
+                    .enumerate()
+                {
+                    check_type(&format!("{}[{}]", path, index), expected_item, actual_item)?;
+                }
+
+                Ok(())
+            }
+            (expected, actual) if expected == actual => Ok(()),
+            (expected, actual) => Err(type_mismatch(path, expected, actual)),
+        }
+    }
+
+    fn type_mismatch(path: &str, expected: &AnalysedType, actual: &AnalysedType) -> RibInputError {
+        RibInputError::TypeMismatch {
+            path: path.to_string(),
+            expected: type_name(expected),
+            actual: type_name(actual),
+        }
+    }
+
+    fn type_name(analysed_type: &AnalysedType) -> String {
+        TypeName::try_from(analysed_type.clone())
+            .map(|type_name| type_name.to_string())
+            .unwrap_or_else(|_| format!("{:?}", analysed_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::{RibInput, RibInputError, RibInputTypeInfo};
+    use golem_wasm_ast::analysis::analysed_type::{field, list, record, str, u64};
+    use golem_wasm_rpc::{IntoValueAndType, Value, ValueAndType};
+
+    fn request_type_info() -> RibInputTypeInfo {
+        RibInputTypeInfo::empty().with_global(
+            "request",
+            record(vec![
+                field("path", record(vec![field("user-id", u64())])),
+                field("tags", list(str())),
+            ]),
+        )
+    }
+
+    #[test]
+    fn test_rib_input_builder_valid() {
+        let request = ValueAndType::new(
+            Value::Record(vec![
+                Value::Record(vec![Value::U64(1)]),
+                Value::List(vec![Value::String("foo".to_string())]),
+            ]),
+            record(vec![
+                field("path", record(vec![field("user-id", u64())])),
+                field("tags", list(str())),
+            ]),
+        );
+
+        let result = RibInput::builder(&request_type_info())
+            .with_global("request", request.clone())
+            .and_then(|builder| builder.build())
+            .unwrap();
+
+        assert_eq!(result.input.get("request"), Some(&request));
+    }
+
+    #[test]
+    fn test_rib_input_builder_type_mismatch_path() {
+        let request = ValueAndType::new(
+            Value::Record(vec![
+                Value::Record(vec![Value::String("1".to_string())]),
+                Value::List(vec![]),
+            ]),
+            record(vec![
+                field("path", record(vec![field("user-id", str())])),
+                field("tags", list(str())),
+            ]),
+        );
+
+        let result = RibInput::builder(&request_type_info())
+            .with_global("request", request)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            result.to_string(),
+            "type mismatch at `request.path.user-id`. expected u64, found string"
+        );
+    }
+
+    #[test]
+    fn test_rib_input_builder_missing_field() {
+        let request = ValueAndType::new(
+            Value::Record(vec![Value::Record(vec![Value::U64(1)])]),
+            record(vec![field("path", record(vec![field("user-id", u64())]))]),
+        );
+
+        let result = RibInput::builder(&request_type_info())
+            .with_global("request", request)
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            result,
+            RibInputError::MissingField {
+                path: "request".to_string(),
+                field: "tags".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_rib_input_builder_unknown_and_missing_globals() {
+        let unknown = RibInput::builder(&request_type_info())
+            .with_global("env", "foo".into_value_and_type())
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            unknown,
+            RibInputError::UnknownGlobalVariable("env".to_string())
+        );
+
+        let missing = RibInput::builder(&request_type_info()).build().unwrap_err();
+
+        assert_eq!(
+            missing,
+            RibInputError::MissingGlobalVariables(vec!["request".to_string()])
+        );
+    }
+}
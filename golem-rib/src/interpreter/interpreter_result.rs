@@ -15,7 +15,8 @@
 use crate::interpreter::interpreter_stack_value::RibInterpreterStackValue;
 use crate::{GetLiteralValue, LiteralValue};
 use golem_wasm_ast::analysis::AnalysedType;
-use golem_wasm_rpc::{Value, ValueAndType};
+use golem_wasm_rpc::{pretty_print_value_and_type, PrettyPrintOptions, Value, ValueAndType};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug)]
 pub enum RibResult {
@@ -74,3 +75,17 @@ impl RibResult {
         })
     }
 }
+
+// Type-annotated, for echoing the results of scripts
+impl Display for RibResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RibResult::Unit => write!(f, "()"),
+            RibResult::Val(value) => write!(
+                f,
+                "{}",
+                pretty_print_value_and_type(value, &PrettyPrintOptions::default())
+            ),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey, EvaluatedWorkerName, RibFunctionInvoke,
+};
+use async_trait::async_trait;
+use golem_wasm_ast::analysis::analysed_type::tuple;
+use golem_wasm_rpc::{Value, ValueAndType};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+// Answers the worker function invocations of Rib scripts from fixtures, and records them,
+// so that embedders of the interpreter can test their integration without a running cluster.
+//
+// Example:
+//
+//   let mock = Arc::new(MockWorkerInvoke::new());
+//   mock.add_fixture(
+//       MockFixture::returning("add-item", "added".into_value_and_type())
+//           .with_args(vec![ArgMatcher::eq("apple".into_value_and_type())]),
+//   );
+//
+//   let result = rib::interpret(&byte_code, &rib_input, mock.clone()).await?;
+//   assert_eq!(mock.calls_of("add-item").len(), 1);
+#[derive(Default)]
+pub struct MockWorkerInvoke {
+    fixtures: Mutex<Vec<MockFixture>>,
+    calls: Mutex<Vec<RecordedCall>>,
+}
+
+impl MockWorkerInvoke {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // An invocation is answered by the last added fixture matching it,
+    // so that tests can override the fixtures shared by them
+    pub fn add_fixture(&self, fixture: MockFixture) {
+        self.fixtures.lock().unwrap().push(fixture);
+    }
+
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    // The recorded invocations of a function, named as in the fixtures
+    pub fn calls_of(&self, function_name: &str) -> Vec<RecordedCall> {
+        self.calls()
+            .into_iter()
+            .filter(|call| function_matches(function_name, &call.function_name))
+            .collect()
+    }
+
+    pub fn clear_calls(&self) {
+        self.calls.lock().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl RibFunctionInvoke for MockWorkerInvoke {
+    async fn invoke(
+        &self,
+        worker_name: Option<EvaluatedWorkerName>,
+        function_name: EvaluatedFqFn,
+        args: EvaluatedFnArgs,
+        idempotency_key: Option<EvaluatedIdempotencyKey>,
+    ) -> Result<ValueAndType, String> {
+        let call = RecordedCall {
+            worker_name: worker_name.map(|name| name.0),
+            function_name: function_name.0,
+            args: args.0,
+            idempotency_key: idempotency_key.map(|key| key.0),
+        };
+
+        let result = self
+            .fixtures
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|fixture| fixture.matches(&call))
+            .map(|fixture| fixture.result.clone())
+            .unwrap_or_else(|| {
+                Err(format!(
+                    "No fixture matches the invocation of {}",
+                    call.function_name
+                ))
+            });
+
+        self.calls.lock().unwrap().push(call);
+
+        result
+    }
+}
+
+// An invocation of a worker function as received by the mock
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedCall {
+    pub worker_name: Option<String>,
+    pub function_name: String,
+    pub args: Vec<ValueAndType>,
+    pub idempotency_key: Option<String>,
+}
+
+// The result of the invocations of a function, optionally restricted to a worker and to the
+// arguments matching the given matchers. The function is named either fully qualified
+// (Example: `golem:it/api.{add-item}`) or by its name only (Example: `add-item`).
+#[derive(Debug, Clone)]
+pub struct MockFixture {
+    function_name: String,
+    worker_name: Option<String>,
+    args: Option<Vec<ArgMatcher>>,
+    result: Result<ValueAndType, String>,
+}
+
+impl MockFixture {
+    pub fn returning(function_name: impl Into<String>, value: ValueAndType) -> Self {
+        Self::new(
+            function_name,
+            Ok(ValueAndType::new(
+                Value::Tuple(vec![value.value]),
+                tuple(vec![value.typ]),
+            )),
+        )
+    }
+
+    // For functions without a return value
+    pub fn returning_unit(function_name: impl Into<String>) -> Self {
+        Self::new(
+            function_name,
+            Ok(ValueAndType::new(Value::Tuple(vec![]), tuple(vec![]))),
+        )
+    }
+
+    pub fn failing(function_name: impl Into<String>, error: impl Into<String>) -> Self {
+        Self::new(function_name, Err(error.into()))
+    }
+
+    fn new(function_name: impl Into<String>, result: Result<ValueAndType, String>) -> Self {
+        MockFixture {
+            function_name: function_name.into(),
+            worker_name: None,
+            args: None,
+            result,
+        }
+    }
+
+    pub fn with_worker_name(mut self, worker_name: impl Into<String>) -> Self {
+        self.worker_name = Some(worker_name.into());
+        self
+    }
+
+    // One matcher per argument. Without them, the fixture matches any arguments.
+    pub fn with_args(mut self, args: Vec<ArgMatcher>) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    fn matches(&self, call: &RecordedCall) -> bool {
+        function_matches(&self.function_name, &call.function_name)
+            && self
+                .worker_name
+                .as_ref()
+                .is_none_or(|worker_name| call.worker_name.as_ref() == Some(worker_name))
+            && self.args.as_ref().is_none_or(|matchers| {
+                matchers.len() == call.args.len()
+                    && matchers
+                        .iter()
+                        .zip(&call.args)
+                        .all(|(matcher, arg)| matcher.matches(arg))
+            })
+    }
+}
+
+#[derive(Clone)]
+pub enum ArgMatcher {
+    Any,
+    Eq(Value),
+    Predicate(Arc<dyn Fn(&ValueAndType) -> bool + Send + Sync>),
+}
+
+impl ArgMatcher {
+    pub fn eq(value: ValueAndType) -> Self {
+        ArgMatcher::Eq(value.value)
+    }
+
+    pub fn predicate(predicate: impl Fn(&ValueAndType) -> bool + Send + Sync + 'static) -> Self {
+        ArgMatcher::Predicate(Arc::new(predicate))
+    }
+
+    pub fn matches(&self, arg: &ValueAndType) -> bool {
+        match self {
+            ArgMatcher::Any => true,
+            ArgMatcher::Eq(value) => arg.value == *value,
+            ArgMatcher::Predicate(predicate) => predicate(arg),
+        }
+    }
+}
+
+impl Debug for ArgMatcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgMatcher::Any => write!(f, "Any"),
+            ArgMatcher::Eq(value) => write!(f, "Eq({:?})", value),
+            ArgMatcher::Predicate(_) => write!(f, "Predicate"),
+        }
+    }
+}
+
+fn function_matches(name: &str, function_name: &str) -> bool {
+    function_name == name || function_name.ends_with(&format!(".{{{}}}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compile, interpret, Expr, RibInput};
+    use golem_wasm_ast::analysis::analysed_type::str;
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedInstance,
+    };
+    use golem_wasm_rpc::IntoValueAndType;
+    use test_r::test;
+
+    fn metadata() -> Vec<AnalysedExport> {
+        vec![AnalysedExport::Instance(AnalysedInstance {
+            name: "golem:it/api".to_string(),
+            functions: vec![AnalysedFunction {
+                name: "add-item".to_string(),
+                parameters: vec![AnalysedFunctionParameter {
+                    name: "item".to_string(),
+                    typ: str(),
+                }],
+                results: vec![AnalysedFunctionResult {
+                    name: None,
+                    typ: str(),
+                }],
+            }],
+        })]
+    }
+
+    #[test]
+    async fn invocations_are_answered_from_fixtures_and_recorded() {
+        let expr = Expr::from_text(
+            r#"
+              let worker = instance("cart");
+              worker.add-item("apple")
+            "#,
+        )
+        .unwrap();
+        let compiled = compile(&expr, &metadata()).unwrap();
+
+        let mock = Arc::new(MockWorkerInvoke::new());
+        mock.add_fixture(MockFixture::failing("add-item", "out of stock"));
+        mock.add_fixture(
+            MockFixture::returning("golem:it/api.{add-item}", "added".into_value_and_type())
+                .with_worker_name("cart")
+                .with_args(vec![ArgMatcher::eq("apple".into_value_and_type())]),
+        );
+
+        let result = interpret(&compiled.byte_code, &RibInput::default(), mock.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(result.get_val(), Some("added".into_value_and_type()));
+        assert_eq!(
+            mock.calls_of("add-item"),
+            vec![RecordedCall {
+                worker_name: Some("cart".to_string()),
+                function_name: "golem:it/api.{add-item}".to_string(),
+                args: vec!["apple".into_value_and_type()],
+                idempotency_key: None,
+            }]
+        );
+
+        mock.add_fixture(
+            MockFixture::failing("add-item", "unavailable")
+                .with_args(vec![ArgMatcher::predicate(|_| true)]),
+        );
+
+        let result = interpret(&compiled.byte_code, &RibInput::default(), mock.clone()).await;
+
+        assert!(result.is_err_and(|err| err.contains("unavailable")));
+        assert_eq!(mock.calls().len(), 2);
+    }
+}
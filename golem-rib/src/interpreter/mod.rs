@@ -12,21 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use coverage::*;
 pub use interpreter_input::*;
 pub use interpreter_result::*;
 pub use literal::*;
+pub use mock_worker_invoke::*;
 pub use rib_function_invoke::*;
 use std::sync::Arc;
 
 use crate::interpreter::rib_interpreter::Interpreter;
 use crate::RibByteCode;
 
+mod coverage;
 mod env;
 mod instruction_cursor;
 mod interpreter_input;
 mod interpreter_result;
 mod interpreter_stack_value;
 mod literal;
+mod mock_worker_invoke;
 mod rib_function_invoke;
 mod rib_interpreter;
 mod stack;
@@ -48,3 +52,14 @@ pub async fn interpret_pure(rib: &RibByteCode, rib_input: &RibInput) -> Result<R
     let mut interpreter = Interpreter::pure(rib_input);
     interpreter.run(rib.clone()).await
 }
+
+// Interprets the byte code recording its coverage, such as from the tests of a script
+pub async fn interpret_with_coverage(
+    rib: &RibByteCode,
+    rib_input: &RibInput,
+    function_invoke: Arc<dyn RibFunctionInvoke + Sync + Send>,
+    coverage: &mut RibCoverage,
+) -> Result<RibResult, String> {
+    let mut interpreter = Interpreter::new(rib_input, function_invoke);
+    interpreter.run_with_coverage(rib.clone(), coverage).await
+}
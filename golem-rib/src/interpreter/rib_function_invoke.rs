@@ -8,7 +8,18 @@ pub trait RibFunctionInvoke {
         worker_name: Option<EvaluatedWorkerName>,
         function_name: EvaluatedFqFn,
         args: EvaluatedFnArgs,
+        idempotency_key: Option<EvaluatedIdempotencyKey>,
     ) -> Result<ValueAndType, String>;
+
+    // Publishes an event emitted with `emit(event-name, payload)`. Events are dropped
+    // unless the embedder of the interpreter has somewhere to publish them.
+    async fn emit(
+        &self,
+        _event_name: EvaluatedEventName,
+        _payload: ValueAndType,
+    ) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 pub struct EvaluatedFqFn(pub String);
@@ -17,3 +28,9 @@ pub struct EvaluatedFqFn(pub String);
 pub struct EvaluatedWorkerName(pub String);
 
 pub struct EvaluatedFnArgs(pub Vec<ValueAndType>);
+
+// Idempotency key explicitly specified in Rib using `with idempotency-key(..)`
+#[derive(Clone)]
+pub struct EvaluatedIdempotencyKey(pub String);
+
+pub struct EvaluatedEventName(pub String);
@@ -15,7 +15,7 @@
 use crate::interpreter::env::InterpreterEnv;
 use crate::interpreter::instruction_cursor::RibByteCodeCursor;
 use crate::interpreter::stack::InterpreterStack;
-use crate::{RibByteCode, RibFunctionInvoke, RibIR, RibInput, RibResult};
+use crate::{RibByteCode, RibCoverage, RibFunctionInvoke, RibIR, RibInput, RibResult};
 use std::sync::Arc;
 
 pub struct Interpreter {
@@ -50,11 +50,33 @@ impl Interpreter {
     }
 
     pub async fn run(&mut self, instructions0: RibByteCode) -> Result<RibResult, String> {
+        self.run_internal(instructions0, None).await
+    }
+
+    // Runs the instructions recording each executed instruction in `coverage`, which
+    // accumulates the executions of the same byte code over several runs
+    pub async fn run_with_coverage(
+        &mut self,
+        instructions0: RibByteCode,
+        coverage: &mut RibCoverage,
+    ) -> Result<RibResult, String> {
+        self.run_internal(instructions0, Some(coverage)).await
+    }
+
+    async fn run_internal(
+        &mut self,
+        instructions0: RibByteCode,
+        mut coverage: Option<&mut RibCoverage>,
+    ) -> Result<RibResult, String> {
         let mut byte_code_cursor = RibByteCodeCursor::from_rib_byte_code(instructions0);
         let mut stack = InterpreterStack::new();
         let mut interpreter_env = InterpreterEnv::from(&self.input, &self.invoke);
 
         while let Some(instruction) = byte_code_cursor.get_instruction() {
+            if let Some(coverage) = coverage.as_deref_mut() {
+                coverage.record(byte_code_cursor.position() - 1);
+            }
+
             match instruction {
                 RibIR::PushLit(val) => {
                     stack.push_val(val);
@@ -168,10 +190,11 @@ impl Interpreter {
                     )?;
                 }
 
-                RibIR::InvokeFunction(worker_type, arg_size, _) => {
+                RibIR::InvokeFunction(worker_type, idempotency_key_type, arg_size, _) => {
                     internal::run_call_instruction(
                         arg_size,
                         worker_type,
+                        idempotency_key_type,
                         &mut stack,
                         &mut interpreter_env,
                     )
@@ -256,6 +279,9 @@ impl Interpreter {
                 RibIR::SinkToList => {
                     internal::run_sink_to_list_instruction(&mut stack)?;
                 }
+                RibIR::EmitEvent => {
+                    internal::run_emit_event_instruction(&mut stack, &interpreter_env).await?;
+                }
             }
         }
 
@@ -276,9 +302,10 @@ mod internal {
     use crate::interpreter::literal::LiteralValue;
     use crate::interpreter::stack::InterpreterStack;
     use crate::{
-        CoercedNumericValue, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName,
-        FunctionReferenceType, InstructionId, ParsedFunctionName, ParsedFunctionReference,
-        ParsedFunctionSite, RibFunctionInvoke, VariableId, WorkerNamePresence,
+        CoercedNumericValue, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey,
+        EvaluatedWorkerName, FunctionReferenceType, IdempotencyKeyPresence, InstructionId,
+        ParsedFunctionName, ParsedFunctionReference, ParsedFunctionSite, RibFunctionInvoke,
+        VariableId, WorkerNamePresence,
     };
     use golem_wasm_ast::analysis::AnalysedType;
     use golem_wasm_ast::analysis::TypeResult;
@@ -298,6 +325,7 @@ mod internal {
             _worker_name: Option<EvaluatedWorkerName>,
             _function_name: EvaluatedFqFn,
             _args: EvaluatedFnArgs,
+            _idempotency_key: Option<EvaluatedIdempotencyKey>,
         ) -> Result<ValueAndType, String> {
             Ok(ValueAndType {
                 value: Value::Tuple(vec![]),
@@ -977,6 +1005,7 @@ mod internal {
     pub(crate) async fn run_call_instruction(
         arg_size: usize,
         worker_type: WorkerNamePresence,
+        idempotency_key_type: IdempotencyKeyPresence,
         interpreter_stack: &mut InterpreterStack,
         interpreter_env: &mut InterpreterEnv,
     ) -> Result<(), String> {
@@ -995,6 +1024,17 @@ mod internal {
             }
         };
 
+        let idempotency_key = match idempotency_key_type {
+            IdempotencyKeyPresence::Absent => None,
+            IdempotencyKeyPresence::Present => {
+                let idempotency_key = interpreter_stack
+                    .pop_str()
+                    .ok_or("Internal Error: Failed to get the idempotency key".to_string())?;
+
+                Some(idempotency_key)
+            }
+        };
+
         let last_n_elements = interpreter_stack
             .pop_n(arg_size)
             .ok_or("Internal Error: Failed to get arguments for the function call".to_string())?;
@@ -1010,7 +1050,12 @@ mod internal {
             .collect::<Result<Vec<ValueAndType>, String>>()?;
 
         let result = interpreter_env
-            .invoke_worker_function_async(worker_name, function_name, parameter_values)
+            .invoke_worker_function_async(
+                worker_name,
+                function_name,
+                parameter_values,
+                idempotency_key,
+            )
             .await?;
 
         let interpreter_result = match result {
@@ -1036,6 +1081,34 @@ mod internal {
 
         Ok(())
     }
+
+    pub(crate) async fn run_emit_event_instruction(
+        interpreter_stack: &mut InterpreterStack,
+        interpreter_env: &InterpreterEnv,
+    ) -> Result<(), String> {
+        let args = interpreter_stack
+            .pop_n(2)
+            .ok_or("Internal Error: Failed to get the arguments of emit".to_string())?;
+
+        let event_name = args[0]
+            .get_val()
+            .and_then(|event_name| match event_name.value {
+                Value::String(event_name) => Some(event_name),
+                _ => None,
+            })
+            .ok_or("Internal Error: The event name of emit is not a string".to_string())?;
+        let payload = args[1]
+            .get_val()
+            .ok_or("Internal Error: Failed to get the payload of emit".to_string())?;
+
+        interpreter_env
+            .emit_event_async(event_name, payload)
+            .await?;
+        interpreter_stack.push(RibInterpreterStackValue::Unit);
+
+        Ok(())
+    }
+
     pub(crate) fn run_deconstruct_instruction(
         interpreter_stack: &mut InterpreterStack,
     ) -> Result<(), String> {
@@ -1411,7 +1484,8 @@ mod interpreter_tests {
         use crate::interpreter::rib_interpreter::interpreter_tests::internal;
         use crate::interpreter::rib_interpreter::interpreter_tests::internal::get_value_and_type;
         use crate::{
-            compiler, Expr, GlobalVariableTypeSpec, InferredType, Path, RibInput, VariableId,
+            compiler, Expr, GlobalVariableTypeSpec, InferredType, Path, RibInput, RibInputTypeInfo,
+            VariableId,
         };
         use golem_wasm_ast::analysis::analysed_type::{field, record, s8, str, u32};
         use golem_wasm_rpc::{Value, ValueAndType};
@@ -1557,6 +1631,81 @@ mod interpreter_tests {
 
             assert_eq!(result, Value::String("21-foo".to_string()))
         }
+
+        #[test]
+        async fn test_global_variable_with_registered_types() {
+            let rib_expr = r#"
+               let res1 = request.path.user-id;
+               let res2 = request.headers.name;
+               "${res1}-${res2}"
+            "#;
+
+            let analysed_type_of_input = record(vec![
+                field("path", record(vec![field("user-id", str())])),
+                field("headers", record(vec![field("name", str())])),
+            ]);
+
+            let rib_input_types =
+                RibInputTypeInfo::empty().with_global("request", analysed_type_of_input.clone());
+
+            let expr = Expr::from_text(rib_expr).unwrap();
+
+            let compiled =
+                compiler::compile_with_global_input_types(&expr, &vec![], &rib_input_types)
+                    .unwrap();
+
+            assert_eq!(compiled.rib_input_type_info, rib_input_types);
+
+            let value_and_type = get_value_and_type(
+                &analysed_type_of_input,
+                r#"{path : { user-id: "1" }, headers: { name: "foo" }}"#,
+            );
+
+            let rib_input = RibInput::builder(&compiled.rib_input_type_info)
+                .with_global("request", value_and_type)
+                .and_then(|builder| builder.build())
+                .unwrap();
+
+            let mut interpreter = internal::static_test_interpreter(
+                &ValueAndType::new(Value::S8(1), s8()),
+                Some(rib_input),
+            );
+
+            let result = interpreter
+                .run(compiled.byte_code)
+                .await
+                .unwrap()
+                .get_val()
+                .unwrap()
+                .value;
+
+            assert_eq!(result, Value::String("1-foo".to_string()))
+        }
+
+        #[test]
+        fn test_global_variable_not_registered() {
+            let rib_expr = r#"
+               let res1 = request.path.user-id;
+               let res2: string = env.name;
+               "${res1}-${res2}"
+            "#;
+
+            let rib_input_types = RibInputTypeInfo::empty().with_global(
+                "request",
+                record(vec![field("path", record(vec![field("user-id", str())]))]),
+            );
+
+            let expr = Expr::from_text(rib_expr).unwrap();
+
+            let error = compiler::compile_with_global_input_types(&expr, &vec![], &rib_input_types)
+                .unwrap_err()
+                .to_string();
+
+            assert_eq!(
+                error,
+                "rib internal error: Global variables not allowed: env. Allowed: request"
+            );
+        }
     }
 
     mod list_reduce_interpreter_tests {
@@ -2257,6 +2406,182 @@ mod interpreter_tests {
         }
     }
 
+    mod idempotency_key_tests {
+        use crate::interpreter::rib_interpreter::interpreter_tests::internal;
+        use crate::{compiler, Expr};
+        use golem_wasm_rpc::IntoValueAndType;
+        use test_r::test;
+
+        #[test]
+        async fn test_invoke_without_idempotency_key() {
+            let expr = r#"
+              let worker = instance("my-worker");
+              let result = worker.foo("bar");
+              result
+            "#;
+            let expr = Expr::from_text(expr).unwrap();
+            let component_metadata = internal::get_metadata();
+
+            let compiled = compiler::compile(&expr, &component_metadata).unwrap();
+
+            let mut rib_interpreter = internal::idempotency_key_test_interpreter();
+
+            let result = rib_interpreter.run(compiled.byte_code).await.unwrap();
+
+            assert_eq!(
+                result.get_val().unwrap(),
+                "no-idempotency-key".into_value_and_type()
+            );
+        }
+
+        #[test]
+        async fn test_invoke_with_idempotency_key_literal() {
+            let expr = r#"
+              let worker = instance("my-worker");
+              let result = worker.foo("bar") with idempotency-key("my-key");
+              result
+            "#;
+            let expr = Expr::from_text(expr).unwrap();
+            let component_metadata = internal::get_metadata();
+
+            let compiled = compiler::compile(&expr, &component_metadata).unwrap();
+
+            let mut rib_interpreter = internal::idempotency_key_test_interpreter();
+
+            let result = rib_interpreter.run(compiled.byte_code).await.unwrap();
+
+            assert_eq!(result.get_val().unwrap(), "my-key".into_value_and_type());
+        }
+
+        #[test]
+        async fn test_invoke_with_idempotency_key_expr() {
+            let expr = r#"
+              let request_id: string = "request-1";
+              let worker = instance("my-worker");
+              let result = worker.foo("bar") with idempotency-key("${request_id}-foo");
+              result
+            "#;
+            let expr = Expr::from_text(expr).unwrap();
+            let component_metadata = internal::get_metadata();
+
+            let compiled = compiler::compile(&expr, &component_metadata).unwrap();
+
+            let mut rib_interpreter = internal::idempotency_key_test_interpreter();
+
+            let result = rib_interpreter.run(compiled.byte_code).await.unwrap();
+
+            assert_eq!(
+                result.get_val().unwrap(),
+                "request-1-foo".into_value_and_type()
+            );
+        }
+
+        #[test]
+        async fn test_invoke_with_invalid_idempotency_key() {
+            let expr = r#"
+              let worker = instance("my-worker");
+              let result = worker.foo("bar") with idempotency-key(1: u64);
+              result
+            "#;
+            let expr = Expr::from_text(expr).unwrap();
+            let component_metadata = internal::get_metadata();
+
+            let compilation_error = compiler::compile(&expr, &component_metadata)
+                .unwrap_err()
+                .to_string();
+
+            assert!(compilation_error.contains("expected string as idempotency key, found u64"));
+        }
+    }
+
+    mod event_emission_tests {
+        use crate::interpreter::rib_interpreter::interpreter_tests::internal;
+        use crate::{
+            compiler, EvaluatedEventName, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey,
+            EvaluatedWorkerName, Expr, Interpreter, RibFunctionInvoke, RibInput,
+        };
+        use async_trait::async_trait;
+        use golem_wasm_ast::analysis::analysed_type::{field, record, str, tuple};
+        use golem_wasm_rpc::{IntoValueAndType, Value, ValueAndType};
+        use std::sync::{Arc, Mutex};
+        use test_r::test;
+
+        #[derive(Default)]
+        struct EventRecordingInvoke {
+            events: Mutex<Vec<(String, ValueAndType)>>,
+        }
+
+        #[async_trait]
+        impl RibFunctionInvoke for EventRecordingInvoke {
+            async fn invoke(
+                &self,
+                _worker_name: Option<EvaluatedWorkerName>,
+                _fqn: EvaluatedFqFn,
+                _args: EvaluatedFnArgs,
+                _idempotency_key: Option<EvaluatedIdempotencyKey>,
+            ) -> Result<ValueAndType, String> {
+                Ok(ValueAndType::new(
+                    Value::Tuple(vec![Value::String("baz".to_string())]),
+                    tuple(vec![str()]),
+                ))
+            }
+
+            async fn emit(
+                &self,
+                event_name: EvaluatedEventName,
+                payload: ValueAndType,
+            ) -> Result<(), String> {
+                self.events.lock().unwrap().push((event_name.0, payload));
+                Ok(())
+            }
+        }
+
+        #[test]
+        async fn test_emit_publishes_event() {
+            let expr = r#"
+              let worker = instance("my-worker");
+              let result = worker.foo("bar");
+              emit("foo-called", { input: "bar", result: result });
+              result
+            "#;
+            let expr = Expr::from_text(expr).unwrap();
+            let component_metadata = internal::get_metadata();
+
+            let compiled = compiler::compile(&expr, &component_metadata).unwrap();
+
+            let invoke = Arc::new(EventRecordingInvoke::default());
+            let mut rib_interpreter = Interpreter::new(&RibInput::default(), invoke.clone());
+
+            let result = rib_interpreter.run(compiled.byte_code).await.unwrap();
+
+            assert_eq!(result.get_val().unwrap(), "baz".into_value_and_type());
+            assert_eq!(
+                *invoke.events.lock().unwrap(),
+                vec![(
+                    "foo-called".to_string(),
+                    ValueAndType::new(
+                        Value::Record(vec![
+                            Value::String("bar".to_string()),
+                            Value::String("baz".to_string()),
+                        ]),
+                        record(vec![field("input", str()), field("result", str())]),
+                    )
+                )]
+            );
+        }
+
+        #[test]
+        async fn test_emit_requires_name_and_payload() {
+            let expr = r#"
+              emit("foo-called")
+            "#;
+            let expr = Expr::from_text(expr).unwrap();
+            let component_metadata = internal::get_metadata();
+
+            assert!(compiler::compile(&expr, &component_metadata).is_err());
+        }
+    }
+
     mod first_class_worker_tests {
         use crate::interpreter::rib_interpreter::interpreter_tests::internal;
         use crate::interpreter::rib_interpreter::interpreter_tests::internal::strip_spaces;
@@ -3018,7 +3343,8 @@ mod interpreter_tests {
     mod internal {
         use crate::interpreter::rib_interpreter::Interpreter;
         use crate::{
-            EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName, RibFunctionInvoke, RibInput,
+            EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey, EvaluatedWorkerName,
+            RibFunctionInvoke, RibInput,
         };
         use async_trait::async_trait;
         use golem_wasm_ast::analysis::analysed_type::{
@@ -3381,6 +3707,7 @@ mod interpreter_tests {
                 _worker_name: Option<EvaluatedWorkerName>,
                 _fqn: EvaluatedFqFn,
                 _args: EvaluatedFnArgs,
+                _idempotency_key: Option<EvaluatedIdempotencyKey>,
             ) -> Result<ValueAndType, String> {
                 let value = self.value.clone();
                 Ok(ValueAndType::new(
@@ -3399,6 +3726,7 @@ mod interpreter_tests {
                 worker_name: Option<EvaluatedWorkerName>,
                 function_name: EvaluatedFqFn,
                 args: EvaluatedFnArgs,
+                _idempotency_key: Option<EvaluatedIdempotencyKey>,
             ) -> Result<ValueAndType, String> {
                 let worker_name = worker_name.map(|x| x.0);
 
@@ -3438,6 +3766,37 @@ mod interpreter_tests {
             }
         }
 
+        // Responds with the idempotency key that reached the invoke,
+        // or `no-idempotency-key` if the script didn't specify any
+        struct IdempotencyKeyEchoInvoke;
+
+        #[async_trait]
+        impl RibFunctionInvoke for IdempotencyKeyEchoInvoke {
+            async fn invoke(
+                &self,
+                _worker_name: Option<EvaluatedWorkerName>,
+                _fqn: EvaluatedFqFn,
+                _args: EvaluatedFnArgs,
+                idempotency_key: Option<EvaluatedIdempotencyKey>,
+            ) -> Result<ValueAndType, String> {
+                let key = idempotency_key
+                    .map(|key| key.0)
+                    .unwrap_or("no-idempotency-key".to_string());
+
+                Ok(ValueAndType::new(
+                    Value::Tuple(vec![Value::String(key)]),
+                    tuple(vec![str()]),
+                ))
+            }
+        }
+
+        pub(crate) fn idempotency_key_test_interpreter() -> Interpreter {
+            Interpreter {
+                input: RibInput::default(),
+                invoke: Arc::new(IdempotencyKeyEchoInvoke),
+            }
+        }
+
         pub(crate) fn dynamic_test_interpreter(rib_input: Option<RibInput>) -> Interpreter {
             let invoke: Arc<dyn RibFunctionInvoke + Send + Sync> = Arc::new(DynamicWorkerFnInvoke);
 
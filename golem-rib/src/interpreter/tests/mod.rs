@@ -2045,7 +2045,8 @@ mod comprehensive_test {
         use crate::interpreter::rib_interpreter::Interpreter;
         use crate::interpreter::tests::comprehensive_test::{mock_data, test_utils};
         use crate::{
-            EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName, RibFunctionInvoke, RibInput,
+            EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey, EvaluatedWorkerName,
+            RibFunctionInvoke, RibInput,
         };
         use async_trait::async_trait;
         use golem_wasm_ast::analysis::analysed_type::tuple;
@@ -2258,6 +2259,7 @@ mod comprehensive_test {
                 _worker_name: Option<EvaluatedWorkerName>,
                 function_name: EvaluatedFqFn,
                 _args: EvaluatedFnArgs,
+                _idempotency_key: Option<EvaluatedIdempotencyKey>,
             ) -> Result<ValueAndType, String> {
                 let function_name = FunctionName(function_name.0);
                 let value = self
@@ -0,0 +1,824 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A stable JSON representation of the syntax tree of Rib programs, for tools manipulating
+// programs without linking this crate.
+//
+// A document is `{ "version": 1, "expr": <node> }`. Every node is an object with a `kind`,
+// and the optional fields (`typeAnnotation`, `genericTypeParameter`, `worker`, `idempotencyKey`)
+// are left out when absent. Type annotations and function names are written as in Rib source.
+//
+//   let                   { name, typeAnnotation?, expr }
+//   identifier            { name, typeAnnotation? }
+//   selectField           { expr, field, typeAnnotation? }
+//   selectIndex           { expr, index, typeAnnotation? }
+//   sequence              { exprs, typeAnnotation? }
+//   record                { fields: [{ name, expr }] }
+//   tuple                 { exprs }
+//   literal               { value }
+//   number                { value (decimal string), typeAnnotation? }
+//   flags                 { flags }
+//   boolean               { value }
+//   concat                { exprs }
+//   block                 { exprs }
+//   not                   { expr }
+//   and, or, equalTo, greaterThan, greaterThanOrEqualTo, lessThan, lessThanOrEqualTo,
+//   plus, minus, multiply, divide
+//                         { lhs, rhs }
+//   cond                  { cond, then, else }
+//   match                 { predicate, arms: [{ pattern, expr }] }
+//   some                  { expr, typeAnnotation? }
+//   none                  { typeAnnotation? }
+//   ok, err               { expr, typeAnnotation? }
+//   call                  { function, genericTypeParameter?, args, worker?, idempotencyKey? }
+//   variantConstructor    { name, args }
+//   enumConstructor       { name }
+//   invokeMethod          { lhs, method, genericTypeParameter?, args, idempotencyKey? }
+//   unwrap, getTag        { expr }
+//   throw                 { message }
+//   listComprehension     { variable, iterable, yield }
+//   listReduce            { reduceVariable, variable, iterable, init, yield }
+//
+// Match patterns are objects with a `kind` as well:
+//
+//   wildcard              {}
+//   as                    { name, pattern }
+//   constructor           { name, patterns }
+//   tuple, list           { patterns }
+//   record                { fields: [{ name, pattern }] }
+//   literal               { expr }
+//
+// New kinds and optional fields may be added within a version, existing ones are not changed.
+// Inferred types and source positions are not part of the representation.
+
+use crate::call_type::CallType;
+use crate::generic_type_parameter::GenericTypeParameter;
+use crate::parser::type_name::{parse_type_name, TypeName};
+use crate::{ArmPattern, DynamicParsedFunctionName, Expr, MatchArm, VariableId};
+use bigdecimal::BigDecimal;
+use combine::stream::position;
+use combine::EasyParser;
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+pub const JSON_AST_VERSION: u64 = 1;
+
+impl Expr {
+    // Fails for the nodes introduced by the compiler, such as resolved instance creations,
+    // which are not part of the syntax tree of a program
+    pub fn to_json_ast(&self) -> Result<Value, String> {
+        Ok(json!({
+            "version": JSON_AST_VERSION,
+            "expr": internal::expr_to_json(self)?,
+        }))
+    }
+
+    pub fn from_json_ast(value: &Value) -> Result<Expr, String> {
+        let version = value
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or("Missing version of the Rib AST")?;
+
+        if version != JSON_AST_VERSION {
+            return Err(format!("Unsupported Rib AST version {}", version));
+        }
+
+        internal::expr_from_json(value.get("expr").ok_or("Missing expr of the Rib AST")?)
+    }
+}
+
+mod internal {
+    use super::*;
+
+    fn node(kind: &str, fields: Vec<(&str, Value)>) -> Value {
+        let mut object = Map::new();
+        object.insert("kind".to_string(), Value::String(kind.to_string()));
+        for (name, value) in fields {
+            if !value.is_null() {
+                object.insert(name.to_string(), value);
+            }
+        }
+        Value::Object(object)
+    }
+
+    fn type_annotation_to_json(type_annotation: &Option<TypeName>) -> Value {
+        type_annotation.as_ref().map_or(Value::Null, |type_name| {
+            Value::String(type_name.to_string())
+        })
+    }
+
+    fn exprs_to_json(exprs: &[Expr]) -> Result<Value, String> {
+        Ok(Value::Array(
+            exprs.iter().map(expr_to_json).collect::<Result<_, _>>()?,
+        ))
+    }
+
+    fn optional_expr_to_json(expr: Option<&Expr>) -> Result<Value, String> {
+        expr.map_or(Ok(Value::Null), expr_to_json)
+    }
+
+    fn binary(kind: &str, lhs: &Expr, rhs: &Expr) -> Result<Value, String> {
+        Ok(node(
+            kind,
+            vec![("lhs", expr_to_json(lhs)?), ("rhs", expr_to_json(rhs)?)],
+        ))
+    }
+
+    pub(crate) fn expr_to_json(expr: &Expr) -> Result<Value, String> {
+        match expr {
+            Expr::Let {
+                variable_id,
+                type_annotation,
+                expr,
+                ..
+            } => Ok(node(
+                "let",
+                vec![
+                    ("name", Value::String(variable_id.name())),
+                    ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                    ("expr", expr_to_json(expr)?),
+                ],
+            )),
+            Expr::Identifier {
+                variable_id,
+                type_annotation,
+                ..
+            } => Ok(node(
+                "identifier",
+                vec![
+                    ("name", Value::String(variable_id.name())),
+                    ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                ],
+            )),
+            Expr::SelectField {
+                expr,
+                field,
+                type_annotation,
+                ..
+            } => Ok(node(
+                "selectField",
+                vec![
+                    ("expr", expr_to_json(expr)?),
+                    ("field", Value::String(field.clone())),
+                    ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                ],
+            )),
+            Expr::SelectIndex {
+                expr,
+                index,
+                type_annotation,
+                ..
+            } => Ok(node(
+                "selectIndex",
+                vec![
+                    ("expr", expr_to_json(expr)?),
+                    ("index", json!(index)),
+                    ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                ],
+            )),
+            Expr::Sequence {
+                exprs,
+                type_annotation,
+                ..
+            } => Ok(node(
+                "sequence",
+                vec![
+                    ("exprs", exprs_to_json(exprs)?),
+                    ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                ],
+            )),
+            Expr::Record { exprs, .. } => Ok(node(
+                "record",
+                vec![(
+                    "fields",
+                    Value::Array(
+                        exprs
+                            .iter()
+                            .map(|(name, expr)| {
+                                Ok(json!({ "name": name, "expr": expr_to_json(expr)? }))
+                            })
+                            .collect::<Result<_, String>>()?,
+                    ),
+                )],
+            )),
+            Expr::Tuple { exprs, .. } => Ok(node("tuple", vec![("exprs", exprs_to_json(exprs)?)])),
+            Expr::Literal { value, .. } => Ok(node(
+                "literal",
+                vec![("value", Value::String(value.clone()))],
+            )),
+            Expr::Number {
+                number,
+                type_annotation,
+                ..
+            } => Ok(node(
+                "number",
+                vec![
+                    ("value", Value::String(number.value.to_string())),
+                    ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                ],
+            )),
+            Expr::Flags { flags, .. } => Ok(node("flags", vec![("flags", json!(flags))])),
+            Expr::Boolean { value, .. } => Ok(node("boolean", vec![("value", json!(value))])),
+            Expr::Concat { exprs, .. } => {
+                Ok(node("concat", vec![("exprs", exprs_to_json(exprs)?)]))
+            }
+            Expr::ExprBlock { exprs, .. } => {
+                Ok(node("block", vec![("exprs", exprs_to_json(exprs)?)]))
+            }
+            Expr::Not { expr, .. } => Ok(node("not", vec![("expr", expr_to_json(expr)?)])),
+            Expr::GreaterThan { lhs, rhs, .. } => binary("greaterThan", lhs, rhs),
+            Expr::GreaterThanOrEqualTo { lhs, rhs, .. } => binary("greaterThanOrEqualTo", lhs, rhs),
+            Expr::LessThan { lhs, rhs, .. } => binary("lessThan", lhs, rhs),
+            Expr::LessThanOrEqualTo { lhs, rhs, .. } => binary("lessThanOrEqualTo", lhs, rhs),
+            Expr::EqualTo { lhs, rhs, .. } => binary("equalTo", lhs, rhs),
+            Expr::And { lhs, rhs, .. } => binary("and", lhs, rhs),
+            Expr::Or { lhs, rhs, .. } => binary("or", lhs, rhs),
+            Expr::Plus { lhs, rhs, .. } => binary("plus", lhs, rhs),
+            Expr::Minus { lhs, rhs, .. } => binary("minus", lhs, rhs),
+            Expr::Multiply { lhs, rhs, .. } => binary("multiply", lhs, rhs),
+            Expr::Divide { lhs, rhs, .. } => binary("divide", lhs, rhs),
+            Expr::Cond { cond, lhs, rhs, .. } => Ok(node(
+                "cond",
+                vec![
+                    ("cond", expr_to_json(cond)?),
+                    ("then", expr_to_json(lhs)?),
+                    ("else", expr_to_json(rhs)?),
+                ],
+            )),
+            Expr::PatternMatch {
+                predicate,
+                match_arms,
+                ..
+            } => Ok(node(
+                "match",
+                vec![
+                    ("predicate", expr_to_json(predicate)?),
+                    (
+                        "arms",
+                        Value::Array(
+                            match_arms
+                                .iter()
+                                .map(|arm| {
+                                    Ok(json!({
+                                        "pattern": pattern_to_json(&arm.arm_pattern)?,
+                                        "expr": expr_to_json(&arm.arm_resolution_expr)?,
+                                    }))
+                                })
+                                .collect::<Result<_, String>>()?,
+                        ),
+                    ),
+                ],
+            )),
+            Expr::Option {
+                expr,
+                type_annotation,
+                ..
+            } => match expr {
+                Some(expr) => Ok(node(
+                    "some",
+                    vec![
+                        ("expr", expr_to_json(expr)?),
+                        ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                    ],
+                )),
+                None => Ok(node(
+                    "none",
+                    vec![("typeAnnotation", type_annotation_to_json(type_annotation))],
+                )),
+            },
+            Expr::Result {
+                expr,
+                type_annotation,
+                ..
+            } => {
+                let (kind, expr) = match expr {
+                    Ok(expr) => ("ok", expr),
+                    Err(expr) => ("err", expr),
+                };
+                Ok(node(
+                    kind,
+                    vec![
+                        ("expr", expr_to_json(expr)?),
+                        ("typeAnnotation", type_annotation_to_json(type_annotation)),
+                    ],
+                ))
+            }
+            Expr::Call {
+                call_type,
+                generic_type_parameter,
+                args,
+                ..
+            } => match call_type {
+                CallType::Function {
+                    worker,
+                    function_name,
+                    idempotency_key,
+                } => Ok(node(
+                    "call",
+                    vec![
+                        ("function", Value::String(function_name.to_string())),
+                        (
+                            "genericTypeParameter",
+                            generic_type_parameter
+                                .as_ref()
+                                .map_or(Value::Null, |g| Value::String(g.value.clone())),
+                        ),
+                        ("args", exprs_to_json(args)?),
+                        ("worker", optional_expr_to_json(worker.as_deref())?),
+                        (
+                            "idempotencyKey",
+                            optional_expr_to_json(idempotency_key.as_deref())?,
+                        ),
+                    ],
+                )),
+                CallType::VariantConstructor(name) => Ok(node(
+                    "variantConstructor",
+                    vec![
+                        ("name", Value::String(name.clone())),
+                        ("args", exprs_to_json(args)?),
+                    ],
+                )),
+                CallType::EnumConstructor(name) => Ok(node(
+                    "enumConstructor",
+                    vec![("name", Value::String(name.clone()))],
+                )),
+                CallType::InstanceCreation(_) => Err(
+                    "Instance creations resolved by the compiler are not part of the Rib AST"
+                        .to_string(),
+                ),
+                CallType::EventEmission => Err(
+                    "Event emissions resolved by the compiler are not part of the Rib AST"
+                        .to_string(),
+                ),
+            },
+            Expr::InvokeMethodLazy {
+                lhs,
+                method,
+                generic_type_parameter,
+                args,
+                idempotency_key,
+                ..
+            } => Ok(node(
+                "invokeMethod",
+                vec![
+                    ("lhs", expr_to_json(lhs)?),
+                    ("method", Value::String(method.clone())),
+                    (
+                        "genericTypeParameter",
+                        generic_type_parameter
+                            .as_ref()
+                            .map_or(Value::Null, |g| Value::String(g.value.clone())),
+                    ),
+                    ("args", exprs_to_json(args)?),
+                    (
+                        "idempotencyKey",
+                        optional_expr_to_json(idempotency_key.as_deref())?,
+                    ),
+                ],
+            )),
+            Expr::Unwrap { expr, .. } => Ok(node("unwrap", vec![("expr", expr_to_json(expr)?)])),
+            Expr::GetTag { expr, .. } => Ok(node("getTag", vec![("expr", expr_to_json(expr)?)])),
+            Expr::Throw { message, .. } => Ok(node(
+                "throw",
+                vec![("message", Value::String(message.clone()))],
+            )),
+            Expr::ListComprehension {
+                iterated_variable,
+                iterable_expr,
+                yield_expr,
+                ..
+            } => Ok(node(
+                "listComprehension",
+                vec![
+                    ("variable", Value::String(iterated_variable.name())),
+                    ("iterable", expr_to_json(iterable_expr)?),
+                    ("yield", expr_to_json(yield_expr)?),
+                ],
+            )),
+            Expr::ListReduce {
+                reduce_variable,
+                iterated_variable,
+                iterable_expr,
+                yield_expr,
+                init_value_expr,
+                ..
+            } => Ok(node(
+                "listReduce",
+                vec![
+                    ("reduceVariable", Value::String(reduce_variable.name())),
+                    ("variable", Value::String(iterated_variable.name())),
+                    ("iterable", expr_to_json(iterable_expr)?),
+                    ("init", expr_to_json(init_value_expr)?),
+                    ("yield", expr_to_json(yield_expr)?),
+                ],
+            )),
+        }
+    }
+
+    fn patterns_to_json(patterns: &[ArmPattern]) -> Result<Value, String> {
+        Ok(Value::Array(
+            patterns
+                .iter()
+                .map(pattern_to_json)
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    fn pattern_to_json(pattern: &ArmPattern) -> Result<Value, String> {
+        match pattern {
+            ArmPattern::WildCard => Ok(node("wildcard", vec![])),
+            ArmPattern::As(name, pattern) => Ok(node(
+                "as",
+                vec![
+                    ("name", Value::String(name.clone())),
+                    ("pattern", pattern_to_json(pattern)?),
+                ],
+            )),
+            ArmPattern::Constructor(name, patterns) => Ok(node(
+                "constructor",
+                vec![
+                    ("name", Value::String(name.clone())),
+                    ("patterns", patterns_to_json(patterns)?),
+                ],
+            )),
+            ArmPattern::TupleConstructor(patterns) => Ok(node(
+                "tuple",
+                vec![("patterns", patterns_to_json(patterns)?)],
+            )),
+            ArmPattern::ListConstructor(patterns) => Ok(node(
+                "list",
+                vec![("patterns", patterns_to_json(patterns)?)],
+            )),
+            ArmPattern::RecordConstructor(fields) => Ok(node(
+                "record",
+                vec![(
+                    "fields",
+                    Value::Array(
+                        fields
+                            .iter()
+                            .map(|(name, pattern)| {
+                                Ok(json!({ "name": name, "pattern": pattern_to_json(pattern)? }))
+                            })
+                            .collect::<Result<_, String>>()?,
+                    ),
+                )],
+            )),
+            ArmPattern::Literal(expr) => Ok(node("literal", vec![("expr", expr_to_json(expr)?)])),
+        }
+    }
+
+    fn field<'a>(value: &'a Value, name: &str) -> Result<&'a Value, String> {
+        value
+            .get(name)
+            .ok_or(format!("Missing field {} in Rib AST node", name))
+    }
+
+    fn string_field(value: &Value, name: &str) -> Result<String, String> {
+        field(value, name)?
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or(format!("Field {} of a Rib AST node must be a string", name))
+    }
+
+    fn optional_string_field(value: &Value, name: &str) -> Result<Option<String>, String> {
+        match value.get(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(_) => string_field(value, name).map(Some),
+        }
+    }
+
+    fn expr_field(value: &Value, name: &str) -> Result<Expr, String> {
+        expr_from_json(field(value, name)?)
+    }
+
+    fn optional_expr_field(value: &Value, name: &str) -> Result<Option<Expr>, String> {
+        match value.get(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(expr) => expr_from_json(expr).map(Some),
+        }
+    }
+
+    fn array_field<'a>(value: &'a Value, name: &str) -> Result<&'a Vec<Value>, String> {
+        field(value, name)?
+            .as_array()
+            .ok_or(format!("Field {} of a Rib AST node must be an array", name))
+    }
+
+    fn exprs_field(value: &Value, name: &str) -> Result<Vec<Expr>, String> {
+        array_field(value, name)?
+            .iter()
+            .map(expr_from_json)
+            .collect()
+    }
+
+    fn type_annotation_field(value: &Value) -> Result<Option<TypeName>, String> {
+        optional_string_field(value, "typeAnnotation")?
+            .map(|type_name| {
+                parse_type_name()
+                    .easy_parse(position::Stream::new(type_name.as_str()))
+                    .map(|(type_name, _)| type_name)
+                    .map_err(|err| format!("Invalid type annotation {}: {}", type_name, err))
+            })
+            .transpose()
+    }
+
+    fn generic_type_parameter_field(value: &Value) -> Result<Option<GenericTypeParameter>, String> {
+        Ok(optional_string_field(value, "genericTypeParameter")?
+            .map(|value| GenericTypeParameter { value }))
+    }
+
+    fn binary_fields(value: &Value) -> Result<(Expr, Expr), String> {
+        Ok((expr_field(value, "lhs")?, expr_field(value, "rhs")?))
+    }
+
+    pub(crate) fn expr_from_json(value: &Value) -> Result<Expr, String> {
+        let kind = string_field(value, "kind")?;
+
+        match kind.as_str() {
+            "let" => Ok(Expr::let_binding(
+                string_field(value, "name")?,
+                expr_field(value, "expr")?,
+                type_annotation_field(value)?,
+            )),
+            "identifier" => Ok(Expr::identifier_global(
+                string_field(value, "name")?,
+                type_annotation_field(value)?,
+            )),
+            "selectField" => Ok(Expr::select_field(
+                expr_field(value, "expr")?,
+                string_field(value, "field")?,
+                type_annotation_field(value)?,
+            )),
+            "selectIndex" => {
+                let expr = expr_field(value, "expr")?;
+                let index = field(value, "index")?
+                    .as_u64()
+                    .ok_or("Field index of a Rib AST node must be a number")?
+                    as usize;
+                match type_annotation_field(value)? {
+                    Some(type_name) => Ok(Expr::select_index_with_type_annotation(
+                        expr, index, type_name,
+                    )),
+                    None => Ok(Expr::select_index(expr, index)),
+                }
+            }
+            "sequence" => Ok(Expr::sequence(
+                exprs_field(value, "exprs")?,
+                type_annotation_field(value)?,
+            )),
+            "record" => Ok(Expr::record(
+                array_field(value, "fields")?
+                    .iter()
+                    .map(|field| Ok((string_field(field, "name")?, expr_field(field, "expr")?)))
+                    .collect::<Result<_, String>>()?,
+            )),
+            "tuple" => Ok(Expr::tuple(exprs_field(value, "exprs")?)),
+            "literal" => Ok(Expr::literal(string_field(value, "value")?)),
+            "number" => {
+                let number = BigDecimal::from_str(&string_field(value, "value")?)
+                    .map_err(|err| format!("Invalid number in Rib AST: {}", err))?;
+                match type_annotation_field(value)? {
+                    Some(type_name) => Ok(Expr::untyped_number_with_type_name(number, type_name)),
+                    None => Ok(Expr::untyped_number(number)),
+                }
+            }
+            "flags" => Ok(Expr::flags(
+                array_field(value, "flags")?
+                    .iter()
+                    .map(|flag| {
+                        flag.as_str()
+                            .map(|flag| flag.to_string())
+                            .ok_or("Flags of a Rib AST node must be strings".to_string())
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            "boolean" => {
+                Ok(Expr::boolean(field(value, "value")?.as_bool().ok_or(
+                    "Field value of a boolean Rib AST node must be a boolean",
+                )?))
+            }
+            "concat" => Ok(Expr::concat(exprs_field(value, "exprs")?)),
+            "block" => Ok(Expr::expr_block(exprs_field(value, "exprs")?)),
+            "not" => Ok(Expr::not(expr_field(value, "expr")?)),
+            "greaterThan" => binary_fields(value).map(|(l, r)| Expr::greater_than(l, r)),
+            "greaterThanOrEqualTo" => {
+                binary_fields(value).map(|(l, r)| Expr::greater_than_or_equal_to(l, r))
+            }
+            "lessThan" => binary_fields(value).map(|(l, r)| Expr::less_than(l, r)),
+            "lessThanOrEqualTo" => {
+                binary_fields(value).map(|(l, r)| Expr::less_than_or_equal_to(l, r))
+            }
+            "equalTo" => binary_fields(value).map(|(l, r)| Expr::equal_to(l, r)),
+            "and" => binary_fields(value).map(|(l, r)| Expr::and(l, r)),
+            "or" => binary_fields(value).map(|(l, r)| Expr::or(l, r)),
+            "plus" => binary_fields(value).map(|(l, r)| Expr::plus(l, r)),
+            "minus" => binary_fields(value).map(|(l, r)| Expr::minus(l, r)),
+            "multiply" => binary_fields(value).map(|(l, r)| Expr::multiply(l, r)),
+            "divide" => binary_fields(value).map(|(l, r)| Expr::divide(l, r)),
+            "cond" => Ok(Expr::cond(
+                expr_field(value, "cond")?,
+                expr_field(value, "then")?,
+                expr_field(value, "else")?,
+            )),
+            "match" => Ok(Expr::pattern_match(
+                expr_field(value, "predicate")?,
+                array_field(value, "arms")?
+                    .iter()
+                    .map(|arm| {
+                        Ok(MatchArm::new(
+                            pattern_from_json(field(arm, "pattern")?)?,
+                            expr_field(arm, "expr")?,
+                        ))
+                    })
+                    .collect::<Result<_, String>>()?,
+            )),
+            "some" | "none" => {
+                let expr = if kind == "some" {
+                    Some(expr_field(value, "expr")?)
+                } else {
+                    None
+                };
+                match type_annotation_field(value)? {
+                    Some(type_name) => Ok(Expr::option_with_type_annotation(expr, type_name)),
+                    None => Ok(Expr::option(expr)),
+                }
+            }
+            "ok" => Ok(Expr::ok(
+                expr_field(value, "expr")?,
+                type_annotation_field(value)?,
+            )),
+            "err" => Ok(Expr::err(
+                expr_field(value, "expr")?,
+                type_annotation_field(value)?,
+            )),
+            "call" => {
+                let function_name =
+                    DynamicParsedFunctionName::parse(string_field(value, "function")?)
+                        .map_err(|err| format!("Invalid function name in Rib AST: {}", err))?;
+
+                Ok(Expr::call(
+                    CallType::Function {
+                        worker: optional_expr_field(value, "worker")?.map(Box::new),
+                        function_name,
+                        idempotency_key: optional_expr_field(value, "idempotencyKey")?
+                            .map(Box::new),
+                    },
+                    generic_type_parameter_field(value)?,
+                    exprs_field(value, "args")?,
+                ))
+            }
+            "variantConstructor" => Ok(Expr::call(
+                CallType::VariantConstructor(string_field(value, "name")?),
+                None,
+                exprs_field(value, "args")?,
+            )),
+            "enumConstructor" => Ok(Expr::call(
+                CallType::EnumConstructor(string_field(value, "name")?),
+                None,
+                vec![],
+            )),
+            "invokeMethod" => {
+                let invoke = Expr::invoke_worker_function(
+                    expr_field(value, "lhs")?,
+                    string_field(value, "method")?,
+                    generic_type_parameter_field(value)?,
+                    exprs_field(value, "args")?,
+                );
+                match optional_expr_field(value, "idempotencyKey")? {
+                    Some(key) => Ok(invoke.with_idempotency_key(key)),
+                    None => Ok(invoke),
+                }
+            }
+            "unwrap" => Ok(expr_field(value, "expr")?.unwrap()),
+            "getTag" => Ok(Expr::get_tag(expr_field(value, "expr")?)),
+            "throw" => Ok(Expr::throw(string_field(value, "message")?)),
+            "listComprehension" => Ok(Expr::list_comprehension(
+                VariableId::list_comprehension_identifier(string_field(value, "variable")?),
+                expr_field(value, "iterable")?,
+                expr_field(value, "yield")?,
+            )),
+            "listReduce" => Ok(Expr::list_reduce(
+                VariableId::list_reduce_identifier(string_field(value, "reduceVariable")?),
+                VariableId::list_comprehension_identifier(string_field(value, "variable")?),
+                expr_field(value, "iterable")?,
+                expr_field(value, "init")?,
+                expr_field(value, "yield")?,
+            )),
+            other => Err(format!("Unknown kind of Rib AST node: {}", other)),
+        }
+    }
+
+    fn patterns_field(value: &Value) -> Result<Vec<ArmPattern>, String> {
+        array_field(value, "patterns")?
+            .iter()
+            .map(pattern_from_json)
+            .collect()
+    }
+
+    fn pattern_from_json(value: &Value) -> Result<ArmPattern, String> {
+        let kind = string_field(value, "kind")?;
+
+        match kind.as_str() {
+            "wildcard" => Ok(ArmPattern::WildCard),
+            "as" => Ok(ArmPattern::As(
+                string_field(value, "name")?,
+                Box::new(pattern_from_json(field(value, "pattern")?)?),
+            )),
+            "constructor" => Ok(ArmPattern::Constructor(
+                string_field(value, "name")?,
+                patterns_field(value)?,
+            )),
+            "tuple" => Ok(ArmPattern::TupleConstructor(patterns_field(value)?)),
+            "list" => Ok(ArmPattern::ListConstructor(patterns_field(value)?)),
+            "record" => Ok(ArmPattern::RecordConstructor(
+                array_field(value, "fields")?
+                    .iter()
+                    .map(|field_value| {
+                        Ok((
+                            string_field(field_value, "name")?,
+                            pattern_from_json(field(field_value, "pattern")?)?,
+                        ))
+                    })
+                    .collect::<Result<_, String>>()?,
+            )),
+            "literal" => Ok(ArmPattern::Literal(Box::new(expr_field(value, "expr")?))),
+            other => Err(format!("Unknown kind of Rib AST pattern: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Expr;
+    use serde_json::json;
+    use test_r::test;
+
+    #[test]
+    fn programs_round_trip_through_the_json_ast() {
+        let programs = vec![
+            r#"let x: u64 = 1; let y = x + 2 * 3; y > 5 && !(x == 1)"#,
+            r#"let worker = instance("cart-${request.path.user-id}"); worker.add-item(request.body)"#,
+            r#"golem:it/api.{get-cart-contents}("a", [1, 2, 3], (1, "b"), {name: "c", flags: {x, y}})"#,
+            r#"let res: result<str, str> = ok("foo"); match res { ok(v) => some(v), err(msg) => none, _ => none }"#,
+            r#"for x in [1, 2] { yield x; }"#,
+            r#"reduce z, p in [1, 2] from 0 { yield z + p; }"#,
+            r#"if request.headers.x-flag == "on" then request.body.items[0] else "none""#,
+            r#"match request.body { {id: i, tags: [t, _]} => i, x @ _ => "other" }"#,
+        ];
+
+        for program in programs {
+            let expr = Expr::from_text(program).unwrap();
+            let json = expr.to_json_ast().unwrap();
+            let parsed = Expr::from_json_ast(&json).unwrap();
+
+            assert_eq!(
+                crate::to_string(&parsed).unwrap(),
+                crate::to_string(&expr).unwrap(),
+                "{}",
+                program
+            );
+            assert_eq!(parsed.to_json_ast().unwrap(), json, "{}", program);
+        }
+    }
+
+    #[test]
+    fn json_ast_schema_is_stable() {
+        let expr = Expr::from_text(r#"let x: u64 = 1; some(x)"#).unwrap();
+
+        assert_eq!(
+            expr.to_json_ast().unwrap(),
+            json!({
+                "version": 1,
+                "expr": {
+                    "kind": "block",
+                    "exprs": [
+                        {
+                            "kind": "let",
+                            "name": "x",
+                            "typeAnnotation": "u64",
+                            "expr": { "kind": "number", "value": "1" }
+                        },
+                        {
+                            "kind": "some",
+                            "expr": { "kind": "identifier", "name": "x" }
+                        }
+                    ]
+                }
+            })
+        );
+
+        assert!(Expr::from_json_ast(&json!({ "version": 2, "expr": {} })).is_err());
+        assert!(Expr::from_json_ast(&json!({ "version": 1, "expr": { "kind": "loop" } })).is_err());
+    }
+}
@@ -17,6 +17,7 @@ pub use expr::*;
 pub use function_name::*;
 pub use inferred_type::*;
 pub use interpreter::*;
+pub use library::*;
 pub use parser::type_name::TypeName;
 pub use text::*;
 pub use type_checker::*;
@@ -32,6 +33,8 @@ mod generic_type_parameter;
 mod inferred_type;
 mod instance_type;
 mod interpreter;
+mod json_ast;
+mod library;
 mod parser;
 mod rib_compilation_error;
 mod rib_source_span;
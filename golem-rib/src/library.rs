@@ -0,0 +1,257 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{from_string, Expr};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+
+// A library of Rib snippets shared between scripts, made of `let` bindings only.
+// Scripts import the bindings they need with `use <library>::<name>;` statements
+// (or `use <library>@<version>::<name>;` to pin a version) before their first expression,
+// and the imported bindings, along with the bindings they refer to, are linked in front of the script.
+// Example library:
+//
+//   let tenant = request.headers.x-tenant-id;
+//   let user_worker = "user-${tenant}-${request.path.user-id}"
+//
+// Example script: `use auth::user_worker; user_worker`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibLibrary {
+    bindings: Vec<(String, Expr)>,
+}
+
+impl RibLibrary {
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        // A library has no return value, so its last binding may be terminated like the others
+        let expr = from_string(text.trim().trim_end_matches(';'))?;
+
+        let exprs = match expr {
+            Expr::ExprBlock { exprs, .. } => exprs,
+            expr => vec![expr],
+        };
+
+        let bindings = exprs
+            .into_iter()
+            .map(|expr| match &expr {
+                Expr::Let { variable_id, .. } => Ok((variable_id.name(), expr)),
+                _ => Err(format!(
+                    "Rib libraries can only contain let bindings, found {}",
+                    crate::to_string(&expr).unwrap_or_default()
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if bindings.is_empty() {
+            return Err("Rib library has no bindings".to_string());
+        }
+
+        Ok(RibLibrary { bindings })
+    }
+
+    pub fn exports(&self) -> Vec<String> {
+        let mut exports = vec![];
+        for (name, _) in &self.bindings {
+            if !exports.contains(name) {
+                exports.push(name.clone());
+            }
+        }
+        exports
+    }
+
+    // All the bindings of the library as a single expression, to compile the library on its own
+    pub fn to_expr(&self) -> Expr {
+        Expr::expr_block(self.bindings.iter().map(|(_, expr)| expr.clone()).collect())
+    }
+
+    // The bindings defining the given names, along with the earlier bindings they refer to,
+    // in the order they are declared in the library
+    pub fn bindings_of(&self, names: &[String]) -> Result<Vec<Expr>, String> {
+        let exports = self.exports();
+        if let Some(missing) = names.iter().find(|name| !exports.contains(name)) {
+            return Err(format!("Rib library does not export {}", missing));
+        }
+
+        let mut needed = names.iter().cloned().collect::<HashSet<_>>();
+        let mut linked = vec![];
+
+        for (name, expr) in self.bindings.iter().rev() {
+            if needed.remove(name) {
+                needed.extend(referenced_identifiers(expr));
+                linked.push(expr.clone());
+            }
+        }
+
+        linked.reverse();
+        Ok(linked)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RibImport {
+    pub library: String,
+    pub version: Option<String>,
+    pub name: String,
+}
+
+impl Display for RibImport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "use {}@{}::{};", self.library, version, self.name),
+            None => write!(f, "use {}::{};", self.library, self.name),
+        }
+    }
+}
+
+// Splits the leading `use` statements of a script from the rest of it
+pub fn split_imports(text: &str) -> Result<(Vec<RibImport>, &str), String> {
+    let mut imports = vec![];
+    let mut rest = text.trim_start();
+
+    while let Some(statement) = rest.strip_prefix("use ") {
+        let (import, remaining) = statement
+            .split_once(';')
+            .ok_or("Missing `;` after a use statement")?;
+
+        let (library, name) = import
+            .trim()
+            .split_once("::")
+            .ok_or(format!("Invalid use statement `use {};`", import.trim()))?;
+
+        let (library, version) = match library.split_once('@') {
+            Some((library, version)) => (library.trim(), Some(version.trim().to_string())),
+            None => (library.trim(), None),
+        };
+
+        if library.is_empty() || name.trim().is_empty() {
+            return Err(format!("Invalid use statement `use {};`", import.trim()));
+        }
+
+        imports.push(RibImport {
+            library: library.to_string(),
+            version,
+            name: name.trim().to_string(),
+        });
+
+        rest = remaining.trim_start();
+    }
+
+    Ok((imports, rest))
+}
+
+// Links the bindings imported by a script in front of it, with the libraries resolved by `resolve`.
+// Scripts without imports are returned as they are parsed.
+pub fn link_imports<F>(text: &str, resolve: F) -> Result<Expr, String>
+where
+    F: Fn(&RibImport) -> Result<RibLibrary, String>,
+{
+    let (imports, script) = split_imports(text)?;
+    let script = from_string(script)?;
+
+    if imports.is_empty() {
+        return Ok(script);
+    }
+
+    // The imports of the same library version are linked together, so shared bindings appear once
+    let mut libraries: Vec<(&RibImport, Vec<String>)> = vec![];
+    for import in &imports {
+        match libraries.iter_mut().find(|(library, _)| {
+            library.library == import.library && library.version == import.version
+        }) {
+            Some((_, names)) => names.push(import.name.clone()),
+            None => libraries.push((import, vec![import.name.clone()])),
+        }
+    }
+
+    let mut exprs = vec![];
+    for (import, names) in libraries {
+        let library = resolve(import)?;
+        let bindings = library
+            .bindings_of(&names)
+            .map_err(|err| format!("{}: {}", import.library, err))?;
+        exprs.extend(bindings);
+    }
+
+    match script {
+        Expr::ExprBlock {
+            exprs: script_exprs,
+            ..
+        } => exprs.extend(script_exprs),
+        script => exprs.push(script),
+    }
+
+    Ok(Expr::expr_block(exprs))
+}
+
+fn referenced_identifiers(expr: &Expr) -> Vec<String> {
+    let mut identifiers = vec![];
+    let mut queue = VecDeque::new();
+    queue.push_back(expr);
+
+    while let Some(expr) = queue.pop_back() {
+        if let Expr::Identifier { variable_id, .. } = expr {
+            identifiers.push(variable_id.name());
+        }
+        expr.visit_children_bottom_up(&mut queue);
+    }
+
+    identifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn imported_bindings_are_linked_with_their_dependencies() {
+        let library = RibLibrary::from_text(
+            r#"
+              let prefix = "user";
+              let unused = "unused";
+              let tenant = request.headers.tenant;
+              let worker = "${prefix}-${tenant}";
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            library.exports(),
+            vec!["prefix", "unused", "tenant", "worker"]
+        );
+
+        let linked = link_imports("use users@1.0::worker; worker", |import| {
+            assert_eq!(import.library, "users");
+            assert_eq!(import.version, Some("1.0".to_string()));
+            Ok(library.clone())
+        })
+        .unwrap();
+
+        let expected = Expr::from_text(
+            r#"
+              let prefix = "user";
+              let tenant = request.headers.tenant;
+              let worker = "${prefix}-${tenant}";
+              worker
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(crate::to_string(&linked), crate::to_string(&expected));
+
+        let missing = link_imports("use users::other; other", |_| Ok(library.clone()));
+        assert!(missing.is_err());
+
+        assert!(RibLibrary::from_text(r#"let x = 1; "not a binding""#).is_err());
+    }
+}
@@ -4,8 +4,8 @@ use crate::parser::rib_expr::rib_expr;
 use crate::parser::RibParseError;
 use crate::rib_source_span::GetSourcePosition;
 use crate::Expr;
-use combine::parser::char::{char, spaces};
-use combine::{ParseError, Parser};
+use combine::parser::char::{alpha_num, char, spaces, string};
+use combine::{attempt, between, not_followed_by, optional, ParseError, Parser};
 
 pub fn worker_function_invoke<Input>() -> impl Parser<Input, Output = Expr>
 where
@@ -15,8 +15,13 @@ where
     >,
     Input::Position: GetSourcePosition,
 {
-    (identifier().skip(spaces()), char('.'), rib_expr())
-        .and_then(|(worker_variable, _, call)| match call {
+    (
+        identifier().skip(spaces()),
+        char('.'),
+        rib_expr(),
+        optional(idempotency_key()),
+    )
+        .and_then(|(worker_variable, _, call, idempotency_key)| match call {
             Expr::Call {
                 call_type: CallType::Function { function_name, .. },
                 generic_type_parameter,
@@ -29,19 +34,89 @@ where
                 let worker_variable_with_source_span =
                     worker_variable.with_source_span(source_span.clone());
 
-                Ok(Expr::invoke_worker_function(
+                let invoke = Expr::invoke_worker_function(
                     worker_variable_with_source_span,
                     function_name,
                     generic_type_parameter,
                     args,
-                )
-                .with_source_span(source_span))
+                );
+
+                let invoke = match idempotency_key {
+                    Some(key) => invoke.with_idempotency_key(key),
+                    None => invoke,
+                };
+
+                Ok(invoke.with_source_span(source_span))
             }
+            // Functions of a resource named through the resource, such as
+            // `worker.cart.new(user-id)` for the constructor or `worker.cart.create(..)`
+            // for the static function `[static]cart.create`
+            Expr::InvokeMethodLazy {
+                lhs,
+                method,
+                generic_type_parameter,
+                args,
+                idempotency_key: inner_idempotency_key,
+                source_span,
+                ..
+            } => match lhs.as_ref() {
+                Expr::Identifier { variable_id, .. } => {
+                    let resource_name = variable_id.name();
+
+                    let function_name = if method == "new" {
+                        resource_name
+                    } else {
+                        format!("{}.{}", resource_name, method)
+                    };
+
+                    let worker_variable_with_source_span =
+                        worker_variable.with_source_span(source_span.clone());
+
+                    let invoke = Expr::invoke_worker_function(
+                        worker_variable_with_source_span,
+                        function_name,
+                        generic_type_parameter,
+                        args,
+                    );
+
+                    let invoke = match idempotency_key.or(inner_idempotency_key.map(|k| *k)) {
+                        Some(key) => invoke.with_idempotency_key(key),
+                        None => invoke,
+                    };
+
+                    Ok(invoke.with_source_span(source_span))
+                }
+                _ => Err(RibParseError::Message("Invalid function call".to_string())),
+            },
             _ => Err(RibParseError::Message("Invalid function call".to_string())),
         })
         .message("Invalid function call")
 }
 
+// `with idempotency-key(<expr>)` following a worker function invocation
+fn idempotency_key<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: combine::Stream<Token = char>,
+    RibParseError: Into<
+        <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError,
+    >,
+    Input::Position: GetSourcePosition,
+{
+    attempt(
+        spaces()
+            .with(string("with"))
+            .skip(not_followed_by(alpha_num().or(char('-')).or(char('_'))))
+            .skip(spaces())
+            .with(string("idempotency-key"))
+            .skip(spaces()),
+    )
+    .with(between(
+        char('(').skip(spaces()),
+        char(')').skip(spaces()),
+        rib_expr(),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,4 +313,91 @@ mod tests {
         ]);
         assert_eq!(expr, expected);
     }
+
+    #[test]
+    fn test_worker_function_invoke_with_idempotency_key() {
+        let expr =
+            Expr::from_text(r#"worker.function-name(foo) with idempotency-key("my-key")"#).unwrap();
+        let worker_variable = Expr::identifier_global("worker", None);
+        let function_name = "function-name".to_string();
+
+        assert_eq!(
+            expr,
+            Expr::invoke_worker_function(
+                worker_variable,
+                function_name,
+                None,
+                vec![Expr::identifier_global("foo", None)]
+            )
+            .with_idempotency_key(Expr::literal("my-key"))
+        );
+    }
+
+    #[test]
+    fn test_worker_function_invoke_with_idempotency_key_expr() {
+        let rib_expr = r#"
+          let worker = instance("my-worker");
+          worker.function-name(foo) with idempotency-key(request.headers.x-request-id)
+        "#;
+        let expr = Expr::from_text(rib_expr).unwrap();
+        let worker_variable = Expr::identifier_global("worker", None);
+        let function_name = "function-name".to_string();
+
+        let expected = Expr::expr_block(vec![
+            Expr::let_binding(
+                "worker",
+                Expr::call_worker_function(
+                    DynamicParsedFunctionName::parse("instance").unwrap(),
+                    None,
+                    None,
+                    vec![Expr::literal("my-worker")],
+                ),
+                None,
+            ),
+            Expr::invoke_worker_function(
+                worker_variable,
+                function_name,
+                None,
+                vec![Expr::identifier_global("foo", None)],
+            )
+            .with_idempotency_key(Expr::select_field(
+                Expr::select_field(Expr::identifier_global("request", None), "headers", None),
+                "x-request-id",
+                None,
+            )),
+        ]);
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn test_worker_resource_static_function_invoke() {
+        let expr = Expr::from_text(r#"worker.cart.create(foo)"#).unwrap();
+        let worker_variable = Expr::identifier_global("worker", None);
+
+        assert_eq!(
+            expr,
+            Expr::invoke_worker_function(
+                worker_variable,
+                "cart.create".to_string(),
+                None,
+                vec![Expr::identifier_global("foo", None)]
+            )
+        );
+    }
+
+    #[test]
+    fn test_worker_resource_named_constructor_invoke() {
+        let expr = Expr::from_text(r#"worker.cart.new(foo)"#).unwrap();
+        let worker_variable = Expr::identifier_global("worker", None);
+
+        assert_eq!(
+            expr,
+            Expr::invoke_worker_function(
+                worker_variable,
+                "cart".to_string(),
+                None,
+                vec![Expr::identifier_global("foo", None)]
+            )
+        );
+    }
 }
@@ -3,8 +3,8 @@ use crate::type_checker::{
 };
 use crate::{
     ActualType, AmbiguousTypeError, CustomError, ExpectedType, Expr, FunctionCallError,
-    InvalidPatternMatchError, InvalidWorkerName, MultipleUnResolvedTypesError, TypeMismatchError,
-    TypeName, UnResolvedTypesError,
+    InvalidIdempotencyKey, InvalidPatternMatchError, InvalidWorkerName,
+    MultipleUnResolvedTypesError, TypeMismatchError, TypeName, UnResolvedTypesError,
 };
 use std::fmt;
 use std::fmt::{Debug, Display};
@@ -277,6 +277,18 @@ impl From<InvalidWorkerName> for RibCompilationError {
     }
 }
 
+impl From<InvalidIdempotencyKey> for RibCompilationError {
+    fn from(value: InvalidIdempotencyKey) -> Self {
+        RibCompilationError {
+            cause: value.message,
+            expr: value.idempotency_key_expr,
+            immediate_parent: None,
+            additional_error_details: vec![],
+            help_messages: vec![],
+        }
+    }
+}
+
 impl From<InvalidMathExprError> for RibCompilationError {
     fn from(value: InvalidMathExprError) -> Self {
         let expr = match value {
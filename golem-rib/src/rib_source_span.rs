@@ -18,6 +18,26 @@ impl SourceSpan {
         self.start.column
     }
 
+    pub fn end_line(&self) -> i32 {
+        self.end.line
+    }
+
+    pub fn end_column(&self) -> i32 {
+        self.end.column
+    }
+
+    // Spans of the expressions synthesised by the compiler have no position
+    pub fn is_empty(&self) -> bool {
+        self.start.line == 0 && self.end.line == 0
+    }
+
+    // Whether `other` is within this span. Note that `==` on spans is always true, as spans
+    // don't take part in the equality of expressions.
+    pub fn contains(&self, other: &SourceSpan) -> bool {
+        (self.start.line, self.start.column) <= (other.start.line, other.start.column)
+            && (other.end.line, other.end.column) <= (self.end.line, self.end.column)
+    }
+
     pub fn new(start: SourcePosition, end: SourcePosition) -> SourceSpan {
         SourceSpan { start, end }
     }
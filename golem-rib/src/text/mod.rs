@@ -1933,4 +1933,24 @@ mod if_cond_tests {
         let output_expr = from_string(expr_str.as_str()).unwrap();
         assert_eq!((expr_str, input_expr), (expected_str, output_expr));
     }
+
+    #[test]
+    fn test_round_trip_worker_function_invoke_with_idempotency_key() {
+        let input_expr = Expr::invoke_worker_function(
+            Expr::identifier_global("worker", None),
+            "foo".to_string(),
+            None,
+            vec![Expr::identifier_global("bar", None)],
+        )
+        .with_idempotency_key(Expr::select_field(
+            Expr::identifier_global("request", None),
+            "id",
+            None,
+        ));
+
+        let expr_str = to_string(&input_expr).unwrap();
+        let expected_str = r#"worker.foo(bar) with idempotency-key(request.id)"#.to_string();
+        let output_expr = from_string(expr_str.as_str()).unwrap();
+        assert_eq!((expr_str, input_expr), (expected_str, output_expr));
+    }
 }
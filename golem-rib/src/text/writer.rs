@@ -438,6 +438,7 @@ impl<W: Write> Writer<W> {
                 method,
                 generic_type_parameter,
                 args,
+                idempotency_key,
                 ..
             } => {
                 self.write_expr(lhs)?;
@@ -456,7 +457,13 @@ impl<W: Write> Writer<W> {
                     }
                     self.write_expr(param)?;
                 }
-                self.write_display(")")
+                self.write_display(")")?;
+                if let Some(idempotency_key) = idempotency_key {
+                    self.write_str(" with idempotency-key(")?;
+                    self.write_expr(idempotency_key)?;
+                    self.write_str(")")?;
+                }
+                Ok(())
             }
         }
     }
@@ -0,0 +1,56 @@
+use crate::{Expr, InvalidIdempotencyKey};
+use std::collections::VecDeque;
+
+// Capture all idempotency keys of worker function invocations
+// and see if they are resolved to a string type
+pub fn check_invalid_idempotency_key(expr: &Expr) -> Result<(), InvalidIdempotencyKey> {
+    let mut queue = VecDeque::new();
+    queue.push_back(expr);
+
+    while let Some(expr) = queue.pop_back() {
+        match expr {
+            Expr::Call { call_type, .. } => {
+                if let Some(idempotency_key) = call_type.idempotency_key_expr() {
+                    internal::check_idempotency_key(idempotency_key)?;
+                }
+            }
+            Expr::InvokeMethodLazy {
+                idempotency_key: Some(idempotency_key),
+                ..
+            } => {
+                internal::check_idempotency_key(idempotency_key)?;
+            }
+            _ => {}
+        }
+
+        expr.visit_children_bottom_up(&mut queue);
+    }
+
+    Ok(())
+}
+
+mod internal {
+    use crate::type_refinement::precise_types::StringType;
+    use crate::type_refinement::TypeRefinement;
+    use crate::{Expr, InvalidIdempotencyKey, TypeName};
+
+    pub(crate) fn check_idempotency_key(
+        idempotency_key: &Expr,
+    ) -> Result<(), InvalidIdempotencyKey> {
+        let inferred_type = idempotency_key.inferred_type();
+
+        match StringType::refine(&inferred_type) {
+            Some(_) => Ok(()),
+            None => {
+                let type_name = TypeName::try_from(inferred_type.clone())
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|_| "unknown".to_string());
+
+                Err(InvalidIdempotencyKey {
+                    idempotency_key_expr: idempotency_key.clone(),
+                    message: format!("expected string as idempotency key, found {}", type_name),
+                })
+            }
+        }
+    }
+}
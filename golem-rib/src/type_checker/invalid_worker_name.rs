@@ -18,6 +18,7 @@ pub fn check_invalid_worker_name(expr: &Expr) -> Result<(), InvalidWorkerName> {
                 }
                 CallType::VariantConstructor(_) => {}
                 CallType::EnumConstructor(_) => {}
+                CallType::EventEmission => {}
                 CallType::InstanceCreation(InstanceCreationType::Resource {
                     worker_name, ..
                 }) => {
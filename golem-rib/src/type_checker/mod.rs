@@ -4,16 +4,19 @@ pub(crate) use invalid_expr::*;
 pub(crate) use invalid_math_expr::*;
 pub(crate) use missing_fields::*;
 pub use path::*;
+pub(crate) use static_worker_name::*;
 pub(crate) use type_mismatch::*;
 pub(crate) use unresolved_types::*;
 
 mod check_instance_returns;
 mod exhaustive_pattern_match;
 mod invalid_expr;
+mod invalid_idempotency_key;
 mod invalid_math_expr;
 mod invalid_worker_name;
 mod missing_fields;
 mod path;
+mod static_worker_name;
 mod type_check_in_function_calls;
 mod type_mismatch;
 mod unresolved_types;
@@ -21,6 +24,7 @@ mod unresolved_types;
 use crate::rib_compilation_error::RibCompilationError;
 use crate::type_checker::exhaustive_pattern_match::check_exhaustive_pattern_match;
 use crate::type_checker::invalid_expr::check_invalid_expr;
+use crate::type_checker::invalid_idempotency_key::check_invalid_idempotency_key;
 use crate::type_checker::invalid_math_expr::check_invalid_math_expr;
 use crate::type_checker::invalid_worker_name::check_invalid_worker_name;
 use crate::type_checker::type_check_in_function_calls::check_type_error_in_function_calls;
@@ -33,6 +37,7 @@ pub fn type_check(
     check_type_error_in_function_calls(expr, function_type_registry)?;
     check_unresolved_types(expr)?;
     check_invalid_worker_name(expr)?;
+    check_invalid_idempotency_key(expr)?;
     check_invalid_expr(expr)?;
     check_invalid_program_return(expr)?;
     check_invalid_math_expr(expr)?;
@@ -0,0 +1,150 @@
+use crate::call_type::{CallType, InstanceCreationType};
+use crate::{Expr, InvalidWorkerName};
+use std::collections::VecDeque;
+
+// With strict worker name typing, capture all worker names and see if they are known
+// at compile time, as opposed to being computed from the inputs such as `request`
+pub fn check_static_worker_name(expr: &Expr) -> Result<(), InvalidWorkerName> {
+    let let_bindings = internal::collect_let_bindings(expr);
+
+    let mut queue = VecDeque::new();
+    queue.push_back(expr);
+
+    while let Some(expr) = queue.pop_back() {
+        if let Expr::Call { call_type, .. } = expr {
+            match call_type {
+                CallType::InstanceCreation(InstanceCreationType::Worker { worker_name })
+                | CallType::InstanceCreation(InstanceCreationType::Resource {
+                    worker_name, ..
+                })
+                | CallType::Function {
+                    worker: worker_name,
+                    ..
+                } => {
+                    if let Some(worker_name) = worker_name {
+                        internal::check_worker_name(worker_name, &let_bindings)?;
+                    }
+                }
+                CallType::VariantConstructor(_) => {}
+                CallType::EnumConstructor(_) => {}
+                CallType::EventEmission => {}
+            }
+        }
+
+        expr.visit_children_bottom_up(&mut queue);
+    }
+
+    Ok(())
+}
+
+mod internal {
+    use crate::{Expr, InvalidWorkerName, VariableId};
+    use std::collections::{HashMap, HashSet, VecDeque};
+
+    pub(crate) fn collect_let_bindings(expr: &Expr) -> HashMap<VariableId, &Expr> {
+        let mut let_bindings = HashMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(expr);
+
+        while let Some(expr) = queue.pop_back() {
+            if let Expr::Let {
+                variable_id, expr, ..
+            } = expr
+            {
+                let_bindings.insert(variable_id.clone(), expr.as_ref());
+            }
+
+            expr.visit_children_bottom_up(&mut queue);
+        }
+
+        let_bindings
+    }
+
+    pub(crate) fn check_worker_name(
+        worker_name: &Expr,
+        let_bindings: &HashMap<VariableId, &Expr>,
+    ) -> Result<(), InvalidWorkerName> {
+        if is_static(worker_name, let_bindings, &mut HashSet::new()) {
+            Ok(())
+        } else {
+            Err(InvalidWorkerName {
+                worker_name_expr: worker_name.clone(),
+                message: "worker name is required to be known at compile time".to_string(),
+            })
+        }
+    }
+
+    // String literals, interpolations of them, and local variables bound to them
+    fn is_static<'a>(
+        expr: &'a Expr,
+        let_bindings: &'a HashMap<VariableId, &'a Expr>,
+        visited: &mut HashSet<&'a VariableId>,
+    ) -> bool {
+        match expr {
+            Expr::Literal { .. } => true,
+            Expr::Concat { exprs, .. } => exprs
+                .iter()
+                .all(|expr| is_static(expr, let_bindings, visited)),
+            Expr::Identifier { variable_id, .. } => match let_bindings.get_key_value(variable_id) {
+                Some((variable_id, bound_expr)) if visited.insert(variable_id) => {
+                    is_static(bound_expr, let_bindings, visited)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compile_with_worker_name_typing, Expr, WorkerNameTyping};
+    use golem_wasm_ast::analysis::analysed_type::str;
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+    };
+    use test_r::test;
+
+    fn compile(rib: &str, worker_name_typing: WorkerNameTyping) -> Result<(), String> {
+        let metadata = vec![AnalysedExport::Function(AnalysedFunction {
+            name: "foo".to_string(),
+            parameters: vec![AnalysedFunctionParameter {
+                name: "arg1".to_string(),
+                typ: str(),
+            }],
+            results: vec![AnalysedFunctionResult {
+                name: None,
+                typ: str(),
+            }],
+        })];
+
+        let expr = Expr::from_text(rib).unwrap();
+
+        compile_with_worker_name_typing(&expr, &metadata, None, &vec![], worker_name_typing)
+            .map(|_| ())
+            .map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn test_strict_worker_name_typing() {
+        let static_worker_names = r#"
+          let region = "eu";
+          let worker = instance("cart-${region}");
+          worker.foo("bar")
+        "#;
+
+        assert!(compile(static_worker_names, WorkerNameTyping::Strict).is_ok());
+
+        let dynamic_worker_name = r#"
+          let user-id: string = request.path.user-id;
+          let worker = instance(user-id);
+          worker.foo("bar")
+        "#;
+
+        assert!(compile(dynamic_worker_name, WorkerNameTyping::Inferred).is_ok());
+
+        let error = compile(dynamic_worker_name, WorkerNameTyping::Strict).unwrap_err();
+
+        assert!(error.contains("worker name is required to be known at compile time"));
+    }
+}
@@ -22,6 +22,7 @@ pub fn check_type_error_in_function_calls(
                 call_type, args, ..
             } => match call_type {
                 CallType::InstanceCreation(_) => {}
+                CallType::EventEmission => {}
                 call_type => internal::check_type_mismatch_in_function_call(
                     call_type,
                     args,
@@ -34,6 +34,7 @@ pub fn check_unresolved_types(expr: &Expr) -> Result<(), UnResolvedTypesError> {
             outer_expr @ Expr::InvokeMethodLazy {
                 lhs,
                 args,
+                idempotency_key,
                 inferred_type,
                 ..
             } => {
@@ -43,6 +44,10 @@ pub fn check_unresolved_types(expr: &Expr) -> Result<(), UnResolvedTypesError> {
                     queue.push_back(QueuedExpr::new(arg, outer_expr));
                 }
 
+                if let Some(idempotency_key) = idempotency_key {
+                    queue.push_back(QueuedExpr::new(idempotency_key, outer_expr));
+                }
+
                 if inferred_type.un_resolved() {
                     return Err(UnResolvedTypesError::from(expr, parent));
                 }
@@ -331,6 +336,10 @@ pub fn check_unresolved_types(expr: &Expr) -> Result<(), UnResolvedTypesError> {
                     queue.push_back(QueuedExpr::new(worker_name, outer_expr));
                 }
 
+                if let Some(idempotency_key) = call_type.idempotency_key_expr() {
+                    queue.push_back(QueuedExpr::new(idempotency_key, outer_expr));
+                }
+
                 let additional_message = match call_type {
                     CallType::Function { function_name, .. } => {
                         format!(
@@ -373,6 +382,9 @@ pub fn check_unresolved_types(expr: &Expr) -> Result<(), UnResolvedTypesError> {
                             )
                         }
                     },
+                    CallType::EventEmission => {
+                        "cannot determine the type of the event emission".to_string()
+                    }
                 };
 
                 if inferred_type.un_resolved() {
@@ -126,6 +126,24 @@ mod internal {
                 }
             }
 
+            // `emit(event-name, payload)` takes the name of the event as a string and a payload
+            // of any type, and has no result
+            CallType::EventEmission => {
+                if args.len() != 2 {
+                    return Err(FunctionCallError::ArgumentSizeMisMatch {
+                        function_name: "emit".to_string(),
+                        expr: original_expr.clone(),
+                        expected: 2,
+                        provided: args.len(),
+                    });
+                }
+
+                args[0].add_infer_type_mut(InferredType::Str);
+                *function_result_inferred_type = InferredType::Sequence(vec![]);
+
+                Ok(())
+            }
+
             CallType::VariantConstructor(variant_name) => {
                 let registry_key = RegistryKey::FunctionName(variant_name.clone());
                 infer_args_and_result_type(
@@ -305,6 +305,11 @@ pub struct InvalidWorkerName {
     pub message: String,
 }
 
+pub struct InvalidIdempotencyKey {
+    pub idempotency_key_expr: Expr,
+    pub message: String,
+}
+
 #[derive(Clone)]
 pub struct CustomError {
     pub expr: Expr,
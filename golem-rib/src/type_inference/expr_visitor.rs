@@ -81,7 +81,8 @@ pub fn visit_children_bottom_up_mut<'a>(expr: &'a mut Expr, queue: &mut VecDeque
             inferred_type,
             ..
         } => {
-            let (exprs, worker) = internal::get_expressions_in_call_type_mut(call_type);
+            let (exprs, worker, idempotency_key) =
+                internal::get_expressions_in_call_type_mut(call_type);
             if let Some(exprs) = exprs {
                 queue.extend(exprs.iter_mut())
             }
@@ -97,7 +98,11 @@ pub fn visit_children_bottom_up_mut<'a>(expr: &'a mut Expr, queue: &mut VecDeque
                 }
             }
 
-            queue.extend(args.iter_mut())
+            queue.extend(args.iter_mut());
+
+            if let Some(idempotency_key) = idempotency_key {
+                queue.push_back(idempotency_key);
+            }
         }
         Expr::Unwrap { expr, .. } => queue.push_back(&mut *expr), // not yet needed
         Expr::And { lhs, rhs, .. } => {
@@ -133,6 +138,7 @@ pub fn visit_children_bottom_up_mut<'a>(expr: &'a mut Expr, queue: &mut VecDeque
         Expr::InvokeMethodLazy {
             lhs,
             args,
+            idempotency_key,
             inferred_type,
             ..
         } => {
@@ -144,6 +150,10 @@ pub fn visit_children_bottom_up_mut<'a>(expr: &'a mut Expr, queue: &mut VecDeque
 
             queue.push_back(lhs);
             queue.extend(args.iter_mut());
+
+            if let Some(idempotency_key) = idempotency_key {
+                queue.push_back(idempotency_key);
+            }
         }
 
         Expr::GetTag { expr, .. } => {
@@ -240,6 +250,7 @@ pub fn visit_children_bottom_up<'a>(expr: &'a Expr, queue: &mut VecDeque<&'a Exp
             if let CallType::Function {
                 function_name,
                 worker,
+                ..
             } = call_type
             {
                 if let Some(params) = function_name.function.raw_resource_params() {
@@ -275,7 +286,11 @@ pub fn visit_children_bottom_up<'a>(expr: &'a Expr, queue: &mut VecDeque<&'a Exp
                 }
             }
 
-            queue.extend(args.iter())
+            queue.extend(args.iter());
+
+            if let Some(idempotency_key) = call_type.idempotency_key_expr() {
+                queue.push_back(idempotency_key);
+            }
         }
         Expr::Unwrap { expr, .. } => queue.push_back(expr),
         Expr::And { lhs, rhs, .. } => {
@@ -310,6 +325,7 @@ pub fn visit_children_bottom_up<'a>(expr: &'a Expr, queue: &mut VecDeque<&'a Exp
         Expr::InvokeMethodLazy {
             lhs,
             args,
+            idempotency_key,
             inferred_type,
             ..
         } => {
@@ -321,6 +337,10 @@ pub fn visit_children_bottom_up<'a>(expr: &'a Expr, queue: &mut VecDeque<&'a Exp
 
             queue.push_back(lhs);
             queue.extend(args.iter());
+
+            if let Some(idempotency_key) = idempotency_key {
+                queue.push_back(idempotency_key);
+            }
         }
 
         Expr::Literal { .. } => {}
@@ -439,7 +459,8 @@ pub fn visit_children_mut_top_down<'a>(expr: &'a mut Expr, queue: &mut VecDeque<
             inferred_type,
             ..
         } => {
-            let (exprs, worker) = internal::get_expressions_in_call_type_mut(call_type);
+            let (exprs, worker, idempotency_key) =
+                internal::get_expressions_in_call_type_mut(call_type);
 
             if let Some(exprs) = exprs {
                 for expr in exprs.iter_mut() {
@@ -461,6 +482,10 @@ pub fn visit_children_mut_top_down<'a>(expr: &'a mut Expr, queue: &mut VecDeque<
             for expr in args.iter_mut() {
                 queue.push_front(expr);
             }
+
+            if let Some(idempotency_key) = idempotency_key {
+                queue.push_front(idempotency_key);
+            }
         }
         Expr::GetTag { expr, .. } => {
             queue.push_front(&mut *expr);
@@ -487,6 +512,7 @@ pub fn visit_children_mut_top_down<'a>(expr: &'a mut Expr, queue: &mut VecDeque<
         Expr::InvokeMethodLazy {
             lhs,
             args,
+            idempotency_key,
             inferred_type,
             ..
         } => {
@@ -499,6 +525,10 @@ pub fn visit_children_mut_top_down<'a>(expr: &'a mut Expr, queue: &mut VecDeque<
             for arg in args.iter_mut() {
                 queue.push_front(arg);
             }
+
+            if let Some(idempotency_key) = idempotency_key {
+                queue.push_front(idempotency_key);
+            }
         }
 
         Expr::Unwrap { expr, .. } => queue.push_front(&mut *expr),
@@ -516,27 +546,39 @@ mod internal {
     use crate::call_type::{CallType, InstanceCreationType};
     use crate::Expr;
 
-    // (args, worker in calls, worker in inferred type)
+    // (resource params, worker in calls, idempotency key in calls)
+    #[allow(clippy::type_complexity)]
     pub(crate) fn get_expressions_in_call_type_mut(
         call_type: &mut CallType,
-    ) -> (Option<&mut Vec<Expr>>, Option<&mut Box<Expr>>) {
+    ) -> (
+        Option<&mut Vec<Expr>>,
+        Option<&mut Box<Expr>>,
+        Option<&mut Box<Expr>>,
+    ) {
         match call_type {
             CallType::Function {
                 function_name,
                 worker,
+                idempotency_key,
             } => (
                 function_name.function.raw_resource_params_mut(),
                 worker.as_mut(),
+                idempotency_key.as_mut(),
             ),
 
             CallType::InstanceCreation(instance_creation) => match instance_creation {
-                InstanceCreationType::Worker { worker_name, .. } => (None, worker_name.as_mut()),
+                InstanceCreationType::Worker { worker_name, .. } => {
+                    (None, worker_name.as_mut(), None)
+                }
 
-                InstanceCreationType::Resource { worker_name, .. } => (None, worker_name.as_mut()),
+                InstanceCreationType::Resource { worker_name, .. } => {
+                    (None, worker_name.as_mut(), None)
+                }
             },
 
-            CallType::VariantConstructor(_) => (None, None),
-            CallType::EnumConstructor(_) => (None, None),
+            CallType::VariantConstructor(_) => (None, None, None),
+            CallType::EnumConstructor(_) => (None, None, None),
+            CallType::EventEmission => (None, None, None),
         }
     }
 }
@@ -0,0 +1,73 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Expr, InferredType, RibInputTypeInfo};
+use std::collections::VecDeque;
+
+// Binds the types of global variables (such as `request`, `env` or `auth`)
+// that the host registered up front. The rest of the type inference phases
+// will then check the usages of these global variables against the registered types.
+pub fn bind_global_input_types(expr: &mut Expr, rib_input_types: &RibInputTypeInfo) {
+    let mut queue = VecDeque::new();
+    queue.push_back(expr);
+
+    while let Some(expr) = queue.pop_back() {
+        match expr {
+            Expr::Identifier {
+                variable_id,
+                inferred_type,
+                ..
+            } => {
+                if variable_id.is_global() {
+                    if let Some(analysed_type) = rib_input_types.types.get(&variable_id.name()) {
+                        *inferred_type =
+                            inferred_type.merge(InferredType::from(analysed_type.clone()));
+                    }
+                }
+            }
+            _ => expr.visit_children_mut_bottom_up(&mut queue),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::{Expr, InferredType, RibInputTypeInfo};
+    use golem_wasm_ast::analysis::analysed_type::{field, record, str};
+
+    #[test]
+    fn test_bind_global_input_types() {
+        let request_type = record(vec![field("path", record(vec![field("id", str())]))]);
+
+        let rib_input_types =
+            RibInputTypeInfo::empty().with_global("request", request_type.clone());
+
+        let mut expr = Expr::from_text("request").unwrap();
+        expr.bind_global_input_types(&rib_input_types);
+
+        assert_eq!(expr.inferred_type(), InferredType::from(request_type));
+    }
+
+    #[test]
+    fn test_bind_global_input_types_ignores_unregistered_globals() {
+        let rib_input_types = RibInputTypeInfo::empty().with_global("request", str());
+
+        let mut expr = Expr::from_text("env").unwrap();
+        expr.bind_global_input_types(&rib_input_types);
+
+        assert_eq!(expr.inferred_type(), InferredType::Unknown);
+    }
+}
@@ -522,12 +522,14 @@ mod internal {
                     method,
                     generic_type_parameter,
                     args,
+                    idempotency_key,
                     inferred_type,
                     source_span,
                 } => handle_invoke_method(
                     lhs,
                     method,
                     args,
+                    idempotency_key,
                     generic_type_parameter.clone(),
                     inferred_type,
                     &mut temp_stack,
@@ -1069,15 +1071,33 @@ mod internal {
         temp_stack.push_front((new_binary, false));
     }
 
+    // The idempotency key is the last child visited in a worker function invocation
+    fn pop_idempotency_key(
+        idempotency_key: Option<&Expr>,
+        temp_stack: &mut VecDeque<(Expr, bool)>,
+    ) -> Option<Box<Expr>> {
+        idempotency_key.map(|idempotency_key| {
+            Box::new(
+                temp_stack
+                    .pop_front()
+                    .map(|x| x.0)
+                    .unwrap_or(idempotency_key.clone()),
+            )
+        })
+    }
+
     pub(crate) fn handle_invoke_method(
         original_lhs_expr: &Expr,
         method_name: &str,
         args: &[Expr],
+        idempotency_key: &Option<Box<Expr>>,
         generic_type_parameter: Option<GenericTypeParameter>,
         inferred_type: &InferredType,
         temp_stack: &mut VecDeque<(Expr, bool)>,
         source_span: &SourceSpan,
     ) {
+        let new_idempotency_key = pop_idempotency_key(idempotency_key.as_deref(), temp_stack);
+
         let mut new_arg_exprs = vec![];
 
         for expr in args.iter().rev() {
@@ -1107,6 +1127,7 @@ mod internal {
                     method: method_name.to_string(),
                     generic_type_parameter,
                     args: new_arg_exprs,
+                    idempotency_key: new_idempotency_key,
                     inferred_type: InferredType::Instance {
                         instance_type: new_instance_type,
                     },
@@ -1120,6 +1141,7 @@ mod internal {
                     method: method_name.to_string(),
                     generic_type_parameter,
                     args: new_arg_exprs,
+                    idempotency_key: new_idempotency_key,
                     inferred_type: inferred_type.clone(),
                     source_span: source_span.clone(),
                 };
@@ -1132,6 +1154,7 @@ mod internal {
                 method: method_name.to_string(),
                 generic_type_parameter,
                 args: new_arg_exprs,
+                idempotency_key: new_idempotency_key,
                 inferred_type: inferred_type.clone(),
                 source_span: source_span.clone(),
             };
@@ -1148,6 +1171,8 @@ mod internal {
         temp_stack: &mut VecDeque<(Expr, bool)>,
         source_span: &SourceSpan,
     ) {
+        let new_idempotency_key = pop_idempotency_key(call_type.idempotency_key_expr(), temp_stack);
+
         let mut new_arg_exprs = vec![];
 
         // retrieving all argument from the stack
@@ -1201,6 +1226,7 @@ mod internal {
             CallType::Function {
                 function_name,
                 worker,
+                ..
             } => {
                 let mut function_name = function_name.clone();
 
@@ -1264,6 +1290,7 @@ mod internal {
                         call_type: CallType::Function {
                             function_name,
                             worker: Some(Box::new(worker)),
+                            idempotency_key: new_idempotency_key,
                         },
                         generic_type_parameter: generic_type_parameter.clone(),
                         args: new_arg_exprs,
@@ -1275,6 +1302,7 @@ mod internal {
                         call_type: CallType::Function {
                             function_name,
                             worker: None,
+                            idempotency_key: new_idempotency_key,
                         },
                         generic_type_parameter: generic_type_parameter.clone(),
                         args: new_arg_exprs,
@@ -1307,6 +1335,17 @@ mod internal {
                 };
                 temp_stack.push_front((new_call, false));
             }
+
+            CallType::EventEmission => {
+                let new_call = Expr::Call {
+                    call_type: CallType::EventEmission,
+                    generic_type_parameter: None,
+                    args: new_arg_exprs,
+                    inferred_type: inferred_type.clone(),
+                    source_span: source_span.clone(),
+                };
+                temp_stack.push_front((new_call, false));
+            }
         }
     }
 
@@ -0,0 +1,65 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::call_type::CallType;
+use crate::{Expr, InferredType};
+use std::collections::VecDeque;
+
+// Identifying `emit(event-name, payload)` out of all parsed function calls.
+// Similar to `instance`, `emit` is a builtin, and a call to it without a worker
+// publishes an event rather than invoking a worker function. The types of its
+// arguments are resolved along with the rest of the function calls.
+pub fn identify_event_emission(expr: &mut Expr) {
+    let mut queue = VecDeque::new();
+    queue.push_back(expr);
+
+    while let Some(expr) = queue.pop_back() {
+        if let Expr::Call {
+            call_type,
+            inferred_type,
+            ..
+        } = expr
+        {
+            if internal::is_event_emission(call_type) {
+                *call_type = CallType::EventEmission;
+                *inferred_type = InferredType::Sequence(vec![]);
+            }
+        }
+
+        expr.visit_children_mut_bottom_up(&mut queue);
+    }
+}
+
+mod internal {
+    use crate::call_type::CallType;
+    use crate::{ParsedFunctionReference, ParsedFunctionSite};
+
+    pub(crate) fn is_event_emission(call_type: &CallType) -> bool {
+        match call_type {
+            CallType::Function {
+                worker: None,
+                function_name,
+                idempotency_key: None,
+            } => {
+                let function_name = function_name.to_parsed_function_name();
+                function_name.site == ParsedFunctionSite::Global
+                    && matches!(
+                        function_name.function,
+                        ParsedFunctionReference::Function { function } if function == "emit"
+                    )
+            }
+            _ => false,
+        }
+    }
+}
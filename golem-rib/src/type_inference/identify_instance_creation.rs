@@ -161,6 +161,7 @@ mod internal {
             }
             CallType::VariantConstructor(_) => None,
             CallType::EnumConstructor(_) => None,
+            CallType::EventEmission => None,
             CallType::InstanceCreation(instance_creation_type) => {
                 Some(instance_creation_type.clone())
             }
@@ -52,6 +52,7 @@ impl InferredExpr {
                         CallType::Function {
                             function_name,
                             worker,
+                            idempotency_key,
                         },
                     ..
                 } => {
@@ -60,6 +61,10 @@ impl InferredExpr {
                     if let Some(worker) = worker {
                         queue.push_back(worker);
                     }
+
+                    if let Some(idempotency_key) = idempotency_key {
+                        queue.push_back(idempotency_key);
+                    }
                 }
                 _ => expr.visit_children_bottom_up(&mut queue),
             }
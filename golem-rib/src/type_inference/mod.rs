@@ -17,8 +17,10 @@ pub use enum_resolution::*;
 pub use errors::*;
 pub use expr_visitor::*;
 pub use global_input_inference::*;
+pub use global_input_type_binding::*;
 pub use global_variable_type_binding::*;
 pub use identifier_inference::*;
+pub use identify_event_emission::*;
 pub use identify_instance_creation::*;
 pub use inference_fix_point::*;
 pub use inferred_expr::*;
@@ -42,8 +44,10 @@ mod enum_resolution;
 mod errors;
 mod expr_visitor;
 mod global_input_inference;
+mod global_input_type_binding;
 mod global_variable_type_binding;
 mod identifier_inference;
+mod identify_event_emission;
 mod identify_instance_creation;
 mod inference_fix_point;
 mod inferred_expr;
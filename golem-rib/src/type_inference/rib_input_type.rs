@@ -32,6 +32,17 @@ impl RibInputTypeInfo {
         }
     }
 
+    // Registers a global variable (such as `request`, `env` or `auth`) along with its type.
+    // Example: `RibInputTypeInfo::empty().with_global("request", request_type)`
+    pub fn with_global(mut self, name: impl Into<String>, analysed_type: AnalysedType) -> Self {
+        self.types.insert(name.into(), analysed_type);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AnalysedType> {
+        self.types.get(name)
+    }
+
     pub fn from_expr(inferred_expr: &InferredExpr) -> Result<RibInputTypeInfo, RibError> {
         let expr: &Expr = inferred_expr.get_expr();
         let mut queue = VecDeque::new();
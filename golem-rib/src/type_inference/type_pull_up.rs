@@ -1047,6 +1047,15 @@ mod internal {
         inferred_type_stack: &mut VecDeque<Expr>,
         source_span: &SourceSpan,
     ) {
+        // idempotency key is the last child visited in a call
+        let new_idempotency_key = call_type.idempotency_key_expr().map(|idempotency_key| {
+            Box::new(
+                inferred_type_stack
+                    .pop_front()
+                    .unwrap_or(idempotency_key.clone()),
+            )
+        });
+
         let mut new_arg_exprs = vec![];
 
         for expr in arguments.iter().rev() {
@@ -1060,6 +1069,7 @@ mod internal {
             CallType::Function {
                 function_name,
                 worker,
+                ..
             } => {
                 let mut function_name = function_name.clone();
 
@@ -1121,6 +1131,7 @@ mod internal {
                         CallType::Function {
                             function_name,
                             worker: Some(Box::new(worker)),
+                            idempotency_key: new_idempotency_key,
                         },
                         None,
                         new_arg_exprs,
@@ -1132,6 +1143,7 @@ mod internal {
                         CallType::Function {
                             function_name,
                             worker: None,
+                            idempotency_key: new_idempotency_key,
                         },
                         None,
                         new_arg_exprs,
@@ -1200,6 +1212,13 @@ mod internal {
                         .with_source_span(source_span.clone());
                 inferred_type_stack.push_front(new_call);
             }
+
+            CallType::EventEmission => {
+                let new_call = Expr::call(CallType::EventEmission, None, new_arg_exprs)
+                    .with_inferred_type(inferred_type.clone())
+                    .with_source_span(source_span.clone());
+                inferred_type_stack.push_front(new_call);
+            }
         }
     }
 
@@ -497,11 +497,16 @@ mod internal {
             CallType::Function {
                 worker,
                 function_name,
+                idempotency_key,
             } => {
                 if let Some(worker) = worker {
                     queue.push_back(worker);
                 }
 
+                if let Some(idempotency_key) = idempotency_key {
+                    queue.push_back(idempotency_key);
+                }
+
                 if let Some(expr) = function_name.raw_resource_params_mut() {
                     queue.extend(expr);
                 }
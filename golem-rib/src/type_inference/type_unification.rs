@@ -310,11 +310,16 @@ pub fn unify_types(expr: &mut Expr) -> Result<(), MultipleUnResolvedTypesError>
                     CallType::Function {
                         worker,
                         function_name,
+                        idempotency_key,
                     } => {
                         if let Some(worker) = worker {
                             queue.push(worker);
                         }
 
+                        if let Some(idempotency_key) = idempotency_key {
+                            queue.push(idempotency_key);
+                        }
+
                         let unified_inferred_type = inferred_type.unify();
 
                         match unified_inferred_type {
@@ -20,6 +20,7 @@ pub fn infer_worker_function_invokes(expr: &mut Expr) -> Result<(), RibCompilati
             method,
             generic_type_parameter,
             args,
+            idempotency_key,
             source_span,
             ..
         } = expr
@@ -70,10 +71,43 @@ pub fn infer_worker_function_invokes(expr: &mut Expr) -> Result<(), RibCompilati
                                 args.clone(),
                             )
                             .with_source_span(source_span.clone());
-                            *expr = new_call;
+
+                            *expr = internal::with_idempotency_key(new_call, idempotency_key);
+                        }
+                        // Static functions of a resource, such as `worker.cart.create()`,
+                        // are invoked without having to create an instance of the resource
+                        FunctionName::ResourceStaticMethod(static_method) => {
+                            let dynamic_parsed_function_name = static_method
+                                .dynamic_parsed_static_function_name()
+                                .map_err(|err| FunctionCallError::InvalidFunctionCall {
+                                    function_name: method.to_string(),
+                                    expr: expr_copied,
+                                    message: format!("Invalid function name: {}", err),
+                                })?;
+
+                            let worker_name = instance_type.worker_name().as_deref().cloned();
+
+                            let new_call = Expr::call_worker_function(
+                                dynamic_parsed_function_name,
+                                None,
+                                worker_name,
+                                args.clone(),
+                            )
+                            .with_source_span(source_span.clone());
+
+                            *expr = internal::with_idempotency_key(new_call, idempotency_key);
                         }
                         // We are yet to be able to create a call_type
                         FunctionName::ResourceConstructor(fully_qualified_resource_constructor) => {
+                            if idempotency_key.is_some() {
+                                return Err(FunctionCallError::InvalidFunctionCall {
+                                    function_name: method.to_string(),
+                                    expr: expr_copied,
+                                    message: "idempotency key cannot be specified when constructing a resource".to_string(),
+                                }
+                                .into());
+                            }
+
                             let resource_instance_type = instance_type.get_resource_instance_type(
                                 fully_qualified_resource_constructor.clone(),
                                 args.clone(),
@@ -144,7 +178,8 @@ pub fn infer_worker_function_invokes(expr: &mut Expr) -> Result<(), RibCompilati
                                     )
                                     .with_source_span(source_span.clone());
 
-                                    *expr = new_call
+                                    *expr =
+                                        internal::with_idempotency_key(new_call, idempotency_key);
                                 }
 
                                 _ => {
@@ -183,3 +218,14 @@ pub fn infer_worker_function_invokes(expr: &mut Expr) -> Result<(), RibCompilati
 
     Ok(())
 }
+
+mod internal {
+    use crate::Expr;
+
+    pub(crate) fn with_idempotency_key(call: Expr, idempotency_key: &Option<Box<Expr>>) -> Expr {
+        match idempotency_key {
+            Some(key) => call.with_idempotency_key(key.as_ref().clone()),
+            None => call,
+        }
+    }
+}
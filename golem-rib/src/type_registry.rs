@@ -65,6 +65,7 @@ impl FunctionTypeRegistry {
                 .types
                 .get(&RegistryKey::FunctionName(enum_name.clone())),
             CallType::InstanceCreation(_) => None,
+            CallType::EventEmission => None,
         }
     }
 
@@ -281,6 +282,7 @@ impl RegistryKey {
                 }),
             },
             CallType::InstanceCreation(_) => None,
+            CallType::EventEmission => None,
         }
     }
 }
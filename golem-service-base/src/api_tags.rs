@@ -9,4 +9,6 @@ pub enum ApiTags {
     Worker,
     HealthCheck,
     Plugin,
+    AuditLog,
+    Quota,
 }
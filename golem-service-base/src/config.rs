@@ -34,11 +34,48 @@ impl Default for WorkerExecutorClientCacheConfig {
     }
 }
 
+/// Retention of soft deleted resources before they are permanently purged
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrashConfig {
+    #[serde(with = "humantime_serde")]
+    pub retention: Duration,
+    #[serde(with = "humantime_serde")]
+    pub purge_interval: Duration,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention: Duration::from_secs(60 * 60 * 24 * 7),
+            purge_interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Polling of the resource lists streamed to watchers, and how many of their latest states
+/// a reconnecting watcher can resume from without a resync
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WatchConfig {
+    #[serde(with = "humantime_serde")]
+    pub poll_interval: Duration,
+    pub history_size: usize,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(2),
+            history_size: 16,
+        }
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "config")]
 pub enum BlobStorageConfig {
     S3(S3BlobStorageConfig),
+    AzureBlob(AzureBlobStorageConfig),
     LocalFileSystem(LocalFileSystemBlobStorageConfig),
     KVStoreSqlite,
     Sqlite(DbSqliteConfig),
@@ -77,6 +114,13 @@ pub struct S3BlobStorageConfig {
     pub compressed_oplog_buckets: Vec<String>,
     pub use_minio_credentials: bool,
     pub initial_component_files_bucket: String,
+    /// Addresses the buckets in the path of the URLs, as required by MinIO and some other
+    /// S3-compatible stores
+    pub force_path_style: bool,
+    /// Streams larger than this many bytes are uploaded with a multipart upload
+    pub multipart_threshold: u64,
+    /// The size of the parts of the multipart uploads, at least 5 MiB
+    pub multipart_part_size: u64,
 }
 
 impl Default for S3BlobStorageConfig {
@@ -92,9 +136,52 @@ impl Default for S3BlobStorageConfig {
             compressed_oplog_buckets: vec!["oplog-archive-1".to_string()],
             use_minio_credentials: false,
             initial_component_files_bucket: "golem-initial-component-files".to_string(),
+            force_path_style: false,
+            multipart_threshold: 64 * 1024 * 1024,
+            multipart_part_size: 16 * 1024 * 1024,
         }
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AzureBlobStorageConfig {
+    pub retries: RetryConfig,
+    pub account: String,
+    /// The base64 encoded shared key of the storage account
+    pub access_key: String,
+    /// Overrides `https://<account>.blob.core.windows.net`, for example to use Azurite
+    pub endpoint_url: Option<String>,
+    pub object_prefix: String,
+    pub compilation_cache_container: String,
+    pub custom_data_container: String,
+    pub oplog_payload_container: String,
+    pub compressed_oplog_containers: Vec<String>,
+    pub initial_component_files_container: String,
+    /// Streams larger than this many bytes are uploaded as multiple blocks
+    pub block_upload_threshold: u64,
+    /// The size of the blocks of the block uploads
+    pub block_size: u64,
+}
+
+impl Default for AzureBlobStorageConfig {
+    fn default() -> Self {
+        Self {
+            retries: RetryConfig::max_attempts_3(),
+            account: "".to_string(),
+            access_key: "".to_string(),
+            endpoint_url: None,
+            object_prefix: "".to_string(),
+            compilation_cache_container: "golem-compiled-components".to_string(),
+            custom_data_container: "custom-data".to_string(),
+            oplog_payload_container: "oplog-payload".to_string(),
+            compressed_oplog_containers: vec!["oplog-archive-1".to_string()],
+            initial_component_files_container: "golem-initial-component-files".to_string(),
+            block_upload_threshold: 64 * 1024 * 1024,
+            block_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LocalFileSystemBlobStorageConfig {
     pub root: PathBuf,
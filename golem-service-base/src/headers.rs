@@ -109,9 +109,57 @@ impl TraceContextHeaders {
     }
 }
 
+/// Preconditions of an `If-Match` header
+///
+/// See https://www.rfc-editor.org/rfc/rfc9110#name-if-match
+#[derive(Debug, Clone, PartialEq)]
+pub enum IfMatch {
+    Any,
+    EntityTags(Vec<String>),
+}
+
+impl IfMatch {
+    pub fn parse(value: &str) -> IfMatch {
+        if value.trim() == "*" {
+            IfMatch::Any
+        } else {
+            IfMatch::EntityTags(
+                value
+                    .split(',')
+                    .map(Self::normalize)
+                    .filter(|tag| !tag.is_empty())
+                    .collect(),
+            )
+        }
+    }
+
+    /// Checks the precondition against the entity tag of the current resource, if any
+    pub fn matches(&self, current: Option<&str>) -> bool {
+        match (self, current) {
+            (_, None) => false,
+            (IfMatch::Any, Some(_)) => true,
+            (IfMatch::EntityTags(tags), Some(current)) => {
+                let current = Self::normalize(current);
+                tags.iter().any(|tag| *tag == current)
+            }
+        }
+    }
+
+    fn normalize(tag: &str) -> String {
+        let tag = tag.trim();
+        let tag = tag.strip_prefix("W/").unwrap_or(tag);
+        tag.trim_matches('"').to_string()
+    }
+}
+
+/// Returns an API response with the entity tag of the resource in its `ETag` header
+pub fn with_etag<T>(response: T, tag: &str) -> poem_openapi::payload::Response<T> {
+    poem_openapi::payload::Response::new(response).header(http::header::ETAG, format!("\"{tag}\""))
+}
+
 #[cfg(test)]
 mod test {
-    use crate::headers::{TraceContextHeaders, TraceFlags};
+    use crate::headers::{IfMatch, TraceContextHeaders, TraceFlags};
     use golem_common::model::invocation_context::{SpanId, TraceId};
     use http::{HeaderMap, HeaderValue};
     use std::num::{NonZeroU128, NonZeroU64};
@@ -191,4 +239,22 @@ mod test {
             ]
         )
     }
+
+    #[test]
+    fn if_match_entity_tags() {
+        let if_match = IfMatch::parse(r#""abc", W/"def""#);
+
+        assert!(if_match.matches(Some("abc")));
+        assert!(if_match.matches(Some("\"def\"")));
+        assert!(!if_match.matches(Some("xyz")));
+        assert!(!if_match.matches(None));
+    }
+
+    #[test]
+    fn if_match_any() {
+        let if_match = IfMatch::parse("*");
+
+        assert!(if_match.matches(Some("abc")));
+        assert!(!if_match.matches(None));
+    }
 }
@@ -26,6 +26,8 @@ pub mod repo;
 pub mod service;
 pub mod storage;
 pub mod stream;
+pub mod value_conversion;
+pub mod watch;
 
 #[cfg(test)]
 test_r::enable!();
@@ -15,9 +15,10 @@
 use std::path::PathBuf;
 
 use futures::future::BoxFuture;
+use include_dir::Dir;
 use sqlx::{
     error::BoxDynError,
-    migrate::{Migration, MigrationSource},
+    migrate::{Migration, MigrationSource, MigrationType},
 };
 
 pub trait Migrations {
@@ -69,3 +70,75 @@ impl Migrations for MigrationsDir {
         }
     }
 }
+
+/// Migrations embedded in the binary, with the same `sqlite` and `postgres` layout as
+/// [`MigrationsDir`]
+pub struct IncludedMigrationsDir(Dir<'static>);
+
+impl IncludedMigrationsDir {
+    pub fn new(dir: Dir<'static>) -> Self {
+        Self(dir)
+    }
+}
+
+impl Migrations for IncludedMigrationsDir {
+    type Output<'a>
+        = SpecificIncludedMigrationsDir<'a>
+    where
+        Self: 'a;
+
+    fn sqlite_migrations(&self) -> Self::Output<'_> {
+        SpecificIncludedMigrationsDir {
+            value: self.0.get_dir("sqlite"),
+        }
+    }
+
+    fn postgres_migrations(&self) -> Self::Output<'_> {
+        SpecificIncludedMigrationsDir {
+            value: self.0.get_dir("postgres"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SpecificIncludedMigrationsDir<'a> {
+    value: Option<&'a Dir<'static>>,
+}
+
+impl<'a> MigrationSource<'a> for SpecificIncludedMigrationsDir<'a> {
+    fn resolve(self) -> BoxFuture<'a, Result<Vec<Migration>, BoxDynError>> {
+        Box::pin(async move {
+            let mut migrations = Vec::new();
+            for file in self.value.iter().flat_map(|dir| dir.files()) {
+                let path = file.path();
+                if path.extension().is_none_or(|ext| ext != "sql") {
+                    continue;
+                }
+                // Named as `<version>_<description>.sql`, like the migrations read from a directory
+                let name = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .ok_or_else(|| format!("Invalid migration file name: {path:?}"))?;
+                let (version, description) = name
+                    .split_once('_')
+                    .ok_or_else(|| format!("Invalid migration file name: {name}"))?;
+                let version: i64 = version
+                    .parse()
+                    .map_err(|_| format!("Invalid migration version: {name}"))?;
+                let sql = file
+                    .contents_utf8()
+                    .ok_or_else(|| format!("Migration is not valid UTF-8: {name}"))?;
+
+                migrations.push(Migration::new(
+                    version,
+                    description.replace('_', " ").into(),
+                    MigrationType::Simple,
+                    sql.into(),
+                    false,
+                ));
+            }
+            migrations.sort_by_key(|migration| migration.version);
+            Ok(migrations)
+        })
+    }
+}
@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::watch::{WatchEvent, WatchEventType};
 use bincode::{Decode, Encode};
 use golem_api_grpc::proto::golem::worker::OplogEntryWithIndex;
 use golem_common::model::component_metadata::ComponentMetadata;
 use golem_common::model::oplog::OplogIndex;
 use golem_common::model::plugin::PluginInstallation;
-use golem_common::model::public_oplog::{OplogCursor, PublicOplogEntry};
+use golem_common::model::public_oplog::{LogParameters, OplogCursor, PublicOplogEntry};
 use golem_common::model::{AccountId, PluginInstallationId};
 use golem_common::model::{
     ComponentFilePermissions, ComponentFileSystemNode, ComponentFileSystemNodeDetails, ComponentId,
@@ -27,9 +28,9 @@ use golem_common::model::{
 use golem_common::SafeDisplay;
 use golem_wasm_ast::analysis::analysed_type::{case, field, record, variant};
 use golem_wasm_ast::analysis::AnalysedType;
-use golem_wasm_rpc::json::OptionallyTypeAnnotatedValueJson;
+use golem_wasm_rpc::json::{OptionallyTypeAnnotatedValueJson, TypeAnnotatedValueJsonExtensions};
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
-use golem_wasm_rpc::{IntoValue, Value};
+use golem_wasm_rpc::{IntoValue, Value, ValueAndType};
 use poem_openapi::{Enum, NewType, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -41,6 +42,12 @@ pub struct WorkerCreationRequest {
     pub name: String,
     pub args: Vec<String>,
     pub env: HashMap<String, String>,
+    #[serde(default)]
+    #[oai(default)]
+    pub wasi_config_vars: HashMap<String, String>,
+    #[serde(default)]
+    #[oai(default)]
+    pub labels: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
@@ -1052,6 +1059,77 @@ impl From<crate::model::GolemErrorShardingNotReady>
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object, thiserror::Error)]
+#[error("Worker executor is draining")]
+pub struct GolemErrorWorkerExecutorDraining {}
+
+impl SafeDisplay for GolemErrorWorkerExecutorDraining {
+    fn to_safe_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::worker::v1::WorkerExecutorDraining>
+    for crate::model::GolemErrorWorkerExecutorDraining
+{
+    fn from(_value: golem_api_grpc::proto::golem::worker::v1::WorkerExecutorDraining) -> Self {
+        Self {}
+    }
+}
+
+impl From<crate::model::GolemErrorWorkerExecutorDraining>
+    for golem_api_grpc::proto::golem::worker::v1::WorkerExecutorDraining
+{
+    fn from(_value: crate::model::GolemErrorWorkerExecutorDraining) -> Self {
+        Self {}
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object, thiserror::Error)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+#[error("Oplog entry {oplog_index} of worker {worker_id} was dropped by compaction, the oplog is truncated until {truncated_until}")]
+pub struct GolemErrorOplogTruncated {
+    pub worker_id: WorkerId,
+    pub oplog_index: OplogIndex,
+    pub truncated_until: OplogIndex,
+}
+
+impl SafeDisplay for GolemErrorOplogTruncated {
+    fn to_safe_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::v1::OplogTruncated>
+    for GolemErrorOplogTruncated
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::v1::OplogTruncated,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            worker_id: value
+                .worker_id
+                .ok_or("Missing field: worker_id")?
+                .try_into()?,
+            oplog_index: OplogIndex::from_u64(value.oplog_index),
+            truncated_until: OplogIndex::from_u64(value.truncated_until),
+        })
+    }
+}
+
+impl From<GolemErrorOplogTruncated> for golem_api_grpc::proto::golem::worker::v1::OplogTruncated {
+    fn from(value: GolemErrorOplogTruncated) -> Self {
+        Self {
+            worker_id: Some(value.worker_id.into()),
+            oplog_index: value.oplog_index.into(),
+            truncated_until: value.truncated_until.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
 pub struct InvokeParameters {
     pub params: Vec<OptionallyTypeAnnotatedValueJson>,
@@ -1081,11 +1159,32 @@ pub struct DeactivatePluginResponse {}
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
 pub struct RevertWorkerResponse {}
 
+/// Forks a worker into a new worker of the same component, from a point of its oplog
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ForkWorkerRequest {
+    pub target_worker_name: String,
+    /// Index of the last oplog entry of the source worker copied to the new worker
+    pub oplog_index_cutoff: OplogIndex,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct ForkWorkerResponse {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
 pub struct CancelInvocationResponse {
     pub canceled: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct UpdateWorkerLabelsRequest {
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd, Serialize, Deserialize, Object)]
+pub struct UpdateWorkerLabelsResponse {}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
@@ -1096,11 +1195,24 @@ pub struct GetOplogResponse {
     pub last_index: u64,
 }
 
+/// The most recent stdout, stderr and log entries emitted by an invocation, in the order they
+/// were emitted
+#[derive(Debug, Clone, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct GetInvocationLogsResponse {
+    pub entries: Vec<LogParameters>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
 pub struct GetFilesResponse {
     pub nodes: Vec<FlatComponentFileSystemNode>,
+    /// Whether the directory has more entries than the listed ones
+    #[serde(default)]
+    #[oai(default)]
+    pub truncated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Enum)]
@@ -1155,6 +1267,40 @@ impl From<ComponentFileSystemNode> for FlatComponentFileSystemNode {
 pub struct PublicOplogEntryWithIndex {
     pub oplog_index: OplogIndex,
     pub entry: PublicOplogEntry,
+    /// The typed payloads of the entry (function inputs and results) as JSON, only present if
+    /// decoding was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<serde_json::Value>,
+}
+
+impl PublicOplogEntryWithIndex {
+    // Decodes the typed payloads of the entry, using the types of the component it was recorded
+    // with, into the same JSON format the invocation API uses
+    pub fn with_decoded_payload(self) -> Self {
+        let decoded = match &self.entry {
+            PublicOplogEntry::ImportedFunctionInvoked(params) => Some(serde_json::json!({
+                "functionName": params.function_name,
+                "request": value_to_json(&params.request),
+                "response": value_to_json(&params.response),
+            })),
+            PublicOplogEntry::ExportedFunctionInvoked(params) => Some(serde_json::json!({
+                "functionName": params.function_name,
+                "request": params.request.iter().map(value_to_json).collect::<Vec<_>>(),
+            })),
+            PublicOplogEntry::ExportedFunctionCompleted(params) => Some(serde_json::json!({
+                "response": value_to_json(&params.response),
+            })),
+            _ => None,
+        };
+
+        Self { decoded, ..self }
+    }
+}
+
+fn value_to_json(value: &ValueAndType) -> serde_json::Value {
+    TypeAnnotatedValue::try_from(value.clone())
+        .map(|value| value.to_json_value())
+        .unwrap_or(serde_json::Value::Null)
 }
 
 impl TryFrom<golem_api_grpc::proto::golem::worker::OplogEntryWithIndex>
@@ -1166,6 +1312,7 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::OplogEntryWithIndex>
         Ok(Self {
             oplog_index: OplogIndex::from_u64(value.oplog_index),
             entry: value.entry.ok_or("Missing field: entry")?.try_into()?,
+            decoded: None,
         })
     }
 }
@@ -1233,6 +1380,82 @@ pub struct WorkersMetadataResponse {
     pub cursor: Option<ScanCursor>,
 }
 
+/// Change event of a worker watch stream
+#[derive(Debug, Clone, PartialEq, Object)]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerWatchEvent {
+    pub event_type: WatchEventType,
+    pub key: Option<String>,
+    pub resource_version: Option<String>,
+    pub cursor: Option<String>,
+    pub worker: Option<WorkerMetadata>,
+}
+
+impl From<WatchEvent<WorkerMetadata>> for WorkerWatchEvent {
+    fn from(value: WatchEvent<WorkerMetadata>) -> Self {
+        Self {
+            event_type: value.event_type,
+            key: value.key,
+            resource_version: value.resource_version,
+            cursor: value.cursor,
+            worker: value.resource,
+        }
+    }
+}
+
+impl From<WatchEvent<WorkerMetadata>>
+    for golem_api_grpc::proto::golem::worker::v1::WorkerWatchEvent
+{
+    fn from(value: WatchEvent<WorkerMetadata>) -> Self {
+        Self {
+            event_type: golem_api_grpc::proto::golem::common::WatchEventType::from(value.event_type)
+                as i32,
+            key: value.key,
+            resource_version: value.resource_version,
+            cursor: value.cursor,
+            worker: value.resource.map(|worker| worker.into()),
+        }
+    }
+}
+
+/// Change event of a component watch stream
+#[derive(Debug, Clone, PartialEq, Object)]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentWatchEvent {
+    pub event_type: WatchEventType,
+    pub key: Option<String>,
+    pub resource_version: Option<String>,
+    pub cursor: Option<String>,
+    pub component: Option<Component>,
+}
+
+impl From<WatchEvent<Component>> for ComponentWatchEvent {
+    fn from(value: WatchEvent<Component>) -> Self {
+        Self {
+            event_type: value.event_type,
+            key: value.key,
+            resource_version: value.resource_version,
+            cursor: value.cursor,
+            component: value.resource,
+        }
+    }
+}
+
+impl From<WatchEvent<Component>>
+    for golem_api_grpc::proto::golem::component::v1::ComponentWatchEvent
+{
+    fn from(value: WatchEvent<Component>) -> Self {
+        Self {
+            event_type: golem_api_grpc::proto::golem::common::WatchEventType::from(value.event_type)
+                as i32,
+            key: value.key,
+            resource_version: value.resource_version,
+            cursor: value.cursor,
+            component: value.resource.map(|component| component.into()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
@@ -1240,6 +1463,8 @@ pub struct WorkerMetadata {
     pub worker_id: WorkerId,
     pub args: Vec<String>,
     pub env: HashMap<String, String>,
+    pub wasi_config_vars: HashMap<String, String>,
+    pub labels: HashMap<String, String>,
     pub status: WorkerStatus,
     pub component_version: ComponentVersion,
     pub retry_count: u64,
@@ -1251,6 +1476,8 @@ pub struct WorkerMetadata {
     pub total_linear_memory_size: u64,
     pub owned_resources: HashMap<u64, ResourceMetadata>,
     pub active_plugins: HashSet<PluginInstallationId>,
+    pub compaction: OplogCompactionStatus,
+    pub fork: Option<WorkerForkOrigin>,
 }
 
 impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerMetadata> for WorkerMetadata {
@@ -1263,6 +1490,8 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerMetadata> for WorkerMet
             worker_id: value.worker_id.ok_or("Missing worker_id")?.try_into()?,
             args: value.args,
             env: value.env,
+            wasi_config_vars: value.wasi_config_vars,
+            labels: value.labels,
             status: value.status.try_into()?,
             component_version: value.component_version,
             retry_count: value.retry_count,
@@ -1286,6 +1515,8 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerMetadata> for WorkerMet
                 .into_iter()
                 .map(|id| id.try_into())
                 .collect::<Result<HashSet<_>, _>>()?,
+            compaction: value.compaction.map(|c| c.into()).unwrap_or_default(),
+            fork: value.fork.map(|fork| fork.try_into()).transpose()?,
         })
     }
 }
@@ -1297,6 +1528,8 @@ impl From<WorkerMetadata> for golem_api_grpc::proto::golem::worker::WorkerMetada
             account_id: Some(AccountId::placeholder().into()),
             args: value.args,
             env: value.env,
+            wasi_config_vars: value.wasi_config_vars,
+            labels: value.labels,
             status: value.status.into(),
             component_version: value.component_version,
             retry_count: value.retry_count,
@@ -1316,6 +1549,94 @@ impl From<WorkerMetadata> for golem_api_grpc::proto::golem::worker::WorkerMetada
                 .into_iter()
                 .map(|id| id.into())
                 .collect(),
+            compaction: Some(value.compaction.into()),
+            fork: value.fork.map(|fork| fork.into()),
+        }
+    }
+}
+
+/// The worker this worker was forked from, and how far it diverged from it.
+///
+/// The oplog of the fork starts as a copy of the oplog of the source worker up to `forkedAt`, and
+/// `divergedEntries` entries were added to it since then.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerForkOrigin {
+    pub source_worker_id: WorkerId,
+    pub forked_at: OplogIndex,
+    pub timestamp: Timestamp,
+    pub diverged_entries: u64,
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::worker::WorkerForkOrigin> for WorkerForkOrigin {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::worker::WorkerForkOrigin,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            source_worker_id: value
+                .source_worker_id
+                .ok_or("Missing source_worker_id")?
+                .try_into()?,
+            forked_at: OplogIndex::from_u64(value.forked_at),
+            timestamp: value.timestamp.ok_or("Missing timestamp")?.into(),
+            diverged_entries: value.diverged_entries,
+        })
+    }
+}
+
+impl From<WorkerForkOrigin> for golem_api_grpc::proto::golem::worker::WorkerForkOrigin {
+    fn from(value: WorkerForkOrigin) -> Self {
+        Self {
+            source_worker_id: Some(value.source_worker_id.into()),
+            forked_at: value.forked_at.into(),
+            timestamp: Some(value.timestamp.into()),
+            diverged_entries: value.diverged_entries,
+        }
+    }
+}
+
+/// How far the oplog of the worker is compacted by snapshots of its state.
+///
+/// Recovering the worker loads its last snapshot and only replays the entries added after it.
+/// The entries before the snapshot (except the first one) are dropped from the oplog when it is
+/// truncated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct OplogCompactionStatus {
+    pub last_snapshot_index: Option<OplogIndex>,
+    pub last_snapshot_timestamp: Option<Timestamp>,
+    pub last_failed_attempt_index: Option<OplogIndex>,
+    pub entries_since_last_attempt: u64,
+    pub bytes_since_last_attempt: u64,
+    pub truncated_until: Option<OplogIndex>,
+}
+
+impl From<golem_api_grpc::proto::golem::worker::OplogCompactionStatus> for OplogCompactionStatus {
+    fn from(value: golem_api_grpc::proto::golem::worker::OplogCompactionStatus) -> Self {
+        Self {
+            last_snapshot_index: value.last_snapshot_index.map(OplogIndex::from_u64),
+            last_snapshot_timestamp: value.last_snapshot_timestamp.map(|t| t.into()),
+            last_failed_attempt_index: value.last_failed_attempt_index.map(OplogIndex::from_u64),
+            entries_since_last_attempt: value.entries_since_last_attempt,
+            bytes_since_last_attempt: value.bytes_since_last_attempt,
+            truncated_until: value.truncated_until.map(OplogIndex::from_u64),
+        }
+    }
+}
+
+impl From<OplogCompactionStatus> for golem_api_grpc::proto::golem::worker::OplogCompactionStatus {
+    fn from(value: OplogCompactionStatus) -> Self {
+        Self {
+            last_snapshot_index: value.last_snapshot_index.map(|idx| idx.into()),
+            last_snapshot_timestamp: value.last_snapshot_timestamp.map(|t| t.into()),
+            last_failed_attempt_index: value.last_failed_attempt_index.map(|idx| idx.into()),
+            entries_since_last_attempt: value.entries_since_last_attempt,
+            bytes_since_last_attempt: value.bytes_since_last_attempt,
+            truncated_until: value.truncated_until.map(|idx| idx.into()),
         }
     }
 }
@@ -1543,6 +1864,10 @@ pub enum GolemError {
     InitialComponentFileDownloadFailed(GolemErrorInitialComponentFileDownloadFailed),
     #[error(transparent)]
     FileSystemError(GolemErrorFileSystemError),
+    #[error(transparent)]
+    WorkerExecutorDraining(GolemErrorWorkerExecutorDraining),
+    #[error(transparent)]
+    OplogTruncated(GolemErrorOplogTruncated),
 }
 
 impl SafeDisplay for GolemError {
@@ -1573,6 +1898,8 @@ impl SafeDisplay for GolemError {
             GolemError::ShardingNotReady(inner) => inner.to_safe_string(),
             GolemError::InitialComponentFileDownloadFailed(inner) => inner.to_safe_string(),
             GolemError::FileSystemError(inner) => inner.to_safe_string(),
+            GolemError::WorkerExecutorDraining(inner) => inner.to_safe_string(),
+            GolemError::OplogTruncated(inner) => inner.to_safe_string(),
         }
     }
 }
@@ -1663,6 +1990,12 @@ impl TryFrom<golem_api_grpc::proto::golem::worker::v1::WorkerExecutionError> for
             Some(golem_api_grpc::proto::golem::worker::v1::worker_execution_error::Error::FileSystemError(err)) => {
                 Ok(GolemError::FileSystemError(err.into()))
             }
+            Some(golem_api_grpc::proto::golem::worker::v1::worker_execution_error::Error::WorkerExecutorDraining(err)) => {
+                Ok(GolemError::WorkerExecutorDraining(err.into()))
+            }
+            Some(golem_api_grpc::proto::golem::worker::v1::worker_execution_error::Error::OplogTruncated(err)) => {
+                Ok(GolemError::OplogTruncated(err.try_into()?))
+            }
             None => Err("Missing field: error".to_string()),
         }
     }
@@ -1754,6 +2087,12 @@ impl From<GolemError> for golem_api_grpc::proto::golem::worker::v1::worker_execu
             GolemError::FileSystemError(err) => {
                 golem_api_grpc::proto::golem::worker::v1::worker_execution_error::Error::FileSystemError(err.into())
             }
+            GolemError::WorkerExecutorDraining(err) => {
+                golem_api_grpc::proto::golem::worker::v1::worker_execution_error::Error::WorkerExecutorDraining(err.into())
+            }
+            GolemError::OplogTruncated(err) => {
+                golem_api_grpc::proto::golem::worker::v1::worker_execution_error::Error::OplogTruncated(err.into())
+            }
         }
     }
 }
@@ -1814,6 +2153,17 @@ pub struct Component {
     pub installed_plugins: Vec<PluginInstallation>,
 }
 
+/// A component moved to the trash, restorable until its retention period expires
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct DeletedComponent {
+    pub component_id: ComponentId,
+    pub component_name: ComponentName,
+    pub deleted_at: chrono::DateTime<chrono::Utc>,
+    pub deleted_by: String,
+}
+
 impl TryFrom<golem_api_grpc::proto::golem::component::Component> for Component {
     type Error = String;
 
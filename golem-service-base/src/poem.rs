@@ -17,14 +17,24 @@ use std::{
     fmt::{self, Debug, Formatter},
 };
 
+use poem::http::StatusCode;
 use poem::web::Field as PoemField;
+use poem::{IntoResponse, Request, RequestBody, Response};
 use tempfile::NamedTempFile;
 
 use poem_openapi::{
-    registry::{MetaSchema, MetaSchemaRef},
-    types::{ParseError, ParseFromMultipartField, ParseResult, Type},
+    error::ParseRequestPayloadError,
+    payload::{Json, ParsePayload, Payload},
+    registry::{
+        MetaMediaType, MetaRequest, MetaResponse, MetaResponses, MetaSchema, MetaSchemaRef,
+        Registry,
+    },
+    types::{ParseError, ParseFromJSON, ParseFromMultipartField, ParseResult, ToJSON, Type},
+    ApiExtractor, ApiExtractorType, ApiResponse, ExtractParamOptions,
 };
 
+use crate::value_conversion::{BinaryCodec, BinaryCodecBody};
+
 /// A uploaded file for multipart.
 ///
 /// Similiar to https://github.com/poem-web/poem/blob/f7bb838253fdf0bf67d3592fce45b92d68242c97/poem-openapi/src/types/multipart/upload.rs#L25,
@@ -122,3 +132,161 @@ async fn field_to_tempfile(field: PoemField) -> Result<NamedTempFile, std::io::E
 
     Ok(file)
 }
+
+/// Body of the worker invocation API, as JSON or in one of the binary codecs.
+///
+/// Responses are sent in the codec of the request, and the content types other than JSON are
+/// listed next to it in the OpenAPI spec.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodecPayload<T> {
+    pub value: T,
+    pub codec: Option<BinaryCodec>,
+}
+
+impl<T> CodecPayload<T> {
+    pub fn new(value: T, codec: Option<BinaryCodec>) -> Self {
+        Self { value, codec }
+    }
+
+    fn meta_media_types() -> Vec<MetaMediaType>
+    where
+        T: Type,
+    {
+        let mut content = vec![MetaMediaType {
+            content_type: "application/json; charset=utf-8",
+            schema: T::schema_ref(),
+        }];
+
+        content.extend(BinaryCodec::ALL.iter().map(|codec| MetaMediaType {
+            content_type: codec.content_type(),
+            schema: T::schema_ref(),
+        }));
+
+        content
+    }
+}
+
+impl<T: Type> Payload for CodecPayload<T> {
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    fn check_content_type(content_type: &str) -> bool {
+        content_type.contains("json") || BinaryCodec::from_content_type(content_type).is_some()
+    }
+
+    fn schema_ref() -> MetaSchemaRef {
+        T::schema_ref()
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+}
+
+impl<'a, T: ParseFromJSON + BinaryCodecBody> ApiExtractor<'a> for CodecPayload<T> {
+    const TYPES: &'static [ApiExtractorType] = &[ApiExtractorType::RequestObject];
+    type ParamType = ();
+    type ParamRawType = ();
+
+    fn register(registry: &mut Registry) {
+        <Self as Payload>::register(registry);
+    }
+
+    fn request_meta() -> Option<MetaRequest> {
+        Some(MetaRequest {
+            description: None,
+            content: Self::meta_media_types(),
+            required: <Self as ParsePayload>::IS_REQUIRED,
+        })
+    }
+
+    async fn from_request(
+        request: &'a Request,
+        body: &mut RequestBody,
+        _param_opts: ExtractParamOptions<Self::ParamType>,
+    ) -> poem::Result<Self> {
+        <Self as ParsePayload>::from_request(request, body).await
+    }
+}
+
+impl<T: ParseFromJSON + BinaryCodecBody> ParsePayload for CodecPayload<T> {
+    const IS_REQUIRED: bool = true;
+
+    async fn from_request(request: &Request, body: &mut RequestBody) -> poem::Result<Self> {
+        let content_type = request
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        let body = body.take().map_err(|e| {
+            poem::Error::from_string(
+                format!("Missing request body {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+        })?;
+
+        let bytes = body.into_bytes().await.map_err(|e| {
+            poem::Error::from_string(
+                format!("Failed to read request body {}", e),
+                StatusCode::BAD_REQUEST,
+            )
+        })?;
+
+        if let Some(codec) = BinaryCodec::from_content_type(content_type) {
+            let value = T::decode_with(codec, &bytes)
+                .map_err(|reason| ParseRequestPayloadError { reason })?;
+            Ok(Self::new(value, Some(codec)))
+        } else if content_type.contains("json") {
+            let json_data = serde_json::from_slice(&bytes).map_err(|e| {
+                poem::Error::from_string(
+                    format!("Failed to read JSON data {}", e),
+                    StatusCode::BAD_REQUEST,
+                )
+            })?;
+            let value =
+                T::parse_from_json(Some(json_data)).map_err(|err| ParseRequestPayloadError {
+                    reason: err.into_message(),
+                })?;
+            Ok(Self::new(value, None))
+        } else {
+            Err(poem::Error::from_string(
+                "Unsupported content type".to_string(),
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ))
+        }
+    }
+}
+
+impl<T: ToJSON + BinaryCodecBody + Send> IntoResponse for CodecPayload<T> {
+    fn into_response(self) -> Response {
+        match self.codec {
+            None => Json(self.value).into_response(),
+            Some(codec) => match self.value.encode_with(codec) {
+                Ok(bytes) => Response::builder()
+                    .content_type(codec.content_type())
+                    .body(bytes),
+                Err(err) => Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(err),
+            },
+        }
+    }
+}
+
+impl<T: ToJSON + BinaryCodecBody + Send> ApiResponse for CodecPayload<T> {
+    fn meta() -> MetaResponses {
+        MetaResponses {
+            responses: vec![MetaResponse {
+                description: "",
+                status: Some(200),
+                status_range: None,
+                content: Self::meta_media_types(),
+                headers: vec![],
+            }],
+        }
+    }
+
+    fn register(registry: &mut Registry) {
+        T::register(registry);
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::repo::RepoError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct AuditLogRecord {
+    pub namespace: String,
+    pub entry_id: String,
+    pub actor: String,
+    pub operation: String,
+    pub resource_id: String,
+    pub before_digest: Option<String>,
+    pub after_digest: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Filters of the audit log entries of a namespace, the missing ones match every entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditLogRecordFilter {
+    pub actor: Option<String>,
+    pub operation: Option<String>,
+    pub resource_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub trait AuditLogRepo {
+    async fn create(&self, entry: &AuditLogRecord) -> Result<(), RepoError>;
+
+    // The matching entries of a namespace, the newest first
+    async fn get_all(
+        &self,
+        namespace: &str,
+        filter: &AuditLogRecordFilter,
+        limit: u32,
+    ) -> Result<Vec<AuditLogRecord>, RepoError>;
+}
+
+pub struct DbAuditLogRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbAuditLogRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedAuditLogRepo<Repo: AuditLogRepo> {
+    repo: Repo,
+}
+
+impl<Repo: AuditLogRepo> LoggedAuditLogRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        namespace: &str,
+        entry_id: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(namespace = namespace, entry_id = entry_id, "{}", message),
+            Err(error) => error!(
+                namespace = namespace,
+                entry_id = entry_id,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: AuditLogRepo + Send + Sync> AuditLogRepo for LoggedAuditLogRepo<Repo> {
+    async fn create(&self, entry: &AuditLogRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(entry).await;
+        Self::logged_with_id("create", &entry.namespace, Some(&entry.entry_id), result)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &str,
+        filter: &AuditLogRecordFilter,
+        limit: u32,
+    ) -> Result<Vec<AuditLogRecord>, RepoError> {
+        let result = self.repo.get_all(namespace, filter, limit).await;
+        Self::logged_with_id("get_all", namespace, None, result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl AuditLogRepo for DbAuditLogRepo<sqlx::Postgres> {
+    async fn create(&self, entry: &AuditLogRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO audit_log
+                (namespace, entry_id, actor, operation, resource_id, before_digest, after_digest, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)
+               "#,
+        )
+        .bind(entry.namespace.clone())
+        .bind(entry.entry_id.clone())
+        .bind(entry.actor.clone())
+        .bind(entry.operation.clone())
+        .bind(entry.resource_id.clone())
+        .bind(entry.before_digest.clone())
+        .bind(entry.after_digest.clone())
+        .bind(entry.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(
+        &self,
+        namespace: &str,
+        filter: &AuditLogRecordFilter,
+        limit: u32,
+    ) -> Result<Vec<AuditLogRecord>, RepoError> {
+        sqlx::query_as::<_, AuditLogRecord>(
+            r#"
+              SELECT namespace, entry_id, actor, operation, resource_id, before_digest, after_digest, created_at::timestamptz
+              FROM audit_log
+              WHERE namespace = $1
+                AND ($2::text IS NULL OR actor = $2)
+                AND ($3::text IS NULL OR operation = $3)
+                AND ($4::text IS NULL OR resource_id = $4)
+                AND ($5::timestamptz IS NULL OR created_at >= $5)
+                AND ($6::timestamptz IS NULL OR created_at < $6)
+              ORDER BY created_at DESC, entry_id
+              LIMIT $7
+               "#,
+        )
+        .bind(namespace)
+        .bind(filter.actor.clone())
+        .bind(filter.operation.clone())
+        .bind(filter.resource_id.clone())
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(limit as i64)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(
+        &self,
+        namespace: &str,
+        filter: &AuditLogRecordFilter,
+        limit: u32,
+    ) -> Result<Vec<AuditLogRecord>, RepoError> {
+        sqlx::query_as::<_, AuditLogRecord>(
+            r#"
+              SELECT namespace, entry_id, actor, operation, resource_id, before_digest, after_digest, created_at
+              FROM audit_log
+              WHERE namespace = $1
+                AND ($2 IS NULL OR actor = $2)
+                AND ($3 IS NULL OR operation = $3)
+                AND ($4 IS NULL OR resource_id = $4)
+                AND ($5 IS NULL OR created_at >= $5)
+                AND ($6 IS NULL OR created_at < $6)
+              ORDER BY created_at DESC, entry_id
+              LIMIT $7
+               "#,
+        )
+        .bind(namespace)
+        .bind(filter.actor.clone())
+        .bind(filter.operation.clone())
+        .bind(filter.resource_id.clone())
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(limit as i64)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+}
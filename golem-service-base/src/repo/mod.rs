@@ -16,6 +16,8 @@ use golem_common::SafeDisplay;
 use sqlx::error::ErrorKind;
 use std::fmt::Display;
 
+pub mod audit_log;
+pub mod namespace_quota;
 pub mod plugin_installation;
 
 #[derive(Debug, thiserror::Error)]
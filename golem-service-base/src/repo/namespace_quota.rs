@@ -0,0 +1,222 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::repo::RepoError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct NamespaceQuotaRecord {
+    pub namespace: String,
+    pub resource: String,
+    pub quota_limit: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait NamespaceQuotaRepo {
+    async fn get_all(&self, namespace: &str) -> Result<Vec<NamespaceQuotaRecord>, RepoError>;
+
+    async fn get(
+        &self,
+        namespace: &str,
+        resource: &str,
+    ) -> Result<Option<NamespaceQuotaRecord>, RepoError>;
+
+    async fn put(&self, quota: &NamespaceQuotaRecord) -> Result<(), RepoError>;
+
+    async fn delete(&self, namespace: &str, resource: &str) -> Result<bool, RepoError>;
+}
+
+pub struct DbNamespaceQuotaRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbNamespaceQuotaRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedNamespaceQuotaRepo<Repo: NamespaceQuotaRepo> {
+    repo: Repo,
+}
+
+impl<Repo: NamespaceQuotaRepo> LoggedNamespaceQuotaRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        namespace: &str,
+        resource: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(namespace = namespace, resource = resource, "{}", message),
+            Err(error) => error!(
+                namespace = namespace,
+                resource = resource,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: NamespaceQuotaRepo + Send + Sync> NamespaceQuotaRepo for LoggedNamespaceQuotaRepo<Repo> {
+    async fn get_all(&self, namespace: &str) -> Result<Vec<NamespaceQuotaRecord>, RepoError> {
+        let result = self.repo.get_all(namespace).await;
+        Self::logged_with_id("get_all", namespace, None, result)
+    }
+
+    async fn get(
+        &self,
+        namespace: &str,
+        resource: &str,
+    ) -> Result<Option<NamespaceQuotaRecord>, RepoError> {
+        let result = self.repo.get(namespace, resource).await;
+        Self::logged_with_id("get", namespace, Some(resource), result)
+    }
+
+    async fn put(&self, quota: &NamespaceQuotaRecord) -> Result<(), RepoError> {
+        let result = self.repo.put(quota).await;
+        Self::logged_with_id("put", &quota.namespace, Some(&quota.resource), result)
+    }
+
+    async fn delete(&self, namespace: &str, resource: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete(namespace, resource).await;
+        Self::logged_with_id("delete", namespace, Some(resource), result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl NamespaceQuotaRepo for DbNamespaceQuotaRepo<sqlx::Postgres> {
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<NamespaceQuotaRecord>, RepoError> {
+        sqlx::query_as::<_, NamespaceQuotaRecord>(
+            r#"
+              SELECT namespace, resource, quota_limit, updated_at::timestamptz
+              FROM namespace_quotas
+              WHERE namespace = $1
+              ORDER BY resource
+               "#,
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<NamespaceQuotaRecord>, RepoError> {
+        sqlx::query_as::<_, NamespaceQuotaRecord>(
+            r#"
+              SELECT namespace, resource, quota_limit, updated_at
+              FROM namespace_quotas
+              WHERE namespace = $1
+              ORDER BY resource
+               "#,
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Postgres -> get)]
+    async fn get_postgres(
+        &self,
+        namespace: &str,
+        resource: &str,
+    ) -> Result<Option<NamespaceQuotaRecord>, RepoError> {
+        sqlx::query_as::<_, NamespaceQuotaRecord>(
+            r#"
+              SELECT namespace, resource, quota_limit, updated_at::timestamptz
+              FROM namespace_quotas
+              WHERE namespace = $1 AND resource = $2
+               "#,
+        )
+        .bind(namespace)
+        .bind(resource)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get)]
+    async fn get_sqlite(
+        &self,
+        namespace: &str,
+        resource: &str,
+    ) -> Result<Option<NamespaceQuotaRecord>, RepoError> {
+        sqlx::query_as::<_, NamespaceQuotaRecord>(
+            r#"
+              SELECT namespace, resource, quota_limit, updated_at
+              FROM namespace_quotas
+              WHERE namespace = $1 AND resource = $2
+               "#,
+        )
+        .bind(namespace)
+        .bind(resource)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn put(&self, quota: &NamespaceQuotaRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO namespace_quotas (namespace, resource, quota_limit, updated_at)
+              VALUES ($1, $2, $3, $4)
+              ON CONFLICT (namespace, resource) DO UPDATE
+              SET quota_limit = $3, updated_at = $4
+            "#,
+        )
+        .bind(quota.namespace.clone())
+        .bind(quota.resource.clone())
+        .bind(quota.quota_limit)
+        .bind(quota.updated_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, namespace: &str, resource: &str) -> Result<bool, RepoError> {
+        let result =
+            sqlx::query("DELETE FROM namespace_quotas WHERE namespace = $1 AND resource = $2")
+                .bind(namespace)
+                .bind(resource)
+                .execute(self.db_pool.deref())
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::auth::EmptyAuthCtx;
+use crate::repo::audit_log::{AuditLogRecord, AuditLogRecordFilter, AuditLogRepo};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use golem_common::SafeDisplay;
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::error;
+use uuid::Uuid;
+
+/// The identity recorded as the actor of the audited operations
+pub trait AuditActor {
+    fn audit_actor(&self) -> String;
+}
+
+impl AuditActor for EmptyAuthCtx {
+    fn audit_actor(&self) -> String {
+        "anonymous".to_string()
+    }
+}
+
+/// The kinds of the mutating management operations recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum AuditOperation {
+    ComponentCreate,
+    ComponentUpdate,
+    ComponentDelete,
+    ApiDeploymentDeploy,
+    ApiDeploymentDelete,
+    WorkerDelete,
+}
+
+impl AuditOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOperation::ComponentCreate => "component-create",
+            AuditOperation::ComponentUpdate => "component-update",
+            AuditOperation::ComponentDelete => "component-delete",
+            AuditOperation::ApiDeploymentDeploy => "api-deployment-deploy",
+            AuditOperation::ApiDeploymentDelete => "api-deployment-delete",
+            AuditOperation::WorkerDelete => "worker-delete",
+        }
+    }
+}
+
+impl Display for AuditOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AuditOperation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "component-create" => Ok(AuditOperation::ComponentCreate),
+            "component-update" => Ok(AuditOperation::ComponentUpdate),
+            "component-delete" => Ok(AuditOperation::ComponentDelete),
+            "api-deployment-deploy" => Ok(AuditOperation::ApiDeploymentDeploy),
+            "api-deployment-delete" => Ok(AuditOperation::ApiDeploymentDelete),
+            "worker-delete" => Ok(AuditOperation::WorkerDelete),
+            _ => Err(format!("Unknown audit operation: {s}")),
+        }
+    }
+}
+
+// An operation to be recorded, with the digests of the state of the resource before and
+// after it, if the resource existed
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditedOperation {
+    pub operation: AuditOperation,
+    pub resource_id: String,
+    pub before_digest: Option<String>,
+    pub after_digest: Option<String>,
+}
+
+impl AuditedOperation {
+    pub fn new(operation: AuditOperation, resource_id: impl Display) -> Self {
+        Self {
+            operation,
+            resource_id: resource_id.to_string(),
+            before_digest: None,
+            after_digest: None,
+        }
+    }
+
+    pub fn before<T: Serialize>(mut self, state: Option<&T>) -> Self {
+        self.before_digest = state.and_then(digest);
+        self
+    }
+
+    pub fn after<T: Serialize>(mut self, state: Option<&T>) -> Self {
+        self.after_digest = state.and_then(digest);
+        self
+    }
+}
+
+// The hex encoded SHA-256 of the JSON representation of the state of a resource. It is
+// converted to a JSON value first, whose objects are sorted by their keys, so maps of the
+// state do not change the digest by their iteration order
+pub fn digest<T: Serialize>(state: &T) -> Option<String> {
+    serde_json::to_value(state)
+        .and_then(|value| serde_json::to_vec(&value))
+        .ok()
+        .map(|json| hex::encode(Sha256::digest(json)))
+}
+
+/// An entry of the audit log, recording a mutating management operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    pub entry_id: String,
+    pub actor: String,
+    pub operation: AuditOperation,
+    /// The id of the component, worker or site the operation was applied to
+    pub resource_id: String,
+    /// The SHA-256 digest of the resource before the operation, missing if it did not exist
+    pub before_digest: Option<String>,
+    /// The SHA-256 digest of the resource after the operation, missing if it was deleted
+    pub after_digest: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<AuditLogRecord> for AuditLogEntry {
+    type Error = String;
+
+    fn try_from(value: AuditLogRecord) -> Result<Self, Self::Error> {
+        Ok(AuditLogEntry {
+            entry_id: value.entry_id,
+            actor: value.actor,
+            operation: AuditOperation::from_str(&value.operation)?,
+            resource_id: value.resource_id,
+            before_digest: value.before_digest,
+            after_digest: value.after_digest,
+            created_at: value.created_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditLogFilter {
+    pub actor: Option<String>,
+    pub operation: Option<AuditOperation>,
+    pub resource_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl From<AuditLogFilter> for AuditLogRecordFilter {
+    fn from(value: AuditLogFilter) -> Self {
+        AuditLogRecordFilter {
+            actor: value.actor,
+            operation: value.operation.map(|operation| operation.to_string()),
+            resource_id: value.resource_id,
+            since: value.since,
+            until: value.until,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum AuditLogError {
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for AuditLogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for AuditLogError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            AuditLogError::InternalError(err) => format!("Internal error: {}", err),
+        }
+    }
+}
+
+// Records the mutating management operations of the services
+#[async_trait]
+pub trait AuditLogService<Namespace> {
+    async fn record(
+        &self,
+        namespace: &Namespace,
+        actor: &(dyn AuditActor + Sync),
+        operation: AuditedOperation,
+    ) -> Result<AuditLogEntry, AuditLogError>;
+
+    // The matching entries, the newest first
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        filter: AuditLogFilter,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogError>;
+
+    // Audited operations are already applied when they are recorded, so failing to record
+    // them is logged instead of failing the operation
+    async fn record_or_log(
+        &self,
+        namespace: &Namespace,
+        actor: &(dyn AuditActor + Sync),
+        operation: AuditedOperation,
+    ) where
+        Namespace: Sync,
+    {
+        let resource_id = operation.resource_id.clone();
+        let kind = operation.operation;
+        if let Err(err) = self.record(namespace, actor, operation).await {
+            error!(
+                resource_id = resource_id,
+                "Failed to record {} in the audit log: {}", kind, err
+            );
+        }
+    }
+}
+
+pub struct DefaultAuditLogService {
+    repo: Arc<dyn AuditLogRepo + Sync + Send>,
+}
+
+impl DefaultAuditLogService {
+    pub fn new(repo: Arc<dyn AuditLogRepo + Sync + Send>) -> Self {
+        DefaultAuditLogService { repo }
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> AuditLogService<Namespace> for DefaultAuditLogService {
+    async fn record(
+        &self,
+        namespace: &Namespace,
+        actor: &(dyn AuditActor + Sync),
+        operation: AuditedOperation,
+    ) -> Result<AuditLogEntry, AuditLogError> {
+        let record = AuditLogRecord {
+            namespace: namespace.to_string(),
+            entry_id: Uuid::new_v4().to_string(),
+            actor: actor.audit_actor(),
+            operation: operation.operation.to_string(),
+            resource_id: operation.resource_id,
+            before_digest: operation.before_digest,
+            after_digest: operation.after_digest,
+            created_at: Utc::now(),
+        };
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| AuditLogError::InternalError(err.to_safe_string()))?;
+
+        AuditLogEntry::try_from(record).map_err(AuditLogError::InternalError)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        filter: AuditLogFilter,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>, AuditLogError> {
+        self.repo
+            .get_all(&namespace.to_string(), &filter.into(), limit)
+            .await
+            .map_err(|err| AuditLogError::InternalError(err.to_safe_string()))?
+            .into_iter()
+            .map(|record| AuditLogEntry::try_from(record).map_err(AuditLogError::InternalError))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn operations_round_trip_through_their_stored_names() {
+        for operation in [
+            AuditOperation::ComponentCreate,
+            AuditOperation::ComponentUpdate,
+            AuditOperation::ComponentDelete,
+            AuditOperation::ApiDeploymentDeploy,
+            AuditOperation::ApiDeploymentDelete,
+            AuditOperation::WorkerDelete,
+        ] {
+            assert_eq!(AuditOperation::from_str(operation.as_str()), Ok(operation));
+            assert_eq!(
+                serde_json::to_value(operation).unwrap(),
+                serde_json::Value::String(operation.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn digests_change_with_the_state() {
+        let before = HashMap::from([("version", 1)]);
+        let after = HashMap::from([("version", 2)]);
+
+        let operation = AuditedOperation::new(AuditOperation::ComponentUpdate, "component")
+            .before(Some(&before))
+            .after(Some(&after));
+
+        assert_eq!(operation.before_digest, digest(&before));
+        assert_ne!(operation.before_digest, operation.after_digest);
+        assert_eq!(operation.before_digest.map(|digest| digest.len()), Some(64));
+    }
+}
@@ -12,5 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod audit_log;
 pub mod initial_component_files;
+pub mod namespace_quota;
 pub mod routing_table;
@@ -0,0 +1,441 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::repo::namespace_quota::{NamespaceQuotaRecord, NamespaceQuotaRepo};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use golem_common::SafeDisplay;
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Changed limits are picked up by the other instances of a service within this period
+const LIMIT_CACHE_TTL: Duration = Duration::from_secs(10);
+
+const INVOCATION_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The resources of a namespace whose use can be limited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum QuotaResource {
+    /// The number of components
+    Components,
+    /// The number of workers
+    Workers,
+    /// The size of a component in bytes
+    ComponentSize,
+    /// The number of worker invocations per minute, counted by each worker service instance on
+    /// its own, so that a namespace can make this many invocations per minute through each of them
+    InvocationsPerMinute,
+}
+
+impl QuotaResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaResource::Components => "components",
+            QuotaResource::Workers => "workers",
+            QuotaResource::ComponentSize => "component-size",
+            QuotaResource::InvocationsPerMinute => "invocations-per-minute",
+        }
+    }
+}
+
+impl Display for QuotaResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for QuotaResource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "components" => Ok(QuotaResource::Components),
+            "workers" => Ok(QuotaResource::Workers),
+            "component-size" => Ok(QuotaResource::ComponentSize),
+            "invocations-per-minute" => Ok(QuotaResource::InvocationsPerMinute),
+            _ => Err(format!("Unknown quota resource: {s}")),
+        }
+    }
+}
+
+/// The limit of a resource of a namespace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct NamespaceQuota {
+    pub resource: QuotaResource,
+    pub limit: u64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<NamespaceQuotaRecord> for NamespaceQuota {
+    type Error = String;
+
+    fn try_from(value: NamespaceQuotaRecord) -> Result<Self, Self::Error> {
+        Ok(NamespaceQuota {
+            resource: QuotaResource::from_str(&value.resource)?,
+            limit: value.quota_limit as u64,
+            updated_at: value.updated_at,
+        })
+    }
+}
+
+/// Sets or removes the limit of a resource of a namespace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct NamespaceQuotaUpdate {
+    pub resource: QuotaResource,
+    /// The new limit of the resource, removing the limit when missing
+    pub limit: Option<u64>,
+}
+
+/// Describes an operation rejected for exceeding the limit of a resource of the namespace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct QuotaExceeded {
+    pub error: String,
+    pub resource: QuotaResource,
+    pub limit: u64,
+    /// The use of the resource the operation would have resulted in
+    pub requested: u64,
+}
+
+impl QuotaExceeded {
+    pub fn new(resource: QuotaResource, limit: u64, requested: u64) -> Self {
+        let error = match resource {
+            QuotaResource::Components => {
+                format!("The namespace has reached its limit of {limit} components")
+            }
+            QuotaResource::Workers => {
+                format!("The namespace has reached its limit of {limit} workers")
+            }
+            QuotaResource::ComponentSize => format!(
+                "The component of {requested} bytes exceeds the limit of {limit} bytes of the namespace"
+            ),
+            QuotaResource::InvocationsPerMinute => {
+                format!("The namespace has reached its limit of {limit} invocations per minute")
+            }
+        };
+
+        Self {
+            error,
+            resource,
+            limit,
+            requested,
+        }
+    }
+}
+
+// Fails if the use the operation results in is over the limit
+pub fn ensure_within_limit(
+    resource: QuotaResource,
+    limit: Option<u64>,
+    requested: u64,
+) -> Result<(), NamespaceQuotaError> {
+    match limit {
+        Some(limit) if requested > limit => Err(NamespaceQuotaError::Exceeded(QuotaExceeded::new(
+            resource, limit, requested,
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NamespaceQuotaError {
+    Exceeded(QuotaExceeded),
+    UnsupportedResource(QuotaResource),
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for NamespaceQuotaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for NamespaceQuotaError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            NamespaceQuotaError::Exceeded(exceeded) => exceeded.error.clone(),
+            NamespaceQuotaError::UnsupportedResource(resource) => {
+                format!("The quota of {resource} is not managed by this service")
+            }
+            NamespaceQuotaError::InternalError(err) => format!("Internal error: {}", err),
+        }
+    }
+}
+
+// Limits the resources of the namespaces. Each service manages and enforces the quotas of
+// the resources it owns.
+#[async_trait]
+pub trait NamespaceQuotaService<Namespace> {
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Vec<NamespaceQuota>, NamespaceQuotaError>;
+
+    async fn set(
+        &self,
+        namespace: &Namespace,
+        resource: QuotaResource,
+        limit: u64,
+    ) -> Result<NamespaceQuota, NamespaceQuotaError>;
+
+    // Removes the limit of the resource
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        resource: QuotaResource,
+    ) -> Result<(), NamespaceQuotaError>;
+
+    // The limit of the resource, if the namespace has one
+    async fn limit(
+        &self,
+        namespace: &Namespace,
+        resource: QuotaResource,
+    ) -> Result<Option<u64>, NamespaceQuotaError>;
+
+    // Counts an invocation in the current window of the namespace, failing if it is over the
+    // limit. The windows are kept in the memory of each instance of the service rather than
+    // shared, so the limit applies to each instance on its own.
+    async fn acquire_invocation(&self, namespace: &Namespace) -> Result<(), NamespaceQuotaError>;
+
+    // Applies the update, returning all the quotas of the namespace
+    async fn update(
+        &self,
+        namespace: &Namespace,
+        update: NamespaceQuotaUpdate,
+    ) -> Result<Vec<NamespaceQuota>, NamespaceQuotaError>
+    where
+        Namespace: Sync,
+    {
+        match update.limit {
+            Some(limit) => {
+                self.set(namespace, update.resource, limit).await?;
+            }
+            None => self.delete(namespace, update.resource).await?,
+        }
+
+        self.get_all(namespace).await
+    }
+}
+
+pub struct DefaultNamespaceQuotaService {
+    repo: Arc<dyn NamespaceQuotaRepo + Sync + Send>,
+    resources: Vec<QuotaResource>,
+    limits: Mutex<HashMap<(String, QuotaResource), (Instant, Option<u64>)>>,
+    invocations: Mutex<HashMap<String, InvocationWindow>>,
+}
+
+struct InvocationWindow {
+    started_at: Instant,
+    count: u64,
+}
+
+impl DefaultNamespaceQuotaService {
+    // Only the quotas of `resources` can be managed through the service
+    pub fn new(
+        repo: Arc<dyn NamespaceQuotaRepo + Sync + Send>,
+        resources: Vec<QuotaResource>,
+    ) -> Self {
+        Self {
+            repo,
+            resources,
+            limits: Mutex::new(HashMap::new()),
+            invocations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn ensure_supported(&self, resource: QuotaResource) -> Result<(), NamespaceQuotaError> {
+        if self.resources.contains(&resource) {
+            Ok(())
+        } else {
+            Err(NamespaceQuotaError::UnsupportedResource(resource))
+        }
+    }
+
+    fn invalidate(&self, namespace: String, resource: QuotaResource) {
+        self.limits.lock().unwrap().remove(&(namespace, resource));
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> NamespaceQuotaService<Namespace>
+    for DefaultNamespaceQuotaService
+{
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Vec<NamespaceQuota>, NamespaceQuotaError> {
+        self.repo
+            .get_all(&namespace.to_string())
+            .await
+            .map_err(|err| NamespaceQuotaError::InternalError(err.to_safe_string()))?
+            .into_iter()
+            .map(NamespaceQuota::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(NamespaceQuotaError::InternalError)
+            .map(|quotas| {
+                quotas
+                    .into_iter()
+                    .filter(|quota| self.resources.contains(&quota.resource))
+                    .collect()
+            })
+    }
+
+    async fn set(
+        &self,
+        namespace: &Namespace,
+        resource: QuotaResource,
+        limit: u64,
+    ) -> Result<NamespaceQuota, NamespaceQuotaError> {
+        self.ensure_supported(resource)?;
+
+        let record = NamespaceQuotaRecord {
+            namespace: namespace.to_string(),
+            resource: resource.to_string(),
+            quota_limit: limit.min(i64::MAX as u64) as i64,
+            updated_at: Utc::now(),
+        };
+
+        self.repo
+            .put(&record)
+            .await
+            .map_err(|err| NamespaceQuotaError::InternalError(err.to_safe_string()))?;
+
+        self.invalidate(namespace.to_string(), resource);
+
+        NamespaceQuota::try_from(record).map_err(NamespaceQuotaError::InternalError)
+    }
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        resource: QuotaResource,
+    ) -> Result<(), NamespaceQuotaError> {
+        self.ensure_supported(resource)?;
+
+        self.repo
+            .delete(&namespace.to_string(), resource.as_str())
+            .await
+            .map_err(|err| NamespaceQuotaError::InternalError(err.to_safe_string()))?;
+
+        self.invalidate(namespace.to_string(), resource);
+
+        Ok(())
+    }
+
+    async fn limit(
+        &self,
+        namespace: &Namespace,
+        resource: QuotaResource,
+    ) -> Result<Option<u64>, NamespaceQuotaError> {
+        let key = (namespace.to_string(), resource);
+
+        let cached = self.limits.lock().unwrap().get(&key).copied();
+        if let Some((cached_at, limit)) = cached {
+            if cached_at.elapsed() < LIMIT_CACHE_TTL {
+                return Ok(limit);
+            }
+        }
+
+        let limit = self
+            .repo
+            .get(&key.0, resource.as_str())
+            .await
+            .map_err(|err| NamespaceQuotaError::InternalError(err.to_safe_string()))?
+            .map(|record| record.quota_limit as u64);
+
+        self.limits
+            .lock()
+            .unwrap()
+            .insert(key, (Instant::now(), limit));
+
+        Ok(limit)
+    }
+
+    async fn acquire_invocation(&self, namespace: &Namespace) -> Result<(), NamespaceQuotaError> {
+        let resource = QuotaResource::InvocationsPerMinute;
+        let Some(limit) = self.limit(namespace, resource).await? else {
+            return Ok(());
+        };
+
+        let mut invocations = self.invocations.lock().unwrap();
+        let window = invocations
+            .entry(namespace.to_string())
+            .or_insert_with(|| InvocationWindow {
+                started_at: Instant::now(),
+                count: 0,
+            });
+
+        if window.started_at.elapsed() >= INVOCATION_RATE_WINDOW {
+            window.started_at = Instant::now();
+            window.count = 0;
+        }
+
+        ensure_within_limit(resource, Some(limit), window.count + 1)?;
+        window.count += 1;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+
+    #[test]
+    fn resources_round_trip_through_their_stored_names() {
+        for resource in [
+            QuotaResource::Components,
+            QuotaResource::Workers,
+            QuotaResource::ComponentSize,
+            QuotaResource::InvocationsPerMinute,
+        ] {
+            assert_eq!(QuotaResource::from_str(resource.as_str()), Ok(resource));
+            assert_eq!(
+                serde_json::to_value(resource).unwrap(),
+                serde_json::Value::String(resource.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn limits_allow_the_use_up_to_the_limit() {
+        assert!(ensure_within_limit(QuotaResource::Components, None, 1000).is_ok());
+        assert!(ensure_within_limit(QuotaResource::Components, Some(3), 3).is_ok());
+
+        match ensure_within_limit(QuotaResource::ComponentSize, Some(1024), 2048) {
+            Err(NamespaceQuotaError::Exceeded(exceeded)) => {
+                assert_eq!(exceeded.resource, QuotaResource::ComponentSize);
+                assert_eq!(exceeded.limit, 1024);
+                assert_eq!(exceeded.requested, 2048);
+            }
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+}
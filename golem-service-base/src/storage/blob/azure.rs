@@ -0,0 +1,867 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::config::AzureBlobStorageConfig;
+use crate::storage::blob::{
+    BlobMetadata, BlobStorage, BlobStorageNamespace, ExistsResult, ReplayableStream,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use golem_common::model::Timestamp;
+use golem_common::retries::with_retries_customized;
+use hmac::{Hmac, Mac};
+use reqwest::header::{CONTENT_LENGTH, LAST_MODIFIED};
+use reqwest::{Method, Response, StatusCode};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use tracing::info;
+use url::Url;
+
+const STORAGE_VERSION: &str = "2021-08-06";
+const DIR_MARKER: &str = "__dir_marker";
+
+/// Blob storage on Azure Blob Storage, authenticated with the shared key of the storage account
+pub struct AzureBlobStorage {
+    client: reqwest::Client,
+    endpoint: Url,
+    access_key: Vec<u8>,
+    config: AzureBlobStorageConfig,
+}
+
+impl Debug for AzureBlobStorage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AzureBlobStorage")
+            .field("endpoint", &self.endpoint)
+            .field("account", &self.config.account)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AzureBlobStorage {
+    pub fn new(config: AzureBlobStorageConfig) -> Result<Self, String> {
+        let endpoint = match &config.endpoint_url {
+            Some(endpoint_url) => {
+                info!("The Azure endpoint url for blob storage is {endpoint_url}");
+                endpoint_url.clone()
+            }
+            None => format!("https://{}.blob.core.windows.net", config.account),
+        };
+        let endpoint = Url::parse(&endpoint)
+            .map_err(|err| format!("Invalid Azure Blob Storage endpoint: {err}"))?;
+        let access_key = base64::engine::general_purpose::STANDARD
+            .decode(&config.access_key)
+            .map_err(|err| format!("Invalid Azure Blob Storage access key: {err}"))?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            endpoint,
+            access_key,
+            config,
+        })
+    }
+
+    /// Creates the configured containers which do not exist yet
+    pub async fn ensure_containers(&self) -> Result<(), String> {
+        let containers = [
+            &self.config.compilation_cache_container,
+            &self.config.custom_data_container,
+            &self.config.oplog_payload_container,
+            &self.config.initial_component_files_container,
+        ]
+        .into_iter()
+        .chain(self.config.compressed_oplog_containers.iter());
+
+        for container in containers {
+            let mut url = self.container_url(container)?;
+            url.query_pairs_mut().append_pair("restype", "container");
+
+            match self.send_once(&Method::PUT, &url, &[], &Bytes::new()).await {
+                Ok(_) => {}
+                Err(AzureRequestError::Status {
+                    status: StatusCode::CONFLICT,
+                    ..
+                }) => {}
+                Err(err) => return Err(err.to_string()),
+            }
+        }
+        Ok(())
+    }
+
+    fn container_of(&self, namespace: &BlobStorageNamespace) -> &String {
+        match namespace {
+            BlobStorageNamespace::CompilationCache => &self.config.compilation_cache_container,
+            BlobStorageNamespace::CustomStorage(_account_id) => &self.config.custom_data_container,
+            BlobStorageNamespace::OplogPayload { .. } => &self.config.oplog_payload_container,
+            BlobStorageNamespace::CompressedOplog { level, .. } => {
+                &self.config.compressed_oplog_containers[*level]
+            }
+            BlobStorageNamespace::InitialComponentFiles { .. } => {
+                &self.config.initial_component_files_container
+            }
+        }
+    }
+
+    fn prefix_of(&self, namespace: &BlobStorageNamespace) -> PathBuf {
+        let prefix = Path::new(&self.config.object_prefix);
+        match namespace {
+            BlobStorageNamespace::CompilationCache => prefix.to_path_buf(),
+            BlobStorageNamespace::CustomStorage(account_id)
+            | BlobStorageNamespace::InitialComponentFiles { account_id } => {
+                prefix.join(account_id.to_string())
+            }
+            BlobStorageNamespace::OplogPayload {
+                account_id,
+                worker_id,
+            } => prefix
+                .join(account_id.to_string())
+                .join(worker_id.to_string()),
+            BlobStorageNamespace::CompressedOplog {
+                account_id,
+                component_id,
+                ..
+            } => prefix
+                .join(account_id.to_string())
+                .join(component_id.to_string()),
+        }
+    }
+
+    fn container_url(&self, container: &str) -> Result<Url, String> {
+        let mut url = self.endpoint.clone();
+        url.path_segments_mut()
+            .map_err(|_| "Azure Blob Storage endpoint cannot be a base url".to_string())?
+            .pop_if_empty()
+            .push(container);
+        Ok(url)
+    }
+
+    fn blob_url(&self, container: &str, key: &Path) -> Result<Url, String> {
+        let mut url = self.container_url(container)?;
+        url.path_segments_mut()
+            .map_err(|_| "Azure Blob Storage endpoint cannot be a base url".to_string())?
+            .extend(key.iter().map(|segment| segment.to_string_lossy()));
+        Ok(url)
+    }
+
+    fn url_of(&self, namespace: &BlobStorageNamespace, path: &Path) -> Result<Url, String> {
+        self.blob_url(
+            self.container_of(namespace),
+            &self.prefix_of(namespace).join(path),
+        )
+    }
+
+    fn marker_url_of(&self, namespace: &BlobStorageNamespace, path: &Path) -> Result<Url, String> {
+        self.url_of(namespace, &path.join(DIR_MARKER))
+    }
+
+    // Signs the request with the shared key, see
+    // https://learn.microsoft.com/en-us/rest/api/storageservices/authorize-with-shared-key
+    fn authorization(
+        &self,
+        method: &Method,
+        url: &Url,
+        headers: &[(&'static str, String)],
+        content_length: usize,
+    ) -> Result<String, String> {
+        let mut ms_headers = headers
+            .iter()
+            .filter(|(name, _)| name.starts_with("x-ms-"))
+            .collect::<Vec<_>>();
+        ms_headers.sort_by_key(|(name, _)| *name);
+        let canonicalized_headers = ms_headers
+            .into_iter()
+            .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+            .collect::<String>();
+
+        let mut query: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (name, value) in url.query_pairs() {
+            query
+                .entry(name.to_lowercase())
+                .or_default()
+                .push(value.to_string());
+        }
+        let mut canonicalized_resource = format!("/{}{}", self.config.account, url.path());
+        for (name, mut values) in query {
+            values.sort();
+            canonicalized_resource.push_str(&format!("\n{name}:{}", values.join(",")));
+        }
+
+        let content_length = if content_length == 0 {
+            "".to_string()
+        } else {
+            content_length.to_string()
+        };
+        let string_to_sign = format!(
+            "{method}\n\n\n{content_length}\n\n\n\n\n\n\n\n\n{canonicalized_headers}{canonicalized_resource}"
+        );
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.access_key)
+            .map_err(|err| format!("Invalid Azure Blob Storage access key: {err}"))?;
+        mac.update(string_to_sign.as_bytes());
+        let signature =
+            base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes());
+
+        Ok(format!("SharedKey {}:{signature}", self.config.account))
+    }
+
+    async fn send_once(
+        &self,
+        method: &Method,
+        url: &Url,
+        headers: &[(&'static str, String)],
+        body: &Bytes,
+    ) -> Result<Response, AzureRequestError> {
+        let mut headers = headers.to_vec();
+        headers.push((
+            "x-ms-date",
+            Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+        ));
+        headers.push(("x-ms-version", STORAGE_VERSION.to_string()));
+
+        let authorization = self
+            .authorization(method, url, &headers, body.len())
+            .map_err(AzureRequestError::Request)?;
+
+        let mut request = self
+            .client
+            .request(method.clone(), url.clone())
+            .header("Authorization", authorization);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        if method == Method::PUT {
+            request = request
+                .header(CONTENT_LENGTH, body.len())
+                .body(body.clone());
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| AzureRequestError::Request(err.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() || status == StatusCode::NOT_FOUND {
+            Ok(response)
+        } else {
+            Err(AzureRequestError::Status {
+                status,
+                message: response.text().await.unwrap_or_default(),
+            })
+        }
+    }
+
+    // Sends a request with retries, returning None if the blob or the container does not exist
+    async fn send(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        method: Method,
+        url: Url,
+        headers: Vec<(&'static str, String)>,
+        body: Bytes,
+    ) -> Result<Option<Response>, String> {
+        let response = with_retries_customized(
+            target_label,
+            op_label,
+            Some(format!("{method} {}", url.path())),
+            &self.config.retries,
+            &(self, method, url, headers, body),
+            |(storage, method, url, headers, body)| {
+                Box::pin(storage.send_once(method, url, headers, body))
+            },
+            AzureRequestError::is_retriable,
+            |err| Some(err.to_string()),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            Ok(None)
+        } else {
+            Ok(Some(response))
+        }
+    }
+
+    async fn head(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        url: Url,
+    ) -> Result<Option<BlobMetadata>, String> {
+        match self
+            .send(
+                target_label,
+                op_label,
+                Method::HEAD,
+                url,
+                vec![],
+                Bytes::new(),
+            )
+            .await?
+        {
+            Some(response) => Ok(Some(metadata_of(&response)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn put_blob(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        url: Url,
+        data: Bytes,
+    ) -> Result<(), String> {
+        self.send(
+            target_label,
+            op_label,
+            Method::PUT,
+            url,
+            vec![("x-ms-blob-type", "BlockBlob".to_string())],
+            data,
+        )
+        .await?;
+        Ok(())
+    }
+
+    // Uploads the stream as blocks of `block_size` bytes and commits them. Blocks which are never
+    // committed because of a failure are removed by the service after a week.
+    async fn put_blocks(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        url: Url,
+        stream: &dyn ReplayableStream<Item = Result<Bytes, String>>,
+    ) -> Result<(), String> {
+        let block_size = self.config.block_size as usize;
+        let mut stream = stream.make_stream().await?;
+        let mut buffer = BytesMut::with_capacity(block_size);
+        let mut block_ids = Vec::new();
+
+        loop {
+            let chunk = stream.next().await.transpose()?;
+            let finished = chunk.is_none();
+            if let Some(chunk) = chunk {
+                buffer.extend_from_slice(&chunk);
+            }
+
+            while buffer.len() >= block_size || (finished && !buffer.is_empty()) {
+                let block = buffer.split_to(buffer.len().min(block_size)).freeze();
+                // The ids of the blocks of a blob must have the same length
+                let block_id = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{:08}", block_ids.len()));
+
+                let mut block_url = url.clone();
+                block_url
+                    .query_pairs_mut()
+                    .append_pair("comp", "block")
+                    .append_pair("blockid", &block_id);
+                self.send(
+                    target_label,
+                    op_label,
+                    Method::PUT,
+                    block_url,
+                    vec![],
+                    block,
+                )
+                .await?;
+
+                block_ids.push(block_id);
+            }
+
+            if finished {
+                break;
+            }
+        }
+
+        let block_list = format!(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList>{}</BlockList>",
+            block_ids
+                .iter()
+                .map(|block_id| format!("<Latest>{block_id}</Latest>"))
+                .collect::<String>()
+        );
+        let mut block_list_url = url;
+        block_list_url
+            .query_pairs_mut()
+            .append_pair("comp", "blocklist");
+        self.send(
+            target_label,
+            op_label,
+            Method::PUT,
+            block_list_url,
+            vec![],
+            Bytes::from(block_list),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn list_blobs(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        container: &str,
+        prefix: &Path,
+    ) -> Result<Vec<PathBuf>, String> {
+        let prefix = prefix.to_string_lossy();
+        let prefix = if prefix.is_empty() || prefix.ends_with('/') {
+            prefix.to_string()
+        } else {
+            format!("{prefix}/")
+        };
+
+        let mut result = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let mut url = self.container_url(container)?;
+            url.query_pairs_mut()
+                .append_pair("restype", "container")
+                .append_pair("comp", "list")
+                .append_pair("prefix", &prefix);
+            if let Some(marker) = &marker {
+                url.query_pairs_mut().append_pair("marker", marker);
+            }
+
+            let response = self
+                .send(
+                    target_label,
+                    op_label,
+                    Method::GET,
+                    url,
+                    vec![],
+                    Bytes::new(),
+                )
+                .await?
+                .ok_or_else(|| format!("Container {container} does not exist"))?;
+            let body = response.text().await.map_err(|err| err.to_string())?;
+
+            result.extend(xml_elements(&body, "Name").into_iter().map(PathBuf::from));
+
+            match xml_elements(&body, "NextMarker")
+                .into_iter()
+                .find(|next_marker| !next_marker.is_empty())
+            {
+                Some(next_marker) => marker = Some(next_marker),
+                None => break,
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl BlobStorage for AzureBlobStorage {
+    async fn get_raw(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<Bytes>, String> {
+        let url = self.url_of(&namespace, path)?;
+
+        match self
+            .send(
+                target_label,
+                op_label,
+                Method::GET,
+                url,
+                vec![],
+                Bytes::new(),
+            )
+            .await?
+        {
+            Some(response) => Ok(Some(response.bytes().await.map_err(|err| err.to_string())?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_stream(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<Pin<Box<dyn futures::Stream<Item = Result<Bytes, String>> + Send>>>, String>
+    {
+        let url = self.url_of(&namespace, path)?;
+
+        match self
+            .send(
+                target_label,
+                op_label,
+                Method::GET,
+                url,
+                vec![],
+                Bytes::new(),
+            )
+            .await?
+        {
+            Some(response) => {
+                let stream = response.bytes_stream().map_err(|err| err.to_string());
+                let pinned: Pin<Box<dyn futures::Stream<Item = Result<Bytes, String>> + Send>> =
+                    Box::pin(stream);
+                Ok(Some(pinned))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn get_raw_slice(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        start: u64,
+        end: u64,
+    ) -> Result<Option<Bytes>, String> {
+        let url = self.url_of(&namespace, path)?;
+
+        match self
+            .send(
+                target_label,
+                op_label,
+                Method::GET,
+                url,
+                vec![("x-ms-range", format!("bytes={start}-{end}"))],
+                Bytes::new(),
+            )
+            .await?
+        {
+            Some(response) => Ok(Some(response.bytes().await.map_err(|err| err.to_string())?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_metadata(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Option<BlobMetadata>, String> {
+        let url = self.url_of(&namespace, path)?;
+
+        match self.head(target_label, op_label, url).await? {
+            Some(metadata) => Ok(Some(metadata)),
+            None => {
+                let marker_url = self.marker_url_of(&namespace, path)?;
+                Ok(self
+                    .head(target_label, op_label, marker_url)
+                    .await?
+                    .map(|metadata| BlobMetadata {
+                        size: 0,
+                        ..metadata
+                    }))
+            }
+        }
+    }
+
+    async fn put_raw(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        data: &[u8],
+    ) -> Result<(), String> {
+        let url = self.url_of(&namespace, path)?;
+        self.put_blob(target_label, op_label, url, Bytes::copy_from_slice(data))
+            .await
+    }
+
+    async fn put_stream(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+        stream: &dyn ReplayableStream<Item = Result<Bytes, String>>,
+    ) -> Result<(), String> {
+        let url = self.url_of(&namespace, path)?;
+
+        if stream.length().await? > self.config.block_upload_threshold {
+            self.put_blocks(target_label, op_label, url, stream).await
+        } else {
+            let data = stream
+                .make_stream()
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?
+                .concat();
+            self.put_blob(target_label, op_label, url, Bytes::from(data))
+                .await
+        }
+    }
+
+    async fn delete(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<(), String> {
+        let url = self.url_of(&namespace, path)?;
+        self.send(
+            target_label,
+            op_label,
+            Method::DELETE,
+            url,
+            vec![],
+            Bytes::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn create_dir(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<(), String> {
+        let marker_url = self.marker_url_of(&namespace, path)?;
+        self.put_blob(target_label, op_label, marker_url, Bytes::new())
+            .await
+    }
+
+    async fn list_dir(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<Vec<PathBuf>, String> {
+        let container = self.container_of(&namespace);
+        let namespace_root = self.prefix_of(&namespace);
+        let key = namespace_root.join(path);
+
+        Ok(self
+            .list_blobs(target_label, op_label, container, &key)
+            .await?
+            .into_iter()
+            .filter_map(|path| {
+                let is_dir_marker = path.file_name().and_then(|s| s.to_str()) == Some(DIR_MARKER);
+                let is_nested = path.parent() != Some(&key);
+                if is_nested {
+                    if is_dir_marker {
+                        path.parent().map(|p| p.to_path_buf())
+                    } else {
+                        None
+                    }
+                } else if is_dir_marker {
+                    None
+                } else {
+                    Some(path)
+                }
+            })
+            .filter_map(|path| {
+                path.strip_prefix(&namespace_root)
+                    .ok()
+                    .map(|p| p.to_path_buf())
+            })
+            .collect::<Vec<_>>())
+    }
+
+    async fn delete_dir(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<bool, String> {
+        let container = self.container_of(&namespace);
+        let key = self.prefix_of(&namespace).join(path);
+
+        let to_delete = self
+            .list_blobs(target_label, op_label, container, &key)
+            .await?;
+        let has_entries = !to_delete.is_empty();
+
+        for blob in to_delete {
+            let url = self.blob_url(container, &blob)?;
+            self.send(
+                target_label,
+                op_label,
+                Method::DELETE,
+                url,
+                vec![],
+                Bytes::new(),
+            )
+            .await?;
+        }
+
+        Ok(has_entries)
+    }
+
+    async fn exists(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        path: &Path,
+    ) -> Result<ExistsResult, String> {
+        let url = self.url_of(&namespace, path)?;
+        if self.head(target_label, op_label, url).await?.is_some() {
+            return Ok(ExistsResult::File);
+        }
+
+        let marker_url = self.marker_url_of(&namespace, path)?;
+        if self
+            .head(target_label, op_label, marker_url)
+            .await?
+            .is_some()
+        {
+            Ok(ExistsResult::Directory)
+        } else {
+            Ok(ExistsResult::DoesNotExist)
+        }
+    }
+
+    async fn copy(
+        &self,
+        target_label: &'static str,
+        op_label: &'static str,
+        namespace: BlobStorageNamespace,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), String> {
+        let from_url = self.url_of(&namespace, from)?;
+        let to_url = self.url_of(&namespace, to)?;
+
+        // Copies within the same storage account are authorized by the shared key of the request
+        match self
+            .send(
+                target_label,
+                op_label,
+                Method::PUT,
+                to_url,
+                vec![("x-ms-copy-source", from_url.to_string())],
+                Bytes::new(),
+            )
+            .await?
+        {
+            Some(_) => Ok(()),
+            None => Err(format!("Entry not found: {:?}", from)),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum AzureRequestError {
+    #[error("{0}")]
+    Request(String),
+    #[error("Azure Blob Storage responded with {status}: {message}")]
+    Status { status: StatusCode, message: String },
+}
+
+impl AzureRequestError {
+    fn is_retriable(&self) -> bool {
+        match self {
+            AzureRequestError::Request(_) => true,
+            AzureRequestError::Status { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+            }
+        }
+    }
+}
+
+fn metadata_of(response: &Response) -> Result<BlobMetadata, String> {
+    let size = response
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_default();
+    let last_modified = response
+        .headers()
+        .get(LAST_MODIFIED)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| "Missing Last-Modified header".to_string())?;
+    let last_modified = DateTime::parse_from_rfc2822(last_modified)
+        .map_err(|err| format!("Invalid Last-Modified header: {err}"))?;
+
+    Ok(BlobMetadata {
+        size,
+        last_modified_at: Timestamp::from(last_modified.timestamp_millis() as u64),
+    })
+}
+
+// The text of the elements with the given tag in the XML responses of the service, which are
+// simple enough not to need a full parser
+fn xml_elements(xml: &str, tag: &str) -> Vec<String> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+
+    let mut result = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let after_open = &rest[start + open.len()..];
+        match after_open.find(&close) {
+            Some(end) => {
+                result.push(unescape_xml(&after_open[..end]));
+                rest = &after_open[end + close.len()..];
+            }
+            None => break,
+        }
+    }
+    result
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn blob_names_and_markers_are_read_from_list_responses() {
+        let response = r#"<?xml version="1.0" encoding="utf-8"?>
+            <EnumerationResults ServiceEndpoint="http://127.0.0.1:10000/devstoreaccount1" ContainerName="custom-data">
+              <Prefix>account/</Prefix>
+              <Blobs>
+                <Blob><Name>account/a &amp; b.txt</Name><Properties><Content-Length>3</Content-Length></Properties></Blob>
+                <Blob><Name>account/dir/__dir_marker</Name><Properties /></Blob>
+              </Blobs>
+              <NextMarker>2!84!MDAwMDE5</NextMarker>
+            </EnumerationResults>"#;
+
+        assert_eq!(
+            xml_elements(response, "Name"),
+            vec!["account/a & b.txt", "account/dir/__dir_marker"]
+        );
+        assert_eq!(xml_elements(response, "NextMarker"), vec!["2!84!MDAwMDE5"]);
+        assert!(xml_elements("<NextMarker />", "NextMarker").is_empty());
+    }
+}
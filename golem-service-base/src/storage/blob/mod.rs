@@ -22,6 +22,7 @@ use std::fmt::Debug;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 
+pub mod azure;
 pub mod fs;
 pub mod memory;
 pub mod s3;
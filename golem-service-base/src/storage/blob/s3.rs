@@ -22,17 +22,19 @@ use aws_sdk_s3::operation::get_object::GetObjectError::NoSuchKey;
 use aws_sdk_s3::operation::head_object::HeadObjectError;
 use aws_sdk_s3::operation::put_object::PutObjectError;
 use aws_sdk_s3::primitives::ByteStream;
-use aws_sdk_s3::types::{Delete, Object, ObjectIdentifier};
+use aws_sdk_s3::types::{
+    CompletedMultipartUpload, CompletedPart, Delete, Object, ObjectIdentifier,
+};
 use aws_sdk_s3::Client;
-use bytes::Bytes;
-use futures::TryFutureExt;
+use bytes::{Bytes, BytesMut};
+use futures::{StreamExt, TryFutureExt};
 use golem_common::model::Timestamp;
 use golem_common::retries::with_retries_customized;
 use std::error::Error;
 use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
-use tracing::info;
+use tracing::{info, warn};
 
 use super::ReplayableStream;
 
@@ -64,8 +66,13 @@ impl S3BlobStorage {
 
         let sdk_config = config_builder.load().await;
 
+        // S3-compatible stores like MinIO address the buckets in the path instead of the host
+        let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            .force_path_style(config.force_path_style)
+            .build();
+
         Self {
-            client: aws_sdk_s3::Client::new(&sdk_config),
+            client: aws_sdk_s3::Client::from_conf(s3_config),
             config,
         }
     }
@@ -197,6 +204,111 @@ impl S3BlobStorage {
         Ok(result)
     }
 
+    // Uploads the stream in parts of `part_size` bytes, aborting the upload if any of them fails
+    async fn upload_multipart(
+        client: &Client,
+        bucket: &str,
+        key: &Path,
+        stream: &dyn ReplayableStream<Item = Result<Bytes, String>>,
+        part_size: u64,
+    ) -> Result<(), String> {
+        let key = key.to_string_lossy().to_string();
+        let upload = client
+            .create_multipart_upload()
+            .bucket(bucket)
+            .key(&key)
+            .send()
+            .await
+            .map_err(|err| Self::error_string(&err))?;
+        let upload_id = upload
+            .upload_id()
+            .ok_or_else(|| "Multipart upload was created without an upload id".to_string())?
+            .to_string();
+
+        match Self::upload_parts(client, bucket, &key, &upload_id, stream, part_size).await {
+            Ok(parts) => client
+                .complete_multipart_upload()
+                .bucket(bucket)
+                .key(&key)
+                .upload_id(&upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(parts))
+                        .build(),
+                )
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(|err| Self::error_string(&err)),
+            Err(err) => {
+                if let Err(abort_err) = client
+                    .abort_multipart_upload()
+                    .bucket(bucket)
+                    .key(&key)
+                    .upload_id(&upload_id)
+                    .send()
+                    .await
+                {
+                    warn!(
+                        upload_id,
+                        error = Self::error_string(&abort_err),
+                        "Failed to abort multipart upload"
+                    );
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn upload_parts(
+        client: &Client,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        stream: &dyn ReplayableStream<Item = Result<Bytes, String>>,
+        part_size: u64,
+    ) -> Result<Vec<CompletedPart>, String> {
+        let part_size = part_size as usize;
+        let mut stream = stream.make_stream().await?;
+        let mut buffer = BytesMut::with_capacity(part_size);
+        let mut parts = Vec::new();
+
+        loop {
+            let chunk = stream.next().await.transpose()?;
+            let finished = chunk.is_none();
+            if let Some(chunk) = chunk {
+                buffer.extend_from_slice(&chunk);
+            }
+
+            while buffer.len() >= part_size || (finished && !buffer.is_empty()) {
+                let part = buffer.split_to(buffer.len().min(part_size)).freeze();
+                let part_number = parts.len() as i32 + 1;
+                let response = client
+                    .upload_part()
+                    .bucket(bucket)
+                    .key(key)
+                    .upload_id(upload_id)
+                    .part_number(part_number)
+                    .body(ByteStream::from(part))
+                    .send()
+                    .await
+                    .map_err(|err| Self::error_string(&err))?;
+                parts.push(
+                    CompletedPart::builder()
+                        .part_number(part_number)
+                        .set_e_tag(response.e_tag().map(|e_tag| e_tag.to_string()))
+                        .build(),
+                );
+            }
+
+            if finished {
+                break;
+            }
+        }
+
+        Ok(parts)
+    }
+
     fn is_get_object_error_retriable(
         error: &SdkError<aws_sdk_s3::operation::get_object::GetObjectError>,
     ) -> bool {
@@ -583,6 +695,41 @@ impl BlobStorage for S3BlobStorage {
         let bucket = self.bucket_of(&namespace);
         let key = self.prefix_of(&namespace).join(path);
 
+        if stream.length().await? > self.config.multipart_threshold {
+            fn go_multipart<'a>(
+                args: &'a (
+                    Client,
+                    &String,
+                    PathBuf,
+                    &dyn ReplayableStream<Item = Result<Bytes, String>>,
+                    u64,
+                ),
+            ) -> Pin<Box<dyn Future<Output = Result<(), String>> + 'a + Send>> {
+                let (client, bucket, key, stream, part_size) = args;
+                Box::pin(S3BlobStorage::upload_multipart(
+                    client, bucket, key, *stream, *part_size,
+                ))
+            }
+
+            return with_retries_customized(
+                target_label,
+                op_label,
+                Some(format!("{bucket} - {key:?}")),
+                &self.config.retries,
+                &(
+                    self.client.clone(),
+                    bucket,
+                    key,
+                    stream,
+                    self.config.multipart_part_size,
+                ),
+                go_multipart,
+                |_| true,
+                |err| Some(err.clone()),
+            )
+            .await;
+        }
+
         fn go<'a>(
             args: &'a (
                 Client,
@@ -1,2 +1,3 @@
 pub mod blob;
+pub mod postgres;
 pub mod sqlite;
@@ -0,0 +1,160 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::db::create_postgres_pool;
+use golem_common::config::DbPostgresConfig;
+use golem_common::metrics::db::{record_db_failure, record_db_success};
+use sqlx::postgres::{PgArguments, PgQueryResult, PgRow};
+use sqlx::query::{Query, QueryAs};
+use sqlx::{Error, FromRow, IntoArguments, Postgres};
+use std::time::Instant;
+
+#[derive(Clone, Debug)]
+pub struct PostgresPool {
+    pool: sqlx::PgPool,
+}
+
+impl PostgresPool {
+    pub async fn new(pool: sqlx::PgPool) -> Result<Self, anyhow::Error> {
+        Ok(Self { pool })
+    }
+
+    pub async fn configured(config: &DbPostgresConfig) -> Result<Self, anyhow::Error> {
+        let pool = create_postgres_pool(config).await?;
+        PostgresPool::new(pool).await
+    }
+
+    pub async fn execute<'a>(
+        &self,
+        query: Query<'a, Postgres, PgArguments>,
+    ) -> Result<PgQueryResult, String> {
+        query
+            .execute(&self.pool)
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    pub fn with(&self, svc_name: &'static str, api_name: &'static str) -> PostgresLabelledApi {
+        PostgresLabelledApi {
+            svc_name,
+            api_name,
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+pub struct PostgresLabelledTransaction {
+    tx: sqlx::Transaction<'static, Postgres>,
+    start: Instant,
+}
+
+impl PostgresLabelledTransaction {
+    pub async fn execute<'a>(
+        &mut self,
+        query: Query<'a, Postgres, PgArguments>,
+    ) -> Result<PgQueryResult, String> {
+        query
+            .execute(&mut *self.tx)
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    async fn commit(self) -> Result<(), Error> {
+        self.tx.commit().await
+    }
+}
+
+pub struct PostgresLabelledApi {
+    svc_name: &'static str,
+    api_name: &'static str,
+    pool: sqlx::PgPool,
+}
+
+impl PostgresLabelledApi {
+    pub async fn execute<'a>(
+        &self,
+        query: Query<'a, Postgres, PgArguments>,
+    ) -> Result<PgQueryResult, String> {
+        let start = Instant::now();
+        self.record(start, query.execute(&self.pool).await)
+    }
+
+    pub async fn fetch_optional<'a, A>(
+        &self,
+        query: Query<'a, Postgres, A>,
+    ) -> Result<Option<PgRow>, String>
+    where
+        A: 'a + IntoArguments<'a, Postgres>,
+    {
+        let start = Instant::now();
+        self.record(start, query.fetch_optional(&self.pool).await)
+    }
+
+    pub async fn fetch_optional_as<'a, O, A>(
+        &self,
+        query_as: QueryAs<'a, Postgres, O, A>,
+    ) -> Result<Option<O>, String>
+    where
+        A: 'a + IntoArguments<'a, Postgres>,
+        O: 'a + Send + Unpin + for<'r> FromRow<'r, PgRow>,
+    {
+        let start = Instant::now();
+        self.record(start, query_as.fetch_optional(&self.pool).await)
+    }
+
+    pub async fn fetch_all<'a, O, A>(
+        &self,
+        query_as: QueryAs<'a, Postgres, O, A>,
+    ) -> Result<Vec<O>, String>
+    where
+        A: 'a + IntoArguments<'a, Postgres>,
+        O: 'a + Send + Unpin + for<'r> FromRow<'r, PgRow>,
+    {
+        let start = Instant::now();
+        self.record(start, query_as.fetch_all(&self.pool).await)
+    }
+
+    pub async fn begin(&self) -> Result<PostgresLabelledTransaction, String> {
+        let tx = self.pool.begin().await.map_err(|err| err.to_string())?;
+        Ok(PostgresLabelledTransaction {
+            tx,
+            start: Instant::now(),
+        })
+    }
+
+    pub async fn commit(&self, tx: PostgresLabelledTransaction) -> Result<(), String> {
+        let start = tx.start;
+        let result = tx.commit().await;
+        self.record(start, result)
+    }
+
+    fn record<R>(&self, start: Instant, result: Result<R, Error>) -> Result<R, String> {
+        let end = Instant::now();
+        match result {
+            Ok(result) => {
+                record_db_success(
+                    "postgres",
+                    self.svc_name,
+                    self.api_name,
+                    end.duration_since(start),
+                );
+                Ok(result)
+            }
+            Err(err) => {
+                record_db_failure("postgres", self.svc_name, self.api_name);
+                Err(err.to_string())
+            }
+        }
+    }
+}
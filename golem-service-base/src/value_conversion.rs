@@ -0,0 +1,474 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{InvokeParameters, InvokeResponse, InvokeResult};
+use golem_wasm_ast::analysis::{AnalysedType, TypeList, TypeOption};
+use golem_wasm_rpc::json::{OptionallyTypeAnnotatedValueJson, TypeAnnotatedValueJsonExtensions};
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use golem_wasm_rpc::ValueAndType;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::fmt::Display;
+
+// Binary encodings of the bodies of the worker invocation API, next to JSON.
+//
+// The encoded documents have the same shape as their JSON representation, except that
+// `list<u8>` values are encoded as byte strings. Byte strings received in requests are accepted
+// anywhere a list of numbers is expected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryCodec {
+    MessagePack,
+    Cbor,
+}
+
+impl BinaryCodec {
+    pub const ALL: [BinaryCodec; 2] = [BinaryCodec::MessagePack, BinaryCodec::Cbor];
+
+    pub fn from_content_type(content_type: &str) -> Option<BinaryCodec> {
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        match media_type.as_str() {
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(BinaryCodec::MessagePack)
+            }
+            "application/cbor" => Some(BinaryCodec::Cbor),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            BinaryCodec::MessagePack => "application/msgpack",
+            BinaryCodec::Cbor => "application/cbor",
+        }
+    }
+
+    // Decodes a document into its JSON representation
+    pub fn decode(&self, bytes: &[u8]) -> Result<JsonValue, String> {
+        let decoded = match self {
+            BinaryCodec::MessagePack => rmp_serde::from_slice::<internal::DecodedJson>(bytes)
+                .map_err(|err| format!("Failed to read MessagePack data: {err}"))?,
+            BinaryCodec::Cbor => ciborium::from_reader::<internal::DecodedJson, _>(bytes)
+                .map_err(|err| format!("Failed to read CBOR data: {err}"))?,
+        };
+
+        Ok(decoded.0)
+    }
+
+    // Encodes the JSON representation of a document, with the `list<u8>` values found by
+    // following the given type encoded as byte strings
+    pub fn encode(&self, json: &JsonValue, typ: Option<&AnalysedType>) -> Result<Vec<u8>, String> {
+        let encoded = internal::TypedJson { json, typ };
+
+        match self {
+            BinaryCodec::MessagePack => rmp_serde::to_vec_named(&encoded)
+                .map_err(|err| format!("Failed to write MessagePack data: {err}")),
+            BinaryCodec::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(&encoded, &mut bytes)
+                    .map_err(|err| format!("Failed to write CBOR data: {err}"))?;
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+impl Display for BinaryCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.content_type())
+    }
+}
+
+// Decodes a `{ "typ": ..., "value": ... }` document into a value of the declared type
+pub fn decode_value_and_type(codec: BinaryCodec, bytes: &[u8]) -> Result<ValueAndType, String> {
+    let json = codec.decode(bytes)?;
+
+    let value: OptionallyTypeAnnotatedValueJson = serde_json::from_value(json)
+        .map_err(|err| format!("Invalid type annotated value: {err}"))?;
+
+    value
+        .try_into_value_and_type()
+        .map_err(|errors| errors.join(", "))?
+        .ok_or("Missing type of the value".to_string())
+}
+
+// Encodes a value as a `{ "typ": ..., "value": ... }` document
+pub fn encode_value_and_type(codec: BinaryCodec, value: &ValueAndType) -> Result<Vec<u8>, String> {
+    let tav: TypeAnnotatedValue = value
+        .clone()
+        .try_into()
+        .map_err(|errors: Vec<String>| errors.join(", "))?;
+
+    codec.encode(
+        &internal::type_annotated_json(&value.typ, &tav)?,
+        Some(&internal::type_annotated_json_type(value.typ.clone())),
+    )
+}
+
+// Bodies of the worker invocation API that can be sent in a binary codec
+pub trait BinaryCodecBody: Sized {
+    fn decode_with(codec: BinaryCodec, bytes: &[u8]) -> Result<Self, String>;
+
+    fn encode_with(&self, codec: BinaryCodec) -> Result<Vec<u8>, String>;
+}
+
+impl BinaryCodecBody for InvokeParameters {
+    fn decode_with(codec: BinaryCodec, bytes: &[u8]) -> Result<Self, String> {
+        serde_json::from_value(codec.decode(bytes)?)
+            .map_err(|err| format!("Invalid invocation parameters: {err}"))
+    }
+
+    fn encode_with(&self, codec: BinaryCodec) -> Result<Vec<u8>, String> {
+        let json = serde_json::to_value(self).map_err(|err| err.to_string())?;
+        codec.encode(&json, None)
+    }
+}
+
+impl BinaryCodecBody for InvokeResult {
+    fn decode_with(codec: BinaryCodec, bytes: &[u8]) -> Result<Self, String> {
+        let json = codec.decode(bytes)?;
+
+        let result = json
+            .get("result")
+            .cloned()
+            .ok_or("Missing result".to_string())?;
+
+        let result: OptionallyTypeAnnotatedValueJson = serde_json::from_value(result)
+            .map_err(|err| format!("Invalid invocation result: {err}"))?;
+
+        let result = result
+            .try_into_type_annotated_value()
+            .map_err(|errors| errors.join(", "))?
+            .ok_or("Missing type of the result".to_string())?;
+
+        Ok(InvokeResult { result })
+    }
+
+    fn encode_with(&self, codec: BinaryCodec) -> Result<Vec<u8>, String> {
+        let typ = AnalysedType::try_from(&self.result)?;
+        let result = internal::type_annotated_json(&typ, &self.result)?;
+
+        codec.encode(
+            &serde_json::json!({ "result": result }),
+            Some(&internal::invoke_result_type(typ)),
+        )
+    }
+}
+
+impl BinaryCodecBody for InvokeResponse {
+    fn decode_with(codec: BinaryCodec, bytes: &[u8]) -> Result<Self, String> {
+        serde_json::from_value(codec.decode(bytes)?)
+            .map_err(|err| format!("Invalid invocation response: {err}"))
+    }
+
+    fn encode_with(&self, codec: BinaryCodec) -> Result<Vec<u8>, String> {
+        let json = serde_json::to_value(self).map_err(|err| err.to_string())?;
+        codec.encode(&json, None)
+    }
+}
+
+mod internal {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{field, record, str};
+    use golem_wasm_ast::analysis::{TypeRecord, TypeResult, TypeTuple, TypeVariant};
+    use serde::de::{MapAccess, SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+    use serde_json::{Map, Number};
+
+    // The JSON representation of a type annotated value, with its type
+    pub(crate) fn type_annotated_json(
+        typ: &AnalysedType,
+        value: &TypeAnnotatedValue,
+    ) -> Result<JsonValue, String> {
+        Ok(serde_json::json!({
+            "typ": serde_json::to_value(typ).map_err(|err| err.to_string())?,
+            "value": value.to_json_value(),
+        }))
+    }
+
+    // The type used to find the binary values of a `{ "typ": ..., "value": ... }` document,
+    // where the type itself is left as it is
+    pub(crate) fn type_annotated_json_type(typ: AnalysedType) -> AnalysedType {
+        record(vec![field("typ", str()), field("value", typ)])
+    }
+
+    pub(crate) fn invoke_result_type(typ: AnalysedType) -> AnalysedType {
+        record(vec![field("result", type_annotated_json_type(typ))])
+    }
+
+    pub(crate) struct TypedJson<'a> {
+        pub json: &'a JsonValue,
+        pub typ: Option<&'a AnalysedType>,
+    }
+
+    impl TypedJson<'_> {
+        fn bytes(&self) -> Option<Vec<u8>> {
+            match (self.json, self.typ) {
+                (JsonValue::Array(items), Some(AnalysedType::List(TypeList { inner })))
+                    if matches!(**inner, AnalysedType::U8(_)) =>
+                {
+                    items
+                        .iter()
+                        .map(|item| item.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                        .collect()
+                }
+                _ => None,
+            }
+        }
+
+        fn item_type(&self, index: usize) -> Option<&AnalysedType> {
+            match self.typ? {
+                AnalysedType::List(TypeList { inner }) => Some(inner),
+                AnalysedType::Tuple(TypeTuple { items }) => items.get(index),
+                _ => None,
+            }
+        }
+
+        fn field_type(&self, name: &str) -> Option<&AnalysedType> {
+            match self.typ? {
+                AnalysedType::Record(TypeRecord { fields }) => fields
+                    .iter()
+                    .find(|field| field.name == name)
+                    .map(|field| &field.typ),
+                AnalysedType::Variant(TypeVariant { cases }) => cases
+                    .iter()
+                    .find(|case| case.name == name)
+                    .and_then(|case| case.typ.as_ref()),
+                AnalysedType::Result(TypeResult { ok, err }) => match name {
+                    "ok" => ok.as_deref(),
+                    "err" => err.as_deref(),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+    }
+
+    impl Serialize for TypedJson<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            use serde::ser::{SerializeMap, SerializeSeq};
+
+            if let Some(AnalysedType::Option(TypeOption { inner })) = self.typ {
+                if !self.json.is_null() {
+                    return TypedJson {
+                        json: self.json,
+                        typ: Some(inner),
+                    }
+                    .serialize(serializer);
+                }
+            }
+
+            if let Some(bytes) = self.bytes() {
+                return serializer.serialize_bytes(&bytes);
+            }
+
+            match self.json {
+                JsonValue::Array(items) => {
+                    let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                    for (index, item) in items.iter().enumerate() {
+                        seq.serialize_element(&TypedJson {
+                            json: item,
+                            typ: self.item_type(index),
+                        })?;
+                    }
+                    seq.end()
+                }
+                JsonValue::Object(fields) => {
+                    let mut map = serializer.serialize_map(Some(fields.len()))?;
+                    for (name, value) in fields {
+                        map.serialize_entry(
+                            name,
+                            &TypedJson {
+                                json: value,
+                                typ: self.field_type(name),
+                            },
+                        )?;
+                    }
+                    map.end()
+                }
+                json => json.serialize(serializer),
+            }
+        }
+    }
+
+    // A JSON value decoded from a binary codec, with byte strings read as lists of numbers
+    pub(crate) struct DecodedJson(pub JsonValue);
+
+    impl<'de> Deserialize<'de> for DecodedJson {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(DecodedJsonVisitor)
+        }
+    }
+
+    struct DecodedJsonVisitor;
+
+    impl<'de> Visitor<'de> for DecodedJsonVisitor {
+        type Value = DecodedJson;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a value with a JSON representation")
+        }
+
+        fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::Bool(v)))
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::Number(v.into())))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::Number(v.into())))
+        }
+
+        fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Number::from_f64(v)
+                .map(|number| DecodedJson(JsonValue::Number(number)))
+                .ok_or(E::custom(format!("Number {v} has no JSON representation")))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::String(v.to_string())))
+        }
+
+        fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::String(v)))
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::Array(
+                v.iter()
+                    .map(|byte| JsonValue::Number((*byte).into()))
+                    .collect(),
+            )))
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::Null))
+        }
+
+        fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            DecodedJson::deserialize(deserializer)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E> {
+            Ok(DecodedJson(JsonValue::Null))
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut items = Vec::new();
+            while let Some(DecodedJson(item)) = seq.next_element()? {
+                items.push(item);
+            }
+            Ok(DecodedJson(JsonValue::Array(items)))
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut fields = Map::new();
+            while let Some((DecodedJson(key), DecodedJson(value))) = map.next_entry()? {
+                let key = match key {
+                    JsonValue::String(key) => key,
+                    key => key.to_string(),
+                };
+                fields.insert(key, value);
+            }
+            Ok(DecodedJson(JsonValue::Object(fields)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{field, list, option, record, str, u8};
+    use golem_wasm_rpc::Value;
+    use test_r::test;
+
+    #[test]
+    fn values_round_trip_through_binary_codecs_with_bytes_as_byte_strings() {
+        let value = ValueAndType::new(
+            Value::Record(vec![
+                Value::String("avatar.png".to_string()),
+                Value::Option(Some(Box::new(Value::List(vec![
+                    Value::U8(1),
+                    Value::U8(2),
+                    Value::U8(255),
+                ])))),
+            ]),
+            record(vec![
+                field("name", str()),
+                field("data", option(list(u8()))),
+            ]),
+        );
+
+        for codec in BinaryCodec::ALL {
+            let encoded = encode_value_and_type(codec, &value).unwrap();
+            assert_eq!(decode_value_and_type(codec, &encoded).unwrap(), value);
+
+            let result = InvokeResult {
+                result: value.clone().try_into().unwrap(),
+            };
+            let encoded = result.encode_with(codec).unwrap();
+            assert_eq!(InvokeResult::decode_with(codec, &encoded).unwrap(), result);
+        }
+
+        // The bytes are a MessagePack bin, not an array of numbers
+        let encoded = BinaryCodec::MessagePack
+            .encode(&serde_json::json!([1, 2, 255]), Some(&list(u8())))
+            .unwrap();
+        assert_eq!(encoded, vec![0xc4, 3, 1, 2, 255]);
+
+        let parameters = InvokeParameters::decode_with(
+            BinaryCodec::Cbor,
+            &BinaryCodec::Cbor
+                .encode(
+                    &serde_json::json!({ "params": [{ "typ": { "type": "Str" }, "value": "jane" }] }),
+                    None,
+                )
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(parameters.params.len(), 1);
+
+        assert_eq!(
+            BinaryCodec::from_content_type("application/x-msgpack; charset=binary"),
+            Some(BinaryCodec::MessagePack)
+        );
+        assert_eq!(BinaryCodec::from_content_type("application/json"), None);
+        assert!(BinaryCodec::Cbor.decode(&[0xff, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,584 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Polling based watch streams over listable control-plane resources.
+//!
+//! A watch stream first emits the current state of the listed resources, followed by a
+//! `Bookmark` event carrying a resumable cursor. Afterwards the resource list is re-read
+//! periodically and only the differences are emitted, each batch terminated by a new bookmark.
+//! All the watchers of the same resource list share a single poller, so the listing load does
+//! not grow with the number of watchers. Clients reconnecting with one of the last seen cursors
+//! only receive the changes since then, otherwise they receive a `Resync` event followed by the
+//! full current state.
+
+use crate::config::WatchConfig;
+use futures::{stream, Stream, StreamExt};
+use poem_openapi::Enum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum WatchEventType {
+    Added,
+    Modified,
+    Deleted,
+    /// The client's cursor is no longer valid, all previously received state must be discarded
+    Resync,
+    /// Marks a consistent point in the stream, carrying the cursor to resume from
+    Bookmark,
+}
+
+impl From<WatchEventType> for golem_api_grpc::proto::golem::common::WatchEventType {
+    fn from(value: WatchEventType) -> Self {
+        match value {
+            WatchEventType::Added => Self::Added,
+            WatchEventType::Modified => Self::Modified,
+            WatchEventType::Deleted => Self::Deleted,
+            WatchEventType::Resync => Self::Resync,
+            WatchEventType::Bookmark => Self::Bookmark,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchEvent<T> {
+    pub event_type: WatchEventType,
+    pub key: Option<String>,
+    pub resource_version: Option<String>,
+    pub cursor: Option<String>,
+    pub resource: Option<T>,
+}
+
+impl<T> WatchEvent<T> {
+    fn resource(
+        event_type: WatchEventType,
+        key: String,
+        resource_version: String,
+        resource: T,
+    ) -> Self {
+        Self {
+            event_type,
+            key: Some(key),
+            resource_version: Some(resource_version),
+            cursor: None,
+            resource: Some(resource),
+        }
+    }
+
+    fn deleted(key: String, resource_version: String) -> Self {
+        Self {
+            event_type: WatchEventType::Deleted,
+            key: Some(key),
+            resource_version: Some(resource_version),
+            cursor: None,
+            resource: None,
+        }
+    }
+
+    fn marker(event_type: WatchEventType, cursor: Option<String>) -> Self {
+        Self {
+            event_type,
+            key: None,
+            resource_version: None,
+            cursor,
+            resource: None,
+        }
+    }
+}
+
+/// Version of a single resource, derived from its serialized form
+pub fn resource_version<T: Serialize>(resource: &T) -> String {
+    let bytes = serde_json::to_vec(resource).unwrap_or_default();
+    hex::encode(&Sha256::digest(bytes)[..8])
+}
+
+/// A keyed, versioned view of a resource list at a point in time
+#[derive(Debug, Clone)]
+pub struct WatchSnapshot<T> {
+    items: BTreeMap<String, (String, T)>,
+    cursor: String,
+}
+
+impl<T: Serialize + Clone> WatchSnapshot<T> {
+    pub fn new(items: Vec<(String, T)>) -> Self {
+        let items: BTreeMap<String, (String, T)> = items
+            .into_iter()
+            .map(|(key, resource)| {
+                let version = resource_version(&resource);
+                (key, (version, resource))
+            })
+            .collect();
+
+        let mut hasher = Sha256::new();
+        for (key, (version, _)) in &items {
+            hasher.update(key.as_bytes());
+            hasher.update([0u8]);
+            hasher.update(version.as_bytes());
+            hasher.update([0u8]);
+        }
+        let cursor = hex::encode(&hasher.finalize()[..16]);
+
+        Self { items, cursor }
+    }
+
+    /// Cursor identifying the exact set of (key, version) pairs of this snapshot
+    pub fn cursor(&self) -> String {
+        self.cursor.clone()
+    }
+
+    fn added(&self) -> Vec<WatchEvent<T>> {
+        self.items
+            .iter()
+            .map(|(key, (version, resource))| {
+                WatchEvent::resource(
+                    WatchEventType::Added,
+                    key.clone(),
+                    version.clone(),
+                    resource.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Events transforming this snapshot into `next`
+    pub fn diff(&self, next: &WatchSnapshot<T>) -> Vec<WatchEvent<T>> {
+        let mut events = Vec::new();
+
+        for (key, (version, _)) in &self.items {
+            if !next.items.contains_key(key) {
+                events.push(WatchEvent::deleted(key.clone(), version.clone()));
+            }
+        }
+
+        for (key, (version, resource)) in &next.items {
+            match self.items.get(key) {
+                None => events.push(WatchEvent::resource(
+                    WatchEventType::Added,
+                    key.clone(),
+                    version.clone(),
+                    resource.clone(),
+                )),
+                Some((old_version, _)) if old_version != version => {
+                    events.push(WatchEvent::resource(
+                        WatchEventType::Modified,
+                        key.clone(),
+                        version.clone(),
+                        resource.clone(),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        events
+    }
+
+    /// Initial events for a client, optionally resuming from a previously received cursor
+    pub fn initial_events(&self, resume_from: Option<&str>) -> Vec<WatchEvent<T>> {
+        let cursor = self.cursor();
+        let mut events = match resume_from {
+            Some(resume_from) if resume_from == cursor => Vec::new(),
+            Some(_) => {
+                let mut events = vec![WatchEvent::marker(WatchEventType::Resync, None)];
+                events.extend(self.added());
+                events
+            }
+            None => self.added(),
+        };
+        events.push(WatchEvent::marker(WatchEventType::Bookmark, Some(cursor)));
+        events
+    }
+
+    /// Initial events for a client resuming from one of the earlier snapshots, which only
+    /// get the changes since then
+    fn resumed_events(
+        &self,
+        history: &VecDeque<Arc<WatchSnapshot<T>>>,
+        resume_from: Option<&str>,
+    ) -> Vec<WatchEvent<T>> {
+        let previous = resume_from.and_then(|resume_from| {
+            history
+                .iter()
+                .find(|snapshot| snapshot.cursor == resume_from)
+        });
+
+        match previous {
+            Some(previous) => {
+                let mut events = previous.diff(self);
+                events.push(WatchEvent::marker(
+                    WatchEventType::Bookmark,
+                    Some(self.cursor()),
+                ));
+                events
+            }
+            None => self.initial_events(resume_from),
+        }
+    }
+}
+
+// The state of a resource list published by its poller to the watchers
+struct Published<T> {
+    // The last listing, or the error it failed with
+    latest: Result<Arc<WatchSnapshot<T>>, String>,
+    // The last distinct snapshots, oldest first
+    history: VecDeque<Arc<WatchSnapshot<T>>>,
+}
+
+type PublishedSender<T> = Arc<watch::Sender<Option<Arc<Published<T>>>>>;
+
+/// Watch streams of the resource lists of one kind, each list identified by a key (such as the
+/// component and the filter of the listed workers).
+///
+/// A resource list is polled by a single task as long as it has watchers.
+pub struct WatchHub<T> {
+    config: WatchConfig,
+    pollers: Arc<Mutex<HashMap<String, PublishedSender<T>>>>,
+}
+
+impl<T: Serialize + Clone + Send + Sync + 'static> WatchHub<T> {
+    pub fn new(config: WatchConfig) -> Self {
+        Self {
+            config,
+            pollers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Watches the resource list identified by `key`, which is read by calling `list` if it is
+    /// not watched yet.
+    ///
+    /// The stream ends after the first failed listing, emitting the error as its last element.
+    pub fn watch<E, F, Fut>(
+        &self,
+        key: String,
+        list: F,
+        resume_from: Option<String>,
+    ) -> impl Stream<Item = Result<WatchEvent<T>, String>> + Send + 'static
+    where
+        E: Display + Send + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<(String, T)>, E>> + Send + 'static,
+    {
+        let receiver = {
+            let mut pollers = self.pollers.lock().unwrap();
+            match pollers.get(&key) {
+                Some(sender) => sender.subscribe(),
+                None => {
+                    let (sender, receiver) = watch::channel(None);
+                    let sender = Arc::new(sender);
+                    pollers.insert(key.clone(), sender.clone());
+                    tokio::spawn(poll(
+                        key,
+                        sender,
+                        self.pollers.clone(),
+                        list,
+                        self.config.clone(),
+                    ));
+                    receiver
+                }
+            }
+        };
+
+        watch_events(receiver, resume_from)
+    }
+}
+
+// Lists the resources until the resource list has no more watchers
+async fn poll<T, E, F, Fut>(
+    key: String,
+    sender: PublishedSender<T>,
+    pollers: Arc<Mutex<HashMap<String, PublishedSender<T>>>>,
+    list: F,
+    config: WatchConfig,
+) where
+    T: Serialize + Clone,
+    E: Display,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<Vec<(String, T)>, E>>,
+{
+    let mut history: VecDeque<Arc<WatchSnapshot<T>>> = VecDeque::new();
+
+    loop {
+        let latest = match list().await {
+            Ok(items) => {
+                let snapshot = WatchSnapshot::new(items);
+                match history.back() {
+                    Some(last) if last.cursor == snapshot.cursor => Ok(last.clone()),
+                    _ => {
+                        let snapshot = Arc::new(snapshot);
+                        history.push_back(snapshot.clone());
+                        while history.len() > config.history_size.max(1) {
+                            history.pop_front();
+                        }
+                        Ok(snapshot)
+                    }
+                }
+            }
+            Err(error) => Err(error.to_string()),
+        };
+
+        sender.send_replace(Some(Arc::new(Published {
+            latest,
+            history: history.clone(),
+        })));
+
+        tokio::select! {
+            _ = sender.closed() => {}
+            _ = tokio::time::sleep(config.poll_interval) => {}
+        }
+
+        // Checked while holding the lock, so that no watcher subscribes to a stopped poller
+        let mut pollers = pollers.lock().unwrap();
+        if sender.receiver_count() == 0 {
+            pollers.remove(&key);
+            break;
+        }
+    }
+}
+
+enum WatchState<T> {
+    Initial(Option<String>),
+    Watching(Arc<WatchSnapshot<T>>),
+    Done,
+}
+
+// The events of a single watcher, diffing the published snapshots against the last one it got
+fn watch_events<T: Serialize + Clone + Send + Sync + 'static>(
+    receiver: watch::Receiver<Option<Arc<Published<T>>>>,
+    resume_from: Option<String>,
+) -> impl Stream<Item = Result<WatchEvent<T>, String>> + Send + 'static {
+    stream::unfold(
+        (WatchState::Initial(resume_from), receiver),
+        |(state, mut receiver)| async move {
+            let published = match &state {
+                WatchState::Initial(_) => receiver
+                    .wait_for(|published| published.is_some())
+                    .await
+                    .ok()?
+                    .clone()?,
+                WatchState::Watching(_) => {
+                    receiver.changed().await.ok()?;
+                    receiver.borrow_and_update().clone()?
+                }
+                WatchState::Done => return None,
+            };
+
+            let snapshot = match &published.latest {
+                Ok(snapshot) => snapshot.clone(),
+                Err(error) => {
+                    return Some((vec![Err(error.clone())], (WatchState::Done, receiver)))
+                }
+            };
+
+            let events = match state {
+                WatchState::Initial(resume_from) => {
+                    snapshot.resumed_events(&published.history, resume_from.as_deref())
+                }
+                WatchState::Watching(previous) => {
+                    let mut events = previous.diff(&snapshot);
+                    if !events.is_empty() {
+                        events.push(WatchEvent::marker(
+                            WatchEventType::Bookmark,
+                            Some(snapshot.cursor()),
+                        ));
+                    }
+                    events
+                }
+                WatchState::Done => vec![],
+            };
+
+            Some((
+                events.into_iter().map(Ok).collect::<Vec<_>>(),
+                (WatchState::Watching(snapshot), receiver),
+            ))
+        },
+    )
+    .flat_map(stream::iter)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    fn snapshot(items: &[(&str, u32)]) -> WatchSnapshot<u32> {
+        WatchSnapshot::new(items.iter().map(|(k, v)| (k.to_string(), *v)).collect())
+    }
+
+    #[test]
+    fn diff_reports_added_modified_and_deleted() {
+        let old = snapshot(&[("a", 1), ("b", 2), ("c", 3)]);
+        let new = snapshot(&[("a", 1), ("b", 20), ("d", 4)]);
+
+        let events = old
+            .diff(&new)
+            .into_iter()
+            .map(|e| (e.event_type, e.key.unwrap(), e.resource))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                (WatchEventType::Deleted, "c".to_string(), None),
+                (WatchEventType::Modified, "b".to_string(), Some(20)),
+                (WatchEventType::Added, "d".to_string(), Some(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn cursor_is_stable_and_content_dependent() {
+        let a = snapshot(&[("a", 1), ("b", 2)]);
+        let b = snapshot(&[("b", 2), ("a", 1)]);
+        let c = snapshot(&[("a", 1), ("b", 3)]);
+
+        assert_eq!(a.cursor(), b.cursor());
+        assert_ne!(a.cursor(), c.cursor());
+    }
+
+    #[test]
+    fn initial_events_with_matching_cursor_only_bookmark() {
+        let s = snapshot(&[("a", 1)]);
+        let events = s.initial_events(Some(&s.cursor()));
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, WatchEventType::Bookmark);
+        assert_eq!(events[0].cursor, Some(s.cursor()));
+    }
+
+    #[test]
+    fn initial_events_with_stale_cursor_resync() {
+        let s = snapshot(&[("a", 1), ("b", 2)]);
+        let types = s
+            .initial_events(Some("stale"))
+            .into_iter()
+            .map(|e| e.event_type)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            types,
+            vec![
+                WatchEventType::Resync,
+                WatchEventType::Added,
+                WatchEventType::Added,
+                WatchEventType::Bookmark
+            ]
+        );
+    }
+
+    #[test]
+    async fn watch_stream_emits_initial_state_then_stops_on_error() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let list = {
+            let calls = calls.clone();
+            move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match call {
+                        0 => Ok(vec![("a".to_string(), 1u32)]),
+                        1 => Ok(vec![("a".to_string(), 2u32)]),
+                        _ => Err("failed".to_string()),
+                    }
+                }
+            }
+        };
+
+        // Long enough for the watcher to see every listing before the next one
+        let hub = WatchHub::new(WatchConfig {
+            poll_interval: Duration::from_millis(50),
+            history_size: 4,
+        });
+        let events = hub
+            .watch("all".to_string(), list, None)
+            .collect::<Vec<_>>()
+            .await;
+
+        let types = events
+            .iter()
+            .map(|e| e.as_ref().map(|e| e.event_type).map_err(|e| e.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            types,
+            vec![
+                Ok(WatchEventType::Added),
+                Ok(WatchEventType::Bookmark),
+                Ok(WatchEventType::Modified),
+                Ok(WatchEventType::Bookmark),
+                Err("failed".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    async fn watchers_of_the_same_list_share_its_poller() {
+        let hub = WatchHub::new(WatchConfig {
+            poll_interval: Duration::from_secs(60),
+            history_size: 4,
+        });
+        let calls = Arc::new(AtomicUsize::new(0));
+        let list = |calls: Arc<AtomicUsize>| {
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok::<_, String>(vec![("a".to_string(), 1u32)]) }
+            }
+        };
+
+        let mut first = hub
+            .watch("all".to_string(), list(calls.clone()), None)
+            .boxed();
+        let mut second = hub
+            .watch("all".to_string(), list(calls.clone()), None)
+            .boxed();
+
+        for watcher in [&mut first, &mut second] {
+            let added = watcher.next().await.unwrap().unwrap();
+            assert_eq!(added.event_type, WatchEventType::Added);
+            let bookmark = watcher.next().await.unwrap().unwrap();
+            assert_eq!(bookmark.event_type, WatchEventType::Bookmark);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn resuming_from_an_earlier_snapshot_only_sends_the_changes() {
+        let first = Arc::new(snapshot(&[("a", 1), ("b", 2)]));
+        let second = Arc::new(snapshot(&[("a", 1), ("b", 3)]));
+        let history = VecDeque::from(vec![first.clone(), second.clone()]);
+
+        let events = second
+            .resumed_events(&history, Some(&first.cursor()))
+            .into_iter()
+            .map(|e| (e.event_type, e.key))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                (WatchEventType::Modified, Some("b".to_string())),
+                (WatchEventType::Bookmark, None),
+            ]
+        );
+
+        let events = second.resumed_events(&history, Some("unknown"));
+        assert_eq!(events[0].event_type, WatchEventType::Resync);
+    }
+}
@@ -17,6 +17,8 @@ use aws_config::BehaviorVersion;
 use aws_sdk_s3::config::Credentials;
 use aws_sdk_s3::Client;
 use golem_common::model::{AccountId, ComponentId};
+#[cfg(feature = "blob-storage-integration-tests")]
+use golem_service_base::config::AzureBlobStorageConfig;
 use golem_service_base::config::S3BlobStorageConfig;
 use golem_service_base::storage::blob::sqlite::SqliteBlobStorage;
 use golem_service_base::storage::blob::{fs, memory, s3, BlobStorage, BlobStorageNamespace};
@@ -25,7 +27,7 @@ use sqlx::sqlite::SqlitePoolOptions;
 use std::fmt::Debug;
 use tempfile::{tempdir, TempDir};
 use testcontainers::runners::AsyncRunner;
-use testcontainers::ContainerAsync;
+use testcontainers::{ContainerAsync, Image};
 use testcontainers_modules::minio::MinIO;
 use uuid::Uuid;
 
@@ -97,6 +99,7 @@ async fn fs() -> Arc<dyn GetBlobStorage + Send + Sync> {
 
 struct S3Test {
     prefixed: Option<String>,
+    multipart: bool,
 }
 
 impl Debug for S3Test {
@@ -125,9 +128,19 @@ impl GetBlobStorage for S3Test {
             use_minio_credentials: true,
             ..std::default::Default::default()
         };
+        // Uploads the streams above the minimal part size of S3 in multiple parts
+        let config = if self.multipart {
+            S3BlobStorageConfig {
+                multipart_threshold: 5 * 1024 * 1024,
+                multipart_part_size: 5 * 1024 * 1024,
+                ..config
+            }
+        } else {
+            config
+        };
         create_buckets(host_port, &config).await;
         let storage = s3::S3BlobStorage::new(config).await;
-        Arc::new(S3BlobStorageWithContainer {
+        Arc::new(BlobStorageWithContainer {
             storage,
             _container: container,
         })
@@ -169,19 +182,20 @@ async fn create_buckets(host_port: u16, config: &S3BlobStorageConfig) {
     }
 }
 
-struct S3BlobStorageWithContainer {
-    storage: s3::S3BlobStorage,
-    _container: ContainerAsync<MinIO>,
+// Keeps the container of the storage running as long as the storage is used
+struct BlobStorageWithContainer<S, I: Image> {
+    storage: S,
+    _container: ContainerAsync<I>,
 }
 
-impl Debug for S3BlobStorageWithContainer {
+impl<S, I: Image> Debug for BlobStorageWithContainer<S, I> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "S3BlobStorageWithContainer")
+        write!(f, "BlobStorageWithContainer")
     }
 }
 
 #[async_trait]
-impl BlobStorage for S3BlobStorageWithContainer {
+impl<S: BlobStorage + Send + Sync, I: Image> BlobStorage for BlobStorageWithContainer<S, I> {
     async fn get_raw(
         &self,
         target_label: &'static str,
@@ -359,13 +373,98 @@ impl BlobStorage for S3BlobStorageWithContainer {
 
 #[test_dep(tagged_as = "s3")]
 async fn s3() -> Arc<dyn GetBlobStorage + Send + Sync> {
-    Arc::new(S3Test { prefixed: None })
+    Arc::new(S3Test {
+        prefixed: None,
+        multipart: false,
+    })
 }
 
 #[test_dep(tagged_as = "s3_prefixed")]
 async fn s3_prefixed() -> Arc<dyn GetBlobStorage + Send + Sync> {
     Arc::new(S3Test {
         prefixed: Some("random-prefix".to_string()),
+        multipart: false,
+    })
+}
+
+#[cfg(feature = "blob-storage-integration-tests")]
+#[test_dep(tagged_as = "s3_multipart")]
+async fn s3_multipart() -> Arc<dyn GetBlobStorage + Send + Sync> {
+    Arc::new(S3Test {
+        prefixed: None,
+        multipart: true,
+    })
+}
+
+#[cfg(feature = "blob-storage-integration-tests")]
+mod azurite {
+    use super::*;
+    use golem_service_base::storage::blob::azure::AzureBlobStorage;
+    use testcontainers::core::{IntoContainerPort, WaitFor};
+    use testcontainers::GenericImage;
+
+    // The well-known development account of Azurite
+    const ACCOUNT: &str = "devstoreaccount1";
+    const ACCESS_KEY: &str =
+        "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+
+    pub struct AzureTest {
+        pub prefixed: Option<String>,
+    }
+
+    impl Debug for AzureTest {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "AzureTest")
+        }
+    }
+
+    #[async_trait]
+    impl GetBlobStorage for AzureTest {
+        async fn get_blob_storage(&self) -> Arc<dyn BlobStorage + Send + Sync> {
+            let container = GenericImage::new("mcr.microsoft.com/azure-storage/azurite", "3.33.0")
+                .with_exposed_port(10000.tcp())
+                .with_wait_for(WaitFor::message_on_stdout(
+                    "Blob service is successfully listening",
+                ))
+                .start()
+                .await
+                .expect("Failed to start Azurite");
+            let host_port = container
+                .get_host_port_ipv4(10000)
+                .await
+                .expect("Failed to get host port");
+
+            // Uploads the 10 MiB streams of the tests in multiple blocks
+            let config = AzureBlobStorageConfig {
+                account: ACCOUNT.to_string(),
+                access_key: ACCESS_KEY.to_string(),
+                endpoint_url: Some(format!("http://127.0.0.1:{host_port}/{ACCOUNT}")),
+                object_prefix: self.prefixed.clone().unwrap_or_default(),
+                block_upload_threshold: 4 * 1024 * 1024,
+                block_size: 4 * 1024 * 1024,
+                ..std::default::Default::default()
+            };
+            let storage = AzureBlobStorage::new(config).unwrap();
+            storage.ensure_containers().await.unwrap();
+            Arc::new(BlobStorageWithContainer {
+                storage,
+                _container: container,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "blob-storage-integration-tests")]
+#[test_dep(tagged_as = "azure")]
+async fn azure() -> Arc<dyn GetBlobStorage + Send + Sync> {
+    Arc::new(azurite::AzureTest { prefixed: None })
+}
+
+#[cfg(feature = "blob-storage-integration-tests")]
+#[test_dep(tagged_as = "azure_prefixed")]
+async fn azure_prefixed() -> Arc<dyn GetBlobStorage + Send + Sync> {
+    Arc::new(azurite::AzureTest {
+        prefixed: Some("random-prefix".to_string()),
     })
 }
 
@@ -416,7 +515,10 @@ fn compressed_oplog() -> BlobStorageNamespace {
     }
 }
 
+#[cfg(not(feature = "blob-storage-integration-tests"))]
 define_matrix_dimension!(storage: Arc<dyn GetBlobStorage + Send + Sync> -> "in_memory", "fs", "s3", "s3_prefixed", "sqlite");
+#[cfg(feature = "blob-storage-integration-tests")]
+define_matrix_dimension!(storage: Arc<dyn GetBlobStorage + Send + Sync> -> "in_memory", "fs", "s3", "s3_prefixed", "s3_multipart", "sqlite", "azure", "azure_prefixed");
 define_matrix_dimension!(ns: BlobStorageNamespace -> "cc", "co");
 
 #[test]
@@ -111,6 +111,19 @@ impl ShardManagerServiceImpl {
         Ok(())
     }
 
+    async fn unregister_internal(
+        &self,
+        source_ip: Option<SocketAddr>,
+        request: golem::shardmanager::v1::UnregisterRequest,
+    ) -> Result<(), ShardManagerError> {
+        let source_ip = source_ip.ok_or(ShardManagerError::NoSourceIpForPod)?.ip();
+
+        let pod = Pod::from_unregister_request(source_ip, request);
+        info!("Shard Manager received request to unregister pod: {}", pod);
+        self.shard_management.unregister_pod(pod).await;
+        Ok(())
+    }
+
     fn start_health_check(&self) {
         let delay = self.shard_manager_config.health_check.delay;
         let shard_management = self.shard_management.clone();
@@ -212,6 +225,44 @@ impl ShardManagerService for ShardManagerServiceImpl {
             result: Some(result),
         }))
     }
+
+    async fn unregister(
+        &self,
+        request: tonic::Request<golem::shardmanager::v1::UnregisterRequest>,
+    ) -> Result<Response<golem::shardmanager::v1::UnregisterResponse>, tonic::Status> {
+        let source_ip = request.remote_addr();
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "unregister",
+            source_ip = source_ip.map(|ip| ip.to_string()),
+            host = &request.host,
+            port = &request.port.to_string(),
+        );
+
+        let response = self
+            .unregister_internal(source_ip, request)
+            .instrument(record.span.clone())
+            .await;
+
+        let result = match response {
+            Ok(_) => record.succeed(
+                golem::shardmanager::v1::unregister_response::Result::Success(
+                    golem::common::Empty {},
+                ),
+            ),
+            Err(error) => {
+                let error: golem::shardmanager::v1::ShardManagerError = error.into();
+                record.fail(
+                    golem::shardmanager::v1::unregister_response::Result::Failure(error.clone()),
+                    &ShardManagerTraceErrorKind(&error),
+                )
+            }
+        };
+
+        Ok(Response::new(golem::shardmanager::v1::UnregisterResponse {
+            result: Some(result),
+        }))
+    }
 }
 
 pub async fn run(
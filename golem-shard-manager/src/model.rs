@@ -108,6 +108,21 @@ impl Pod {
     }
 }
 
+impl Pod {
+    // The pod of an executor leaving, which is identified the same way as when it registered
+    pub fn from_unregister_request(
+        source_ip: IpAddr,
+        request: golem::shardmanager::v1::UnregisterRequest,
+    ) -> Self {
+        Pod {
+            host: request.host,
+            port: request.port as u16,
+            pod_name: request.pod_name,
+            ip: source_ip,
+        }
+    }
+}
+
 impl From<Pod> for golem::shardmanager::Pod {
     fn from(value: Pod) -> golem::shardmanager::Pod {
         golem::shardmanager::Pod {
@@ -261,3 +261,153 @@ impl Drop for WorkerHandle {
         self.0.abort();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use std::collections::BTreeSet;
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use golem_api_grpc::proto::golem::shardmanager::v1::{RegisterRequest, UnregisterRequest};
+    use golem_common::model::ShardId;
+
+    use crate::error::{HealthCheckError, ShardManagerError};
+    use crate::healthcheck::HealthCheck;
+    use crate::model::{Pod, RoutingTable};
+    use crate::persistence::RoutingTablePersistence;
+    use crate::shard_management::ShardManagement;
+    use crate::worker_executor::WorkerExecutorService;
+
+    struct InMemoryPersistence(std::sync::Mutex<RoutingTable>);
+
+    #[async_trait]
+    impl RoutingTablePersistence for InMemoryPersistence {
+        async fn write(&self, routing_table: &RoutingTable) -> Result<(), ShardManagerError> {
+            *self.0.lock().unwrap() = routing_table.clone();
+            Ok(())
+        }
+
+        async fn read(&self) -> Result<RoutingTable, ShardManagerError> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+    }
+
+    struct AcceptingWorkerExecutors;
+
+    #[async_trait]
+    impl WorkerExecutorService for AcceptingWorkerExecutors {
+        async fn assign_shards(
+            &self,
+            _pod: &Pod,
+            _shard_ids: &BTreeSet<ShardId>,
+        ) -> Result<(), ShardManagerError> {
+            Ok(())
+        }
+
+        async fn health_check(&self, _pod: &Pod) -> Result<(), HealthCheckError> {
+            Ok(())
+        }
+
+        async fn revoke_shards(
+            &self,
+            _pod: &Pod,
+            _shard_ids: &BTreeSet<ShardId>,
+        ) -> Result<(), ShardManagerError> {
+            Ok(())
+        }
+    }
+
+    struct HealthyPods;
+
+    #[async_trait]
+    impl HealthCheck for HealthyPods {
+        async fn health_check(&self, _pod: &Pod) -> bool {
+            true
+        }
+    }
+
+    async fn wait_for_routing_table(
+        shard_management: &ShardManagement,
+        condition: impl Fn(&RoutingTable) -> bool,
+    ) -> RoutingTable {
+        for _ in 0..100 {
+            let routing_table = shard_management.current_snapshot().await;
+            if condition(&routing_table) {
+                return routing_table;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        shard_management.current_snapshot().await
+    }
+
+    #[test]
+    async fn unregistered_pod_shards_are_reassigned_to_the_remaining_pods() {
+        let shard_management = ShardManagement::new(
+            Arc::new(InMemoryPersistence(std::sync::Mutex::new(
+                RoutingTable::new(4),
+            ))),
+            Arc::new(AcceptingWorkerExecutors),
+            Arc::new(HealthyPods),
+            0.1,
+        )
+        .await
+        .unwrap();
+
+        let source_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let draining_pod = Pod::from_register_request(
+            source_ip,
+            RegisterRequest {
+                host: "localhost".to_string(),
+                port: 9000,
+                pod_name: None,
+            },
+        )
+        .unwrap();
+        let remaining_pod = Pod::new("localhost".to_string(), 9001);
+
+        shard_management.register_pod(draining_pod.clone()).await;
+        shard_management.register_pod(remaining_pod.clone()).await;
+
+        let routing_table = wait_for_routing_table(&shard_management, |routing_table| {
+            routing_table.get_unassigned_shards().is_empty()
+                && routing_table
+                    .shard_assignments
+                    .values()
+                    .all(|shard_ids| !shard_ids.is_empty())
+        })
+        .await;
+        assert_eq!(routing_table.get_pod_count(), 2);
+        assert!(routing_table.get_unassigned_shards().is_empty());
+
+        // The pod leaving is identified the same way as when it registered
+        shard_management
+            .unregister_pod(Pod::from_unregister_request(
+                source_ip,
+                UnregisterRequest {
+                    host: "localhost".to_string(),
+                    port: 9000,
+                    pod_name: None,
+                },
+            ))
+            .await;
+
+        let routing_table = wait_for_routing_table(&shard_management, |routing_table| {
+            !routing_table.has_pod(&draining_pod)
+                && routing_table
+                    .get_shards(&remaining_pod)
+                    .unwrap_or_default()
+                    .len()
+                    == 4
+        })
+        .await;
+        assert!(!routing_table.has_pod(&draining_pod));
+        assert_eq!(
+            routing_table.get_shards(&remaining_pod),
+            Some((0..4).map(ShardId::new).collect())
+        );
+    }
+}
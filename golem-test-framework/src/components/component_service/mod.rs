@@ -906,6 +906,7 @@ impl ComponentServiceEnvVars for GolemEnvVars {
             )
             .with("GOLEM__GRPC_PORT", grpc_port.to_string())
             .with("GOLEM__HTTP_PORT", http_port.to_string())
+            .with_str("GOLEM__WORKER_SERVICE__TYPE", "Disabled")
             .with_all(rdb.info().env("golem_component"));
 
         match component_compilation_service {
@@ -127,6 +127,8 @@ impl WorkerService for ForwardingWorkerService {
                     component_version: latest_component_version,
                     args: request.args.clone(),
                     env: request.env.clone(),
+                    wasi_config_vars: request.wasi_config_vars.clone(),
+                    labels: request.labels.clone(),
                     account_id: Some(
                         AccountId {
                             value: "test-account".to_string(),
@@ -320,6 +322,7 @@ impl WorkerService for ForwardingWorkerService {
                         max_memory_per_worker: i64::MAX,
                     }),
                     context: context.clone(),
+                    invoke_at: None,
                 })
                 .await;
 
@@ -710,6 +713,7 @@ impl WorkerService for ForwardingWorkerService {
                 query: request.query,
                 cursor: request.cursor,
                 count: request.count,
+                filter: request.filter,
             })
             .await?
             .into_inner();
@@ -68,8 +68,8 @@ use golem_api_grpc::proto::golem::worker::{
     FileFileSystemNode, FileSystemNode, IdempotencyKey, IndexedResourceMetadata, InvocationContext,
     InvokeParameters, InvokeResult, LogEvent, OplogCursor, OplogEntry, OplogEntryWithIndex,
     PendingUpdate, ResourceMetadata, SuccessfulUpdate, TargetWorkerId, UpdateMode, UpdateRecord,
-    WorkerCreatedAtFilter, WorkerEnvFilter, WorkerMetadata, WorkerNameFilter, WorkerStatusFilter,
-    WorkerVersionFilter,
+    WorkerCreatedAtFilter, WorkerEnvFilter, WorkerLabelFilter, WorkerLastErrorFilter,
+    WorkerMetadata, WorkerNameFilter, WorkerStatusFilter, WorkerVersionFilter,
 };
 use golem_client::api::ApiDefinitionClient as ApiDefinitionServiceHttpClient;
 use golem_client::api::ApiDefinitionClientLive as ApiDefinitionServiceHttpClientLive;
@@ -156,6 +156,7 @@ pub trait WorkerService {
                             name: request.name,
                             args: request.args,
                             env: request.env,
+                            wasi_config_vars: Some(request.wasi_config_vars),
                         },
                     )
                     .await
@@ -305,6 +306,7 @@ pub trait WorkerService {
                     function,
                     invoke_parameters: invoke_parameters_to_grpc(invoke_parameters),
                     context,
+                    invoke_at: None,
                 })
                 .await?
                 .into_inner()),
@@ -1406,6 +1408,8 @@ fn http_worker_metadata_to_grpc(
         }),
         args: worker_metadata.args,
         env: worker_metadata.env,
+        wasi_config_vars: worker_metadata.wasi_config_vars,
+        labels: HashMap::new(),
         status: worker_metadata.status.into(),
         component_version: worker_metadata.component_version,
         retry_count: worker_metadata.retry_count,
@@ -1474,6 +1478,8 @@ fn http_worker_metadata_to_grpc(
                 value: Some(id.into()),
             })
             .collect(),
+        compaction: None,
+        fork: None,
     }
 }
 
@@ -1494,6 +1500,7 @@ fn grpc_string_filter_comparator_to_http(comparator: i32) -> &'static str {
         StringFilterComparator::StringNotEqual => "!=",
         StringFilterComparator::StringLike => "like",
         StringFilterComparator::StringNotLike => "notlike",
+        StringFilterComparator::StringStartsWith => "startswith",
     }
 }
 
@@ -1540,6 +1547,25 @@ fn grpc_filter_to_http_filter(filter: Filter) -> Vec<String> {
                     value
                 )]
             }
+            Filter::LastError(WorkerLastErrorFilter { comparator, value }) => {
+                vec![format!(
+                    "last_error {} {}",
+                    grpc_string_filter_comparator_to_http(comparator),
+                    value
+                )]
+            }
+            Filter::Label(WorkerLabelFilter {
+                name,
+                comparator,
+                value,
+            }) => {
+                vec![format!(
+                    "label.{} {} {}",
+                    name,
+                    grpc_string_filter_comparator_to_http(comparator),
+                    value
+                )]
+            }
             Filter::And(and_filter) => {
                 if !allow_and {
                     panic!("'And' filters are only supported on the root level on the HTTP API")
@@ -1668,6 +1694,15 @@ fn http_api_definition_to_grpc(
                             }
                         }),
                         invocation_context: None, // TODO
+                        grpc_binding: None,
+                        traffic_mirror: None,
+                        http_proxy_binding: None,
+                        worker_per_user: None,
+                        bootstrap: None,
+                        pagination: None,
+                        content_negotiation: None,
+                        error_mapping: None,
+                        static_worker_names: None,
                     }),
                     middleware: None, // TODO
                 })
@@ -1694,13 +1729,14 @@ fn grpc_api_definition_request_to_http(
                         let cors_preflight = binding
                             .static_binding
                             .and_then(|binding| binding.static_binding)
-                            .map(|binding| match binding {
+                            .and_then(|binding| match binding {
                                 static_binding::StaticBinding::HttpCorsPreflight(
                                     cors_preflight,
-                                ) => cors_preflight,
+                                ) => Some(cors_preflight),
                                 static_binding::StaticBinding::AuthCallback(_) => {
                                     todo!() // TODO: is this supported on http?
                                 }
+                                static_binding::StaticBinding::StatusPage(_) => None,
                             });
 
                         golem_client::model::RouteRequestData {
@@ -1734,6 +1770,31 @@ fn grpc_api_definition_request_to_http(
                                         GatewayBindingType::HttpHandler => {
                                             golem_client::model::GatewayBindingType::HttpHandler
                                         }
+                                        GatewayBindingType::Grpc => {
+                                            panic!(
+                                                "grpc binding is not supported by the HTTP client"
+                                            )
+                                        }
+                                        GatewayBindingType::WebSocket => {
+                                            panic!(
+                                                "web socket binding is not supported by the HTTP client"
+                                            )
+                                        }
+                                        GatewayBindingType::ServerSentEvents => {
+                                            panic!(
+                                                "server-sent events binding is not supported by the HTTP client"
+                                            )
+                                        }
+                                        GatewayBindingType::HttpProxy => {
+                                            panic!(
+                                                "http proxy binding is not supported by the HTTP client"
+                                            )
+                                        }
+                                        GatewayBindingType::StatusPage => {
+                                            panic!(
+                                                "status page binding is not supported by the HTTP client"
+                                            )
+                                        }
                                     }
                                 }),
                                 component_id: binding.component.map(|version_component_id| {
@@ -1778,6 +1839,7 @@ fn grpc_api_definition_request_to_http(
             })
             .unwrap_or_default(),
         draft: request.draft,
+        taint_policy: None,
     }
 }
 
@@ -578,6 +578,8 @@ impl<T: TestDependencies + Send + Sync> TestDsl for T {
                 name: name.to_string(),
                 args,
                 env,
+                wasi_config_vars: HashMap::new(),
+                labels: HashMap::new(),
             })
             .await?;
 
@@ -1137,6 +1139,7 @@ impl<T: TestDependencies + Send + Sync> TestDsl for T {
                     cursor,
                     count: 100,
                     query: query.to_string(),
+                    filter: None,
                 })
                 .await?;
 
@@ -1538,6 +1541,15 @@ pub fn worker_error_message(error: &Error) -> String {
                 worker_execution_error::Error::FileSystemError(error) => {
                     format!("File system error: {}", error.reason)
                 }
+                worker_execution_error::Error::WorkerExecutorDraining(_error) => {
+                    "Worker executor draining".to_string()
+                }
+                worker_execution_error::Error::OplogTruncated(error) => {
+                    format!(
+                        "Oplog truncated until {}, cannot access entry {}",
+                        error.truncated_until, error.oplog_index
+                    )
+                }
             },
         },
     }
@@ -1561,6 +1573,11 @@ pub fn to_worker_metadata(
                 .iter()
                 .map(|(k, v)| (k.clone(), v.clone()))
                 .collect::<Vec<_>>(),
+            wasi_config_vars: metadata
+                .wasi_config_vars
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
             account_id: metadata
                 .account_id
                 .clone()
@@ -1645,7 +1662,7 @@ pub fn to_worker_metadata(
                         )
                     })
                     .collect(),
-                extensions: WorkerStatusRecordExtensions::Extension2 {
+                extensions: WorkerStatusRecordExtensions::Extension5 {
                     active_plugins: HashSet::from_iter(
                         metadata
                             .active_plugins
@@ -1654,6 +1671,20 @@ pub fn to_worker_metadata(
                             .map(|id| id.try_into().expect("invalid plugin installation id")),
                     ),
                     deleted_regions: DeletedRegions::new(),
+                    labels: metadata
+                        .labels
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                    compaction: metadata
+                        .compaction
+                        .clone()
+                        .map(|compaction| compaction.into())
+                        .unwrap_or_default(),
+                    fork: metadata
+                        .fork
+                        .clone()
+                        .map(|fork| fork.try_into().expect("invalid fork origin")),
                 },
             },
             parent: None,
@@ -0,0 +1,128 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use golem_common::config::{DbPostgresConfig, DbSqliteConfig};
+use golem_service_base::db;
+use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
+use golem_service_base::storage::postgres::PostgresPool;
+use golem_service_base::storage::sqlite::SqlitePool;
+use golem_test_framework::components::rdb::docker_postgres::DockerPostgresRdb;
+use golem_test_framework::components::rdb::{DbInfo, Rdb};
+use golem_worker_executor_base::storage::indexed::memory::InMemoryIndexedStorage;
+use golem_worker_executor_base::storage::indexed::postgres::PostgresIndexedStorage;
+use golem_worker_executor_base::storage::indexed::sqlite::SqliteIndexedStorage;
+use golem_worker_executor_base::storage::indexed::{IndexedStorage, IndexedStorageNamespace};
+use std::sync::Arc;
+use std::time::Instant;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+criterion_group!(benches, oplog_append);
+criterion_main!(benches);
+
+const ENTRY_SIZES: &[usize] = &[64, 1024, 16 * 1024];
+
+/// Appends entries one by one to a fresh oplog, the way a running worker does
+fn oplog_append(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let storages = runtime.block_on(Storages::new());
+
+    let mut group = c.benchmark_group("oplog_append");
+    group.throughput(Throughput::Elements(1));
+    for &size in ENTRY_SIZES {
+        let entry = (0..size).map(|i| i as u8).collect::<Vec<_>>();
+        for (name, storage) in &storages.storages {
+            group.bench_with_input(BenchmarkId::new(*name, size), &entry, |b, entry| {
+                b.to_async(&runtime).iter_custom(|iters| {
+                    let storage = storage.clone();
+                    let entry = entry.clone();
+                    async move {
+                        let key = Uuid::new_v4().to_string();
+                        let start = Instant::now();
+                        for id in 1..=iters {
+                            storage
+                                .append(
+                                    "bench",
+                                    "append",
+                                    "entry",
+                                    IndexedStorageNamespace::OpLog,
+                                    &key,
+                                    id,
+                                    &entry,
+                                )
+                                .await
+                                .unwrap();
+                        }
+                        start.elapsed()
+                    }
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+struct Storages {
+    storages: Vec<(&'static str, Arc<dyn IndexedStorage + Send + Sync>)>,
+    // Kept until the end of the benchmark
+    _sqlite_dir: TempDir,
+    _postgres: DockerPostgresRdb,
+}
+
+impl Storages {
+    async fn new() -> Self {
+        let sqlite_dir = TempDir::new().unwrap();
+        let sqlite = SqlitePool::configured(&DbSqliteConfig {
+            database: sqlite_dir
+                .path()
+                .join("oplog.db")
+                .to_string_lossy()
+                .to_string(),
+            max_connections: 10,
+        })
+        .await
+        .unwrap();
+
+        let postgres = DockerPostgresRdb::new(true, false).await;
+        let DbInfo::Postgres(info) = postgres.info() else {
+            panic!("Postgres database expected")
+        };
+        let postgres_config = DbPostgresConfig {
+            host: info.host,
+            database: info.database_name,
+            username: info.username,
+            password: info.password,
+            port: info.port,
+            max_connections: 10,
+            schema: Some("bench".to_string()),
+        };
+        db::postgres_migrate(
+            &postgres_config,
+            IncludedMigrationsDir::new(golem_worker_executor_base::db_migrations())
+                .postgres_migrations(),
+        )
+        .await
+        .unwrap();
+        let postgres_pool = PostgresPool::configured(&postgres_config).await.unwrap();
+
+        Self {
+            storages: vec![
+                (
+                    "in_memory",
+                    Arc::new(InMemoryIndexedStorage::new())
+                        as Arc<dyn IndexedStorage + Send + Sync>,
+                ),
+                (
+                    "sqlite",
+                    Arc::new(SqliteIndexedStorage::new(sqlite).await.unwrap())
+                        as Arc<dyn IndexedStorage + Send + Sync>,
+                ),
+                (
+                    "postgres",
+                    Arc::new(PostgresIndexedStorage::new(postgres_pool))
+                        as Arc<dyn IndexedStorage + Send + Sync>,
+                ),
+            ],
+            _sqlite_dir: sqlite_dir,
+            _postgres: postgres,
+        }
+    }
+}
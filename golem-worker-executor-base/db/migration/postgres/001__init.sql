@@ -0,0 +1,35 @@
+CREATE TABLE kv_storage
+(
+    namespace   text    NOT NULL,
+    key         text    NOT NULL,
+    value       bytea   NOT NULL,
+    PRIMARY KEY (namespace, key)
+);
+
+CREATE TABLE set_storage
+(
+    namespace   text    NOT NULL,
+    key         text    NOT NULL,
+    value       bytea   NOT NULL,
+    PRIMARY KEY (namespace, key, value)
+);
+
+CREATE TABLE sorted_set_storage
+(
+    namespace   text             NOT NULL,
+    key         text             NOT NULL,
+    value       bytea            NOT NULL,
+    score       double precision NOT NULL,
+    PRIMARY KEY (namespace, key, value)
+);
+
+CREATE INDEX sorted_set_storage_score_idx ON sorted_set_storage (namespace, key, score);
+
+CREATE TABLE index_storage
+(
+    namespace   text    NOT NULL,
+    key         text    NOT NULL,
+    id          bigint  NOT NULL,
+    value       bytea   NOT NULL,
+    PRIMARY KEY (namespace, key, id)
+);
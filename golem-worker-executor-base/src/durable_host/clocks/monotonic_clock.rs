@@ -17,6 +17,7 @@ use wasmtime::component::Resource;
 
 use crate::durable_host::serialized::SerializableError;
 use crate::durable_host::{Durability, DurabilityHost, DurableWorkerCtx};
+use crate::model::DeadlineExceededCall;
 use crate::services::oplog::CommitLevel;
 use crate::workerctx::WorkerCtx;
 use golem_common::model::oplog::DurableFunctionType;
@@ -60,6 +61,12 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
 
     async fn subscribe_instant(&mut self, when: Instant) -> anyhow::Result<Resource<Pollable>> {
         self.observe_function_call("clocks::monotonic_clock", "subscribe_instant");
+        let when = if self.time_until_deadline().is_some() {
+            let now = Host::now(&mut self.as_wasi_view()).await?;
+            now.saturating_add(self.limit_sleep_to_deadline(when.saturating_sub(now)))
+        } else {
+            when
+        };
         Host::subscribe_instant(&mut self.as_wasi_view(), when).await
     }
 
@@ -82,7 +89,32 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         }?;
 
         self.state.oplog.commit(CommitLevel::DurableOnly).await;
-        let when = now.saturating_add(when);
+        let when = now.saturating_add(self.limit_sleep_to_deadline(when));
         Host::subscribe_instant(&mut self.as_wasi_view(), when).await
     }
 }
+
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    // Sleeping no longer than the deadline of the invocation, and recording it when the sleep
+    // gets cut short, so the worker can tell that it woke up early
+    fn limit_sleep_to_deadline(&mut self, duration: Duration) -> Duration {
+        let (Some(deadline), Some(time_until_deadline)) = (
+            self.state.invocation_context.deadline(),
+            self.time_until_deadline(),
+        ) else {
+            return duration;
+        };
+
+        let time_until_deadline = as_nanos(time_until_deadline);
+        if time_until_deadline < duration {
+            self.record_deadline_exceeded(deadline, DeadlineExceededCall::Sleep);
+            time_until_deadline
+        } else {
+            duration
+        }
+    }
+}
+
+fn as_nanos(duration: std::time::Duration) -> Duration {
+    duration.as_nanos().min(Duration::MAX as u128) as Duration
+}
@@ -0,0 +1,239 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::durable_host::serialized::SerializableError;
+use crate::durable_host::{Durability, DurabilityHost, DurableWorkerCtx};
+use crate::model::InterruptKind;
+use crate::preview2::golem_api_1_x::host::{Awaited, PromiseId};
+use crate::workerctx::WorkerCtx;
+use anyhow::anyhow;
+use bincode::{Decode, Encode};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
+use golem_common::model::oplog::DurableFunctionType;
+use golem_wasm_rpc::golem_rpc_0_1_x::types::FutureInvokeResult;
+use golem_wasm_rpc::{FutureInvokeResultEntry, SubscribeAny};
+use std::collections::HashMap;
+use tracing::debug;
+use wasmtime::component::Resource;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AwaitCondition {
+    Any,
+    All,
+}
+
+impl AwaitCondition {
+    fn is_met(&self, completed: usize, total: usize) -> bool {
+        match self {
+            AwaitCondition::Any => total == 0 || completed > 0,
+            AwaitCondition::All => completed == total,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct SerializableAwaited {
+    pub promises: Vec<(u32, Vec<u8>)>,
+    pub rpc_results: Vec<u32>,
+    pub timed_out: bool,
+}
+
+impl From<Awaited> for SerializableAwaited {
+    fn from(value: Awaited) -> Self {
+        Self {
+            promises: value.promises,
+            rpc_results: value.rpc_results,
+            timed_out: value.timed_out,
+        }
+    }
+}
+
+impl From<SerializableAwaited> for Awaited {
+    fn from(value: SerializableAwaited) -> Self {
+        Self {
+            promises: value.promises,
+            rpc_results: value.rpc_results,
+            timed_out: value.timed_out,
+        }
+    }
+}
+
+impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
+    /// Waits for a set of promises and RPC results, and records which of them were completed as
+    /// a single oplog entry, so the decision made by the worker is the same when it gets replayed.
+    ///
+    /// The deadline is recorded before waiting, so it does not move when the worker gets suspended
+    /// while waiting for promises only, and resumed by their completion or by the deadline.
+    pub(crate) async fn await_promises_and_rpc_results(
+        &mut self,
+        condition: AwaitCondition,
+        promise_ids: Vec<PromiseId>,
+        rpc_results: Vec<Resource<FutureInvokeResult>>,
+        timeout: Option<u64>,
+    ) -> anyhow::Result<Awaited> {
+        let deadline_durability = Durability::<Option<i64>, SerializableError>::new(
+            self,
+            "golem::api",
+            "await_deadline",
+            DurableFunctionType::ReadLocal,
+        )
+        .await?;
+        let deadline: Option<i64> = if deadline_durability.is_live() {
+            let deadline = timeout.map(|timeout| {
+                (Utc::now() + chrono::Duration::nanoseconds(timeout as i64)).timestamp_millis()
+            });
+            deadline_durability
+                .persist(self, timeout, Ok::<_, anyhow::Error>(deadline))
+                .await?
+        } else {
+            deadline_durability.replay(self).await?
+        };
+        let deadline = deadline.and_then(DateTime::<Utc>::from_timestamp_millis);
+
+        let function = match condition {
+            AwaitCondition::Any => "await_any",
+            AwaitCondition::All => "await_all",
+        };
+        let durability = Durability::<SerializableAwaited, SerializableError>::new(
+            self,
+            "golem::api",
+            function,
+            DurableFunctionType::ReadRemote,
+        )
+        .await?;
+
+        if !durability.is_live() {
+            return durability.replay(self).await;
+        }
+
+        let promise_ids: Vec<golem_common::model::PromiseId> =
+            promise_ids.into_iter().map(|id| id.into()).collect();
+        let total = promise_ids.len() + rpc_results.len();
+
+        let awaited = loop {
+            let mut promises = Vec::new();
+            for (idx, promise_id) in promise_ids.iter().enumerate() {
+                if let Some(data) = self
+                    .public_state
+                    .promise_service
+                    .poll(promise_id.clone())
+                    .await?
+                {
+                    promises.push((idx as u32, data));
+                }
+            }
+
+            let mut ready_rpc_results = Vec::new();
+            for (idx, rpc_result) in rpc_results.iter().enumerate() {
+                let entry = self.table().get_mut(rpc_result)?;
+                if futures::future::poll_immediate(entry.payload.ready())
+                    .await
+                    .is_some()
+                {
+                    ready_rpc_results.push(idx as u32);
+                }
+            }
+
+            let met = condition.is_met(promises.len() + ready_rpc_results.len(), total);
+            let timed_out = !met && deadline.is_some_and(|deadline| Utc::now() >= deadline);
+            if met || timed_out {
+                break Awaited {
+                    promises,
+                    rpc_results: ready_rpc_results,
+                    timed_out,
+                };
+            }
+
+            if rpc_results.is_empty() {
+                // Promises are only completed from the outside, so the worker can be suspended
+                // until one of them gets completed, or until the deadline
+                debug!("Suspending worker until the awaited promises get completed");
+                if let Some(deadline) = deadline {
+                    self.state.sleep_until(deadline).await?;
+                }
+                return Err(InterruptKind::Suspend.into());
+            }
+
+            // The RPC results only exist in the memory of this executor, so the worker stays
+            // loaded and waits for the first pending promise or RPC result getting completed, or
+            // for the deadline, before checking them again
+            let promise_service = self.public_state.promise_service.clone();
+            let mut completions: FuturesUnordered<BoxFuture<'_, ()>> = FuturesUnordered::new();
+
+            for (idx, promise_id) in promise_ids.iter().enumerate() {
+                if !promises
+                    .iter()
+                    .any(|(completed, _)| *completed == idx as u32)
+                {
+                    let promise_service = promise_service.clone();
+                    let promise_id = promise_id.clone();
+                    completions.push(
+                        async move {
+                            let _ = promise_service.wait_for(promise_id).await;
+                        }
+                        .boxed(),
+                    );
+                }
+            }
+
+            let pending_rpc_results = rpc_results
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !ready_rpc_results.contains(&(*idx as u32)))
+                .map(|(_, rpc_result)| (rpc_result.rep(), ()))
+                .collect::<HashMap<_, _>>();
+            for (entry, _) in self.table().iter_entries(pending_rpc_results) {
+                let entry = entry?
+                    .downcast_mut::<FutureInvokeResultEntry>()
+                    .ok_or_else(|| anyhow!("Awaited resource is not an RPC result"))?;
+                completions.push(entry.payload.ready());
+            }
+
+            if let Some(deadline) = deadline {
+                let wait = (deadline - Utc::now()).to_std().unwrap_or_default();
+                completions.push(tokio::time::sleep(wait).boxed());
+            }
+
+            completions.next().await;
+        };
+
+        durability
+            .persist(
+                self,
+                (promise_ids, rpc_results.len() as u32),
+                Ok::<_, anyhow::Error>(awaited),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::AwaitCondition;
+
+    #[test]
+    fn await_conditions() {
+        assert!(AwaitCondition::Any.is_met(0, 0));
+        assert!(!AwaitCondition::Any.is_met(0, 3));
+        assert!(AwaitCondition::Any.is_met(1, 3));
+        assert!(AwaitCondition::All.is_met(0, 0));
+        assert!(!AwaitCondition::All.is_met(2, 3));
+        assert!(AwaitCondition::All.is_met(3, 3));
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::durable_host::golem::awaiting::AwaitCondition;
 use crate::durable_host::serialized::SerializableError;
 use crate::durable_host::{Durability, DurabilityHost, DurableWorkerCtx};
 use crate::error::GolemError;
@@ -23,22 +24,26 @@ use crate::preview2::golem_api_0_2_x;
 use crate::preview2::golem_api_0_2_x::host::GetWorkers;
 use crate::preview2::golem_api_1_x;
 use crate::preview2::golem_api_1_x::host::{
-    ComponentId, ComponentVersion, FilterComparator, Host, HostGetWorkers, OplogIndex,
-    PersistenceLevel, PromiseId, RetryPolicy, RevertWorkerTarget, StringFilterComparator,
-    UpdateMode, Uuid, WorkerAllFilter, WorkerAnyFilter, WorkerCreatedAtFilter, WorkerEnvFilter,
-    WorkerId, WorkerMetadata, WorkerNameFilter, WorkerPropertyFilter, WorkerStatus,
-    WorkerStatusFilter, WorkerVersionFilter,
+    Awaited, ComponentId, ComponentVersion, DeadlineExceeded, FilterComparator, Host,
+    HostGetWorkers, OplogIndex, PersistenceLevel, PromiseId, RetryPolicy, RevertWorkerTarget,
+    StringFilterComparator, UpdateMode, Uuid, WorkerAllFilter, WorkerAnyFilter,
+    WorkerCreatedAtFilter, WorkerEnvFilter, WorkerId, WorkerMetadata, WorkerNameFilter,
+    WorkerPropertyFilter, WorkerStatus, WorkerStatusFilter, WorkerVersionFilter,
 };
 use crate::preview2::golem_api_1_x::oplog::{
     Host as OplogHost, HostGetOplog, HostSearchOplog, OplogEntry, SearchOplog,
 };
+use crate::services::worker::WorkerService;
 use crate::services::{HasOplogService, HasPlugins};
 use crate::workerctx::WorkerCtx;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use golem_common::model::oplog::DurableFunctionType;
+use golem_common::model::public_oplog::PublicOplogEntryFilter;
 use golem_common::model::OwnedWorkerId;
 use golem_common::model::RetryConfig;
+use golem_wasm_rpc::golem_rpc_0_1_x::types::FutureInvokeResult;
+use std::sync::Arc;
 use std::time::Duration;
 use wasmtime::component::Resource;
 use wasmtime_wasi::WasiView;
@@ -110,6 +115,48 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
         Ok(result?)
     }
 
+    async fn await_any(
+        &mut self,
+        promise_ids: Vec<PromiseId>,
+        rpc_results: Vec<Resource<FutureInvokeResult>>,
+        timeout: Option<u64>,
+    ) -> anyhow::Result<Awaited> {
+        self.await_promises_and_rpc_results(AwaitCondition::Any, promise_ids, rpc_results, timeout)
+            .await
+    }
+
+    async fn await_all(
+        &mut self,
+        promise_ids: Vec<PromiseId>,
+        rpc_results: Vec<Resource<FutureInvokeResult>>,
+        timeout: Option<u64>,
+    ) -> anyhow::Result<Awaited> {
+        self.await_promises_and_rpc_results(AwaitCondition::All, promise_ids, rpc_results, timeout)
+            .await
+    }
+
+    async fn take_deadline_exceeded(&mut self) -> anyhow::Result<Option<DeadlineExceeded>> {
+        let durability =
+            Durability::<Option<crate::model::DeadlineExceeded>, SerializableError>::new(
+                self,
+                "golem::api",
+                "take_deadline_exceeded",
+                DurableFunctionType::ReadLocal,
+            )
+            .await?;
+
+        let deadline_exceeded = self.state.deadline_exceeded.take();
+        let deadline_exceeded = if durability.is_live() {
+            durability
+                .persist(self, (), Ok::<_, anyhow::Error>(deadline_exceeded))
+                .await?
+        } else {
+            durability.replay::<_, anyhow::Error>(self).await?
+        };
+
+        Ok(deadline_exceeded.map(|deadline_exceeded| deadline_exceeded.into()))
+    }
+
     async fn complete_promise(
         &mut self,
         promise_id: PromiseId,
@@ -405,8 +452,13 @@ impl<Ctx: WorkerCtx> HostGetOplog for DurableWorkerCtx<Ctx> {
         let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
 
         let start = golem_common::model::oplog::OplogIndex::from_u64(start);
-        let initial_component_version =
-            find_component_version_at(self.state.oplog_service(), &owned_worker_id, start).await?;
+        let initial_component_version = find_component_version_at(
+            self.state.oplog_service(),
+            &owned_worker_id,
+            start,
+            truncated_until(&self.state.worker_service, &owned_worker_id).await,
+        )
+        .await?;
 
         let entry = GetOplogEntry::new(owned_worker_id, start, initial_component_version, 100);
         let resource = self.as_wasi_view().table().push(entry)?;
@@ -433,6 +485,7 @@ impl<Ctx: WorkerCtx> HostGetOplog for DurableWorkerCtx<Ctx> {
             entry.current_component_version,
             entry.next_oplog_index,
             entry.page_size,
+            truncated_until(&self.state.worker_service, &entry.owned_worker_id).await,
         )
         .await
         .map_err(|msg| anyhow!(msg))?;
@@ -461,6 +514,18 @@ impl<Ctx: WorkerCtx> HostGetOplog for DurableWorkerCtx<Ctx> {
     }
 }
 
+// The index the oplog of the worker is truncated until by compaction, the oplog queries skip the
+// history dropped before it
+async fn truncated_until(
+    worker_service: &Arc<dyn WorkerService + Send + Sync>,
+    owned_worker_id: &OwnedWorkerId,
+) -> Option<golem_common::model::oplog::OplogIndex> {
+    worker_service
+        .get(owned_worker_id)
+        .await
+        .and_then(|metadata| metadata.last_known_status.compaction().truncated_until)
+}
+
 #[derive(Debug, Clone)]
 pub struct GetOplogEntry {
     pub owned_worker_id: OwnedWorkerId,
@@ -508,8 +573,13 @@ impl<Ctx: WorkerCtx> HostSearchOplog for DurableWorkerCtx<Ctx> {
         let owned_worker_id = OwnedWorkerId::new(&account_id, &worker_id);
 
         let start = golem_common::model::oplog::OplogIndex::INITIAL;
-        let initial_component_version =
-            find_component_version_at(self.state.oplog_service(), &owned_worker_id, start).await?;
+        let initial_component_version = find_component_version_at(
+            self.state.oplog_service(),
+            &owned_worker_id,
+            start,
+            truncated_until(&self.state.worker_service, &owned_worker_id).await,
+        )
+        .await?;
 
         let entry =
             SearchOplogEntry::new(owned_worker_id, start, initial_component_version, 100, text);
@@ -538,6 +608,8 @@ impl<Ctx: WorkerCtx> HostSearchOplog for DurableWorkerCtx<Ctx> {
             entry.next_oplog_index,
             entry.page_size,
             &entry.query,
+            &PublicOplogEntryFilter::default(),
+            truncated_until(&self.state.worker_service, &entry.owned_worker_id).await,
         )
         .await
         .map_err(|msg| anyhow!(msg))?;
@@ -24,8 +24,10 @@ use golem_common::model::oplog::DurableFunctionType;
 use golem_service_base::headers::TraceContextHeaders;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use wasmtime::component::Resource;
 use wasmtime_wasi_http::bindings::http::types;
+use wasmtime_wasi_http::bindings::http::types::HostRequestOptions;
 use wasmtime_wasi_http::bindings::wasi::http::outgoing_handler::Host;
 use wasmtime_wasi_http::types::{HostFutureIncomingResponse, HostOutgoingRequest};
 use wasmtime_wasi_http::{HttpError, HttpResult};
@@ -84,6 +86,15 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
             }
         }
 
+        // The request times out at the deadline of the invocation at the latest
+        let options = match self.time_until_deadline() {
+            Some(time_until_deadline) => Some(
+                limit_request_timeouts(self, options, time_until_deadline)
+                    .map_err(HttpError::trap)?,
+            ),
+            None => options,
+        };
+
         let result = Host::handle(&mut self.as_wasi_http_view(), request, options).await;
 
         match &result {
@@ -124,6 +135,49 @@ impl<Ctx: WorkerCtx> Host for DurableWorkerCtx<Ctx> {
     }
 }
 
+// Caps the timeouts of the request options, creating them if the request has none, so that the
+// request fails with a timeout error code once the limit is reached
+fn limit_request_timeouts<Ctx: WorkerCtx>(
+    ctx: &mut DurableWorkerCtx<Ctx>,
+    options: Option<Resource<types::RequestOptions>>,
+    limit: Duration,
+) -> anyhow::Result<Resource<types::RequestOptions>> {
+    let mut view = ctx.as_wasi_http_view();
+    let options = match options {
+        Some(options) => options,
+        None => HostRequestOptions::new(&mut view)?,
+    };
+    let rep = options.rep();
+    let limit = limit.as_nanos().min(u64::MAX as u128) as u64;
+    let limited = |timeout: Option<u64>| Some(timeout.map_or(limit, |timeout| timeout.min(limit)));
+
+    let connect_timeout =
+        HostRequestOptions::connect_timeout(&mut view, Resource::new_borrow(rep))?;
+    let _ = HostRequestOptions::set_connect_timeout(
+        &mut view,
+        Resource::new_borrow(rep),
+        limited(connect_timeout),
+    )?;
+
+    let first_byte_timeout =
+        HostRequestOptions::first_byte_timeout(&mut view, Resource::new_borrow(rep))?;
+    let _ = HostRequestOptions::set_first_byte_timeout(
+        &mut view,
+        Resource::new_borrow(rep),
+        limited(first_byte_timeout),
+    )?;
+
+    let between_bytes_timeout =
+        HostRequestOptions::between_bytes_timeout(&mut view, Resource::new_borrow(rep))?;
+    let _ = HostRequestOptions::set_between_bytes_timeout(
+        &mut view,
+        Resource::new_borrow(rep),
+        limited(between_bytes_timeout),
+    )?;
+
+    Ok(options)
+}
+
 fn setup_outgoing_http_request_span(
     span: &Arc<InvocationContextSpan>,
     uri: &str,
@@ -41,7 +41,7 @@ use crate::durable_host::http::{continue_http_request, end_http_request};
 use crate::durable_host::serialized::SerializableError;
 use crate::durable_host::{Durability, DurabilityHost, DurableWorkerCtx, HttpRequestCloseOwner};
 use crate::get_oplog_entry;
-use crate::model::PersistenceLevel;
+use crate::model::{DeadlineExceededCall, PersistenceLevel};
 use crate::services::oplog::{CommitLevel, OplogOps};
 use crate::workerctx::WorkerCtx;
 
@@ -637,6 +637,20 @@ impl<Ctx: WorkerCtx> HostFutureIncomingResponse for DurableWorkerCtx<Ctx> {
             let response =
                 HostFutureIncomingResponse::get(&mut self.as_wasi_http_view(), self_).await;
 
+            // The timeouts of the request are capped at the deadline of the invocation, so a
+            // timeout after the deadline passed means the request was cut short by it
+            if let Ok(Some(Ok(Err(
+                ErrorCode::ConnectionTimeout | ErrorCode::ConnectionReadTimeout,
+            )))) = &response
+            {
+                if let (Some(deadline), Some(std::time::Duration::ZERO)) = (
+                    self.state.invocation_context.deadline(),
+                    self.time_until_deadline(),
+                ) {
+                    self.record_deadline_exceeded(deadline, DeadlineExceededCall::OutgoingHttp);
+                }
+            }
+
             let serializable_response = match &response {
                 Ok(None) => SerializableResponse::Pending,
                 Ok(Some(Ok(Ok(resource)))) => {
@@ -23,8 +23,9 @@ use crate::durable_host::wasm_rpc::UrnExtensions;
 use crate::error::GolemError;
 use crate::metrics::wasm::{record_number_of_replayed_functions, record_resume_worker};
 use crate::model::{
-    CurrentResourceLimits, ExecutionStatus, InterruptKind, InvocationContext, LastError,
-    ListDirectoryResult, PersistenceLevel, ReadFileResult, TrapType, WorkerConfig,
+    CurrentResourceLimits, DeadlineExceeded, DeadlineExceededCall, ExecutionStatus, InterruptKind,
+    InvocationContext, LastError, ListDirectoryResult, PersistenceLevel, ReadFileResult, TrapType,
+    WorkerConfig,
 };
 use crate::services::blob_store::BlobStoreService;
 use crate::services::component::{ComponentMetadata, ComponentService};
@@ -44,6 +45,7 @@ use crate::services::{HasOplogService, HasPlugins};
 use crate::wasi_host;
 use crate::worker::function_result_interpreter::interpret_function_results;
 use crate::worker::invocation::{find_first_available_function, invoke_worker, InvokeResult};
+use crate::worker::lifecycle::LifecycleHook;
 use crate::worker::status::calculate_last_known_status;
 use crate::worker::{is_worker_error_retriable, RetryDecision, Worker};
 use crate::workerctx::{
@@ -60,8 +62,8 @@ use futures_util::TryFutureExt;
 use futures_util::TryStreamExt;
 use golem_common::model::invocation_context::{InvocationContextStack, SpanId};
 use golem_common::model::oplog::{
-    DurableFunctionType, IndexedResourceKey, LogLevel, OplogEntry, OplogIndex, UpdateDescription,
-    WorkerError, WorkerResourceId,
+    DurableFunctionType, IndexedResourceKey, LogLevel, OplogEntry, OplogIndex,
+    TimestampedUpdateDescription, UpdateDescription, WorkerError, WorkerResourceId,
 };
 use golem_common::model::regions::{DeletedRegions, OplogRegion};
 use golem_common::model::{exports, PluginInstallationId};
@@ -69,8 +71,9 @@ use golem_common::model::{
     AccountId, ComponentFilePath, ComponentFilePermissions, ComponentFileSystemNode,
     ComponentFileSystemNodeDetails, ComponentId, ComponentType, ComponentVersion,
     FailedUpdateRecord, IdempotencyKey, InitialComponentFile, OwnedWorkerId, ScanCursor,
-    ScheduledAction, SuccessfulUpdateRecord, Timestamp, WorkerEvent, WorkerFilter, WorkerId,
-    WorkerMetadata, WorkerResourceDescription, WorkerStatus, WorkerStatusRecord,
+    ScheduledAction, SuccessfulUpdateRecord, Timestamp, TimestampedWorkerInvocation, WorkerEvent,
+    WorkerFilter, WorkerId, WorkerMetadata, WorkerResourceDescription, WorkerStatus,
+    WorkerStatusRecord,
 };
 use golem_common::model::{RetryConfig, TargetWorkerId};
 use golem_common::retries::get_delay;
@@ -332,6 +335,8 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
             .clone_from(&self.state.current_idempotency_key);
         status.total_linear_memory_size = self.state.total_linear_memory_size;
         status.oplog_idx = self.state.oplog.current_oplog_index().await;
+        status.compaction_mut().bytes_since_last_attempt +=
+            self.state.oplog.take_added_bytes().await;
         f(&mut status);
         self.public_state.worker().update_status(status).await;
     }
@@ -340,6 +345,24 @@ impl<Ctx: WorkerCtx> DurableWorkerCtx<Ctx> {
         self.state.rpc.clone()
     }
 
+    /// The time left until the deadline of the current invocation, if it has one.
+    /// It is zero once the deadline passed.
+    pub fn time_until_deadline(&self) -> Option<Duration> {
+        let deadline = self.state.invocation_context.deadline()?;
+        Some(Duration::from_millis(
+            deadline
+                .to_millis()
+                .saturating_sub(Timestamp::now_utc().to_millis()),
+        ))
+    }
+
+    /// Records a host call of the current invocation which was cut short by its deadline, so the
+    /// worker can tell it apart from other failures with `take-deadline-exceeded`
+    pub fn record_deadline_exceeded(&mut self, deadline: Timestamp, call: DeadlineExceededCall) {
+        debug!("{call:?} host call cut short by the deadline {deadline} of the invocation");
+        self.state.deadline_exceeded = Some(DeadlineExceeded { deadline, call });
+    }
+
     pub fn worker_proxy(&self) -> Arc<dyn WorkerProxy + Send + Sync> {
         self.state.worker_proxy.clone()
     }
@@ -545,84 +568,22 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> DurableWorkerCtx<Ctx> {
                             .await
                         {
                             Ok(Some(data)) => {
-                                let failed = if let Some(load_snapshot) =
-                                    find_first_available_function(
-                                        store,
-                                        instance,
-                                        vec![
-                                            "golem:api/load-snapshot@1.1.0.{load}".to_string(),
-                                            "golem:api/load-snapshot@0.2.0.{load}".to_string(),
-                                        ],
-                                    ) {
-                                    let idempotency_key = IdempotencyKey::fresh();
-                                    store
-                                        .as_context_mut()
-                                        .data_mut()
-                                        .durable_ctx_mut()
-                                        .set_current_idempotency_key(idempotency_key.clone())
-                                        .await;
-
-                                    store
-                                        .as_context_mut()
-                                        .data_mut()
-                                        .begin_call_snapshotting_function();
-                                    let load_result = invoke_worker(
-                                        load_snapshot,
-                                        vec![Value::List(
-                                            data.iter().map(|b| Value::U8(*b)).collect(),
-                                        )],
-                                        store,
-                                        instance,
-                                    )
-                                    .await;
-                                    store
-                                        .as_context_mut()
-                                        .data_mut()
-                                        .end_call_snapshotting_function();
-
-                                    match load_result {
-                                        Err(error) => Some(format!(
-                                            "Manual update failed to load snapshot: {error}"
-                                        )),
-                                        Ok(InvokeResult::Failed { error, .. }) => {
-                                            let stderr = store
-                                                .as_context()
-                                                .data()
-                                                .get_public_state()
-                                                .event_service()
-                                                .get_last_invocation_errors();
-                                            let error = error.to_string(&stderr);
-                                            Some(format!(
-                                                "Manual update failed to load snapshot: {error}"
-                                            ))
-                                        }
-                                        Ok(InvokeResult::Succeeded { output, .. }) => {
-                                            if output.len() == 1 {
-                                                match &output[0] {
-                                                        Value::Result(Err(Some(boxed_error_value))) => {
-                                                            match &**boxed_error_value {
-                                                                Value::String(error) =>
-                                                                    Some(format!("Manual update failed to load snapshot: {error}")),
-                                                                _ =>
-                                                                    Some("Unexpected result value from the snapshot load function".to_string())
-                                                            }
-                                                        }
-                                                        _ => None
-                                                    }
-                                            } else {
-                                                Some("Unexpected result value from the snapshot load function".to_string())
-                                            }
-                                        }
-                                        _ => None,
-                                    }
-                                } else {
-                                    Some(
-                                        "Failed to find exported load-snapshot function"
-                                            .to_string(),
-                                    )
-                                };
+                                // Compaction takes its snapshots as manual updates to the
+                                // current version of the worker
+                                let current_version = store
+                                    .as_context()
+                                    .data()
+                                    .durable_ctx()
+                                    .get_worker_status_record()
+                                    .component_version;
+                                let failed = Self::load_snapshot(store, instance, &data).await;
 
                                 if let Some(error) = failed {
+                                    let error = if target_version == current_version {
+                                        format!("Oplog compaction failed to load snapshot: {error}")
+                                    } else {
+                                        format!("Manual update failed to load snapshot: {error}")
+                                    };
                                     store
                                         .as_context_mut()
                                         .data_mut()
@@ -646,6 +607,7 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> DurableWorkerCtx<Ctx> {
                                             ),
                                         )
                                         .await;
+                                    Self::compact_after_snapshot(store, &pending_update).await;
                                     RetryDecision::None
                                 }
                             }
@@ -726,6 +688,166 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> DurableWorkerCtx<Ctx> {
             }
         }
     }
+
+    // Invokes the exported load-snapshot function with a snapshot, returning the error if the
+    // worker could not load it, for the caller to describe what the snapshot was loaded for
+    async fn load_snapshot(
+        store: &mut (impl AsContextMut<Data = Ctx> + Send),
+        instance: &Instance,
+        data: &[u8],
+    ) -> Option<String> {
+        if let Some(load_snapshot) = find_first_available_function(
+            store,
+            instance,
+            vec![
+                "golem:api/load-snapshot@1.1.0.{load}".to_string(),
+                "golem:api/load-snapshot@0.2.0.{load}".to_string(),
+            ],
+        ) {
+            let idempotency_key = IdempotencyKey::fresh();
+            store
+                .as_context_mut()
+                .data_mut()
+                .durable_ctx_mut()
+                .set_current_idempotency_key(idempotency_key.clone())
+                .await;
+
+            store
+                .as_context_mut()
+                .data_mut()
+                .begin_call_snapshotting_function();
+            let load_result = invoke_worker(
+                load_snapshot,
+                vec![Value::List(data.iter().map(|b| Value::U8(*b)).collect())],
+                store,
+                instance,
+            )
+            .await;
+            store
+                .as_context_mut()
+                .data_mut()
+                .end_call_snapshotting_function();
+
+            match load_result {
+                Err(error) => Some(error.to_string()),
+                Ok(InvokeResult::Failed { error, .. }) => {
+                    let stderr = store
+                        .as_context()
+                        .data()
+                        .get_public_state()
+                        .event_service()
+                        .get_last_invocation_errors();
+                    Some(error.to_string(&stderr))
+                }
+                Ok(InvokeResult::Succeeded { output, .. }) => {
+                    if output.len() == 1 {
+                        match &output[0] {
+                            Value::Result(Err(Some(boxed_error_value))) => {
+                                match &**boxed_error_value {
+                                    Value::String(error) => Some(error.clone()),
+                                    _ => Some(
+                                        "Unexpected result value from the snapshot load function"
+                                            .to_string(),
+                                    ),
+                                }
+                            }
+                            _ => None,
+                        }
+                    } else {
+                        Some("Unexpected result value from the snapshot load function".to_string())
+                    }
+                }
+                _ => None,
+            }
+        } else {
+            Some("Failed to find exported load-snapshot function".to_string())
+        }
+    }
+
+    // Loads the last snapshot of a compacted worker before replaying the entries after it, as the
+    // history before the snapshot is skipped during recovery. Nothing is loaded while a new
+    // snapshot is pending, as it is loaded when the pending update gets finalized.
+    async fn restore_last_snapshot(
+        store: &mut (impl AsContextMut<Data = Ctx> + Send),
+        instance: &Instance,
+    ) -> Result<(), GolemError> {
+        let status = store
+            .as_context()
+            .data()
+            .durable_ctx()
+            .get_worker_status_record();
+
+        let snapshot_index = match status.compaction().last_snapshot_index {
+            Some(snapshot_index) if !status.skipped_regions.is_overridden() => snapshot_index,
+            _ => return Ok(()),
+        };
+
+        let oplog = store.as_context().data().get_public_state().oplog();
+        let description = match oplog.read(snapshot_index).await {
+            OplogEntry::PendingUpdate { description, .. } => description,
+            _ => {
+                return Err(GolemError::runtime(format!(
+                    "missing snapshot at oplog index {snapshot_index}"
+                )))
+            }
+        };
+        let data = oplog
+            .get_upload_description_payload(&description)
+            .await
+            .map_err(GolemError::runtime)?
+            .ok_or_else(|| {
+                GolemError::runtime(format!(
+                    "missing snapshot data at oplog index {snapshot_index}"
+                ))
+            })?;
+
+        debug!("Restoring the snapshot at oplog index {snapshot_index}");
+        match Self::load_snapshot(store, instance, &data).await {
+            Some(error) => Err(GolemError::runtime(format!(
+                "Oplog compaction failed to load the snapshot at index {snapshot_index}: {error}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    // Records a successfully loaded snapshot as the last compaction of the worker, and drops the
+    // entries made obsolete by it from the oplog, keeping the create entry and the pending update
+    // entry holding the snapshot
+    async fn compact_after_snapshot(
+        store: &mut (impl AsContextMut<Data = Ctx> + Send),
+        snapshot: &TimestampedUpdateDescription,
+    ) {
+        let ctx = store.as_context().data().durable_ctx();
+        let snapshot_index = snapshot.oplog_index;
+
+        let first_dropped = OplogIndex::INITIAL.next();
+        let last_dropped = snapshot_index.previous();
+        let truncate = ctx.state.config.oplog.compaction.truncate && last_dropped >= first_dropped;
+
+        if truncate {
+            debug!("Truncating the oplog between {first_dropped} and {last_dropped}");
+            ctx.state
+                .oplog
+                .drop_range(first_dropped, last_dropped)
+                .await;
+        }
+
+        ctx.update_worker_status(|status| {
+            if truncate {
+                // The results of the dropped invocations cannot be looked up anymore
+                status
+                    .invocation_results
+                    .retain(|_, oplog_idx| *oplog_idx >= snapshot_index);
+                status.compaction_mut().truncated_until = Some(snapshot_index);
+            }
+
+            let compaction = status.compaction_mut();
+            compaction.last_snapshot_index = Some(snapshot_index);
+            compaction.last_snapshot_timestamp = Some(snapshot.timestamp);
+            compaction.bytes_since_last_attempt = 0;
+        })
+        .await;
+    }
 }
 
 #[async_trait]
@@ -747,6 +869,7 @@ impl<Ctx: WorkerCtx> InvocationManagement for DurableWorkerCtx<Ctx> {
 
         self.state.invocation_context = invocation_context;
         self.state.current_span_id = current_span_id;
+        self.state.deadline_exceeded = None;
 
         Ok(())
     }
@@ -904,6 +1027,9 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
                 .map(|value| value.clone().into())
                 .collect();
 
+            let invocation_context = self.get_current_invocation_context().await;
+            let trace_id = invocation_context.trace_id.clone();
+
             self.state
                 .oplog
                 .add_exported_function_invoked(
@@ -912,6 +1038,7 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
                     self.get_current_idempotency_key().await.ok_or(anyhow!(
                         "No active invocation key is associated with the worker"
                     ))?,
+                    invocation_context,
                 )
                 .await
                 .unwrap_or_else(|err| {
@@ -921,6 +1048,11 @@ impl<Ctx: WorkerCtx> InvocationHooks for DurableWorkerCtx<Ctx> {
                     )
                 });
             self.state.oplog.commit(CommitLevel::Always).await;
+
+            self.state
+                .worker_service
+                .add_to_trace(&trace_id, &self.owned_worker_id)
+                .await;
         }
         Ok(())
     }
@@ -1128,6 +1260,12 @@ impl<Ctx: WorkerCtx> UpdateManagement for DurableWorkerCtx<Ctx> {
                 details: details.clone(),
             });
 
+            // The failed update entry was just added, so it is the last entry of the oplog
+            let failed_update_index = status.oplog_idx;
+            let compaction = status.compaction_mut();
+            compaction.last_failed_attempt_index = Some(failed_update_index);
+            compaction.bytes_since_last_attempt = 0;
+
             // As part of performing a manual update, after the executor called the save-snapshot function
             // it marks the whole history of the worker as "skipped" and reloads the worker with the new
             // version. As the history is skipped it immediately can start with calling load-snapshot on the
@@ -1157,6 +1295,7 @@ impl<Ctx: WorkerCtx> UpdateManagement for DurableWorkerCtx<Ctx> {
     ) {
         info!("Worker update to {} finished successfully", target_version);
 
+        let old_version = self.get_worker_status_record().component_version;
         let entry = OplogEntry::successful_update(
             target_version,
             new_component_size,
@@ -1182,6 +1321,31 @@ impl<Ctx: WorkerCtx> UpdateManagement for DurableWorkerCtx<Ctx> {
             }
         })
         .await;
+
+        let hook = LifecycleHook::Updated { old_version };
+        if let Some(invocation) = hook.invocation(
+            self.worker_id(),
+            target_version,
+            &self.component_metadata().exports,
+        ) {
+            debug!("Enqueuing the on-updated lifecycle hook");
+            // Persisting the hook before the update is reported as done, so it survives a restart
+            let entry = OplogEntry::pending_worker_invocation(invocation.clone());
+            let timestamped_invocation = TimestampedWorkerInvocation {
+                timestamp: entry.timestamp(),
+                invocation,
+            };
+            self.public_state.oplog.add_and_commit(entry).await;
+
+            // The update is finalized from within the invocation loop, which the worker's instance
+            // lock may be waiting for, so the hook is queued from a separate task
+            let worker = self.public_state.worker();
+            tokio::spawn(async move {
+                worker
+                    .enqueue_persisted_invocation(timestamped_invocation)
+                    .await;
+            });
+        }
     }
 }
 
@@ -1493,7 +1657,10 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> ExternalOperations<Ctx> for Dur
                 .get_out_of_skipped_region()
                 .await;
 
-            let result = Self::resume_replay(store, instance).await;
+            let result = match Self::restore_last_snapshot(store, instance).await {
+                Ok(()) => Self::resume_replay(store, instance).await,
+                Err(error) => Err(error),
+            };
 
             record_resume_worker(start.elapsed());
 
@@ -1568,6 +1735,7 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> ExternalOperations<Ctx> for Dur
                         None,
                         None,
                         None,
+                        None,
                     )
                     .await?;
                 }
@@ -1718,7 +1886,7 @@ impl<Ctx: WorkerCtx + DurableWorkerCtxView<Ctx>> FileSystemReading for DurableWo
     }
 }
 
-async fn last_error_and_retry_count<T: HasOplogService + HasConfig>(
+pub(crate) async fn last_error_and_retry_count<T: HasOplogService + HasConfig>(
     this: &T,
     owned_worker_id: &OwnedWorkerId,
     latest_worker_status: &WorkerStatusRecord,
@@ -1812,7 +1980,8 @@ pub(crate) async fn recover_stderr_logs<T: HasOplogService + HasConfig>(
                     break;
                 }
             }
-            Some((_, OplogEntry::ExportedFunctionInvoked { .. })) => break,
+            Some((_, OplogEntry::ExportedFunctionInvokedV1 { .. }))
+            | Some((_, OplogEntry::ExportedFunctionInvoked { .. })) => break,
             _ => {}
         }
         if idx > OplogIndex::INITIAL {
@@ -1885,6 +2054,9 @@ struct PrivateDurableWorkerState<Ctx: WorkerCtx> {
     invocation_context: InvocationContext,
     current_span_id: SpanId,
     forward_trace_context_headers: bool,
+
+    /// The last host call of the current invocation cut short by its deadline, not yet taken by the worker
+    deadline_exceeded: Option<DeadlineExceeded>,
 }
 
 impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
@@ -1951,6 +2123,7 @@ impl<Ctx: WorkerCtx> PrivateDurableWorkerState<Ctx> {
             invocation_context,
             current_span_id,
             forward_trace_context_headers: true,
+            deadline_exceeded: None,
         }
     }
 
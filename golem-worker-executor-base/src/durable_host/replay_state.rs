@@ -242,29 +242,31 @@ impl ReplayState {
             if self.is_replay() {
                 let (_, oplog_entry) = self.get_oplog_entry().await;
                 match &oplog_entry {
+                    OplogEntry::ExportedFunctionInvokedV1 {
+                        function_name,
+                        idempotency_key,
+                        ..
+                    } => {
+                        let request = self.get_exported_function_request(&oplog_entry).await;
+                        break Ok(Some((
+                            function_name.to_string(),
+                            request,
+                            idempotency_key.clone(),
+                            InvocationContextStack::fresh(),
+                        )));
+                    }
                     OplogEntry::ExportedFunctionInvoked {
                         function_name,
                         idempotency_key,
+                        invocation_context,
                         ..
                     } => {
-                        let request: Vec<golem_wasm_rpc::protobuf::Val> = self
-                            .oplog
-                            .get_payload_of_entry(&oplog_entry)
-                            .await
-                            .expect("failed to deserialize function request payload")
-                            .unwrap();
-                        let request = request
-                            .into_iter()
-                            .map(|val| {
-                                val.try_into()
-                                    .expect("failed to decode serialized protobuf value")
-                            })
-                            .collect::<Vec<Value>>();
+                        let request = self.get_exported_function_request(&oplog_entry).await;
                         break Ok(Some((
                             function_name.to_string(),
                             request,
                             idempotency_key.clone(),
-                            InvocationContextStack::fresh(), // TODO: read from oplog
+                            invocation_context.clone(),
                         )));
                     }
                     entry if entry.is_hint() => {}
@@ -281,6 +283,22 @@ impl ReplayState {
         }
     }
 
+    async fn get_exported_function_request(&self, oplog_entry: &OplogEntry) -> Vec<Value> {
+        let request: Vec<golem_wasm_rpc::protobuf::Val> = self
+            .oplog
+            .get_payload_of_entry(oplog_entry)
+            .await
+            .expect("failed to deserialize function request payload")
+            .unwrap();
+        request
+            .into_iter()
+            .map(|val| {
+                val.try_into()
+                    .expect("failed to decode serialized protobuf value")
+            })
+            .collect()
+    }
+
     pub async fn get_oplog_entry_exported_function_completed(
         &mut self,
     ) -> Result<Option<TypeAnnotatedValue>, GolemError> {
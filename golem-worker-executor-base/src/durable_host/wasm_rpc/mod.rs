@@ -23,10 +23,10 @@ use crate::durable_host::wasm_rpc::serialized::{
 use crate::durable_host::{Durability, DurabilityHost, DurableWorkerCtx, OplogEntryVersion};
 use crate::error::GolemError;
 use crate::get_oplog_entry;
-use crate::model::PersistenceLevel;
+use crate::model::{DeadlineExceededCall, PersistenceLevel};
 use crate::services::component::ComponentService;
 use crate::services::oplog::{CommitLevel, OplogOps};
-use crate::services::rpc::{RpcDemand, RpcError};
+use crate::services::rpc::{await_until_deadline, RpcDemand, RpcError};
 use crate::workerctx::{InvocationManagement, WorkerCtx};
 use anyhow::anyhow;
 use async_trait::async_trait;
@@ -156,9 +156,9 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                 .state
                 .invocation_context
                 .get_stack(&self.state.current_span_id);
-            let result = self
-                .rpc()
-                .invoke_and_await(
+            let result = await_until_deadline(
+                self.state.invocation_context.deadline(),
+                self.rpc().invoke_and_await(
                     &remote_worker_id,
                     Some(idempotency_key),
                     function_name,
@@ -167,8 +167,9 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                     &args,
                     &env,
                     stack,
-                )
-                .await;
+                ),
+            )
+            .await;
             durability
                 .persist_serializable(self, input, result.clone().map_err(|err| (&err).into()))
                 .await?;
@@ -220,6 +221,9 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
             Ok(wit_value) => Ok(Ok(wit_value)),
             Err(err) => {
                 error!("RPC error: {err}");
+                if let RpcError::DeadlineExceeded { deadline } = &err {
+                    self.record_deadline_exceeded(*deadline, DeadlineExceededCall::Rpc);
+                }
                 Ok(Err(err.into()))
             }
         }
@@ -389,9 +393,11 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                 .state
                 .invocation_context
                 .get_stack(&self.state.current_span_id);
+            let deadline = self.state.invocation_context.deadline();
             let handle = wasmtime_wasi::runtime::spawn(async move {
-                Ok(rpc
-                    .invoke_and_await(
+                Ok(await_until_deadline(
+                    deadline,
+                    rpc.invoke_and_await(
                         &remote_worker_id,
                         Some(idempotency_key),
                         function_name,
@@ -400,8 +406,9 @@ impl<Ctx: WorkerCtx> HostWasmRpc for DurableWorkerCtx<Ctx> {
                         &args,
                         &env,
                         stack,
-                    )
-                    .await)
+                    ),
+                )
+                .await)
             });
 
             let fut = self.table().push(FutureInvokeResultEntry {
@@ -583,6 +590,11 @@ impl From<RpcError> for golem_wasm_rpc::RpcError {
             RpcError::RemoteInternalError { details } => {
                 golem_wasm_rpc::RpcError::RemoteInternalError(details)
             }
+            // golem:rpc has no case for it, the worker can tell it apart from other protocol
+            // errors with take-deadline-exceeded
+            error @ RpcError::DeadlineExceeded { .. } => {
+                golem_wasm_rpc::RpcError::ProtocolError(error.to_string())
+            }
         }
     }
 }
@@ -656,6 +668,7 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                 .state
                 .invocation_context
                 .get_stack(&self.state.current_span_id);
+            let deadline = self.state.invocation_context.deadline();
 
             let entry = self.table().get_mut(&this)?;
             let entry = entry
@@ -723,8 +736,9 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                         else {
                             return Err(anyhow!("unexpected incoming response state".to_string()));
                         };
-                        Ok(rpc
-                            .invoke_and_await(
+                        Ok(await_until_deadline(
+                            deadline,
+                            rpc.invoke_and_await(
                                 &remote_worker_id,
                                 Some(idempotency_key),
                                 function_name,
@@ -733,8 +747,9 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                                 &args,
                                 &env,
                                 stack,
-                            )
-                            .await)
+                            ),
+                        )
+                        .await)
                     });
                     let FutureInvokeResultState::Deferred {
                         remote_worker_id,
@@ -802,6 +817,13 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                 self.state.oplog.commit(CommitLevel::DurableOnly).await;
             }
 
+            if let SerializableInvokeResult::Completed(Err(RpcError::DeadlineExceeded {
+                deadline,
+            })) = &serializable_invoke_result
+            {
+                self.record_deadline_exceeded(*deadline, DeadlineExceededCall::Rpc);
+            }
+
             match result {
                 Ok(Some(Ok(tav))) => {
                     let wit_value = tav.try_into().map_err(|s: String| anyhow!(s))?;
@@ -849,7 +871,12 @@ impl<Ctx: WorkerCtx> HostFutureInvokeResult for DurableWorkerCtx<Ctx> {
                             let wit_value = tav.try_into().map_err(|s: String| anyhow!(s))?;
                             Ok(Some(Ok(wit_value)))
                         }
-                        Err(error) => Ok(Some(Err(error.into()))),
+                        Err(error) => {
+                            if let RpcError::DeadlineExceeded { deadline } = &error {
+                                self.record_deadline_exceeded(*deadline, DeadlineExceededCall::Rpc);
+                            }
+                            Ok(Some(Err(error.into())))
+                        }
                     },
                     SerializableInvokeResult::Failed(error) => Err(error.into()),
                 }
@@ -19,6 +19,7 @@ use std::fmt::{Display, Formatter};
 use bincode::{Decode, Encode};
 use golem_api_grpc::proto::golem;
 use golem_common::metrics::api::TraceErrorKind;
+use golem_common::model::oplog::OplogIndex;
 use golem_common::model::{ComponentId, PromiseId, ShardId, WorkerId};
 use golem_wasm_rpc::wasmtime::EncodingError;
 use tonic::Status;
@@ -105,6 +106,12 @@ pub enum GolemError {
         path: String,
         reason: String,
     },
+    WorkerExecutorDraining,
+    OplogTruncated {
+        worker_id: WorkerId,
+        oplog_index: OplogIndex,
+        truncated_until: OplogIndex,
+    },
 }
 
 impl GolemError {
@@ -165,6 +172,18 @@ impl GolemError {
         }
     }
 
+    pub fn oplog_truncated(
+        worker_id: WorkerId,
+        oplog_index: OplogIndex,
+        truncated_until: OplogIndex,
+    ) -> Self {
+        GolemError::OplogTruncated {
+            worker_id,
+            oplog_index,
+            truncated_until,
+        }
+    }
+
     pub fn unexpected_oplog_entry(expected: impl Into<String>, got: impl Into<String>) -> Self {
         GolemError::UnexpectedOplogEntry {
             expected: expected.into(),
@@ -286,6 +305,19 @@ impl Display for GolemError {
                     "Failed to access file in worker filesystem {path}: {reason}"
                 )
             }
+            GolemError::WorkerExecutorDraining => {
+                write!(f, "The worker executor is draining")
+            }
+            GolemError::OplogTruncated {
+                worker_id,
+                oplog_index,
+                truncated_until,
+            } => {
+                write!(
+                    f,
+                    "Oplog entry {oplog_index} of worker {worker_id} was dropped by compaction, the oplog is truncated until {truncated_until}"
+                )
+            }
         }
     }
 }
@@ -322,6 +354,8 @@ impl Error for GolemError {
             GolemError::Unknown { .. } => "Unknown error",
             GolemError::ShardingNotReady => "Sharding not ready",
             GolemError::FileSystemError { .. } => "File system error",
+            GolemError::WorkerExecutorDraining => "The worker executor is draining",
+            GolemError::OplogTruncated { .. } => "Oplog truncated",
         }
     }
 }
@@ -358,6 +392,8 @@ impl TraceErrorKind for GolemError {
             GolemError::Unknown { .. } => "Unknown",
             GolemError::ShardingNotReady => "ShardingNotReady",
             GolemError::FileSystemError { .. } => "FileSystemError",
+            GolemError::WorkerExecutorDraining => "WorkerExecutorDraining",
+            GolemError::OplogTruncated { .. } => "OplogTruncated",
         }
     }
 }
@@ -635,6 +671,28 @@ impl From<GolemError> for golem::worker::v1::WorkerExecutionError {
                     ),
                 ),
             },
+            GolemError::WorkerExecutorDraining => golem::worker::v1::WorkerExecutionError {
+                error: Some(
+                    golem::worker::v1::worker_execution_error::Error::WorkerExecutorDraining(
+                        golem::worker::v1::WorkerExecutorDraining {},
+                    ),
+                ),
+            },
+            GolemError::OplogTruncated {
+                worker_id,
+                oplog_index,
+                truncated_until,
+            } => golem::worker::v1::WorkerExecutionError {
+                error: Some(
+                    golem::worker::v1::worker_execution_error::Error::OplogTruncated(
+                        golem::worker::v1::OplogTruncated {
+                            worker_id: Some(worker_id.into()),
+                            oplog_index: oplog_index.into(),
+                            truncated_until: truncated_until.into(),
+                        },
+                    ),
+                ),
+            },
         }
     }
 }
@@ -815,6 +873,19 @@ impl TryFrom<golem::worker::v1::WorkerExecutionError> for GolemError {
                 path: file_system_error.path,
                 reason: file_system_error.reason,
             }),
+            Some(golem::worker::v1::worker_execution_error::Error::WorkerExecutorDraining(_)) => {
+                Ok(GolemError::WorkerExecutorDraining)
+            }
+            Some(golem::worker::v1::worker_execution_error::Error::OplogTruncated(
+                oplog_truncated,
+            )) => Ok(GolemError::OplogTruncated {
+                worker_id: oplog_truncated
+                    .worker_id
+                    .ok_or("Missing worker_id")?
+                    .try_into()?,
+                oplog_index: OplogIndex::from_u64(oplog_truncated.oplog_index),
+                truncated_until: OplogIndex::from_u64(oplog_truncated.truncated_until),
+            }),
         }
     }
 }
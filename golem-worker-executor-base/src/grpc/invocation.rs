@@ -18,6 +18,7 @@ use crate::services::HasComponentService;
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
 use crate::GolemTypes;
+use chrono::{DateTime, Utc};
 use golem_api_grpc::proto::golem::common::ResourceLimits as GrpcResourceLimits;
 use golem_common::base_model::{TargetWorkerId, WorkerId};
 use golem_common::model::invocation_context::InvocationContextStack;
@@ -28,6 +29,7 @@ use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use golem_wasm_rpc::protobuf::Val;
 use golem_wasm_rpc::Value;
 use rib::{ParsedFunctionName, ParsedFunctionSite};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tracing::warn;
 
@@ -37,6 +39,7 @@ pub trait CanStartWorker {
     fn worker_id(&self) -> Result<TargetWorkerId, GolemError>;
     fn args(&self) -> Option<Vec<String>>;
     fn env(&self) -> Option<Vec<(String, String)>>;
+    fn wasi_config_vars(&self) -> Option<BTreeMap<String, String>>;
     fn parent(&self) -> Option<WorkerId>;
 }
 
@@ -48,6 +51,11 @@ pub trait GrpcInvokeRequest: CanStartWorker {
     fn idempotency_key(&self) -> Result<Option<IdempotencyKey>, GolemError>;
     fn name(&self) -> String;
     fn invocation_context(&self) -> InvocationContextStack;
+
+    /// The time to schedule the invocation at, instead of enqueuing it immediately
+    fn invoke_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
 }
 
 trait ProtobufInvocationDetails {
@@ -93,6 +101,12 @@ impl<T: ProtobufInvocationDetails> CanStartWorker for T {
             .map(|ctx| ctx.env.clone().into_iter().collect::<Vec<_>>())
     }
 
+    fn wasi_config_vars(&self) -> Option<BTreeMap<String, String>> {
+        self.proto_invocation_context()
+            .as_ref()
+            .map(|ctx| ctx.wasi_config_vars.clone().into_iter().collect())
+    }
+
     fn parent(&self) -> Option<WorkerId> {
         self.proto_invocation_context().as_ref().and_then(|ctx| {
             ctx.parent
@@ -241,6 +255,10 @@ impl GrpcInvokeRequest for golem_api_grpc::proto::golem::workerexecutor::v1::Inv
     fn invocation_context(&self) -> InvocationContextStack {
         from_proto_invocation_context(&self.context)
     }
+
+    fn invoke_at(&self) -> Option<DateTime<Utc>> {
+        from_proto_timestamp(&self.invoke_at)
+    }
 }
 
 impl ProtobufInvocationDetails
@@ -334,6 +352,16 @@ impl GrpcInvokeRequest
     fn invocation_context(&self) -> InvocationContextStack {
         from_proto_invocation_context(&self.context)
     }
+
+    fn invoke_at(&self) -> Option<DateTime<Utc>> {
+        from_proto_timestamp(&self.invoke_at)
+    }
+}
+
+fn from_proto_timestamp(timestamp: &Option<prost_types::Timestamp>) -> Option<DateTime<Utc>> {
+    timestamp
+        .as_ref()
+        .and_then(|ts| DateTime::from_timestamp(ts.seconds, ts.nanos as u32))
 }
 
 /// Assumes what component version a worker will execute the next enqueued invocation with
@@ -15,6 +15,7 @@
 mod invocation;
 
 use crate::error::*;
+use chrono::Utc;
 use futures_util::Stream;
 use futures_util::StreamExt;
 use gethostname::gethostname;
@@ -25,13 +26,16 @@ use golem_api_grpc::proto::golem::workerexecutor::v1::{
     ActivatePluginRequest, ActivatePluginResponse, CancelInvocationRequest,
     CancelInvocationResponse, ConnectWorkerRequest, DeactivatePluginRequest,
     DeactivatePluginResponse, DeleteWorkerRequest, ForkWorkerRequest, ForkWorkerResponse,
-    GetFileContentsRequest, GetFileContentsResponse, GetOplogRequest, GetOplogResponse,
-    GetRunningWorkersMetadataRequest, GetRunningWorkersMetadataResponse, GetWorkersMetadataRequest,
-    GetWorkersMetadataResponse, InvokeAndAwaitWorkerJsonRequest, InvokeAndAwaitWorkerRequest,
+    GetFileContentsRequest, GetFileContentsResponse, GetInvocationLogsRequest,
+    GetInvocationLogsResponse, GetInvocationLogsSuccess, GetInvocationTreeRequest,
+    GetInvocationTreeResponse, GetOplogRequest, GetOplogResponse, GetRunningWorkersMetadataRequest,
+    GetRunningWorkersMetadataResponse, GetWorkerStatusSummaryRequest,
+    GetWorkerStatusSummaryResponse, GetWorkersMetadataRequest, GetWorkersMetadataResponse,
+    InvokeAndAwaitWorkerJsonRequest, InvokeAndAwaitWorkerRequest,
     InvokeAndAwaitWorkerResponseTyped, InvokeAndAwaitWorkerSuccess, InvokeJsonWorkerRequest,
     InvokeWorkerResponse, ListDirectoryRequest, ListDirectoryResponse, RevertWorkerRequest,
-    RevertWorkerResponse, SearchOplogRequest, SearchOplogResponse, UpdateWorkerRequest,
-    UpdateWorkerResponse,
+    RevertWorkerResponse, SearchOplogRequest, SearchOplogResponse, UpdateWorkerLabelsRequest,
+    UpdateWorkerLabelsResponse, UpdateWorkerRequest, UpdateWorkerResponse,
 };
 use golem_common::grpc::{
     proto_account_id_string, proto_component_id_string, proto_idempotency_key_string,
@@ -39,11 +43,14 @@ use golem_common::grpc::{
     proto_worker_id_string,
 };
 use golem_common::metrics::api::record_new_grpc_api_active_stream;
+use golem_common::model::invocation_context::{InvocationContextStack, TraceId};
+use golem_common::model::invocation_tree::InvocationTree;
 use golem_common::model::oplog::{OplogIndex, UpdateDescription};
+use golem_common::model::public_oplog::{LogParameters, PublicOplogEntryFilter};
 use golem_common::model::{
     AccountId, ComponentFilePath, ComponentId, ComponentType, IdempotencyKey, OwnedWorkerId,
-    PluginInstallationId, ScanCursor, ShardId, TimestampedWorkerInvocation, WorkerEvent,
-    WorkerFilter, WorkerId, WorkerInvocation, WorkerMetadata, WorkerStatus,
+    PluginInstallationId, ScanCursor, ScheduledAction, ShardId, TimestampedWorkerInvocation,
+    WorkerEvent, WorkerFilter, WorkerId, WorkerInvocation, WorkerMetadata, WorkerStatus,
 };
 use golem_common::{model as common_model, recorded_grpc_api_request};
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
@@ -73,9 +80,12 @@ use crate::services::worker_activator::{DefaultWorkerActivator, LazyWorkerActiva
 use crate::services::worker_event::WorkerEventReceiver;
 use crate::services::{
     All, HasActiveWorkers, HasAll, HasComponentService, HasEvents, HasOplogService, HasPlugins,
-    HasPromiseService, HasRunningWorkerEnumerationService, HasShardManagerService, HasShardService,
-    HasWorkerEnumerationService, HasWorkerService, UsesAllDeps,
+    HasPromiseService, HasRunningWorkerEnumerationService, HasSchedulerService,
+    HasShardManagerService, HasShardService, HasWorkerEnumerationService, HasWorkerService,
+    UsesAllDeps,
 };
+use crate::worker::lifecycle::LifecycleHook;
+use crate::worker::trace::get_traced_invocations;
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
 use tokio;
@@ -145,6 +155,9 @@ pub struct WorkerExecutorImpl<
 > {
     /// Reference to all the initialized services
     services: Svcs,
+    /// The host and port the executor registered with the shard manager
+    host: String,
+    port: u16,
     ctx: PhantomData<Ctx>,
 }
 
@@ -154,12 +167,17 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
     fn clone(&self) -> Self {
         Self {
             services: self.services.clone(),
+            host: self.host.clone(),
+            port: self.port,
             ctx: PhantomData,
         }
     }
 }
 
 type ResponseResult<T> = Result<Response<T>, Status>;
+
+const DEFAULT_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+const DRAIN_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
 type ResponseStream = WorkerEventStream;
 
 impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync + 'static>
@@ -170,16 +188,18 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         lazy_worker_activator: Arc<LazyWorkerActivator<Ctx>>,
         port: u16,
     ) -> Result<Self, Error> {
+        let host = gethostname().to_string_lossy().to_string();
+
         let worker_executor = WorkerExecutorImpl {
             services: services.clone(),
+            host: host.clone(),
+            port,
             ctx: PhantomData,
         };
         let worker_activator = Arc::new(DefaultWorkerActivator::new(services.clone()));
 
         lazy_worker_activator.set(worker_activator);
 
-        let host = gethostname().to_string_lossy().to_string();
-
         info!(host, port, "Registering worker executor");
 
         let shard_assignment = worker_executor
@@ -277,17 +297,25 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             .iter()
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
+        let wasi_config_vars = request.wasi_config_vars.into_iter().collect();
 
         let worker = Worker::get_or_create_suspended(
             self,
             &owned_worker_id,
             Some(args),
             Some(env),
+            Some(wasi_config_vars),
             Some(component_version),
             None,
         )
         .await?;
 
+        if !request.labels.is_empty() {
+            worker
+                .set_labels(request.labels.into_iter().collect())
+                .await?;
+        }
+
         let mut subscription = self.events().subscribe();
         Worker::start_if_needed(worker.clone()).await?;
         if worker.is_loading() {
@@ -353,8 +381,16 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             // By making sure the worker is in memory. If it was suspended because of waiting
             // for a promise, replaying that call will now not suspend as the promise has been
             // completed, and the worker will continue running.
-            Worker::get_or_create_running(&self.services, &owned_worker_id, None, None, None, None)
-                .await?;
+            Worker::get_or_create_running(
+                &self.services,
+                &owned_worker_id,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?;
         }
 
         let success = golem::workerexecutor::v1::CompletePromiseSuccess { completed };
@@ -378,10 +414,27 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                 WorkerStatus::Exited | WorkerStatus::Failed | WorkerStatus::Interrupted => false,
             };
 
+            let accepts_invocations = matches!(
+                &metadata.last_known_status.status,
+                WorkerStatus::Idle | WorkerStatus::Running | WorkerStatus::Suspended
+            );
+
+            if accepts_invocations {
+                self.invoke_on_delete_hook(&owned_worker_id, &metadata)
+                    .await?;
+            }
+
             if should_interrupt {
-                let worker =
-                    Worker::get_or_create_suspended(self, &owned_worker_id, None, None, None, None)
-                        .await?;
+                let worker = Worker::get_or_create_suspended(
+                    self,
+                    &owned_worker_id,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
 
                 if let Some(mut await_interrupted) =
                     worker.set_interrupting(InterruptKind::Interrupt).await
@@ -400,6 +453,52 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         Ok(())
     }
 
+    // Gives the worker a chance to clean up before it gets deleted. A failing hook is only
+    // logged, as it must not prevent the deletion
+    async fn invoke_on_delete_hook(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        metadata: &WorkerMetadata,
+    ) -> Result<(), GolemError> {
+        let component_version = metadata.last_known_status.component_version;
+        let component_metadata = self
+            .component_service()
+            .get_metadata(
+                &owned_worker_id.account_id,
+                &owned_worker_id.worker_id.component_id,
+                Some(component_version),
+            )
+            .await?;
+
+        let hook = LifecycleHook::Delete;
+        if let Some(full_function_name) = hook.find(&component_metadata.exports) {
+            debug!("Invoking the on-delete lifecycle hook");
+            let worker = Worker::get_or_create_running(
+                &self.services,
+                owned_worker_id,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?;
+            if let Err(err) = worker
+                .invoke_and_await(
+                    hook.idempotency_key(&owned_worker_id.worker_id, component_version),
+                    full_function_name,
+                    hook.function_input(),
+                    InvocationContextStack::fresh(),
+                )
+                .await
+            {
+                warn!("The on-delete lifecycle hook failed: {err}");
+            }
+        }
+
+        Ok(())
+    }
+
     async fn fork_worker_internal(
         &self,
         request: ForkWorkerRequest,
@@ -465,9 +564,16 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
 
         match metadata {
             Some(_) => {
-                let worker =
-                    Worker::get_or_create_suspended(self, &owned_worker_id, None, None, None, None)
-                        .await?;
+                let worker = Worker::get_or_create_suspended(
+                    self,
+                    &owned_worker_id,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
                 worker.revert(target).await?;
                 Ok(())
             }
@@ -475,6 +581,30 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn get_invocation_tree_internal(
+        &self,
+        request: GetInvocationTreeRequest,
+    ) -> Result<golem::workerexecutor::v1::InvocationTree, GolemError> {
+        let trace_id =
+            TraceId::from_string(&request.trace_id).map_err(GolemError::invalid_request)?;
+        let account_id: AccountId = request
+            .account_id
+            .ok_or(GolemError::invalid_request("account_id not found"))?
+            .into();
+
+        let mut invocations = Vec::new();
+        for owned_worker_id in self.worker_service().get_trace_workers(&trace_id).await {
+            // The trace may span the workers of other accounts, which are not visible
+            if owned_worker_id.account_id == account_id {
+                invocations.extend(
+                    get_traced_invocations(self.oplog_service(), &owned_worker_id, &trace_id).await,
+                );
+            }
+        }
+
+        Ok(InvocationTree::new(&trace_id, invocations).into())
+    }
+
     async fn cancel_invocation_internal(
         &self,
         request: CancelInvocationRequest,
@@ -507,6 +637,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                         None,
                         None,
                         None,
+                        None,
                     )
                     .await?;
                     worker.cancel_invocation(idempotency_key).await?;
@@ -525,6 +656,33 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn get_invocation_logs_internal(
+        &self,
+        request: GetInvocationLogsRequest,
+    ) -> Result<GetInvocationLogsSuccess, GolemError> {
+        let owned_worker_id =
+            extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.account_id)?;
+
+        let idempotency_key = request
+            .idempotency_key
+            .ok_or(GolemError::invalid_request("idempotency_key not found"))?
+            .into();
+
+        if self.worker_service().get(&owned_worker_id).await.is_none() {
+            return Err(GolemError::worker_not_found(owned_worker_id.worker_id()));
+        }
+
+        let entries = self
+            .worker_service()
+            .get_invocation_logs(&owned_worker_id, &idempotency_key)
+            .await
+            .into_iter()
+            .map(|entry| LogParameters::from(entry).into())
+            .collect();
+
+        Ok(GetInvocationLogsSuccess { entries })
+    }
+
     async fn interrupt_worker_internal(
         &self,
         request: golem::workerexecutor::v1::InterruptWorkerRequest,
@@ -574,6 +732,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                         None,
                         None,
                         None,
+                        None,
                     )
                     .await?;
                     worker.set_interrupting(InterruptKind::Interrupt).await;
@@ -589,6 +748,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                         None,
                         None,
                         None,
+                        None,
                     )
                     .await?;
                     worker.set_interrupting(InterruptKind::Interrupt).await;
@@ -603,6 +763,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                         None,
                         None,
                         None,
+                        None,
                     )
                     .await?;
                     worker
@@ -651,6 +812,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await?;
                 Ok(())
@@ -667,6 +829,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                     None,
                     None,
                     None,
+                    None,
                 )
                 .await?;
                 Ok(())
@@ -786,6 +949,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             &owned_worker_id,
             request.args(),
             request.env(),
+            request.wasi_config_vars(),
             None,
             request.parent(),
         )
@@ -798,6 +962,19 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
     ) -> Result<(), GolemError> {
         let full_function_name = request.name();
 
+        // Invocations due already are enqueued immediately
+        let invoke_at = request
+            .invoke_at()
+            .filter(|invoke_at| *invoke_at > Utc::now());
+
+        // The name of ephemeral workers is generated for each invocation, so there would be no
+        // worker to invoke later
+        if invoke_at.is_some() && request.worker_id()?.worker_name.is_none() {
+            return Err(GolemError::invalid_request(
+                "Scheduling an invocation with invoke_at requires a worker name",
+            ));
+        }
+
         let worker = self.get_or_create(request).await?;
 
         let idempotency_key = request
@@ -812,14 +989,34 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             .collect::<Result<Vec<_>, _>>()
             .map_err(|msg| GolemError::ValueMismatch { details: msg })?;
 
-        worker
-            .invoke(
-                idempotency_key,
-                full_function_name,
-                function_input,
-                request.invocation_context(),
-            )
-            .await?;
+        match invoke_at {
+            Some(invoke_at) => {
+                // The scheduler persists the action, and only enqueues the invocation in the
+                // worker's oplog once it is due
+                self.scheduler_service()
+                    .schedule(
+                        invoke_at,
+                        ScheduledAction::Invoke {
+                            owned_worker_id: worker.owned_worker_id().clone(),
+                            idempotency_key,
+                            full_function_name,
+                            function_input,
+                            invocation_context: request.invocation_context(),
+                        },
+                    )
+                    .await;
+            }
+            None => {
+                worker
+                    .invoke(
+                        idempotency_key,
+                        full_function_name,
+                        function_input,
+                        request.invocation_context(),
+                    )
+                    .await?;
+            }
+        }
 
         Ok(())
     }
@@ -848,6 +1045,67 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         Ok(())
     }
 
+    // Stops accepting invocations by giving up all the shards, waits for the in-flight invocations
+    // to complete until the timeout, and interrupts the remaining ones, which continue from their
+    // oplog where the shards get assigned next. The shard manager only reassigns the shards once
+    // no worker of them runs here anymore.
+    async fn drain_internal(
+        &self,
+        request: golem::workerexecutor::v1::DrainRequest,
+    ) -> Result<golem::workerexecutor::v1::DrainSuccess, GolemError> {
+        let timeout = request
+            .timeout_millis
+            .map(std::time::Duration::from_millis)
+            .unwrap_or(DEFAULT_DRAIN_TIMEOUT);
+
+        let released_shard_ids = self.shard_service().drain()?;
+        info!(
+            shards = released_shard_ids.len(),
+            "Draining worker executor, waiting for in-flight invocations"
+        );
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let mut busy = 0;
+            for (_, worker) in self.active_workers().iter() {
+                if worker.is_busy().await {
+                    busy += 1;
+                }
+            }
+
+            if busy == 0 || tokio::time::Instant::now() >= deadline {
+                break;
+            }
+            debug!(busy, "Waiting for in-flight invocations to complete");
+            tokio::time::sleep(DRAIN_POLL_INTERVAL.min(timeout)).await;
+        }
+
+        let mut interrupted_workers = 0;
+        for (worker_id, worker) in self.active_workers().iter() {
+            if worker.is_busy().await {
+                debug!("Interrupting {worker_id}, its invocation continues after the drain");
+                if let Some(mut await_interrupted) =
+                    worker.set_interrupting(InterruptKind::Suspend).await
+                {
+                    let _ = await_interrupted.recv().await;
+                }
+                interrupted_workers += 1;
+            }
+            worker.stop().await;
+        }
+
+        self.shard_manager_service()
+            .unregister(self.host.clone(), self.port)
+            .await?;
+
+        info!(interrupted_workers, "Drained worker executor");
+
+        Ok(golem::workerexecutor::v1::DrainSuccess {
+            released_shard_ids: released_shard_ids.into_iter().map(|id| id.into()).collect(),
+            interrupted_workers,
+        })
+    }
+
     async fn assign_shards_internal(
         &self,
         request: golem::workerexecutor::v1::AssignShardsRequest,
@@ -974,6 +1232,28 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         ))
     }
 
+    async fn get_worker_status_summary_internal(
+        &self,
+        request: GetWorkerStatusSummaryRequest,
+    ) -> Result<golem::workerexecutor::v1::WorkerStatusSummary, GolemError> {
+        let component_id: ComponentId = request
+            .component_id
+            .and_then(|t| t.try_into().ok())
+            .ok_or(GolemError::invalid_request("Invalid component id"))?;
+
+        let account_id: AccountId = request
+            .account_id
+            .map(|t| t.into())
+            .ok_or(GolemError::invalid_request("Invalid account id"))?;
+
+        let summary = self
+            .worker_service()
+            .get_status_summary(&account_id, &component_id, request.backfill)
+            .await?;
+
+        Ok(summary.into())
+    }
+
     async fn update_worker_internal(&self, request: UpdateWorkerRequest) -> Result<(), GolemError> {
         let owned_worker_id =
             extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.account_id)?;
@@ -1041,6 +1321,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                             &owned_worker_id,
                             None,
                             None,
+                            None,
                             Some(metadata.last_known_status.component_version),
                             None,
                         )
@@ -1078,6 +1359,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                             None,
                             None,
                             None,
+                            None,
                         )
                         .await?;
 
@@ -1105,9 +1387,16 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                 // This is in a race condition with other worker invocations, so the whole update
                 // process need to be initiated through the worker's invocation queue.
 
-                let worker =
-                    Worker::get_or_create_suspended(self, &owned_worker_id, None, None, None, None)
-                        .await?;
+                let worker = Worker::get_or_create_suspended(
+                    self,
+                    &owned_worker_id,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
                 worker.enqueue_manual_update(request.target_version).await;
             }
         }
@@ -1138,10 +1427,17 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         self.ensure_not_failed(&owned_worker_id, &metadata).await?;
 
         if metadata.last_known_status.status != WorkerStatus::Interrupted {
-            let event_service =
-                Worker::get_or_create_suspended(self, &owned_worker_id, None, None, None, None)
-                    .await?
-                    .event_service();
+            let event_service = Worker::get_or_create_suspended(
+                self,
+                &owned_worker_id,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await?
+            .event_service();
 
             let receiver = event_service.receiver();
 
@@ -1166,6 +1462,11 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.account_id)?;
         self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
 
+        // The history dropped from the oplog by compaction is skipped
+        let truncated_until = Worker::<Ctx>::get_latest_metadata(&self.services, &owned_worker_id)
+            .await?
+            .and_then(|metadata| metadata.last_known_status.compaction().truncated_until);
+
         let chunk = match request.cursor {
             Some(cursor) => {
                 get_public_oplog_chunk(
@@ -1176,15 +1477,20 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                     cursor.current_component_version,
                     OplogIndex::from_u64(cursor.next_oplog_index),
                     min(request.count as usize, 100), // TODO: configurable maximum
+                    truncated_until,
                 )
                 .await
                 .map_err(GolemError::unknown)?
             }
             None => {
                 let start = OplogIndex::from_u64(request.from_oplog_index);
-                let initial_component_version =
-                    find_component_version_at(self.oplog_service(), &owned_worker_id, start)
-                        .await?;
+                let initial_component_version = find_component_version_at(
+                    self.oplog_service(),
+                    &owned_worker_id,
+                    start,
+                    truncated_until,
+                )
+                .await?;
 
                 get_public_oplog_chunk(
                     self.component_service(),
@@ -1194,6 +1500,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                     initial_component_version,
                     start,
                     min(request.count as usize, 100), // TODO: configurable maximum
+                    truncated_until,
                 )
                 .await
                 .map_err(GolemError::unknown)?
@@ -1236,6 +1543,13 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.account_id)?;
         self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
 
+        // The history dropped from the oplog by compaction is skipped
+        let truncated_until = Worker::<Ctx>::get_latest_metadata(&self.services, &owned_worker_id)
+            .await?
+            .and_then(|metadata| metadata.last_known_status.compaction().truncated_until);
+
+        let filter: PublicOplogEntryFilter = request.filter.map(|f| f.into()).unwrap_or_default();
+
         let chunk = match request.cursor {
             Some(cursor) => {
                 search_public_oplog(
@@ -1247,15 +1561,21 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                     OplogIndex::from_u64(cursor.next_oplog_index),
                     min(request.count as usize, 100), // TODO: configurable maximum,
                     &request.query,
+                    &filter,
+                    truncated_until,
                 )
                 .await
                 .map_err(GolemError::unknown)?
             }
             None => {
                 let start = OplogIndex::INITIAL;
-                let initial_component_version =
-                    find_component_version_at(self.oplog_service(), &owned_worker_id, start)
-                        .await?;
+                let initial_component_version = find_component_version_at(
+                    self.oplog_service(),
+                    &owned_worker_id,
+                    start,
+                    truncated_until,
+                )
+                .await?;
                 search_public_oplog(
                     self.component_service(),
                     self.oplog_service(),
@@ -1265,6 +1585,8 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                     start,
                     min(request.count as usize, 100), // TODO: configurable maximum,
                     &request.query,
+                    &filter,
+                    truncated_until,
                 )
                 .await
                 .map_err(GolemError::unknown)?
@@ -1473,6 +1795,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                             None,
                             None,
                             None,
+                            None,
                         )
                         .await?;
                         worker.activate_plugin(plugin_installation_id).await?;
@@ -1488,6 +1811,27 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn update_worker_labels_internal(
+        &self,
+        request: UpdateWorkerLabelsRequest,
+    ) -> Result<(), GolemError> {
+        let owned_worker_id =
+            extract_owned_worker_id(&request, |r| &r.worker_id, |r| &r.account_id)?;
+        self.ensure_worker_belongs_to_this_executor(&owned_worker_id)?;
+
+        let metadata = self.worker_service().get(&owned_worker_id).await;
+        if metadata.is_none() {
+            return Err(GolemError::worker_not_found(owned_worker_id.worker_id()));
+        }
+
+        let worker =
+            Worker::get_or_create_suspended(self, &owned_worker_id, None, None, None, None, None)
+                .await?;
+        worker
+            .set_labels(request.labels.into_iter().collect())
+            .await
+    }
+
     async fn deactivate_plugin_internal(
         &self,
         request: DeactivatePluginRequest,
@@ -1539,6 +1883,7 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
                             None,
                             None,
                             None,
+                            None,
                         )
                         .await?;
                         worker.deactivate_plugin(plugin_installation_id).await?;
@@ -1627,6 +1972,8 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             worker_id: Some(metadata.worker_id.into()),
             args: metadata.args.clone(),
             env: HashMap::from_iter(metadata.env.iter().cloned()),
+            wasi_config_vars: HashMap::from_iter(metadata.wasi_config_vars.clone()),
+            labels: HashMap::from_iter(latest_status.labels().clone()),
             account_id: Some(metadata.account_id.into()),
             component_version: latest_status.component_version,
             status: Into::<golem::worker::WorkerStatus>::into(latest_status.status.clone()).into(),
@@ -1644,6 +1991,14 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             total_linear_memory_size: metadata.last_known_status.total_linear_memory_size,
             owned_resources,
             active_plugins: active_plugins.into_iter().map(|id| id.into()).collect(),
+            compaction: Some(
+                latest_status
+                    .compaction()
+                    .to_protobuf(latest_status.oplog_idx),
+            ),
+            fork: latest_status
+                .fork()
+                .map(|fork| fork.to_protobuf(latest_status.oplog_idx)),
         }
     }
 }
@@ -2083,6 +2438,36 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn drain(
+        &self,
+        request: Request<golem::workerexecutor::v1::DrainRequest>,
+    ) -> Result<Response<golem::workerexecutor::v1::DrainResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!("drain",);
+
+        match self
+            .drain_internal(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(success) => record.succeed(Ok(Response::new(
+                golem::workerexecutor::v1::DrainResponse {
+                    result: Some(golem::workerexecutor::v1::drain_response::Result::Success(
+                        success,
+                    )),
+                },
+            ))),
+            Err(err) => record.fail(
+                Ok(Response::new(golem::workerexecutor::v1::DrainResponse {
+                    result: Some(golem::workerexecutor::v1::drain_response::Result::Failure(
+                        err.clone().into(),
+                    )),
+                })),
+                &err,
+            ),
+        }
+    }
+
     async fn assign_shards(
         &self,
         request: Request<golem::workerexecutor::v1::AssignShardsRequest>,
@@ -2288,6 +2673,41 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn get_worker_status_summary(
+        &self,
+        request: Request<GetWorkerStatusSummaryRequest>,
+    ) -> Result<Response<GetWorkerStatusSummaryResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_worker_status_summary",
+            component_id = proto_component_id_string(&request.component_id),
+        );
+
+        let result = self
+            .get_worker_status_summary_internal(request)
+            .instrument(record.span.clone())
+            .await;
+        match result {
+            Ok(summary) => record.succeed(Ok(Response::new(GetWorkerStatusSummaryResponse {
+                result: Some(
+                    golem::workerexecutor::v1::get_worker_status_summary_response::Result::Success(
+                        summary,
+                    ),
+                ),
+            }))),
+            Err(err) => record.fail(
+                Ok(Response::new(GetWorkerStatusSummaryResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::get_worker_status_summary_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &err,
+            ),
+        }
+    }
+
     async fn update_worker(
         &self,
         request: Request<UpdateWorkerRequest>,
@@ -2495,6 +2915,78 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
         }
     }
 
+    async fn get_invocation_tree(
+        &self,
+        request: Request<GetInvocationTreeRequest>,
+    ) -> Result<Response<GetInvocationTreeResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_invocation_tree",
+            trace_id = request.trace_id.clone(),
+            account_id = proto_account_id_string(&request.account_id),
+        );
+
+        let result = self
+            .get_invocation_tree_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(tree) => record.succeed(Ok(Response::new(GetInvocationTreeResponse {
+                result: Some(
+                    golem::workerexecutor::v1::get_invocation_tree_response::Result::Success(tree),
+                ),
+            }))),
+            Err(err) => record.fail(
+                Ok(Response::new(GetInvocationTreeResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::get_invocation_tree_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &err,
+            ),
+        }
+    }
+
+    async fn get_invocation_logs(
+        &self,
+        request: Request<GetInvocationLogsRequest>,
+    ) -> Result<Response<GetInvocationLogsResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_invocation_logs",
+            worker_id = proto_worker_id_string(&request.worker_id),
+            idempotency_key = proto_idempotency_key_string(&request.idempotency_key),
+        );
+
+        let result = self
+            .get_invocation_logs_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(success) => record.succeed(Ok(Response::new(GetInvocationLogsResponse {
+                result: Some(
+                    golem::workerexecutor::v1::get_invocation_logs_response::Result::Success(
+                        success,
+                    ),
+                ),
+            }))),
+            Err(err) => record.fail(
+                Ok(Response::new(GetInvocationLogsResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::get_invocation_logs_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &err,
+            ),
+        }
+    }
+
     async fn list_directory(
         &self,
         request: Request<ListDirectoryRequest>,
@@ -2637,6 +3129,42 @@ impl<Ctx: WorkerCtx, Svcs: HasAll<Ctx> + UsesAllDeps<Ctx = Ctx> + Send + Sync +
             ),
         }
     }
+
+    async fn update_worker_labels(
+        &self,
+        request: Request<UpdateWorkerLabelsRequest>,
+    ) -> Result<Response<UpdateWorkerLabelsResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "update_worker_labels",
+            worker_id = proto_worker_id_string(&request.worker_id)
+        );
+
+        let result = self
+            .update_worker_labels_internal(request)
+            .instrument(record.span.clone())
+            .await;
+
+        match result {
+            Ok(_) => record.succeed(Ok(Response::new(UpdateWorkerLabelsResponse {
+                result: Some(
+                    golem::workerexecutor::v1::update_worker_labels_response::Result::Success(
+                        golem::common::Empty {},
+                    ),
+                ),
+            }))),
+            Err(err) => record.fail(
+                Ok(Response::new(UpdateWorkerLabelsResponse {
+                    result: Some(
+                        golem::workerexecutor::v1::update_worker_labels_response::Result::Failure(
+                            err.clone().into(),
+                        ),
+                    ),
+                })),
+                &err,
+            ),
+        }
+    }
 }
 
 pub fn authorised_grpc_request<T>(request: T, access_token: &Uuid) -> Request<T> {
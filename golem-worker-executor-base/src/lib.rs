@@ -55,17 +55,21 @@ use crate::services::worker_enumeration::{
 };
 use crate::services::worker_proxy::{RemoteWorkerProxy, WorkerProxy};
 use crate::services::{shard_manager, All, HasConfig};
+use crate::storage::indexed::postgres::PostgresIndexedStorage;
 use crate::storage::indexed::redis::RedisIndexedStorage;
 use crate::storage::indexed::sqlite::SqliteIndexedStorage;
 use crate::storage::indexed::IndexedStorage;
 use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+use crate::storage::keyvalue::postgres::PostgresKeyValueStorage;
 use crate::storage::keyvalue::redis::RedisKeyValueStorage;
 use crate::storage::keyvalue::KeyValueStorage;
+use crate::worker::host_interfaces::supported_host_interfaces;
 use crate::workerctx::WorkerCtx;
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use golem_api_grpc::proto;
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_server::WorkerExecutorServer;
+use golem_common::config::DbPostgresConfig;
 use golem_common::golem_version;
 use golem_common::model::component::{ComponentOwner, DefaultComponentOwner};
 use golem_common::model::plugin::{
@@ -73,12 +77,17 @@ use golem_common::model::plugin::{
 };
 use golem_common::redis::RedisPool;
 use golem_service_base::config::BlobStorageConfig;
+use golem_service_base::db;
+use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
+use golem_service_base::storage::blob::azure::AzureBlobStorage;
 use golem_service_base::storage::blob::s3::S3BlobStorage;
 use golem_service_base::storage::blob::sqlite::SqliteBlobStorage;
 use golem_service_base::storage::blob::BlobStorage;
+use golem_service_base::storage::postgres::PostgresPool;
 use golem_service_base::storage::sqlite::SqlitePool;
 use humansize::{ISizeFormatter, BINARY};
+use include_dir::{include_dir, Dir};
 use nonempty_collections::NEVec;
 use prometheus::Registry;
 use services::file_loader::FileLoader;
@@ -97,6 +106,13 @@ use wasmtime::{Config, Engine, WasmBacktraceDetails};
 
 const VERSION: &str = golem_version!();
 
+static DB_MIGRATIONS: Dir = include_dir!("$CARGO_MANIFEST_DIR/db/migration");
+
+/// The migrations of the tables used by the Postgres key-value and indexed storages
+pub fn db_migrations() -> Dir<'static> {
+    DB_MIGRATIONS.clone()
+}
+
 pub struct RunDetails {
     pub http_port: u16,
     pub grpc_port: u16,
@@ -242,6 +258,7 @@ pub trait Bootstrap<Ctx: WorkerCtx> {
             ISizeFormatter::new(system_memory, BINARY),
             ISizeFormatter::new(worker_memory, BINARY)
         );
+        info!("Supported host interfaces: {}", supported_host_interfaces());
 
         let addr = golem_config.grpc_addr()?;
 
@@ -280,9 +297,10 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
     runtime: Handle,
     lazy_worker_activator: &Arc<LazyWorkerActivator<Ctx>>,
 ) -> Result<(All<Ctx>, std::thread::JoinHandle<()>), anyhow::Error> {
-    let (redis, sqlite, key_value_storage): (
+    let (redis, sqlite, postgres, key_value_storage): (
         Option<RedisPool>,
         Option<SqlitePool>,
+        Option<PostgresPool>,
         Arc<dyn KeyValueStorage + Send + Sync>,
     ) = match &golem_config.key_value_storage {
         KeyValueStorageConfig::Redis(redis) => {
@@ -292,11 +310,11 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
                 .map_err(|err| anyhow!(err))?;
             let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
                 Arc::new(RedisKeyValueStorage::new(pool.clone()));
-            (Some(pool), None, key_value_storage)
+            (Some(pool), None, None, key_value_storage)
         }
         KeyValueStorageConfig::InMemory => {
             info!("Using in-memory key-value storage");
-            (None, None, Arc::new(InMemoryKeyValueStorage::new()))
+            (None, None, None, Arc::new(InMemoryKeyValueStorage::new()))
         }
         KeyValueStorageConfig::Sqlite(sqlite) => {
             info!("Using Sqlite for key-value storage at {}", sqlite.database);
@@ -308,7 +326,17 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
                     .await
                     .map_err(|err| anyhow!(err))?,
             );
-            (None, Some(pool), key_value_storage)
+            (None, Some(pool), None, key_value_storage)
+        }
+        KeyValueStorageConfig::Postgres(postgres) => {
+            info!(
+                "Using Postgres for key-value storage at {}:{}/{}",
+                postgres.host, postgres.port, postgres.database
+            );
+            let pool = migrated_postgres_pool(postgres).await?;
+            let key_value_storage: Arc<dyn KeyValueStorage + Send + Sync> =
+                Arc::new(PostgresKeyValueStorage::new(pool.clone()));
+            (None, None, Some(pool), key_value_storage)
         }
     };
 
@@ -347,6 +375,21 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
                     .map_err(|err| anyhow!(err))?,
             )
         }
+        IndexedStorageConfig::KVStorePostgres => {
+            info!("Using the same Postgres for indexed-storage");
+            let postgres = postgres.expect(
+                "Postgres must be configured as key-value storage when using KVStorePostgres",
+            );
+            Arc::new(PostgresIndexedStorage::new(postgres))
+        }
+        IndexedStorageConfig::Postgres(postgres) => {
+            info!(
+                "Using Postgres for indexed storage at {}:{}/{}",
+                postgres.host, postgres.port, postgres.database
+            );
+            let pool = migrated_postgres_pool(postgres).await?;
+            Arc::new(PostgresIndexedStorage::new(pool))
+        }
         IndexedStorageConfig::InMemory => {
             info!("Using in-memory indexed storage");
             Arc::new(storage::indexed::memory::InMemoryIndexedStorage::new())
@@ -357,6 +400,10 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
             info!("Using S3 for blob storage");
             Arc::new(S3BlobStorage::new(config.clone()).await)
         }
+        BlobStorageConfig::AzureBlob(config) => {
+            info!("Using Azure Blob Storage for blob storage");
+            Arc::new(AzureBlobStorage::new(config.clone()).map_err(|err| anyhow!(err))?)
+        }
         BlobStorageConfig::LocalFileSystem(config) => {
             info!(
                 "Using local file system for blob storage at {:?}",
@@ -456,6 +503,8 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
     };
 
     let active_workers = bootstrap.create_active_workers(&golem_config);
+    active_workers.start_passivation();
+    active_workers.start_idle_policies();
 
     let running_worker_enumeration_service = Arc::new(RunningWorkerEnumerationServiceDefault::new(
         active_workers.clone(),
@@ -510,8 +559,10 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
 
     let worker_service = Arc::new(DefaultWorkerService::new(
         key_value_storage.clone(),
+        indexed_storage.clone(),
         shard_service.clone(),
         oplog_service.clone(),
+        golem_config.invocation_logs.clone(),
     ));
     let worker_enumeration_service = Arc::new(DefaultWorkerEnumerationService::new(
         worker_service.clone(),
@@ -559,6 +610,16 @@ async fn create_worker_executor_impl<Ctx: WorkerCtx, A: Bootstrap<Ctx> + ?Sized>
     Ok((all, epoch_thread))
 }
 
+async fn migrated_postgres_pool(config: &DbPostgresConfig) -> Result<PostgresPool, anyhow::Error> {
+    db::postgres_migrate(
+        config,
+        IncludedMigrationsDir::new(db_migrations()).postgres_migrations(),
+    )
+    .await
+    .context("Postgres DB migration")?;
+    PostgresPool::configured(config).await
+}
+
 /// Trait to encapsulate different types that are used throughout the codebase (oss, cloud, testing, ...).
 /// Implementating types should be fieldless structs.
 ///
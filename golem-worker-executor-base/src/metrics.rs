@@ -117,6 +117,27 @@ pub mod workers {
             &["api"]
         )
         .unwrap();
+        static ref WORKER_PASSIVATION_TOTAL: CounterVec = register_counter_vec!(
+            "worker_passivation_total",
+            "Number of idle workers stopped to free up worker memory",
+            &["trigger"]
+        )
+        .unwrap();
+        static ref WORKER_REACTIVATION_TOTAL: Counter = register_counter!(
+            "worker_reactivation_total",
+            "Number of passivated workers loaded again"
+        )
+        .unwrap();
+        static ref WORKER_EVICTION_TOTAL: Counter = register_counter!(
+            "worker_eviction_total",
+            "Number of idle workers dropped from the active workers by their idle policy"
+        )
+        .unwrap();
+        static ref WORKER_MEMORY_USAGE_RATIO: Gauge = register_gauge!(
+            "worker_memory_usage_ratio",
+            "Ratio of the worker memory in use by the active workers"
+        )
+        .unwrap();
     }
 
     pub fn record_worker_call(api_name: &'static str) {
@@ -124,6 +145,22 @@ pub mod workers {
             .with_label_values(&[api_name])
             .inc();
     }
+
+    pub fn record_worker_passivated(trigger: &'static str) {
+        WORKER_PASSIVATION_TOTAL.with_label_values(&[trigger]).inc();
+    }
+
+    pub fn record_worker_reactivated() {
+        WORKER_REACTIVATION_TOTAL.inc();
+    }
+
+    pub fn record_worker_evicted() {
+        WORKER_EVICTION_TOTAL.inc();
+    }
+
+    pub fn record_worker_memory_usage_ratio(ratio: f64) {
+        WORKER_MEMORY_USAGE_RATIO.set(ratio);
+    }
 }
 
 pub mod promises {
@@ -31,7 +31,7 @@ use golem_common::model::{
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use nonempty_collections::NEVec;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::pin::Pin;
@@ -83,6 +83,7 @@ impl Error for InterruptKind {}
 pub struct WorkerConfig {
     pub args: Vec<String>,
     pub env: Vec<(String, String)>,
+    pub wasi_config_vars: BTreeMap<String, String>,
     pub deleted_regions: DeletedRegions,
     pub total_linear_memory_size: u64,
 }
@@ -93,6 +94,7 @@ impl WorkerConfig {
         component_version: u64,
         worker_args: Vec<String>,
         mut worker_env: Vec<(String, String)>,
+        worker_wasi_config_vars: BTreeMap<String, String>,
         deleted_regions: DeletedRegions,
         total_linear_memory_size: u64,
     ) -> WorkerConfig {
@@ -110,6 +112,7 @@ impl WorkerConfig {
         WorkerConfig {
             args: worker_args,
             env: worker_env,
+            wasi_config_vars: worker_wasi_config_vars,
             deleted_regions,
             total_linear_memory_size,
         }
@@ -340,6 +343,45 @@ impl From<PersistenceLevel> for crate::preview2::golem_api_0_2_x::host::Persiste
     }
 }
 
+/// A host call of an invocation which was cut short by the deadline of the invocation
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct DeadlineExceeded {
+    pub deadline: Timestamp,
+    pub call: DeadlineExceededCall,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum DeadlineExceededCall {
+    Rpc,
+    OutgoingHttp,
+    Sleep,
+}
+
+impl From<DeadlineExceeded> for crate::preview2::golem_api_1_x::host::DeadlineExceeded {
+    fn from(value: DeadlineExceeded) -> Self {
+        Self {
+            deadline: value.deadline.into(),
+            call: value.call.into(),
+        }
+    }
+}
+
+impl From<DeadlineExceededCall> for crate::preview2::golem_api_1_x::host::DeadlineExceededCall {
+    fn from(value: DeadlineExceededCall) -> Self {
+        match value {
+            DeadlineExceededCall::Rpc => {
+                crate::preview2::golem_api_1_x::host::DeadlineExceededCall::Rpc
+            }
+            DeadlineExceededCall::OutgoingHttp => {
+                crate::preview2::golem_api_1_x::host::DeadlineExceededCall::OutgoingHttp
+            }
+            DeadlineExceededCall::Sleep => {
+                crate::preview2::golem_api_1_x::host::DeadlineExceededCall::Sleep
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum LookupResult {
     New,
@@ -481,6 +523,11 @@ impl InvocationContext {
         }
     }
 
+    /// The earliest deadline of the spans of the invocation
+    pub fn deadline(&self) -> Option<Timestamp> {
+        self.spans.values().filter_map(|span| span.deadline()).min()
+    }
+
     fn span(&self, span_id: &SpanId) -> Result<&Arc<InvocationContextSpan>, String> {
         self.spans
             .get(span_id)
@@ -494,6 +541,7 @@ mod tests {
 
     use uuid::Uuid;
 
+    use golem_common::model::invocation_context::DEADLINE_ATTRIBUTE;
     use golem_common::model::ComponentId;
 
     use super::*;
@@ -511,4 +559,34 @@ mod tests {
         println!("hash: {:?}", hash);
         assert_eq!(hash, -6692039695739768661);
     }
+
+    #[test]
+    fn invocation_context_deadline_is_the_earliest_of_its_spans() {
+        let deadline = |millis: u64| {
+            HashMap::from([(
+                DEADLINE_ATTRIBUTE.to_string(),
+                AttributeValue::String(Timestamp::from(millis).to_string()),
+            )])
+        };
+
+        let mut stack = InvocationContextStack::new(
+            TraceId::generate(),
+            InvocationContextSpan::new_with_attributes(None, deadline(1_700_000_060_000)),
+            Vec::new(),
+        );
+        stack.push(InvocationContextSpan::new_with_attributes(
+            None,
+            deadline(1_700_000_030_000),
+        ));
+        stack.push(InvocationContextSpan::new(None));
+
+        let (invocation_context, _) = InvocationContext::from_stack(stack.clone()).unwrap();
+
+        assert_eq!(stack.deadline(), Some(Timestamp::from(1_700_000_030_000)));
+        assert_eq!(
+            invocation_context.deadline(),
+            Some(Timestamp::from(1_700_000_030_000))
+        );
+        assert_eq!(InvocationContext::new(None).deadline(), None);
+    }
 }
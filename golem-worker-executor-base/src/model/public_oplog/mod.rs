@@ -14,6 +14,7 @@
 
 pub mod wit;
 
+use crate::durable_host::golem::awaiting::SerializableAwaited;
 use crate::durable_host::http::serialized::{
     SerializableDnsErrorPayload, SerializableErrorCode, SerializableFieldSizePayload,
     SerializableHttpMethod, SerializableHttpRequest, SerializableResponse,
@@ -28,7 +29,7 @@ use crate::durable_host::wasm_rpc::serialized::{
     SerializableScheduleInvocationRequest,
 };
 use crate::error::GolemError;
-use crate::model::InterruptKind;
+use crate::model::{DeadlineExceeded, DeadlineExceededCall, InterruptKind};
 use crate::services::component::ComponentService;
 use crate::services::oplog::OplogService;
 use crate::services::plugins::Plugins;
@@ -39,27 +40,30 @@ use async_trait::async_trait;
 use bincode::Decode;
 use golem_api_grpc::proto::golem::worker::UpdateMode;
 use golem_common::model::exports::{find_resource_site, function_by_name};
+use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::lucene::Query;
-use golem_common::model::oplog::{OplogEntry, OplogIndex, UpdateDescription};
+use golem_common::model::oplog::{OplogEntry, OplogIndex, OplogPayload, UpdateDescription};
 use golem_common::model::public_oplog::{
-    ActivatePluginParameters, CancelInvocationParameters, ChangeRetryPolicyParameters,
-    CreateParameters, DeactivatePluginParameters, DescribeResourceParameters, EndRegionParameters,
-    ErrorParameters, ExportedFunctionCompletedParameters, ExportedFunctionInvokedParameters,
+    ActivatePluginParameters, CancelInvocationParameters, ChangeLabelsParameters,
+    ChangeRetryPolicyParameters, CreateParameters, DeactivatePluginParameters,
+    DescribeResourceParameters, EndRegionParameters, ErrorParameters,
+    ExportedFunctionCompletedParameters, ExportedFunctionInvokedParameters,
     ExportedFunctionParameters, FailedUpdateParameters, GrowMemoryParameters,
     ImportedFunctionInvokedParameters, JumpParameters, LogParameters, ManualUpdateParameters,
     PendingUpdateParameters, PendingWorkerInvocationParameters, PublicOplogEntry,
-    PublicUpdateDescription, PublicWorkerInvocation, ResourceParameters, RevertParameters,
-    SnapshotBasedUpdateParameters, SuccessfulUpdateParameters, TimestampParameter,
+    PublicOplogEntryFilter, PublicUpdateDescription, PublicWorkerInvocation, ResourceParameters,
+    RevertParameters, SnapshotBasedUpdateParameters, SuccessfulUpdateParameters,
+    TimestampParameter,
 };
 use golem_common::model::{
     ComponentId, ComponentVersion, Empty, IdempotencyKey, OwnedWorkerId, PromiseId, ShardId,
-    WorkerId, WorkerInvocation,
+    Timestamp, WorkerId, WorkerInvocation,
 };
 use golem_common::serialization::try_deserialize as core_try_deserialize;
 use golem_service_base::model::RevertWorkerTarget;
 use golem_wasm_ast::analysis::analysed_type::{
-    case, field, list, option, r#enum, record, result, result_err, str, tuple, u16, u32, u64, u8,
-    unit_case, variant,
+    bool, case, field, list, option, r#enum, record, result, result_err, str, tuple, u16, u32, u64,
+    u8, unit_case, variant,
 };
 use golem_wasm_ast::analysis::{AnalysedType, NameOptionTypePair, TypeVariant};
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
@@ -88,7 +92,18 @@ pub async fn get_public_oplog_chunk<T: GolemTypes>(
     initial_component_version: ComponentVersion,
     initial_oplog_index: OplogIndex,
     count: usize,
+    truncated_until: Option<OplogIndex>,
 ) -> Result<PublicOplogChunk, String> {
+    let initial_oplog_index = skip_truncated(initial_oplog_index, truncated_until);
+
+    // The create entry is returned in a chunk of its own if the entries after it were dropped
+    // by compaction, so the entries of each chunk are contiguous
+    let count = if initial_oplog_index == OplogIndex::INITIAL && truncated_until.is_some() {
+        1
+    } else {
+        count
+    };
+
     let raw_entries = oplog_service
         .read(owned_worker_id, initial_oplog_index, count as u64)
         .await;
@@ -100,7 +115,7 @@ pub async fn get_public_oplog_chunk<T: GolemTypes>(
     let mut next_oplog_index = initial_oplog_index;
 
     for (index, raw_entry) in raw_entries {
-        if let Some(version) = raw_entry.specifies_component_version() {
+        if let Some(version) = specified_component_version(index, &raw_entry, truncated_until) {
             current_component_version = version;
         }
 
@@ -114,7 +129,7 @@ pub async fn get_public_oplog_chunk<T: GolemTypes>(
         )
         .await?;
         entries.push(entry);
-        next_oplog_index = index.next();
+        next_oplog_index = skip_truncated(index.next(), truncated_until);
     }
 
     Ok(PublicOplogChunk {
@@ -142,13 +157,20 @@ pub async fn search_public_oplog<T: GolemTypes>(
     initial_oplog_index: OplogIndex,
     count: usize,
     query: &str,
+    filter: &PublicOplogEntryFilter,
+    truncated_until: Option<OplogIndex>,
 ) -> Result<PublicOplogSearchResult, String> {
     let mut results = Vec::new();
     let mut last_index;
     let mut current_index = initial_oplog_index;
     let mut current_component_version = initial_component_version;
 
-    let query = Query::parse(query)?;
+    // An empty query only searches by the filter
+    let query = if query.trim().is_empty() {
+        None
+    } else {
+        Some(Query::parse(query)?)
+    };
 
     loop {
         let chunk = get_public_oplog_chunk(
@@ -159,13 +181,15 @@ pub async fn search_public_oplog<T: GolemTypes>(
             current_component_version,
             current_index,
             count,
+            truncated_until,
         )
         .await?;
 
+        let empty_chunk = chunk.entries.is_empty();
         for (idx, entry) in chunk.entries.into_iter().enumerate() {
-            if entry.matches(&query) {
+            if filter.matches(&entry) && query.as_ref().is_none_or(|query| entry.matches(query)) {
                 results.push((
-                    OplogIndex::from_u64(u64::from(current_index) + idx as u64),
+                    OplogIndex::from_u64(u64::from(chunk.first_index_in_chunk) + idx as u64),
                     entry,
                 ));
             }
@@ -175,7 +199,7 @@ pub async fn search_public_oplog<T: GolemTypes>(
         current_index = chunk.next_oplog_index;
         current_component_version = chunk.current_component_version;
 
-        if current_index >= last_index || results.len() >= count {
+        if empty_chunk || current_index >= last_index || results.len() >= count {
             break;
         }
     }
@@ -192,6 +216,7 @@ pub async fn find_component_version_at(
     oplog_service: Arc<dyn OplogService + Send + Sync>,
     owned_worker_id: &OwnedWorkerId,
     start: OplogIndex,
+    truncated_until: Option<OplogIndex>,
 ) -> Result<ComponentVersion, GolemError> {
     let mut initial_component_version = 0;
     let last_oplog_index = oplog_service.get_last_index(owned_worker_id).await;
@@ -205,16 +230,45 @@ pub async fn find_component_version_at(
             .next()
             .map(|(_, v)| v.clone());
 
-        if let Some(version) = entry.and_then(|entry| entry.specifies_component_version()) {
+        if let Some(version) =
+            entry.and_then(|entry| specified_component_version(current, &entry, truncated_until))
+        {
             initial_component_version = version;
         }
 
-        current = current.next();
+        current = skip_truncated(current.next(), truncated_until);
     }
 
     Ok(initial_component_version)
 }
 
+// Moves an index pointing into the history dropped from a truncated oplog to the first entry
+// kept after it
+fn skip_truncated(index: OplogIndex, truncated_until: Option<OplogIndex>) -> OplogIndex {
+    match truncated_until {
+        Some(truncated_until) if index > OplogIndex::INITIAL && index < truncated_until => {
+            truncated_until
+        }
+        _ => index,
+    }
+}
+
+// The component version an oplog entry switches the worker to. A truncated oplog continues
+// with the pending update holding the snapshot, which also stands for the updates dropped
+// before it.
+fn specified_component_version(
+    index: OplogIndex,
+    entry: &OplogEntry,
+    truncated_until: Option<OplogIndex>,
+) -> Option<ComponentVersion> {
+    match entry {
+        OplogEntry::PendingUpdate { description, .. } if Some(index) == truncated_until => {
+            Some(*description.target_version())
+        }
+        _ => entry.specifies_component_version(),
+    }
+}
+
 #[async_trait]
 pub trait PublicOplogEntryOps<T: GolemTypes>: Sized {
     async fn from_oplog_entry(
@@ -260,7 +314,7 @@ impl<T: GolemTypes> PublicOplogEntryOps<T> for PublicOplogEntry {
                 initial_total_linear_memory_size,
                 initial_active_plugins: BTreeSet::new(),
             })),
-            OplogEntry::Create {
+            OplogEntry::CreateV2 {
                 timestamp,
                 worker_id,
                 component_version,
@@ -271,6 +325,19 @@ impl<T: GolemTypes> PublicOplogEntryOps<T> for PublicOplogEntry {
                 component_size,
                 initial_total_linear_memory_size,
                 initial_active_plugins,
+            }
+            | OplogEntry::Create {
+                timestamp,
+                worker_id,
+                component_version,
+                args,
+                env,
+                account_id,
+                parent,
+                component_size,
+                initial_total_linear_memory_size,
+                initial_active_plugins,
+                ..
             } => {
                 let mut initial_plugins = BTreeSet::new();
                 for installation_id in initial_active_plugins {
@@ -346,48 +413,44 @@ impl<T: GolemTypes> PublicOplogEntryOps<T> for PublicOplogEntry {
                     },
                 ))
             }
+            OplogEntry::ExportedFunctionInvokedV1 {
+                timestamp,
+                function_name,
+                request,
+                idempotency_key,
+            } => {
+                exported_function_invoked(
+                    oplog_service,
+                    components,
+                    owned_worker_id,
+                    component_version,
+                    timestamp,
+                    function_name,
+                    request,
+                    idempotency_key,
+                    None,
+                )
+                .await
+            }
             OplogEntry::ExportedFunctionInvoked {
                 timestamp,
                 function_name,
                 request,
                 idempotency_key,
+                invocation_context,
             } => {
-                let payload_bytes = oplog_service
-                    .download_payload(owned_worker_id, &request)
-                    .await?;
-                let proto_params: Vec<golem_wasm_rpc::protobuf::Val> =
-                    core_try_deserialize(&payload_bytes)?.unwrap_or_default();
-                let params = proto_params
-                    .into_iter()
-                    .map(Value::try_from)
-                    .collect::<Result<Vec<_>, _>>()?;
-
-                let metadata = components
-                    .get_metadata(
-                        &owned_worker_id.account_id,
-                        &owned_worker_id.worker_id.component_id,
-                        Some(component_version),
-                    )
-                    .await
-                    .map_err(|err| err.to_string())?;
-                let function = function_by_name(&metadata.exports, &function_name)?.ok_or(
-                        format!("Exported function {function_name} not found in component {} version {component_version}", owned_worker_id.component_id())
-                    )?;
-                let request = function
-                    .parameters
-                    .iter()
-                    .zip(params)
-                    .map(|(param, value)| ValueAndType::new(value, param.typ.clone()))
-                    .collect();
-
-                Ok(PublicOplogEntry::ExportedFunctionInvoked(
-                    ExportedFunctionInvokedParameters {
-                        timestamp,
-                        function_name,
-                        request,
-                        idempotency_key,
-                    },
-                ))
+                exported_function_invoked(
+                    oplog_service,
+                    components,
+                    owned_worker_id,
+                    component_version,
+                    timestamp,
+                    function_name,
+                    request,
+                    idempotency_key,
+                    Some(invocation_context),
+                )
+                .await
             }
             OplogEntry::ExportedFunctionCompleted {
                 timestamp,
@@ -738,10 +801,79 @@ impl<T: GolemTypes> PublicOplogEntryOps<T> for PublicOplogEntry {
                     idempotency_key,
                 },
             )),
+            OplogEntry::ChangeLabels { timestamp, labels } => {
+                Ok(PublicOplogEntry::ChangeLabels(ChangeLabelsParameters {
+                    timestamp,
+                    labels,
+                }))
+            }
         }
     }
 }
 
+// The public view of an invocation of an exported function, with the trace and spans of its
+// invocation context if it has been recorded
+#[allow(clippy::too_many_arguments)]
+async fn exported_function_invoked<T: GolemTypes>(
+    oplog_service: Arc<dyn OplogService + Send + Sync>,
+    components: Arc<dyn ComponentService<T>>,
+    owned_worker_id: &OwnedWorkerId,
+    component_version: ComponentVersion,
+    timestamp: Timestamp,
+    function_name: String,
+    request: OplogPayload,
+    idempotency_key: IdempotencyKey,
+    invocation_context: Option<InvocationContextStack>,
+) -> Result<PublicOplogEntry, String> {
+    let payload_bytes = oplog_service
+        .download_payload(owned_worker_id, &request)
+        .await?;
+    let proto_params: Vec<golem_wasm_rpc::protobuf::Val> =
+        core_try_deserialize(&payload_bytes)?.unwrap_or_default();
+    let params = proto_params
+        .into_iter()
+        .map(Value::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let metadata = components
+        .get_metadata(
+            &owned_worker_id.account_id,
+            &owned_worker_id.worker_id.component_id,
+            Some(component_version),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    let function = function_by_name(&metadata.exports, &function_name)?.ok_or(format!(
+        "Exported function {function_name} not found in component {} version {component_version}",
+        owned_worker_id.component_id()
+    ))?;
+    let request = function
+        .parameters
+        .iter()
+        .zip(params)
+        .map(|(param, value)| ValueAndType::new(value, param.typ.clone()))
+        .collect();
+
+    Ok(PublicOplogEntry::ExportedFunctionInvoked(
+        ExportedFunctionInvokedParameters {
+            timestamp,
+            function_name,
+            request,
+            idempotency_key,
+            trace_id: invocation_context
+                .as_ref()
+                .map(|context| context.trace_id.to_string()),
+            span_id: invocation_context
+                .as_ref()
+                .map(|context| context.current_span_id().to_string()),
+            parent_span_id: invocation_context
+                .as_ref()
+                .and_then(|context| context.parent_span_id())
+                .map(|span_id| span_id.to_string()),
+        },
+    ))
+}
+
 fn try_deserialize<T: Decode>(data: &[u8]) -> Result<T, String> {
     core_try_deserialize(data)?.ok_or("Unexpected oplog payload, cannot deserialize".to_string())
 }
@@ -1052,6 +1184,15 @@ fn encode_host_function_request_as_value(
             let payload: PromiseId = try_deserialize(bytes)?;
             Ok(payload.into_value_and_type())
         }
+        "golem::api::await_deadline" => {
+            let payload: Option<u64> = try_deserialize(bytes)?;
+            Ok(payload.into_value_and_type())
+        }
+        "golem::api::take_deadline_exceeded" => no_payload(),
+        "golem::api::await_any" | "golem::api::await_all" => {
+            let payload: (Vec<PromiseId>, u32) = try_deserialize(bytes)?;
+            Ok(payload.into_value_and_type())
+        }
         "golem::api::resolve_component_id" => {
             let payload: String = try_deserialize(bytes)?;
             Ok(payload.into_value_and_type())
@@ -1398,6 +1539,19 @@ fn encode_host_function_response_as_value(
             let payload: Result<Option<Vec<u8>>, SerializableError> = try_deserialize(bytes)?;
             Ok(payload.into_value_and_type())
         }
+        "golem::api::await_deadline" => {
+            let payload: Result<Option<i64>, SerializableError> = try_deserialize(bytes)?;
+            Ok(payload.into_value_and_type())
+        }
+        "golem::api::take_deadline_exceeded" => {
+            let payload: Result<Option<DeadlineExceeded>, SerializableError> =
+                try_deserialize(bytes)?;
+            Ok(payload.into_value_and_type())
+        }
+        "golem::api::await_any" | "golem::api::await_all" => {
+            let payload: Result<SerializableAwaited, SerializableError> = try_deserialize(bytes)?;
+            Ok(payload.into_value_and_type())
+        }
         "golem::api::resolve_component_id" => {
             let payload: Result<Option<ComponentId>, SerializableError> = try_deserialize(bytes)?;
             Ok(payload.into_value_and_type())
@@ -1529,6 +1683,10 @@ impl IntoValue for RpcError {
                 case_idx: 3,
                 case_value: Some(Box::new(details.into_value())),
             },
+            RpcError::DeadlineExceeded { deadline } => Value::Variant {
+                case_idx: 4,
+                case_value: Some(Box::new(deadline.into_value())),
+            },
         }
     }
 
@@ -1551,6 +1709,10 @@ impl IntoValue for RpcError {
                     name: "RemoteInternalError".to_string(),
                     typ: Some(String::get_type()),
                 },
+                NameOptionTypePair {
+                    name: "DeadlineExceeded".to_string(),
+                    typ: Some(Timestamp::get_type()),
+                },
             ],
         })
     }
@@ -2038,6 +2200,22 @@ impl IntoValue for GolemError {
                         reason.into_value(),
                     ]))),
                 },
+                GolemError::WorkerExecutorDraining => Value::Variant {
+                    case_idx: 25,
+                    case_value: None,
+                },
+                GolemError::OplogTruncated {
+                    worker_id,
+                    oplog_index,
+                    truncated_until,
+                } => Value::Variant {
+                    case_idx: 26,
+                    case_value: Some(Box::new(Value::Record(vec![
+                        worker_id.into_value(),
+                        oplog_index.into_value(),
+                        truncated_until.into_value(),
+                    ]))),
+                },
             }
         }
         into_value(self, true)
@@ -2135,6 +2313,19 @@ impl IntoValue for GolemError {
                     "InitialComponentFileDownloadFailed",
                     record(vec![field("path", str()), field("reason", str())]),
                 ),
+                case(
+                    "FileSystemError",
+                    record(vec![field("path", str()), field("reason", str())]),
+                ),
+                unit_case("WorkerExecutorDraining"),
+                case(
+                    "OplogTruncated",
+                    record(vec![
+                        field("worker_id", WorkerId::get_type()),
+                        field("oplog_index", u64()),
+                        field("truncated_until", u64()),
+                    ]),
+                ),
             ])
         }
         get_type(true)
@@ -2410,6 +2601,51 @@ impl IntoValueAndType for SerializableScheduleInvocationRequest {
     }
 }
 
+impl IntoValue for SerializableAwaited {
+    fn into_value(self) -> Value {
+        Value::Record(vec![
+            self.promises.into_value(),
+            self.rpc_results.into_value(),
+            self.timed_out.into_value(),
+        ])
+    }
+
+    fn get_type() -> AnalysedType {
+        record(vec![
+            field("promises", list(tuple(vec![u32(), list(u8())]))),
+            field("rpc-results", list(u32())),
+            field("timed-out", bool()),
+        ])
+    }
+}
+
+impl IntoValue for DeadlineExceeded {
+    fn into_value(self) -> Value {
+        Value::Record(vec![self.deadline.into_value(), self.call.into_value()])
+    }
+
+    fn get_type() -> AnalysedType {
+        record(vec![
+            field("deadline", Timestamp::get_type()),
+            field("call", DeadlineExceededCall::get_type()),
+        ])
+    }
+}
+
+impl IntoValue for DeadlineExceededCall {
+    fn into_value(self) -> Value {
+        match self {
+            DeadlineExceededCall::Rpc => Value::Enum(0),
+            DeadlineExceededCall::OutgoingHttp => Value::Enum(1),
+            DeadlineExceededCall::Sleep => Value::Enum(2),
+        }
+    }
+
+    fn get_type() -> AnalysedType {
+        r#enum(&["Rpc", "OutgoingHttp", "Sleep"])
+    }
+}
+
 impl IntoValue for SerializableScheduleId {
     fn into_value(self) -> Value {
         Value::List(self.data.into_iter().map(Value::U8).collect())
@@ -16,9 +16,10 @@ use crate::model::public_oplog::{PublicOplogEntry, PublicUpdateDescription};
 use crate::preview2::golem_api_1_x::oplog;
 use crate::preview2::wasi::clocks::wall_clock::Datetime;
 use golem_common::model::public_oplog::{
-    ActivatePluginParameters, CancelInvocationParameters, ChangeRetryPolicyParameters,
-    CreateParameters, DeactivatePluginParameters, DescribeResourceParameters, EndRegionParameters,
-    ErrorParameters, ExportedFunctionCompletedParameters, ExportedFunctionInvokedParameters,
+    ActivatePluginParameters, CancelInvocationParameters, ChangeLabelsParameters,
+    ChangeRetryPolicyParameters, CreateParameters, DeactivatePluginParameters,
+    DescribeResourceParameters, EndRegionParameters, ErrorParameters,
+    ExportedFunctionCompletedParameters, ExportedFunctionInvokedParameters,
     ExportedFunctionParameters, FailedUpdateParameters, GrowMemoryParameters,
     ImportedFunctionInvokedParameters, JumpParameters, LogParameters, ManualUpdateParameters,
     PendingUpdateParameters, PendingWorkerInvocationParameters, PluginInstallationDescription,
@@ -77,6 +78,7 @@ impl From<PublicOplogEntry> for oplog::OplogEntry {
                 function_name,
                 request,
                 idempotency_key,
+                ..
             }) => Self::ExportedFunctionInvoked(oplog::ExportedFunctionInvokedParameters {
                 timestamp: timestamp.into(),
                 function_name,
@@ -265,6 +267,22 @@ impl From<PublicOplogEntry> for oplog::OplogEntry {
                     ),
                 })
             }
+            PublicOplogEntry::ChangeLabels(ChangeLabelsParameters { timestamp, labels }) => {
+                // TODO: Adding new entries to the WIT oplog-entry variant breaks the interface so for now we don't support the new entries and mark them as fake Log entries instead
+                Self::Log(oplog::LogParameters {
+                    timestamp: timestamp.into(),
+                    level: oplog::LogLevel::Info,
+                    context: "ChangeLabels".to_string(),
+                    message: format!(
+                        "Changed labels to {}",
+                        labels
+                            .iter()
+                            .map(|(key, value)| format!("{key}={value}"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                })
+            }
         }
     }
 }
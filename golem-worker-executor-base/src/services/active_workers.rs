@@ -12,29 +12,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore, TryAcquireError};
 
-use tracing::{debug, Instrument};
+use tracing::{debug, info, Instrument};
 
 use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
-use golem_common::model::{OwnedWorkerId, WorkerId};
+use golem_common::model::{OwnedWorkerId, Timestamp, WorkerId, WorkerStatus, WorkerStatusRecord};
 
 use crate::error::GolemError;
-use crate::services::golem_config::MemoryConfig;
+use crate::metrics::workers::{
+    record_worker_evicted, record_worker_memory_usage_ratio, record_worker_passivated,
+    record_worker_reactivated,
+};
+use crate::services::golem_config::{
+    IdleAction, IdlePolicyConfig, MemoryConfig, PassivationConfig,
+};
 use crate::services::HasAll;
 use crate::worker::Worker;
 use crate::workerctx::WorkerCtx;
 
-/// Holds the metadata and wasmtime structures of currently active Golem workers
+/// Holds the metadata and wasmtime structures of currently active Golem workers.
+///
+/// The worker memory is not measured: it is the semaphore of the permits reserved by the loaded
+/// workers for their estimated memory requirement, so its usage is the ratio of the reserved permits.
 pub struct ActiveWorkers<Ctx: WorkerCtx> {
     workers: Cache<WorkerId, (), Arc<Worker<Ctx>>, GolemError>,
     worker_memory: Arc<Semaphore>,
+    worker_memory_size: usize,
     priority_allocation_lock: Arc<Mutex<()>>,
     acquire_retry_delay: Duration,
+    passivation: PassivationConfig,
+    idle_policy: IdlePolicyConfig,
+    passivated_workers: std::sync::Mutex<PassivatedWorkers>,
 }
 
+// Number of passivated workers remembered to count their reactivations
+const MAX_TRACKED_PASSIVATED_WORKERS: usize = 10_000;
+
 impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
     pub fn new(memory_config: &MemoryConfig) -> Self {
         let worker_memory_size = memory_config.worker_memory();
@@ -46,9 +63,63 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
                 "active_workers",
             ),
             worker_memory: Arc::new(Semaphore::new(worker_memory_size)),
+            worker_memory_size,
             acquire_retry_delay: memory_config.acquire_retry_delay,
             priority_allocation_lock: Arc::new(Mutex::new(())),
+            passivation: memory_config.passivation.clone(),
+            idle_policy: memory_config.idle_policy.clone(),
+            passivated_workers: std::sync::Mutex::new(PassivatedWorkers::new(
+                MAX_TRACKED_PASSIVATED_WORKERS,
+            )),
+        }
+    }
+
+    /// Starts passivating the least recently used idle workers in the background whenever the
+    /// reserved worker memory crosses the configured high watermark
+    pub fn start_passivation(self: &Arc<Self>) {
+        if !self.passivation.enabled {
+            return;
         }
+
+        let active_workers: Weak<Self> = Arc::downgrade(self);
+        let check_interval = self.passivation.check_interval;
+
+        tokio::spawn(
+            async move {
+                loop {
+                    tokio::time::sleep(check_interval).await;
+                    match active_workers.upgrade() {
+                        Some(active_workers) => active_workers.passivate_idle_workers().await,
+                        None => break,
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Starts applying the idle policies of the components to their workers in the background,
+    /// suspending and evicting the workers which are idle for long enough
+    pub fn start_idle_policies(self: &Arc<Self>) {
+        if !self.idle_policy.enabled {
+            return;
+        }
+
+        let active_workers: Weak<Self> = Arc::downgrade(self);
+        let check_interval = self.idle_policy.check_interval;
+
+        tokio::spawn(
+            async move {
+                loop {
+                    tokio::time::sleep(check_interval).await;
+                    match active_workers.upgrade() {
+                        Some(active_workers) => active_workers.apply_idle_policies().await,
+                        None => break,
+                    }
+                }
+            }
+            .in_current_span(),
+        );
     }
 
     pub async fn get_or_add<T>(
@@ -57,6 +128,7 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
         owned_worker_id: &OwnedWorkerId,
         worker_args: Option<Vec<String>>,
         worker_env: Option<Vec<(String, String)>>,
+        worker_wasi_config_vars: Option<BTreeMap<String, String>>,
         component_version: Option<u64>,
         parent: Option<WorkerId>,
     ) -> Result<Arc<Worker<Ctx>>, GolemError>
@@ -76,6 +148,7 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
                             owned_worker_id,
                             worker_args,
                             worker_env,
+                            worker_wasi_config_vars,
                             component_version,
                             parent,
                         )
@@ -94,6 +167,15 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
 
     pub fn remove(&self, worker_id: &WorkerId) {
         self.workers.remove(worker_id);
+        self.passivated_workers.lock().unwrap().remove(worker_id);
+    }
+
+    /// Called when an unloaded worker starts loading, to count the reactivations of the
+    /// passivated workers
+    pub fn worker_loading(&self, worker_id: &WorkerId) {
+        if self.passivated_workers.lock().unwrap().remove(worker_id) {
+            record_worker_reactivated();
+        }
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (WorkerId, Arc<Worker<Ctx>>)> + '_ {
@@ -185,45 +267,331 @@ impl<Ctx: WorkerCtx> ActiveWorkers<Ctx> {
         let needed = memory.saturating_sub(current_avail as u64);
 
         if needed > 0 {
-            let mut possibilities = Vec::new();
-
-            debug!("Collecting possibilities");
-            // Collecting the workers which are currently idle but loaded into memory
-            for (worker_id, worker) in self.workers.iter() {
-                if worker.is_currently_idle_but_running().await {
-                    if let Ok(mem) = worker.memory_requirement() {
-                        let last_changed = worker.last_execution_state_change();
-                        possibilities.push((worker_id, worker, mem, last_changed));
-                    }
+            // Freeing up memory for a worker which could not be loaded otherwise, so the
+            // protections of the passivation do not apply here
+            let freed = self.stop_idle_workers(needed, false, "allocation").await;
+            freed >= needed
+        } else {
+            debug!("Memory was freed up in the meantime");
+            // Memory was freed up in the meantime, we can retry
+            true
+        }
+    }
+
+    // The usage is the ratio of the worker memory permits reserved by the loaded workers, which is
+    // what limits loading new workers, not the memory actually used by the process
+    async fn passivate_idle_workers(&self) {
+        let available = self.worker_memory.available_permits();
+        let used = self.worker_memory_size.saturating_sub(available);
+        let usage_ratio = if self.worker_memory_size > 0 {
+            used as f64 / self.worker_memory_size as f64
+        } else {
+            0.0
+        };
+        record_worker_memory_usage_ratio(usage_ratio);
+
+        if usage_ratio < self.passivation.high_watermark {
+            return;
+        }
+
+        let target_available =
+            (self.worker_memory_size as f64 * (1.0 - self.passivation.low_watermark)) as u64;
+        let needed = target_available.saturating_sub(available as u64);
+
+        if needed > 0 {
+            debug!(
+                "Worker memory usage {usage_ratio:.2} is above the high watermark, passivating idle workers to free up {needed}"
+            );
+            let freed = self
+                .stop_idle_workers(needed, true, "memory_pressure")
+                .await;
+            if freed > 0 {
+                info!("Passivated idle workers to free up {freed} of worker memory");
+            }
+        }
+    }
+
+    // Stops the idle workers, the least recently used first, until the needed memory is freed up,
+    // and returns the memory freed up
+    async fn stop_idle_workers(&self, needed: u64, protect: bool, trigger: &'static str) -> u64 {
+        let mut possibilities = Vec::new();
+
+        debug!("Collecting possibilities");
+        // Collecting the workers which are currently idle but loaded into memory
+        for (worker_id, worker) in self.workers.iter() {
+            if worker.is_currently_idle_but_running().await
+                && !(protect && self.is_protected_from_passivation(&worker))
+            {
+                if let Ok(mem) = worker.memory_requirement() {
+                    let last_changed = worker.last_execution_state_change();
+                    possibilities.push(((worker_id, worker), mem, last_changed));
                 }
             }
+        }
 
-            // Sorting them by last time they changed their status - newest first
-            possibilities
-                .sort_by_key(|(_worker_id, _worker, _mem, last_changed)| last_changed.to_millis());
-            possibilities.reverse();
+        let mut freed = 0;
 
-            let mut freed = 0;
+        // Dropping the oldest ones until we have enough memory available - rechecking the idle status before
+        for ((worker_id, worker), mem) in least_recently_used_first(possibilities) {
+            if freed >= needed {
+                break;
+            }
 
-            // Dropping the oldest ones until we have enough memory available - rechecking the idle status before
-            while freed < needed && !possibilities.is_empty() {
-                let (worker_id, worker, mem, _) = possibilities.pop().unwrap();
+            debug!("Trying to stop {worker_id} to free up memory");
+            if worker.stop_if_idle().await {
+                debug!("Stopped {worker_id} to free up {mem} memory");
+                freed += mem;
+                record_worker_passivated(trigger);
+                self.track_passivated_worker(worker_id);
+            }
+        }
 
-                debug!("Trying to stop {worker_id} to free up memory");
-                if worker.stop_if_idle().await {
-                    debug!("Stopped {worker_id} to free up {mem} memory");
-                    freed += mem;
-                }
+        if freed > 0 {
+            debug!("Freed up {freed}");
+        }
+        freed
+    }
+
+    async fn apply_idle_policies(&self) {
+        let now = Timestamp::now_utc().to_millis();
+
+        // Collecting the workers first, as the cache cannot be modified while iterating it
+        let workers = self.workers.iter().collect::<Vec<_>>();
+
+        for (worker_id, worker) in workers {
+            let idle_for = Duration::from_millis(
+                now.saturating_sub(worker.last_execution_state_change().to_millis()),
+            );
+            let Some(action) = self
+                .idle_policy
+                .policy(&worker_id.component_id)
+                .action(idle_for)
+            else {
+                continue;
+            };
+
+            if worker.is_currently_idle_but_running().await && worker.stop_if_idle().await {
+                debug!("Suspended {worker_id}, idle for {idle_for:?}");
+                record_worker_passivated("idle_policy");
+                self.track_passivated_worker(worker_id.clone());
             }
 
-            if freed > 0 {
-                debug!("Freed up {freed}");
+            // Only dropping the workers nothing else refers to, so the next request for them
+            // loads them again instead of using a dropped instance
+            if action == IdleAction::Evict
+                && Arc::strong_count(&worker) <= 2
+                && worker.is_unloaded_and_idle().await
+            {
+                debug!("Evicted {worker_id}, idle for {idle_for:?}");
+                self.workers.remove(&worker_id);
+                record_worker_evicted();
             }
-            freed >= needed
-        } else {
-            debug!("Memory was freed up in the meantime");
-            // Memory was freed up in the meantime, we can retry
-            true
         }
     }
+
+    fn is_protected_from_passivation(&self, worker: &Worker<Ctx>) -> bool {
+        let Ok(metadata) = worker.get_metadata() else {
+            return true;
+        };
+
+        is_protected_from_passivation(
+            &self.passivation,
+            &metadata.last_known_status,
+            worker.last_execution_state_change(),
+            Timestamp::now_utc(),
+        )
+    }
+
+    fn track_passivated_worker(&self, worker_id: WorkerId) {
+        self.passivated_workers.lock().unwrap().track(worker_id);
+    }
+}
+
+// Orders the idle workers to stop, with their memory requirement, by the last time they
+// changed their status - the oldest first
+fn least_recently_used_first<W>(mut workers: Vec<(W, u64, Timestamp)>) -> Vec<(W, u64)> {
+    workers.sort_by_key(|(_worker, _mem, last_changed)| last_changed.to_millis());
+    workers
+        .into_iter()
+        .map(|(worker, mem, _last_changed)| (worker, mem))
+        .collect()
+}
+
+// Workers having pending updates or having recently failed (retrying, or a failed update) are
+// not passivated under memory pressure, as they are likely to be used again soon
+fn is_protected_from_passivation(
+    config: &PassivationConfig,
+    status: &WorkerStatusRecord,
+    last_changed: Timestamp,
+    now: Timestamp,
+) -> bool {
+    if config.protect_updating_workers && !status.pending_updates.is_empty() {
+        return true;
+    }
+
+    let protected_since = now
+        .to_millis()
+        .saturating_sub(config.recently_failed_protection.as_millis() as u64);
+
+    let recently_retrying =
+        status.status == WorkerStatus::Retrying && last_changed.to_millis() >= protected_since;
+    let recently_failed_update = status
+        .failed_updates
+        .last()
+        .is_some_and(|update| update.timestamp.to_millis() >= protected_since);
+
+    recently_retrying || recently_failed_update
+}
+
+// Workers stopped to free up memory, by the time they were stopped, to count their reactivations.
+// Only the most recently passivated ones are remembered.
+struct PassivatedWorkers {
+    capacity: usize,
+    workers: HashMap<WorkerId, Instant>,
+}
+
+impl PassivatedWorkers {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            workers: HashMap::new(),
+        }
+    }
+
+    fn track(&mut self, worker_id: WorkerId) {
+        if self.workers.len() >= self.capacity && !self.workers.contains_key(&worker_id) {
+            let oldest = self
+                .workers
+                .iter()
+                .min_by_key(|(_, passivated_at)| **passivated_at)
+                .map(|(worker_id, _)| worker_id.clone());
+            if let Some(oldest) = oldest {
+                self.workers.remove(&oldest);
+            }
+        }
+
+        self.workers.insert(worker_id, Instant::now());
+    }
+
+    // Whether the worker was passivated, forgetting it, so that its reactivation is counted once
+    fn remove(&mut self, worker_id: &WorkerId) -> bool {
+        self.workers.remove(worker_id).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::oplog::{OplogIndex, TimestampedUpdateDescription, UpdateDescription};
+    use golem_common::model::{ComponentId, FailedUpdateRecord};
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn worker_id(name: &str) -> WorkerId {
+        WorkerId {
+            component_id: ComponentId(Uuid::nil()),
+            worker_name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn idle_workers_are_stopped_least_recently_used_first() {
+        let ordered = least_recently_used_first(vec![
+            ("recent", 10, Timestamp::from(3_000)),
+            ("oldest", 20, Timestamp::from(1_000)),
+            ("middle", 30, Timestamp::from(2_000)),
+        ]);
+
+        assert_eq!(
+            ordered,
+            vec![("oldest", 20), ("middle", 30), ("recent", 10)]
+        );
+    }
+
+    #[test]
+    fn recently_failed_and_updating_workers_are_protected() {
+        let config = PassivationConfig {
+            recently_failed_protection: Duration::from_secs(60),
+            ..PassivationConfig::default()
+        };
+        let now = Timestamp::from(1_000_000);
+        let recently = Timestamp::from(1_000_000 - 10_000);
+        let long_ago = Timestamp::from(1_000_000 - 120_000);
+
+        let idle = WorkerStatusRecord::default();
+        assert!(!is_protected_from_passivation(
+            &config, &idle, recently, now
+        ));
+
+        let retrying = WorkerStatusRecord {
+            status: WorkerStatus::Retrying,
+            ..WorkerStatusRecord::default()
+        };
+        assert!(is_protected_from_passivation(
+            &config, &retrying, recently, now
+        ));
+        assert!(!is_protected_from_passivation(
+            &config, &retrying, long_ago, now
+        ));
+
+        let failed_update = |timestamp| WorkerStatusRecord {
+            failed_updates: vec![FailedUpdateRecord {
+                timestamp,
+                target_version: 1,
+                details: None,
+            }],
+            ..WorkerStatusRecord::default()
+        };
+        assert!(is_protected_from_passivation(
+            &config,
+            &failed_update(recently),
+            long_ago,
+            now
+        ));
+        assert!(!is_protected_from_passivation(
+            &config,
+            &failed_update(long_ago),
+            long_ago,
+            now
+        ));
+
+        let updating = WorkerStatusRecord {
+            pending_updates: vec![TimestampedUpdateDescription {
+                timestamp: long_ago,
+                oplog_index: OplogIndex::NONE,
+                description: UpdateDescription::Automatic { target_version: 1 },
+            }]
+            .into(),
+            ..WorkerStatusRecord::default()
+        };
+        assert!(is_protected_from_passivation(
+            &config, &updating, long_ago, now
+        ));
+
+        let config = PassivationConfig {
+            protect_updating_workers: false,
+            ..config
+        };
+        assert!(!is_protected_from_passivation(
+            &config, &updating, long_ago, now
+        ));
+    }
+
+    #[test]
+    fn reactivations_of_passivated_workers_are_counted_once() {
+        let mut passivated = PassivatedWorkers::new(2);
+
+        passivated.track(worker_id("first"));
+        passivated.track(worker_id("second"));
+        assert!(passivated.remove(&worker_id("first")));
+        assert!(!passivated.remove(&worker_id("first")));
+        assert!(!passivated.remove(&worker_id("never-passivated")));
+
+        // Only the most recently passivated workers are remembered
+        passivated.track(worker_id("third"));
+        passivated.track(worker_id("fourth"));
+        assert!(!passivated.remove(&worker_id("second")));
+        assert!(passivated.remove(&worker_id("third")));
+        assert!(passivated.remove(&worker_id("fourth")));
+    }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -20,9 +21,10 @@ use anyhow::Context;
 use figment::providers::{Format, Toml};
 use figment::Figment;
 use golem_common::config::{
-    ConfigExample, ConfigLoader, DbSqliteConfig, HasConfigExamples, RedisConfig,
+    ConfigExample, ConfigLoader, DbPostgresConfig, DbSqliteConfig, HasConfigExamples, RedisConfig,
 };
-use golem_common::model::RetryConfig;
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::{ComponentId, OplogCompactionStatus, RetryConfig};
 use golem_common::tracing::TracingConfig;
 use golem_service_base::config::BlobStorageConfig;
 use http::Uri;
@@ -36,6 +38,7 @@ pub struct GolemConfig {
     pub tracing_file_name_with_port: bool,
     pub key_value_storage: KeyValueStorageConfig,
     pub indexed_storage: IndexedStorageConfig,
+    pub invocation_logs: InvocationLogsConfig,
     pub blob_storage: BlobStorageConfig,
     pub limits: Limits,
     pub retry: RetryConfig,
@@ -239,6 +242,54 @@ pub struct OplogConfig {
     pub entry_count_limit: u64,
     #[serde(with = "humantime_serde")]
     pub archive_interval: Duration,
+    pub compaction: OplogCompactionConfig,
+}
+
+// Compaction: once a worker added `entry_threshold` entries or `size_threshold` bytes of entries to
+// its oplog since its last snapshot, the state of the worker is saved by its exported save-snapshot
+// function, and recovering it loads this snapshot instead of replaying the entries before it. If
+// `truncate` is set, the entries before the snapshot (except the first one) are also dropped from
+// the oplog.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OplogCompactionConfig {
+    pub enabled: bool,
+    pub entry_threshold: Option<u64>,
+    pub size_threshold: Option<u64>,
+    pub truncate: bool,
+}
+
+impl OplogCompactionConfig {
+    pub fn is_due(&self, status: &OplogCompactionStatus, current: OplogIndex) -> bool {
+        self.enabled
+            && (self
+                .entry_threshold
+                .is_some_and(|threshold| status.entries_since_last_attempt(current) >= threshold)
+                || self
+                    .size_threshold
+                    .is_some_and(|threshold| status.bytes_since_last_attempt >= threshold))
+    }
+}
+
+// The stdout, stderr and log entries of an invocation are stored when it finishes: the last
+// `max_entries_per_invocation` of them, with their messages cut to `max_message_size` bytes. Only the
+// last `max_entries_per_worker` entries of a worker are kept.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InvocationLogsConfig {
+    pub enabled: bool,
+    pub max_entries_per_invocation: usize,
+    pub max_message_size: usize,
+    pub max_entries_per_worker: u64,
+}
+
+impl InvocationLogsConfig {
+    /// The number of entries of an invocation kept until it finishes
+    pub fn captured_entries(&self) -> usize {
+        if self.enabled {
+            self.max_entries_per_invocation
+        } else {
+            0
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -246,6 +297,9 @@ pub struct OplogConfig {
 pub enum KeyValueStorageConfig {
     Redis(RedisConfig),
     Sqlite(DbSqliteConfig),
+    // The executor migrates its own tables, so when sharing a database with the other services
+    // it must be configured with a schema of its own
+    Postgres(DbPostgresConfig),
     InMemory,
 }
 
@@ -256,6 +310,8 @@ pub enum IndexedStorageConfig {
     Redis(RedisConfig),
     KVStoreSqlite,
     Sqlite(DbSqliteConfig),
+    KVStorePostgres,
+    Postgres(DbPostgresConfig),
     InMemory,
 }
 
@@ -267,6 +323,73 @@ pub struct MemoryConfig {
     #[serde(with = "humantime_serde")]
     pub acquire_retry_delay: Duration,
     pub oom_retry_config: RetryConfig,
+    pub passivation: PassivationConfig,
+    pub idle_policy: IdlePolicyConfig,
+}
+
+// Proactive passivation of idle workers: once the memory used by the workers crosses the high
+// watermark (a ratio of the worker memory), the least recently used idle workers are stopped
+// until it gets below the low watermark. Workers which recently failed or have pending updates
+// can be protected from it, but not from the passivations freeing up memory for a new worker.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PassivationConfig {
+    pub enabled: bool,
+    #[serde(with = "humantime_serde")]
+    pub check_interval: Duration,
+    pub high_watermark: f64,
+    pub low_watermark: f64,
+    #[serde(with = "humantime_serde")]
+    pub recently_failed_protection: Duration,
+    pub protect_updating_workers: bool,
+}
+
+// Idle policies: the workers idle for longer than `suspend_after` are stopped, keeping their state
+// only in their oplog, and the ones idle for longer than `evict_after` are also dropped from the
+// active workers. The default policy can be overridden for the workers of a component, by the id
+// of the component.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IdlePolicyConfig {
+    pub enabled: bool,
+    #[serde(with = "humantime_serde")]
+    pub check_interval: Duration,
+    pub default: IdlePolicy,
+    #[serde(default)]
+    pub components: HashMap<String, IdlePolicy>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdlePolicy {
+    #[serde(default, with = "humantime_serde")]
+    pub suspend_after: Option<Duration>,
+    #[serde(default, with = "humantime_serde")]
+    pub evict_after: Option<Duration>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdleAction {
+    Suspend,
+    Evict,
+}
+
+impl IdlePolicyConfig {
+    pub fn policy(&self, component_id: &ComponentId) -> &IdlePolicy {
+        self.components
+            .get(&component_id.to_string())
+            .unwrap_or(&self.default)
+    }
+}
+
+impl IdlePolicy {
+    // Eviction also suspends the worker if it is still loaded
+    pub fn action(&self, idle_for: Duration) -> Option<IdleAction> {
+        if self.evict_after.is_some_and(|after| idle_for >= after) {
+            Some(IdleAction::Evict)
+        } else if self.suspend_after.is_some_and(|after| idle_for >= after) {
+            Some(IdleAction::Suspend)
+        } else {
+            None
+        }
+    }
 }
 
 impl MemoryConfig {
@@ -296,6 +419,7 @@ impl Default for GolemConfig {
             tracing_file_name_with_port: true,
             key_value_storage: KeyValueStorageConfig::default(),
             indexed_storage: IndexedStorageConfig::default(),
+            invocation_logs: InvocationLogsConfig::default(),
             blob_storage: BlobStorageConfig::default(),
             limits: Limits::default(),
             retry: RetryConfig::max_attempts_3(),
@@ -417,6 +541,29 @@ impl Default for OplogConfig {
             blob_storage_layers: 1,
             entry_count_limit: 1024,
             archive_interval: Duration::from_secs(60 * 60 * 24), // 24 hours
+            compaction: OplogCompactionConfig::default(),
+        }
+    }
+}
+
+impl Default for OplogCompactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            entry_threshold: Some(100_000),
+            size_threshold: Some(64 * 1024 * 1024),
+            truncate: true,
+        }
+    }
+}
+
+impl Default for InvocationLogsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries_per_invocation: 100,
+            max_message_size: 4096,
+            max_entries_per_worker: 1000,
         }
     }
 }
@@ -488,6 +635,32 @@ impl Default for MemoryConfig {
                 multiplier: 2.0,
                 max_jitter_factor: None, // TODO: should we add jitter here?
             },
+            passivation: PassivationConfig::default(),
+            idle_policy: IdlePolicyConfig::default(),
+        }
+    }
+}
+
+impl Default for PassivationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval: Duration::from_secs(5),
+            high_watermark: 0.9,
+            low_watermark: 0.75,
+            recently_failed_protection: Duration::from_secs(60),
+            protect_updating_workers: true,
+        }
+    }
+}
+
+impl Default for IdlePolicyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_interval: Duration::from_secs(30),
+            default: IdlePolicy::default(),
+            components: HashMap::new(),
         }
     }
 }
@@ -495,3 +668,78 @@ impl Default for MemoryConfig {
 pub fn make_config_loader() -> ConfigLoader<GolemConfig> {
     ConfigLoader::new_with_examples(Path::new("config/worker-executor.toml"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn idle_policies_can_be_overridden_per_component() {
+        let component_id = ComponentId(Uuid::new_v4());
+        let config = IdlePolicyConfig {
+            default: IdlePolicy {
+                suspend_after: Some(Duration::from_secs(600)),
+                evict_after: None,
+            },
+            components: HashMap::from([(
+                component_id.to_string(),
+                IdlePolicy {
+                    suspend_after: Some(Duration::from_secs(60)),
+                    evict_after: Some(Duration::from_secs(300)),
+                },
+            )]),
+            ..IdlePolicyConfig::default()
+        };
+
+        let policy = config.policy(&component_id);
+        assert_eq!(policy.action(Duration::from_secs(30)), None);
+        assert_eq!(
+            policy.action(Duration::from_secs(60)),
+            Some(IdleAction::Suspend)
+        );
+        assert_eq!(
+            policy.action(Duration::from_secs(301)),
+            Some(IdleAction::Evict)
+        );
+
+        let default = config.policy(&ComponentId(Uuid::new_v4()));
+        assert_eq!(default.action(Duration::from_secs(300)), None);
+        assert_eq!(
+            default.action(Duration::from_secs(3600)),
+            Some(IdleAction::Suspend)
+        );
+    }
+
+    #[test]
+    fn compaction_is_due_by_entries_or_bytes() {
+        let config = OplogCompactionConfig {
+            enabled: true,
+            entry_threshold: Some(1000),
+            size_threshold: Some(1024 * 1024),
+            truncate: true,
+        };
+
+        let mut status = OplogCompactionStatus {
+            last_snapshot_index: Some(OplogIndex::from_u64(500)),
+            bytes_since_last_attempt: 4096,
+            ..OplogCompactionStatus::default()
+        };
+        assert!(!config.is_due(&status, OplogIndex::from_u64(1499)));
+        assert!(config.is_due(&status, OplogIndex::from_u64(1500)));
+
+        // A failed attempt postpones the next one
+        status.last_failed_attempt_index = Some(OplogIndex::from_u64(1400));
+        assert!(!config.is_due(&status, OplogIndex::from_u64(1500)));
+
+        status.bytes_since_last_attempt = 2 * 1024 * 1024;
+        assert!(config.is_due(&status, OplogIndex::from_u64(1500)));
+
+        let disabled = OplogCompactionConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(!disabled.is_due(&status, OplogIndex::from_u64(1500)));
+    }
+}
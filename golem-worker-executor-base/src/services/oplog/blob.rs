@@ -117,6 +117,7 @@ impl OplogArchiveService for BlobOplogArchiveService {
         &self,
         account_id: &AccountId,
         component_id: &ComponentId,
+        worker_name_prefix: Option<&str>,
         cursor: ScanCursor,
         _count: u64,
     ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError> {
@@ -147,13 +148,16 @@ impl OplogArchiveService for BlobOplogArchiveService {
 
                 paths
                     .into_iter()
-                    .map(|path| {
-                        let worker_name = path.file_name().unwrap().to_str().unwrap();
+                    .map(|path| path.file_name().unwrap().to_str().unwrap().to_string())
+                    .filter(|worker_name| {
+                        worker_name_prefix.is_none_or(|prefix| worker_name.starts_with(prefix))
+                    })
+                    .map(|worker_name| {
                         OwnedWorkerId {
                             account_id: account_id.clone(),
                             worker_id: WorkerId {
                                 component_id: component_id.clone(),
-                                worker_name: worker_name.to_string(),
+                                worker_name,
                             },
                         }
                     })
@@ -99,6 +99,7 @@ impl OplogArchiveService for CompressedOplogArchiveService {
         &self,
         account_id: &AccountId,
         component_id: &ComponentId,
+        worker_name_prefix: Option<&str>,
         cursor: ScanCursor,
         count: u64,
     ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError> {
@@ -108,7 +109,7 @@ impl OplogArchiveService for CompressedOplogArchiveService {
             .with("compressed_oplog", "scan")
             .scan(
                 IndexedStorageNamespace::CompressedOpLog { level: self.level },
-                &PrimaryOplogService::key_pattern(component_id),
+                &PrimaryOplogService::key_pattern(component_id, worker_name_prefix),
                 cursor,
                 count,
             )
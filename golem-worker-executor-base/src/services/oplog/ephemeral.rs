@@ -119,6 +119,11 @@ impl Oplog for EphemeralOplog {
         self.target.drop_prefix(last_dropped_id).await;
     }
 
+    async fn drop_range(&self, _first_dropped_id: OplogIndex, _last_dropped_id: OplogIndex) {
+        // Ephemeral workers never replay their oplog, so it is never truncated
+        record_oplog_call("drop_range");
+    }
+
     async fn commit(&self, level: CommitLevel) {
         record_oplog_call("commit");
         match level {
@@ -170,6 +175,11 @@ impl Oplog for EphemeralOplog {
         self.target.length().await
     }
 
+    async fn take_added_bytes(&self) -> u64 {
+        // Ephemeral workers are never compacted
+        0
+    }
+
     async fn upload_payload(&self, data: &[u8]) -> Result<OplogPayload, String> {
         // Storing oplog payloads through the primary layer
         self.primary.upload_payload(data).await
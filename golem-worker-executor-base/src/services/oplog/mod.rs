@@ -26,6 +26,7 @@ pub use blob::BlobOplogArchiveService;
 use bytes::Bytes;
 pub use compressed::{CompressedOplogArchive, CompressedOplogArchiveService, CompressedOplogChunk};
 use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode};
+use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::{
     DurableFunctionType, OplogEntry, OplogIndex, OplogPayload, UpdateDescription,
 };
@@ -130,10 +131,13 @@ pub trait OplogService: Debug {
     /// Scans the oplog for all workers belonging to the given component, in a paginated way.
     ///
     /// Pages can be empty. This operation is slow and is not locking the oplog.
+    /// If a worker name prefix is given, the scan may skip the workers not having it, but
+    /// it can also return them.
     async fn scan_for_component(
         &self,
         account_id: &AccountId,
         component_id: &ComponentId,
+        worker_name_prefix: Option<&str>,
         cursor: ScanCursor,
         count: u64,
     ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError>;
@@ -175,6 +179,13 @@ pub trait Oplog: Any + Debug {
     /// This should only be called _after_ `append` succeeded in the layer below this one
     async fn drop_prefix(&self, last_dropped_id: OplogIndex);
 
+    /// Drops the entries in the closed range `[first_dropped_id, last_dropped_id]`, keeping
+    /// the entries before and after it
+    ///
+    /// Used to truncate the history of a worker made obsolete by a snapshot, which is never
+    /// read again during recovery.
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex);
+
     /// Commits the buffered entries to the oplog
     async fn commit(&self, level: CommitLevel);
 
@@ -193,6 +204,12 @@ pub trait Oplog: Any + Debug {
     /// Gets the total number of entries in the oplog
     async fn length(&self) -> u64;
 
+    /// Returns the serialized size of the entries added since the previous call, and resets it
+    ///
+    /// Used to track the growth of the oplog for compaction without serializing the entries
+    /// again.
+    async fn take_added_bytes(&self) -> u64;
+
     /// Adds an entry to the oplog and immediately commits it
     async fn add_and_commit(&self, entry: OplogEntry) -> OplogIndex {
         self.add(entry).await;
@@ -263,6 +280,7 @@ pub trait OplogOps: Oplog {
         function_name: String,
         request: &R,
         idempotency_key: IdempotencyKey,
+        invocation_context: InvocationContextStack,
     ) -> Result<OplogEntry, String> {
         let serialized_request = serialize(request)?.to_vec();
 
@@ -272,6 +290,7 @@ pub trait OplogOps: Oplog {
             function_name,
             request: payload,
             idempotency_key,
+            invocation_context,
         };
         self.add(entry.clone()).await;
         Ok(entry)
@@ -314,6 +333,9 @@ pub trait OplogOps: Oplog {
             OplogEntry::ImportedFunctionInvoked { response, .. } => {
                 Ok(Some(self.download_payload(response).await?))
             }
+            OplogEntry::ExportedFunctionInvokedV1 { request, .. } => {
+                Ok(Some(self.download_payload(request).await?))
+            }
             OplogEntry::ExportedFunctionInvoked { request, .. } => {
                 Ok(Some(self.download_payload(request).await?))
             }
@@ -63,6 +63,7 @@ pub trait OplogArchiveService: Debug {
         &self,
         account_id: &AccountId,
         component_id: &ComponentId,
+        worker_name_prefix: Option<&str>,
         cursor: ScanCursor,
         count: u64,
     ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError>;
@@ -389,6 +390,7 @@ impl OplogService for MultiLayerOplogService {
         &self,
         account_id: &AccountId,
         component_id: &ComponentId,
+        worker_name_prefix: Option<&str>,
         cursor: ScanCursor,
         count: u64,
     ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError> {
@@ -396,7 +398,7 @@ impl OplogService for MultiLayerOplogService {
             0 => {
                 let (new_cursor, ids) = self
                     .primary
-                    .scan_for_component(account_id, component_id, cursor, count)
+                    .scan_for_component(account_id, component_id, worker_name_prefix, cursor, count)
                     .await?;
                 if new_cursor.is_active_layer_finished() {
                     // Continuing with the first lower layer
@@ -414,7 +416,7 @@ impl OplogService for MultiLayerOplogService {
             }
             layer if layer <= self.lower.len().get() => {
                 let (new_cursor, ids) = self.lower[layer - 1]
-                    .scan_for_component(account_id, component_id, cursor, count)
+                    .scan_for_component(account_id, component_id, worker_name_prefix, cursor, count)
                     .await?;
                 if new_cursor.is_active_layer_finished() && (layer + 1) <= self.lower.len().get() {
                     // Continuing with the next lower layer
@@ -709,6 +711,18 @@ impl Oplog for MultiLayerOplog {
         self.primary_length.store(new_length, Ordering::Release);
     }
 
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) {
+        // Only the primary layer is truncated, the entries already transferred to the lower
+        // layers are kept in their archives
+        self.primary
+            .drop_range(first_dropped_id, last_dropped_id)
+            .await;
+        let new_length = self.primary.length().await;
+        let old_length = self.primary_length.load(Ordering::Acquire);
+        let new_length = min(new_length, old_length);
+        self.primary_length.store(new_length, Ordering::Release);
+    }
+
     async fn commit(&self, level: CommitLevel) {
         self.primary.commit(level).await;
         let count = self.primary_length.load(Ordering::Acquire);
@@ -750,6 +764,10 @@ impl Oplog for MultiLayerOplog {
         total_length
     }
 
+    async fn take_added_bytes(&self) -> u64 {
+        self.primary.take_added_bytes().await
+    }
+
     async fn upload_payload(&self, data: &[u8]) -> Result<OplogPayload, String> {
         self.primary.upload_payload(data).await
     }
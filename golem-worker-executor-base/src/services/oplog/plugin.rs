@@ -205,6 +205,7 @@ impl<Ctx: WorkerCtx> OplogProcessorPlugin for PerExecutorOplogProcessorPlugin<Ct
                 &running_plugin.owned_worker_id,
                 None,
                 None,
+                None,
                 Some(running_plugin.component_version),
                 None,
             )
@@ -538,11 +539,12 @@ impl<T: GolemTypes> OplogService for ForwardingOplogService<T> {
         &self,
         account_id: &AccountId,
         component_id: &ComponentId,
+        worker_name_prefix: Option<&str>,
         cursor: ScanCursor,
         count: u64,
     ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError> {
         self.inner
-            .scan_for_component(account_id, component_id, cursor, count)
+            .scan_for_component(account_id, component_id, worker_name_prefix, cursor, count)
             .await
     }
 
@@ -650,6 +652,12 @@ impl<T: GolemTypes> Oplog for ForwardingOplog<T> {
         self.inner.drop_prefix(last_dropped_id).await
     }
 
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) {
+        self.inner
+            .drop_range(first_dropped_id, last_dropped_id)
+            .await
+    }
+
     async fn commit(&self, level: CommitLevel) {
         let mut state = self.state.lock().await;
         self.inner.commit(level).await;
@@ -675,6 +683,10 @@ impl<T: GolemTypes> Oplog for ForwardingOplog<T> {
         self.inner.length().await
     }
 
+    async fn take_added_bytes(&self) -> u64 {
+        self.inner.take_added_bytes().await
+    }
+
     async fn upload_payload(&self, data: &[u8]) -> Result<OplogPayload, String> {
         self.inner.upload_payload(data).await
     }
@@ -24,13 +24,14 @@ use golem_common::model::oplog::{OplogEntry, OplogIndex, OplogPayload, PayloadId
 use golem_common::model::{
     AccountId, ComponentId, OwnedWorkerId, ScanCursor, WorkerId, WorkerMetadata,
 };
+use golem_common::serialization::serialize;
 use golem_service_base::storage::blob::{BlobStorage, BlobStorageNamespace};
 use std::collections::{BTreeMap, VecDeque};
 use std::fmt::{Debug, Formatter};
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
-use tracing::error;
+use tracing::{error, info_span, Instrument};
 
 /// The primary oplog service implementation, suitable for direct use (top level of a multi-layered setup).
 ///
@@ -74,8 +75,18 @@ impl PrimaryOplogService {
         worker_id.to_redis_key()
     }
 
-    pub fn key_pattern(component_id: &ComponentId) -> String {
-        format!("{}*", component_id.0)
+    pub fn key_pattern(component_id: &ComponentId, worker_name_prefix: Option<&str>) -> String {
+        match worker_name_prefix {
+            // Prefixes with characters which are wildcards in some of the indexed storages
+            // are not narrowing the scan, the scanned workers are filtered by name anyway
+            Some(prefix)
+                if !prefix.is_empty()
+                    && !prefix.contains(['*', '?', '[', ']', '\\', '^', '%', '_']) =>
+            {
+                format!("{}:{}*", component_id.0, prefix)
+            }
+            _ => format!("{}*", component_id.0),
+        }
     }
 
     pub fn get_worker_id_from_key(key: &str, component_id: &ComponentId) -> WorkerId {
@@ -300,6 +311,7 @@ impl OplogService for PrimaryOplogService {
         &self,
         account_id: &AccountId,
         component_id: &ComponentId,
+        worker_name_prefix: Option<&str>,
         cursor: ScanCursor,
         count: u64,
     ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError> {
@@ -310,7 +322,7 @@ impl OplogService for PrimaryOplogService {
             .with("oplog", "scan")
             .scan(
                 IndexedStorageNamespace::OpLog,
-                &Self::key_pattern(component_id),
+                &Self::key_pattern(component_id, worker_name_prefix),
                 cursor.cursor,
                 count,
             )
@@ -444,6 +456,7 @@ impl PrimaryOplog {
                 max_payload_size,
                 key: key.clone(),
                 buffer: VecDeque::new(),
+                added_bytes: 0,
                 last_committed_idx: last_oplog_idx,
                 last_oplog_idx,
                 owned_worker_id,
@@ -461,21 +474,23 @@ struct PrimaryOplogState {
     max_operations_before_commit: u64,
     max_payload_size: usize,
     key: String,
-    buffer: VecDeque<OplogEntry>,
+    // The entries waiting to be committed, serialized once when added
+    buffer: VecDeque<Bytes>,
+    added_bytes: u64,
     last_oplog_idx: OplogIndex,
     last_committed_idx: OplogIndex,
     owned_worker_id: OwnedWorkerId,
 }
 
 impl PrimaryOplogState {
-    async fn append(&mut self, entries: &[OplogEntry]) {
+    async fn append(&mut self, entries: &[Bytes]) {
         record_oplog_call("append");
 
         for entry in entries {
             let oplog_idx = self.last_committed_idx.next();
             self.indexed_storage
                 .with_entity("oplog", "append", "entry")
-                .append(
+                .append_raw(
                     IndexedStorageNamespace::OpLog,
                     &self.key,
                     oplog_idx.into(),
@@ -495,6 +510,10 @@ impl PrimaryOplogState {
     async fn add(&mut self, entry: OplogEntry) {
         record_oplog_call("add");
 
+        let entry = serialize(&entry).unwrap_or_else(|err| {
+            panic!("failed to serialize oplog entry for {}: {err}", self.key)
+        });
+        self.added_bytes += entry.len() as u64;
         self.buffer.push_back(entry);
         if self.buffer.len() > self.max_operations_before_commit as usize {
             self.commit().await;
@@ -505,8 +524,15 @@ impl PrimaryOplogState {
     async fn commit(&mut self) {
         record_oplog_call("commit");
 
-        let entries = self.buffer.drain(..).collect::<Vec<OplogEntry>>();
-        self.append(&entries).await
+        let entries = self.buffer.drain(..).collect::<Vec<Bytes>>();
+        if !entries.is_empty() {
+            let span = info_span!(
+                "oplog_write",
+                worker_id = %self.owned_worker_id.worker_id,
+                entries = entries.len()
+            );
+            self.append(&entries).instrument(span).await
+        }
     }
 
     async fn wait_for_replicas(&self, replicas: u8, timeout: Duration) -> bool {
@@ -578,6 +604,26 @@ impl PrimaryOplogState {
             });
     }
 
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) {
+        record_oplog_call("drop_range");
+
+        self.indexed_storage
+            .with("oplog", "drop_range")
+            .drop_range(
+                IndexedStorageNamespace::OpLog,
+                &self.key,
+                first_dropped_id.into(),
+                last_dropped_id.into(),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to drop range for {} in indexed storage: {err}",
+                    self.key
+                )
+            });
+    }
+
     async fn length(&self) -> u64 {
         record_oplog_call("length");
 
@@ -631,6 +677,13 @@ impl Oplog for PrimaryOplog {
         }
     }
 
+    async fn drop_range(&self, first_dropped_id: OplogIndex, last_dropped_id: OplogIndex) {
+        let mut state = self.state.lock().await;
+        // Making sure the buffered entries are not added back after dropping them
+        state.commit().await;
+        state.drop_range(first_dropped_id, last_dropped_id).await;
+    }
+
     async fn commit(&self, _level: CommitLevel) {
         let mut state = self.state.lock().await;
         state.commit().await
@@ -657,6 +710,11 @@ impl Oplog for PrimaryOplog {
         state.length().await
     }
 
+    async fn take_added_bytes(&self) -> u64 {
+        let mut state = self.state.lock().await;
+        std::mem::take(&mut state.added_bytes)
+    }
+
     async fn upload_payload(&self, data: &[u8]) -> Result<OplogPayload, String> {
         let (blob_storage, owned_worker_id, max_length) = {
             let state = self.state.lock().await;
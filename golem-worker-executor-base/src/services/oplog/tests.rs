@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::RwLock;
 use std::time::Instant;
 use test_r::{flaky, test, test_dep};
@@ -23,6 +23,7 @@ use tracing::{debug, info};
 use uuid::Uuid;
 
 use golem_common::config::RedisConfig;
+use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::WorkerError;
 use golem_common::model::regions::OplogRegion;
 use golem_common::model::{ComponentId, ComponentType, WorkerStatusRecord};
@@ -81,12 +82,36 @@ pub fn rounded(entry: OplogEntry) -> OplogEntry {
             component_size,
             initial_total_linear_memory_size,
         },
+        OplogEntry::CreateV2 {
+            timestamp,
+            worker_id,
+            component_version,
+            args,
+            env,
+            account_id,
+            parent,
+            component_size,
+            initial_total_linear_memory_size,
+            initial_active_plugins,
+        } => OplogEntry::CreateV2 {
+            timestamp: rounded_ts(timestamp),
+            worker_id,
+            component_version,
+            args,
+            env,
+            account_id,
+            parent,
+            component_size,
+            initial_total_linear_memory_size,
+            initial_active_plugins,
+        },
         OplogEntry::Create {
             timestamp,
             worker_id,
             component_version,
             args,
             env,
+            wasi_config_vars,
             account_id,
             parent,
             component_size,
@@ -98,6 +123,7 @@ pub fn rounded(entry: OplogEntry) -> OplogEntry {
             component_version,
             args,
             env,
+            wasi_config_vars,
             account_id,
             parent,
             component_size,
@@ -128,16 +154,29 @@ pub fn rounded(entry: OplogEntry) -> OplogEntry {
             response,
             wrapped_function_type,
         },
+        OplogEntry::ExportedFunctionInvokedV1 {
+            timestamp,
+            function_name,
+            request,
+            idempotency_key,
+        } => OplogEntry::ExportedFunctionInvokedV1 {
+            timestamp: rounded_ts(timestamp),
+            function_name,
+            request,
+            idempotency_key,
+        },
         OplogEntry::ExportedFunctionInvoked {
             timestamp,
             function_name,
             request,
             idempotency_key,
+            invocation_context,
         } => OplogEntry::ExportedFunctionInvoked {
             timestamp: rounded_ts(timestamp),
             function_name,
             request,
             idempotency_key,
+            invocation_context,
         },
         OplogEntry::ExportedFunctionCompleted {
             timestamp,
@@ -295,6 +334,10 @@ pub fn rounded(entry: OplogEntry) -> OplogEntry {
             timestamp: rounded_ts(timestamp),
             idempotency_key,
         },
+        OplogEntry::ChangeLabels { timestamp, labels } => OplogEntry::ChangeLabels {
+            timestamp: rounded_ts(timestamp),
+            labels,
+        },
     }
 }
 
@@ -468,6 +511,7 @@ async fn entries_with_small_payload(_tracing: &Tracing) {
                 "f2".to_string(),
                 &"request".to_string(),
                 IdempotencyKey::fresh(),
+                InvocationContextStack::fresh(),
             )
             .await
             .unwrap(),
@@ -587,6 +631,7 @@ async fn entries_with_large_payload(_tracing: &Tracing) {
                 "f2".to_string(),
                 &large_payload2,
                 IdempotencyKey::fresh(),
+                InvocationContextStack::fresh(),
             )
             .await
             .unwrap(),
@@ -1633,6 +1678,7 @@ async fn multilayer_scan_for_component(_tracing: &Tracing) {
             1,
             Vec::new(),
             Vec::new(),
+            BTreeMap::new(),
             account_id.clone(),
             None,
             100,
@@ -1682,7 +1728,7 @@ async fn multilayer_scan_for_component(_tracing: &Tracing) {
     let page_size = 10;
     loop {
         let (new_cursor, ids) = oplog_service
-            .scan_for_component(&account_id, &component_id, cursor, page_size)
+            .scan_for_component(&account_id, &component_id, None, cursor, page_size)
             .await
             .unwrap();
         debug!("Got {} elements, new cursor is {}", ids.len(), new_cursor);
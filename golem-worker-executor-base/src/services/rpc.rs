@@ -14,7 +14,9 @@
 
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::file_loader::FileLoader;
 use crate::error::GolemError;
@@ -37,7 +39,7 @@ use crate::workerctx::WorkerCtx;
 use async_trait::async_trait;
 use bincode::{Decode, Encode};
 use golem_common::model::invocation_context::InvocationContextStack;
-use golem_common::model::{IdempotencyKey, OwnedWorkerId, TargetWorkerId, WorkerId};
+use golem_common::model::{IdempotencyKey, OwnedWorkerId, TargetWorkerId, Timestamp, WorkerId};
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use golem_wasm_rpc::WitValue;
 use tokio::runtime::Handle;
@@ -83,6 +85,7 @@ pub enum RpcError {
     Denied { details: String },
     NotFound { details: String },
     RemoteInternalError { details: String },
+    DeadlineExceeded { deadline: Timestamp },
 }
 
 impl Display for RpcError {
@@ -94,12 +97,35 @@ impl Display for RpcError {
             RpcError::RemoteInternalError { details } => {
                 write!(f, "Remote internal error: {}", details)
             }
+            RpcError::DeadlineExceeded { deadline } => {
+                write!(f, "Deadline exceeded: {}", deadline)
+            }
         }
     }
 }
 
 impl std::error::Error for RpcError {}
 
+// Awaits the result of an RPC invocation until the deadline of the invocation making it, if any
+pub async fn await_until_deadline(
+    deadline: Option<Timestamp>,
+    invocation: impl Future<Output = Result<TypeAnnotatedValue, RpcError>>,
+) -> Result<TypeAnnotatedValue, RpcError> {
+    match deadline {
+        Some(deadline) => {
+            let timeout = Duration::from_millis(
+                deadline
+                    .to_millis()
+                    .saturating_sub(Timestamp::now_utc().to_millis()),
+            );
+            tokio::time::timeout(timeout, invocation)
+                .await
+                .unwrap_or(Err(RpcError::DeadlineExceeded { deadline }))
+        }
+        None => invocation.await,
+    }
+}
+
 impl From<tonic::transport::Error> for RpcError {
     fn from(value: tonic::transport::Error) -> Self {
         Self::ProtocolError {
@@ -581,6 +607,7 @@ impl<Ctx: WorkerCtx> Rpc for DirectWorkerInvocationRpc<Ctx> {
                 Some(self_args.to_vec()),
                 Some(self_env.to_vec()),
                 None,
+                None,
                 Some(self_worker_id.clone()),
             )
             .await?;
@@ -637,6 +664,7 @@ impl<Ctx: WorkerCtx> Rpc for DirectWorkerInvocationRpc<Ctx> {
                 Some(self_args.to_vec()),
                 Some(self_env.to_vec()),
                 None,
+                None,
                 Some(self_worker_id.clone()),
             )
             .await?;
@@ -672,3 +700,34 @@ impl<Ctx: WorkerCtx> Rpc for DirectWorkerInvocationRpc<Ctx> {
 }
 
 impl RpcDemand for () {}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+
+    #[test]
+    async fn rpc_invocations_are_cut_short_at_the_deadline() {
+        let passed_deadline = Timestamp::from(Timestamp::now_utc().to_millis() - 1000);
+        let pending = std::future::pending::<Result<TypeAnnotatedValue, RpcError>>();
+
+        let result = await_until_deadline(Some(passed_deadline), pending).await;
+
+        assert_eq!(
+            result,
+            Err(RpcError::DeadlineExceeded {
+                deadline: passed_deadline
+            })
+        );
+    }
+
+    #[test]
+    async fn rpc_invocations_without_deadline_are_not_cut_short() {
+        let completed = async { Ok(TypeAnnotatedValue::Bool(true)) };
+
+        let result = await_until_deadline(None, completed).await;
+
+        assert_eq!(result, Ok(TypeAnnotatedValue::Bool(true)));
+    }
+}
@@ -79,7 +79,7 @@ impl<Ctx: WorkerCtx> SchedulerWorkerAccess for Arc<dyn WorkerActivator<Ctx> + Se
         owned_worker_id: &OwnedWorkerId,
     ) -> Result<Arc<dyn Oplog + Send + Sync>, GolemError> {
         let worker = self
-            .get_or_create_suspended(owned_worker_id, None, None, None, None)
+            .get_or_create_suspended(owned_worker_id, None, None, None, None, None)
             .await?;
         Ok(worker.oplog())
     }
@@ -93,7 +93,7 @@ impl<Ctx: WorkerCtx> SchedulerWorkerAccess for Arc<dyn WorkerActivator<Ctx> + Se
         invocation_context: InvocationContextStack,
     ) -> Result<(), GolemError> {
         let worker = self
-            .get_or_create_suspended(owned_worker_id, None, None, None, None)
+            .get_or_create_suspended(owned_worker_id, None, None, None, None, None)
             .await?;
 
         worker
@@ -429,6 +429,7 @@ mod tests {
     use chrono::DateTime;
 
     use crate::error::GolemError;
+    use crate::services::golem_config::InvocationLogsConfig;
     use crate::services::oplog::{Oplog, OplogService, PrimaryOplogService};
     use crate::services::promise::PromiseServiceMock;
     use crate::services::scheduler::{
@@ -508,7 +509,13 @@ mod tests {
         shard_service: Arc<dyn ShardService + Send + Sync>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
     ) -> Arc<dyn WorkerService + Send + Sync> {
-        Arc::new(DefaultWorkerService::new(kvs, shard_service, oplog_service))
+        Arc::new(DefaultWorkerService::new(
+            kvs,
+            Arc::new(InMemoryIndexedStorage::new()),
+            shard_service,
+            oplog_service,
+            InvocationLogsConfig::default(),
+        ))
     }
 
     #[test]
@@ -14,6 +14,7 @@
 
 use std::collections::HashSet;
 use std::convert::identity;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 
 use itertools::Itertools;
@@ -34,10 +35,15 @@ pub trait ShardService {
     fn revoke_shards(&self, shard_ids: &HashSet<ShardId>) -> Result<(), GolemError>;
     fn current_assignment(&self) -> Result<ShardAssignment, GolemError>;
     fn try_get_current_assignment(&self) -> Option<ShardAssignment>;
+    /// Revokes all the assigned shards and rejects any further assignment, returning the
+    /// revoked shards
+    fn drain(&self) -> Result<HashSet<ShardId>, GolemError>;
+    fn is_draining(&self) -> bool;
 }
 
 pub struct ShardServiceDefault {
     shard_assignment: Arc<RwLock<Option<ShardAssignment>>>,
+    draining: AtomicBool,
 }
 
 impl Default for ShardServiceDefault {
@@ -50,6 +56,7 @@ impl ShardServiceDefault {
     pub fn new() -> Self {
         Self {
             shard_assignment: Arc::new(RwLock::new(None)),
+            draining: AtomicBool::new(false),
         }
     }
 
@@ -82,6 +89,10 @@ impl ShardService for ShardServiceDefault {
     }
 
     fn assign_shards(&self, shard_ids: &HashSet<ShardId>) -> Result<(), GolemError> {
+        if self.is_draining() {
+            return Err(GolemError::WorkerExecutorDraining);
+        }
+
         self.with_write_shard_assignment(|shard_assignment| match shard_assignment {
             Some(shard_assignment) => {
                 debug!(
@@ -150,6 +161,28 @@ impl ShardService for ShardServiceDefault {
     fn try_get_current_assignment(&self) -> Option<ShardAssignment> {
         self.shard_assignment.read().unwrap().clone()
     }
+
+    fn drain(&self) -> Result<HashSet<ShardId>, GolemError> {
+        self.draining.store(true, Ordering::Release);
+
+        self.with_write_shard_assignment(|shard_assignment| match shard_assignment {
+            Some(shard_assignment) => {
+                let shard_ids = shard_assignment.shard_ids.clone();
+                debug!(
+                    shard_ids_to_revoke = shard_ids.iter().join(", "),
+                    "ShardService.drain"
+                );
+                shard_assignment.revoke_shards(&shard_ids);
+                record_assigned_shard_count(0);
+                Ok(shard_ids)
+            }
+            None => Err(sharding_not_ready_error()),
+        })
+    }
+
+    fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
 }
 
 fn sharding_not_ready_error() -> GolemError {
@@ -157,3 +190,34 @@ fn sharding_not_ready_error() -> GolemError {
         details: "Sharding is not ready".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn draining_revokes_all_shards_and_rejects_new_assignments() {
+        let shard_service = ShardServiceDefault::new();
+        shard_service.register(4, &HashSet::from([ShardId::new(0), ShardId::new(1)]));
+
+        let released = shard_service.drain().unwrap();
+
+        assert_eq!(released, HashSet::from([ShardId::new(0), ShardId::new(1)]));
+        assert!(shard_service.is_draining());
+        assert!(shard_service
+            .current_assignment()
+            .unwrap()
+            .shard_ids
+            .is_empty());
+        assert_eq!(
+            shard_service.assign_shards(&HashSet::from([ShardId::new(2)])),
+            Err(GolemError::WorkerExecutorDraining)
+        );
+        assert!(shard_service
+            .current_assignment()
+            .unwrap()
+            .shard_ids
+            .is_empty());
+    }
+}
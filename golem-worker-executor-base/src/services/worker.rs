@@ -12,25 +12,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::{Arc, RwLock};
 
 use crate::error::GolemError;
 use crate::metrics::workers::record_worker_call;
 use crate::model::ExecutionStatus;
+use crate::services::golem_config::InvocationLogsConfig;
 use crate::services::oplog::OplogService;
 use crate::services::shard::ShardService;
+use crate::storage::indexed::{IndexedStorage, IndexedStorageLabelledApi, IndexedStorageNamespace};
 use crate::storage::keyvalue::{
     KeyValueStorage, KeyValueStorageLabelledApi, KeyValueStorageNamespace,
 };
 use async_trait::async_trait;
-use golem_common::model::oplog::{OplogEntry, OplogIndex};
+use bincode::{Decode, Encode};
+use dashmap::DashMap;
+use golem_common::model::invocation_context::TraceId;
+use golem_common::model::oplog::{LogLevel, OplogEntry, OplogIndex};
+use golem_common::model::public_oplog::LogParameters;
 use golem_common::model::regions::DeletedRegions;
 use golem_common::model::{
-    ComponentType, OwnedWorkerId, ShardId, Timestamp, WorkerId, WorkerMetadata, WorkerStatus,
-    WorkerStatusRecord, WorkerStatusRecordExtensions,
+    AccountId, ComponentDeployment, ComponentId, ComponentType, ComponentVersion, IdempotencyKey,
+    OplogCompactionStatus, OwnedWorkerId, ScanCursor, ShardId, Timestamp, WorkerEvent, WorkerId,
+    WorkerMetadata, WorkerStatus, WorkerStatusRecord, WorkerStatusRecordExtensions,
+    WorkerStatusSummary,
 };
 use tracing::{debug, warn};
 
+const STATUS_SUMMARY_BACKFILL_PAGE_SIZE: u64 = 100;
+
 /// Service for persisting the current set of Golem workers represented by their metadata
 #[async_trait]
 pub trait WorkerService {
@@ -54,25 +65,121 @@ pub trait WorkerService {
         status_value: &WorkerStatusRecord,
         component_type: ComponentType,
     );
+
+    /// Gets the number of workers per status of a component, maintained on every status change.
+    ///
+    /// The first time the summary of a component is requested, or when `backfill` is set, the
+    /// existing workers of the component missing from the summary are added to it.
+    async fn get_status_summary(
+        &self,
+        account_id: &AccountId,
+        component_id: &ComponentId,
+        backfill: bool,
+    ) -> Result<WorkerStatusSummary, GolemError>;
+
+    /// Records that a worker has been invoked as part of a trace, so the invocations of a trace
+    /// can be found without scanning the oplogs of all the workers
+    async fn add_to_trace(&self, trace_id: &TraceId, owned_worker_id: &OwnedWorkerId);
+
+    /// Gets the workers invoked as part of a trace
+    async fn get_trace_workers(&self, trace_id: &TraceId) -> Vec<OwnedWorkerId>;
+
+    /// Stores the stdout, stderr and log entries emitted by an invocation, keeping only the most
+    /// recent entries of the worker
+    async fn add_invocation_logs(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        entries: Vec<InvocationLogEntry>,
+    );
+
+    /// Gets the stored stdout, stderr and log entries emitted by an invocation
+    async fn get_invocation_logs(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: &IdempotencyKey,
+    ) -> Vec<InvocationLogEntry>;
+}
+
+/// A stdout, stderr or log entry emitted by an invocation
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct InvocationLogEntry {
+    pub idempotency_key: IdempotencyKey,
+    pub timestamp: Timestamp,
+    pub level: LogLevel,
+    pub context: String,
+    pub message: String,
+}
+
+impl InvocationLogEntry {
+    /// Converts a log event of an invocation, cutting its message to `max_message_size` bytes
+    pub fn from_event(
+        idempotency_key: &IdempotencyKey,
+        event: &WorkerEvent,
+        max_message_size: usize,
+    ) -> Option<Self> {
+        match event.as_oplog_entry()? {
+            OplogEntry::Log {
+                timestamp,
+                level,
+                context,
+                mut message,
+            } => {
+                if message.len() > max_message_size {
+                    let mut end = max_message_size;
+                    while !message.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    message.truncate(end);
+                }
+                Some(Self {
+                    idempotency_key: idempotency_key.clone(),
+                    timestamp,
+                    level,
+                    context,
+                    message,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<InvocationLogEntry> for LogParameters {
+    fn from(value: InvocationLogEntry) -> Self {
+        Self {
+            timestamp: value.timestamp,
+            level: value.level,
+            context: value.context,
+            message: value.message,
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct DefaultWorkerService {
     key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+    indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
     shard_service: Arc<dyn ShardService + Send + Sync>,
     oplog_service: Arc<dyn OplogService + Send + Sync>,
+    invocation_logs: InvocationLogsConfig,
+    last_deployments: Arc<DashMap<ComponentId, ComponentVersion>>,
 }
 
 impl DefaultWorkerService {
     pub fn new(
         key_value_storage: Arc<dyn KeyValueStorage + Send + Sync>,
+        indexed_storage: Arc<dyn IndexedStorage + Send + Sync>,
         shard_service: Arc<dyn ShardService + Send + Sync>,
         oplog_service: Arc<dyn OplogService + Send + Sync>,
+        invocation_logs: InvocationLogsConfig,
     ) -> Self {
         Self {
             key_value_storage,
+            indexed_storage,
             shard_service,
             oplog_service,
+            invocation_logs,
+            last_deployments: Arc::new(DashMap::new()),
         }
     }
 
@@ -106,6 +213,203 @@ impl DefaultWorkerService {
     fn running_in_shard_key(shard_id: &ShardId) -> String {
         format!("worker:running_in_shard:{shard_id}")
     }
+
+    fn status_summary_key(component_id: &ComponentId, status: &WorkerStatus) -> String {
+        format!("worker:status_summary:{component_id}:{status}")
+    }
+
+    fn last_deployment_key(component_id: &ComponentId) -> String {
+        format!("worker:last_deployment:{component_id}")
+    }
+
+    fn trace_key(trace_id: &TraceId) -> String {
+        format!("worker:trace:{trace_id}")
+    }
+
+    // The log entries of all the invocations of a worker are in one index, so the most recent
+    // ones can be kept by dropping its prefix
+    fn invocation_logs_key(worker_id: &WorkerId) -> String {
+        worker_id.to_redis_key()
+    }
+
+    fn status_summary_keys(component_id: &ComponentId) -> Vec<String> {
+        WorkerStatus::ALL
+            .iter()
+            .map(|status| Self::status_summary_key(component_id, status))
+            .collect()
+    }
+
+    fn status_summary_index(status: &WorkerStatus) -> usize {
+        WorkerStatus::ALL
+            .iter()
+            .position(|s| s == status)
+            .expect("WorkerStatus::ALL contains every status")
+    }
+
+    fn status_summary_backfilled_key(component_id: &ComponentId) -> String {
+        format!("worker:status_summary_backfilled:{component_id}")
+    }
+
+    /// Updates the per-component status summary projection with a worker's status change.
+    ///
+    /// Every worker is a member of exactly one status set of its component, so the summary can be
+    /// computed from the set sizes without enumerating the workers. The worker is moved between
+    /// the sets in a single atomic operation, so concurrent updates cannot leave it in two sets.
+    async fn project_status_change(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        current: Option<&WorkerStatusRecord>,
+    ) {
+        let component_id = &owned_worker_id.worker_id.component_id;
+
+        self.key_value_storage
+            .with_entity("worker", "project_status", "worker_id")
+            .move_to_set(
+                KeyValueStorageNamespace::Worker,
+                &Self::status_summary_keys(component_id),
+                current.map(|record| Self::status_summary_index(&record.status)),
+                owned_worker_id,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to update the status summary in KV storage: {err}")
+            });
+
+        if let Some(current) = current {
+            self.record_deployment(component_id, current.component_version)
+                .await;
+        }
+    }
+
+    /// Records the component version a worker was created with or updated to, if it is newer
+    /// than the last one. The last known versions are cached, so the storage is only accessed
+    /// when a component gets a new version.
+    async fn record_deployment(
+        &self,
+        component_id: &ComponentId,
+        component_version: ComponentVersion,
+    ) {
+        let is_known = self
+            .last_deployments
+            .get(component_id)
+            .is_some_and(|last| *last >= component_version);
+
+        if !is_known {
+            let last_deployment: Option<ComponentDeployment> = self
+                .key_value_storage
+                .with_entity("worker", "project_status", "last_deployment")
+                .get(
+                    KeyValueStorageNamespace::Worker,
+                    &Self::last_deployment_key(component_id),
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to get last deployment from KV storage: {err}")
+                });
+
+            let last_version = match last_deployment {
+                Some(last_deployment) if last_deployment.component_version >= component_version => {
+                    last_deployment.component_version
+                }
+                _ => {
+                    self.key_value_storage
+                        .with_entity("worker", "project_status", "last_deployment")
+                        .set(
+                            KeyValueStorageNamespace::Worker,
+                            &Self::last_deployment_key(component_id),
+                            &ComponentDeployment {
+                                component_version,
+                                timestamp: Timestamp::now_utc(),
+                            },
+                        )
+                        .await
+                        .unwrap_or_else(|err| {
+                            panic!("failed to set last deployment in KV storage: {err}")
+                        });
+                    component_version
+                }
+            };
+
+            self.last_deployments
+                .entry(component_id.clone())
+                .and_modify(|last| *last = (*last).max(last_version))
+                .or_insert(last_version);
+        }
+    }
+
+    /// Adds the workers of a component which are not part of its status summary yet, such as
+    /// the ones created before the summaries were maintained.
+    ///
+    /// A worker is only added if it is not in any of the status sets, so a status change
+    /// projected while the backfill is running is never overwritten by the status read here.
+    async fn backfill_status_summary(
+        &self,
+        account_id: &AccountId,
+        component_id: &ComponentId,
+    ) -> Result<(), GolemError> {
+        let keys = Self::status_summary_keys(component_id);
+        let mut cursor = ScanCursor::default();
+        let mut added = 0;
+
+        loop {
+            let (next_cursor, owned_worker_ids) = self
+                .oplog_service
+                .scan_for_component(
+                    account_id,
+                    component_id,
+                    None,
+                    cursor,
+                    STATUS_SUMMARY_BACKFILL_PAGE_SIZE,
+                )
+                .await?;
+
+            for owned_worker_id in owned_worker_ids {
+                if let Some(metadata) = self.get(&owned_worker_id).await {
+                    let was_added = self
+                        .key_value_storage
+                        .with_entity("worker", "backfill_status_summary", "worker_id")
+                        .add_to_set_if_absent(
+                            KeyValueStorageNamespace::Worker,
+                            &keys,
+                            Self::status_summary_index(&metadata.last_known_status.status),
+                            &owned_worker_id,
+                        )
+                        .await
+                        .unwrap_or_else(|err| {
+                            panic!("failed to backfill the status summary in KV storage: {err}")
+                        });
+                    if was_added {
+                        added += 1;
+                    }
+                    self.record_deployment(
+                        component_id,
+                        metadata.last_known_status.component_version,
+                    )
+                    .await;
+                }
+            }
+
+            if next_cursor.is_finished() {
+                break;
+            }
+            cursor = next_cursor;
+        }
+
+        self.key_value_storage
+            .with_entity("worker", "backfill_status_summary", "backfilled")
+            .set(
+                KeyValueStorageNamespace::Worker,
+                &Self::status_summary_backfilled_key(component_id),
+                &Timestamp::now_utc(),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to mark the status summary as backfilled in KV storage: {err}")
+            });
+
+        debug!("Backfilled the status summary of component {component_id} with {added} workers");
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -125,6 +429,7 @@ impl WorkerService for DefaultWorkerService {
             worker_metadata.last_known_status.component_version,
             worker_metadata.args.clone(),
             worker_metadata.env.clone(),
+            worker_metadata.wasi_config_vars.clone(),
             worker_metadata.account_id.clone(),
             worker_metadata.parent.clone(),
             worker_metadata.last_known_status.component_size,
@@ -158,6 +463,9 @@ impl WorkerService for DefaultWorkerService {
                 .await
                 .unwrap_or_else(|err| panic!("failed to set worker status in KV storage: {err}"));
 
+            self.project_status_change(&owned_worker_id, Some(&worker_metadata.last_known_status))
+                .await;
+
             if worker_metadata.last_known_status.status == WorkerStatus::Running {
                 let shard_assignment = self.shard_service.current_assignment()?;
                 let shard_id =
@@ -193,8 +501,8 @@ impl WorkerService for DefaultWorkerService {
             .into_iter()
             .next();
 
-        match initial_oplog_entry {
-            None => None,
+        let mut details = match initial_oplog_entry {
+            None => return None,
             Some((
                 _,
                 OplogEntry::CreateV1 {
@@ -208,40 +516,57 @@ impl WorkerService for DefaultWorkerService {
                     component_size,
                     initial_total_linear_memory_size,
                 },
-            )) => {
-                let mut details = WorkerMetadata {
+            )) => WorkerMetadata {
+                worker_id,
+                args,
+                env,
+                wasi_config_vars: BTreeMap::new(),
+                account_id,
+                created_at: timestamp,
+                parent,
+                last_known_status: WorkerStatusRecord {
+                    component_version,
+                    component_size,
+                    total_linear_memory_size: initial_total_linear_memory_size,
+                    ..WorkerStatusRecord::default()
+                },
+            },
+            Some((
+                _,
+                OplogEntry::CreateV2 {
                     worker_id,
+                    component_version,
                     args,
                     env,
                     account_id,
-                    created_at: timestamp,
+                    timestamp,
                     parent,
-                    last_known_status: WorkerStatusRecord {
-                        component_version,
-                        component_size,
-                        total_linear_memory_size: initial_total_linear_memory_size,
-                        ..WorkerStatusRecord::default()
+                    component_size,
+                    initial_total_linear_memory_size,
+                    initial_active_plugins,
+                },
+            )) => WorkerMetadata {
+                worker_id,
+                args,
+                env,
+                wasi_config_vars: BTreeMap::new(),
+                account_id,
+                created_at: timestamp,
+                parent,
+                last_known_status: WorkerStatusRecord {
+                    component_version,
+                    component_size,
+                    total_linear_memory_size: initial_total_linear_memory_size,
+                    extensions: WorkerStatusRecordExtensions::Extension5 {
+                        active_plugins: initial_active_plugins,
+                        deleted_regions: DeletedRegions::new(),
+                        labels: BTreeMap::new(),
+                        compaction: OplogCompactionStatus::default(),
+                        fork: None,
                     },
-                };
-
-                let status_value: Option<WorkerStatusRecord> = self
-                    .key_value_storage
-                    .with_entity("worker", "get", "worker_status")
-                    .get(
-                        KeyValueStorageNamespace::Worker,
-                        &Self::status_key(&owned_worker_id.worker_id),
-                    )
-                    .await
-                    .unwrap_or_else(|err| {
-                        panic!("failed to get worker status for {owned_worker_id} from KV storage: {err}")
-                    });
-
-                if let Some(status) = status_value {
-                    details.last_known_status = status;
-                }
-
-                Some(details)
-            }
+                    ..WorkerStatusRecord::default()
+                },
+            },
             Some((
                 _,
                 OplogEntry::Create {
@@ -249,6 +574,7 @@ impl WorkerService for DefaultWorkerService {
                     component_version,
                     args,
                     env,
+                    wasi_config_vars,
                     account_id,
                     timestamp,
                     parent,
@@ -256,44 +582,28 @@ impl WorkerService for DefaultWorkerService {
                     initial_total_linear_memory_size,
                     initial_active_plugins,
                 },
-            )) => {
-                let mut details = WorkerMetadata {
-                    worker_id,
-                    args,
-                    env,
-                    account_id,
-                    created_at: timestamp,
-                    parent,
-                    last_known_status: WorkerStatusRecord {
-                        component_version,
-                        component_size,
-                        total_linear_memory_size: initial_total_linear_memory_size,
-                        extensions: WorkerStatusRecordExtensions::Extension2 {
-                            active_plugins: initial_active_plugins,
-                            deleted_regions: DeletedRegions::new(),
-                        },
-                        ..WorkerStatusRecord::default()
+            )) => WorkerMetadata {
+                worker_id,
+                args,
+                env,
+                wasi_config_vars,
+                account_id,
+                created_at: timestamp,
+                parent,
+                last_known_status: WorkerStatusRecord {
+                    component_version,
+                    component_size,
+                    total_linear_memory_size: initial_total_linear_memory_size,
+                    extensions: WorkerStatusRecordExtensions::Extension5 {
+                        active_plugins: initial_active_plugins,
+                        deleted_regions: DeletedRegions::new(),
+                        labels: BTreeMap::new(),
+                        compaction: OplogCompactionStatus::default(),
+                        fork: None,
                     },
-                };
-
-                let status_value: Option<WorkerStatusRecord> = self
-                    .key_value_storage
-                    .with_entity("worker", "get", "worker_status")
-                    .get(
-                        KeyValueStorageNamespace::Worker,
-                        &Self::status_key(&owned_worker_id.worker_id),
-                    )
-                    .await
-                    .unwrap_or_else(|err| {
-                        panic!("failed to get worker status for {owned_worker_id} from KV storage: {err}")
-                    });
-
-                if let Some(status) = status_value {
-                    details.last_known_status = status;
-                }
-
-                Some(details)
-            }
+                    ..WorkerStatusRecord::default()
+                },
+            },
             Some((_, entry)) => {
                 // This should never happen, but there were some issues previously causing a corrupt oplog
                 // leading to this state.
@@ -309,10 +619,11 @@ impl WorkerService for DefaultWorkerService {
                     "Unexpected initial oplog entry found, returning fake failed worker metadata"
                 );
                 let last_oplog_idx = self.oplog_service.get_last_index(owned_worker_id).await;
-                Some(WorkerMetadata {
+                return Some(WorkerMetadata {
                     worker_id: owned_worker_id.worker_id(),
                     args: vec![],
                     env: vec![],
+                    wasi_config_vars: BTreeMap::new(),
                     account_id: owned_worker_id.account_id(),
                     created_at: Timestamp::now_utc(),
                     parent: None,
@@ -321,9 +632,27 @@ impl WorkerService for DefaultWorkerService {
                         oplog_idx: last_oplog_idx,
                         ..WorkerStatusRecord::default()
                     },
-                })
+                });
             }
+        };
+
+        let status_value: Option<WorkerStatusRecord> = self
+            .key_value_storage
+            .with_entity("worker", "get", "worker_status")
+            .get(
+                KeyValueStorageNamespace::Worker,
+                &Self::status_key(&owned_worker_id.worker_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get worker status for {owned_worker_id} from KV storage: {err}")
+            });
+
+        if let Some(status) = status_value {
+            details.last_known_status = status;
         }
+
+        Some(details)
     }
 
     async fn get_running_workers_in_shards(&self) -> Vec<WorkerMetadata> {
@@ -342,9 +671,22 @@ impl WorkerService for DefaultWorkerService {
     async fn remove(&self, owned_worker_id: &OwnedWorkerId) {
         record_worker_call("remove");
 
+        self.project_status_change(owned_worker_id, None).await;
+
         self.oplog_service.delete(owned_worker_id).await;
         self.remove_cached_status(owned_worker_id).await;
 
+        self.indexed_storage
+            .with("worker", "remove")
+            .delete(
+                IndexedStorageNamespace::InvocationLogs,
+                &Self::invocation_logs_key(&owned_worker_id.worker_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to remove the invocation logs from indexed storage: {err}")
+            });
+
         let shard_assignment = self
             .shard_service
             .current_assignment()
@@ -400,6 +742,9 @@ impl WorkerService for DefaultWorkerService {
                 .await
                 .unwrap_or_else(|err| panic!("failed to set worker status in KV storage: {err}"));
 
+            self.project_status_change(owned_worker_id, Some(status_value))
+                .await;
+
             let shard_assignment = self
                 .shard_service
                 .current_assignment()
@@ -438,4 +783,312 @@ impl WorkerService for DefaultWorkerService {
             }
         }
     }
+
+    async fn get_status_summary(
+        &self,
+        account_id: &AccountId,
+        component_id: &ComponentId,
+        backfill: bool,
+    ) -> Result<WorkerStatusSummary, GolemError> {
+        record_worker_call("get_status_summary");
+
+        let backfilled = self
+            .key_value_storage
+            .with("worker", "get_status_summary")
+            .exists(
+                KeyValueStorageNamespace::Worker,
+                &Self::status_summary_backfilled_key(component_id),
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to check the status summary backfill in KV storage: {err}")
+            });
+        if backfill || !backfilled {
+            self.backfill_status_summary(account_id, component_id)
+                .await?;
+        }
+
+        let mut counts = Vec::new();
+        for status in WorkerStatus::ALL {
+            let count = self
+                .key_value_storage
+                .with_entity("worker", "get_status_summary", "worker_id")
+                .size_of_set(
+                    KeyValueStorageNamespace::Worker,
+                    &Self::status_summary_key(component_id, &status),
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to get the status summary from KV storage: {err}")
+                });
+            counts.push((status, count));
+        }
+
+        let last_deployment: Option<ComponentDeployment> = self
+            .key_value_storage
+            .with_entity("worker", "get_status_summary", "last_deployment")
+            .get(
+                KeyValueStorageNamespace::Worker,
+                &Self::last_deployment_key(component_id),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("failed to get last deployment from KV storage: {err}"));
+
+        Ok(WorkerStatusSummary::new(counts, last_deployment))
+    }
+
+    async fn add_to_trace(&self, trace_id: &TraceId, owned_worker_id: &OwnedWorkerId) {
+        record_worker_call("add_to_trace");
+
+        self.key_value_storage
+            .with_entity("worker", "add_to_trace", "worker_id")
+            .add_to_set(
+                KeyValueStorageNamespace::Worker,
+                &Self::trace_key(trace_id),
+                owned_worker_id,
+            )
+            .await
+            .unwrap_or_else(|err| panic!("failed to add worker to the trace in KV storage: {err}"));
+    }
+
+    async fn get_trace_workers(&self, trace_id: &TraceId) -> Vec<OwnedWorkerId> {
+        record_worker_call("get_trace_workers");
+
+        self.key_value_storage
+            .with_entity("worker", "get_trace_workers", "worker_id")
+            .members_of_set(KeyValueStorageNamespace::Worker, &Self::trace_key(trace_id))
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get the workers of the trace from KV storage: {err}")
+            })
+    }
+
+    async fn add_invocation_logs(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        entries: Vec<InvocationLogEntry>,
+    ) {
+        record_worker_call("add_invocation_logs");
+
+        if entries.is_empty() {
+            return;
+        }
+
+        let key = Self::invocation_logs_key(&owned_worker_id.worker_id);
+        let storage = self.indexed_storage.with_entity(
+            "worker",
+            "add_invocation_logs",
+            "invocation_log_entry",
+        );
+
+        let last_id = storage
+            .last_id(IndexedStorageNamespace::InvocationLogs, &key)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get the last invocation log entry from indexed storage: {err}")
+            })
+            .unwrap_or(0);
+
+        let mut id = last_id;
+        for entry in &entries {
+            id += 1;
+            storage
+                .append(IndexedStorageNamespace::InvocationLogs, &key, id, entry)
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to append invocation log entry to indexed storage: {err}")
+                });
+        }
+
+        let max_entries = self.invocation_logs.max_entries_per_worker;
+        if id > max_entries {
+            self.indexed_storage
+                .with("worker", "add_invocation_logs")
+                .drop_prefix(
+                    IndexedStorageNamespace::InvocationLogs,
+                    &key,
+                    id - max_entries,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to drop old invocation log entries from indexed storage: {err}")
+                });
+        }
+    }
+
+    async fn get_invocation_logs(
+        &self,
+        owned_worker_id: &OwnedWorkerId,
+        idempotency_key: &IdempotencyKey,
+    ) -> Vec<InvocationLogEntry> {
+        record_worker_call("get_invocation_logs");
+
+        let key = Self::invocation_logs_key(&owned_worker_id.worker_id);
+        let storage = self.indexed_storage.with_entity(
+            "worker",
+            "get_invocation_logs",
+            "invocation_log_entry",
+        );
+
+        let last_id = storage
+            .last_id(IndexedStorageNamespace::InvocationLogs, &key)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("failed to get the last invocation log entry from indexed storage: {err}")
+            });
+
+        match last_id {
+            Some(last_id) => storage
+                .read::<InvocationLogEntry>(
+                    IndexedStorageNamespace::InvocationLogs,
+                    &key,
+                    0,
+                    last_id,
+                )
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("failed to read invocation log entries from indexed storage: {err}")
+                })
+                .into_iter()
+                .map(|(_, entry)| entry)
+                .filter(|entry| &entry.idempotency_key == idempotency_key)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use crate::services::golem_config::InvocationLogsConfig;
+    use crate::services::oplog::PrimaryOplogService;
+    use crate::services::shard::{ShardService, ShardServiceDefault};
+    use crate::services::worker::{DefaultWorkerService, WorkerService};
+    use crate::storage::indexed::memory::InMemoryIndexedStorage;
+    use crate::storage::keyvalue::memory::InMemoryKeyValueStorage;
+    use golem_common::model::{
+        AccountId, ComponentId, ComponentType, OwnedWorkerId, ShardId, WorkerId, WorkerMetadata,
+        WorkerStatus, WorkerStatusRecord,
+    };
+    use golem_service_base::storage::blob::memory::InMemoryBlobStorage;
+
+    async fn create_worker_service(kvs: Arc<InMemoryKeyValueStorage>) -> Arc<DefaultWorkerService> {
+        let shard_service = Arc::new(ShardServiceDefault::new());
+        shard_service.register(1, &HashSet::from_iter(vec![ShardId::new(0)]));
+        let oplog_service = Arc::new(
+            PrimaryOplogService::new(
+                Arc::new(InMemoryIndexedStorage::new()),
+                Arc::new(InMemoryBlobStorage::new()),
+                1,
+                1024,
+            )
+            .await,
+        );
+        Arc::new(DefaultWorkerService::new(
+            kvs,
+            Arc::new(InMemoryIndexedStorage::new()),
+            shard_service,
+            oplog_service,
+            InvocationLogsConfig::default(),
+        ))
+    }
+
+    fn worker_metadata(
+        account_id: &AccountId,
+        component_id: &ComponentId,
+        name: &str,
+    ) -> WorkerMetadata {
+        WorkerMetadata::default(
+            WorkerId {
+                component_id: component_id.clone(),
+                worker_name: name.to_string(),
+            },
+            account_id.clone(),
+        )
+    }
+
+    #[test]
+    async fn concurrent_status_transitions_keep_one_status_per_worker() {
+        let worker_service = create_worker_service(Arc::new(InMemoryKeyValueStorage::new())).await;
+        let account_id = AccountId::placeholder();
+        let component_id = ComponentId::new_v4();
+        let metadata = worker_metadata(&account_id, &component_id, "worker-1");
+        let owned_worker_id = OwnedWorkerId::new(&account_id, &metadata.worker_id);
+
+        worker_service
+            .add(&metadata, ComponentType::Durable)
+            .await
+            .unwrap();
+
+        let tasks = (0..50).map(|idx| {
+            let worker_service = worker_service.clone();
+            let owned_worker_id = owned_worker_id.clone();
+            tokio::spawn(async move {
+                let status = WorkerStatusRecord {
+                    status: WorkerStatus::ALL[idx % WorkerStatus::ALL.len()].clone(),
+                    ..WorkerStatusRecord::default()
+                };
+                worker_service
+                    .update_status(&owned_worker_id, &status, ComponentType::Durable)
+                    .await;
+            })
+        });
+        for result in futures::future::join_all(tasks).await {
+            result.unwrap();
+        }
+
+        let summary = worker_service
+            .get_status_summary(&account_id, &component_id, false)
+            .await
+            .unwrap();
+
+        // The worker is in exactly one of the status sets
+        assert_eq!(summary.total, 1);
+        assert_eq!(
+            WorkerStatus::ALL
+                .iter()
+                .filter(|status| summary.count(status) == 1)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    async fn status_summary_is_backfilled_with_existing_workers() {
+        let kvs = Arc::new(InMemoryKeyValueStorage::new());
+        let worker_service = create_worker_service(kvs.clone()).await;
+        let account_id = AccountId::placeholder();
+        let component_id = ComponentId::new_v4();
+
+        for name in ["worker-1", "worker-2", "worker-3"] {
+            worker_service
+                .add(
+                    &worker_metadata(&account_id, &component_id, name),
+                    ComponentType::Durable,
+                )
+                .await
+                .unwrap();
+        }
+        // The workers created before the summaries were maintained are in none of the sets
+        kvs.sets().clear();
+
+        let summary = worker_service
+            .get_status_summary(&account_id, &component_id, false)
+            .await
+            .unwrap();
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.idle, 3);
+
+        // A backfill does not count the workers already in the summary twice
+        let summary = worker_service
+            .get_status_summary(&account_id, &component_id, true)
+            .await
+            .unwrap();
+        assert_eq!(summary.total, 3);
+    }
 }
@@ -1,3 +1,4 @@
+use crate::durable_host::last_error_and_retry_count;
 use crate::error::GolemError;
 use crate::services::active_workers::ActiveWorkers;
 use crate::services::golem_config::GolemConfig;
@@ -111,13 +112,29 @@ impl DefaultWorkerEnumerationService {
     ) -> Result<(Option<ScanCursor>, Vec<WorkerMetadata>), GolemError> {
         let mut workers: Vec<WorkerMetadata> = vec![];
 
+        let worker_name_prefix = filter.as_ref().and_then(|f| f.name_prefix());
         let (new_cursor, keys) = self
             .oplog_service
-            .scan_for_component(account_id, component_id, cursor, count)
+            .scan_for_component(
+                account_id,
+                component_id,
+                worker_name_prefix.as_deref(),
+                cursor,
+                count,
+            )
             .instrument(tracing::info_span!("scan_for_component"))
             .await?;
 
         for owned_worker_id in keys {
+            // The scan can return workers not having the prefix, which are filtered out before
+            // loading their metadata
+            if worker_name_prefix
+                .as_ref()
+                .is_some_and(|prefix| !owned_worker_id.worker_id.worker_name.starts_with(prefix))
+            {
+                continue;
+            }
+
             let worker_metadata = self
                 .worker_service
                 .get(&owned_worker_id)
@@ -141,7 +158,23 @@ impl DefaultWorkerEnumerationService {
                     worker_metadata
                 };
 
-                if filter.clone().is_none_or(|f| f.matches(&metadata)) {
+                let matches = match &filter {
+                    Some(filter) if filter.needs_last_error() => {
+                        let last_error = last_error_and_retry_count(
+                            self,
+                            &owned_worker_id,
+                            &metadata.last_known_status,
+                        )
+                        .instrument(tracing::info_span!("last_error_and_retry_count"))
+                        .await
+                        .map(|last_error| last_error.error.to_string(&last_error.stderr));
+                        filter.matches_with_last_error(&metadata, last_error.as_deref())
+                    }
+                    Some(filter) => filter.matches(&metadata),
+                    None => true,
+                };
+
+                if matches {
                     workers.push(metadata);
                 }
             }
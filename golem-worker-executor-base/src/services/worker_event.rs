@@ -43,6 +43,11 @@ pub trait WorkerEventService {
     /// guaranteed to contain information only emitted during the _last_ invocation.
     fn get_last_invocation_errors(&self) -> String;
 
+    /// Takes the stdout, stderr and log events emitted by the invocation with the given idempotency
+    /// key, if it is the last started one. Only the last events of an invocation are kept, up to
+    /// the limit of the service.
+    fn take_invocation_logs(&self, idempotency_key: &IdempotencyKey) -> Vec<WorkerEvent>;
+
     fn emit_stdout(&self, bytes: Vec<u8>, is_live: bool) {
         self.emit_event(WorkerEvent::stdout(bytes), is_live)
     }
@@ -118,20 +123,60 @@ impl WorkerEventReceiver {
     }
 }
 
+// The log events of the last started invocation
+#[derive(Default)]
+struct InvocationLogs {
+    idempotency_key: Option<IdempotencyKey>,
+    events: VecDeque<WorkerEvent>,
+}
+
 pub struct WorkerEventServiceDefault {
     sender: Sender<WorkerEvent>,
     ring_prod: Arc<Mutex<<SharedRb<Heap<WorkerEventEntry>> as Split>::Prod>>,
     ring_cons: Arc<Mutex<<SharedRb<Heap<WorkerEventEntry>> as Split>::Cons>>,
+    invocation_logs: Mutex<InvocationLogs>,
+    max_invocation_logs: usize,
 }
 
 impl WorkerEventServiceDefault {
-    pub fn new(channel_capacity: usize, ring_capacity: usize) -> WorkerEventServiceDefault {
+    pub fn new(
+        channel_capacity: usize,
+        ring_capacity: usize,
+        max_invocation_logs: usize,
+    ) -> WorkerEventServiceDefault {
         let (tx, _) = channel(channel_capacity);
         let (ring_prod, ring_cons) = HeapRb::new(ring_capacity).split();
         WorkerEventServiceDefault {
             sender: tx,
             ring_prod: Arc::new(Mutex::new(ring_prod)),
             ring_cons: Arc::new(Mutex::new(ring_cons)),
+            invocation_logs: Mutex::new(InvocationLogs::default()),
+            max_invocation_logs,
+        }
+    }
+
+    fn add_to_invocation_logs(&self, event: &WorkerEvent) {
+        if self.max_invocation_logs == 0 {
+            return;
+        }
+
+        let mut invocation_logs = self.invocation_logs.lock().unwrap();
+        match event {
+            WorkerEvent::InvocationStart {
+                idempotency_key, ..
+            } => {
+                invocation_logs.idempotency_key = Some(idempotency_key.clone());
+                invocation_logs.events.clear();
+            }
+            WorkerEvent::StdOut { .. } | WorkerEvent::StdErr { .. } | WorkerEvent::Log { .. }
+                if invocation_logs.idempotency_key.is_some() =>
+            {
+                if invocation_logs.events.len() == self.max_invocation_logs {
+                    invocation_logs.events.pop_front();
+                }
+                invocation_logs.events.push_back(event.clone());
+            }
+            _ => {}
         }
     }
 }
@@ -144,6 +189,9 @@ impl Drop for WorkerEventServiceDefault {
 
 impl WorkerEventService for WorkerEventServiceDefault {
     fn emit_event(&self, event: WorkerEvent, is_live: bool) {
+        // Replayed events are kept too, as the logs of a resumed invocation start with them
+        self.add_to_invocation_logs(&event);
+
         if is_live {
             record_event(label(&event));
 
@@ -185,6 +233,16 @@ impl WorkerEventService for WorkerEventServiceDefault {
         stderr_chunks.reverse();
         String::from_utf8_lossy(&stderr_chunks.concat()).to_string()
     }
+
+    fn take_invocation_logs(&self, idempotency_key: &IdempotencyKey) -> Vec<WorkerEvent> {
+        let mut invocation_logs = self.invocation_logs.lock().unwrap();
+        if invocation_logs.idempotency_key.as_ref() == Some(idempotency_key) {
+            invocation_logs.idempotency_key = None;
+            invocation_logs.events.drain(..).collect()
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 fn label(event: &WorkerEvent) -> &'static str {
@@ -211,12 +269,13 @@ mod tests {
     use crate::services::worker_event::{
         WorkerEvent, WorkerEventService, WorkerEventServiceDefault,
     };
+    use golem_common::model::{IdempotencyKey, LogLevel};
 
     #[test]
     #[non_flaky(10)]
     #[timeout(120000)]
     pub async fn both_subscriber_gets_events_small() {
-        let svc = Arc::new(WorkerEventServiceDefault::new(4, 16));
+        let svc = Arc::new(WorkerEventServiceDefault::new(4, 16, 0));
         let rx1_events = Arc::new(Mutex::new(Vec::<WorkerEvent>::new()));
         let rx2_events = Arc::new(Mutex::new(Vec::<WorkerEvent>::new()));
 
@@ -332,7 +391,7 @@ mod tests {
     #[test]
     #[timeout(120000)]
     pub async fn both_subscriber_gets_events_stream_small() {
-        let svc = Arc::new(WorkerEventServiceDefault::new(4, 16));
+        let svc = Arc::new(WorkerEventServiceDefault::new(4, 16, 0));
         let rx1_events = Arc::new(Mutex::new(Vec::<WorkerEvent>::new()));
         let rx2_events = Arc::new(Mutex::new(Vec::<WorkerEvent>::new()));
 
@@ -447,7 +506,7 @@ mod tests {
     #[non_flaky(10)]
     #[timeout(120000)]
     pub async fn both_subscriber_gets_events_large() {
-        let svc = Arc::new(WorkerEventServiceDefault::new(4, 4));
+        let svc = Arc::new(WorkerEventServiceDefault::new(4, 4, 0));
         let rx1_events = Arc::new(Mutex::new(Vec::<WorkerEvent>::new()));
         let rx2_events = Arc::new(Mutex::new(Vec::<WorkerEvent>::new()));
 
@@ -543,4 +602,26 @@ mod tests {
             "result2"
         );
     }
+
+    #[test]
+    pub fn only_the_last_logs_of_the_invocation_are_taken() {
+        let svc = WorkerEventServiceDefault::new(4, 16, 2);
+        let first = IdempotencyKey::fresh();
+        let second = IdempotencyKey::fresh();
+
+        svc.emit_invocation_start("f", &first, false);
+        svc.emit_stdout(vec![1], false);
+        svc.emit_invocation_start("f", &second, true);
+        svc.emit_stdout(vec![2], true);
+        svc.emit_log(LogLevel::Info, "", "3", true);
+        svc.emit_stderr(vec![4], true);
+        svc.emit_invocation_finished("f", &second, true);
+
+        assert!(svc.take_invocation_logs(&first).is_empty());
+        let logs = svc.take_invocation_logs(&second);
+        assert_eq!(logs.len(), 2);
+        assert!(matches!(&logs[0], WorkerEvent::Log { message, .. } if message == "3"));
+        assert!(matches!(&logs[1], WorkerEvent::StdErr { bytes, .. } if bytes == &vec![4]));
+        assert!(svc.take_invocation_logs(&second).is_empty());
+    }
 }
@@ -20,7 +20,9 @@ use crate::services::oplog::CommitLevel;
 use crate::services::rpc::Rpc;
 use crate::services::{rpc, HasOplog, HasWorkerForkService};
 use golem_common::model::oplog::{OplogIndex, OplogIndexRange};
-use golem_common::model::{AccountId, Timestamp, WorkerMetadata, WorkerStatusRecord};
+use golem_common::model::{
+    AccountId, Timestamp, WorkerForkOrigin, WorkerMetadata, WorkerStatusRecord,
+};
 use std::sync::Arc;
 
 use super::file_loader::FileLoader;
@@ -385,16 +387,38 @@ impl<Ctx: WorkerCtx> WorkerForkService for DefaultWorkerFork<Ctx> {
         let target_worker_id = owned_target_worker_id.worker_id.clone();
         let account_id = owned_target_worker_id.account_id.clone();
 
-        let source_worker_instance =
-            Worker::get_or_create_suspended(self, &owned_source_worker_id, None, None, None, None)
-                .await?;
+        let source_worker_instance = Worker::get_or_create_suspended(
+            self,
+            &owned_source_worker_id,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
 
         let source_worker_metadata = source_worker_instance.get_metadata()?;
 
+        // The fork replays the whole copied history, which is not available anymore once the
+        // oplog of the source worker got truncated by compaction
+        if let Some(truncated_until) = source_worker_metadata
+            .last_known_status
+            .compaction()
+            .truncated_until
+        {
+            return Err(GolemError::oplog_truncated(
+                source_worker_id.worker_id.clone(),
+                OplogIndex::INITIAL.next(),
+                truncated_until,
+            ));
+        }
+
         let target_worker_metadata = WorkerMetadata {
             worker_id: target_worker_id.clone(),
             account_id,
             env: source_worker_metadata.env.clone(),
+            wasi_config_vars: source_worker_metadata.wasi_config_vars.clone(),
             args: source_worker_metadata.args.clone(),
             created_at: Timestamp::now_utc(),
             parent: None,
@@ -405,6 +429,13 @@ impl<Ctx: WorkerCtx> WorkerForkService for DefaultWorkerFork<Ctx> {
 
         source_oplog.commit(CommitLevel::Always).await;
 
+        let source_last_index = source_oplog.current_oplog_index().await;
+        if oplog_index_cut_off > source_last_index {
+            return Err(GolemError::invalid_request(format!(
+                "oplog_index_cut_off must be at most {source_last_index}, the last oplog index of the source worker"
+            )));
+        }
+
         let initial_oplog_entry = source_oplog.read(OplogIndex::INITIAL).await;
 
         // Update the oplog initial entry with the new worker
@@ -428,6 +459,23 @@ impl<Ctx: WorkerCtx> WorkerForkService for DefaultWorkerFork<Ctx> {
             )
             .await;
 
+        // The status of the forked worker is folded from this one over the copied entries,
+        // keeping the point it diverges from the source worker
+        if let Some(mut forked_metadata) = self.worker_service.get(&owned_target_worker_id).await {
+            *forked_metadata.last_known_status.fork_mut() = Some(WorkerForkOrigin {
+                source_worker_id: source_worker_id.worker_id.clone(),
+                forked_at: oplog_index_cut_off,
+                timestamp: Timestamp::now_utc(),
+            });
+            self.worker_service
+                .update_status(
+                    &owned_target_worker_id,
+                    &forked_metadata.last_known_status,
+                    source_worker_instance.component_type(),
+                )
+                .await;
+        }
+
         let oplog_range = OplogIndexRange::new(OplogIndex::INITIAL.next(), oplog_index_cut_off);
 
         for oplog_index in oplog_range {
@@ -242,6 +242,7 @@ impl WorkerProxy for RemoteWorkerProxy {
                             parent: Some(caller_worker_id.clone().into()),
                             args: caller_args.clone(),
                             env: caller_env.clone(),
+                            wasi_config_vars: HashMap::new(),
                             tracing: Some(caller_stack.clone().into()),
                         }),
                     },
@@ -310,8 +311,10 @@ impl WorkerProxy for RemoteWorkerProxy {
                             parent: Some(caller_worker_id.clone().into()),
                             args: caller_args.clone(),
                             env: caller_env.clone(),
+                            wasi_config_vars: HashMap::new(),
                             tracing: Some(caller_stack.clone().into()),
                         }),
+                        invoke_at: None,
                     },
                     &self.access_token,
                 )))
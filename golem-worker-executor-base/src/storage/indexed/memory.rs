@@ -253,6 +253,24 @@ impl IndexedStorage for InMemoryIndexedStorage {
         }
         Ok(())
     }
+
+    async fn drop_range(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        let composite_key = Self::composite_key(namespace, key);
+        if let Some(mut entry) = self.data.get_mut(&composite_key) {
+            entry
+                .value_mut()
+                .retain(|k, _| *k < first_dropped_id || *k > last_dropped_id);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -468,4 +486,30 @@ mod tests {
 
         check!(result == vec![(3, 300), (4, 400)]);
     }
+
+    #[test]
+    async fn drop_range() {
+        let storage = super::InMemoryIndexedStorage::new();
+        let api = storage.with_entity("test", "test", "test");
+        let key = "key";
+
+        for id in 1..=5 {
+            api.append(IndexedStorageNamespace::OpLog, key, id, &(id * 100))
+                .await
+                .unwrap();
+        }
+
+        storage
+            .with("test", "test")
+            .drop_range(IndexedStorageNamespace::OpLog, key, 2, 3)
+            .await
+            .unwrap();
+
+        let result = api
+            .read(IndexedStorageNamespace::OpLog, key, 1, 5)
+            .await
+            .unwrap();
+
+        check!(result == vec![(1, 100), (4, 400), (5, 500)]);
+    }
 }
@@ -22,6 +22,7 @@ use bytes::Bytes;
 use golem_common::serialization::{deserialize, serialize};
 
 pub mod memory;
+pub mod postgres;
 pub mod redis;
 pub mod sqlite;
 
@@ -158,6 +159,18 @@ pub trait IndexedStorage: Debug {
         key: &str,
         last_dropped_id: u64,
     ) -> Result<(), String>;
+
+    /// Deletes the entries with ids in the closed range `[first_dropped_id, last_dropped_id]`
+    /// from the index of the given key, keeping the entries before and after it.
+    async fn drop_range(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String>;
 }
 
 pub trait IndexedStorageLabelledApi<T: IndexedStorage + ?Sized> {
@@ -277,6 +290,25 @@ impl<'a, S: ?Sized + IndexedStorage> LabelledIndexedStorage<'a, S> {
             )
             .await
     }
+
+    pub async fn drop_range(
+        &self,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        self.storage
+            .drop_range(
+                self.svc_name,
+                self.api_name,
+                namespace,
+                key,
+                first_dropped_id,
+                last_dropped_id,
+            )
+            .await
+    }
 }
 
 pub struct LabelledEntityIndexedStorage<'a, S: IndexedStorage + ?Sized> {
@@ -553,4 +585,5 @@ impl<'a, S: ?Sized + IndexedStorage> LabelledEntityIndexedStorage<'a, S> {
 pub enum IndexedStorageNamespace {
     OpLog,
     CompressedOpLog { level: usize },
+    InvocationLogs,
 }
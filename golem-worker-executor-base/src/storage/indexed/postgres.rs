@@ -0,0 +1,328 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use golem_service_base::storage::postgres::PostgresPool;
+use std::time::Duration;
+
+use super::{IndexedStorage, IndexedStorageNamespace, ScanCursor};
+
+/// Indexed storage in the `index_storage` table created by the executor's Postgres migrations
+#[derive(Debug)]
+pub struct PostgresIndexedStorage {
+    pool: PostgresPool,
+}
+
+impl PostgresIndexedStorage {
+    pub fn new(pool: PostgresPool) -> Self {
+        Self { pool }
+    }
+
+    fn namespace(namespace: IndexedStorageNamespace) -> String {
+        match namespace {
+            IndexedStorageNamespace::OpLog => "worker-oplog".to_string(),
+            IndexedStorageNamespace::CompressedOpLog { level } => {
+                format!("worker-c{level}-oplog")
+            }
+            IndexedStorageNamespace::InvocationLogs => "worker-invocation-logs".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl IndexedStorage for PostgresIndexedStorage {
+    async fn number_of_replicas(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+    ) -> Result<u8, String> {
+        Ok(1)
+    }
+
+    async fn wait_for_replicas(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _replicas: u8,
+        _timeout: Duration,
+    ) -> Result<u8, String> {
+        Ok(1)
+    }
+
+    async fn exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<bool, String> {
+        let query = sqlx::query_as::<_, (bool,)>(
+            "SELECT EXISTS(SELECT 1 FROM index_storage WHERE namespace = $1 AND key = $2);",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as(query)
+            .await
+            .map(|row| row.unwrap_or((false,)).0)
+    }
+
+    async fn scan(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        pattern: &str,
+        cursor: ScanCursor,
+        count: u64,
+    ) -> Result<(ScanCursor, Vec<String>), String> {
+        let key = pattern.replace("*", "%").replace("?", "_");
+        let query = sqlx::query_as(
+            "SELECT DISTINCT key FROM index_storage WHERE namespace = $1 AND key LIKE $2 ORDER BY key LIMIT $3 OFFSET $4;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(&key)
+        .bind(count as i64)
+        .bind(cursor as i64);
+
+        let keys = self
+            .pool
+            .with(svc_name, api_name)
+            .fetch_all::<(String,), _>(query)
+            .await
+            .map(|keys| keys.into_iter().map(|k| k.0).collect::<Vec<String>>())?;
+
+        let new_cursor = if keys.len() < count as usize {
+            0
+        } else {
+            cursor + count
+        };
+
+        Ok((new_cursor, keys))
+    }
+
+    async fn append(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "INSERT INTO index_storage (namespace, key, id, value) VALUES ($1, $2, $3, $4);",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(id as i64)
+        .bind(value);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn length(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String> {
+        let query = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM index_storage WHERE namespace = $1 AND key = $2;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as(query)
+            .await
+            .map(|row| row.map(|r| r.0 as u64).unwrap_or(0))
+    }
+
+    async fn delete(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<(), String> {
+        let query = sqlx::query("DELETE FROM index_storage WHERE namespace = $1 AND key = $2;")
+            .bind(Self::namespace(namespace))
+            .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn read(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        start_id: u64,
+        end_id: u64,
+    ) -> Result<Vec<(u64, Bytes)>, String> {
+        let query = sqlx::query_as(
+            "SELECT id, value FROM index_storage WHERE namespace = $1 AND key = $2 AND id BETWEEN $3 AND $4 ORDER BY id ASC;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(start_id as i64)
+        .bind(end_id as i64);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_all::<DBIdValue, _>(query)
+            .await
+            .map(|vec| vec.into_iter().map(|row| row.into_pair()).collect())
+    }
+
+    async fn first(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Bytes)>, String> {
+        let query = sqlx::query_as(
+            "SELECT id, value FROM index_storage WHERE namespace = $1 AND key = $2 ORDER BY id ASC LIMIT 1;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as::<DBIdValue, _>(query)
+            .await
+            .map(|op| op.map(|row| row.into_pair()))
+    }
+
+    async fn last(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+    ) -> Result<Option<(u64, Bytes)>, String> {
+        let query = sqlx::query_as(
+            "SELECT id, value FROM index_storage WHERE namespace = $1 AND key = $2 ORDER BY id DESC LIMIT 1;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as::<DBIdValue, _>(query)
+            .await
+            .map(|op| op.map(|row| row.into_pair()))
+    }
+
+    async fn closest(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        id: u64,
+    ) -> Result<Option<(u64, Bytes)>, String> {
+        let query = sqlx::query_as(
+            "SELECT id, value FROM index_storage WHERE namespace = $1 AND key = $2 AND id >= $3 ORDER BY id ASC LIMIT 1;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(id as i64);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as::<DBIdValue, _>(query)
+            .await
+            .map(|op| op.map(|row| row.into_pair()))
+    }
+
+    async fn drop_prefix(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM index_storage WHERE namespace = $1 AND key = $2 AND id <= $3;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(last_dropped_id as i64);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn drop_range(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM index_storage WHERE namespace = $1 AND key = $2 AND id >= $3 AND id <= $4;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(first_dropped_id as i64)
+        .bind(last_dropped_id as i64);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct DBIdValue {
+    id: i64,
+    value: Vec<u8>,
+}
+
+impl DBIdValue {
+    fn into_pair(self) -> (u64, Bytes) {
+        (self.id as u64, Bytes::from(self.value))
+    }
+}
@@ -37,6 +37,7 @@ impl RedisIndexedStorage {
             IndexedStorageNamespace::CompressedOpLog { level } => {
                 format!("worker:c{level}-oplog:{key}")
             }
+            IndexedStorageNamespace::InvocationLogs => format!("worker:invocation-logs:{key}"),
         }
     }
 
@@ -50,6 +51,7 @@ impl RedisIndexedStorage {
     }
 
     const KEY: &'static str = "key";
+    const DROP_RANGE_CHUNK_SIZE: u64 = 1000;
 
     fn parse_entry_id(id: &str) -> Result<u64, String> {
         if let Some((id, _)) = id.split_once('-') {
@@ -308,4 +310,33 @@ impl IndexedStorage for RedisIndexedStorage {
             .map_err(|e| e.to_string())?;
         Ok(())
     }
+
+    async fn drop_range(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        // Entries are added with their id as the stream id, so the ids in the range can be
+        // deleted without reading the entries first (deleting missing ids is a no-op)
+        let composite_key = Self::composite_key(namespace, key);
+        let mut first = first_dropped_id;
+        while first <= last_dropped_id {
+            let last = last_dropped_id.min(first.saturating_add(Self::DROP_RANGE_CHUNK_SIZE - 1));
+            let ids = (first..=last)
+                .map(|id| format!("{id}-0"))
+                .collect::<Vec<_>>();
+            let _: u64 = self
+                .redis
+                .with(svc_name, api_name)
+                .xdel(&composite_key, ids)
+                .await
+                .map_err(|e| e.to_string())?;
+            first = last + 1;
+        }
+        Ok(())
+    }
 }
@@ -60,6 +60,7 @@ impl SqliteIndexedStorage {
             IndexedStorageNamespace::CompressedOpLog { level } => {
                 format!("worker-c{level}-oplog")
             }
+            IndexedStorageNamespace::InvocationLogs => "worker-invocation-logs".to_string(),
         }
     }
 }
@@ -312,6 +313,30 @@ impl IndexedStorage for SqliteIndexedStorage {
             .await
             .map(|_| ())
     }
+
+    async fn drop_range(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: IndexedStorageNamespace,
+        key: &str,
+        first_dropped_id: u64,
+        last_dropped_id: u64,
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM index_storage WHERE namespace = ? AND key = ? AND id >= ? AND id <= ?;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(sqlx::types::Json(first_dropped_id))
+        .bind(sqlx::types::Json(last_dropped_id));
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
 }
 
 #[derive(sqlx::FromRow, Debug)]
@@ -17,12 +17,15 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use dashmap::mapref::entry::Entry;
 use dashmap::{DashMap, DashSet};
+use std::sync::Mutex;
 
 #[derive(Debug)]
 pub struct InMemoryKeyValueStorage {
     kvs: DashMap<String, Vec<u8>>,
     sets: DashMap<String, DashSet<Vec<u8>>>,
     sorted_sets: DashMap<String, Vec<(f64, Vec<u8>)>>,
+    // Serializes the operations touching multiple sets, as the sets are locked one by one
+    set_moves: Mutex<()>,
 }
 
 impl Default for InMemoryKeyValueStorage {
@@ -37,6 +40,7 @@ impl InMemoryKeyValueStorage {
             kvs: DashMap::new(),
             sets: DashMap::new(),
             sorted_sets: DashMap::new(),
+            set_moves: Mutex::new(()),
         }
     }
 
@@ -246,6 +250,73 @@ impl KeyValueStorage for InMemoryKeyValueStorage {
         }
     }
 
+    async fn size_of_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String> {
+        Ok(self
+            .sets
+            .get(&Self::composite_key(&namespace, key))
+            .map(|entry| entry.value().len() as u64)
+            .unwrap_or_default())
+    }
+
+    async fn move_to_set(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: Option<usize>,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let _guard = self.set_moves.lock().unwrap();
+        for (idx, key) in keys.iter().enumerate() {
+            if Some(idx) != target {
+                if let Some(set) = self.sets.get(&Self::composite_key(&namespace, key)) {
+                    set.remove(value);
+                }
+            }
+        }
+        if let Some(key) = target.and_then(|idx| keys.get(idx)) {
+            self.sets
+                .entry(Self::composite_key(&namespace, key))
+                .or_default()
+                .insert(value.to_vec());
+        }
+        Ok(())
+    }
+
+    async fn add_to_set_if_absent(
+        &self,
+        _svc_name: &'static str,
+        _api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: usize,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        let _guard = self.set_moves.lock().unwrap();
+        let present = keys.iter().any(|key| {
+            self.sets
+                .get(&Self::composite_key(&namespace, key))
+                .is_some_and(|set| set.contains(value))
+        });
+        if !present {
+            self.sets
+                .entry(Self::composite_key(&namespace, &keys[target]))
+                .or_default()
+                .insert(value.to_vec());
+        }
+        Ok(!present)
+    }
+
     async fn add_to_sorted_set(
         &self,
         _svc_name: &'static str,
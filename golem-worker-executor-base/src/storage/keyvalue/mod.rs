@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod memory;
+pub mod postgres;
 pub mod redis;
 pub mod sqlite;
 
@@ -132,6 +133,41 @@ pub trait KeyValueStorage: Debug {
         key: &str,
     ) -> Result<Vec<Bytes>, String>;
 
+    async fn size_of_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String>;
+
+    /// Atomically removes a value from all the sets of `keys` and adds it to the set
+    /// `keys[target]`, or only removes it if there is no target
+    async fn move_to_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: Option<usize>,
+        value: &[u8],
+    ) -> Result<(), String>;
+
+    /// Atomically adds a value to the set `keys[target]` unless it is already a member of one of
+    /// the sets of `keys`. Returns whether the value was added.
+    async fn add_to_set_if_absent(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: usize,
+        value: &[u8],
+    ) -> Result<bool, String>;
+
     async fn add_to_sorted_set(
         &self,
         svc_name: &'static str,
@@ -509,6 +545,64 @@ impl<'a, S: ?Sized + KeyValueStorage> LabelledEntityKeyValueStorage<'a, S> {
         Ok(values)
     }
 
+    pub async fn size_of_set(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String> {
+        self.storage
+            .size_of_set(
+                self.svc_name,
+                self.api_name,
+                self.entity_name,
+                namespace,
+                key,
+            )
+            .await
+    }
+
+    pub async fn move_to_set<V: Encode>(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: Option<usize>,
+        value: &V,
+    ) -> Result<(), String> {
+        let serialized = serialize(value)?;
+        self.storage
+            .move_to_set(
+                self.svc_name,
+                self.api_name,
+                self.entity_name,
+                namespace,
+                keys,
+                target,
+                &serialized,
+            )
+            .await
+    }
+
+    pub async fn add_to_set_if_absent<V: Encode>(
+        &self,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: usize,
+        value: &V,
+    ) -> Result<bool, String> {
+        let serialized = serialize(value)?;
+        self.storage
+            .add_to_set_if_absent(
+                self.svc_name,
+                self.api_name,
+                self.entity_name,
+                namespace,
+                keys,
+                target,
+                &serialized,
+            )
+            .await
+    }
+
     pub async fn add_to_sorted_set<V: Encode>(
         &self,
         namespace: KeyValueStorageNamespace,
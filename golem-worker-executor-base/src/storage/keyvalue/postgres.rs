@@ -0,0 +1,556 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
+use async_trait::async_trait;
+use bytes::Bytes;
+use golem_service_base::storage::postgres::PostgresPool;
+use std::collections::HashMap;
+
+/// Key-value storage in the `kv_storage`, `set_storage` and `sorted_set_storage` tables
+/// created by the executor's Postgres migrations
+#[derive(Debug)]
+pub struct PostgresKeyValueStorage {
+    pool: PostgresPool,
+}
+
+impl PostgresKeyValueStorage {
+    pub fn new(pool: PostgresPool) -> Self {
+        Self { pool }
+    }
+
+    fn namespace(ns: KeyValueStorageNamespace) -> String {
+        match ns {
+            KeyValueStorageNamespace::Worker => "worker".to_string(),
+            KeyValueStorageNamespace::Promise => "promise".to_string(),
+            KeyValueStorageNamespace::Schedule => "schedule".to_string(),
+            KeyValueStorageNamespace::UserDefined { account_id, bucket } => {
+                format!("user-defined:{}:{}", account_id, bucket)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl KeyValueStorage for PostgresKeyValueStorage {
+    async fn set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            r#"
+            INSERT INTO kv_storage (namespace, key, value) VALUES ($1, $2, $3)
+            ON CONFLICT (namespace, key) DO UPDATE SET value = excluded.value;
+            "#,
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(value);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn set_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        pairs: &[(&str, &[u8])],
+    ) -> Result<(), String> {
+        let api = self.pool.with(svc_name, api_name);
+        let mut tx = api.begin().await?;
+
+        for (field_key, field_value) in pairs {
+            tx.execute(
+                sqlx::query(
+                    r#"
+                    INSERT INTO kv_storage (namespace, key, value) VALUES ($1, $2, $3)
+                    ON CONFLICT (namespace, key) DO UPDATE SET value = excluded.value;
+                    "#,
+                )
+                .bind(Self::namespace(namespace.clone()))
+                .bind(field_key)
+                .bind(field_value),
+            )
+            .await?;
+        }
+        api.commit(tx).await
+    }
+
+    async fn set_if_not_exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        let query = sqlx::query(
+            "INSERT INTO kv_storage (namespace, key, value) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(value);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|result| result.rows_affected() > 0)
+    }
+
+    async fn get(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Option<Bytes>, String> {
+        let query =
+            sqlx::query_as("SELECT value FROM kv_storage WHERE namespace = $1 AND key = $2;")
+                .bind(Self::namespace(namespace))
+                .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as::<DBValue, _>(query)
+            .await
+            .map(|r| r.map(|op| op.into_bytes()))
+    }
+
+    async fn get_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<Vec<Option<Bytes>>, String> {
+        let query = sqlx::query_as(
+            "SELECT key, value FROM kv_storage WHERE namespace = $1 AND key = ANY($2);",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(&keys);
+
+        let results: Vec<DBKeyValue> = self.pool.with(svc_name, api_name).fetch_all(query).await?;
+
+        let mut result_map = results
+            .into_iter()
+            .map(|kv| kv.into_pair())
+            .collect::<HashMap<String, Bytes>>();
+
+        let values = keys
+            .into_iter()
+            .map(|key| result_map.remove(&key))
+            .collect::<Vec<Option<Bytes>>>();
+
+        Ok(values)
+    }
+
+    async fn del(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<(), String> {
+        let query = sqlx::query("DELETE FROM kv_storage WHERE namespace = $1 AND key = $2;")
+            .bind(Self::namespace(namespace))
+            .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn del_many(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: Vec<String>,
+    ) -> Result<(), String> {
+        let query = sqlx::query("DELETE FROM kv_storage WHERE namespace = $1 AND key = ANY($2);")
+            .bind(Self::namespace(namespace))
+            .bind(keys);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn exists(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<bool, String> {
+        let query = sqlx::query("SELECT 1 FROM kv_storage WHERE namespace = $1 AND key = $2;")
+            .bind(Self::namespace(namespace))
+            .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional(query)
+            .await
+            .map(|row| row.is_some())
+    }
+
+    async fn keys(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+    ) -> Result<Vec<String>, String> {
+        let query = sqlx::query_as("SELECT key FROM kv_storage WHERE namespace = $1;")
+            .bind(Self::namespace(namespace));
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_all::<(String,), _>(query)
+            .await
+            .map(|vec| vec.into_iter().map(|k| k.0).collect::<Vec<String>>())
+    }
+
+    async fn add_to_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "INSERT INTO set_storage (namespace, key, value) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(value);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn remove_from_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM set_storage WHERE namespace = $1 AND key = $2 AND value = $3;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(value);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn members_of_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<Bytes>, String> {
+        let query =
+            sqlx::query_as("SELECT value FROM set_storage WHERE namespace = $1 AND key = $2;")
+                .bind(Self::namespace(namespace))
+                .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_all::<DBValue, _>(query)
+            .await
+            .map(|vec| {
+                vec.into_iter()
+                    .map(|k| k.into_bytes())
+                    .collect::<Vec<Bytes>>()
+            })
+    }
+
+    async fn size_of_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String> {
+        let query = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM set_storage WHERE namespace = $1 AND key = $2;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as(query)
+            .await
+            .map(|count| count.map(|(count,)| count as u64).unwrap_or_default())
+    }
+
+    async fn move_to_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: Option<usize>,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let api = self.pool.with(svc_name, api_name);
+        let mut tx = api.begin().await?;
+        for (idx, key) in keys.iter().enumerate() {
+            if Some(idx) != target {
+                tx.execute(
+                    sqlx::query(
+                        "DELETE FROM set_storage WHERE namespace = $1 AND key = $2 AND value = $3;",
+                    )
+                    .bind(Self::namespace(namespace.clone()))
+                    .bind(key)
+                    .bind(value),
+                )
+                .await?;
+            }
+        }
+        if let Some(key) = target.and_then(|idx| keys.get(idx)) {
+            tx.execute(
+                sqlx::query(
+                    "INSERT INTO set_storage (namespace, key, value) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING;",
+                )
+                .bind(Self::namespace(namespace.clone()))
+                .bind(key)
+                .bind(value),
+            )
+            .await?;
+        }
+        api.commit(tx).await
+    }
+
+    async fn add_to_set_if_absent(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: usize,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        let api = self.pool.with(svc_name, api_name);
+        let mut tx = api.begin().await?;
+        // Serializes the concurrent additions of the same value, as a row lock cannot be taken
+        // on a member which is not there yet
+        tx.execute(
+            sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1));").bind(format!(
+                "{}:{}",
+                Self::namespace(namespace.clone()),
+                hex::encode(value)
+            )),
+        )
+        .await?;
+        let result = tx
+            .execute(
+                sqlx::query(
+                    r#"
+                    INSERT INTO set_storage (namespace, key, value)
+                    SELECT $1, $2, $3
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM set_storage WHERE namespace = $1 AND key = ANY($4) AND value = $3
+                    )
+                    ON CONFLICT DO NOTHING;
+                    "#,
+                )
+                .bind(Self::namespace(namespace.clone()))
+                .bind(&keys[target])
+                .bind(value)
+                .bind(keys),
+            )
+            .await?;
+        api.commit(tx).await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn add_to_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        score: f64,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            r#"
+            INSERT INTO sorted_set_storage (namespace, key, value, score) VALUES ($1, $2, $3, $4)
+            ON CONFLICT (namespace, key, value) DO UPDATE SET score = excluded.score;
+            "#,
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(value)
+        .bind(score);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn remove_from_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let query = sqlx::query(
+            "DELETE FROM sorted_set_storage WHERE namespace = $1 AND key = $2 AND value = $3;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(value);
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|_| ())
+    }
+
+    async fn get_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        let query = sqlx::query_as(
+            "SELECT score, value FROM sorted_set_storage WHERE namespace = $1 AND key = $2 ORDER BY score ASC;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_all::<DBScoreValue, _>(query)
+            .await
+            .map(|vec| {
+                vec.into_iter()
+                    .map(|k| k.into_pair())
+                    .collect::<Vec<(f64, Bytes)>>()
+            })
+    }
+
+    async fn query_sorted_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+        min: f64,
+        max: f64,
+    ) -> Result<Vec<(f64, Bytes)>, String> {
+        let query = sqlx::query_as(
+            "SELECT score, value FROM sorted_set_storage WHERE namespace = $1 AND key = $2 AND score BETWEEN $3 AND $4 ORDER BY score ASC;",
+        )
+        .bind(Self::namespace(namespace))
+        .bind(key)
+        .bind(min)
+        .bind(max);
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_all::<DBScoreValue, _>(query)
+            .await
+            .map(|vec| {
+                vec.into_iter()
+                    .map(|k| k.into_pair())
+                    .collect::<Vec<(f64, Bytes)>>()
+            })
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct DBValue {
+    value: Vec<u8>,
+}
+
+impl DBValue {
+    fn into_bytes(self) -> Bytes {
+        Bytes::from(self.value)
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct DBKeyValue {
+    key: String,
+    value: Vec<u8>,
+}
+
+impl DBKeyValue {
+    fn into_pair(self) -> (String, Bytes) {
+        (self.key, Bytes::from(self.value))
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct DBScoreValue {
+    score: f64,
+    value: Vec<u8>,
+}
+
+impl DBScoreValue {
+    fn into_pair(self) -> (f64, Bytes) {
+        (self.score, Bytes::from(self.value))
+    }
+}
@@ -14,7 +14,7 @@
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use fred::types::SetOptions;
+use fred::types::{RedisValue, SetOptions};
 use golem_common::metrics::redis::{record_redis_deserialized_size, record_redis_serialized_size};
 use golem_common::redis::RedisPool;
 use std::collections::HashMap;
@@ -22,6 +22,32 @@ use tracing::debug;
 
 use crate::storage::keyvalue::{KeyValueStorage, KeyValueStorageNamespace};
 
+// Removes ARGV[1] from all the sets of KEYS and adds it to the set KEYS[ARGV[2]], if ARGV[2] is
+// a valid (1-based) index
+const MOVE_TO_SET_SCRIPT: &str = r#"
+local target = tonumber(ARGV[2])
+for i, key in ipairs(KEYS) do
+    if i ~= target then
+        redis.call('SREM', key, ARGV[1])
+    end
+end
+if target >= 1 and target <= #KEYS then
+    redis.call('SADD', KEYS[target], ARGV[1])
+end
+return 1
+"#;
+
+// Adds ARGV[1] to the set KEYS[ARGV[2]] unless it is already a member of one of the sets of KEYS
+const ADD_TO_SET_IF_ABSENT_SCRIPT: &str = r#"
+for _, key in ipairs(KEYS) do
+    if redis.call('SISMEMBER', key, ARGV[1]) == 1 then
+        return 0
+    end
+end
+redis.call('SADD', KEYS[tonumber(ARGV[2])], ARGV[1])
+return 1
+"#;
+
 #[derive(Debug)]
 pub struct RedisKeyValueStorage {
     redis: RedisPool,
@@ -42,6 +68,13 @@ impl RedisKeyValueStorage {
             }
         }
     }
+
+    fn set_keys(namespace: &KeyValueStorageNamespace, keys: &[String]) -> Vec<String> {
+        match Self::use_hash(namespace) {
+            Some(ns) => keys.iter().map(|key| format!("{}:{}", ns, key)).collect(),
+            None => keys.to_vec(),
+        }
+    }
 }
 
 #[async_trait]
@@ -361,6 +394,82 @@ impl KeyValueStorage for RedisKeyValueStorage {
         Ok(members)
     }
 
+    async fn size_of_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String> {
+        let key = match Self::use_hash(&namespace) {
+            Some(ns) => format!("{}:{}", ns, key),
+            None => key.to_string(),
+        };
+        self.redis
+            .with(svc_name, api_name)
+            .scard(&key)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn move_to_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: Option<usize>,
+        value: &[u8],
+    ) -> Result<(), String> {
+        record_redis_serialized_size(svc_name, entity_name, value.len());
+
+        let target = target.map(|idx| idx as i64 + 1).unwrap_or(0);
+        let _: i64 = self
+            .redis
+            .with(svc_name, api_name)
+            .eval(
+                MOVE_TO_SET_SCRIPT,
+                Self::set_keys(&namespace, keys),
+                vec![
+                    RedisValue::Bytes(Bytes::copy_from_slice(value)),
+                    RedisValue::Integer(target),
+                ],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    async fn add_to_set_if_absent(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: usize,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        record_redis_serialized_size(svc_name, entity_name, value.len());
+
+        let added: i64 = self
+            .redis
+            .with(svc_name, api_name)
+            .eval(
+                ADD_TO_SET_IF_ABSENT_SCRIPT,
+                Self::set_keys(&namespace, keys),
+                vec![
+                    RedisValue::Bytes(Bytes::copy_from_slice(value)),
+                    RedisValue::Integer(target as i64 + 1),
+                ],
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(added == 1)
+    }
+
     async fn add_to_sorted_set(
         &self,
         svc_name: &'static str,
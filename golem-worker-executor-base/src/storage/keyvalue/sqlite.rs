@@ -374,6 +374,105 @@ impl KeyValueStorage for SqliteKeyValueStorage {
             })
     }
 
+    async fn size_of_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        key: &str,
+    ) -> Result<u64, String> {
+        let query = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM set_storage WHERE key = ? AND namespace = ?;",
+        )
+        .bind(key)
+        .bind(Self::namespace(namespace));
+
+        self.pool
+            .with(svc_name, api_name)
+            .fetch_optional_as(query)
+            .await
+            .map(|count| count.map(|(count,)| count as u64).unwrap_or_default())
+    }
+
+    async fn move_to_set(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: Option<usize>,
+        value: &[u8],
+    ) -> Result<(), String> {
+        let api = self.pool.with(svc_name, api_name);
+        let mut tx = api.begin().await?;
+        for (idx, key) in keys.iter().enumerate() {
+            if Some(idx) != target {
+                tx.execute(
+                    sqlx::query(
+                        "DELETE FROM set_storage WHERE key = ? AND value = ? AND namespace = ?;",
+                    )
+                    .bind(key)
+                    .bind(value)
+                    .bind(Self::namespace(namespace.clone())),
+                )
+                .await?;
+            }
+        }
+        if let Some(key) = target.and_then(|idx| keys.get(idx)) {
+            tx.execute(
+                sqlx::query(
+                    "INSERT OR REPLACE INTO set_storage (namespace, key, value) VALUES (?, ?, ?);",
+                )
+                .bind(Self::namespace(namespace.clone()))
+                .bind(key)
+                .bind(value),
+            )
+            .await?;
+        }
+        api.commit(tx).await
+    }
+
+    async fn add_to_set_if_absent(
+        &self,
+        svc_name: &'static str,
+        api_name: &'static str,
+        _entity_name: &'static str,
+        namespace: KeyValueStorageNamespace,
+        keys: &[String],
+        target: usize,
+        value: &[u8],
+    ) -> Result<bool, String> {
+        // A single statement, so the membership check and the insertion cannot interleave with
+        // other writers
+        let placeholders = vec!["?"; keys.len()].join(", ");
+        let statement = format!(
+            r#"
+            INSERT OR IGNORE INTO set_storage (namespace, key, value)
+            SELECT ?, ?, ?
+            WHERE NOT EXISTS (
+                SELECT 1 FROM set_storage WHERE namespace = ? AND value = ? AND key IN ({placeholders})
+            );
+            "#
+        );
+        let mut query = sqlx::query(&statement)
+            .bind(Self::namespace(namespace.clone()))
+            .bind(&keys[target])
+            .bind(value)
+            .bind(Self::namespace(namespace))
+            .bind(value);
+        for key in keys {
+            query = query.bind(key);
+        }
+
+        self.pool
+            .with(svc_name, api_name)
+            .execute(query)
+            .await
+            .map(|result| result.rows_affected() > 0)
+    }
+
     async fn add_to_sorted_set(
         &self,
         svc_name: &'static str,
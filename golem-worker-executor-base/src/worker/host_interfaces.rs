@@ -0,0 +1,192 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use semver::Version;
+use std::fmt::{Display, Formatter};
+
+/// A versioned Golem host interface implemented by the worker executor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostInterface {
+    pub package: &'static str,
+    pub interface: &'static str,
+    pub version: &'static str,
+}
+
+impl Display for HostInterface {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}@{}", self.package, self.interface, self.version)
+    }
+}
+
+// Must be kept in sync with the host interfaces imported by the `golem:api/golem` world
+pub const SUPPORTED_HOST_INTERFACES: &[HostInterface] = &[
+    HostInterface {
+        package: "golem:api",
+        interface: "host",
+        version: "0.2.2",
+    },
+    HostInterface {
+        package: "golem:api",
+        interface: "host",
+        version: "1.1.5",
+    },
+    HostInterface {
+        package: "golem:api",
+        interface: "oplog",
+        version: "1.1.5",
+    },
+    HostInterface {
+        package: "golem:durability",
+        interface: "durability",
+        version: "1.2.0",
+    },
+    HostInterface {
+        package: "golem:rpc",
+        interface: "types",
+        version: "0.1.3",
+    },
+];
+
+/// Lists the supported host interfaces in a human-readable form
+pub fn supported_host_interfaces() -> String {
+    SUPPORTED_HOST_INTERFACES
+        .iter()
+        .map(|interface| interface.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Checks the imports of a component against the host interfaces supported by this executor.
+///
+/// Imports of packages which are not provided by the executor (such as WASI or the stubs of
+/// other components) are not checked here. On failure, returns a description of every
+/// unsupported import, which is better than the linking error the component would otherwise
+/// fail with, as a newer minor version can only fail on a single missing function.
+pub fn negotiate_host_interfaces<'a>(
+    imports: impl IntoIterator<Item = &'a str>,
+) -> Result<(), Vec<String>> {
+    let errors = imports
+        .into_iter()
+        .filter_map(|import| check_import(import).err())
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn check_import(import: &str) -> Result<(), String> {
+    let (path, version) = match import.split_once('@') {
+        Some((path, version)) => (path, Some(version)),
+        None => (import, None),
+    };
+    let Some((package, interface)) = path.split_once('/') else {
+        return Ok(());
+    };
+
+    let provided = SUPPORTED_HOST_INTERFACES
+        .iter()
+        .filter(|supported| supported.package == package)
+        .collect::<Vec<_>>();
+    if provided.is_empty() {
+        return Ok(());
+    }
+
+    let required = version
+        .map(|version| {
+            Version::parse(version)
+                .map_err(|err| format!("{import} has an invalid version ({err})"))
+        })
+        .transpose()?;
+
+    let compatible = provided.iter().any(|supported| {
+        supported.interface == interface
+            && required.as_ref().is_none_or(|required| {
+                Version::parse(supported.version)
+                    .is_ok_and(|supported| is_compatible(&supported, required))
+            })
+    });
+
+    if compatible {
+        Ok(())
+    } else {
+        let newer = provided.iter().all(|supported| {
+            required.as_ref().is_some_and(|required| {
+                Version::parse(supported.version).is_ok_and(|supported| supported < *required)
+            })
+        });
+        if newer {
+            Err(format!(
+                "{import} is newer than the host interfaces of {package} this executor supports, upgrade the worker executor or build the component with an older Golem SDK"
+            ))
+        } else {
+            Err(format!(
+                "{import} is not supported by this executor, build the component with a Golem SDK targeting one of the supported versions"
+            ))
+        }
+    }
+}
+
+// Follows the semver rules used by the component model when linking imports, but also requires
+// the provided version to be at least the required one
+fn is_compatible(provided: &Version, required: &Version) -> bool {
+    let same_line = if provided.major != 0 {
+        provided.major == required.major
+    } else if provided.minor != 0 {
+        required.major == 0 && provided.minor == required.minor
+    } else {
+        required.major == 0 && required.minor == 0 && provided.patch == required.patch
+    };
+    same_line && provided >= required
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::negotiate_host_interfaces;
+
+    #[test]
+    fn compatible_and_unrelated_imports_are_accepted() {
+        let imports = [
+            "golem:api/host@1.1.0",
+            "golem:api/host@0.2.0",
+            "golem:api/oplog@1.1.5",
+            "golem:rpc/types@0.1.0",
+            "wasi:io/streams@0.2.0",
+            "golem:it-stub/stub-counters",
+        ];
+
+        assert_eq!(negotiate_host_interfaces(imports), Ok(()));
+    }
+
+    #[test]
+    fn newer_and_unknown_imports_are_rejected() {
+        let imports = [
+            "golem:api/host@1.2.0",
+            "golem:api/context@1.1.5",
+            "golem:durability/durability@2.0.0",
+            "golem:rpc/types@0.1.3",
+        ];
+
+        let errors = negotiate_host_interfaces(imports).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("golem:api/host@1.2.0 is newer than"));
+        assert!(errors[1].starts_with("golem:api/context@1.1.5 is not supported"));
+        assert!(errors[2].starts_with("golem:durability/durability@2.0.0 is newer than"));
+    }
+}
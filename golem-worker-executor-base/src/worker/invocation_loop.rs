@@ -16,7 +16,8 @@ use crate::error::GolemError;
 use crate::model::{InterruptKind, ListDirectoryResult, ReadFileResult, TrapType};
 use crate::services::events::Event;
 use crate::services::oplog::{CommitLevel, OplogOps};
-use crate::services::{HasEvents, HasOplog, HasWorker};
+use crate::services::worker::InvocationLogEntry;
+use crate::services::{HasConfig, HasEvents, HasOplog, HasWorker, HasWorkerService};
 use crate::worker::function_result_interpreter::interpret_function_results;
 use crate::worker::invocation::{find_first_available_function, invoke_worker, InvokeResult};
 use crate::worker::{QueuedWorkerInvocation, RetryDecision, RunningWorker, Worker, WorkerCommand};
@@ -26,15 +27,14 @@ use async_mutex::Mutex;
 use drop_stream::DropStream;
 use futures::channel::oneshot;
 use futures::channel::oneshot::Sender;
-use golem_common::model::invocation_context::{
-    AttributeValue, InvocationContextSpan, InvocationContextStack,
-};
+use golem_common::model::invocation_context::{AttributeValue, InvocationContextStack};
 use golem_common::model::oplog::WorkerError;
 use golem_common::model::{
     exports, ComponentFilePath, ComponentType, ComponentVersion, IdempotencyKey, OwnedWorkerId,
     TimestampedWorkerInvocation, WorkerInvocation,
 };
 use golem_common::retries::get_delay;
+use golem_common::tracing::set_parent_from_invocation_context;
 use golem_wasm_ast::analysis::AnalysedFunctionResult;
 use golem_wasm_rpc::Value;
 use std::collections::VecDeque;
@@ -423,8 +423,10 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
             "invocation",
             worker_id = %self.owned_worker_id.worker_id,
             %idempotency_key,
-            function = full_function_name
+            function = full_function_name,
+            trace_id = %invocation_context.trace_id
         );
+        set_parent_from_invocation_context(&span, &invocation_context);
 
         self.invoke_exported_function_inner(
             invocation_context,
@@ -449,12 +451,14 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         let result = self
             .invoke_exported_function_with_context(
                 invocation_context,
-                idempotency_key,
+                idempotency_key.clone(),
                 &full_function_name,
                 &function_input,
             )
             .await;
 
+        self.store_invocation_logs(&idempotency_key).await;
+
         match result {
             Ok(InvokeResult::Succeeded {
                 output,
@@ -472,6 +476,26 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         }
     }
 
+    /// Stores the stdout, stderr and log entries emitted by the finished invocation, so they can
+    /// be queried later
+    async fn store_invocation_logs(&self, idempotency_key: &IdempotencyKey) {
+        let max_message_size = self.parent.config().invocation_logs.max_message_size;
+        let entries = self
+            .parent
+            .event_service()
+            .take_invocation_logs(idempotency_key)
+            .iter()
+            .filter_map(|event| {
+                InvocationLogEntry::from_event(idempotency_key, event, max_message_size)
+            })
+            .collect::<Vec<_>>();
+
+        self.parent
+            .worker_service()
+            .add_invocation_logs(&self.owned_worker_id, entries)
+            .await;
+    }
+
     /// Sets the necessary contextual information on the worker, and performs the actual
     /// invocation.
     async fn invoke_exported_function_with_context(
@@ -602,6 +626,11 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
                 if self.store.data().component_metadata().component_type == ComponentType::Ephemeral
                 {
                     CommandOutcome::BreakInnerLoop(RetryDecision::None)
+                } else if self.is_compaction_due().await {
+                    // Compacting the oplog by taking a snapshot of the worker, just like a manual
+                    // update to the current version
+                    let current_version = self.store.data().component_metadata().version;
+                    self.manual_update(current_version).await
                 } else {
                     CommandOutcome::Continue
                 }
@@ -640,6 +669,31 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         CommandOutcome::BreakInnerLoop(decision)
     }
 
+    /// Checks whether the oplog of the worker grew enough since its last snapshot (or failed
+    /// attempt to take one) to be compacted, if the worker is able to take snapshots
+    async fn is_compaction_due(&mut self) -> bool {
+        let Ok(metadata) = self.parent.get_metadata() else {
+            return false;
+        };
+        let status = metadata.last_known_status;
+        let current_oplog_index = self.parent.oplog().current_oplog_index().await;
+
+        status.pending_updates.is_empty()
+            && self
+                .parent
+                .oplog_compaction_config()
+                .is_due(status.compaction(), current_oplog_index)
+            && find_first_available_function(
+                self.store,
+                self.instance,
+                vec![
+                    "golem:api/save-snapshot@1.1.0.{save}".to_string(),
+                    "golem:api/save-snapshot@0.2.0.{save}".to_string(),
+                ],
+            )
+            .is_some()
+    }
+
     /// Try to perform the save-snapshot step of a manual update on the worker
     async fn manual_update(&mut self, target_version: ComponentVersion) -> CommandOutcome {
         let span = span!(
@@ -842,7 +896,7 @@ impl<Ctx: WorkerCtx> Invocation<'_, Ctx> {
         idempotency_key: &IdempotencyKey,
         full_function_name: &str,
     ) {
-        let invocation_span = InvocationContextSpan::new(None);
+        let invocation_span = invocation_context.spans.first().start_span(None);
         invocation_span.set_attribute(
             "name".to_string(),
             AttributeValue::String("invoke-exported-function".to_string()),
@@ -0,0 +1,161 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::model::invocation_context::InvocationContextStack;
+use golem_common::model::{ComponentVersion, IdempotencyKey, WorkerId, WorkerInvocation};
+use golem_wasm_ast::analysis::AnalysedExport;
+use golem_wasm_rpc::Value;
+
+/// Optional functions a component can export to get notified about the lifecycle of its workers.
+///
+/// The hooks are invoked as regular durable invocations with an idempotency key derived from the
+/// worker and the lifecycle event, so each of them runs at most once per event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LifecycleHook {
+    Create,
+    Delete,
+    Updated { old_version: ComponentVersion },
+}
+
+impl LifecycleHook {
+    pub fn function_name(&self) -> &'static str {
+        match self {
+            LifecycleHook::Create => "on-create",
+            LifecycleHook::Delete => "on-delete",
+            LifecycleHook::Updated { .. } => "on-updated",
+        }
+    }
+
+    /// Finds the hook among the exports, either as a root function or in an exported interface,
+    /// and returns its full function name
+    pub fn find(&self, exports: &[AnalysedExport]) -> Option<String> {
+        let name = self.function_name();
+        exports.iter().find_map(|export| match export {
+            AnalysedExport::Function(function) if function.name == name => Some(name.to_string()),
+            AnalysedExport::Instance(instance) => instance
+                .functions
+                .iter()
+                .any(|function| function.name == name)
+                .then(|| format!("{}.{{{name}}}", instance.name)),
+            _ => None,
+        })
+    }
+
+    pub fn idempotency_key(
+        &self,
+        worker_id: &WorkerId,
+        new_version: ComponentVersion,
+    ) -> IdempotencyKey {
+        let key = match self {
+            LifecycleHook::Updated { old_version } => {
+                format!(
+                    "{worker_id}/{}/{old_version}-{new_version}",
+                    self.function_name()
+                )
+            }
+            _ => format!("{worker_id}/{}", self.function_name()),
+        };
+        IdempotencyKey::new(key)
+    }
+
+    pub fn function_input(&self) -> Vec<Value> {
+        match self {
+            LifecycleHook::Updated { old_version } => vec![Value::U64(*old_version)],
+            _ => vec![],
+        }
+    }
+
+    /// Constructs the invocation of the hook if the component exports it
+    pub fn invocation(
+        &self,
+        worker_id: &WorkerId,
+        component_version: ComponentVersion,
+        exports: &[AnalysedExport],
+    ) -> Option<WorkerInvocation> {
+        self.find(exports)
+            .map(|full_function_name| WorkerInvocation::ExportedFunction {
+                idempotency_key: self.idempotency_key(worker_id, component_version),
+                full_function_name,
+                function_input: self.function_input(),
+                invocation_context: InvocationContextStack::fresh(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use golem_common::model::ComponentId;
+    use golem_wasm_ast::analysis::analysed_type::u64;
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedInstance,
+    };
+
+    fn function(name: &str, parameters: Vec<AnalysedFunctionParameter>) -> AnalysedFunction {
+        AnalysedFunction {
+            name: name.to_string(),
+            parameters,
+            results: vec![],
+        }
+    }
+
+    #[test]
+    fn hooks_are_found_in_root_and_interface_exports() {
+        let worker_id = WorkerId {
+            component_id: ComponentId::new_v4(),
+            worker_name: "worker-1".to_string(),
+        };
+        let exports = vec![
+            AnalysedExport::Function(function("on-create", vec![])),
+            AnalysedExport::Instance(AnalysedInstance {
+                name: "golem:it/lifecycle".to_string(),
+                functions: vec![function(
+                    "on-updated",
+                    vec![AnalysedFunctionParameter {
+                        name: "old-version".to_string(),
+                        typ: u64(),
+                    }],
+                )],
+            }),
+        ];
+
+        assert_eq!(
+            LifecycleHook::Create.find(&exports),
+            Some("on-create".to_string())
+        );
+        assert_eq!(LifecycleHook::Delete.find(&exports), None);
+
+        let updated = LifecycleHook::Updated { old_version: 1 }
+            .invocation(&worker_id, 2, &exports)
+            .unwrap();
+        match updated {
+            WorkerInvocation::ExportedFunction {
+                idempotency_key,
+                full_function_name,
+                function_input,
+                ..
+            } => {
+                assert_eq!(full_function_name, "golem:it/lifecycle.{on-updated}");
+                assert_eq!(function_input, vec![Value::U64(1)]);
+                assert_eq!(
+                    idempotency_key,
+                    IdempotencyKey::new(format!("{worker_id}/on-updated/1-2"))
+                );
+            }
+            _ => panic!("Unexpected invocation"),
+        }
+    }
+}
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 pub mod function_result_interpreter;
+pub mod host_interfaces;
 pub mod invocation;
 mod invocation_loop;
+pub mod lifecycle;
 pub mod status;
+pub mod trace;
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::mem;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -30,6 +33,7 @@ use crate::model::{
     WorkerConfig,
 };
 use crate::services::events::Event;
+use crate::services::golem_config::OplogCompactionConfig;
 use crate::services::oplog::{CommitLevel, Oplog, OplogOps};
 use crate::services::worker_event::{WorkerEventService, WorkerEventServiceDefault};
 use crate::services::{
@@ -38,7 +42,9 @@ use crate::services::{
     HasPromiseService, HasRpc, HasSchedulerService, HasWasmtimeEngine, HasWorkerEnumerationService,
     HasWorkerProxy, HasWorkerService, UsesAllDeps,
 };
+use crate::worker::host_interfaces::{negotiate_host_interfaces, supported_host_interfaces};
 use crate::worker::invocation_loop::InvocationLoop;
+use crate::worker::lifecycle::LifecycleHook;
 use crate::worker::status::calculate_last_known_status;
 use crate::workerctx::WorkerCtx;
 use anyhow::anyhow;
@@ -99,6 +105,7 @@ pub struct Worker<Ctx: WorkerCtx> {
 
     instance: Arc<Mutex<WorkerInstance>>,
     oom_retry_config: RetryConfig,
+    oplog_compaction_config: OplogCompactionConfig,
 }
 
 impl<Ctx: WorkerCtx> HasOplog for Worker<Ctx> {
@@ -122,6 +129,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         owned_worker_id: &OwnedWorkerId,
         worker_args: Option<Vec<String>>,
         worker_env: Option<Vec<(String, String)>>,
+        worker_wasi_config_vars: Option<BTreeMap<String, String>>,
         component_version: Option<u64>,
         parent: Option<WorkerId>,
     ) -> Result<Arc<Self>, GolemError>
@@ -134,6 +142,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                 owned_worker_id,
                 worker_args,
                 worker_env,
+                worker_wasi_config_vars,
                 component_version,
                 parent,
             )
@@ -146,6 +155,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         owned_worker_id: &OwnedWorkerId,
         worker_args: Option<Vec<String>>,
         worker_env: Option<Vec<(String, String)>>,
+        worker_wasi_config_vars: Option<BTreeMap<String, String>>,
         component_version: Option<u64>,
         parent: Option<WorkerId>,
     ) -> Result<Arc<Self>, GolemError>
@@ -157,6 +167,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
             owned_worker_id,
             worker_args,
             worker_env,
+            worker_wasi_config_vars,
             component_version,
             parent,
         )
@@ -193,15 +204,17 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         owned_worker_id: OwnedWorkerId,
         worker_args: Option<Vec<String>>,
         worker_env: Option<Vec<(String, String)>>,
+        worker_wasi_config_vars: Option<BTreeMap<String, String>>,
         component_version: Option<u64>,
         parent: Option<WorkerId>,
     ) -> Result<Self, GolemError> {
-        let (worker_metadata, execution_status) = Self::get_or_create_worker_metadata(
+        let (worker_metadata, execution_status, created) = Self::get_or_create_worker_metadata(
             deps,
             &owned_worker_id,
             component_version,
             worker_args,
             worker_env,
+            worker_wasi_config_vars,
             parent,
         )
         .await?;
@@ -268,12 +281,13 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
 
         let stopping = AtomicBool::new(false);
 
-        Ok(Worker {
+        let worker = Worker {
             owned_worker_id,
             oplog,
             event_service: Arc::new(WorkerEventServiceDefault::new(
                 deps.config().limits.event_broadcast_capacity,
                 deps.config().limits.event_history_size,
+                deps.config().invocation_logs.captured_entries(),
             )),
             deps: All::from_other(deps),
             queue,
@@ -285,13 +299,39 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
             initial_worker_metadata: worker_metadata,
             worker_estimate_coefficient: deps.config().memory.worker_estimate_coefficient,
             oom_retry_config: deps.config().memory.oom_retry_config.clone(),
-        })
+            oplog_compaction_config: deps.config().oplog.compaction.clone(),
+        };
+
+        if created {
+            if let Some(full_function_name) =
+                LifecycleHook::Create.find(&initial_component_metadata.exports)
+            {
+                debug!("Enqueuing the on-create lifecycle hook");
+                worker
+                    .enqueue(
+                        LifecycleHook::Create.idempotency_key(
+                            &worker.owned_worker_id.worker_id,
+                            initial_component_metadata.version,
+                        ),
+                        full_function_name,
+                        LifecycleHook::Create.function_input(),
+                        InvocationContextStack::fresh(),
+                    )
+                    .await;
+            }
+        }
+
+        Ok(worker)
     }
 
     pub fn oom_retry_config(&self) -> &RetryConfig {
         &self.oom_retry_config
     }
 
+    pub fn oplog_compaction_config(&self) -> &OplogCompactionConfig {
+        &self.oplog_compaction_config
+    }
+
     pub async fn start_if_needed(this: Arc<Worker<Ctx>>) -> Result<bool, GolemError> {
         Self::start_if_needed_internal(this, 0).await
     }
@@ -302,6 +342,8 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
     ) -> Result<bool, GolemError> {
         let mut instance = this.instance.lock().await;
         if instance.is_unloaded() {
+            this.active_workers()
+                .worker_loading(&this.owned_worker_id.worker_id);
             this.mark_as_loading();
             *instance = WorkerInstance::WaitingForPermit(WaitingWorker::new(
                 this.clone(),
@@ -395,6 +437,10 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         self.event_service.clone()
     }
 
+    pub fn owned_worker_id(&self) -> &OwnedWorkerId {
+        &self.owned_worker_id
+    }
+
     pub fn is_loading(&self) -> bool {
         matches!(
             &*self.execution_status.read().unwrap(),
@@ -625,6 +671,27 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         }
     }
 
+    /// Enqueue an invocation which has already been written to the oplog as a pending invocation
+    pub(crate) async fn enqueue_persisted_invocation(
+        &self,
+        invocation: TimestampedWorkerInvocation,
+    ) {
+        match &*self.instance.lock().await {
+            WorkerInstance::Running(running) => {
+                running.enqueue_timestamped_invocation(invocation).await;
+            }
+            WorkerInstance::Unloaded | WorkerInstance::WaitingForPermit(_) => {
+                self.queue
+                    .write()
+                    .await
+                    .push_back(QueuedWorkerInvocation::External {
+                        invocation,
+                        canceled: false,
+                    });
+            }
+        }
+    }
+
     pub async fn pending_invocations(&self) -> Vec<TimestampedWorkerInvocation> {
         self.queue
             .read()
@@ -796,6 +863,29 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         }
     }
 
+    /// Returns true if the worker is not loaded into memory, and it has no queued invocations or
+    /// pending updates. These workers can be dropped from the active workers.
+    pub async fn is_unloaded_and_idle(&self) -> bool {
+        match self.instance.try_lock() {
+            Ok(guard) => {
+                matches!(&*guard, WorkerInstance::Unloaded)
+                    && !self.is_loading()
+                    && self.queue.read().await.is_empty()
+                    && self.pending_updates.read().await.is_empty()
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Returns true if the worker is executing an invocation, or it has pending invocations
+    pub async fn is_busy(&self) -> bool {
+        let running = matches!(
+            &*self.execution_status.read().unwrap(),
+            ExecutionStatus::Running { .. }
+        );
+        running || !self.pending_invocations().await.is_empty()
+    }
+
     /// Gets the timestamp of the last time the execution status changed
     pub fn last_execution_state_change(&self) -> Timestamp {
         self.execution_status.read().unwrap().timestamp()
@@ -930,6 +1020,15 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         Ok(())
     }
 
+    /// Replaces the labels of the worker
+    pub async fn set_labels(&self, labels: BTreeMap<String, String>) -> Result<(), GolemError> {
+        self.oplog
+            .add_and_commit(OplogEntry::change_labels(labels))
+            .await;
+        self.update_metadata().await?;
+        Ok(())
+    }
+
     /// Reverts the worker to a previous state, selected by either the last oplog index to keep
     /// or the number of invocations to drop.
     ///
@@ -942,12 +1041,28 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                     .await
             }
             RevertWorkerTarget::RevertLastInvocations(target) => {
+                let truncated_until = self
+                    .get_metadata()?
+                    .last_known_status
+                    .compaction()
+                    .truncated_until;
+
                 if let Some(last_oplog_index) = self
-                    .find_nth_invocation_from_end(target.number_of_invocations as usize)
+                    .find_nth_invocation_from_end(
+                        target.number_of_invocations as usize,
+                        truncated_until.unwrap_or(OplogIndex::INITIAL),
+                    )
                     .await
                 {
                     self.revert_to_last_oplog_index(last_oplog_index.previous())
                         .await
+                } else if let Some(truncated_until) = truncated_until {
+                    // The earlier invocations were dropped from the oplog by compaction
+                    Err(GolemError::oplog_truncated(
+                        self.owned_worker_id.worker_id(),
+                        truncated_until.previous(),
+                        truncated_until,
+                    ))
                 } else {
                     Err(GolemError::invalid_request(format!(
                         "Could not find {} invocations to revert",
@@ -973,21 +1088,30 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         Ok(())
     }
 
-    /// Starting from the end of the oplog, find the Nth ExportedFunctionInvoked entry's index.
-    async fn find_nth_invocation_from_end(&self, n: usize) -> Option<OplogIndex> {
+    /// Starting from the end of the oplog, find the Nth ExportedFunctionInvoked entry's index,
+    /// looking back until the given first index.
+    async fn find_nth_invocation_from_end(
+        &self,
+        n: usize,
+        first: OplogIndex,
+    ) -> Option<OplogIndex> {
         let mut current = self.oplog.current_oplog_index().await;
         let mut found = 0;
         loop {
             let entry = self.oplog.read(current).await;
 
-            if matches!(entry, OplogEntry::ExportedFunctionInvoked { .. }) {
+            if matches!(
+                entry,
+                OplogEntry::ExportedFunctionInvokedV1 { .. }
+                    | OplogEntry::ExportedFunctionInvoked { .. }
+            ) {
                 found += 1;
                 if found == n {
                     return Some(current);
                 }
             }
 
-            if current == OplogIndex::INITIAL {
+            if current <= first {
                 return None;
             } else {
                 current = current.previous();
@@ -999,6 +1123,23 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         &self,
         last_oplog_index: OplogIndex,
     ) -> Result<(), GolemError> {
+        // The snapshot the worker recovers from would be reverted, and the history before it
+        // is not available anymore to replay instead
+        if let Some(truncated_until) = self
+            .get_metadata()?
+            .last_known_status
+            .compaction()
+            .truncated_until
+        {
+            if last_oplog_index < truncated_until {
+                return Err(GolemError::oplog_truncated(
+                    self.owned_worker_id.worker_id(),
+                    last_oplog_index,
+                    truncated_until,
+                ));
+            }
+        }
+
         self.stop().await;
 
         let region_end = self.oplog.current_oplog_index().await;
@@ -1241,8 +1382,16 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
         component_version: Option<ComponentVersion>,
         worker_args: Option<Vec<String>>,
         worker_env: Option<Vec<(String, String)>>,
+        worker_wasi_config_vars: Option<BTreeMap<String, String>>,
         parent: Option<WorkerId>,
-    ) -> Result<(WorkerMetadata, Arc<std::sync::RwLock<ExecutionStatus>>), GolemError> {
+    ) -> Result<
+        (
+            WorkerMetadata,
+            Arc<std::sync::RwLock<ExecutionStatus>>,
+            bool,
+        ),
+        GolemError,
+    > {
         let component_id = owned_worker_id.component_id();
         let component_metadata = this
             .component_service()
@@ -1260,6 +1409,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                     worker_id: owned_worker_id.worker_id(),
                     args: worker_args.unwrap_or_default(),
                     env: worker_env.unwrap_or_default(),
+                    wasi_config_vars: worker_wasi_config_vars.unwrap_or_default(),
                     account_id: owned_worker_id.account_id(),
                     created_at: Timestamp::now_utc(),
                     parent,
@@ -1271,13 +1421,16 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                             .iter()
                             .map(|m| m.initial)
                             .sum(),
-                        extensions: WorkerStatusRecordExtensions::Extension2 {
+                        extensions: WorkerStatusRecordExtensions::Extension5 {
                             active_plugins: component_metadata
                                 .plugin_installations
                                 .iter()
                                 .map(|i| i.id.clone())
                                 .collect(),
                             deleted_regions: initial_status.deleted_regions().clone(),
+                            labels: initial_status.labels().clone(),
+                            compaction: initial_status.compaction().clone(),
+                            fork: initial_status.fork().cloned(),
                         },
                         ..initial_status
                     },
@@ -1286,7 +1439,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                     .worker_service()
                     .add(&worker_metadata, component_metadata.component_type)
                     .await?;
-                Ok((worker_metadata, execution_status))
+                Ok((worker_metadata, execution_status, true))
             }
             Some(previous_metadata) => {
                 let worker_metadata = WorkerMetadata {
@@ -1304,7 +1457,7 @@ impl<Ctx: WorkerCtx> Worker<Ctx> {
                         component_type: component_metadata.component_type,
                         timestamp: Timestamp::now_utc(),
                     }));
-                Ok((worker_metadata, execution_status))
+                Ok((worker_metadata, execution_status, false))
             }
         }
     }
@@ -1476,11 +1629,16 @@ impl RunningWorker {
             // The worker is currently busy, so we write the pending worker invocation to the oplog
             self.oplog.add_and_commit(entry).await;
         }
+        self.enqueue_timestamped_invocation(timestamped_invocation)
+            .await;
+    }
+
+    async fn enqueue_timestamped_invocation(&self, invocation: TimestampedWorkerInvocation) {
         self.queue
             .write()
             .await
             .push_back(QueuedWorkerInvocation::External {
-                invocation: timestamped_invocation,
+                invocation,
                 canceled: false,
             });
         self.sender.send(WorkerCommand::Invocation).unwrap()
@@ -1526,6 +1684,24 @@ impl RunningWorker {
             )
             .await?;
 
+        let engine = parent.engine();
+        negotiate_host_interfaces(
+            component
+                .component_type()
+                .imports(&engine)
+                .map(|(name, _)| name),
+        )
+        .map_err(|errors| {
+            GolemError::worker_creation_failed(
+                parent.owned_worker_id.worker_id(),
+                format!(
+                    "Component {component_id} version {component_version} requires host interfaces not supported by this executor: {}. Supported host interfaces: {}",
+                    errors.join("; "),
+                    supported_host_interfaces()
+                ),
+            )
+        })?;
+
         let context = Ctx::create(
             OwnedWorkerId::new(&worker_metadata.account_id, &worker_metadata.worker_id),
             component_metadata.clone(),
@@ -1550,6 +1726,7 @@ impl RunningWorker {
                 worker_metadata.last_known_status.component_version,
                 worker_metadata.args.clone(),
                 worker_metadata.env.clone(),
+                worker_metadata.wasi_config_vars.clone(),
                 worker_metadata.last_known_status.skipped_regions.clone(),
                 worker_metadata.last_known_status.total_linear_memory_size,
             ),
@@ -1559,7 +1736,6 @@ impl RunningWorker {
         )
         .await?;
 
-        let engine = parent.engine();
         let mut store = Store::new(&engine, context);
         store.set_epoch_deadline(parent.config().limits.epoch_ticks);
         let worker_id_clone = worker_metadata.worker_id.clone();
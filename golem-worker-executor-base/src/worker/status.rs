@@ -8,9 +8,9 @@ use golem_common::model::oplog::{
 };
 use golem_common::model::regions::{DeletedRegions, DeletedRegionsBuilder, OplogRegion};
 use golem_common::model::{
-    FailedUpdateRecord, IdempotencyKey, OwnedWorkerId, RetryConfig, SuccessfulUpdateRecord,
-    TimestampedWorkerInvocation, WorkerInvocation, WorkerMetadata, WorkerResourceDescription,
-    WorkerStatus, WorkerStatusRecord, WorkerStatusRecordExtensions,
+    FailedUpdateRecord, IdempotencyKey, OplogCompactionStatus, OwnedWorkerId, RetryConfig,
+    SuccessfulUpdateRecord, TimestampedWorkerInvocation, WorkerInvocation, WorkerMetadata,
+    WorkerResourceDescription, WorkerStatus, WorkerStatusRecord, WorkerStatusRecordExtensions,
 };
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
@@ -60,6 +60,15 @@ where
             calculate_last_known_status(this, owned_worker_id, &None).await
         } else {
             let active_plugins = last_known.active_plugins().clone();
+            // The fork origin is only recorded when forking the worker
+            let fork = last_known.fork().cloned();
+            let labels = calculate_labels(last_known.labels().clone(), &new_entries);
+            let compaction = calculate_compaction_status(
+                last_known.compaction().clone(),
+                &last_known.pending_updates,
+                &deleted_regions,
+                &new_entries,
+            );
 
             let overridden_retry_config = calculate_overridden_retry_policy(
                 last_known.overridden_retry_config.clone(),
@@ -132,9 +141,12 @@ where
                 component_size,
                 owned_resources,
                 total_linear_memory_size,
-                extensions: WorkerStatusRecordExtensions::Extension2 {
+                extensions: WorkerStatusRecordExtensions::Extension5 {
                     active_plugins,
                     deleted_regions,
+                    labels,
+                    compaction,
+                    fork,
                 },
             };
             Ok(result)
@@ -163,7 +175,7 @@ fn calculate_latest_worker_status(
         }
 
         match entry {
-            OplogEntry::Create { .. } => {
+            OplogEntry::Create { .. } | OplogEntry::CreateV2 { .. } => {
                 result = WorkerStatus::Idle;
             }
             OplogEntry::ImportedFunctionInvokedV1 { .. } => {
@@ -172,6 +184,9 @@ fn calculate_latest_worker_status(
             OplogEntry::ImportedFunctionInvoked { .. } => {
                 result = WorkerStatus::Running;
             }
+            OplogEntry::ExportedFunctionInvokedV1 { .. } => {
+                result = WorkerStatus::Running;
+            }
             OplogEntry::ExportedFunctionInvoked { .. } => {
                 result = WorkerStatus::Running;
             }
@@ -250,6 +265,7 @@ fn calculate_latest_worker_status(
             OplogEntry::DeactivatePlugin { .. } => {}
             OplogEntry::Revert { .. } => {}
             OplogEntry::CancelPendingInvocation { .. } => {}
+            OplogEntry::ChangeLabels { .. } => {}
         }
     }
     result
@@ -375,7 +391,10 @@ fn calculate_pending_invocations(
                     invocation: invocation.clone(),
                 });
             }
-            OplogEntry::ExportedFunctionInvoked {
+            OplogEntry::ExportedFunctionInvokedV1 {
+                idempotency_key, ..
+            }
+            | OplogEntry::ExportedFunctionInvoked {
                 idempotency_key, ..
             } => {
                 result.retain(|invocation| match invocation {
@@ -456,6 +475,11 @@ fn calculate_update_fields(
                 component_version,
                 component_size,
                 ..
+            }
+            | OplogEntry::CreateV2 {
+                component_version,
+                component_size,
+                ..
             } => {
                 version = *component_version;
                 size = *component_size;
@@ -546,7 +570,10 @@ fn calculate_invocation_results(
         }
 
         match entry {
-            OplogEntry::ExportedFunctionInvoked {
+            OplogEntry::ExportedFunctionInvokedV1 {
+                idempotency_key, ..
+            }
+            | OplogEntry::ExportedFunctionInvoked {
                 idempotency_key, ..
             } => {
                 current_idempotency_key = Some(idempotency_key.clone());
@@ -590,6 +617,10 @@ fn calculate_total_linear_memory_size(
             OplogEntry::Create {
                 initial_total_linear_memory_size,
                 ..
+            }
+            | OplogEntry::CreateV2 {
+                initial_total_linear_memory_size,
+                ..
             } => {
                 result = *initial_total_linear_memory_size;
             }
@@ -678,6 +709,78 @@ fn calculate_active_plugins(
     result
 }
 
+fn calculate_labels(
+    initial: BTreeMap<String, String>,
+    entries: &BTreeMap<OplogIndex, OplogEntry>,
+) -> BTreeMap<String, String> {
+    // Labels are not part of the state of the worker, so they are kept even if the
+    // entry changing them gets into a deleted region
+    let mut result = initial;
+    for entry in entries.values() {
+        if let OplogEntry::ChangeLabels { labels, .. } = entry {
+            result = labels.clone();
+        }
+    }
+    result
+}
+
+fn calculate_compaction_status(
+    initial: OplogCompactionStatus,
+    initial_pending_updates: &VecDeque<TimestampedUpdateDescription>,
+    deleted_regions: &DeletedRegions,
+    entries: &BTreeMap<OplogIndex, OplogEntry>,
+) -> OplogCompactionStatus {
+    let mut result = initial;
+
+    // The entries after the first one are missing if the oplog has been truncated
+    let mut indices = entries.keys();
+    if let (Some(first), Some(second)) = (indices.next(), indices.next()) {
+        if *first == OplogIndex::INITIAL && *second > first.next() {
+            result.truncated_until = Some(*second);
+        }
+    }
+
+    let mut pending_updates = initial_pending_updates.clone();
+    for (oplog_idx, entry) in entries {
+        // Skipping entries in deleted regions (by revert)
+        if deleted_regions.is_in_deleted_region(*oplog_idx) {
+            continue;
+        }
+
+        match entry {
+            OplogEntry::PendingUpdate {
+                timestamp,
+                description,
+            } => {
+                pending_updates.push_back(TimestampedUpdateDescription {
+                    timestamp: *timestamp,
+                    oplog_index: *oplog_idx,
+                    description: description.clone(),
+                });
+            }
+            OplogEntry::FailedUpdate { .. } => {
+                pending_updates.pop_front();
+                result.last_failed_attempt_index = Some(*oplog_idx);
+                result.bytes_since_last_attempt = 0;
+            }
+            OplogEntry::SuccessfulUpdate { .. } | OplogEntry::SuccessfulUpdateV1 { .. } => {
+                if let Some(TimestampedUpdateDescription {
+                    timestamp,
+                    oplog_index,
+                    description: UpdateDescription::SnapshotBased { .. },
+                }) = pending_updates.pop_front()
+                {
+                    result.last_snapshot_index = Some(oplog_index);
+                    result.last_snapshot_timestamp = Some(timestamp);
+                    result.bytes_since_last_attempt = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod test {
     use test_r::test;
@@ -994,6 +1097,30 @@ mod test {
         run_test_case(test_case).await;
     }
 
+    #[test]
+    async fn compaction_by_snapshots() {
+        let k1 = IdempotencyKey::fresh();
+        let k2 = IdempotencyKey::fresh();
+        let snapshot = UpdateDescription::SnapshotBased {
+            target_version: 1,
+            payload: OplogPayload::Inline(vec![1, 2, 3]),
+        };
+
+        let test_case = TestCase::builder(1)
+            .exported_function_invoked("a", &0, k1.clone())
+            .grow_memory(10)
+            .exported_function_completed(&'x', k1)
+            .failed_update(snapshot.clone())
+            .pending_invocation(WorkerInvocation::ManualUpdate { target_version: 1 })
+            .pending_update(&snapshot)
+            .successful_update(snapshot, 100, &HashSet::new())
+            .exported_function_invoked("b", &1, k2.clone())
+            .exported_function_completed(&'y', k2)
+            .build();
+
+        run_test_case(test_case).await;
+    }
+
     struct TestCaseBuilder {
         entries: Vec<TestEntry>,
         previous_status_record: WorkerStatusRecord,
@@ -1002,6 +1129,18 @@ mod test {
 
     impl TestCaseBuilder {
         pub fn new(owned_worker_id: OwnedWorkerId, component_version: ComponentVersion) -> Self {
+            let create = OplogEntry::create(
+                owned_worker_id.worker_id(),
+                component_version,
+                vec![],
+                vec![],
+                BTreeMap::new(),
+                owned_worker_id.account_id(),
+                None,
+                100,
+                200,
+                HashSet::new(),
+            );
             let status = WorkerStatusRecord {
                 component_version,
                 component_size: 100,
@@ -1011,17 +1150,7 @@ mod test {
             };
             TestCaseBuilder {
                 entries: vec![TestEntry {
-                    oplog_entry: OplogEntry::create(
-                        owned_worker_id.worker_id(),
-                        component_version,
-                        vec![],
-                        vec![],
-                        owned_worker_id.account_id(),
-                        None,
-                        100,
-                        200,
-                        HashSet::new(),
-                    ),
+                    oplog_entry: create,
                     expected_status: status.clone(),
                 }],
                 previous_status_record: status,
@@ -1055,6 +1184,7 @@ mod test {
                     function_name: function_name.to_string(),
                     request: OplogPayload::Inline(serialize(request).unwrap().to_vec()),
                     idempotency_key: idempotency_key.clone(),
+                    invocation_context: InvocationContextStack::fresh(),
                 },
                 move |mut status| {
                     status.current_idempotency_key = Some(idempotency_key);
@@ -1170,6 +1300,7 @@ mod test {
                 status.successful_updates = old_status.successful_updates;
                 status.failed_updates = old_status.failed_updates;
                 status.invocation_results = old_status.invocation_results;
+                *status.compaction_mut() = old_status.compaction().clone();
 
                 status
             })
@@ -1252,11 +1383,17 @@ mod test {
                     new_active_plugins.clone(),
                 )),
                 move |mut status| {
-                    let pending = status.pending_updates.pop_front();
+                    let pending = status.pending_updates.pop_front().unwrap();
                     status.successful_updates.push(SuccessfulUpdateRecord {
-                        timestamp: pending.unwrap().timestamp,
+                        timestamp: pending.timestamp,
                         target_version: *update_description.target_version(),
                     });
+                    if let UpdateDescription::SnapshotBased { .. } = pending.description {
+                        let compaction = status.compaction_mut();
+                        compaction.last_snapshot_index = Some(pending.oplog_index);
+                        compaction.last_snapshot_timestamp = Some(pending.timestamp);
+                        compaction.bytes_since_last_attempt = 0;
+                    }
                     status.component_size = new_component_size;
                     status.component_version = *update_description.target_version();
                     *status.active_plugins_mut() = new_active_plugins.clone();
@@ -1284,6 +1421,10 @@ mod test {
                     details: Some("details".to_string()),
                 });
                 status.pending_updates.pop_front();
+                let oplog_idx = status.oplog_idx;
+                let compaction = status.compaction_mut();
+                compaction.last_failed_attempt_index = Some(oplog_idx);
+                compaction.bytes_since_last_attempt = 0;
 
                 if status.skipped_regions.is_overridden() {
                     status.skipped_regions.drop_override();
@@ -1401,6 +1542,7 @@ mod test {
             &self,
             _account_id: &AccountId,
             _component_id: &ComponentId,
+            _worker_name_prefix: Option<&str>,
             _cursor: ScanCursor,
             _count: u64,
         ) -> Result<(ScanCursor, Vec<OwnedWorkerId>), GolemError> {
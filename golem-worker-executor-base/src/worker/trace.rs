@@ -0,0 +1,199 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::services::oplog::OplogService;
+use golem_common::model::invocation_context::TraceId;
+use golem_common::model::invocation_tree::{TracedInvocation, TracedInvocationStatus};
+use golem_common::model::oplog::{OplogEntry, OplogIndex};
+use golem_common::model::{OwnedWorkerId, WorkerId};
+use std::sync::Arc;
+
+const READ_CHUNK_SIZE: u64 = 100;
+
+/// Reads the invocations of a worker belonging to a trace from its oplog
+pub async fn get_traced_invocations(
+    oplog_service: Arc<dyn OplogService + Send + Sync>,
+    owned_worker_id: &OwnedWorkerId,
+    trace_id: &TraceId,
+) -> Vec<TracedInvocation> {
+    let last_index = oplog_service.get_last_index(owned_worker_id).await;
+
+    let mut collector = TracedInvocationCollector::new(&owned_worker_id.worker_id, trace_id);
+    let mut current_index = OplogIndex::INITIAL;
+
+    while current_index <= last_index {
+        let entries = oplog_service
+            .read(owned_worker_id, current_index, READ_CHUNK_SIZE)
+            .await;
+
+        match entries.last_key_value() {
+            Some((last_read, _)) => current_index = last_read.next(),
+            None => break,
+        }
+
+        for entry in entries.values() {
+            collector.add(entry);
+        }
+    }
+
+    collector.invocations
+}
+
+// Folds the entries of an oplog to the invocations belonging to a trace
+struct TracedInvocationCollector<'a> {
+    worker_id: &'a WorkerId,
+    trace_id: &'a TraceId,
+    invocations: Vec<TracedInvocation>,
+    // The invocation of the trace in progress at the current entry, if any
+    current: Option<usize>,
+}
+
+impl<'a> TracedInvocationCollector<'a> {
+    fn new(worker_id: &'a WorkerId, trace_id: &'a TraceId) -> Self {
+        Self {
+            worker_id,
+            trace_id,
+            invocations: Vec::new(),
+            current: None,
+        }
+    }
+
+    fn add(&mut self, entry: &OplogEntry) {
+        match entry {
+            OplogEntry::ExportedFunctionInvoked {
+                timestamp,
+                function_name,
+                idempotency_key,
+                invocation_context,
+                ..
+            } if &invocation_context.trace_id == self.trace_id => {
+                self.current = Some(self.invocations.len());
+                self.invocations.push(TracedInvocation {
+                    worker_id: self.worker_id.clone(),
+                    idempotency_key: idempotency_key.clone(),
+                    function_name: function_name.clone(),
+                    span_id: invocation_context.current_span_id().clone(),
+                    ancestor_span_ids: invocation_context
+                        .spans
+                        .iter()
+                        .skip(1)
+                        .map(|span| span.span_id().clone())
+                        .collect(),
+                    started_at: *timestamp,
+                    completed_at: None,
+                    status: TracedInvocationStatus::Running,
+                    error: None,
+                });
+            }
+            OplogEntry::ExportedFunctionInvokedV1 { .. }
+            | OplogEntry::ExportedFunctionInvoked { .. } => {
+                self.current = None;
+            }
+            OplogEntry::ExportedFunctionCompleted { timestamp, .. } => {
+                if let Some(invocation) = self.current_invocation() {
+                    invocation.completed_at = Some(*timestamp);
+                    invocation.status = TracedInvocationStatus::Completed;
+                    invocation.error = None;
+                }
+                self.current = None;
+            }
+            OplogEntry::Error { timestamp, error } => {
+                // The invocation may still be retried, in which case it is followed by more
+                // entries of the same invocation
+                if let Some(invocation) = self.current_invocation() {
+                    invocation.completed_at = Some(*timestamp);
+                    invocation.status = TracedInvocationStatus::Failed;
+                    invocation.error = Some(error.to_string(""));
+                }
+            }
+            OplogEntry::Exited { timestamp } => {
+                if let Some(invocation) = self.current_invocation() {
+                    invocation.completed_at = Some(*timestamp);
+                    invocation.status = TracedInvocationStatus::Completed;
+                }
+                self.current = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn current_invocation(&mut self) -> Option<&mut TracedInvocation> {
+        self.current
+            .and_then(|index| self.invocations.get_mut(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::invocation_context::{InvocationContextStack, SpanId};
+    use golem_common::model::oplog::{OplogPayload, WorkerError};
+    use golem_common::model::{ComponentId, IdempotencyKey, Timestamp};
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn invoked(invocation_context: &InvocationContextStack, key: &str) -> OplogEntry {
+        OplogEntry::ExportedFunctionInvoked {
+            timestamp: Timestamp::now_utc(),
+            function_name: "golem:it/api.{call}".to_string(),
+            request: OplogPayload::Inline(vec![]),
+            idempotency_key: IdempotencyKey::new(key.to_string()),
+            invocation_context: invocation_context.clone(),
+        }
+    }
+
+    #[test]
+    fn only_the_invocations_of_the_trace_are_collected() {
+        let worker_id = WorkerId {
+            component_id: ComponentId(Uuid::new_v4()),
+            worker_name: "worker".to_string(),
+        };
+        let traced = InvocationContextStack::fresh();
+        let mut caller = traced.clone();
+        caller.push(traced.spans.first().start_span(Some(SpanId::generate())));
+        let other = InvocationContextStack::fresh();
+
+        let entries = vec![
+            invoked(&caller, "traced-1"),
+            OplogEntry::error(WorkerError::OutOfMemory),
+            OplogEntry::ExportedFunctionCompleted {
+                timestamp: Timestamp::now_utc(),
+                response: OplogPayload::Inline(vec![]),
+                consumed_fuel: 0,
+            },
+            invoked(&other, "other"),
+            OplogEntry::error(WorkerError::OutOfMemory),
+            invoked(&traced, "traced-2"),
+            OplogEntry::error(WorkerError::OutOfMemory),
+        ];
+
+        let mut collector = TracedInvocationCollector::new(&worker_id, &traced.trace_id);
+        for entry in &entries {
+            collector.add(entry);
+        }
+
+        let invocations = collector.invocations;
+        assert_eq!(invocations.len(), 2);
+        assert_eq!(invocations[0].idempotency_key.value, "traced-1");
+        assert_eq!(invocations[0].status, TracedInvocationStatus::Completed);
+        assert_eq!(invocations[0].error, None);
+        assert_eq!(
+            invocations[0].ancestor_span_ids,
+            vec![traced.current_span_id().clone()]
+        );
+        assert_eq!(invocations[1].idempotency_key.value, "traced-2");
+        assert_eq!(invocations[1].status, TracedInvocationStatus::Failed);
+        assert!(invocations[1].error.is_some());
+    }
+}
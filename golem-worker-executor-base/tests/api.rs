@@ -20,8 +20,11 @@ use crate::{LastUniqueId, Tracing, WorkerExecutorTestDependencies};
 use assert2::check;
 use axum::routing::get;
 use axum::Router;
+use golem_api_grpc::proto::golem::common::ResourceLimits;
 use golem_api_grpc::proto::golem::worker::v1::{worker_execution_error, ComponentParseFailed};
-use golem_api_grpc::proto::golem::workerexecutor::v1::CompletePromiseRequest;
+use golem_api_grpc::proto::golem::workerexecutor::v1::{
+    invoke_worker_response, CompletePromiseRequest, InvokeWorkerRequest,
+};
 use golem_common::model::oplog::{IndexedResourceKey, OplogIndex, WorkerResourceId};
 use golem_common::model::{
     AccountId, ComponentId, FilterComparator, IdempotencyKey, PromiseId, ScanCursor,
@@ -44,7 +47,7 @@ use std::io::Write;
 use std::net::SocketAddr;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 use system_interface::fs::FileIoExt;
 use tokio::time::sleep;
 use tracing::{debug, info};
@@ -92,6 +95,76 @@ async fn interruption(
     check!(worker_error_message(&result.err().unwrap()).contains("Interrupted via the Golem API"));
 }
 
+#[test]
+#[tracing::instrument]
+#[timeout(120_000)]
+async fn drain_interrupts_invocations_running_past_the_timeout(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await.unwrap();
+
+    let component_id = executor.component("interruption").store().await;
+    let worker_id = executor.start_worker(&component_id, "drain-1").await;
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let fiber = tokio::spawn(async move {
+        executor_clone
+            .invoke_and_await(worker_id_clone, "run", vec![])
+            .await
+    });
+
+    executor
+        .wait_for_status(&worker_id, WorkerStatus::Running, Duration::from_secs(10))
+        .await;
+
+    let drained = executor.drain(Duration::from_secs(1)).await;
+    let _ = fiber.await;
+
+    drop(executor);
+
+    check!(drained.interrupted_workers == 1);
+    check!(!drained.released_shard_ids.is_empty());
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout(120_000)]
+async fn drain_waits_for_invocations_completing_within_the_timeout(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await.unwrap();
+
+    let component_id = executor.component("interruption").store().await;
+    let worker_id = executor.start_worker(&component_id, "drain-2").await;
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let fiber = tokio::spawn(async move {
+        executor_clone
+            .invoke_and_await(worker_id_clone, "run", vec![])
+            .await
+    });
+
+    executor
+        .wait_for_status(&worker_id, WorkerStatus::Running, Duration::from_secs(10))
+        .await;
+
+    let drained = executor.drain(Duration::from_secs(60)).await;
+    let result = fiber.await.unwrap();
+
+    drop(executor);
+
+    check!(drained.interrupted_workers == 0);
+    check!(result == Ok(vec![Value::String("done".to_string())]));
+}
+
 #[test]
 #[tracing::instrument]
 #[timeout(120_000)]
@@ -528,6 +601,194 @@ async fn promise(
     );
 }
 
+async fn complete_promise(
+    executor: &TestWorkerExecutor,
+    worker_id: &WorkerId,
+    promise_id: &Value,
+    data: Vec<u8>,
+) {
+    let oplog_idx = match promise_id {
+        Value::Record(fields) => match fields[1] {
+            Value::U64(oplog_idx) => oplog_idx,
+            _ => panic!("Unexpected promise id: {promise_id:?}"),
+        },
+        _ => panic!("Unexpected promise id: {promise_id:?}"),
+    };
+
+    executor
+        .client()
+        .await
+        .expect("Failed to get client")
+        .complete_promise(CompletePromiseRequest {
+            promise_id: Some(
+                PromiseId {
+                    worker_id: worker_id.clone(),
+                    oplog_idx: OplogIndex::from_u64(oplog_idx),
+                }
+                .into(),
+            ),
+            data,
+            account_id: Some(
+                AccountId {
+                    value: "test-account".to_string(),
+                }
+                .into(),
+            ),
+        })
+        .await
+        .unwrap();
+}
+
+fn promise_ids(promise_ids: &[Value]) -> ValueAndType {
+    ValueAndType::new(
+        Value::List(promise_ids.to_vec()),
+        analysed_type::list(PromiseId::get_type()),
+    )
+}
+
+fn awaited(promises: Vec<(u32, Vec<u8>)>, timed_out: bool) -> Value {
+    Value::Record(vec![
+        Value::List(
+            promises
+                .into_iter()
+                .map(|(idx, data)| {
+                    Value::Tuple(vec![
+                        Value::U32(idx),
+                        Value::List(data.into_iter().map(Value::U8).collect()),
+                    ])
+                })
+                .collect(),
+        ),
+        Value::List(vec![]),
+        Value::Bool(timed_out),
+    ])
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout(120_000)]
+async fn await_any_promise(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await.unwrap();
+
+    let component_id = executor.component("promise").store().await;
+    let worker_id = executor.start_worker(&component_id, "await-any-1").await;
+
+    let promise1 = executor
+        .invoke_and_await(&worker_id, "golem:it/api.{create}", vec![])
+        .await
+        .unwrap()[0]
+        .clone();
+    let promise2 = executor
+        .invoke_and_await(&worker_id, "golem:it/api.{create}", vec![])
+        .await
+        .unwrap()[0]
+        .clone();
+
+    let executor_clone = executor.clone();
+    let worker_id_clone = worker_id.clone();
+    let awaited_promises = promise_ids(&[promise1.clone(), promise2.clone()]);
+
+    let fiber = tokio::spawn(async move {
+        executor_clone
+            .invoke_and_await(
+                &worker_id_clone,
+                "golem:it/api.{await-any}",
+                vec![awaited_promises, None::<u64>.into_value_and_type()],
+            )
+            .await
+    });
+
+    // While waiting for the promises, the worker gets suspended, and it gets resumed by the
+    // completion of the second one
+    executor
+        .wait_for_status(&worker_id, WorkerStatus::Suspended, Duration::from_secs(10))
+        .await;
+
+    complete_promise(&executor, &worker_id, &promise2, vec![2]).await;
+
+    let result = fiber.await.unwrap();
+
+    // Once both promises are completed, the recovered worker still sees the recorded outcome
+    complete_promise(&executor, &worker_id, &promise1, vec![1]).await;
+
+    drop(executor);
+    let executor = start(deps, &context).await.unwrap();
+
+    let history = executor
+        .invoke_and_await(&worker_id, "golem:it/api.{awaited-history}", vec![])
+        .await;
+
+    executor.check_oplog_is_queryable(&worker_id).await;
+    drop(executor);
+
+    check!(result == Ok(vec![awaited(vec![(1, vec![2])], false)]));
+    check!(history == Ok(vec![Value::List(vec![awaited(vec![(1, vec![2])], false)])]));
+}
+
+#[test]
+#[tracing::instrument]
+#[timeout(120_000)]
+async fn await_all_promises_with_timeout(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await.unwrap();
+
+    let component_id = executor.component("promise").store().await;
+    let worker_id = executor.start_worker(&component_id, "await-all-1").await;
+
+    let promise1 = executor
+        .invoke_and_await(&worker_id, "golem:it/api.{create}", vec![])
+        .await
+        .unwrap()[0]
+        .clone();
+    let promise2 = executor
+        .invoke_and_await(&worker_id, "golem:it/api.{create}", vec![])
+        .await
+        .unwrap()[0]
+        .clone();
+
+    complete_promise(&executor, &worker_id, &promise1, vec![1]).await;
+
+    // The worker is suspended until the deadline, as the second promise never gets completed
+    let start_time = Instant::now();
+    let result = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api.{await-all}",
+            vec![
+                promise_ids(&[promise1.clone(), promise2.clone()]),
+                Some(Duration::from_secs(2).as_nanos() as u64).into_value_and_type(),
+            ],
+        )
+        .await;
+    let elapsed = start_time.elapsed();
+
+    // Completing the second promise later does not change the recorded outcome
+    complete_promise(&executor, &worker_id, &promise2, vec![2]).await;
+
+    drop(executor);
+    let executor = start(deps, &context).await.unwrap();
+
+    let history = executor
+        .invoke_and_await(&worker_id, "golem:it/api.{awaited-history}", vec![])
+        .await;
+
+    executor.check_oplog_is_queryable(&worker_id).await;
+    drop(executor);
+
+    check!(result == Ok(vec![awaited(vec![(0, vec![1])], true)]));
+    check!(elapsed >= Duration::from_secs(2));
+    check!(history == Ok(vec![Value::List(vec![awaited(vec![(0, vec![1])], true)])]));
+}
+
 #[test]
 #[tracing::instrument]
 #[timeout(120_000)]
@@ -3142,6 +3403,137 @@ async fn scheduled_invocation_self(
     executor.check_oplog_is_queryable(&worker_id).await;
 }
 
+/// Test enqueuing invocations at a given time with invoke_at: the ones in the future are only
+/// enqueued once due, and the ones in the past immediately
+#[test]
+#[tracing::instrument]
+#[timeout(120_000)]
+async fn invoke_at_enqueues_invocations_when_due(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await.unwrap();
+
+    let component_id = executor.component("shopping-cart").store().await;
+    let worker_id = executor.start_worker(&component_id, "invoke-at-1").await;
+
+    executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api.{initialize-cart}",
+            vec!["test-user-1".into_value_and_type()],
+        )
+        .await
+        .unwrap();
+
+    let add_item =
+        |worker_id: TargetWorkerId, product_id: &str, invoke_at: SystemTime| InvokeWorkerRequest {
+            worker_id: Some(worker_id.into()),
+            name: "golem:it/api.{add-item}".to_string(),
+            input: vec![vec![
+                ("product-id", product_id.into_value_and_type()),
+                ("name", "Golem T-Shirt M".into_value_and_type()),
+                ("price", 100.0f32.into_value_and_type()),
+                ("quantity", 1u32.into_value_and_type()),
+            ]
+            .into_value_and_type()
+            .value
+            .into()],
+            idempotency_key: Some(IdempotencyKey::fresh().into()),
+            account_id: Some(
+                AccountId {
+                    value: "test-account".to_string(),
+                }
+                .into(),
+            ),
+            account_limits: Some(ResourceLimits {
+                available_fuel: i64::MAX,
+                max_memory_per_worker: i64::MAX,
+            }),
+            context: None,
+            invoke_at: Some(invoke_at.into()),
+        };
+
+    let cart_size = || async {
+        let contents = executor
+            .invoke_and_await(&worker_id, "golem:it/api.{get-cart-contents}", vec![])
+            .await
+            .unwrap();
+        match &contents[0] {
+            Value::List(items) => items.len(),
+            _ => panic!("Expected a list"),
+        }
+    };
+
+    let mut client = executor.client().await.expect("Failed to get client");
+
+    let past = client
+        .invoke_worker(add_item(
+            worker_id.clone().into(),
+            "G1000",
+            SystemTime::now() - Duration::from_secs(3600),
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let size_after_past = cart_size().await;
+
+    let scheduled_at = Instant::now();
+    let future = client
+        .invoke_worker(add_item(
+            worker_id.clone().into(),
+            "G1001",
+            SystemTime::now() + Duration::from_secs(5),
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+    let size_before_due = cart_size().await;
+
+    let mut size_after_due = size_before_due;
+    while size_after_due < 2 && scheduled_at.elapsed() < Duration::from_secs(60) {
+        sleep(Duration::from_millis(500)).await;
+        size_after_due = cart_size().await;
+    }
+    let due_after = scheduled_at.elapsed();
+
+    // Ephemeral workers have no name to invoke later
+    let nameless = client
+        .invoke_worker(add_item(
+            TargetWorkerId {
+                component_id: component_id.clone(),
+                worker_name: None,
+            },
+            "G1002",
+            SystemTime::now() + Duration::from_secs(5),
+        ))
+        .await
+        .unwrap()
+        .into_inner();
+
+    executor.check_oplog_is_queryable(&worker_id).await;
+    drop(executor);
+
+    check!(matches!(
+        past.result,
+        Some(invoke_worker_response::Result::Success(_))
+    ));
+    check!(size_after_past == 1);
+    check!(matches!(
+        future.result,
+        Some(invoke_worker_response::Result::Success(_))
+    ));
+    check!(size_before_due == 1);
+    check!(size_after_due == 2);
+    check!(due_after >= Duration::from_secs(4));
+    check!(matches!(
+        nameless.result,
+        Some(invoke_worker_response::Result::Failure(_))
+    ));
+}
+
 /// Test scheduling an invocation for a different worker.
 #[test]
 #[tracing::instrument]
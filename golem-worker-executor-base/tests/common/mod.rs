@@ -1,9 +1,12 @@
+#[cfg(feature = "postgres-storage-integration-tests")]
+pub mod postgres;
+
 use anyhow::Error;
 use async_trait::async_trait;
 use golem_worker_executor_base::services::additional_config::{
     ComponentServiceConfig, ComponentServiceLocalConfig, DefaultAdditionalGolemConfig,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
 use golem_service_base::storage::blob::BlobStorage;
@@ -18,18 +21,19 @@ use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use std::path::Path;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, RwLock, Weak};
+use std::time::Duration;
 
 use golem_common::model::{
     AccountId, ComponentFilePath, ComponentId, ComponentVersion, IdempotencyKey, OwnedWorkerId,
-    PluginInstallationId, ScanCursor, TargetWorkerId, WorkerFilter, WorkerId, WorkerMetadata,
-    WorkerStatus, WorkerStatusRecord,
+    PluginInstallationId, ScanCursor, TargetWorkerId, Timestamp, WorkerFilter, WorkerId,
+    WorkerMetadata, WorkerStatus, WorkerStatusRecord,
 };
 use golem_service_base::config::{BlobStorageConfig, LocalFileSystemBlobStorageConfig};
 use golem_worker_executor_base::error::GolemError;
 use golem_worker_executor_base::services::golem_config::{
     CompiledComponentServiceConfig, CompiledComponentServiceEnabledConfig, GolemConfig,
-    IndexedStorageConfig, KeyValueStorageConfig, MemoryConfig, ShardManagerServiceConfig,
-    WorkerServiceGrpcConfig,
+    IndexedStorageConfig, KeyValueStorageConfig, MemoryConfig, OplogCompactionConfig, OplogConfig,
+    ShardManagerServiceConfig, WorkerServiceGrpcConfig,
 };
 
 use golem_worker_executor_base::durable_host::{
@@ -66,11 +70,14 @@ use tokio::task::JoinSet;
 use golem_common::config::RedisConfig;
 
 use golem_api_grpc::proto::golem::workerexecutor::v1::{
-    get_running_workers_metadata_response, get_workers_metadata_response,
-    GetRunningWorkersMetadataRequest, GetRunningWorkersMetadataSuccessResponse,
-    GetWorkersMetadataRequest, GetWorkersMetadataSuccessResponse,
+    drain_response, get_running_workers_metadata_response, get_workers_metadata_response,
+    DrainRequest, DrainSuccess, GetRunningWorkersMetadataRequest,
+    GetRunningWorkersMetadataSuccessResponse, GetWorkersMetadataRequest,
+    GetWorkersMetadataSuccessResponse,
+};
+use golem_common::model::invocation_context::{
+    AttributeValue, InvocationContextSpan, InvocationContextStack, TraceId, DEADLINE_ATTRIBUTE,
 };
-use golem_common::model::invocation_context::InvocationContextStack;
 use golem_common::model::oplog::WorkerResourceId;
 use golem_test_framework::components::component_compilation_service::ComponentCompilationService;
 use golem_test_framework::components::rdb::Rdb;
@@ -146,6 +153,27 @@ impl TestWorkerExecutor {
         }
     }
 
+    pub async fn drain(&self, timeout: Duration) -> DrainSuccess {
+        let response = self
+            .client()
+            .await
+            .expect("Failed to get client")
+            .drain(DrainRequest {
+                timeout_millis: Some(timeout.as_millis() as u64),
+            })
+            .await
+            .expect("Failed to drain the worker executor")
+            .into_inner();
+
+        match response.result {
+            None => panic!("No response from drain"),
+            Some(drain_response::Result::Success(success)) => success,
+            Some(drain_response::Result::Failure(error)) => {
+                panic!("Failed to drain the worker executor: {error:?}")
+            }
+        }
+    }
+
     pub async fn get_workers_metadata(
         &self,
         component_id: &ComponentId,
@@ -293,6 +321,31 @@ impl TestContext {
     }
 }
 
+/// An invocation context carrying the given deadline, for invoking workers with a deadline
+pub fn invocation_context_with_deadline(
+    deadline: Timestamp,
+) -> golem_api_grpc::proto::golem::worker::InvocationContext {
+    let stack = InvocationContextStack::new(
+        TraceId::generate(),
+        InvocationContextSpan::new_with_attributes(
+            None,
+            HashMap::from([(
+                DEADLINE_ATTRIBUTE.to_string(),
+                AttributeValue::String(deadline.to_string()),
+            )]),
+        ),
+        Vec::new(),
+    );
+
+    golem_api_grpc::proto::golem::worker::InvocationContext {
+        parent: None,
+        args: Vec::new(),
+        env: HashMap::new(),
+        tracing: Some(stack.into()),
+        wasi_config_vars: HashMap::new(),
+    }
+}
+
 pub async fn start(
     deps: &WorkerExecutorTestDependencies,
     context: &TestContext,
@@ -304,6 +357,39 @@ pub async fn start_limited(
     deps: &WorkerExecutorTestDependencies,
     context: &TestContext,
     system_memory_override: Option<u64>,
+) -> anyhow::Result<TestWorkerExecutor> {
+    start_customized(
+        deps,
+        context,
+        MemoryConfig {
+            system_memory_override,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+pub async fn start_customized(
+    deps: &WorkerExecutorTestDependencies,
+    context: &TestContext,
+    memory: MemoryConfig,
+) -> anyhow::Result<TestWorkerExecutor> {
+    start_with_config(deps, context, memory, OplogCompactionConfig::default()).await
+}
+
+pub async fn start_compacting(
+    deps: &WorkerExecutorTestDependencies,
+    context: &TestContext,
+    compaction: OplogCompactionConfig,
+) -> anyhow::Result<TestWorkerExecutor> {
+    start_with_config(deps, context, MemoryConfig::default(), compaction).await
+}
+
+async fn start_with_config(
+    deps: &WorkerExecutorTestDependencies,
+    context: &TestContext,
+    memory: MemoryConfig,
+    compaction: OplogCompactionConfig,
 ) -> anyhow::Result<TestWorkerExecutor> {
     let redis = deps.redis();
     let redis_monitor = deps.redis_monitor();
@@ -334,8 +420,9 @@ pub async fn start_limited(
             port: context.grpc_port(),
             access_token: "03494299-B515-4427-8C37-4C1C915679B7".to_string(),
         },
-        memory: MemoryConfig {
-            system_memory_override,
+        memory,
+        oplog: OplogConfig {
+            compaction,
             ..Default::default()
         },
         ..Default::default()
@@ -0,0 +1,75 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::config::DbPostgresConfig;
+use golem_service_base::db;
+use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
+use golem_service_base::storage::postgres::PostgresPool;
+use golem_test_framework::components::rdb::docker_postgres::DockerPostgresRdb;
+use golem_test_framework::components::rdb::{DbInfo, Rdb};
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use test_r::test_dep;
+use uuid::Uuid;
+
+/// A Postgres container shared by the storage tests, each of them getting a freshly migrated
+/// schema of its own
+pub struct PostgresStorageDb {
+    rdb: DockerPostgresRdb,
+}
+
+impl PostgresStorageDb {
+    pub async fn new() -> Self {
+        Self {
+            rdb: DockerPostgresRdb::new(true, false).await,
+        }
+    }
+
+    pub async fn pool(&self) -> PostgresPool {
+        let DbInfo::Postgres(info) = self.rdb.info() else {
+            panic!("Postgres database expected")
+        };
+        let config = DbPostgresConfig {
+            host: info.host,
+            database: info.database_name,
+            username: info.username,
+            password: info.password,
+            port: info.port,
+            max_connections: 10,
+            schema: Some(format!("test_{}", Uuid::new_v4().simple())),
+        };
+
+        db::postgres_migrate(
+            &config,
+            IncludedMigrationsDir::new(golem_worker_executor_base::db_migrations())
+                .postgres_migrations(),
+        )
+        .await
+        .expect("Cannot migrate the Postgres database");
+        PostgresPool::configured(&config)
+            .await
+            .expect("Cannot connect to the Postgres database")
+    }
+}
+
+impl Debug for PostgresStorageDb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PostgresStorageDb")
+    }
+}
+
+#[test_dep]
+async fn postgres_storage_db() -> Arc<PostgresStorageDb> {
+    Arc::new(PostgresStorageDb::new().await)
+}
@@ -0,0 +1,170 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::{start_compacting, TestContext, TestWorkerExecutor};
+use crate::{LastUniqueId, Tracing, WorkerExecutorTestDependencies};
+use assert2::check;
+use golem_common::model::oplog::OplogIndex;
+use golem_common::model::public_oplog::PublicOplogEntry;
+use golem_common::model::WorkerId;
+use golem_service_base::model::{RevertLastInvocations, RevertToOplogIndex, RevertWorkerTarget};
+use golem_test_framework::dsl::TestDslUnsafe;
+use golem_wasm_rpc::{IntoValueAndType, Value};
+use golem_worker_executor_base::services::golem_config::OplogCompactionConfig;
+use std::time::Duration;
+use test_r::{inherit_test_dep, test};
+use tokio::time::{sleep, Instant};
+
+inherit_test_dep!(WorkerExecutorTestDependencies);
+inherit_test_dep!(LastUniqueId);
+inherit_test_dep!(Tracing);
+
+fn compaction_config() -> OplogCompactionConfig {
+    OplogCompactionConfig {
+        enabled: true,
+        entry_threshold: Some(10),
+        size_threshold: None,
+        truncate: true,
+    }
+}
+
+async fn wait_for_truncation(executor: &TestWorkerExecutor, worker_id: &WorkerId) -> OplogIndex {
+    let start = Instant::now();
+    loop {
+        let (metadata, _) = executor.get_worker_metadata(worker_id).await.unwrap();
+        if let Some(truncated_until) = metadata.last_known_status.compaction().truncated_until {
+            break truncated_until;
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            panic!("The oplog of {worker_id} was not truncated");
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[test]
+#[tracing::instrument]
+async fn truncated_oplog_is_recovered_queried_and_protected_from_fork_and_revert(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start_compacting(deps, &context, compaction_config())
+        .await
+        .unwrap();
+
+    let component_id = executor.component("update-test-v3").store().await;
+    let worker_id = executor
+        .start_worker(&component_id, "truncated-oplog")
+        .await;
+
+    for value in 1..=10u64 {
+        let _ = executor
+            .invoke_and_await(
+                &worker_id,
+                "golem:component/api.{set}",
+                vec![value.into_value_and_type()],
+            )
+            .await
+            .unwrap();
+    }
+
+    let truncated_until = wait_for_truncation(&executor, &worker_id).await;
+
+    // Recovering the worker from its snapshot on a restarted executor
+    drop(executor);
+    let executor = start_compacting(deps, &context, compaction_config())
+        .await
+        .unwrap();
+
+    let recovered = executor
+        .invoke_and_await(&worker_id, "golem:component/api.{get}", vec![])
+        .await
+        .unwrap();
+
+    // The oplog query skips the dropped history
+    let oplog = executor.get_oplog(&worker_id, OplogIndex::INITIAL).await;
+    let from_dropped = executor
+        .get_oplog(&worker_id, OplogIndex::INITIAL.next())
+        .await;
+
+    // The fork would have to replay the dropped history
+    let fork_result = golem_test_framework::dsl::TestDsl::fork_worker(
+        &executor,
+        &worker_id,
+        &WorkerId {
+            component_id: component_id.clone(),
+            worker_name: "truncated-oplog-fork".to_string(),
+        },
+        truncated_until,
+    )
+    .await;
+
+    // Reverting to the dropped history, or past the snapshot is rejected
+    let revert_to_dropped_result = golem_test_framework::dsl::TestDsl::revert(
+        &executor,
+        &worker_id,
+        RevertWorkerTarget::RevertToOplogIndex(RevertToOplogIndex {
+            last_oplog_index: OplogIndex::INITIAL.next(),
+        }),
+    )
+    .await;
+    let revert_all_invocations_result = golem_test_framework::dsl::TestDsl::revert(
+        &executor,
+        &worker_id,
+        RevertWorkerTarget::RevertLastInvocations(RevertLastInvocations {
+            number_of_invocations: 100,
+        }),
+    )
+    .await;
+
+    // Reverting the invocations after the snapshot is still possible
+    let _ = executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:component/api.{set}",
+            vec![11u64.into_value_and_type()],
+        )
+        .await
+        .unwrap();
+    executor
+        .revert(
+            &worker_id,
+            RevertWorkerTarget::RevertLastInvocations(RevertLastInvocations {
+                number_of_invocations: 1,
+            }),
+        )
+        .await;
+    let reverted = executor
+        .invoke_and_await(&worker_id, "golem:component/api.{get}", vec![])
+        .await
+        .unwrap();
+
+    drop(executor);
+
+    check!(truncated_until > OplogIndex::INITIAL.next());
+    check!(recovered == vec![Value::U64(10)]);
+    check!(matches!(oplog[0], PublicOplogEntry::Create(_)));
+    check!(matches!(oplog[1], PublicOplogEntry::PendingUpdate(_)));
+    check!(matches!(
+        from_dropped[0],
+        PublicOplogEntry::PendingUpdate(_)
+    ));
+    check!(fork_result.is_err_and(|err| format!("{err:?}").contains("OplogTruncated")));
+    check!(revert_to_dropped_result.is_err_and(|err| format!("{err:?}").contains("OplogTruncated")));
+    check!(revert_all_invocations_result
+        .is_err_and(|err| format!("{err:?}").contains("OplogTruncated")));
+    check!(reverted == vec![Value::U64(10)]);
+}
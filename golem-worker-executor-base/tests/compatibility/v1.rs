@@ -780,7 +780,7 @@ pub fn oplog_entry() {
         wrapped_function_type: DurableFunctionType::ReadLocal,
     };
 
-    let oe3 = OplogEntry::ExportedFunctionInvoked {
+    let oe3 = OplogEntry::ExportedFunctionInvokedV1 {
         timestamp: Timestamp::from(1724701938466),
         function_name: "test:pkg/iface.{fn}".to_string(),
         request: OplogPayload::Inline(vec![0, 1, 2, 3, 4]),
@@ -1105,12 +1105,16 @@ pub fn rpc_error() {
     let rpc4 = RpcError::RemoteInternalError {
         details: "not working".to_string(),
     };
+    let rpc5 = RpcError::DeadlineExceeded {
+        deadline: Timestamp::from(1724701938466),
+    };
 
     let mut mint = Mint::new("tests/goldenfiles");
     backward_compatible("rpc_error_protocol_error", &mut mint, rpc1);
     backward_compatible("rpc_error_denied", &mut mint, rpc2);
     backward_compatible("rpc_error_not_found", &mut mint, rpc3);
     backward_compatible("rpc_error_remote_internal_error", &mut mint, rpc4);
+    backward_compatible("rpc_error_deadline_exceeded", &mut mint, rpc5);
 }
 
 #[test]
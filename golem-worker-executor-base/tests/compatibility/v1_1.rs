@@ -54,7 +54,7 @@ pub fn oplog_entry() {
         response: OplogPayload::Inline(vec![0, 1, 2, 3, 4]),
         wrapped_function_type: DurableFunctionType::ReadLocal,
     };
-    let oe27a = OplogEntry::Create {
+    let oe27a = OplogEntry::CreateV2 {
         timestamp: Timestamp::from(1724701938466),
         worker_id: WorkerId {
             component_id: ComponentId(
@@ -79,7 +79,7 @@ pub fn oplog_entry() {
             PluginInstallationId(Uuid::parse_str("339ED9E3-9D93-440C-BC07-377F56642ABB").unwrap()),
         ]),
     };
-    let oe27b = OplogEntry::Create {
+    let oe27b = OplogEntry::CreateV2 {
         timestamp: Timestamp::from(1724701938466),
         worker_id: WorkerId {
             component_id: ComponentId(
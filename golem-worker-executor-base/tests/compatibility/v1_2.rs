@@ -20,11 +20,14 @@ use golem_common::model::invocation_context::{
 use golem_common::model::oplog::OplogEntry;
 use golem_common::model::regions::OplogRegion;
 use golem_common::model::{
-    IdempotencyKey, OplogIndex, Timestamp, TimestampedWorkerInvocation, WorkerInvocation,
+    AccountId, ComponentId, IdempotencyKey, OplogIndex, PluginInstallationId, Timestamp,
+    TimestampedWorkerInvocation, WorkerId, WorkerInvocation,
 };
 use golem_wasm_rpc::Value;
+use std::collections::{BTreeMap, HashSet};
 use std::num::{NonZeroU128, NonZeroU64};
 use test_r::test;
+use uuid::Uuid;
 
 #[test]
 pub fn oplog_entry() {
@@ -43,9 +46,40 @@ pub fn oplog_entry() {
         },
     };
 
+    let oe33 = OplogEntry::Create {
+        timestamp: Timestamp::from(1724701938466),
+        worker_id: WorkerId {
+            component_id: ComponentId(
+                Uuid::parse_str("4B29BF7C-13F6-4E37-AC03-830B81EAD478").unwrap(),
+            ),
+            worker_name: "worker_name".to_string(),
+        },
+        component_version: 0,
+        args: vec!["hello".to_string(), "world".to_string()],
+        env: vec![
+            ("key1".to_string(), "value1".to_string()),
+            ("key2".to_string(), "value2".to_string()),
+        ],
+        wasi_config_vars: BTreeMap::from_iter(vec![
+            ("api-url".to_string(), "https://example.com".to_string()),
+            ("region".to_string(), "eu".to_string()),
+        ]),
+        account_id: AccountId {
+            value: "account_id".to_string(),
+        },
+        parent: None,
+        component_size: 100_000_000,
+        initial_total_linear_memory_size: 100_000_000,
+        initial_active_plugins: HashSet::from_iter(vec![
+            PluginInstallationId(Uuid::parse_str("E7AA7893-B8F8-4DC7-B3AC-3A9E3472EA18").unwrap()),
+            PluginInstallationId(Uuid::parse_str("339ED9E3-9D93-440C-BC07-377F56642ABB").unwrap()),
+        ]),
+    };
+
     let mut mint = Mint::new("tests/goldenfiles");
     backward_compatible("oplog_entry_revert", &mut mint, oe31);
     backward_compatible("oplog_entry_cancel_pending_invocation", &mut mint, oe32);
+    backward_compatible("oplog_entry_create_v12", &mut mint, oe33);
 }
 
 #[test]
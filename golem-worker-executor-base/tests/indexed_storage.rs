@@ -135,6 +135,42 @@ async fn sqlite_storage(
     Arc::new(SqliteIndexedStorageWrapper)
 }
 
+#[cfg(feature = "postgres-storage-integration-tests")]
+mod postgres {
+    use super::GetIndexedStorage;
+    use crate::common::postgres::PostgresStorageDb;
+    use async_trait::async_trait;
+    use golem_worker_executor_base::storage::indexed::postgres::PostgresIndexedStorage;
+    use golem_worker_executor_base::storage::indexed::IndexedStorage;
+    use std::fmt::{Debug, Formatter};
+    use std::sync::Arc;
+    use test_r::test_dep;
+
+    struct PostgresIndexedStorageWrapper {
+        db: Arc<PostgresStorageDb>,
+    }
+
+    impl Debug for PostgresIndexedStorageWrapper {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("PostgresIndexedStorageWrapper")
+        }
+    }
+
+    #[async_trait]
+    impl GetIndexedStorage for PostgresIndexedStorageWrapper {
+        async fn get_indexed_storage(&self) -> Arc<dyn IndexedStorage + Send + Sync> {
+            Arc::new(PostgresIndexedStorage::new(self.db.pool().await))
+        }
+    }
+
+    #[test_dep(tagged_as = "postgres")]
+    async fn postgres_storage(
+        db: &Arc<PostgresStorageDb>,
+    ) -> Arc<dyn GetIndexedStorage + Send + Sync> {
+        Arc::new(PostgresIndexedStorageWrapper { db: db.clone() })
+    }
+}
+
 #[test_dep(tagged_as = "ns1")]
 fn ns() -> IndexedStorageNamespace {
     IndexedStorageNamespace::OpLog
@@ -147,7 +183,10 @@ fn ns2() -> IndexedStorageNamespace {
 
 inherit_test_dep!(WorkerExecutorTestDependencies);
 
+#[cfg(not(feature = "postgres-storage-integration-tests"))]
 define_matrix_dimension!(is: Arc<dyn GetIndexedStorage + Send + Sync> -> "in_memory", "redis", "sqlite");
+#[cfg(feature = "postgres-storage-integration-tests")]
+define_matrix_dimension!(is: Arc<dyn GetIndexedStorage + Send + Sync> -> "in_memory", "redis", "sqlite", "postgres");
 
 #[test]
 #[tracing::instrument]
@@ -927,3 +966,43 @@ async fn drop_prefix_full(
 
     check!(result == vec![]);
 }
+
+#[test]
+#[tracing::instrument]
+async fn drop_range_keeps_surrounding_entries(
+    deps: &WorkerExecutorTestDependencies,
+    #[dimension(is)] is: &Arc<dyn GetIndexedStorage + Send + Sync>,
+    #[tagged_as("ns1")] ns: &IndexedStorageNamespace,
+    #[tagged_as("ns2")] ns2: &IndexedStorageNamespace,
+) {
+    let is = is.get_indexed_storage().await;
+
+    let key1 = "key1";
+    let value1 = "value1".as_bytes();
+    let value2 = "value2".as_bytes();
+    let value3 = "value3".as_bytes();
+    let value4 = "value4".as_bytes();
+
+    is.append("svc", "api", "entity", ns.clone(), key1, 10, value1)
+        .await
+        .unwrap();
+    is.append("svc", "api", "entity", ns.clone(), key1, 11, value2)
+        .await
+        .unwrap();
+    is.append("svc", "api", "entity", ns.clone(), key1, 12, value3)
+        .await
+        .unwrap();
+    is.append("svc", "api", "entity", ns.clone(), key1, 13, value4)
+        .await
+        .unwrap();
+
+    is.drop_range("svc", "api", ns.clone(), key1, 11, 12)
+        .await
+        .unwrap();
+    let result = is
+        .read("svc", "api", "entity", ns.clone(), key1, 1, 100)
+        .await
+        .unwrap();
+
+    check!(result == vec![(10, value1.into()), (13, value4.into())]);
+}
@@ -139,6 +139,42 @@ struct Namespaces {
     pub ns2: KeyValueStorageNamespace,
 }
 
+#[cfg(feature = "postgres-storage-integration-tests")]
+mod postgres {
+    use super::GetKeyValueStorage;
+    use crate::common::postgres::PostgresStorageDb;
+    use async_trait::async_trait;
+    use golem_worker_executor_base::storage::keyvalue::postgres::PostgresKeyValueStorage;
+    use golem_worker_executor_base::storage::keyvalue::KeyValueStorage;
+    use std::fmt::{Debug, Formatter};
+    use std::sync::Arc;
+    use test_r::test_dep;
+
+    struct PostgresKeyValueStorageWrapper {
+        db: Arc<PostgresStorageDb>,
+    }
+
+    impl Debug for PostgresKeyValueStorageWrapper {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("PostgresKeyValueStorageWrapper")
+        }
+    }
+
+    #[async_trait]
+    impl GetKeyValueStorage for PostgresKeyValueStorageWrapper {
+        async fn get_key_value_storage(&self) -> Arc<dyn KeyValueStorage + Send + Sync> {
+            Arc::new(PostgresKeyValueStorage::new(self.db.pool().await))
+        }
+    }
+
+    #[test_dep(tagged_as = "postgres")]
+    async fn postgres_storage(
+        db: &Arc<PostgresStorageDb>,
+    ) -> Arc<dyn GetKeyValueStorage + Send + Sync> {
+        Arc::new(PostgresKeyValueStorageWrapper { db: db.clone() })
+    }
+}
+
 #[test_dep(tagged_as = "ns1")]
 fn ns() -> Namespaces {
     Namespaces {
@@ -163,7 +199,10 @@ fn ns2() -> Namespaces {
 
 inherit_test_dep!(WorkerExecutorTestDependencies);
 
+#[cfg(not(feature = "postgres-storage-integration-tests"))]
 define_matrix_dimension!(kvs: Arc<dyn GetKeyValueStorage + Send + Sync> -> "in_memory", "redis", "sqlite");
+#[cfg(feature = "postgres-storage-integration-tests")]
+define_matrix_dimension!(kvs: Arc<dyn GetKeyValueStorage + Send + Sync> -> "in_memory", "redis", "sqlite", "postgres");
 define_matrix_dimension!(nss: Namespaces -> "ns1", "ns2");
 
 #[test]
@@ -544,6 +583,10 @@ async fn sets(
         .members_of_set("test", "api", "entity", ns.clone(), set2)
         .await
         .unwrap();
+    let size12 = kvs
+        .size_of_set("test", "api", "entity", ns.clone(), set1)
+        .await
+        .unwrap();
 
     kvs.remove_from_set("test", "api", "entity", ns.clone(), set1, value2)
         .await
@@ -576,6 +619,14 @@ async fn sets(
         .members_of_set("test", "api", "entity", ns.clone(), set2)
         .await
         .unwrap();
+    let size14 = kvs
+        .size_of_set("test", "api", "entity", ns.clone(), set1)
+        .await
+        .unwrap();
+    let size_missing = kvs
+        .size_of_set("test", "api", "entity", ns.clone(), "set3")
+        .await
+        .unwrap();
 
     assert2::check!(s11 == Vec::<Vec<u8>>::new());
     assert2::check!(s21 == Vec::<Vec<u8>>::new());
@@ -593,6 +644,158 @@ async fn sets(
     assert2::check!(s14.contains(&value1.to_vec().into()));
     assert2::check!(s24.len() == 1);
     assert2::check!(s24.contains(&value3.to_vec().into()));
+    assert2::check!(size12 == 2);
+    assert2::check!(size14 == 1);
+    assert2::check!(size_missing == 0);
+}
+
+#[test]
+#[tracing::instrument]
+async fn move_to_set(
+    _deps: &WorkerExecutorTestDependencies,
+    #[dimension(kvs)] kvs: &Arc<dyn GetKeyValueStorage + Send + Sync>,
+    #[dimension(nss)] nss: &Namespaces,
+) {
+    let kvs = kvs.get_key_value_storage().await;
+    let ns = nss.ns.clone();
+
+    let keys = vec!["set1".to_string(), "set2".to_string(), "set3".to_string()];
+    let value1 = "value1".as_bytes();
+    let value2 = "value2".as_bytes();
+
+    kvs.add_to_set("test", "api", "entity", ns.clone(), "set1", value1)
+        .await
+        .unwrap();
+    kvs.add_to_set("test", "api", "entity", ns.clone(), "set1", value2)
+        .await
+        .unwrap();
+    kvs.add_to_set("test", "api", "entity", ns.clone(), "set2", value1)
+        .await
+        .unwrap();
+
+    kvs.move_to_set("test", "api", "entity", ns.clone(), &keys, Some(2), value1)
+        .await
+        .unwrap();
+
+    let s1 = kvs
+        .members_of_set("test", "api", "entity", ns.clone(), "set1")
+        .await
+        .unwrap();
+    let s2 = kvs
+        .members_of_set("test", "api", "entity", ns.clone(), "set2")
+        .await
+        .unwrap();
+    let s3 = kvs
+        .members_of_set("test", "api", "entity", ns.clone(), "set3")
+        .await
+        .unwrap();
+
+    kvs.move_to_set("test", "api", "entity", ns.clone(), &keys, None, value1)
+        .await
+        .unwrap();
+
+    let s3_removed = kvs
+        .members_of_set("test", "api", "entity", ns.clone(), "set3")
+        .await
+        .unwrap();
+
+    assert2::check!(s1 == vec![value2.to_vec()]);
+    assert2::check!(s2 == Vec::<Vec<u8>>::new());
+    assert2::check!(s3 == vec![value1.to_vec()]);
+    assert2::check!(s3_removed == Vec::<Vec<u8>>::new());
+}
+
+#[test]
+#[tracing::instrument]
+async fn concurrent_moves_keep_the_value_in_one_set(
+    _deps: &WorkerExecutorTestDependencies,
+    #[dimension(kvs)] kvs: &Arc<dyn GetKeyValueStorage + Send + Sync>,
+    #[dimension(nss)] nss: &Namespaces,
+) {
+    let kvs = kvs.get_key_value_storage().await;
+    let ns = nss.ns.clone();
+
+    let keys = (0..5).map(|idx| format!("set{idx}")).collect::<Vec<_>>();
+    let value = "value".as_bytes();
+
+    let tasks = (0..40).map(|idx| {
+        let kvs = kvs.clone();
+        let ns = ns.clone();
+        let keys = keys.clone();
+        tokio::spawn(async move {
+            kvs.move_to_set(
+                "test",
+                "api",
+                "entity",
+                ns,
+                &keys,
+                Some(idx % keys.len()),
+                value,
+            )
+            .await
+            .unwrap();
+        })
+    });
+    for result in futures::future::join_all(tasks).await {
+        result.unwrap();
+    }
+
+    let mut total = 0;
+    for key in &keys {
+        total += kvs
+            .size_of_set("test", "api", "entity", ns.clone(), key)
+            .await
+            .unwrap();
+    }
+
+    assert2::check!(total == 1);
+}
+
+#[test]
+#[tracing::instrument]
+async fn add_to_set_if_absent(
+    _deps: &WorkerExecutorTestDependencies,
+    #[dimension(kvs)] kvs: &Arc<dyn GetKeyValueStorage + Send + Sync>,
+    #[dimension(nss)] nss: &Namespaces,
+) {
+    let kvs = kvs.get_key_value_storage().await;
+    let ns = nss.ns.clone();
+
+    let keys = vec!["set1".to_string(), "set2".to_string()];
+    let value1 = "value1".as_bytes();
+    let value2 = "value2".as_bytes();
+
+    kvs.add_to_set("test", "api", "entity", ns.clone(), "set2", value1)
+        .await
+        .unwrap();
+
+    let added1 = kvs
+        .add_to_set_if_absent("test", "api", "entity", ns.clone(), &keys, 0, value1)
+        .await
+        .unwrap();
+    let added2 = kvs
+        .add_to_set_if_absent("test", "api", "entity", ns.clone(), &keys, 0, value2)
+        .await
+        .unwrap();
+    let added2_again = kvs
+        .add_to_set_if_absent("test", "api", "entity", ns.clone(), &keys, 1, value2)
+        .await
+        .unwrap();
+
+    let s1 = kvs
+        .members_of_set("test", "api", "entity", ns.clone(), "set1")
+        .await
+        .unwrap();
+    let s2 = kvs
+        .members_of_set("test", "api", "entity", ns.clone(), "set2")
+        .await
+        .unwrap();
+
+    assert2::check!(!added1);
+    assert2::check!(added2);
+    assert2::check!(!added2_again);
+    assert2::check!(s1 == vec![value2.to_vec()]);
+    assert2::check!(s2 == vec![value1.to_vec()]);
 }
 
 #[test]
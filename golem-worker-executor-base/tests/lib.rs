@@ -46,6 +46,7 @@ mod common;
 
 pub mod api;
 pub mod blobstore;
+pub mod compaction;
 pub mod compatibility;
 pub mod guest_languages1;
 pub mod guest_languages2;
@@ -14,10 +14,13 @@
 
 use test_r::{inherit_test_dep, test};
 
-use crate::common::{start, TestContext};
+use crate::common::{invocation_context_with_deadline, start, TestContext};
 use crate::{LastUniqueId, Tracing, WorkerExecutorTestDependencies};
 use assert2::check;
+use golem_api_grpc::proto::golem::worker::v1::{invoke_and_await_response, WorkerError};
 use golem_common::model::component_metadata::{DynamicLinkedInstance, DynamicLinkedWasmRpc};
+use golem_common::model::{IdempotencyKey, TargetWorkerId, Timestamp};
+use golem_test_framework::config::TestDependencies;
 use golem_test_framework::dsl::{worker_error_message, TestDslUnsafe};
 use golem_wasm_ast::analysis::analysed_type;
 use golem_wasm_rpc::{IntoValueAndType, Value, ValueAndType};
@@ -265,6 +268,79 @@ async fn counter_resource_test_1(
     );
 }
 
+#[test]
+#[tracing::instrument]
+async fn rpc_call_is_cut_short_by_the_invocation_deadline(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await.unwrap();
+
+    let counters_component_id = executor.component("counters").store().await;
+    let caller_component_id = executor
+        .component("caller")
+        .with_dynamic_linking(&[
+            (
+                "rpc:counters-client/counters-client",
+                DynamicLinkedInstance::WasmRpc(DynamicLinkedWasmRpc {
+                    target_interface_name: HashMap::from_iter(vec![
+                        ("api".to_string(), "rpc:counters-exports/api".to_string()),
+                        (
+                            "counter".to_string(),
+                            "rpc:counters-exports/api".to_string(),
+                        ),
+                    ]),
+                }),
+            ),
+            (
+                "rpc:ephemeral-client/ephemeral-client",
+                DynamicLinkedInstance::WasmRpc(DynamicLinkedWasmRpc {
+                    target_interface_name: HashMap::from_iter(vec![(
+                        "api".to_string(),
+                        "rpc:ephemeral-exports/api".to_string(),
+                    )]),
+                }),
+            ),
+        ])
+        .store()
+        .await;
+
+    let mut env = HashMap::new();
+    env.insert(
+        "COUNTERS_COMPONENT_ID".to_string(),
+        counters_component_id.to_string(),
+    );
+    let caller_worker_id = executor
+        .start_worker_with(&caller_component_id, "rpc-counters-deadline", vec![], env)
+        .await;
+
+    // The deadline already passed when the caller makes its first remote call
+    let deadline = Timestamp::from(Timestamp::now_utc().to_millis() - 1000);
+    let response = executor
+        .worker_service()
+        .invoke_and_await(
+            TargetWorkerId::from(caller_worker_id.clone()).into(),
+            Some(IdempotencyKey::fresh().into()),
+            "rpc:caller-exports/caller-inline-functions.{test1}".to_string(),
+            vec![],
+            Some(invocation_context_with_deadline(deadline)),
+        )
+        .await
+        .unwrap();
+
+    drop(executor);
+
+    // The guest gets the deadline error from the remote call, and fails with it
+    let Some(invoke_and_await_response::Result::Error(WorkerError { error: Some(error) })) =
+        response.result
+    else {
+        panic!("expected a failed invocation")
+    };
+    check!(worker_error_message(&error).contains(&format!("Deadline exceeded: {deadline}")));
+}
+
 #[test]
 #[tracing::instrument]
 async fn counter_resource_test_2(
@@ -14,7 +14,7 @@
 
 use test_r::{inherit_test_dep, test, timeout};
 
-use crate::common::{start, start_limited, TestContext, TestWorkerExecutor};
+use crate::common::{start, start_customized, start_limited, TestContext, TestWorkerExecutor};
 use crate::{LastUniqueId, Tracing, WorkerExecutorTestDependencies};
 use assert2::check;
 use futures_util::stream::FuturesUnordered;
@@ -22,8 +22,11 @@ use futures_util::StreamExt;
 use golem_common::model::ComponentId;
 use golem_test_framework::dsl::TestDslUnsafe;
 use golem_wasm_rpc::{IntoValueAndType, Value};
+use golem_worker_executor_base::services::golem_config::{
+    IdlePolicy, IdlePolicyConfig, MemoryConfig,
+};
 use std::future::Future;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::spawn;
 use tokio::task::JoinSet;
 use tracing::info;
@@ -312,3 +315,112 @@ async fn dynamic_large_memory_allocation(
         check!(results[i][0] == Value::U64(0));
     }
 }
+
+#[test]
+#[timeout(120000)]
+#[tracing::instrument]
+async fn idle_policy_suspends_and_evicts_idle_workers(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start_customized(
+        deps,
+        &context,
+        MemoryConfig {
+            idle_policy: IdlePolicyConfig {
+                enabled: true,
+                check_interval: Duration::from_millis(200),
+                default: IdlePolicy {
+                    suspend_after: Some(Duration::from_secs(1)),
+                    evict_after: Some(Duration::from_secs(5)),
+                },
+                components: Default::default(),
+            },
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let component_id = executor.component("shopping-cart").store().await;
+    let worker_id = executor.start_worker(&component_id, "idle-policy-1").await;
+
+    let suspended_before = worker_metric("worker_passivation_total", Some("idle_policy"));
+    let evicted_before = worker_metric("worker_eviction_total", None);
+
+    executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api.{initialize-cart}",
+            vec!["test-user-1".into_value_and_type()],
+        )
+        .await
+        .unwrap();
+    executor
+        .invoke_and_await(
+            &worker_id,
+            "golem:it/api.{add-item}",
+            vec![vec![
+                ("product-id", "G1000".into_value_and_type()),
+                ("name", "Golem T-Shirt M".into_value_and_type()),
+                ("price", 100.0f32.into_value_and_type()),
+                ("quantity", 1u32.into_value_and_type()),
+            ]
+            .into_value_and_type()],
+        )
+        .await
+        .unwrap();
+
+    // The worker is suspended first, and only evicted once it is idle for longer
+    let suspended = wait_for_worker_metric(
+        "worker_passivation_total",
+        Some("idle_policy"),
+        suspended_before,
+    )
+    .await;
+    let evicted_when_suspended = worker_metric("worker_eviction_total", None);
+    let evicted = wait_for_worker_metric("worker_eviction_total", None, evicted_before).await;
+
+    // The evicted worker is loaded again by the next invocation, with its state recovered
+    let contents = executor
+        .invoke_and_await(&worker_id, "golem:it/api.{get-cart-contents}", vec![])
+        .await
+        .unwrap();
+
+    drop(executor);
+
+    check!(suspended);
+    check!(evicted_when_suspended == evicted_before);
+    check!(evicted);
+    check!(matches!(&contents[0], Value::List(items) if items.len() == 1));
+}
+
+// The sum of the values of a counter of the worker executor, optionally only for one trigger
+fn worker_metric(name: &str, trigger: Option<&str>) -> f64 {
+    prometheus::gather()
+        .iter()
+        .filter(|family| family.get_name() == name)
+        .flat_map(|family| family.get_metric())
+        .filter(|metric| {
+            trigger.is_none_or(|trigger| {
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|label| label.get_name() == "trigger" && label.get_value() == trigger)
+            })
+        })
+        .map(|metric| metric.get_counter().get_value())
+        .sum()
+}
+
+async fn wait_for_worker_metric(name: &str, trigger: Option<&str>, before: f64) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(30) {
+        if worker_metric(name, trigger) > before {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    false
+}
@@ -20,7 +20,7 @@ use std::sync::atomic::AtomicU8;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 
-use crate::common::{start, TestContext};
+use crate::common::{invocation_context_with_deadline, start, TestContext};
 use crate::{LastUniqueId, Tracing, WorkerExecutorTestDependencies};
 use assert2::{assert, check};
 use axum::response::Response;
@@ -28,11 +28,13 @@ use axum::routing::{get, post};
 use axum::{BoxError, Router};
 use bytes::Bytes;
 use futures_util::stream;
+use golem_api_grpc::proto::golem::worker::v1::invoke_and_await_response;
 use golem_common::model::{
     AccountId, ComponentFilePermissions, ComponentFileSystemNode, ComponentFileSystemNodeDetails,
-    IdempotencyKey, WorkerStatus,
+    IdempotencyKey, TargetWorkerId, Timestamp, WorkerStatus,
 };
 use golem_common::virtual_exports::http_incoming_handler::IncomingHttpRequest;
+use golem_test_framework::config::TestDependencies;
 use golem_test_framework::dsl::{
     drain_connection, stderr_events, stdout_events, worker_error_message, TestDslUnsafe,
 };
@@ -181,6 +183,54 @@ async fn clocks(
     check!(odt_diff < 5.0);
 }
 
+#[test]
+#[tracing::instrument]
+async fn sleep_is_cut_short_by_the_invocation_deadline(
+    last_unique_id: &LastUniqueId,
+    deps: &WorkerExecutorTestDependencies,
+    _tracing: &Tracing,
+) {
+    let context = TestContext::new(last_unique_id);
+    let executor = start(deps, &context).await.unwrap();
+
+    let component_id = executor.component("clocks").store().await;
+    let worker_id = executor
+        .start_worker(&component_id, "clocks-deadline-1")
+        .await;
+
+    let deadline = Timestamp::from(Timestamp::now_utc().to_millis() + 2000);
+    let response = executor
+        .worker_service()
+        .invoke_and_await(
+            TargetWorkerId::from(worker_id.clone()).into(),
+            Some(IdempotencyKey::fresh().into()),
+            "sleep-for".to_string(),
+            vec![10.0f64.into_value_and_type()],
+            Some(invocation_context_with_deadline(deadline)),
+        )
+        .await
+        .unwrap();
+
+    drop(executor);
+
+    let Some(invoke_and_await_response::Result::Success(response)) = response.result else {
+        panic!("expected a successful invocation")
+    };
+    let result = response
+        .result
+        .into_iter()
+        .map(|value| value.try_into())
+        .collect::<Result<Vec<Value>, String>>()
+        .unwrap();
+
+    // The guest observes that it woke up at the deadline instead of the requested time
+    let [Value::F64(elapsed)] = result.as_slice() else {
+        panic!("expected f64")
+    };
+    check!(*elapsed >= 1.0);
+    check!(*elapsed < 10.0);
+}
+
 #[test]
 #[tracing::instrument]
 async fn file_write_read_delete(
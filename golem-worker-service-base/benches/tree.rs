@@ -69,6 +69,9 @@ fn build_radix_tree(routes: &[Vec<RouterPattern>]) -> RadixNode<(usize, String)>
             .map(|segment| match segment {
                 RouterPattern::Literal(literal) => literal.0.clone(),
                 RouterPattern::Variable => "{var}".to_string(),
+                RouterPattern::ConstrainedVariable(constraint) => {
+                    format!("{{var:{}}}", constraint.pattern())
+                }
                 RouterPattern::CatchAll => "*".to_string(),
             })
             .collect::<Vec<_>>()
@@ -109,7 +112,9 @@ fn generate_match_route(route: &[RouterPattern]) -> Vec<String> {
         .iter()
         .flat_map(|segment| match segment {
             RouterPattern::Literal(literal) => vec![literal.0.clone()],
-            RouterPattern::Variable => vec![fastrand::u32(1..1000).to_string()],
+            RouterPattern::Variable | RouterPattern::ConstrainedVariable(_) => {
+                vec![fastrand::u32(1..1000).to_string()]
+            }
             RouterPattern::CatchAll => (1..10)
                 .map(|_| fastrand::u32(1..1000).to_string())
                 .collect::<Vec<_>>(),
@@ -0,0 +1,335 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::api::{GatewayBindingResponseData, HttpApiDefinitionResponseData, RouteResponseData};
+use crate::gateway_api_definition::http::MethodPattern;
+use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use poem_openapi::*;
+use serde::{Deserialize, Serialize};
+
+/// Structured difference between two versions of an API definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDefinitionDiff {
+    pub id: ApiDefinitionId,
+    pub from_version: ApiVersion,
+    pub to_version: ApiVersion,
+    pub added_routes: Vec<RouteKeyData>,
+    pub removed_routes: Vec<RouteKeyData>,
+    pub changed_routes: Vec<RouteChangeData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+pub struct RouteKeyData {
+    pub method: MethodPattern,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+pub struct RouteChangeData {
+    pub method: MethodPattern,
+    pub path: String,
+    pub changes: Vec<RouteFieldChange>,
+}
+
+/// A changed field of a route. For Rib scripts, `scriptDiff` lists the statements of both
+/// versions prefixed with `-` (removed), `+` (added) or a space (unchanged).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RouteFieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub script_diff: Option<Vec<String>>,
+}
+
+impl ApiDefinitionDiff {
+    // Routes are matched by method and path
+    pub fn between(
+        from: &HttpApiDefinitionResponseData,
+        to: &HttpApiDefinitionResponseData,
+    ) -> ApiDefinitionDiff {
+        let find = |routes: &[RouteResponseData], route: &RouteResponseData| {
+            routes
+                .iter()
+                .find(|r| r.method == route.method && r.path == route.path)
+                .cloned()
+        };
+
+        let mut added_routes = vec![];
+        let mut changed_routes = vec![];
+
+        for route in &to.routes {
+            match find(&from.routes, route) {
+                None => added_routes.push(RouteKeyData::from(route)),
+                Some(previous) => {
+                    let changes = route_changes(&previous, route);
+                    if !changes.is_empty() {
+                        changed_routes.push(RouteChangeData {
+                            method: route.method.clone(),
+                            path: route.path.clone(),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+
+        let removed_routes = from
+            .routes
+            .iter()
+            .filter(|route| find(&to.routes, route).is_none())
+            .map(RouteKeyData::from)
+            .collect();
+
+        ApiDefinitionDiff {
+            id: to.id.clone(),
+            from_version: from.version.clone(),
+            to_version: to.version.clone(),
+            added_routes,
+            removed_routes,
+            changed_routes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_routes.is_empty()
+            && self.removed_routes.is_empty()
+            && self.changed_routes.is_empty()
+    }
+}
+
+impl From<&RouteResponseData> for RouteKeyData {
+    fn from(value: &RouteResponseData) -> Self {
+        RouteKeyData {
+            method: value.method.clone(),
+            path: value.path.clone(),
+        }
+    }
+}
+
+fn route_changes(from: &RouteResponseData, to: &RouteResponseData) -> Vec<RouteFieldChange> {
+    let mut changes = vec![];
+
+    let scripts = |binding: &GatewayBindingResponseData| {
+        vec![
+            ("workerName", binding.worker_name.clone()),
+            ("idempotencyKey", binding.idempotency_key.clone()),
+            ("response", binding.response.clone()),
+            ("requestMapping", binding.request_mapping.clone()),
+        ]
+    };
+
+    for ((field, before), (_, after)) in
+        scripts(&from.binding).into_iter().zip(scripts(&to.binding))
+    {
+        if before != after {
+            changes.push(RouteFieldChange {
+                field: field.to_string(),
+                script_diff: Some(script_diff(
+                    before.as_deref().unwrap_or_default(),
+                    after.as_deref().unwrap_or_default(),
+                )),
+                before,
+                after,
+            });
+        }
+    }
+
+    if from.security != to.security {
+        changes.push(RouteFieldChange {
+            field: "security".to_string(),
+            before: from.security.clone(),
+            after: to.security.clone(),
+            script_diff: None,
+        });
+    }
+
+    // The rest of the binding is compared as a whole, leaving out the scripts and the type
+    // information inferred from them
+    let rest = |binding: &GatewayBindingResponseData| {
+        let binding = GatewayBindingResponseData {
+            worker_name: None,
+            idempotency_key: None,
+            response: None,
+            request_mapping: None,
+            response_mapping_input: None,
+            worker_name_input: None,
+            idempotency_key_input: None,
+            response_mapping_output: None,
+            request_mapping_input: None,
+            ..binding.clone()
+        };
+        serde_json::to_string(&binding).ok()
+    };
+
+    let (before, after) = (rest(&from.binding), rest(&to.binding));
+    if before != after {
+        changes.push(RouteFieldChange {
+            field: "binding".to_string(),
+            before,
+            after,
+            script_diff: None,
+        });
+    }
+
+    changes
+}
+
+// Statement level diff of two Rib scripts, based on their longest common subsequence
+fn script_diff(from: &str, to: &str) -> Vec<String> {
+    let statements = |script: &str| {
+        script
+            .split(['\n', ';'])
+            .map(|statement| statement.trim().to_string())
+            .filter(|statement| !statement.is_empty())
+            .collect::<Vec<_>>()
+    };
+
+    let (from, to) = (statements(from), statements(to));
+
+    let mut lcs = vec![vec![0usize; to.len() + 1]; from.len() + 1];
+    for i in (0..from.len()).rev() {
+        for j in (0..to.len()).rev() {
+            lcs[i][j] = if from[i] == to[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < from.len() || j < to.len() {
+        if i < from.len() && j < to.len() && from[i] == to[j] {
+            diff.push(format!("  {}", from[i]));
+            i += 1;
+            j += 1;
+        } else if i < from.len() && (j == to.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(format!("- {}", from[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+ {}", to[j]));
+            j += 1;
+        }
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    fn route(method: MethodPattern, path: &str, response: &str) -> RouteResponseData {
+        RouteResponseData {
+            method,
+            path: path.to_string(),
+            security: None,
+            binding: GatewayBindingResponseData {
+                component_id: None,
+                worker_name: None,
+                idempotency_key: None,
+                response: Some(response.to_string()),
+                binding_type: None,
+                response_mapping_input: None,
+                worker_name_input: None,
+                idempotency_key_input: None,
+                cors_preflight: None,
+                response_mapping_output: None,
+                grpc_endpoint: None,
+                grpc_service: None,
+                grpc_method: None,
+                traffic_mirror: None,
+                worker_per_user: None,
+                bootstrap: None,
+                pagination: None,
+                content_negotiation: None,
+                error_mapping: None,
+                static_worker_names: None,
+                upstream: None,
+                request_mapping: None,
+                request_mapping_input: None,
+                timeout_ms: None,
+            },
+        }
+    }
+
+    fn definition(version: &str, routes: Vec<RouteResponseData>) -> HttpApiDefinitionResponseData {
+        HttpApiDefinitionResponseData {
+            id: ApiDefinitionId("shop".to_string()),
+            version: ApiVersion(version.to_string()),
+            routes,
+            draft: false,
+            created_at: None,
+            etag: None,
+            taint_findings: vec![],
+        }
+    }
+
+    #[test]
+    fn routes_and_scripts_are_diffed() {
+        let from = definition(
+            "1",
+            vec![
+                route(MethodPattern::Get, "/cart", "let x = 1; x"),
+                route(MethodPattern::Delete, "/cart", "\"deleted\""),
+            ],
+        );
+        let to = definition(
+            "2",
+            vec![
+                route(MethodPattern::Get, "/cart", "let x = 2; x"),
+                route(MethodPattern::Post, "/cart", "\"added\""),
+            ],
+        );
+
+        let diff = ApiDefinitionDiff::between(&from, &to);
+
+        assert_eq!(
+            diff.added_routes,
+            vec![RouteKeyData {
+                method: MethodPattern::Post,
+                path: "/cart".to_string()
+            }]
+        );
+        assert_eq!(
+            diff.removed_routes,
+            vec![RouteKeyData {
+                method: MethodPattern::Delete,
+                path: "/cart".to_string()
+            }]
+        );
+        assert_eq!(diff.changed_routes.len(), 1);
+        assert_eq!(
+            diff.changed_routes[0].changes,
+            vec![RouteFieldChange {
+                field: "response".to_string(),
+                before: Some("let x = 1; x".to_string()),
+                after: Some("let x = 2; x".to_string()),
+                script_diff: Some(vec![
+                    "- let x = 1".to_string(),
+                    "+ let x = 2".to_string(),
+                    "  x".to_string()
+                ]),
+            }]
+        );
+
+        assert!(ApiDefinitionDiff::between(&from, &from).is_empty());
+    }
+}
@@ -53,6 +53,8 @@ pub enum ApiEndpointError {
     NotFound(Json<ErrorBody>),
     #[oai(status = 409)]
     AlreadyExists(Json<String>),
+    #[oai(status = 412)]
+    PreconditionFailed(Json<ErrorBody>),
     #[oai(status = 500)]
     InternalError(Json<ErrorBody>),
 }
@@ -63,6 +65,7 @@ impl TraceErrorKind for ApiEndpointError {
             ApiEndpointError::BadRequest(_) => "BadRequest",
             ApiEndpointError::NotFound(_) => "NotFound",
             ApiEndpointError::AlreadyExists(_) => "AlreadyExists",
+            ApiEndpointError::PreconditionFailed(_) => "PreconditionFailed",
             ApiEndpointError::Forbidden(_) => "Forbidden",
             ApiEndpointError::Unauthorized(_) => "Unauthorized",
             ApiEndpointError::InternalError(_) => "InternalError",
@@ -106,6 +109,12 @@ impl ApiEndpointError {
     pub fn already_exists<T: SafeDisplay>(error: T) -> Self {
         Self::AlreadyExists(Json(error.to_safe_string()))
     }
+
+    pub fn precondition_failed<T: SafeDisplay>(error: T) -> Self {
+        Self::PreconditionFailed(Json(ErrorBody {
+            error: error.to_safe_string(),
+        }))
+    }
 }
 
 pub struct WorkerTraceErrorKind<'a>(pub &'a worker::v1::WorkerError);
@@ -165,6 +174,11 @@ mod conversion {
     use crate::service::gateway::api_deployment::ApiDeploymentError;
 
     use crate::gateway_security::IdentityProviderError;
+    use crate::service::gateway::api_key::ApiKeyServiceError;
+    use crate::service::gateway::dead_letter::DeadLetterServiceError;
+    use crate::service::gateway::emitted_event::EmittedEventServiceError;
+    use crate::service::gateway::request_fixture::RequestFixtureServiceError;
+    use crate::service::gateway::rib_library::RibLibraryServiceError;
     use crate::service::gateway::security_scheme::SecuritySchemeServiceError;
     use golem_api_grpc::proto::golem::common::ErrorsBody;
     use golem_api_grpc::proto::golem::{
@@ -172,6 +186,7 @@ mod conversion {
         common::ErrorBody,
     };
     use golem_common::{safe, SafeDisplay};
+    use golem_service_base::service::audit_log::AuditLogError;
     use poem_openapi::payload::Json;
     use std::fmt::Display;
 
@@ -183,6 +198,73 @@ mod conversion {
                 }
                 SecuritySchemeServiceError::InternalError(_) => ApiEndpointError::internal(value),
                 SecuritySchemeServiceError::NotFound(_) => ApiEndpointError::not_found(value),
+                SecuritySchemeServiceError::UnexpectedType(_) => {
+                    ApiEndpointError::bad_request(value)
+                }
+                SecuritySchemeServiceError::InvalidSecret(_) => {
+                    ApiEndpointError::bad_request(value)
+                }
+            }
+        }
+    }
+
+    impl From<ApiKeyServiceError> for ApiEndpointError {
+        fn from(value: ApiKeyServiceError) -> Self {
+            match value {
+                ApiKeyServiceError::SecuritySchemeError(error) => ApiEndpointError::from(error),
+                ApiKeyServiceError::NotAnApiKeyScheme(_) => ApiEndpointError::bad_request(value),
+                ApiKeyServiceError::InvalidName(_) => ApiEndpointError::bad_request(value),
+                ApiKeyServiceError::KeyNotFound(_) => ApiEndpointError::not_found(value),
+                ApiKeyServiceError::InternalError(_) => ApiEndpointError::internal(value),
+            }
+        }
+    }
+
+    impl From<DeadLetterServiceError> for ApiEndpointError {
+        fn from(value: DeadLetterServiceError) -> Self {
+            match value {
+                DeadLetterServiceError::NotFound(_) => ApiEndpointError::not_found(value),
+                DeadLetterServiceError::NotReplayable(_) => ApiEndpointError::bad_request(value),
+                DeadLetterServiceError::InternalError(_) => ApiEndpointError::internal(value),
+            }
+        }
+    }
+
+    impl From<RequestFixtureServiceError> for ApiEndpointError {
+        fn from(value: RequestFixtureServiceError) -> Self {
+            match value {
+                RequestFixtureServiceError::NotFound(_) => ApiEndpointError::not_found(value),
+                RequestFixtureServiceError::InternalError(_) => ApiEndpointError::internal(value),
+            }
+        }
+    }
+
+    impl From<AuditLogError> for ApiEndpointError {
+        fn from(value: AuditLogError) -> Self {
+            match value {
+                AuditLogError::InternalError(_) => ApiEndpointError::internal(value),
+            }
+        }
+    }
+
+    impl From<EmittedEventServiceError> for ApiEndpointError {
+        fn from(value: EmittedEventServiceError) -> Self {
+            match value {
+                EmittedEventServiceError::InternalError(_) => ApiEndpointError::internal(value),
+            }
+        }
+    }
+
+    impl From<RibLibraryServiceError> for ApiEndpointError {
+        fn from(value: RibLibraryServiceError) -> Self {
+            match value {
+                RibLibraryServiceError::AlreadyExists(_, _) => {
+                    ApiEndpointError::already_exists(value)
+                }
+                RibLibraryServiceError::NotFound(_, _) => ApiEndpointError::not_found(value),
+                RibLibraryServiceError::InvalidLibrary(_) => ApiEndpointError::bad_request(value),
+                RibLibraryServiceError::LinkError(_) => ApiEndpointError::bad_request(value),
+                RibLibraryServiceError::InternalError(_) => ApiEndpointError::internal(value),
             }
         }
     }
@@ -242,6 +324,9 @@ mod conversion {
                 }
                 ApiDefinitionServiceError::Internal(_) => ApiEndpointError::internal(error),
                 ApiDefinitionServiceError::RibInternal(_) => ApiEndpointError::internal(error),
+                ApiDefinitionServiceError::PreconditionFailed(_) => {
+                    ApiEndpointError::precondition_failed(error)
+                }
             }
         }
     }
@@ -255,9 +340,15 @@ mod conversion {
                 ApiDeploymentError::ApiDeploymentNotFound(_, _) => {
                     ApiEndpointError::not_found(error)
                 }
+                ApiDeploymentError::ApiDefinitionNotDeployed(_, _) => {
+                    ApiEndpointError::bad_request(error)
+                }
                 ApiDeploymentError::ApiDeploymentConflict(_) => {
                     ApiEndpointError::already_exists(error)
                 }
+                ApiDeploymentError::PreconditionFailed(_) => {
+                    ApiEndpointError::precondition_failed(error)
+                }
                 ApiDeploymentError::ApiDefinitionsConflict(_) => {
                     ApiEndpointError::bad_request(error)
                 }
@@ -268,6 +359,22 @@ mod conversion {
                 ApiDeploymentError::ComponentConstraintCreateError(_) => {
                     ApiEndpointError::internal(error)
                 }
+                ApiDeploymentError::InvalidErrorPages(_) => ApiEndpointError::bad_request(error),
+                ApiDeploymentError::InvalidCors(_) => ApiEndpointError::bad_request(error),
+                ApiDeploymentError::InvalidTrafficSplit(_) => ApiEndpointError::bad_request(error),
+                ApiDeploymentError::InvalidExperiments(_) => ApiEndpointError::bad_request(error),
+                ApiDeploymentError::InvalidCapacity(_) => ApiEndpointError::bad_request(error),
+                ApiDeploymentError::InvalidCompression(_) => ApiEndpointError::bad_request(error),
+                ApiDeploymentError::HealthCheckFailed(_) => ApiEndpointError::bad_request(error),
+                ApiDeploymentError::InvalidClientCertificates(_) => {
+                    ApiEndpointError::bad_request(error)
+                }
+                ApiDeploymentError::InvalidDeadLetterQueue(_) => {
+                    ApiEndpointError::bad_request(error)
+                }
+                ApiDeploymentError::InvalidRequestFixtures(_) => {
+                    ApiEndpointError::bad_request(error)
+                }
             }
         }
     }
@@ -326,7 +433,8 @@ mod conversion {
                         error: error.to_safe_string(),
                     })),
                 },
-                ApiDefinitionServiceError::ApiDefinitionAlreadyExists(_) => ApiDefinitionError {
+                ApiDefinitionServiceError::ApiDefinitionAlreadyExists(_)
+                | ApiDefinitionServiceError::PreconditionFailed(_) => ApiDefinitionError {
                     error: Some(api_definition_error::Error::AlreadyExists(ErrorBody {
                         error: error.to_safe_string(),
                     })),
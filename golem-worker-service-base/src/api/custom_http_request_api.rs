@@ -17,15 +17,30 @@ use std::sync::Arc;
 
 use crate::gateway_execution::api_definition_lookup::HttpApiDefinitionsLookup;
 use crate::gateway_execution::auth_call_back_binding_handler::DefaultAuthCallBack;
+use crate::gateway_execution::deployment_capacity_limiter::DeploymentCapacityLimiter;
+use crate::gateway_execution::experiment_assignment::ExperimentAssignmentStore;
 use crate::gateway_execution::file_server_binding_handler::FileServerBindingHandler;
 use crate::gateway_execution::gateway_http_input_executor::{
     DefaultGatewayInputExecutor, GatewayHttpInputExecutor,
 };
 use crate::gateway_execution::gateway_session::GatewaySession;
+use crate::gateway_execution::grpc_transcoding::DefaultGrpcBindingHandler;
 use crate::gateway_execution::http_handler_binding_handler::HttpHandlerBindingHandler;
+use crate::gateway_execution::http_proxy_binding_handler::DefaultHttpProxyBindingHandler;
+use crate::gateway_execution::rate_limiter::RateLimiterStore;
+use crate::gateway_execution::response_cache::ResponseCacheStore;
+use crate::gateway_execution::route_concurrency_limiter::RouteConcurrencyLimiter;
+use crate::gateway_execution::route_metrics::RouteMetrics;
+use crate::gateway_execution::server_sent_events_bridge::WorkerEventSubscriber;
+use crate::gateway_execution::shadow_traffic_diff::ShadowTrafficDiffStore;
 use crate::gateway_execution::GatewayWorkerRequestExecutor;
 use crate::gateway_rib_interpreter::DefaultRibInterpreter;
-use crate::gateway_security::DefaultIdentityProvider;
+use crate::gateway_security::{
+    ApiKeyValidator, DefaultIdentityProvider, DefaultJwtValidator, HmacSignatureValidator,
+};
+use crate::service::gateway::dead_letter::DeadLetterService;
+use crate::service::gateway::emitted_event::EmittedEventService;
+use crate::service::gateway::request_fixture::RequestFixtureService;
 use futures_util::FutureExt;
 use poem::{Endpoint, Request, Response};
 
@@ -42,9 +57,20 @@ impl CustomHttpRequestApi {
         file_server_binding_handler: Arc<dyn FileServerBindingHandler<Namespace> + Sync + Send>,
         http_handler_binding_handler: Arc<dyn HttpHandlerBindingHandler<Namespace> + Sync + Send>,
         gateway_session_store: Arc<dyn GatewaySession + Sync + Send>,
+        shadow_traffic_diff_store: Arc<dyn ShadowTrafficDiffStore + Sync + Send>,
+        experiment_assignment_store: Arc<dyn ExperimentAssignmentStore + Sync + Send>,
+        dead_letter_service: Arc<dyn DeadLetterService<Namespace> + Sync + Send>,
+        request_fixture_service: Arc<dyn RequestFixtureService<Namespace> + Sync + Send>,
+        emitted_event_service: Arc<dyn EmittedEventService<Namespace> + Sync + Send>,
+        worker_event_subscriber: Arc<dyn WorkerEventSubscriber + Sync + Send>,
+        rate_limiter: RateLimiterStore,
+        response_cache: ResponseCacheStore,
+        api_key_validator: Arc<dyn ApiKeyValidator<Namespace> + Sync + Send>,
+        hmac_signature_validator: Arc<dyn HmacSignatureValidator<Namespace> + Sync + Send>,
     ) -> Self {
         let evaluator = Arc::new(DefaultRibInterpreter::from_worker_request_executor(
             worker_request_executor_service.clone(),
+            emitted_event_service,
         ));
 
         let auth_call_back_binding_handler = Arc::new(DefaultAuthCallBack);
@@ -54,9 +80,24 @@ impl CustomHttpRequestApi {
             file_server_binding_handler,
             auth_call_back_binding_handler,
             http_handler_binding_handler,
+            grpc_binding_handler: Arc::new(DefaultGrpcBindingHandler::default()),
+            http_proxy_binding_handler: Arc::new(DefaultHttpProxyBindingHandler::default()),
+            shadow_traffic_diff_store,
+            experiment_assignment_store,
+            dead_letter_service,
+            request_fixture_service,
+            worker_event_subscriber,
+            rate_limiter,
+            response_cache,
+            route_concurrency_limiter: Arc::new(RouteConcurrencyLimiter::default()),
+            deployment_capacity_limiter: Arc::new(DeploymentCapacityLimiter::default()),
+            route_metrics: Arc::new(RouteMetrics::default()),
             api_definition_lookup_service,
             gateway_session_store,
             identity_provider: Arc::new(DefaultIdentityProvider),
+            jwt_validator: Arc::new(DefaultJwtValidator::default()),
+            api_key_validator,
+            hmac_signature_validator,
         });
 
         Self {
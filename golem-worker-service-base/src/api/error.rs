@@ -13,10 +13,11 @@
 // limitations under the License.
 
 use crate::service::component::ComponentServiceError;
-use crate::service::worker::WorkerServiceError;
+use crate::service::worker::{WorkerScheduleServiceError, WorkerServiceError};
 use golem_common::metrics::api::TraceErrorKind;
 use golem_common::SafeDisplay;
 use golem_service_base::model::*;
+use golem_service_base::service::namespace_quota::{NamespaceQuotaError, QuotaExceeded};
 use poem_openapi::payload::Json;
 use poem_openapi::*;
 use tonic::Status;
@@ -39,6 +40,8 @@ pub enum WorkerApiBaseError {
     NotFound(Json<ErrorBody>),
     #[oai(status = 409)]
     AlreadyExists(Json<ErrorBody>),
+    #[oai(status = 429)]
+    TooManyRequests(Json<QuotaExceeded>),
     #[oai(status = 500)]
     InternalError(Json<GolemErrorBody>),
 }
@@ -51,6 +54,7 @@ impl TraceErrorKind for WorkerApiBaseError {
             WorkerApiBaseError::AlreadyExists(_) => "AlreadyExists",
             WorkerApiBaseError::Forbidden(_) => "Forbidden",
             WorkerApiBaseError::Unauthorized(_) => "Unauthorized",
+            WorkerApiBaseError::TooManyRequests(_) => "TooManyRequests",
             WorkerApiBaseError::InternalError(_) => "InternalError",
         }
     }
@@ -121,6 +125,60 @@ impl From<WorkerServiceError> for WorkerApiBaseError {
             ServiceError::BadFileType(_) => WorkerApiBaseError::BadRequest(Json(ErrorsBody {
                 errors: vec![error.to_safe_string()],
             })),
+            ServiceError::QuotaExceeded(exceeded) => {
+                WorkerApiBaseError::TooManyRequests(Json(exceeded))
+            }
+        }
+    }
+}
+
+impl From<WorkerScheduleServiceError> for WorkerApiBaseError {
+    fn from(error: WorkerScheduleServiceError) -> Self {
+        match error {
+            WorkerScheduleServiceError::AlreadyExists(_) => {
+                WorkerApiBaseError::AlreadyExists(Json(ErrorBody {
+                    error: error.to_safe_string(),
+                }))
+            }
+            WorkerScheduleServiceError::NotFound(_) => {
+                WorkerApiBaseError::NotFound(Json(ErrorBody {
+                    error: error.to_safe_string(),
+                }))
+            }
+            WorkerScheduleServiceError::InvalidSchedule(errors) => {
+                WorkerApiBaseError::BadRequest(Json(ErrorsBody { errors }))
+            }
+            WorkerScheduleServiceError::InternalError(_) => {
+                WorkerApiBaseError::InternalError(Json(GolemErrorBody {
+                    golem_error: GolemError::Unknown(GolemErrorUnknown {
+                        details: error.to_safe_string(),
+                    }),
+                }))
+            }
+        }
+    }
+}
+
+// The quotas enforced by the worker service limit the number of workers and the rate of the
+// invocations, both rejected with a 429
+impl From<NamespaceQuotaError> for WorkerApiBaseError {
+    fn from(value: NamespaceQuotaError) -> Self {
+        match value {
+            NamespaceQuotaError::Exceeded(exceeded) => {
+                WorkerApiBaseError::TooManyRequests(Json(exceeded))
+            }
+            NamespaceQuotaError::UnsupportedResource(_) => {
+                WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                    errors: vec![value.to_safe_string()],
+                }))
+            }
+            NamespaceQuotaError::InternalError(_) => {
+                WorkerApiBaseError::InternalError(Json(GolemErrorBody {
+                    golem_error: GolemError::Unknown(GolemErrorUnknown {
+                        details: value.to_safe_string(),
+                    }),
+                }))
+            }
         }
     }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use api_definition_diff::*;
 pub use common::*;
 pub use custom_http_request_api::*;
 pub use error::*;
@@ -19,6 +20,7 @@ pub use healthcheck::*;
 pub use register_api_definition_api::*;
 
 // Components and request data that can be reused for implementing server API endpoints
+mod api_definition_diff;
 mod common;
 mod custom_http_request_api;
 mod error;
@@ -16,25 +16,41 @@ use crate::gateway_api_definition::http::{
     AllPathPatterns, CompiledHttpApiDefinition, CompiledRoute, MethodPattern, Route, RouteRequest,
 };
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
-use crate::gateway_api_deployment::ApiSite;
+use crate::gateway_api_deployment::{
+    ApiDeploymentCapacity, ApiDeploymentExperiment, ApiDeploymentTrafficSplit, ApiSite,
+};
 use crate::gateway_binding::{
-    GatewayBinding, GatewayBindingCompiled, HttpHandlerBinding, HttpHandlerBindingCompiled,
-    StaticBinding, WorkerBinding, WorkerBindingCompiled,
+    ContentNegotiation, ErrorMapping, GatewayBinding, GatewayBindingCompiled, GrpcBinding,
+    HttpHandlerBinding, HttpHandlerBindingCompiled, HttpProxyBinding, Pagination, StaticBinding,
+    TrafficMirror, WorkerBinding, WorkerBindingCompiled, WorkerBootstrap, WorkerPerUser,
+};
+use crate::gateway_execution::deployment_probe::DeploymentProbe;
+use crate::gateway_middleware::{
+    ConditionalMiddleware, CorsPreflightExpr, HttpBodyLimits, HttpCompression,
+    HttpConcurrencyLimit, HttpCors, HttpMiddleware, HttpMiddlewareConditions, HttpMiddlewares,
+    HttpRateLimit, HttpResponseCache, HttpTransforms,
 };
-use crate::gateway_middleware::{CorsPreflightExpr, HttpCors, HttpMiddleware, HttpMiddlewares};
+use crate::gateway_rib_compiler::TaintAnalysisPolicy;
 use crate::gateway_security::{
-    Provider, SecurityScheme, SecuritySchemeIdentifier, SecuritySchemeReference,
-    SecuritySchemeWithProviderMetadata,
+    ApiKey, ApiKeyLocation, ApiKeySecurityScheme, HmacSecurityScheme, HmacSignatureEncoding,
+    HmacSignatureFormat, JwtSecurityScheme, Provider, SecurityScheme, SecuritySchemeIdentifier,
+    SecuritySchemeReference, SecuritySchemeWithProviderMetadata,
 };
+use crate::repo::api_definition::DeletedApiDefinitionRecord;
+use crate::service::gateway::rib_library::RibLibraryDefinition;
+use base64::Engine;
 use golem_api_grpc::proto::golem::apidefinition as grpc_apidefinition;
 use golem_common::model::GatewayBindingType;
 use golem_service_base::model::VersionedComponentId;
+use golem_service_base::watch::{resource_version, WatchEvent, WatchEventType};
 use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
 use poem_openapi::*;
 use rib::{RibInputTypeInfo, RibOutputTypeInfo};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::result::Result;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
+use url::Url;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
@@ -42,6 +58,23 @@ use std::time::SystemTime;
 pub struct ApiDeploymentRequest {
     pub api_definitions: Vec<ApiDefinitionInfo>,
     pub site: ApiSite,
+    /// CORS configuration of the routes of the deployment without their own,
+    /// including the automatic answer to preflight requests. Deploying without one
+    /// keeps the current configuration of the site.
+    pub cors: Option<HttpCors>,
+    /// Split of the traffic between two versions of an API definition, both deployed to the
+    /// site. Deploying without one keeps the current split of the site, and undeploying either
+    /// version ends it.
+    pub traffic_split: Option<ApiDeploymentTrafficSplit>,
+    /// A/B experiments assigning the requests of the site to their variants. Deploying without
+    /// them keeps the current experiments of the site, and deploying with none ends them.
+    pub experiments: Option<Vec<ApiDeploymentExperiment>>,
+    /// Worker invocation capacity of the site on each gateway, with priority lanes reserving
+    /// part of it. Deploying without one keeps the current capacity of the site.
+    pub capacity: Option<ApiDeploymentCapacity>,
+    /// Response compression of the routes of the site without a compression of their own.
+    /// Deploying without one keeps the current compression of the site.
+    pub compression: Option<HttpCompression>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
@@ -51,6 +84,37 @@ pub struct ApiDeployment {
     pub api_definitions: Vec<ApiDefinitionInfo>,
     pub site: ApiSite,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub cors: Option<HttpCors>,
+    pub traffic_split: Option<ApiDeploymentTrafficSplit>,
+    pub experiments: Option<Vec<ApiDeploymentExperiment>>,
+    pub capacity: Option<ApiDeploymentCapacity>,
+    pub compression: Option<HttpCompression>,
+    /// Entity tag of the deployment, usable in `If-Match` preconditions
+    #[serde(default)]
+    pub etag: Option<String>,
+}
+
+/// Change event of an API deployment watch stream
+#[derive(Debug, Clone, PartialEq, Object)]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentWatchEvent {
+    pub event_type: WatchEventType,
+    pub key: Option<String>,
+    pub resource_version: Option<String>,
+    pub cursor: Option<String>,
+    pub deployment: Option<ApiDeployment>,
+}
+
+impl From<WatchEvent<ApiDeployment>> for ApiDeploymentWatchEvent {
+    fn from(value: WatchEvent<ApiDeployment>) -> Self {
+        Self {
+            event_type: value.event_type,
+            key: value.key,
+            resource_version: value.resource_version,
+            cursor: value.cursor,
+            deployment: value.resource,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
@@ -61,6 +125,40 @@ pub struct ApiDefinitionInfo {
     pub version: ApiVersion,
 }
 
+/// Swap of the version of an API definition served by a site. The probes are sent to the
+/// new version before the swap, which is aborted if any of them fails.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentSwapRequest {
+    pub api_definition: ApiDefinitionInfo,
+    #[serde(default)]
+    #[oai(default)]
+    pub probes: Vec<DeploymentProbe>,
+}
+
+/// An API definition moved to the trash, restorable until its retention period expires
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct DeletedApiDefinition {
+    pub id: ApiDefinitionId,
+    pub version: ApiVersion,
+    pub deleted_at: chrono::DateTime<chrono::Utc>,
+    pub deleted_by: String,
+}
+
+impl From<DeletedApiDefinitionRecord> for DeletedApiDefinition {
+    fn from(value: DeletedApiDefinitionRecord) -> Self {
+        Self {
+            id: ApiDefinitionId(value.id),
+            version: ApiVersion(value.version),
+            deleted_at: value.deleted_at,
+            deleted_by: value.deleted_by,
+        }
+    }
+}
+
 // Mostly this data structures that represents the actual incoming request
 // exist due to the presence of complicated Expr data type in gateway_api_definition::ApiDefinition.
 // Consider them to be otherwise same
@@ -74,6 +172,31 @@ pub struct HttpApiDefinitionRequest {
     pub routes: Vec<RouteRequestData>,
     #[serde(default)]
     pub draft: bool,
+    /// Whether the untrusted request values flowing into worker names and response headers
+    /// are ignored (`off`), reported (`warn`, the default) or rejected (`deny`)
+    #[serde(default)]
+    #[oai(default)]
+    pub taint_policy: TaintAnalysisPolicy,
+}
+
+impl HttpApiDefinitionRequest {
+    // The Rib scripts of the route bindings, which can import the bindings of Rib libraries
+    pub fn rib_scripts_mut(&mut self) -> Vec<&mut String> {
+        self.routes
+            .iter_mut()
+            .flat_map(|route| {
+                let binding = &mut route.binding;
+                [
+                    binding.worker_name.as_mut(),
+                    binding.idempotency_key.as_mut(),
+                    binding.response.as_mut(),
+                    binding.invocation_context.as_mut(),
+                    binding.request_mapping.as_mut(),
+                ]
+            })
+            .flatten()
+            .collect()
+    }
 }
 
 // Mostly this data structures that represents the actual incoming request
@@ -150,6 +273,270 @@ impl From<SecuritySchemeWithProviderMetadata> for SecuritySchemeData {
     }
 }
 
+// A security scheme validating bearer JWTs issued by `issuer` for any of the `audiences`,
+// with the keys published at `jwksUrl`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct JwtSecuritySchemeData {
+    pub scheme_identifier: String,
+    pub issuer: String,
+    pub audiences: Vec<String>,
+    pub jwks_url: String,
+}
+
+impl TryFrom<JwtSecuritySchemeData> for JwtSecurityScheme {
+    type Error = String;
+
+    fn try_from(value: JwtSecuritySchemeData) -> Result<Self, Self::Error> {
+        let jwks_url = Url::parse(&value.jwks_url).map_err(|e| e.to_string())?;
+
+        JwtSecurityScheme::new(
+            SecuritySchemeIdentifier::new(value.scheme_identifier),
+            value.issuer,
+            value.audiences,
+            jwks_url,
+        )
+    }
+}
+
+impl From<JwtSecurityScheme> for JwtSecuritySchemeData {
+    fn from(value: JwtSecurityScheme) -> Self {
+        Self {
+            scheme_identifier: value.scheme_identifier().to_string(),
+            issuer: value.issuer().to_string(),
+            audiences: value.audiences().to_vec(),
+            jwks_url: value.jwks_url().to_string(),
+        }
+    }
+}
+
+/// An API key security scheme, taking the key from either a header or a query parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiKeySecuritySchemeData {
+    pub scheme_identifier: String,
+    pub header: Option<String>,
+    pub query_param: Option<String>,
+}
+
+impl TryFrom<ApiKeySecuritySchemeData> for ApiKeySecurityScheme {
+    type Error = String;
+
+    fn try_from(value: ApiKeySecuritySchemeData) -> Result<Self, Self::Error> {
+        let location = match (value.header, value.query_param) {
+            (Some(header), None) => ApiKeyLocation::Header(header),
+            (None, Some(query_param)) => ApiKeyLocation::QueryParam(query_param),
+            _ => {
+                return Err(
+                    "Exactly one of header or query parameter of the API key must be specified"
+                        .to_string(),
+                )
+            }
+        };
+
+        ApiKeySecurityScheme::new(
+            SecuritySchemeIdentifier::new(value.scheme_identifier),
+            location,
+        )
+    }
+}
+
+impl From<ApiKeySecurityScheme> for ApiKeySecuritySchemeData {
+    fn from(value: ApiKeySecurityScheme) -> Self {
+        let (header, query_param) = match value.location() {
+            ApiKeyLocation::Header(header) => (Some(header.clone()), None),
+            ApiKeyLocation::QueryParam(query_param) => (None, Some(query_param.clone())),
+        };
+
+        Self {
+            scheme_identifier: value.scheme_identifier().to_string(),
+            header,
+            query_param,
+        }
+    }
+}
+
+/// An HMAC security scheme, verifying that the signature in `header` is the HMAC of the request body.
+/// The algorithm is one of `sha1`, `sha256` or `sha512` and the encoding of the signature is
+/// either `hex` (the default) or `base64`. The `prefix`, such as `sha256=`, is stripped from the
+/// signature before decoding it. With the `stripe` format (rather than the default `plain`), the
+/// header is `t=<timestamp>,v1=<signature>`, the signature is the HMAC of `<timestamp>.<body>` and
+/// requests whose timestamp is more than `timestampToleranceSeconds` (300 by default) away from
+/// the current time are rejected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HmacSecuritySchemeData {
+    pub scheme_identifier: String,
+    pub header: String,
+    pub algorithm: String,
+    pub encoding: Option<String>,
+    pub prefix: Option<String>,
+    pub format: Option<String>,
+    pub timestamp_tolerance_seconds: Option<u64>,
+}
+
+impl TryFrom<HmacSecuritySchemeData> for HmacSecurityScheme {
+    type Error = String;
+
+    fn try_from(value: HmacSecuritySchemeData) -> Result<Self, Self::Error> {
+        let encoding = match value.encoding {
+            Some(encoding) => encoding.parse()?,
+            None => HmacSignatureEncoding::Hex,
+        };
+
+        let format = match value.format {
+            Some(format) => match format.parse()? {
+                HmacSignatureFormat::Stripe { .. } => HmacSignatureFormat::Stripe {
+                    timestamp_tolerance: value
+                        .timestamp_tolerance_seconds
+                        .map(Duration::from_secs)
+                        .unwrap_or(HmacSignatureFormat::DEFAULT_TIMESTAMP_TOLERANCE),
+                },
+                HmacSignatureFormat::Plain => HmacSignatureFormat::Plain,
+            },
+            None => HmacSignatureFormat::Plain,
+        };
+
+        HmacSecurityScheme::new(
+            SecuritySchemeIdentifier::new(value.scheme_identifier),
+            value.header,
+            value.algorithm.parse()?,
+            encoding,
+            value.prefix,
+            format,
+        )
+    }
+}
+
+impl From<HmacSecurityScheme> for HmacSecuritySchemeData {
+    fn from(value: HmacSecurityScheme) -> Self {
+        Self {
+            scheme_identifier: value.scheme_identifier().to_string(),
+            header: value.header().to_string(),
+            algorithm: value.algorithm().to_string(),
+            encoding: Some(value.encoding().to_string()),
+            prefix: value.prefix().map(|prefix| prefix.to_string()),
+            format: Some(value.format().to_string()),
+            timestamp_tolerance_seconds: match value.format() {
+                HmacSignatureFormat::Plain => None,
+                HmacSignatureFormat::Stripe {
+                    timestamp_tolerance,
+                } => Some(timestamp_tolerance.as_secs()),
+            },
+        }
+    }
+}
+
+/// A new HMAC security scheme along with the secret shared with the sender of the requests.
+/// The secret is never returned once the scheme is created
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HmacSecuritySchemeCreationData {
+    pub scheme_identifier: String,
+    pub header: String,
+    pub algorithm: String,
+    pub encoding: Option<String>,
+    pub prefix: Option<String>,
+    pub format: Option<String>,
+    pub timestamp_tolerance_seconds: Option<u64>,
+    pub secret: String,
+}
+
+impl HmacSecuritySchemeCreationData {
+    pub fn into_security_scheme(self) -> Result<(HmacSecurityScheme, String), String> {
+        let security_scheme = HmacSecurityScheme::try_from(HmacSecuritySchemeData {
+            scheme_identifier: self.scheme_identifier,
+            header: self.header,
+            algorithm: self.algorithm,
+            encoding: self.encoding,
+            prefix: self.prefix,
+            format: self.format,
+            timestamp_tolerance_seconds: self.timestamp_tolerance_seconds,
+        })?;
+
+        Ok((security_scheme, self.secret))
+    }
+}
+
+/// Name and metadata of a new API key. The metadata is available to Rib as `request.auth.metadata`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiKeyCreationData {
+    pub name: String,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiKeyData {
+    pub key_id: String,
+    pub name: String,
+    pub metadata: BTreeMap<String, String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<ApiKey> for ApiKeyData {
+    fn from(value: ApiKey) -> Self {
+        Self {
+            key_id: value.key_id,
+            name: value.name,
+            metadata: value.metadata,
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// A newly created or rotated API key. The key is only ever returned here,
+/// as only a salted hash of it is stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct IssuedApiKeyData {
+    pub api_key: ApiKeyData,
+    pub key: String,
+}
+
+/// A version of a Rib library, made of `let` bindings the binding scripts of API definitions
+/// can import with `use <name>::<binding>;` or `use <name>@<version>::<binding>;`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RibLibraryCreationData {
+    pub name: String,
+    pub version: String,
+    pub source: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RibLibraryData {
+    pub name: String,
+    pub version: String,
+    pub source: String,
+    pub exports: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<RibLibraryDefinition> for RibLibraryData {
+    fn from(value: RibLibraryDefinition) -> Self {
+        Self {
+            name: value.name,
+            version: value.version,
+            source: value.source,
+            exports: value.exports,
+            created_at: value.created_at,
+        }
+    }
+}
+
 // HttpApiDefinitionResponse is a trimmed down version of CompiledHttpApiDefinition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
@@ -161,6 +548,28 @@ pub struct HttpApiDefinitionResponseData {
     #[serde(default)]
     pub draft: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Entity tag of the definition, usable in `If-Match` preconditions
+    #[serde(default)]
+    pub etag: Option<String>,
+    /// The untrusted request values flowing into worker names or response headers, found when
+    /// the definition was created or updated with a `warn` taint policy
+    #[serde(default)]
+    #[oai(default)]
+    pub taint_findings: Vec<String>,
+}
+
+impl HttpApiDefinitionResponseData {
+    // The findings are not part of the definition, so they do not change its entity tag
+    pub fn with_taint_findings(
+        mut self,
+        taint_policy: TaintAnalysisPolicy,
+        taint_findings: Vec<String>,
+    ) -> Self {
+        if taint_policy != TaintAnalysisPolicy::Off {
+            self.taint_findings = taint_findings;
+        }
+        self
+    }
 }
 
 impl<Namespace> TryFrom<CompiledHttpApiDefinition<Namespace>> for HttpApiDefinitionResponseData {
@@ -177,13 +586,20 @@ impl<Namespace> TryFrom<CompiledHttpApiDefinition<Namespace>> for HttpApiDefinit
             }
         }
 
-        Ok(Self {
+        let mut data = Self {
             id: value.id,
             version: value.version,
             routes,
             draft: value.draft,
-            created_at: Some(value.created_at),
-        })
+            created_at: None,
+            etag: None,
+            taint_findings: vec![],
+        };
+        // the entity tag only depends on the content of the definition
+        data.etag = Some(resource_version(&data));
+        data.created_at = Some(value.created_at);
+
+        Ok(data)
     }
 }
 
@@ -194,6 +610,13 @@ pub struct RouteRequestData {
     pub binding: GatewayBindingData,
     pub cors: Option<HttpCors>,
     pub security: Option<String>,
+    pub concurrency_limit: Option<HttpConcurrencyLimit>,
+    pub rate_limit: Option<HttpRateLimitData>,
+    pub body_limits: Option<HttpBodyLimits>,
+    pub response_cache: Option<HttpResponseCache>,
+    pub transforms: Option<HttpTransformsData>,
+    pub conditions: Option<HttpMiddlewareConditionsData>,
+    pub compression: Option<HttpCompression>,
 }
 
 impl TryFrom<RouteRequestData> for RouteRequest {
@@ -212,6 +635,16 @@ impl TryFrom<RouteRequestData> for RouteRequest {
             binding,
             security,
             cors: value.cors,
+            concurrency_limit: value.concurrency_limit,
+            rate_limit: value.rate_limit.map(HttpRateLimit::try_from).transpose()?,
+            body_limits: value.body_limits,
+            response_cache: value.response_cache,
+            transforms: value.transforms.map(HttpTransforms::try_from).transpose()?,
+            conditions: value
+                .conditions
+                .map(HttpMiddlewareConditions::try_from)
+                .transpose()?,
+            compression: value.compression,
         })
     }
 }
@@ -223,24 +656,69 @@ impl TryFrom<Route> for RouteRequestData {
         let path = value.path.to_string();
         let binding = GatewayBindingData::try_from(value.binding.clone())?;
         let security = value.middlewares.clone().and_then(|middlewares| {
-            middlewares.get_http_authentication_middleware().map(|x| {
-                x.security_scheme_with_metadata
-                    .security_scheme
-                    .scheme_identifier()
-                    .to_string()
-            })
+            middlewares
+                .get_security_scheme_reference()
+                .map(|x| x.security_scheme_identifier.to_string())
         });
 
         let cors = value
             .middlewares
+            .clone()
             .and_then(|middlewares| middlewares.get_cors_middleware());
 
+        let concurrency_limit = value
+            .middlewares
+            .clone()
+            .and_then(|middlewares| middlewares.get_concurrency_limit_middleware());
+
+        let rate_limit = value
+            .middlewares
+            .clone()
+            .and_then(|middlewares| middlewares.get_rate_limit_middleware())
+            .map(HttpRateLimitData::try_from)
+            .transpose()?;
+
+        let body_limits = value
+            .middlewares
+            .clone()
+            .and_then(|middlewares| middlewares.get_body_limits_middleware());
+
+        let response_cache = value
+            .middlewares
+            .clone()
+            .and_then(|middlewares| middlewares.get_response_cache_middleware());
+
+        let transforms = value
+            .middlewares
+            .clone()
+            .and_then(|middlewares| middlewares.get_transforms_middleware())
+            .map(HttpTransformsData::try_from)
+            .transpose()?;
+
+        let conditions = value
+            .middlewares
+            .clone()
+            .and_then(|middlewares| middlewares.get_conditions_middleware())
+            .map(HttpMiddlewareConditionsData::try_from)
+            .transpose()?;
+
+        let compression = value
+            .middlewares
+            .and_then(|middlewares| middlewares.get_compression_middleware());
+
         Ok(Self {
             method,
             path,
             binding,
             security,
             cors,
+            concurrency_limit,
+            rate_limit,
+            body_limits,
+            response_cache,
+            transforms,
+            conditions,
+            compression,
         })
     }
 }
@@ -263,10 +741,167 @@ impl TryFrom<RouteRequest> for RouteRequestData {
             binding,
             security,
             cors,
+            concurrency_limit: value.concurrency_limit,
+            rate_limit: value
+                .rate_limit
+                .map(HttpRateLimitData::try_from)
+                .transpose()?,
+            body_limits: value.body_limits,
+            response_cache: value.response_cache,
+            transforms: value
+                .transforms
+                .map(HttpTransformsData::try_from)
+                .transpose()?,
+            conditions: value
+                .conditions
+                .map(HttpMiddlewareConditionsData::try_from)
+                .transpose()?,
+            compression: value.compression,
+        })
+    }
+}
+
+// Token bucket rate limit of a route, with its optional key as a Rib script
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpRateLimitData {
+    pub capacity: u32,
+    pub refill_interval_ms: u64,
+    pub key: Option<String>,
+}
+
+impl TryFrom<HttpRateLimit> for HttpRateLimitData {
+    type Error = String;
+
+    fn try_from(value: HttpRateLimit) -> Result<Self, Self::Error> {
+        let key = value
+            .key()
+            .map(|key| rib::to_string(key).map_err(|e| e.to_string()))
+            .transpose()?;
+
+        Ok(HttpRateLimitData {
+            capacity: value.capacity,
+            refill_interval_ms: value.refill_interval_ms,
+            key,
+        })
+    }
+}
+
+impl TryFrom<HttpRateLimitData> for HttpRateLimit {
+    type Error = String;
+
+    fn try_from(value: HttpRateLimitData) -> Result<Self, Self::Error> {
+        let key = value
+            .key
+            .map(|key| rib::from_string(key.as_str()).map_err(|e| e.to_string()))
+            .transpose()?;
+
+        HttpRateLimit::new(value.capacity, value.refill_interval_ms, key)
+    }
+}
+
+// The request and response transforms of a route as Rib scripts, in the order they are applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpTransformsData {
+    #[serde(default)]
+    #[oai(default)]
+    pub request: Vec<String>,
+    #[serde(default)]
+    #[oai(default)]
+    pub response: Vec<String>,
+}
+
+impl TryFrom<HttpTransforms> for HttpTransformsData {
+    type Error = String;
+
+    fn try_from(value: HttpTransforms) -> Result<Self, Self::Error> {
+        let to_strings = |scripts: Vec<&rib::Expr>| {
+            scripts
+                .into_iter()
+                .map(|script| rib::to_string(script).map_err(|e| e.to_string()))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(HttpTransformsData {
+            request: to_strings(value.request_scripts())?,
+            response: to_strings(value.response_scripts())?,
+        })
+    }
+}
+
+impl TryFrom<HttpTransformsData> for HttpTransforms {
+    type Error = String;
+
+    fn try_from(value: HttpTransformsData) -> Result<Self, Self::Error> {
+        let from_strings = |scripts: Vec<String>| {
+            scripts
+                .iter()
+                .map(|script| rib::from_string(script.as_str()).map_err(|e| e.to_string()))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        HttpTransforms::new(from_strings(value.request)?, from_strings(value.response)?)
+    }
+}
+
+// The conditions of the middlewares of a route as Rib predicates over the request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpMiddlewareConditionsData {
+    pub security: Option<String>,
+    pub rate_limit: Option<String>,
+    pub response_cache: Option<String>,
+    pub transforms: Option<String>,
+}
+
+impl TryFrom<HttpMiddlewareConditions> for HttpMiddlewareConditionsData {
+    type Error = String;
+
+    fn try_from(value: HttpMiddlewareConditions) -> Result<Self, Self::Error> {
+        let to_string = |middleware| {
+            value
+                .get(middleware)
+                .map(|condition| rib::to_string(&condition.predicate).map_err(|e| e.to_string()))
+                .transpose()
+        };
+
+        Ok(HttpMiddlewareConditionsData {
+            security: to_string(ConditionalMiddleware::Security)?,
+            rate_limit: to_string(ConditionalMiddleware::RateLimit)?,
+            response_cache: to_string(ConditionalMiddleware::ResponseCache)?,
+            transforms: to_string(ConditionalMiddleware::Transforms)?,
         })
     }
 }
 
+impl TryFrom<HttpMiddlewareConditionsData> for HttpMiddlewareConditions {
+    type Error = String;
+
+    fn try_from(value: HttpMiddlewareConditionsData) -> Result<Self, Self::Error> {
+        let conditions = [
+            (ConditionalMiddleware::Security, value.security),
+            (ConditionalMiddleware::RateLimit, value.rate_limit),
+            (ConditionalMiddleware::ResponseCache, value.response_cache),
+            (ConditionalMiddleware::Transforms, value.transforms),
+        ]
+        .into_iter()
+        .filter_map(|(middleware, predicate)| {
+            predicate.map(|predicate| {
+                rib::from_string(predicate.as_str())
+                    .map(|predicate| (middleware, predicate))
+                    .map_err(|e| e.to_string())
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+        HttpMiddlewareConditions::new(conditions)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
 pub struct RouteResponseData {
     pub method: MethodPattern,
@@ -282,14 +917,8 @@ impl TryFrom<CompiledRoute> for RouteResponseData {
         let path = value.path.to_string();
         let security = value.middlewares.and_then(|middlewares| {
             middlewares
-                .get_http_authentication_middleware()
-                .map(|http_authentication_middleware| {
-                    http_authentication_middleware
-                        .security_scheme_with_metadata
-                        .security_scheme
-                        .scheme_identifier()
-                        .to_string()
-                })
+                .get_security_scheme_reference()
+                .map(|x| x.security_scheme_identifier.to_string())
         });
 
         Ok(Self {
@@ -301,6 +930,110 @@ impl TryFrom<CompiledRoute> for RouteResponseData {
     }
 }
 
+// Mirroring of a share of the requests of a worker binding to a shadow component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct TrafficMirrorData {
+    pub component_id: VersionedComponentId,
+    // Percentage of the requests to mirror, between 0 and 100
+    pub percentage: u32,
+    // Log the requests for which the shadow response differs from the primary response
+    pub log_divergence: Option<bool>,
+    // Fields ignored when comparing the shadow response with the primary response
+    pub ignored_fields: Option<Vec<String>>,
+}
+
+impl From<TrafficMirror> for TrafficMirrorData {
+    fn from(value: TrafficMirror) -> Self {
+        TrafficMirrorData {
+            component_id: value.component_id,
+            percentage: value.percentage,
+            log_divergence: Some(value.log_divergence),
+            ignored_fields: Some(value.ignored_fields),
+        }
+    }
+}
+
+impl From<TrafficMirrorData> for TrafficMirror {
+    fn from(value: TrafficMirrorData) -> Self {
+        TrafficMirror {
+            component_id: value.component_id,
+            percentage: value.percentage,
+            log_divergence: value.log_divergence.unwrap_or(false),
+            ignored_fields: value.ignored_fields.unwrap_or_default(),
+        }
+    }
+}
+
+// Arguments, environment variables and WASI config variables of the worker of a binding,
+// given as Rib scripts, used only when the request creates the worker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerBootstrapData {
+    pub args: Option<Vec<String>>,
+    pub env: Option<BTreeMap<String, String>>,
+    pub config: Option<BTreeMap<String, String>>,
+}
+
+impl From<WorkerBootstrap> for WorkerBootstrapData {
+    fn from(value: WorkerBootstrap) -> Self {
+        WorkerBootstrapData {
+            args: Some(value.args.iter().map(|arg| arg.to_string()).collect()),
+            env: Some(
+                value
+                    .env
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_string()))
+                    .collect(),
+            ),
+            config: Some(
+                value
+                    .config
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_string()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl TryFrom<WorkerBootstrapData> for WorkerBootstrap {
+    type Error = String;
+
+    fn try_from(value: WorkerBootstrapData) -> Result<Self, Self::Error> {
+        Ok(WorkerBootstrap {
+            args: value
+                .args
+                .unwrap_or_default()
+                .iter()
+                .map(|arg| rib::from_string(arg.as_str()).map_err(|e| e.to_string()))
+                .collect::<Result<Vec<_>, _>>()?,
+            env: value
+                .env
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, value)| {
+                    rib::from_string(value.as_str())
+                        .map(|value| (name, value))
+                        .map_err(|e| e.to_string())
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()?,
+            config: value
+                .config
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, value)| {
+                    rib::from_string(value.as_str())
+                        .map(|value| (name, value))
+                        .map_err(|e| e.to_string())
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()?,
+        })
+    }
+}
+
 // GatewayBindingData is a user exposed structure of GatewayBinding
 // GatewayBindingData is flattened here only to keep the REST API backward compatibility.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
@@ -323,6 +1056,20 @@ pub struct GatewayBindingData {
     pub response: Option<String>,
     // For binding type - worker
     pub invocation_context: Option<String>,
+    // For binding type - worker
+    pub traffic_mirror: Option<TrafficMirrorData>,
+    // For binding type - worker
+    pub worker_per_user: Option<WorkerPerUser>,
+    // For binding type - worker
+    pub bootstrap: Option<WorkerBootstrapData>,
+    // For binding type - worker
+    pub pagination: Option<Pagination>,
+    // For binding type - worker
+    pub content_negotiation: Option<ContentNegotiation>,
+    // For binding type - worker
+    pub error_mapping: Option<ErrorMapping>,
+    // For binding type - worker, file-server, web-socket or server-sent-events
+    pub static_worker_names: Option<bool>,
 
     // CORS binding type
     //  For binding type - cors-middleware
@@ -340,6 +1087,22 @@ pub struct GatewayBindingData {
     pub max_age: Option<u64>,
     //  For binding type - cors-middleware
     pub allow_credentials: Option<bool>,
+
+    // For binding type - grpc
+    pub grpc_endpoint: Option<String>,
+    // For binding type - grpc
+    pub grpc_service: Option<String>,
+    // For binding type - grpc
+    pub grpc_method: Option<String>,
+    // For binding type - grpc. Base64 encoded google.protobuf.FileDescriptorSet
+    pub grpc_descriptor: Option<String>,
+
+    // For binding type - http-proxy
+    pub upstream: Option<String>,
+    // For binding type - http-proxy
+    pub request_mapping: Option<String>,
+    // For binding type - http-proxy
+    pub timeout_ms: Option<u64>,
 }
 
 impl GatewayBindingData {
@@ -374,6 +1137,13 @@ impl GatewayBindingData {
             worker_name: worker_id,
             idempotency_key,
             invocation_context,
+            traffic_mirror: worker_binding.traffic_mirror.map(TrafficMirrorData::from),
+            worker_per_user: worker_binding.worker_per_user,
+            bootstrap: worker_binding.bootstrap.map(WorkerBootstrapData::from),
+            pagination: worker_binding.pagination,
+            content_negotiation: worker_binding.content_negotiation,
+            error_mapping: worker_binding.error_mapping,
+            static_worker_names: worker_binding.static_worker_names.then_some(true),
             response: Some(response),
             allow_origin: None,
             allow_methods: None,
@@ -381,6 +1151,13 @@ impl GatewayBindingData {
             expose_headers: None,
             max_age: None,
             allow_credentials: None,
+            grpc_endpoint: None,
+            grpc_service: None,
+            grpc_method: None,
+            grpc_descriptor: None,
+            upstream: None,
+            request_mapping: None,
+            timeout_ms: None,
         })
     }
 
@@ -406,12 +1183,95 @@ impl GatewayBindingData {
             idempotency_key,
             response: None,
             invocation_context: None,
+            traffic_mirror: None,
+            worker_per_user: None,
+            bootstrap: None,
+            pagination: None,
+            content_negotiation: None,
+            error_mapping: None,
+            static_worker_names: None,
+            allow_origin: None,
+            allow_methods: None,
+            allow_headers: None,
+            expose_headers: None,
+            max_age: None,
+            allow_credentials: None,
+            grpc_endpoint: None,
+            grpc_service: None,
+            grpc_method: None,
+            grpc_descriptor: None,
+            upstream: None,
+            request_mapping: None,
+            timeout_ms: None,
+        })
+    }
+
+    pub fn from_grpc_binding(grpc_binding: GrpcBinding) -> Self {
+        Self {
+            binding_type: Some(GatewayBindingType::Grpc),
+            component_id: None,
+            worker_name: None,
+            idempotency_key: None,
+            response: None,
+            invocation_context: None,
+            traffic_mirror: None,
+            worker_per_user: None,
+            bootstrap: None,
+            pagination: None,
+            content_negotiation: None,
+            error_mapping: None,
+            static_worker_names: None,
+            allow_origin: None,
+            allow_methods: None,
+            allow_headers: None,
+            expose_headers: None,
+            max_age: None,
+            allow_credentials: None,
+            grpc_endpoint: Some(grpc_binding.endpoint),
+            grpc_service: Some(grpc_binding.service),
+            grpc_method: Some(grpc_binding.method),
+            grpc_descriptor: Some(
+                base64::engine::general_purpose::STANDARD.encode(grpc_binding.descriptor),
+            ),
+            upstream: None,
+            request_mapping: None,
+            timeout_ms: None,
+        }
+    }
+
+    pub fn from_http_proxy_binding(http_proxy_binding: HttpProxyBinding) -> Result<Self, String> {
+        let request_mapping = http_proxy_binding
+            .request_mapping
+            .map(|expr| rib::to_string(&expr).map_err(|e| e.to_string()))
+            .transpose()?;
+
+        Ok(Self {
+            binding_type: Some(GatewayBindingType::HttpProxy),
+            component_id: None,
+            worker_name: None,
+            idempotency_key: None,
+            response: None,
+            invocation_context: None,
+            traffic_mirror: None,
+            worker_per_user: None,
+            bootstrap: None,
+            pagination: None,
+            content_negotiation: None,
+            error_mapping: None,
+            static_worker_names: None,
             allow_origin: None,
             allow_methods: None,
             allow_headers: None,
             expose_headers: None,
             max_age: None,
             allow_credentials: None,
+            grpc_endpoint: None,
+            grpc_service: None,
+            grpc_method: None,
+            grpc_descriptor: None,
+            upstream: Some(http_proxy_binding.upstream),
+            request_mapping,
+            timeout_ms: http_proxy_binding.timeout_ms,
         })
     }
 }
@@ -422,12 +1282,28 @@ impl GatewayBindingData {
 pub struct MiddlewareData {
     pub cors: Option<HttpCors>,
     pub auth: Option<SecuritySchemeReferenceData>,
+    pub concurrency_limit: Option<HttpConcurrencyLimit>,
+    pub rate_limit: Option<HttpRateLimitData>,
+    pub body_limits: Option<HttpBodyLimits>,
+    pub response_cache: Option<HttpResponseCache>,
+    pub transforms: Option<HttpTransformsData>,
+    pub conditions: Option<HttpMiddlewareConditionsData>,
+    pub compression: Option<HttpCompression>,
 }
 
-impl From<HttpMiddlewares> for MiddlewareData {
-    fn from(value: HttpMiddlewares) -> Self {
+impl TryFrom<HttpMiddlewares> for MiddlewareData {
+    type Error = String;
+
+    fn try_from(value: HttpMiddlewares) -> Result<Self, Self::Error> {
         let mut cors = None;
         let mut auth = None;
+        let mut concurrency_limit = None;
+        let mut rate_limit = None;
+        let mut body_limits = None;
+        let mut response_cache = None;
+        let mut transforms = None;
+        let mut conditions = None;
+        let mut compression = None;
 
         for i in value.0.iter() {
             match i {
@@ -438,10 +1314,57 @@ impl From<HttpMiddlewares> for MiddlewareData {
                     );
                     auth = Some(security_scheme_reference)
                 }
+                HttpMiddleware::LimitConcurrency(concurrency_limit0) => {
+                    concurrency_limit = Some(concurrency_limit0.clone())
+                }
+                HttpMiddleware::LimitRate(rate_limit0) => {
+                    rate_limit = Some(HttpRateLimitData::try_from(rate_limit0.clone())?)
+                }
+                HttpMiddleware::LimitBody(body_limits0) => body_limits = Some(body_limits0.clone()),
+                HttpMiddleware::CacheResponse(response_cache0) => {
+                    response_cache = Some(response_cache0.clone())
+                }
+                HttpMiddleware::Transform(transforms0) => {
+                    transforms = Some(HttpTransformsData::try_from(transforms0.clone())?)
+                }
+                HttpMiddleware::ApplyConditionally(conditions0) => {
+                    conditions = Some(HttpMiddlewareConditionsData::try_from(conditions0.clone())?)
+                }
+                HttpMiddleware::CompressResponse(compression0) => {
+                    compression = Some(compression0.clone())
+                }
+                HttpMiddleware::AuthenticateJwt(auth0) => {
+                    let security_scheme_reference = SecuritySchemeReferenceData::from(
+                        SecuritySchemeReference::from(auth0.jwt_security_scheme.clone()),
+                    );
+                    auth = Some(security_scheme_reference)
+                }
+                HttpMiddleware::AuthenticateApiKey(auth0) => {
+                    let security_scheme_reference = SecuritySchemeReferenceData::from(
+                        SecuritySchemeReference::from(auth0.api_key_security_scheme.clone()),
+                    );
+                    auth = Some(security_scheme_reference)
+                }
+                HttpMiddleware::AuthenticateHmac(auth0) => {
+                    let security_scheme_reference = SecuritySchemeReferenceData::from(
+                        SecuritySchemeReference::from(auth0.hmac_security_scheme.clone()),
+                    );
+                    auth = Some(security_scheme_reference)
+                }
             }
         }
 
-        MiddlewareData { cors, auth }
+        Ok(MiddlewareData {
+            cors,
+            auth,
+            concurrency_limit,
+            rate_limit,
+            body_limits,
+            response_cache,
+            transforms,
+            conditions,
+            compression,
+        })
     }
 }
 
@@ -500,6 +1423,20 @@ pub struct GatewayBindingResponseData {
     pub idempotency_key_input: Option<RibInputTypeInfo>, // If bindingType is Default or FilerServer
     pub cors_preflight: Option<HttpCors>, // If bindingType is CorsPreflight (internally, a static binding)
     pub response_mapping_output: Option<RibOutputTypeInfo>, // If bindingType is Default or FileServer
+    pub grpc_endpoint: Option<String>,                      // If bindingType is Grpc
+    pub grpc_service: Option<String>,                       // If bindingType is Grpc
+    pub grpc_method: Option<String>,                        // If bindingType is Grpc
+    pub traffic_mirror: Option<TrafficMirrorData>,          // If bindingType is Default
+    pub worker_per_user: Option<WorkerPerUser>, // If bindingType is Default, FileServer, WebSocket or ServerSentEvents
+    pub bootstrap: Option<WorkerBootstrapData>, // If bindingType is Default, FileServer, WebSocket or ServerSentEvents
+    pub pagination: Option<Pagination>,         // If bindingType is Default
+    pub content_negotiation: Option<ContentNegotiation>, // If bindingType is Default
+    pub error_mapping: Option<ErrorMapping>,    // If bindingType is Default
+    pub static_worker_names: Option<bool>, // If bindingType is Default, FileServer, WebSocket or ServerSentEvents
+    pub upstream: Option<String>,          // If bindingType is HttpProxy
+    pub request_mapping: Option<String>,   // If bindingType is HttpProxy
+    pub request_mapping_input: Option<RibInputTypeInfo>, // If bindingType is HttpProxy
+    pub timeout_ms: Option<u64>,           // If bindingType is HttpProxy
 }
 
 impl GatewayBindingResponseData {
@@ -532,6 +1469,24 @@ impl GatewayBindingResponseData {
                 .map(|idempotency_key_compiled| idempotency_key_compiled.rib_input),
             cors_preflight: None,
             response_mapping_output: worker_binding.response_compiled.rib_output,
+            grpc_endpoint: None,
+            grpc_service: None,
+            grpc_method: None,
+            traffic_mirror: worker_binding
+                .traffic_mirror_compiled
+                .map(|compiled| TrafficMirrorData::from(TrafficMirror::from(compiled))),
+            worker_per_user: worker_binding.worker_per_user,
+            bootstrap: worker_binding
+                .bootstrap_compiled
+                .map(|compiled| WorkerBootstrapData::from(WorkerBootstrap::from(compiled))),
+            pagination: worker_binding.pagination,
+            content_negotiation: worker_binding.content_negotiation,
+            error_mapping: worker_binding.error_mapping,
+            static_worker_names: worker_binding.static_worker_names.then_some(true),
+            upstream: None,
+            request_mapping: None,
+            request_mapping_input: None,
+            timeout_ms: None,
         }
     }
 
@@ -559,6 +1514,20 @@ impl GatewayBindingResponseData {
                 .map(|idempotency_key_compiled| idempotency_key_compiled.rib_input),
             cors_preflight: None,
             response_mapping_output: None,
+            grpc_endpoint: None,
+            grpc_service: None,
+            grpc_method: None,
+            traffic_mirror: None,
+            worker_per_user: None,
+            bootstrap: None,
+            pagination: None,
+            content_negotiation: None,
+            error_mapping: None,
+            static_worker_names: None,
+            upstream: None,
+            request_mapping: None,
+            request_mapping_input: None,
+            timeout_ms: None,
         }
     }
 }
@@ -582,6 +1551,18 @@ impl TryFrom<GatewayBindingCompiled> for GatewayBindingResponseData {
                     GatewayBindingType::Default,
                 ))
             }
+            GatewayBindingCompiled::WebSocket(worker_binding) => {
+                Ok(GatewayBindingResponseData::from_worker_binding_compiled(
+                    worker_binding,
+                    GatewayBindingType::WebSocket,
+                ))
+            }
+            GatewayBindingCompiled::ServerSentEvents(worker_binding) => {
+                Ok(GatewayBindingResponseData::from_worker_binding_compiled(
+                    worker_binding,
+                    GatewayBindingType::ServerSentEvents,
+                ))
+            }
             GatewayBindingCompiled::HttpHandler(http_handler_binding) => Ok(
                 GatewayBindingResponseData::from_http_handler_binding_compiled(
                     http_handler_binding,
@@ -591,6 +1572,7 @@ impl TryFrom<GatewayBindingCompiled> for GatewayBindingResponseData {
             GatewayBindingCompiled::Static(static_binding) => {
                 let binding_type = match static_binding {
                     StaticBinding::HttpCorsPreflight(_) => GatewayBindingType::CorsPreflight,
+                    StaticBinding::StatusPage => GatewayBindingType::StatusPage,
                     StaticBinding::HttpAuthCallBack(_) => {
                         return Err(
                             "Auth call back static binding not to be exposed to users".to_string()
@@ -609,6 +1591,83 @@ impl TryFrom<GatewayBindingCompiled> for GatewayBindingResponseData {
                     idempotency_key_input: None,
                     cors_preflight: static_binding.get_cors_preflight(),
                     response_mapping_output: None,
+                    grpc_endpoint: None,
+                    grpc_service: None,
+                    grpc_method: None,
+                    traffic_mirror: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
+                    upstream: None,
+                    request_mapping: None,
+                    request_mapping_input: None,
+                    timeout_ms: None,
+                })
+            }
+            GatewayBindingCompiled::Grpc(grpc_binding) => Ok(GatewayBindingResponseData {
+                component_id: None,
+                worker_name: None,
+                idempotency_key: None,
+                response: None,
+                binding_type: Some(GatewayBindingType::Grpc),
+                response_mapping_input: None,
+                worker_name_input: None,
+                idempotency_key_input: None,
+                cors_preflight: None,
+                response_mapping_output: None,
+                grpc_endpoint: Some(grpc_binding.endpoint),
+                grpc_service: Some(grpc_binding.service),
+                grpc_method: Some(grpc_binding.method),
+                traffic_mirror: None,
+                worker_per_user: None,
+                bootstrap: None,
+                pagination: None,
+                content_negotiation: None,
+                error_mapping: None,
+                static_worker_names: None,
+                upstream: None,
+                request_mapping: None,
+                request_mapping_input: None,
+                timeout_ms: None,
+            }),
+            GatewayBindingCompiled::HttpProxy(http_proxy_binding) => {
+                let (request_mapping, request_mapping_input) =
+                    match http_proxy_binding.request_mapping_compiled {
+                        Some(compiled) => (
+                            Some(compiled.request_mapping.to_string()),
+                            Some(compiled.rib_input),
+                        ),
+                        None => (None, None),
+                    };
+
+                Ok(GatewayBindingResponseData {
+                    component_id: None,
+                    worker_name: None,
+                    idempotency_key: None,
+                    response: None,
+                    binding_type: Some(GatewayBindingType::HttpProxy),
+                    response_mapping_input: None,
+                    worker_name_input: None,
+                    idempotency_key_input: None,
+                    cors_preflight: None,
+                    response_mapping_output: None,
+                    grpc_endpoint: None,
+                    grpc_service: None,
+                    grpc_method: None,
+                    traffic_mirror: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
+                    upstream: Some(http_proxy_binding.upstream),
+                    request_mapping,
+                    request_mapping_input,
+                    timeout_ms: http_proxy_binding.timeout_ms,
                 })
             }
         }
@@ -617,7 +1676,7 @@ impl TryFrom<GatewayBindingCompiled> for GatewayBindingResponseData {
 
 impl<N> From<crate::gateway_api_deployment::ApiDeployment<N>> for ApiDeployment {
     fn from(value: crate::gateway_api_deployment::ApiDeployment<N>) -> Self {
-        let api_definitions = value
+        let mut api_definitions: Vec<ApiDefinitionInfo> = value
             .api_definition_keys
             .into_iter()
             .map(|key| ApiDefinitionInfo {
@@ -626,11 +1685,25 @@ impl<N> From<crate::gateway_api_deployment::ApiDeployment<N>> for ApiDeployment
             })
             .collect();
 
-        Self {
+        // the order of the definitions is not significant, keep it stable for the entity tag
+        api_definitions.sort_by(|a, b| (&a.id.0, &a.version.0).cmp(&(&b.id.0, &b.version.0)));
+
+        let mut deployment = Self {
             api_definitions,
             site: value.site,
-            created_at: Some(value.created_at),
-        }
+            created_at: None,
+            cors: value.cors,
+            traffic_split: value.traffic_split,
+            experiments: value.experiments,
+            capacity: value.capacity,
+            compression: value.compression,
+            etag: None,
+        };
+        // the entity tag only depends on the content of the deployment
+        deployment.etag = Some(resource_version(&deployment));
+        deployment.created_at = Some(value.created_at);
+
+        deployment
     }
 }
 
@@ -686,6 +1759,7 @@ impl TryInto<crate::gateway_api_definition::http::HttpApiDefinitionRequest>
                     .map(|x| x.into_iter().map(SecuritySchemeReference::new).collect()),
                 routes,
                 draft: self.draft,
+                taint_policy: self.taint_policy,
             },
         )
     }
@@ -705,6 +1779,18 @@ impl TryFrom<GatewayBinding> for GatewayBindingData {
                 GatewayBindingType::FileServer,
             ),
 
+            GatewayBinding::WebSocket(worker_binding) => GatewayBindingData::from_worker_binding(
+                worker_binding,
+                GatewayBindingType::WebSocket,
+            ),
+
+            GatewayBinding::ServerSentEvents(worker_binding) => {
+                GatewayBindingData::from_worker_binding(
+                    worker_binding,
+                    GatewayBindingType::ServerSentEvents,
+                )
+            }
+
             GatewayBinding::HttpHandler(http_handler_binding) => {
                 GatewayBindingData::from_http_handler_binding(
                     http_handler_binding,
@@ -720,18 +1806,69 @@ impl TryFrom<GatewayBinding> for GatewayBindingData {
                     idempotency_key: None,
                     response: None,
                     invocation_context: None,
+                    traffic_mirror: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
                     allow_origin: Some(cors.get_allow_origin()),
                     allow_methods: Some(cors.get_allow_methods()),
                     allow_headers: Some(cors.get_allow_headers()),
                     expose_headers: cors.get_expose_headers(),
                     max_age: cors.get_max_age(),
                     allow_credentials: cors.get_allow_credentials(),
+                    grpc_endpoint: None,
+                    grpc_service: None,
+                    grpc_method: None,
+                    grpc_descriptor: None,
+                    upstream: None,
+                    request_mapping: None,
+                    timeout_ms: None,
+                }),
+
+                StaticBinding::StatusPage => Ok(GatewayBindingData {
+                    binding_type: Some(GatewayBindingType::StatusPage),
+                    component_id: None,
+                    worker_name: None,
+                    idempotency_key: None,
+                    response: None,
+                    invocation_context: None,
+                    traffic_mirror: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
+                    allow_origin: None,
+                    allow_methods: None,
+                    allow_headers: None,
+                    expose_headers: None,
+                    max_age: None,
+                    allow_credentials: None,
+                    grpc_endpoint: None,
+                    grpc_service: None,
+                    grpc_method: None,
+                    grpc_descriptor: None,
+                    upstream: None,
+                    request_mapping: None,
+                    timeout_ms: None,
                 }),
 
                 StaticBinding::HttpAuthCallBack(_) => {
                     Err("Auth call back static binding not to be exposed to users".to_string())
                 }
             },
+
+            GatewayBinding::Grpc(grpc_binding) => {
+                Ok(GatewayBindingData::from_grpc_binding(grpc_binding))
+            }
+
+            GatewayBinding::HttpProxy(http_proxy_binding) => {
+                GatewayBindingData::from_http_proxy_binding(http_proxy_binding)
+            }
         }
     }
 }
@@ -743,7 +1880,11 @@ impl TryFrom<GatewayBindingData> for GatewayBinding {
         let v = gateway_binding_data.clone().binding_type;
 
         match v {
-            Some(GatewayBindingType::Default) | Some(GatewayBindingType::FileServer) | None => {
+            Some(GatewayBindingType::Default)
+            | Some(GatewayBindingType::FileServer)
+            | Some(GatewayBindingType::WebSocket)
+            | Some(GatewayBindingType::ServerSentEvents)
+            | None => {
                 let response = gateway_binding_data
                     .response
                     .ok_or("Missing response field in binding")?;
@@ -780,12 +1921,31 @@ impl TryFrom<GatewayBindingData> for GatewayBinding {
                     idempotency_key,
                     response_mapping: response,
                     invocation_context,
+                    traffic_mirror: gateway_binding_data.traffic_mirror.map(TrafficMirror::from),
+                    worker_per_user: gateway_binding_data.worker_per_user,
+                    bootstrap: gateway_binding_data
+                        .bootstrap
+                        .map(WorkerBootstrap::try_from)
+                        .transpose()?,
+                    pagination: gateway_binding_data.pagination,
+                    content_negotiation: gateway_binding_data.content_negotiation,
+                    error_mapping: gateway_binding_data.error_mapping,
+                    static_worker_names: gateway_binding_data
+                        .static_worker_names
+                        .unwrap_or_default(),
                 };
 
-                if v == Some(GatewayBindingType::FileServer) {
-                    Ok(GatewayBinding::FileServer(worker_binding))
-                } else {
-                    Ok(GatewayBinding::Default(worker_binding))
+                match v {
+                    Some(GatewayBindingType::FileServer) => {
+                        Ok(GatewayBinding::FileServer(worker_binding))
+                    }
+                    Some(GatewayBindingType::WebSocket) => {
+                        Ok(GatewayBinding::WebSocket(worker_binding))
+                    }
+                    Some(GatewayBindingType::ServerSentEvents) => {
+                        Ok(GatewayBinding::ServerSentEvents(worker_binding))
+                    }
+                    _ => Ok(GatewayBinding::Default(worker_binding)),
                 }
             }
 
@@ -814,6 +1974,52 @@ impl TryFrom<GatewayBindingData> for GatewayBinding {
                 Ok(GatewayBinding::HttpHandler(binding))
             }
 
+            Some(GatewayBindingType::Grpc) => {
+                let descriptor = gateway_binding_data
+                    .grpc_descriptor
+                    .ok_or("Missing grpcDescriptor field in binding")?;
+
+                let descriptor = base64::engine::general_purpose::STANDARD
+                    .decode(descriptor)
+                    .map_err(|e| format!("Invalid base64 in grpcDescriptor: {}", e))?;
+
+                let binding = GrpcBinding {
+                    endpoint: gateway_binding_data
+                        .grpc_endpoint
+                        .ok_or("Missing grpcEndpoint field in binding")?,
+                    service: gateway_binding_data
+                        .grpc_service
+                        .ok_or("Missing grpcService field in binding")?,
+                    method: gateway_binding_data
+                        .grpc_method
+                        .ok_or("Missing grpcMethod field in binding")?,
+                    descriptor,
+                };
+
+                Ok(GatewayBinding::Grpc(binding))
+            }
+
+            Some(GatewayBindingType::HttpProxy) => {
+                let request_mapping = gateway_binding_data
+                    .request_mapping
+                    .map(|expr| rib::from_string(expr.as_str()).map_err(|e| e.to_string()))
+                    .transpose()?;
+
+                let binding = HttpProxyBinding {
+                    upstream: gateway_binding_data
+                        .upstream
+                        .ok_or("Missing upstream field in binding")?,
+                    request_mapping,
+                    timeout_ms: gateway_binding_data.timeout_ms,
+                };
+
+                Ok(GatewayBinding::HttpProxy(binding))
+            }
+
+            Some(GatewayBindingType::StatusPage) => {
+                Ok(GatewayBinding::static_binding(StaticBinding::StatusPage))
+            }
+
             Some(GatewayBindingType::CorsPreflight) => {
                 let response_mapping = gateway_binding_data.response;
 
@@ -872,6 +2078,36 @@ impl TryFrom<crate::gateway_api_definition::http::HttpApiDefinition>
     }
 }
 
+impl TryFrom<crate::gateway_api_definition::http::HttpApiDefinitionRequest>
+    for HttpApiDefinitionRequest
+{
+    type Error = String;
+
+    fn try_from(
+        value: crate::gateway_api_definition::http::HttpApiDefinitionRequest,
+    ) -> Result<Self, Self::Error> {
+        let mut routes = Vec::new();
+
+        for route in value.routes {
+            routes.push(RouteRequestData::try_from(route)?);
+        }
+
+        Ok(Self {
+            id: value.id,
+            version: value.version,
+            security: value.security.map(|security| {
+                security
+                    .into_iter()
+                    .map(|s| s.security_scheme_identifier.to_string())
+                    .collect()
+            }),
+            routes,
+            draft: value.draft,
+            taint_policy: value.taint_policy,
+        })
+    }
+}
+
 impl TryFrom<grpc_apidefinition::v1::ApiDefinitionRequest>
     for crate::gateway_api_definition::http::HttpApiDefinitionRequest
 {
@@ -909,6 +2145,7 @@ impl TryFrom<grpc_apidefinition::v1::ApiDefinitionRequest>
             routes: route_requests,
             draft: value.draft,
             security,
+            taint_policy: TaintAnalysisPolicy::default(),
         };
 
         Ok(result)
@@ -1020,16 +2257,68 @@ impl TryFrom<grpc_apidefinition::HttpRoute> for crate::gateway_api_definition::h
             })
         });
 
-        let cors = value.middleware.and_then(|x| x.cors);
+        let cors = value.middleware.clone().and_then(|x| x.cors);
 
         let cors = cors.map(HttpCors::try_from).transpose()?;
 
+        let concurrency_limit = value
+            .middleware
+            .clone()
+            .and_then(|x| x.concurrency_limit)
+            .map(HttpConcurrencyLimit::try_from)
+            .transpose()?;
+
+        let rate_limit = value
+            .middleware
+            .clone()
+            .and_then(|x| x.rate_limit)
+            .map(HttpRateLimit::try_from)
+            .transpose()?;
+
+        let body_limits = value
+            .middleware
+            .clone()
+            .and_then(|x| x.body_limits)
+            .map(HttpBodyLimits::from);
+
+        let response_cache = value
+            .middleware
+            .clone()
+            .and_then(|x| x.response_cache)
+            .map(HttpResponseCache::from);
+
+        let transforms = value
+            .middleware
+            .clone()
+            .and_then(|x| x.transforms)
+            .map(HttpTransforms::try_from)
+            .transpose()?;
+
+        let conditions = value
+            .middleware
+            .clone()
+            .and_then(|x| x.conditions)
+            .map(HttpMiddlewareConditions::try_from)
+            .transpose()?;
+
+        let compression = value
+            .middleware
+            .and_then(|x| x.compression)
+            .map(HttpCompression::from);
+
         let result = crate::gateway_api_definition::http::RouteRequest {
             method,
             path,
             binding: gateway_binding,
             security,
             cors,
+            concurrency_limit,
+            rate_limit,
+            body_limits,
+            response_cache,
+            transforms,
+            conditions,
+            compression,
         };
 
         Ok(result)
@@ -13,9 +13,10 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+use std::path::PathBuf;
 use std::time::Duration;
 
-use golem_service_base::config::BlobStorageConfig;
+use golem_service_base::config::{BlobStorageConfig, TrashConfig, WatchConfig};
 use http::Uri;
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -43,6 +44,16 @@ pub struct WorkerServiceBaseConfig {
     pub routing_table: RoutingTableConfig,
     pub worker_executor_retries: RetryConfig,
     pub blob_storage: BlobStorageConfig,
+    pub api_definition_trash: TrashConfig,
+    pub async_invocation: AsyncInvocationConfig,
+    pub batch_invocation: BatchInvocationConfig,
+    pub scheduler: SchedulerConfig,
+    pub worker_files: WorkerFilesConfig,
+    pub metrics: MetricsConfig,
+    pub watch: WatchConfig,
+    pub local_gateway: LocalGatewayConfig,
+    pub rate_limit: RateLimitConfig,
+    pub client_certificates: ClientCertificatesConfig,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -93,6 +104,16 @@ impl Default for WorkerServiceBaseConfig {
                 max_jitter_factor: Some(0.15),
             },
             blob_storage: BlobStorageConfig::default(),
+            api_definition_trash: TrashConfig::default(),
+            async_invocation: AsyncInvocationConfig::default(),
+            batch_invocation: BatchInvocationConfig::default(),
+            scheduler: SchedulerConfig::default(),
+            worker_files: WorkerFilesConfig::default(),
+            metrics: MetricsConfig::default(),
+            watch: WatchConfig::default(),
+            local_gateway: LocalGatewayConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+            client_certificates: ClientCertificatesConfig::default(),
         }
     }
 }
@@ -119,6 +140,155 @@ impl HasConfigExamples<WorkerServiceBaseConfig> for WorkerServiceBaseConfig {
     }
 }
 
+// Serving the API definition of a local JSON or YAML file on localhost, on the custom request
+// port, instead of running the service, to try out its routes without deploying it. Its bindings
+// invoke the workers of the executors found through the routing table, so it is pointed to a
+// local or a remote cluster by the `routing_table` config. The worker service binary also sets the
+// definition file from its `api-definition serve --local <file>` command.
+// The state of the gateway is kept in SQLite databases in `state_dir` (a directory in the temp
+// directory by default) rather than in the database of the service.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LocalGatewayConfig {
+    pub definition_file: Option<PathBuf>,
+    pub state_dir: Option<PathBuf>,
+}
+
+// Async invocations of workers: how long their results are kept to be polled,
+// and how their results are posted to the callback URLs.
+// Callbacks are only posted to the allowed hosts, if any are set (a host starting with a dot
+// allows its subdomains), and never to private, loopback or link-local addresses unless
+// allowed. With a signing secret, the callbacks are signed with HMAC-SHA256.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AsyncInvocationConfig {
+    #[serde(with = "humantime_serde")]
+    pub result_ttl: Duration,
+    #[serde(with = "humantime_serde")]
+    pub callback_timeout: Duration,
+    pub callback_retries: RetryConfig,
+    pub callback_allowed_hosts: Vec<String>,
+    pub allow_private_callback_addresses: bool,
+    pub callback_signing_secret: Option<String>,
+}
+
+impl Default for AsyncInvocationConfig {
+    fn default() -> Self {
+        Self {
+            result_ttl: Duration::from_secs(24 * 60 * 60),
+            callback_timeout: Duration::from_secs(10),
+            callback_retries: RetryConfig {
+                max_attempts: 5,
+                min_delay: Duration::from_secs(1),
+                max_delay: Duration::from_secs(60),
+                multiplier: 3.0,
+                max_jitter_factor: Some(0.15),
+            },
+            callback_allowed_hosts: vec![],
+            allow_private_callback_addresses: false,
+            callback_signing_secret: None,
+        }
+    }
+}
+
+// Limits of the batch invocations of workers: the number of invocations in a batch,
+// and the number of them dispatched at the same time
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BatchInvocationConfig {
+    pub max_batch_size: usize,
+    pub max_parallelism: usize,
+}
+
+impl Default for BatchInvocationConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 1000,
+            max_parallelism: 16,
+        }
+    }
+}
+
+// The scheduler triggering the invocations of the cron schedules of workers. Runs which are
+// late by more than the misfire threshold are handled by the misfire policy of their schedule.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchedulerConfig {
+    pub enabled: bool,
+    #[serde(with = "humantime_serde")]
+    pub poll_interval: Duration,
+    #[serde(with = "humantime_serde")]
+    pub misfire_threshold: Duration,
+    pub max_due_per_poll: u32,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval: Duration::from_secs(5),
+            misfire_threshold: Duration::from_secs(60),
+            max_due_per_poll: 100,
+        }
+    }
+}
+
+// Limits of the browsing of the files of workers: the number of entries of a listed directory,
+// and the size of a downloaded file, in bytes
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerFilesConfig {
+    pub max_directory_entries: usize,
+    pub max_download_size: u64,
+}
+
+impl Default for WorkerFilesConfig {
+    fn default() -> Self {
+        Self {
+            max_directory_entries: 1000,
+            max_download_size: 100 * 1024 * 1024,
+        }
+    }
+}
+
+// The labels of the gateway and invocation metrics: whether the gateway metrics are labelled by
+// route, and the number of distinct values of a label before the new ones are recorded as `other`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    pub route_labels: bool,
+    pub max_label_values: usize,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            route_labels: true,
+            max_label_values: 200,
+        }
+    }
+}
+
+// The token buckets of the rate limits of the routes: whether the requests are let through without
+// a limit when the buckets shared in Redis cannot be reached, and the number of buckets kept in
+// memory by a gateway without Redis before the least recently used ones are dropped
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub fail_open: bool,
+    pub max_in_memory_buckets: usize,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            fail_open: true,
+            max_in_memory_buckets: 10_000,
+        }
+    }
+}
+
+// The client certificates of the requests are forwarded by the proxies terminating TLS in front
+// of the gateway, and the header carrying them is only accepted from the addresses (or CIDR
+// ranges) of these proxies. It is dropped from the requests of any other peer.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ClientCertificatesConfig {
+    pub trusted_proxies: Vec<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ComponentServiceConfig {
     pub host: String,
@@ -16,12 +16,18 @@ use crate::gateway_api_definition::http::path_pattern_parser::parse_path_pattern
 use crate::gateway_api_definition::http::{HttpApiDefinitionRequest, RouteRequest};
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion, HasGolemBindings};
 use crate::gateway_api_definition_transformer::transform_http_api_definition;
-use crate::gateway_binding::{GatewayBinding, GatewayBindingCompiled};
-use crate::gateway_binding::{HttpHandlerBindingCompiled, WorkerBindingCompiled};
+use crate::gateway_binding::{GatewayBinding, GatewayBindingCompiled, StaticBinding};
+use crate::gateway_binding::{
+    HttpHandlerBindingCompiled, HttpProxyBindingCompiled, Pagination, TrafficMirrorCompiled,
+    WorkerBinding, WorkerBindingCompiled,
+};
 use crate::gateway_middleware::{
     HttpAuthenticationMiddleware, HttpCors, HttpMiddleware, HttpMiddlewares,
 };
-use crate::gateway_security::SecuritySchemeReference;
+use crate::gateway_rib_compiler::{
+    with_trusted_sources, with_worker_name_typing, TaintAnalysisPolicy,
+};
+use crate::gateway_security::{RegisteredSecurityScheme, SecuritySchemeReference};
 use crate::service::gateway::api_definition::ApiDefinitionError;
 use crate::service::gateway::api_definition_validator::ValidationErrors;
 use crate::service::gateway::security_scheme::SecuritySchemeService;
@@ -31,7 +37,7 @@ use golem_api_grpc::proto::golem::apidefinition::HttpRoute;
 use golem_service_base::model::{Component, VersionedComponentId};
 use golem_wasm_ast::analysis::AnalysedExport;
 use poem_openapi::Enum;
-use rib::RibError;
+use rib::{RibError, WorkerNameTyping};
 use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -64,10 +70,9 @@ impl HttpApiDefinition {
             .filter_map(|route| {
                 route
                     .middlewares
-                    .clone()
-                    .and_then(|x| x.get_http_authentication_middleware())
+                    .as_ref()
+                    .and_then(|x| x.get_security_scheme_reference())
             })
-            .map(|x| SecuritySchemeReference::from(x.security_scheme_with_metadata))
             .collect()
     }
 
@@ -82,7 +87,7 @@ impl HttpApiDefinition {
         if let Some(security_schemes) = request.security {
             for security_scheme_reference in security_schemes {
                 let security_scheme = security_scheme_service
-                    .get(
+                    .get_registered(
                         &security_scheme_reference.security_scheme_identifier,
                         namespace,
                     )
@@ -101,19 +106,132 @@ impl HttpApiDefinition {
         for route in request.routes {
             let mut http_middlewares = vec![];
 
+            // The cached responses are shared by all the clients of the route, so the responses
+            // of a route which authenticates its clients must not be cached
+            if route.security.is_some() && route.response_cache.is_some() {
+                return Err(ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!(
+                        "Invalid response cache of route {} {}: the responses of secured routes cannot be cached",
+                        route.method, route.path
+                    )],
+                }));
+            }
+
             if let Some(security) = route.security {
                 let security_scheme = security_scheme_service
-                    .get(&security.security_scheme_identifier, namespace)
+                    .get_registered(&security.security_scheme_identifier, namespace)
                     .await
                     .map_err(ApiDefinitionError::SecuritySchemeError)?;
 
-                http_middlewares.push(HttpMiddleware::authenticate_request(security_scheme));
+                match security_scheme {
+                    RegisteredSecurityScheme::OpenId(security_scheme) => {
+                        http_middlewares.push(HttpMiddleware::authenticate_request(security_scheme))
+                    }
+                    RegisteredSecurityScheme::Jwt(security_scheme) => {
+                        http_middlewares.push(HttpMiddleware::authenticate_jwt(security_scheme))
+                    }
+                    RegisteredSecurityScheme::ApiKey(security_scheme) => {
+                        http_middlewares.push(HttpMiddleware::authenticate_api_key(security_scheme))
+                    }
+                    RegisteredSecurityScheme::Hmac(security_scheme) => {
+                        http_middlewares.push(HttpMiddleware::authenticate_hmac(security_scheme))
+                    }
+                }
             }
 
             if let Some(cors) = route.cors {
                 http_middlewares.push(HttpMiddleware::cors(cors));
             }
 
+            if let Some(concurrency_limit) = route.concurrency_limit {
+                concurrency_limit.validate().map_err(|error| {
+                    ApiDefinitionError::ValidationError(ValidationErrors {
+                        errors: vec![format!(
+                            "Invalid concurrency limit of route {} {}: {}",
+                            route.method, route.path, error
+                        )],
+                    })
+                })?;
+
+                http_middlewares.push(HttpMiddleware::limit_concurrency(concurrency_limit));
+            }
+
+            if let Some(rate_limit) = route.rate_limit {
+                http_middlewares.push(HttpMiddleware::limit_rate(rate_limit));
+            }
+
+            if let Some(body_limits) = route.body_limits {
+                body_limits.validate().map_err(|error| {
+                    ApiDefinitionError::ValidationError(ValidationErrors {
+                        errors: vec![format!(
+                            "Invalid body limits of route {} {}: {}",
+                            route.method, route.path, error
+                        )],
+                    })
+                })?;
+
+                http_middlewares.push(HttpMiddleware::limit_body(body_limits));
+            }
+
+            if let Some(response_cache) = route.response_cache {
+                let validation = if route.method == MethodPattern::Get {
+                    response_cache.validate()
+                } else {
+                    Err("Only the responses of GET routes can be cached".to_string())
+                };
+
+                validation.map_err(|error| {
+                    ApiDefinitionError::ValidationError(ValidationErrors {
+                        errors: vec![format!(
+                            "Invalid response cache of route {} {}: {}",
+                            route.method, route.path, error
+                        )],
+                    })
+                })?;
+
+                http_middlewares.push(HttpMiddleware::cache_response(response_cache));
+            }
+
+            if let Some(transforms) = route.transforms.filter(|transforms| !transforms.is_empty()) {
+                http_middlewares.push(HttpMiddleware::transform(transforms));
+            }
+
+            if let Some(compression) = route.compression {
+                compression.validate().map_err(|error| {
+                    ApiDefinitionError::ValidationError(ValidationErrors {
+                        errors: vec![format!(
+                            "Invalid compression of route {} {}: {}",
+                            route.method, route.path, error
+                        )],
+                    })
+                })?;
+
+                http_middlewares.push(HttpMiddleware::compress_response(compression));
+            }
+
+            if let Some(conditions) = route.conditions.filter(|conditions| !conditions.is_empty()) {
+                let undeclared = conditions
+                    .conditions()
+                    .into_iter()
+                    .map(|(middleware, _)| middleware)
+                    .find(|middleware| {
+                        !http_middlewares
+                            .iter()
+                            .any(|m| m.conditional_middleware() == Some(*middleware))
+                    });
+
+                if let Some(middleware) = undeclared {
+                    return Err(ApiDefinitionError::ValidationError(ValidationErrors {
+                        errors: vec![format!(
+                            "Invalid conditions of route {} {}: the route has no {} to apply conditionally",
+                            route.method, route.path, middleware
+                        )],
+                    }));
+                }
+
+                http_middlewares.push(HttpMiddleware::apply_conditionally(conditions));
+            }
+
             routes.push(Route {
                 method: route.method,
                 path: route.path,
@@ -160,6 +278,7 @@ impl From<HttpApiDefinition> for HttpApiDefinitionRequest {
             security,
             routes: value.routes.into_iter().map(RouteRequest::from).collect(),
             draft: value.draft,
+            taint_policy: TaintAnalysisPolicy::default(),
         }
     }
 }
@@ -380,6 +499,21 @@ pub struct VarInfo {
     pub key_name: String,
 }
 
+// A path parameter matching only the segments fully matched by the regex (Example: `{id:[0-9]+}`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
+pub struct RegexVarInfo {
+    pub key_name: String,
+    pub pattern: String,
+}
+
+impl RegexVarInfo {
+    pub fn var_info(&self) -> VarInfo {
+        VarInfo {
+            key_name: self.key_name.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
 pub struct QueryInfo {
     pub key_name: String,
@@ -401,6 +535,17 @@ impl AllPathPatterns {
     pub fn parse(input: &str) -> Result<AllPathPatterns, String> {
         input.parse()
     }
+
+    // The path parameters whose values are validated by a regex before the route is invoked
+    pub fn validated_path_params(&self) -> Vec<String> {
+        self.path_patterns
+            .iter()
+            .filter_map(|pattern| match pattern {
+                PathPattern::RegexVar(regex_var) => Some(regex_var.key_name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl Display for AllPathPatterns {
@@ -428,7 +573,7 @@ impl FromStr for AllPathPatterns {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_path_pattern(s)
+        let result = parse_path_pattern(s)
             .map_err(|err| err.to_string())
             .and_then(|(leftover, result)| {
                 if !leftover.is_empty() {
@@ -436,7 +581,20 @@ impl FromStr for AllPathPatterns {
                 } else {
                     Ok(result)
                 }
-            })
+            })?;
+
+        for pattern in &result.path_patterns {
+            if let PathPattern::RegexVar(info) = pattern {
+                regex::Regex::new(&info.pattern).map_err(|err| {
+                    format!(
+                        "Invalid regex constraint of path parameter {}: {}",
+                        info.key_name, err
+                    )
+                })?;
+            }
+        }
+
+        Ok(result)
     }
 }
 
@@ -474,6 +632,7 @@ pub enum PathPattern {
     Literal(LiteralInfo),
     Var(VarInfo),
     CatchAllVar(VarInfo),
+    RegexVar(RegexVarInfo),
 }
 
 impl PathPattern {
@@ -492,6 +651,13 @@ impl PathPattern {
             key_name: value.into(),
         })
     }
+
+    pub fn regex_var(value: impl Into<String>, pattern: impl Into<String>) -> PathPattern {
+        PathPattern::RegexVar(RegexVarInfo {
+            key_name: value.into(),
+            pattern: pattern.into(),
+        })
+    }
 }
 
 impl Display for PathPattern {
@@ -500,6 +666,7 @@ impl Display for PathPattern {
             PathPattern::Literal(info) => write!(f, "{}", info.0),
             PathPattern::Var(info) => write!(f, "{{{}}}", info.key_name),
             PathPattern::CatchAllVar(info) => write!(f, "{{+{}}}", info.key_name),
+            PathPattern::RegexVar(info) => write!(f, "{{{}:{}}}", info.key_name, info.pattern),
         }
     }
 }
@@ -552,6 +719,18 @@ pub struct CompiledRoute {
 pub enum RouteCompilationErrors {
     MetadataNotFoundError(VersionedComponentId),
     RibError(RibError),
+    InvalidGrpcBinding(String),
+    InvalidTrafficMirror(String),
+    InvalidWebSocketBinding(String),
+    InvalidServerSentEventsBinding(String),
+    InvalidHttpProxyBinding(String),
+    InvalidStatusPageBinding(String),
+    InvalidWorkerPerUser(String),
+    InvalidWorkerBootstrap(String),
+    InvalidPagination(String),
+    InvalidContentNegotiation(String),
+    InvalidErrorMapping(String),
+    InvalidStaticWorkerNames(String),
 }
 
 #[derive(Clone, Debug)]
@@ -574,18 +753,117 @@ impl ComponentMetadataDictionary {
 }
 
 impl CompiledRoute {
+    // The route as `METHOD /path/{pattern}`, identifying it in diagnostics and metrics
+    pub fn name(&self) -> String {
+        format!(
+            "{} {}",
+            hyper::http::Method::from(self.method.clone()),
+            self.path
+        )
+    }
+
     pub fn get_security_middleware(&self) -> Option<HttpAuthenticationMiddleware> {
         match &self.middlewares {
             Some(middlewares) => middlewares.get_http_authentication_middleware(),
             None => None,
         }
     }
+    // The worker of a worker per user binding is named after the identity of the request,
+    // which only exists for secured routes
+    fn validate_worker_binding(
+        route: &Route,
+        worker_binding: &WorkerBinding,
+    ) -> Result<(), RouteCompilationErrors> {
+        if let Some(bootstrap) = &worker_binding.bootstrap {
+            bootstrap
+                .validate()
+                .map_err(RouteCompilationErrors::InvalidWorkerBootstrap)?;
+        }
+
+        if let Some(worker_per_user) = &worker_binding.worker_per_user {
+            worker_per_user
+                .validate()
+                .map_err(RouteCompilationErrors::InvalidWorkerPerUser)?;
+
+            if worker_binding.worker_name.is_some() {
+                return Err(RouteCompilationErrors::InvalidWorkerPerUser(
+                    "A worker per user binding cannot have a worker name".to_string(),
+                ));
+            }
+
+            let is_secured = route
+                .middlewares
+                .as_ref()
+                .and_then(|middlewares| middlewares.get_security_scheme_reference())
+                .is_some();
+
+            if !is_secured {
+                return Err(RouteCompilationErrors::InvalidWorkerPerUser(format!(
+                    "Route {} {} has a worker per user binding but no security",
+                    route.method, route.path
+                )));
+            }
+        }
+
+        // The workers of such a binding are named by the type checked `instance()` calls of its
+        // scripts, whereas a worker name script or a worker per user computes them at runtime
+        if worker_binding.static_worker_names {
+            if worker_binding.worker_name.is_some() {
+                return Err(RouteCompilationErrors::InvalidStaticWorkerNames(
+                    "A binding with static worker names cannot have a worker name".to_string(),
+                ));
+            }
+
+            if worker_binding.worker_per_user.is_some() {
+                return Err(RouteCompilationErrors::InvalidStaticWorkerNames(
+                    "A binding with static worker names cannot have a worker per user".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // The values of the validated path parameters are trusted by the taint analysis of the scripts,
+    // and the worker names are strictly typed for the bindings requiring static worker names
     pub fn from_route(
         route: &Route,
         metadata_dictionary: &ComponentMetadataDictionary,
+    ) -> Result<CompiledRoute, RouteCompilationErrors> {
+        let trusted_sources = route
+            .path
+            .validated_path_params()
+            .into_iter()
+            .map(|param| format!("request.path.{}", param))
+            .collect();
+
+        let worker_name_typing = match &route.binding {
+            GatewayBinding::Default(worker_binding)
+            | GatewayBinding::FileServer(worker_binding)
+            | GatewayBinding::WebSocket(worker_binding)
+            | GatewayBinding::ServerSentEvents(worker_binding)
+                if worker_binding.static_worker_names =>
+            {
+                WorkerNameTyping::Strict
+            }
+            _ => WorkerNameTyping::Inferred,
+        };
+
+        with_trusted_sources(trusted_sources, || {
+            with_worker_name_typing(worker_name_typing, || {
+                Self::compile_route(route, metadata_dictionary)
+            })
+        })
+    }
+
+    fn compile_route(
+        route: &Route,
+        metadata_dictionary: &ComponentMetadataDictionary,
     ) -> Result<CompiledRoute, RouteCompilationErrors> {
         match &route.binding {
             GatewayBinding::Default(worker_binding) => {
+                Self::validate_worker_binding(route, worker_binding)?;
+
                 let metadata = metadata_dictionary
                     .metadata
                     .get(&worker_binding.component_id)
@@ -593,10 +871,57 @@ impl CompiledRoute {
                         worker_binding.component_id.clone(),
                     ))?;
 
-                let binding =
+                let mut binding =
                     WorkerBindingCompiled::from_raw_worker_binding(worker_binding, metadata)
                         .map_err(RouteCompilationErrors::RibError)?;
 
+                if let Some(pagination) = &worker_binding.pagination {
+                    pagination
+                        .validate()
+                        .map_err(RouteCompilationErrors::InvalidPagination)?;
+
+                    if let Some(rib_output) = &binding.response_compiled.rib_output {
+                        Pagination::validate_page_type(&rib_output.analysed_type)
+                            .map_err(RouteCompilationErrors::InvalidPagination)?;
+                    }
+                }
+
+                if let Some(content_negotiation) = &worker_binding.content_negotiation {
+                    content_negotiation
+                        .validate()
+                        .map_err(RouteCompilationErrors::InvalidContentNegotiation)?;
+                }
+
+                // The mapped cases are the ones of the error types of the component
+                if let Some(error_mapping) = &worker_binding.error_mapping {
+                    error_mapping
+                        .validate()
+                        .and_then(|_| error_mapping.validate_against(metadata))
+                        .map_err(RouteCompilationErrors::InvalidErrorMapping)?;
+                }
+
+                if let Some(traffic_mirror) = &worker_binding.traffic_mirror {
+                    traffic_mirror
+                        .validate(&worker_binding.component_id)
+                        .map_err(RouteCompilationErrors::InvalidTrafficMirror)?;
+
+                    let shadow_metadata = metadata_dictionary
+                        .metadata
+                        .get(&traffic_mirror.component_id)
+                        .ok_or(RouteCompilationErrors::MetadataNotFoundError(
+                            traffic_mirror.component_id.clone(),
+                        ))?;
+
+                    binding.traffic_mirror_compiled = Some(
+                        TrafficMirrorCompiled::from_traffic_mirror(
+                            traffic_mirror,
+                            &worker_binding.response_mapping,
+                            shadow_metadata,
+                        )
+                        .map_err(RouteCompilationErrors::RibError)?,
+                    );
+                }
+
                 Ok(CompiledRoute {
                     method: route.method.clone(),
                     path: route.path.clone(),
@@ -606,6 +931,32 @@ impl CompiledRoute {
             }
 
             GatewayBinding::FileServer(worker_binding) => {
+                Self::validate_worker_binding(route, worker_binding)?;
+
+                if worker_binding.pagination.is_some() {
+                    return Err(RouteCompilationErrors::InvalidPagination(
+                        "Pagination is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if worker_binding.content_negotiation.is_some() {
+                    return Err(RouteCompilationErrors::InvalidContentNegotiation(
+                        "Content negotiation is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if worker_binding.error_mapping.is_some() {
+                    return Err(RouteCompilationErrors::InvalidErrorMapping(
+                        "Error mapping is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if worker_binding.traffic_mirror.is_some() {
+                    return Err(RouteCompilationErrors::InvalidTrafficMirror(
+                        "Traffic mirroring is supported only for worker bindings".to_string(),
+                    ));
+                }
+
                 let metadata = metadata_dictionary
                     .metadata
                     .get(&worker_binding.component_id)
@@ -647,12 +998,164 @@ impl CompiledRoute {
                 })
             }
 
-            GatewayBinding::Static(static_binding) => Ok(CompiledRoute {
-                method: route.method.clone(),
-                path: route.path.clone(),
-                binding: GatewayBindingCompiled::Static(static_binding.clone()),
-                middlewares: route.middlewares.clone(),
-            }),
+            GatewayBinding::Static(static_binding) => {
+                if static_binding == &StaticBinding::StatusPage
+                    && route.method != MethodPattern::Get
+                {
+                    return Err(RouteCompilationErrors::InvalidStatusPageBinding(format!(
+                        "Status pages are served for GET requests, but the route method is {}",
+                        route.method
+                    )));
+                }
+
+                Ok(CompiledRoute {
+                    method: route.method.clone(),
+                    path: route.path.clone(),
+                    binding: GatewayBindingCompiled::Static(static_binding.clone()),
+                    middlewares: route.middlewares.clone(),
+                })
+            }
+
+            GatewayBinding::WebSocket(worker_binding) => {
+                Self::validate_worker_binding(route, worker_binding)?;
+
+                if worker_binding.pagination.is_some() {
+                    return Err(RouteCompilationErrors::InvalidPagination(
+                        "Pagination is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if worker_binding.content_negotiation.is_some() {
+                    return Err(RouteCompilationErrors::InvalidContentNegotiation(
+                        "Content negotiation is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if worker_binding.error_mapping.is_some() {
+                    return Err(RouteCompilationErrors::InvalidErrorMapping(
+                        "Error mapping is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if route.method != MethodPattern::Get {
+                    return Err(RouteCompilationErrors::InvalidWebSocketBinding(format!(
+                        "WebSocket upgrades are GET requests, but the route method is {}",
+                        route.method
+                    )));
+                }
+
+                // The connection is bound to a single worker, which holds its state
+                if worker_binding.worker_name.is_none() && worker_binding.worker_per_user.is_none()
+                {
+                    return Err(RouteCompilationErrors::InvalidWebSocketBinding(
+                        "WebSocket bindings require a worker name".to_string(),
+                    ));
+                }
+
+                let metadata = metadata_dictionary
+                    .metadata
+                    .get(&worker_binding.component_id)
+                    .ok_or(RouteCompilationErrors::MetadataNotFoundError(
+                        worker_binding.component_id.clone(),
+                    ))?;
+
+                let binding =
+                    WorkerBindingCompiled::from_raw_web_socket_binding(worker_binding, metadata)
+                        .map_err(RouteCompilationErrors::RibError)?;
+
+                Ok(CompiledRoute {
+                    method: route.method.clone(),
+                    path: route.path.clone(),
+                    binding: GatewayBindingCompiled::WebSocket(binding),
+                    middlewares: route.middlewares.clone(),
+                })
+            }
+
+            GatewayBinding::ServerSentEvents(worker_binding) => {
+                Self::validate_worker_binding(route, worker_binding)?;
+
+                if worker_binding.pagination.is_some() {
+                    return Err(RouteCompilationErrors::InvalidPagination(
+                        "Pagination is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if worker_binding.content_negotiation.is_some() {
+                    return Err(RouteCompilationErrors::InvalidContentNegotiation(
+                        "Content negotiation is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if worker_binding.error_mapping.is_some() {
+                    return Err(RouteCompilationErrors::InvalidErrorMapping(
+                        "Error mapping is supported only for worker bindings".to_string(),
+                    ));
+                }
+
+                if route.method != MethodPattern::Get {
+                    return Err(RouteCompilationErrors::InvalidServerSentEventsBinding(format!(
+                        "Event streams are subscribed to with GET requests, but the route method is {}",
+                        route.method
+                    )));
+                }
+
+                // Events are emitted by a single worker, there is nothing to stream from an ephemeral one
+                if worker_binding.worker_name.is_none() && worker_binding.worker_per_user.is_none()
+                {
+                    return Err(RouteCompilationErrors::InvalidServerSentEventsBinding(
+                        "Server-sent events bindings require a worker name".to_string(),
+                    ));
+                }
+
+                let metadata = metadata_dictionary
+                    .metadata
+                    .get(&worker_binding.component_id)
+                    .ok_or(RouteCompilationErrors::MetadataNotFoundError(
+                        worker_binding.component_id.clone(),
+                    ))?;
+
+                let binding = WorkerBindingCompiled::from_raw_server_sent_events_binding(
+                    worker_binding,
+                    metadata,
+                )
+                .map_err(RouteCompilationErrors::RibError)?;
+
+                Ok(CompiledRoute {
+                    method: route.method.clone(),
+                    path: route.path.clone(),
+                    binding: GatewayBindingCompiled::ServerSentEvents(binding),
+                    middlewares: route.middlewares.clone(),
+                })
+            }
+
+            GatewayBinding::Grpc(grpc_binding) => {
+                grpc_binding
+                    .validate()
+                    .map_err(RouteCompilationErrors::InvalidGrpcBinding)?;
+
+                Ok(CompiledRoute {
+                    method: route.method.clone(),
+                    path: route.path.clone(),
+                    binding: GatewayBindingCompiled::Grpc(grpc_binding.clone()),
+                    middlewares: route.middlewares.clone(),
+                })
+            }
+
+            GatewayBinding::HttpProxy(http_proxy_binding) => {
+                http_proxy_binding
+                    .validate()
+                    .map_err(RouteCompilationErrors::InvalidHttpProxyBinding)?;
+
+                let binding = HttpProxyBindingCompiled::from_http_proxy_binding(http_proxy_binding)
+                    .map_err(RouteCompilationErrors::RibError)?;
+
+                Ok(CompiledRoute {
+                    method: route.method.clone(),
+                    path: route.path.clone(),
+                    binding: GatewayBindingCompiled::HttpProxy(binding),
+                    middlewares: route.middlewares.clone(),
+                })
+            }
         }
     }
 }
@@ -675,7 +1178,8 @@ mod tests {
     use async_trait::async_trait;
 
     use crate::gateway_security::{
-        SecurityScheme, SecuritySchemeIdentifier, SecuritySchemeWithProviderMetadata,
+        ApiKeySecurityScheme, HmacSecurityScheme, JwtSecurityScheme, SecurityScheme,
+        SecuritySchemeIdentifier, SecuritySchemeWithProviderMetadata,
     };
     use crate::service::gateway::security_scheme::SecuritySchemeServiceError;
     use chrono::{DateTime, Utc};
@@ -903,6 +1407,16 @@ mod tests {
             ))
         }
 
+        async fn get_registered(
+            &self,
+            _security_scheme_name: &SecuritySchemeIdentifier,
+            _namespace: &Namespace,
+        ) -> Result<RegisteredSecurityScheme, SecuritySchemeServiceError> {
+            Err(SecuritySchemeServiceError::InternalError(
+                "Not implemented".to_string(),
+            ))
+        }
+
         async fn create(
             &self,
             _namespace: &Namespace,
@@ -912,6 +1426,69 @@ mod tests {
                 "Not implemented".to_string(),
             ))
         }
+
+        async fn create_jwt(
+            &self,
+            _namespace: &Namespace,
+            _security_scheme: &JwtSecurityScheme,
+        ) -> Result<JwtSecurityScheme, SecuritySchemeServiceError> {
+            Err(SecuritySchemeServiceError::InternalError(
+                "Not implemented".to_string(),
+            ))
+        }
+
+        async fn create_api_key(
+            &self,
+            _namespace: &Namespace,
+            _security_scheme: &ApiKeySecurityScheme,
+        ) -> Result<ApiKeySecurityScheme, SecuritySchemeServiceError> {
+            Err(SecuritySchemeServiceError::InternalError(
+                "Not implemented".to_string(),
+            ))
+        }
+
+        async fn create_hmac(
+            &self,
+            _namespace: &Namespace,
+            _security_scheme: &HmacSecurityScheme,
+            _secret: &str,
+        ) -> Result<HmacSecurityScheme, SecuritySchemeServiceError> {
+            Err(SecuritySchemeServiceError::InternalError(
+                "Not implemented".to_string(),
+            ))
+        }
+    }
+
+    #[test]
+    async fn response_cache_is_rejected_on_secured_routes() {
+        let security_scheme_service: Arc<
+            dyn SecuritySchemeService<DefaultNamespace> + Send + Sync,
+        > = Arc::new(TestSecuritySchemeService);
+
+        let mut yaml = get_api_spec("/foo", "\"shopping-cart\"", "${ {status: 200} }");
+        let route = &mut yaml["routes"][0];
+        route["security"] = serde_yaml::Value::String("openid".to_string());
+        route["response_cache"] = serde_yaml::from_str("ttlSeconds: 30").unwrap();
+
+        let api_http_definition_request: api::HttpApiDefinitionRequest =
+            serde_yaml::from_value(yaml).unwrap();
+        let core_http_definition_request: HttpApiDefinitionRequest =
+            api_http_definition_request.try_into().unwrap();
+
+        let result = HttpApiDefinition::from_http_api_definition_request(
+            &DefaultNamespace(),
+            core_http_definition_request,
+            Utc::now(),
+            &security_scheme_service,
+        )
+        .await;
+
+        match result {
+            Err(ApiDefinitionError::ValidationError(errors)) => {
+                assert!(errors.errors[0].contains("secured routes cannot be cached"))
+            }
+            other => panic!("Expected a validation error, got {other:?}"),
+        }
     }
 
     #[test]
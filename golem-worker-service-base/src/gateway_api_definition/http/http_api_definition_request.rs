@@ -15,7 +15,11 @@
 use crate::gateway_api_definition::http::{AllPathPatterns, MethodPattern, Route};
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
 use crate::gateway_binding::GatewayBinding;
-use crate::gateway_middleware::HttpCors;
+use crate::gateway_middleware::{
+    HttpBodyLimits, HttpCompression, HttpConcurrencyLimit, HttpCors, HttpMiddlewareConditions,
+    HttpRateLimit, HttpResponseCache, HttpTransforms,
+};
+use crate::gateway_rib_compiler::TaintAnalysisPolicy;
 use crate::gateway_security::SecuritySchemeReference;
 
 // HttpApiDefinitionRequest corresponds to the user facing http api definition.
@@ -28,6 +32,8 @@ pub struct HttpApiDefinitionRequest {
     pub version: ApiVersion,
     pub routes: Vec<RouteRequest>,
     pub draft: bool,
+    // Applied to the findings of the taint analysis of the scripts when the definition is compiled
+    pub taint_policy: TaintAnalysisPolicy,
 }
 
 // In a RouteRequest, security is defined at the outer level
@@ -42,24 +48,74 @@ pub struct RouteRequest {
     pub binding: GatewayBinding,
     pub cors: Option<HttpCors>,
     pub security: Option<SecuritySchemeReference>,
+    pub concurrency_limit: Option<HttpConcurrencyLimit>,
+    pub rate_limit: Option<HttpRateLimit>,
+    pub body_limits: Option<HttpBodyLimits>,
+    pub response_cache: Option<HttpResponseCache>,
+    pub transforms: Option<HttpTransforms>,
+    pub conditions: Option<HttpMiddlewareConditions>,
+    pub compression: Option<HttpCompression>,
 }
 
 impl From<Route> for RouteRequest {
     fn from(value: Route) -> Self {
-        let security_middleware = value
+        let security = value
+            .middlewares
+            .as_ref()
+            .and_then(|x| x.get_security_scheme_reference());
+
+        let cors_middleware = value
+            .middlewares
+            .clone()
+            .and_then(|x| x.get_cors_middleware());
+
+        let concurrency_limit_middleware = value
+            .middlewares
+            .clone()
+            .and_then(|x| x.get_concurrency_limit_middleware());
+
+        let rate_limit_middleware = value
+            .middlewares
+            .clone()
+            .and_then(|x| x.get_rate_limit_middleware());
+
+        let body_limits_middleware = value
             .middlewares
             .clone()
-            .and_then(|x| x.get_http_authentication_middleware());
+            .and_then(|x| x.get_body_limits_middleware());
 
-        let cors_middleware = value.middlewares.and_then(|x| x.get_cors_middleware());
+        let response_cache_middleware = value
+            .middlewares
+            .clone()
+            .and_then(|x| x.get_response_cache_middleware());
+
+        let transforms_middleware = value
+            .middlewares
+            .clone()
+            .and_then(|x| x.get_transforms_middleware());
+
+        let conditions_middleware = value
+            .middlewares
+            .clone()
+            .and_then(|x| x.get_conditions_middleware());
+
+        let compression_middleware = value
+            .middlewares
+            .and_then(|x| x.get_compression_middleware());
 
         RouteRequest {
             method: value.method,
             path: value.path,
             binding: value.binding,
-            security: security_middleware
-                .map(|x| SecuritySchemeReference::from(x.security_scheme_with_metadata)),
+            security,
             cors: cors_middleware,
+            concurrency_limit: concurrency_limit_middleware,
+            rate_limit: rate_limit_middleware,
+            body_limits: body_limits_middleware,
+            response_cache: response_cache_middleware,
+            transforms: transforms_middleware,
+            conditions: conditions_middleware,
+            compression: compression_middleware,
         }
     }
 }
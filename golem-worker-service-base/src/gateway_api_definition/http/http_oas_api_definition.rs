@@ -14,6 +14,7 @@
 
 use crate::gateway_api_definition::http::HttpApiDefinitionRequest;
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use golem_service_base::model::VersionedComponentId;
 use internal::*;
 use openapiv3::OpenAPI;
 use poem_openapi::registry::{MetaSchema, MetaSchemaRef};
@@ -23,6 +24,17 @@ use std::borrow::Cow;
 
 pub struct OpenApiHttpApiDefinitionRequest(pub OpenAPI);
 
+/// Defaults used to generate worker bindings for the operations of an OpenAPI spec
+/// which have no Golem binding extension
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibBindingStubs {
+    pub component_id: VersionedComponentId,
+    /// Rib expression of the worker name, such as `"cart-${request.path.user-id}"`
+    pub worker_name: Option<String>,
+    /// Interface the called functions are exported from, such as `golem:it/api`
+    pub interface: Option<String>,
+}
+
 impl OpenApiHttpApiDefinitionRequest {
     pub fn to_http_api_definition_request(&self) -> Result<HttpApiDefinitionRequest, String> {
         let open_api = &self.0;
@@ -38,7 +50,7 @@ impl OpenApiHttpApiDefinitionRequest {
 
         let security = get_global_security(open_api);
 
-        let routes = get_routes(&open_api.paths)?;
+        let routes = get_routes(open_api, None)?;
 
         Ok(HttpApiDefinitionRequest {
             id: api_definition_id,
@@ -46,6 +58,52 @@ impl OpenApiHttpApiDefinitionRequest {
             routes,
             draft: true,
             security,
+            taint_policy: get_taint_policy(open_api)?,
+        })
+    }
+
+    /// Converts any OpenAPI spec to an API definition, generating a templated Rib binding for
+    /// every operation without a Golem binding extension.
+    ///
+    /// The generated bindings call the function named after the operation id, passing the path,
+    /// query, header and cookie parameters (and the request body, if any) as arguments. They are
+    /// meant as a starting point, and need to be adjusted to the actual signature of the exported
+    /// functions. If the Golem id and version extensions are missing, the definition id is
+    /// derived from the title of the spec and the version is taken from its info section.
+    pub fn to_http_api_definition_request_with_stubs(
+        &self,
+        stubs: &RibBindingStubs,
+    ) -> Result<HttpApiDefinitionRequest, String> {
+        let open_api = &self.0;
+
+        let api_definition_id = ApiDefinitionId(
+            get_root_extension_str(open_api, GOLEM_API_DEFINITION_ID_EXTENSION)
+                .unwrap_or_else(|_| to_kebab_case(&open_api.info.title)),
+        );
+
+        let api_definition_version = ApiVersion(
+            get_root_extension_str(open_api, GOLEM_API_DEFINITION_VERSION)
+                .unwrap_or_else(|_| open_api.info.version.clone()),
+        );
+
+        if api_definition_id.0.is_empty() {
+            return Err(format!(
+                "{} not found in the open API spec, and the title is empty",
+                GOLEM_API_DEFINITION_ID_EXTENSION
+            ));
+        }
+
+        let security = get_global_security(open_api);
+
+        let routes = get_routes(open_api, Some(stubs))?;
+
+        Ok(HttpApiDefinitionRequest {
+            id: api_definition_id,
+            version: api_definition_version,
+            routes,
+            draft: true,
+            security,
+            taint_policy: get_taint_policy(open_api)?,
         })
     }
 }
@@ -53,7 +111,7 @@ impl OpenApiHttpApiDefinitionRequest {
 impl ParseFromJSON for OpenApiHttpApiDefinitionRequest {
     fn parse_from_json(value: Option<serde_json::Value>) -> ParseResult<Self> {
         match value {
-            Some(value) => match serde_json::from_value::<openapiv3::OpenAPI>(value) {
+            Some(value) => match parse_open_api(value) {
                 Ok(openapi) => Ok(OpenApiHttpApiDefinitionRequest(openapi)),
                 Err(e) => Err(ParseError::<Self>::custom(format!(
                     "Failed to parse OpenAPI: {}",
@@ -71,7 +129,7 @@ impl ParseFromJSON for OpenApiHttpApiDefinitionRequest {
 impl ParseFromYAML for OpenApiHttpApiDefinitionRequest {
     fn parse_from_yaml(value: Option<Value>) -> ParseResult<Self> {
         match value {
-            Some(value) => match serde_json::from_value::<openapiv3::OpenAPI>(value) {
+            Some(value) => match parse_open_api(value) {
                 Ok(openapi) => Ok(OpenApiHttpApiDefinitionRequest(openapi)),
                 Err(e) => Err(ParseError::<Self>::custom(format!(
                     "Failed to parse OpenAPI: {}",
@@ -118,19 +176,32 @@ impl poem_openapi::types::Type for OpenApiHttpApiDefinitionRequest {
 
 mod internal {
     use crate::gateway_api_definition::http::{AllPathPatterns, MethodPattern, RouteRequest};
+    use base64::Engine;
     use golem_common::model::{ComponentId, GatewayBindingType};
-    use openapiv3::{OpenAPI, Operation, Paths, ReferenceOr};
+    use openapiv3::{OpenAPI, Operation, Parameter, ReferenceOr};
     use rib::Expr;
     use serde_json::Value;
 
     use crate::gateway_binding::{
-        GatewayBinding, HttpHandlerBinding, ResponseMapping, StaticBinding, WorkerBinding,
+        ContentNegotiation, ErrorCaseMapping, ErrorMapping, ErrorResponseShape, GatewayBinding,
+        GrpcBinding, HttpHandlerBinding, HttpProxyBinding, Pagination, ResponseMapping,
+        ResponseMediaType, StaticBinding, TrafficMirror, WorkerBinding, WorkerBootstrap,
+        WorkerPerUser,
+    };
+    use crate::gateway_middleware::{
+        ConditionalMiddleware, CorsPreflightExpr, HttpBodyLimits, HttpCompression,
+        HttpConcurrencyLimit, HttpCors, HttpMiddlewareConditions, HttpRateLimit, HttpResponseCache,
+        HttpTransforms,
     };
-    use crate::gateway_middleware::{CorsPreflightExpr, HttpCors};
+    use crate::gateway_rib_compiler::TaintAnalysisPolicy;
     use crate::gateway_security::{SecuritySchemeIdentifier, SecuritySchemeReference};
     use golem_service_base::model::VersionedComponentId;
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
     use uuid::Uuid;
 
+    use super::RibBindingStubs;
+
     pub(crate) const GOLEM_API_DEFINITION_ID_EXTENSION: &str = "x-golem-api-definition-id";
     pub(crate) const GOLEM_API_DEFINITION_VERSION: &str = "x-golem-api-definition-version";
 
@@ -139,6 +210,8 @@ mod internal {
 
     pub(crate) const GOLEM_API_GATEWAY_BINDING: &str = "x-golem-api-gateway-binding";
 
+    pub(crate) const GOLEM_TAINT_POLICY_EXTENSION: &str = "x-golem-taint-policy";
+
     pub(crate) fn get_global_security(open_api: &OpenAPI) -> Option<Vec<SecuritySchemeReference>> {
         open_api.security.as_ref().and_then(|requirements| {
             let global_security: Vec<_> = requirements
@@ -168,6 +241,17 @@ mod internal {
             .map(|x| x.to_string())
     }
 
+    // The taint policy is optional, `warn` being the default
+    pub(crate) fn get_taint_policy(open_api: &OpenAPI) -> Result<TaintAnalysisPolicy, String> {
+        match get_root_extension_value(open_api, GOLEM_TAINT_POLICY_EXTENSION) {
+            Some(_) => TaintAnalysisPolicy::from_str(&get_root_extension_str(
+                open_api,
+                GOLEM_TAINT_POLICY_EXTENSION,
+            )?),
+            None => Ok(TaintAnalysisPolicy::default()),
+        }
+    }
+
     pub(crate) fn get_root_extension_value(open_api: &OpenAPI, key_name: &str) -> Option<Value> {
         open_api
             .extensions
@@ -176,17 +260,40 @@ mod internal {
             .map(|(_, v)| v.clone())
     }
 
-    pub(crate) fn get_routes(paths: &Paths) -> Result<Vec<RouteRequest>, String> {
+    pub(crate) fn get_routes(
+        open_api: &OpenAPI,
+        stubs: Option<&RibBindingStubs>,
+    ) -> Result<Vec<RouteRequest>, String> {
         let mut routes: Vec<RouteRequest> = vec![];
 
-        for (path, path_item) in paths.iter() {
+        for (path, path_item) in open_api.paths.iter() {
             match path_item {
                 ReferenceOr::Item(item) => {
                     let path_pattern = get_path_pattern(path)?;
 
                     for (method, method_operation) in item.iter() {
-                        let route =
-                            get_route_from_path_item(method, method_operation, &path_pattern)?;
+                        let route = match stubs {
+                            Some(stubs)
+                                if method != "options"
+                                    && get_gateway_binding_extension(method_operation)
+                                        .is_none() =>
+                            {
+                                let parameters = get_parameters(
+                                    open_api,
+                                    &item.parameters,
+                                    &method_operation.parameters,
+                                )?;
+                                get_stub_route(
+                                    method,
+                                    path,
+                                    method_operation,
+                                    &parameters,
+                                    &path_pattern,
+                                    stubs,
+                                )?
+                            }
+                            _ => get_route_from_path_item(method, method_operation, &path_pattern)?,
+                        };
                         routes.push(route);
                     }
                 }
@@ -202,12 +309,8 @@ mod internal {
         Ok(routes)
     }
 
-    pub(crate) fn get_route_from_path_item(
-        method: &str,
-        method_operation: &Operation,
-        path_pattern: &AllPathPatterns,
-    ) -> Result<RouteRequest, String> {
-        let method_res = match method {
+    fn get_method(method: &str) -> Result<MethodPattern, String> {
+        match method {
             "get" => Ok(MethodPattern::Get),
             "post" => Ok(MethodPattern::Post),
             "put" => Ok(MethodPattern::Put),
@@ -217,10 +320,10 @@ mod internal {
             "patch" => Ok(MethodPattern::Patch),
             "trace" => Ok(MethodPattern::Trace),
             _ => Err("Other methods not supported".to_string()),
-        };
-
-        let method = method_res?;
+        }
+    }
 
+    fn get_security(method_operation: &Operation) -> Option<SecuritySchemeReference> {
         let security = method_operation
             .security
             .clone()
@@ -230,19 +333,300 @@ mod internal {
         // Multiple security schemes to be supported later
         let security_name = security.and_then(|x| x.first().map(|x| x.0.clone()));
 
-        let security = security_name.map(|x| SecuritySchemeReference {
+        security_name.map(|x| SecuritySchemeReference {
             security_scheme_identifier: SecuritySchemeIdentifier::new(x),
-        });
+        })
+    }
 
-        let worker_gateway_info_optional = method_operation
+    fn get_gateway_binding_extension(method_operation: &Operation) -> Option<&Value> {
+        method_operation
             .extensions
             // TO keep backward compatibility with the old extension
             .get(GOLEM_WORKER_GATEWAY_EXTENSION_LEGACY)
-            .or(method_operation.extensions.get(GOLEM_API_GATEWAY_BINDING));
+            .or(method_operation.extensions.get(GOLEM_API_GATEWAY_BINDING))
+    }
+
+    /// Parameters of an operation, including the ones inherited from its path item.
+    /// Operation level parameters override path level ones with the same name and location.
+    pub(crate) fn get_parameters(
+        open_api: &OpenAPI,
+        path_parameters: &[ReferenceOr<Parameter>],
+        operation_parameters: &[ReferenceOr<Parameter>],
+    ) -> Result<Vec<Parameter>, String> {
+        let mut parameters: Vec<Parameter> = vec![];
+
+        for parameter in path_parameters.iter().chain(operation_parameters) {
+            let parameter = resolve_parameter(open_api, parameter)?;
+            let existing = parameters.iter_mut().find(|existing| {
+                existing.parameter_data_ref().name == parameter.parameter_data_ref().name
+                    && std::mem::discriminant(&**existing) == std::mem::discriminant(&parameter)
+            });
+
+            match existing {
+                Some(existing) => *existing = parameter,
+                None => parameters.push(parameter),
+            }
+        }
+
+        Ok(parameters)
+    }
+
+    fn resolve_parameter(
+        open_api: &OpenAPI,
+        parameter: &ReferenceOr<Parameter>,
+    ) -> Result<Parameter, String> {
+        match parameter {
+            ReferenceOr::Item(parameter) => Ok(parameter.clone()),
+            ReferenceOr::Reference { reference } => {
+                let name = reference
+                    .strip_prefix("#/components/parameters/")
+                    .ok_or(format!("Unsupported parameter reference {}", reference))?;
+
+                let resolved = open_api
+                    .components
+                    .as_ref()
+                    .and_then(|components| components.parameters.get(name))
+                    .ok_or(format!("Parameter reference {} not found", reference))?;
+
+                match resolved {
+                    ReferenceOr::Item(parameter) => Ok(parameter.clone()),
+                    ReferenceOr::Reference { .. } => resolve_parameter(open_api, resolved),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn get_stub_route(
+        method: &str,
+        path: &str,
+        method_operation: &Operation,
+        parameters: &[Parameter],
+        path_pattern: &AllPathPatterns,
+        stubs: &RibBindingStubs,
+    ) -> Result<RouteRequest, String> {
+        let function_name = match &method_operation.operation_id {
+            Some(operation_id) => to_kebab_case(operation_id),
+            None => to_kebab_case(&format!("{} {}", method, path)),
+        };
+
+        let function = match &stubs.interface {
+            Some(interface) => format!("{}.{{{}}}", interface, function_name),
+            None => function_name,
+        };
+
+        let arguments = get_stub_arguments(parameters, method_operation.request_body.is_some());
+
+        let response = format!(
+            "let result = {}({});\n{{status: 200u64, body: result}}",
+            function,
+            arguments.join(", ")
+        );
+
+        let binding = WorkerBinding {
+            worker_name: stubs
+                .worker_name
+                .as_ref()
+                .map(|worker_name| rib::from_string(worker_name).map_err(|err| err.to_string()))
+                .transpose()?,
+            component_id: stubs.component_id.clone(),
+            idempotency_key: None,
+            response_mapping: ResponseMapping(
+                rib::from_string(response).map_err(|err| err.to_string())?,
+            ),
+            invocation_context: None,
+            traffic_mirror: None,
+            worker_per_user: None,
+            bootstrap: None,
+            pagination: None,
+            content_negotiation: None,
+            error_mapping: None,
+            static_worker_names: false,
+        };
+
+        Ok(RouteRequest {
+            method: get_method(method)?,
+            path: path_pattern.clone(),
+            binding: GatewayBinding::Default(binding),
+            security: get_security(method_operation),
+            cors: None,
+            concurrency_limit: None,
+            rate_limit: None,
+            body_limits: None,
+            response_cache: None,
+            transforms: None,
+            conditions: None,
+            compression: None,
+        })
+    }
+
+    /// Rib expressions passing the request parameters to the called function: path parameters
+    /// first, followed by query, header and cookie parameters, and finally the request body
+    pub(crate) fn get_stub_arguments(parameters: &[Parameter], has_body: bool) -> Vec<String> {
+        let by_location = |location: &str| {
+            parameters
+                .iter()
+                .filter_map(|parameter| {
+                    let parameter_location = match parameter {
+                        Parameter::Path { .. } => "path",
+                        Parameter::Query { .. } => "query",
+                        Parameter::Header { .. } => "headers",
+                        Parameter::Cookie { .. } => "cookies",
+                    };
+                    (parameter_location == location).then(|| {
+                        format!(
+                            "request.{}.{}",
+                            location,
+                            parameter.parameter_data_ref().name
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut arguments = vec![];
+        for location in ["path", "query", "headers", "cookies"] {
+            arguments.extend(by_location(location));
+        }
+        if has_body {
+            arguments.push("request.body".to_string());
+        }
+        arguments
+    }
+
+    /// Converts operation ids such as `getCartContents` or `get_cart_contents` to the kebab-case
+    /// naming of exported functions (`get-cart-contents`)
+    pub(crate) fn to_kebab_case(name: &str) -> String {
+        let mut result = String::new();
+        let mut previous_lowercase = false;
+
+        for c in name.chars() {
+            if c.is_ascii_alphanumeric() {
+                if c.is_ascii_uppercase() && previous_lowercase {
+                    result.push('-');
+                }
+                previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+                result.push(c.to_ascii_lowercase());
+            } else {
+                if !result.is_empty() && !result.ends_with('-') {
+                    result.push('-');
+                }
+                previous_lowercase = false;
+            }
+        }
+
+        result.trim_end_matches('-').to_string()
+    }
+
+    /// Parses an OpenAPI 3.0 or 3.1 document.
+    ///
+    /// 3.1 documents are first rewritten to their 3.0 equivalent: type arrays containing `null`
+    /// become nullable schemas, `const` becomes a single valued `enum`, numeric exclusive bounds
+    /// become boolean ones and 3.1 only root fields (such as webhooks) are dropped.
+    pub(crate) fn parse_open_api(mut value: Value) -> Result<OpenAPI, serde_json::Error> {
+        let is_3_1 = value
+            .get("openapi")
+            .and_then(|version| version.as_str())
+            .is_some_and(|version| version.starts_with("3.1"));
+
+        if is_3_1 {
+            if let Value::Object(root) = &mut value {
+                root.insert("openapi".to_string(), Value::String("3.0.3".to_string()));
+                root.remove("webhooks");
+                root.remove("jsonSchemaDialect");
+                root.entry("paths")
+                    .or_insert_with(|| Value::Object(Default::default()));
+            }
+            downgrade_json_schema(&mut value);
+        }
+
+        serde_json::from_value(value)
+    }
+
+    fn downgrade_json_schema(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::Array(types)) = map.get("type").cloned() {
+                    let non_null = types
+                        .iter()
+                        .filter(|t| t.as_str() != Some("null"))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if non_null.len() < types.len() {
+                        map.insert("nullable".to_string(), Value::Bool(true));
+                    }
+                    match non_null.as_slice() {
+                        [single] => {
+                            map.insert("type".to_string(), single.clone());
+                        }
+                        _ => {
+                            map.remove("type");
+                        }
+                    }
+                }
+
+                if let Some(constant) = map.remove("const") {
+                    map.insert("enum".to_string(), Value::Array(vec![constant]));
+                }
+
+                for (bound, exclusive) in [
+                    ("minimum", "exclusiveMinimum"),
+                    ("maximum", "exclusiveMaximum"),
+                ] {
+                    if let Some(limit) = map.get(exclusive).filter(|v| v.is_number()).cloned() {
+                        map.insert(bound.to_string(), limit);
+                        map.insert(exclusive.to_string(), Value::Bool(true));
+                    }
+                }
+
+                if let Some(Value::Array(examples)) = map.get("examples").cloned() {
+                    map.remove("examples");
+                    if let Some(example) = examples.into_iter().next() {
+                        map.insert("example".to_string(), example);
+                    }
+                }
+
+                for (key, child) in map.iter_mut() {
+                    match (key.as_str(), child) {
+                        // maps keyed by names, which must not be mistaken for schema keywords
+                        ("properties" | "patternProperties" | "schemas", Value::Object(named)) => {
+                            for schema in named.values_mut() {
+                                downgrade_json_schema(schema);
+                            }
+                        }
+                        (_, child) => downgrade_json_schema(child),
+                    }
+                }
+            }
+            Value::Array(values) => {
+                for child in values {
+                    downgrade_json_schema(child);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn get_route_from_path_item(
+        method: &str,
+        method_operation: &Operation,
+        path_pattern: &AllPathPatterns,
+    ) -> Result<RouteRequest, String> {
+        let method = get_method(method)?;
+
+        let security = get_security(method_operation);
+
+        let worker_gateway_info_optional = get_gateway_binding_extension(method_operation);
 
         match worker_gateway_info_optional {
             Some(worker_gateway_info) => {
                 let binding_type = get_binding_type(worker_gateway_info)?;
+                let concurrency_limit = get_concurrency_limit(worker_gateway_info)?;
+                let rate_limit = get_rate_limit(worker_gateway_info)?;
+                let body_limits = get_body_limits(worker_gateway_info)?;
+                let response_cache = get_response_cache(worker_gateway_info)?;
+                let transforms = get_transforms(worker_gateway_info)?;
+                let conditions = get_conditions(worker_gateway_info)?;
+                let compression = get_compression(worker_gateway_info)?;
 
                 match (&binding_type, &method) {
                     (GatewayBindingType::CorsPreflight, MethodPattern::Options) => {
@@ -253,7 +637,14 @@ mod internal {
                             path: path_pattern.clone(),
                             binding: GatewayBinding::static_binding(binding),
                             security,
-                            cors: None
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
                         })
                     }
 
@@ -265,7 +656,14 @@ mod internal {
                             method,
                             binding: GatewayBinding::Default(binding),
                             security,
-                            cors: None
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
                         })
                     }
                     (GatewayBindingType::FileServer, _) => {
@@ -276,7 +674,14 @@ mod internal {
                             method,
                             binding: GatewayBinding::Default(binding),
                             security,
-                            cors: None
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
                         })
                     }
                     (GatewayBindingType::HttpHandler, _) => {
@@ -287,9 +692,108 @@ mod internal {
                             method,
                             binding: GatewayBinding::HttpHandler(binding),
                             security,
-                            cors: None
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
+                        })
+                    }
+                    (GatewayBindingType::WebSocket, MethodPattern::Get) => {
+                        let binding = get_worker_binding(worker_gateway_info)?;
+
+                        Ok(RouteRequest {
+                            path: path_pattern.clone(),
+                            method,
+                            binding: GatewayBinding::WebSocket(binding),
+                            security,
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
                         })
                     }
+                    (GatewayBindingType::WebSocket, method) => {
+                        Err(format!("web-socket binding type is supported only for 'get' method, but found method '{}'", method))
+                    }
+                    (GatewayBindingType::ServerSentEvents, MethodPattern::Get) => {
+                        let binding = get_worker_binding(worker_gateway_info)?;
+
+                        Ok(RouteRequest {
+                            path: path_pattern.clone(),
+                            method,
+                            binding: GatewayBinding::ServerSentEvents(binding),
+                            security,
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
+                        })
+                    }
+                    (GatewayBindingType::ServerSentEvents, method) => {
+                        Err(format!("server-sent-events binding type is supported only for 'get' method, but found method '{}'", method))
+                    }
+                    (GatewayBindingType::Grpc, _) => {
+                        let binding = get_grpc_binding(worker_gateway_info)?;
+
+                        Ok(RouteRequest {
+                            path: path_pattern.clone(),
+                            method,
+                            binding: GatewayBinding::Grpc(binding),
+                            security,
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
+                        })
+                    }
+                    (GatewayBindingType::HttpProxy, _) => {
+                        let binding = get_http_proxy_binding(worker_gateway_info)?;
+
+                        Ok(RouteRequest {
+                            path: path_pattern.clone(),
+                            method,
+                            binding: GatewayBinding::HttpProxy(binding),
+                            security,
+                            cors: None,
+                            concurrency_limit,
+                            rate_limit,
+                            body_limits,
+                            response_cache: response_cache.clone(),
+                            transforms: transforms.clone(),
+                            conditions: conditions.clone(),
+                            compression: compression.clone(),
+                        })
+                    }
+                    (GatewayBindingType::StatusPage, _) => Ok(RouteRequest {
+                        path: path_pattern.clone(),
+                        method,
+                        binding: GatewayBinding::static_binding(StaticBinding::StatusPage),
+                        security,
+                        cors: None,
+                        concurrency_limit,
+                        rate_limit,
+                        body_limits,
+                        response_cache: response_cache.clone(),
+                        transforms: transforms.clone(),
+                        conditions: conditions.clone(),
+                        compression: compression.clone(),
+                    }),
                     (GatewayBindingType::CorsPreflight, method) => {
                         Err(format!("cors-preflight binding type is supported only for 'options' method, but found method '{}'", method))
                     }
@@ -306,6 +810,13 @@ mod internal {
                         binding: GatewayBinding::static_binding(binding),
                         security,
                         cors: None,
+                        concurrency_limit: None,
+                        rate_limit: None,
+                        body_limits: None,
+                        response_cache: None,
+                        transforms: None,
+                        conditions: None,
+                        compression: None,
                     })
                 } else {
                     Err(format!(
@@ -326,6 +837,13 @@ mod internal {
             idempotency_key: get_idempotency_key(gateway_binding_value)?,
             response_mapping: get_response_mapping(gateway_binding_value)?,
             invocation_context: get_invocation_context(gateway_binding_value)?,
+            traffic_mirror: get_traffic_mirror(gateway_binding_value)?,
+            worker_per_user: get_worker_per_user(gateway_binding_value)?,
+            bootstrap: get_bootstrap(gateway_binding_value)?,
+            pagination: get_pagination(gateway_binding_value)?,
+            content_negotiation: get_content_negotiation(gateway_binding_value)?,
+            error_mapping: get_error_mapping(gateway_binding_value)?,
+            static_worker_names: get_static_worker_names(gateway_binding_value)?,
         };
 
         Ok(binding)
@@ -343,6 +861,61 @@ mod internal {
         Ok(binding)
     }
 
+    pub(crate) fn get_grpc_binding(gateway_binding_value: &Value) -> Result<GrpcBinding, String> {
+        let get_str = |key: &str| {
+            gateway_binding_value
+                .get(key)
+                .ok_or(format!("No {} found", key))?
+                .as_str()
+                .map(|value| value.to_string())
+                .ok_or(format!("{} is not a string", key))
+        };
+
+        // The descriptor set is embedded as base64, as the spec itself is JSON or YAML
+        let descriptor = base64::engine::general_purpose::STANDARD
+            .decode(get_str("grpc-descriptor")?)
+            .map_err(|err| format!("grpc-descriptor is not valid base64: {}", err))?;
+
+        Ok(GrpcBinding {
+            endpoint: get_str("grpc-endpoint")?,
+            service: get_str("grpc-service")?,
+            method: get_str("grpc-method")?,
+            descriptor,
+        })
+    }
+
+    pub(crate) fn get_http_proxy_binding(
+        gateway_binding_value: &Value,
+    ) -> Result<HttpProxyBinding, String> {
+        let upstream = gateway_binding_value
+            .get("upstream")
+            .ok_or("No upstream found")?
+            .as_str()
+            .ok_or("upstream is not a string")?
+            .to_string();
+
+        let request_mapping = gateway_binding_value
+            .get("request-mapping")
+            .map(|value| {
+                let rib_expr_text = value
+                    .as_str()
+                    .ok_or("request-mapping is not a Rib expression string")?;
+                rib::from_string(rib_expr_text).map_err(|err| err.to_string())
+            })
+            .transpose()?;
+
+        let timeout_ms = gateway_binding_value
+            .get("timeout-ms")
+            .map(|value| value.as_u64().ok_or("timeout-ms is not a number"))
+            .transpose()?;
+
+        Ok(HttpProxyBinding {
+            upstream,
+            request_mapping,
+            timeout_ms,
+        })
+    }
+
     pub(crate) fn get_cors_static_binding(
         worker_gateway_info: &Value,
     ) -> Result<StaticBinding, String> {
@@ -465,6 +1038,489 @@ mod internal {
         }
     }
 
+    // The traffic mirror is an object with the same component-id and component-version keys as the binding,
+    // along with the percentage of the requests to mirror and an optional log-divergence flag
+    pub(crate) fn get_traffic_mirror(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<TrafficMirror>, String> {
+        if let Some(traffic_mirror) = gateway_binding_value.get("traffic-mirror") {
+            let percentage = traffic_mirror
+                .get("percentage")
+                .ok_or("No percentage found in traffic-mirror")?
+                .as_u64()
+                .ok_or("traffic-mirror percentage is not a u64")?;
+
+            let log_divergence = traffic_mirror
+                .get("log-divergence")
+                .map(|value| {
+                    value
+                        .as_bool()
+                        .ok_or("traffic-mirror log-divergence is not a boolean")
+                })
+                .transpose()?
+                .unwrap_or(false);
+
+            let ignored_fields = traffic_mirror
+                .get("ignored-fields")
+                .map(|value| {
+                    value
+                        .as_array()
+                        .ok_or("traffic-mirror ignored-fields is not an array")?
+                        .iter()
+                        .map(|field| {
+                            field
+                                .as_str()
+                                .map(|field| field.to_string())
+                                .ok_or("traffic-mirror ignored-fields must be strings")
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?
+                .unwrap_or_default();
+
+            Ok(Some(TrafficMirror {
+                component_id: get_component_id(traffic_mirror)?,
+                percentage: u32::try_from(percentage).map_err(|err| err.to_string())?,
+                log_divergence,
+                ignored_fields,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Either `true`, deriving the worker name from the `sub` claim, or an object
+    // with the optional prefix of the worker names and claim to derive them from
+    pub(crate) fn get_worker_per_user(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<WorkerPerUser>, String> {
+        match gateway_binding_value.get("worker-per-user") {
+            None | Some(Value::Bool(false)) => Ok(None),
+            Some(Value::Bool(true)) => Ok(Some(WorkerPerUser {
+                prefix: None,
+                claim: None,
+            })),
+            Some(Value::Object(worker_per_user)) => {
+                let get_string = |key: &str| {
+                    worker_per_user
+                        .get(key)
+                        .map(|value| {
+                            value
+                                .as_str()
+                                .map(|value| value.to_string())
+                                .ok_or(format!("worker-per-user {} is not a string", key))
+                        })
+                        .transpose()
+                };
+
+                Ok(Some(WorkerPerUser {
+                    prefix: get_string("prefix")?,
+                    claim: get_string("claim")?,
+                }))
+            }
+            Some(_) => Err("worker-per-user is neither a boolean nor an object".to_string()),
+        }
+    }
+
+    // Either `true`, with the default limits, or an object with the optional
+    // `default-limit` and `max-limit` of the pages
+    pub(crate) fn get_pagination(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<Pagination>, String> {
+        match gateway_binding_value.get("pagination") {
+            None | Some(Value::Bool(false)) => Ok(None),
+            Some(Value::Bool(true)) => Ok(Some(Pagination {
+                default_limit: None,
+                max_limit: None,
+            })),
+            Some(Value::Object(pagination)) => {
+                let get_u32 = |key: &str| {
+                    pagination
+                        .get(key)
+                        .map(|value| {
+                            value
+                                .as_u64()
+                                .and_then(|value| u32::try_from(value).ok())
+                                .ok_or(format!("pagination {} is not a valid limit", key))
+                        })
+                        .transpose()
+                };
+
+                Ok(Some(Pagination {
+                    default_limit: get_u32("default-limit")?,
+                    max_limit: get_u32("max-limit")?,
+                }))
+            }
+            Some(_) => Err("pagination is neither a boolean nor an object".to_string()),
+        }
+    }
+
+    // An array of the media types of the responses, the first one being the default
+    pub(crate) fn get_content_negotiation(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<ContentNegotiation>, String> {
+        match gateway_binding_value.get("content-negotiation") {
+            None => Ok(None),
+            Some(Value::Array(media_types)) => {
+                let media_types = media_types
+                    .iter()
+                    .map(|media_type| {
+                        media_type
+                            .as_str()
+                            .ok_or("content-negotiation media type is not a string".to_string())
+                            .and_then(ResponseMediaType::from_str)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(Some(ContentNegotiation { media_types }))
+            }
+            Some(_) => Err("content-negotiation is not an array of media types".to_string()),
+        }
+    }
+
+    // Whether the worker names of the scripts are required to be known at compile time
+    pub(crate) fn get_static_worker_names(gateway_binding_value: &Value) -> Result<bool, String> {
+        match gateway_binding_value.get("static-worker-names") {
+            None => Ok(false),
+            Some(Value::Bool(static_worker_names)) => Ok(*static_worker_names),
+            Some(_) => Err("static-worker-names is not a boolean".to_string()),
+        }
+    }
+
+    // An object keyed by the error cases, with either the status of the case,
+    // or an object with its `status` and `shape`
+    pub(crate) fn get_error_mapping(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<ErrorMapping>, String> {
+        match gateway_binding_value.get("error-mapping") {
+            None => Ok(None),
+            Some(Value::Object(cases)) => {
+                let status = |case: &str, value: &Value| {
+                    value
+                        .as_u64()
+                        .and_then(|status| u16::try_from(status).ok())
+                        .ok_or(format!("error-mapping status of {} is not a status", case))
+                };
+
+                let cases = cases
+                    .iter()
+                    .map(|(case, value)| match value {
+                        Value::Object(mapping) => Ok(ErrorCaseMapping {
+                            case: case.clone(),
+                            status: status(case, mapping.get("status").unwrap_or(&Value::Null))?,
+                            shape: match mapping.get("shape") {
+                                None => ErrorResponseShape::default(),
+                                Some(shape) => shape
+                                    .as_str()
+                                    .ok_or(format!(
+                                        "error-mapping shape of {} is not a string",
+                                        case
+                                    ))
+                                    .and_then(ErrorResponseShape::from_str)?,
+                            },
+                        }),
+                        value => Ok(ErrorCaseMapping {
+                            case: case.clone(),
+                            status: status(case, value)?,
+                            shape: ErrorResponseShape::default(),
+                        }),
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+
+                Ok(Some(ErrorMapping { cases }))
+            }
+            Some(_) => Err("error-mapping is not an object of error cases".to_string()),
+        }
+    }
+
+    // An object with the Rib scripts of the args of the worker, as an array, and of its
+    // environment and config variables, as objects keyed by their names
+    pub(crate) fn get_bootstrap(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<WorkerBootstrap>, String> {
+        if let Some(bootstrap) = gateway_binding_value.get("bootstrap") {
+            let parse = |value: &Value| {
+                let expr = value
+                    .as_str()
+                    .ok_or("bootstrap values must be strings".to_string())?;
+                rib::from_string(expr).map_err(|err| err.to_string())
+            };
+
+            let args = bootstrap
+                .get("args")
+                .map(|args| {
+                    args.as_array()
+                        .ok_or("bootstrap args is not an array".to_string())?
+                        .iter()
+                        .map(parse)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?
+                .unwrap_or_default();
+
+            let vars = |key: &str| {
+                bootstrap
+                    .get(key)
+                    .map(|vars| {
+                        vars.as_object()
+                            .ok_or(format!("bootstrap {} is not an object", key))?
+                            .iter()
+                            .map(|(name, value)| Ok((name.clone(), parse(value)?)))
+                            .collect::<Result<BTreeMap<_, _>, String>>()
+                    })
+                    .transpose()
+                    .map(|vars| vars.unwrap_or_default())
+            };
+
+            Ok(Some(WorkerBootstrap {
+                args,
+                env: vars("env")?,
+                config: vars("config")?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn get_concurrency_limit(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<HttpConcurrencyLimit>, String> {
+        if let Some(concurrency_limit) = gateway_binding_value.get("concurrency-limit") {
+            let get_u64 = |key: &str| {
+                concurrency_limit
+                    .get(key)
+                    .map(|value| {
+                        value
+                            .as_u64()
+                            .ok_or(format!("concurrency-limit {} is not a u64", key))
+                    })
+                    .transpose()
+            };
+            let get_u32 = |key: &str| {
+                get_u64(key)?
+                    .map(|value| u32::try_from(value).map_err(|err| err.to_string()))
+                    .transpose()
+            };
+
+            let shed_body = concurrency_limit
+                .get("shed-body")
+                .map(|value| {
+                    value
+                        .as_str()
+                        .map(|value| value.to_string())
+                        .ok_or("concurrency-limit shed-body is not a string")
+                })
+                .transpose()?;
+
+            Ok(Some(HttpConcurrencyLimit {
+                max_in_flight: get_u32("max-in-flight")?
+                    .ok_or("No max-in-flight found in concurrency-limit")?,
+                max_queued: get_u32("max-queued")?.unwrap_or(0),
+                queue_timeout_ms: get_u64("queue-timeout-ms")?,
+                shed_status: get_u64("shed-status")?
+                    .map(|value| u16::try_from(value).map_err(|err| err.to_string()))
+                    .transpose()?,
+                shed_body,
+                retry_after: get_u64("retry-after")?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn get_rate_limit(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<HttpRateLimit>, String> {
+        if let Some(rate_limit) = gateway_binding_value.get("rate-limit") {
+            let capacity = rate_limit
+                .get("capacity")
+                .ok_or("No capacity found in rate-limit")?
+                .as_u64()
+                .ok_or("rate-limit capacity is not a u64")?;
+
+            let refill_interval_ms = rate_limit
+                .get("refill-interval-ms")
+                .ok_or("No refill-interval-ms found in rate-limit")?
+                .as_u64()
+                .ok_or("rate-limit refill-interval-ms is not a u64")?;
+
+            let key = rate_limit
+                .get("key")
+                .map(|value| {
+                    let rib_expr_text = value
+                        .as_str()
+                        .ok_or("rate-limit key is not a Rib expression string")?;
+                    rib::from_string(rib_expr_text).map_err(|err| err.to_string())
+                })
+                .transpose()?;
+
+            let capacity = u32::try_from(capacity).map_err(|err| err.to_string())?;
+
+            HttpRateLimit::new(capacity, refill_interval_ms, key).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn get_body_limits(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<HttpBodyLimits>, String> {
+        if let Some(body_limits) = gateway_binding_value.get("body-limits") {
+            let get_u64 = |key: &str| {
+                body_limits
+                    .get(key)
+                    .map(|value| {
+                        value
+                            .as_u64()
+                            .ok_or(format!("body-limits {} is not a u64", key))
+                    })
+                    .transpose()
+            };
+
+            Ok(Some(HttpBodyLimits {
+                max_request_body_bytes: get_u64("max-request-body-bytes")?,
+                max_response_body_bytes: get_u64("max-response-body-bytes")?,
+                read_timeout_ms: get_u64("read-timeout-ms")?,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn get_response_cache(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<HttpResponseCache>, String> {
+        if let Some(response_cache) = gateway_binding_value.get("response-cache") {
+            let ttl_seconds = response_cache
+                .get("ttl-seconds")
+                .ok_or("response-cache ttl-seconds is required")?
+                .as_u64()
+                .ok_or("response-cache ttl-seconds is not a u64")?;
+
+            let vary = match response_cache.get("vary") {
+                Some(vary) => vary
+                    .as_array()
+                    .ok_or("response-cache vary is not an array")?
+                    .iter()
+                    .map(|vary| {
+                        vary.as_str()
+                            .map(|vary| vary.to_string())
+                            .ok_or("response-cache vary is not an array of strings".to_string())
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => vec![],
+            };
+
+            Ok(Some(HttpResponseCache { ttl_seconds, vary }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn get_transforms(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<HttpTransforms>, String> {
+        if let Some(transforms) = gateway_binding_value.get("transforms") {
+            let scripts = |name: &str| match transforms.get(name) {
+                Some(scripts) => scripts
+                    .as_array()
+                    .ok_or(format!("transforms {} is not an array", name))?
+                    .iter()
+                    .map(|script| {
+                        let rib_expr_text = script.as_str().ok_or(format!(
+                            "transforms {} is not an array of Rib expression strings",
+                            name
+                        ))?;
+                        rib::from_string(rib_expr_text).map_err(|err| err.to_string())
+                    })
+                    .collect::<Result<Vec<_>, _>>(),
+                None => Ok(vec![]),
+            };
+
+            HttpTransforms::new(scripts("request")?, scripts("response")?).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn get_conditions(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<HttpMiddlewareConditions>, String> {
+        if let Some(conditions) = gateway_binding_value.get("conditions") {
+            let conditions = conditions
+                .as_object()
+                .ok_or("conditions is not an object of Rib expression strings")?
+                .iter()
+                .map(|(middleware, predicate)| {
+                    let middleware = ConditionalMiddleware::from_str(middleware)?;
+                    let rib_expr_text = predicate.as_str().ok_or(format!(
+                        "condition of the {} is not a Rib expression string",
+                        middleware
+                    ))?;
+                    let predicate =
+                        rib::from_string(rib_expr_text).map_err(|err| err.to_string())?;
+                    Ok((middleware, predicate))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            HttpMiddlewareConditions::new(conditions).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn get_compression(
+        gateway_binding_value: &Value,
+    ) -> Result<Option<HttpCompression>, String> {
+        if let Some(compression) = gateway_binding_value.get("compression") {
+            let strings = |key: &str| {
+                compression
+                    .get(key)
+                    .map(|values| {
+                        values
+                            .as_array()
+                            .ok_or(format!("compression {} is not an array", key))?
+                            .iter()
+                            .map(|value| {
+                                value.as_str().map(|value| value.to_string()).ok_or(format!(
+                                    "compression {} is not an array of strings",
+                                    key
+                                ))
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .transpose()
+            };
+
+            let defaults = HttpCompression::default();
+
+            let enabled = compression
+                .get("enabled")
+                .map(|enabled| enabled.as_bool().ok_or("compression enabled is not a bool"))
+                .transpose()?
+                .unwrap_or(defaults.enabled);
+
+            let min_size_bytes = compression
+                .get("min-size-bytes")
+                .map(|min_size| {
+                    min_size
+                        .as_u64()
+                        .ok_or("compression min-size-bytes is not a u64")
+                })
+                .transpose()?
+                .unwrap_or(defaults.min_size_bytes);
+
+            Ok(Some(HttpCompression {
+                enabled,
+                algorithms: strings("algorithms")?.unwrap_or(defaults.algorithms),
+                min_size_bytes,
+                content_types: strings("content-types")?.unwrap_or(defaults.content_types),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub(crate) fn get_path_pattern(path: &str) -> Result<AllPathPatterns, String> {
         AllPathPatterns::parse(path)
     }
@@ -540,6 +1596,143 @@ mod tests {
         assert_eq!(result, Ok(expected));
     }
 
+    #[test]
+    fn test_to_kebab_case() {
+        assert_eq!(to_kebab_case("getCartContents"), "get-cart-contents");
+        assert_eq!(to_kebab_case("get_cart_contents"), "get-cart-contents");
+        assert_eq!(to_kebab_case("get /{user-id}/cart"), "get-user-id-cart");
+        assert_eq!(to_kebab_case("Shopping Cart API"), "shopping-cart-api");
+    }
+
+    #[test]
+    fn test_open_api_3_1_with_binding_stubs() {
+        let spec = json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Shopping Cart", "version": "1.0.0" },
+            "jsonSchemaDialect": "https://spec.openapis.org/oas/3.1/dialect/base",
+            "paths": {
+                "/{user-id}/cart": {
+                    "parameters": [
+                        { "name": "user-id", "in": "path", "required": true, "schema": { "type": "string" } }
+                    ],
+                    "get": {
+                        "operationId": "getCartContents",
+                        "parameters": [
+                            { "$ref": "#/components/parameters/Limit" }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "post": {
+                        "operationId": "addItem",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "type": ["object", "null"], "properties": { "const": { "const": 1 } } }
+                                }
+                            }
+                        },
+                        "responses": { "200": { "description": "OK" } }
+                    },
+                    "delete": {
+                        "x-golem-api-gateway-binding": {
+                            "component-id": "2696abdc-df3a-4771-8215-d6af7aa4c408",
+                            "component-version": 1,
+                            "response": "{status: 204u64}"
+                        },
+                        "responses": { "204": { "description": "Deleted" } }
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "Limit": { "name": "limit", "in": "query", "schema": { "type": ["integer", "null"], "exclusiveMinimum": 0 } }
+                }
+            }
+        });
+
+        let open_api = parse_open_api(spec).unwrap();
+        let stubs = RibBindingStubs {
+            component_id: VersionedComponentId {
+                component_id: golem_common::model::ComponentId::new_v4(),
+                version: 0,
+            },
+            worker_name: Some("\"cart-${request.path.user-id}\"".to_string()),
+            interface: Some("golem:it/api".to_string()),
+        };
+
+        let result = OpenApiHttpApiDefinitionRequest(open_api)
+            .to_http_api_definition_request_with_stubs(&stubs)
+            .unwrap();
+
+        assert_eq!(result.id, ApiDefinitionId("shopping-cart".to_string()));
+        assert_eq!(result.version, ApiVersion("1.0.0".to_string()));
+
+        let responses = result
+            .routes
+            .iter()
+            .map(|route| match &route.binding {
+                GatewayBinding::Default(binding) => (
+                    route.method.clone(),
+                    binding.component_id.clone(),
+                    binding.response_mapping.0.clone(),
+                ),
+                _ => panic!("Unexpected binding"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            responses,
+            vec![
+                (
+                    MethodPattern::Get,
+                    stubs.component_id.clone(),
+                    rib::from_string(
+                        "let result = golem:it/api.{get-cart-contents}(request.path.user-id, request.query.limit);\n{status: 200u64, body: result}"
+                    )
+                    .unwrap()
+                ),
+                (
+                    MethodPattern::Post,
+                    stubs.component_id.clone(),
+                    rib::from_string(
+                        "let result = golem:it/api.{add-item}(request.path.user-id, request.body);\n{status: 200u64, body: result}"
+                    )
+                    .unwrap()
+                ),
+                (
+                    MethodPattern::Delete,
+                    VersionedComponentId {
+                        component_id: golem_common::model::ComponentId(
+                            uuid::Uuid::parse_str("2696abdc-df3a-4771-8215-d6af7aa4c408").unwrap()
+                        ),
+                        version: 1,
+                    },
+                    rib::from_string("{status: 204u64}").unwrap()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_api_without_extension_requires_binding_stubs() {
+        let spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Shopping Cart", "version": "1.0.0" },
+            "x-golem-api-definition-id": "shopping-cart",
+            "x-golem-api-definition-version": "0.1.0",
+            "paths": {
+                "/cart": {
+                    "get": { "responses": { "200": { "description": "OK" } } }
+                }
+            }
+        });
+
+        let result = OpenApiHttpApiDefinitionRequest(parse_open_api(spec).unwrap())
+            .to_http_api_definition_request();
+
+        assert!(result.is_err());
+    }
+
     fn expected_route_with_cors_preflight_binding_default(
         path_pattern: &AllPathPatterns,
     ) -> RouteRequest {
@@ -551,6 +1744,13 @@ mod tests {
             )),
             security: None,
             cors: None,
+            concurrency_limit: None,
+            rate_limit: None,
+            body_limits: None,
+            response_cache: None,
+            transforms: None,
+            conditions: None,
+            compression: None,
         }
     }
 
@@ -563,6 +1763,13 @@ mod tests {
             binding: GatewayBinding::static_binding(StaticBinding::from_http_cors(cors_preflight)),
             security: None,
             cors: None,
+            concurrency_limit: None,
+            rate_limit: None,
+            body_limits: None,
+            response_cache: None,
+            transforms: None,
+            conditions: None,
+            compression: None,
         }
     }
 }
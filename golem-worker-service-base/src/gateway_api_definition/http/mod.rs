@@ -15,9 +15,13 @@
 pub use http_api_definition::*;
 pub use http_api_definition_request::*;
 pub use http_oas_api_definition::*;
+pub use openapi_export::*;
+pub use rib_api_definition::*;
 
 mod http_api_definition;
 mod http_api_definition_request;
 mod http_oas_api_definition;
+mod openapi_export;
 pub(crate) mod path_pattern_parser;
 pub(crate) mod place_holder_parser;
+mod rib_api_definition;
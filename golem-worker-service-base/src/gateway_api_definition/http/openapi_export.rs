@@ -0,0 +1,814 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::{
+    AllPathPatterns, CompiledHttpApiDefinition, CompiledRoute, MethodPattern, PathPattern,
+};
+use crate::gateway_binding::{
+    page_field_indices, ContentNegotiation, ErrorMapping, ErrorResponseShape,
+    GatewayBindingCompiled, Pagination, ResponseMediaType, StaticBinding, CURSOR_QUERY_PARAM,
+    LIMIT_QUERY_PARAM,
+};
+use crate::gateway_execution::error_mapping::PROBLEM_JSON_CONTENT_TYPE;
+use crate::gateway_execution::field_selection::{ResponseBodyPosition, FIELDS_QUERY_PARAM};
+use crate::gateway_security::ApiKeyLocation;
+use golem_wasm_ast::analysis::{AnalysedType, TypeList, TypeOption};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Path under which every API deployment serves the OpenAPI document of its routes
+pub const OPEN_API_WELL_KNOWN_PATH: &str = "/.well-known/openapi.json";
+
+/// Exports the routes of the API definitions deployed to a site as an OpenAPI 3.0 document.
+///
+/// Parameter and request body schemas are derived from the inferred type of the `request`
+/// input of the response mapping Rib scripts, and response body schemas from their inferred
+/// output type. Path and query parameters not used by the scripts are documented as strings.
+pub fn to_open_api_document<Namespace>(
+    site: &str,
+    definitions: &[CompiledHttpApiDefinition<Namespace>],
+) -> Value {
+    let mut paths = Map::new();
+    let mut security_schemes = Map::new();
+
+    for definition in definitions {
+        for route in &definition.routes {
+            let Some(method) = open_api_method(&route.method) else {
+                continue;
+            };
+            let Some(mut operation) = route_operation(route) else {
+                continue;
+            };
+
+            operation.insert("tags".to_string(), json!([definition.id.0]));
+
+            if let Some(authentication) = route
+                .middlewares
+                .as_ref()
+                .and_then(|middlewares| middlewares.get_http_authentication_middleware())
+            {
+                let metadata = authentication.security_scheme_with_metadata;
+                let name = metadata.security_scheme.scheme_identifier().to_string();
+                let issuer = metadata.provider_metadata.issuer().url().to_string();
+                security_schemes.insert(
+                    name.clone(),
+                    json!({
+                        "type": "openIdConnect",
+                        "openIdConnectUrl": format!(
+                            "{}/.well-known/openid-configuration",
+                            issuer.trim_end_matches('/')
+                        )
+                    }),
+                );
+                operation.insert("security".to_string(), json!([{ name: [] }]));
+            } else if let Some(authentication) = route
+                .middlewares
+                .as_ref()
+                .and_then(|middlewares| middlewares.get_jwt_authentication_middleware())
+            {
+                let name = authentication
+                    .jwt_security_scheme
+                    .scheme_identifier()
+                    .to_string();
+                security_schemes.insert(
+                    name.clone(),
+                    json!({
+                        "type": "http",
+                        "scheme": "bearer",
+                        "bearerFormat": "JWT"
+                    }),
+                );
+                operation.insert("security".to_string(), json!([{ name: [] }]));
+            } else if let Some(authentication) = route
+                .middlewares
+                .as_ref()
+                .and_then(|middlewares| middlewares.get_api_key_authentication_middleware())
+            {
+                let scheme = &authentication.api_key_security_scheme;
+                let name = scheme.scheme_identifier().to_string();
+                let (location, key_name) = match scheme.location() {
+                    ApiKeyLocation::Header(header) => ("header", header),
+                    ApiKeyLocation::QueryParam(query_param) => ("query", query_param),
+                };
+                security_schemes.insert(
+                    name.clone(),
+                    json!({
+                        "type": "apiKey",
+                        "in": location,
+                        "name": key_name
+                    }),
+                );
+                operation.insert("security".to_string(), json!([{ name: [] }]));
+            } else if let Some(authentication) = route
+                .middlewares
+                .as_ref()
+                .and_then(|middlewares| middlewares.get_hmac_authentication_middleware())
+            {
+                // OpenAPI has no notion of signed requests, the signature header is the closest
+                let scheme = &authentication.hmac_security_scheme;
+                let name = scheme.scheme_identifier().to_string();
+                security_schemes.insert(
+                    name.clone(),
+                    json!({
+                        "type": "apiKey",
+                        "in": "header",
+                        "name": scheme.header(),
+                        "description": format!("HMAC-{} signature of the request body", scheme.algorithm().to_string().to_uppercase())
+                    }),
+                );
+                operation.insert("security".to_string(), json!([{ name: [] }]));
+            }
+
+            let path_item = paths
+                .entry(open_api_path(&route.path))
+                .or_insert_with(|| Value::Object(Map::new()));
+            if let Value::Object(path_item) = path_item {
+                path_item.insert(method.to_string(), Value::Object(operation));
+            }
+        }
+    }
+
+    let version = match definitions {
+        [definition] => definition.version.0.clone(),
+        _ => definitions
+            .iter()
+            .map(|definition| format!("{}@{}", definition.id.0, definition.version.0))
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+
+    let mut document = json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": site,
+            "version": version
+        },
+        "paths": paths
+    });
+
+    if !security_schemes.is_empty() {
+        document["components"] = json!({ "securitySchemes": security_schemes });
+    }
+
+    document
+}
+
+fn route_operation(route: &CompiledRoute) -> Option<Map<String, Value>> {
+    match &route.binding {
+        GatewayBindingCompiled::Worker(binding) => {
+            let response_compiled = &binding.response_compiled;
+            let request_type = response_compiled.rib_input.types.get("request");
+            let response_type = response_compiled
+                .rib_output
+                .as_ref()
+                .map(|output| &output.analysed_type);
+
+            let mut operation = match &binding.pagination {
+                Some(pagination) => {
+                    paginated_operation(&route.path, request_type, response_type, pagination)
+                }
+                None => operation(&route.path, request_type, response_type),
+            };
+
+            if let Some(content_negotiation) = &binding.content_negotiation {
+                negotiate_response_content(&mut operation, content_negotiation);
+            }
+
+            if let Some(error_mapping) = &binding.error_mapping {
+                add_error_responses(&mut operation, error_mapping);
+            }
+
+            if let Some(body_type) = response_type.and_then(|typ| {
+                ResponseBodyPosition::of(typ, binding.pagination.is_some()).body_type(typ)
+            }) {
+                add_fields_parameter(&mut operation, &route.path, body_type);
+            }
+
+            Some(operation)
+        }
+        GatewayBindingCompiled::FileServer(binding) => {
+            let mut operation = operation(
+                &route.path,
+                binding.response_compiled.rib_input.types.get("request"),
+                None,
+            );
+            operation.insert(
+                "responses".to_string(),
+                json!({
+                    "200": {
+                        "description": "File contents",
+                        "content": {
+                            "application/octet-stream": {
+                                "schema": { "type": "string", "format": "binary" }
+                            }
+                        }
+                    }
+                }),
+            );
+            Some(operation)
+        }
+        GatewayBindingCompiled::HttpHandler(_) => Some(operation(&route.path, None, None)),
+        GatewayBindingCompiled::Grpc(_) => Some(operation(&route.path, None, None)),
+        GatewayBindingCompiled::HttpProxy(_) => Some(operation(&route.path, None, None)),
+        GatewayBindingCompiled::Static(StaticBinding::StatusPage) => {
+            Some(operation(&route.path, None, None))
+        }
+        // WebSocket upgrades cannot be described by an OpenAPI operation
+        GatewayBindingCompiled::WebSocket(_) => None,
+        GatewayBindingCompiled::ServerSentEvents(binding) => {
+            let mut operation = operation(
+                &route.path,
+                binding.response_compiled.rib_input.types.get("request"),
+                None,
+            );
+            operation.insert(
+                "responses".to_string(),
+                json!({
+                    "200": {
+                        "description": "Stream of the events emitted by the worker",
+                        "content": {
+                            "text/event-stream": {
+                                "schema": { "type": "string" }
+                            }
+                        }
+                    }
+                }),
+            );
+            Some(operation)
+        }
+        GatewayBindingCompiled::Static(StaticBinding::HttpCorsPreflight(_))
+        | GatewayBindingCompiled::Static(StaticBinding::HttpAuthCallBack(_)) => None,
+    }
+}
+
+/// Describes a route given the inferred type of its `request` input and of its response
+pub(crate) fn operation(
+    path: &AllPathPatterns,
+    request_type: Option<&AnalysedType>,
+    response_type: Option<&AnalysedType>,
+) -> Map<String, Value> {
+    let path_types = request_type.and_then(|typ| record_field(typ, "path"));
+    let query_types = request_type.and_then(|typ| record_field(typ, "query"));
+    let header_types = request_type.and_then(|typ| record_field(typ, "headers"));
+    let body_type = request_type.and_then(|typ| record_field(typ, "body"));
+
+    let mut parameters = vec![];
+
+    for pattern in &path.path_patterns {
+        let name = match pattern {
+            PathPattern::Var(info) => &info.key_name,
+            PathPattern::CatchAllVar(info) => &info.key_name,
+            PathPattern::RegexVar(info) => &info.key_name,
+            PathPattern::Literal(_) => continue,
+        };
+        parameters.push(parameter(name, "path", true, path_types));
+    }
+
+    for query in &path.query_params {
+        parameters.push(parameter(&query.key_name, "query", false, query_types));
+    }
+
+    if let Some(AnalysedType::Record(headers)) = header_types {
+        for field in &headers.fields {
+            parameters.push(json!({
+                "name": field.name,
+                "in": "header",
+                "required": !matches!(field.typ, AnalysedType::Option(_)),
+                "schema": analysed_type_schema(&field.typ)
+            }));
+        }
+    }
+
+    let mut operation = Map::new();
+
+    if !parameters.is_empty() {
+        operation.insert("parameters".to_string(), Value::Array(parameters));
+    }
+
+    if let Some(body_type) = body_type {
+        operation.insert(
+            "requestBody".to_string(),
+            json!({
+                "required": !matches!(body_type, AnalysedType::Option(_)),
+                "content": {
+                    "application/json": { "schema": analysed_type_schema(body_type) }
+                }
+            }),
+        );
+    }
+
+    // Response mappings evaluating to a record with a `body` field describe the full response,
+    // any other value is returned as the response body
+    let response_body_type = match response_type {
+        Some(AnalysedType::Record(record))
+            if record
+                .fields
+                .iter()
+                .any(|field| field.name == "status" || field.name == "headers") =>
+        {
+            record
+                .fields
+                .iter()
+                .find(|field| field.name == "body")
+                .map(|field| &field.typ)
+        }
+        other => other,
+    };
+
+    let response = match response_body_type {
+        Some(typ) => json!({
+            "description": "Response",
+            "content": {
+                "application/json": { "schema": analysed_type_schema(typ) }
+            }
+        }),
+        None => json!({ "description": "Response" }),
+    };
+    operation.insert("responses".to_string(), json!({ "default": response }));
+
+    operation
+}
+
+/// Describes a route of a paginated binding, which responds with the items of the page,
+/// given the inferred type of the page returned by its response mapping
+pub(crate) fn paginated_operation(
+    path: &AllPathPatterns,
+    request_type: Option<&AnalysedType>,
+    page_type: Option<&AnalysedType>,
+    pagination: &Pagination,
+) -> Map<String, Value> {
+    let items_type = page_type.and_then(|typ| {
+        let (items_index, _) = page_field_indices(typ)?;
+        match typ {
+            AnalysedType::Tuple(tuple) => tuple.items.get(items_index),
+            AnalysedType::Record(record) => record.fields.get(items_index).map(|field| &field.typ),
+            _ => None,
+        }
+    });
+
+    let mut operation = operation(path, request_type, items_type);
+
+    let parameters = operation
+        .entry("parameters".to_string())
+        .or_insert_with(|| Value::Array(vec![]));
+
+    if let Value::Array(parameters) = parameters {
+        // The pagination query parameters may also be declared by the path pattern
+        parameters.retain(|parameter| {
+            parameter["in"] != "query"
+                || (parameter["name"] != CURSOR_QUERY_PARAM
+                    && parameter["name"] != LIMIT_QUERY_PARAM)
+        });
+        parameters.push(json!({
+            "name": CURSOR_QUERY_PARAM,
+            "in": "query",
+            "required": false,
+            "description": "Cursor of the page, as linked by the previous page",
+            "schema": { "type": "string" }
+        }));
+        parameters.push(json!({
+            "name": LIMIT_QUERY_PARAM,
+            "in": "query",
+            "required": false,
+            "description": "Maximum number of items of the page",
+            "schema": {
+                "type": "integer",
+                "format": "int32",
+                "minimum": 1,
+                "maximum": pagination.max_limit(),
+                "default": pagination.default_limit()
+            }
+        }));
+    }
+
+    if let Some(Value::Object(response)) = operation
+        .get_mut("responses")
+        .and_then(|responses| responses.get_mut("default"))
+    {
+        response.insert(
+            "headers".to_string(),
+            json!({
+                "Link": {
+                    "description": "Link to the next page, with rel=\"next\", unless this is the last page",
+                    "schema": { "type": "string" }
+                }
+            }),
+        );
+    }
+
+    operation
+}
+
+// Describes the response of an operation in each of the negotiated media types. Values are
+// encoded from their JSON representation, except as plain text.
+fn negotiate_response_content(
+    operation: &mut Map<String, Value>,
+    content_negotiation: &ContentNegotiation,
+) {
+    let Some(Value::Object(response)) = operation
+        .get_mut("responses")
+        .and_then(|responses| responses.get_mut("default"))
+    else {
+        return;
+    };
+
+    let Some(schema) = response
+        .get("content")
+        .and_then(|content| content.get("application/json"))
+        .and_then(|content| content.get("schema"))
+        .cloned()
+    else {
+        return;
+    };
+
+    let content = content_negotiation
+        .media_types
+        .iter()
+        .map(|media_type| {
+            let schema = match media_type {
+                ResponseMediaType::Text => json!({ "type": "string" }),
+                _ => schema.clone(),
+            };
+            (media_type.to_string(), json!({ "schema": schema }))
+        })
+        .collect::<Map<_, _>>();
+
+    response.insert("content".to_string(), Value::Object(content));
+}
+
+// Documents the responses of the mapped error cases, by status
+fn add_error_responses(operation: &mut Map<String, Value>, error_mapping: &ErrorMapping) {
+    let Some(Value::Object(responses)) = operation.get_mut("responses") else {
+        return;
+    };
+
+    let mut cases_by_status = BTreeMap::<u16, Vec<_>>::new();
+    for case in &error_mapping.cases {
+        cases_by_status.entry(case.status).or_default().push(case);
+    }
+
+    for (status, cases) in cases_by_status {
+        let content = cases
+            .iter()
+            .filter_map(|case| match case.shape {
+                ErrorResponseShape::Problem => Some((
+                    PROBLEM_JSON_CONTENT_TYPE.to_string(),
+                    json!({ "schema": problem_schema() }),
+                )),
+                ErrorResponseShape::Payload => {
+                    Some(("application/json".to_string(), json!({ "schema": {} })))
+                }
+                ErrorResponseShape::Empty => None,
+            })
+            .collect::<Map<_, _>>();
+
+        let mut response = json!({
+            "description": format!(
+                "Error {}",
+                cases
+                    .iter()
+                    .map(|case| case.case.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        });
+        if !content.is_empty() {
+            response["content"] = Value::Object(content);
+        }
+
+        responses.insert(status.to_string(), response);
+    }
+}
+
+fn problem_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "type": { "type": "string" },
+            "title": { "type": "string" },
+            "status": { "type": "integer" },
+            "error": { "type": "string" },
+            "detail": {}
+        },
+        "required": ["type", "title", "status", "error"]
+    })
+}
+
+// Documents the `fields` query parameter of the responses with records,
+// unless the route declares a `fields` query parameter of its own
+fn add_fields_parameter(
+    operation: &mut Map<String, Value>,
+    path: &AllPathPatterns,
+    body_type: &AnalysedType,
+) {
+    let mut record_type = body_type;
+    while let AnalysedType::List(TypeList { inner }) | AnalysedType::Option(TypeOption { inner }) =
+        record_type
+    {
+        record_type = inner;
+    }
+
+    if !matches!(record_type, AnalysedType::Record(_))
+        || path
+            .query_params
+            .iter()
+            .any(|query| query.key_name == FIELDS_QUERY_PARAM)
+    {
+        return;
+    }
+
+    if let Value::Array(parameters) = operation
+        .entry("parameters".to_string())
+        .or_insert_with(|| Value::Array(vec![]))
+    {
+        parameters.push(json!({
+            "name": FIELDS_QUERY_PARAM,
+            "in": "query",
+            "required": false,
+            "description": "Comma separated fields of the response to keep, with nested fields as dotted paths",
+            "schema": { "type": "string" }
+        }));
+    }
+}
+
+fn parameter(name: &str, location: &str, required: bool, types: Option<&AnalysedType>) -> Value {
+    let schema = types
+        .and_then(|typ| record_field(typ, name))
+        .map(analysed_type_schema)
+        .unwrap_or_else(|| json!({ "type": "string" }));
+
+    json!({
+        "name": name,
+        "in": location,
+        "required": required,
+        "schema": schema
+    })
+}
+
+fn record_field<'a>(typ: &'a AnalysedType, name: &str) -> Option<&'a AnalysedType> {
+    match typ {
+        AnalysedType::Record(record) => record
+            .fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| &field.typ),
+        _ => None,
+    }
+}
+
+/// JSON Schema of the JSON representation of values of the given type
+pub fn analysed_type_schema(typ: &AnalysedType) -> Value {
+    match typ {
+        AnalysedType::Bool(_) => json!({ "type": "boolean" }),
+        AnalysedType::S8(_) | AnalysedType::S16(_) | AnalysedType::S32(_) => {
+            json!({ "type": "integer", "format": "int32" })
+        }
+        AnalysedType::S64(_) => json!({ "type": "integer", "format": "int64" }),
+        AnalysedType::U8(_) | AnalysedType::U16(_) | AnalysedType::U32(_) => {
+            json!({ "type": "integer", "format": "int32", "minimum": 0 })
+        }
+        AnalysedType::U64(_) => json!({ "type": "integer", "format": "int64", "minimum": 0 }),
+        AnalysedType::F32(_) => json!({ "type": "number", "format": "float" }),
+        AnalysedType::F64(_) => json!({ "type": "number", "format": "double" }),
+        AnalysedType::Chr(_) => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+        AnalysedType::Str(_) => json!({ "type": "string" }),
+        AnalysedType::List(list) => json!({
+            "type": "array",
+            "items": analysed_type_schema(&list.inner)
+        }),
+        AnalysedType::Tuple(tuple) => json!({
+            "type": "array",
+            "items": {
+                "oneOf": tuple.items.iter().map(analysed_type_schema).collect::<Vec<_>>()
+            },
+            "minItems": tuple.items.len(),
+            "maxItems": tuple.items.len()
+        }),
+        AnalysedType::Record(record) => json!({
+            "type": "object",
+            "properties": record
+                .fields
+                .iter()
+                .map(|field| (field.name.clone(), analysed_type_schema(&field.typ)))
+                .collect::<Map<_, _>>(),
+            "required": record
+                .fields
+                .iter()
+                .filter(|field| !matches!(field.typ, AnalysedType::Option(_)))
+                .map(|field| field.name.clone())
+                .collect::<Vec<_>>()
+        }),
+        AnalysedType::Option(option) => {
+            let mut schema = analysed_type_schema(&option.inner);
+            schema["nullable"] = Value::Bool(true);
+            schema
+        }
+        AnalysedType::Enum(enum_type) => json!({
+            "type": "string",
+            "enum": enum_type.cases
+        }),
+        AnalysedType::Flags(flags) => json!({
+            "type": "array",
+            "items": { "type": "string", "enum": flags.names },
+            "uniqueItems": true
+        }),
+        AnalysedType::Variant(variant) => json!({
+            "oneOf": variant
+                .cases
+                .iter()
+                .map(|case| single_property_object(
+                    &case.name,
+                    case.typ.as_ref().map(analysed_type_schema)
+                ))
+                .collect::<Vec<_>>()
+        }),
+        AnalysedType::Result(result) => json!({
+            "oneOf": [
+                single_property_object(
+                    "ok",
+                    result.ok.as_ref().map(|ok| analysed_type_schema(ok))
+                ),
+                single_property_object(
+                    "err",
+                    result.err.as_ref().map(|err| analysed_type_schema(err))
+                )
+            ]
+        }),
+        AnalysedType::Handle(_) => json!({ "type": "string" }),
+    }
+}
+
+fn single_property_object(name: &str, schema: Option<Value>) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            name: schema.unwrap_or_else(|| json!({ "nullable": true }))
+        },
+        "required": [name],
+        "additionalProperties": false
+    })
+}
+
+fn open_api_method(method: &MethodPattern) -> Option<&'static str> {
+    match method {
+        MethodPattern::Get => Some("get"),
+        MethodPattern::Post => Some("post"),
+        MethodPattern::Put => Some("put"),
+        MethodPattern::Delete => Some("delete"),
+        MethodPattern::Patch => Some("patch"),
+        MethodPattern::Options => Some("options"),
+        MethodPattern::Head => Some("head"),
+        MethodPattern::Trace => Some("trace"),
+        MethodPattern::Connect => None,
+    }
+}
+
+/// OpenAPI path template of a path pattern, without its query parameters
+fn open_api_path(path: &AllPathPatterns) -> String {
+    let path = path
+        .path_patterns
+        .iter()
+        .map(|pattern| match pattern {
+            PathPattern::Literal(literal) => literal.0.clone(),
+            PathPattern::Var(info) => format!("{{{}}}", info.key_name),
+            PathPattern::CatchAllVar(info) => format!("{{{}}}", info.key_name),
+            PathPattern::RegexVar(info) => format!("{{{}}}", info.key_name),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("/{}", path)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{
+        field, list, option, record, str, tuple, u32, u64,
+    };
+
+    #[test]
+    fn operation_uses_inferred_request_and_response_types() {
+        let path = AllPathPatterns::parse("/users/{user-id}/cart?limit&offset").unwrap();
+
+        let request_type = record(vec![
+            field("path", record(vec![field("user-id", u64())])),
+            field("query", record(vec![field("limit", u32())])),
+            field("body", record(vec![field("name", str())])),
+        ]);
+
+        let response_type = record(vec![field("status", u64()), field("body", option(str()))]);
+
+        let operation = operation(&path, Some(&request_type), Some(&response_type));
+
+        assert_eq!(open_api_path(&path), "/users/{user-id}/cart");
+        assert_eq!(
+            Value::Object(operation),
+            json!({
+                "parameters": [
+                    {
+                        "name": "user-id",
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "integer", "format": "int64", "minimum": 0 }
+                    },
+                    {
+                        "name": "limit",
+                        "in": "query",
+                        "required": false,
+                        "schema": { "type": "integer", "format": "int32", "minimum": 0 }
+                    },
+                    {
+                        "name": "offset",
+                        "in": "query",
+                        "required": false,
+                        "schema": { "type": "string" }
+                    }
+                ],
+                "requestBody": {
+                    "required": true,
+                    "content": {
+                        "application/json": {
+                            "schema": {
+                                "type": "object",
+                                "properties": { "name": { "type": "string" } },
+                                "required": ["name"]
+                            }
+                        }
+                    }
+                },
+                "responses": {
+                    "default": {
+                        "description": "Response",
+                        "content": {
+                            "application/json": {
+                                "schema": { "type": "string", "nullable": true }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn paginated_operation_documents_cursor_limit_and_link() {
+        let path = AllPathPatterns::parse("/orders?status&limit").unwrap();
+        let page_type = tuple(vec![list(str()), option(str())]);
+        let pagination = Pagination {
+            default_limit: None,
+            max_limit: Some(50),
+        };
+
+        let operation = paginated_operation(&path, None, Some(&page_type), &pagination);
+
+        assert_eq!(
+            operation["parameters"],
+            json!([
+                {
+                    "name": "status",
+                    "in": "query",
+                    "required": false,
+                    "schema": { "type": "string" }
+                },
+                {
+                    "name": "cursor",
+                    "in": "query",
+                    "required": false,
+                    "description": "Cursor of the page, as linked by the previous page",
+                    "schema": { "type": "string" }
+                },
+                {
+                    "name": "limit",
+                    "in": "query",
+                    "required": false,
+                    "description": "Maximum number of items of the page",
+                    "schema": {
+                        "type": "integer",
+                        "format": "int32",
+                        "minimum": 1,
+                        "maximum": 50,
+                        "default": 20
+                    }
+                }
+            ])
+        );
+        assert_eq!(
+            operation["responses"]["default"]["content"]["application/json"]["schema"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert!(operation["responses"]["default"]["headers"]["Link"].is_object());
+    }
+}
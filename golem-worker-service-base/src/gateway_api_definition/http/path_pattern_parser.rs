@@ -60,6 +60,7 @@ fn path_parser(input: &str) -> IResult<&str, Vec<PathPattern>> {
             ParsedPattern::Literal(literal) => PathPattern::literal(literal),
             ParsedPattern::Var(var) => PathPattern::var(var),
             ParsedPattern::CatchAllVar(var) => PathPattern::catch_all_var(var),
+            ParsedPattern::RegexVar(var, pattern) => PathPattern::regex_var(var, pattern),
         })
         .collect();
 
@@ -83,11 +84,22 @@ fn path_var_parser(input: &str) -> IResult<&str, ParsedPattern<'_>> {
     )(input)
 }
 
+// Either `{name}`, or `{name:regex}` constraining the segments matched by the parameter
 fn path_var_inner_parser(
     input: &str,
 ) -> Result<ParsedPattern<'_>, nom::Err<nom::error::Error<&str>>> {
     let (i, _) = peek(not(char('+')))(input)?;
-    Ok(ParsedPattern::Var(i))
+
+    match i.split_once(':') {
+        Some((name, pattern)) if !name.is_empty() && !pattern.is_empty() => {
+            Ok(ParsedPattern::RegexVar(name, pattern))
+        }
+        None if !i.ends_with('+') => Ok(ParsedPattern::Var(i)),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
 }
 
 fn catch_all_path_var_parser(input: &str) -> IResult<&str, ParsedPattern<'_>> {
@@ -97,11 +109,19 @@ fn catch_all_path_var_parser(input: &str) -> IResult<&str, ParsedPattern<'_>> {
     )(input)
 }
 
+// Either `{+name}` or `{name+}`
 fn catch_all_path_var_inner_parser(
     input: &str,
 ) -> Result<ParsedPattern<'_>, nom::Err<nom::error::Error<&str>>> {
-    let (i, _) = char('+')(input)?;
-    Ok(ParsedPattern::CatchAllVar(i))
+    match input.strip_prefix('+').or_else(|| input.strip_suffix('+')) {
+        Some(name) if !name.is_empty() && !name.contains(':') => {
+            Ok(ParsedPattern::CatchAllVar(name))
+        }
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Char,
+        ))),
+    }
 }
 
 #[derive(Debug)]
@@ -109,6 +129,7 @@ enum ParsedPattern<'a> {
     Literal(&'a str),
     Var(&'a str),
     CatchAllVar(&'a str),
+    RegexVar(&'a str, &'a str),
 }
 
 fn literal_parser(input: &str) -> IResult<&str, ParsedPattern<'_>> {
@@ -182,4 +203,23 @@ mod tests {
         // {+var} is not allowed in the middle of the path
         assert!(AllPathPatterns::parse("/api/{foo}/{+others}/{bar}").is_err());
     }
+
+    #[test]
+    fn test_parse_wildcards_and_regex_constraints() {
+        let result = AllPathPatterns::parse("/files/{id:[a-z]{2}[0-9]+}/{path+}").unwrap();
+
+        assert_eq!(
+            result.path_patterns,
+            vec![
+                PathPattern::literal("files"),
+                PathPattern::regex_var("id", "[a-z]{2}[0-9]+"),
+                PathPattern::catch_all_var("path"),
+            ]
+        );
+        assert_eq!(result.to_string(), "/files/{id:[a-z]{2}[0-9]+}/{+path}");
+
+        assert!(AllPathPatterns::parse("/files/{path+}/{id}").is_err());
+        assert!(AllPathPatterns::parse("/files/{id:[0-9}").is_err());
+        assert!(AllPathPatterns::parse("/files/{:[0-9]+}").is_err());
+    }
 }
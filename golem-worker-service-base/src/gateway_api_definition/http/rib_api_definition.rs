@@ -0,0 +1,1323 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API definitions authored as a single Rib document.
+//!
+//! The document declares the routes of an API definition, and embeds the Rib scripts of their
+//! bindings in `{ ... }` blocks:
+//!
+//! ```text
+//! api shopping-cart version "0.0.1" {
+//!   security my-security;
+//!   taint-policy deny;
+//!
+//!   route get "/v1/cart/{user-id}" {
+//!     security my-security;
+//!     cors { { Access-Control-Allow-Origin: "*" } }
+//!     concurrency-limit { max-in-flight 4; max-queued 16; retry-after 1; }
+//!     rate-limit { capacity 10; refill-interval-ms 1000; key { request.headers.x-api-key } }
+//!     body-limits { max-request-body-bytes 1048576; read-timeout-ms 5000; }
+//!     response-cache { ttl-seconds 30; vary "header:accept"; }
+//!     compression { algorithm "br"; algorithm "gzip"; min-size-bytes 512; }
+//!     transform-request { { headers: { x-user-id: "${request.path.user-id}" } } }
+//!     transform-response { { headers: { cache-control: "no-store" } } }
+//!     when rate-limit { request.headers.user-agent != "health-check" }
+//!
+//!     worker "e1f2b6a0-4c5d-4e6f-8a9b-0c1d2e3f4a5b" version 0 {
+//!       worker-name { "cart-${request.path.user-id}" }
+//!       response {
+//!         let result = golem:it/api.{get-cart-contents}();
+//!         { status: 200u64, body: result }
+//!       }
+//!     }
+//!   }
+//!
+//!   route options "/v1/cart/{user-id}" {
+//!     cors-preflight { { Access-Control-Allow-Origin: "*" } }
+//!   }
+//! }
+//! ```
+//!
+//! Bindings are either `worker`, `file-server`, `http-handler`, `web-socket` or
+//! `server-sent-events` bindings of a component version, a `cors-preflight` binding, an
+//! `http-proxy` binding to an upstream URL, with an optional `request-mapping` script and
+//! `timeout-ms` (as in `http-proxy "http://legacy:8080" { timeout-ms 5000; }`), or a
+//! `status-page;` binding of a `get` route, serving the health of the deployment.
+//! A `concurrency-limit` of a route takes `max-in-flight`, `max-queued`, `queue-timeout-ms`,
+//! `shed-status`, `shed-body` and `retry-after` settings, and a `rate-limit` takes a `capacity`,
+//! a `refill-interval-ms` and an optional `key` script (the client IP being the key otherwise).
+//! The `body-limits` of a route take `max-request-body-bytes`, `max-response-body-bytes` and
+//! `read-timeout-ms` settings, and the `response-cache` of a `get` route takes a `ttl-seconds`
+//! and any number of `vary` entries (`"header:<name>"`), the query string always varying it.
+//! The `compression` of a route lists the `algorithm`s (`"br"` or `"gzip"`) in order of preference,
+//! with a `min-size-bytes` and any number of `content-type` entries (such as `"text/*"`), and
+//! `compression off;` turns off the compression of the deployment for the route.
+//! The `transform-request` and `transform-response` scripts of a route are applied in the order
+//! they are declared, to the request before the binding and to the response after it.
+//! A `when` block makes the `security`, `rate-limit`, `response-cache` or `transforms` of a route
+//! conditional on a Rib predicate over the request, skipping it for the requests it is false for.
+//! In place of a `worker-name`, a secured route's binding can declare `worker-per-user;` to route
+//! every user to their own worker, named after the `sub` claim of their identity, or
+//! `worker-per-user { prefix "cart-"; claim "email"; }` to pick the claim and prefix the names.
+//! A `bootstrap` block of a binding gives the arguments (`arg { ... }`), environment variables
+//! (`env NAME { ... }`) and WASI config variables (`config NAME { ... }`) of its worker,
+//! evaluated from the request when it creates the worker.
+//! The `response` of a `worker` or `file-server` binding can use the labels of its worker, such
+//! as `worker.labels.tenant`, which are empty strings for the labels the worker does not have.
+//! A `worker` binding declaring `pagination;` (or `pagination { default-limit 20; max-limit 100; }`)
+//! serves a list-returning function as pages, with `request.page.cursor` and `request.page.limit`
+//! taken from the `cursor` and `limit` query parameters, and a `Link` header to the next page.
+//! A `worker` binding declaring `content-negotiation "application/json" "text/plain";` responds
+//! in the media type accepted by the client among these, available as `request.media_type`.
+//! An `error-mapping { cart-not-found 404; out-of-stock 409 payload; }` block of a `worker`
+//! binding responds to the error cases returned by the component with their status, and a shape
+//! of `problem` (the default), `payload` or `empty`.
+//! A binding declaring `static-worker-names;` requires the worker names its scripts give to
+//! `instance()` to be known at compile time, rejecting the ones computed from the request.
+//! The `response` of a `web-socket` binding is evaluated for every incoming message, which is
+//! available as `message`, and the `response` of a `server-sent-events` binding for every event
+//! emitted by the worker, which is available as `event`. Every embedded script is parsed as Rib when the document is
+//! read, and type checked against the bound component when the API definition is created.
+//! The `taint-policy` of a definition (`off`, `warn` by default, or `deny`) decides what happens
+//! when untrusted request values flow into worker names or response headers.
+//! Definitions created from a document are drafts. Line comments start with `//`.
+
+use crate::gateway_api_definition::http::{
+    AllPathPatterns, HttpApiDefinitionRequest, MethodPattern, RouteRequest,
+};
+use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use crate::gateway_binding::{
+    ContentNegotiation, ErrorCaseMapping, ErrorMapping, ErrorResponseShape, GatewayBinding,
+    HttpHandlerBinding, HttpProxyBinding, Pagination, ResponseMapping, ResponseMediaType,
+    StaticBinding, WorkerBinding, WorkerBootstrap, WorkerPerUser,
+};
+use crate::gateway_middleware::{
+    ConditionalMiddleware, CorsPreflightExpr, HttpBodyLimits, HttpCompression,
+    HttpConcurrencyLimit, HttpCors, HttpMiddlewareConditions, HttpRateLimit, HttpResponseCache,
+    HttpTransforms,
+};
+use crate::gateway_rib_compiler::TaintAnalysisPolicy;
+use crate::gateway_security::SecuritySchemeReference;
+use golem_common::model::ComponentId;
+use golem_service_base::model::VersionedComponentId;
+use rib::Expr;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// An API definition in the Rib document format
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibHttpApiDefinitionRequest(pub String);
+
+impl RibHttpApiDefinitionRequest {
+    pub fn to_http_api_definition_request(&self) -> Result<HttpApiDefinitionRequest, String> {
+        let mut parser = Parser::new(&self.0);
+        let definition = parser.api_definition()?;
+        parser.skip_trivia();
+        if parser.is_at_end() {
+            Ok(definition)
+        } else {
+            Err(parser.error("unexpected content after the API definition"))
+        }
+    }
+}
+
+enum BindingKind {
+    Worker,
+    FileServer,
+    HttpHandler,
+    WebSocket,
+    ServerSentEvents,
+}
+
+#[derive(Default)]
+struct BindingScripts {
+    worker_name: Option<Expr>,
+    idempotency_key: Option<Expr>,
+    invocation_context: Option<Expr>,
+    response: Option<Expr>,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Self {
+        Parser { input, position: 0 }
+    }
+
+    fn api_definition(&mut self) -> Result<HttpApiDefinitionRequest, String> {
+        self.keyword("api")?;
+        let id = self.name("API definition id")?;
+        self.keyword("version")?;
+        let version = self.string()?;
+        self.symbol('{')?;
+
+        let mut security = vec![];
+        let mut routes = vec![];
+        let mut taint_policy = TaintAnalysisPolicy::default();
+
+        while !self.try_symbol('}') {
+            match self.word()?.as_str() {
+                "security" => {
+                    security.push(SecuritySchemeReference::new(self.name("security scheme")?));
+                    self.symbol(';')?;
+                }
+                "taint-policy" => {
+                    let policy = self.word()?;
+                    taint_policy =
+                        TaintAnalysisPolicy::from_str(&policy).map_err(|err| self.error(&err))?;
+                    self.symbol(';')?;
+                }
+                "route" => routes.push(self.route()?),
+                other => {
+                    return Err(self.error(&format!(
+                        "expected 'security', 'taint-policy' or 'route', found '{}'",
+                        other
+                    )))
+                }
+            }
+        }
+
+        Ok(HttpApiDefinitionRequest {
+            id: ApiDefinitionId(id),
+            security: if security.is_empty() {
+                None
+            } else {
+                Some(security)
+            },
+            version: ApiVersion(version),
+            routes,
+            draft: true,
+            taint_policy,
+        })
+    }
+
+    fn route(&mut self) -> Result<RouteRequest, String> {
+        let method_word = self.word()?;
+        let method = MethodPattern::from_str(&method_word)
+            .map_err(|_| self.error(&format!("unknown HTTP method '{}'", method_word)))?;
+        let path_text = self.string()?;
+        let path = AllPathPatterns::parse(&path_text)
+            .map_err(|err| self.error(&format!("invalid path '{}': {}", path_text, err)))?;
+        let route_name = format!("{} {}", method_word.to_ascii_uppercase(), path_text);
+        self.symbol('{')?;
+
+        let mut security = None;
+        let mut cors = None;
+        let mut concurrency_limit = None;
+        let mut rate_limit = None;
+        let mut body_limits = None;
+        let mut response_cache = None;
+        let mut compression = None;
+        let mut request_transforms = vec![];
+        let mut response_transforms = vec![];
+        let mut conditions = vec![];
+        let mut binding = None;
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            match item.as_str() {
+                "security" => {
+                    if security.is_some() {
+                        return Err(self.error_at(start, "duplicate 'security'"));
+                    }
+                    security = Some(SecuritySchemeReference::new(self.name("security scheme")?));
+                    self.symbol(';')?;
+                }
+                "cors" => {
+                    if cors.is_some() {
+                        return Err(self.error_at(start, "duplicate 'cors'"));
+                    }
+                    cors = Some(self.cors(&route_name)?);
+                }
+                "concurrency-limit" => {
+                    if concurrency_limit.is_some() {
+                        return Err(self.error_at(start, "duplicate 'concurrency-limit'"));
+                    }
+                    concurrency_limit = Some(self.concurrency_limit(&route_name)?);
+                }
+                "rate-limit" => {
+                    if rate_limit.is_some() {
+                        return Err(self.error_at(start, "duplicate 'rate-limit'"));
+                    }
+                    rate_limit = Some(self.rate_limit(&route_name)?);
+                }
+                "body-limits" => {
+                    if body_limits.is_some() {
+                        return Err(self.error_at(start, "duplicate 'body-limits'"));
+                    }
+                    body_limits = Some(self.body_limits(&route_name)?);
+                }
+                "response-cache" => {
+                    if response_cache.is_some() {
+                        return Err(self.error_at(start, "duplicate 'response-cache'"));
+                    }
+                    response_cache = Some(self.response_cache(&route_name)?);
+                }
+                "compression" => {
+                    if compression.is_some() {
+                        return Err(self.error_at(start, "duplicate 'compression'"));
+                    }
+                    compression = Some(self.compression(&route_name)?);
+                }
+                "transform-request" => {
+                    request_transforms.push(self.rib_block(&route_name, &item)?);
+                }
+                "transform-response" => {
+                    response_transforms.push(self.rib_block(&route_name, &item)?);
+                }
+                "when" => {
+                    let middleware_word = self.word()?;
+                    let middleware = ConditionalMiddleware::from_str(&middleware_word)
+                        .map_err(|err| self.error_at(start, &err))?;
+                    conditions.push((
+                        middleware,
+                        self.rib_block(&route_name, &format!("when {}", middleware))?,
+                    ));
+                }
+                "worker" | "file-server" | "http-handler" | "web-socket" | "server-sent-events"
+                | "http-proxy" | "cors-preflight" | "status-page" => {
+                    if binding.is_some() {
+                        return Err(self.error_at(start, "a route can only have one binding"));
+                    }
+                    binding = Some(match item.as_str() {
+                        "worker" => self.worker_binding(BindingKind::Worker, &route_name)?,
+                        "file-server" => {
+                            self.worker_binding(BindingKind::FileServer, &route_name)?
+                        }
+                        "http-handler" => {
+                            self.worker_binding(BindingKind::HttpHandler, &route_name)?
+                        }
+                        "web-socket" => self.worker_binding(BindingKind::WebSocket, &route_name)?,
+                        "server-sent-events" => {
+                            self.worker_binding(BindingKind::ServerSentEvents, &route_name)?
+                        }
+                        "http-proxy" => self.http_proxy_binding(&route_name)?,
+                        "status-page" => {
+                            if method != MethodPattern::Get {
+                                return Err(self.error_at(
+                                    start,
+                                    "status-page bindings are only supported for the 'get' method",
+                                ));
+                            }
+                            self.symbol(';')?;
+                            GatewayBinding::static_binding(StaticBinding::StatusPage)
+                        }
+                        _ => {
+                            if method != MethodPattern::Options {
+                                return Err(self.error_at(
+                                    start,
+                                    "cors-preflight bindings are only supported for the 'options' method",
+                                ));
+                            }
+                            GatewayBinding::static_binding(StaticBinding::from_http_cors(
+                                self.cors(&route_name)?,
+                            ))
+                        }
+                    });
+                }
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!(
+                            "expected 'security', 'cors', 'concurrency-limit', 'rate-limit', 'body-limits', 'response-cache', 'compression', 'transform-request', 'transform-response', 'when' or a binding in route {}, found '{}'",
+                            route_name, other
+                        ),
+                    ))
+                }
+            }
+        }
+
+        let binding = binding.ok_or_else(|| format!("Route {} has no binding", route_name))?;
+
+        let transforms = if request_transforms.is_empty() && response_transforms.is_empty() {
+            None
+        } else {
+            Some(
+                HttpTransforms::new(request_transforms, response_transforms).map_err(|err| {
+                    format!("Invalid transforms of route {}: {}", route_name, err)
+                })?,
+            )
+        };
+
+        let conditions =
+            if conditions.is_empty() {
+                None
+            } else {
+                Some(HttpMiddlewareConditions::new(conditions).map_err(|err| {
+                    format!("Invalid conditions of route {}: {}", route_name, err)
+                })?)
+            };
+
+        Ok(RouteRequest {
+            method,
+            path,
+            binding,
+            cors,
+            security,
+            concurrency_limit,
+            rate_limit,
+            body_limits,
+            response_cache,
+            transforms,
+            conditions,
+            compression,
+        })
+    }
+
+    fn cors(&mut self, route_name: &str) -> Result<HttpCors, String> {
+        let start = self.start();
+        let expr = self.rib_block(route_name, "cors")?;
+        HttpCors::from_cors_preflight_expr(&CorsPreflightExpr(expr)).map_err(|err| {
+            self.error_at(start, &format!("invalid cors in {}: {}", route_name, err))
+        })
+    }
+
+    fn concurrency_limit(&mut self, route_name: &str) -> Result<HttpConcurrencyLimit, String> {
+        self.symbol('{')?;
+
+        let mut max_in_flight = None;
+        let mut max_queued = None;
+        let mut queue_timeout_ms = None;
+        let mut shed_status = None;
+        let mut shed_body = None;
+        let mut retry_after = None;
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            let is_duplicate = match item.as_str() {
+                "max-in-flight" => max_in_flight.replace(self.number()?).is_some(),
+                "max-queued" => max_queued.replace(self.number()?).is_some(),
+                "queue-timeout-ms" => queue_timeout_ms.replace(self.number()?).is_some(),
+                "shed-status" => shed_status.replace(self.number()?).is_some(),
+                "shed-body" => shed_body.replace(self.string()?).is_some(),
+                "retry-after" => retry_after.replace(self.number()?).is_some(),
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!(
+                            "unexpected '{}' in concurrency-limit of route {}",
+                            other, route_name
+                        ),
+                    ))
+                }
+            };
+            if is_duplicate {
+                return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+            }
+            self.symbol(';')?;
+        }
+
+        let to_u32 = |value: u64| {
+            u32::try_from(value).map_err(|_| {
+                format!(
+                    "Value {} in concurrency-limit of route {} is too large",
+                    value, route_name
+                )
+            })
+        };
+
+        Ok(HttpConcurrencyLimit {
+            max_in_flight: to_u32(max_in_flight.ok_or_else(|| {
+                format!(
+                    "Concurrency limit of route {} has no max-in-flight",
+                    route_name
+                )
+            })?)?,
+            max_queued: max_queued.map(to_u32).transpose()?.unwrap_or(0),
+            queue_timeout_ms,
+            shed_status: shed_status
+                .map(|status| {
+                    u16::try_from(status).map_err(|_| {
+                        format!("Invalid shed-status {} in route {}", status, route_name)
+                    })
+                })
+                .transpose()?,
+            shed_body,
+            retry_after,
+        })
+    }
+
+    fn worker_binding(
+        &mut self,
+        kind: BindingKind,
+        route_name: &str,
+    ) -> Result<GatewayBinding, String> {
+        let component_id = self.component_id()?;
+        self.keyword("version")?;
+        let version = self.number()?;
+        let component_id = VersionedComponentId {
+            component_id,
+            version,
+        };
+        self.symbol('{')?;
+
+        let mut scripts = BindingScripts::default();
+        let mut worker_per_user = None;
+        let mut bootstrap = None;
+        let mut pagination = None;
+        let mut content_negotiation = None;
+        let mut error_mapping = None;
+        let mut static_worker_names = false;
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            if item == "worker-per-user" && !matches!(kind, BindingKind::HttpHandler) {
+                if worker_per_user.is_some() {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                worker_per_user = Some(self.worker_per_user(route_name)?);
+                continue;
+            }
+            if item == "bootstrap" && !matches!(kind, BindingKind::HttpHandler) {
+                if bootstrap.is_some() {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                bootstrap = Some(self.bootstrap(route_name)?);
+                continue;
+            }
+            if item == "pagination" && matches!(kind, BindingKind::Worker) {
+                if pagination.is_some() {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                pagination = Some(self.pagination(route_name)?);
+                continue;
+            }
+            if item == "content-negotiation" && matches!(kind, BindingKind::Worker) {
+                if content_negotiation.is_some() {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                content_negotiation = Some(self.content_negotiation()?);
+                continue;
+            }
+            if item == "error-mapping" && matches!(kind, BindingKind::Worker) {
+                if error_mapping.is_some() {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                error_mapping = Some(self.error_mapping()?);
+                continue;
+            }
+            if item == "static-worker-names" && !matches!(kind, BindingKind::HttpHandler) {
+                if static_worker_names {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                self.symbol(';')?;
+                static_worker_names = true;
+                continue;
+            }
+            let script = match item.as_str() {
+                "worker-name" => &mut scripts.worker_name,
+                "idempotency-key" => &mut scripts.idempotency_key,
+                "invocation-context" if !matches!(kind, BindingKind::HttpHandler) => {
+                    &mut scripts.invocation_context
+                }
+                "response" if !matches!(kind, BindingKind::HttpHandler) => &mut scripts.response,
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!("unexpected '{}' in binding of route {}", other, route_name),
+                    ))
+                }
+            };
+            if script.is_some() {
+                return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+            }
+            *script = Some(self.rib_block(route_name, &item)?);
+        }
+
+        let binding = match kind {
+            BindingKind::HttpHandler => GatewayBinding::HttpHandler(HttpHandlerBinding {
+                component_id,
+                worker_name: scripts.worker_name,
+                idempotency_key: scripts.idempotency_key,
+            }),
+            BindingKind::Worker
+            | BindingKind::FileServer
+            | BindingKind::WebSocket
+            | BindingKind::ServerSentEvents => {
+                let response = scripts
+                    .response
+                    .ok_or_else(|| format!("Binding of route {} has no response", route_name))?;
+
+                let worker_binding = WorkerBinding {
+                    component_id,
+                    worker_name: scripts.worker_name,
+                    idempotency_key: scripts.idempotency_key,
+                    response_mapping: ResponseMapping(response),
+                    invocation_context: scripts.invocation_context,
+                    traffic_mirror: None,
+                    worker_per_user,
+                    bootstrap,
+                    pagination,
+                    content_negotiation,
+                    error_mapping,
+                    static_worker_names,
+                };
+
+                match kind {
+                    BindingKind::FileServer => GatewayBinding::FileServer(worker_binding),
+                    BindingKind::WebSocket => GatewayBinding::WebSocket(worker_binding),
+                    BindingKind::ServerSentEvents => {
+                        GatewayBinding::ServerSentEvents(worker_binding)
+                    }
+                    _ => GatewayBinding::Default(worker_binding),
+                }
+            }
+        };
+
+        Ok(binding)
+    }
+
+    // Either `worker-per-user;`, or a block with the optional `prefix` and `claim` of the workers
+    fn worker_per_user(&mut self, route_name: &str) -> Result<WorkerPerUser, String> {
+        let mut prefix = None;
+        let mut claim = None;
+
+        if !self.try_symbol(';') {
+            self.symbol('{')?;
+
+            while !self.try_symbol('}') {
+                let start = self.position;
+                let item = self.word()?;
+                let is_duplicate = match item.as_str() {
+                    "prefix" => prefix.replace(self.string()?).is_some(),
+                    "claim" => claim.replace(self.string()?).is_some(),
+                    other => {
+                        return Err(self.error_at(
+                            start,
+                            &format!(
+                                "unexpected '{}' in worker-per-user of route {}",
+                                other, route_name
+                            ),
+                        ))
+                    }
+                };
+                if is_duplicate {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                self.symbol(';')?;
+            }
+        }
+
+        Ok(WorkerPerUser { prefix, claim })
+    }
+
+    // Either `pagination;`, or a block with the optional `default-limit` and `max-limit` of the pages
+    fn pagination(&mut self, route_name: &str) -> Result<Pagination, String> {
+        let mut default_limit = None;
+        let mut max_limit = None;
+
+        if !self.try_symbol(';') {
+            self.symbol('{')?;
+
+            while !self.try_symbol('}') {
+                let start = self.position;
+                let item = self.word()?;
+                let limit = match item.as_str() {
+                    "default-limit" => &mut default_limit,
+                    "max-limit" => &mut max_limit,
+                    other => {
+                        return Err(self.error_at(
+                            start,
+                            &format!(
+                                "unexpected '{}' in pagination of route {}",
+                                other, route_name
+                            ),
+                        ))
+                    }
+                };
+                if limit.is_some() {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+                }
+                let value_start = self.start();
+                let value = self.number()?;
+                *limit = Some(u32::try_from(value).map_err(|_| {
+                    self.error_at(value_start, &format!("limit {} is too large", value))
+                })?);
+                self.symbol(';')?;
+            }
+        }
+
+        Ok(Pagination {
+            default_limit,
+            max_limit,
+        })
+    }
+
+    // The media types of the responses, as strings, the first one being the default
+    fn content_negotiation(&mut self) -> Result<ContentNegotiation, String> {
+        let mut media_types = Vec::new();
+
+        while !self.try_symbol(';') {
+            let start = self.start();
+            let media_type = self.string()?;
+            media_types.push(
+                ResponseMediaType::from_str(&media_type)
+                    .map_err(|err| self.error_at(start, &err))?,
+            );
+        }
+
+        Ok(ContentNegotiation { media_types })
+    }
+
+    // A block of error cases, each with its status and an optional shape
+    fn error_mapping(&mut self) -> Result<ErrorMapping, String> {
+        self.symbol('{')?;
+
+        let mut cases = Vec::new();
+
+        while !self.try_symbol('}') {
+            let case = self.name("an error case")?;
+            let status_start = self.start();
+            let status = self.number()?;
+            let status = u16::try_from(status)
+                .map_err(|_| self.error_at(status_start, &format!("invalid status {}", status)))?;
+            let shape = if self.try_symbol(';') {
+                ErrorResponseShape::default()
+            } else {
+                let shape_start = self.start();
+                let shape = self.word()?;
+                let shape = ErrorResponseShape::from_str(&shape)
+                    .map_err(|err| self.error_at(shape_start, &err))?;
+                self.symbol(';')?;
+                shape
+            };
+
+            cases.push(ErrorCaseMapping {
+                case,
+                status,
+                shape,
+            });
+        }
+
+        Ok(ErrorMapping { cases })
+    }
+
+    // Rib scripts of the args (`arg { ... }`), environment variables (`env NAME { ... }`) and
+    // config variables (`config NAME { ... }`) of the worker of a binding, used when the request
+    // creates the worker
+    fn bootstrap(&mut self, route_name: &str) -> Result<WorkerBootstrap, String> {
+        self.symbol('{')?;
+
+        let mut args = Vec::new();
+        let mut env = BTreeMap::new();
+        let mut config = BTreeMap::new();
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            match item.as_str() {
+                "arg" => args.push(self.rib_block(route_name, "bootstrap arg")?),
+                "env" => {
+                    let name = self.name("an environment variable name")?;
+                    let value = self.rib_block(route_name, "bootstrap env")?;
+                    if env.insert(name.clone(), value).is_some() {
+                        return Err(self.error_at(start, &format!("duplicate env '{}'", name)));
+                    }
+                }
+                "config" => {
+                    let name = self.name("a config variable name")?;
+                    let value = self.rib_block(route_name, "bootstrap config")?;
+                    if config.insert(name.clone(), value).is_some() {
+                        return Err(self.error_at(start, &format!("duplicate config '{}'", name)));
+                    }
+                }
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!(
+                            "unexpected '{}' in bootstrap of route {}",
+                            other, route_name
+                        ),
+                    ))
+                }
+            }
+        }
+
+        Ok(WorkerBootstrap { args, env, config })
+    }
+
+    fn rate_limit(&mut self, route_name: &str) -> Result<HttpRateLimit, String> {
+        let start = self.start();
+        self.symbol('{')?;
+
+        let mut capacity = None;
+        let mut refill_interval_ms = None;
+        let mut key = None;
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            match item.as_str() {
+                "capacity" if capacity.is_none() => {
+                    capacity = Some(self.number()?);
+                    self.symbol(';')?;
+                }
+                "refill-interval-ms" if refill_interval_ms.is_none() => {
+                    refill_interval_ms = Some(self.number()?);
+                    self.symbol(';')?;
+                }
+                "key" if key.is_none() => {
+                    key = Some(self.rib_block(route_name, "rate-limit key")?);
+                }
+                "capacity" | "refill-interval-ms" | "key" => {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)))
+                }
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!(
+                            "unexpected '{}' in rate-limit of route {}",
+                            other, route_name
+                        ),
+                    ))
+                }
+            }
+        }
+
+        let capacity = capacity
+            .ok_or_else(|| format!("Rate limit of route {} has no capacity", route_name))?;
+        let capacity = u32::try_from(capacity).map_err(|_| {
+            format!(
+                "Capacity {} in rate-limit of route {} is too large",
+                capacity, route_name
+            )
+        })?;
+        let refill_interval_ms = refill_interval_ms.ok_or_else(|| {
+            format!(
+                "Rate limit of route {} has no refill-interval-ms",
+                route_name
+            )
+        })?;
+
+        HttpRateLimit::new(capacity, refill_interval_ms, key).map_err(|err| {
+            self.error_at(
+                start,
+                &format!("invalid rate-limit in {}: {}", route_name, err),
+            )
+        })
+    }
+
+    fn body_limits(&mut self, route_name: &str) -> Result<HttpBodyLimits, String> {
+        self.symbol('{')?;
+
+        let mut body_limits = HttpBodyLimits {
+            max_request_body_bytes: None,
+            max_response_body_bytes: None,
+            read_timeout_ms: None,
+        };
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            let is_duplicate = match item.as_str() {
+                "max-request-body-bytes" => body_limits
+                    .max_request_body_bytes
+                    .replace(self.number()?)
+                    .is_some(),
+                "max-response-body-bytes" => body_limits
+                    .max_response_body_bytes
+                    .replace(self.number()?)
+                    .is_some(),
+                "read-timeout-ms" => body_limits
+                    .read_timeout_ms
+                    .replace(self.number()?)
+                    .is_some(),
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!(
+                            "unexpected '{}' in body-limits of route {}",
+                            other, route_name
+                        ),
+                    ))
+                }
+            };
+            if is_duplicate {
+                return Err(self.error_at(start, &format!("duplicate '{}'", item)));
+            }
+            self.symbol(';')?;
+        }
+
+        Ok(body_limits)
+    }
+
+    fn response_cache(&mut self, route_name: &str) -> Result<HttpResponseCache, String> {
+        self.symbol('{')?;
+
+        let mut ttl_seconds = None;
+        let mut vary = vec![];
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            match item.as_str() {
+                "ttl-seconds" => {
+                    if ttl_seconds.replace(self.number()?).is_some() {
+                        return Err(self.error_at(start, "duplicate 'ttl-seconds'"));
+                    }
+                }
+                "vary" => vary.push(self.string()?),
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!(
+                            "unexpected '{}' in response-cache of route {}",
+                            other, route_name
+                        ),
+                    ))
+                }
+            }
+            self.symbol(';')?;
+        }
+
+        let ttl_seconds = ttl_seconds
+            .ok_or_else(|| format!("Response cache of route {} has no ttl-seconds", route_name))?;
+
+        Ok(HttpResponseCache { ttl_seconds, vary })
+    }
+
+    fn compression(&mut self, route_name: &str) -> Result<HttpCompression, String> {
+        if !self.try_symbol('{') {
+            let start = self.position;
+            let item = self.word()?;
+            if item != "off" {
+                return Err(self.error_at(
+                    start,
+                    &format!(
+                        "expected '{{' or 'off' after compression of route {}, found '{}'",
+                        route_name, item
+                    ),
+                ));
+            }
+            self.symbol(';')?;
+
+            return Ok(HttpCompression {
+                enabled: false,
+                ..HttpCompression::default()
+            });
+        }
+
+        let mut algorithms = vec![];
+        let mut min_size_bytes = None;
+        let mut content_types = vec![];
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            match item.as_str() {
+                "algorithm" => algorithms.push(self.string()?),
+                "min-size-bytes" => {
+                    if min_size_bytes.replace(self.number()?).is_some() {
+                        return Err(self.error_at(start, "duplicate 'min-size-bytes'"));
+                    }
+                }
+                "content-type" => content_types.push(self.string()?),
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!(
+                            "unexpected '{}' in compression of route {}",
+                            other, route_name
+                        ),
+                    ))
+                }
+            }
+            self.symbol(';')?;
+        }
+
+        let defaults = HttpCompression::default();
+
+        Ok(HttpCompression {
+            enabled: true,
+            algorithms: if algorithms.is_empty() {
+                defaults.algorithms
+            } else {
+                algorithms
+            },
+            min_size_bytes: min_size_bytes.unwrap_or(defaults.min_size_bytes),
+            content_types,
+        })
+    }
+
+    fn http_proxy_binding(&mut self, route_name: &str) -> Result<GatewayBinding, String> {
+        let upstream = self.string()?;
+        self.symbol('{')?;
+
+        let mut request_mapping = None;
+        let mut timeout_ms = None;
+
+        while !self.try_symbol('}') {
+            let start = self.position;
+            let item = self.word()?;
+            match item.as_str() {
+                "request-mapping" if request_mapping.is_none() => {
+                    request_mapping = Some(self.rib_block(route_name, &item)?);
+                }
+                "timeout-ms" if timeout_ms.is_none() => {
+                    timeout_ms = Some(self.number()?);
+                    self.symbol(';')?;
+                }
+                "request-mapping" | "timeout-ms" => {
+                    return Err(self.error_at(start, &format!("duplicate '{}'", item)))
+                }
+                other => {
+                    return Err(self.error_at(
+                        start,
+                        &format!("unexpected '{}' in binding of route {}", other, route_name),
+                    ))
+                }
+            }
+        }
+
+        Ok(GatewayBinding::HttpProxy(HttpProxyBinding {
+            upstream,
+            request_mapping,
+            timeout_ms,
+        }))
+    }
+
+    fn component_id(&mut self) -> Result<ComponentId, String> {
+        let start = self.start();
+        let text = self.string()?;
+        Uuid::parse_str(&text)
+            .map(ComponentId)
+            .map_err(|err| self.error_at(start, &format!("invalid component id: {}", err)))
+    }
+
+    /// Reads a `{ ... }` block and parses its content as Rib
+    fn rib_block(&mut self, route_name: &str, item: &str) -> Result<Expr, String> {
+        self.symbol('{')?;
+        let input = self.input;
+        let start = self.position;
+        let end = self.balanced_block_end()?;
+        let text = &input[start..end];
+        self.position = end + 1;
+
+        rib::from_string(text).map_err(|err| {
+            self.error_at(
+                start,
+                &format!("invalid Rib in {} of route {}: {}", item, route_name, err),
+            )
+        })
+    }
+
+    /// Finds the closing brace of a block whose opening brace has just been consumed,
+    /// skipping over braces in string literals but not in their `${...}` interpolations
+    fn balanced_block_end(&self) -> Result<usize, String> {
+        enum Context {
+            Code,
+            Str,
+        }
+
+        let mut stack = vec![Context::Code];
+        let mut chars = self.input[self.position..].char_indices().peekable();
+
+        while let Some((offset, c)) = chars.next() {
+            match (stack.last(), c) {
+                (Some(Context::Code), '{') => stack.push(Context::Code),
+                (Some(Context::Code), '}') => {
+                    stack.pop();
+                    if stack.is_empty() {
+                        return Ok(self.position + offset);
+                    }
+                }
+                (Some(Context::Code), '"') => stack.push(Context::Str),
+                (Some(Context::Str), '\\') => {
+                    chars.next();
+                }
+                (Some(Context::Str), '"') => {
+                    stack.pop();
+                }
+                (Some(Context::Str), '$') if matches!(chars.peek(), Some((_, '{'))) => {
+                    chars.next();
+                    stack.push(Context::Code);
+                }
+                _ => {}
+            }
+        }
+
+        Err(self.error("unterminated block"))
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            let rest = &self.input[self.position..];
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Position of the next token
+    fn start(&mut self) -> usize {
+        self.skip_trivia();
+        self.position
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_trivia();
+        self.input[self.position..].chars().next()
+    }
+
+    fn word(&mut self) -> Result<String, String> {
+        self.skip_trivia();
+        let rest = &self.input[self.position..];
+        let length = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(rest.len());
+        if length == 0 {
+            return Err(self.error("expected a keyword or name"));
+        }
+        self.position += length;
+        Ok(rest[..length].to_string())
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), String> {
+        let start = self.start();
+        match self.word() {
+            Ok(word) if word == keyword => Ok(()),
+            _ => Err(self.error_at(start, &format!("expected '{}'", keyword))),
+        }
+    }
+
+    /// A name given either as a bare word or as a string literal
+    fn name(&mut self, description: &str) -> Result<String, String> {
+        match self.peek() {
+            Some('"') => self.string(),
+            _ => self
+                .word()
+                .map_err(|_| self.error(&format!("expected {}", description))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        if self.peek() != Some('"') {
+            return Err(self.error("expected a string literal"));
+        }
+        let start = self.position;
+        let mut result = String::new();
+        let mut chars = self.input[self.position + 1..].char_indices();
+
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position += offset + 2;
+                    return Ok(result);
+                }
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => result.push('\n'),
+                    Some((_, 't')) => result.push('\t'),
+                    Some((_, escaped)) => result.push(escaped),
+                    None => break,
+                },
+                c => result.push(c),
+            }
+        }
+
+        Err(self.error_at(start, "unterminated string literal"))
+    }
+
+    fn number(&mut self) -> Result<u64, String> {
+        let start = self.start();
+        let word = self.word()?;
+        word.parse()
+            .map_err(|_| self.error_at(start, &format!("expected a number, found '{}'", word)))
+    }
+
+    fn symbol(&mut self, symbol: char) -> Result<(), String> {
+        if self.try_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", symbol)))
+        }
+    }
+
+    fn try_symbol(&mut self, symbol: char) -> bool {
+        if self.peek() == Some(symbol) {
+            self.position += symbol.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&mut self, message: &str) -> String {
+        self.skip_trivia();
+        self.error_at(self.position, message)
+    }
+
+    fn error_at(&self, position: usize, message: &str) -> String {
+        let before = &self.input[..position.min(self.input.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+        format!("Line {}, column {}: {}", line, column, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+
+    const COMPONENT_ID: &str = "e1f2b6a0-4c5d-4e6f-8a9b-0c1d2e3f4a5b";
+
+    fn parse(document: &str) -> Result<HttpApiDefinitionRequest, String> {
+        RibHttpApiDefinitionRequest(document.to_string()).to_http_api_definition_request()
+    }
+
+    #[test]
+    fn parses_routes_with_bindings_and_security() {
+        let document = format!(
+            r#"
+            // Shopping cart API
+            api shopping-cart version "0.0.1" {{
+              security my-security;
+
+              route get "/v1/cart/{{user-id}}" {{
+                security my-security;
+                concurrency-limit {{ max-in-flight 4; shed-body "busy"; }}
+                worker "{COMPONENT_ID}" version 2 {{
+                  worker-name {{ "cart-${{request.path.user-id}}" }}
+                  response {{
+                    let result = golem:it/api.{{get-cart-contents}}();
+                    {{ status: 200u64, body: result }}
+                  }}
+                }}
+              }}
+
+              route post "/v1/handler" {{
+                rate-limit {{ capacity 10; refill-interval-ms 1000; }}
+                http-handler "{COMPONENT_ID}" version 0 {{ }}
+              }}
+            }}
+            "#
+        );
+
+        let definition = parse(&document).unwrap();
+
+        let component_id = VersionedComponentId {
+            component_id: ComponentId(Uuid::parse_str(COMPONENT_ID).unwrap()),
+            version: 2,
+        };
+
+        assert_eq!(definition.id, ApiDefinitionId("shopping-cart".to_string()));
+        assert_eq!(definition.version, ApiVersion("0.0.1".to_string()));
+        assert!(definition.draft);
+        assert_eq!(
+            definition.security,
+            Some(vec![SecuritySchemeReference::new(
+                "my-security".to_string()
+            )])
+        );
+        assert_eq!(
+            definition.routes[0],
+            RouteRequest {
+                method: MethodPattern::Get,
+                path: AllPathPatterns::parse("/v1/cart/{user-id}").unwrap(),
+                binding: GatewayBinding::Default(WorkerBinding {
+                    component_id,
+                    worker_name: Some(
+                        rib::from_string(r#""cart-${request.path.user-id}""#).unwrap()
+                    ),
+                    idempotency_key: None,
+                    response_mapping: ResponseMapping(
+                        rib::from_string(
+                            r#"let result = golem:it/api.{get-cart-contents}();
+                    { status: 200u64, body: result }"#
+                        )
+                        .unwrap()
+                    ),
+                    invocation_context: None,
+                    traffic_mirror: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: false,
+                }),
+                cors: None,
+                security: Some(SecuritySchemeReference::new("my-security".to_string())),
+                concurrency_limit: Some(HttpConcurrencyLimit {
+                    max_in_flight: 4,
+                    max_queued: 0,
+                    queue_timeout_ms: None,
+                    shed_status: None,
+                    shed_body: Some("busy".to_string()),
+                    retry_after: None,
+                }),
+                rate_limit: None,
+                body_limits: None,
+                response_cache: None,
+                transforms: None,
+                conditions: None,
+                compression: None,
+            }
+        );
+        assert!(matches!(
+            definition.routes[1].binding,
+            GatewayBinding::HttpHandler(_)
+        ));
+        assert_eq!(
+            definition.routes[1].rate_limit,
+            Some(HttpRateLimit::new(10, 1000, None).unwrap())
+        );
+    }
+
+    #[test]
+    fn reports_location_of_invalid_items() {
+        let document = format!(
+            r#"api test version "0.0.1" {{
+  route get "/test" {{
+    http-handler "{COMPONENT_ID}" version 0 {{
+      response {{ "hello" }}
+    }}
+  }}
+}}"#
+        );
+
+        assert_eq!(
+            parse(&document),
+            Err(
+                "Line 4, column 7: unexpected 'response' in binding of route GET /test".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_routes_without_binding() {
+        let document = r#"api test version "0.0.1" { route get "/test" { } }"#;
+
+        assert_eq!(
+            parse(document),
+            Err("Route GET /test has no binding".to_string())
+        );
+    }
+}
@@ -144,6 +144,13 @@ mod tests {
             idempotency_key: None,
             response_mapping: ResponseMapping(Expr::literal("")),
             invocation_context: None,
+            traffic_mirror: None,
+            worker_per_user: None,
+            bootstrap: None,
+            pagination: None,
+            content_negotiation: None,
+            error_mapping: None,
+            static_worker_names: false,
         };
 
         Route {
@@ -164,6 +171,13 @@ mod tests {
             idempotency_key: None,
             response_mapping: ResponseMapping(Expr::literal("")),
             invocation_context: None,
+            traffic_mirror: None,
+            worker_per_user: None,
+            bootstrap: None,
+            pagination: None,
+            content_negotiation: None,
+            error_mapping: None,
+            static_worker_names: false,
         };
 
         Route {
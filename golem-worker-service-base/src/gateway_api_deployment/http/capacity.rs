@@ -0,0 +1,186 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+
+const DEFAULT_QUEUE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The worker invocation capacity of a gateway for a site, part of which can be reserved for
+/// priority lanes.
+///
+/// At most `maxInFlight` requests of the site invoke workers at the same time on a gateway.
+/// Every lane reserves `reservedPercent` percent of this capacity for its requests, which also
+/// use the unreserved capacity when their reservation is in use. The other requests only use the
+/// unreserved capacity, and are shed with `503 Service Unavailable` if they cannot get any of it
+/// within `queueTimeoutMs`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentCapacity {
+    pub max_in_flight: u32,
+    pub queue_timeout_ms: Option<u64>,
+    #[serde(default)]
+    #[oai(default)]
+    pub lanes: Vec<PriorityLane>,
+}
+
+/// A priority lane of a site, made of the requests of its routes (as `METHOD /path`) and of the
+/// requests authenticated with its API keys (by name)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct PriorityLane {
+    pub name: String,
+    pub reserved_percent: u32,
+    #[serde(default)]
+    #[oai(default)]
+    pub routes: Vec<String>,
+    #[serde(default)]
+    #[oai(default)]
+    pub api_keys: Vec<String>,
+}
+
+impl ApiDeploymentCapacity {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_in_flight == 0 {
+            return Err("max-in-flight must be greater than 0".to_string());
+        }
+
+        let mut names = HashSet::new();
+        for lane in &self.lanes {
+            if lane.name.is_empty() {
+                return Err("priority lanes must have a name".to_string());
+            }
+
+            if !names.insert(lane.name.as_str()) {
+                return Err(format!(
+                    "more than one priority lane is named '{}'",
+                    lane.name
+                ));
+            }
+
+            if lane.routes.is_empty() && lane.api_keys.is_empty() {
+                return Err(format!(
+                    "priority lane '{}' has neither routes nor API keys",
+                    lane.name
+                ));
+            }
+
+            if lane.reserved_percent == 0 || self.reserved(lane) == 0 {
+                return Err(format!(
+                    "priority lane '{}' does not reserve any capacity",
+                    lane.name
+                ));
+            }
+        }
+
+        let reserved_percent: u32 = self.lanes.iter().map(|lane| lane.reserved_percent).sum();
+        if reserved_percent > 100 {
+            return Err(format!(
+                "priority lanes reserve {}% of the capacity, more than all of it",
+                reserved_percent
+            ));
+        }
+
+        Ok(())
+    }
+
+    // The number of requests in flight reserved for a lane, rounded down
+    pub fn reserved(&self, lane: &PriorityLane) -> u32 {
+        (self.max_in_flight as u64 * lane.reserved_percent as u64 / 100) as u32
+    }
+
+    pub fn unreserved(&self) -> u32 {
+        let reserved: u32 = self.lanes.iter().map(|lane| self.reserved(lane)).sum();
+        self.max_in_flight.saturating_sub(reserved)
+    }
+
+    // The lane of a request, by its route first, then by the name of its API key
+    pub fn lane(&self, route: &str, api_key_name: Option<&str>) -> Option<&PriorityLane> {
+        self.lanes
+            .iter()
+            .find(|lane| lane.routes.iter().any(|lane_route| lane_route == route))
+            .or_else(|| {
+                api_key_name.and_then(|api_key_name| {
+                    self.lanes
+                        .iter()
+                        .find(|lane| lane.api_keys.iter().any(|key| key == api_key_name))
+                })
+            })
+    }
+
+    pub fn queue_timeout(&self) -> Duration {
+        self.queue_timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_QUEUE_TIMEOUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn lanes_reserve_part_of_the_capacity() {
+        let capacity = ApiDeploymentCapacity {
+            max_in_flight: 10,
+            queue_timeout_ms: None,
+            lanes: vec![
+                PriorityLane {
+                    name: "admin".to_string(),
+                    reserved_percent: 25,
+                    routes: vec!["POST /admin/reindex".to_string()],
+                    api_keys: vec![],
+                },
+                PriorityLane {
+                    name: "internal".to_string(),
+                    reserved_percent: 10,
+                    routes: vec![],
+                    api_keys: vec!["ops".to_string()],
+                },
+            ],
+        };
+        assert!(capacity.validate().is_ok());
+
+        assert_eq!(capacity.reserved(&capacity.lanes[0]), 2);
+        assert_eq!(capacity.reserved(&capacity.lanes[1]), 1);
+        assert_eq!(capacity.unreserved(), 7);
+
+        assert_eq!(
+            capacity
+                .lane("POST /admin/reindex", Some("ops"))
+                .map(|lane| lane.name.as_str()),
+            Some("admin")
+        );
+        assert_eq!(
+            capacity
+                .lane("GET /cart", Some("ops"))
+                .map(|lane| lane.name.as_str()),
+            Some("internal")
+        );
+        assert_eq!(capacity.lane("GET /cart", None), None);
+
+        let mut overbooked = capacity.clone();
+        overbooked.lanes[0].reserved_percent = 95;
+        assert!(overbooked.validate().is_err());
+
+        let mut too_small = capacity;
+        too_small.max_in_flight = 5;
+        assert!(too_small.validate().is_err());
+    }
+}
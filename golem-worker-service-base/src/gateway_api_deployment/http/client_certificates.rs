@@ -0,0 +1,538 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use percent_encoding::percent_decode_str;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use x509_parser::certificate::X509Certificate;
+use x509_parser::pem::Pem;
+use x509_parser::prelude::FromDer;
+use x509_parser::time::ASN1Time;
+use x509_parser::x509::SubjectPublicKeyInfo;
+
+// TLS is terminated in front of the gateway, which forwards the verified client certificate
+// in this header as a URL encoded PEM (such as `$ssl_client_escaped_cert` of nginx), followed by
+// the certificates of the intermediate certificate authorities which issued it, if any.
+// The header is only accepted from the trusted proxies of the gateway, and dropped from the
+// requests of other peers.
+pub const CLIENT_CERTIFICATE_HEADER: &str = "x-client-cert";
+
+const CERTIFICATE_PEM_LABEL: &str = "CERTIFICATE";
+
+// The number of intermediate certificate authorities followed from a client certificate to one
+// of the certificate authorities of the bundle
+const MAX_INTERMEDIATE_CERTIFICATES: usize = 4;
+
+/// Client certificate (mutual TLS) authentication of a deployment.
+///
+/// The certificates presented by the clients have to be issued by one of the certificate
+/// authorities of the bundle, directly or through intermediate certificate authorities forwarded
+/// along with them. The details of the certificate of a request, such as its subject,
+/// are available to bindings as `request.client_certificate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentClientCertificates {
+    /// When enabled, requests without a client certificate are rejected.
+    /// Requests presenting an invalid certificate are always rejected.
+    #[serde(default)]
+    #[oai(default)]
+    pub required: bool,
+    /// PEM encoded certificates of the trusted certificate authorities
+    pub ca_bundle: String,
+}
+
+// A client certificate issued by one of the certificate authorities of the deployment
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCertificate {
+    pub subject: String,
+    pub issuer: String,
+    pub serial_number: String,
+    pub fingerprint: String,
+    pub not_after: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ClientCertificate {
+    fn new(certificate: &X509Certificate, der: &[u8]) -> Self {
+        ClientCertificate {
+            subject: certificate.subject().to_string(),
+            issuer: certificate.issuer().to_string(),
+            serial_number: certificate.raw_serial_as_string(),
+            fingerprint: hex::encode(Sha256::digest(der)),
+            not_after: chrono::DateTime::from_timestamp(
+                certificate.validity().not_after.timestamp(),
+                0,
+            ),
+        }
+    }
+
+    // The certificate as available to Rib as `request.client_certificate`
+    pub fn to_json(&self) -> Value {
+        json!({
+            "subject": self.subject,
+            "issuer": self.issuer,
+            "serial_number": self.serial_number,
+            "fingerprint": self.fingerprint,
+            "not_after": self.not_after.map(|not_after| not_after.to_rfc3339())
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientCertificateError {
+    Missing,
+    Invalid(String),
+}
+
+impl Display for ClientCertificateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientCertificateError::Missing => write!(f, "Client certificate required"),
+            ClientCertificateError::Invalid(err) => {
+                write!(f, "Invalid client certificate: {}", err)
+            }
+        }
+    }
+}
+
+impl ApiDeploymentClientCertificates {
+    pub fn validate(&self) -> Result<(), String> {
+        ClientCertificateVerifier::try_from(self).map(|_| ())
+    }
+}
+
+// A certificate authority of the bundle of a deployment, with what is needed to verify the
+// certificates it issued
+#[derive(Debug, Clone)]
+struct CertificateAuthority {
+    subject: Vec<u8>,
+    public_key: Vec<u8>,
+    not_before: ASN1Time,
+    not_after: ASN1Time,
+    path_length_constraint: Option<u32>,
+}
+
+impl CertificateAuthority {
+    fn has_issued(&self, certificate: &X509Certificate, intermediates: usize) -> bool {
+        let now = ASN1Time::now();
+
+        self.subject == certificate.issuer().as_raw()
+            && self.not_before <= now
+            && now <= self.not_after
+            && allows_intermediates(self.path_length_constraint, intermediates)
+            && SubjectPublicKeyInfo::from_der(&self.public_key).is_ok_and(|(_, public_key)| {
+                certificate.verify_signature(Some(&public_key)).is_ok()
+            })
+    }
+}
+
+/// Verifies the client certificates of the requests of a deployment, with the CA bundle of its
+/// client certificate authentication parsed once, when the configuration of the deployment is
+/// loaded by the gateway.
+#[derive(Debug, Clone)]
+pub struct ClientCertificateVerifier {
+    required: bool,
+    certificate_authorities: Vec<CertificateAuthority>,
+}
+
+impl TryFrom<&ApiDeploymentClientCertificates> for ClientCertificateVerifier {
+    type Error = String;
+
+    fn try_from(value: &ApiDeploymentClientCertificates) -> Result<Self, Self::Error> {
+        let pems = certificates(value.ca_bundle.as_bytes())
+            .map_err(|err| format!("Invalid CA bundle: {}", err))?;
+
+        if pems.is_empty() {
+            return Err("The CA bundle has to contain at least one certificate".to_string());
+        }
+
+        let mut certificate_authorities = Vec::with_capacity(pems.len());
+
+        for pem in &pems {
+            let certificate = pem
+                .parse_x509()
+                .map_err(|err| format!("Invalid certificate in the CA bundle: {}", err))?;
+
+            let path_length_constraint =
+                certificate_authority_constraints(&certificate).map_err(|err| {
+                    format!(
+                        "Certificate {} of the CA bundle is {}",
+                        certificate.subject(),
+                        err
+                    )
+                })?;
+
+            certificate_authorities.push(CertificateAuthority {
+                subject: certificate.subject().as_raw().to_vec(),
+                public_key: certificate.public_key().raw.to_vec(),
+                not_before: certificate.validity().not_before,
+                not_after: certificate.validity().not_after,
+                path_length_constraint,
+            });
+        }
+
+        Ok(ClientCertificateVerifier {
+            required: value.required,
+            certificate_authorities,
+        })
+    }
+}
+
+impl ClientCertificateVerifier {
+    // Verifies the client certificate forwarded with the request, if there is one
+    pub fn verify(
+        &self,
+        forwarded_certificate: Option<&str>,
+    ) -> Result<Option<ClientCertificate>, ClientCertificateError> {
+        let forwarded_certificate = match forwarded_certificate
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+        {
+            Some(value) => value,
+            None if self.required => return Err(ClientCertificateError::Missing),
+            None => return Ok(None),
+        };
+
+        let pem_bytes = percent_decode_str(forwarded_certificate).collect::<Vec<u8>>();
+
+        let pems = certificates(&pem_bytes)
+            .ok()
+            .filter(|pems| !pems.is_empty())
+            .ok_or_else(|| ClientCertificateError::Invalid("malformed PEM".to_string()))?;
+
+        if pems.len() > MAX_INTERMEDIATE_CERTIFICATES + 1 {
+            return Err(ClientCertificateError::Invalid(
+                "too many intermediate certificates".to_string(),
+            ));
+        }
+
+        let certificates = pems
+            .iter()
+            .map(|pem| pem.parse_x509())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ClientCertificateError::Invalid(err.to_string()))?;
+
+        let (certificate, intermediates) = certificates
+            .split_first()
+            .expect("The forwarded certificates are not empty");
+
+        if !certificate.validity().is_valid() {
+            return Err(ClientCertificateError::Invalid(
+                "expired or not yet valid".to_string(),
+            ));
+        }
+
+        if self.is_trusted(certificate, intermediates) {
+            Ok(Some(ClientCertificate::new(certificate, &pems[0].contents)))
+        } else {
+            Err(ClientCertificateError::Invalid(
+                "not issued by a trusted certificate authority".to_string(),
+            ))
+        }
+    }
+
+    // Follows the issuers of the certificate through the intermediate certificate authorities,
+    // until one issued by a certificate authority of the bundle
+    fn is_trusted(&self, certificate: &X509Certificate, intermediates: &[X509Certificate]) -> bool {
+        let mut current = certificate;
+
+        for followed_intermediates in 0..=MAX_INTERMEDIATE_CERTIFICATES {
+            if self
+                .certificate_authorities
+                .iter()
+                .any(|certificate_authority| {
+                    certificate_authority.has_issued(current, followed_intermediates)
+                })
+            {
+                return true;
+            }
+
+            match intermediates
+                .iter()
+                .find(|intermediate| is_issuer(intermediate, current, followed_intermediates))
+            {
+                Some(intermediate) => current = intermediate,
+                None => return false,
+            }
+        }
+
+        false
+    }
+}
+
+// Whether the intermediate certificate authority issued the certificate, with the given number of
+// intermediate certificate authorities between the certificate and the client certificate
+fn is_issuer(
+    intermediate: &X509Certificate,
+    certificate: &X509Certificate,
+    intermediates: usize,
+) -> bool {
+    intermediate.subject().as_raw() == certificate.issuer().as_raw()
+        && intermediate.validity().is_valid()
+        && certificate_authority_constraints(intermediate).is_ok_and(|path_length_constraint| {
+            allows_intermediates(path_length_constraint, intermediates)
+        })
+        && certificate
+            .verify_signature(Some(intermediate.public_key()))
+            .is_ok()
+}
+
+// The path length constraint of a certificate authority, which has to be marked as one in its
+// basic constraints, and allowed to sign certificates if the usage of its key is limited
+fn certificate_authority_constraints(certificate: &X509Certificate) -> Result<Option<u32>, String> {
+    let basic_constraints = certificate
+        .basic_constraints()
+        .map_err(|err| format!("invalid: {}", err))?
+        .filter(|basic_constraints| basic_constraints.value.ca)
+        .ok_or_else(|| "not a certificate authority".to_string())?;
+
+    let key_usage = certificate
+        .key_usage()
+        .map_err(|err| format!("invalid: {}", err))?;
+
+    if key_usage.is_some_and(|key_usage| !key_usage.value.key_cert_sign()) {
+        return Err("not allowed to sign certificates".to_string());
+    }
+
+    Ok(basic_constraints.value.path_len_constraint)
+}
+
+fn allows_intermediates(path_length_constraint: Option<u32>, intermediates: usize) -> bool {
+    path_length_constraint
+        .is_none_or(|path_length_constraint| intermediates <= path_length_constraint as usize)
+}
+
+fn certificates(pem_bytes: &[u8]) -> Result<Vec<Pem>, String> {
+    Pem::iter_from_buffer(pem_bytes)
+        .filter(|pem| !matches!(pem, Ok(pem) if pem.label != CERTIFICATE_PEM_LABEL))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    const CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBhjCCAS2gAwIBAgIUMMOwI2e/GjH2Z4f7QYKJksTu3vAwCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNR29sZW0gVGVzdCBDQTAgFw0yNjEwMTYxMTE5MDlaGA8yMTI2
+MDkyMjExMTkwOVowGDEWMBQGA1UEAwwNR29sZW0gVGVzdCBDQTBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABGetYkB8Wk7J6lLpXZMnKhcvQAHLquw5Y11HjisankBP
+Pah0hwF+8wbccz3rSDgj0mhnvy/O38AiBFKv8RdJVdGjUzBRMB0GA1UdDgQWBBTE
+Bvnjg2MjwDzRhEYbo4HBSMfkhDAfBgNVHSMEGDAWgBTEBvnjg2MjwDzRhEYbo4HB
+SMfkhDAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0cAMEQCIBEYF/viKkLu
+jrIe4tgkrmCD8KnNNlA4BBzM/OcYKpBhAiBk7LF0//Qw6vEqm4BVCwrLpM9LVpva
+mfE2Iq2u1GLMGg==
+-----END CERTIFICATE-----
+";
+
+    const OTHER_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBfTCCASOgAwIBAgIUK4csC/q/NKuY9YNhsNAXUUSb/N4wCgYIKoZIzj0EAwIw
+EzERMA8GA1UEAwwIT3RoZXIgQ0EwIBcNMjYxMDE2MTExOTA5WhgPMjEyNjA5MjIx
+MTE5MDlaMBMxETAPBgNVBAMMCE90aGVyIENBMFkwEwYHKoZIzj0CAQYIKoZIzj0D
+AQcDQgAEN+uDnHSkpDa0uRkdezLjyi36Fs4fVRlCVbTAHD+ewz5zvUqEfEBFoVPp
+aMQr52ZBOjXfyIgacnN4bOZe2YLqHaNTMFEwHQYDVR0OBBYEFKlI0OeiiLP/Lzo7
+dEVd4J0dgD4zMB8GA1UdIwQYMBaAFKlI0OeiiLP/Lzo7dEVd4J0dgD4zMA8GA1Ud
+EwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIgP9DGrO81eGP17k9vPwXZFhGO
+/pwT7LkjnL03XS2K1dUCIQCO4CFEUfGb2hD9RsJshlNVQDuX161Sldb7ya1EeglM
+4g==
+-----END CERTIFICATE-----
+";
+
+    // Issued by CA for `CN=partner.example.com, O=Partner`
+    const CLIENT: &str = "-----BEGIN CERTIFICATE-----
+MIIBjjCCATSgAwIBAgIUKGWgx/kqvuL4djtSBSquCMymbPwwCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNR29sZW0gVGVzdCBDQTAgFw0yNjEwMTYxMTE5MDlaGA8yMTI2
+MDkyMjExMTkwOVowMDEcMBoGA1UEAwwTcGFydG5lci5leGFtcGxlLmNvbTEQMA4G
+A1UECgwHUGFydG5lcjBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABOf7p3GQG8AX
+csJdtCsq3YabFUNkjKVA7cKaJcodUHY8WOgdrlfXC54hI5VXpKCg5P9zf8GP+Nrt
+wwjP6aMTdLijQjBAMB0GA1UdDgQWBBTv76Z13yuadv1paWFIgLYpOeudTTAfBgNV
+HSMEGDAWgBTEBvnjg2MjwDzRhEYbo4HBSMfkhDAKBggqhkjOPQQDAgNIADBFAiEA
+9LA3MYWKHgPWQGW19U7NhiXkTFEOwmjV99ToPefBQQMCIETgMfkKTiszbxoTLw7t
+wTnKuP35dlaqaT6/W06a4gz8
+-----END CERTIFICATE-----
+";
+
+    const ROOT_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBojCCAUegAwIBAgIUPPM6Hb2w3km8H0lZyj9OAmGX1T0wCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSR29sZW0gVGVzdCBSb290IENBMCAXDTI2MTAxNjE2MzY0N1oY
+DzIxMjYwOTIyMTYzNjQ3WjAdMRswGQYDVQQDDBJHb2xlbSBUZXN0IFJvb3QgQ0Ew
+WTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAARW6hhM8ltns8ma+WbP5JH2EKeWkWbx
+NMDG7UGy9odlUHx56XQeLf6FjeV3l9zXjRqGPTESXsuVd/Ntf0+LlFUCo2MwYTAd
+BgNVHQ4EFgQUIb53Vekrf+ey+limMFZn0BEjVsgwHwYDVR0jBBgwFoAUIb53Vekr
+f+ey+limMFZn0BEjVsgwDwYDVR0TAQH/BAUwAwEB/zAOBgNVHQ8BAf8EBAMCAQYw
+CgYIKoZIzj0EAwIDSQAwRgIhAOf7jPlm/dpaXx76AtP/0yGibB6/1t2UQHAhmORp
+U5IRAiEAioEA8OT/5YVj9/2tGQPuQqy5X8Yd+9p54bgcg/nWYmU=
+-----END CERTIFICATE-----
+";
+
+    // Issued by ROOT_CA
+    const INTERMEDIATE_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBqDCCAU+gAwIBAgIUDIYGaGa5gcq4KWmo84Fo+t//ItUwCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSR29sZW0gVGVzdCBSb290IENBMCAXDTI2MTAxNjE2MzY0N1oY
+DzIxMjYwOTIyMTYzNjQ3WjAlMSMwIQYDVQQDDBpHb2xlbSBUZXN0IEludGVybWVk
+aWF0ZSBDQTBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABODYks7alw0p0OvYsUUj
+p8aOyW7RuuFIN/Vk1p2KdwKmY8mQ97rirNh5HV+PPx/HdtC/tiKhB9KMgVj3oJUU
+VMOjYzBhMA8GA1UdEwEB/wQFMAMBAf8wDgYDVR0PAQH/BAQDAgEGMB0GA1UdDgQW
+BBTwTUwKqT9Xp9wWbeinHlJhe5GbKTAfBgNVHSMEGDAWgBQhvndV6St/57L6WKYw
+VmfQESNWyDAKBggqhkjOPQQDAgNHADBEAiAqH3vr+M20OeKiABL0i0ccabYYVnHA
+8ybttXoP9rA1wgIgM4kkyjpj/SvHJgyzot7yskekrHmjGDJrEMv96iPNKEk=
+-----END CERTIFICATE-----
+";
+
+    // Issued by INTERMEDIATE_CA for `CN=service.example.com, O=Partner`
+    const SERVICE: &str = "-----BEGIN CERTIFICATE-----
+MIIBqTCCAU+gAwIBAgIUXaEw9RE1NWNZgxFyz77CqvS/zrIwCgYIKoZIzj0EAwIw
+JTEjMCEGA1UEAwwaR29sZW0gVGVzdCBJbnRlcm1lZGlhdGUgQ0EwIBcNMjYxMDE2
+MTYzNjQ3WhgPMjEyNjA5MjIxNjM2NDdaMDAxHDAaBgNVBAMME3NlcnZpY2UuZXhh
+bXBsZS5jb20xEDAOBgNVBAoMB1BhcnRuZXIwWTATBgcqhkjOPQIBBggqhkjOPQMB
+BwNCAARSAZqbktVYSY9xBnhN1OAcN4jfJ2vcFJD8b2oCecNofml44K4ky2R2dyBK
+nXzi+iv1711K/XpQhAmBDxoexonto1AwTjAMBgNVHRMBAf8EAjAAMB0GA1UdDgQW
+BBTIyw7qj+zrHTUa1JcvgpA9brsomzAfBgNVHSMEGDAWgBTwTUwKqT9Xp9wWbein
+HlJhe5GbKTAKBggqhkjOPQQDAgNIADBFAiA//Kemc/eE3HnBi+QucZrcmRXEZS7l
+ZB6ZARntCrLXmgIhALaqLpH6+BHAnI47zahKID9NEDD96ay2b5LAWryvzQe1
+-----END CERTIFICATE-----
+";
+
+    // Issued by ROOT_CA, but not marked as a certificate authority
+    const NOT_A_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBhDCCASqgAwIBAgIUDIYGaGa5gcq4KWmo84Fo+t//ItYwCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSR29sZW0gVGVzdCBSb290IENBMCAXDTI2MTAxNjE2MzY0N1oY
+DzIxMjYwOTIyMTYzNjQ3WjATMREwDwYDVQQDDAhOb3QgQSBDQTBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABPQqcLNunwLzfBZZ2iwQ3Juo3Q3PHhMsVhszT141tip6
+FXPHEeRqvZJmJxRnBq4liB2DtDOgTJGJSXPXq4CHYJKjUDBOMAwGA1UdEwEB/wQC
+MAAwHQYDVR0OBBYEFBsdqIT+g9H6pQ7t7AtL1Qq+V2DHMB8GA1UdIwQYMBaAFCG+
+d1XpK3/nsvpYpjBWZ9ARI1bIMAoGCCqGSM49BAMCA0gAMEUCIEoFc/z8Su0UtMUz
+ZGmPAX1YhDXHVkPgEVm9uKqHObZSAiEA2kb877Ka96ah0Fcv96L1yI5kZIXXX5Wr
+IoEHCrFA9GI=
+-----END CERTIFICATE-----
+";
+
+    // Issued by NOT_A_CA for `CN=forged.example.com, O=Partner`
+    const FORGED: &str = "-----BEGIN CERTIFICATE-----
+MIIBlTCCATygAwIBAgIUXIRYYYhIu+BmtogNTbfdqUnwc+IwCgYIKoZIzj0EAwIw
+EzERMA8GA1UEAwwITm90IEEgQ0EwIBcNMjYxMDE2MTYzNjQ3WhgPMjEyNjA5MjIx
+NjM2NDdaMC8xGzAZBgNVBAMMEmZvcmdlZC5leGFtcGxlLmNvbTEQMA4GA1UECgwH
+UGFydG5lcjBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABBmtyiRMChMDIJfPYZIc
+4duSjgUlhxDiO1dto0yuARo3lqNdG6o+U/SSCMaBJF0AYe71dDqHEgMptz8BimU7
+IeejUDBOMAwGA1UdEwEB/wQCMAAwHQYDVR0OBBYEFNI+JaFbjfUKM6myIGBLKRwi
+LPYGMB8GA1UdIwQYMBaAFBsdqIT+g9H6pQ7t7AtL1Qq+V2DHMAoGCCqGSM49BAMC
+A0cAMEQCIB4FuIX8DLIptpgljj+EwqL2jb6IqaAn+SUF3/CuBh+sAiB8MSmmTa4B
+esgo1G9Wl51w3nALwy7T1qxrIFEhj0usaw==
+-----END CERTIFICATE-----
+";
+
+    fn forwarded(pem: &str) -> String {
+        percent_encoding::utf8_percent_encode(pem, percent_encoding::NON_ALPHANUMERIC).to_string()
+    }
+
+    fn verifier(required: bool, ca_bundle: &str) -> ClientCertificateVerifier {
+        ClientCertificateVerifier::try_from(&ApiDeploymentClientCertificates {
+            required,
+            ca_bundle: ca_bundle.to_string(),
+        })
+        .unwrap()
+    }
+
+    fn untrusted() -> ClientCertificateError {
+        ClientCertificateError::Invalid("not issued by a trusted certificate authority".to_string())
+    }
+
+    #[test]
+    fn client_certificates_are_verified_against_the_ca_bundle() {
+        let client_certificates = verifier(true, &format!("{}{}", OTHER_CA, CA));
+
+        let certificate = client_certificates
+            .verify(Some(&forwarded(CLIENT)))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(certificate.subject, "CN=partner.example.com, O=Partner");
+        assert_eq!(certificate.issuer, "CN=Golem Test CA");
+
+        assert_eq!(
+            client_certificates.verify(None),
+            Err(ClientCertificateError::Missing)
+        );
+
+        let optional_client_certificates = verifier(false, OTHER_CA);
+
+        assert_eq!(optional_client_certificates.verify(None), Ok(None));
+        assert_eq!(
+            optional_client_certificates.verify(Some(&forwarded(CLIENT))),
+            Err(untrusted())
+        );
+    }
+
+    #[test]
+    fn client_certificates_are_verified_through_intermediate_certificate_authorities() {
+        let client_certificates = verifier(true, ROOT_CA);
+
+        let certificate = client_certificates
+            .verify(Some(&forwarded(&format!("{}{}", SERVICE, INTERMEDIATE_CA))))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(certificate.subject, "CN=service.example.com, O=Partner");
+        assert_eq!(certificate.issuer, "CN=Golem Test Intermediate CA");
+
+        assert_eq!(
+            client_certificates.verify(Some(&forwarded(SERVICE))),
+            Err(untrusted())
+        );
+
+        // The intermediate certificate authority can be in the bundle as well
+        assert!(verifier(true, INTERMEDIATE_CA)
+            .verify(Some(&forwarded(SERVICE)))
+            .is_ok());
+    }
+
+    #[test]
+    fn only_certificate_authorities_can_issue_client_certificates() {
+        assert_eq!(
+            verifier(true, ROOT_CA).verify(Some(&forwarded(&format!("{}{}", FORGED, NOT_A_CA)))),
+            Err(untrusted())
+        );
+
+        assert!(ApiDeploymentClientCertificates {
+            required: true,
+            ca_bundle: NOT_A_CA.to_string(),
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn invalid_ca_bundles_are_rejected() {
+        assert_eq!(
+            ApiDeploymentClientCertificates {
+                required: true,
+                ca_bundle: format!("{}{}", ROOT_CA, CA),
+            }
+            .validate(),
+            Ok(())
+        );
+
+        assert!(ApiDeploymentClientCertificates {
+            required: true,
+            ca_bundle: "not a certificate".to_string(),
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::HeaderName;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// Dead-letter queue of a deployment.
+///
+/// Requests of the site whose worker invocation failed are kept along with the error,
+/// so that they can be inspected and replayed once the cause of the failure is fixed.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentDeadLetterQueue {
+    /// Headers not stored with the requests, such as credentials.
+    /// Requests are replayed without them.
+    #[serde(default)]
+    #[oai(default)]
+    pub redacted_headers: Vec<String>,
+    /// Upper bound of the stored request bodies in bytes.
+    /// Larger requests are stored without their body, and cannot be replayed.
+    pub max_body_size: Option<u64>,
+}
+
+impl ApiDeploymentDeadLetterQueue {
+    pub fn validate(&self) -> Result<(), String> {
+        for header in &self.redacted_headers {
+            HeaderName::from_bytes(header.as_bytes())
+                .map_err(|_| format!("Invalid redacted header name '{}'", header))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_redacted(&self, header: &HeaderName) -> bool {
+        self.redacted_headers
+            .iter()
+            .any(|redacted| redacted.eq_ignore_ascii_case(header.as_str()))
+    }
+
+    pub fn keeps_body_of_size(&self, size: usize) -> bool {
+        self.max_body_size
+            .is_none_or(|max_body_size| size as u64 <= max_body_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn redacted_headers_are_matched_case_insensitively() {
+        let dead_letter_queue = ApiDeploymentDeadLetterQueue {
+            redacted_headers: vec!["Authorization".to_string()],
+            max_body_size: Some(4),
+        };
+
+        assert_eq!(dead_letter_queue.validate(), Ok(()));
+        assert!(dead_letter_queue.is_redacted(&http::header::AUTHORIZATION));
+        assert!(!dead_letter_queue.is_redacted(&http::header::CONTENT_TYPE));
+        assert!(dead_letter_queue.keeps_body_of_size(4));
+        assert!(!dead_letter_queue.keeps_body_of_size(5));
+    }
+
+    #[test]
+    fn invalid_redacted_headers_are_rejected() {
+        let dead_letter_queue = ApiDeploymentDeadLetterQueue {
+            redacted_headers: vec!["not a header".to_string()],
+            max_body_size: None,
+        };
+
+        assert!(dead_letter_queue.validate().is_err());
+    }
+}
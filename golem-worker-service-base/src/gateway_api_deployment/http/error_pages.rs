@@ -0,0 +1,271 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::StatusCode;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// Error responses of a deployment, returned instead of the bare messages of the gateway.
+///
+/// Every kind of error can have multiple representations (for example HTML and JSON),
+/// negotiated against the `Accept` header of the request. Templates can refer to
+/// `{{status}}`, `{{reason}}`, `{{path}}` and `{{request_id}}`, which are escaped according to
+/// the content type.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentErrorPages {
+    /// When enabled, every request to the site is answered with the maintenance page
+    #[serde(default)]
+    #[oai(default)]
+    pub maintenance: bool,
+    /// Value of the `Retry-After` header of maintenance responses, in seconds
+    pub maintenance_retry_after: Option<u64>,
+    /// Returned when no route of the deployment matches the request
+    #[serde(default)]
+    #[oai(default)]
+    pub not_found: Vec<ErrorPage>,
+    /// Returned instead of `429 Too Many Requests` responses
+    #[serde(default)]
+    #[oai(default)]
+    pub too_many_requests: Vec<ErrorPage>,
+    /// Returned instead of `5xx` responses
+    #[serde(default)]
+    #[oai(default)]
+    pub server_error: Vec<ErrorPage>,
+    /// Returned with `503 Service Unavailable` while the site is in maintenance
+    #[serde(default)]
+    #[oai(default)]
+    pub maintenance_page: Vec<ErrorPage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ErrorPage {
+    pub content_type: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorPageKind {
+    NotFound,
+    TooManyRequests,
+    ServerError,
+    Maintenance,
+}
+
+impl ErrorPageKind {
+    // The kind of page replacing a response with the given status, if any.
+    // Not found pages are only used for requests not matching any route, as a 404
+    // returned by a worker is part of the API rather than a gateway error.
+    pub fn from_response_status(status: StatusCode) -> Option<ErrorPageKind> {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            Some(ErrorPageKind::TooManyRequests)
+        } else if status.is_server_error() {
+            Some(ErrorPageKind::ServerError)
+        } else {
+            None
+        }
+    }
+}
+
+impl ApiDeploymentErrorPages {
+    pub fn validate(&self) -> Result<(), String> {
+        for page in self
+            .not_found
+            .iter()
+            .chain(&self.too_many_requests)
+            .chain(&self.server_error)
+            .chain(&self.maintenance_page)
+        {
+            let essence = media_type_essence(&page.content_type);
+            let is_valid = http::HeaderValue::from_str(&page.content_type).is_ok()
+                && essence
+                    .split_once('/')
+                    .is_some_and(|(tpe, subtype)| is_media_token(tpe) && is_media_token(subtype));
+
+            if !is_valid {
+                return Err(format!(
+                    "Invalid error page content type '{}', expected a media type such as text/html",
+                    page.content_type
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn pages(&self, kind: ErrorPageKind) -> &[ErrorPage] {
+        match kind {
+            ErrorPageKind::NotFound => &self.not_found,
+            ErrorPageKind::TooManyRequests => &self.too_many_requests,
+            ErrorPageKind::ServerError => &self.server_error,
+            ErrorPageKind::Maintenance => &self.maintenance_page,
+        }
+    }
+
+    // Selects the representation of a page acceptable for the request, falling back
+    // to the first one, as a branded page is preferable to a bare message even if not acceptable
+    pub fn negotiate(&self, kind: ErrorPageKind, accept: Option<&str>) -> Option<&ErrorPage> {
+        let pages = self.pages(kind);
+
+        let selected = accept.and_then(|accept| {
+            media_ranges(accept).into_iter().find_map(|range| {
+                pages.iter().find(|page| {
+                    media_range_matches(&range, &media_type_essence(&page.content_type))
+                })
+            })
+        });
+
+        selected.or(pages.first())
+    }
+}
+
+impl ErrorPage {
+    pub fn render(&self, status: StatusCode, path: &str, request_id: &str) -> String {
+        let essence = media_type_essence(&self.content_type);
+        let escape = |value: &str| escape_for(&essence, value);
+
+        self.body
+            .replace("{{status}}", status.as_str())
+            .replace(
+                "{{reason}}",
+                &escape(status.canonical_reason().unwrap_or_default()),
+            )
+            .replace("{{path}}", &escape(path))
+            .replace("{{request_id}}", &escape(request_id))
+    }
+}
+
+fn escape_for(essence: &str, value: &str) -> String {
+    if essence.ends_with("html") || essence.ends_with("xml") {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#39;")
+    } else if essence.ends_with("json") {
+        let quoted = serde_json::Value::String(value.to_string()).to_string();
+        quoted[1..quoted.len() - 1].to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+// The media type without its parameters, such as `text/html` for `text/html; charset=utf-8`
+fn media_type_essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn is_media_token(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+}
+
+// The media ranges of an Accept header, most preferred first, without the ones refused with q=0
+fn media_ranges(accept: &str) -> Vec<String> {
+    let mut ranges: Vec<(String, f32)> = accept
+        .split(',')
+        .filter_map(|part| {
+            let mut params = part.split(';');
+            let range = params.next()?.trim().to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            (!range.is_empty() && quality > 0.0).then_some((range, quality))
+        })
+        .collect();
+
+    // Stable, so that ranges of the same quality keep the order of the header
+    ranges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    ranges.into_iter().map(|(range, _)| range).collect()
+}
+
+fn media_range_matches(range: &str, essence: &str) -> bool {
+    match range.split_once('/') {
+        Some(("*", "*")) => true,
+        Some((tpe, "*")) => essence.split_once('/').is_some_and(|(t, _)| t == tpe),
+        _ => range == essence,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    fn page(content_type: &str, body: &str) -> ErrorPage {
+        ErrorPage {
+            content_type: content_type.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn error_pages_are_negotiated_and_rendered() {
+        let error_pages = ApiDeploymentErrorPages {
+            not_found: vec![
+                page(
+                    "text/html; charset=utf-8",
+                    "<h1>{{status}}</h1><p>{{path}}</p><small>{{request_id}}</small>",
+                ),
+                page(
+                    "application/json",
+                    r#"{"error":"{{reason}}","path":"{{path}}","requestId":"{{request_id}}"}"#,
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let json = error_pages
+            .negotiate(
+                ErrorPageKind::NotFound,
+                Some("text/html;q=0.5, application/*"),
+            )
+            .unwrap();
+        let html = error_pages
+            .negotiate(ErrorPageKind::NotFound, Some("image/png"))
+            .unwrap();
+
+        assert_eq!(
+            json.render(StatusCode::NOT_FOUND, "/a\"b", "checkout-42"),
+            r#"{"error":"Not Found","path":"/a\"b","requestId":"checkout-42"}"#
+        );
+        assert_eq!(
+            html.render(StatusCode::NOT_FOUND, "/<script>", "checkout-42"),
+            "<h1>404</h1><p>/&lt;script&gt;</p><small>checkout-42</small>"
+        );
+        assert!(error_pages
+            .negotiate(ErrorPageKind::ServerError, None)
+            .is_none());
+        assert!(error_pages.validate().is_ok());
+        assert!(ApiDeploymentErrorPages {
+            server_error: vec![page("text/*", "")],
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// An A/B experiment of a deployment, assigning every request of the site to one of its variants.
+///
+/// Requests are bucketed by the value of the bucket header, if configured and present in the
+/// request (such as a user id), or else by a random id kept in a cookie, so that a client
+/// sticks to the same variant. The assignment is available in Rib as
+/// `request.experiments.<experiment>`, and is returned in the `X-Golem-Experiment-<experiment>`
+/// response header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentExperiment {
+    pub name: String,
+    pub variants: Vec<ExperimentVariant>,
+    pub bucket_header: Option<String>,
+}
+
+/// A variant of an experiment, assigned to a share of the buckets proportional to its weight
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+pub struct ExperimentVariant {
+    pub name: String,
+    pub weight: u32,
+}
+
+impl ApiDeploymentExperiment {
+    pub fn validate(&self) -> Result<(), String> {
+        validate_name(&self.name).map_err(|err| format!("experiment {}", err))?;
+
+        if self.variants.is_empty() {
+            return Err(format!("experiment '{}' has no variants", self.name));
+        }
+
+        let mut names = HashSet::new();
+        for variant in &self.variants {
+            validate_name(&variant.name)
+                .map_err(|err| format!("variant {} of experiment '{}'", err, self.name))?;
+
+            if !names.insert(variant.name.as_str()) {
+                return Err(format!(
+                    "experiment '{}' has more than one variant '{}'",
+                    self.name, variant.name
+                ));
+            }
+        }
+
+        if self.total_weight() == 0 {
+            return Err(format!(
+                "the variants of experiment '{}' have no weight",
+                self.name
+            ));
+        }
+
+        if let Some(header) = &self.bucket_header {
+            http::HeaderName::try_from(header.as_str())
+                .map_err(|_| format!("invalid header name '{}'", header))?;
+        }
+
+        Ok(())
+    }
+
+    fn total_weight(&self) -> u64 {
+        self.variants
+            .iter()
+            .map(|variant| variant.weight as u64)
+            .sum()
+    }
+
+    // The variant of the bucket of a key. The buckets of an experiment only depend on its name
+    // and on the key, so they do not change with the instance serving the request, and a key
+    // is assigned to the buckets of different experiments independently.
+    pub fn assign(&self, key: &str) -> Option<&ExperimentVariant> {
+        let total_weight = self.total_weight();
+
+        if total_weight == 0 {
+            return None;
+        }
+
+        let digest = Sha256::digest(format!("{}:{}", self.name, key));
+        let mut bucket = u64::from_be_bytes(digest[..8].try_into().unwrap()) % total_weight;
+
+        self.variants.iter().find(|variant| {
+            if bucket < variant.weight as u64 {
+                true
+            } else {
+                bucket -= variant.weight as u64;
+                false
+            }
+        })
+    }
+}
+
+pub fn validate_experiments(experiments: &[ApiDeploymentExperiment]) -> Result<(), String> {
+    let mut names = HashSet::new();
+
+    for experiment in experiments {
+        experiment.validate()?;
+
+        if !names.insert(experiment.name.as_str()) {
+            return Err(format!(
+                "more than one experiment is named '{}'",
+                experiment.name
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Names are used in header names and in Rib field names
+fn validate_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "name '{}' has to be made of letters, digits, '-' and '_'",
+            name
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    fn experiment(variants: Vec<(&str, u32)>) -> ApiDeploymentExperiment {
+        ApiDeploymentExperiment {
+            name: "checkout".to_string(),
+            variants: variants
+                .into_iter()
+                .map(|(name, weight)| ExperimentVariant {
+                    name: name.to_string(),
+                    weight,
+                })
+                .collect(),
+            bucket_header: Some("x-user-id".to_string()),
+        }
+    }
+
+    #[test]
+    fn keys_are_assigned_to_variants_by_weight() {
+        let checkout = experiment(vec![("control", 1), ("treatment", 3), ("disabled", 0)]);
+        assert!(checkout.validate().is_ok());
+
+        let mut control = 0;
+        for key in 0..1000 {
+            let variant = checkout.assign(&key.to_string()).unwrap();
+
+            // The assignment of a key is sticky
+            assert_eq!(checkout.assign(&key.to_string()), Some(variant));
+            assert_ne!(variant.name, "disabled");
+
+            if variant.name == "control" {
+                control += 1;
+            }
+        }
+        assert!((200..300).contains(&control), "{} in control", control);
+
+        assert!(experiment(vec![]).validate().is_err());
+        assert!(experiment(vec![("control", 0)]).validate().is_err());
+        assert!(experiment(vec![("a", 1), ("a", 1)]).validate().is_err());
+        assert!(experiment(vec![("a b", 1)]).validate().is_err());
+        assert!(validate_experiments(&[checkout.clone(), checkout]).is_err());
+    }
+}
@@ -12,6 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use capacity::*;
+pub use client_certificates::*;
+pub use dead_letter_queue::*;
+pub use error_pages::*;
+pub use experiments::*;
+pub use request_fixtures::*;
+pub use traffic_split::*;
+
+mod capacity;
+mod client_certificates;
+mod dead_letter_queue;
+mod error_pages;
+mod experiments;
+mod request_fixtures;
+mod traffic_split;
+
+use crate::gateway_middleware::{HttpCompression, HttpCors};
 use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
 use poem_openapi::{NewType, Object};
 use serde::{Deserialize, Serialize};
@@ -22,6 +39,21 @@ pub struct ApiDeploymentRequest<Namespace> {
     pub namespace: Namespace,
     pub api_definition_keys: Vec<ApiDefinitionIdWithVersion>,
     pub site: ApiSite,
+    // CORS configuration of every route of the deployment, unless the route has its own.
+    // Deploying without one keeps the current configuration of the site.
+    pub cors: Option<HttpCors>,
+    // Split of the traffic between two versions of a definition deployed to the site.
+    // Deploying without one keeps the current split of the site.
+    pub traffic_split: Option<ApiDeploymentTrafficSplit>,
+    // A/B experiments the requests of the site are assigned to.
+    // Deploying without them keeps the current experiments of the site, and with none ends them.
+    pub experiments: Option<Vec<ApiDeploymentExperiment>>,
+    // Invocation capacity of the site, with its priority lanes.
+    // Deploying without one keeps the current capacity of the site.
+    pub capacity: Option<ApiDeploymentCapacity>,
+    // Response compression of the routes of the site without their own.
+    // Deploying without one keeps the current compression of the site.
+    pub compression: Option<HttpCompression>,
 }
 
 #[derive(Eq, Hash, PartialEq, Clone, Debug, serde::Deserialize)]
@@ -30,6 +62,26 @@ pub struct ApiDeployment<Namespace> {
     pub api_definition_keys: Vec<ApiDefinitionIdWithVersion>,
     pub site: ApiSite,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub cors: Option<HttpCors>,
+    pub traffic_split: Option<ApiDeploymentTrafficSplit>,
+    pub experiments: Option<Vec<ApiDeploymentExperiment>>,
+    pub capacity: Option<ApiDeploymentCapacity>,
+    pub compression: Option<HttpCompression>,
+}
+
+// The configuration of a deployment applying to every request of its site. The gateway loads it
+// once per request, from a cache invalidated when the deployment changes.
+#[derive(Clone, Debug, Default)]
+pub struct ApiDeploymentConfig {
+    pub error_pages: Option<ApiDeploymentErrorPages>,
+    pub cors: Option<HttpCors>,
+    pub client_certificates: Option<ClientCertificateVerifier>,
+    pub dead_letter_queue: Option<ApiDeploymentDeadLetterQueue>,
+    pub request_fixtures: Option<ApiDeploymentRequestFixtures>,
+    pub traffic_split: Option<ApiDeploymentTrafficSplit>,
+    pub experiments: Vec<ApiDeploymentExperiment>,
+    pub capacity: Option<ApiDeploymentCapacity>,
+    pub compression: Option<HttpCompression>,
 }
 
 #[derive(Debug, Eq, Clone, Hash, PartialEq, Serialize, Deserialize, Object)]
@@ -0,0 +1,108 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::HeaderName;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_MAX_FIXTURES: u32 = 100;
+
+/// Recording of request fixtures of a deployment.
+///
+/// A sample of the requests of the site is kept along with the status and the schema of their
+/// response, so that they can be replayed against a new version of the API definitions in a
+/// staging deployment before promoting it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentRequestFixtures {
+    /// Ratio of the requests recorded as fixtures, between 0 and 1
+    pub sample_rate: f64,
+    /// Upper bound of the fixtures kept for the site, 100 by default.
+    /// Recording stops once it is reached, until fixtures are deleted.
+    pub max_fixtures: Option<u32>,
+    /// Headers not stored with the fixtures, such as credentials.
+    /// Fixtures are replayed without them.
+    #[serde(default)]
+    #[oai(default)]
+    pub redacted_headers: Vec<String>,
+    /// Upper bound of the stored request bodies in bytes.
+    /// Larger requests are not recorded.
+    pub max_body_size: Option<u64>,
+}
+
+impl ApiDeploymentRequestFixtures {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.sample_rate > 0.0 && self.sample_rate <= 1.0) {
+            return Err(format!(
+                "Sample rate must be greater than 0 and at most 1, got {}",
+                self.sample_rate
+            ));
+        }
+
+        for header in &self.redacted_headers {
+            HeaderName::from_bytes(header.as_bytes())
+                .map_err(|_| format!("Invalid redacted header name '{}'", header))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn max_fixtures(&self) -> u32 {
+        self.max_fixtures.unwrap_or(DEFAULT_MAX_FIXTURES)
+    }
+
+    // `sample` is uniformly distributed between 0 and 1
+    pub fn is_sampled(&self, sample: f64) -> bool {
+        sample < self.sample_rate
+    }
+
+    pub fn is_redacted(&self, header: &HeaderName) -> bool {
+        self.redacted_headers
+            .iter()
+            .any(|redacted| redacted.eq_ignore_ascii_case(header.as_str()))
+    }
+
+    pub fn keeps_body_of_size(&self, size: usize) -> bool {
+        self.max_body_size
+            .is_none_or(|max_body_size| size as u64 <= max_body_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn sample_rate_must_be_a_ratio() {
+        let request_fixtures = |sample_rate| ApiDeploymentRequestFixtures {
+            sample_rate,
+            max_fixtures: None,
+            redacted_headers: vec!["Authorization".to_string()],
+            max_body_size: None,
+        };
+
+        assert_eq!(request_fixtures(0.25).validate(), Ok(()));
+        assert_eq!(request_fixtures(1.0).validate(), Ok(()));
+        assert!(request_fixtures(0.0).validate().is_err());
+        assert!(request_fixtures(1.5).validate().is_err());
+        assert!(request_fixtures(f64::NAN).validate().is_err());
+
+        assert!(request_fixtures(0.25).is_sampled(0.1));
+        assert!(!request_fixtures(0.25).is_sampled(0.25));
+        assert!(request_fixtures(0.25).is_redacted(&http::header::AUTHORIZATION));
+        assert_eq!(request_fixtures(0.25).max_fixtures(), 100);
+    }
+}
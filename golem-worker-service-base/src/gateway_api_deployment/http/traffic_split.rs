@@ -0,0 +1,191 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::CompiledHttpApiDefinition;
+use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+/// Splits the traffic of a site between two versions of an API definition deployed to it,
+/// for a gradual rollout of the canary version.
+///
+/// Requests are routed by the header, if configured and present in the request,
+/// or else by weight.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDeploymentTrafficSplit {
+    pub api_definition_id: ApiDefinitionId,
+    pub stable_version: ApiVersion,
+    pub canary_version: ApiVersion,
+    /// Percentage of the requests routed to the canary version, from 0 to 100
+    pub canary_weight: u8,
+    pub header: Option<TrafficSplitHeader>,
+}
+
+/// Requests with the header set to the value are routed to the canary version,
+/// and requests with any other value to the stable version
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+pub struct TrafficSplitHeader {
+    pub name: String,
+    pub value: String,
+}
+
+impl ApiDeploymentTrafficSplit {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.stable_version == self.canary_version {
+            return Err("the stable and canary versions have to be different".to_string());
+        }
+
+        if self.canary_weight > 100 {
+            return Err(format!(
+                "invalid canary weight {}, expected a percentage from 0 to 100",
+                self.canary_weight
+            ));
+        }
+
+        if let Some(header) = &self.header {
+            http::HeaderName::try_from(header.name.as_str())
+                .map_err(|_| format!("invalid header name '{}'", header.name))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn stable(&self) -> ApiDefinitionIdWithVersion {
+        ApiDefinitionIdWithVersion {
+            id: self.api_definition_id.clone(),
+            version: self.stable_version.clone(),
+        }
+    }
+
+    pub fn canary(&self) -> ApiDefinitionIdWithVersion {
+        ApiDefinitionIdWithVersion {
+            id: self.api_definition_id.clone(),
+            version: self.canary_version.clone(),
+        }
+    }
+
+    pub fn is_split(&self, key: &ApiDefinitionIdWithVersion) -> bool {
+        *key == self.stable() || *key == self.canary()
+    }
+
+    // Leaves out the version of the definition that does not serve a request, given the value
+    // of its routing header and a random number from 0 to 99 for routing by weight.
+    // Until both versions are deployed, the split has no effect.
+    pub fn select<Namespace>(
+        &self,
+        definitions: Vec<CompiledHttpApiDefinition<Namespace>>,
+        header_value: Option<&str>,
+        roll: u8,
+    ) -> Vec<CompiledHttpApiDefinition<Namespace>> {
+        let is_deployed = |version: &ApiVersion| {
+            definitions
+                .iter()
+                .any(|def| def.id == self.api_definition_id && def.version == *version)
+        };
+
+        if !is_deployed(&self.stable_version) || !is_deployed(&self.canary_version) {
+            return definitions;
+        }
+
+        let to_canary = match (&self.header, header_value) {
+            (Some(header), Some(value)) => header.value == value,
+            _ => roll < self.canary_weight,
+        };
+
+        let left_out = if to_canary {
+            &self.stable_version
+        } else {
+            &self.canary_version
+        };
+
+        definitions
+            .into_iter()
+            .filter(|def| !(def.id == self.api_definition_id && def.version == *left_out))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    fn definition(id: &str, version: &str) -> CompiledHttpApiDefinition<()> {
+        CompiledHttpApiDefinition {
+            id: ApiDefinitionId(id.to_string()),
+            version: ApiVersion(version.to_string()),
+            routes: vec![],
+            draft: false,
+            created_at: chrono::Utc::now(),
+            namespace: (),
+        }
+    }
+
+    fn selected(definitions: Vec<CompiledHttpApiDefinition<()>>) -> Vec<(String, String)> {
+        definitions
+            .into_iter()
+            .map(|def| (def.id.0, def.version.0))
+            .collect()
+    }
+
+    #[test]
+    fn requests_are_routed_by_header_then_weight() {
+        let split = ApiDeploymentTrafficSplit {
+            api_definition_id: ApiDefinitionId("shop".to_string()),
+            stable_version: ApiVersion("1".to_string()),
+            canary_version: ApiVersion("2".to_string()),
+            canary_weight: 10,
+            header: Some(TrafficSplitHeader {
+                name: "x-canary".to_string(),
+                value: "true".to_string(),
+            }),
+        };
+        assert!(split.validate().is_ok());
+
+        let definitions = vec![
+            definition("shop", "1"),
+            definition("shop", "2"),
+            definition("auth", "1"),
+        ];
+        let stable = vec![
+            ("shop".to_string(), "1".to_string()),
+            ("auth".to_string(), "1".to_string()),
+        ];
+        let canary = vec![
+            ("shop".to_string(), "2".to_string()),
+            ("auth".to_string(), "1".to_string()),
+        ];
+
+        assert_eq!(selected(split.select(definitions.clone(), None, 9)), canary);
+        assert_eq!(
+            selected(split.select(definitions.clone(), None, 10)),
+            stable
+        );
+        assert_eq!(
+            selected(split.select(definitions.clone(), Some("true"), 99)),
+            canary
+        );
+        assert_eq!(
+            selected(split.select(definitions.clone(), Some("false"), 0)),
+            stable
+        );
+
+        // Without the canary version deployed, the split has no effect
+        let definitions = vec![definition("shop", "1"), definition("auth", "1")];
+        assert_eq!(selected(split.select(definitions, None, 0)), stable);
+    }
+}
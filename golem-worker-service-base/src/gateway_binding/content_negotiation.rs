@@ -0,0 +1,267 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Media types a worker binding can respond with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Enum)]
+pub enum ResponseMediaType {
+    #[serde(rename = "application/json")]
+    #[oai(rename = "application/json")]
+    Json,
+    #[serde(rename = "application/yaml")]
+    #[oai(rename = "application/yaml")]
+    Yaml,
+    #[serde(rename = "application/msgpack")]
+    #[oai(rename = "application/msgpack")]
+    MessagePack,
+    #[serde(rename = "text/plain")]
+    #[oai(rename = "text/plain")]
+    Text,
+}
+
+impl ResponseMediaType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResponseMediaType::Json => "application/json",
+            ResponseMediaType::Yaml => "application/yaml",
+            ResponseMediaType::MessagePack => "application/msgpack",
+            ResponseMediaType::Text => "text/plain",
+        }
+    }
+
+    // Whether the media type (without parameters) of an Accept header entry names this one
+    fn is_named_by(&self, media_type: &str) -> bool {
+        ResponseMediaType::from_str(media_type).is_ok_and(|named| named == *self)
+    }
+}
+
+impl FromStr for ResponseMediaType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "application/json" => Ok(ResponseMediaType::Json),
+            "application/yaml" | "application/x-yaml" | "text/yaml" => Ok(ResponseMediaType::Yaml),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Ok(ResponseMediaType::MessagePack)
+            }
+            "text/plain" => Ok(ResponseMediaType::Text),
+            _ => Err(format!("Unsupported response media type: {}", s)),
+        }
+    }
+}
+
+impl Display for ResponseMediaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Serves the response of a worker binding in the media type requested by the `Accept` header
+/// of the client, among the ones declared by the binding.
+///
+/// The first declared media type is used when the client accepts any of them, or does not send
+/// an `Accept` header, and requests accepting none of them are rejected with 406. The chosen media
+/// type is available to the response mapping as `request.media_type`, and a `Content-Type` header
+/// set by the response mapping takes precedence over it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ContentNegotiation {
+    pub media_types: Vec<ResponseMediaType>,
+}
+
+impl ContentNegotiation {
+    pub fn default_media_type(&self) -> Option<ResponseMediaType> {
+        self.media_types.first().copied()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.media_types.is_empty() {
+            return Err("At least one media type has to be declared".to_string());
+        }
+
+        for (index, media_type) in self.media_types.iter().enumerate() {
+            if self.media_types[..index].contains(media_type) {
+                return Err(format!(
+                    "Media type {} is declared more than once",
+                    media_type
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Picks the media type of the response given the Accept header of the request, preferring
+    // the entries with the highest quality, and the earliest ones among those
+    pub fn negotiate(&self, accept: Option<&str>) -> Result<ResponseMediaType, String> {
+        let default_media_type = self
+            .default_media_type()
+            .ok_or("No media types declared for the response".to_string())?;
+
+        let accept = match accept.map(|accept| accept.trim()) {
+            Some(accept) if !accept.is_empty() => accept,
+            _ => return Ok(default_media_type),
+        };
+
+        let mut ranges = accept
+            .split(',')
+            .filter_map(internal::parse_media_range)
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect::<Vec<_>>();
+
+        ranges.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+
+        for (range, _) in &ranges {
+            let chosen = match range.split_once('/') {
+                Some(("*", "*")) => Some(default_media_type),
+                Some((main_type, "*")) => self
+                    .media_types
+                    .iter()
+                    .find(|media_type| {
+                        media_type
+                            .as_str()
+                            .split_once('/')
+                            .is_some_and(|(declared, _)| declared == main_type)
+                    })
+                    .copied(),
+                _ => self
+                    .media_types
+                    .iter()
+                    .find(|media_type| media_type.is_named_by(range))
+                    .copied(),
+            };
+
+            if let Some(chosen) = chosen {
+                return Ok(chosen);
+            }
+        }
+
+        Err(format!(
+            "None of the accepted media types {} is available, expected one of {}",
+            accept,
+            self.media_types
+                .iter()
+                .map(|media_type| media_type.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
+
+mod internal {
+    // The lowercase media range of an Accept header entry, and its quality
+    pub(crate) fn parse_media_range(entry: &str) -> Option<(String, f32)> {
+        let mut parts = entry.split(';');
+        let range = parts.next()?.trim().to_ascii_lowercase();
+
+        if range.is_empty() {
+            return None;
+        }
+
+        let quality = parts
+            .filter_map(|parameter| parameter.split_once('='))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+            .and_then(|(_, value)| value.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        Some((range, quality))
+    }
+}
+
+impl From<ContentNegotiation> for golem_api_grpc::proto::golem::apidefinition::ContentNegotiation {
+    fn from(value: ContentNegotiation) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::ContentNegotiation {
+            media_types: value
+                .media_types
+                .iter()
+                .map(|media_type| media_type.as_str().to_string())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::ContentNegotiation>
+    for ContentNegotiation
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::ContentNegotiation,
+    ) -> Result<Self, Self::Error> {
+        let media_types = value
+            .media_types
+            .iter()
+            .map(|media_type| ResponseMediaType::from_str(media_type))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ContentNegotiation { media_types })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn media_type_is_negotiated_from_the_accept_header() {
+        let content_negotiation = ContentNegotiation {
+            media_types: vec![
+                ResponseMediaType::Json,
+                ResponseMediaType::Yaml,
+                ResponseMediaType::Text,
+            ],
+        };
+
+        assert!(content_negotiation.validate().is_ok());
+
+        let negotiate = |accept| content_negotiation.negotiate(accept);
+
+        assert_eq!(negotiate(None), Ok(ResponseMediaType::Json));
+        assert_eq!(negotiate(Some("*/*")), Ok(ResponseMediaType::Json));
+        assert_eq!(
+            negotiate(Some("application/x-yaml")),
+            Ok(ResponseMediaType::Yaml)
+        );
+        assert_eq!(
+            negotiate(Some("application/json;q=0.5, text/plain")),
+            Ok(ResponseMediaType::Text)
+        );
+        assert_eq!(
+            negotiate(Some("text/html, text/*;q=0.8, */*;q=0.1")),
+            Ok(ResponseMediaType::Text)
+        );
+        assert_eq!(
+            negotiate(Some("text/plain;q=0, application/yaml;q=0.2")),
+            Ok(ResponseMediaType::Yaml)
+        );
+        assert!(negotiate(Some("application/msgpack")).is_err());
+
+        let duplicated = ContentNegotiation {
+            media_types: vec![ResponseMediaType::Json, ResponseMediaType::Json],
+        };
+        assert!(duplicated.validate().is_err());
+        assert!(ContentNegotiation {
+            media_types: vec![]
+        }
+        .validate()
+        .is_err());
+    }
+}
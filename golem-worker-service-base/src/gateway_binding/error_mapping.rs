@@ -0,0 +1,283 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_wasm_ast::analysis::{AnalysedExport, AnalysedFunction, AnalysedType};
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The body of the responses of a mapped error case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "kebab-case")]
+#[oai(rename_all = "kebab-case")]
+pub enum ErrorResponseShape {
+    /// An `application/problem+json` document naming the error case, with its payload as detail
+    #[default]
+    Problem,
+    /// The payload of the error case as JSON
+    Payload,
+    /// No body
+    Empty,
+}
+
+impl ErrorResponseShape {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorResponseShape::Problem => "problem",
+            ErrorResponseShape::Payload => "payload",
+            ErrorResponseShape::Empty => "empty",
+        }
+    }
+}
+
+impl FromStr for ErrorResponseShape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "problem" => Ok(ErrorResponseShape::Problem),
+            "payload" => Ok(ErrorResponseShape::Payload),
+            "empty" => Ok(ErrorResponseShape::Empty),
+            _ => Err(format!(
+                "Unknown error response shape {}, expected problem, payload or empty",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for ErrorResponseShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The response of an error case of the component
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ErrorCaseMapping {
+    /// Name of the variant or enum case of the error type
+    pub case: String,
+    pub status: u16,
+    #[serde(default)]
+    #[oai(default)]
+    pub shape: ErrorResponseShape,
+}
+
+/// Maps the error cases returned by the component of a worker binding to HTTP responses.
+///
+/// When the response mapping returns a `result` (or a response whose `body` is a `result`) which
+/// is an error of a variant or enum type, and the case of the error is mapped, the gateway
+/// responds with the status and the shape of the mapping instead of the response mapping.
+/// The mapped cases are checked against the error types of the functions exported by the
+/// component when the API definition is uploaded, and are documented in the OpenAPI export.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ErrorMapping {
+    pub cases: Vec<ErrorCaseMapping>,
+}
+
+impl ErrorMapping {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cases.is_empty() {
+            return Err("At least one error case has to be mapped".to_string());
+        }
+
+        for (index, case) in self.cases.iter().enumerate() {
+            if self.cases[..index]
+                .iter()
+                .any(|previous| previous.case == case.case)
+            {
+                return Err(format!("Error case {} is mapped more than once", case.case));
+            }
+
+            if !(400..=599).contains(&case.status) {
+                return Err(format!(
+                    "Error case {} is mapped to status {}, expected a client or server error status",
+                    case.case, case.status
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks that every mapped case is a case of the error type of an exported function
+    pub fn validate_against(&self, exports: &[AnalysedExport]) -> Result<(), String> {
+        let known_cases = error_cases(exports);
+
+        let unknown_cases = self
+            .cases
+            .iter()
+            .filter(|case| !known_cases.contains(case.case.as_str()))
+            .map(|case| case.case.as_str())
+            .collect::<Vec<_>>();
+
+        if unknown_cases.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Error cases {} are not returned by any function of the component, expected some of {}",
+                unknown_cases.join(", "),
+                known_cases.into_iter().collect::<Vec<_>>().join(", ")
+            ))
+        }
+    }
+
+    pub fn get(&self, case: &str) -> Option<&ErrorCaseMapping> {
+        self.cases.iter().find(|mapping| mapping.case == case)
+    }
+}
+
+// The names of the cases of the variant and enum error types of the exported functions
+fn error_cases(exports: &[AnalysedExport]) -> BTreeSet<&str> {
+    let functions = exports.iter().flat_map(|export| match export {
+        AnalysedExport::Function(function) => std::slice::from_ref(function),
+        AnalysedExport::Instance(instance) => instance.functions.as_slice(),
+    });
+
+    functions
+        .flat_map(|function: &AnalysedFunction| function.results.iter())
+        .filter_map(|result| match &result.typ {
+            AnalysedType::Result(result) => result.err.as_deref(),
+            _ => None,
+        })
+        .flat_map(|err| match err {
+            AnalysedType::Variant(variant) => variant
+                .cases
+                .iter()
+                .map(|case| case.name.as_str())
+                .collect::<Vec<_>>(),
+            AnalysedType::Enum(enum_type) => {
+                enum_type.cases.iter().map(|case| case.as_str()).collect()
+            }
+            _ => vec![],
+        })
+        .collect()
+}
+
+impl From<ErrorMapping> for golem_api_grpc::proto::golem::apidefinition::ErrorMapping {
+    fn from(value: ErrorMapping) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::ErrorMapping {
+            cases: value
+                .cases
+                .into_iter()
+                .map(
+                    |case| golem_api_grpc::proto::golem::apidefinition::ErrorCaseMapping {
+                        case: case.case,
+                        status: case.status as u32,
+                        shape: case.shape.as_str().to_string(),
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::ErrorMapping> for ErrorMapping {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::ErrorMapping,
+    ) -> Result<Self, Self::Error> {
+        let cases = value
+            .cases
+            .into_iter()
+            .map(|case| {
+                Ok(ErrorCaseMapping {
+                    status: u16::try_from(case.status)
+                        .map_err(|_| format!("Invalid status {}", case.status))?,
+                    shape: ErrorResponseShape::from_str(&case.shape)?,
+                    case: case.case,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(ErrorMapping { cases })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{
+        case, r#enum, result, str, u64, unit_case, variant,
+    };
+    use golem_wasm_ast::analysis::{AnalysedFunctionResult, AnalysedInstance};
+    use test_r::test;
+
+    #[test]
+    fn mapped_cases_must_be_error_cases_of_the_component() {
+        let exports = vec![AnalysedExport::Instance(AnalysedInstance {
+            name: "shop:api/cart".to_string(),
+            functions: vec![
+                AnalysedFunction {
+                    name: "checkout".to_string(),
+                    parameters: vec![],
+                    results: vec![AnalysedFunctionResult {
+                        name: None,
+                        typ: result(
+                            u64(),
+                            variant(vec![
+                                unit_case("cart-not-found"),
+                                case("out-of-stock", str()),
+                            ]),
+                        ),
+                    }],
+                },
+                AnalysedFunction {
+                    name: "get-cart".to_string(),
+                    parameters: vec![],
+                    results: vec![AnalysedFunctionResult {
+                        name: None,
+                        typ: result(str(), r#enum(&["forbidden"])),
+                    }],
+                },
+            ],
+        })];
+
+        let mapping = |cases: Vec<(&str, u16)>| ErrorMapping {
+            cases: cases
+                .into_iter()
+                .map(|(case, status)| ErrorCaseMapping {
+                    case: case.to_string(),
+                    status,
+                    shape: ErrorResponseShape::Problem,
+                })
+                .collect(),
+        };
+
+        let valid = mapping(vec![
+            ("cart-not-found", 404),
+            ("out-of-stock", 409),
+            ("forbidden", 403),
+        ]);
+        assert!(valid.validate().is_ok());
+        assert!(valid.validate_against(&exports).is_ok());
+        assert_eq!(valid.get("out-of-stock").map(|case| case.status), Some(409));
+
+        assert!(mapping(vec![("payment-declined", 402)])
+            .validate_against(&exports)
+            .is_err());
+        assert!(mapping(vec![("cart-not-found", 200)]).validate().is_err());
+        assert!(mapping(vec![("forbidden", 403), ("forbidden", 401)])
+            .validate()
+            .is_err());
+        assert!(mapping(vec![]).validate().is_err());
+    }
+}
@@ -19,10 +19,15 @@ use crate::gateway_binding::{
 use crate::gateway_binding::{InvocationContextCompiled, StaticBinding};
 use golem_api_grpc::proto::golem::apidefinition::GatewayBindingType as ProtoGatewayBindingType;
 use golem_common::model::GatewayBindingType;
+use golem_service_base::model::VersionedComponentId;
 use rib::RibOutputTypeInfo;
 
 use super::http_handler_binding::HttpHandlerBindingCompiled;
-use super::HttpHandlerBinding;
+use super::{
+    ContentNegotiation, ErrorMapping, GrpcBinding, HttpHandlerBinding, HttpProxyBinding,
+    HttpProxyBindingCompiled, Pagination, ResponseMapping, TrafficMirrorCompiled,
+    WorkerBootstrapCompiled, WorkerPerUser,
+};
 
 // A compiled binding is a binding with all existence of Rib Expr
 // get replaced with their compiled form - RibByteCode.
@@ -32,6 +37,10 @@ pub enum GatewayBindingCompiled {
     Static(StaticBinding),
     FileServer(WorkerBindingCompiled),
     HttpHandler(HttpHandlerBindingCompiled),
+    Grpc(GrpcBinding),
+    WebSocket(WorkerBindingCompiled),
+    ServerSentEvents(WorkerBindingCompiled),
+    HttpProxy(HttpProxyBindingCompiled),
 }
 
 impl GatewayBindingCompiled {
@@ -40,12 +49,40 @@ impl GatewayBindingCompiled {
             GatewayBindingCompiled::Worker(_) => false,
             GatewayBindingCompiled::FileServer(_) => false,
             GatewayBindingCompiled::HttpHandler(_) => false,
+            GatewayBindingCompiled::Grpc(_) => false,
+            GatewayBindingCompiled::WebSocket(_) => false,
+            GatewayBindingCompiled::ServerSentEvents(_) => false,
+            GatewayBindingCompiled::HttpProxy(_) => false,
             GatewayBindingCompiled::Static(static_binding) => match static_binding {
                 StaticBinding::HttpCorsPreflight(_) => false,
                 StaticBinding::HttpAuthCallBack(_) => true,
+                StaticBinding::StatusPage => false,
             },
         }
     }
+
+    pub fn get_component_id(&self) -> Option<VersionedComponentId> {
+        match self {
+            GatewayBindingCompiled::Worker(worker_binding) => {
+                Some(worker_binding.component_id.clone())
+            }
+            GatewayBindingCompiled::FileServer(worker_binding) => {
+                Some(worker_binding.component_id.clone())
+            }
+            GatewayBindingCompiled::HttpHandler(http_handler_binding) => {
+                Some(http_handler_binding.component_id.clone())
+            }
+            GatewayBindingCompiled::WebSocket(worker_binding) => {
+                Some(worker_binding.component_id.clone())
+            }
+            GatewayBindingCompiled::ServerSentEvents(worker_binding) => {
+                Some(worker_binding.component_id.clone())
+            }
+            GatewayBindingCompiled::Static(_) => None,
+            GatewayBindingCompiled::Grpc(_) => None,
+            GatewayBindingCompiled::HttpProxy(_) => None,
+        }
+    }
 }
 
 impl From<GatewayBindingCompiled> for GatewayBinding {
@@ -75,6 +112,16 @@ impl From<GatewayBindingCompiled> for GatewayBinding {
 
                 GatewayBinding::HttpHandler(worker_binding)
             }
+            GatewayBindingCompiled::Grpc(grpc_binding) => GatewayBinding::Grpc(grpc_binding),
+            GatewayBindingCompiled::WebSocket(value) => {
+                GatewayBinding::WebSocket(WorkerBinding::from(value))
+            }
+            GatewayBindingCompiled::ServerSentEvents(value) => {
+                GatewayBinding::ServerSentEvents(WorkerBinding::from(value))
+            }
+            GatewayBindingCompiled::HttpProxy(value) => {
+                GatewayBinding::HttpProxy(HttpProxyBinding::from(value))
+            }
         }
     }
 }
@@ -99,6 +146,20 @@ impl TryFrom<GatewayBindingCompiled>
                 )?)
             }
 
+            GatewayBindingCompiled::WebSocket(worker_binding) => {
+                Ok(internal::worker_binding_to_gateway_binding_compiled_proto(
+                    worker_binding,
+                    GatewayBindingType::WebSocket,
+                )?)
+            }
+
+            GatewayBindingCompiled::ServerSentEvents(worker_binding) => {
+                Ok(internal::worker_binding_to_gateway_binding_compiled_proto(
+                    worker_binding,
+                    GatewayBindingType::ServerSentEvents,
+                )?)
+            }
+
             GatewayBindingCompiled::HttpHandler(http_handler_binding) => {
                 Ok(internal::http_handler_to_gateway_binding_compiled_proto(
                     http_handler_binding,
@@ -110,6 +171,7 @@ impl TryFrom<GatewayBindingCompiled>
                 let binding_type = match static_binding {
                     StaticBinding::HttpCorsPreflight(_) => golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::CorsPreflight,
                     StaticBinding::HttpAuthCallBack(_) => golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::AuthCallBack,
+                    StaticBinding::StatusPage => golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::StatusPage,
                 };
 
                 Ok(
@@ -135,9 +197,80 @@ impl TryFrom<GatewayBindingCompiled>
                         invocation_context: None,
                         compiled_invocation_context_expr: None,
                         invocation_context_rib_input: None,
+                        grpc_binding: None,
+                        traffic_mirror: None,
+                        http_proxy_binding: None,
+                        worker_per_user: None,
+                        bootstrap: None,
+                        pagination: None,
+                        content_negotiation: None,
+                        error_mapping: None,
+                        static_worker_names: None,
                     },
                 )
             }
+
+            GatewayBindingCompiled::Grpc(grpc_binding) => Ok(
+                golem_api_grpc::proto::golem::apidefinition::CompiledGatewayBinding {
+                    component: None,
+                    worker_name: None,
+                    compiled_worker_name_expr: None,
+                    worker_name_rib_input: None,
+                    idempotency_key: None,
+                    compiled_idempotency_key_expr: None,
+                    idempotency_key_rib_input: None,
+                    response: None,
+                    compiled_response_expr: None,
+                    response_rib_input: None,
+                    worker_functions_in_response: None,
+                    binding_type: Some(ProtoGatewayBindingType::Grpc as i32),
+                    static_binding: None,
+                    response_rib_output: None,
+                    invocation_context: None,
+                    compiled_invocation_context_expr: None,
+                    invocation_context_rib_input: None,
+                    grpc_binding: Some(grpc_binding.into()),
+                    traffic_mirror: None,
+                    http_proxy_binding: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
+                },
+            ),
+
+            GatewayBindingCompiled::HttpProxy(http_proxy_binding) => Ok(
+                golem_api_grpc::proto::golem::apidefinition::CompiledGatewayBinding {
+                    component: None,
+                    worker_name: None,
+                    compiled_worker_name_expr: None,
+                    worker_name_rib_input: None,
+                    idempotency_key: None,
+                    compiled_idempotency_key_expr: None,
+                    idempotency_key_rib_input: None,
+                    response: None,
+                    compiled_response_expr: None,
+                    response_rib_input: None,
+                    worker_functions_in_response: None,
+                    binding_type: Some(ProtoGatewayBindingType::HttpProxy as i32),
+                    static_binding: None,
+                    response_rib_output: None,
+                    invocation_context: None,
+                    compiled_invocation_context_expr: None,
+                    invocation_context_rib_input: None,
+                    grpc_binding: None,
+                    traffic_mirror: None,
+                    http_proxy_binding: Some(http_proxy_binding.try_into()?),
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
+                },
+            ),
         }
     }
 }
@@ -156,7 +289,10 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::CompiledGatewayBinding
             .map_err(|e| format!("Failed to convert binding type: {}", e))?;
 
         match binding_type {
-            ProtoGatewayBindingType::FileServer | ProtoGatewayBindingType::Default => {
+            ProtoGatewayBindingType::FileServer
+            | ProtoGatewayBindingType::Default
+            | ProtoGatewayBindingType::WebSocket
+            | ProtoGatewayBindingType::ServerSentEvents => {
                 // Convert fields for the Worker variant
                 let component_id = value
                     .component
@@ -240,24 +376,51 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::CompiledGatewayBinding
                         .transpose()?,
                 };
 
-                let binding_type = value.binding_type.ok_or("Missing binding_type")?;
+                let traffic_mirror_compiled = value
+                    .traffic_mirror
+                    .map(|traffic_mirror| {
+                        TrafficMirrorCompiled::from_proto(
+                            traffic_mirror,
+                            &ResponseMapping(response_compiled.response_mapping_expr.clone()),
+                        )
+                    })
+                    .transpose()?;
+
+                let worker_binding = WorkerBindingCompiled {
+                    component_id,
+                    worker_name_compiled,
+                    idempotency_key_compiled,
+                    response_compiled,
+                    invocation_context_compiled,
+                    traffic_mirror_compiled,
+                    worker_per_user: value.worker_per_user.map(WorkerPerUser::from),
+                    bootstrap_compiled: value
+                        .bootstrap
+                        .map(WorkerBootstrapCompiled::try_from)
+                        .transpose()?,
+                    pagination: value.pagination.map(Pagination::from),
+                    content_negotiation: value
+                        .content_negotiation
+                        .map(ContentNegotiation::try_from)
+                        .transpose()?,
+                    error_mapping: value
+                        .error_mapping
+                        .map(ErrorMapping::try_from)
+                        .transpose()?,
+                    static_worker_names: value.static_worker_names.unwrap_or_default(),
+                };
 
-                if binding_type == 0 {
-                    Ok(GatewayBindingCompiled::Worker(WorkerBindingCompiled {
-                        component_id,
-                        worker_name_compiled,
-                        idempotency_key_compiled,
-                        response_compiled,
-                        invocation_context_compiled,
-                    }))
-                } else {
-                    Ok(GatewayBindingCompiled::FileServer(WorkerBindingCompiled {
-                        component_id,
-                        worker_name_compiled,
-                        idempotency_key_compiled,
-                        response_compiled,
-                        invocation_context_compiled,
-                    }))
+                match binding_type {
+                    ProtoGatewayBindingType::Default => {
+                        Ok(GatewayBindingCompiled::Worker(worker_binding))
+                    }
+                    ProtoGatewayBindingType::WebSocket => {
+                        Ok(GatewayBindingCompiled::WebSocket(worker_binding))
+                    }
+                    ProtoGatewayBindingType::ServerSentEvents => {
+                        Ok(GatewayBindingCompiled::ServerSentEvents(worker_binding))
+                    }
+                    _ => Ok(GatewayBindingCompiled::FileServer(worker_binding)),
                 }
             }
             ProtoGatewayBindingType::HttpHandler => {
@@ -309,13 +472,29 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::CompiledGatewayBinding
                     },
                 ))
             }
-            ProtoGatewayBindingType::CorsPreflight | ProtoGatewayBindingType::AuthCallBack => {
+            ProtoGatewayBindingType::CorsPreflight
+            | ProtoGatewayBindingType::AuthCallBack
+            | ProtoGatewayBindingType::StatusPage => {
                 let static_binding = value
                     .static_binding
                     .ok_or("Missing static_binding for Static")?;
 
                 Ok(GatewayBindingCompiled::Static(static_binding.try_into()?))
             }
+            ProtoGatewayBindingType::Grpc => {
+                let grpc_binding = value.grpc_binding.ok_or("Missing grpc_binding for Grpc")?;
+
+                Ok(GatewayBindingCompiled::Grpc(grpc_binding.into()))
+            }
+            ProtoGatewayBindingType::HttpProxy => {
+                let http_proxy_binding = value
+                    .http_proxy_binding
+                    .ok_or("Missing http_proxy_binding for HttpProxy")?;
+
+                Ok(GatewayBindingCompiled::HttpProxy(
+                    http_proxy_binding.try_into()?,
+                ))
+            }
         }
     }
 }
@@ -385,11 +564,32 @@ mod internal {
             .worker_calls
             .map(|x| x.into());
 
+        let traffic_mirror = worker_binding
+            .traffic_mirror_compiled
+            .map(golem_api_grpc::proto::golem::apidefinition::CompiledTrafficMirror::try_from)
+            .transpose()?;
+
+        let worker_per_user = worker_binding.worker_per_user.map(|x| x.into());
+
+        let pagination = worker_binding.pagination.map(|x| x.into());
+        let content_negotiation = worker_binding.content_negotiation.map(|x| x.into());
+        let error_mapping = worker_binding.error_mapping.map(|x| x.into());
+
+        let bootstrap = worker_binding
+            .bootstrap_compiled
+            .map(golem_api_grpc::proto::golem::apidefinition::CompiledWorkerBootstrap::try_from)
+            .transpose()?;
+
         let binding_type = match binding_type {
             GatewayBindingType::Default => 0,
             GatewayBindingType::FileServer => 1,
             GatewayBindingType::CorsPreflight => 2,
             GatewayBindingType::HttpHandler => 4,
+            GatewayBindingType::Grpc => 5,
+            GatewayBindingType::WebSocket => 6,
+            GatewayBindingType::ServerSentEvents => 7,
+            GatewayBindingType::HttpProxy => 8,
+            GatewayBindingType::StatusPage => 9,
         };
 
         Ok(
@@ -411,6 +611,15 @@ mod internal {
                 invocation_context,
                 compiled_invocation_context_expr,
                 invocation_context_rib_input,
+                grpc_binding: None,
+                traffic_mirror,
+                http_proxy_binding: None,
+                worker_per_user,
+                bootstrap,
+                pagination,
+                content_negotiation,
+                error_mapping,
+                static_worker_names: Some(worker_binding.static_worker_names),
             },
         )
     }
@@ -446,6 +655,11 @@ mod internal {
             GatewayBindingType::FileServer => 1,
             GatewayBindingType::CorsPreflight => 2,
             GatewayBindingType::HttpHandler => 4,
+            GatewayBindingType::Grpc => 5,
+            GatewayBindingType::WebSocket => 6,
+            GatewayBindingType::ServerSentEvents => 7,
+            GatewayBindingType::HttpProxy => 8,
+            GatewayBindingType::StatusPage => 9,
         };
 
         Ok(
@@ -467,6 +681,15 @@ mod internal {
                 invocation_context: None,
                 compiled_invocation_context_expr: None,
                 invocation_context_rib_input: None,
+                grpc_binding: None,
+                traffic_mirror: None,
+                http_proxy_binding: None,
+                worker_per_user: None,
+                bootstrap: None,
+                pagination: None,
+                content_negotiation: None,
+                error_mapping: None,
+                static_worker_names: None,
             },
         )
     }
@@ -0,0 +1,66 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_execution::grpc_transcoding::GrpcMethodDescriptor;
+
+// A gRPC binding forwards the HTTP+JSON request to a unary method of a backend gRPC service.
+// The request message is built from the JSON body, with path and query parameters
+// set on the top level fields of the same name, and the response message is returned as JSON.
+// Like static bindings, it contains no Rib, and therefore has no separate compiled form.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GrpcBinding {
+    // Base URL of the backend service, such as http://cart-service:9090
+    pub endpoint: String,
+    // Fully qualified name of the service, such as shop.v1.CartService
+    pub service: String,
+    pub method: String,
+    // Serialized google.protobuf.FileDescriptorSet describing the service and its messages
+    pub descriptor: Vec<u8>,
+}
+
+impl GrpcBinding {
+    pub fn method_descriptor(&self) -> Result<GrpcMethodDescriptor, String> {
+        GrpcMethodDescriptor::from_descriptor_set(&self.descriptor, &self.service, &self.method)
+    }
+
+    // Validates the binding when the API definition is compiled, so that invalid descriptors
+    // are rejected at registration rather than when serving requests
+    pub fn validate(&self) -> Result<(), String> {
+        http::Uri::try_from(self.endpoint.as_str())
+            .map_err(|err| format!("Invalid gRPC endpoint {}: {}", self.endpoint, err))?;
+        self.method_descriptor().map(|_| ())
+    }
+}
+
+impl From<GrpcBinding> for golem_api_grpc::proto::golem::apidefinition::GrpcBinding {
+    fn from(value: GrpcBinding) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::GrpcBinding {
+            endpoint: value.endpoint,
+            service: value.service,
+            method: value.method,
+            descriptor: value.descriptor,
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::GrpcBinding> for GrpcBinding {
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::GrpcBinding) -> Self {
+        GrpcBinding {
+            endpoint: value.endpoint,
+            service: value.service,
+            method: value.method,
+            descriptor: value.descriptor,
+        }
+    }
+}
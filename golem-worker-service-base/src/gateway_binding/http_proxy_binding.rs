@@ -0,0 +1,302 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::{DefaultWorkerServiceRibCompiler, WorkerServiceRibCompiler};
+use rib::{Expr, RibByteCode, RibError, RibInputTypeInfo};
+use std::time::Duration;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// An HTTP proxy binding forwards the request as is to an external HTTP service,
+// so that a gateway can front both Golem workers and existing services under one domain.
+// The optional request mapping is a Rib script with only the `request` available,
+// returning a record with an optional `path` replacing the path of the request,
+// and optional `headers` to set on the forwarded request (an empty value removes the header).
+// As the script cannot call workers, it is compiled without any component metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpProxyBinding {
+    // Base URL of the upstream service, such as http://legacy-shop:8080/api
+    pub upstream: String,
+    pub request_mapping: Option<Expr>,
+    pub timeout_ms: Option<u64>,
+}
+
+impl HttpProxyBinding {
+    pub fn validate(&self) -> Result<(), String> {
+        let upstream = url::Url::parse(&self.upstream)
+            .map_err(|err| format!("Invalid upstream {}: {}", self.upstream, err))?;
+
+        if upstream.scheme() != "http" && upstream.scheme() != "https" {
+            return Err(format!(
+                "Invalid upstream {}, expected an http or https URL",
+                self.upstream
+            ));
+        }
+
+        if upstream.query().is_some() || upstream.fragment().is_some() {
+            return Err(format!(
+                "Invalid upstream {}, query and fragment are taken from the request",
+                self.upstream
+            ));
+        }
+
+        if self.timeout_ms == Some(0) {
+            return Err("HTTP proxy timeout must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpProxyBindingCompiled {
+    pub upstream: String,
+    pub request_mapping_compiled: Option<RequestMappingCompiled>,
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestMappingCompiled {
+    pub request_mapping: Expr,
+    pub compiled_request_mapping: RibByteCode,
+    pub rib_input: RibInputTypeInfo,
+}
+
+impl HttpProxyBindingCompiled {
+    pub fn from_http_proxy_binding(binding: &HttpProxyBinding) -> Result<Self, RibError> {
+        let request_mapping_compiled = binding
+            .request_mapping
+            .as_ref()
+            .map(|request_mapping| {
+                DefaultWorkerServiceRibCompiler::compile_request_mapping(request_mapping).map(
+                    |compiled| RequestMappingCompiled {
+                        request_mapping: request_mapping.clone(),
+                        compiled_request_mapping: compiled.byte_code,
+                        rib_input: compiled.rib_input_type_info,
+                    },
+                )
+            })
+            .transpose()?;
+
+        Ok(HttpProxyBindingCompiled {
+            upstream: binding.upstream.clone(),
+            request_mapping_compiled,
+            timeout_ms: binding.timeout_ms,
+        })
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_TIMEOUT)
+    }
+
+    // The URL of the forwarded request, which is the path of the request (or the rewritten one)
+    // appended to the path of the upstream, keeping the query of the request.
+    // Paths with dot segments are rejected, as they could reach outside the path of the upstream.
+    pub fn upstream_url(&self, path: &str, query: Option<&str>) -> Result<url::Url, String> {
+        let mut url = url::Url::parse(&self.upstream)
+            .map_err(|err| format!("Invalid upstream {}: {}", self.upstream, err))?;
+
+        let base_path = url.path().trim_end_matches('/').to_string();
+        let path = path.trim_start_matches('/');
+
+        // Backslashes are separators in http URLs as well
+        if path.split(['/', '\\']).any(is_dot_segment) {
+            return Err(format!("Invalid path {path}: dot segments are not allowed"));
+        }
+
+        url.set_path(&format!("{}/{}", base_path, path));
+        url.set_query(query.filter(|query| !query.is_empty()));
+
+        if !url.path().starts_with(&format!("{}/", base_path)) {
+            return Err(format!(
+                "Invalid path {path}: it is outside of the path of the upstream"
+            ));
+        }
+
+        Ok(url)
+    }
+}
+
+// `.` and `..`, including their percent-encoded forms which URLs treat the same way
+fn is_dot_segment(segment: &str) -> bool {
+    let segment = segment.to_ascii_lowercase().replace("%2e", ".");
+    segment == "." || segment == ".."
+}
+
+impl From<HttpProxyBindingCompiled> for HttpProxyBinding {
+    fn from(value: HttpProxyBindingCompiled) -> Self {
+        HttpProxyBinding {
+            upstream: value.upstream,
+            request_mapping: value
+                .request_mapping_compiled
+                .map(|compiled| compiled.request_mapping),
+            timeout_ms: value.timeout_ms,
+        }
+    }
+}
+
+impl From<HttpProxyBinding> for golem_api_grpc::proto::golem::apidefinition::HttpProxyBinding {
+    fn from(value: HttpProxyBinding) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::HttpProxyBinding {
+            upstream: value.upstream,
+            request_mapping: value.request_mapping.map(|x| x.into()),
+            timeout_ms: value.timeout_ms,
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::HttpProxyBinding> for HttpProxyBinding {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::HttpProxyBinding,
+    ) -> Result<Self, Self::Error> {
+        Ok(HttpProxyBinding {
+            upstream: value.upstream,
+            request_mapping: value.request_mapping.map(Expr::try_from).transpose()?,
+            timeout_ms: value.timeout_ms,
+        })
+    }
+}
+
+impl TryFrom<HttpProxyBindingCompiled>
+    for golem_api_grpc::proto::golem::apidefinition::CompiledHttpProxyBinding
+{
+    type Error = String;
+
+    fn try_from(value: HttpProxyBindingCompiled) -> Result<Self, Self::Error> {
+        let (request_mapping, compiled_request_mapping_expr, request_mapping_rib_input) =
+            match value.request_mapping_compiled {
+                Some(x) => (
+                    Some(x.request_mapping.into()),
+                    Some(x.compiled_request_mapping.try_into()?),
+                    Some(x.rib_input.into()),
+                ),
+                None => (None, None, None),
+            };
+
+        Ok(
+            golem_api_grpc::proto::golem::apidefinition::CompiledHttpProxyBinding {
+                upstream: value.upstream,
+                request_mapping,
+                compiled_request_mapping_expr,
+                request_mapping_rib_input,
+                timeout_ms: value.timeout_ms,
+            },
+        )
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::CompiledHttpProxyBinding>
+    for HttpProxyBindingCompiled
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::CompiledHttpProxyBinding,
+    ) -> Result<Self, Self::Error> {
+        let request_mapping_compiled = match (
+            value.request_mapping,
+            value.compiled_request_mapping_expr,
+            value.request_mapping_rib_input,
+        ) {
+            (Some(request_mapping), Some(compiled_request_mapping), Some(rib_input)) => {
+                Some(RequestMappingCompiled {
+                    request_mapping: Expr::try_from(request_mapping)?,
+                    compiled_request_mapping: RibByteCode::try_from(compiled_request_mapping)?,
+                    rib_input: RibInputTypeInfo::try_from(rib_input)?,
+                })
+            }
+            _ => None,
+        };
+
+        Ok(HttpProxyBindingCompiled {
+            upstream: value.upstream,
+            request_mapping_compiled,
+            timeout_ms: value.timeout_ms,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn upstream_url_appends_path_and_keeps_query() {
+        let binding = HttpProxyBinding {
+            upstream: "http://legacy-shop:8080/api/".to_string(),
+            request_mapping: None,
+            timeout_ms: None,
+        };
+        let compiled = HttpProxyBindingCompiled::from_http_proxy_binding(&binding).unwrap();
+
+        assert_eq!(
+            compiled
+                .upstream_url("/orders/1", Some("expand=items"))
+                .unwrap()
+                .as_str(),
+            "http://legacy-shop:8080/api/orders/1?expand=items"
+        );
+        assert_eq!(
+            compiled.upstream_url("", Some("")).unwrap().as_str(),
+            "http://legacy-shop:8080/api/"
+        );
+        assert_eq!(
+            compiled
+                .upstream_url("/files/..data/v1.2", None)
+                .unwrap()
+                .as_str(),
+            "http://legacy-shop:8080/api/files/..data/v1.2"
+        );
+        assert!(binding.validate().is_ok());
+        assert!(HttpProxyBinding {
+            upstream: "ftp://legacy-shop/api".to_string(),
+            ..binding.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(HttpProxyBinding {
+            timeout_ms: Some(0),
+            ..binding
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn upstream_url_rejects_dot_segments() {
+        let binding = HttpProxyBinding {
+            upstream: "http://legacy-shop:8080/api/".to_string(),
+            request_mapping: None,
+            timeout_ms: None,
+        };
+        let compiled = HttpProxyBindingCompiled::from_http_proxy_binding(&binding).unwrap();
+
+        for path in [
+            "/../admin",
+            "/orders/../../admin",
+            "/%2e%2e/admin",
+            "/%2E%2e/admin",
+            "/.%2e/admin",
+            "/orders/%2e/1",
+            "/..\\admin",
+        ] {
+            assert!(compiled.upstream_url(path, None).is_err(), "{path}");
+        }
+    }
+}
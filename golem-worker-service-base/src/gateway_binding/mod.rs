@@ -13,21 +13,37 @@
 // limitations under the License.
 
 pub(crate) use self::http_handler_binding::*;
+pub(crate) use self::traffic_mirror::*;
 pub(crate) use self::worker_binding::*;
 pub(crate) use crate::gateway_execution::gateway_binding_resolver::*;
 use crate::gateway_rib_compiler::DefaultWorkerServiceRibCompiler;
 use crate::gateway_rib_compiler::WorkerServiceRibCompiler;
+pub use content_negotiation::*;
+pub use error_mapping::*;
 pub(crate) use gateway_binding_compiled::*;
 use golem_api_grpc::proto::golem::apidefinition::GatewayBindingType;
 use golem_service_base::model::VersionedComponentId;
 use golem_wasm_ast::analysis::AnalysedExport;
+pub use grpc_binding::*;
+pub use http_proxy_binding::*;
+pub use pagination::*;
 use rib::{Expr, RibByteCode, RibError, RibInputTypeInfo};
 pub use static_binding::*;
+pub use worker_bootstrap::*;
+pub use worker_per_user::*;
 
+mod content_negotiation;
+mod error_mapping;
 mod gateway_binding_compiled;
+mod grpc_binding;
 mod http_handler_binding;
+mod http_proxy_binding;
+mod pagination;
 mod static_binding;
+mod traffic_mirror;
 mod worker_binding;
+mod worker_bootstrap;
+mod worker_per_user;
 
 // A gateway binding is integration to the backend. This is similar to AWS's x-amazon-gateway-integration
 // where it holds the details of where to re-route.
@@ -41,6 +57,14 @@ pub enum GatewayBinding {
     FileServer(WorkerBinding),
     Static(StaticBinding),
     HttpHandler(HttpHandlerBinding),
+    Grpc(GrpcBinding),
+    // A WebSocket binding reuses the worker binding, with the response mapping
+    // being evaluated for every incoming message instead of once per request
+    WebSocket(WorkerBinding),
+    // A server-sent events binding also reuses the worker binding, with the response mapping
+    // being evaluated for every event emitted by the worker, to stream them to the client
+    ServerSentEvents(WorkerBinding),
+    HttpProxy(HttpProxyBinding),
 }
 
 impl GatewayBinding {
@@ -49,9 +73,14 @@ impl GatewayBinding {
             Self::Default(_) => false,
             Self::FileServer(_) => false,
             Self::HttpHandler(_) => false,
+            Self::Grpc(_) => false,
+            Self::WebSocket(_) => false,
+            Self::ServerSentEvents(_) => false,
+            Self::HttpProxy(_) => false,
             Self::Static(s) => match s {
                 StaticBinding::HttpCorsPreflight(_) => true,
                 StaticBinding::HttpAuthCallBack(_) => false,
+                StaticBinding::StatusPage => false,
             },
         }
     }
@@ -61,9 +90,14 @@ impl GatewayBinding {
             Self::Default(_) => false,
             Self::FileServer(_) => false,
             Self::HttpHandler(_) => false,
+            Self::Grpc(_) => false,
+            Self::WebSocket(_) => false,
+            Self::ServerSentEvents(_) => false,
+            Self::HttpProxy(_) => false,
             Self::Static(s) => match s {
                 StaticBinding::HttpCorsPreflight(_) => false,
                 StaticBinding::HttpAuthCallBack(_) => true,
+                StaticBinding::StatusPage => false,
             },
         }
     }
@@ -72,6 +106,20 @@ impl GatewayBinding {
         GatewayBinding::Static(value)
     }
 
+    // All the components the binding depends on, including the shadow component of a traffic mirror
+    pub fn get_component_ids(&self) -> Vec<VersionedComponentId> {
+        let mut component_ids: Vec<VersionedComponentId> =
+            self.get_component_id().into_iter().collect();
+
+        if let Self::Default(worker_binding) = self {
+            if let Some(traffic_mirror) = &worker_binding.traffic_mirror {
+                component_ids.push(traffic_mirror.component_id.clone());
+            }
+        }
+
+        component_ids
+    }
+
     pub fn get_component_id(&self) -> Option<VersionedComponentId> {
         match self {
             Self::Default(worker_binding) => Some(worker_binding.component_id.clone()),
@@ -79,7 +127,11 @@ impl GatewayBinding {
             Self::HttpHandler(http_handler_binding) => {
                 Some(http_handler_binding.component_id.clone())
             }
+            Self::WebSocket(worker_binding) => Some(worker_binding.component_id.clone()),
+            Self::ServerSentEvents(worker_binding) => Some(worker_binding.component_id.clone()),
             Self::Static(_) => None,
+            Self::Grpc(_) => None,
+            Self::HttpProxy(_) => None,
         }
     }
 }
@@ -97,6 +149,15 @@ impl TryFrom<GatewayBinding> for golem_api_grpc::proto::golem::apidefinition::Ga
                     idempotency_key: worker_binding.idempotency_key.map(|x| x.into()),
                     static_binding: None,
                     invocation_context: worker_binding.invocation_context.map(|x| x.into()),
+                    grpc_binding: None,
+                    traffic_mirror: worker_binding.traffic_mirror.map(|x| x.into()),
+                    http_proxy_binding: None,
+                    worker_per_user: worker_binding.worker_per_user.map(|x| x.into()),
+                    bootstrap: worker_binding.bootstrap.map(|x| x.into()),
+                    pagination: worker_binding.pagination.map(|x| x.into()),
+                    content_negotiation: worker_binding.content_negotiation.map(|x| x.into()),
+                    error_mapping: worker_binding.error_mapping.map(|x| x.into()),
+                    static_worker_names: Some(worker_binding.static_worker_names),
                 },
             ),
             GatewayBinding::FileServer(worker_binding) => Ok(
@@ -108,6 +169,55 @@ impl TryFrom<GatewayBinding> for golem_api_grpc::proto::golem::apidefinition::Ga
                     idempotency_key: worker_binding.idempotency_key.map(|x| x.into()),
                     static_binding: None,
                     invocation_context: None,
+                    grpc_binding: None,
+                    traffic_mirror: None,
+                    http_proxy_binding: None,
+                    worker_per_user: worker_binding.worker_per_user.map(|x| x.into()),
+                    bootstrap: worker_binding.bootstrap.map(|x| x.into()),
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: Some(worker_binding.static_worker_names),
+                },
+            ),
+            GatewayBinding::WebSocket(worker_binding) => Ok(
+                golem_api_grpc::proto::golem::apidefinition::GatewayBinding {
+                    binding_type: Some(GatewayBindingType::WebSocket.into()),
+                    component: Some(worker_binding.component_id.into()),
+                    worker_name: worker_binding.worker_name.map(|x| x.into()),
+                    response: Some(worker_binding.response_mapping.0.into()),
+                    idempotency_key: None,
+                    static_binding: None,
+                    invocation_context: worker_binding.invocation_context.map(|x| x.into()),
+                    grpc_binding: None,
+                    traffic_mirror: None,
+                    http_proxy_binding: None,
+                    worker_per_user: worker_binding.worker_per_user.map(|x| x.into()),
+                    bootstrap: worker_binding.bootstrap.map(|x| x.into()),
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: Some(worker_binding.static_worker_names),
+                },
+            ),
+            GatewayBinding::ServerSentEvents(worker_binding) => Ok(
+                golem_api_grpc::proto::golem::apidefinition::GatewayBinding {
+                    binding_type: Some(GatewayBindingType::ServerSentEvents.into()),
+                    component: Some(worker_binding.component_id.into()),
+                    worker_name: worker_binding.worker_name.map(|x| x.into()),
+                    response: Some(worker_binding.response_mapping.0.into()),
+                    idempotency_key: None,
+                    static_binding: None,
+                    invocation_context: worker_binding.invocation_context.map(|x| x.into()),
+                    grpc_binding: None,
+                    traffic_mirror: None,
+                    http_proxy_binding: None,
+                    worker_per_user: worker_binding.worker_per_user.map(|x| x.into()),
+                    bootstrap: worker_binding.bootstrap.map(|x| x.into()),
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: Some(worker_binding.static_worker_names),
                 },
             ),
             GatewayBinding::Static(static_binding) => {
@@ -124,6 +234,7 @@ impl TryFrom<GatewayBinding> for golem_api_grpc::proto::golem::apidefinition::Ga
                 let gateway_binding_type: GatewayBindingType = match inner {
                     golem_api_grpc::proto::golem::apidefinition::static_binding::StaticBinding::HttpCorsPreflight(_) => GatewayBindingType::CorsPreflight,
                     golem_api_grpc::proto::golem::apidefinition::static_binding::StaticBinding::AuthCallback(_)  => GatewayBindingType::AuthCallBack,
+                    golem_api_grpc::proto::golem::apidefinition::static_binding::StaticBinding::StatusPage(_) => GatewayBindingType::StatusPage,
                 };
 
                 Ok(
@@ -135,6 +246,15 @@ impl TryFrom<GatewayBinding> for golem_api_grpc::proto::golem::apidefinition::Ga
                         idempotency_key: None,
                         static_binding: Some(static_binding),
                         invocation_context: None,
+                        grpc_binding: None,
+                        traffic_mirror: None,
+                        http_proxy_binding: None,
+                        worker_per_user: None,
+                        bootstrap: None,
+                        pagination: None,
+                        content_negotiation: None,
+                        error_mapping: None,
+                        static_worker_names: None,
                     },
                 )
             }
@@ -147,6 +267,55 @@ impl TryFrom<GatewayBinding> for golem_api_grpc::proto::golem::apidefinition::Ga
                     idempotency_key: worker_binding.idempotency_key.map(|x| x.into()),
                     static_binding: None,
                     invocation_context: None,
+                    grpc_binding: None,
+                    traffic_mirror: None,
+                    http_proxy_binding: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
+                },
+            ),
+            GatewayBinding::Grpc(grpc_binding) => Ok(
+                golem_api_grpc::proto::golem::apidefinition::GatewayBinding {
+                    binding_type: Some(GatewayBindingType::Grpc.into()),
+                    component: None,
+                    worker_name: None,
+                    response: None,
+                    idempotency_key: None,
+                    static_binding: None,
+                    invocation_context: None,
+                    grpc_binding: Some(grpc_binding.into()),
+                    traffic_mirror: None,
+                    http_proxy_binding: None,
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
+                },
+            ),
+            GatewayBinding::HttpProxy(http_proxy_binding) => Ok(
+                golem_api_grpc::proto::golem::apidefinition::GatewayBinding {
+                    binding_type: Some(GatewayBindingType::HttpProxy.into()),
+                    component: None,
+                    worker_name: None,
+                    response: None,
+                    idempotency_key: None,
+                    static_binding: None,
+                    invocation_context: None,
+                    grpc_binding: None,
+                    traffic_mirror: None,
+                    http_proxy_binding: Some(http_proxy_binding.into()),
+                    worker_per_user: None,
+                    bootstrap: None,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: None,
                 },
             ),
         }
@@ -170,12 +339,18 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::GatewayBinding> for Ga
                 let component_id = VersionedComponentId::try_from(
                     value.component.ok_or("Missing component id".to_string())?,
                 )?;
+                let worker_per_user = value.worker_per_user.map(WorkerPerUser::from);
+                let bootstrap = value.bootstrap.map(WorkerBootstrap::try_from).transpose()?;
                 let worker_name = value.worker_name.map(Expr::try_from).transpose()?;
                 let idempotency_key = value.idempotency_key.map(Expr::try_from).transpose()?;
                 let invocation_context =
                     value.invocation_context.map(Expr::try_from).transpose()?;
                 let response_proto = value.response.ok_or("Missing response field")?;
                 let response = Expr::try_from(response_proto)?;
+                let traffic_mirror = value
+                    .traffic_mirror
+                    .map(TrafficMirror::try_from)
+                    .transpose()?;
 
                 Ok(GatewayBinding::Default(WorkerBinding {
                     component_id,
@@ -183,12 +358,27 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::GatewayBinding> for Ga
                     idempotency_key,
                     response_mapping: ResponseMapping(response),
                     invocation_context,
+                    traffic_mirror,
+                    worker_per_user,
+                    bootstrap,
+                    pagination: value.pagination.map(Pagination::from),
+                    content_negotiation: value
+                        .content_negotiation
+                        .map(ContentNegotiation::try_from)
+                        .transpose()?,
+                    error_mapping: value
+                        .error_mapping
+                        .map(ErrorMapping::try_from)
+                        .transpose()?,
+                    static_worker_names: value.static_worker_names.unwrap_or_default(),
                 }))
             }
             golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::FileServer => {
                 let component_id = VersionedComponentId::try_from(
                     value.component.ok_or("Missing component id".to_string())?,
                 )?;
+                let worker_per_user = value.worker_per_user.map(WorkerPerUser::from);
+                let bootstrap = value.bootstrap.map(WorkerBootstrap::try_from).transpose()?;
                 let worker_name = value.worker_name.map(Expr::try_from).transpose()?;
                 let idempotency_key = value.idempotency_key.map(Expr::try_from).transpose()?;
                 let response_proto = value.response.ok_or("Missing response field")?;
@@ -200,6 +390,67 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::GatewayBinding> for Ga
                     idempotency_key,
                     response_mapping: ResponseMapping(response),
                     invocation_context: None,
+                    traffic_mirror: None,
+                    worker_per_user,
+                    bootstrap,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: value.static_worker_names.unwrap_or_default(),
+                }))
+            }
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::WebSocket => {
+                let component_id = VersionedComponentId::try_from(
+                    value.component.ok_or("Missing component id".to_string())?,
+                )?;
+                let worker_per_user = value.worker_per_user.map(WorkerPerUser::from);
+                let bootstrap = value.bootstrap.map(WorkerBootstrap::try_from).transpose()?;
+                let worker_name = value.worker_name.map(Expr::try_from).transpose()?;
+                let invocation_context =
+                    value.invocation_context.map(Expr::try_from).transpose()?;
+                let response_proto = value.response.ok_or("Missing response field")?;
+                let response = Expr::try_from(response_proto)?;
+
+                Ok(GatewayBinding::WebSocket(WorkerBinding {
+                    component_id,
+                    worker_name,
+                    idempotency_key: None,
+                    response_mapping: ResponseMapping(response),
+                    invocation_context,
+                    traffic_mirror: None,
+                    worker_per_user,
+                    bootstrap,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: value.static_worker_names.unwrap_or_default(),
+                }))
+            }
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::ServerSentEvents => {
+                let component_id = VersionedComponentId::try_from(
+                    value.component.ok_or("Missing component id".to_string())?,
+                )?;
+                let worker_per_user = value.worker_per_user.map(WorkerPerUser::from);
+                let bootstrap = value.bootstrap.map(WorkerBootstrap::try_from).transpose()?;
+                let worker_name = value.worker_name.map(Expr::try_from).transpose()?;
+                let invocation_context =
+                    value.invocation_context.map(Expr::try_from).transpose()?;
+                let response_proto = value.response.ok_or("Missing response field")?;
+                let response = Expr::try_from(response_proto)?;
+
+                Ok(GatewayBinding::ServerSentEvents(WorkerBinding {
+                    component_id,
+                    worker_name,
+                    idempotency_key: None,
+                    response_mapping: ResponseMapping(response),
+                    invocation_context,
+                    traffic_mirror: None,
+                    worker_per_user,
+                    bootstrap,
+                    pagination: None,
+                    content_negotiation: None,
+                    error_mapping: None,
+                    static_worker_names: value.static_worker_names.unwrap_or_default(),
                 }))
             }
             golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::HttpHandler => {
@@ -230,6 +481,30 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::GatewayBinding> for Ga
                     static_binding,
                 )?))
             }
+
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::StatusPage => {
+                let static_binding = value.static_binding.ok_or("Missing static binding")?;
+
+                Ok(GatewayBinding::static_binding(StaticBinding::try_from(
+                    static_binding,
+                )?))
+            }
+
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::Grpc => {
+                let grpc_binding = value.grpc_binding.ok_or("Missing grpc binding")?;
+
+                Ok(GatewayBinding::Grpc(GrpcBinding::from(grpc_binding)))
+            }
+
+            golem_api_grpc::proto::golem::apidefinition::GatewayBindingType::HttpProxy => {
+                let http_proxy_binding = value
+                    .http_proxy_binding
+                    .ok_or("Missing http proxy binding")?;
+
+                Ok(GatewayBinding::HttpProxy(HttpProxyBinding::try_from(
+                    http_proxy_binding,
+                )?))
+            }
         }
     }
 }
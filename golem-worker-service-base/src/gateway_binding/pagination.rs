@@ -0,0 +1,165 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::pretty_print_type_name;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+
+pub const CURSOR_QUERY_PARAM: &str = "cursor";
+pub const LIMIT_QUERY_PARAM: &str = "limit";
+
+const DEFAULT_LIMIT: u32 = 20;
+const DEFAULT_MAX_LIMIT: u32 = 100;
+
+/// Serves a list-returning worker function as pages, with the standard `cursor` and `limit`
+/// query parameters and a `Link` header to the next page.
+///
+/// The page requested by the client is available to the response mapping as
+/// `request.page.cursor` (`option<string>`) and `request.page.limit` (`u32`), to be passed to a
+/// function returning the items and the cursor of the next page, either as a tuple of
+/// `list<T>` and `option<string>`, or as a record with `items` and `next-cursor` fields.
+/// The response mapping returns this result as it is, and the items become the response body.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct Pagination {
+    /// Number of items of the pages requested without a limit, 20 by default
+    pub default_limit: Option<u32>,
+    /// Largest limit a client can request, 100 by default
+    pub max_limit: Option<u32>,
+}
+
+impl Pagination {
+    pub fn default_limit(&self) -> u32 {
+        self.default_limit
+            .unwrap_or(DEFAULT_LIMIT.min(self.max_limit()))
+    }
+
+    pub fn max_limit(&self) -> u32 {
+        self.max_limit.unwrap_or(DEFAULT_MAX_LIMIT)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_limit() == 0 {
+            return Err("The max limit of the pagination must be positive".to_string());
+        }
+
+        if self.default_limit() == 0 || self.default_limit() > self.max_limit() {
+            return Err(format!(
+                "The default limit of the pagination must be between 1 and {}, but found {}",
+                self.max_limit(),
+                self.default_limit()
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Checks that the response mapping returns a page
+    pub fn validate_page_type(typ: &AnalysedType) -> Result<(), String> {
+        page_field_indices(typ).map(|_| ()).ok_or(format!(
+            "A paginated binding has to return a tuple of list and option<string>, or a record with items and next-cursor fields, but returns {}",
+            pretty_print_type_name(typ)
+        ))
+    }
+}
+
+// The positions of the items and of the next cursor in the tuple or record of a page
+pub fn page_field_indices(typ: &AnalysedType) -> Option<(usize, usize)> {
+    let is_list = |typ: &AnalysedType| matches!(typ, AnalysedType::List(_));
+    let is_cursor = |typ: &AnalysedType| match typ {
+        AnalysedType::Option(option) => matches!(*option.inner, AnalysedType::Str(_)),
+        _ => false,
+    };
+
+    match typ {
+        AnalysedType::Tuple(tuple)
+            if tuple.items.len() == 2 && is_list(&tuple.items[0]) && is_cursor(&tuple.items[1]) =>
+        {
+            Some((0, 1))
+        }
+        AnalysedType::Record(record) => {
+            let items = record
+                .fields
+                .iter()
+                .position(|field| field.name == "items" && is_list(&field.typ))?;
+
+            let next_cursor = record.fields.iter().position(|field| {
+                (field.name == "next-cursor" || field.name == "next_cursor")
+                    && is_cursor(&field.typ)
+            })?;
+
+            Some((items, next_cursor))
+        }
+        _ => None,
+    }
+}
+
+impl From<Pagination> for golem_api_grpc::proto::golem::apidefinition::Pagination {
+    fn from(value: Pagination) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::Pagination {
+            default_limit: value.default_limit,
+            max_limit: value.max_limit,
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::Pagination> for Pagination {
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::Pagination) -> Self {
+        Pagination {
+            default_limit: value.default_limit,
+            max_limit: value.max_limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{field, list, option, record, str, tuple, u32};
+    use test_r::test;
+
+    #[test]
+    fn pages_are_tuples_or_records_of_items_and_next_cursor() {
+        let pagination = Pagination {
+            default_limit: None,
+            max_limit: Some(10),
+        };
+
+        assert_eq!(pagination.default_limit(), 10);
+        assert!(pagination.validate().is_ok());
+
+        let invalid = Pagination {
+            default_limit: Some(50),
+            max_limit: Some(10),
+        };
+
+        assert!(invalid.validate().is_err());
+
+        assert_eq!(
+            page_field_indices(&tuple(vec![list(str()), option(str())])),
+            Some((0, 1))
+        );
+        assert_eq!(
+            page_field_indices(&record(vec![
+                field("next-cursor", option(str())),
+                field("items", list(u32())),
+            ])),
+            Some((1, 0))
+        );
+        assert!(Pagination::validate_page_type(&tuple(vec![list(str()), str()])).is_err());
+        assert!(Pagination::validate_page_type(&list(str())).is_err());
+    }
+}
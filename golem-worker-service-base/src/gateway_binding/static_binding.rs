@@ -25,6 +25,8 @@ use crate::gateway_security::SecuritySchemeWithProviderMetadata;
 pub enum StaticBinding {
     HttpCorsPreflight(Box<HttpCors>),
     HttpAuthCallBack(Box<HttpAuthenticationMiddleware>),
+    // A read-only status page of the deployment, rendered from the gateway's own route metrics
+    StatusPage,
 }
 
 impl StaticBinding {
@@ -63,6 +65,9 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::StaticBinding> for Sta
                     security_scheme_with_metadata
                 })))
             }
+            Some(golem_api_grpc::proto::golem::apidefinition::static_binding::StaticBinding::StatusPage(_)) => {
+                Ok(StaticBinding::StatusPage)
+            }
             None => Err("Static Binding missing".to_string()),
         }
     }
@@ -90,6 +95,13 @@ impl TryFrom<StaticBinding> for golem_api_grpc::proto::golem::apidefinition::Sta
                     )),
                 })
             }
+            StaticBinding::StatusPage => {
+                Ok(golem_api_grpc::proto::golem::apidefinition::StaticBinding {
+                    static_binding: Some(golem_api_grpc::proto::golem::apidefinition::static_binding::StaticBinding::StatusPage(
+                        golem_api_grpc::proto::golem::apidefinition::StatusPage {}
+                    )),
+                })
+            }
         }
     }
 }
@@ -0,0 +1,270 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{ResponseMapping, ResponseMappingCompiled};
+use golem_service_base::model::VersionedComponentId;
+use golem_wasm_ast::analysis::AnalysedExport;
+use rib::{RibError, RibOutputTypeInfo};
+
+// Traffic mirroring (shadowing) of a worker binding.
+// A share of the requests served by the binding is replayed asynchronously against
+// a shadow component, using the same worker name and response mapping.
+// The responses of the shadow component are never returned to the client,
+// and are only compared with the primary response, to aggregate mismatch statistics
+// and, if divergence logging is enabled, to log the requests they differ for.
+// This allows validating a rewrite of a component against real traffic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficMirror {
+    pub component_id: VersionedComponentId,
+    // Percentage of the requests to mirror, between 0 and 100
+    pub percentage: u32,
+    pub log_divergence: bool,
+    // Fields ignored when comparing the responses, such as timestamps or generated ids,
+    // given as dot separated paths into the JSON representation of the response
+    pub ignored_fields: Vec<String>,
+}
+
+impl TrafficMirror {
+    pub fn validate(&self, primary_component_id: &VersionedComponentId) -> Result<(), String> {
+        if self.percentage > 100 {
+            return Err(format!(
+                "Traffic mirror percentage must be between 0 and 100, but found {}",
+                self.percentage
+            ));
+        }
+
+        // Workers are identified by component and name, so a shadow with the same component
+        // would end up invoking (and mutating) the very same workers as the primary binding
+        if self.component_id.component_id == primary_component_id.component_id {
+            return Err(format!(
+                "Traffic mirror component {} must be different from the component of the binding",
+                self.component_id.component_id
+            ));
+        }
+
+        if let Some(field) = self
+            .ignored_fields
+            .iter()
+            .find(|field| field.is_empty() || field.split('.').any(|segment| segment.is_empty()))
+        {
+            return Err(format!(
+                "Invalid ignored field '{}' in traffic mirror, expected a dot separated path",
+                field
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficMirrorCompiled {
+    pub component_id: VersionedComponentId,
+    pub percentage: u32,
+    pub log_divergence: bool,
+    pub ignored_fields: Vec<String>,
+    // The response mapping of the binding, compiled against the exports of the shadow component
+    pub response_compiled: ResponseMappingCompiled,
+}
+
+impl TrafficMirrorCompiled {
+    pub fn from_traffic_mirror(
+        traffic_mirror: &TrafficMirror,
+        response_mapping: &ResponseMapping,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<Self, RibError> {
+        let response_compiled =
+            ResponseMappingCompiled::from_response_mapping(response_mapping, export_metadata)?;
+
+        Ok(TrafficMirrorCompiled {
+            component_id: traffic_mirror.component_id.clone(),
+            percentage: traffic_mirror.percentage,
+            log_divergence: traffic_mirror.log_divergence,
+            ignored_fields: traffic_mirror.ignored_fields.clone(),
+            response_compiled,
+        })
+    }
+
+    // Decides whether a request should be mirrored, given a uniformly distributed sample in [0, 100)
+    pub fn should_mirror(&self, sample: u32) -> bool {
+        sample < self.percentage
+    }
+}
+
+impl From<TrafficMirrorCompiled> for TrafficMirror {
+    fn from(value: TrafficMirrorCompiled) -> Self {
+        TrafficMirror {
+            component_id: value.component_id,
+            percentage: value.percentage,
+            log_divergence: value.log_divergence,
+            ignored_fields: value.ignored_fields,
+        }
+    }
+}
+
+impl From<TrafficMirror> for golem_api_grpc::proto::golem::apidefinition::TrafficMirror {
+    fn from(value: TrafficMirror) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::TrafficMirror {
+            component: Some(value.component_id.into()),
+            percentage: value.percentage,
+            log_divergence: value.log_divergence,
+            ignored_fields: value.ignored_fields,
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::TrafficMirror> for TrafficMirror {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::TrafficMirror,
+    ) -> Result<Self, Self::Error> {
+        Ok(TrafficMirror {
+            component_id: value
+                .component
+                .ok_or("Missing component for traffic mirror")?
+                .try_into()?,
+            percentage: value.percentage,
+            log_divergence: value.log_divergence,
+            ignored_fields: value.ignored_fields,
+        })
+    }
+}
+
+impl TryFrom<TrafficMirrorCompiled>
+    for golem_api_grpc::proto::golem::apidefinition::CompiledTrafficMirror
+{
+    type Error = String;
+
+    fn try_from(value: TrafficMirrorCompiled) -> Result<Self, Self::Error> {
+        Ok(
+            golem_api_grpc::proto::golem::apidefinition::CompiledTrafficMirror {
+                component: Some(value.component_id.into()),
+                percentage: value.percentage,
+                log_divergence: value.log_divergence,
+                compiled_response_expr: Some(
+                    value
+                        .response_compiled
+                        .response_mapping_compiled
+                        .try_into()?,
+                ),
+                response_rib_input: Some(value.response_compiled.rib_input.into()),
+                worker_functions_in_response: value
+                    .response_compiled
+                    .worker_calls
+                    .map(|x| x.into()),
+                response_rib_output: value
+                    .response_compiled
+                    .rib_output
+                    .map(golem_api_grpc::proto::golem::rib::RibOutputType::from),
+                ignored_fields: value.ignored_fields,
+            },
+        )
+    }
+}
+
+impl TrafficMirrorCompiled {
+    // The response mapping expression is not repeated in the compiled traffic mirror,
+    // as it is the same as the one of the binding it belongs to
+    pub fn from_proto(
+        value: golem_api_grpc::proto::golem::apidefinition::CompiledTrafficMirror,
+        response_mapping: &ResponseMapping,
+    ) -> Result<Self, String> {
+        let response_compiled = ResponseMappingCompiled {
+            response_mapping_expr: response_mapping.0.clone(),
+            response_mapping_compiled: rib::RibByteCode::try_from(
+                value
+                    .compiled_response_expr
+                    .ok_or("Missing compiled_response for traffic mirror")?,
+            )?,
+            rib_input: rib::RibInputTypeInfo::try_from(
+                value
+                    .response_rib_input
+                    .ok_or("Missing response_rib_input for traffic mirror")?,
+            )?,
+            worker_calls: value
+                .worker_functions_in_response
+                .map(rib::WorkerFunctionsInRib::try_from)
+                .transpose()?,
+            rib_output: value
+                .response_rib_output
+                .map(RibOutputTypeInfo::try_from)
+                .transpose()?,
+        };
+
+        Ok(TrafficMirrorCompiled {
+            component_id: value
+                .component
+                .ok_or("Missing component for traffic mirror")?
+                .try_into()?,
+            percentage: value.percentage,
+            log_divergence: value.log_divergence,
+            ignored_fields: value.ignored_fields,
+            response_compiled,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::ComponentId;
+    use test_r::test;
+    use uuid::Uuid;
+
+    fn component(version: u64) -> VersionedComponentId {
+        VersionedComponentId {
+            component_id: ComponentId(Uuid::nil()),
+            version,
+        }
+    }
+
+    #[test]
+    fn traffic_mirror_validation() {
+        let primary = VersionedComponentId {
+            component_id: ComponentId(Uuid::new_v4()),
+            version: 0,
+        };
+
+        let valid = TrafficMirror {
+            component_id: component(1),
+            percentage: 10,
+            log_divergence: true,
+            ignored_fields: vec!["timestamp".to_string(), "meta.request-id".to_string()],
+        };
+
+        let invalid_percentage = TrafficMirror {
+            percentage: 101,
+            ..valid.clone()
+        };
+
+        let same_component = TrafficMirror {
+            component_id: VersionedComponentId {
+                version: 3,
+                ..primary.clone()
+            },
+            ..valid.clone()
+        };
+
+        let invalid_ignored_field = TrafficMirror {
+            ignored_fields: vec!["meta..request-id".to_string()],
+            ..valid.clone()
+        };
+
+        assert!(valid.validate(&primary).is_ok());
+        assert!(invalid_percentage.validate(&primary).is_err());
+        assert!(same_component.validate(&primary).is_err());
+        assert!(invalid_ignored_field.validate(&primary).is_err());
+    }
+}
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{IdempotencyKeyCompiled, InvocationContextCompiled, WorkerNameCompiled};
+use super::{
+    ContentNegotiation, ErrorMapping, IdempotencyKeyCompiled, InvocationContextCompiled,
+    Pagination, TrafficMirror, TrafficMirrorCompiled, WorkerBootstrap, WorkerBootstrapCompiled,
+    WorkerNameCompiled, WorkerPerUser,
+};
 use crate::gateway_rib_compiler::DefaultWorkerServiceRibCompiler;
 use crate::gateway_rib_compiler::WorkerServiceRibCompiler;
 use golem_service_base::model::VersionedComponentId;
@@ -27,6 +31,16 @@ pub struct WorkerBinding {
     pub idempotency_key: Option<Expr>,
     pub response_mapping: ResponseMapping,
     pub invocation_context: Option<Expr>,
+    pub traffic_mirror: Option<TrafficMirror>,
+    // Derives the worker name from the identity of the request, in place of `worker_name`
+    pub worker_per_user: Option<WorkerPerUser>,
+    // Arguments and environment of the worker, if the request creates it
+    pub bootstrap: Option<WorkerBootstrap>,
+    pub pagination: Option<Pagination>,
+    pub content_negotiation: Option<ContentNegotiation>,
+    pub error_mapping: Option<ErrorMapping>,
+    // Requires the worker names given to `instance()` by the scripts to be known at compile time
+    pub static_worker_names: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -36,6 +50,14 @@ pub struct WorkerBindingCompiled {
     pub idempotency_key_compiled: Option<IdempotencyKeyCompiled>,
     pub response_compiled: ResponseMappingCompiled,
     pub invocation_context_compiled: Option<InvocationContextCompiled>,
+    pub traffic_mirror_compiled: Option<TrafficMirrorCompiled>,
+    // The worker name compiled from it is kept in `worker_name_compiled`
+    pub worker_per_user: Option<WorkerPerUser>,
+    pub bootstrap_compiled: Option<WorkerBootstrapCompiled>,
+    pub pagination: Option<Pagination>,
+    pub content_negotiation: Option<ContentNegotiation>,
+    pub error_mapping: Option<ErrorMapping>,
+    pub static_worker_names: bool,
 }
 
 impl WorkerBindingCompiled {
@@ -43,13 +65,8 @@ impl WorkerBindingCompiled {
         gateway_worker_binding: &WorkerBinding,
         export_metadata: &[AnalysedExport],
     ) -> Result<Self, RibError> {
-        let worker_name_compiled: Option<WorkerNameCompiled> = gateway_worker_binding
-            .worker_name
-            .clone()
-            .map(|worker_name_expr| {
-                WorkerNameCompiled::from_worker_name(&worker_name_expr, export_metadata)
-            })
-            .transpose()?;
+        let worker_name_compiled =
+            Self::compile_worker_name(gateway_worker_binding, export_metadata)?;
 
         let idempotency_key_compiled = match &gateway_worker_binding.idempotency_key {
             Some(idempotency_key) => Some(IdempotencyKeyCompiled::from_idempotency_key(
@@ -62,6 +79,12 @@ impl WorkerBindingCompiled {
             &gateway_worker_binding.response_mapping,
             export_metadata,
         )?;
+        let bootstrap_compiled = gateway_worker_binding
+            .bootstrap
+            .as_ref()
+            .map(WorkerBootstrapCompiled::from_bootstrap)
+            .transpose()?;
+
         let invocation_context_compiled = match &gateway_worker_binding.invocation_context {
             Some(invocation_context) => Some(InvocationContextCompiled::from_invocation_context(
                 invocation_context,
@@ -76,6 +99,106 @@ impl WorkerBindingCompiled {
             idempotency_key_compiled,
             response_compiled,
             invocation_context_compiled,
+            // The shadow component has its own exports, and is compiled separately
+            traffic_mirror_compiled: None,
+            worker_per_user: gateway_worker_binding.worker_per_user.clone(),
+            bootstrap_compiled,
+            pagination: gateway_worker_binding.pagination.clone(),
+            content_negotiation: gateway_worker_binding.content_negotiation.clone(),
+            error_mapping: gateway_worker_binding.error_mapping.clone(),
+            static_worker_names: gateway_worker_binding.static_worker_names,
+        })
+    }
+
+    fn compile_worker_name(
+        gateway_worker_binding: &WorkerBinding,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<Option<WorkerNameCompiled>, RibError> {
+        let worker_name_expr = match &gateway_worker_binding.worker_per_user {
+            Some(worker_per_user) => Some(
+                worker_per_user
+                    .worker_name_expr()
+                    .map_err(RibError::InternalError)?,
+            ),
+            None => gateway_worker_binding.worker_name.clone(),
+        };
+
+        worker_name_expr
+            .map(|worker_name_expr| {
+                WorkerNameCompiled::from_worker_name(&worker_name_expr, export_metadata)
+            })
+            .transpose()
+    }
+}
+
+impl WorkerBindingCompiled {
+    // A WebSocket binding evaluates its response mapping for every message of the connection.
+    // Every message is a separate invocation, so there is no idempotency key for the connection.
+    pub fn from_raw_web_socket_binding(
+        gateway_worker_binding: &WorkerBinding,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<Self, RibError> {
+        Self::from_raw_streaming_binding(
+            gateway_worker_binding,
+            export_metadata,
+            ResponseMappingCompiled::from_web_socket_message_mapping,
+        )
+    }
+
+    // A server-sent events binding evaluates its response mapping for every event emitted
+    // by the worker, and like WebSocket bindings has no idempotency key.
+    pub fn from_raw_server_sent_events_binding(
+        gateway_worker_binding: &WorkerBinding,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<Self, RibError> {
+        Self::from_raw_streaming_binding(
+            gateway_worker_binding,
+            export_metadata,
+            ResponseMappingCompiled::from_server_sent_event_mapping,
+        )
+    }
+
+    fn from_raw_streaming_binding(
+        gateway_worker_binding: &WorkerBinding,
+        export_metadata: &[AnalysedExport],
+        compile_response_mapping: fn(
+            &ResponseMapping,
+            &[AnalysedExport],
+        ) -> Result<ResponseMappingCompiled, RibError>,
+    ) -> Result<Self, RibError> {
+        let worker_name_compiled =
+            Self::compile_worker_name(gateway_worker_binding, export_metadata)?;
+
+        let response_compiled =
+            compile_response_mapping(&gateway_worker_binding.response_mapping, export_metadata)?;
+
+        let bootstrap_compiled = gateway_worker_binding
+            .bootstrap
+            .as_ref()
+            .map(WorkerBootstrapCompiled::from_bootstrap)
+            .transpose()?;
+
+        let invocation_context_compiled = match &gateway_worker_binding.invocation_context {
+            Some(invocation_context) => Some(InvocationContextCompiled::from_invocation_context(
+                invocation_context,
+                export_metadata,
+            )?),
+            None => None,
+        };
+
+        Ok(WorkerBindingCompiled {
+            component_id: gateway_worker_binding.component_id.clone(),
+            worker_name_compiled,
+            idempotency_key_compiled: None,
+            response_compiled,
+            invocation_context_compiled,
+            traffic_mirror_compiled: None,
+            worker_per_user: gateway_worker_binding.worker_per_user.clone(),
+            bootstrap_compiled,
+            pagination: gateway_worker_binding.pagination.clone(),
+            content_negotiation: gateway_worker_binding.content_negotiation.clone(),
+            error_mapping: gateway_worker_binding.error_mapping.clone(),
+            static_worker_names: gateway_worker_binding.static_worker_names,
         })
     }
 }
@@ -86,8 +209,10 @@ impl From<WorkerBindingCompiled> for WorkerBinding {
 
         WorkerBinding {
             component_id: worker_binding.component_id,
+            // The worker name of a worker per user binding is derived, and not part of the binding
             worker_name: worker_binding
                 .worker_name_compiled
+                .filter(|_| worker_binding.worker_per_user.is_none())
                 .map(|compiled| compiled.worker_name),
             idempotency_key: worker_binding
                 .idempotency_key_compiled
@@ -98,6 +223,15 @@ impl From<WorkerBindingCompiled> for WorkerBinding {
             invocation_context: worker_binding
                 .invocation_context_compiled
                 .map(|compiled| compiled.invocation_context),
+            traffic_mirror: worker_binding
+                .traffic_mirror_compiled
+                .map(TrafficMirror::from),
+            worker_per_user: worker_binding.worker_per_user,
+            bootstrap: worker_binding.bootstrap_compiled.map(WorkerBootstrap::from),
+            pagination: worker_binding.pagination,
+            content_negotiation: worker_binding.content_negotiation,
+            error_mapping: worker_binding.error_mapping,
+            static_worker_names: worker_binding.static_worker_names,
         }
     }
 }
@@ -132,4 +266,40 @@ impl ResponseMappingCompiled {
             rib_output: response_compiled.rib_output_type_info,
         })
     }
+
+    pub fn from_web_socket_message_mapping(
+        response_mapping: &ResponseMapping,
+        exports: &[AnalysedExport],
+    ) -> Result<Self, RibError> {
+        let response_compiled = DefaultWorkerServiceRibCompiler::compile_web_socket_message(
+            &response_mapping.0,
+            exports,
+        )?;
+
+        Ok(ResponseMappingCompiled {
+            response_mapping_expr: response_mapping.0.clone(),
+            response_mapping_compiled: response_compiled.byte_code,
+            rib_input: response_compiled.rib_input_type_info,
+            worker_calls: response_compiled.worker_invoke_calls,
+            rib_output: response_compiled.rib_output_type_info,
+        })
+    }
+
+    pub fn from_server_sent_event_mapping(
+        response_mapping: &ResponseMapping,
+        exports: &[AnalysedExport],
+    ) -> Result<Self, RibError> {
+        let response_compiled = DefaultWorkerServiceRibCompiler::compile_server_sent_event(
+            &response_mapping.0,
+            exports,
+        )?;
+
+        Ok(ResponseMappingCompiled {
+            response_mapping_expr: response_mapping.0.clone(),
+            response_mapping_compiled: response_compiled.byte_code,
+            rib_input: response_compiled.rib_input_type_info,
+            worker_calls: response_compiled.worker_invoke_calls,
+            rib_output: response_compiled.rib_output_type_info,
+        })
+    }
 }
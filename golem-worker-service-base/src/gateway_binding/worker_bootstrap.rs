@@ -0,0 +1,297 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::{DefaultWorkerServiceRibCompiler, WorkerServiceRibCompiler};
+use rib::{Expr, RibByteCode, RibError, RibInputTypeInfo};
+use std::collections::BTreeMap;
+
+// Arguments, environment variables and WASI config variables of the worker of a binding, used
+// only if the request is the one implicitly creating it, to seed its initial state.
+// Every value is a Rib script with only the `request` available, such as `request.path.user-id`,
+// and as the scripts cannot call workers, they are compiled without any component metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerBootstrap {
+    pub args: Vec<Expr>,
+    pub env: BTreeMap<String, Expr>,
+    pub config: BTreeMap<String, Expr>,
+}
+
+impl WorkerBootstrap {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.args.is_empty() && self.env.is_empty() && self.config.is_empty() {
+            return Err("Worker bootstrap must have at least one argument or variable".to_string());
+        }
+
+        if let Some(name) = self
+            .env
+            .keys()
+            .find(|name| name.is_empty() || name.contains('=') || name.contains('\0'))
+        {
+            return Err(format!(
+                "Invalid worker bootstrap environment variable name '{}'",
+                name
+            ));
+        }
+
+        if let Some(name) = self.config.keys().find(|name| name.is_empty()) {
+            return Err(format!(
+                "Invalid worker bootstrap config variable name '{}'",
+                name
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerBootstrapCompiled {
+    pub args: Vec<BootstrapValueCompiled>,
+    pub env: BTreeMap<String, BootstrapValueCompiled>,
+    pub config: BTreeMap<String, BootstrapValueCompiled>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapValueCompiled {
+    pub expr: Expr,
+    pub compiled_expr: RibByteCode,
+    pub rib_input: RibInputTypeInfo,
+}
+
+impl BootstrapValueCompiled {
+    fn from_expr(expr: &Expr) -> Result<Self, RibError> {
+        let compiled = DefaultWorkerServiceRibCompiler::compile(expr, &[])?;
+
+        Ok(BootstrapValueCompiled {
+            expr: expr.clone(),
+            compiled_expr: compiled.byte_code,
+            rib_input: compiled.rib_input_type_info,
+        })
+    }
+}
+
+impl WorkerBootstrapCompiled {
+    pub fn from_bootstrap(bootstrap: &WorkerBootstrap) -> Result<Self, RibError> {
+        let args = bootstrap
+            .args
+            .iter()
+            .map(BootstrapValueCompiled::from_expr)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let compile_vars = |vars: &BTreeMap<String, Expr>| {
+            vars.iter()
+                .map(|(name, expr)| Ok((name.clone(), BootstrapValueCompiled::from_expr(expr)?)))
+                .collect::<Result<BTreeMap<_, _>, RibError>>()
+        };
+
+        Ok(WorkerBootstrapCompiled {
+            args,
+            env: compile_vars(&bootstrap.env)?,
+            config: compile_vars(&bootstrap.config)?,
+        })
+    }
+}
+
+impl From<WorkerBootstrapCompiled> for WorkerBootstrap {
+    fn from(value: WorkerBootstrapCompiled) -> Self {
+        WorkerBootstrap {
+            args: value.args.into_iter().map(|arg| arg.expr).collect(),
+            env: value
+                .env
+                .into_iter()
+                .map(|(name, value)| (name, value.expr))
+                .collect(),
+            config: value
+                .config
+                .into_iter()
+                .map(|(name, value)| (name, value.expr))
+                .collect(),
+        }
+    }
+}
+
+impl From<WorkerBootstrap> for golem_api_grpc::proto::golem::apidefinition::WorkerBootstrap {
+    fn from(value: WorkerBootstrap) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::WorkerBootstrap {
+            args: value.args.into_iter().map(|arg| arg.into()).collect(),
+            env: value
+                .env
+                .into_iter()
+                .map(|(name, value)| (name, value.into()))
+                .collect(),
+            config: value
+                .config
+                .into_iter()
+                .map(|(name, value)| (name, value.into()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::WorkerBootstrap> for WorkerBootstrap {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::WorkerBootstrap,
+    ) -> Result<Self, Self::Error> {
+        Ok(WorkerBootstrap {
+            args: value
+                .args
+                .into_iter()
+                .map(Expr::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            env: value
+                .env
+                .into_iter()
+                .map(|(name, value)| Ok((name, Expr::try_from(value)?)))
+                .collect::<Result<BTreeMap<_, _>, String>>()?,
+            config: value
+                .config
+                .into_iter()
+                .map(|(name, value)| Ok((name, Expr::try_from(value)?)))
+                .collect::<Result<BTreeMap<_, _>, String>>()?,
+        })
+    }
+}
+
+impl TryFrom<BootstrapValueCompiled>
+    for golem_api_grpc::proto::golem::apidefinition::CompiledBootstrapValue
+{
+    type Error = String;
+
+    fn try_from(value: BootstrapValueCompiled) -> Result<Self, Self::Error> {
+        Ok(
+            golem_api_grpc::proto::golem::apidefinition::CompiledBootstrapValue {
+                expr: Some(value.expr.into()),
+                compiled_expr: Some(value.compiled_expr.try_into()?),
+                rib_input: Some(value.rib_input.into()),
+            },
+        )
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::CompiledBootstrapValue>
+    for BootstrapValueCompiled
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::CompiledBootstrapValue,
+    ) -> Result<Self, Self::Error> {
+        Ok(BootstrapValueCompiled {
+            expr: Expr::try_from(value.expr.ok_or("Missing bootstrap expr")?)?,
+            compiled_expr: RibByteCode::try_from(
+                value
+                    .compiled_expr
+                    .ok_or("Missing compiled bootstrap expr")?,
+            )?,
+            rib_input: RibInputTypeInfo::try_from(
+                value.rib_input.ok_or("Missing bootstrap rib input")?,
+            )?,
+        })
+    }
+}
+
+impl TryFrom<WorkerBootstrapCompiled>
+    for golem_api_grpc::proto::golem::apidefinition::CompiledWorkerBootstrap
+{
+    type Error = String;
+
+    fn try_from(value: WorkerBootstrapCompiled) -> Result<Self, Self::Error> {
+        Ok(
+            golem_api_grpc::proto::golem::apidefinition::CompiledWorkerBootstrap {
+                args: value
+                    .args
+                    .into_iter()
+                    .map(|arg| arg.try_into())
+                    .collect::<Result<Vec<_>, _>>()?,
+                env: value
+                    .env
+                    .into_iter()
+                    .map(|(name, value)| Ok((name, value.try_into()?)))
+                    .collect::<Result<_, String>>()?,
+                config: value
+                    .config
+                    .into_iter()
+                    .map(|(name, value)| Ok((name, value.try_into()?)))
+                    .collect::<Result<_, String>>()?,
+            },
+        )
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::CompiledWorkerBootstrap>
+    for WorkerBootstrapCompiled
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::CompiledWorkerBootstrap,
+    ) -> Result<Self, Self::Error> {
+        Ok(WorkerBootstrapCompiled {
+            args: value
+                .args
+                .into_iter()
+                .map(BootstrapValueCompiled::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            env: value
+                .env
+                .into_iter()
+                .map(|(name, value)| Ok((name, BootstrapValueCompiled::try_from(value)?)))
+                .collect::<Result<BTreeMap<_, _>, String>>()?,
+            config: value
+                .config
+                .into_iter()
+                .map(|(name, value)| Ok((name, BootstrapValueCompiled::try_from(value)?)))
+                .collect::<Result<BTreeMap<_, _>, String>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn bootstrap_round_trips_through_its_compiled_form() {
+        let bootstrap = WorkerBootstrap {
+            args: vec![rib::from_string(r#""seed""#).unwrap()],
+            env: BTreeMap::from([("TIER".to_string(), rib::from_string(r#""gold""#).unwrap())]),
+            config: BTreeMap::from([(
+                "region".to_string(),
+                rib::from_string(r#""eu-west""#).unwrap(),
+            )]),
+        };
+
+        assert!(bootstrap.validate().is_ok());
+
+        let compiled = WorkerBootstrapCompiled::from_bootstrap(&bootstrap).unwrap();
+        let proto = golem_api_grpc::proto::golem::apidefinition::CompiledWorkerBootstrap::try_from(
+            compiled.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(WorkerBootstrapCompiled::try_from(proto).unwrap(), compiled);
+        assert_eq!(WorkerBootstrap::from(compiled), bootstrap);
+
+        let invalid = WorkerBootstrap {
+            args: vec![],
+            env: BTreeMap::from([("A=B".to_string(), rib::from_string(r#""gold""#).unwrap())]),
+            config: BTreeMap::new(),
+        };
+
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::Object;
+use rib::Expr;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_CLAIM: &str = "sub";
+
+/// Routes every request of a worker binding to a worker dedicated to the authenticated user.
+///
+/// The name of the worker is the value of `claim` (`sub` by default) of the identity of the
+/// request, prefixed with `prefix`. The worker is created by its first request, and its name
+/// is available to the response mapping as `worker.name`. Requires the route to be secured.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerPerUser {
+    pub prefix: Option<String>,
+    pub claim: Option<String>,
+}
+
+impl WorkerPerUser {
+    pub fn claim(&self) -> &str {
+        self.claim.as_deref().unwrap_or(DEFAULT_CLAIM)
+    }
+
+    pub fn prefix(&self) -> &str {
+        self.prefix.as_deref().unwrap_or_default()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let claim = self.claim();
+
+        let valid_claim = claim
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && claim
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+        if !valid_claim {
+            return Err(format!("Invalid identity claim '{}'", claim));
+        }
+
+        // The prefix ends up in the worker name, so it is restricted to the characters
+        // that are valid in it, and that need no escaping in a Rib string literal
+        if let Some(c) = self
+            .prefix()
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')))
+        {
+            return Err(format!("Invalid character '{}' in worker name prefix", c));
+        }
+
+        Ok(())
+    }
+
+    // The worker name expression the binding is compiled with, in place of an explicit one
+    pub fn worker_name_expr(&self) -> Result<Expr, String> {
+        self.validate()?;
+
+        rib::from_string(format!(
+            "let user: string = request.auth.{}; \"{}${{user}}\"",
+            self.claim(),
+            self.prefix()
+        ))
+        .map_err(|err| format!("Invalid worker per user: {}", err))
+    }
+}
+
+impl From<WorkerPerUser> for golem_api_grpc::proto::golem::apidefinition::WorkerPerUser {
+    fn from(value: WorkerPerUser) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::WorkerPerUser {
+            prefix: value.prefix,
+            claim: value.claim,
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::WorkerPerUser> for WorkerPerUser {
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::WorkerPerUser) -> Self {
+        WorkerPerUser {
+            prefix: value.prefix,
+            claim: value.claim,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn worker_name_is_derived_from_the_identity_claim() {
+        let worker_per_user = WorkerPerUser {
+            prefix: Some("cart-".to_string()),
+            claim: Some("email".to_string()),
+        };
+
+        let expected =
+            rib::from_string(r#"let user: string = request.auth.email; "cart-${user}""#).unwrap();
+
+        assert_eq!(worker_per_user.worker_name_expr(), Ok(expected));
+
+        let default = WorkerPerUser {
+            prefix: None,
+            claim: None,
+        };
+
+        assert_eq!(default.claim(), "sub");
+        assert!(default.worker_name_expr().is_ok());
+
+        let invalid_claim = WorkerPerUser {
+            prefix: None,
+            claim: Some("email; foo()".to_string()),
+        };
+
+        assert!(invalid_claim.validate().is_err());
+
+        let invalid_prefix = WorkerPerUser {
+            prefix: Some("cart\"".to_string()),
+            claim: None,
+        };
+
+        assert!(invalid_prefix.validate().is_err());
+    }
+}
@@ -16,7 +16,7 @@ use std::fmt::Display;
 use std::sync::Arc;
 
 use crate::gateway_api_definition::http::CompiledHttpApiDefinition;
-use crate::gateway_api_deployment::ApiSiteString;
+use crate::gateway_api_deployment::{ApiDeploymentConfig, ApiSiteString};
 use crate::service::gateway::api_deployment::ApiDeploymentService;
 use async_trait::async_trait;
 use golem_common::model::HasAccountId;
@@ -32,6 +32,15 @@ pub trait HttpApiDefinitionsLookup<Namespace> {
         &self,
         host: &ApiSiteString,
     ) -> Result<Vec<CompiledHttpApiDefinition<Namespace>>, ApiDefinitionLookupError>;
+
+    // The configuration of the deployment applying to all the requests of the site, loaded once
+    // per request
+    async fn get_config(
+        &self,
+        _host: &ApiSiteString,
+    ) -> Result<Arc<ApiDeploymentConfig>, ApiDefinitionLookupError> {
+        Ok(Arc::new(ApiDeploymentConfig::default()))
+    }
 }
 
 pub struct ApiDefinitionLookupError(pub String);
@@ -83,4 +92,20 @@ impl<AuthCtx, Namespace: HasAccountId + Send + Sync> HttpApiDefinitionsLookup<Na
 
         Ok(http_api_defs)
     }
+
+    async fn get_config(
+        &self,
+        host: &ApiSiteString,
+    ) -> Result<Arc<ApiDeploymentConfig>, ApiDefinitionLookupError> {
+        self.deployment_service
+            .get_config_by_site(host)
+            .await
+            .map_err(|err| {
+                error!("Error getting API deployment config from the repo: {}", err);
+                ApiDefinitionLookupError(format!(
+                    "Error getting API deployment config from the repo: {}",
+                    err
+                ))
+            })
+    }
 }
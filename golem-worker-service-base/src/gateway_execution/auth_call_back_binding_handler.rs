@@ -16,7 +16,7 @@ use crate::gateway_execution::gateway_session::{
     DataKey, DataValue, GatewaySessionError, GatewaySessionStore, SessionId,
 };
 use crate::gateway_security::{
-    IdentityProvider, IdentityProviderError, SecuritySchemeWithProviderMetadata,
+    IdentityProvider, IdentityProviderError, JwtValidationError, SecuritySchemeWithProviderMetadata,
 };
 use async_trait::async_trait;
 use golem_common::SafeDisplay;
@@ -65,6 +65,12 @@ pub enum AuthorisationError {
     ClaimFetchError(IdentityProviderError),
     IdentityProviderError(IdentityProviderError),
     SessionError(GatewaySessionError),
+    BearerTokenNotFound,
+    InvalidBearerToken(JwtValidationError),
+    ApiKeyNotFound,
+    InvalidApiKey,
+    SignatureNotFound,
+    InvalidSignature,
 }
 
 // Only SafeDisplay is allowed for AuthorisationError
@@ -113,6 +119,16 @@ impl SafeDisplay for AuthorisationError {
                 "An error occurred while updating the session. Error details: {}",
                 err.to_safe_string()
             ),
+            AuthorisationError::BearerTokenNotFound => {
+                "Missing bearer token in the Authorization header".to_string()
+            }
+            AuthorisationError::InvalidBearerToken(err) => {
+                format!("Invalid bearer token: {}", err.to_safe_string())
+            }
+            AuthorisationError::ApiKeyNotFound => "Missing API key".to_string(),
+            AuthorisationError::InvalidApiKey => "Invalid API key".to_string(),
+            AuthorisationError::SignatureNotFound => "Missing request signature".to_string(),
+            AuthorisationError::InvalidSignature => "Invalid request signature".to_string(),
         }
     }
 }
@@ -0,0 +1,208 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use golem_wasm_rpc::{Value, ValueAndType};
+use serde_json::Value as JsonValue;
+
+// Binary request bodies and multipart file parts are kept as raw bytes next to the JSON
+// representation of the request, where they are `null`, instead of as JSON arrays of numbers.
+// They are turned into `list<u8>` values only when resolving the input of the response script,
+// so they reach the workers without going through JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryField {
+    // Path of the field in the request (Example: `body.avatar.data`)
+    pub path: Vec<String>,
+    pub data: Bytes,
+}
+
+impl BinaryField {
+    pub fn new(path: &[&str], data: Bytes) -> Self {
+        BinaryField {
+            path: path.iter().map(|segment| segment.to_string()).collect(),
+            data,
+        }
+    }
+}
+
+// Content types of request bodies passed through as raw bytes
+pub fn is_binary_content_type(content_type: &str) -> bool {
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    media_type == "application/octet-stream"
+        || media_type.starts_with("image/")
+        || media_type.starts_with("audio/")
+        || media_type.starts_with("video/")
+}
+
+pub fn bytes_to_value(data: &Bytes) -> Value {
+    Value::List(data.iter().map(|byte| Value::U8(*byte)).collect())
+}
+
+// The bytes of a `list<u8>` value
+pub fn value_to_bytes(value: &ValueAndType) -> Option<Bytes> {
+    match (&value.value, &value.typ) {
+        (Value::List(items), AnalysedType::List(list))
+            if matches!(*list.inner, AnalysedType::U8(_)) =>
+        {
+            items
+                .iter()
+                .map(|item| match item {
+                    Value::U8(byte) => Some(*byte),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(Bytes::from)
+        }
+        _ => None,
+    }
+}
+
+// Parses the JSON representation of the request with the given type,
+// taking the binary fields from their raw bytes
+pub fn parse_with_binary_fields(
+    json: &JsonValue,
+    typ: &AnalysedType,
+    binary_fields: &[BinaryField],
+) -> Result<ValueAndType, String> {
+    internal::parse(json, typ, &binary_fields.iter().collect::<Vec<_>>(), 0)
+}
+
+mod internal {
+    use super::*;
+
+    pub(crate) fn parse(
+        json: &JsonValue,
+        typ: &AnalysedType,
+        binary_fields: &[&BinaryField],
+        depth: usize,
+    ) -> Result<ValueAndType, String> {
+        if binary_fields.is_empty() {
+            let parsed =
+                TypeAnnotatedValue::parse_with_type(json, typ).map_err(|err| err.join(", "))?;
+
+            return parsed.try_into().map_err(|err| {
+                format!("Internal error converting between value representations: {err}")
+            });
+        }
+
+        if let Some(field) = binary_fields.iter().find(|field| field.path.len() == depth) {
+            return match typ {
+                AnalysedType::List(list) if matches!(*list.inner, AnalysedType::U8(_)) => {
+                    Ok(ValueAndType::new(bytes_to_value(&field.data), typ.clone()))
+                }
+                _ => Err(format!(
+                    "Binary payload {} can only be used as list<u8>",
+                    field.path.join(".")
+                )),
+            };
+        }
+
+        match typ {
+            AnalysedType::Record(record) => {
+                let values = record
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let nested = binary_fields
+                            .iter()
+                            .filter(|binary_field| binary_field.path[depth] == field.name)
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        let json = json.get(&field.name).unwrap_or(&JsonValue::Null);
+
+                        parse(json, &field.typ, &nested, depth + 1).map(|value| value.value)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(ValueAndType::new(Value::Record(values), typ.clone()))
+            }
+            _ => parse(json, typ, &[], depth),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{field, list, record, str, u8};
+    use test_r::test;
+
+    #[test]
+    fn binary_fields_are_parsed_from_their_bytes() {
+        let typ = record(vec![
+            field("path", record(vec![field("user", str())])),
+            field(
+                "body",
+                record(vec![
+                    field("name", str()),
+                    field("avatar", record(vec![field("data", list(u8()))])),
+                ]),
+            ),
+        ]);
+
+        let json = serde_json::json!({
+            "path": { "user": "jane" },
+            "body": { "name": "Jane", "avatar": { "file_name": "jane.png", "data": null } }
+        });
+
+        let binary_fields = vec![BinaryField::new(
+            &["body", "avatar", "data"],
+            Bytes::from_static(&[1, 2, 3]),
+        )];
+
+        let parsed = parse_with_binary_fields(&json, &typ, &binary_fields).unwrap();
+
+        assert_eq!(
+            parsed.value,
+            Value::Record(vec![
+                Value::Record(vec![Value::String("jane".to_string())]),
+                Value::Record(vec![
+                    Value::String("Jane".to_string()),
+                    Value::Record(vec![Value::List(vec![
+                        Value::U8(1),
+                        Value::U8(2),
+                        Value::U8(3)
+                    ])]),
+                ]),
+            ])
+        );
+        assert_eq!(
+            value_to_bytes(&ValueAndType::new(
+                bytes_to_value(&binary_fields[0].data),
+                list(u8())
+            )),
+            Some(binary_fields[0].data.clone())
+        );
+
+        let not_bytes = record(vec![field("body", str())]);
+        let binary_body = vec![BinaryField::new(&["body"], Bytes::from_static(&[1]))];
+        assert!(parse_with_binary_fields(&json, &not_bytes, &binary_body).is_err());
+
+        assert!(is_binary_content_type("image/png"));
+        assert!(is_binary_content_type(
+            "Application/Octet-Stream; charset=binary"
+        ));
+        assert!(!is_binary_content_type("application/json"));
+    }
+}
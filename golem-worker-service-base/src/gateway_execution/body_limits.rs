@@ -0,0 +1,173 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::request::RichRequest;
+use crate::gateway_middleware::HttpBodyLimits;
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use http::StatusCode;
+use poem::{Body, IntoResponse};
+
+#[derive(Debug, PartialEq)]
+pub enum BodyLimitError {
+    TooLarge { max_bytes: u64 },
+    Timeout,
+    ReadFailed(String),
+}
+
+impl IntoResponse for BodyLimitError {
+    fn into_response(self) -> poem::Response {
+        let (status, message) = match self {
+            BodyLimitError::TooLarge { max_bytes } => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Request body exceeds the limit of {} bytes", max_bytes),
+            ),
+            BodyLimitError::Timeout => (
+                StatusCode::REQUEST_TIMEOUT,
+                "Timed out reading the request body".to_string(),
+            ),
+            BodyLimitError::ReadFailed(err) => (
+                StatusCode::BAD_REQUEST,
+                format!("Failed reading request body: {}", err),
+            ),
+        };
+
+        poem::Response::builder()
+            .status(status)
+            .body(Body::from_string(message))
+    }
+}
+
+// Reads the whole body of the request within the limits of the route, so that the binding
+// gets a body already in memory and a slow or oversized upload is rejected before reaching it
+pub async fn read_limited_request_body(
+    request: &mut RichRequest,
+    limits: &HttpBodyLimits,
+) -> Result<(), BodyLimitError> {
+    if let Some(max_bytes) = limits.max_request_body_bytes {
+        let content_length = request
+            .underlying
+            .headers()
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+
+        if content_length.is_some_and(|length| length > max_bytes) {
+            return Err(BodyLimitError::TooLarge { max_bytes });
+        }
+    }
+
+    let body = request.underlying.take_body();
+    let read = read_body(body, limits.max_request_body_bytes);
+
+    let bytes = match limits.read_timeout() {
+        Some(timeout) => tokio::time::timeout(timeout, read)
+            .await
+            .map_err(|_| BodyLimitError::Timeout)??,
+        None => read.await?,
+    };
+
+    request.underlying.set_body(Body::from_bytes(bytes));
+
+    Ok(())
+}
+
+async fn read_body(body: Body, max_bytes: Option<u64>) -> Result<Bytes, BodyLimitError> {
+    let mut stream = body.into_bytes_stream();
+    let mut buffer = BytesMut::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|err| BodyLimitError::ReadFailed(err.to_string()))?;
+
+        if let Some(max_bytes) = max_bytes {
+            if (buffer.len() + chunk.len()) as u64 > max_bytes {
+                return Err(BodyLimitError::TooLarge { max_bytes });
+            }
+        }
+
+        buffer.extend_from_slice(&chunk);
+    }
+
+    Ok(buffer.freeze())
+}
+
+// The body of the response is streamed as it is, failing (and so closing the connection)
+// once it goes over the limit. Upgraded connections, such as web sockets, have no body to limit.
+pub fn limit_response_body(
+    mut response: poem::Response,
+    limits: &HttpBodyLimits,
+) -> poem::Response {
+    match limits.max_response_body_bytes {
+        Some(max_bytes) if response.status() != StatusCode::SWITCHING_PROTOCOLS => {
+            let mut size: u64 = 0;
+            let body = response.take_body().into_bytes_stream().map(move |chunk| {
+                let chunk = chunk?;
+                size += chunk.len() as u64;
+                if size > max_bytes {
+                    Err(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        format!("Response body exceeds the limit of {} bytes", max_bytes),
+                    ))
+                } else {
+                    Ok(chunk)
+                }
+            });
+
+            response.set_body(Body::from_bytes_stream(body));
+            response
+        }
+        _ => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    async fn bodies_over_the_limits_are_rejected() {
+        let limits = HttpBodyLimits {
+            max_request_body_bytes: Some(4),
+            max_response_body_bytes: Some(4),
+            read_timeout_ms: Some(1000),
+        };
+
+        assert_eq!(
+            read_body(Body::from_string("hello".to_string()), Some(4)).await,
+            Err(BodyLimitError::TooLarge { max_bytes: 4 })
+        );
+        assert_eq!(
+            read_body(Body::from_string("hey".to_string()), Some(4)).await,
+            Ok(Bytes::from("hey"))
+        );
+
+        let response = poem::Response::builder().body(Body::from_string("hello".to_string()));
+        assert!(limit_response_body(response, &limits)
+            .into_body()
+            .into_bytes()
+            .await
+            .is_err());
+
+        let response = poem::Response::builder().body(Body::from_string("hey".to_string()));
+        assert_eq!(
+            limit_response_body(response, &limits)
+                .into_body()
+                .into_bytes()
+                .await
+                .unwrap(),
+            Bytes::from("hey")
+        );
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_deployment::ApiDeploymentCapacity;
+use http::StatusCode;
+use poem::Body;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Keeps track of the worker invocations in flight of the sites with a capacity.
+///
+/// Every priority lane of a site has its own slots, and the requests of a lane fall back to the
+/// slots shared by all the requests of the site when their own are in use. Like the route
+/// concurrency limits, the capacity is enforced per gateway instance, and changing the capacity
+/// of a site (by redeploying it) starts counting anew.
+#[derive(Default)]
+pub struct DeploymentCapacityLimiter {
+    sites: Mutex<HashMap<String, Arc<SiteSlots>>>,
+}
+
+struct SiteSlots {
+    capacity: ApiDeploymentCapacity,
+    shared: Arc<Semaphore>,
+    lanes: HashMap<String, Arc<Semaphore>>,
+}
+
+// Held by a request for as long as it invokes workers
+pub struct CapacityPermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl DeploymentCapacityLimiter {
+    // Waits for a slot of the site, in the lane of the request if it has one,
+    // returning None if the request has to be shed
+    pub async fn acquire(
+        &self,
+        site: &str,
+        capacity: &ApiDeploymentCapacity,
+        lane: Option<&str>,
+    ) -> Option<CapacityPermit> {
+        let slots = self.slots(site, capacity);
+        let lane = lane.and_then(|lane| slots.lanes.get(lane)).cloned();
+
+        if let Some(permit) = lane
+            .as_ref()
+            .and_then(|lane| lane.clone().try_acquire_owned().ok())
+            .or_else(|| slots.shared.clone().try_acquire_owned().ok())
+        {
+            return Some(CapacityPermit { _permit: permit });
+        }
+
+        let timeout = capacity.queue_timeout();
+        let permit = match lane {
+            Some(lane) => {
+                tokio::time::timeout(timeout, async {
+                    tokio::select! {
+                        permit = lane.acquire_owned() => permit,
+                        permit = slots.shared.clone().acquire_owned() => permit,
+                    }
+                })
+                .await
+            }
+            None => tokio::time::timeout(timeout, slots.shared.clone().acquire_owned()).await,
+        };
+
+        match permit {
+            Ok(Ok(permit)) => Some(CapacityPermit { _permit: permit }),
+            _ => None,
+        }
+    }
+
+    fn slots(&self, site: &str, capacity: &ApiDeploymentCapacity) -> Arc<SiteSlots> {
+        let mut sites = self.sites.lock().unwrap();
+
+        match sites.get(site) {
+            Some(slots) if &slots.capacity == capacity => slots.clone(),
+            _ => {
+                let slots = Arc::new(SiteSlots {
+                    capacity: capacity.clone(),
+                    shared: Arc::new(Semaphore::new(capacity.unreserved() as usize)),
+                    lanes: capacity
+                        .lanes
+                        .iter()
+                        .map(|lane| {
+                            (
+                                lane.name.clone(),
+                                Arc::new(Semaphore::new(capacity.reserved(lane) as usize)),
+                            )
+                        })
+                        .collect(),
+                });
+                sites.insert(site.to_string(), slots.clone());
+                slots
+            }
+        }
+    }
+}
+
+pub fn capacity_exceeded_response() -> poem::Response {
+    poem::Response::builder()
+        .status(StatusCode::SERVICE_UNAVAILABLE)
+        .body(Body::from_string(
+            "The capacity of the deployment is in use".to_string(),
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway_api_deployment::PriorityLane;
+    use test_r::test;
+
+    #[test]
+    async fn lanes_keep_their_reserved_capacity() {
+        let limiter = DeploymentCapacityLimiter::default();
+        let capacity = ApiDeploymentCapacity {
+            max_in_flight: 4,
+            queue_timeout_ms: Some(10),
+            lanes: vec![PriorityLane {
+                name: "admin".to_string(),
+                reserved_percent: 50,
+                routes: vec!["POST /admin/reindex".to_string()],
+                api_keys: vec![],
+            }],
+        };
+
+        // Public traffic saturates the unreserved capacity
+        let public1 = limiter.acquire("site", &capacity, None).await.unwrap();
+        let _public2 = limiter.acquire("site", &capacity, None).await.unwrap();
+        assert!(limiter.acquire("site", &capacity, None).await.is_none());
+
+        // While the lane still gets its reserved capacity
+        let _admin1 = limiter
+            .acquire("site", &capacity, Some("admin"))
+            .await
+            .unwrap();
+        let _admin2 = limiter
+            .acquire("site", &capacity, Some("admin"))
+            .await
+            .unwrap();
+        assert!(limiter
+            .acquire("site", &capacity, Some("admin"))
+            .await
+            .is_none());
+
+        // And also uses the shared capacity when its own is in use
+        drop(public1);
+        assert!(limiter
+            .acquire("site", &capacity, Some("admin"))
+            .await
+            .is_some());
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::{CompiledHttpApiDefinition, MethodPattern};
+use crate::gateway_api_deployment::ApiSiteString;
+use crate::gateway_execution::api_definition_lookup::{
+    ApiDefinitionLookupError, HttpApiDefinitionsLookup,
+};
+use crate::gateway_execution::gateway_http_input_executor::GatewayHttpInputExecutor;
+use crate::service::gateway::api_deployment::DeploymentHealthCheck;
+use async_trait::async_trait;
+use http::Uri;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// A synthetic request sent to the API definitions a site is about to serve, before it serves them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct DeploymentProbe {
+    pub method: MethodPattern,
+    /// Path of the request, including its query
+    pub path: String,
+    pub headers: Option<BTreeMap<String, String>>,
+    pub body: Option<String>,
+    /// Expected status code of the response. Any 2xx status passes the probe when not set.
+    pub expected_status: Option<u16>,
+}
+
+// Serves the definitions staged for the probes, each under a site of its own
+pub struct StagedHttpApiDefinitionLookup<Namespace> {
+    definitions: Mutex<HashMap<ApiSiteString, Vec<CompiledHttpApiDefinition<Namespace>>>>,
+}
+
+impl<Namespace> Default for StagedHttpApiDefinitionLookup<Namespace> {
+    fn default() -> Self {
+        Self {
+            definitions: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl<Namespace: Clone + Send + Sync> HttpApiDefinitionsLookup<Namespace>
+    for StagedHttpApiDefinitionLookup<Namespace>
+{
+    async fn get(
+        &self,
+        host: &ApiSiteString,
+    ) -> Result<Vec<CompiledHttpApiDefinition<Namespace>>, ApiDefinitionLookupError> {
+        self.definitions
+            .lock()
+            .unwrap()
+            .get(host)
+            .cloned()
+            .ok_or(ApiDefinitionLookupError(format!(
+                "No API definitions staged for probe site: {}",
+                host
+            )))
+    }
+}
+
+// Sends probes to API definitions before they are deployed, through a gateway executor of its own
+// that looks up the staged definitions instead of the deployments. The probes reach the workers
+// like any other request, so they have to be safe to run against production workers.
+pub struct DeploymentProber<Namespace> {
+    staged_definitions: Arc<StagedHttpApiDefinitionLookup<Namespace>>,
+    executor: Arc<dyn GatewayHttpInputExecutor + Sync + Send>,
+}
+
+impl<Namespace: Clone> DeploymentProber<Namespace> {
+    // The executor has to look up the definitions with `staged_definitions`
+    pub fn new(
+        staged_definitions: Arc<StagedHttpApiDefinitionLookup<Namespace>>,
+        executor: Arc<dyn GatewayHttpInputExecutor + Sync + Send>,
+    ) -> Self {
+        Self {
+            staged_definitions,
+            executor,
+        }
+    }
+
+    // Fails with the description of the failed probes
+    pub async fn probe(
+        &self,
+        definitions: Vec<CompiledHttpApiDefinition<Namespace>>,
+        probes: &[DeploymentProbe],
+    ) -> Result<(), String> {
+        let site = ApiSiteString(format!("{}.probe.golem", Uuid::new_v4()));
+
+        self.staged_definitions
+            .definitions
+            .lock()
+            .unwrap()
+            .insert(site.clone(), definitions);
+
+        let mut failures = vec![];
+
+        for probe in probes {
+            if let Err(failure) = self.send(&site, probe).await {
+                failures.push(format!("{:?} {}: {}", probe.method, probe.path, failure));
+            }
+        }
+
+        self.staged_definitions
+            .definitions
+            .lock()
+            .unwrap()
+            .remove(&site);
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join(", "))
+        }
+    }
+
+    async fn send(&self, site: &ApiSiteString, probe: &DeploymentProbe) -> Result<(), String> {
+        let uri = probe
+            .path
+            .parse::<Uri>()
+            .map_err(|err| format!("invalid path: {}", err))?;
+
+        let mut request = poem::Request::builder()
+            .method(probe.method.clone().into())
+            .uri(uri)
+            .header(http::header::HOST, site.0.as_str());
+
+        for (name, value) in probe.headers.iter().flatten() {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        let request = request.body(probe.body.clone().unwrap_or_default());
+
+        let status = self.executor.execute_http_request(request).await.status();
+
+        let passed = match probe.expected_status {
+            Some(expected_status) => status.as_u16() == expected_status,
+            None => status.is_success(),
+        };
+
+        if passed {
+            Ok(())
+        } else {
+            Err(format!("unexpected status {}", status))
+        }
+    }
+}
+
+// Health check of a deployment passing when all the probes pass
+pub struct ProbesHealthCheck<Namespace> {
+    pub prober: Arc<DeploymentProber<Namespace>>,
+    pub probes: Vec<DeploymentProbe>,
+}
+
+#[async_trait]
+impl<Namespace: Clone + Send + Sync> DeploymentHealthCheck<Namespace>
+    for ProbesHealthCheck<Namespace>
+{
+    async fn check(
+        &self,
+        definitions: Vec<CompiledHttpApiDefinition<Namespace>>,
+    ) -> Result<(), String> {
+        self.prober.probe(definitions, &self.probes).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+    use http::StatusCode;
+    use test_r::test;
+
+    // Answers with 200 for the paths starting with `/healthy` of the sites with staged definitions
+    struct StagedSiteExecutor {
+        staged_definitions: Arc<StagedHttpApiDefinitionLookup<()>>,
+    }
+
+    #[async_trait]
+    impl GatewayHttpInputExecutor for StagedSiteExecutor {
+        async fn execute_http_request(&self, request: poem::Request) -> poem::Response {
+            let site = ApiSiteString(request.header(http::header::HOST).unwrap().to_string());
+            let staged = self.staged_definitions.get(&site).await.is_ok();
+
+            let status = if staged && request.uri().path().starts_with("/healthy") {
+                StatusCode::OK
+            } else {
+                StatusCode::INTERNAL_SERVER_ERROR
+            };
+
+            poem::Response::builder().status(status).finish()
+        }
+    }
+
+    fn probe(path: &str, expected_status: Option<u16>) -> DeploymentProbe {
+        DeploymentProbe {
+            method: MethodPattern::Get,
+            path: path.to_string(),
+            headers: None,
+            body: None,
+            expected_status,
+        }
+    }
+
+    #[test]
+    async fn probes_are_sent_to_the_staged_definitions() {
+        let staged_definitions = Arc::new(StagedHttpApiDefinitionLookup::default());
+        let prober = DeploymentProber::new(
+            staged_definitions.clone(),
+            Arc::new(StagedSiteExecutor {
+                staged_definitions: staged_definitions.clone(),
+            }),
+        );
+
+        let definitions = vec![CompiledHttpApiDefinition {
+            id: ApiDefinitionId("shop".to_string()),
+            version: ApiVersion("2".to_string()),
+            routes: vec![],
+            draft: false,
+            created_at: chrono::Utc::now(),
+            namespace: (),
+        }];
+
+        let passed = prober
+            .probe(
+                definitions.clone(),
+                &[probe("/healthy", None), probe("/broken", Some(500))],
+            )
+            .await;
+        assert_eq!(passed, Ok(()));
+
+        let failed = prober
+            .probe(
+                definitions,
+                &[probe("/healthy?deep=true", None), probe("/broken", None)],
+            )
+            .await;
+        assert_eq!(
+            failed,
+            Err("Get /broken: unexpected status 500 Internal Server Error".to_string())
+        );
+
+        // The definitions are only staged for the probes
+        assert!(staged_definitions.definitions.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::field_selection::ResponseBodyPosition;
+use crate::gateway_binding::{ErrorCaseMapping, ErrorMapping, ErrorResponseShape};
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use golem_wasm_rpc::{Value, ValueAndType};
+use http::StatusCode;
+use poem::Body;
+use rib::RibResult;
+use serde_json::json;
+
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+// An error case returned by the component, with its payload if it has one
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorCase {
+    pub name: String,
+    pub payload: Option<ValueAndType>,
+}
+
+impl ErrorCase {
+    // The error case in the result of a response mapping, which is either the result of the
+    // component itself, or a response with this result as body
+    pub fn from_rib_result(result: &RibResult) -> Option<ErrorCase> {
+        let RibResult::Val(value_and_type) = result else {
+            return None;
+        };
+
+        Self::from_result(&value_and_type.value, &value_and_type.typ).or_else(|| {
+            let position = ResponseBodyPosition::of(&value_and_type.typ, false);
+            match (&position, &value_and_type.value) {
+                (ResponseBodyPosition::Field(index), Value::Record(fields)) => Self::from_result(
+                    fields.get(*index)?,
+                    position.body_type(&value_and_type.typ)?,
+                ),
+                _ => None,
+            }
+        })
+    }
+
+    fn from_result(value: &Value, typ: &AnalysedType) -> Option<ErrorCase> {
+        let (Value::Result(Err(Some(err))), AnalysedType::Result(result_type)) = (value, typ)
+        else {
+            return None;
+        };
+
+        match (err.as_ref(), result_type.err.as_deref()?) {
+            (
+                Value::Variant {
+                    case_idx,
+                    case_value,
+                },
+                AnalysedType::Variant(variant),
+            ) => {
+                let case = variant.cases.get(*case_idx as usize)?;
+                let payload = match (case_value, &case.typ) {
+                    (Some(value), Some(typ)) => {
+                        Some(ValueAndType::new(*value.clone(), typ.clone()))
+                    }
+                    _ => None,
+                };
+
+                Some(ErrorCase {
+                    name: case.name.clone(),
+                    payload,
+                })
+            }
+            (Value::Enum(case_idx), AnalysedType::Enum(enum_type)) => Some(ErrorCase {
+                name: enum_type.cases.get(*case_idx as usize)?.clone(),
+                payload: None,
+            }),
+            _ => None,
+        }
+    }
+
+    fn payload_json(&self) -> Option<serde_json::Value> {
+        self.payload.as_ref().and_then(|payload| {
+            TypeAnnotatedValue::try_from(payload.clone())
+                .ok()
+                .map(|payload| payload.to_json_value())
+        })
+    }
+}
+
+// The response of the error case returned by the component, if the binding maps it
+pub fn mapped_error_response(
+    result: &RibResult,
+    error_mapping: &ErrorMapping,
+) -> Option<poem::Response> {
+    let error_case = ErrorCase::from_rib_result(result)?;
+    let mapping = error_mapping.get(&error_case.name)?;
+
+    Some(error_response(&error_case, mapping))
+}
+
+fn error_response(error_case: &ErrorCase, mapping: &ErrorCaseMapping) -> poem::Response {
+    let status = StatusCode::from_u16(mapping.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let builder = poem::Response::builder().status(status);
+
+    match mapping.shape {
+        ErrorResponseShape::Problem => {
+            let mut problem = json!({
+                "type": "about:blank",
+                "title": status.canonical_reason().unwrap_or(error_case.name.as_str()),
+                "status": status.as_u16(),
+                "error": error_case.name,
+            });
+
+            if let Some(payload) = error_case.payload_json() {
+                problem["detail"] = payload;
+            }
+
+            builder
+                .content_type(PROBLEM_JSON_CONTENT_TYPE)
+                .body(Body::from_json(problem).unwrap_or_else(|_| Body::empty()))
+        }
+        ErrorResponseShape::Payload => builder
+            .content_type("application/json")
+            .body(Body::from_json(error_case.payload_json()).unwrap_or_else(|_| Body::empty())),
+        ErrorResponseShape::Empty => builder.body(Body::empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{
+        case, field, list, record, result, str, u16, u64, unit_case, variant,
+    };
+    use test_r::test;
+
+    fn checkout_error(case_idx: u32, case_value: Option<Value>) -> ValueAndType {
+        ValueAndType::new(
+            Value::Result(Err(Some(Box::new(Value::Variant {
+                case_idx,
+                case_value: case_value.map(Box::new),
+            })))),
+            result(
+                u64(),
+                variant(vec![
+                    unit_case("cart-not-found"),
+                    case("out-of-stock", list(str())),
+                ]),
+            ),
+        )
+    }
+
+    #[test]
+    async fn mapped_error_cases_become_responses() {
+        let error_mapping = ErrorMapping {
+            cases: vec![
+                ErrorCaseMapping {
+                    case: "cart-not-found".to_string(),
+                    status: 404,
+                    shape: ErrorResponseShape::Empty,
+                },
+                ErrorCaseMapping {
+                    case: "out-of-stock".to_string(),
+                    status: 409,
+                    shape: ErrorResponseShape::Problem,
+                },
+            ],
+        };
+
+        let out_of_stock = checkout_error(
+            1,
+            Some(Value::List(vec![Value::String("sku-1".to_string())])),
+        );
+
+        let response =
+            mapped_error_response(&RibResult::Val(out_of_stock.clone()), &error_mapping).unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.content_type(), Some(PROBLEM_JSON_CONTENT_TYPE));
+        let problem: serde_json::Value =
+            serde_json::from_slice(&response.into_body().into_vec().await.unwrap()).unwrap();
+        assert_eq!(problem["error"], "out-of-stock");
+        assert_eq!(problem["detail"], json!(["sku-1"]));
+
+        // The result can also be the body of the response of the response mapping
+        let response_record = ValueAndType::new(
+            Value::Record(vec![Value::U16(200), checkout_error(0, None).value]),
+            record(vec![
+                field("status", u16()),
+                field("body", checkout_error(0, None).typ),
+            ]),
+        );
+        let response =
+            mapped_error_response(&RibResult::Val(response_record), &error_mapping).unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let ok = ValueAndType::new(
+            Value::Result(Ok(Some(Box::new(Value::U64(1))))),
+            out_of_stock.typ,
+        );
+        assert!(mapped_error_response(&RibResult::Val(ok), &error_mapping).is_none());
+        assert!(mapped_error_response(&RibResult::Unit, &error_mapping).is_none());
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_deployment::ApiDeploymentExperiment;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+// The cookie keeping the random bucket id of a client, shared by the experiments of a site
+pub const EXPERIMENT_BUCKET_COOKIE: &str = "golem-experiment-bucket";
+pub const EXPERIMENT_HEADER_PREFIX: &str = "x-golem-experiment-";
+const EXPERIMENT_BUCKET_COOKIE_MAX_AGE_SECONDS: u64 = 365 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExperimentAssignment {
+    pub experiment: String,
+    pub variant: String,
+}
+
+// The variants a request is assigned to, for each experiment of its site
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ExperimentAssignments {
+    pub assignments: Vec<ExperimentAssignment>,
+    // The bucket id generated for a client without one, set as its cookie with the response
+    pub new_bucket_id: Option<String>,
+}
+
+impl ExperimentAssignments {
+    // Requests are bucketed by the bucket header of the experiment if present, or else by the
+    // bucket cookie of the client. A client without a cookie gets a new bucket id.
+    pub fn assign(
+        experiments: &[ApiDeploymentExperiment],
+        headers: &HeaderMap,
+        cookies: &HashMap<&str, &str>,
+    ) -> ExperimentAssignments {
+        let mut new_bucket_id: Option<String> = None;
+
+        let assignments = experiments
+            .iter()
+            .filter_map(|experiment| {
+                let header_value = experiment
+                    .bucket_header
+                    .as_ref()
+                    .and_then(|header| headers.get(header.as_str()))
+                    .and_then(|value| value.to_str().ok());
+
+                let key = match (header_value, cookies.get(EXPERIMENT_BUCKET_COOKIE)) {
+                    (Some(value), _) => value.to_string(),
+                    (None, Some(bucket_id)) => bucket_id.to_string(),
+                    (None, None) => new_bucket_id
+                        .get_or_insert_with(|| Uuid::new_v4().to_string())
+                        .clone(),
+                };
+
+                experiment.assign(&key).map(|variant| ExperimentAssignment {
+                    experiment: experiment.name.clone(),
+                    variant: variant.name.clone(),
+                })
+            })
+            .collect();
+
+        ExperimentAssignments {
+            assignments,
+            new_bucket_id,
+        }
+    }
+
+    // The variants by experiment name, as `request.experiments` in Rib
+    pub fn to_json(&self) -> Value {
+        Value::Object(
+            self.assignments
+                .iter()
+                .map(|assignment| {
+                    (
+                        assignment.experiment.clone(),
+                        Value::String(assignment.variant.clone()),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    // Adds the assigned variants as headers of the response, along with the cookie of a new
+    // bucket id, so that the next requests of the client stay in the same buckets
+    pub fn apply_to_response(&self, mut response: poem::Response) -> poem::Response {
+        let headers = response.headers_mut();
+
+        for assignment in &self.assignments {
+            let name = HeaderName::try_from(format!(
+                "{}{}",
+                EXPERIMENT_HEADER_PREFIX, assignment.experiment
+            ));
+            let value = HeaderValue::try_from(assignment.variant.as_str());
+
+            if let (Ok(name), Ok(value)) = (name, value) {
+                headers.insert(name, value);
+            }
+        }
+
+        if let Some(bucket_id) = &self.new_bucket_id {
+            let cookie = format!(
+                "{}={}; Path=/; Max-Age={}; HttpOnly; SameSite=Lax",
+                EXPERIMENT_BUCKET_COOKIE, bucket_id, EXPERIMENT_BUCKET_COOKIE_MAX_AGE_SECONDS
+            );
+
+            if let Ok(cookie) = HeaderValue::try_from(cookie) {
+                headers.append(http::header::SET_COOKIE, cookie);
+            }
+        }
+
+        response
+    }
+}
+
+/// The number of requests assigned to each variant of an experiment of a site
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+pub struct ExperimentAssignmentCounts {
+    pub site: String,
+    pub experiment: String,
+    pub variants: Vec<VariantAssignmentCount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+pub struct VariantAssignmentCount {
+    pub variant: String,
+    pub requests: u64,
+}
+
+/// Counts the requests assigned to the variants of the experiments.
+/// Counts are kept in memory, so they are per worker service instance
+/// and are lost on restart.
+pub trait ExperimentAssignmentStore {
+    fn record(&self, site: &str, assignments: &ExperimentAssignments);
+
+    fn get_counts(&self) -> Vec<ExperimentAssignmentCounts>;
+
+    fn clear(&self);
+}
+
+#[derive(Default)]
+pub struct DefaultExperimentAssignmentStore {
+    // Requests by variant, by site and experiment
+    counts: Mutex<BTreeMap<(String, String), BTreeMap<String, u64>>>,
+}
+
+impl ExperimentAssignmentStore for DefaultExperimentAssignmentStore {
+    fn record(&self, site: &str, assignments: &ExperimentAssignments) {
+        let mut counts = self.counts.lock().unwrap();
+
+        for assignment in &assignments.assignments {
+            *counts
+                .entry((site.to_string(), assignment.experiment.clone()))
+                .or_default()
+                .entry(assignment.variant.clone())
+                .or_default() += 1;
+        }
+    }
+
+    fn get_counts(&self) -> Vec<ExperimentAssignmentCounts> {
+        self.counts
+            .lock()
+            .unwrap()
+            .iter()
+            .map(
+                |((site, experiment), variants)| ExperimentAssignmentCounts {
+                    site: site.clone(),
+                    experiment: experiment.clone(),
+                    variants: variants
+                        .iter()
+                        .map(|(variant, requests)| VariantAssignmentCount {
+                            variant: variant.clone(),
+                            requests: *requests,
+                        })
+                        .collect(),
+                },
+            )
+            .collect()
+    }
+
+    fn clear(&self) {
+        self.counts.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway_api_deployment::ExperimentVariant;
+    use test_r::test;
+
+    fn experiment(name: &str, bucket_header: Option<&str>) -> ApiDeploymentExperiment {
+        ApiDeploymentExperiment {
+            name: name.to_string(),
+            variants: vec![
+                ExperimentVariant {
+                    name: "control".to_string(),
+                    weight: 1,
+                },
+                ExperimentVariant {
+                    name: "treatment".to_string(),
+                    weight: 1,
+                },
+            ],
+            bucket_header: bucket_header.map(|header| header.to_string()),
+        }
+    }
+
+    #[test]
+    fn requests_stick_to_the_variants_of_their_bucket() {
+        let experiments = vec![
+            experiment("checkout", Some("x-user-id")),
+            experiment("search", None),
+        ];
+
+        // A new client gets a bucket id, shared by the experiments without a bucket header
+        let first = ExperimentAssignments::assign(&experiments, &HeaderMap::new(), &HashMap::new());
+        let bucket_id = first.new_bucket_id.clone().unwrap();
+        assert_eq!(first.assignments.len(), 2);
+
+        let cookies = HashMap::from([(EXPERIMENT_BUCKET_COOKIE, bucket_id.as_str())]);
+        let next = ExperimentAssignments::assign(&experiments, &HeaderMap::new(), &cookies);
+        assert_eq!(next.assignments, first.assignments);
+        assert_eq!(next.new_bucket_id, None);
+
+        // The bucket header takes precedence over the cookie
+        let mut headers = HeaderMap::new();
+        headers.insert("x-user-id", HeaderValue::from_static("user-1"));
+        let by_user = ExperimentAssignments::assign(&experiments, &headers, &HashMap::new());
+        assert_eq!(
+            by_user.assignments[0].variant,
+            experiments[0].assign("user-1").unwrap().name
+        );
+        assert_eq!(
+            by_user.to_json()["checkout"],
+            Value::String(by_user.assignments[0].variant.clone())
+        );
+
+        let response = first.apply_to_response(poem::Response::default());
+        assert_eq!(
+            response.header("x-golem-experiment-search"),
+            Some(first.assignments[1].variant.as_str())
+        );
+        assert!(response.header(http::header::SET_COOKIE).is_some_and(
+            |cookie| cookie.starts_with(&format!("{}={}", EXPERIMENT_BUCKET_COOKIE, bucket_id))
+        ));
+
+        let store = DefaultExperimentAssignmentStore::default();
+        store.record("shop.com", &first);
+        store.record("shop.com", &next);
+
+        let counts = store.get_counts();
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].experiment, "checkout");
+        assert_eq!(counts[0].variants.len(), 1);
+        assert_eq!(counts[0].variants[0].requests, 2);
+
+        store.clear();
+        assert!(store.get_counts().is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_binding::page_field_indices;
+use golem_wasm_ast::analysis::{AnalysedType, NameTypePair, TypeList, TypeOption, TypeRecord};
+use golem_wasm_rpc::{Value, ValueAndType};
+use rib::RibResult;
+use std::collections::BTreeMap;
+use url::form_urlencoded;
+
+pub const FIELDS_QUERY_PARAM: &str = "fields";
+
+// The fields of the response body requested by the client of a worker binding with the `fields`
+// query parameter, as comma separated paths (Example: `?fields=id,name,address.city`).
+// Records in lists and options are pruned the same way as the list or option itself.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FieldSelection {
+    // A field without nested fields is selected as a whole
+    pub fields: BTreeMap<String, FieldSelection>,
+}
+
+impl FieldSelection {
+    pub fn from_query(query: Option<&str>) -> Result<Option<FieldSelection>, String> {
+        let Some(fields) = form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+            .find(|(key, _)| key == FIELDS_QUERY_PARAM)
+            .map(|(_, value)| value.into_owned())
+            .filter(|fields| !fields.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let mut selection = FieldSelection::default();
+
+        for path in fields.split(',') {
+            let segments = path.trim().split('.').collect::<Vec<_>>();
+
+            if segments.iter().any(|segment| segment.is_empty()) {
+                return Err(format!("Invalid field '{}'", path.trim()));
+            }
+
+            selection.insert(&segments);
+        }
+
+        Ok(Some(selection))
+    }
+
+    fn insert(&mut self, path: &[&str]) {
+        let Some((name, rest)) = path.split_first() else {
+            return;
+        };
+
+        let is_new = !self.fields.contains_key(*name);
+        let field = self.fields.entry(name.to_string()).or_default();
+
+        if rest.is_empty() {
+            // The field is selected as a whole, including the nested fields selected so far
+            field.fields.clear();
+        } else if is_new || !field.fields.is_empty() {
+            field.insert(rest);
+        }
+    }
+
+    // Checks the selected fields against the type of the response body
+    pub fn validate(&self, typ: &AnalysedType) -> Result<(), String> {
+        self.validate_at(typ, &[])
+    }
+
+    fn validate_at(&self, typ: &AnalysedType, path: &[&str]) -> Result<(), String> {
+        match typ {
+            AnalysedType::List(TypeList { inner }) | AnalysedType::Option(TypeOption { inner }) => {
+                self.validate_at(inner, path)
+            }
+            AnalysedType::Record(TypeRecord { fields }) => {
+                for (name, selection) in &self.fields {
+                    let field = fields.iter().find(|field| &field.name == name);
+
+                    let mut field_path = path.to_vec();
+                    field_path.push(name.as_str());
+
+                    match field {
+                        Some(_) if selection.fields.is_empty() => {}
+                        Some(field) => selection.validate_at(&field.typ, &field_path)?,
+                        None => {
+                            return Err(format!(
+                                "Unknown field '{}' in the response",
+                                field_path.join(".")
+                            ))
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+            _ if path.is_empty() => {
+                Err("Fields can only be selected in responses of records".to_string())
+            }
+            _ => Err(format!(
+                "Field '{}' of the response has no fields to select",
+                path.join(".")
+            )),
+        }
+    }
+
+    // Prunes a value checked with `validate` down to the selected fields
+    pub fn apply(&self, value: Value, typ: AnalysedType) -> (Value, AnalysedType) {
+        match (value, typ) {
+            (Value::List(items), AnalysedType::List(TypeList { inner })) => {
+                let inner_type = self.apply_to_type(&inner);
+                let items = items
+                    .into_iter()
+                    .map(|item| self.apply(item, (*inner).clone()).0)
+                    .collect();
+
+                (
+                    Value::List(items),
+                    AnalysedType::List(TypeList {
+                        inner: Box::new(inner_type),
+                    }),
+                )
+            }
+            (Value::Option(item), AnalysedType::Option(TypeOption { inner })) => {
+                let inner_type = self.apply_to_type(&inner);
+                let item = item.map(|item| Box::new(self.apply(*item, (*inner).clone()).0));
+
+                (
+                    Value::Option(item),
+                    AnalysedType::Option(TypeOption {
+                        inner: Box::new(inner_type),
+                    }),
+                )
+            }
+            (Value::Record(values), AnalysedType::Record(TypeRecord { fields })) => {
+                let (values, fields) = values
+                    .into_iter()
+                    .zip(fields)
+                    .filter_map(|(value, field)| {
+                        let selection = self.fields.get(&field.name)?;
+
+                        if selection.fields.is_empty() {
+                            Some((value, field))
+                        } else {
+                            let (value, typ) = selection.apply(value, field.typ);
+                            Some((value, NameTypePair { typ, ..field }))
+                        }
+                    })
+                    .unzip();
+
+                (
+                    Value::Record(values),
+                    AnalysedType::Record(TypeRecord { fields }),
+                )
+            }
+            (value, typ) => (value, typ),
+        }
+    }
+
+    fn apply_to_type(&self, typ: &AnalysedType) -> AnalysedType {
+        match typ {
+            AnalysedType::List(TypeList { inner }) => AnalysedType::List(TypeList {
+                inner: Box::new(self.apply_to_type(inner)),
+            }),
+            AnalysedType::Option(TypeOption { inner }) => AnalysedType::Option(TypeOption {
+                inner: Box::new(self.apply_to_type(inner)),
+            }),
+            AnalysedType::Record(TypeRecord { fields }) => AnalysedType::Record(TypeRecord {
+                fields: fields
+                    .iter()
+                    .filter_map(|field| {
+                        let selection = self.fields.get(&field.name)?;
+
+                        if selection.fields.is_empty() {
+                            Some(field.clone())
+                        } else {
+                            Some(NameTypePair {
+                                name: field.name.clone(),
+                                typ: selection.apply_to_type(&field.typ),
+                            })
+                        }
+                    })
+                    .collect(),
+            }),
+            other => other.clone(),
+        }
+    }
+}
+
+// The position of the response body in the result of a response mapping: the items of a page,
+// the `body` of a response with a status or headers, or the whole result otherwise
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResponseBodyPosition {
+    Whole,
+    Field(usize),
+}
+
+impl ResponseBodyPosition {
+    pub fn of(typ: &AnalysedType, paginated: bool) -> ResponseBodyPosition {
+        if paginated {
+            return page_field_indices(typ)
+                .map(|(items_index, _)| ResponseBodyPosition::Field(items_index))
+                .unwrap_or(ResponseBodyPosition::Whole);
+        }
+
+        match typ {
+            AnalysedType::Record(TypeRecord { fields })
+                if fields
+                    .iter()
+                    .any(|field| field.name == "status" || field.name == "headers") =>
+            {
+                fields
+                    .iter()
+                    .position(|field| field.name == "body")
+                    .map(ResponseBodyPosition::Field)
+                    .unwrap_or(ResponseBodyPosition::Whole)
+            }
+            _ => ResponseBodyPosition::Whole,
+        }
+    }
+
+    pub fn body_type<'a>(&self, typ: &'a AnalysedType) -> Option<&'a AnalysedType> {
+        match (self, typ) {
+            (ResponseBodyPosition::Whole, typ) => Some(typ),
+            (ResponseBodyPosition::Field(index), AnalysedType::Record(record)) => {
+                record.fields.get(*index).map(|field| &field.typ)
+            }
+            (ResponseBodyPosition::Field(index), AnalysedType::Tuple(tuple)) => {
+                tuple.items.get(*index)
+            }
+            _ => None,
+        }
+    }
+}
+
+// Prunes the response body in the result of a response mapping down to the selected fields
+pub fn select_response_fields(
+    result: RibResult,
+    selection: &FieldSelection,
+    paginated: bool,
+) -> Result<RibResult, String> {
+    let RibResult::Val(ValueAndType { value, typ }) = result else {
+        return Ok(result);
+    };
+
+    let position = ResponseBodyPosition::of(&typ, paginated);
+
+    selection.validate(
+        position
+            .body_type(&typ)
+            .ok_or("Response body not found".to_string())?,
+    )?;
+
+    let (value, typ) = match (position, value, typ) {
+        (ResponseBodyPosition::Whole, value, typ) => selection.apply(value, typ),
+        (
+            ResponseBodyPosition::Field(index),
+            Value::Record(mut values),
+            AnalysedType::Record(mut record),
+        ) if index < values.len() => {
+            let (value, typ) =
+                selection.apply(values.remove(index), record.fields[index].typ.clone());
+            values.insert(index, value);
+            record.fields[index].typ = typ;
+            (Value::Record(values), AnalysedType::Record(record))
+        }
+        (
+            ResponseBodyPosition::Field(index),
+            Value::Tuple(mut values),
+            AnalysedType::Tuple(mut tuple),
+        ) if index < values.len() => {
+            let (value, typ) = selection.apply(values.remove(index), tuple.items.remove(index));
+            values.insert(index, value);
+            tuple.items.insert(index, typ);
+            (Value::Tuple(values), AnalysedType::Tuple(tuple))
+        }
+        _ => return Err("Response value does not match its type".to_string()),
+    };
+
+    Ok(RibResult::Val(ValueAndType::new(value, typ)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{field, list, option, record, str, u32, u64};
+    use test_r::test;
+
+    fn order_type() -> AnalysedType {
+        record(vec![
+            field("id", str()),
+            field("total", u64()),
+            field(
+                "address",
+                option(record(vec![field("city", str()), field("zip", str())])),
+            ),
+        ])
+    }
+
+    fn order(id: &str, city: &str) -> Value {
+        Value::Record(vec![
+            Value::String(id.to_string()),
+            Value::U64(100),
+            Value::Option(Some(Box::new(Value::Record(vec![
+                Value::String(city.to_string()),
+                Value::String("1000".to_string()),
+            ])))),
+        ])
+    }
+
+    #[test]
+    fn selected_fields_are_kept_in_records_of_the_body() {
+        let selection = FieldSelection::from_query(Some("status=open&fields=id,address.city"))
+            .unwrap()
+            .unwrap();
+
+        let response = RibResult::Val(ValueAndType::new(
+            Value::Record(vec![
+                Value::U32(200),
+                Value::List(vec![order("order-1", "Paris"), order("order-2", "Rome")]),
+            ]),
+            record(vec![
+                field("status", u32()),
+                field("body", list(order_type())),
+            ]),
+        ));
+
+        let RibResult::Val(selected) = select_response_fields(response, &selection, false).unwrap()
+        else {
+            panic!("Expected a value");
+        };
+
+        let selected_order_type = record(vec![
+            field("id", str()),
+            field("address", option(record(vec![field("city", str())]))),
+        ]);
+        let selected_order = |id: &str, city: &str| {
+            Value::Record(vec![
+                Value::String(id.to_string()),
+                Value::Option(Some(Box::new(Value::Record(vec![Value::String(
+                    city.to_string(),
+                )])))),
+            ])
+        };
+
+        assert_eq!(
+            selected,
+            ValueAndType::new(
+                Value::Record(vec![
+                    Value::U32(200),
+                    Value::List(vec![
+                        selected_order("order-1", "Paris"),
+                        selected_order("order-2", "Rome")
+                    ]),
+                ]),
+                record(vec![
+                    field("status", u32()),
+                    field("body", list(selected_order_type))
+                ]),
+            )
+        );
+
+        let unknown = FieldSelection::from_query(Some("fields=id,address.street"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            unknown.validate(&list(order_type())),
+            Err("Unknown field 'address.street' in the response".to_string())
+        );
+        assert!(FieldSelection::from_query(Some("fields=id.name"))
+            .unwrap()
+            .unwrap()
+            .validate(&order_type())
+            .is_err());
+        assert!(FieldSelection::from_query(Some("fields=id,,total")).is_err());
+        assert_eq!(FieldSelection::from_query(Some("fields=")), Ok(None));
+    }
+}
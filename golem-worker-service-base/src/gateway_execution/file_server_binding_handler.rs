@@ -48,6 +48,9 @@ pub type FileServerBindingResult = Result<FileServerBindingSuccess, FileServerBi
 
 pub struct FileServerBindingSuccess {
     pub binding_details: FileServerBindingDetails,
+    // Strong entity tag of the file, only known for read-only files, which are content addressed.
+    // Read-write files can change at any time, and are served without one.
+    pub etag: Option<String>,
     pub data: Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + 'static>>,
 }
 
@@ -63,6 +66,8 @@ pub struct FileServerBindingDetails {
     pub content_type: ContentType,
     pub status_code: StatusCode,
     pub file_path: ComponentFilePath,
+    // Taken from the `Cache-Control` header of the response record, allowing it to be set per route
+    pub cache_control: Option<String>,
 }
 
 impl FileServerBindingDetails {
@@ -101,7 +106,7 @@ impl FileServerBindingDetails {
             ValueAndType {
                 value: Value::String(raw_path),
                 ..
-            } => Self::make_from(raw_path.clone(), None, None),
+            } => Self::make_from(raw_path.clone(), None, None, None),
             ValueAndType {
                 value: Value::Record(field_values),
                 typ: AnalysedType::Record(record),
@@ -121,8 +126,18 @@ impl FileServerBindingDetails {
                 let status = get_status_code(field_values, record)?;
                 let headers = get_response_headers_or_default(&value)?;
                 let content_type = headers.get_content_type();
+                let cache_control = headers
+                    .headers
+                    .get(http::header::CACHE_CONTROL)
+                    .map(|value| {
+                        value
+                            .to_str()
+                            .map(|value| value.to_string())
+                            .map_err(|_| "Invalid Cache-Control header".to_string())
+                    })
+                    .transpose()?;
 
-                Self::make_from(path.to_string(), content_type, status)
+                Self::make_from(path.to_string(), content_type, status, cache_control)
             }
             _ => Err("Response value expected".to_string()),
         }
@@ -132,6 +147,7 @@ impl FileServerBindingDetails {
         path: String,
         content_type: Option<ContentType>,
         status_code: Option<StatusCode>,
+        cache_control: Option<String>,
     ) -> Result<FileServerBindingDetails, String> {
         let file_path = ComponentFilePath::from_either_str(&path)?;
 
@@ -152,6 +168,7 @@ impl FileServerBindingDetails {
             status_code,
             content_type,
             file_path,
+            cache_control,
         })
     }
 }
@@ -227,6 +244,7 @@ impl<Namespace: HasAccountId + Send + Sync + 'static> FileServerBindingHandler<N
 
             Ok(FileServerBindingSuccess {
                 binding_details,
+                etag: Some(format!("\"{}\"", file.key)),
                 data,
             })
         } else {
@@ -263,8 +281,138 @@ impl<Namespace: HasAccountId + Send + Sync + 'static> FileServerBindingHandler<N
 
             Ok(FileServerBindingSuccess {
                 binding_details,
+                etag: None,
                 data: Box::pin(stream),
             })
         }
     }
 }
+
+/// The outcome of a `Range` header for a file of a given length.
+/// Only single byte ranges are supported, other ranges are ignored and the whole file
+/// is served, which is allowed as servers can ignore the `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteRangeRequest {
+    Full,
+    // First and last byte positions, both inclusive
+    Partial { start: u64, end: u64 },
+    Unsatisfiable,
+}
+
+impl ByteRangeRequest {
+    pub fn from_header(range: &str, length: u64) -> ByteRangeRequest {
+        let range = match range.trim().split_once('=') {
+            Some((unit, range)) if unit.trim().eq_ignore_ascii_case("bytes") => range.trim(),
+            _ => return ByteRangeRequest::Full,
+        };
+
+        if range.contains(',') {
+            return ByteRangeRequest::Full;
+        }
+
+        let Some((first, last)) = range.split_once('-') else {
+            return ByteRangeRequest::Full;
+        };
+
+        match (first.trim(), last.trim()) {
+            ("", suffix_length) => match suffix_length.parse::<u64>() {
+                Ok(0) => ByteRangeRequest::Unsatisfiable,
+                Ok(_) if length == 0 => ByteRangeRequest::Unsatisfiable,
+                Ok(suffix_length) => ByteRangeRequest::Partial {
+                    start: length.saturating_sub(suffix_length),
+                    end: length - 1,
+                },
+                Err(_) => ByteRangeRequest::Full,
+            },
+            (first, last) => {
+                let Ok(start) = first.parse::<u64>() else {
+                    return ByteRangeRequest::Full;
+                };
+
+                let end = if last.is_empty() {
+                    None
+                } else {
+                    match last.parse::<u64>() {
+                        Ok(end) if end >= start => Some(end),
+                        _ => return ByteRangeRequest::Full,
+                    }
+                };
+
+                if start >= length {
+                    ByteRangeRequest::Unsatisfiable
+                } else {
+                    ByteRangeRequest::Partial {
+                        start,
+                        end: end.map_or(length - 1, |end| end.min(length - 1)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Whether an `If-None-Match` header matches the entity tag of a file, using the weak comparison
+pub fn if_none_match_matches(if_none_match: &str, etag: &str) -> bool {
+    let weak = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+
+    if_none_match.trim() == "*"
+        || if_none_match
+            .split(',')
+            .any(|candidate| weak(candidate) == weak(etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn byte_ranges_are_resolved_against_the_file_length() {
+        let partial = |start, end| ByteRangeRequest::Partial { start, end };
+
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=0-99", 1000),
+            partial(0, 99)
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=900-", 1000),
+            partial(900, 999)
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=990-2000", 1000),
+            partial(990, 999)
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=-100", 1000),
+            partial(900, 999)
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=-2000", 1000),
+            partial(0, 999)
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=1000-", 1000),
+            ByteRangeRequest::Unsatisfiable
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=-0", 1000),
+            ByteRangeRequest::Unsatisfiable
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=0-1,5-6", 1000),
+            ByteRangeRequest::Full
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("items=0-1", 1000),
+            ByteRangeRequest::Full
+        );
+        assert_eq!(
+            ByteRangeRequest::from_header("bytes=5-1", 1000),
+            ByteRangeRequest::Full
+        );
+
+        assert!(if_none_match_matches("\"a\", W/\"b\"", "\"b\""));
+        assert!(if_none_match_matches("*", "\"b\""));
+        assert!(!if_none_match_matches("\"a\"", "\"b\""));
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_deployment::http::CLIENT_CERTIFICATE_HEADER;
+use poem::{Endpoint, Middleware, Request};
+use std::future::Future;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+// An address, or a range of addresses in CIDR notation, of a proxy terminating TLS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedProxy {
+    address: IpAddr,
+    prefix_length: u8,
+}
+
+impl TrustedProxy {
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        match (self.address, address.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_length as u32)
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_length as u32)
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for TrustedProxy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_length) = match s.trim().split_once('/') {
+            Some((address, prefix_length)) => (address, Some(prefix_length)),
+            None => (s.trim(), None),
+        };
+
+        let address = IpAddr::from_str(address)
+            .map_err(|err| format!("Invalid trusted proxy address {}: {}", s, err))?
+            .to_canonical();
+
+        let max_prefix_length = if address.is_ipv4() { 32 } else { 128 };
+
+        let prefix_length = match prefix_length {
+            Some(prefix_length) => prefix_length
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix_length| *prefix_length <= max_prefix_length)
+                .ok_or_else(|| format!("Invalid prefix length of trusted proxy {}", s))?,
+            None => max_prefix_length,
+        };
+
+        Ok(TrustedProxy {
+            address,
+            prefix_length,
+        })
+    }
+}
+
+/// Drops the client certificate header from the requests which are not coming from one of the
+/// trusted proxies, so that only the certificates verified by the proxies terminating TLS reach
+/// the client certificate authentication of the deployments
+#[derive(Clone)]
+pub struct ForwardedClientCertificateMiddleware {
+    trusted_proxies: Arc<Vec<TrustedProxy>>,
+}
+
+impl ForwardedClientCertificateMiddleware {
+    pub fn new(trusted_proxies: &[String]) -> Result<Self, String> {
+        let trusted_proxies = trusted_proxies
+            .iter()
+            .map(|trusted_proxy| TrustedProxy::from_str(trusted_proxy))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ForwardedClientCertificateMiddleware {
+            trusted_proxies: Arc::new(trusted_proxies),
+        })
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ForwardedClientCertificateMiddleware {
+    type Output = ForwardedClientCertificateEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        ForwardedClientCertificateEndpoint {
+            inner,
+            trusted_proxies: self.trusted_proxies.clone(),
+        }
+    }
+}
+
+pub struct ForwardedClientCertificateEndpoint<E> {
+    inner: E,
+    trusted_proxies: Arc<Vec<TrustedProxy>>,
+}
+
+impl<E: Endpoint> ForwardedClientCertificateEndpoint<E> {
+    fn is_from_trusted_proxy(&self, request: &Request) -> bool {
+        match request.remote_addr().as_socket_addr() {
+            Some(remote_addr) => {
+                let address = remote_addr.ip();
+                self.trusted_proxies
+                    .iter()
+                    .any(|trusted_proxy| trusted_proxy.contains(&address))
+            }
+            None => false,
+        }
+    }
+}
+
+impl<E: Endpoint> Endpoint for ForwardedClientCertificateEndpoint<E> {
+    type Output = E::Output;
+
+    fn call(
+        &self,
+        mut request: Request,
+    ) -> impl Future<Output = poem::Result<Self::Output>> + Send {
+        if !self.is_from_trusted_proxy(&request) {
+            request.headers_mut().remove(CLIENT_CERTIFICATE_HEADER);
+        }
+
+        self.inner.call(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    fn is_trusted(address: &str, trusted_proxies: &[&str]) -> bool {
+        let address = IpAddr::from_str(address).unwrap();
+        trusted_proxies.iter().any(|trusted_proxy| {
+            TrustedProxy::from_str(trusted_proxy)
+                .unwrap()
+                .contains(&address)
+        })
+    }
+
+    #[test]
+    fn client_certificates_are_only_accepted_from_trusted_proxies() {
+        assert!(is_trusted("10.1.2.3", &["10.0.0.0/8"]));
+        assert!(is_trusted("::ffff:10.1.2.3", &["10.0.0.0/8"]));
+        assert!(is_trusted("fd00::1", &["127.0.0.1", "fd00::/8"]));
+        assert!(is_trusted("192.168.1.1", &["0.0.0.0/0"]));
+        assert!(is_trusted("127.0.0.1", &["127.0.0.1"]));
+        assert!(!is_trusted("127.0.0.2", &["127.0.0.1"]));
+        assert!(!is_trusted("11.1.2.3", &["10.0.0.0/8"]));
+        assert!(!is_trusted("::1", &["127.0.0.1"]));
+        assert!(!is_trusted("127.0.0.1", &[]));
+
+        assert!(TrustedProxy::from_str("10.0.0.0/33").is_err());
+        assert!(TrustedProxy::from_str("proxy.example.com").is_err());
+    }
+}
@@ -13,46 +13,97 @@
 // limitations under the License.
 
 use super::auth_call_back_binding_handler::AuthorisationSuccess;
+use super::binary_payload::{parse_with_binary_fields, BinaryField};
+use super::body_limits::{limit_response_body, read_limited_request_body};
+use super::deployment_capacity_limiter::{capacity_exceeded_response, DeploymentCapacityLimiter};
+use super::error_mapping::mapped_error_response;
+use super::experiment_assignment::{ExperimentAssignmentStore, ExperimentAssignments};
+use super::field_selection::{
+    select_response_fields, FieldSelection, ResponseBodyPosition, FIELDS_QUERY_PARAM,
+};
 use super::file_server_binding_handler::FileServerBindingSuccess;
+use super::grpc_transcoding::GrpcBindingHandler;
 use super::http_handler_binding_handler::{HttpHandlerBindingHandler, HttpHandlerBindingResult};
+use super::http_proxy_binding_handler::HttpProxyBindingHandler;
+use super::middleware_conditions::skipped_middlewares;
+use super::pagination::{Page, PageRequest};
+use super::rate_limiter::{rate_limited_response, RateLimitDecision, RateLimiterStore};
 use super::request::{
     authority_from_request, split_resolved_route_entry, RichRequest, SplitResolvedRouteEntryResult,
 };
+use super::request_id::request_id;
+use super::response_cache::{
+    response_cache_key, CachedResponse, ResponseCacheStore, CACHE_STATUS_HEADER,
+};
+use super::response_compression::compress_response;
+use super::route_concurrency_limiter::{shed_response, RouteConcurrencyLimiter};
+use super::route_metrics::RouteMetrics;
+use super::server_sent_events_bridge::{EventId, ServerSentEventsBridge, WorkerEventSubscriber};
+use super::shadow_traffic_diff::{diff_responses, ShadowTrafficDiffStore};
+use super::status_page::StatusPage;
 use super::to_response::GatewayHttpResult;
-use super::WorkerDetail;
-use crate::gateway_api_deployment::ApiSiteString;
+use super::transforms::{apply_request_transforms, apply_response_transforms};
+use super::web_socket_bridge::WebSocketBridge;
+use super::{WorkerBootstrapValues, WorkerDetail};
+use crate::gateway_api_definition::http::{
+    to_open_api_document, CompiledHttpApiDefinition, OPEN_API_WELL_KNOWN_PATH,
+};
+use crate::gateway_api_deployment::{
+    ApiDeploymentConfig, ApiDeploymentErrorPages, ApiSiteString, ClientCertificate, ErrorPageKind,
+    CLIENT_CERTIFICATE_HEADER,
+};
 use crate::gateway_binding::{
-    resolve_gateway_binding, GatewayBindingCompiled, HttpHandlerBindingCompiled,
-    IdempotencyKeyCompiled, InvocationContextCompiled, ResponseMappingCompiled, StaticBinding,
-    WorkerBindingCompiled, WorkerNameCompiled,
+    resolve_gateway_binding, BootstrapValueCompiled, GatewayBindingCompiled,
+    HttpHandlerBindingCompiled, IdempotencyKeyCompiled, InvocationContextCompiled,
+    ResponseMappingCompiled, StaticBinding, TrafficMirrorCompiled, WorkerBindingCompiled,
+    WorkerBootstrapCompiled, WorkerNameCompiled,
 };
 use crate::gateway_execution::api_definition_lookup::HttpApiDefinitionsLookup;
 use crate::gateway_execution::auth_call_back_binding_handler::AuthCallBackBindingHandler;
 use crate::gateway_execution::file_server_binding_handler::FileServerBindingHandler;
 use crate::gateway_execution::gateway_session::GatewaySessionStore;
-use crate::gateway_execution::to_response::{GatewayHttpError, ToHttpResponse};
+use crate::gateway_execution::to_response::{
+    cors_preflight_response, GatewayHttpError, ToHttpResponse,
+};
 use crate::gateway_execution::to_response_failure::ToHttpResponseFromSafeDisplay;
-use crate::gateway_middleware::{HttpMiddlewares, MiddlewareError, MiddlewareSuccess};
-use crate::gateway_rib_interpreter::WorkerServiceRibInterpreter;
-use crate::gateway_security::{IdentityProvider, SecuritySchemeWithProviderMetadata};
+use crate::gateway_middleware::{
+    HttpCompression, HttpMiddlewares, HttpRateLimit, MiddlewareError, MiddlewareSuccess,
+};
+use crate::gateway_rib_interpreter::{EvaluationError, WorkerServiceRibInterpreter};
+use crate::gateway_security::{
+    ApiKeyValidator, HmacSignatureValidator, IdentityProvider, JwtValidator,
+    SecuritySchemeWithProviderMetadata,
+};
 use crate::http_invocation_context::{extract_request_attributes, invocation_context_from_request};
+use crate::metrics::gateway as gateway_metrics;
+use crate::service::gateway::dead_letter::{
+    DeadLetterReplay, DeadLetterRequest, DeadLetterService,
+};
+use crate::service::gateway::request_fixture::{
+    capture_response, RequestFixtureReplay, RequestFixtureService,
+};
 use async_trait::async_trait;
 use golem_common::model::invocation_context::{
     AttributeValue, InvocationContextSpan, InvocationContextStack, SpanId, TraceId,
 };
 use golem_common::model::IdempotencyKey;
+use golem_common::SafeDisplay;
 use golem_service_base::headers::TraceContextHeaders;
 use golem_service_base::model::VersionedComponentId;
+use golem_wasm_ast::analysis::AnalysedType;
 use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use golem_wasm_rpc::ValueAndType;
 use http::StatusCode;
-use poem::Body;
+use poem::web::websocket::WebSocket;
+use poem::{Body, FromRequest, IntoResponse};
 use rib::{RibInput, RibInputTypeInfo, RibResult};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::Arc;
-use tracing::error;
+use std::time::Instant;
+use tracing::{error, info_span, Instrument};
 
 #[async_trait]
 pub trait GatewayHttpInputExecutor {
@@ -64,46 +115,296 @@ pub struct DefaultGatewayInputExecutor<Namespace> {
     pub file_server_binding_handler: Arc<dyn FileServerBindingHandler<Namespace> + Sync + Send>,
     pub auth_call_back_binding_handler: Arc<dyn AuthCallBackBindingHandler + Sync + Send>,
     pub http_handler_binding_handler: Arc<dyn HttpHandlerBindingHandler<Namespace> + Sync + Send>,
+    pub grpc_binding_handler: Arc<dyn GrpcBindingHandler + Sync + Send>,
+    pub http_proxy_binding_handler: Arc<dyn HttpProxyBindingHandler + Sync + Send>,
+    pub shadow_traffic_diff_store: Arc<dyn ShadowTrafficDiffStore + Sync + Send>,
+    pub experiment_assignment_store: Arc<dyn ExperimentAssignmentStore + Sync + Send>,
+    pub dead_letter_service: Arc<dyn DeadLetterService<Namespace> + Sync + Send>,
+    pub request_fixture_service: Arc<dyn RequestFixtureService<Namespace> + Sync + Send>,
+    pub worker_event_subscriber: Arc<dyn WorkerEventSubscriber + Sync + Send>,
+    pub rate_limiter: RateLimiterStore,
+    pub response_cache: ResponseCacheStore,
+    pub route_concurrency_limiter: Arc<RouteConcurrencyLimiter>,
+    pub deployment_capacity_limiter: Arc<DeploymentCapacityLimiter>,
+    pub route_metrics: Arc<RouteMetrics>,
     pub api_definition_lookup_service: Arc<dyn HttpApiDefinitionsLookup<Namespace> + Sync + Send>,
     pub gateway_session_store: GatewaySessionStore,
     pub identity_provider: Arc<dyn IdentityProvider + Send + Sync>,
+    pub jwt_validator: Arc<dyn JwtValidator + Send + Sync>,
+    pub api_key_validator: Arc<dyn ApiKeyValidator<Namespace> + Send + Sync>,
+    pub hmac_signature_validator: Arc<dyn HmacSignatureValidator<Namespace> + Send + Sync>,
 }
 
-impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
+impl<Namespace: Clone + Send + Sync + 'static> DefaultGatewayInputExecutor<Namespace> {
     pub fn new(
         evaluator: Arc<dyn WorkerServiceRibInterpreter<Namespace> + Sync + Send>,
         file_server_binding_handler: Arc<dyn FileServerBindingHandler<Namespace> + Sync + Send>,
         auth_call_back_binding_handler: Arc<dyn AuthCallBackBindingHandler + Sync + Send>,
         http_handler_binding_handler: Arc<dyn HttpHandlerBindingHandler<Namespace> + Sync + Send>,
+        grpc_binding_handler: Arc<dyn GrpcBindingHandler + Sync + Send>,
+        http_proxy_binding_handler: Arc<dyn HttpProxyBindingHandler + Sync + Send>,
+        shadow_traffic_diff_store: Arc<dyn ShadowTrafficDiffStore + Sync + Send>,
+        experiment_assignment_store: Arc<dyn ExperimentAssignmentStore + Sync + Send>,
+        dead_letter_service: Arc<dyn DeadLetterService<Namespace> + Sync + Send>,
+        request_fixture_service: Arc<dyn RequestFixtureService<Namespace> + Sync + Send>,
+        worker_event_subscriber: Arc<dyn WorkerEventSubscriber + Sync + Send>,
+        rate_limiter: RateLimiterStore,
+        response_cache: ResponseCacheStore,
+        route_concurrency_limiter: Arc<RouteConcurrencyLimiter>,
+        deployment_capacity_limiter: Arc<DeploymentCapacityLimiter>,
+        route_metrics: Arc<RouteMetrics>,
         api_definition_lookup_service: Arc<dyn HttpApiDefinitionsLookup<Namespace> + Sync + Send>,
         gateway_session_store: GatewaySessionStore,
         identity_provider: Arc<dyn IdentityProvider + Send + Sync>,
+        jwt_validator: Arc<dyn JwtValidator + Send + Sync>,
+        api_key_validator: Arc<dyn ApiKeyValidator<Namespace> + Send + Sync>,
+        hmac_signature_validator: Arc<dyn HmacSignatureValidator<Namespace> + Send + Sync>,
     ) -> Self {
         Self {
             evaluator,
             file_server_binding_handler,
             auth_call_back_binding_handler,
             http_handler_binding_handler,
+            grpc_binding_handler,
+            http_proxy_binding_handler,
+            shadow_traffic_diff_store,
+            experiment_assignment_store,
+            dead_letter_service,
+            request_fixture_service,
+            worker_event_subscriber,
+            rate_limiter,
+            response_cache,
+            route_concurrency_limiter,
+            deployment_capacity_limiter,
+            route_metrics,
             api_definition_lookup_service,
             gateway_session_store,
             identity_provider,
+            jwt_validator,
+            api_key_validator,
+            hmac_signature_validator,
+        }
+    }
+
+    // Verifies the client certificate of the request, if the deployment requires one
+    fn verify_client_certificate(
+        authority: &str,
+        config: &ApiDeploymentConfig,
+        request: &poem::Request,
+    ) -> Result<Option<ClientCertificate>, poem::Response> {
+        match &config.client_certificates {
+            Some(client_certificates) => client_certificates
+                .verify(request.header(CLIENT_CERTIFICATE_HEADER))
+                .map_err(|err| {
+                    tracing::debug!("API request host: {} - {}", authority, err);
+                    poem::Response::builder()
+                        .status(StatusCode::FORBIDDEN)
+                        .body(Body::from_string(err.to_string()))
+                }),
+            None => Ok(None),
+        }
+    }
+
+    // Leaves out the version of a split definition that does not serve the request
+    fn apply_traffic_split(
+        config: &ApiDeploymentConfig,
+        request: &poem::Request,
+        api_definitions: Vec<CompiledHttpApiDefinition<Namespace>>,
+    ) -> Vec<CompiledHttpApiDefinition<Namespace>> {
+        match &config.traffic_split {
+            Some(traffic_split) => {
+                let header_value = traffic_split
+                    .header
+                    .as_ref()
+                    .and_then(|header| request.header(header.name.as_str()));
+
+                traffic_split.select(api_definitions, header_value, fastrand::u8(0..100))
+            }
+            None => api_definitions,
+        }
+    }
+
+    // Assigns the request to the variants of the experiments of its site, counting the assignments
+    fn assign_experiments(
+        &self,
+        authority: &str,
+        config: &ApiDeploymentConfig,
+        request: &RichRequest,
+    ) -> ExperimentAssignments {
+        let assignments = ExperimentAssignments::assign(
+            &config.experiments,
+            request.underlying.headers(),
+            &request.get_cookie_values(),
+        );
+
+        self.experiment_assignment_store
+            .record(authority, &assignments);
+
+        assignments
+    }
+
+    // Requests of the sites with a dead-letter queue are captured before being handled, except for
+    // the replays of dead letters, which stay in the queue when they fail again
+    async fn capture_dead_letter_request(
+        config: &ApiDeploymentConfig,
+        request: &mut poem::Request,
+    ) -> Option<DeadLetterRequest> {
+        if request.extensions().get::<DeadLetterReplay>().is_some() {
+            return None;
+        }
+
+        let dead_letter_queue = config.dead_letter_queue.as_ref()?;
+
+        Some(DeadLetterRequest::capture(dead_letter_queue, request).await)
+    }
+
+    // Failing to record the dead letter does not change the response of the failed request
+    async fn record_dead_letter(
+        &self,
+        namespace: &Namespace,
+        authority: &str,
+        route: &str,
+        request: DeadLetterRequest,
+        error: &EvaluationError,
+    ) {
+        if let Err(err) = self
+            .dead_letter_service
+            .record(
+                namespace,
+                &ApiSiteString(authority.to_string()),
+                route,
+                request,
+                error.to_safe_string(),
+            )
+            .await
+        {
+            error!(
+                "Failed to add the request of route {} to the dead-letter queue: {}",
+                route,
+                err.to_safe_string()
+            );
         }
     }
 
+    // A sample of the requests of the sites recording fixtures is captured before being handled,
+    // along with the maximum number of fixtures of the site. Replays of fixtures are not sampled.
+    async fn capture_request_fixture(
+        config: &ApiDeploymentConfig,
+        request: &mut poem::Request,
+    ) -> Option<(DeadLetterRequest, u32)> {
+        if request.extensions().get::<RequestFixtureReplay>().is_some() {
+            return None;
+        }
+
+        let request_fixtures = config
+            .request_fixtures
+            .as_ref()
+            .filter(|request_fixtures| request_fixtures.is_sampled(fastrand::f64()))?;
+
+        let fixture_request = DeadLetterRequest::capture_with(
+            request,
+            |name| request_fixtures.is_redacted(name),
+            |size| request_fixtures.keeps_body_of_size(size),
+        )
+        .await;
+
+        Some((fixture_request, request_fixtures.max_fixtures()))
+    }
+
+    // Failing to record the fixture does not change the response, which is buffered to derive
+    // the schema of its body
+    async fn record_request_fixture(
+        &self,
+        namespace: &Namespace,
+        authority: &str,
+        route: &str,
+        (request, max_fixtures): (DeadLetterRequest, u32),
+        response: poem::Response,
+    ) -> poem::Response {
+        let status = response.status().as_u16();
+        let (response, response_schema) = capture_response(response).await;
+
+        if let Err(err) = self
+            .request_fixture_service
+            .record(
+                namespace,
+                &ApiSiteString(authority.to_string()),
+                route,
+                request,
+                status,
+                response_schema,
+                max_fixtures,
+            )
+            .await
+        {
+            error!(
+                "Failed to record the request of route {} as a fixture: {}",
+                route,
+                err.to_safe_string()
+            );
+        }
+
+        response
+    }
+
     async fn handle_worker_binding(
         &self,
         namespace: &Namespace,
         request: &mut RichRequest,
         binding: &WorkerBindingCompiled,
+        route: &str,
     ) -> GatewayHttpResult<RibResult> {
         let mut rib_input: serde_json::Map<String, Value> = serde_json::Map::new();
 
+        if let Some(content_negotiation) = &binding.content_negotiation {
+            let media_type = content_negotiation
+                .negotiate(request.underlying.header(http::header::ACCEPT))
+                .map_err(GatewayHttpError::NotAcceptable)?;
+            request.negotiated_media_type = Some(media_type);
+        }
+
+        // Routes with a `fields` query parameter of their own keep it for their response mapping
+        if !request
+            .query_info
+            .iter()
+            .any(|query| query.key_name == FIELDS_QUERY_PARAM)
+        {
+            let field_selection = FieldSelection::from_query(request.underlying.uri().query())
+                .map_err(GatewayHttpError::BadRequest)?;
+
+            if let (Some(field_selection), Some(rib_output)) =
+                (&field_selection, &binding.response_compiled.rib_output)
+            {
+                let response_type = &rib_output.analysed_type;
+                let position =
+                    ResponseBodyPosition::of(response_type, binding.pagination.is_some());
+
+                if let Some(body_type) = position.body_type(response_type) {
+                    field_selection
+                        .validate(body_type)
+                        .map_err(GatewayHttpError::BadRequest)?;
+                }
+            }
+
+            request.field_selection = field_selection;
+        }
+
         // phase 1. we only have the request details available
         {
-            let request_value = request
+            let mut request_value = request
                 .as_json_with_body()
                 .await
                 .map_err(GatewayHttpError::BadRequest)?;
+
+            if let (Some(pagination), Value::Object(request_fields)) =
+                (&binding.pagination, &mut request_value)
+            {
+                let page_request =
+                    PageRequest::from_query(pagination, request.underlying.uri().query())
+                        .map_err(GatewayHttpError::BadRequest)?;
+                request_fields.insert("page".to_string(), page_request.as_json());
+            }
+
             rib_input.insert("request".to_string(), request_value);
         }
 
@@ -115,22 +416,251 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
                 &binding.idempotency_key_compiled,
                 &binding.component_id,
                 &binding.invocation_context_compiled,
+                &binding.bootstrap_compiled,
             )
             .await?;
 
         // phase 2. we have both the request and the worker details available
         {
-            let worker_value: Value = worker_detail.as_json();
+            let mut worker_value: Value = worker_detail.as_json();
+            self.add_worker_labels(
+                namespace,
+                &mut worker_value,
+                &worker_detail,
+                &binding.response_compiled.rib_input,
+            )
+            .await?;
             rib_input.insert("worker".to_string(), worker_value);
         }
 
-        self.get_response_script_result(
-            namespace,
-            &binding.response_compiled,
-            &rib_input,
-            &worker_detail,
-        )
-        .await
+        let result = self
+            .get_response_script_result(
+                namespace,
+                &binding.response_compiled,
+                &rib_input,
+                &request.binary_fields,
+                &worker_detail,
+            )
+            .await;
+
+        if let Some(traffic_mirror) = &binding.traffic_mirror_compiled {
+            if traffic_mirror.should_mirror(fastrand::u32(0..100)) {
+                self.mirror_to_shadow(
+                    namespace,
+                    route,
+                    traffic_mirror,
+                    rib_input,
+                    request.binary_fields.clone(),
+                    worker_detail,
+                    &result,
+                );
+            }
+        }
+
+        result
+    }
+
+    // Replays the request against the shadow component in the background.
+    // The shadow response is discarded after being compared with the primary one,
+    // and the outcome of the comparison is recorded in the diff store of the route.
+    #[allow(clippy::too_many_arguments)]
+    fn mirror_to_shadow(
+        &self,
+        namespace: &Namespace,
+        route: &str,
+        traffic_mirror: &TrafficMirrorCompiled,
+        mut rib_input: serde_json::Map<String, Value>,
+        request_binary_fields: Vec<BinaryField>,
+        worker_detail: WorkerDetail,
+        primary_result: &GatewayHttpResult<RibResult>,
+    ) {
+        let shadow_worker_detail = WorkerDetail {
+            component_id: traffic_mirror.component_id.clone(),
+            ..worker_detail
+        };
+
+        rib_input.insert("worker".to_string(), shadow_worker_detail.as_json());
+
+        let primary_value = match primary_result {
+            Ok(rib_result) => rib_result_to_json(rib_result),
+            Err(_) => Err("Primary request failed".to_string()),
+        };
+
+        let evaluator = self.evaluator.clone();
+        let diff_store = self.shadow_traffic_diff_store.clone();
+        let route = route.to_string();
+        let namespace = namespace.clone();
+        let traffic_mirror = traffic_mirror.clone();
+
+        tokio::spawn(gateway_metrics::with_current_route(
+            async move {
+                let shadow_result = match resolve_rib_input_with_binary_fields(
+                    &rib_input,
+                    &traffic_mirror.response_compiled.rib_input,
+                    &request_binary_fields,
+                )
+                .await
+                {
+                    Ok(shadow_rib_input) => evaluator
+                        .evaluate(
+                            shadow_worker_detail.worker_name.as_deref(),
+                            &shadow_worker_detail.component_id.component_id,
+                            &shadow_worker_detail.idempotency_key,
+                            shadow_worker_detail.invocation_context.clone(),
+                            shadow_worker_detail.bootstrap.clone(),
+                            &traffic_mirror.response_compiled.response_mapping_compiled,
+                            &shadow_rib_input,
+                            namespace,
+                        )
+                        .await
+                        .map_err(|err| err.to_string()),
+                    Err(err) => Err(err),
+                };
+
+                if let Err(err) = &shadow_result {
+                    tracing::debug!(
+                        component_id = traffic_mirror.component_id.to_string(),
+                        error = %err,
+                        "Mirrored request failed"
+                    );
+                }
+
+                let shadow_value = shadow_result.and_then(|result| rib_result_to_json(&result));
+                let diffs =
+                    diff_responses(primary_value, shadow_value, &traffic_mirror.ignored_fields);
+
+                if traffic_mirror.log_divergence && !diffs.is_empty() {
+                    tracing::warn!(
+                        component_id = traffic_mirror.component_id.to_string(),
+                        worker_name = ?shadow_worker_detail.worker_name,
+                        route = route,
+                        diffs = ?diffs,
+                        "Response of the shadow component diverged from the primary response"
+                    );
+                }
+
+                diff_store.record(
+                    &route,
+                    &traffic_mirror.component_id,
+                    shadow_worker_detail.worker_name,
+                    diffs,
+                );
+            }
+            .in_current_span(),
+        ));
+    }
+
+    // Upgrades the request to a WebSocket connection bridged to the worker of the binding.
+    // The worker details are resolved once, from the upgrade request, and shared by all messages.
+    async fn handle_web_socket_binding(
+        &self,
+        namespace: &Namespace,
+        request: &RichRequest,
+        binding: &WorkerBindingCompiled,
+    ) -> GatewayHttpResult<poem::Response> {
+        let web_socket = WebSocket::from_request_without_body(&request.underlying)
+            .await
+            .map_err(|_| {
+                GatewayHttpError::BadRequest("Expected a WebSocket upgrade request".to_string())
+            })?;
+
+        let mut rib_input: serde_json::Map<String, Value> = serde_json::Map::new();
+
+        {
+            let request_value = request.as_json().map_err(GatewayHttpError::BadRequest)?;
+            rib_input.insert("request".to_string(), request_value);
+        }
+
+        let worker_detail = self
+            .get_worker_detail(
+                request,
+                &rib_input,
+                &binding.worker_name_compiled,
+                &None,
+                &binding.component_id,
+                &binding.invocation_context_compiled,
+                &binding.bootstrap_compiled,
+            )
+            .await?;
+
+        rib_input.insert("worker".to_string(), worker_detail.as_json());
+
+        let bridge = WebSocketBridge {
+            evaluator: self.evaluator.clone(),
+            namespace: namespace.clone(),
+            worker_detail,
+            rib_input,
+            message_mapping: binding.response_compiled.clone(),
+        };
+
+        Ok(web_socket
+            .on_upgrade(move |socket| bridge.run(socket).in_current_span())
+            .into_response())
+    }
+
+    // Streams the events emitted by the worker of the binding as server-sent events.
+    // Clients reconnecting with a Last-Event-ID header resume after the last event they received.
+    async fn handle_server_sent_events_binding(
+        &self,
+        namespace: &Namespace,
+        request: &RichRequest,
+        binding: &WorkerBindingCompiled,
+    ) -> GatewayHttpResult<poem::Response> {
+        let last_event_id = request
+            .underlying
+            .headers()
+            .get("Last-Event-ID")
+            .map(|value| {
+                value
+                    .to_str()
+                    .map_err(|err| err.to_string())
+                    .and_then(EventId::from_str)
+                    .map_err(GatewayHttpError::BadRequest)
+            })
+            .transpose()?;
+
+        let mut rib_input: serde_json::Map<String, Value> = serde_json::Map::new();
+
+        {
+            let request_value = request.as_json().map_err(GatewayHttpError::BadRequest)?;
+            rib_input.insert("request".to_string(), request_value);
+        }
+
+        let worker_detail = self
+            .get_worker_detail(
+                request,
+                &rib_input,
+                &binding.worker_name_compiled,
+                &None,
+                &binding.component_id,
+                &binding.invocation_context_compiled,
+                &binding.bootstrap_compiled,
+            )
+            .await?;
+
+        rib_input.insert("worker".to_string(), worker_detail.as_json());
+
+        let events = self
+            .worker_event_subscriber
+            .subscribe(&worker_detail)
+            .await
+            .map_err(|err| {
+                GatewayHttpError::BadRequest(format!(
+                    "Failed to subscribe to the events of the worker: {}",
+                    err
+                ))
+            })?;
+
+        let bridge = ServerSentEventsBridge {
+            evaluator: self.evaluator.clone(),
+            namespace: namespace.clone(),
+            worker_detail,
+            rib_input,
+            event_mapping: binding.response_compiled.clone(),
+            last_event_id,
+        };
+
+        Ok(bridge.into_response(events).into_response())
     }
 
     async fn handle_http_handler_binding(
@@ -154,6 +684,7 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
                 &binding.idempotency_key_compiled,
                 &binding.component_id,
                 &None,
+                &None,
             )
             .await?;
 
@@ -200,12 +731,20 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
                 &binding.idempotency_key_compiled,
                 &binding.component_id,
                 &None,
+                &binding.bootstrap_compiled,
             )
             .await?;
 
         // phase 2. we have both the request and the worker details available
         {
-            let worker_value: Value = worker_detail.as_json();
+            let mut worker_value: Value = worker_detail.as_json();
+            self.add_worker_labels(
+                namespace,
+                &mut worker_value,
+                &worker_detail,
+                &binding.response_compiled.rib_input,
+            )
+            .await?;
             rib_input.insert("worker".to_string(), worker_value);
         }
 
@@ -214,6 +753,7 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
                 namespace,
                 &binding.response_compiled,
                 &rib_input,
+                &request.binary_fields,
                 &worker_detail,
             )
             .await?;
@@ -224,6 +764,36 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
             .map_err(GatewayHttpError::FileServerBindingError)
     }
 
+    // Renders the status page of the deployment, listing all of its API definitions,
+    // as opposed to only the ones a traffic split would route the request to
+    async fn handle_status_page_binding(
+        &self,
+        authority: &str,
+        rich_request: &RichRequest,
+    ) -> poem::Response {
+        match self
+            .api_definition_lookup_service
+            .get(&ApiSiteString(authority.to_string()))
+            .await
+        {
+            Ok(definitions) => {
+                let accept = rich_request
+                    .underlying
+                    .header(http::header::ACCEPT)
+                    .map(|accept| accept.to_string());
+
+                StatusPage::new(authority, &definitions, &self.route_metrics)
+                    .to_response(accept.as_deref())
+            }
+            Err(err) => {
+                error!("Failed to render the status page of {}: {}", authority, err);
+                poem::Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from_string("Internal error".to_string()))
+            }
+        }
+    }
+
     async fn handle_http_auth_callback_binding(
         &self,
         security_scheme_with_metadata: &SecuritySchemeWithProviderMetadata,
@@ -265,6 +835,46 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
         Ok(result)
     }
 
+    // Requests share the bucket of their key, or of their client IP if the route has no key
+    async fn evaluate_rate_limit_key(
+        &self,
+        rate_limit: &HttpRateLimit,
+        request: &RichRequest,
+    ) -> GatewayHttpResult<String> {
+        match &rate_limit.key_compiled {
+            Some(key_compiled) => {
+                let mut request_value = serde_json::Map::new();
+                request_value.insert(
+                    "request".to_string(),
+                    request.as_json().map_err(GatewayHttpError::BadRequest)?,
+                );
+
+                let rib_input: RibInput =
+                    resolve_rib_input(&request_value, &key_compiled.rib_input)
+                        .await
+                        .map_err(GatewayHttpError::BadRequest)?;
+
+                let key = rib::interpret_pure(&key_compiled.compiled_key, &rib_input)
+                    .await
+                    .map_err(GatewayHttpError::RibInterpretPureError)?
+                    .get_literal()
+                    .ok_or(GatewayHttpError::BadRequest(
+                        "Rate limit key is not a Rib expression that resolves to String"
+                            .to_string(),
+                    ))?
+                    .as_string();
+
+                Ok(key)
+            }
+            None => Ok(request
+                .underlying
+                .remote_addr()
+                .as_socket_addr()
+                .map(|address| address.ip().to_string())
+                .unwrap_or_default()),
+        }
+    }
+
     async fn evaluate_idempotency_key_rib_script(
         &self,
         script: &IdempotencyKeyCompiled,
@@ -286,6 +896,58 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
         Ok(IdempotencyKey::new(value))
     }
 
+    // Evaluated for every request, as only the worker executor knows whether the worker exists
+    async fn evaluate_bootstrap_rib_scripts(
+        &self,
+        bootstrap: &WorkerBootstrapCompiled,
+        request_value: &serde_json::Map<String, Value>,
+    ) -> GatewayHttpResult<WorkerBootstrapValues> {
+        let mut args = Vec::with_capacity(bootstrap.args.len());
+        for arg in &bootstrap.args {
+            args.push(self.evaluate_bootstrap_value(arg, request_value).await?);
+        }
+
+        let mut env = HashMap::new();
+        for (name, value) in &bootstrap.env {
+            env.insert(
+                name.clone(),
+                self.evaluate_bootstrap_value(value, request_value).await?,
+            );
+        }
+
+        let mut config = HashMap::new();
+        for (name, value) in &bootstrap.config {
+            config.insert(
+                name.clone(),
+                self.evaluate_bootstrap_value(value, request_value).await?,
+            );
+        }
+
+        Ok(WorkerBootstrapValues { args, env, config })
+    }
+
+    async fn evaluate_bootstrap_value(
+        &self,
+        value: &BootstrapValueCompiled,
+        request_value: &serde_json::Map<String, Value>,
+    ) -> GatewayHttpResult<String> {
+        let rib_input: RibInput = resolve_rib_input(request_value, &value.rib_input)
+            .await
+            .map_err(GatewayHttpError::BadRequest)?;
+
+        let value = rib::interpret_pure(&value.compiled_expr, &rib_input)
+            .await
+            .map_err(GatewayHttpError::RibInterpretPureError)?
+            .get_literal()
+            .ok_or(GatewayHttpError::BadRequest(
+                "Worker bootstrap value is not a Rib expression that resolves to String"
+                    .to_string(),
+            ))?
+            .as_string();
+
+        Ok(value)
+    }
+
     async fn evaluate_invocation_context_rib_script(
         &self,
         script: &InvocationContextCompiled,
@@ -342,6 +1004,7 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
         idempotency_key_compiled: &Option<IdempotencyKeyCompiled>,
         component_id: &VersionedComponentId,
         invocation_context_compiled: &Option<InvocationContextCompiled>,
+        bootstrap_compiled: &Option<WorkerBootstrapCompiled>,
     ) -> GatewayHttpResult<WorkerDetail> {
         let worker_name = if let Some(worker_name_compiled) = worker_name_compiled {
             let result = self
@@ -412,11 +1075,20 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
             invocation_context_from_request(&request.underlying)
         };
 
+        let bootstrap = match bootstrap_compiled {
+            Some(bootstrap_compiled) => Some(
+                self.evaluate_bootstrap_rib_scripts(bootstrap_compiled, request_value)
+                    .await?,
+            ),
+            None => None,
+        };
+
         Ok(WorkerDetail {
             component_id: component_id.clone(),
             worker_name,
             idempotency_key,
             invocation_context,
+            bootstrap,
         })
     }
 
@@ -425,37 +1097,135 @@ impl<Namespace: Clone> DefaultGatewayInputExecutor<Namespace> {
         namespace: &Namespace,
         compiled_response_mapping: &ResponseMappingCompiled,
         request_value: &serde_json::Map<String, Value>,
+        request_binary_fields: &[BinaryField],
         worker_detail: &WorkerDetail,
     ) -> GatewayHttpResult<RibResult> {
-        let rib_input = resolve_rib_input(request_value, &compiled_response_mapping.rib_input)
-            .await
-            .map_err(GatewayHttpError::BadRequest)?;
+        let rib_input = resolve_rib_input_with_binary_fields(
+            request_value,
+            &compiled_response_mapping.rib_input,
+            request_binary_fields,
+        )
+        .await
+        .map_err(GatewayHttpError::BadRequest)?;
 
-        self.evaluator
+        let start = Instant::now();
+        let result = self
+            .evaluator
             .evaluate(
                 worker_detail.worker_name.as_deref(),
                 &worker_detail.component_id.component_id,
                 &worker_detail.idempotency_key,
                 worker_detail.invocation_context.clone(),
+                worker_detail.bootstrap.clone(),
                 &compiled_response_mapping.response_mapping_compiled,
                 &rib_input,
                 namespace.clone(),
             )
             .await
-            .map_err(GatewayHttpError::EvaluationError)
+            .map_err(GatewayHttpError::EvaluationError);
+        gateway_metrics::record_rib_evaluation(start.elapsed());
+
+        result
+    }
+
+    // Looks up the labels of the worker only if the response mapping uses `worker.labels`, and
+    // gives every label it uses a value, the empty string for the ones the worker does not have
+    async fn add_worker_labels(
+        &self,
+        namespace: &Namespace,
+        worker_value: &mut Value,
+        worker_detail: &WorkerDetail,
+        rib_input_types: &RibInputTypeInfo,
+    ) -> GatewayHttpResult<()> {
+        let label_fields = match rib_input_types.types.get("worker") {
+            Some(AnalysedType::Record(worker_type)) => worker_type
+                .fields
+                .iter()
+                .find(|field| field.name == "labels")
+                .and_then(|field| match &field.typ {
+                    AnalysedType::Record(labels_type) => Some(&labels_type.fields),
+                    _ => None,
+                }),
+            _ => None,
+        };
+
+        let (Some(label_fields), Value::Object(worker_fields)) = (label_fields, worker_value)
+        else {
+            return Ok(());
+        };
+
+        let labels = match &worker_detail.worker_name {
+            Some(worker_name) => self
+                .evaluator
+                .worker_labels(
+                    worker_name,
+                    &worker_detail.component_id.component_id,
+                    namespace.clone(),
+                )
+                .await
+                .map_err(GatewayHttpError::EvaluationError)?,
+            None => HashMap::new(),
+        };
+
+        let labels_value = label_fields
+            .iter()
+            .map(|field| {
+                (
+                    field.name.clone(),
+                    Value::String(labels.get(&field.name).cloned().unwrap_or_default()),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        worker_fields.insert("labels".to_string(), Value::Object(labels_value));
+        Ok(())
+    }
+
+    // Responds with the items of the page returned by a paginated binding, linking to the next page
+    async fn paginated_response(
+        &self,
+        result: GatewayHttpResult<RibResult>,
+        request: &RichRequest,
+    ) -> poem::Response {
+        let page = result.and_then(|result| {
+            Page::from_rib_result(result)
+                .map_err(|err| GatewayHttpError::EvaluationError(EvaluationError(err)))
+        });
+
+        let page = match page {
+            Ok(page) => page,
+            Err(err) => return err.to_response(request, &self.gateway_session_store).await,
+        };
+
+        let link = page.next_page_link(request.underlying.uri());
+
+        let mut response = RibResult::Val(page.items)
+            .to_response(request, &self.gateway_session_store)
+            .await;
+
+        if let Some(link) = link.and_then(|link| http::HeaderValue::from_str(&link).ok()) {
+            response.headers_mut().insert(http::header::LINK, link);
+        }
+
+        response
     }
 
     async fn maybe_apply_middlewares_in(
         &self,
+        namespace: &Namespace,
         mut request: RichRequest,
         middlewares: &Option<HttpMiddlewares>,
     ) -> Result<RichRequest, poem::Response> {
         if let Some(middlewares) = middlewares {
             let input_middleware_result = middlewares
                 .process_middleware_in(
-                    &request,
+                    namespace,
+                    &mut request,
                     &self.gateway_session_store,
                     &self.identity_provider,
+                    &self.jwt_validator,
+                    &self.api_key_validator,
+                    &self.hmac_signature_validator,
                 )
                 .await;
 
@@ -515,6 +1285,45 @@ impl<Namespace: Send + Sync + Clone + 'static> GatewayHttpInputExecutor
             }
         };
 
+        // The deployment may require a client certificate, so the request fails if its configuration
+        // cannot be loaded
+        let config = match self
+            .api_definition_lookup_service
+            .get_config(&ApiSiteString(authority.clone()))
+            .await
+        {
+            Ok(config) => config,
+            Err(err) => {
+                error!("API request host: {} - error: {}", authority, err);
+                return poem::Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from_string("Internal error".to_string()));
+            }
+        };
+
+        let error_pages = ErrorPageContext {
+            error_pages: config.error_pages.clone(),
+            accept: request
+                .header(http::header::ACCEPT)
+                .map(|accept| accept.to_string()),
+            path: request.uri().path().to_string(),
+            request_id: request_id(&request).unwrap_or_default().to_string(),
+        };
+
+        if error_pages
+            .error_pages
+            .as_ref()
+            .is_some_and(|error_pages| error_pages.maintenance)
+        {
+            return error_pages.maintenance_response();
+        }
+
+        let client_certificate =
+            match Self::verify_client_certificate(&authority, &config, &request) {
+                Ok(client_certificate) => client_certificate,
+                Err(response) => return error_pages.apply(response),
+            };
+
         let possible_api_definitions = self
             .api_definition_lookup_service
             .get(&ApiSiteString(authority.clone()))
@@ -527,41 +1336,351 @@ impl<Namespace: Send + Sync + Clone + 'static> GatewayHttpInputExecutor
                     "API request host: {} - error: {}",
                     authority, api_defs_lookup_error
                 );
-                return poem::Response::builder()
-                    .status(StatusCode::INTERNAL_SERVER_ERROR)
-                    .body(Body::from_string("Internal error".to_string()));
+                return error_pages.apply(
+                    poem::Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from_string("Internal error".to_string())),
+                );
             }
         };
 
+        let possible_api_definitions =
+            Self::apply_traffic_split(&config, &request, possible_api_definitions);
+
+        if request.method() == http::Method::GET && request.uri().path() == OPEN_API_WELL_KNOWN_PATH
+        {
+            let document = to_open_api_document(&authority, &possible_api_definitions);
+            return poem::Response::builder()
+                .status(StatusCode::OK)
+                .content_type("application/json")
+                .body(Body::from_string(document.to_string()));
+        }
+
         let resolved_route_entry = if let Some(resolved_route_entry) =
-            resolve_gateway_binding(possible_api_definitions, &request).await
+            resolve_gateway_binding(possible_api_definitions, &request)
+                .instrument(info_span!("route_matching", %authority))
+                .await
         {
             resolved_route_entry
+        } else if let Some(cors) = config
+            .cors
+            .as_ref()
+            .filter(|_| request.method() == http::Method::OPTIONS)
+        {
+            // Preflight requests of routes without their own preflight binding
+            return cors_preflight_response(cors, request.header(http::header::ORIGIN));
         } else {
-            return poem::Response::builder()
-                .status(StatusCode::NOT_FOUND)
-                .body(Body::from_string("Route not found".to_string()));
+            return error_pages
+                .response(ErrorPageKind::NotFound, StatusCode::NOT_FOUND)
+                .unwrap_or_else(|| {
+                    poem::Response::builder()
+                        .status(StatusCode::NOT_FOUND)
+                        .body(Body::from_string("Route not found".to_string()))
+                });
         };
 
         let SplitResolvedRouteEntryResult {
             namespace,
             binding,
             middlewares,
-            rich_request,
+            route,
+            mut rich_request,
         } = split_resolved_route_entry(request, resolved_route_entry);
 
+        // The CORS headers of the responses depend on the origin of the request
+        let origin = rich_request
+            .underlying
+            .header(http::header::ORIGIN)
+            .map(|origin| origin.to_string());
+
+        rich_request.client_certificate =
+            client_certificate.map(|certificate| certificate.to_json());
+
+        // Requests of the status page itself are left out of the route metrics it shows
+        let is_status_page = matches!(
+            &binding,
+            GatewayBindingCompiled::Static(StaticBinding::StatusPage)
+        );
+
+        // Captured as received, as replays go through the middlewares again
+        let dead_letter_request = match &binding {
+            GatewayBindingCompiled::Worker(_) => {
+                Self::capture_dead_letter_request(&config, &mut rich_request.underlying).await
+            }
+            _ => None,
+        };
+
+        let request_fixture = match &binding {
+            GatewayBindingCompiled::Worker(_) => {
+                Self::capture_request_fixture(&config, &mut rich_request.underlying).await
+            }
+            _ => None,
+        };
+
+        // Assigned before the middlewares, so that their conditions can depend on the variants
+        rich_request.experiment_assignments =
+            self.assign_experiments(&authority, &config, &rich_request);
+
+        // The CORS configuration of the route, if any, overrides the one of the deployment
+        let middlewares = match config.cors.clone() {
+            Some(cors)
+                if middlewares
+                    .as_ref()
+                    .and_then(|middlewares| middlewares.get_cors_middleware())
+                    .is_none() =>
+            {
+                let mut middlewares = middlewares.unwrap_or_default();
+                middlewares.add_cors(cors);
+                Some(middlewares)
+            }
+            _ => middlewares,
+        };
+
+        // Middlewares whose condition does not hold for the request are skipped for it
+        let middlewares = match middlewares
+            .as_ref()
+            .and_then(|middlewares| middlewares.get_conditions_middleware())
+        {
+            Some(conditions) => match skipped_middlewares(&conditions, &rich_request).await {
+                Ok(skipped) => middlewares.map(|middlewares| middlewares.without(&skipped)),
+                Err(err) => {
+                    let response = err
+                        .to_response(&rich_request, &self.gateway_session_store)
+                        .await;
+                    return error_pages.apply(
+                        maybe_apply_middlewares_out(response, &middlewares, origin.as_deref())
+                            .await,
+                    );
+                }
+            },
+            None => middlewares,
+        };
+
+        // The compression of the route, if any, overrides the one of the deployment.
+        // Responses to HEAD requests have no body to compress.
+        let compression = match middlewares
+            .as_ref()
+            .and_then(|middlewares| middlewares.get_compression_middleware())
+        {
+            Some(compression) => Some(compression),
+            None => config.compression.clone(),
+        }
+        .filter(|compression| {
+            compression.enabled && rich_request.underlying.method() != http::Method::HEAD
+        });
+
+        let accept_encoding = rich_request
+            .underlying
+            .header(http::header::ACCEPT_ENCODING)
+            .map(|accept_encoding| accept_encoding.to_string());
+
         let mut rich_request = match self
-            .maybe_apply_middlewares_in(rich_request, &middlewares)
+            .maybe_apply_middlewares_in(&namespace, rich_request, &middlewares)
             .await
         {
             Ok(req) => req,
             Err(resp) => {
                 tracing::debug!("Middleware short-circuited the request handling");
-                return resp;
+                return error_pages.apply(resp);
             }
         };
 
-        match binding {
+        // Request transforms run before the rate limit and the response cache, so that their keys
+        // see the transformed request
+        let transforms = middlewares
+            .as_ref()
+            .and_then(|middlewares| middlewares.get_transforms_middleware());
+
+        if let Some(transforms) = &transforms {
+            if let Err(err) = apply_request_transforms(transforms, &mut rich_request).await {
+                let response = err
+                    .to_response(&rich_request, &self.gateway_session_store)
+                    .await;
+                return error_pages.apply(
+                    maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await,
+                );
+            }
+        }
+
+        if let Some(rate_limit) = middlewares
+            .as_ref()
+            .and_then(|middlewares| middlewares.get_rate_limit_middleware())
+        {
+            let key = match self
+                .evaluate_rate_limit_key(&rate_limit, &rich_request)
+                .await
+            {
+                Ok(key) => key,
+                Err(err) => {
+                    let response = err
+                        .to_response(&rich_request, &self.gateway_session_store)
+                        .await;
+                    return error_pages.apply(
+                        maybe_apply_middlewares_out(response, &middlewares, origin.as_deref())
+                            .await,
+                    );
+                }
+            };
+
+            match self
+                .rate_limiter
+                .try_acquire(&format!("{}:{}:{}", authority, route, key), &rate_limit)
+                .await
+            {
+                Ok(RateLimitDecision::Allowed) => {}
+                Ok(RateLimitDecision::Limited { retry_after }) => {
+                    tracing::debug!(route = route, "Request rejected by the rate limit");
+                    let response = rate_limited_response(retry_after);
+                    return error_pages.apply(
+                        maybe_apply_middlewares_out(response, &middlewares, origin.as_deref())
+                            .await,
+                    );
+                }
+                // Limiters failing open let the requests through when the buckets cannot be
+                // reached, instead of failing
+                Err(err) => {
+                    error!(
+                        "Failed to apply the rate limit of route {}: {}",
+                        route,
+                        err.to_safe_string()
+                    );
+                    let response = poem::Response::builder()
+                        .status(StatusCode::SERVICE_UNAVAILABLE)
+                        .body(Body::from_string("Rate limit unavailable".to_string()));
+                    return error_pages.apply(
+                        maybe_apply_middlewares_out(response, &middlewares, origin.as_deref())
+                            .await,
+                    );
+                }
+            }
+        }
+
+        // Cached responses are served once the request is within the rate limit, without counting
+        // against the concurrency limit of the route. They are shared by all the clients, so the
+        // responses of secured routes (rejected when the definition is created) are never cached
+        let response_cache = middlewares
+            .as_ref()
+            .filter(|middlewares| middlewares.get_security_scheme_reference().is_none())
+            .and_then(|middlewares| middlewares.get_response_cache_middleware())
+            .filter(|_| rich_request.underlying.method() == http::Method::GET)
+            .map(|response_cache| {
+                let key = response_cache_key(&response_cache, &rich_request.underlying);
+                (response_cache, key)
+            });
+
+        if let Some((_, key)) = &response_cache {
+            match self.response_cache.get(&authority, &route, key).await {
+                Ok(Some(cached)) => {
+                    tracing::debug!(route = route, "Response served from the cache");
+                    let response = error_pages.apply(
+                        maybe_apply_middlewares_out(
+                            cached.to_response(),
+                            &middlewares,
+                            origin.as_deref(),
+                        )
+                        .await,
+                    );
+                    return maybe_compress_response(
+                        response,
+                        &compression,
+                        accept_encoding.as_deref(),
+                    )
+                    .await;
+                }
+                Ok(None) => {}
+                // The route is served without the cache if the cache cannot be reached
+                Err(err) => error!(
+                    "Failed to get the cached response of route {}: {}",
+                    route,
+                    err.to_safe_string()
+                ),
+            }
+        }
+
+        // Held until the response is ready, counting the request against the limit of its route
+        let _route_permit = match middlewares
+            .as_ref()
+            .and_then(|middlewares| middlewares.get_concurrency_limit_middleware())
+        {
+            Some(concurrency_limit) => {
+                match self
+                    .route_concurrency_limiter
+                    .acquire(&authority, &route, &concurrency_limit)
+                    .await
+                {
+                    Some(permit) => Some(permit),
+                    None => {
+                        tracing::debug!(route = route, "Request shed by the concurrency limit");
+                        let response = shed_response(&concurrency_limit);
+                        return error_pages.apply(
+                            maybe_apply_middlewares_out(response, &middlewares, origin.as_deref())
+                                .await,
+                        );
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // Held while the workers are invoked, counting the request against the capacity of its
+        // site, in the priority lane of its route or API key if it has one
+        let _capacity_permit = match &binding {
+            GatewayBindingCompiled::Worker(_) => match &config.capacity {
+                Some(capacity) => {
+                    let lane = capacity
+                        .lane(&route, rich_request.api_key_name())
+                        .map(|lane| lane.name.clone());
+
+                    match self
+                        .deployment_capacity_limiter
+                        .acquire(&authority, capacity, lane.as_deref())
+                        .await
+                    {
+                        Some(permit) => Some(permit),
+                        None => {
+                            tracing::debug!(
+                                route = route,
+                                "Request shed by the capacity of the deployment"
+                            );
+                            let response = capacity_exceeded_response();
+                            return error_pages.apply(
+                                maybe_apply_middlewares_out(
+                                    response,
+                                    &middlewares,
+                                    origin.as_deref(),
+                                )
+                                .await,
+                            );
+                        }
+                    }
+                }
+                None => None,
+            },
+            _ => None,
+        };
+
+        let body_limits = middlewares
+            .as_ref()
+            .and_then(|middlewares| middlewares.get_body_limits_middleware());
+
+        if let Some(body_limits) = body_limits
+            .as_ref()
+            .filter(|limits| limits.limits_request())
+        {
+            if let Err(err) = read_limited_request_body(&mut rich_request, body_limits).await {
+                tracing::debug!(route = route, "Request body rejected by the body limits");
+                return error_pages.apply(
+                    maybe_apply_middlewares_out(
+                        err.into_response(),
+                        &middlewares,
+                        origin.as_deref(),
+                    )
+                    .await,
+                );
+            }
+        }
+
+        let response = match binding {
             GatewayBindingCompiled::Static(StaticBinding::HttpCorsPreflight(cors_preflight)) => {
                 cors_preflight
                     .clone()
@@ -569,6 +1688,14 @@ impl<Namespace: Send + Sync + Clone + 'static> GatewayHttpInputExecutor
                     .await
             }
 
+            GatewayBindingCompiled::Static(StaticBinding::StatusPage) => {
+                let response = self
+                    .handle_status_page_binding(&authority, &rich_request)
+                    .await;
+
+                maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await
+            }
+
             GatewayBindingCompiled::Static(StaticBinding::HttpAuthCallBack(auth_call_back)) => {
                 let result = self
                     .handle_http_auth_callback_binding(
@@ -583,15 +1710,85 @@ impl<Namespace: Send + Sync + Clone + 'static> GatewayHttpInputExecutor
             }
 
             GatewayBindingCompiled::Worker(resolved_worker_binding) => {
-                let result = self
-                    .handle_worker_binding(&namespace, &mut rich_request, &resolved_worker_binding)
-                    .await;
+                let result = gateway_metrics::in_route(
+                    &authority,
+                    &route,
+                    self.handle_worker_binding(
+                        &namespace,
+                        &mut rich_request,
+                        &resolved_worker_binding,
+                        &route,
+                    ),
+                )
+                .await;
 
-                let response = result
-                    .to_response(&rich_request, &self.gateway_session_store)
+                if let (Err(GatewayHttpError::EvaluationError(err)), Some(dead_letter_request)) =
+                    (&result, dead_letter_request)
+                {
+                    self.record_dead_letter(
+                        &namespace,
+                        &authority,
+                        &route,
+                        dead_letter_request,
+                        err,
+                    )
                     .await;
+                }
 
-                maybe_apply_middlewares_out(response, &middlewares).await
+                // The error cases mapped by the binding are responded to as they are declared,
+                // in place of the response mapping
+                let mapped_error = match (&resolved_worker_binding.error_mapping, &result) {
+                    (Some(error_mapping), Ok(result)) => {
+                        mapped_error_response(result, error_mapping)
+                    }
+                    _ => None,
+                };
+
+                let response = match mapped_error {
+                    Some(response) => response,
+                    None => {
+                        let result = match &rich_request.field_selection {
+                            Some(field_selection) => result.and_then(|result| {
+                                select_response_fields(
+                                    result,
+                                    field_selection,
+                                    resolved_worker_binding.pagination.is_some(),
+                                )
+                                .map_err(|err| {
+                                    GatewayHttpError::EvaluationError(EvaluationError(err))
+                                })
+                            }),
+                            None => result,
+                        };
+
+                        match &resolved_worker_binding.pagination {
+                            Some(_) => self.paginated_response(result, &rich_request).await,
+                            None => {
+                                result
+                                    .to_response(&rich_request, &self.gateway_session_store)
+                                    .await
+                            }
+                        }
+                    }
+                };
+
+                let response =
+                    maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await;
+                let response = with_recorded_size(&authority, &route, response).await;
+
+                match request_fixture {
+                    Some(request_fixture) => {
+                        self.record_request_fixture(
+                            &namespace,
+                            &authority,
+                            &route,
+                            request_fixture,
+                            response,
+                        )
+                        .await
+                    }
+                    None => response,
+                }
             }
 
             GatewayBindingCompiled::HttpHandler(http_handler_binding) => {
@@ -607,7 +1804,7 @@ impl<Namespace: Send + Sync + Clone + 'static> GatewayHttpInputExecutor
                     .to_response(&rich_request, &self.gateway_session_store)
                     .await;
 
-                maybe_apply_middlewares_out(response, &middlewares).await
+                maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await
             }
 
             GatewayBindingCompiled::FileServer(resolved_file_server_binding) => {
@@ -623,15 +1820,211 @@ impl<Namespace: Send + Sync + Clone + 'static> GatewayHttpInputExecutor
                     .to_response(&rich_request, &self.gateway_session_store)
                     .await;
 
-                maybe_apply_middlewares_out(response, &middlewares).await
+                maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await
+            }
+
+            GatewayBindingCompiled::WebSocket(web_socket_binding) => {
+                match self
+                    .handle_web_socket_binding(&namespace, &rich_request, &web_socket_binding)
+                    .await
+                {
+                    Ok(response) => response,
+                    Err(err) => {
+                        err.to_response(&rich_request, &self.gateway_session_store)
+                            .await
+                    }
+                }
+            }
+
+            GatewayBindingCompiled::ServerSentEvents(server_sent_events_binding) => {
+                match self
+                    .handle_server_sent_events_binding(
+                        &namespace,
+                        &rich_request,
+                        &server_sent_events_binding,
+                    )
+                    .await
+                {
+                    Ok(response) => {
+                        maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await
+                    }
+                    Err(err) => {
+                        err.to_response(&rich_request, &self.gateway_session_store)
+                            .await
+                    }
+                }
+            }
+
+            GatewayBindingCompiled::Grpc(grpc_binding) => {
+                let result = self
+                    .grpc_binding_handler
+                    .handle_grpc_binding(&grpc_binding, &mut rich_request)
+                    .await;
+
+                let response = result
+                    .to_response(&rich_request, &self.gateway_session_store)
+                    .await;
+
+                maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await
             }
+
+            GatewayBindingCompiled::HttpProxy(http_proxy_binding) => {
+                let result = self
+                    .http_proxy_binding_handler
+                    .handle_http_proxy_binding(&http_proxy_binding, &mut rich_request)
+                    .await;
+
+                let response = result
+                    .to_response(&rich_request, &self.gateway_session_store)
+                    .await;
+
+                maybe_apply_middlewares_out(response, &middlewares, origin.as_deref()).await
+            }
+        };
+
+        let response = match &transforms {
+            Some(transforms) => {
+                match apply_response_transforms(transforms, &rich_request, response).await {
+                    Ok(response) => response,
+                    Err(err) => {
+                        err.to_response(&rich_request, &self.gateway_session_store)
+                            .await
+                    }
+                }
+            }
+            None => response,
+        };
+
+        let response = match &response_cache {
+            Some((response_cache, key)) => {
+                let (mut response, cached) = CachedResponse::from_response(response).await;
+
+                if let Some(cached) = cached {
+                    if let Err(err) = self
+                        .response_cache
+                        .insert(&authority, &route, key, &cached, response_cache.ttl())
+                        .await
+                    {
+                        error!(
+                            "Failed to cache the response of route {}: {}",
+                            route,
+                            err.to_safe_string()
+                        );
+                    }
+
+                    response
+                        .headers_mut()
+                        .insert(CACHE_STATUS_HEADER, http::HeaderValue::from_static("miss"));
+                }
+
+                response
+            }
+            None => response,
+        };
+
+        let response = match &body_limits {
+            Some(body_limits) => limit_response_body(response, body_limits),
+            None => response,
+        };
+
+        let response = rich_request
+            .experiment_assignments
+            .apply_to_response(error_pages.apply(response));
+
+        // Cached responses are kept uncompressed, and compressed for each client as they are served
+        let response =
+            maybe_compress_response(response, &compression, accept_encoding.as_deref()).await;
+
+        if !is_status_page {
+            self.route_metrics
+                .record(&authority, &route, response.status());
         }
+
+        response
     }
 }
 
-async fn resolve_rib_input(
+// The error pages of the deployment a request is for, along with what is needed to render them
+struct ErrorPageContext {
+    error_pages: Option<ApiDeploymentErrorPages>,
+    accept: Option<String>,
+    path: String,
+    request_id: String,
+}
+
+impl ErrorPageContext {
+    fn response(&self, kind: ErrorPageKind, status: StatusCode) -> Option<poem::Response> {
+        let page = self
+            .error_pages
+            .as_ref()?
+            .negotiate(kind, self.accept.as_deref())?;
+
+        Some(
+            poem::Response::builder()
+                .status(status)
+                .content_type(page.content_type.clone())
+                .body(Body::from_string(page.render(
+                    status,
+                    &self.path,
+                    &self.request_id,
+                ))),
+        )
+    }
+
+    // Replaces 429 and 5xx responses with the corresponding error page, if there is one
+    fn apply(&self, response: poem::Response) -> poem::Response {
+        let status = response.status();
+
+        ErrorPageKind::from_response_status(status)
+            .and_then(|kind| self.response(kind, status))
+            .map(|mut error_page| {
+                if let Some(retry_after) = response.headers().get(http::header::RETRY_AFTER) {
+                    error_page
+                        .headers_mut()
+                        .insert(http::header::RETRY_AFTER, retry_after.clone());
+                }
+                error_page
+            })
+            .unwrap_or(response)
+    }
+
+    fn maintenance_response(&self) -> poem::Response {
+        let status = StatusCode::SERVICE_UNAVAILABLE;
+
+        let mut response = self
+            .response(ErrorPageKind::Maintenance, status)
+            .unwrap_or_else(|| {
+                poem::Response::builder()
+                    .status(status)
+                    .body(Body::from_string("Service under maintenance".to_string()))
+            });
+
+        if let Some(retry_after) = self
+            .error_pages
+            .as_ref()
+            .and_then(|error_pages| error_pages.maintenance_retry_after)
+        {
+            response
+                .headers_mut()
+                .insert(http::header::RETRY_AFTER, retry_after.into());
+        }
+
+        response
+    }
+}
+
+pub(crate) async fn resolve_rib_input(
     input: &serde_json::Map<String, Value>,
     required_types: &RibInputTypeInfo,
+) -> Result<RibInput, String> {
+    resolve_rib_input_with_binary_fields(input, required_types, &[]).await
+}
+
+// Takes the binary fields of the request from their raw bytes
+pub(crate) async fn resolve_rib_input_with_binary_fields(
+    input: &serde_json::Map<String, Value>,
+    required_types: &RibInputTypeInfo,
+    request_binary_fields: &[BinaryField],
 ) -> Result<RibInput, String> {
     let mut result_map: HashMap<String, ValueAndType> = HashMap::new();
 
@@ -640,6 +2033,15 @@ async fn resolve_rib_input(
             .get(key)
             .ok_or(format!("Required input not available: {key}"))?;
 
+        if key == "request" && !request_binary_fields.is_empty() {
+            let parsed_value =
+                parse_with_binary_fields(input_value, analysed_type, request_binary_fields)
+                    .map_err(|err| format!("Input {key} doesn't match the requirements for rib expression to execute: {}. Requirements. {:?}", err, analysed_type))?;
+
+            result_map.insert(key.clone(), parsed_value);
+            continue;
+        }
+
         let parsed_value = TypeAnnotatedValue::parse_with_type(
             input_value,
             analysed_type,
@@ -655,12 +2057,37 @@ async fn resolve_rib_input(
     Ok(RibInput { input: result_map })
 }
 
+fn rib_result_to_json(result: &RibResult) -> Result<Value, String> {
+    match result {
+        RibResult::Unit => Ok(Value::Null),
+        RibResult::Val(value_and_type) => TypeAnnotatedValue::try_from(value_and_type.clone())
+            .map(|value| value.to_json_value())
+            .map_err(|errs| errs.join(", ")),
+    }
+}
+
+// Records the size of the body of a response built from the result of a response mapping,
+// which is in memory already
+async fn with_recorded_size(
+    site: &str,
+    route: &str,
+    mut response: poem::Response,
+) -> poem::Response {
+    let body = response.take_body().into_bytes().await.unwrap_or_default();
+    gateway_metrics::record_response_size(site, route, body.len());
+    response.set_body(body);
+    response
+}
+
 async fn maybe_apply_middlewares_out(
     mut response: poem::Response,
     middlewares: &Option<HttpMiddlewares>,
+    origin: Option<&str>,
 ) -> poem::Response {
     if let Some(middlewares) = middlewares {
-        let result = middlewares.process_middleware_out(&mut response).await;
+        let result = middlewares
+            .process_middleware_out(&mut response, origin)
+            .await;
         match result {
             Ok(_) => response,
             Err(err) => err.to_response_from_safe_display(|_| StatusCode::INTERNAL_SERVER_ERROR),
@@ -670,6 +2097,17 @@ async fn maybe_apply_middlewares_out(
     }
 }
 
+async fn maybe_compress_response(
+    response: poem::Response,
+    compression: &Option<HttpCompression>,
+    accept_encoding: Option<&str>,
+) -> poem::Response {
+    match compression {
+        Some(compression) => compress_response(response, compression, accept_encoding).await,
+        None => response,
+    }
+}
+
 fn to_attribute_value(value: &ValueAndType) -> GatewayHttpResult<AttributeValue> {
     match &value.value {
         golem_wasm_rpc::Value::String(value) => Ok(AttributeValue::String(value.clone())),
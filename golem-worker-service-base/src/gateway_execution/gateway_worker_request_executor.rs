@@ -15,7 +15,9 @@
 use crate::gateway_execution::GatewayResolvedWorkerRequest;
 use async_trait::async_trait;
 
+use golem_common::model::ComponentId;
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use std::collections::HashMap;
 use std::fmt::Display;
 
 #[async_trait]
@@ -24,6 +26,14 @@ pub trait GatewayWorkerRequestExecutor<Namespace> {
         &self,
         resolved_worker_request: GatewayResolvedWorkerRequest<Namespace>,
     ) -> Result<WorkerResponse, WorkerRequestExecutorError>;
+
+    // The labels of a worker, which are empty if the worker does not exist yet
+    async fn get_worker_labels(
+        &self,
+        component_id: &ComponentId,
+        worker_name: &str,
+        namespace: Namespace,
+    ) -> Result<HashMap<String, String>, WorkerRequestExecutorError>;
 }
 
 // The result of a worker execution from worker-bridge,
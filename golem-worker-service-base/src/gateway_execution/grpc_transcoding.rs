@@ -0,0 +1,1058 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transcoding of HTTP+JSON requests to unary gRPC calls for gRPC bindings.
+//!
+//! Messages are encoded and decoded dynamically, using the `FileDescriptorSet` given when the
+//! API definition was registered. The JSON representation follows the proto3 JSON mapping:
+//! fields are named by their JSON name (the original field name is accepted as well), 64-bit
+//! integers are rendered as strings, enums by their value name and bytes as base64. Well-known
+//! types are not special-cased and are represented like any other message.
+
+use crate::gateway_binding::GrpcBinding;
+use crate::gateway_execution::request::RichRequest;
+use async_trait::async_trait;
+use base64::Engine;
+use bytes::{Buf, BufMut};
+use http::uri::PathAndQuery;
+use http::StatusCode;
+use prost::encoding::{decode_varint, encode_varint};
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorSet};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::metadata::{MetadataKey, MetadataValue};
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Status};
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_FIXED64: u64 = 1;
+const WIRE_LENGTH_DELIMITED: u64 = 2;
+const WIRE_FIXED32: u64 = 5;
+
+// Request headers which are not forwarded as gRPC metadata
+const SKIPPED_HEADERS: [&str; 9] = [
+    "host",
+    "connection",
+    "content-length",
+    "content-type",
+    "te",
+    "transfer-encoding",
+    "upgrade",
+    "accept-encoding",
+    "keep-alive",
+];
+
+#[async_trait]
+pub trait GrpcBindingHandler {
+    async fn handle_grpc_binding(
+        &self,
+        binding: &GrpcBinding,
+        request: &mut RichRequest,
+    ) -> GrpcBindingResult;
+}
+
+pub type GrpcBindingResult = Result<GrpcBindingSuccess, GrpcBindingError>;
+
+pub struct GrpcBindingSuccess {
+    pub body: Value,
+}
+
+#[derive(Debug)]
+pub enum GrpcBindingError {
+    InvalidRequest(String),
+    InvalidResponse(String),
+    Status(Status),
+}
+
+/// Calls the backend services through lazily connected channels, shared by all bindings
+/// targeting the same endpoint
+#[derive(Default)]
+pub struct DefaultGrpcBindingHandler {
+    channels: Mutex<HashMap<String, Channel>>,
+}
+
+impl DefaultGrpcBindingHandler {
+    fn channel(&self, endpoint: &str) -> Result<Channel, GrpcBindingError> {
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(channel) = channels.get(endpoint) {
+            return Ok(channel.clone());
+        }
+
+        let channel = Endpoint::from_shared(endpoint.to_string())
+            .map_err(|err| {
+                GrpcBindingError::InvalidRequest(format!("Invalid gRPC endpoint: {err}"))
+            })?
+            .connect_lazy();
+        channels.insert(endpoint.to_string(), channel.clone());
+        Ok(channel)
+    }
+}
+
+#[async_trait]
+impl GrpcBindingHandler for DefaultGrpcBindingHandler {
+    async fn handle_grpc_binding(
+        &self,
+        binding: &GrpcBinding,
+        request: &mut RichRequest,
+    ) -> GrpcBindingResult {
+        let descriptor = binding
+            .method_descriptor()
+            .map_err(GrpcBindingError::InvalidRequest)?;
+
+        let mut metadata = tonic::metadata::MetadataMap::new();
+        for (name, value) in request.underlying.headers() {
+            if SKIPPED_HEADERS.contains(&name.as_str()) {
+                continue;
+            }
+            if let (Ok(key), Some(value)) = (
+                MetadataKey::from_bytes(name.as_str().as_bytes()),
+                value
+                    .to_str()
+                    .ok()
+                    .and_then(|value| MetadataValue::try_from(value).ok()),
+            ) {
+                metadata.append(key, value);
+            }
+        }
+
+        let request_json = request
+            .as_json_with_body()
+            .await
+            .map_err(GrpcBindingError::InvalidRequest)?;
+
+        let parameters = match request_json.get("path") {
+            Some(Value::Object(parameters)) => parameters.clone(),
+            _ => Map::new(),
+        };
+        let body = request_json.get("body").cloned().unwrap_or(Value::Null);
+
+        let message = descriptor
+            .encode_request(&parameters, &body)
+            .map_err(GrpcBindingError::InvalidRequest)?;
+
+        let mut grpc_request = tonic::Request::new(message);
+        *grpc_request.metadata_mut() = metadata;
+
+        let path = PathAndQuery::from_str(&descriptor.path)
+            .map_err(|err| GrpcBindingError::InvalidRequest(err.to_string()))?;
+
+        let mut client = tonic::client::Grpc::new(self.channel(&binding.endpoint)?);
+        client
+            .ready()
+            .await
+            .map_err(|err| GrpcBindingError::Status(Status::unavailable(err.to_string())))?;
+
+        let response = client
+            .unary(grpc_request, path, RawCodec)
+            .await
+            .map_err(GrpcBindingError::Status)?;
+
+        let body = descriptor
+            .decode_response(response.get_ref())
+            .map_err(GrpcBindingError::InvalidResponse)?;
+
+        Ok(GrpcBindingSuccess { body })
+    }
+}
+
+/// HTTP status corresponding to a gRPC status code, as defined for HTTP/JSON transcoding
+pub fn http_status(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        Code::Cancelled => StatusCode::from_u16(499).unwrap(),
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::Unknown | Code::Internal | Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+// Passes already encoded messages through to the gRPC framing
+#[derive(Clone, Copy)]
+struct RawCodec;
+
+impl Codec for RawCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = RawCodec;
+    type Decoder = RawCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        RawCodec
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        RawCodec
+    }
+}
+
+impl Encoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining()).to_vec()))
+    }
+}
+
+/// A unary gRPC method, together with the message and enum types needed to transcode its
+/// request and response messages
+#[derive(Debug, Clone)]
+pub struct GrpcMethodDescriptor {
+    /// Request path of the method, such as `/shop.v1.CartService/GetCart`
+    pub path: String,
+    input_type: String,
+    output_type: String,
+    messages: HashMap<String, DescriptorProto>,
+    enums: HashMap<String, EnumDescriptorProto>,
+}
+
+impl GrpcMethodDescriptor {
+    pub fn from_descriptor_set(
+        descriptor: &[u8],
+        service: &str,
+        method: &str,
+    ) -> Result<Self, String> {
+        let descriptor_set = FileDescriptorSet::decode(descriptor)
+            .map_err(|err| format!("Invalid gRPC descriptor: {err}"))?;
+
+        let mut messages = HashMap::new();
+        let mut enums = HashMap::new();
+        let mut method_types = None;
+
+        for file in &descriptor_set.file {
+            let scope = qualified_name("", file.package());
+
+            for message in &file.message_type {
+                register_message(&scope, message, &mut messages, &mut enums);
+            }
+            for enum_type in &file.enum_type {
+                enums.insert(qualified_name(&scope, enum_type.name()), enum_type.clone());
+            }
+
+            for service_descriptor in &file.service {
+                if qualified_name(&scope, service_descriptor.name()) != format!(".{service}") {
+                    continue;
+                }
+                for method_descriptor in &service_descriptor.method {
+                    if method_descriptor.name() == method {
+                        if method_descriptor.client_streaming()
+                            || method_descriptor.server_streaming()
+                        {
+                            return Err(format!(
+                                "gRPC method {service}/{method} is streaming, only unary methods can be bound"
+                            ));
+                        }
+                        method_types = Some((
+                            method_descriptor.input_type().to_string(),
+                            method_descriptor.output_type().to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let (input_type, output_type) = method_types
+            .ok_or_else(|| format!("gRPC method {service}/{method} not found in descriptor"))?;
+
+        for type_name in [&input_type, &output_type] {
+            if !messages.contains_key(type_name) {
+                return Err(format!(
+                    "Message type {} not found in descriptor",
+                    type_name.trim_start_matches('.')
+                ));
+            }
+        }
+
+        Ok(GrpcMethodDescriptor {
+            path: format!("/{service}/{method}"),
+            input_type,
+            output_type,
+            messages,
+            enums,
+        })
+    }
+
+    /// Encodes the request message from the JSON body, with the path and query parameters
+    /// overriding top level fields of the same name. Parameters not matching any field are ignored.
+    pub fn encode_request(
+        &self,
+        parameters: &Map<String, Value>,
+        body: &Value,
+    ) -> Result<Vec<u8>, String> {
+        let mut fields = match body {
+            Value::Object(fields) => fields.clone(),
+            Value::Null => Map::new(),
+            _ => return Err("Request body must be a JSON object".to_string()),
+        };
+
+        let message = self.message(&self.input_type)?;
+        for (name, value) in parameters {
+            if find_field_by_json_name(message, name).is_some() {
+                fields.insert(name.clone(), value.clone());
+            }
+        }
+
+        let mut buf = Vec::new();
+        self.encode_message(&self.input_type, &Value::Object(fields), &mut buf)?;
+        Ok(buf)
+    }
+
+    pub fn decode_response(&self, bytes: &[u8]) -> Result<Value, String> {
+        self.decode_message(&self.output_type, bytes)
+    }
+
+    fn message(&self, type_name: &str) -> Result<&DescriptorProto, String> {
+        self.messages
+            .get(type_name)
+            .ok_or_else(|| format!("Unknown message type {type_name}"))
+    }
+
+    fn encode_message(
+        &self,
+        type_name: &str,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), String> {
+        let message = self.message(type_name)?;
+        let Value::Object(fields) = value else {
+            return Err(format!(
+                "Expected a JSON object for message {}",
+                type_name.trim_start_matches('.')
+            ));
+        };
+
+        for (name, value) in fields {
+            let field = find_field_by_json_name(message, name).ok_or_else(|| {
+                format!(
+                    "Unknown field {name} in message {}",
+                    type_name.trim_start_matches('.')
+                )
+            })?;
+
+            if value.is_null() {
+                continue;
+            }
+
+            if let Some(entry) = self.map_entry(field) {
+                let Value::Object(entries) = value else {
+                    return Err(format!("Expected a JSON object for map field {name}"));
+                };
+                let (key_field, value_field) = map_entry_fields(entry)?;
+                for (key, value) in entries {
+                    let mut entry_buf = Vec::new();
+                    self.encode_value(key_field, &Value::String(key.clone()), &mut entry_buf)?;
+                    self.encode_value(value_field, value, &mut entry_buf)?;
+                    encode_key(field.number(), WIRE_LENGTH_DELIMITED, buf);
+                    encode_varint(entry_buf.len() as u64, buf);
+                    buf.put_slice(&entry_buf);
+                }
+            } else if field.label() == Label::Repeated {
+                let Value::Array(items) = value else {
+                    return Err(format!("Expected a JSON array for repeated field {name}"));
+                };
+                for item in items {
+                    self.encode_value(field, item, buf)?;
+                }
+            } else {
+                self.encode_value(field, value, buf)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_value(
+        &self,
+        field: &FieldDescriptorProto,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), String> {
+        let number = field.number();
+        let invalid = || format!("Invalid value {} for field {}", value, field.name());
+
+        match field.r#type() {
+            Type::Double => {
+                encode_key(number, WIRE_FIXED64, buf);
+                buf.put_f64_le(json_f64(value).ok_or_else(invalid)?);
+            }
+            Type::Float => {
+                encode_key(number, WIRE_FIXED32, buf);
+                buf.put_f32_le(json_f64(value).ok_or_else(invalid)? as f32);
+            }
+            Type::Int64 => {
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(json_i64(value).ok_or_else(invalid)? as u64, buf);
+            }
+            Type::Uint64 => {
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(json_u64(value).ok_or_else(invalid)?, buf);
+            }
+            Type::Int32 => {
+                let value = json_i64(value)
+                    .and_then(|value| i32::try_from(value).ok())
+                    .ok_or_else(invalid)?;
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(value as i64 as u64, buf);
+            }
+            Type::Uint32 => {
+                let value = json_u64(value)
+                    .and_then(|value| u32::try_from(value).ok())
+                    .ok_or_else(invalid)?;
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(value as u64, buf);
+            }
+            Type::Sint32 => {
+                let value = json_i64(value)
+                    .and_then(|value| i32::try_from(value).ok())
+                    .ok_or_else(invalid)?;
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(((value << 1) ^ (value >> 31)) as u32 as u64, buf);
+            }
+            Type::Sint64 => {
+                let value = json_i64(value).ok_or_else(invalid)?;
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(((value << 1) ^ (value >> 63)) as u64, buf);
+            }
+            Type::Fixed64 => {
+                encode_key(number, WIRE_FIXED64, buf);
+                buf.put_u64_le(json_u64(value).ok_or_else(invalid)?);
+            }
+            Type::Sfixed64 => {
+                encode_key(number, WIRE_FIXED64, buf);
+                buf.put_i64_le(json_i64(value).ok_or_else(invalid)?);
+            }
+            Type::Fixed32 => {
+                let value = json_u64(value)
+                    .and_then(|value| u32::try_from(value).ok())
+                    .ok_or_else(invalid)?;
+                encode_key(number, WIRE_FIXED32, buf);
+                buf.put_u32_le(value);
+            }
+            Type::Sfixed32 => {
+                let value = json_i64(value)
+                    .and_then(|value| i32::try_from(value).ok())
+                    .ok_or_else(invalid)?;
+                encode_key(number, WIRE_FIXED32, buf);
+                buf.put_i32_le(value);
+            }
+            Type::Bool => {
+                let value = match value {
+                    Value::Bool(value) => *value,
+                    Value::String(value) => bool::from_str(value).map_err(|_| invalid())?,
+                    _ => return Err(invalid()),
+                };
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(value as u64, buf);
+            }
+            Type::Enum => {
+                let value = match value {
+                    Value::String(name) => self
+                        .enums
+                        .get(field.type_name())
+                        .and_then(|enum_type| {
+                            enum_type
+                                .value
+                                .iter()
+                                .find(|enum_value| enum_value.name() == name)
+                        })
+                        .map(|enum_value| enum_value.number())
+                        .ok_or_else(invalid)?,
+                    _ => json_i64(value)
+                        .and_then(|value| i32::try_from(value).ok())
+                        .ok_or_else(invalid)?,
+                };
+                encode_key(number, WIRE_VARINT, buf);
+                encode_varint(value as i64 as u64, buf);
+            }
+            Type::String => {
+                let Value::String(value) = value else {
+                    return Err(invalid());
+                };
+                encode_length_delimited(number, value.as_bytes(), buf);
+            }
+            Type::Bytes => {
+                let Value::String(value) = value else {
+                    return Err(invalid());
+                };
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(value)
+                    .or_else(|_| base64::engine::general_purpose::URL_SAFE.decode(value))
+                    .map_err(|_| invalid())?;
+                encode_length_delimited(number, &bytes, buf);
+            }
+            Type::Message => {
+                let mut message_buf = Vec::new();
+                self.encode_message(field.type_name(), value, &mut message_buf)?;
+                encode_length_delimited(number, &message_buf, buf);
+            }
+            Type::Group => {
+                return Err(format!("Group field {} is not supported", field.name()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decode_message(&self, type_name: &str, mut bytes: &[u8]) -> Result<Value, String> {
+        let message = self.message(type_name)?;
+        let mut result = Map::new();
+
+        while bytes.has_remaining() {
+            let key = decode_varint(&mut bytes).map_err(|err| err.to_string())?;
+            let number = (key >> 3) as i32;
+            let wire_type = key & 0x7;
+
+            let Some(field) = message.field.iter().find(|field| field.number() == number) else {
+                skip_value(wire_type, &mut bytes)?;
+                continue;
+            };
+            let name = json_name(field);
+
+            if self.map_entry(field).is_some() {
+                let entry_bytes = read_length_delimited(&mut bytes)?;
+                let Value::Object(mut entry_value) =
+                    self.decode_message(field.type_name(), entry_bytes)?
+                else {
+                    continue;
+                };
+                let key = match entry_value.remove("key") {
+                    Some(Value::String(key)) => key,
+                    Some(key) => key.to_string(),
+                    None => String::new(),
+                };
+                let value = entry_value.remove("value").unwrap_or(Value::Null);
+                if let Value::Object(map) = result
+                    .entry(name)
+                    .or_insert_with(|| Value::Object(Map::new()))
+                {
+                    map.insert(key, value);
+                }
+            } else if field.label() == Label::Repeated {
+                let mut values = Vec::new();
+                if wire_type == WIRE_LENGTH_DELIMITED && is_packable(field.r#type()) {
+                    let mut packed = read_length_delimited(&mut bytes)?;
+                    while packed.has_remaining() {
+                        let wire_type = natural_wire_type(field.r#type());
+                        values.push(self.decode_value(field, wire_type, &mut packed)?);
+                    }
+                } else {
+                    values.push(self.decode_value(field, wire_type, &mut bytes)?);
+                }
+                if let Value::Array(items) = result
+                    .entry(name)
+                    .or_insert_with(|| Value::Array(Vec::new()))
+                {
+                    items.extend(values);
+                }
+            } else {
+                let value = self.decode_value(field, wire_type, &mut bytes)?;
+                result.insert(name, value);
+            }
+        }
+
+        Ok(Value::Object(result))
+    }
+
+    fn decode_value(
+        &self,
+        field: &FieldDescriptorProto,
+        wire_type: u64,
+        bytes: &mut &[u8],
+    ) -> Result<Value, String> {
+        let typ = field.r#type();
+        if wire_type != natural_wire_type(typ) {
+            return Err(format!(
+                "Unexpected wire type {wire_type} for field {}",
+                field.name()
+            ));
+        }
+
+        let value = match typ {
+            Type::Double => float_value(f64::from_bits(read_fixed64(bytes)?)),
+            Type::Float => float_value(f32::from_bits(read_fixed32(bytes)?) as f64),
+            Type::Fixed64 => Value::String(read_fixed64(bytes)?.to_string()),
+            Type::Sfixed64 => Value::String((read_fixed64(bytes)? as i64).to_string()),
+            Type::Fixed32 => Value::from(read_fixed32(bytes)?),
+            Type::Sfixed32 => Value::from(read_fixed32(bytes)? as i32),
+            Type::String => {
+                let data = read_length_delimited(bytes)?;
+                Value::String(
+                    String::from_utf8(data.to_vec())
+                        .map_err(|_| format!("Invalid UTF-8 in field {}", field.name()))?,
+                )
+            }
+            Type::Bytes => Value::String(
+                base64::engine::general_purpose::STANDARD.encode(read_length_delimited(bytes)?),
+            ),
+            Type::Message => {
+                let data = read_length_delimited(bytes)?;
+                self.decode_message(field.type_name(), data)?
+            }
+            Type::Group => {
+                return Err(format!("Group field {} is not supported", field.name()));
+            }
+            _ => {
+                let raw = decode_varint(bytes).map_err(|err| err.to_string())?;
+                match typ {
+                    Type::Int64 => Value::String((raw as i64).to_string()),
+                    Type::Uint64 => Value::String(raw.to_string()),
+                    Type::Sint64 => {
+                        Value::String(((raw >> 1) as i64 ^ -((raw & 1) as i64)).to_string())
+                    }
+                    Type::Int32 => Value::from(raw as i32),
+                    Type::Uint32 => Value::from(raw as u32),
+                    Type::Sint32 => Value::from(((raw >> 1) as i64 ^ -((raw & 1) as i64)) as i32),
+                    Type::Bool => Value::Bool(raw != 0),
+                    _ => {
+                        let number = raw as i32;
+                        self.enums
+                            .get(field.type_name())
+                            .and_then(|enum_type| {
+                                enum_type
+                                    .value
+                                    .iter()
+                                    .find(|enum_value| enum_value.number() == number)
+                            })
+                            .map(|enum_value| Value::String(enum_value.name().to_string()))
+                            .unwrap_or_else(|| Value::from(number))
+                    }
+                }
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn map_entry(&self, field: &FieldDescriptorProto) -> Option<&DescriptorProto> {
+        if field.label() != Label::Repeated || field.r#type() != Type::Message {
+            return None;
+        }
+        self.messages
+            .get(field.type_name())
+            .filter(|message| message.options.as_ref().is_some_and(|o| o.map_entry()))
+    }
+}
+
+fn register_message(
+    scope: &str,
+    message: &DescriptorProto,
+    messages: &mut HashMap<String, DescriptorProto>,
+    enums: &mut HashMap<String, EnumDescriptorProto>,
+) {
+    let name = qualified_name(scope, message.name());
+    for nested in &message.nested_type {
+        register_message(&name, nested, messages, enums);
+    }
+    for enum_type in &message.enum_type {
+        enums.insert(qualified_name(&name, enum_type.name()), enum_type.clone());
+    }
+    messages.insert(name, message.clone());
+}
+
+// Fully qualified names in descriptors have a leading dot, such as .shop.v1.Cart
+fn qualified_name(scope: &str, name: &str) -> String {
+    if name.is_empty() {
+        scope.to_string()
+    } else {
+        format!("{scope}.{name}")
+    }
+}
+
+fn map_entry_fields(
+    entry: &DescriptorProto,
+) -> Result<(&FieldDescriptorProto, &FieldDescriptorProto), String> {
+    let key = entry.field.iter().find(|field| field.number() == 1);
+    let value = entry.field.iter().find(|field| field.number() == 2);
+    key.zip(value)
+        .ok_or_else(|| format!("Invalid map entry {}", entry.name()))
+}
+
+fn find_field_by_json_name<'a>(
+    message: &'a DescriptorProto,
+    name: &str,
+) -> Option<&'a FieldDescriptorProto> {
+    message
+        .field
+        .iter()
+        .find(|field| json_name(field) == name || field.name() == name)
+}
+
+fn json_name(field: &FieldDescriptorProto) -> String {
+    match field.json_name() {
+        "" => lower_camel_case(field.name()),
+        json_name => json_name.to_string(),
+    }
+}
+
+fn lower_camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            result.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn is_packable(typ: Type) -> bool {
+    !matches!(
+        typ,
+        Type::String | Type::Bytes | Type::Message | Type::Group
+    )
+}
+
+fn natural_wire_type(typ: Type) -> u64 {
+    match typ {
+        Type::Double | Type::Fixed64 | Type::Sfixed64 => WIRE_FIXED64,
+        Type::Float | Type::Fixed32 | Type::Sfixed32 => WIRE_FIXED32,
+        Type::String | Type::Bytes | Type::Message => WIRE_LENGTH_DELIMITED,
+        Type::Group => 3,
+        _ => WIRE_VARINT,
+    }
+}
+
+fn encode_key(number: i32, wire_type: u64, buf: &mut Vec<u8>) {
+    encode_varint(((number as u64) << 3) | wire_type, buf);
+}
+
+fn encode_length_delimited(number: i32, data: &[u8], buf: &mut Vec<u8>) {
+    encode_key(number, WIRE_LENGTH_DELIMITED, buf);
+    encode_varint(data.len() as u64, buf);
+    buf.put_slice(data);
+}
+
+fn read_length_delimited<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8], String> {
+    let length = decode_varint(bytes).map_err(|err| err.to_string())? as usize;
+    if bytes.len() < length {
+        return Err("Truncated message".to_string());
+    }
+    let (data, rest) = bytes.split_at(length);
+    *bytes = rest;
+    Ok(data)
+}
+
+fn read_fixed64(bytes: &mut &[u8]) -> Result<u64, String> {
+    if bytes.remaining() < 8 {
+        return Err("Truncated message".to_string());
+    }
+    Ok(bytes.get_u64_le())
+}
+
+fn read_fixed32(bytes: &mut &[u8]) -> Result<u32, String> {
+    if bytes.remaining() < 4 {
+        return Err("Truncated message".to_string());
+    }
+    Ok(bytes.get_u32_le())
+}
+
+fn skip_value(wire_type: u64, bytes: &mut &[u8]) -> Result<(), String> {
+    match wire_type {
+        WIRE_VARINT => decode_varint(bytes)
+            .map(|_| ())
+            .map_err(|err| err.to_string()),
+        WIRE_FIXED64 => read_fixed64(bytes).map(|_| ()),
+        WIRE_LENGTH_DELIMITED => read_length_delimited(bytes).map(|_| ()),
+        WIRE_FIXED32 => read_fixed32(bytes).map(|_| ()),
+        other => Err(format!("Unsupported wire type {other}")),
+    }
+}
+
+fn float_value(value: f64) -> Value {
+    match Number::from_f64(value) {
+        Some(number) => Value::Number(number),
+        None if value.is_nan() => Value::String("NaN".to_string()),
+        None if value > 0.0 => Value::String("Infinity".to_string()),
+        None => Value::String("-Infinity".to_string()),
+    }
+}
+
+fn json_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => number.as_i64(),
+        Value::String(value) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(number) => number.as_u64(),
+        Value::String(value) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(value) => match value.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            _ => value.parse().ok(),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use prost_types::{
+        EnumValueDescriptorProto, FileDescriptorProto, MessageOptions, MethodDescriptorProto,
+        ServiceDescriptorProto,
+    };
+    use serde_json::json;
+
+    fn field(
+        name: &str,
+        number: i32,
+        typ: Type,
+        label: Label,
+        type_name: &str,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(label as i32),
+            r#type: Some(typ as i32),
+            type_name: (!type_name.is_empty()).then(|| type_name.to_string()),
+            json_name: Some(lower_camel_case(name)),
+            ..Default::default()
+        }
+    }
+
+    fn descriptor_set() -> Vec<u8> {
+        let item = DescriptorProto {
+            name: Some("Item".to_string()),
+            field: vec![
+                field("item_id", 1, Type::Int64, Label::Optional, ""),
+                field("quantity", 2, Type::Sint32, Label::Optional, ""),
+                field("price", 3, Type::Double, Label::Optional, ""),
+            ],
+            ..Default::default()
+        };
+
+        let labels_entry = DescriptorProto {
+            name: Some("LabelsEntry".to_string()),
+            field: vec![
+                field("key", 1, Type::String, Label::Optional, ""),
+                field("value", 2, Type::Uint32, Label::Optional, ""),
+            ],
+            options: Some(MessageOptions {
+                map_entry: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let cart = DescriptorProto {
+            name: Some("Cart".to_string()),
+            field: vec![
+                field("user_id", 1, Type::String, Label::Optional, ""),
+                field("items", 2, Type::Message, Label::Repeated, ".shop.v1.Item"),
+                field("status", 3, Type::Enum, Label::Optional, ".shop.v1.Status"),
+                field("tags", 4, Type::Int32, Label::Repeated, ""),
+                field(
+                    "labels",
+                    5,
+                    Type::Message,
+                    Label::Repeated,
+                    ".shop.v1.Cart.LabelsEntry",
+                ),
+                field("token", 6, Type::Bytes, Label::Optional, ""),
+                field("active", 7, Type::Bool, Label::Optional, ""),
+            ],
+            nested_type: vec![labels_entry],
+            ..Default::default()
+        };
+
+        let status = EnumDescriptorProto {
+            name: Some("Status".to_string()),
+            value: vec![
+                EnumValueDescriptorProto {
+                    name: Some("OPEN".to_string()),
+                    number: Some(0),
+                    ..Default::default()
+                },
+                EnumValueDescriptorProto {
+                    name: Some("CHECKED_OUT".to_string()),
+                    number: Some(1),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let service = ServiceDescriptorProto {
+            name: Some("CartService".to_string()),
+            method: vec![
+                MethodDescriptorProto {
+                    name: Some("UpdateCart".to_string()),
+                    input_type: Some(".shop.v1.Cart".to_string()),
+                    output_type: Some(".shop.v1.Cart".to_string()),
+                    ..Default::default()
+                },
+                MethodDescriptorProto {
+                    name: Some("WatchCart".to_string()),
+                    input_type: Some(".shop.v1.Cart".to_string()),
+                    output_type: Some(".shop.v1.Cart".to_string()),
+                    server_streaming: Some(true),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("shop.proto".to_string()),
+                package: Some("shop.v1".to_string()),
+                message_type: vec![item, cart],
+                enum_type: vec![status],
+                service: vec![service],
+                syntax: Some("proto3".to_string()),
+                ..Default::default()
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn request_roundtrips_through_protobuf() {
+        let descriptor = GrpcMethodDescriptor::from_descriptor_set(
+            &descriptor_set(),
+            "shop.v1.CartService",
+            "UpdateCart",
+        )
+        .unwrap();
+
+        let body = json!({
+            "items": [
+                { "itemId": "9007199254740993", "quantity": -3, "price": 2.5 },
+                { "item_id": 7 }
+            ],
+            "status": "CHECKED_OUT",
+            "tags": [1, -2, 3],
+            "labels": { "priority": 5 },
+            "token": "AQID",
+            "active": true
+        });
+        let parameters = Map::from_iter([
+            ("userId".to_string(), json!("user-1")),
+            ("unrelated".to_string(), json!("ignored")),
+        ]);
+
+        let message = descriptor.encode_request(&parameters, &body).unwrap();
+        let decoded = descriptor.decode_response(&message).unwrap();
+
+        assert_eq!(descriptor.path, "/shop.v1.CartService/UpdateCart");
+        assert_eq!(
+            decoded,
+            json!({
+                "userId": "user-1",
+                "items": [
+                    { "itemId": "9007199254740993", "quantity": -3, "price": 2.5 },
+                    { "itemId": "7" }
+                ],
+                "status": "CHECKED_OUT",
+                "tags": [1, -2, 3],
+                "labels": { "priority": 5 },
+                "token": "AQID",
+                "active": true
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_packed_repeated_fields() {
+        let descriptor = GrpcMethodDescriptor::from_descriptor_set(
+            &descriptor_set(),
+            "shop.v1.CartService",
+            "UpdateCart",
+        )
+        .unwrap();
+
+        // field 4 (tags), length delimited, containing the varints 1, 2 and 300
+        let message = [0x22, 0x04, 0x01, 0x02, 0xac, 0x02];
+
+        assert_eq!(
+            descriptor.decode_response(&message).unwrap(),
+            json!({ "tags": [1, 2, 300] })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields_and_streaming_methods() {
+        let descriptor = GrpcMethodDescriptor::from_descriptor_set(
+            &descriptor_set(),
+            "shop.v1.CartService",
+            "UpdateCart",
+        )
+        .unwrap();
+
+        assert_eq!(
+            descriptor.encode_request(&Map::new(), &json!({ "unknown": 1 })),
+            Err("Unknown field unknown in message shop.v1.Cart".to_string())
+        );
+
+        assert!(GrpcMethodDescriptor::from_descriptor_set(
+            &descriptor_set(),
+            "shop.v1.CartService",
+            "WatchCart"
+        )
+        .unwrap_err()
+        .contains("streaming"));
+
+        assert!(GrpcMethodDescriptor::from_descriptor_set(
+            &descriptor_set(),
+            "shop.v1.CartService",
+            "Missing"
+        )
+        .unwrap_err()
+        .contains("not found"));
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::gateway_binding::ResponseMediaType;
 use golem_wasm_ast::analysis::AnalysedType;
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use poem::web::headers::ContentType;
@@ -30,6 +31,8 @@ pub trait HttpContentTypeResponseMapper {
 pub enum ContentTypeHeaders {
     FromClientAccept(AcceptHeaders),
     FromUserDefinedResponseMapping(ContentType),
+    // Chosen by the content negotiation of the binding
+    Negotiated(ResponseMediaType),
     Empty,
 }
 
@@ -114,6 +117,9 @@ impl HttpContentTypeResponseMapper for TypeAnnotatedValue {
             ContentTypeHeaders::FromClientAccept(accept_content_headers) => {
                 internal::get_response_body_based_on_content_type(self, &accept_content_headers)
             }
+            ContentTypeHeaders::Negotiated(media_type) => {
+                internal::get_response_body_in_media_type(self, media_type)
+            }
             ContentTypeHeaders::Empty => internal::get_response_body(self),
         }
     }
@@ -165,6 +171,7 @@ impl Display for ContentTypeMapError {
 }
 
 mod internal {
+    use crate::gateway_binding::ResponseMediaType;
     use crate::gateway_execution::http_content_type_mapper::{
         AcceptHeaders, ContentTypeHeaderExt, ContentTypeMapError,
     };
@@ -173,6 +180,7 @@ mod internal {
     };
     use golem_wasm_ast::analysis::AnalysedType;
     use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
+    use golem_wasm_rpc::print_type_annotated_value;
     use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
     use golem_wasm_rpc::protobuf::{PrimitiveType, TypedEnum, TypedList};
     use poem::web::headers::ContentType;
@@ -319,6 +327,42 @@ mod internal {
         }
     }
 
+    // Values are encoded from their JSON representation, except as plain text, where strings and
+    // enums are served as they are, and any other value in the WAVE syntax
+    pub(crate) fn get_response_body_in_media_type(
+        type_annotated_value: &TypeAnnotatedValue,
+        media_type: ResponseMediaType,
+    ) -> Result<WithContentType<Body>, ContentTypeMapError> {
+        let body = match media_type {
+            ResponseMediaType::Json => Body::from_json(type_annotated_value.to_json_value())
+                .map_err(|_| ContentTypeMapError::internal("Failed to convert to json body"))?,
+            ResponseMediaType::Yaml => {
+                let yaml = serde_yaml::to_string(&type_annotated_value.to_json_value())
+                    .map_err(|_| ContentTypeMapError::internal("Failed to convert to yaml body"))?;
+                Body::from_string(yaml)
+            }
+            ResponseMediaType::MessagePack => {
+                let bytes = rmp_serde::to_vec_named(&type_annotated_value.to_json_value())
+                    .map_err(|_| {
+                        ContentTypeMapError::internal("Failed to convert to msgpack body")
+                    })?;
+                Body::from_bytes(bytes::Bytes::from(bytes))
+            }
+            ResponseMediaType::Text => match type_annotated_value {
+                TypeAnnotatedValue::Str(string) => Body::from_string(string.to_string()),
+                TypeAnnotatedValue::Enum(TypedEnum { value, .. }) => {
+                    Body::from_string(value.to_string())
+                }
+                _ => Body::from_string(
+                    print_type_annotated_value(type_annotated_value)
+                        .map_err(ContentTypeMapError::internal)?,
+                ),
+            },
+        };
+
+        Ok(body.with_content_type(media_type.to_string()))
+    }
+
     pub(crate) fn pick_highest_priority_content_type(
         input_content_types: &AcceptHeaders,
     ) -> Result<ContentType, ContentTypeMapError> {
@@ -970,4 +1014,72 @@ mod tests {
             ));
         }
     }
+
+    #[cfg(test)]
+    mod with_negotiated_media_type {
+        use test_r::test;
+
+        use super::*;
+
+        async fn get_content_type_and_body(
+            input: &TypeAnnotatedValue,
+            media_type: ResponseMediaType,
+        ) -> (Option<String>, bytes::Bytes) {
+            let response_body =
+                internal::get_response_body_in_media_type(input, media_type).unwrap();
+            let response = response_body.into_response();
+            let (parts, body) = response.into_parts();
+            let content_type = parts
+                .headers
+                .get("content-type")
+                .map(|v| v.to_str().unwrap().to_string());
+            (content_type, body.into_bytes().await.unwrap())
+        }
+
+        #[test]
+        async fn test_record_type_in_each_media_type() {
+            let type_annotated_value = sample_record();
+
+            let (content_type, body) =
+                get_content_type_and_body(&type_annotated_value, ResponseMediaType::Yaml).await;
+            assert_eq!(
+                (String::from_utf8_lossy(&body).to_string(), content_type),
+                (
+                    "name: Hello\n".to_string(),
+                    Some("application/yaml".to_string())
+                )
+            );
+
+            let (content_type, body) =
+                get_content_type_and_body(&type_annotated_value, ResponseMediaType::MessagePack)
+                    .await;
+            let result: Value = rmp_serde::from_slice(&body).unwrap();
+            assert_eq!(
+                (result, content_type),
+                (
+                    serde_json::json!({"name": "Hello"}),
+                    Some("application/msgpack".to_string())
+                )
+            );
+
+            let (content_type, body) =
+                get_content_type_and_body(&type_annotated_value, ResponseMediaType::Text).await;
+            assert_eq!(
+                (String::from_utf8_lossy(&body).to_string(), content_type),
+                (
+                    "{name: \"Hello\"}".to_string(),
+                    Some("text/plain".to_string())
+                )
+            );
+
+            // Strings are served as they are in plain text, and as JSON strings in JSON
+            let string = TypeAnnotatedValue::Str("Hello".to_string());
+            let (_, text) = get_content_type_and_body(&string, ResponseMediaType::Text).await;
+            let (_, json) = get_content_type_and_body(&string, ResponseMediaType::Json).await;
+            assert_eq!(
+                (text, json),
+                (bytes::Bytes::from("Hello"), bytes::Bytes::from("\"Hello\""))
+            );
+        }
+    }
 }
@@ -94,6 +94,7 @@ impl<Namespace: HasAccountId + Send + Sync + Clone + 'static> HttpHandlerBinding
                 function_params: vec![type_annotated_param],
                 idempotency_key: worker_detail.idempotency_key.clone(),
                 invocation_context: worker_detail.invocation_context.clone(),
+                bootstrap: worker_detail.bootstrap.clone(),
                 namespace: namespace.clone(),
             };
 
@@ -0,0 +1,306 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::gateway_http_input_executor::resolve_rib_input;
+use super::request::{authority_from_request, RichRequest};
+use crate::gateway_binding::{HttpProxyBindingCompiled, RequestMappingCompiled};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::Value;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use poem::Body;
+use std::str::FromStr;
+
+// Hop-by-hop headers, which are only meaningful for a single connection and are never forwarded
+const HOP_BY_HOP_HEADERS: [&str; 9] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+#[async_trait]
+pub trait HttpProxyBindingHandler {
+    async fn handle_http_proxy_binding(
+        &self,
+        binding: &HttpProxyBindingCompiled,
+        request: &mut RichRequest,
+    ) -> HttpProxyBindingResult;
+}
+
+pub type HttpProxyBindingResult = Result<HttpProxyBindingSuccess, HttpProxyBindingError>;
+
+// The response of the upstream, with its body streamed to the client
+pub struct HttpProxyBindingSuccess {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Body,
+}
+
+#[derive(Debug)]
+pub enum HttpProxyBindingError {
+    InvalidRequest(String),
+    Timeout,
+    UpstreamError(String),
+}
+
+// The changes the request mapping script of the binding makes to the forwarded request
+#[derive(Debug, Default, PartialEq)]
+pub struct RequestMappingResult {
+    pub path: Option<String>,
+    pub headers: Vec<(String, String)>,
+}
+
+/// Forwards requests with a client shared by all the bindings.
+/// Redirects are returned to the client rather than followed, and bodies are
+/// streamed through as they are, without decompressing them.
+pub struct DefaultHttpProxyBindingHandler {
+    client: reqwest::Client,
+}
+
+impl Default for DefaultHttpProxyBindingHandler {
+    fn default() -> Self {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .no_gzip()
+            .build()
+            .expect("Failed to create HTTP proxy client");
+
+        DefaultHttpProxyBindingHandler { client }
+    }
+}
+
+#[async_trait]
+impl HttpProxyBindingHandler for DefaultHttpProxyBindingHandler {
+    async fn handle_http_proxy_binding(
+        &self,
+        binding: &HttpProxyBindingCompiled,
+        request: &mut RichRequest,
+    ) -> HttpProxyBindingResult {
+        let mapping = match &binding.request_mapping_compiled {
+            Some(request_mapping) => evaluate_request_mapping(request_mapping, request).await?,
+            None => RequestMappingResult::default(),
+        };
+
+        let path = mapping
+            .path
+            .unwrap_or_else(|| request.underlying.uri().path().to_string());
+
+        let url = binding
+            .upstream_url(&path, request.underlying.uri().query())
+            .map_err(HttpProxyBindingError::InvalidRequest)?;
+
+        let headers = forwarded_headers(request, mapping.headers)?;
+
+        // Streamed to the upstream rather than read into memory first, the body limits of the
+        // route are applied to it before it gets here
+        let body = reqwest::Body::wrap_stream(request.underlying.take_body().into_bytes_stream());
+
+        let response = self
+            .client
+            .request(request.underlying.method().clone(), url)
+            .headers(headers)
+            .body(body)
+            .timeout(binding.timeout())
+            .send()
+            .await
+            .map_err(|err| {
+                if err.is_timeout() {
+                    HttpProxyBindingError::Timeout
+                } else {
+                    HttpProxyBindingError::UpstreamError(err.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        let mut headers = response.headers().clone();
+        remove_hop_by_hop_headers(&mut headers);
+
+        let body = Body::from_bytes_stream(
+            response
+                .bytes_stream()
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
+        );
+
+        Ok(HttpProxyBindingSuccess {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+async fn evaluate_request_mapping(
+    request_mapping: &RequestMappingCompiled,
+    request: &RichRequest,
+) -> Result<RequestMappingResult, HttpProxyBindingError> {
+    let mut rib_input = serde_json::Map::new();
+    rib_input.insert(
+        "request".to_string(),
+        request
+            .as_json()
+            .map_err(HttpProxyBindingError::InvalidRequest)?,
+    );
+
+    let rib_input = resolve_rib_input(&rib_input, &request_mapping.rib_input)
+        .await
+        .map_err(HttpProxyBindingError::InvalidRequest)?;
+
+    let result = rib::interpret_pure(&request_mapping.compiled_request_mapping, &rib_input)
+        .await
+        .map_err(HttpProxyBindingError::InvalidRequest)?;
+
+    let fields = result.get_record().ok_or_else(|| {
+        HttpProxyBindingError::InvalidRequest(
+            "Request mapping must be a Rib expression that resolves to record".to_string(),
+        )
+    })?;
+
+    let mut mapping = RequestMappingResult::default();
+
+    for (name, value) in fields {
+        match (name.as_str(), value.value) {
+            ("path", Value::String(path)) => mapping.path = Some(path),
+            ("headers", Value::Record(values)) => {
+                let names = match value.typ {
+                    AnalysedType::Record(record) => record.fields,
+                    _ => vec![],
+                };
+
+                for (field, value) in names.into_iter().zip(values) {
+                    match value {
+                        Value::String(value) => mapping.headers.push((field.name, value)),
+                        _ => {
+                            return Err(HttpProxyBindingError::InvalidRequest(format!(
+                                "Value of header {} in request mapping must be a string",
+                                field.name
+                            )))
+                        }
+                    }
+                }
+            }
+            (name, _) => {
+                return Err(HttpProxyBindingError::InvalidRequest(format!(
+                    "Unexpected field {} in request mapping, expected a string path or a record of headers",
+                    name
+                )))
+            }
+        }
+    }
+
+    Ok(mapping)
+}
+
+// The headers of the forwarded request: the headers of the request without the hop-by-hop ones,
+// the forwarding headers, and the ones set by the request mapping, removed if their value is empty
+fn forwarded_headers(
+    request: &RichRequest,
+    mapped_headers: Vec<(String, String)>,
+) -> Result<HeaderMap, HttpProxyBindingError> {
+    let mut headers = request.underlying.headers().clone();
+    remove_hop_by_hop_headers(&mut headers);
+    headers.remove(http::header::HOST);
+    headers.remove(http::header::CONTENT_LENGTH);
+
+    if let Ok(authority) = authority_from_request(&request.underlying) {
+        if let Ok(value) = HeaderValue::from_str(&authority) {
+            headers.insert("x-forwarded-host", value);
+        }
+    }
+
+    if let Ok(value) = HeaderValue::from_str(request.underlying.scheme().as_str()) {
+        headers.insert("x-forwarded-proto", value);
+    }
+
+    if let Some(address) = request.underlying.remote_addr().as_socket_addr() {
+        let forwarded_for = match headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(previous) => format!("{}, {}", previous, address.ip()),
+            None => address.ip().to_string(),
+        };
+
+        if let Ok(value) = HeaderValue::from_str(&forwarded_for) {
+            headers.insert("x-forwarded-for", value);
+        }
+    }
+
+    for (name, value) in mapped_headers {
+        let name = HeaderName::from_str(&name).map_err(|err| {
+            HttpProxyBindingError::InvalidRequest(format!("Invalid header name {name}: {err}"))
+        })?;
+
+        if value.is_empty() {
+            headers.remove(&name);
+        } else {
+            let value = HeaderValue::from_str(&value).map_err(|err| {
+                HttpProxyBindingError::InvalidRequest(format!(
+                    "Invalid value of header {name}: {err}"
+                ))
+            })?;
+            headers.insert(name, value);
+        }
+    }
+
+    Ok(headers)
+}
+
+fn remove_hop_by_hop_headers(headers: &mut HeaderMap) {
+    // Headers listed in Connection are hop-by-hop as well
+    let listed: Vec<String> = headers
+        .get_all(http::header::CONNECTION)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+
+    for name in HOP_BY_HOP_HEADERS
+        .iter()
+        .copied()
+        .chain(listed.iter().map(|name| name.as_str()))
+    {
+        headers.remove(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn hop_by_hop_headers_are_removed() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "connection",
+            HeaderValue::from_static("keep-alive, x-debug"),
+        );
+        headers.insert("keep-alive", HeaderValue::from_static("timeout=5"));
+        headers.insert("x-debug", HeaderValue::from_static("1"));
+        headers.insert("accept", HeaderValue::from_static("application/json"));
+
+        remove_hop_by_hop_headers(&mut headers);
+
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers.get("accept").unwrap(), "application/json");
+    }
+}
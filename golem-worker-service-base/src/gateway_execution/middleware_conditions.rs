@@ -0,0 +1,63 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::gateway_http_input_executor::resolve_rib_input;
+use super::request::RichRequest;
+use super::to_response::{GatewayHttpError, GatewayHttpResult};
+use crate::gateway_middleware::{
+    ConditionalMiddleware, HttpMiddlewareConditions, MiddlewareConditionCompiled,
+};
+
+// The middlewares of a route whose condition is false for the request, which are skipped for it
+pub async fn skipped_middlewares(
+    conditions: &HttpMiddlewareConditions,
+    request: &RichRequest,
+) -> GatewayHttpResult<Vec<ConditionalMiddleware>> {
+    let mut skipped = vec![];
+
+    for (middleware, condition) in conditions.conditions() {
+        if !evaluate(middleware, condition, request).await? {
+            skipped.push(middleware);
+        }
+    }
+
+    Ok(skipped)
+}
+
+async fn evaluate(
+    middleware: ConditionalMiddleware,
+    condition: &MiddlewareConditionCompiled,
+    request: &RichRequest,
+) -> GatewayHttpResult<bool> {
+    let mut input = serde_json::Map::new();
+    input.insert(
+        "request".to_string(),
+        request.as_json().map_err(GatewayHttpError::BadRequest)?,
+    );
+
+    let rib_input = resolve_rib_input(&input, &condition.rib_input)
+        .await
+        .map_err(GatewayHttpError::BadRequest)?;
+
+    let result = rib::interpret_pure(&condition.compiled_predicate, &rib_input)
+        .await
+        .map_err(GatewayHttpError::RibInterpretPureError)?;
+
+    result
+        .get_bool()
+        .ok_or(GatewayHttpError::RibInterpretPureError(format!(
+            "Condition of the {} is not a Rib expression that resolves to a bool",
+            middleware
+        )))
+}
@@ -20,17 +20,40 @@ use std::collections::HashMap;
 use std::fmt::Display;
 pub mod api_definition_lookup;
 pub mod auth_call_back_binding_handler;
+pub mod binary_payload;
+pub mod body_limits;
+pub mod deployment_capacity_limiter;
+pub mod deployment_probe;
+pub mod error_mapping;
+pub mod experiment_assignment;
+pub mod field_selection;
 pub mod file_server_binding_handler;
+pub mod forwarded_client_certificate;
 pub mod gateway_binding_resolver;
 pub mod gateway_http_input_executor;
 pub mod gateway_session;
 mod gateway_worker_request_executor;
+pub mod grpc_transcoding;
 mod http_content_type_mapper;
 pub mod http_handler_binding_handler;
+pub mod http_proxy_binding_handler;
+pub mod middleware_conditions;
+pub mod pagination;
+pub mod rate_limiter;
 pub mod request;
+pub mod request_id;
+pub mod response_cache;
+pub mod response_compression;
+pub mod route_concurrency_limiter;
+pub mod route_metrics;
 pub mod router;
+pub mod server_sent_events_bridge;
+pub mod shadow_traffic_diff;
+pub mod status_page;
 pub mod to_response;
 pub mod to_response_failure;
+pub mod transforms;
+pub mod web_socket_bridge;
 pub use gateway_worker_request_executor::*;
 use golem_common::model::invocation_context::InvocationContextStack;
 use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
@@ -45,6 +68,7 @@ pub struct GatewayResolvedWorkerRequest<Namespace> {
     pub function_params: Vec<TypeAnnotatedValue>,
     pub idempotency_key: Option<IdempotencyKey>,
     pub invocation_context: InvocationContextStack,
+    pub bootstrap: Option<WorkerBootstrapValues>,
     pub namespace: Namespace,
 }
 
@@ -54,6 +78,16 @@ pub struct WorkerDetail {
     pub worker_name: Option<String>,
     pub idempotency_key: Option<IdempotencyKey>,
     pub invocation_context: InvocationContextStack,
+    pub bootstrap: Option<WorkerBootstrapValues>,
+}
+
+// The evaluated bootstrap of a binding, the arguments, environment variables and WASI config
+// variables the worker is created with if it does not exist yet
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WorkerBootstrapValues {
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+    pub config: HashMap<String, String>,
 }
 
 impl WorkerDetail {
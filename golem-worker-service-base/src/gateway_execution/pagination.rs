@@ -0,0 +1,187 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_binding::{
+    page_field_indices, Pagination, CURSOR_QUERY_PARAM, LIMIT_QUERY_PARAM,
+};
+use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_rpc::{Value, ValueAndType};
+use http::Uri;
+use rib::RibResult;
+use serde_json::json;
+use url::form_urlencoded;
+
+// The page requested by the client of a paginated binding,
+// available to its response mapping as `request.page`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRequest {
+    pub cursor: Option<String>,
+    pub limit: u32,
+}
+
+impl PageRequest {
+    pub fn from_query(pagination: &Pagination, query: Option<&str>) -> Result<Self, String> {
+        let mut cursor = None;
+        let mut limit = None;
+
+        for (key, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+            if key == CURSOR_QUERY_PARAM {
+                cursor = Some(value.into_owned()).filter(|cursor| !cursor.is_empty());
+            } else if key == LIMIT_QUERY_PARAM {
+                limit = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid limit '{}', expected a number", value))?,
+                );
+            }
+        }
+
+        let limit = limit.unwrap_or(pagination.default_limit());
+
+        if limit == 0 || limit > pagination.max_limit() {
+            return Err(format!(
+                "Invalid limit {}, expected a number between 1 and {}",
+                limit,
+                pagination.max_limit()
+            ));
+        }
+
+        Ok(PageRequest { cursor, limit })
+    }
+
+    pub fn as_json(&self) -> serde_json::Value {
+        json!({ "cursor": self.cursor, "limit": self.limit })
+    }
+}
+
+// A page returned by the response mapping of a paginated binding
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub items: ValueAndType,
+    pub next_cursor: Option<String>,
+}
+
+impl Page {
+    pub fn from_rib_result(result: RibResult) -> Result<Self, String> {
+        let RibResult::Val(page) = result else {
+            return Err("The response mapping of a paginated binding returned no page".to_string());
+        };
+
+        let (items_index, next_cursor_index) = page_field_indices(&page.typ)
+            .ok_or("The response mapping of a paginated binding returned no page".to_string())?;
+
+        let (mut values, types) = match (page.value, page.typ) {
+            (Value::Tuple(values), AnalysedType::Tuple(tuple)) => (values, tuple.items),
+            (Value::Record(values), AnalysedType::Record(record)) => (
+                values,
+                record.fields.into_iter().map(|field| field.typ).collect(),
+            ),
+            _ => return Err("Page value does not match its type".to_string()),
+        };
+
+        if values.len() != types.len() {
+            return Err("Page value does not match its type".to_string());
+        }
+
+        let next_cursor = match &values[next_cursor_index] {
+            Value::Option(Some(cursor)) => match cursor.as_ref() {
+                Value::String(cursor) => Some(cursor.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let items = ValueAndType::new(values.swap_remove(items_index), types[items_index].clone());
+
+        Ok(Page { items, next_cursor })
+    }
+
+    // The `Link` header to the next page, if any, keeping the other query parameters of the request
+    pub fn next_page_link(&self, uri: &Uri) -> Option<String> {
+        let next_cursor = self.next_cursor.as_ref()?;
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+
+        for (key, value) in form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes()) {
+            if key != CURSOR_QUERY_PARAM {
+                query.append_pair(&key, &value);
+            }
+        }
+
+        query.append_pair(CURSOR_QUERY_PARAM, next_cursor);
+
+        Some(format!("<{}?{}>; rel=\"next\"", uri.path(), query.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_wasm_ast::analysis::analysed_type::{list, option, str, tuple};
+    use test_r::test;
+
+    #[test]
+    fn pages_are_read_from_the_query_and_link_to_the_next_one() {
+        let pagination = Pagination {
+            default_limit: Some(10),
+            max_limit: Some(50),
+        };
+
+        assert_eq!(
+            PageRequest::from_query(&pagination, None),
+            Ok(PageRequest {
+                cursor: None,
+                limit: 10
+            })
+        );
+        assert_eq!(
+            PageRequest::from_query(&pagination, Some("status=open&cursor=a%2Bb&limit=25")),
+            Ok(PageRequest {
+                cursor: Some("a+b".to_string()),
+                limit: 25
+            })
+        );
+        assert!(PageRequest::from_query(&pagination, Some("limit=51")).is_err());
+        assert!(PageRequest::from_query(&pagination, Some("limit=ten")).is_err());
+
+        let page = Page::from_rib_result(RibResult::Val(ValueAndType::new(
+            Value::Tuple(vec![
+                Value::List(vec![Value::String("order-1".to_string())]),
+                Value::Option(Some(Box::new(Value::String("b/2".to_string())))),
+            ]),
+            tuple(vec![list(str()), option(str())]),
+        )))
+        .unwrap();
+
+        assert_eq!(
+            page.items,
+            ValueAndType::new(
+                Value::List(vec![Value::String("order-1".to_string())]),
+                list(str())
+            )
+        );
+
+        let uri = Uri::from_static("/v1/orders?status=open&cursor=a%2Bb&limit=25");
+        assert_eq!(
+            page.next_page_link(&uri),
+            Some("</v1/orders?status=open&limit=25&cursor=b%2F2>; rel=\"next\"".to_string())
+        );
+
+        let last_page = Page {
+            next_cursor: None,
+            ..page
+        };
+        assert_eq!(last_page.next_page_link(&uri), None);
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_middleware::HttpRateLimit;
+use async_trait::async_trait;
+use golem_common::redis::RedisPool;
+use golem_common::SafeDisplay;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{error, warn};
+
+pub const DEFAULT_MAX_IN_MEMORY_BUCKETS: usize = 10_000;
+
+// The buckets kept in memory are split between shards, each with a lock of its own
+const IN_MEMORY_BUCKET_SHARDS: usize = 16;
+
+// Takes a token from the bucket, and refills it, atomically in Redis.
+// The time of Redis is used so that replicas with skewed clocks share the same buckets.
+// Returns 0 if a token was taken, or the milliseconds until a token is available otherwise.
+const REDIS_TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_interval = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated_at')
+local tokens = tonumber(bucket[1])
+local updated_at = tonumber(bucket[2])
+if tokens == nil or updated_at == nil then
+  tokens = capacity
+  updated_at = now
+end
+tokens = math.min(capacity, tokens + math.max(0, now - updated_at) / refill_interval)
+local retry_after = 0
+if tokens >= 1 then
+  tokens = tokens - 1
+else
+  retry_after = math.ceil((1 - tokens) * refill_interval)
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated_at', now)
+redis.call('PEXPIRE', KEYS[1], math.ceil(capacity * refill_interval))
+return retry_after
+"#;
+
+#[async_trait]
+pub trait RateLimiter {
+    // Takes a token from the bucket of the key, which is created full if it does not exist
+    async fn try_acquire(
+        &self,
+        key: &str,
+        limit: &HttpRateLimit,
+    ) -> Result<RateLimitDecision, RateLimiterError>;
+}
+
+pub type RateLimiterStore = Arc<dyn RateLimiter + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitDecision {
+    Allowed,
+    Limited { retry_after: Duration },
+}
+
+#[derive(Debug, Clone)]
+pub enum RateLimiterError {
+    InternalError(String),
+}
+
+impl SafeDisplay for RateLimiterError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            RateLimiterError::InternalError(e) => format!("Internal error: {}", e),
+        }
+    }
+}
+
+// Each bucket keeps the limit it was last used with, as the buckets of the routes with different
+// limits are kept together
+#[derive(Debug, Clone, PartialEq)]
+struct TokenBucket {
+    capacity: f64,
+    refill_interval_ms: f64,
+    tokens: f64,
+    updated_at_ms: u64,
+}
+
+impl TokenBucket {
+    fn full(limit: &HttpRateLimit, now_ms: u64) -> Self {
+        TokenBucket {
+            capacity: limit.capacity as f64,
+            refill_interval_ms: limit.refill_interval_ms as f64,
+            tokens: limit.capacity as f64,
+            updated_at_ms: now_ms,
+        }
+    }
+
+    // The limit of a route changes when its definition is deployed again
+    fn set_limit(&mut self, limit: &HttpRateLimit) {
+        self.capacity = limit.capacity as f64;
+        self.refill_interval_ms = limit.refill_interval_ms as f64;
+        self.tokens = self.tokens.min(self.capacity);
+    }
+
+    fn refill(&mut self, now_ms: u64) {
+        let elapsed_ms = now_ms.saturating_sub(self.updated_at_ms);
+        self.tokens =
+            (self.tokens + elapsed_ms as f64 / self.refill_interval_ms).min(self.capacity);
+        self.updated_at_ms = now_ms;
+    }
+
+    fn take(&mut self, now_ms: u64) -> RateLimitDecision {
+        self.refill(now_ms);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            RateLimitDecision::Allowed
+        } else {
+            let retry_after_ms = ((1.0 - self.tokens) * self.refill_interval_ms).ceil();
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(retry_after_ms as u64),
+            }
+        }
+    }
+}
+
+// The buckets of a shard, with the order they were last used in. Once the shard is full, the least
+// recently used bucket is dropped for a new one.
+struct BucketShard {
+    buckets: HashMap<String, (TokenBucket, u64)>,
+    last_used: BTreeMap<u64, String>,
+    next_use: u64,
+    max_buckets: usize,
+}
+
+impl BucketShard {
+    fn new(max_buckets: usize) -> Self {
+        Self {
+            buckets: HashMap::new(),
+            last_used: BTreeMap::new(),
+            next_use: 0,
+            max_buckets,
+        }
+    }
+
+    fn take(&mut self, key: &str, limit: &HttpRateLimit, now_ms: u64) -> RateLimitDecision {
+        let use_id = self.next_use;
+        self.next_use += 1;
+
+        match self.buckets.get_mut(key) {
+            Some((_, last_use)) => {
+                let previous_use = std::mem::replace(last_use, use_id);
+                self.last_used.remove(&previous_use);
+            }
+            None => {
+                if self.buckets.len() >= self.max_buckets {
+                    if let Some((_, least_recently_used)) = self.last_used.pop_first() {
+                        self.buckets.remove(&least_recently_used);
+                    }
+                }
+
+                self.buckets
+                    .insert(key.to_string(), (TokenBucket::full(limit, now_ms), use_id));
+            }
+        }
+
+        self.last_used.insert(use_id, key.to_string());
+
+        let (bucket, _) = self
+            .buckets
+            .get_mut(key)
+            .expect("The key has a bucket");
+        bucket.set_limit(limit);
+        bucket.take(now_ms)
+    }
+}
+
+/// Keeps the buckets in the memory of a single gateway instance,
+/// used when there is no Redis shared by the replicas.
+pub struct InMemoryRateLimiter {
+    shards: Vec<Mutex<BucketShard>>,
+}
+
+impl InMemoryRateLimiter {
+    pub fn new(max_buckets: usize) -> Self {
+        let max_shard_buckets = max_buckets.div_ceil(IN_MEMORY_BUCKET_SHARDS).max(1);
+
+        Self {
+            shards: (0..IN_MEMORY_BUCKET_SHARDS)
+                .map(|_| Mutex::new(BucketShard::new(max_shard_buckets)))
+                .collect(),
+        }
+    }
+
+    fn shard(&self, key: &str) -> &Mutex<BucketShard> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+}
+
+impl Default for InMemoryRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_IN_MEMORY_BUCKETS)
+    }
+}
+
+#[async_trait]
+impl RateLimiter for InMemoryRateLimiter {
+    async fn try_acquire(
+        &self,
+        key: &str,
+        limit: &HttpRateLimit,
+    ) -> Result<RateLimitDecision, RateLimiterError> {
+        Ok(self.shard(key).lock().unwrap().take(key, limit, now_ms()))
+    }
+}
+
+/// Keeps the buckets in Redis, so that all the replicas of the gateway share the same limits.
+/// Failing to reach Redis either lets the requests through without a limit (failing open),
+/// or fails them.
+#[derive(Clone)]
+pub struct RedisRateLimiter {
+    redis: RedisPool,
+    fail_open: bool,
+}
+
+impl RedisRateLimiter {
+    pub fn new(redis: RedisPool, fail_open: bool) -> Self {
+        Self { redis, fail_open }
+    }
+
+    pub fn redis_key(key: &str) -> String {
+        format!("gateway_rate_limit:{}", key)
+    }
+}
+
+#[async_trait]
+impl RateLimiter for RedisRateLimiter {
+    async fn try_acquire(
+        &self,
+        key: &str,
+        limit: &HttpRateLimit,
+    ) -> Result<RateLimitDecision, RateLimiterError> {
+        let result: Result<u64, _> = self
+            .redis
+            .with("gateway_rate_limit", "try_acquire")
+            .eval(
+                REDIS_TOKEN_BUCKET_SCRIPT,
+                vec![Self::redis_key(key)],
+                vec![limit.capacity as u64, limit.refill_interval_ms],
+            )
+            .await;
+
+        let retry_after_ms = match result {
+            Ok(retry_after_ms) => retry_after_ms,
+            Err(e) if self.fail_open => {
+                warn!(
+                    "Failed to take a rate limit token from Redis, letting the request through: {}",
+                    e
+                );
+                return Ok(RateLimitDecision::Allowed);
+            }
+            Err(e) => {
+                error!("Failed to take a rate limit token from Redis: {}", e);
+                return Err(RateLimiterError::InternalError(e.to_string()));
+            }
+        };
+
+        if retry_after_ms == 0 {
+            Ok(RateLimitDecision::Allowed)
+        } else {
+            Ok(RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(retry_after_ms),
+            })
+        }
+    }
+}
+
+// Retry-After is in whole seconds, rounded up so that clients do not retry too early
+pub fn rate_limited_response(retry_after: Duration) -> poem::Response {
+    let retry_after_secs = retry_after.as_millis().div_ceil(1000).max(1) as u64;
+
+    let mut response = poem::Response::builder()
+        .status(http::StatusCode::TOO_MANY_REQUESTS)
+        .body(poem::Body::from_string("Too many requests".to_string()));
+
+    response
+        .headers_mut()
+        .insert(http::header::RETRY_AFTER, retry_after_secs.into());
+
+    response
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn bucket_is_refilled_over_time_up_to_its_capacity() {
+        let limit = HttpRateLimit::new(2, 1000, None).unwrap();
+        let mut bucket = TokenBucket::full(&limit, 0);
+
+        assert_eq!(bucket.take(0), RateLimitDecision::Allowed);
+        assert_eq!(bucket.take(0), RateLimitDecision::Allowed);
+        assert_eq!(
+            bucket.take(250),
+            RateLimitDecision::Limited {
+                retry_after: Duration::from_millis(750)
+            }
+        );
+        assert_eq!(bucket.take(1000), RateLimitDecision::Allowed);
+
+        bucket.refill(60_000);
+        assert_eq!(bucket.tokens, 2.0);
+
+        let response = rate_limited_response(Duration::from_millis(750));
+        assert_eq!(response.status(), http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response.headers().get(http::header::RETRY_AFTER).unwrap(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn least_recently_used_bucket_is_dropped_and_others_keep_their_limits() {
+        let strict = HttpRateLimit::new(1, 60_000, None).unwrap();
+        let loose = HttpRateLimit::new(100, 1, None).unwrap();
+        let mut shard = BucketShard::new(2);
+
+        assert_eq!(shard.take("strict", &strict, 0), RateLimitDecision::Allowed);
+        assert_eq!(shard.take("loose", &loose, 0), RateLimitDecision::Allowed);
+        assert!(matches!(
+            shard.take("strict", &strict, 10),
+            RateLimitDecision::Limited { .. }
+        ));
+
+        // The loose bucket is the least recently used one
+        assert_eq!(shard.take("other", &loose, 20), RateLimitDecision::Allowed);
+        assert_eq!(shard.buckets.len(), 2);
+        assert_eq!(shard.last_used.len(), 2);
+        assert!(!shard.buckets.contains_key("loose"));
+
+        // Adding a bucket with another limit does not refill the strict one
+        assert!(matches!(
+            shard.take("strict", &strict, 30),
+            RateLimitDecision::Limited { .. }
+        ));
+    }
+}
@@ -12,18 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::binary_payload::{is_binary_content_type, BinaryField};
+use super::experiment_assignment::ExperimentAssignments;
+use super::field_selection::FieldSelection;
 use super::gateway_session::{DataKey, GatewaySessionStore, SessionId};
+use super::request_id::request_id;
 use crate::gateway_api_definition::http::{QueryInfo, VarInfo};
-use crate::gateway_binding::{GatewayBindingCompiled, ResolvedRouteEntry};
+use crate::gateway_binding::{GatewayBindingCompiled, ResolvedRouteEntry, ResponseMediaType};
 use crate::gateway_middleware::HttpMiddlewares;
 use crate::gateway_request::http_request::router::PathParamExtractor;
 use bytes::Bytes;
 use golem_common::SafeDisplay;
 use http::HeaderMap;
+use poem::web::Multipart;
+use poem::{FromRequest, RequestBody};
 use serde_json::Value;
 use std::collections::HashMap;
 
 const COOKIE_HEADER_NAMES: [&str; 2] = ["cookie", "Cookie"];
+const MULTIPART_FORM_DATA: &str = "multipart/form-data";
+const DEFAULT_FILE_CONTENT_TYPE: &str = "application/octet-stream";
 
 /// Thin wrapper around a poem::Request that is used to evaluate all binding types when coming from an http gateway.
 pub struct RichRequest {
@@ -32,6 +40,15 @@ pub struct RichRequest {
     pub path_param_extractors: Vec<PathParamExtractor>,
     pub query_info: Vec<QueryInfo>,
     pub auth_data: Option<Value>,
+    pub client_certificate: Option<Value>,
+    // The binary parts of the body, which are `null` in its JSON representation
+    pub binary_fields: Vec<BinaryField>,
+    // The media type of the response, when negotiated by the binding
+    pub negotiated_media_type: Option<ResponseMediaType>,
+    // The fields of the response body requested with the `fields` query parameter
+    pub field_selection: Option<FieldSelection>,
+    // The variants of the experiments of the site the request is assigned to
+    pub experiment_assignments: ExperimentAssignments,
 }
 
 impl RichRequest {
@@ -55,6 +72,15 @@ impl RichRequest {
         Ok(())
     }
 
+    // The name of the API key the request is authenticated with, if any
+    pub fn api_key_name(&self) -> Option<&str> {
+        self.auth_data
+            .as_ref()
+            .filter(|auth_data| auth_data.get("key_id").is_some())
+            .and_then(|auth_data| auth_data.get("name"))
+            .and_then(|name| name.as_str())
+    }
+
     fn path_and_query(&self) -> Result<String, String> {
         self.underlying
             .uri()
@@ -100,8 +126,39 @@ impl RichRequest {
             .map_err(|e| format!("Found malformed headers: [{}]", e.join(",")))
     }
 
+    fn is_multipart(&self) -> bool {
+        self.underlying
+            .content_type()
+            .map(|content_type| content_type.starts_with(MULTIPART_FORM_DATA))
+            .unwrap_or(false)
+    }
+
+    fn is_binary(&self) -> bool {
+        self.underlying
+            .content_type()
+            .map(is_binary_content_type)
+            .unwrap_or(false)
+    }
+
     /// consumes the body of the underlying request
     async fn request_body_value(&mut self) -> Result<RequestBodyValue, String> {
+        if self.is_multipart() {
+            return self.request_multipart_body_value().await;
+        }
+
+        if self.is_binary() {
+            let data = self
+                .underlying
+                .take_body()
+                .into_bytes()
+                .await
+                .map_err(|err| format!("Failed reading request body: {err}"))?;
+
+            self.binary_fields.push(BinaryField::new(&["body"], data));
+
+            return Ok(RequestBodyValue(Value::Null));
+        }
+
         let body = self.underlying.take_body();
 
         let json_request_body: Value = if body.is_empty() {
@@ -119,6 +176,65 @@ impl RichRequest {
         Ok(RequestBodyValue(json_request_body))
     }
 
+    /// consumes the body of the underlying request
+    ///
+    /// Text parts are exposed by their field name, file parts as a record
+    /// of `file_name`, `content_type` and the raw `data` as `list<u8>`,
+    /// kept out of the JSON representation as a binary field.
+    async fn request_multipart_body_value(&mut self) -> Result<RequestBodyValue, String> {
+        let mut body = RequestBody::new(self.underlying.take_body());
+
+        let mut multipart = Multipart::from_request(&self.underlying, &mut body)
+            .await
+            .map_err(|err| format!("Request body parse error: {err}"))?;
+
+        let mut fields = serde_json::Map::new();
+
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|err| format!("Request body parse error: {err}"))?
+        {
+            let Some(name) = field.name().map(|name| name.to_string()) else {
+                continue;
+            };
+
+            let value =
+                match field.file_name().map(|file_name| file_name.to_string()) {
+                    Some(file_name) => {
+                        let content_type = field
+                            .content_type()
+                            .unwrap_or(DEFAULT_FILE_CONTENT_TYPE)
+                            .to_string();
+
+                        let data = field.bytes().await.map_err(|err| {
+                            format!("Failed reading multipart field {name}: {err}")
+                        })?;
+
+                        self.binary_fields
+                            .push(BinaryField::new(&["body", name.as_str(), "data"], data));
+
+                        Value::Object(serde_json::Map::from_iter(vec![
+                            ("file_name".to_string(), Value::String(file_name)),
+                            ("content_type".to_string(), Value::String(content_type)),
+                            ("data".to_string(), Value::Null),
+                        ]))
+                    }
+                    None => {
+                        let text = field.text().await.map_err(|err| {
+                            format!("Failed reading multipart field {name}: {err}")
+                        })?;
+
+                        internal::refine_json_str_value(text)
+                    }
+                };
+
+            fields.insert(name, value);
+        }
+
+        Ok(RequestBodyValue(Value::Object(fields)))
+    }
+
     fn as_basic_json_hashmap(&self) -> Result<serde_json::Map<String, Value>, String> {
         let typed_path_values = self.request_path_values();
         let typed_query_values = self.request_query_values()?;
@@ -144,15 +260,41 @@ impl RichRequest {
 
         let header_value = Value::Object(header_records);
 
+        let cookie_records = self
+            .get_cookie_values()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), Value::String(value.to_string())))
+            .collect::<serde_json::Map<String, Value>>();
+
         let mut basic = serde_json::Map::from_iter(vec![
             ("path".to_string(), merged_request_path_and_query),
             ("headers".to_string(), header_value),
+            ("cookies".to_string(), Value::Object(cookie_records)),
+            (
+                "experiments".to_string(),
+                self.experiment_assignments.to_json(),
+            ),
         ]);
 
+        if let Some(request_id) = request_id(&self.underlying) {
+            basic.insert("id".to_string(), Value::String(request_id.to_string()));
+        };
+
         if let Some(auth_data) = self.auth_data.as_ref() {
             basic.insert("auth".to_string(), auth_data.clone());
         };
 
+        if let Some(client_certificate) = self.client_certificate.as_ref() {
+            basic.insert("client_certificate".to_string(), client_certificate.clone());
+        };
+
+        if let Some(media_type) = self.negotiated_media_type {
+            basic.insert(
+                "media_type".to_string(),
+                Value::String(media_type.to_string()),
+            );
+        };
+
         Ok(basic)
     }
 
@@ -219,8 +361,7 @@ impl RichRequest {
             if let Some(value) = self.underlying.header(header_name) {
                 let parts: Vec<&str> = value.split(';').collect();
                 for part in parts {
-                    let key_value: Vec<&str> = part.split('=').collect();
-                    if let (Some(key), Some(value)) = (key_value.first(), key_value.get(1)) {
+                    if let Some((key, value)) = part.split_once('=') {
                         result.insert(key.trim(), value.trim());
                     }
                 }
@@ -235,6 +376,7 @@ pub struct SplitResolvedRouteEntryResult<Namespace> {
     pub namespace: Namespace,
     pub binding: GatewayBindingCompiled,
     pub middlewares: Option<HttpMiddlewares>,
+    pub route: String,
     pub rich_request: RichRequest,
 }
 
@@ -247,6 +389,7 @@ pub fn split_resolved_route_entry<Namespace>(
     let namespace = entry.route_entry.namespace;
     let binding = entry.route_entry.binding;
     let middlewares = entry.route_entry.middlewares;
+    let route = entry.route_entry.route;
 
     let rich_request = RichRequest {
         underlying: request,
@@ -254,12 +397,18 @@ pub fn split_resolved_route_entry<Namespace>(
         path_param_extractors: entry.route_entry.path_params,
         query_info: entry.route_entry.query_params,
         auth_data: None,
+        client_certificate: None,
+        binary_fields: vec![],
+        negotiated_media_type: None,
+        field_selection: None,
+        experiment_assignments: ExperimentAssignments::default(),
     };
 
     SplitResolvedRouteEntryResult {
         namespace,
         binding,
         middlewares,
+        route,
         rich_request,
     }
 }
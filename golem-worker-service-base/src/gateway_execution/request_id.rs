@@ -0,0 +1,217 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::tracing::set_parent_from_headers;
+use http::HeaderValue;
+use poem::{Body, Endpoint, IntoResponse, Middleware, Request, Response};
+use std::future::Future;
+use std::time::Instant;
+use tracing::{info, info_span, Instrument};
+use uuid::Uuid;
+
+// Identifies a request of the gateway end to end: it is available to Rib as `request.id`, passed
+// to the invoked workers in their invocation context, and returned in the response
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// The attribute of the invocation context with the id of the request
+pub const REQUEST_ID_ATTRIBUTE: &str = "request.id";
+
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+// The id the request came with, if it is a valid one, and a new one otherwise, which is set
+// on the request so that it is passed along with its other headers
+fn ensure_request_id(request: &mut poem::Request) -> String {
+    if let Some(request_id) = request_id(request) {
+        return request_id.to_string();
+    }
+
+    let request_id = Uuid::new_v4().to_string();
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        request.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    request_id
+}
+
+pub fn request_id(request: &poem::Request) -> Option<&str> {
+    request
+        .header(REQUEST_ID_HEADER)
+        .filter(|request_id| is_valid_request_id(request_id))
+}
+
+fn set_response_request_id(response: &mut poem::Response, request_id: &str) {
+    if let Ok(value) = HeaderValue::from_str(request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+}
+
+/// Assigns an id to every request of the gateway, running it in a span with the id, logging its
+/// outcome, and returning the id with the response, in its error message as well
+pub struct RequestIdMiddleware;
+
+impl<E: Endpoint> Middleware<E> for RequestIdMiddleware {
+    type Output = RequestIdEndpoint<E>;
+
+    fn transform(&self, inner: E) -> Self::Output {
+        RequestIdEndpoint { inner }
+    }
+}
+
+pub struct RequestIdEndpoint<E> {
+    inner: E,
+}
+
+impl<E: Endpoint> Endpoint for RequestIdEndpoint<E> {
+    type Output = Response;
+
+    fn call(
+        &self,
+        mut request: Request,
+    ) -> impl Future<Output = poem::Result<Self::Output>> + Send {
+        async move {
+            let request_id = ensure_request_id(&mut request);
+            let method = request.method().clone();
+            let path = request.uri().path().to_string();
+
+            let span = info_span!(
+                "gateway_request",
+                method = %method,
+                path = %path,
+                request_id = %request_id
+            );
+            set_parent_from_headers(&span, request.headers());
+
+            let start = Instant::now();
+
+            let response = match self.inner.call(request).instrument(span.clone()).await {
+                Ok(output) => output.into_response(),
+                Err(error) => error.into_response(),
+            };
+
+            let mut response = with_request_id_in_error_message(response, &request_id).await;
+            set_response_request_id(&mut response, &request_id);
+
+            span.in_scope(|| {
+                info!(
+                    method = %method,
+                    path = %path,
+                    status = response.status().as_u16(),
+                    elapsed_ms = start.elapsed().as_millis() as u64,
+                    "Gateway request completed"
+                )
+            });
+
+            Ok(response)
+        }
+    }
+}
+
+// The errors of the gateway itself are bare messages without a content type, unlike the responses
+// of the bindings and the error pages, so only those get the id appended, for it to be quoted
+// when reporting them
+async fn with_request_id_in_error_message(response: Response, request_id: &str) -> Response {
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) || response.content_type().is_some()
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match body.into_bytes().await {
+        Ok(bytes) => match std::str::from_utf8(&bytes) {
+            Ok(message) if !message.is_empty() => {
+                parts.headers.remove(http::header::CONTENT_LENGTH);
+                Body::from_string(format!("{message} (request id: {request_id})"))
+            }
+            _ => Body::from_bytes(bytes),
+        },
+        Err(_) => Body::empty(),
+    };
+    Response::from_parts(parts, body)
+}
+
+// Incoming ids end up in logs and in the headers of other requests, so they are kept short and
+// without whitespace or control characters
+fn is_valid_request_id(request_id: &str) -> bool {
+    !request_id.is_empty()
+        && request_id.len() <= MAX_REQUEST_ID_LENGTH
+        && request_id.chars().all(|c| c.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::StatusCode;
+    use poem::endpoint::make_sync;
+    use poem::EndpointExt;
+    use test_r::test;
+
+    #[test]
+    fn valid_incoming_request_ids_are_kept_and_others_replaced() {
+        let mut request = poem::Request::builder()
+            .header(REQUEST_ID_HEADER, "checkout-42")
+            .finish();
+        assert_eq!(ensure_request_id(&mut request), "checkout-42");
+
+        let mut request = poem::Request::builder()
+            .header(REQUEST_ID_HEADER, "a".repeat(MAX_REQUEST_ID_LENGTH + 1))
+            .finish();
+        let request_id = ensure_request_id(&mut request);
+        assert!(Uuid::parse_str(&request_id).is_ok());
+        assert_eq!(request.header(REQUEST_ID_HEADER), Some(request_id.as_str()));
+
+        let mut request = poem::Request::builder().finish();
+        let request_id = ensure_request_id(&mut request);
+        assert_eq!(super::request_id(&request), Some(request_id.as_str()));
+    }
+
+    #[test]
+    async fn request_ids_are_returned_and_added_to_gateway_error_messages() {
+        let endpoint = make_sync(|request: Request| match request.uri().path() {
+            "/error" => Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body("Route not found"),
+            "/page" => Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .content_type("application/json")
+                .body(r#"{"error":"Not Found"}"#),
+            _ => Response::builder().body("ok"),
+        })
+        .with(RequestIdMiddleware);
+
+        let request = |path: &str| {
+            Request::builder()
+                .uri_str(path)
+                .header(REQUEST_ID_HEADER, "checkout-42")
+                .finish()
+        };
+
+        let response = endpoint.call(request("/error")).await.unwrap();
+        assert_eq!(response.header(REQUEST_ID_HEADER), Some("checkout-42"));
+        assert_eq!(
+            response.into_body().into_string().await.unwrap(),
+            "Route not found (request id: checkout-42)"
+        );
+
+        let response = endpoint.call(request("/page")).await.unwrap();
+        assert_eq!(response.header(REQUEST_ID_HEADER), Some("checkout-42"));
+        assert_eq!(
+            response.into_body().into_string().await.unwrap(),
+            r#"{"error":"Not Found"}"#
+        );
+
+        let response = endpoint.call(request("/")).await.unwrap();
+        assert_eq!(response.header(REQUEST_ID_HEADER), Some("checkout-42"));
+        assert_eq!(response.into_body().into_string().await.unwrap(), "ok");
+    }
+}
@@ -0,0 +1,742 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_middleware::HttpResponseCache;
+use async_trait::async_trait;
+use bytes::Bytes;
+use fred::types::Expiration;
+use golem_common::redis::RedisPool;
+use golem_common::SafeDisplay;
+use http::{HeaderName, HeaderValue, StatusCode};
+use poem::Body;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::error;
+
+// Responses with a larger body are served without being cached
+const MAX_CACHED_BODY_BYTES: usize = 1024 * 1024;
+
+// Cached responses kept in memory, the least recently used ones are evicted beyond it
+const MAX_IN_MEMORY_RESPONSES: usize = 10_000;
+
+// Tells whether the response was served from the cache of the gateway
+pub const CACHE_STATUS_HEADER: &str = "x-golem-cache";
+
+#[async_trait]
+pub trait ResponseCache {
+    async fn get(
+        &self,
+        authority: &str,
+        route: &str,
+        key: &str,
+    ) -> Result<Option<CachedResponse>, ResponseCacheError>;
+
+    async fn insert(
+        &self,
+        authority: &str,
+        route: &str,
+        key: &str,
+        response: &CachedResponse,
+        ttl: Duration,
+    ) -> Result<(), ResponseCacheError>;
+
+    // Drops the cached responses of the routes of a site, or only of the routes
+    // with the given path (such as `/users/{id}`), returning how many routes were invalidated
+    async fn invalidate(
+        &self,
+        authority: &str,
+        path: Option<&str>,
+    ) -> Result<u64, ResponseCacheError>;
+}
+
+pub type ResponseCacheStore = Arc<dyn ResponseCache + Send + Sync>;
+
+#[derive(Debug, Clone)]
+pub enum ResponseCacheError {
+    InternalError(String),
+}
+
+impl SafeDisplay for ResponseCacheError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            ResponseCacheError::InternalError(e) => format!("Internal error: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, bincode::Encode, bincode::Decode)]
+pub struct CachedResponse {
+    pub status: u16,
+    pub headers: Vec<(String, Vec<u8>)>,
+    pub body: Vec<u8>,
+    pub cached_at_ms: u64,
+}
+
+impl CachedResponse {
+    // Buffers the response if it can be cached, giving back the response to serve either way
+    pub async fn from_response(
+        response: poem::Response,
+    ) -> (poem::Response, Option<CachedResponse>) {
+        if !is_cacheable(&response) {
+            return (response, None);
+        }
+
+        let (parts, body) = response.into_parts();
+
+        let body = match body.into_bytes().await {
+            Ok(body) => body,
+            Err(err) => {
+                error!("Failed reading the response body to cache: {}", err);
+                let response = poem::Response::from_parts(parts, Body::empty());
+                return (response, None);
+            }
+        };
+
+        let cached = (body.len() <= MAX_CACHED_BODY_BYTES).then(|| CachedResponse {
+            status: parts.status.as_u16(),
+            headers: parts
+                .headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+                .collect(),
+            body: body.to_vec(),
+            cached_at_ms: now_ms(),
+        });
+
+        (
+            poem::Response::from_parts(parts, Body::from_bytes(body)),
+            cached,
+        )
+    }
+
+    pub fn to_response(&self) -> poem::Response {
+        let mut response = poem::Response::builder()
+            .status(StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK))
+            .body(Body::from_bytes(Bytes::from(self.body.clone())));
+
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) =
+                (HeaderName::from_str(name), HeaderValue::from_bytes(value))
+            {
+                response.headers_mut().append(name, value);
+            }
+        }
+
+        let age_secs = now_ms().saturating_sub(self.cached_at_ms) / 1000;
+        response
+            .headers_mut()
+            .insert(http::header::AGE, age_secs.into());
+        response
+            .headers_mut()
+            .insert(CACHE_STATUS_HEADER, HeaderValue::from_static("hit"));
+
+        response
+    }
+}
+
+// Only plain successful responses, which are the same for every client, are cached
+fn is_cacheable(response: &poem::Response) -> bool {
+    let headers = response.headers();
+
+    let cache_control_forbids = headers
+        .get_all(http::header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|directive| {
+            let directive = directive.trim().to_ascii_lowercase();
+            directive == "no-store" || directive == "private"
+        });
+
+    let content_length_exceeds = headers
+        .get(http::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok())
+        .is_some_and(|length| length > MAX_CACHED_BODY_BYTES);
+
+    let is_event_stream = headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+
+    response.status() == StatusCode::OK
+        && !headers.contains_key(http::header::SET_COOKIE)
+        && !cache_control_forbids
+        && !content_length_exceeds
+        && !is_event_stream
+}
+
+// The key of the cached response of a request within its route. Requests to the same path with
+// the same query string share their responses, unless they differ in the headers the route
+// varies on.
+pub fn response_cache_key(response_cache: &HttpResponseCache, request: &poem::Request) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(request.uri().path().as_bytes());
+    hasher.update(b"\0?");
+    hasher.update(request.uri().query().unwrap_or_default().as_bytes());
+
+    for name in response_cache.vary_by().unwrap_or_default() {
+        hasher.update(b"\0");
+        hasher.update(name.as_str().as_bytes());
+        for value in request.headers().get_all(&name) {
+            hasher.update(b"\0:");
+            hasher.update(value.as_bytes());
+        }
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+// Whether the route, such as `GET /users/{id}`, is for the given path
+fn route_has_path(route: &str, path: &str) -> bool {
+    route
+        .split_once(' ')
+        .is_some_and(|(_, route_path)| route_path == path)
+}
+
+#[derive(Debug, Clone)]
+struct InMemoryCachedResponse {
+    route: String,
+    response: CachedResponse,
+    expires_at_ms: u64,
+    // When the response was last inserted or served, to evict the least recently used ones
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct InMemoryResponses {
+    sites: HashMap<String, HashMap<String, InMemoryCachedResponse>>,
+    len: usize,
+    clock: u64,
+}
+
+impl InMemoryResponses {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn recount(&mut self) {
+        self.sites.retain(|_, site| !site.is_empty());
+        self.len = self.sites.values().map(|site| site.len()).sum();
+    }
+
+    // Makes room for a new response: drops the expired responses first, then the least recently
+    // used ones, keeping a tenth of the capacity free so that it is not done on every insert
+    fn make_room(&mut self, capacity: usize, now_ms: u64) {
+        if self.len < capacity {
+            return;
+        }
+
+        for site in self.sites.values_mut() {
+            site.retain(|_, cached| cached.expires_at_ms > now_ms);
+        }
+        self.recount();
+
+        if self.len < capacity {
+            return;
+        }
+
+        let target = capacity - (capacity / 10).max(1);
+        let mut by_last_use = self
+            .sites
+            .iter()
+            .flat_map(|(authority, site)| {
+                site.iter()
+                    .map(move |(key, cached)| (cached.last_used, authority.clone(), key.clone()))
+            })
+            .collect::<Vec<_>>();
+        by_last_use.sort();
+
+        for (_, authority, key) in by_last_use.into_iter().take(self.len - target) {
+            if let Some(site) = self.sites.get_mut(&authority) {
+                site.remove(&key);
+            }
+        }
+        self.recount();
+    }
+}
+
+/// Keeps the responses in the memory of a single gateway instance,
+/// used when there is no Redis shared by the replicas.
+/// At most a fixed number of responses are kept, evicting the least recently used ones.
+pub struct InMemoryResponseCache {
+    responses: Mutex<InMemoryResponses>,
+    capacity: usize,
+}
+
+impl Default for InMemoryResponseCache {
+    fn default() -> Self {
+        Self::with_capacity(MAX_IN_MEMORY_RESPONSES)
+    }
+}
+
+impl InMemoryResponseCache {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            responses: Mutex::new(InMemoryResponses::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn entry_key(route: &str, key: &str) -> String {
+        format!("{}:{}", route, key)
+    }
+}
+
+#[async_trait]
+impl ResponseCache for InMemoryResponseCache {
+    async fn get(
+        &self,
+        authority: &str,
+        route: &str,
+        key: &str,
+    ) -> Result<Option<CachedResponse>, ResponseCacheError> {
+        let now_ms = now_ms();
+        let mut responses = self.responses.lock().unwrap();
+        let last_used = responses.tick();
+
+        Ok(responses
+            .sites
+            .get_mut(authority)
+            .and_then(|site| site.get_mut(&Self::entry_key(route, key)))
+            .filter(|cached| cached.expires_at_ms > now_ms)
+            .map(|cached| {
+                cached.last_used = last_used;
+                cached.response.clone()
+            }))
+    }
+
+    async fn insert(
+        &self,
+        authority: &str,
+        route: &str,
+        key: &str,
+        response: &CachedResponse,
+        ttl: Duration,
+    ) -> Result<(), ResponseCacheError> {
+        let now_ms = now_ms();
+        let entry_key = Self::entry_key(route, key);
+        let mut responses = self.responses.lock().unwrap();
+
+        let exists = responses
+            .sites
+            .get(authority)
+            .is_some_and(|site| site.contains_key(&entry_key));
+        if !exists {
+            responses.make_room(self.capacity, now_ms);
+        }
+
+        let last_used = responses.tick();
+        let previous = responses
+            .sites
+            .entry(authority.to_string())
+            .or_default()
+            .insert(
+                entry_key,
+                InMemoryCachedResponse {
+                    route: route.to_string(),
+                    response: response.clone(),
+                    expires_at_ms: now_ms + ttl.as_millis() as u64,
+                    last_used,
+                },
+            );
+        if previous.is_none() {
+            responses.len += 1;
+        }
+
+        Ok(())
+    }
+
+    async fn invalidate(
+        &self,
+        authority: &str,
+        path: Option<&str>,
+    ) -> Result<u64, ResponseCacheError> {
+        let mut responses = self.responses.lock().unwrap();
+
+        let Some(site) = responses.sites.get_mut(authority) else {
+            return Ok(0);
+        };
+
+        let mut invalidated_routes = site
+            .values()
+            .map(|cached| cached.route.clone())
+            .filter(|route| path.is_none_or(|path| route_has_path(route, path)))
+            .collect::<Vec<_>>();
+        invalidated_routes.sort();
+        invalidated_routes.dedup();
+
+        site.retain(|_, cached| !invalidated_routes.contains(&cached.route));
+        responses.recount();
+
+        Ok(invalidated_routes.len() as u64)
+    }
+}
+
+/// Keeps the responses in Redis, so that all the replicas of the gateway share the same cache.
+///
+/// Along with the responses, the routes of each site with cached responses and the keys of the
+/// responses of each route are kept, so that they can be invalidated without scanning Redis.
+#[derive(Clone)]
+pub struct RedisResponseCache {
+    redis: RedisPool,
+}
+
+impl RedisResponseCache {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn response_key(authority: &str, route: &str, key: &str) -> String {
+        format!("gateway_response_cache:{}:{}:{}", authority, route, key)
+    }
+
+    fn route_keys_key(authority: &str, route: &str) -> String {
+        format!("gateway_response_cache_keys:{}:{}", authority, route)
+    }
+
+    fn routes_key(authority: &str) -> String {
+        format!("gateway_response_cache_routes:{}", authority)
+    }
+}
+
+#[async_trait]
+impl ResponseCache for RedisResponseCache {
+    async fn get(
+        &self,
+        authority: &str,
+        route: &str,
+        key: &str,
+    ) -> Result<Option<CachedResponse>, ResponseCacheError> {
+        let cached: Option<Bytes> = self
+            .redis
+            .with("gateway_response_cache", "get")
+            .get(Self::response_key(authority, route, key))
+            .await
+            .map_err(|e| {
+                error!("Failed to get a cached response from Redis: {}", e);
+                ResponseCacheError::InternalError(e.to_string())
+            })?;
+
+        cached
+            .map(|cached| {
+                golem_common::serialization::deserialize(&cached)
+                    .map_err(ResponseCacheError::InternalError)
+            })
+            .transpose()
+    }
+
+    async fn insert(
+        &self,
+        authority: &str,
+        route: &str,
+        key: &str,
+        response: &CachedResponse,
+        ttl: Duration,
+    ) -> Result<(), ResponseCacheError> {
+        let serialized = golem_common::serialization::serialize(response)
+            .map_err(ResponseCacheError::InternalError)?;
+
+        let response_key = Self::response_key(authority, route, key);
+        let route_keys_key = Self::route_keys_key(authority, route);
+        let ttl_secs = ttl.as_secs().max(1) as i64;
+
+        let result: Result<(), fred::error::RedisError> = async {
+            self.redis
+                .with("gateway_response_cache", "insert")
+                .set::<(), _, _>(
+                    &response_key,
+                    serialized,
+                    Some(Expiration::EX(ttl_secs)),
+                    None,
+                    false,
+                )
+                .await?;
+
+            self.redis
+                .with("gateway_response_cache", "insert")
+                .sadd::<(), _, _>(&route_keys_key, response_key.as_str())
+                .await?;
+
+            // The keys of a route are kept as long as its most recently cached response
+            self.redis
+                .with("gateway_response_cache", "insert")
+                .expire::<(), _>(&route_keys_key, ttl_secs)
+                .await?;
+
+            self.redis
+                .with("gateway_response_cache", "insert")
+                .sadd::<(), _, _>(Self::routes_key(authority), route)
+                .await
+        }
+        .await;
+
+        result.map_err(|e| {
+            error!("Failed to cache a response in Redis: {}", e);
+            ResponseCacheError::InternalError(e.to_string())
+        })
+    }
+
+    async fn invalidate(
+        &self,
+        authority: &str,
+        path: Option<&str>,
+    ) -> Result<u64, ResponseCacheError> {
+        let routes_key = Self::routes_key(authority);
+
+        let result: Result<u64, fred::error::RedisError> = async {
+            let routes: Vec<String> = self
+                .redis
+                .with("gateway_response_cache", "invalidate")
+                .smembers(&routes_key)
+                .await?;
+
+            let routes = routes
+                .into_iter()
+                .filter(|route| path.is_none_or(|path| route_has_path(route, path)))
+                .collect::<Vec<_>>();
+
+            for route in &routes {
+                let route_keys_key = Self::route_keys_key(authority, route);
+
+                let mut keys: Vec<String> = self
+                    .redis
+                    .with("gateway_response_cache", "invalidate")
+                    .smembers(&route_keys_key)
+                    .await?;
+                keys.push(route_keys_key);
+
+                self.redis
+                    .with("gateway_response_cache", "invalidate")
+                    .del_many::<(), _>(keys)
+                    .await?;
+
+                self.redis
+                    .with("gateway_response_cache", "invalidate")
+                    .srem::<(), _, _>(&routes_key, route.as_str())
+                    .await?;
+            }
+
+            Ok(routes.len() as u64)
+        }
+        .await;
+
+        result.map_err(|e| {
+            error!("Failed to invalidate cached responses in Redis: {}", e);
+            ResponseCacheError::InternalError(e.to_string())
+        })
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    async fn cached_responses_vary_and_are_invalidated_per_route() {
+        let response_cache = HttpResponseCache {
+            ttl_seconds: 30,
+            vary: vec!["header:accept".to_string()],
+        };
+
+        let request = |uri: &str, accept: &str| {
+            poem::Request::builder()
+                .uri(uri.parse().unwrap())
+                .header(http::header::ACCEPT, accept)
+                .finish()
+        };
+
+        let key = response_cache_key(&response_cache, &request("/users/1?a=1", "text/html"));
+        assert_eq!(
+            key,
+            response_cache_key(&response_cache, &request("/users/1?a=1", "text/html"))
+        );
+        assert_ne!(
+            key,
+            response_cache_key(&response_cache, &request("/users/1?a=2", "text/html"))
+        );
+        assert_ne!(
+            key,
+            response_cache_key(
+                &response_cache,
+                &request("/users/1?a=1", "application/json")
+            )
+        );
+        assert_ne!(
+            key,
+            response_cache_key(&response_cache, &request("/users/2?a=1", "text/html"))
+        );
+
+        let response = poem::Response::builder()
+            .status(StatusCode::OK)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body("{}");
+        let (response, cached) = CachedResponse::from_response(response).await;
+        assert_eq!(response.into_body().into_string().await.unwrap(), "{}");
+        let cached = cached.unwrap();
+
+        let (_, not_cached) = CachedResponse::from_response(
+            poem::Response::builder()
+                .status(StatusCode::OK)
+                .header(http::header::CACHE_CONTROL, "private, max-age=60")
+                .body("{}"),
+        )
+        .await;
+        assert_eq!(not_cached, None);
+
+        let store = InMemoryResponseCache::default();
+        let route = "GET /users/{id}";
+        let ttl = Duration::from_secs(30);
+        store
+            .insert("api.com", route, &key, &cached, ttl)
+            .await
+            .unwrap();
+        store
+            .insert("api.com", "GET /orders", &key, &cached, ttl)
+            .await
+            .unwrap();
+
+        let hit = store.get("api.com", route, &key).await.unwrap().unwrap();
+        let hit = hit.to_response();
+        assert_eq!(hit.headers().get(CACHE_STATUS_HEADER).unwrap(), "hit");
+        assert_eq!(
+            hit.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+
+        assert_eq!(
+            store
+                .invalidate("api.com", Some("/users/{id}"))
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(store.get("api.com", route, &key).await.unwrap(), None);
+        assert!(store
+            .get("api.com", "GET /orders", &key)
+            .await
+            .unwrap()
+            .is_some());
+
+        assert_eq!(store.invalidate("api.com", None).await.unwrap(), 1);
+        assert_eq!(
+            store.get("api.com", "GET /orders", &key).await.unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn cached_responses_always_vary_by_query() {
+        let response_cache = HttpResponseCache {
+            ttl_seconds: 30,
+            vary: vec![],
+        };
+        let legacy_response_cache = HttpResponseCache {
+            ttl_seconds: 30,
+            vary: vec!["query".to_string()],
+        };
+
+        let request = |uri: &str| poem::Request::builder().uri(uri.parse().unwrap()).finish();
+
+        let key = response_cache_key(&response_cache, &request("/search?q=golem"));
+
+        assert_ne!(
+            key,
+            response_cache_key(&response_cache, &request("/search?q=wasm"))
+        );
+        assert_ne!(
+            key,
+            response_cache_key(&response_cache, &request("/search"))
+        );
+        assert_eq!(
+            key,
+            response_cache_key(&legacy_response_cache, &request("/search?q=golem"))
+        );
+    }
+
+    #[test]
+    async fn in_memory_cache_evicts_the_least_recently_used_responses() {
+        let store = InMemoryResponseCache::with_capacity(10);
+        let route = "GET /users/{id}";
+        let ttl = Duration::from_secs(30);
+        let cached = CachedResponse {
+            status: 200,
+            headers: vec![],
+            body: b"{}".to_vec(),
+            cached_at_ms: now_ms(),
+        };
+
+        for i in 0..10 {
+            store
+                .insert("api.com", route, &i.to_string(), &cached, ttl)
+                .await
+                .unwrap();
+        }
+
+        // Serving the first response makes the second one the least recently used
+        assert!(store.get("api.com", route, "0").await.unwrap().is_some());
+
+        // Replacing a cached response does not need room
+        store
+            .insert("api.com", route, "5", &cached, ttl)
+            .await
+            .unwrap();
+        assert_eq!(store.responses.lock().unwrap().len, 10);
+
+        store
+            .insert("other.com", route, "new", &cached, ttl)
+            .await
+            .unwrap();
+
+        let responses = store.responses.lock().unwrap();
+        assert_eq!(
+            responses.len,
+            responses
+                .sites
+                .values()
+                .map(|site| site.len())
+                .sum::<usize>()
+        );
+        assert_eq!(responses.len, 10);
+        drop(responses);
+
+        assert!(store.get("api.com", route, "1").await.unwrap().is_none());
+        assert!(store.get("api.com", route, "0").await.unwrap().is_some());
+        assert!(store
+            .get("other.com", route, "new")
+            .await
+            .unwrap()
+            .is_some());
+
+        for i in 0..100 {
+            store
+                .insert("api.com", "GET /orders", &i.to_string(), &cached, ttl)
+                .await
+                .unwrap();
+            assert!(store.responses.lock().unwrap().len <= 10);
+        }
+    }
+}
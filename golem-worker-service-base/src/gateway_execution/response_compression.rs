@@ -0,0 +1,275 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_middleware::{CompressionAlgorithm, HttpCompression};
+use async_compression::tokio::bufread::{BrotliEncoder, GzipEncoder};
+use async_compression::Level;
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use http::header::{CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, VARY};
+use http::{HeaderValue, StatusCode};
+use poem::Body;
+use tokio::io::BufReader;
+
+// Brotli at its default (maximal) quality is too slow to compress the responses on the fly
+const BROTLI_QUALITY: i32 = 4;
+
+// Compresses the body of the response with the preferred algorithm accepted by the client, while
+// it is streamed. Responses without a Content-Length are read up to the minimal size first, to
+// tell whether they are large enough to be compressed.
+pub async fn compress_response(
+    mut response: poem::Response,
+    compression: &HttpCompression,
+    accept_encoding: Option<&str>,
+) -> poem::Response {
+    if !compression.enabled || !is_compressible(&response, compression) {
+        return response;
+    }
+
+    // From now on the response depends on the encodings accepted by the client
+    response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept-encoding"));
+
+    let algorithm = compression
+        .algorithms()
+        .ok()
+        .and_then(|algorithms| negotiate(&algorithms, accept_encoding));
+
+    let Some(algorithm) = algorithm else {
+        return response;
+    };
+
+    let content_length = response
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+
+    let body = response.take_body();
+
+    let body = match content_length {
+        Some(length) if length < compression.min_size_bytes => {
+            response.set_body(body);
+            return response;
+        }
+        Some(_) => body,
+        None => match read_start(body, compression.min_size_bytes).await {
+            BodyStart::Whole(bytes) => {
+                response.set_body(Body::from_bytes(bytes));
+                return response;
+            }
+            BodyStart::Partial(body) => body,
+        },
+    };
+
+    let reader = BufReader::new(body.into_async_read());
+    let body = match algorithm {
+        CompressionAlgorithm::Gzip => Body::from_async_read(GzipEncoder::new(reader)),
+        CompressionAlgorithm::Brotli => Body::from_async_read(BrotliEncoder::with_quality(
+            reader,
+            Level::Precise(BROTLI_QUALITY),
+        )),
+    };
+
+    let headers = response.headers_mut();
+    headers.remove(CONTENT_LENGTH);
+    if let Ok(content_encoding) = HeaderValue::from_str(&algorithm.to_string()) {
+        headers.insert(CONTENT_ENCODING, content_encoding);
+    }
+
+    // The compressed body is no longer byte for byte the one a strong entity tag stands for
+    let weak_etag = headers
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"))
+        .and_then(|etag| HeaderValue::from_str(&format!("W/{}", etag)).ok());
+    if let Some(weak_etag) = weak_etag {
+        headers.insert(ETAG, weak_etag);
+    }
+
+    response.set_body(body);
+    response
+}
+
+// Upgraded connections, responses without a body and partial or already encoded bodies are left
+// as they are, as are the ones the origin asked not to transform
+fn is_compressible(response: &poem::Response, compression: &HttpCompression) -> bool {
+    let status = response.status();
+    if status.is_informational()
+        || status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED
+        || status == StatusCode::PARTIAL_CONTENT
+    {
+        return false;
+    }
+
+    let headers = response.headers();
+
+    if headers.contains_key(CONTENT_ENCODING) {
+        return false;
+    }
+
+    let no_transform = headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.to_lowercase().contains("no-transform"));
+
+    if no_transform {
+        return false;
+    }
+
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|content_type| compression.compresses_content_type(content_type))
+}
+
+// The first of the algorithms with the highest quality in the Accept-Encoding of the request
+fn negotiate(
+    algorithms: &[CompressionAlgorithm],
+    accept_encoding: Option<&str>,
+) -> Option<CompressionAlgorithm> {
+    let accepted = accept_encoding?
+        .split(',')
+        .filter_map(|coding| {
+            let mut parts = coding.split(';');
+            let name = parts.next()?.trim().to_lowercase();
+            let quality = parts
+                .find_map(|parameter| parameter.trim().strip_prefix("q="))
+                .map(|quality| quality.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            (!name.is_empty()).then_some((name, quality))
+        })
+        .collect::<Vec<_>>();
+
+    let quality_of = |algorithm: &CompressionAlgorithm| {
+        let name = algorithm.to_string();
+        accepted
+            .iter()
+            .find(|(coding, _)| *coding == name)
+            .or_else(|| accepted.iter().find(|(coding, _)| coding == "*"))
+            .map(|(_, quality)| *quality)
+            .unwrap_or(0.0)
+    };
+
+    let mut best: Option<(CompressionAlgorithm, f32)> = None;
+    for algorithm in algorithms {
+        let quality = quality_of(algorithm);
+        if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+            best = Some((*algorithm, quality));
+        }
+    }
+
+    best.map(|(algorithm, _)| algorithm)
+}
+
+enum BodyStart {
+    // The body ended before reaching the minimal size
+    Whole(Bytes),
+    // The body with at least the minimal size, the part already read included
+    Partial(Body),
+}
+
+async fn read_start(body: Body, min_size_bytes: u64) -> BodyStart {
+    let mut stream = body.into_bytes_stream();
+    let mut start = BytesMut::new();
+
+    while (start.len() as u64) < min_size_bytes {
+        match stream.next().await {
+            Some(Ok(chunk)) => start.extend_from_slice(&chunk),
+            // The error is left for the client, after the part already read
+            Some(Err(err)) => {
+                return BodyStart::Partial(Body::from_bytes_stream(futures::stream::iter(vec![
+                    Ok(start.freeze()),
+                    Err(err),
+                ])))
+            }
+            None => return BodyStart::Whole(start.freeze()),
+        }
+    }
+
+    let start = start.freeze();
+    BodyStart::Partial(Body::from_bytes_stream(
+        futures::stream::once(async move { Ok::<_, std::io::Error>(start) }).chain(stream),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_compression::tokio::bufread::GzipDecoder;
+    use test_r::test;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn the_preferred_accepted_algorithm_is_negotiated() {
+        let algorithms = [CompressionAlgorithm::Brotli, CompressionAlgorithm::Gzip];
+
+        assert_eq!(
+            negotiate(&algorithms, Some("gzip, deflate, br")),
+            Some(CompressionAlgorithm::Brotli)
+        );
+        assert_eq!(
+            negotiate(&algorithms, Some("gzip, br;q=0.5")),
+            Some(CompressionAlgorithm::Gzip)
+        );
+        assert_eq!(
+            negotiate(&algorithms, Some("*;q=0.1")),
+            Some(CompressionAlgorithm::Brotli)
+        );
+        assert_eq!(negotiate(&algorithms, Some("br;q=0, deflate")), None);
+        assert_eq!(negotiate(&algorithms, None), None);
+    }
+
+    #[test]
+    async fn large_enough_responses_of_the_content_types_are_compressed() {
+        let compression = HttpCompression {
+            min_size_bytes: 64,
+            ..HttpCompression::default()
+        };
+        let json = format!("[{}]", vec!["{\"item\":\"cart\"}"; 100].join(","));
+
+        let response = poem::Response::builder()
+            .content_type("application/json")
+            .body(Body::from_string(json.clone()));
+        let mut response = compress_response(response, &compression, Some("gzip")).await;
+
+        assert_eq!(response.headers().get(CONTENT_ENCODING).unwrap(), "gzip");
+        assert_eq!(response.headers().get(VARY).unwrap(), "accept-encoding");
+
+        let compressed = response.take_body().into_bytes().await.unwrap();
+        let mut decompressed = String::new();
+        GzipDecoder::new(&compressed[..])
+            .read_to_string(&mut decompressed)
+            .await
+            .unwrap();
+        assert_eq!(decompressed, json);
+
+        let response = poem::Response::builder()
+            .content_type("application/json")
+            .body(Body::from_string("[]".to_string()));
+        let response = compress_response(response, &compression, Some("gzip")).await;
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(response.into_body().into_string().await.unwrap(), "[]");
+
+        let response = poem::Response::builder()
+            .content_type("text/event-stream")
+            .body(Body::from_string(json.clone()));
+        let response = compress_response(response, &compression, Some("gzip")).await;
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+    }
+}
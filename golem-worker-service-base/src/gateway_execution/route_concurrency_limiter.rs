@@ -0,0 +1,185 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_middleware::HttpConcurrencyLimit;
+use poem::Body;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Keeps track of the requests in flight of the routes with a concurrency limit.
+///
+/// Limits are enforced per gateway instance, as the slots of a route are held for the whole
+/// processing of its requests. Routes are identified by their site and `METHOD /path`, and
+/// changing the limit of a route (by redeploying its definition) starts counting anew.
+#[derive(Default)]
+pub struct RouteConcurrencyLimiter {
+    routes: Mutex<HashMap<(String, String), Arc<RouteSlots>>>,
+}
+
+struct RouteSlots {
+    limit: HttpConcurrencyLimit,
+    in_flight: Arc<Semaphore>,
+    queued: AtomicU32,
+}
+
+// Held by a request for as long as it is processed
+pub struct RoutePermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl RouteConcurrencyLimiter {
+    // Waits for a slot of the route, returning None if the request has to be shed
+    pub async fn acquire(
+        &self,
+        site: &str,
+        route: &str,
+        limit: &HttpConcurrencyLimit,
+    ) -> Option<RoutePermit> {
+        let slots = self.slots(site, route, limit);
+
+        if let Ok(permit) = slots.in_flight.clone().try_acquire_owned() {
+            return Some(RoutePermit { _permit: permit });
+        }
+
+        let _queued = QueuedRequest::enter(&slots)?;
+
+        match tokio::time::timeout(
+            limit.queue_timeout(),
+            slots.in_flight.clone().acquire_owned(),
+        )
+        .await
+        {
+            Ok(Ok(permit)) => Some(RoutePermit { _permit: permit }),
+            _ => None,
+        }
+    }
+
+    fn slots(&self, site: &str, route: &str, limit: &HttpConcurrencyLimit) -> Arc<RouteSlots> {
+        let mut routes = self.routes.lock().unwrap();
+        let key = (site.to_string(), route.to_string());
+
+        match routes.get(&key) {
+            Some(slots) if &slots.limit == limit => slots.clone(),
+            _ => {
+                let slots = Arc::new(RouteSlots {
+                    limit: limit.clone(),
+                    in_flight: Arc::new(Semaphore::new(limit.max_in_flight as usize)),
+                    queued: AtomicU32::new(0),
+                });
+                routes.insert(key, slots.clone());
+                slots
+            }
+        }
+    }
+}
+
+// A place in the queue of a route, released when the request stops waiting,
+// including when it is cancelled because the client went away
+struct QueuedRequest<'a> {
+    slots: &'a RouteSlots,
+}
+
+impl<'a> QueuedRequest<'a> {
+    fn enter(slots: &'a RouteSlots) -> Option<Self> {
+        slots
+            .queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                (queued < slots.limit.max_queued).then_some(queued + 1)
+            })
+            .ok()
+            .map(|_| QueuedRequest { slots })
+    }
+}
+
+impl Drop for QueuedRequest<'_> {
+    fn drop(&mut self) {
+        self.slots.queued.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+pub fn shed_response(limit: &HttpConcurrencyLimit) -> poem::Response {
+    let body = limit
+        .shed_body
+        .clone()
+        .unwrap_or_else(|| "Too many concurrent requests".to_string());
+
+    let mut response = poem::Response::builder()
+        .status(limit.shed_status())
+        .body(Body::from_string(body));
+
+    if let Some(retry_after) = limit.retry_after {
+        response
+            .headers_mut()
+            .insert(http::header::RETRY_AFTER, retry_after.into());
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    async fn requests_over_the_limit_are_queued_then_shed() {
+        let limiter = Arc::new(RouteConcurrencyLimiter::default());
+        let limit = HttpConcurrencyLimit {
+            max_in_flight: 1,
+            max_queued: 1,
+            queue_timeout_ms: Some(5000),
+            shed_status: None,
+            shed_body: None,
+            retry_after: Some(2),
+        };
+
+        let first = limiter.acquire("site", "GET /cart", &limit).await.unwrap();
+
+        let queued = {
+            let limiter = limiter.clone();
+            let limit = limit.clone();
+            tokio::spawn(
+                async move { limiter.acquire("site", "GET /cart", &limit).await.is_some() },
+            )
+        };
+
+        // Waits for the second request to enter the queue
+        while limiter
+            .slots("site", "GET /cart", &limit)
+            .queued
+            .load(Ordering::Acquire)
+            == 0
+        {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(limiter.acquire("site", "GET /cart", &limit).await.is_none());
+        assert!(limiter
+            .acquire("site", "GET /other", &limit)
+            .await
+            .is_some());
+
+        drop(first);
+        assert!(queued.await.unwrap());
+
+        let response = shed_response(&limit);
+        assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers().get(http::header::RETRY_AFTER).unwrap(),
+            "2"
+        );
+    }
+}
@@ -0,0 +1,171 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::StatusCode;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const ROUTE_METRICS_WINDOW_MINUTES: u64 = 15;
+
+/// Counts the responses of the routes of every site over the last minutes, for their status pages.
+///
+/// Counts are kept per gateway instance in one-minute buckets, and responses with a server error
+/// status are counted as errors. Routes are identified by their site and `METHOD /path`.
+pub struct RouteMetrics {
+    started_at: Instant,
+    routes: Mutex<HashMap<(String, String), VecDeque<MinuteCounts>>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MinuteCounts {
+    minute: u64,
+    requests: u64,
+    errors: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteStatus {
+    pub route: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+}
+
+impl Default for RouteMetrics {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            routes: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl RouteMetrics {
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    pub fn record(&self, site: &str, route: &str, status: StatusCode) {
+        self.record_at(site, route, status.is_server_error(), self.current_minute());
+    }
+
+    // The routes of the site with responses in the window, sorted by route
+    pub fn routes(&self, site: &str) -> Vec<RouteStatus> {
+        self.routes_at(site, self.current_minute())
+    }
+
+    fn current_minute(&self) -> u64 {
+        self.uptime().as_secs() / 60
+    }
+
+    fn record_at(&self, site: &str, route: &str, error: bool, minute: u64) {
+        let mut routes = self.routes.lock().unwrap();
+        let counts = routes
+            .entry((site.to_string(), route.to_string()))
+            .or_default();
+
+        match counts.back_mut() {
+            Some(last) if last.minute == minute => {
+                last.requests += 1;
+                last.errors += error as u64;
+            }
+            _ => counts.push_back(MinuteCounts {
+                minute,
+                requests: 1,
+                errors: error as u64,
+            }),
+        }
+
+        while counts
+            .front()
+            .is_some_and(|first| first.minute + ROUTE_METRICS_WINDOW_MINUTES <= minute)
+        {
+            counts.pop_front();
+        }
+    }
+
+    fn routes_at(&self, site: &str, minute: u64) -> Vec<RouteStatus> {
+        let routes = self.routes.lock().unwrap();
+
+        let mut result = routes
+            .iter()
+            .filter(|((route_site, _), _)| route_site == site)
+            .filter_map(|((_, route), counts)| {
+                let (requests, errors) = counts
+                    .iter()
+                    .filter(|counts| counts.minute + ROUTE_METRICS_WINDOW_MINUTES > minute)
+                    .fold((0, 0), |(requests, errors), counts| {
+                        (requests + counts.requests, errors + counts.errors)
+                    });
+
+                (requests > 0).then(|| RouteStatus {
+                    route: route.clone(),
+                    requests,
+                    errors,
+                    error_rate: errors as f64 / requests as f64,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        result.sort_by(|a, b| a.route.cmp(&b.route));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn error_rates_are_counted_over_the_window() {
+        let metrics = RouteMetrics::default();
+
+        metrics.record_at("site", "GET /cart", false, 0);
+        metrics.record_at("site", "GET /cart", true, 0);
+        metrics.record_at("site", "GET /cart", false, 10);
+        metrics.record_at("site", "POST /cart", true, 10);
+        metrics.record_at("other-site", "GET /cart", true, 10);
+
+        assert_eq!(
+            metrics.routes_at("site", 10),
+            vec![
+                RouteStatus {
+                    route: "GET /cart".to_string(),
+                    requests: 3,
+                    errors: 1,
+                    error_rate: 1.0 / 3.0,
+                },
+                RouteStatus {
+                    route: "POST /cart".to_string(),
+                    requests: 1,
+                    errors: 1,
+                    error_rate: 1.0,
+                },
+            ]
+        );
+
+        // The first minute is out of the window
+        let routes = metrics.routes_at("site", ROUTE_METRICS_WINDOW_MINUTES);
+        assert_eq!(routes[0].requests, 1);
+        assert_eq!(routes[0].errors, 0);
+
+        assert!(metrics
+            .routes_at("site", 10 + ROUTE_METRICS_WINDOW_MINUTES)
+            .is_empty());
+    }
+}
@@ -12,16 +12,51 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use regex::Regex;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RouterPattern {
     Literal(LiteralPattern),
     Variable,
+    ConstrainedVariable(ConstraintPattern),
     CatchAll,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct LiteralPattern(pub String);
 
+// A regex matching whole path segments. Constraints are equal when their regexes are,
+// which is what makes routes differing only in the name of a constrained parameter conflict.
+#[derive(Debug, Clone)]
+pub struct ConstraintPattern {
+    pattern: String,
+    regex: Regex,
+}
+
+impl ConstraintPattern {
+    pub fn new(pattern: impl Into<String>) -> Result<Self, regex::Error> {
+        let pattern = pattern.into();
+        let regex = Regex::new(&format!("^(?:{})$", pattern))?;
+        Ok(ConstraintPattern { pattern, regex })
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn matches(&self, segment: &str) -> bool {
+        self.regex.is_match(segment)
+    }
+}
+
+impl PartialEq for ConstraintPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for ConstraintPattern {}
+
 impl RouterPattern {
     pub fn literal(literal: impl Into<String>) -> Self {
         Self::Literal(LiteralPattern(literal.into()))
@@ -39,7 +74,13 @@ impl RouterPattern {
 
     #[inline]
     fn parse_single(s: &str) -> RouterPattern {
-        if s.starts_with(':') || (s.starts_with('{') && s.ends_with('}')) {
+        if s.starts_with('{') && s.ends_with('}') {
+            s[1..s.len() - 1]
+                .split_once(':')
+                .and_then(|(_, pattern)| ConstraintPattern::new(pattern).ok())
+                .map(RouterPattern::ConstrainedVariable)
+                .unwrap_or(RouterPattern::Variable)
+        } else if s.starts_with(':') {
             RouterPattern::Variable
         } else if s == "*" {
             RouterPattern::CatchAll
@@ -47,6 +88,16 @@ impl RouterPattern {
             RouterPattern::literal(s)
         }
     }
+
+    // Whether a path segment is matched by the pattern, with catch all matching any segment
+    #[inline]
+    pub fn matches_segment(&self, segment: &str) -> bool {
+        match self {
+            RouterPattern::Literal(literal) => literal.0 == segment,
+            RouterPattern::Variable | RouterPattern::CatchAll => true,
+            RouterPattern::ConstrainedVariable(constraint) => constraint.matches(segment),
+        }
+    }
 }
 
 use crate::gateway_api_definition::http::PathPattern;
@@ -57,6 +108,16 @@ impl From<PathPattern> for RouterPattern {
             PathPattern::Literal(literal) => RouterPattern::literal(literal.0),
             PathPattern::Var(_) => RouterPattern::Variable,
             PathPattern::CatchAllVar(_) => RouterPattern::CatchAll,
+            // The constraints are validated when parsing the path,
+            // should an invalid one get here it matches nothing
+            PathPattern::RegexVar(info) => RouterPattern::ConstrainedVariable(
+                ConstraintPattern::new(info.pattern.as_str()).unwrap_or_else(|_| {
+                    ConstraintPattern {
+                        pattern: info.pattern,
+                        regex: Regex::new(r"[^\s\S]").unwrap(),
+                    }
+                }),
+            ),
         }
     }
 }
@@ -38,6 +38,8 @@ struct Children<T> {
     // Given the paths are perfectly de-duplicated,
     // We can assume that each child has a unique first pattern.
     literal_children: FxHashMap<LiteralPattern, RadixNode<T>>,
+    // In the order they were added, which is the order they are tried in
+    constrained_children: Vec<RadixNode<T>>,
     variable_child: Option<Box<RadixNode<T>>>,
     catch_all_child: Option<Box<RadixNode<T>>>,
 }
@@ -46,6 +48,7 @@ impl<T> Default for Children<T> {
     fn default() -> Self {
         Self {
             literal_children: Default::default(),
+            constrained_children: Vec::new(),
             variable_child: None,
             catch_all_child: None,
         }
@@ -53,17 +56,14 @@ impl<T> Default for Children<T> {
 }
 
 impl<T> Children<T> {
-    #[inline]
-    fn get_child_by_str(&self, input: &str) -> Option<&RadixNode<T>> {
-        self.literal_children
-            .get(input)
-            .or_else(|| self.variable_child.as_ref().map(|c| c.as_ref()))
-    }
-
     fn get_child(&self, pattern: &RouterPattern) -> Option<&RadixNode<T>> {
         match pattern {
             RouterPattern::Literal(literal_pattern) => self.literal_children.get(literal_pattern),
             RouterPattern::Variable => self.variable_child.as_ref().map(|c| c.as_ref()),
+            RouterPattern::ConstrainedVariable(_) => self
+                .constrained_children
+                .iter()
+                .find(|c| c.pattern.first() == Some(pattern)),
             RouterPattern::CatchAll => self.catch_all_child.as_ref().map(|c| c.as_ref()),
         }
     }
@@ -74,6 +74,10 @@ impl<T> Children<T> {
                 self.literal_children.get_mut(literal_pattern)
             }
             RouterPattern::Variable => self.variable_child.as_mut().map(|c| c.as_mut()),
+            RouterPattern::ConstrainedVariable(_) => self
+                .constrained_children
+                .iter_mut()
+                .find(|c| c.pattern.first() == Some(pattern)),
             RouterPattern::CatchAll => self.catch_all_child.as_mut().map(|c| c.as_mut()),
         }
     }
@@ -93,6 +97,9 @@ impl<T> Children<T> {
 
                 self.variable_child = Some(Box::new(node));
             }
+            Some(RouterPattern::ConstrainedVariable(_)) => {
+                self.constrained_children.push(node);
+            }
             Some(RouterPattern::CatchAll) => {
                 debug_assert!(
                     self.catch_all_child.is_none(),
@@ -262,41 +269,57 @@ impl<T> RadixNode<T> {
         }
     }
 
-    #[inline]
+    // Literal children are tried first, then the constrained variables, the variable and
+    // finally the catch all, backtracking to the next one when a child does not match the rest
+    // of the path
     pub fn matches(&self, path: &[&str]) -> Option<&T> {
-        let mut node = self;
         let mut path_segments = path;
-        let mut last_catch_all: Option<&RadixNode<T>> = None;
 
-        loop {
-            let common_prefix_len =
-                node.interpret_common_prefix_len(path_segments, &mut last_catch_all);
-
-            if let Some(child) = node.children.catch_all_child.as_ref() {
-                last_catch_all.replace(child.as_ref());
-            }
-            if common_prefix_len == node.pattern.len() {
-                if common_prefix_len == path_segments.len() {
-                    // The path fully matches the current node's pattern
-                    return node.data.as_ref();
+        for pattern in &self.pattern {
+            if let RouterPattern::CatchAll = pattern {
+                // Catch all matches one or more segments
+                return if path_segments.is_empty() {
+                    None
                 } else {
-                    // The path partially matches the current node's pattern
-                    path_segments = &path_segments[common_prefix_len..];
-
-                    if let Some(first_segment) = path_segments.first() {
-                        let next_child = node.children.get_child_by_str(first_segment);
-                        if let Some(child) = next_child {
-                            node = child;
-                            continue;
-                        }
-                    }
-                }
+                    self.data.as_ref()
+                };
+            }
+
+            let (segment, rest) = path_segments.split_first()?;
+
+            if !pattern.matches_segment(segment) {
+                return None;
             }
 
-            break;
+            path_segments = rest;
         }
 
-        last_catch_all.and_then(|node| node.data.as_ref())
+        let Some(first_segment) = path_segments.first() else {
+            return self.data.as_ref();
+        };
+
+        self.children
+            .literal_children
+            .get(*first_segment)
+            .and_then(|child| child.matches(path_segments))
+            .or_else(|| {
+                self.children
+                    .constrained_children
+                    .iter()
+                    .find_map(|child| child.matches(path_segments))
+            })
+            .or_else(|| {
+                self.children
+                    .variable_child
+                    .as_ref()
+                    .and_then(|child| child.matches(path_segments))
+            })
+            .or_else(|| {
+                self.children
+                    .catch_all_child
+                    .as_ref()
+                    .and_then(|child| child.matches(path_segments))
+            })
     }
 
     // Stops iterating when it finds a catch all node.
@@ -317,32 +340,6 @@ impl<T> RadixNode<T> {
             .count()
     }
 
-    #[inline(always)]
-    fn interpret_common_prefix_len<'a>(
-        &'a self,
-        path_segments: &[&str],
-        last_catch_all: &mut Option<&'a RadixNode<T>>,
-    ) -> usize {
-        let mut common_prefix_len = 0;
-
-        for (a, b) in self.pattern.iter().zip(path_segments.iter()) {
-            match a {
-                RouterPattern::Literal(s) => {
-                    if s.0 != **b {
-                        break;
-                    }
-                }
-                RouterPattern::Variable => {}
-                RouterPattern::CatchAll => {
-                    *last_catch_all = Some(self);
-                }
-            }
-            common_prefix_len += 1;
-        }
-
-        common_prefix_len
-    }
-
     #[cfg(test)]
     fn matches_str(&self, path: &str) -> Option<&T> {
         let path: Vec<&str> = RouterPattern::split(path).collect();
@@ -599,6 +596,39 @@ mod test {
         test_three(&root);
     }
 
+    #[test]
+    fn test_constrained_variables() {
+        let mut root = RadixNode::default();
+
+        let path1 = RouterPattern::parse("/users/{id:[0-9]+}/posts");
+        root.insert_path(&path1, 1).unwrap();
+
+        let path2 = RouterPattern::parse("/users/{name:[a-z]+}/posts");
+        root.insert_path(&path2, 2).unwrap();
+
+        let path3 = RouterPattern::parse("/users/:user/profile");
+        root.insert_path(&path3, 3).unwrap();
+
+        let path4 = RouterPattern::parse("/users/me/posts");
+        root.insert_path(&path4, 4).unwrap();
+
+        assert_eq!(Some(&1), root.matches_str("/users/123/posts"));
+        assert_eq!(Some(&2), root.matches_str("/users/jane/posts"));
+        assert_eq!(Some(&4), root.matches_str("/users/me/posts"));
+        assert_eq!(None, root.matches_str("/users/Jane/posts"));
+
+        // Backtracks to the variable when the rest of the path does not match
+        assert_eq!(Some(&3), root.matches_str("/users/123/profile"));
+        assert_eq!(Some(&3), root.matches_str("/users/me/profile"));
+
+        // The same constraint under another name is a conflict
+        let path5 = RouterPattern::parse("/users/{user_id:[0-9]+}/posts");
+        assert!(matches!(
+            root.insert_path(&path5, 5),
+            Err(InsertionError::Conflict)
+        ));
+    }
+
     #[test]
     fn test_catch_all() {
         let mut root = RadixNode::default();
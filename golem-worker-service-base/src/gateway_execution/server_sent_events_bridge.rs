@@ -0,0 +1,292 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::web_socket_bridge::evaluate_to_text;
+use super::WorkerDetail;
+use crate::empty_worker_metadata;
+use crate::gateway_binding::ResponseMappingCompiled;
+use crate::gateway_rib_interpreter::WorkerServiceRibInterpreter;
+use crate::service::worker::{WorkerService, WorkerServiceError};
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use golem_common::model::{WorkerEvent, WorkerId};
+use poem::web::sse::{Event, SSE};
+use serde_json::{json, Value};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+// Subscribes to the events emitted by a worker, such as its logs and the invocations it processes
+#[async_trait]
+pub trait WorkerEventSubscriber {
+    async fn subscribe(
+        &self,
+        worker_detail: &WorkerDetail,
+    ) -> Result<BoxStream<'static, Result<WorkerEvent, String>>, WorkerServiceError>;
+}
+
+pub struct DefaultWorkerEventSubscriber {
+    worker_service: Arc<dyn WorkerService + Sync + Send>,
+}
+
+impl DefaultWorkerEventSubscriber {
+    pub fn new(worker_service: Arc<dyn WorkerService + Sync + Send>) -> Self {
+        Self { worker_service }
+    }
+}
+
+#[async_trait]
+impl WorkerEventSubscriber for DefaultWorkerEventSubscriber {
+    async fn subscribe(
+        &self,
+        worker_detail: &WorkerDetail,
+    ) -> Result<BoxStream<'static, Result<WorkerEvent, String>>, WorkerServiceError> {
+        let worker_name = worker_detail.worker_name.clone().ok_or_else(|| {
+            WorkerServiceError::Internal("Worker name is required to subscribe to events".into())
+        })?;
+
+        let worker_id = WorkerId {
+            component_id: worker_detail.component_id.component_id.clone(),
+            worker_name,
+        };
+
+        let stream = self
+            .worker_service
+            .connect(&worker_id, empty_worker_metadata())
+            .await?;
+
+        Ok(stream
+            .map(|event| {
+                event
+                    .map_err(|status| status.to_string())
+                    .and_then(WorkerEvent::try_from)
+            })
+            .boxed())
+    }
+}
+
+// Identifies an event of the stream, to let clients resume after reconnecting with Last-Event-ID.
+// Events are identified by their timestamp, and their position among the events of the same
+// millisecond, which is stable as the worker replays its recent events in order to new subscribers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EventId {
+    pub millis: u64,
+    pub sequence: u32,
+}
+
+impl std::fmt::Display for EventId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.millis, self.sequence)
+    }
+}
+
+impl FromStr for EventId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (millis, sequence) = s
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid event id: {}", s))?;
+
+        Ok(EventId {
+            millis: millis
+                .parse()
+                .map_err(|_| format!("Invalid event id: {}", s))?,
+            sequence: sequence
+                .parse()
+                .map_err(|_| format!("Invalid event id: {}", s))?,
+        })
+    }
+}
+
+// Streams the events of the worker a server-sent events binding is bound to.
+// Every event is evaluated by the Rib script of the binding, with the event available as `event`,
+// and the result of the script is sent as the data of a server-sent event.
+// Events for which the script returns nothing are skipped, so the script can filter the stream.
+pub struct ServerSentEventsBridge<Namespace> {
+    pub evaluator: Arc<dyn WorkerServiceRibInterpreter<Namespace> + Sync + Send>,
+    pub namespace: Namespace,
+    pub worker_detail: WorkerDetail,
+    // The `request` and `worker` inputs, which are the same for all the events of the stream
+    pub rib_input: serde_json::Map<String, Value>,
+    pub event_mapping: ResponseMappingCompiled,
+    // Events up to this one were already received by the client before reconnecting
+    pub last_event_id: Option<EventId>,
+}
+
+impl<Namespace: Clone + Send + Sync + 'static> ServerSentEventsBridge<Namespace> {
+    pub fn into_response(self, events: BoxStream<'static, Result<WorkerEvent, String>>) -> SSE {
+        let bridge = Arc::new(self);
+        let mut previous: Option<EventId> = None;
+
+        let identified = events.filter_map(move |event| {
+            let result = match event {
+                Ok(event) => event_json(&event).map(|(millis, json)| {
+                    let sequence = match previous {
+                        Some(previous) if previous.millis == millis => previous.sequence + 1,
+                        _ => 0,
+                    };
+                    let id = EventId { millis, sequence };
+                    previous = Some(id);
+                    Ok((id, json))
+                }),
+                Err(err) => Some(Err(err)),
+            };
+            futures::future::ready(result)
+        });
+
+        let last_event_id = bridge.last_event_id;
+
+        let stream = identified
+            .filter(move |event| {
+                let already_received = match (event, last_event_id) {
+                    (Ok((id, _)), Some(last_event_id)) => *id <= last_event_id,
+                    _ => false,
+                };
+                futures::future::ready(!already_received)
+            })
+            .then(move |event| {
+                let bridge = bridge.clone();
+                async move {
+                    match event {
+                        Ok((id, json)) => match bridge.handle_event(json).await {
+                            Ok(data) => data.map(|data| Event::message(data).id(id.to_string())),
+                            Err(err) => Some(Event::message(err).event_type("error")),
+                        },
+                        Err(err) => Some(Event::message(err).event_type("error")),
+                    }
+                }
+            })
+            .filter_map(futures::future::ready);
+
+        SSE::new(stream).keep_alive(KEEP_ALIVE_INTERVAL)
+    }
+
+    async fn handle_event(&self, event: Value) -> Result<Option<String>, String> {
+        let mut rib_input = self.rib_input.clone();
+        rib_input.insert("event".to_string(), event);
+
+        evaluate_to_text(
+            self.evaluator.as_ref(),
+            &self.namespace,
+            &self.worker_detail,
+            &rib_input,
+            &self.event_mapping,
+        )
+        .await
+    }
+}
+
+// The representation of an event available to the Rib script of the binding, along with
+// the timestamp of the event. All the fields are strings, and are empty if not applicable.
+fn event_json(event: &WorkerEvent) -> Option<(u64, Value)> {
+    let (kind, timestamp, message, level, context, function) = match event {
+        WorkerEvent::StdOut { timestamp, bytes } => (
+            "stdout",
+            timestamp,
+            String::from_utf8_lossy(bytes).to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ),
+        WorkerEvent::StdErr { timestamp, bytes } => (
+            "stderr",
+            timestamp,
+            String::from_utf8_lossy(bytes).to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ),
+        WorkerEvent::Log {
+            timestamp,
+            level,
+            context,
+            message,
+        } => (
+            "log",
+            timestamp,
+            message.clone(),
+            format!("{:?}", level).to_lowercase(),
+            context.clone(),
+            String::new(),
+        ),
+        WorkerEvent::InvocationStart {
+            timestamp,
+            function,
+            ..
+        } => (
+            "invocation-started",
+            timestamp,
+            String::new(),
+            String::new(),
+            String::new(),
+            function.clone(),
+        ),
+        WorkerEvent::InvocationFinished {
+            timestamp,
+            function,
+            ..
+        } => (
+            "invocation-finished",
+            timestamp,
+            String::new(),
+            String::new(),
+            String::new(),
+            function.clone(),
+        ),
+        WorkerEvent::Close => return None,
+    };
+
+    Some((
+        timestamp.to_millis(),
+        json!({
+            "kind": kind,
+            "timestamp": timestamp.to_string(),
+            "message": message,
+            "level": level,
+            "context": context,
+            "function": function,
+        }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn event_ids_round_trip_and_order_by_time_then_sequence() {
+        let first = EventId {
+            millis: 1700000000000,
+            sequence: 1,
+        };
+        let second = EventId {
+            millis: 1700000000000,
+            sequence: 2,
+        };
+        let third = EventId {
+            millis: 1700000000001,
+            sequence: 0,
+        };
+
+        assert_eq!(EventId::from_str(&first.to_string()), Ok(first));
+        assert!(first < second && second < third);
+        assert!(EventId::from_str("1700000000000").is_err());
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Utc};
+use golem_service_base::model::VersionedComponentId;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::Mutex;
+
+// Upper bound of the field differences kept for a single comparison,
+// so that a completely different response does not blow up the stored samples
+const MAX_FIELD_DIFFS: usize = 32;
+
+/// A single difference between the primary and the shadow response.
+/// The path is a JSON path into the response, and a missing value means
+/// the field is only present in one of the responses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ResponseFieldDiff {
+    pub path: String,
+    pub primary: Option<Value>,
+    pub shadow: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ShadowDiffSample {
+    pub timestamp: DateTime<Utc>,
+    pub worker_name: Option<String>,
+    pub diffs: Vec<ResponseFieldDiff>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ShadowRouteDiffStats {
+    // The route as `METHOD /path/{pattern}`
+    pub route: String,
+    pub shadow_component_id: VersionedComponentId,
+    pub compared: u64,
+    pub mismatched: u64,
+    // The most recent mismatches, newest last
+    pub samples: Vec<ShadowDiffSample>,
+}
+
+/// Normalizes and compares the primary and shadow responses of mirrored requests.
+/// Responses are compared in their JSON representation, and failures are represented
+/// as an object with an `error` field, so that a shadow failing where the primary
+/// succeeded (or the other way round) is reported as a mismatch. Requests failing
+/// for both are considered matching, whatever the errors are.
+pub fn diff_responses(
+    primary: Result<Value, String>,
+    shadow: Result<Value, String>,
+    ignored_fields: &[String],
+) -> Vec<ResponseFieldDiff> {
+    if primary.is_err() && shadow.is_err() {
+        return vec![];
+    }
+
+    let mut primary = primary.unwrap_or_else(|err| json!({ "error": err }));
+    let mut shadow = shadow.unwrap_or_else(|err| json!({ "error": err }));
+
+    for field in ignored_fields {
+        let segments: Vec<&str> = field.split('.').collect();
+        remove_field(&mut primary, &segments);
+        remove_field(&mut shadow, &segments);
+    }
+
+    let mut diffs = vec![];
+    diff_values("$".to_string(), Some(&primary), Some(&shadow), &mut diffs);
+    diffs
+}
+
+// Removes a field given as path segments. Arrays on the way are traversed,
+// so that `items.price` ignores the price of every item.
+fn remove_field(value: &mut Value, segments: &[&str]) {
+    match value {
+        Value::Object(map) => match segments {
+            [last] => {
+                map.remove(*last);
+            }
+            [first, rest @ ..] => {
+                if let Some(inner) = map.get_mut(*first) {
+                    remove_field(inner, rest);
+                }
+            }
+            [] => {}
+        },
+        Value::Array(values) => {
+            for value in values {
+                remove_field(value, segments);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn diff_values(
+    path: String,
+    primary: Option<&Value>,
+    shadow: Option<&Value>,
+    diffs: &mut Vec<ResponseFieldDiff>,
+) {
+    if diffs.len() >= MAX_FIELD_DIFFS {
+        return;
+    }
+
+    match (primary, shadow) {
+        (Some(Value::Object(primary)), Some(Value::Object(shadow))) => {
+            let keys: BTreeSet<&String> = primary.keys().chain(shadow.keys()).collect();
+            for key in keys {
+                diff_values(
+                    format!("{}.{}", path, key),
+                    primary.get(key),
+                    shadow.get(key),
+                    diffs,
+                );
+            }
+        }
+        (Some(Value::Array(primary)), Some(Value::Array(shadow))) => {
+            for index in 0..primary.len().max(shadow.len()) {
+                diff_values(
+                    format!("{}[{}]", path, index),
+                    primary.get(index),
+                    shadow.get(index),
+                    diffs,
+                );
+            }
+        }
+        (primary, shadow) if primary != shadow => diffs.push(ResponseFieldDiff {
+            path,
+            primary: primary.cloned(),
+            shadow: shadow.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+/// Aggregates the outcome of response comparisons per mirrored route.
+/// Statistics are kept in memory, so they are per worker service instance
+/// and are lost on restart.
+pub trait ShadowTrafficDiffStore {
+    fn record(
+        &self,
+        route: &str,
+        shadow_component_id: &VersionedComponentId,
+        worker_name: Option<String>,
+        diffs: Vec<ResponseFieldDiff>,
+    );
+
+    fn get_stats(&self) -> Vec<ShadowRouteDiffStats>;
+
+    fn clear(&self);
+}
+
+pub struct DefaultShadowTrafficDiffStore {
+    max_samples: usize,
+    routes: Mutex<HashMap<(String, VersionedComponentId), RouteDiffState>>,
+}
+
+#[derive(Default)]
+struct RouteDiffState {
+    compared: u64,
+    mismatched: u64,
+    samples: VecDeque<ShadowDiffSample>,
+}
+
+impl DefaultShadowTrafficDiffStore {
+    pub fn new(max_samples: usize) -> Self {
+        DefaultShadowTrafficDiffStore {
+            max_samples,
+            routes: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for DefaultShadowTrafficDiffStore {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+impl ShadowTrafficDiffStore for DefaultShadowTrafficDiffStore {
+    fn record(
+        &self,
+        route: &str,
+        shadow_component_id: &VersionedComponentId,
+        worker_name: Option<String>,
+        diffs: Vec<ResponseFieldDiff>,
+    ) {
+        let mut routes = self.routes.lock().unwrap();
+        let state = routes
+            .entry((route.to_string(), shadow_component_id.clone()))
+            .or_default();
+
+        state.compared += 1;
+
+        if !diffs.is_empty() {
+            state.mismatched += 1;
+
+            if self.max_samples > 0 {
+                if state.samples.len() >= self.max_samples {
+                    state.samples.pop_front();
+                }
+                state.samples.push_back(ShadowDiffSample {
+                    timestamp: Utc::now(),
+                    worker_name,
+                    diffs,
+                });
+            }
+        }
+    }
+
+    fn get_stats(&self) -> Vec<ShadowRouteDiffStats> {
+        let routes = self.routes.lock().unwrap();
+        let mut stats: Vec<ShadowRouteDiffStats> = routes
+            .iter()
+            .map(
+                |((route, shadow_component_id), state)| ShadowRouteDiffStats {
+                    route: route.clone(),
+                    shadow_component_id: shadow_component_id.clone(),
+                    compared: state.compared,
+                    mismatched: state.mismatched,
+                    samples: state.samples.iter().cloned().collect(),
+                },
+            )
+            .collect();
+
+        stats.sort_by(|a, b| {
+            (&a.route, &a.shadow_component_id).cmp(&(&b.route, &b.shadow_component_id))
+        });
+        stats
+    }
+
+    fn clear(&self) {
+        self.routes.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::ComponentId;
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn diff_ignores_fields_and_reports_paths() {
+        let primary = json!({
+            "id": 1,
+            "timestamp": "2025-01-01T00:00:00Z",
+            "items": [{"name": "a", "price": 10}, {"name": "b", "price": 20}]
+        });
+
+        let shadow = json!({
+            "id": 1,
+            "timestamp": "2025-01-01T00:00:05Z",
+            "items": [{"name": "a", "price": 11}, {"name": "c", "price": 20}, {"name": "d"}]
+        });
+
+        let ignored_fields = vec!["timestamp".to_string(), "items.price".to_string()];
+        let diffs = diff_responses(Ok(primary), Ok(shadow), &ignored_fields);
+
+        assert_eq!(
+            diffs,
+            vec![
+                ResponseFieldDiff {
+                    path: "$.items[1].name".to_string(),
+                    primary: Some(json!("b")),
+                    shadow: Some(json!("c")),
+                },
+                ResponseFieldDiff {
+                    path: "$.items[2]".to_string(),
+                    primary: None,
+                    shadow: Some(json!({"name": "d"})),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn store_aggregates_per_route_and_keeps_recent_samples() {
+        let store = DefaultShadowTrafficDiffStore::new(1);
+        let shadow = VersionedComponentId {
+            component_id: ComponentId(Uuid::nil()),
+            version: 1,
+        };
+
+        let diff = |value: i32| diff_responses(Ok(json!(value)), Err("failed".to_string()), &[]);
+
+        store.record("GET /cart/{id}", &shadow, None, vec![]);
+        store.record("GET /cart/{id}", &shadow, None, diff(1));
+        store.record("GET /cart/{id}", &shadow, Some("w".to_string()), diff(2));
+
+        let stats = store.get_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].compared, 3);
+        assert_eq!(stats[0].mismatched, 2);
+        assert_eq!(stats[0].samples.len(), 1);
+        assert_eq!(stats[0].samples[0].worker_name, Some("w".to_string()));
+        assert_eq!(stats[0].samples[0].diffs[0].primary, Some(json!(2)));
+
+        store.clear();
+        assert!(store.get_stats().is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::CompiledHttpApiDefinition;
+use crate::gateway_execution::route_metrics::{
+    RouteMetrics, RouteStatus, ROUTE_METRICS_WINDOW_MINUTES,
+};
+use http::StatusCode;
+use poem::Body;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+// The health of a deployment as served by a status page binding: the uptime of the gateway,
+// the recent error rate of every route of the deployment, and the component versions it binds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusPage {
+    pub site: String,
+    pub uptime_seconds: u64,
+    pub window_minutes: u64,
+    pub routes: Vec<RouteStatus>,
+    pub components: Vec<ComponentVersions>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentVersions {
+    pub component_id: String,
+    pub versions: Vec<u64>,
+}
+
+impl StatusPage {
+    pub fn new<Namespace>(
+        site: &str,
+        definitions: &[CompiledHttpApiDefinition<Namespace>],
+        metrics: &RouteMetrics,
+    ) -> Self {
+        let mut recent = metrics
+            .routes(site)
+            .into_iter()
+            .map(|route| (route.route.clone(), route))
+            .collect::<HashMap<_, _>>();
+        let mut routes = BTreeMap::new();
+        let mut components = BTreeMap::<String, BTreeSet<u64>>::new();
+
+        // Only the routes currently deployed are listed, including the ones without recent requests
+        for route in definitions.iter().flat_map(|definition| &definition.routes) {
+            let name = route.name();
+            let status = recent.remove(&name).unwrap_or_else(|| RouteStatus {
+                route: name.clone(),
+                requests: 0,
+                errors: 0,
+                error_rate: 0.0,
+            });
+            routes.entry(name).or_insert(status);
+
+            if let Some(component_id) = route.binding.get_component_id() {
+                components
+                    .entry(component_id.component_id.to_string())
+                    .or_default()
+                    .insert(component_id.version);
+            }
+        }
+
+        StatusPage {
+            site: site.to_string(),
+            uptime_seconds: metrics.uptime().as_secs(),
+            window_minutes: ROUTE_METRICS_WINDOW_MINUTES,
+            routes: routes.into_values().collect(),
+            components: components
+                .into_iter()
+                .map(|(component_id, versions)| ComponentVersions {
+                    component_id,
+                    versions: versions.into_iter().collect(),
+                })
+                .collect(),
+        }
+    }
+
+    // JSON for clients asking for it, such as monitoring tools, and HTML otherwise
+    pub fn to_response(&self, accept: Option<&str>) -> poem::Response {
+        let wants_json = accept.is_some_and(|accept| {
+            accept.contains("application/json") && !accept.contains("text/html")
+        });
+
+        if wants_json {
+            poem::Response::builder()
+                .status(StatusCode::OK)
+                .content_type("application/json")
+                .body(Body::from_string(
+                    serde_json::to_string(self).unwrap_or_default(),
+                ))
+        } else {
+            poem::Response::builder()
+                .status(StatusCode::OK)
+                .content_type("text/html; charset=utf-8")
+                .body(Body::from_string(self.to_html()))
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+
+        let _ = write!(
+            html,
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Status of {site}</title></head><body><h1>Status of {site}</h1><p>Uptime: {uptime}</p>",
+            site = escape_html(&self.site),
+            uptime = format_uptime(self.uptime_seconds),
+        );
+
+        let _ = write!(
+            html,
+            "<h2>Routes</h2><table><tr><th>Route</th><th>Requests (last {} minutes)</th><th>Error rate</th></tr>",
+            self.window_minutes
+        );
+        for route in &self.routes {
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{:.2}%</td></tr>",
+                escape_html(&route.route),
+                route.requests,
+                route.error_rate * 100.0
+            );
+        }
+        html.push_str("</table>");
+
+        html.push_str("<h2>Components</h2><table><tr><th>Component</th><th>Versions</th></tr>");
+        for component in &self.components {
+            let versions = component
+                .versions
+                .iter()
+                .map(|version| version.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                escape_html(&component.component_id),
+                versions
+            );
+        }
+        html.push_str("</table></body></html>");
+
+        html
+    }
+}
+
+fn format_uptime(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86400, seconds % 86400 / 3600, seconds % 3600 / 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m {}s", seconds % 60)
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn status_page_is_rendered_as_html_or_json() {
+        let page = StatusPage {
+            site: "shop.example.com".to_string(),
+            uptime_seconds: 90061,
+            window_minutes: ROUTE_METRICS_WINDOW_MINUTES,
+            routes: vec![RouteStatus {
+                route: "GET /cart/<id>".to_string(),
+                requests: 4,
+                errors: 1,
+                error_rate: 0.25,
+            }],
+            components: vec![ComponentVersions {
+                component_id: "e1f2b6a0-4c5d-4e6f-8a9b-0c1d2e3f4a5b".to_string(),
+                versions: vec![1, 2],
+            }],
+        };
+
+        let html = page.to_html();
+        assert!(html.contains("<p>Uptime: 1d 1h 1m</p>"));
+        assert!(html.contains("<td>GET /cart/&lt;id&gt;</td><td>4</td><td>25.00%</td>"));
+        assert!(html.contains("<td>e1f2b6a0-4c5d-4e6f-8a9b-0c1d2e3f4a5b</td><td>1, 2</td>"));
+
+        let response = page.to_response(Some("application/json"));
+        assert_eq!(response.content_type(), Some("application/json"));
+        let response = page.to_response(Some("text/html,application/json;q=0.9"));
+        assert_eq!(response.content_type(), Some("text/html; charset=utf-8"));
+    }
+}
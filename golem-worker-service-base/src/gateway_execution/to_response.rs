@@ -13,8 +13,12 @@
 // limitations under the License.
 
 use super::auth_call_back_binding_handler::{AuthorisationError, AuthorisationSuccess};
-use super::file_server_binding_handler::FileServerBindingSuccess;
+use super::file_server_binding_handler::{
+    if_none_match_matches, ByteRangeRequest, FileServerBindingSuccess,
+};
+use super::grpc_transcoding::{http_status, GrpcBindingError, GrpcBindingSuccess};
 use super::http_handler_binding_handler::{HttpHandlerBindingError, HttpHandlerBindingSuccess};
+use super::http_proxy_binding_handler::{HttpProxyBindingError, HttpProxyBindingSuccess};
 use super::RibInputTypeMismatch;
 use crate::api::WorkerApiBaseError;
 use crate::gateway_execution::file_server_binding_handler::FileServerBindingError;
@@ -24,6 +28,7 @@ use crate::gateway_execution::to_response_failure::ToHttpResponseFromSafeDisplay
 use crate::gateway_middleware::HttpCors as CorsPreflight;
 use crate::gateway_rib_interpreter::EvaluationError;
 use async_trait::async_trait;
+use futures::TryStreamExt;
 use http::header::*;
 use http::StatusCode;
 use poem::Body;
@@ -57,6 +62,7 @@ pub type GatewayHttpResult<T> = Result<T, GatewayHttpError>;
 
 pub enum GatewayHttpError {
     BadRequest(String),
+    NotAcceptable(String),
     RibInputTypeMismatch(RibInputTypeMismatch),
     EvaluationError(EvaluationError),
     RibInterpretPureError(String),
@@ -76,6 +82,9 @@ impl ToHttpResponse for GatewayHttpError {
             GatewayHttpError::BadRequest(e) => poem::Response::builder()
                 .status(StatusCode::BAD_REQUEST)
                 .body(Body::from_string(format!("Invalid input: {e}"))),
+            GatewayHttpError::NotAcceptable(e) => poem::Response::builder()
+                .status(StatusCode::NOT_ACCEPTABLE)
+                .body(Body::from_string(e)),
             GatewayHttpError::RibInputTypeMismatch(err) => {
                 err.to_response_from_safe_display(|_| StatusCode::BAD_REQUEST)
             }
@@ -104,13 +113,91 @@ impl ToHttpResponse for GatewayHttpError {
 impl ToHttpResponse for FileServerBindingSuccess {
     async fn to_response(
         self,
-        _request_details: &RichRequest,
+        request_details: &RichRequest,
         _session_store: &GatewaySessionStore,
     ) -> poem::Response {
-        Body::from_bytes_stream(self.data)
-            .with_content_type(self.binding_details.content_type.to_string())
-            .with_status(self.binding_details.status_code)
-            .into_response()
+        let FileServerBindingSuccess {
+            binding_details,
+            etag,
+            data,
+        } = self;
+
+        let request_header = |name: HeaderName| {
+            request_details
+                .underlying
+                .header(name)
+                .map(|value| value.to_string())
+        };
+
+        let mut response =
+            poem::Response::builder().content_type(binding_details.content_type.to_string());
+
+        if let Some(cache_control) = &binding_details.cache_control {
+            response = response.header(CACHE_CONTROL, cache_control);
+        }
+
+        if let Some(etag) = &etag {
+            response = response.header(ETAG, etag);
+        }
+
+        // Conditional and range requests only apply to files served successfully,
+        // a custom status code from the binding is served as is
+        if binding_details.status_code != StatusCode::OK {
+            return response
+                .status(binding_details.status_code)
+                .body(Body::from_bytes_stream(data));
+        }
+
+        if let (Some(etag), Some(if_none_match)) = (&etag, request_header(IF_NONE_MATCH)) {
+            if if_none_match_matches(&if_none_match, etag) {
+                return response.status(StatusCode::NOT_MODIFIED).finish();
+            }
+        }
+
+        response = response.header(ACCEPT_RANGES, "bytes");
+
+        // With If-Range, the range is only served if the file did not change since the client got it
+        let range = request_header(RANGE).filter(|_| match request_header(IF_RANGE) {
+            Some(if_range) => etag.as_deref() == Some(if_range.trim()),
+            None => true,
+        });
+
+        let Some(range) = range else {
+            return response
+                .status(StatusCode::OK)
+                .body(Body::from_bytes_stream(data));
+        };
+
+        // The length of the file is needed to resolve the range, so the file is read
+        // in the gateway, and only the requested part is sent to the client
+        let contents = match data
+            .try_fold(Vec::new(), |mut contents, chunk| async move {
+                contents.extend_from_slice(&chunk);
+                Ok(contents)
+            })
+            .await
+        {
+            Ok(contents) => contents,
+            Err(err) => {
+                return poem::Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from_string(format!("Error {err}")))
+            }
+        };
+
+        let length = contents.len() as u64;
+
+        match ByteRangeRequest::from_header(&range, length) {
+            ByteRangeRequest::Full => response.status(StatusCode::OK).body(contents),
+            ByteRangeRequest::Partial { start, end } => response
+                .status(StatusCode::PARTIAL_CONTENT)
+                .header(CONTENT_RANGE, format!("bytes {start}-{end}/{length}"))
+                .body(contents[start as usize..=end as usize].to_vec()),
+            ByteRangeRequest::Unsatisfiable => response
+                .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                .header(CONTENT_RANGE, format!("bytes */{length}"))
+                .finish(),
+        }
     }
 }
 
@@ -171,51 +258,140 @@ impl ToHttpResponse for HttpHandlerBindingError {
     }
 }
 
+#[async_trait]
+impl ToHttpResponse for GrpcBindingSuccess {
+    async fn to_response(
+        self,
+        _request_details: &RichRequest,
+        _session_store: &GatewaySessionStore,
+    ) -> poem::Response {
+        Body::from_json(self.body)
+            .map(|body| body.with_content_type("application/json").into_response())
+            .unwrap_or_else(|e| {
+                poem::Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body(Body::from_string(format!("Error {e}")))
+            })
+    }
+}
+
+// gRPC status codes are mapped to HTTP status codes the same way as the HTTP/JSON transcoding
+// of google.api.http does, keeping the status details in the body
+#[async_trait]
+impl ToHttpResponse for GrpcBindingError {
+    async fn to_response(
+        self,
+        _request_details: &RichRequest,
+        _session_store: &GatewaySessionStore,
+    ) -> poem::Response {
+        match self {
+            GrpcBindingError::InvalidRequest(e) => poem::Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from_string(format!("Invalid gRPC request: {e}"))),
+            GrpcBindingError::InvalidResponse(e) => poem::Response::builder()
+                .status(StatusCode::BAD_GATEWAY)
+                .body(Body::from_string(format!("Invalid gRPC response: {e}"))),
+            GrpcBindingError::Status(status) => {
+                let body = serde_json::json!({
+                    "code": status.code() as i32,
+                    "message": status.message(),
+                });
+
+                poem::Response::builder()
+                    .status(http_status(status.code()))
+                    .content_type("application/json")
+                    .body(Body::from_string(body.to_string()))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ToHttpResponse for HttpProxyBindingSuccess {
+    async fn to_response(
+        self,
+        _request_details: &RichRequest,
+        _session_store: &GatewaySessionStore,
+    ) -> poem::Response {
+        let mut response = poem::Response::builder()
+            .status(self.status)
+            .body(self.body);
+        *response.headers_mut() = self.headers;
+        response
+    }
+}
+
+#[async_trait]
+impl ToHttpResponse for HttpProxyBindingError {
+    async fn to_response(
+        self,
+        _request_details: &RichRequest,
+        _session_store: &GatewaySessionStore,
+    ) -> poem::Response {
+        match self {
+            HttpProxyBindingError::InvalidRequest(e) => poem::Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from_string(format!("Invalid proxy request: {e}"))),
+            HttpProxyBindingError::Timeout => poem::Response::builder()
+                .status(StatusCode::GATEWAY_TIMEOUT)
+                .body(Body::from_string("Upstream timed out".to_string())),
+            HttpProxyBindingError::UpstreamError(e) => poem::Response::builder()
+                .status(StatusCode::BAD_GATEWAY)
+                .body(Body::from_string(format!("Upstream error: {e}"))),
+        }
+    }
+}
+
 // Preflight (OPTIONS) response that will consist of all configured CORS headers
 #[async_trait]
 impl ToHttpResponse for CorsPreflight {
     async fn to_response(
         self,
-        _request_details: &RichRequest,
+        request_details: &RichRequest,
         _session_store: &GatewaySessionStore,
     ) -> poem::Response {
-        let mut response = poem::Response::builder().status(StatusCode::OK).finish();
+        cors_preflight_response(&self, request_details.underlying.header(ORIGIN))
+    }
+}
 
+// Also used to answer the preflight requests of deployments with a CORS configuration
+pub(crate) fn cors_preflight_response(
+    cors: &CorsPreflight,
+    origin: Option<&str>,
+) -> poem::Response {
+    let mut response = poem::Response::builder().status(StatusCode::OK).finish();
+
+    cors.set_allow_origin_header(response.headers_mut(), origin);
+    response.headers_mut().insert(
+        ACCESS_CONTROL_ALLOW_METHODS,
+        cors.get_allow_methods().clone().parse().unwrap(),
+    );
+    response.headers_mut().insert(
+        ACCESS_CONTROL_ALLOW_HEADERS,
+        cors.get_allow_headers().clone().parse().unwrap(),
+    );
+
+    if let Some(expose_headers) = &cors.get_expose_headers() {
         response.headers_mut().insert(
-            ACCESS_CONTROL_ALLOW_ORIGIN,
-            self.get_allow_origin().clone().parse().unwrap(),
-        );
-        response.headers_mut().insert(
-            ACCESS_CONTROL_ALLOW_METHODS,
-            self.get_allow_methods().clone().parse().unwrap(),
+            ACCESS_CONTROL_EXPOSE_HEADERS,
+            expose_headers.clone().parse().unwrap(),
         );
+    }
+
+    if let Some(allow_credentials) = cors.get_allow_credentials() {
         response.headers_mut().insert(
-            ACCESS_CONTROL_ALLOW_HEADERS,
-            self.get_allow_headers().clone().parse().unwrap(),
+            ACCESS_CONTROL_ALLOW_CREDENTIALS,
+            allow_credentials.to_string().parse().unwrap(),
         );
+    }
 
-        if let Some(expose_headers) = &self.get_expose_headers() {
-            response.headers_mut().insert(
-                ACCESS_CONTROL_EXPOSE_HEADERS,
-                expose_headers.clone().parse().unwrap(),
-            );
-        }
-
-        if let Some(allow_credentials) = self.get_allow_credentials() {
-            response.headers_mut().insert(
-                ACCESS_CONTROL_ALLOW_CREDENTIALS,
-                allow_credentials.to_string().parse().unwrap(),
-            );
-        }
-
-        if let Some(max_age) = self.get_max_age() {
-            response
-                .headers_mut()
-                .insert(ACCESS_CONTROL_MAX_AGE, max_age.to_string().parse().unwrap());
-        }
-
+    if let Some(max_age) = cors.get_max_age() {
         response
+            .headers_mut()
+            .insert(ACCESS_CONTROL_MAX_AGE, max_age.to_string().parse().unwrap());
     }
+
+    response
 }
 
 #[async_trait]
@@ -291,8 +467,9 @@ impl ToHttpResponse for AuthorisationError {
 }
 
 mod internal {
+    use crate::gateway_execution::binary_payload::value_to_bytes;
     use crate::gateway_execution::http_content_type_mapper::{
-        ContentTypeHeaders, HttpContentTypeResponseMapper,
+        ContentTypeHeaderExt, ContentTypeHeaders, HttpContentTypeResponseMapper,
     };
     use crate::gateway_execution::request::RichRequest;
     use crate::gateway_rib_interpreter::EvaluationError;
@@ -302,17 +479,28 @@ mod internal {
     use crate::path::Path;
 
     use crate::headers::ResolvedResponseHeaders;
+    use bytes::Bytes;
+    use golem_wasm_ast::analysis::AnalysedType;
     use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+    use golem_wasm_rpc::{Value, ValueAndType};
+    use poem::web::headers::ContentType;
     use poem::{Body, IntoResponse, ResponseParts};
     use rib::RibResult;
 
     #[derive(Debug)]
     pub(crate) struct IntermediateHttpResponse {
-        body: Option<TypeAnnotatedValue>,
+        body: Option<IntermediateHttpBody>,
         status: StatusCode,
         headers: ResolvedResponseHeaders,
     }
 
+    #[derive(Debug)]
+    enum IntermediateHttpBody {
+        Value(TypeAnnotatedValue),
+        // A `list<u8>` body, served without converting its bytes one by one
+        Binary(Bytes),
+    }
+
     impl IntermediateHttpResponse {
         pub(crate) fn from(
             evaluation_result: &RibResult,
@@ -324,14 +512,20 @@ mod internal {
                     let headers =
                         get_response_headers_or_default(rib_result).map_err(EvaluationError)?;
 
-                    let tav: TypeAnnotatedValue = rib_result
-                        .clone()
-                        .try_into()
-                        .map_err(|errs: Vec<String>| EvaluationError(errs.join(", ")))?;
-
-                    let body = tav
-                        .get_optional(&Path::from_key("body"))
-                        .unwrap_or(tav.clone());
+                    let body = match binary_body(rib_result) {
+                        Some(bytes) => IntermediateHttpBody::Binary(bytes),
+                        None => {
+                            let tav: TypeAnnotatedValue = rib_result
+                                .clone()
+                                .try_into()
+                                .map_err(|errs: Vec<String>| EvaluationError(errs.join(", ")))?;
+
+                            IntermediateHttpBody::Value(
+                                tav.get_optional(&Path::from_key("body"))
+                                    .unwrap_or(tav.clone()),
+                            )
+                        }
+                    };
 
                     Ok(IntermediateHttpResponse {
                         body: Some(body),
@@ -359,11 +553,43 @@ mod internal {
                 .header(http::header::ACCEPT)
                 .map(|s| s.to_string());
 
-            let content_type =
-                ContentTypeHeaders::from(response_content_type, accepted_content_types);
+            // A content type set by the response mapping takes precedence over the negotiated one
+            let content_type = match request_details.negotiated_media_type {
+                Some(media_type) if response_content_type.is_none() => {
+                    ContentTypeHeaders::Negotiated(media_type)
+                }
+                _ => ContentTypeHeaders::from(response_content_type, accepted_content_types),
+            };
 
             let response = match evaluation_result {
-                Some(type_annotated_value) => {
+                Some(IntermediateHttpBody::Binary(bytes)) => {
+                    let content_type = match content_type {
+                        ContentTypeHeaders::FromUserDefinedResponseMapping(content_type) => {
+                            Ok(content_type)
+                        }
+                        ContentTypeHeaders::FromClientAccept(accept_headers) => {
+                            accept_headers.response_content_type()
+                        }
+                        ContentTypeHeaders::Negotiated(_) | ContentTypeHeaders::Empty => {
+                            Ok(ContentType::octet_stream())
+                        }
+                    };
+
+                    match content_type {
+                        Ok(content_type) => {
+                            let mut response = Body::from_bytes(bytes.clone())
+                                .with_content_type(content_type.to_string())
+                                .into_response();
+                            response.set_status(*status);
+                            response.headers_mut().extend(response_headers);
+                            response
+                        }
+                        Err(content_map_error) => poem::Response::builder()
+                            .status(StatusCode::BAD_REQUEST)
+                            .body(Body::from_string(content_map_error.to_string())),
+                    }
+                }
+                Some(IntermediateHttpBody::Value(type_annotated_value)) => {
                     match type_annotated_value.to_http_resp_with_content_type(content_type) {
                         Ok(body_with_header) => {
                             let mut response = body_with_header.into_response();
@@ -391,6 +617,24 @@ mod internal {
             response
         }
     }
+
+    // The body of a response, or the response itself if it has no body, when it is a `list<u8>`
+    fn binary_body(rib_result: &ValueAndType) -> Option<Bytes> {
+        match (&rib_result.value, &rib_result.typ) {
+            (Value::Record(values), AnalysedType::Record(record)) => {
+                let index = record
+                    .fields
+                    .iter()
+                    .position(|field| field.name == "body")?;
+
+                value_to_bytes(&ValueAndType::new(
+                    values.get(index)?.clone(),
+                    record.fields[index].typ.clone(),
+                ))
+            }
+            _ => value_to_bytes(rib_result),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -436,6 +680,11 @@ mod test {
             path_param_extractors: vec![],
             query_info: vec![],
             auth_data: None,
+            client_certificate: None,
+            binary_fields: vec![],
+            negotiated_media_type: None,
+            field_selection: None,
+            experiment_assignments: Default::default(),
         }
     }
 
@@ -0,0 +1,202 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::gateway_http_input_executor::resolve_rib_input;
+use super::request::RichRequest;
+use super::to_response::{GatewayHttpError, GatewayHttpResult};
+use crate::gateway_middleware::{HttpTransforms, TransformCompiled};
+use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use rib::RibResult;
+use serde_json::Value;
+use std::str::FromStr;
+
+// Applies the request transforms of a route in order, each of them seeing the headers
+// set by the previous ones
+pub async fn apply_request_transforms(
+    transforms: &HttpTransforms,
+    request: &mut RichRequest,
+) -> GatewayHttpResult<()> {
+    for transform in &transforms.request_transforms {
+        let mut input = serde_json::Map::new();
+        input.insert(
+            "request".to_string(),
+            request.as_json().map_err(GatewayHttpError::BadRequest)?,
+        );
+
+        let result = evaluate(transform, &input).await?;
+
+        set_headers(request.underlying.headers_mut(), &result)
+            .map_err(GatewayHttpError::BadRequest)?;
+    }
+
+    Ok(())
+}
+
+// Applies the response transforms of a route in order, each of them seeing the status
+// and the headers set by the previous ones
+pub async fn apply_response_transforms(
+    transforms: &HttpTransforms,
+    request: &RichRequest,
+    mut response: poem::Response,
+) -> GatewayHttpResult<poem::Response> {
+    for transform in &transforms.response_transforms {
+        let mut input = serde_json::Map::new();
+        input.insert(
+            "request".to_string(),
+            request.as_json().map_err(GatewayHttpError::BadRequest)?,
+        );
+        input.insert("response".to_string(), response_as_json(&response));
+
+        let result = evaluate(transform, &input).await?;
+
+        if let Some(status) = result.get("status") {
+            let status = status
+                .as_u64()
+                .and_then(|status| u16::try_from(status).ok())
+                .and_then(|status| StatusCode::from_u16(status).ok())
+                .ok_or(GatewayHttpError::RibInterpretPureError(format!(
+                    "Invalid status {} returned by a response transform",
+                    status
+                )))?;
+
+            response.set_status(status);
+        }
+
+        set_headers(response.headers_mut(), &result)
+            .map_err(GatewayHttpError::RibInterpretPureError)?;
+    }
+
+    Ok(response)
+}
+
+async fn evaluate(
+    transform: &TransformCompiled,
+    input: &serde_json::Map<String, Value>,
+) -> GatewayHttpResult<serde_json::Map<String, Value>> {
+    let rib_input = resolve_rib_input(input, &transform.rib_input)
+        .await
+        .map_err(GatewayHttpError::BadRequest)?;
+
+    let result = rib::interpret_pure(&transform.compiled_script, &rib_input)
+        .await
+        .map_err(GatewayHttpError::RibInterpretPureError)?;
+
+    match result {
+        RibResult::Val(value) => match TypeAnnotatedValue::try_from(value)
+            .map_err(|errs| GatewayHttpError::RibInterpretPureError(errs.join(", ")))?
+            .to_json_value()
+        {
+            Value::Object(fields) => Ok(fields),
+            _ => Err(GatewayHttpError::RibInterpretPureError(
+                "Transform is not a Rib expression that resolves to a record".to_string(),
+            )),
+        },
+        RibResult::Unit => Err(GatewayHttpError::RibInterpretPureError(
+            "Transform is not a Rib expression that resolves to a record".to_string(),
+        )),
+    }
+}
+
+// Sets the `headers` of the result of a transform, removing the ones with an empty value
+fn set_headers(
+    headers: &mut HeaderMap,
+    result: &serde_json::Map<String, Value>,
+) -> Result<(), String> {
+    let Some(new_headers) = result.get("headers") else {
+        return Ok(());
+    };
+
+    let new_headers = new_headers
+        .as_object()
+        .ok_or("Headers of a transform must be a record")?;
+
+    for (name, value) in new_headers {
+        let name = HeaderName::from_str(name)
+            .map_err(|_| format!("Invalid header name '{}' in transform", name))?;
+
+        let value = value
+            .as_str()
+            .ok_or(format!("Header {} of a transform must be a string", name))?;
+
+        if value.is_empty() {
+            headers.remove(&name);
+        } else {
+            let value = HeaderValue::from_str(value)
+                .map_err(|_| format!("Invalid value of header {} in transform", name))?;
+            headers.insert(name, value);
+        }
+    }
+
+    Ok(())
+}
+
+fn response_as_json(response: &poem::Response) -> Value {
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.to_string(), Value::String(value.to_string())))
+        })
+        .collect::<serde_json::Map<String, Value>>();
+
+    serde_json::json!({
+        "status": response.status().as_u16(),
+        "headers": headers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn headers_are_set_and_removed() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-internal", HeaderValue::from_static("secret"));
+        headers.insert("x-kept", HeaderValue::from_static("kept"));
+
+        let result = serde_json::json!({
+            "headers": { "x-internal": "", "x-tenant": "acme" }
+        });
+
+        set_headers(&mut headers, result.as_object().unwrap()).unwrap();
+
+        assert_eq!(headers.get("x-internal"), None);
+        assert_eq!(headers.get("x-tenant").unwrap(), "acme");
+        assert_eq!(headers.get("x-kept").unwrap(), "kept");
+
+        let invalid = serde_json::json!({ "headers": { "x-count": 1 } });
+
+        assert!(set_headers(&mut headers, invalid.as_object().unwrap()).is_err());
+    }
+
+    #[test]
+    fn response_is_available_to_transforms() {
+        let response = poem::Response::builder()
+            .status(StatusCode::CREATED)
+            .header("x-version", "2")
+            .finish();
+
+        assert_eq!(
+            response_as_json(&response),
+            serde_json::json!({ "status": 201, "headers": { "x-version": "2" } })
+        );
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::gateway_http_input_executor::resolve_rib_input;
+use super::WorkerDetail;
+use crate::gateway_binding::ResponseMappingCompiled;
+use crate::gateway_rib_interpreter::WorkerServiceRibInterpreter;
+use futures::{SinkExt, StreamExt};
+use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use poem::web::websocket::{CloseCode, Message, WebSocketStream};
+use rib::RibResult;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{debug, info};
+
+// Close frame reasons are limited to 123 bytes by the WebSocket protocol
+const MAX_CLOSE_REASON_LENGTH: usize = 123;
+
+// Bridges an upgraded WebSocket connection to the worker the connection is bound to.
+// Every incoming text message is evaluated by the Rib script of the binding, with the
+// message available as `message`, and the result of the script is sent back to the client.
+// Messages are processed one by one, in the order they are received, so that the worker
+// observes the messages of a connection in order.
+pub struct WebSocketBridge<Namespace> {
+    pub evaluator: Arc<dyn WorkerServiceRibInterpreter<Namespace> + Sync + Send>,
+    pub namespace: Namespace,
+    pub worker_detail: WorkerDetail,
+    // The `request` and `worker` inputs, which are the same for all the messages of the connection
+    pub rib_input: serde_json::Map<String, Value>,
+    pub message_mapping: ResponseMappingCompiled,
+}
+
+impl<Namespace: Clone + Send + Sync + 'static> WebSocketBridge<Namespace> {
+    pub async fn run(self, socket: WebSocketStream) {
+        let (mut sink, mut stream) = socket.split();
+
+        while let Some(message) = stream.next().await {
+            let reply = match message {
+                Ok(Message::Text(text)) => match self.handle_message(text).await {
+                    Ok(Some(reply)) => Message::Text(reply),
+                    Ok(None) => continue,
+                    Err(err) => close_message(CloseCode::Error, err),
+                },
+                Ok(Message::Binary(_)) => close_message(
+                    CloseCode::Unsupported,
+                    "Only text messages are supported".to_string(),
+                ),
+                Ok(Message::Close(_)) => {
+                    debug!("Client closed WebSocket connection");
+                    break;
+                }
+                // Pings are answered by the underlying stream
+                Ok(Message::Ping(_)) | Ok(Message::Pong(_)) => continue,
+                Err(err) => {
+                    info!(error = err.to_string(), "Received WebSocket error");
+                    break;
+                }
+            };
+
+            let is_close = matches!(reply, Message::Close(_));
+
+            if let Err(err) = sink.send(reply).await {
+                info!(error = err.to_string(), "Failed to send WebSocket message");
+                break;
+            }
+
+            if is_close {
+                break;
+            }
+        }
+
+        let _ = sink.close().await;
+    }
+
+    // Evaluates the Rib script for a message, which is available to the script as `message`
+    pub async fn handle_message(&self, message: String) -> Result<Option<String>, String> {
+        let mut rib_input = self.rib_input.clone();
+        rib_input.insert("message".to_string(), Value::String(message));
+
+        evaluate_to_text(
+            self.evaluator.as_ref(),
+            &self.namespace,
+            &self.worker_detail,
+            &rib_input,
+            &self.message_mapping,
+        )
+        .await
+    }
+}
+
+// Evaluates a Rib script producing a text to send to the client. Scripts returning a string
+// have it sent as is, other values are sent as JSON, and nothing is sent for scripts not
+// returning a value. Every evaluation is a separate invocation, the executor generates
+// their idempotency keys.
+pub(crate) async fn evaluate_to_text<Namespace: Clone>(
+    evaluator: &(dyn WorkerServiceRibInterpreter<Namespace> + Sync + Send),
+    namespace: &Namespace,
+    worker_detail: &WorkerDetail,
+    rib_input: &serde_json::Map<String, Value>,
+    mapping: &ResponseMappingCompiled,
+) -> Result<Option<String>, String> {
+    let rib_input = resolve_rib_input(rib_input, &mapping.rib_input).await?;
+
+    let result = evaluator
+        .evaluate(
+            worker_detail.worker_name.as_deref(),
+            &worker_detail.component_id.component_id,
+            &None,
+            worker_detail.invocation_context.clone(),
+            worker_detail.bootstrap.clone(),
+            &mapping.response_mapping_compiled,
+            &rib_input,
+            namespace.clone(),
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+
+    match result {
+        RibResult::Unit => Ok(None),
+        RibResult::Val(value_and_type) => {
+            let value =
+                TypeAnnotatedValue::try_from(value_and_type).map_err(|errs| errs.join(", "))?;
+
+            match value {
+                TypeAnnotatedValue::Str(text) => Ok(Some(text)),
+                other => Ok(Some(other.to_json_value().to_string())),
+            }
+        }
+    }
+}
+
+fn close_message(code: CloseCode, reason: String) -> Message {
+    let mut reason = reason;
+    if reason.len() > MAX_CLOSE_REASON_LENGTH {
+        let mut end = MAX_CLOSE_REASON_LENGTH;
+        while !reason.is_char_boundary(end) {
+            end -= 1;
+        }
+        reason.truncate(end);
+    }
+    Message::Close(Some((code, reason)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn close_reason_is_truncated_on_char_boundary() {
+        let reason = "é".repeat(100);
+
+        match close_message(CloseCode::Error, reason) {
+            Message::Close(Some((code, reason))) => {
+                assert_eq!(code, CloseCode::Error);
+                assert_eq!(reason.len(), 122);
+            }
+            _ => panic!("Expected a close message"),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_execution::auth_call_back_binding_handler::AuthorisationError;
+use crate::gateway_execution::request::RichRequest;
+use crate::gateway_middleware::MiddlewareError;
+use crate::gateway_security::{
+    ApiKeyLocation, ApiKeySecurityScheme, ApiKeyValidationError, ApiKeyValidator,
+};
+use std::sync::Arc;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpApiKeyAuthenticationMiddleware {
+    pub api_key_security_scheme: ApiKeySecurityScheme,
+}
+
+impl HttpApiKeyAuthenticationMiddleware {
+    // Requests without a valid key are rejected, and the details of the key
+    // of valid ones (but never its secret) are made available to Rib as `request.auth`
+    pub async fn apply_api_key_auth<Namespace>(
+        &self,
+        namespace: &Namespace,
+        input: &mut RichRequest,
+        api_key_validator: &Arc<dyn ApiKeyValidator<Namespace> + Send + Sync>,
+    ) -> Result<(), MiddlewareError> {
+        let presented_key = presented_key(input, self.api_key_security_scheme.location()).ok_or(
+            MiddlewareError::Unauthorized(AuthorisationError::ApiKeyNotFound),
+        )?;
+
+        let api_key = api_key_validator
+            .validate(namespace, &self.api_key_security_scheme, &presented_key)
+            .await
+            .map_err(|err| match err {
+                ApiKeyValidationError::InvalidKey => {
+                    debug!(
+                        "Invalid API key for security scheme {}",
+                        self.api_key_security_scheme.scheme_identifier()
+                    );
+                    MiddlewareError::Unauthorized(AuthorisationError::InvalidApiKey)
+                }
+                ApiKeyValidationError::InternalError(err) => MiddlewareError::InternalError(err),
+            })?;
+
+        input.auth_data = Some(api_key.auth_data());
+
+        Ok(())
+    }
+}
+
+fn presented_key(input: &RichRequest, location: &ApiKeyLocation) -> Option<String> {
+    let key = match location {
+        ApiKeyLocation::Header(name) => input
+            .underlying
+            .headers()
+            .get(name.as_str())
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string()),
+        ApiKeyLocation::QueryParam(name) => input.underlying.uri().query().and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        }),
+    };
+
+    key.filter(|key| !key.trim().is_empty())
+}
@@ -0,0 +1,81 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Limits the size of the bodies of a route, and the time clients have to send their request body.
+///
+/// Request bodies are read by the gateway before evaluating the binding, and requests whose body
+/// is over `maxRequestBodyBytes` are rejected with `413 Payload Too Large`, and the ones not sent
+/// within `readTimeoutMs` with `408 Request Timeout`. Responses are cut short once their body
+/// goes over `maxResponseBodyBytes`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpBodyLimits {
+    pub max_request_body_bytes: Option<u64>,
+    pub max_response_body_bytes: Option<u64>,
+    pub read_timeout_ms: Option<u64>,
+}
+
+impl HttpBodyLimits {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_request_body_bytes.is_none()
+            && self.max_response_body_bytes.is_none()
+            && self.read_timeout_ms.is_none()
+        {
+            return Err("Body limits must set at least one limit".to_string());
+        }
+
+        if self.max_request_body_bytes == Some(0)
+            || self.max_response_body_bytes == Some(0)
+            || self.read_timeout_ms == Some(0)
+        {
+            return Err("Body limits must be greater than 0".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout_ms.map(Duration::from_millis)
+    }
+
+    // Whether the request body has to be read before evaluating the binding
+    pub fn limits_request(&self) -> bool {
+        self.max_request_body_bytes.is_some() || self.read_timeout_ms.is_some()
+    }
+}
+
+impl From<HttpBodyLimits> for golem_api_grpc::proto::golem::apidefinition::BodyLimits {
+    fn from(value: HttpBodyLimits) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::BodyLimits {
+            max_request_body_bytes: value.max_request_body_bytes,
+            max_response_body_bytes: value.max_response_body_bytes,
+            read_timeout_ms: value.read_timeout_ms,
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::BodyLimits> for HttpBodyLimits {
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::BodyLimits) -> Self {
+        HttpBodyLimits {
+            max_request_body_bytes: value.max_request_body_bytes,
+            max_response_body_bytes: value.max_response_body_bytes,
+            read_timeout_ms: value.read_timeout_ms,
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Compresses the responses of a route, or of all the routes of a deployment, with one of the
+/// `algorithms` (`gzip` or `br`) accepted by the client.
+///
+/// When the client accepts several of them equally, the first one listed is used. Only the
+/// responses of at least `minSizeBytes` whose content type matches one of the `contentTypes`
+/// (such as `application/json`, `text/*` or `application/*+json`) are compressed, and without
+/// `contentTypes` the usual text, JSON, XML, JavaScript and SVG types are. Responses are
+/// compressed while they are streamed. The compression of a route overrides the one of its
+/// deployment, and a route can turn it off with `enabled` set to false.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpCompression {
+    #[serde(default = "default_enabled")]
+    #[oai(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_algorithms")]
+    #[oai(default = "default_algorithms")]
+    pub algorithms: Vec<String>,
+    #[serde(default = "default_min_size_bytes")]
+    #[oai(default = "default_min_size_bytes")]
+    pub min_size_bytes: u64,
+    #[serde(default)]
+    #[oai(default)]
+    pub content_types: Vec<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_algorithms() -> Vec<String> {
+    vec![
+        CompressionAlgorithm::Brotli.to_string(),
+        CompressionAlgorithm::Gzip.to_string(),
+    ]
+}
+
+fn default_min_size_bytes() -> u64 {
+    1024
+}
+
+const DEFAULT_CONTENT_TYPES: [&str; 7] = [
+    "text/*",
+    "application/json",
+    "application/*+json",
+    "application/xml",
+    "application/*+xml",
+    "application/javascript",
+    "image/svg+xml",
+];
+
+impl Default for HttpCompression {
+    fn default() -> Self {
+        HttpCompression {
+            enabled: default_enabled(),
+            algorithms: default_algorithms(),
+            min_size_bytes: default_min_size_bytes(),
+            content_types: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Brotli,
+}
+
+impl FromStr for CompressionAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "gzip" => Ok(CompressionAlgorithm::Gzip),
+            "br" => Ok(CompressionAlgorithm::Brotli),
+            other => Err(format!(
+                "Invalid compression algorithm '{}', expected 'gzip' or 'br'",
+                other
+            )),
+        }
+    }
+}
+
+// The content coding of the algorithm, as in the Accept-Encoding and Content-Encoding headers
+impl Display for CompressionAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompressionAlgorithm::Gzip => write!(f, "gzip"),
+            CompressionAlgorithm::Brotli => write!(f, "br"),
+        }
+    }
+}
+
+impl HttpCompression {
+    pub fn validate(&self) -> Result<(), String> {
+        let algorithms = self.algorithms()?;
+
+        if self.enabled && algorithms.is_empty() {
+            return Err("Compression must have at least one algorithm".to_string());
+        }
+
+        for content_type in &self.content_types {
+            match content_type.trim().split_once('/') {
+                Some((type_, subtype)) if !type_.is_empty() && !subtype.is_empty() => {}
+                _ => {
+                    return Err(format!(
+                        "Invalid content type '{}', expected '<type>/<subtype>'",
+                        content_type
+                    ))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn algorithms(&self) -> Result<Vec<CompressionAlgorithm>, String> {
+        self.algorithms
+            .iter()
+            .map(|algorithm| CompressionAlgorithm::from_str(algorithm))
+            .collect()
+    }
+
+    // Whether responses of the content type (without its parameters) are compressed.
+    // Event streams never are, as the encoders hold back the events until they have enough of them.
+    pub fn compresses_content_type(&self, content_type: &str) -> bool {
+        let content_type = content_type.trim().to_lowercase();
+
+        if content_type == "text/event-stream" {
+            return false;
+        }
+
+        if self.content_types.is_empty() {
+            DEFAULT_CONTENT_TYPES
+                .iter()
+                .any(|pattern| content_type_matches(pattern, &content_type))
+        } else {
+            self.content_types
+                .iter()
+                .any(|pattern| content_type_matches(&pattern.trim().to_lowercase(), &content_type))
+        }
+    }
+}
+
+// Matches `type/subtype`, `type/*` and `type/*+suffix` patterns
+fn content_type_matches(pattern: &str, content_type: &str) -> bool {
+    match (pattern.split_once('/'), content_type.split_once('/')) {
+        (Some((pattern_type, pattern_subtype)), Some((type_, subtype))) => {
+            pattern_type == type_
+                && match pattern_subtype.strip_prefix('*') {
+                    Some(suffix) => subtype.ends_with(suffix),
+                    None => pattern_subtype == subtype,
+                }
+        }
+        _ => false,
+    }
+}
+
+impl From<HttpCompression> for golem_api_grpc::proto::golem::apidefinition::Compression {
+    fn from(value: HttpCompression) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::Compression {
+            enabled: value.enabled,
+            algorithms: value.algorithms,
+            min_size_bytes: value.min_size_bytes,
+            content_types: value.content_types,
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::Compression> for HttpCompression {
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::Compression) -> Self {
+        HttpCompression {
+            enabled: value.enabled,
+            algorithms: value.algorithms,
+            min_size_bytes: value.min_size_bytes,
+            content_types: value.content_types,
+        }
+    }
+}
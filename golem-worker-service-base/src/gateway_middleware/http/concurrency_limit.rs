@@ -0,0 +1,107 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::StatusCode;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const DEFAULT_QUEUE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Limits the number of requests of a route processed at the same time by a gateway.
+///
+/// Requests over `maxInFlight` wait in a queue of at most `maxQueued` requests for up to
+/// `queueTimeoutMs`, and requests which do not fit in the queue or time out waiting are shed
+/// with `shedStatus` (`503 Service Unavailable` by default), `shedBody` and, if set,
+/// a `Retry-After` header of `retryAfter` seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpConcurrencyLimit {
+    pub max_in_flight: u32,
+    #[serde(default)]
+    #[oai(default)]
+    pub max_queued: u32,
+    pub queue_timeout_ms: Option<u64>,
+    pub shed_status: Option<u16>,
+    pub shed_body: Option<String>,
+    pub retry_after: Option<u64>,
+}
+
+impl HttpConcurrencyLimit {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_in_flight == 0 {
+            return Err("Concurrency limit max-in-flight must be greater than 0".to_string());
+        }
+
+        if let Some(shed_status) = self.shed_status {
+            if !(400..600).contains(&shed_status) {
+                return Err(format!(
+                    "Concurrency limit shed status must be an error status, but found {}",
+                    shed_status
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn queue_timeout(&self) -> Duration {
+        self.queue_timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_QUEUE_TIMEOUT)
+    }
+
+    pub fn shed_status(&self) -> StatusCode {
+        self.shed_status
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .unwrap_or(StatusCode::SERVICE_UNAVAILABLE)
+    }
+}
+
+impl From<HttpConcurrencyLimit> for golem_api_grpc::proto::golem::apidefinition::ConcurrencyLimit {
+    fn from(value: HttpConcurrencyLimit) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::ConcurrencyLimit {
+            max_in_flight: value.max_in_flight,
+            max_queued: value.max_queued,
+            queue_timeout_ms: value.queue_timeout_ms,
+            shed_status: value.shed_status.map(|status| status as u32),
+            shed_body: value.shed_body,
+            retry_after: value.retry_after,
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::ConcurrencyLimit>
+    for HttpConcurrencyLimit
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::ConcurrencyLimit,
+    ) -> Result<Self, Self::Error> {
+        Ok(HttpConcurrencyLimit {
+            max_in_flight: value.max_in_flight,
+            max_queued: value.max_queued,
+            queue_timeout_ms: value.queue_timeout_ms,
+            shed_status: value
+                .shed_status
+                .map(u16::try_from)
+                .transpose()
+                .map_err(|err| format!("Invalid shed status: {}", err))?,
+            shed_body: value.shed_body,
+            retry_after: value.retry_after,
+        })
+    }
+}
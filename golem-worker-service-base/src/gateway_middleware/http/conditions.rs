@@ -0,0 +1,300 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::{DefaultWorkerServiceRibCompiler, WorkerServiceRibCompiler};
+use rib::{Expr, RibByteCode, RibInputTypeInfo};
+use std::fmt::Display;
+use std::str::FromStr;
+
+// The middlewares of a route that can be made conditional
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConditionalMiddleware {
+    Security,
+    RateLimit,
+    ResponseCache,
+    Transforms,
+}
+
+impl ConditionalMiddleware {
+    pub const ALL: [ConditionalMiddleware; 4] = [
+        ConditionalMiddleware::Security,
+        ConditionalMiddleware::RateLimit,
+        ConditionalMiddleware::ResponseCache,
+        ConditionalMiddleware::Transforms,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConditionalMiddleware::Security => "security",
+            ConditionalMiddleware::RateLimit => "rate-limit",
+            ConditionalMiddleware::ResponseCache => "response-cache",
+            ConditionalMiddleware::Transforms => "transforms",
+        }
+    }
+}
+
+impl FromStr for ConditionalMiddleware {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ConditionalMiddleware::ALL
+            .into_iter()
+            .find(|middleware| middleware.name() == s.trim())
+            .ok_or(format!(
+                "Unknown middleware '{}', expected one of {}",
+                s,
+                ConditionalMiddleware::ALL
+                    .iter()
+                    .map(|middleware| middleware.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+    }
+}
+
+impl Display for ConditionalMiddleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// The Rib predicates deciding whether the middlewares of a route apply to a request.
+// A middleware without a condition always applies, and one whose condition is false for a request
+// is skipped for it, as if the route did not declare it.
+// The conditions see the request as it was received, before the request transforms.
+// Example: `request.headers.user-agent != "health-check"` as the condition of the security
+// skips the authentication of the health checks.
+// As the predicates cannot call workers, they are compiled without any component metadata.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HttpMiddlewareConditions {
+    pub security: Option<MiddlewareConditionCompiled>,
+    pub rate_limit: Option<MiddlewareConditionCompiled>,
+    pub response_cache: Option<MiddlewareConditionCompiled>,
+    pub transforms: Option<MiddlewareConditionCompiled>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiddlewareConditionCompiled {
+    pub predicate: Expr,
+    pub compiled_predicate: RibByteCode,
+    pub rib_input: RibInputTypeInfo,
+}
+
+impl MiddlewareConditionCompiled {
+    pub fn new(middleware: ConditionalMiddleware, predicate: Expr) -> Result<Self, String> {
+        DefaultWorkerServiceRibCompiler::compile_middleware_condition(&predicate)
+            .map(|compiled| MiddlewareConditionCompiled {
+                predicate,
+                compiled_predicate: compiled.byte_code,
+                rib_input: compiled.rib_input_type_info,
+            })
+            .map_err(|err| format!("Invalid condition of the {}: {}", middleware, err))
+    }
+}
+
+impl HttpMiddlewareConditions {
+    pub fn new(conditions: Vec<(ConditionalMiddleware, Expr)>) -> Result<Self, String> {
+        let mut result = HttpMiddlewareConditions::default();
+
+        for (middleware, predicate) in conditions {
+            if result.get(middleware).is_some() {
+                return Err(format!("The {} has more than one condition", middleware));
+            }
+
+            let compiled = MiddlewareConditionCompiled::new(middleware, predicate)?;
+            *result.get_mut(middleware) = Some(compiled);
+        }
+
+        Ok(result)
+    }
+
+    pub fn get(&self, middleware: ConditionalMiddleware) -> Option<&MiddlewareConditionCompiled> {
+        match middleware {
+            ConditionalMiddleware::Security => self.security.as_ref(),
+            ConditionalMiddleware::RateLimit => self.rate_limit.as_ref(),
+            ConditionalMiddleware::ResponseCache => self.response_cache.as_ref(),
+            ConditionalMiddleware::Transforms => self.transforms.as_ref(),
+        }
+    }
+
+    fn get_mut(
+        &mut self,
+        middleware: ConditionalMiddleware,
+    ) -> &mut Option<MiddlewareConditionCompiled> {
+        match middleware {
+            ConditionalMiddleware::Security => &mut self.security,
+            ConditionalMiddleware::RateLimit => &mut self.rate_limit,
+            ConditionalMiddleware::ResponseCache => &mut self.response_cache,
+            ConditionalMiddleware::Transforms => &mut self.transforms,
+        }
+    }
+
+    // The conditions of the middlewares, in the order the middlewares are applied
+    pub fn conditions(&self) -> Vec<(ConditionalMiddleware, &MiddlewareConditionCompiled)> {
+        ConditionalMiddleware::ALL
+            .into_iter()
+            .filter_map(|middleware| {
+                self.get(middleware)
+                    .map(|condition| (middleware, condition))
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions().is_empty()
+    }
+}
+
+impl TryFrom<MiddlewareConditionCompiled>
+    for golem_api_grpc::proto::golem::apidefinition::MiddlewareCondition
+{
+    type Error = String;
+
+    fn try_from(value: MiddlewareConditionCompiled) -> Result<Self, Self::Error> {
+        Ok(
+            golem_api_grpc::proto::golem::apidefinition::MiddlewareCondition {
+                predicate: Some(value.predicate.into()),
+                compiled_predicate: Some(value.compiled_predicate.try_into()?),
+                rib_input: Some(value.rib_input.into()),
+            },
+        )
+    }
+}
+
+impl TryFrom<HttpMiddlewareConditions>
+    for golem_api_grpc::proto::golem::apidefinition::MiddlewareConditions
+{
+    type Error = String;
+
+    fn try_from(value: HttpMiddlewareConditions) -> Result<Self, Self::Error> {
+        Ok(
+            golem_api_grpc::proto::golem::apidefinition::MiddlewareConditions {
+                security: value.security.map(|c| c.try_into()).transpose()?,
+                rate_limit: value.rate_limit.map(|c| c.try_into()).transpose()?,
+                response_cache: value.response_cache.map(|c| c.try_into()).transpose()?,
+                transforms: value.transforms.map(|c| c.try_into()).transpose()?,
+            },
+        )
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::MiddlewareConditions>
+    for HttpMiddlewareConditions
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::MiddlewareConditions,
+    ) -> Result<Self, Self::Error> {
+        let convert = |middleware, condition| internal::condition_compiled(middleware, condition);
+
+        Ok(HttpMiddlewareConditions {
+            security: value
+                .security
+                .map(|c| convert(ConditionalMiddleware::Security, c))
+                .transpose()?,
+            rate_limit: value
+                .rate_limit
+                .map(|c| convert(ConditionalMiddleware::RateLimit, c))
+                .transpose()?,
+            response_cache: value
+                .response_cache
+                .map(|c| convert(ConditionalMiddleware::ResponseCache, c))
+                .transpose()?,
+            transforms: value
+                .transforms
+                .map(|c| convert(ConditionalMiddleware::Transforms, c))
+                .transpose()?,
+        })
+    }
+}
+
+mod internal {
+    use super::{ConditionalMiddleware, MiddlewareConditionCompiled};
+    use rib::{Expr, RibByteCode, RibInputTypeInfo};
+
+    // Conditions received without the compiled predicate, such as user facing ones, are compiled
+    pub(crate) fn condition_compiled(
+        middleware: ConditionalMiddleware,
+        value: golem_api_grpc::proto::golem::apidefinition::MiddlewareCondition,
+    ) -> Result<MiddlewareConditionCompiled, String> {
+        let predicate = Expr::try_from(value.predicate.ok_or("Missing condition predicate")?)?;
+
+        match (value.compiled_predicate, value.rib_input) {
+            (Some(compiled_predicate), Some(rib_input)) => Ok(MiddlewareConditionCompiled {
+                predicate,
+                compiled_predicate: RibByteCode::try_from(compiled_predicate)?,
+                rib_input: RibInputTypeInfo::try_from(rib_input)?,
+            }),
+            _ => MiddlewareConditionCompiled::new(middleware, predicate),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway_middleware::{HttpCors, HttpMiddleware, HttpMiddlewares, HttpRateLimit};
+    use test_r::test;
+
+    #[test]
+    fn middlewares_are_skipped_by_their_conditions() {
+        let not_health_check =
+            || rib::from_string(r#"request.headers.user-agent != "health-check""#).unwrap();
+
+        let conditions = HttpMiddlewareConditions::new(vec![(
+            ConditionalMiddleware::RateLimit,
+            not_health_check(),
+        )])
+        .unwrap();
+
+        assert!(conditions.get(ConditionalMiddleware::RateLimit).is_some());
+        assert!(conditions.get(ConditionalMiddleware::Security).is_none());
+        assert_eq!(
+            conditions
+                .conditions()
+                .into_iter()
+                .map(|(middleware, _)| middleware)
+                .collect::<Vec<_>>(),
+            vec![ConditionalMiddleware::RateLimit]
+        );
+
+        assert_eq!(
+            HttpMiddlewareConditions::new(vec![
+                (ConditionalMiddleware::RateLimit, not_health_check()),
+                (ConditionalMiddleware::RateLimit, not_health_check()),
+            ]),
+            Err("The rate-limit has more than one condition".to_string())
+        );
+
+        assert_eq!(
+            ConditionalMiddleware::from_str("response-cache"),
+            Ok(ConditionalMiddleware::ResponseCache)
+        );
+        assert!(ConditionalMiddleware::from_str("cors").is_err());
+
+        let middlewares = HttpMiddlewares(vec![
+            HttpMiddleware::cors(HttpCors::default()),
+            HttpMiddleware::limit_rate(HttpRateLimit::new(10, 1000, None).unwrap()),
+            HttpMiddleware::apply_conditionally(conditions),
+        ]);
+
+        let applying = middlewares.without(&[ConditionalMiddleware::RateLimit]);
+
+        assert!(applying.get_rate_limit_middleware().is_none());
+        assert!(applying.get_cors_middleware().is_some());
+        assert!(applying.get_conditions_middleware().is_some());
+        assert_eq!(middlewares.without(&[]), middlewares);
+    }
+}
@@ -18,7 +18,7 @@ use poem_openapi::Object;
 use rib::{Expr, GetLiteralValue, RibInput, TypeName};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
 #[serde(rename_all = "camelCase")]
 #[oai(rename_all = "camelCase")]
 pub struct HttpCors {
@@ -66,6 +66,42 @@ impl HttpCors {
         self.allow_origin.clone()
     }
 
+    // The allowed origin is either `*`, a single origin, or a comma separated list of origins
+    pub fn has_allow_origin_list(&self) -> bool {
+        self.allow_origin.contains(',')
+    }
+
+    // The value of the Access-Control-Allow-Origin header of the responses to the requests from the
+    // given origin. With a list of allowed origins, the origin of the request is echoed back if it
+    // is one of them, and no origin is allowed otherwise.
+    pub fn get_allow_origin_for(&self, origin: Option<&str>) -> Option<String> {
+        if !self.has_allow_origin_list() {
+            return Some(self.allow_origin.clone());
+        }
+
+        origin
+            .filter(|origin| {
+                self.allow_origin
+                    .split(',')
+                    .any(|allowed| allowed.trim().eq_ignore_ascii_case(origin))
+            })
+            .map(|origin| origin.to_string())
+    }
+
+    // With a list of allowed origins, the responses vary with the Origin of the requests, so caches
+    // have to keep them apart
+    pub fn set_allow_origin_header(&self, headers: &mut HeaderMap, origin: Option<&str>) {
+        if self.has_allow_origin_list() {
+            headers.append(VARY, HeaderValue::from_static("Origin"));
+        }
+
+        if let Some(allow_origin) = self.get_allow_origin_for(origin) {
+            if let Ok(allow_origin) = HeaderValue::from_str(&allow_origin) {
+                headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+            }
+        }
+    }
+
     pub fn get_allow_methods(&self) -> String {
         self.allow_methods.clone()
     }
@@ -122,6 +158,29 @@ impl HttpCors {
         Ok(cors_preflight)
     }
 
+    // Checks a configuration which did not go through the setters, such as one
+    // received as is from the API, as its values are used as headers in the hot path
+    pub fn validate(&self) -> Result<(), String> {
+        HttpCors::from_parameters(
+            Some(self.allow_origin.clone()),
+            Some(self.allow_methods.clone()),
+            Some(self.allow_headers.clone()),
+            self.expose_headers.clone(),
+            self.allow_credentials,
+            self.max_age,
+        )?;
+
+        for value in [&self.allow_origin, &self.allow_methods, &self.allow_headers]
+            .into_iter()
+            .chain(self.expose_headers.as_ref())
+        {
+            HeaderValue::from_str(value)
+                .map_err(|_| format!("Invalid CORS header value: {}", value))?;
+        }
+
+        Ok(())
+    }
+
     pub fn from_cors_preflight_expr(expr: &CorsPreflightExpr) -> Result<HttpCors, String> {
         let compiled_expr = rib::compile(&expr.0, &vec![])
             .map_err(|err| format!("Rib compilation for cors-preflight response. {}", err))?;
@@ -167,7 +226,18 @@ impl HttpCors {
     }
 
     pub fn set_allow_origin(&mut self, allow_origin: &str) -> Result<(), String> {
-        if allow_origin == "*" || !allow_origin.is_empty() {
+        if allow_origin.contains(',') {
+            if allow_origin
+                .split(',')
+                .map(|origin| origin.trim())
+                .all(|origin| !origin.is_empty() && origin != "*")
+            {
+                self.allow_origin = allow_origin.to_string();
+                Ok(())
+            } else {
+                Err("Invalid allow_origin value. A list of origins cannot contain an empty origin or '*'.".to_string())
+            }
+        } else if allow_origin == "*" || !allow_origin.is_empty() {
             self.allow_origin = allow_origin.to_string();
             Ok(())
         } else {
@@ -0,0 +1,82 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_execution::auth_call_back_binding_handler::AuthorisationError;
+use crate::gateway_execution::request::RichRequest;
+use crate::gateway_middleware::MiddlewareError;
+use crate::gateway_security::{
+    HmacSecurityScheme, HmacSignatureValidationError, HmacSignatureValidator,
+};
+use poem::Body;
+use std::sync::Arc;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpHmacAuthenticationMiddleware {
+    pub hmac_security_scheme: HmacSecurityScheme,
+}
+
+impl HttpHmacAuthenticationMiddleware {
+    // The signature covers the raw body, so the body is read (and put back for the binding)
+    // before anything parses it. Requests without a valid signature are rejected.
+    // The secret is the one of the scheme in the namespace of the route.
+    pub async fn apply_hmac_auth<Namespace>(
+        &self,
+        namespace: &Namespace,
+        input: &mut RichRequest,
+        hmac_signature_validator: &Arc<dyn HmacSignatureValidator<Namespace> + Send + Sync>,
+    ) -> Result<(), MiddlewareError> {
+        let presented_signature = input
+            .underlying
+            .headers()
+            .get(self.hmac_security_scheme.header())
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| value.to_string())
+            .ok_or(MiddlewareError::Unauthorized(
+                AuthorisationError::SignatureNotFound,
+            ))?;
+
+        let body = input
+            .underlying
+            .take_body()
+            .into_bytes()
+            .await
+            .map_err(|err| {
+                MiddlewareError::InternalError(format!("Failed reading request body: {}", err))
+            })?;
+
+        let result = hmac_signature_validator
+            .validate(
+                namespace,
+                &self.hmac_security_scheme,
+                &body,
+                &presented_signature,
+            )
+            .await;
+
+        input.underlying.set_body(Body::from_bytes(body));
+
+        result.map_err(|err| match err {
+            HmacSignatureValidationError::InvalidSignature => {
+                debug!(
+                    "Invalid signature for security scheme {}",
+                    self.hmac_security_scheme.scheme_identifier()
+                );
+                MiddlewareError::Unauthorized(AuthorisationError::InvalidSignature)
+            }
+            HmacSignatureValidationError::InternalError(err) => MiddlewareError::InternalError(err),
+        })
+    }
+}
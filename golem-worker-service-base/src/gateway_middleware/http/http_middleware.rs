@@ -12,19 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::gateway_middleware::http::api_key_authentication::HttpApiKeyAuthenticationMiddleware;
 use crate::gateway_middleware::http::authentication::HttpAuthenticationMiddleware;
 use std::ops::Deref;
 
+use crate::gateway_middleware::http::body_limits::HttpBodyLimits;
+use crate::gateway_middleware::http::compression::HttpCompression;
+use crate::gateway_middleware::http::concurrency_limit::HttpConcurrencyLimit;
+use crate::gateway_middleware::http::conditions::{
+    ConditionalMiddleware, HttpMiddlewareConditions,
+};
 use crate::gateway_middleware::http::cors::HttpCors;
-use crate::gateway_security::SecuritySchemeWithProviderMetadata;
-use http::header::{
-    ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+use crate::gateway_middleware::http::hmac_authentication::HttpHmacAuthenticationMiddleware;
+use crate::gateway_middleware::http::jwt_authentication::HttpJwtAuthenticationMiddleware;
+use crate::gateway_middleware::http::rate_limit::HttpRateLimit;
+use crate::gateway_middleware::http::response_cache::HttpResponseCache;
+use crate::gateway_middleware::http::transforms::HttpTransforms;
+use crate::gateway_security::{
+    ApiKeySecurityScheme, HmacSecurityScheme, JwtSecurityScheme, SecuritySchemeWithProviderMetadata,
 };
+use http::header::{ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_EXPOSE_HEADERS};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum HttpMiddleware {
     AddCorsHeaders(HttpCors),
     AuthenticateRequest(Box<HttpAuthenticationMiddleware>), // Middleware to authenticate before feeding the input to the binding executor
+    LimitConcurrency(HttpConcurrencyLimit), // Enforced by the executor, which keeps track of the requests in flight
+    LimitRate(HttpRateLimit), // Enforced by the executor, with buckets shared by the gateway instances
+    LimitBody(HttpBodyLimits), // Enforced by the executor, which reads the request body before the binding
+    AuthenticateJwt(Box<HttpJwtAuthenticationMiddleware>), // Middleware to validate the bearer token before feeding the input to the binding executor
+    AuthenticateApiKey(Box<HttpApiKeyAuthenticationMiddleware>), // Middleware to validate the API key before feeding the input to the binding executor
+    AuthenticateHmac(Box<HttpHmacAuthenticationMiddleware>), // Middleware to verify the signature of the body before feeding the input to the binding executor
+    CacheResponse(HttpResponseCache), // Enforced by the executor, which serves the cached responses instead of evaluating the binding
+    Transform(HttpTransforms), // Applied by the executor, which evaluates the request transforms before the binding and the response transforms after it
+    ApplyConditionally(HttpMiddlewareConditions), // Evaluated by the executor, which skips the middlewares whose condition is false for the request
+    CompressResponse(HttpCompression), // Applied by the executor, which compresses the response while streaming it to the client
 }
 
 impl HttpMiddleware {
@@ -32,6 +54,16 @@ impl HttpMiddleware {
         match self {
             HttpMiddleware::AddCorsHeaders(cors) => Some(cors.clone()),
             HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
         }
     }
 
@@ -41,6 +73,208 @@ impl HttpMiddleware {
                 Some(authentication.deref().clone())
             }
             HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_concurrency_limit(&self) -> Option<HttpConcurrencyLimit> {
+        match self {
+            HttpMiddleware::LimitConcurrency(concurrency_limit) => Some(concurrency_limit.clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_rate_limit(&self) -> Option<HttpRateLimit> {
+        match self {
+            HttpMiddleware::LimitRate(rate_limit) => Some(rate_limit.clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_body_limits(&self) -> Option<HttpBodyLimits> {
+        match self {
+            HttpMiddleware::LimitBody(body_limits) => Some(body_limits.clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_response_cache(&self) -> Option<HttpResponseCache> {
+        match self {
+            HttpMiddleware::CacheResponse(response_cache) => Some(response_cache.clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_transforms(&self) -> Option<HttpTransforms> {
+        match self {
+            HttpMiddleware::Transform(transforms) => Some(transforms.clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_conditions(&self) -> Option<HttpMiddlewareConditions> {
+        match self {
+            HttpMiddleware::ApplyConditionally(conditions) => Some(conditions.clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_compression(&self) -> Option<HttpCompression> {
+        match self {
+            HttpMiddleware::CompressResponse(compression) => Some(compression.clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+        }
+    }
+
+    // The conditional middleware this one is, if it can be made conditional
+    pub fn conditional_middleware(&self) -> Option<ConditionalMiddleware> {
+        match self {
+            HttpMiddleware::AuthenticateRequest(_)
+            | HttpMiddleware::AuthenticateJwt(_)
+            | HttpMiddleware::AuthenticateApiKey(_)
+            | HttpMiddleware::AuthenticateHmac(_) => Some(ConditionalMiddleware::Security),
+            HttpMiddleware::LimitRate(_) => Some(ConditionalMiddleware::RateLimit),
+            HttpMiddleware::CacheResponse(_) => Some(ConditionalMiddleware::ResponseCache),
+            HttpMiddleware::Transform(_) => Some(ConditionalMiddleware::Transforms),
+            HttpMiddleware::AddCorsHeaders(_)
+            | HttpMiddleware::LimitConcurrency(_)
+            | HttpMiddleware::LimitBody(_)
+            | HttpMiddleware::ApplyConditionally(_)
+            | HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_jwt_authentication(&self) -> Option<HttpJwtAuthenticationMiddleware> {
+        match self {
+            HttpMiddleware::AuthenticateJwt(authentication) => Some(authentication.deref().clone()),
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_api_key_authentication(&self) -> Option<HttpApiKeyAuthenticationMiddleware> {
+        match self {
+            HttpMiddleware::AuthenticateApiKey(authentication) => {
+                Some(authentication.deref().clone())
+            }
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateHmac(_) => None,
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+        }
+    }
+
+    pub fn get_hmac_authentication(&self) -> Option<HttpHmacAuthenticationMiddleware> {
+        match self {
+            HttpMiddleware::AuthenticateHmac(authentication) => {
+                Some(authentication.deref().clone())
+            }
+            HttpMiddleware::CacheResponse(_) => None,
+            HttpMiddleware::Transform(_) => None,
+            HttpMiddleware::ApplyConditionally(_) => None,
+            HttpMiddleware::CompressResponse(_) => None,
+            HttpMiddleware::AddCorsHeaders(_) => None,
+            HttpMiddleware::AuthenticateRequest(_) => None,
+            HttpMiddleware::LimitConcurrency(_) => None,
+            HttpMiddleware::LimitRate(_) => None,
+            HttpMiddleware::LimitBody(_) => None,
+            HttpMiddleware::AuthenticateJwt(_) => None,
+            HttpMiddleware::AuthenticateApiKey(_) => None,
         }
     }
 
@@ -51,16 +285,59 @@ impl HttpMiddleware {
             security_scheme_with_metadata: security_scheme,
         }))
     }
+
+    pub fn authenticate_jwt(jwt_security_scheme: JwtSecurityScheme) -> HttpMiddleware {
+        HttpMiddleware::AuthenticateJwt(Box::new(HttpJwtAuthenticationMiddleware {
+            jwt_security_scheme,
+        }))
+    }
+
+    pub fn authenticate_api_key(api_key_security_scheme: ApiKeySecurityScheme) -> HttpMiddleware {
+        HttpMiddleware::AuthenticateApiKey(Box::new(HttpApiKeyAuthenticationMiddleware {
+            api_key_security_scheme,
+        }))
+    }
+
+    pub fn authenticate_hmac(hmac_security_scheme: HmacSecurityScheme) -> HttpMiddleware {
+        HttpMiddleware::AuthenticateHmac(Box::new(HttpHmacAuthenticationMiddleware {
+            hmac_security_scheme,
+        }))
+    }
+
     pub fn cors(cors: HttpCors) -> Self {
         HttpMiddleware::AddCorsHeaders(cors)
     }
 
-    pub fn apply_cors(response: &mut poem::Response, cors: &HttpCors) {
-        response.headers_mut().insert(
-            ACCESS_CONTROL_ALLOW_ORIGIN,
-            // hot path, and this unwrap will not fail unless we bypassed it during configuration
-            cors.get_allow_origin().clone().parse().unwrap(),
-        );
+    pub fn limit_concurrency(concurrency_limit: HttpConcurrencyLimit) -> Self {
+        HttpMiddleware::LimitConcurrency(concurrency_limit)
+    }
+
+    pub fn limit_rate(rate_limit: HttpRateLimit) -> Self {
+        HttpMiddleware::LimitRate(rate_limit)
+    }
+
+    pub fn limit_body(body_limits: HttpBodyLimits) -> Self {
+        HttpMiddleware::LimitBody(body_limits)
+    }
+
+    pub fn cache_response(response_cache: HttpResponseCache) -> Self {
+        HttpMiddleware::CacheResponse(response_cache)
+    }
+
+    pub fn transform(transforms: HttpTransforms) -> Self {
+        HttpMiddleware::Transform(transforms)
+    }
+
+    pub fn apply_conditionally(conditions: HttpMiddlewareConditions) -> Self {
+        HttpMiddleware::ApplyConditionally(conditions)
+    }
+
+    pub fn compress_response(compression: HttpCompression) -> Self {
+        HttpMiddleware::CompressResponse(compression)
+    }
+
+    pub fn apply_cors(response: &mut poem::Response, cors: &HttpCors, origin: Option<&str>) {
+        cors.set_allow_origin_header(response.headers_mut(), origin);
 
         if let Some(allow_credentials) = &cors.get_allow_credentials() {
             response.headers_mut().insert(
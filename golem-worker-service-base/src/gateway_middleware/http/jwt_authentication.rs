@@ -0,0 +1,78 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_execution::auth_call_back_binding_handler::AuthorisationError;
+use crate::gateway_execution::request::RichRequest;
+use crate::gateway_middleware::MiddlewareError;
+use crate::gateway_security::{JwtSecurityScheme, JwtValidationError, JwtValidator};
+use golem_common::SafeDisplay;
+use std::sync::Arc;
+use tracing::debug;
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpJwtAuthenticationMiddleware {
+    pub jwt_security_scheme: JwtSecurityScheme,
+}
+
+impl HttpJwtAuthenticationMiddleware {
+    // Unlike OpenID Connect, there is no session and no redirect: requests without a valid
+    // bearer token are rejected, and the claims of valid ones are made available to Rib as `request.auth`
+    pub async fn apply_jwt_auth(
+        &self,
+        input: &mut RichRequest,
+        jwt_validator: &Arc<dyn JwtValidator + Send + Sync>,
+    ) -> Result<(), MiddlewareError> {
+        let token = bearer_token(input).ok_or(MiddlewareError::Unauthorized(
+            AuthorisationError::BearerTokenNotFound,
+        ))?;
+
+        let claims = jwt_validator
+            .validate(&self.jwt_security_scheme, token)
+            .await
+            .map_err(|err| match err {
+                JwtValidationError::FailedToFetchKeys(_) => {
+                    MiddlewareError::InternalError(err.to_safe_string())
+                }
+                _ => {
+                    debug!(
+                        "Invalid bearer token for security scheme {}: {}",
+                        self.jwt_security_scheme.scheme_identifier(),
+                        err
+                    );
+                    MiddlewareError::Unauthorized(AuthorisationError::InvalidBearerToken(err))
+                }
+            })?;
+
+        input.auth_data = Some(claims);
+
+        Ok(())
+    }
+}
+
+fn bearer_token(input: &RichRequest) -> Option<&str> {
+    input
+        .underlying
+        .headers()
+        .get(http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .get(..BEARER_PREFIX.len())
+                .filter(|prefix| prefix.eq_ignore_ascii_case(BEARER_PREFIX))
+                .map(|_| value[BEARER_PREFIX.len()..].trim())
+        })
+        .filter(|token| !token.is_empty())
+}
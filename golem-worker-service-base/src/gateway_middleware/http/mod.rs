@@ -12,12 +12,32 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use api_key_authentication::*;
 pub use authentication::*;
+pub use body_limits::*;
+pub use compression::*;
+pub use concurrency_limit::*;
+pub use conditions::*;
 pub use cors::*;
+pub use hmac_authentication::*;
 pub use http_middleware::*;
+pub use jwt_authentication::*;
 pub use middleware_error::*;
+pub use rate_limit::*;
+pub use response_cache::*;
+pub use transforms::*;
 
+mod api_key_authentication;
 mod authentication;
+mod body_limits;
+mod compression;
+mod concurrency_limit;
+mod conditions;
 mod cors;
+mod hmac_authentication;
 mod http_middleware;
+mod jwt_authentication;
 mod middleware_error;
+mod rate_limit;
+mod response_cache;
+mod transforms;
@@ -0,0 +1,124 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::{DefaultWorkerServiceRibCompiler, WorkerServiceRibCompiler};
+use rib::{Expr, RibByteCode, RibInputTypeInfo};
+use std::time::Duration;
+
+// Token bucket rate limit of a route.
+// A bucket holds up to `capacity` tokens and gains one every `refill_interval_ms`,
+// and every request takes a token, being rejected with 429 Too Many Requests if there is none.
+// There is a bucket per client IP, or per value of the optional key, which is a Rib script
+// with only the `request` available, such as `request.headers.x-api-key`.
+// As the script cannot call workers, it is compiled without any component metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRateLimit {
+    pub capacity: u32,
+    pub refill_interval_ms: u64,
+    pub key_compiled: Option<RateLimitKeyCompiled>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitKeyCompiled {
+    pub key: Expr,
+    pub compiled_key: RibByteCode,
+    pub rib_input: RibInputTypeInfo,
+}
+
+impl HttpRateLimit {
+    pub fn new(capacity: u32, refill_interval_ms: u64, key: Option<Expr>) -> Result<Self, String> {
+        if capacity == 0 {
+            return Err("Rate limit capacity must be greater than 0".to_string());
+        }
+
+        if refill_interval_ms == 0 {
+            return Err("Rate limit refill interval must be greater than 0".to_string());
+        }
+
+        let key_compiled = key
+            .map(|key| {
+                DefaultWorkerServiceRibCompiler::compile(&key, &[])
+                    .map(|compiled| RateLimitKeyCompiled {
+                        key,
+                        compiled_key: compiled.byte_code,
+                        rib_input: compiled.rib_input_type_info,
+                    })
+                    .map_err(|err| format!("Invalid rate limit key: {}", err))
+            })
+            .transpose()?;
+
+        Ok(HttpRateLimit {
+            capacity,
+            refill_interval_ms,
+            key_compiled,
+        })
+    }
+
+    pub fn refill_interval(&self) -> Duration {
+        Duration::from_millis(self.refill_interval_ms)
+    }
+
+    pub fn key(&self) -> Option<&Expr> {
+        self.key_compiled.as_ref().map(|compiled| &compiled.key)
+    }
+}
+
+impl TryFrom<HttpRateLimit> for golem_api_grpc::proto::golem::apidefinition::RateLimit {
+    type Error = String;
+
+    fn try_from(value: HttpRateLimit) -> Result<Self, Self::Error> {
+        let (key, compiled_key_expr, key_rib_input) = match value.key_compiled {
+            Some(x) => (
+                Some(x.key.into()),
+                Some(x.compiled_key.try_into()?),
+                Some(x.rib_input.into()),
+            ),
+            None => (None, None, None),
+        };
+
+        Ok(golem_api_grpc::proto::golem::apidefinition::RateLimit {
+            capacity: value.capacity,
+            refill_interval_ms: value.refill_interval_ms,
+            key,
+            compiled_key_expr,
+            key_rib_input,
+        })
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::RateLimit> for HttpRateLimit {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::RateLimit,
+    ) -> Result<Self, Self::Error> {
+        match (value.key, value.compiled_key_expr, value.key_rib_input) {
+            (Some(key), Some(compiled_key), Some(rib_input)) => Ok(HttpRateLimit {
+                capacity: value.capacity,
+                refill_interval_ms: value.refill_interval_ms,
+                key_compiled: Some(RateLimitKeyCompiled {
+                    key: Expr::try_from(key)?,
+                    compiled_key: RibByteCode::try_from(compiled_key)?,
+                    rib_input: RibInputTypeInfo::try_from(rib_input)?,
+                }),
+            }),
+            // Definitions received without the compiled key, such as user facing ones, are compiled
+            (key, _, _) => HttpRateLimit::new(
+                value.capacity,
+                value.refill_interval_ms,
+                key.map(Expr::try_from).transpose()?,
+            ),
+        }
+    }
+}
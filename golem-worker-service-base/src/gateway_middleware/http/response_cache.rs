@@ -0,0 +1,94 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use http::HeaderName;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Caches the responses of a `GET` route in the gateway for `ttlSeconds`, serving the cached
+/// responses without invoking the worker.
+///
+/// Responses are cached per request path and query string, and per the request headers listed
+/// in `vary` as `header:<name>`. A `query` entry is accepted for compatibility, the query string
+/// always being part of the key.
+/// The cached responses are shared by all the callers, so routes with a security scheme cannot
+/// cache their responses. Only `200 OK` responses without `Set-Cookie` or a `Cache-Control` of
+/// `no-store` or `private` are cached.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct HttpResponseCache {
+    pub ttl_seconds: u64,
+    #[serde(default)]
+    #[oai(default)]
+    pub vary: Vec<String>,
+}
+
+// The header a vary entry is for, or none for the query string which is always part of the key
+fn vary_header(vary: &str) -> Result<Option<HeaderName>, String> {
+    match vary.trim() {
+        "query" => Ok(None),
+        other => match other.strip_prefix("header:") {
+            Some(header) => HeaderName::from_str(header.trim())
+                .map(Some)
+                .map_err(|_| format!("Invalid header name '{}' in vary", header)),
+            None => Err(format!(
+                "Invalid vary '{}', expected 'header:<name>'",
+                other
+            )),
+        },
+    }
+}
+
+impl HttpResponseCache {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.ttl_seconds == 0 {
+            return Err("Response cache ttl-seconds must be greater than 0".to_string());
+        }
+
+        self.vary_by().map(|_| ())
+    }
+
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_seconds)
+    }
+
+    // The request headers the cached responses vary on
+    pub fn vary_by(&self) -> Result<Vec<HeaderName>, String> {
+        self.vary
+            .iter()
+            .filter_map(|vary| vary_header(vary).transpose())
+            .collect()
+    }
+}
+
+impl From<HttpResponseCache> for golem_api_grpc::proto::golem::apidefinition::ResponseCache {
+    fn from(value: HttpResponseCache) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::ResponseCache {
+            ttl_seconds: value.ttl_seconds,
+            vary: value.vary,
+        }
+    }
+}
+
+impl From<golem_api_grpc::proto::golem::apidefinition::ResponseCache> for HttpResponseCache {
+    fn from(value: golem_api_grpc::proto::golem::apidefinition::ResponseCache) -> Self {
+        HttpResponseCache {
+            ttl_seconds: value.ttl_seconds,
+            vary: value.vary,
+        }
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::{DefaultWorkerServiceRibCompiler, WorkerServiceRibCompiler};
+use rib::{Expr, RibByteCode, RibInputTypeInfo};
+
+// The Rib scripts transforming the requests of a route before its binding,
+// and its responses after the binding, each in the order they are declared.
+// A request transform has the `request` available and returns a record with the `headers`
+// to set on the request (an empty value removes the header), which the following transforms,
+// the rate limit key and the binding see.
+// A response transform has the `request` and the `response` (its `status` and `headers`)
+// available, and returns a record with an optional `status` and the `headers` to set on the response.
+// Example: `{ headers: { x-tenant: "${request.headers.x-tenant-id}" } }`
+// As the scripts cannot call workers, they are compiled without any component metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpTransforms {
+    pub request_transforms: Vec<TransformCompiled>,
+    pub response_transforms: Vec<TransformCompiled>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransformCompiled {
+    pub script: Expr,
+    pub compiled_script: RibByteCode,
+    pub rib_input: RibInputTypeInfo,
+}
+
+impl HttpTransforms {
+    pub fn new(
+        request_transforms: Vec<Expr>,
+        response_transforms: Vec<Expr>,
+    ) -> Result<Self, String> {
+        let request_transforms = request_transforms
+            .into_iter()
+            .enumerate()
+            .map(|(index, script)| {
+                DefaultWorkerServiceRibCompiler::compile_request_transform(&script)
+                    .map(|compiled| TransformCompiled {
+                        script,
+                        compiled_script: compiled.byte_code,
+                        rib_input: compiled.rib_input_type_info,
+                    })
+                    .map_err(|err| format!("Invalid request transform {}: {}", index + 1, err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let response_transforms = response_transforms
+            .into_iter()
+            .enumerate()
+            .map(|(index, script)| {
+                DefaultWorkerServiceRibCompiler::compile_response_transform(&script)
+                    .map(|compiled| TransformCompiled {
+                        script,
+                        compiled_script: compiled.byte_code,
+                        rib_input: compiled.rib_input_type_info,
+                    })
+                    .map_err(|err| format!("Invalid response transform {}: {}", index + 1, err))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(HttpTransforms {
+            request_transforms,
+            response_transforms,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.request_transforms.is_empty() && self.response_transforms.is_empty()
+    }
+
+    pub fn request_scripts(&self) -> Vec<&Expr> {
+        self.request_transforms
+            .iter()
+            .map(|transform| &transform.script)
+            .collect()
+    }
+
+    pub fn response_scripts(&self) -> Vec<&Expr> {
+        self.response_transforms
+            .iter()
+            .map(|transform| &transform.script)
+            .collect()
+    }
+}
+
+impl TryFrom<TransformCompiled> for golem_api_grpc::proto::golem::apidefinition::Transform {
+    type Error = String;
+
+    fn try_from(value: TransformCompiled) -> Result<Self, Self::Error> {
+        Ok(golem_api_grpc::proto::golem::apidefinition::Transform {
+            script: Some(value.script.into()),
+            compiled_script: Some(value.compiled_script.try_into()?),
+            rib_input: Some(value.rib_input.into()),
+        })
+    }
+}
+
+impl TryFrom<HttpTransforms> for golem_api_grpc::proto::golem::apidefinition::Transforms {
+    type Error = String;
+
+    fn try_from(value: HttpTransforms) -> Result<Self, Self::Error> {
+        Ok(golem_api_grpc::proto::golem::apidefinition::Transforms {
+            request_transforms: value
+                .request_transforms
+                .into_iter()
+                .map(|transform| transform.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+            response_transforms: value
+                .response_transforms
+                .into_iter()
+                .map(|transform| transform.try_into())
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::Transforms> for HttpTransforms {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::Transforms,
+    ) -> Result<Self, Self::Error> {
+        let all_compiled = value
+            .request_transforms
+            .iter()
+            .chain(value.response_transforms.iter())
+            .all(|transform| transform.compiled_script.is_some() && transform.rib_input.is_some());
+
+        if all_compiled {
+            Ok(HttpTransforms {
+                request_transforms: value
+                    .request_transforms
+                    .into_iter()
+                    .map(internal::transform_compiled)
+                    .collect::<Result<Vec<_>, _>>()?,
+                response_transforms: value
+                    .response_transforms
+                    .into_iter()
+                    .map(internal::transform_compiled)
+                    .collect::<Result<Vec<_>, _>>()?,
+            })
+        } else {
+            // Definitions received without the compiled scripts, such as user facing ones, are compiled
+            HttpTransforms::new(
+                value
+                    .request_transforms
+                    .into_iter()
+                    .map(internal::script)
+                    .collect::<Result<Vec<_>, _>>()?,
+                value
+                    .response_transforms
+                    .into_iter()
+                    .map(internal::script)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        }
+    }
+}
+
+mod internal {
+    use super::TransformCompiled;
+    use rib::{Expr, RibByteCode, RibInputTypeInfo};
+
+    pub(crate) fn script(
+        value: golem_api_grpc::proto::golem::apidefinition::Transform,
+    ) -> Result<Expr, String> {
+        Expr::try_from(value.script.ok_or("Missing transform script")?)
+    }
+
+    pub(crate) fn transform_compiled(
+        value: golem_api_grpc::proto::golem::apidefinition::Transform,
+    ) -> Result<TransformCompiled, String> {
+        Ok(TransformCompiled {
+            script: Expr::try_from(value.script.ok_or("Missing transform script")?)?,
+            compiled_script: RibByteCode::try_from(
+                value
+                    .compiled_script
+                    .ok_or("Missing compiled transform script")?,
+            )?,
+            rib_input: RibInputTypeInfo::try_from(
+                value.rib_input.ok_or("Missing transform rib input")?,
+            )?,
+        })
+    }
+}
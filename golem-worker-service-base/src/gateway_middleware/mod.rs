@@ -14,7 +14,11 @@
 
 use crate::gateway_execution::gateway_session::GatewaySessionStore;
 use crate::gateway_execution::request::RichRequest;
-use crate::gateway_security::{IdentityProvider, SecuritySchemeWithProviderMetadata};
+use crate::gateway_security::{
+    ApiKeySecurityScheme, ApiKeyValidator, HmacSecurityScheme, HmacSignatureValidator,
+    IdentityProvider, JwtSecurityScheme, JwtValidator, SecuritySchemeReference,
+    SecuritySchemeWithProviderMetadata,
+};
 pub use http::*;
 use std::sync::Arc;
 
@@ -38,17 +42,41 @@ impl HttpMiddlewares {
         self.0.push(HttpMiddleware::cors(cors));
     }
 
-    pub async fn process_middleware_in(
+    // The namespace is the one of the API definition of the route
+    #[allow(clippy::too_many_arguments)]
+    pub async fn process_middleware_in<Namespace>(
         &self,
-        rich_request: &RichRequest,
+        namespace: &Namespace,
+        rich_request: &mut RichRequest,
         session_store: &GatewaySessionStore,
         identity_provider: &Arc<dyn IdentityProvider + Sync + Send>,
+        jwt_validator: &Arc<dyn JwtValidator + Sync + Send>,
+        api_key_validator: &Arc<dyn ApiKeyValidator<Namespace> + Sync + Send>,
+        hmac_signature_validator: &Arc<dyn HmacSignatureValidator<Namespace> + Sync + Send>,
     ) -> Result<MiddlewareSuccess, MiddlewareError> {
         let mut final_session_id = None;
 
         for middleware in self.0.iter() {
             match middleware {
                 HttpMiddleware::AddCorsHeaders(_) => {}
+                HttpMiddleware::LimitConcurrency(_) => {}
+                HttpMiddleware::LimitRate(_) => {}
+                HttpMiddleware::LimitBody(_) => {}
+                HttpMiddleware::CacheResponse(_) => {}
+                HttpMiddleware::Transform(_) => {}
+                HttpMiddleware::ApplyConditionally(_) => {}
+                HttpMiddleware::CompressResponse(_) => {}
+                HttpMiddleware::AuthenticateJwt(auth) => {
+                    auth.apply_jwt_auth(rich_request, jwt_validator).await?;
+                }
+                HttpMiddleware::AuthenticateApiKey(auth) => {
+                    auth.apply_api_key_auth(namespace, rich_request, api_key_validator)
+                        .await?;
+                }
+                HttpMiddleware::AuthenticateHmac(auth) => {
+                    auth.apply_hmac_auth(namespace, rich_request, hmac_signature_validator)
+                        .await?;
+                }
                 HttpMiddleware::AuthenticateRequest(auth) => {
                     let result = auth
                         .apply_http_auth(rich_request, session_store, identity_provider)
@@ -71,16 +99,28 @@ impl HttpMiddlewares {
         })
     }
 
+    // The origin is the one of the request the response is for
     pub async fn process_middleware_out(
         &self,
         response: &mut poem::Response,
+        origin: Option<&str>,
     ) -> Result<(), MiddlewareError> {
         for middleware in self.0.iter() {
             match middleware {
                 HttpMiddleware::AddCorsHeaders(cors) => {
-                    HttpMiddleware::apply_cors(response, cors);
+                    HttpMiddleware::apply_cors(response, cors, origin);
                 }
                 HttpMiddleware::AuthenticateRequest(_) => {}
+                HttpMiddleware::LimitConcurrency(_) => {}
+                HttpMiddleware::LimitRate(_) => {}
+                HttpMiddleware::LimitBody(_) => {}
+                HttpMiddleware::AuthenticateJwt(_) => {}
+                HttpMiddleware::AuthenticateApiKey(_) => {}
+                HttpMiddleware::AuthenticateHmac(_) => {}
+                HttpMiddleware::CacheResponse(_) => {}
+                HttpMiddleware::Transform(_) => {}
+                HttpMiddleware::ApplyConditionally(_) => {}
+                HttpMiddleware::CompressResponse(_) => {}
             }
         }
 
@@ -98,6 +138,80 @@ impl HttpMiddlewares {
     pub fn get_http_authentication_middleware(&self) -> Option<HttpAuthenticationMiddleware> {
         self.0.iter().find_map(|m| m.get_http_authentication())
     }
+
+    pub fn get_jwt_authentication_middleware(&self) -> Option<HttpJwtAuthenticationMiddleware> {
+        self.0.iter().find_map(|m| m.get_jwt_authentication())
+    }
+
+    pub fn get_api_key_authentication_middleware(
+        &self,
+    ) -> Option<HttpApiKeyAuthenticationMiddleware> {
+        self.0.iter().find_map(|m| m.get_api_key_authentication())
+    }
+
+    pub fn get_hmac_authentication_middleware(&self) -> Option<HttpHmacAuthenticationMiddleware> {
+        self.0.iter().find_map(|m| m.get_hmac_authentication())
+    }
+
+    // The security scheme the route is secured with, whichever its type is
+    pub fn get_security_scheme_reference(&self) -> Option<SecuritySchemeReference> {
+        self.get_http_authentication_middleware()
+            .map(|x| SecuritySchemeReference::from(x.security_scheme_with_metadata))
+            .or_else(|| {
+                self.get_jwt_authentication_middleware()
+                    .map(|x| SecuritySchemeReference::from(x.jwt_security_scheme))
+            })
+            .or_else(|| {
+                self.get_api_key_authentication_middleware()
+                    .map(|x| SecuritySchemeReference::from(x.api_key_security_scheme))
+            })
+            .or_else(|| {
+                self.get_hmac_authentication_middleware()
+                    .map(|x| SecuritySchemeReference::from(x.hmac_security_scheme))
+            })
+    }
+
+    pub fn get_concurrency_limit_middleware(&self) -> Option<HttpConcurrencyLimit> {
+        self.0.iter().find_map(|m| m.get_concurrency_limit())
+    }
+
+    pub fn get_rate_limit_middleware(&self) -> Option<HttpRateLimit> {
+        self.0.iter().find_map(|m| m.get_rate_limit())
+    }
+
+    pub fn get_body_limits_middleware(&self) -> Option<HttpBodyLimits> {
+        self.0.iter().find_map(|m| m.get_body_limits())
+    }
+
+    pub fn get_response_cache_middleware(&self) -> Option<HttpResponseCache> {
+        self.0.iter().find_map(|m| m.get_response_cache())
+    }
+
+    pub fn get_transforms_middleware(&self) -> Option<HttpTransforms> {
+        self.0.iter().find_map(|m| m.get_transforms())
+    }
+
+    pub fn get_conditions_middleware(&self) -> Option<HttpMiddlewareConditions> {
+        self.0.iter().find_map(|m| m.get_conditions())
+    }
+
+    pub fn get_compression_middleware(&self) -> Option<HttpCompression> {
+        self.0.iter().find_map(|m| m.get_compression())
+    }
+
+    // The middlewares applying to a request, given the ones skipped for it by their conditions
+    pub fn without(&self, skipped: &[ConditionalMiddleware]) -> HttpMiddlewares {
+        HttpMiddlewares(
+            self.0
+                .iter()
+                .filter(|m| {
+                    m.conditional_middleware()
+                        .is_none_or(|middleware| !skipped.contains(&middleware))
+                })
+                .cloned()
+                .collect(),
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -122,6 +236,55 @@ impl TryFrom<golem_api_grpc::proto::golem::apidefinition::Middleware> for HttpMi
             http_middlewares.push(HttpMiddleware::authenticate_request(auth))
         }
 
+        if let Some(concurrency_limit) = value.concurrency_limit {
+            let concurrency_limit = HttpConcurrencyLimit::try_from(concurrency_limit)?;
+            http_middlewares.push(HttpMiddleware::limit_concurrency(concurrency_limit));
+        }
+
+        if let Some(rate_limit) = value.rate_limit {
+            let rate_limit = HttpRateLimit::try_from(rate_limit)?;
+            http_middlewares.push(HttpMiddleware::limit_rate(rate_limit));
+        }
+
+        if let Some(body_limits) = value.body_limits {
+            http_middlewares.push(HttpMiddleware::limit_body(body_limits.into()));
+        }
+
+        if let Some(response_cache) = value.response_cache {
+            http_middlewares.push(HttpMiddleware::cache_response(response_cache.into()));
+        }
+
+        if let Some(transforms) = value.transforms {
+            let transforms = HttpTransforms::try_from(transforms)?;
+            http_middlewares.push(HttpMiddleware::transform(transforms));
+        }
+
+        if let Some(conditions) = value.conditions {
+            let conditions = HttpMiddlewareConditions::try_from(conditions)?;
+            http_middlewares.push(HttpMiddleware::apply_conditionally(conditions));
+        }
+
+        if let Some(compression) = value.compression {
+            http_middlewares.push(HttpMiddleware::compress_response(compression.into()));
+        }
+
+        if let Some(jwt_authentication) = value.jwt_authentication {
+            let jwt_security_scheme = JwtSecurityScheme::try_from(jwt_authentication)?;
+            http_middlewares.push(HttpMiddleware::authenticate_jwt(jwt_security_scheme));
+        }
+
+        if let Some(api_key_authentication) = value.api_key_authentication {
+            let api_key_security_scheme = ApiKeySecurityScheme::try_from(api_key_authentication)?;
+            http_middlewares.push(HttpMiddleware::authenticate_api_key(
+                api_key_security_scheme,
+            ));
+        }
+
+        if let Some(hmac_authentication) = value.hmac_authentication {
+            let hmac_security_scheme = HmacSecurityScheme::try_from(hmac_authentication)?;
+            http_middlewares.push(HttpMiddleware::authenticate_hmac(hmac_security_scheme));
+        }
+
         Ok(HttpMiddlewares(http_middlewares))
     }
 }
@@ -131,6 +294,16 @@ impl TryFrom<HttpMiddlewares> for golem_api_grpc::proto::golem::apidefinition::M
     fn try_from(value: HttpMiddlewares) -> Result<Self, String> {
         let mut cors = None;
         let mut auth = None;
+        let mut concurrency_limit = None;
+        let mut rate_limit = None;
+        let mut body_limits = None;
+        let mut jwt_authentication = None;
+        let mut api_key_authentication = None;
+        let mut hmac_authentication = None;
+        let mut response_cache = None;
+        let mut transforms = None;
+        let mut conditions = None;
+        let mut compression = None;
 
         for http_middleware in value.0.iter() {
             match http_middleware {
@@ -140,12 +313,52 @@ impl TryFrom<HttpMiddlewares> for golem_api_grpc::proto::golem::apidefinition::M
                 HttpMiddleware::AuthenticateRequest(http_request_authentication) => {
                     auth = Some(golem_api_grpc::proto::golem::apidefinition::SecurityWithProviderMetadata::try_from(http_request_authentication.security_scheme_with_metadata.clone())?)
                 }
+                HttpMiddleware::LimitConcurrency(concurrency_limit0) => {
+                    concurrency_limit = Some(concurrency_limit0.clone().into());
+                }
+                HttpMiddleware::LimitRate(rate_limit0) => {
+                    rate_limit = Some(rate_limit0.clone().try_into()?);
+                }
+                HttpMiddleware::LimitBody(body_limits0) => {
+                    body_limits = Some(body_limits0.clone().into());
+                }
+                HttpMiddleware::AuthenticateJwt(jwt_authentication0) => {
+                    jwt_authentication = Some(jwt_authentication0.jwt_security_scheme.clone().into());
+                }
+                HttpMiddleware::AuthenticateApiKey(api_key_authentication0) => {
+                    api_key_authentication = Some(api_key_authentication0.api_key_security_scheme.clone().into());
+                }
+                HttpMiddleware::AuthenticateHmac(hmac_authentication0) => {
+                    hmac_authentication = Some(hmac_authentication0.hmac_security_scheme.clone().into());
+                }
+                HttpMiddleware::CacheResponse(response_cache0) => {
+                    response_cache = Some(response_cache0.clone().into());
+                }
+                HttpMiddleware::Transform(transforms0) => {
+                    transforms = Some(transforms0.clone().try_into()?);
+                }
+                HttpMiddleware::ApplyConditionally(conditions0) => {
+                    conditions = Some(conditions0.clone().try_into()?);
+                }
+                HttpMiddleware::CompressResponse(compression0) => {
+                    compression = Some(compression0.clone().into());
+                }
             }
         }
 
         Ok(golem_api_grpc::proto::golem::apidefinition::Middleware {
             cors,
             http_authentication: auth,
+            concurrency_limit,
+            rate_limit,
+            body_limits,
+            jwt_authentication,
+            api_key_authentication,
+            hmac_authentication,
+            response_cache,
+            transforms,
+            conditions,
+            compression,
         })
     }
 }
@@ -89,6 +89,8 @@ pub mod router {
         pub namespace: Namespace,
         pub binding: GatewayBindingCompiled,
         pub middlewares: Option<HttpMiddlewares>,
+        // The route as `METHOD /path/{pattern}`, identifying it in diagnostics
+        pub route: String,
     }
 
     pub fn build<Namespace>(
@@ -97,6 +99,7 @@ pub mod router {
         let mut router = Router::new();
 
         for (namespace, route) in routes {
+            let route_name = route.name();
             let method = route.method.into();
             let path = route.path;
             let binding = route.binding;
@@ -114,6 +117,10 @@ pub mod router {
                         var_info: var_info.clone(),
                         index: i,
                     }),
+                    PathPattern::RegexVar(regex_var_info) => Some(PathParamExtractor::Single {
+                        var_info: regex_var_info.var_info(),
+                        index: i,
+                    }),
                     _ => None,
                 })
                 .collect();
@@ -124,6 +131,7 @@ pub mod router {
                 namespace,
                 binding,
                 middlewares: route.middlewares,
+                route: route_name,
             };
 
             let path: Vec<RouterPattern> = path
@@ -13,34 +13,349 @@
 // limitations under the License.
 
 use golem_wasm_ast::analysis::AnalysedExport;
-use rib::{CompilerOutput, Expr, GlobalVariableTypeSpec, InferredType, Path, RibError, VariableId};
+use poem_openapi::Enum;
+use rib::{
+    CompilerOutput, Expr, GlobalVariableTypeSpec, InferredType, Path, RibError, TaintPolicy,
+    TaintSink, TaintTracking, VariableId, WorkerNameTyping,
+};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tracing::warn;
+
+/// How the values derived from the request flowing into worker names and response headers
+/// are treated when the scripts of an API definition are compiled. They are ignored with `off`,
+/// reported with `warn`, and fail the creation of the definition with `deny`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "lowercase")]
+#[oai(rename_all = "lowercase")]
+pub enum TaintAnalysisPolicy {
+    Off,
+    #[default]
+    Warn,
+    Deny,
+}
+
+impl FromStr for TaintAnalysisPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(TaintAnalysisPolicy::Off),
+            "warn" => Ok(TaintAnalysisPolicy::Warn),
+            "deny" => Ok(TaintAnalysisPolicy::Deny),
+            other => Err(format!(
+                "Invalid taint policy '{}', expected 'off', 'warn' or 'deny'",
+                other
+            )),
+        }
+    }
+}
+
+impl Display for TaintAnalysisPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaintAnalysisPolicy::Off => write!(f, "off"),
+            TaintAnalysisPolicy::Warn => write!(f, "warn"),
+            TaintAnalysisPolicy::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+tokio::task_local! {
+    // The findings of the taint analysis of the scripts compiled within `with_taint_findings`
+    static TAINT_FINDINGS: Mutex<Vec<String>>;
+}
+
+thread_local! {
+    // The parts of the request validated by the route of the scripts being compiled
+    static TRUSTED_SOURCES: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+
+    // The typing of the worker names of the scripts being compiled, chosen by their binding
+    static WORKER_NAME_TYPING: RefCell<WorkerNameTyping> =
+        const { RefCell::new(WorkerNameTyping::Inferred) };
+}
+
+/// Runs the compilation of the scripts of an API definition, returning the findings of their
+/// taint analysis along with its result. Nested calls share the findings of the outermost one,
+/// so the scripts compiled when a definition is parsed are reported along with the rest.
+/// The findings of the scripts compiled outside of it are logged as warnings.
+pub async fn with_taint_findings<F: Future>(f: F) -> (F::Output, Vec<String>) {
+    if TAINT_FINDINGS.try_with(|_| ()).is_ok() {
+        let output = f.await;
+        (output, current_taint_findings())
+    } else {
+        TAINT_FINDINGS
+            .scope(Mutex::new(vec![]), async {
+                let output = f.await;
+                (output, current_taint_findings())
+            })
+            .await
+    }
+}
+
+fn current_taint_findings() -> Vec<String> {
+    TAINT_FINDINGS
+        .try_with(|findings| findings.lock().unwrap().clone())
+        .unwrap_or_default()
+}
+
+/// Compiles the scripts of a route in `f`, trusting the given parts of the request
+/// (Example: `request.path.user-id` matched by a regex) as they are validated by the route
+pub fn with_trusted_sources<T>(trusted: Vec<String>, f: impl FnOnce() -> T) -> T {
+    let previous = TRUSTED_SOURCES.with(|sources| sources.replace(trusted));
+    let result = f();
+    TRUSTED_SOURCES.with(|sources| sources.replace(previous));
+    result
+}
+
+/// Compiles the scripts of a route in `f` with the given typing of the worker names they
+/// pass to `instance()`, such as strict typing for the bindings requiring static worker names
+pub fn with_worker_name_typing<T>(
+    worker_name_typing: WorkerNameTyping,
+    f: impl FnOnce() -> T,
+) -> T {
+    let previous = WORKER_NAME_TYPING.with(|typing| typing.replace(worker_name_typing));
+    let result = f();
+    WORKER_NAME_TYPING.with(|typing| typing.replace(previous));
+    result
+}
 
 // A wrapper service over original Rib Compiler concerning
 // the details of the worker bridge.
 pub trait WorkerServiceRibCompiler {
     fn compile(rib: &Expr, export_metadata: &[AnalysedExport]) -> Result<CompilerOutput, RibError>;
+
+    // Compiles the request mapping of an HTTP proxy binding, whose `path` becomes part of the
+    // URL of the upstream request
+    fn compile_request_mapping(rib: &Expr) -> Result<CompilerOutput, RibError>;
+
+    // Compiles a request transform of a route, which returns the headers to set on the request
+    fn compile_request_transform(rib: &Expr) -> Result<CompilerOutput, RibError>;
+
+    // Compiles a response transform of a route, which has the response available as `response`
+    // in addition to `request`. The headers of the response are strings, and its status is a u64.
+    fn compile_response_transform(rib: &Expr) -> Result<CompilerOutput, RibError>;
+
+    // Compiles the condition of a middleware of a route, a predicate over the `request`
+    fn compile_middleware_condition(rib: &Expr) -> Result<CompilerOutput, RibError>;
+
+    // Compiles a Rib script evaluated for each message of a WebSocket connection,
+    // which has the message available as `message` in addition to `request`
+    fn compile_web_socket_message(
+        rib: &Expr,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<CompilerOutput, RibError>;
+
+    // Compiles a Rib script evaluated for each event emitted by a worker streamed as server-sent
+    // events, which has the event available as `event` in addition to `request`.
+    // The fields of the event (kind, message, level, context, function and timestamp) are strings.
+    fn compile_server_sent_event(
+        rib: &Expr,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<CompilerOutput, RibError>;
 }
 
 pub struct DefaultWorkerServiceRibCompiler;
 
 impl WorkerServiceRibCompiler for DefaultWorkerServiceRibCompiler {
     fn compile(rib: &Expr, export_metadata: &[AnalysedExport]) -> Result<CompilerOutput, RibError> {
+        compile_with_taint_tracking(
+            rib,
+            export_metadata,
+            vec!["request".to_string()],
+            request_type_specs(),
+            vec!["request".to_string()],
+            vec![],
+        )
+    }
+
+    fn compile_request_mapping(rib: &Expr) -> Result<CompilerOutput, RibError> {
+        compile_with_taint_tracking(
+            rib,
+            &[],
+            vec!["request".to_string()],
+            request_type_specs(),
+            vec!["request".to_string()],
+            vec![TaintSink::OutputField("path".to_string())],
+        )
+    }
+
+    // The headers set on the request are not echoed back to the client, and the request transforms
+    // are meant to derive them from the request, so they are compiled without taint tracking
+    fn compile_request_transform(rib: &Expr) -> Result<CompilerOutput, RibError> {
         rib::compile_with_restricted_global_variables(
             rib,
-            &export_metadata.to_vec(),
+            &vec![],
+            Some(vec!["request".to_string()]),
+            &request_type_specs(),
+        )
+    }
+
+    fn compile_response_transform(rib: &Expr) -> Result<CompilerOutput, RibError> {
+        compile_with_taint_tracking(
+            rib,
+            &[],
+            vec!["request".to_string(), "response".to_string()],
+            [
+                request_type_specs(),
+                vec![
+                    GlobalVariableTypeSpec {
+                        variable_id: VariableId::global("response".to_string()),
+                        path: Path::from_elems(vec!["headers"]),
+                        inferred_type: InferredType::Str,
+                    },
+                    GlobalVariableTypeSpec {
+                        variable_id: VariableId::global("response".to_string()),
+                        path: Path::from_elems(vec!["status"]),
+                        inferred_type: InferredType::U64,
+                    },
+                ],
+            ]
+            .concat(),
+            vec!["request".to_string()],
+            vec![],
+        )
+    }
+
+    // A condition only decides whether a middleware applies, so there is nothing to taint
+    fn compile_middleware_condition(rib: &Expr) -> Result<CompilerOutput, RibError> {
+        rib::compile_with_restricted_global_variables(
+            rib,
+            &vec![],
             Some(vec!["request".to_string()]),
-            &vec![
-                GlobalVariableTypeSpec {
-                    variable_id: VariableId::global("request".to_string()),
-                    path: Path::from_elems(vec!["path"]),
+            &request_type_specs(),
+        )
+    }
+
+    fn compile_web_socket_message(
+        rib: &Expr,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<CompilerOutput, RibError> {
+        compile_with_taint_tracking(
+            rib,
+            export_metadata,
+            vec!["request".to_string(), "message".to_string()],
+            [
+                request_type_specs(),
+                vec![GlobalVariableTypeSpec {
+                    variable_id: VariableId::global("message".to_string()),
+                    path: Path::from_elems(vec![]),
                     inferred_type: InferredType::Str,
-                },
-                GlobalVariableTypeSpec {
-                    variable_id: VariableId::global("request".to_string()),
-                    path: Path::from_elems(vec!["headers"]),
+                }],
+            ]
+            .concat(),
+            vec!["request".to_string(), "message".to_string()],
+            vec![],
+        )
+    }
+
+    fn compile_server_sent_event(
+        rib: &Expr,
+        export_metadata: &[AnalysedExport],
+    ) -> Result<CompilerOutput, RibError> {
+        compile_with_taint_tracking(
+            rib,
+            export_metadata,
+            vec!["request".to_string(), "event".to_string()],
+            [
+                request_type_specs(),
+                vec![GlobalVariableTypeSpec {
+                    variable_id: VariableId::global("event".to_string()),
+                    path: Path::from_elems(vec![]),
                     inferred_type: InferredType::Str,
-                },
-            ],
+                }],
+            ]
+            .concat(),
+            vec!["request".to_string(), "event".to_string()],
+            vec![],
         )
     }
 }
+
+// Every gateway script tracks the values derived from the request (and from the messages and events
+// of the clients) into the worker names and the response headers it computes, as they are the
+// usual places of injection-style mistakes. The findings are collected for the policy of the API
+// definition to be applied to them.
+// Example: `{ status: 200, headers: { Location: request.headers.referer } }`
+fn compile_with_taint_tracking(
+    rib: &Expr,
+    export_metadata: &[AnalysedExport],
+    global_variables: Vec<String>,
+    global_variable_type_spec: Vec<GlobalVariableTypeSpec>,
+    taint_sources: Vec<String>,
+    additional_sinks: Vec<TaintSink>,
+) -> Result<CompilerOutput, RibError> {
+    // The worker names are type checked by a separate compilation, only done for the routes
+    // requiring static worker names, when their API definition is created
+    let worker_name_typing = WORKER_NAME_TYPING.with(|typing| *typing.borrow());
+    if worker_name_typing == WorkerNameTyping::Strict {
+        rib::compile_with_worker_name_typing(
+            rib,
+            &export_metadata.to_vec(),
+            Some(global_variables.clone()),
+            &global_variable_type_spec,
+            worker_name_typing,
+        )?;
+    }
+
+    let taint_tracking = TaintTracking::new(
+        taint_sources,
+        [
+            vec![
+                TaintSink::WorkerName,
+                TaintSink::OutputField("headers".to_string()),
+            ],
+            additional_sinks,
+        ]
+        .concat(),
+        TaintPolicy::Warn,
+    )
+    .with_trusted(TRUSTED_SOURCES.with(|sources| sources.borrow().clone()));
+
+    let (compiler_output, tainted_sinks) = rib::compile_with_taint_tracking(
+        rib,
+        &export_metadata.to_vec(),
+        Some(global_variables),
+        &global_variable_type_spec,
+        &taint_tracking,
+    )?;
+
+    for tainted_sink in tainted_sinks {
+        let finding = format!("Untrusted input in Rib script: {}", tainted_sink);
+        let collected = TAINT_FINDINGS.try_with(|findings| {
+            let mut findings = findings.lock().unwrap();
+            if !findings.contains(&finding) {
+                findings.push(finding.clone());
+            }
+        });
+        if collected.is_err() {
+            warn!("{}", finding);
+        }
+    }
+
+    Ok(compiler_output)
+}
+
+fn request_type_specs() -> Vec<GlobalVariableTypeSpec> {
+    vec![
+        GlobalVariableTypeSpec {
+            variable_id: VariableId::global("request".to_string()),
+            path: Path::from_elems(vec!["path"]),
+            inferred_type: InferredType::Str,
+        },
+        GlobalVariableTypeSpec {
+            variable_id: VariableId::global("request".to_string()),
+            path: Path::from_elems(vec!["headers"]),
+            inferred_type: InferredType::Str,
+        },
+        GlobalVariableTypeSpec {
+            variable_id: VariableId::global("request".to_string()),
+            path: Path::from_elems(vec!["cookies"]),
+            inferred_type: InferredType::Str,
+        },
+    ]
+}
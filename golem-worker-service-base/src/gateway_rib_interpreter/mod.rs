@@ -13,20 +13,28 @@
 // limitations under the License.
 
 use async_trait::async_trait;
+use golem_wasm_rpc::json::TypeAnnotatedValueJsonExtensions;
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::sync::Arc;
+use std::time::Instant;
+use tracing::{info_span, warn, Instrument};
 
-use golem_common::model::invocation_context::InvocationContextStack;
+use golem_common::model::invocation_context::{AttributeValue, InvocationContextStack};
 use golem_common::model::{ComponentId, IdempotencyKey};
 use golem_common::SafeDisplay;
 use golem_wasm_rpc::ValueAndType;
 use rib::{
-    EvaluatedFnArgs, EvaluatedFqFn, EvaluatedWorkerName, RibByteCode, RibFunctionInvoke, RibInput,
-    RibResult,
+    EvaluatedEventName, EvaluatedFnArgs, EvaluatedFqFn, EvaluatedIdempotencyKey,
+    EvaluatedWorkerName, RibByteCode, RibFunctionInvoke, RibInput, RibResult,
 };
 
-use crate::gateway_execution::{GatewayResolvedWorkerRequest, GatewayWorkerRequestExecutor};
+use crate::gateway_execution::{
+    GatewayResolvedWorkerRequest, GatewayWorkerRequestExecutor, WorkerBootstrapValues,
+};
+use crate::metrics::gateway as gateway_metrics;
+use crate::service::gateway::emitted_event::{EmittedEventService, EventEmission};
 
 // A wrapper service over original RibInterpreter concerning
 // the details of the worker service.
@@ -40,10 +48,19 @@ pub trait WorkerServiceRibInterpreter<Namespace> {
         component_id: &ComponentId,
         idempotency_key: &Option<IdempotencyKey>,
         invocation_context: InvocationContextStack,
+        bootstrap: Option<WorkerBootstrapValues>,
         rib_byte_code: &RibByteCode,
         rib_input: &RibInput,
         namespace: Namespace,
     ) -> Result<RibResult, EvaluationError>;
+
+    // The labels of a worker, available to the Rib scripts as `worker.labels`
+    async fn worker_labels(
+        &self,
+        worker_name: &str,
+        component_id: &ComponentId,
+        namespace: Namespace,
+    ) -> Result<HashMap<String, String>, EvaluationError>;
 }
 
 #[derive(Debug, PartialEq)]
@@ -69,14 +86,17 @@ impl From<String> for EvaluationError {
 
 pub struct DefaultRibInterpreter<Namespace> {
     worker_request_executor: Arc<dyn GatewayWorkerRequestExecutor<Namespace> + Sync + Send>,
+    emitted_event_service: Arc<dyn EmittedEventService<Namespace> + Sync + Send>,
 }
 
 impl<Namespace: Clone + Send + Sync + 'static> DefaultRibInterpreter<Namespace> {
     pub fn from_worker_request_executor(
         worker_request_executor: Arc<dyn GatewayWorkerRequestExecutor<Namespace> + Sync + Send>,
+        emitted_event_service: Arc<dyn EmittedEventService<Namespace> + Sync + Send>,
     ) -> Self {
         DefaultRibInterpreter {
             worker_request_executor,
+            emitted_event_service,
         }
     }
 
@@ -86,6 +106,7 @@ impl<Namespace: Clone + Send + Sync + 'static> DefaultRibInterpreter<Namespace>
         component_id: ComponentId,
         idempotency_key: Option<IdempotencyKey>,
         invocation_context: InvocationContextStack,
+        bootstrap: Option<WorkerBootstrapValues>,
         namespace: Namespace,
     ) -> Arc<dyn RibFunctionInvoke + Sync + Send> {
         Arc::new(WorkerServiceRibInvoke {
@@ -93,7 +114,9 @@ impl<Namespace: Clone + Send + Sync + 'static> DefaultRibInterpreter<Namespace>
             component_id,
             idempotency_key,
             invocation_context,
+            bootstrap,
             executor: self.worker_request_executor.clone(),
+            emitted_event_service: self.emitted_event_service.clone(),
             namespace,
         })
     }
@@ -109,6 +132,7 @@ impl<Namespace: Clone + Send + Sync + 'static> WorkerServiceRibInterpreter<Names
         component_id: &ComponentId,
         idempotency_key: &Option<IdempotencyKey>,
         invocation_context: InvocationContextStack,
+        bootstrap: Option<WorkerBootstrapValues>,
         expr: &RibByteCode,
         rib_input: &RibInput,
         namespace: Namespace,
@@ -118,14 +142,28 @@ impl<Namespace: Clone + Send + Sync + 'static> WorkerServiceRibInterpreter<Names
             component_id.clone(),
             idempotency_key.clone(),
             invocation_context,
+            bootstrap,
             namespace.clone(),
         );
 
         let result = rib::interpret(expr, rib_input, worker_invoke_function)
+            .instrument(info_span!("rib_evaluation", component_id = %component_id))
             .await
             .map_err(EvaluationError)?;
         Ok(result)
     }
+
+    async fn worker_labels(
+        &self,
+        worker_name: &str,
+        component_id: &ComponentId,
+        namespace: Namespace,
+    ) -> Result<HashMap<String, String>, EvaluationError> {
+        self.worker_request_executor
+            .get_worker_labels(component_id, worker_name, namespace)
+            .await
+            .map_err(|err| EvaluationError(err.to_string()))
+    }
 }
 
 struct WorkerServiceRibInvoke<Namespace> {
@@ -137,10 +175,24 @@ struct WorkerServiceRibInvoke<Namespace> {
     component_id: ComponentId,
     idempotency_key: Option<IdempotencyKey>,
     invocation_context: InvocationContextStack,
+    bootstrap: Option<WorkerBootstrapValues>,
     executor: Arc<dyn GatewayWorkerRequestExecutor<Namespace> + Sync + Send>,
+    emitted_event_service: Arc<dyn EmittedEventService<Namespace> + Sync + Send>,
     namespace: Namespace,
 }
 
+impl<Namespace> WorkerServiceRibInvoke<Namespace> {
+    fn request_attribute(&self, key: &str) -> Option<String> {
+        self.invocation_context
+            .spans
+            .first()
+            .get_attribute(key, true)
+            .map(|value| match value {
+                AttributeValue::String(value) => value,
+            })
+    }
+}
+
 #[async_trait]
 impl<Namespace: Clone + Send + Sync + 'static> RibFunctionInvoke
     for WorkerServiceRibInvoke<Namespace>
@@ -150,12 +202,18 @@ impl<Namespace: Clone + Send + Sync + 'static> RibFunctionInvoke
         worker_name: Option<EvaluatedWorkerName>,
         function_name: EvaluatedFqFn,
         parameters: EvaluatedFnArgs,
+        idempotency_key: Option<EvaluatedIdempotencyKey>,
     ) -> Result<ValueAndType, String> {
         let component_id = self.component_id.clone();
         let worker_name: Option<String> =
             worker_name.map(|x| x.0).or(self.global_worker_name.clone());
-        let idempotency_key = self.idempotency_key.clone();
+        // An idempotency key specified in the Rib script takes precedence over
+        // the one that came in as part of the request
+        let idempotency_key = idempotency_key
+            .map(|x| IdempotencyKey::new(x.0))
+            .or(self.idempotency_key.clone());
         let invocation_context = self.invocation_context.clone();
+        let bootstrap = self.bootstrap.clone();
         let executor = self.executor.clone();
         let namespace = self.namespace.clone();
 
@@ -175,15 +233,47 @@ impl<Namespace: Clone + Send + Sync + 'static> RibFunctionInvoke
             function_params,
             idempotency_key,
             invocation_context,
+            bootstrap,
             namespace,
         };
 
-        let tav = executor
-            .execute(worker_request)
-            .await
-            .map(|v| v.result)
-            .map_err(|e| e.to_string())?;
+        let start = Instant::now();
+        let result = executor.execute(worker_request).await;
+        gateway_metrics::record_worker_invoke(start.elapsed());
+
+        let tav = result.map(|v| v.result).map_err(|e| e.to_string())?;
 
         tav.try_into()
     }
+    // Events are published on a best effort basis, failing to keep one
+    // does not fail the request that emitted it
+    async fn emit(
+        &self,
+        event_name: EvaluatedEventName,
+        payload: ValueAndType,
+    ) -> Result<(), String> {
+        let payload = TypeAnnotatedValue::try_from(payload)
+            .map_err(|errs: Vec<String>| errs.join(", "))?
+            .to_json_value();
+
+        let event = EventEmission {
+            event_name: event_name.0,
+            payload,
+            component_id: self.component_id.clone(),
+            worker_name: self.global_worker_name.clone(),
+            trace_id: self.invocation_context.trace_id.to_string(),
+            method: self.request_attribute("request.method"),
+            uri: self.request_attribute("request.uri"),
+        };
+
+        if let Err(err) = self
+            .emitted_event_service
+            .publish(&self.namespace, event)
+            .await
+        {
+            warn!("Failed to publish emitted event: {}", err);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,196 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_security::ApiKeySecurityScheme;
+use async_trait::async_trait;
+use golem_common::SafeDisplay;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+const KEY_ID_BYTES: usize = 8;
+const SECRET_BYTES: usize = 32;
+const SALT_BYTES: usize = 16;
+
+// Validates the keys presented by the requests of routes secured with an API key security scheme
+#[async_trait]
+pub trait ApiKeyValidator<Namespace> {
+    // Returns the key the presented one is the current secret of, among the keys of the
+    // namespace of the route, whose details are made available to Rib as `request.auth`
+    async fn validate(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &ApiKeySecurityScheme,
+        presented_key: &str,
+    ) -> Result<ApiKey, ApiKeyValidationError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiKeyValidationError {
+    // Deliberately not telling apart unknown keys from wrong secrets
+    InvalidKey,
+    InternalError(String),
+}
+
+// To satisfy thiserror
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for ApiKeyValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for ApiKeyValidationError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            ApiKeyValidationError::InvalidKey => "InvalidKey".to_string(),
+            ApiKeyValidationError::InternalError(err) => format!("InternalError: {}", err),
+        }
+    }
+}
+
+// An issued API key, without its secret, which is only known to its holder
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKey {
+    pub key_id: String,
+    pub name: String,
+    pub metadata: BTreeMap<String, String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ApiKey {
+    // The identity of requests authenticated with the key, available to Rib as `request.auth`
+    pub fn auth_data(&self) -> Value {
+        json!({
+            "key_id": self.key_id,
+            "name": self.name,
+            "metadata": self.metadata
+        })
+    }
+}
+
+// The key as handed out to its holder, once, on creation or rotation.
+// It is made of the public key id, used to look the key up, and the secret, of which only
+// a salted hash is stored: `<key-id>.<secret>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaintextApiKey {
+    pub key_id: String,
+    pub secret: String,
+}
+
+impl PlaintextApiKey {
+    pub fn generate() -> Self {
+        PlaintextApiKey {
+            key_id: random_hex(KEY_ID_BYTES),
+            secret: random_hex(SECRET_BYTES),
+        }
+    }
+
+    // Generates a new secret for an existing key id
+    pub fn rotate(key_id: &str) -> Self {
+        PlaintextApiKey {
+            key_id: key_id.to_string(),
+            secret: random_hex(SECRET_BYTES),
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<PlaintextApiKey> {
+        let (key_id, secret) = value.trim().split_once('.')?;
+
+        if key_id.is_empty() || secret.is_empty() {
+            return None;
+        }
+
+        Some(PlaintextApiKey {
+            key_id: key_id.to_string(),
+            secret: secret.to_string(),
+        })
+    }
+}
+
+impl Display for PlaintextApiKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.key_id, self.secret)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashedApiKeySecret {
+    pub salt: Vec<u8>,
+    pub hash: Vec<u8>,
+}
+
+impl HashedApiKeySecret {
+    pub fn new(secret: &str) -> Self {
+        let mut salt = vec![0u8; SALT_BYTES];
+        OsRng.fill_bytes(&mut salt);
+
+        let hash = hash_secret(&salt, secret);
+
+        HashedApiKeySecret { salt, hash }
+    }
+
+    // Compares in constant time, so the time taken does not reveal how much of the hash matched
+    pub fn matches(&self, secret: &str) -> bool {
+        let hash = hash_secret(&self.salt, secret);
+
+        hash.len() == self.hash.len()
+            && hash
+                .iter()
+                .zip(self.hash.iter())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
+fn hash_secret(salt: &[u8], secret: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(secret.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+fn random_hex(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    OsRng.fill_bytes(&mut buffer);
+    hex::encode(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn only_the_current_secret_matches_the_stored_hash() {
+        let key = PlaintextApiKey::generate();
+        let hashed = HashedApiKeySecret::new(&key.secret);
+
+        assert_eq!(PlaintextApiKey::parse(&key.to_string()), Some(key.clone()));
+        assert!(hashed.matches(&key.secret));
+
+        // The same secret is hashed differently with every salt
+        assert_ne!(HashedApiKeySecret::new(&key.secret).hash, hashed.hash);
+
+        let rotated = PlaintextApiKey::rotate(&key.key_id);
+        assert_eq!(rotated.key_id, key.key_id);
+        assert!(!hashed.matches(&rotated.secret));
+
+        assert_eq!(PlaintextApiKey::parse("no-separator"), None);
+        assert_eq!(PlaintextApiKey::parse(".secret"), None);
+    }
+}
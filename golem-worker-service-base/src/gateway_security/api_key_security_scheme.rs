@@ -0,0 +1,135 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_security::SecuritySchemeIdentifier;
+use http::HeaderName;
+use std::str::FromStr;
+
+// A security scheme authenticating requests with keys issued by Golem itself,
+// presented in a header or a query parameter. The keys are managed separately from the scheme,
+// and can be created, rotated and deleted without touching the routes referring to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiKeySecurityScheme {
+    scheme_identifier: SecuritySchemeIdentifier,
+    location: ApiKeyLocation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApiKeyLocation {
+    Header(String),
+    QueryParam(String),
+}
+
+impl ApiKeySecurityScheme {
+    pub fn new(
+        scheme_identifier: SecuritySchemeIdentifier,
+        location: ApiKeyLocation,
+    ) -> Result<Self, String> {
+        match &location {
+            ApiKeyLocation::Header(name) => {
+                HeaderName::from_str(name)
+                    .map_err(|_| format!("Invalid API key header name '{}'", name))?;
+            }
+            ApiKeyLocation::QueryParam(name) => {
+                if name.trim().is_empty() {
+                    return Err("API key query parameter name must not be empty".to_string());
+                }
+            }
+        }
+
+        Ok(ApiKeySecurityScheme {
+            scheme_identifier,
+            location,
+        })
+    }
+
+    pub fn scheme_identifier(&self) -> SecuritySchemeIdentifier {
+        self.scheme_identifier.clone()
+    }
+
+    pub fn location(&self) -> &ApiKeyLocation {
+        &self.location
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::ApiKeySecurityScheme>
+    for ApiKeySecurityScheme
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::ApiKeySecurityScheme,
+    ) -> Result<Self, Self::Error> {
+        use golem_api_grpc::proto::golem::apidefinition::api_key_security_scheme::Location;
+
+        let location = match value.location.ok_or("Missing API key location")? {
+            Location::Header(name) => ApiKeyLocation::Header(name),
+            Location::QueryParam(name) => ApiKeyLocation::QueryParam(name),
+        };
+
+        ApiKeySecurityScheme::new(
+            SecuritySchemeIdentifier::new(value.scheme_identifier),
+            location,
+        )
+    }
+}
+
+impl From<ApiKeySecurityScheme>
+    for golem_api_grpc::proto::golem::apidefinition::ApiKeySecurityScheme
+{
+    fn from(value: ApiKeySecurityScheme) -> Self {
+        use golem_api_grpc::proto::golem::apidefinition::api_key_security_scheme::Location;
+
+        let location = match value.location {
+            ApiKeyLocation::Header(name) => Location::Header(name),
+            ApiKeyLocation::QueryParam(name) => Location::QueryParam(name),
+        };
+
+        golem_api_grpc::proto::golem::apidefinition::ApiKeySecurityScheme {
+            scheme_identifier: value.scheme_identifier.to_string(),
+            location: Some(location),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn api_key_security_scheme_requires_a_valid_location() {
+        let scheme = ApiKeySecurityScheme::new(
+            SecuritySchemeIdentifier::new("api-key".to_string()),
+            ApiKeyLocation::Header("x-api-key".to_string()),
+        )
+        .unwrap();
+
+        let proto =
+            golem_api_grpc::proto::golem::apidefinition::ApiKeySecurityScheme::from(scheme.clone());
+        assert_eq!(ApiKeySecurityScheme::try_from(proto), Ok(scheme));
+
+        assert!(ApiKeySecurityScheme::new(
+            SecuritySchemeIdentifier::new("api-key".to_string()),
+            ApiKeyLocation::Header("x api key".to_string()),
+        )
+        .is_err());
+
+        assert!(ApiKeySecurityScheme::new(
+            SecuritySchemeIdentifier::new("api-key".to_string()),
+            ApiKeyLocation::QueryParam("".to_string()),
+        )
+        .is_err());
+    }
+}
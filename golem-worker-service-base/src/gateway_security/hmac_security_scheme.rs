@@ -0,0 +1,495 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_security::SecuritySchemeIdentifier;
+use async_trait::async_trait;
+use base64::Engine;
+use golem_common::SafeDisplay;
+use hmac::{Hmac, Mac};
+use http::HeaderName;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// A security scheme verifying webhook style signatures, that is the HMAC of the request body
+// computed with a secret shared with the sender, such as GitHub's `X-Hub-Signature-256`,
+// or of the body along with a timestamp, such as Stripe's `Stripe-Signature`.
+// The secret is stored with the scheme and only ever read by the gateway to verify requests,
+// so it is not part of the scheme referred to by the routes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HmacSecurityScheme {
+    scheme_identifier: SecuritySchemeIdentifier,
+    header: String,
+    algorithm: HmacAlgorithm,
+    encoding: HmacSignatureEncoding,
+    prefix: Option<String>,
+    format: HmacSignatureFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmacAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmacSignatureEncoding {
+    Hex,
+    Base64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmacSignatureFormat {
+    // The header holds the signature of the body, after the optional prefix
+    Plain,
+    // The header is `t=<timestamp>,v1=<signature>`, the signature being the one of
+    // `<timestamp>.<body>`, and requests whose timestamp is further than the tolerance
+    // from the current time are rejected, so that they cannot be replayed
+    Stripe { timestamp_tolerance: Duration },
+}
+
+impl HmacSignatureFormat {
+    // The tolerance of Stripe's own libraries
+    pub const DEFAULT_TIMESTAMP_TOLERANCE: Duration = Duration::from_secs(300);
+}
+
+impl HmacSecurityScheme {
+    pub fn new(
+        scheme_identifier: SecuritySchemeIdentifier,
+        header: String,
+        algorithm: HmacAlgorithm,
+        encoding: HmacSignatureEncoding,
+        prefix: Option<String>,
+        format: HmacSignatureFormat,
+    ) -> Result<Self, String> {
+        HeaderName::from_str(&header)
+            .map_err(|_| format!("Invalid signature header name '{}'", header))?;
+
+        let prefix = prefix.filter(|prefix| !prefix.is_empty());
+
+        if prefix.is_some() && matches!(format, HmacSignatureFormat::Stripe { .. }) {
+            return Err("A signature prefix is not supported by the stripe format".to_string());
+        }
+
+        Ok(HmacSecurityScheme {
+            scheme_identifier,
+            header,
+            algorithm,
+            encoding,
+            prefix,
+            format,
+        })
+    }
+
+    pub fn scheme_identifier(&self) -> SecuritySchemeIdentifier {
+        self.scheme_identifier.clone()
+    }
+
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    pub fn algorithm(&self) -> HmacAlgorithm {
+        self.algorithm
+    }
+
+    pub fn encoding(&self) -> HmacSignatureEncoding {
+        self.encoding
+    }
+
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    pub fn format(&self) -> HmacSignatureFormat {
+        self.format
+    }
+
+    // Whether the signature presented in the header (such as `sha256=<hex digest>`)
+    // is the HMAC of the body, compared in constant time
+    pub fn verify(&self, secret: &[u8], body: &[u8], presented_signature: &str) -> bool {
+        let presented_signature = presented_signature.trim();
+
+        match self.format {
+            HmacSignatureFormat::Plain => {
+                let encoded_signature = match &self.prefix {
+                    Some(prefix) => match presented_signature.strip_prefix(prefix.as_str()) {
+                        Some(encoded_signature) => encoded_signature,
+                        None => return false,
+                    },
+                    None => presented_signature,
+                };
+
+                self.verify_encoded(secret, body, encoded_signature)
+            }
+            HmacSignatureFormat::Stripe {
+                timestamp_tolerance,
+            } => {
+                let mut timestamp = None;
+                let mut encoded_signatures = vec![];
+
+                for element in presented_signature.split(',') {
+                    match element.trim().split_once('=') {
+                        Some(("t", value)) => timestamp = Some(value),
+                        Some(("v1", value)) => encoded_signatures.push(value),
+                        _ => {}
+                    }
+                }
+
+                let Some(timestamp) = timestamp else {
+                    return false;
+                };
+
+                let Ok(seconds) = timestamp.parse::<u64>() else {
+                    return false;
+                };
+
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+
+                if now.abs_diff(Duration::from_secs(seconds)) > timestamp_tolerance {
+                    return false;
+                }
+
+                let signed_payload = [timestamp.as_bytes(), b".", body].concat();
+
+                // The sender may sign with several secrets while rolling them
+                encoded_signatures.into_iter().any(|encoded_signature| {
+                    self.verify_encoded(secret, &signed_payload, encoded_signature)
+                })
+            }
+        }
+    }
+
+    fn verify_encoded(&self, secret: &[u8], body: &[u8], encoded_signature: &str) -> bool {
+        let signature = match self.encoding {
+            HmacSignatureEncoding::Hex => hex::decode(encoded_signature).ok(),
+            HmacSignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(encoded_signature)
+                .ok(),
+        };
+
+        let Some(signature) = signature else {
+            return false;
+        };
+
+        match self.algorithm {
+            HmacAlgorithm::Sha1 => Hmac::<Sha1>::new_from_slice(secret)
+                .is_ok_and(|mac| mac.chain_update(body).verify_slice(&signature).is_ok()),
+            HmacAlgorithm::Sha256 => Hmac::<Sha256>::new_from_slice(secret)
+                .is_ok_and(|mac| mac.chain_update(body).verify_slice(&signature).is_ok()),
+            HmacAlgorithm::Sha512 => Hmac::<Sha512>::new_from_slice(secret)
+                .is_ok_and(|mac| mac.chain_update(body).verify_slice(&signature).is_ok()),
+        }
+    }
+}
+
+impl FromStr for HmacAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sha1" => Ok(HmacAlgorithm::Sha1),
+            "sha256" => Ok(HmacAlgorithm::Sha256),
+            "sha512" => Ok(HmacAlgorithm::Sha512),
+            _ => Err(format!(
+                "Unsupported HMAC algorithm '{}', expected one of sha1, sha256 or sha512",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for HmacAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HmacAlgorithm::Sha1 => write!(f, "sha1"),
+            HmacAlgorithm::Sha256 => write!(f, "sha256"),
+            HmacAlgorithm::Sha512 => write!(f, "sha512"),
+        }
+    }
+}
+
+impl FromStr for HmacSignatureEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hex" => Ok(HmacSignatureEncoding::Hex),
+            "base64" => Ok(HmacSignatureEncoding::Base64),
+            _ => Err(format!(
+                "Unsupported signature encoding '{}', expected hex or base64",
+                s
+            )),
+        }
+    }
+}
+
+impl FromStr for HmacSignatureFormat {
+    type Err = String;
+
+    // The tolerance of the stripe format is set separately
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" => Ok(HmacSignatureFormat::Plain),
+            "stripe" => Ok(HmacSignatureFormat::Stripe {
+                timestamp_tolerance: HmacSignatureFormat::DEFAULT_TIMESTAMP_TOLERANCE,
+            }),
+            _ => Err(format!(
+                "Unsupported signature format '{}', expected plain or stripe",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for HmacSignatureFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HmacSignatureFormat::Plain => write!(f, "plain"),
+            HmacSignatureFormat::Stripe { .. } => write!(f, "stripe"),
+        }
+    }
+}
+
+impl Display for HmacSignatureEncoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HmacSignatureEncoding::Hex => write!(f, "hex"),
+            HmacSignatureEncoding::Base64 => write!(f, "base64"),
+        }
+    }
+}
+
+// Verifies the signatures of the requests of routes secured with an HMAC security scheme,
+// looking up the secret of the scheme in the namespace of the route
+#[async_trait]
+pub trait HmacSignatureValidator<Namespace> {
+    async fn validate(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &HmacSecurityScheme,
+        body: &[u8],
+        presented_signature: &str,
+    ) -> Result<(), HmacSignatureValidationError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HmacSignatureValidationError {
+    InvalidSignature,
+    InternalError(String),
+}
+
+// To satisfy thiserror
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for HmacSignatureValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for HmacSignatureValidationError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            HmacSignatureValidationError::InvalidSignature => "InvalidSignature".to_string(),
+            HmacSignatureValidationError::InternalError(err) => format!("InternalError: {}", err),
+        }
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::HmacSecurityScheme>
+    for HmacSecurityScheme
+{
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::HmacSecurityScheme,
+    ) -> Result<Self, Self::Error> {
+        use golem_api_grpc::proto::golem::apidefinition::hmac_security_scheme::{
+            Algorithm, Encoding, Format,
+        };
+
+        let algorithm = match value.algorithm() {
+            Algorithm::Sha1 => HmacAlgorithm::Sha1,
+            Algorithm::Sha256 => HmacAlgorithm::Sha256,
+            Algorithm::Sha512 => HmacAlgorithm::Sha512,
+        };
+
+        let encoding = match value.encoding() {
+            Encoding::Hex => HmacSignatureEncoding::Hex,
+            Encoding::Base64 => HmacSignatureEncoding::Base64,
+        };
+
+        let format = match value.format() {
+            Format::Plain => HmacSignatureFormat::Plain,
+            Format::Stripe => HmacSignatureFormat::Stripe {
+                timestamp_tolerance: value
+                    .timestamp_tolerance_seconds
+                    .map(Duration::from_secs)
+                    .unwrap_or(HmacSignatureFormat::DEFAULT_TIMESTAMP_TOLERANCE),
+            },
+        };
+
+        HmacSecurityScheme::new(
+            SecuritySchemeIdentifier::new(value.scheme_identifier),
+            value.header,
+            algorithm,
+            encoding,
+            value.prefix,
+            format,
+        )
+    }
+}
+
+impl From<HmacSecurityScheme> for golem_api_grpc::proto::golem::apidefinition::HmacSecurityScheme {
+    fn from(value: HmacSecurityScheme) -> Self {
+        use golem_api_grpc::proto::golem::apidefinition::hmac_security_scheme::{
+            Algorithm, Encoding, Format,
+        };
+
+        let algorithm = match value.algorithm {
+            HmacAlgorithm::Sha1 => Algorithm::Sha1,
+            HmacAlgorithm::Sha256 => Algorithm::Sha256,
+            HmacAlgorithm::Sha512 => Algorithm::Sha512,
+        };
+
+        let encoding = match value.encoding {
+            HmacSignatureEncoding::Hex => Encoding::Hex,
+            HmacSignatureEncoding::Base64 => Encoding::Base64,
+        };
+
+        let (format, timestamp_tolerance_seconds) = match value.format {
+            HmacSignatureFormat::Plain => (Format::Plain, None),
+            HmacSignatureFormat::Stripe {
+                timestamp_tolerance,
+            } => (Format::Stripe, Some(timestamp_tolerance.as_secs())),
+        };
+
+        golem_api_grpc::proto::golem::apidefinition::HmacSecurityScheme {
+            scheme_identifier: value.scheme_identifier.to_string(),
+            header: value.header,
+            algorithm: algorithm.into(),
+            encoding: encoding.into(),
+            prefix: value.prefix,
+            format: format.into(),
+            timestamp_tolerance_seconds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn hmac_signatures_are_verified_against_the_body() {
+        // The example of the GitHub webhook documentation
+        let secret = b"It's a Secret to Everybody";
+        let body = b"Hello, World!";
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        let scheme = HmacSecurityScheme::new(
+            SecuritySchemeIdentifier::new("github".to_string()),
+            "X-Hub-Signature-256".to_string(),
+            HmacAlgorithm::Sha256,
+            HmacSignatureEncoding::Hex,
+            Some("sha256=".to_string()),
+            HmacSignatureFormat::Plain,
+        )
+        .unwrap();
+
+        assert!(scheme.verify(secret, body, signature));
+        assert!(!scheme.verify(secret, b"Hello, World?", signature));
+        assert!(!scheme.verify(b"another secret", body, signature));
+        assert!(!scheme.verify(secret, body, &signature["sha256=".len()..]));
+
+        let proto =
+            golem_api_grpc::proto::golem::apidefinition::HmacSecurityScheme::from(scheme.clone());
+        assert_eq!(HmacSecurityScheme::try_from(proto), Ok(scheme));
+
+        assert!(HmacSecurityScheme::new(
+            SecuritySchemeIdentifier::new("github".to_string()),
+            "x signature".to_string(),
+            HmacAlgorithm::Sha256,
+            HmacSignatureEncoding::Hex,
+            None,
+            HmacSignatureFormat::Plain,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn stripe_signatures_are_verified_against_the_timestamp_and_the_body() {
+        let secret = b"whsec_test_secret";
+        let body = br#"{"id":"evt_1","type":"payment_intent.succeeded"}"#;
+
+        let sign = |timestamp: u64| {
+            let mac = Hmac::<Sha256>::new_from_slice(secret)
+                .unwrap()
+                .chain_update(format!("{}.", timestamp).as_bytes())
+                .chain_update(body);
+            hex::encode(mac.finalize().into_bytes())
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let scheme = HmacSecurityScheme::new(
+            SecuritySchemeIdentifier::new("stripe".to_string()),
+            "Stripe-Signature".to_string(),
+            HmacAlgorithm::Sha256,
+            HmacSignatureEncoding::Hex,
+            None,
+            HmacSignatureFormat::Stripe {
+                timestamp_tolerance: Duration::from_secs(300),
+            },
+        )
+        .unwrap();
+
+        let signature = format!("t={},v1={}", now, sign(now));
+        let rolled_signature = format!("t={},v1={},v1={},v0=legacy", now, "00", sign(now));
+        let expired_signature = format!("t={},v1={}", now - 600, sign(now - 600));
+        let tampered_timestamp = format!("t={},v1={}", now - 1, sign(now));
+
+        assert!(scheme.verify(secret, body, &signature));
+        assert!(scheme.verify(secret, body, &rolled_signature));
+        assert!(!scheme.verify(secret, body, &expired_signature));
+        assert!(!scheme.verify(secret, body, &tampered_timestamp));
+        assert!(!scheme.verify(secret, b"{}", &signature));
+        assert!(!scheme.verify(secret, body, &format!("v1={}", sign(now))));
+
+        let proto =
+            golem_api_grpc::proto::golem::apidefinition::HmacSecurityScheme::from(scheme.clone());
+        assert_eq!(HmacSecurityScheme::try_from(proto), Ok(scheme));
+
+        assert!(HmacSecurityScheme::new(
+            SecuritySchemeIdentifier::new("stripe".to_string()),
+            "Stripe-Signature".to_string(),
+            HmacAlgorithm::Sha256,
+            HmacSignatureEncoding::Hex,
+            Some("sha256=".to_string()),
+            HmacSignatureFormat::Stripe {
+                timestamp_tolerance: Duration::from_secs(300),
+            },
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_security::SecuritySchemeIdentifier;
+use url::{Host, Url};
+
+// A security scheme validating the bearer JWT of each request against the keys published
+// by the issuer at the JWKS URL, without any of the browser flow of OpenID Connect.
+// Unlike OpenID Connect schemes, there is no provider metadata to discover on registration,
+// as the keys are fetched (and refetched on rotation) while serving requests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JwtSecurityScheme {
+    scheme_identifier: SecuritySchemeIdentifier,
+    issuer: String,
+    audiences: Vec<String>,
+    jwks_url: Url,
+}
+
+impl JwtSecurityScheme {
+    pub fn new(
+        scheme_identifier: SecuritySchemeIdentifier,
+        issuer: String,
+        audiences: Vec<String>,
+        jwks_url: Url,
+    ) -> Result<Self, String> {
+        if issuer.trim().is_empty() {
+            return Err("JWT issuer must not be empty".to_string());
+        }
+
+        if audiences.is_empty() || audiences.iter().any(|audience| audience.trim().is_empty()) {
+            return Err("JWT audiences must be a non-empty list of non-empty values".to_string());
+        }
+
+        // The keys fetched over plain http could be replaced on the way, so it is only allowed
+        // for an issuer running on the same host, such as in local development
+        let is_loopback = match jwks_url.host() {
+            Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+            Some(Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(Host::Ipv6(ip)) => ip.is_loopback(),
+            None => false,
+        };
+
+        match jwks_url.scheme() {
+            "https" => {}
+            "http" if is_loopback => {}
+            "http" => {
+                return Err(format!(
+                    "JWKS URL must be an https URL unless it is on localhost, found {}",
+                    jwks_url
+                ))
+            }
+            _ => return Err(format!("JWKS URL must be an https URL, found {}", jwks_url)),
+        }
+
+        Ok(JwtSecurityScheme {
+            scheme_identifier,
+            issuer,
+            audiences,
+            jwks_url,
+        })
+    }
+
+    pub fn scheme_identifier(&self) -> SecuritySchemeIdentifier {
+        self.scheme_identifier.clone()
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn audiences(&self) -> &[String] {
+        &self.audiences
+    }
+
+    pub fn jwks_url(&self) -> &Url {
+        &self.jwks_url
+    }
+}
+
+impl TryFrom<golem_api_grpc::proto::golem::apidefinition::JwtSecurityScheme> for JwtSecurityScheme {
+    type Error = String;
+
+    fn try_from(
+        value: golem_api_grpc::proto::golem::apidefinition::JwtSecurityScheme,
+    ) -> Result<Self, Self::Error> {
+        let jwks_url =
+            Url::parse(&value.jwks_url).map_err(|err| format!("Invalid JWKS URL. {}", err))?;
+
+        JwtSecurityScheme::new(
+            SecuritySchemeIdentifier::new(value.scheme_identifier),
+            value.issuer,
+            value.audiences,
+            jwks_url,
+        )
+    }
+}
+
+impl From<JwtSecurityScheme> for golem_api_grpc::proto::golem::apidefinition::JwtSecurityScheme {
+    fn from(value: JwtSecurityScheme) -> Self {
+        golem_api_grpc::proto::golem::apidefinition::JwtSecurityScheme {
+            scheme_identifier: value.scheme_identifier.to_string(),
+            issuer: value.issuer,
+            audiences: value.audiences,
+            jwks_url: value.jwks_url.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn jwt_security_scheme_requires_issuer_and_audiences() {
+        let jwks_url = Url::parse("https://issuer.example.com/.well-known/jwks.json").unwrap();
+
+        let scheme = JwtSecurityScheme::new(
+            SecuritySchemeIdentifier::new("jwt".to_string()),
+            "https://issuer.example.com".to_string(),
+            vec!["my-api".to_string()],
+            jwks_url.clone(),
+        )
+        .unwrap();
+
+        let proto =
+            golem_api_grpc::proto::golem::apidefinition::JwtSecurityScheme::from(scheme.clone());
+        assert_eq!(JwtSecurityScheme::try_from(proto), Ok(scheme));
+
+        assert!(JwtSecurityScheme::new(
+            SecuritySchemeIdentifier::new("jwt".to_string()),
+            "".to_string(),
+            vec!["my-api".to_string()],
+            jwks_url.clone(),
+        )
+        .is_err());
+
+        assert!(JwtSecurityScheme::new(
+            SecuritySchemeIdentifier::new("jwt".to_string()),
+            "https://issuer.example.com".to_string(),
+            vec![],
+            jwks_url,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn jwt_security_scheme_requires_https_unless_on_localhost() {
+        let new = |jwks_url: &str| {
+            JwtSecurityScheme::new(
+                SecuritySchemeIdentifier::new("jwt".to_string()),
+                "https://issuer.example.com".to_string(),
+                vec!["my-api".to_string()],
+                Url::parse(jwks_url).unwrap(),
+            )
+        };
+
+        assert!(new("https://issuer.example.com/.well-known/jwks.json").is_ok());
+        assert!(new("http://localhost:8080/.well-known/jwks.json").is_ok());
+        assert!(new("http://127.0.0.1:8080/.well-known/jwks.json").is_ok());
+        assert!(new("http://[::1]:8080/.well-known/jwks.json").is_ok());
+
+        assert!(new("http://issuer.example.com/.well-known/jwks.json").is_err());
+        assert!(new("http://10.0.0.1/.well-known/jwks.json").is_err());
+        assert!(new("ftp://issuer.example.com/jwks.json").is_err());
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_security::JwtSecurityScheme;
+use async_trait::async_trait;
+use golem_common::SafeDisplay;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{DecodingKey, Validation};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+use url::Url;
+
+// Keys are refetched once this old, picking up keys the issuer stopped publishing
+const JWKS_CACHE_TTL: Duration = Duration::from_secs(600);
+
+// A token signed with a key that is not in the cached keys triggers a refetch, as the issuer
+// may have rotated its keys, but at most once within this interval, so that tokens with
+// made up key ids cannot be used to flood the issuer with requests
+const JWKS_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+// Validates the bearer tokens of the requests of routes secured with a JWT security scheme
+#[async_trait]
+pub trait JwtValidator {
+    // Verifies the signature, expiry, issuer and audience of the token,
+    // returning its claims, which are made available to Rib as `request.auth`
+    async fn validate(
+        &self,
+        security_scheme: &JwtSecurityScheme,
+        token: &str,
+    ) -> Result<Value, JwtValidationError>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JwtValidationError {
+    InvalidToken(String),
+    UnknownKey(String),
+    FailedToFetchKeys(String),
+}
+
+// To satisfy thiserror
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for JwtValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for JwtValidationError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            JwtValidationError::InvalidToken(err) => format!("InvalidToken: {}", err),
+            JwtValidationError::UnknownKey(kid) => format!("UnknownKey: {}", kid),
+            JwtValidationError::FailedToFetchKeys(err) => format!("FailedToFetchKeys: {}", err),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CachedJwks {
+    keys: Arc<JwkSet>,
+    fetched_at: Instant,
+}
+
+// Caches the keys of each JWKS URL, shared by all the schemes of the issuer
+pub struct DefaultJwtValidator {
+    client: reqwest::Client,
+    cache: Mutex<HashMap<Url, CachedJwks>>,
+}
+
+impl Default for DefaultJwtValidator {
+    fn default() -> Self {
+        DefaultJwtValidator {
+            client: reqwest::Client::new(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl DefaultJwtValidator {
+    fn cached_keys(&self, jwks_url: &Url) -> Option<CachedJwks> {
+        self.cache.lock().unwrap().get(jwks_url).cloned()
+    }
+
+    async fn fetch_keys(&self, jwks_url: &Url) -> Result<Arc<JwkSet>, JwtValidationError> {
+        debug!("Fetching JWKS from {}", jwks_url);
+
+        let keys = self
+            .client
+            .get(jwks_url.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| JwtValidationError::FailedToFetchKeys(err.to_string()))?
+            .json::<JwkSet>()
+            .await
+            .map_err(|err| JwtValidationError::FailedToFetchKeys(err.to_string()))?;
+
+        let keys = Arc::new(keys);
+
+        self.cache.lock().unwrap().insert(
+            jwks_url.clone(),
+            CachedJwks {
+                keys: keys.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+
+        Ok(keys)
+    }
+
+    async fn get_decoding_key(
+        &self,
+        jwks_url: &Url,
+        kid: &str,
+    ) -> Result<DecodingKey, JwtValidationError> {
+        let keys = match self.cached_keys(jwks_url) {
+            Some(cached) if cached.fetched_at.elapsed() < JWKS_CACHE_TTL => {
+                if cached.keys.find(kid).is_some()
+                    || cached.fetched_at.elapsed() < JWKS_MIN_REFRESH_INTERVAL
+                {
+                    cached.keys
+                } else {
+                    self.fetch_keys(jwks_url).await?
+                }
+            }
+            _ => self.fetch_keys(jwks_url).await?,
+        };
+
+        let jwk = keys
+            .find(kid)
+            .ok_or(JwtValidationError::UnknownKey(kid.to_string()))?;
+
+        DecodingKey::from_jwk(jwk).map_err(|err| JwtValidationError::InvalidToken(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl JwtValidator for DefaultJwtValidator {
+    async fn validate(
+        &self,
+        security_scheme: &JwtSecurityScheme,
+        token: &str,
+    ) -> Result<Value, JwtValidationError> {
+        let header = jsonwebtoken::decode_header(token)
+            .map_err(|err| JwtValidationError::InvalidToken(err.to_string()))?;
+
+        let kid = header.kid.ok_or(JwtValidationError::InvalidToken(
+            "Token has no key id".to_string(),
+        ))?;
+
+        let decoding_key = self
+            .get_decoding_key(security_scheme.jwks_url(), &kid)
+            .await?;
+
+        // The algorithm of the header has to match the family of the key,
+        // so a token cannot pick an algorithm the key was not published for
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[security_scheme.issuer()]);
+        validation.set_audience(security_scheme.audiences());
+
+        let token_data = jsonwebtoken::decode::<Value>(token, &decoding_key, &validation)
+            .map_err(|err| JwtValidationError::InvalidToken(err.to_string()))?;
+
+        Ok(token_data.claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gateway_security::SecuritySchemeIdentifier;
+    use jsonwebtoken::{Algorithm, EncodingKey, Header};
+    use serde_json::json;
+    use test_r::test;
+
+    #[test]
+    async fn tokens_are_validated_against_the_cached_keys() {
+        let jwks_url = Url::parse("https://issuer.example.com/.well-known/jwks.json").unwrap();
+
+        let security_scheme = JwtSecurityScheme::new(
+            SecuritySchemeIdentifier::new("jwt".to_string()),
+            "https://issuer.example.com".to_string(),
+            vec!["my-api".to_string()],
+            jwks_url.clone(),
+        )
+        .unwrap();
+
+        // The key is the base64url encoded "secret", cached as if fetched from the issuer
+        let keys: JwkSet = serde_json::from_value(json!({
+            "keys": [{ "kty": "oct", "kid": "key-1", "alg": "HS256", "k": "c2VjcmV0" }]
+        }))
+        .unwrap();
+
+        let validator = DefaultJwtValidator::default();
+        validator.cache.lock().unwrap().insert(
+            jwks_url,
+            CachedJwks {
+                keys: Arc::new(keys),
+                fetched_at: Instant::now(),
+            },
+        );
+
+        let sign = |kid: &str, claims: Value| {
+            let mut header = Header::new(Algorithm::HS256);
+            header.kid = Some(kid.to_string());
+            jsonwebtoken::encode(&header, &claims, &EncodingKey::from_secret(b"secret")).unwrap()
+        };
+
+        let exp = jsonwebtoken::get_current_timestamp() + 60;
+
+        let claims = json!({
+            "sub": "user-1",
+            "iss": "https://issuer.example.com",
+            "aud": "my-api",
+            "exp": exp
+        });
+
+        let result = validator
+            .validate(&security_scheme, &sign("key-1", claims.clone()))
+            .await;
+        assert_eq!(result, Ok(claims));
+
+        let other_audience = json!({
+            "sub": "user-1",
+            "iss": "https://issuer.example.com",
+            "aud": "other-api",
+            "exp": exp
+        });
+
+        let result = validator
+            .validate(&security_scheme, &sign("key-1", other_audience))
+            .await;
+        assert!(matches!(result, Err(JwtValidationError::InvalidToken(_))));
+
+        // Recently fetched keys are not refetched for an unknown key id
+        let unknown_key = json!({
+            "sub": "user-1",
+            "iss": "https://issuer.example.com",
+            "aud": "my-api",
+            "exp": exp
+        });
+
+        let result = validator
+            .validate(&security_scheme, &sign("key-2", unknown_key))
+            .await;
+        assert_eq!(
+            result,
+            Err(JwtValidationError::UnknownKey("key-2".to_string()))
+        );
+    }
+}
@@ -1,14 +1,24 @@
+pub use api_key::*;
+pub use api_key_security_scheme::*;
 pub use default_provider::*;
+pub use hmac_security_scheme::*;
 pub use identity_provider::*;
 pub use identity_provider_metadata::*;
+pub use jwt_security_scheme::*;
+pub use jwt_validator::*;
 pub use open_id_client::*;
 pub use security_scheme::*;
 pub use security_scheme_metadata::*;
 pub use security_scheme_reference::*;
 
+mod api_key;
+mod api_key_security_scheme;
 mod default_provider;
+mod hmac_security_scheme;
 mod identity_provider;
 mod identity_provider_metadata;
+mod jwt_security_scheme;
+mod jwt_validator;
 mod open_id_client;
 mod security_scheme;
 mod security_scheme_metadata;
@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::gateway_security::{GolemIdentityProviderMetadata, SecurityScheme};
+use crate::gateway_security::{
+    ApiKeySecurityScheme, GolemIdentityProviderMetadata, HmacSecurityScheme, JwtSecurityScheme,
+    SecurityScheme, SecuritySchemeIdentifier,
+};
 
 // This can exist as part of the middleware to initiate the authorisation workflow
 // redirecting user to provider login page, or it can be part of the static binding
@@ -23,6 +26,32 @@ pub struct SecuritySchemeWithProviderMetadata {
     pub provider_metadata: GolemIdentityProviderMetadata,
 }
 
+// A security scheme as registered with Golem, which routes refer to by its identifier.
+// OpenID Connect schemes come with the metadata of their provider, discovered on registration,
+// while JWT schemes only need the keys of the issuer, which are fetched while serving requests,
+// API key schemes the keys issued by Golem, which are managed separately,
+// and HMAC schemes the secret shared with the sender of the requests, which is stored along.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegisteredSecurityScheme {
+    OpenId(SecuritySchemeWithProviderMetadata),
+    Jwt(JwtSecurityScheme),
+    ApiKey(ApiKeySecurityScheme),
+    Hmac(HmacSecurityScheme),
+}
+
+impl RegisteredSecurityScheme {
+    pub fn scheme_identifier(&self) -> SecuritySchemeIdentifier {
+        match self {
+            RegisteredSecurityScheme::OpenId(open_id) => {
+                open_id.security_scheme.scheme_identifier()
+            }
+            RegisteredSecurityScheme::Jwt(jwt) => jwt.scheme_identifier(),
+            RegisteredSecurityScheme::ApiKey(api_key) => api_key.scheme_identifier(),
+            RegisteredSecurityScheme::Hmac(hmac) => hmac.scheme_identifier(),
+        }
+    }
+}
+
 impl TryFrom<golem_api_grpc::proto::golem::apidefinition::SecurityWithProviderMetadata>
     for SecuritySchemeWithProviderMetadata
 {
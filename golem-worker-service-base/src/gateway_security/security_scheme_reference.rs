@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::gateway_security::{SecuritySchemeIdentifier, SecuritySchemeWithProviderMetadata};
+use crate::gateway_security::{
+    ApiKeySecurityScheme, HmacSecurityScheme, JwtSecurityScheme, SecuritySchemeIdentifier,
+    SecuritySchemeWithProviderMetadata,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SecuritySchemeReference {
@@ -34,3 +37,27 @@ impl From<SecuritySchemeWithProviderMetadata> for SecuritySchemeReference {
         }
     }
 }
+
+impl From<JwtSecurityScheme> for SecuritySchemeReference {
+    fn from(value: JwtSecurityScheme) -> Self {
+        SecuritySchemeReference {
+            security_scheme_identifier: value.scheme_identifier(),
+        }
+    }
+}
+
+impl From<ApiKeySecurityScheme> for SecuritySchemeReference {
+    fn from(value: ApiKeySecurityScheme) -> Self {
+        SecuritySchemeReference {
+            security_scheme_identifier: value.scheme_identifier(),
+        }
+    }
+}
+
+impl From<HmacSecurityScheme> for SecuritySchemeReference {
+    fn from(value: HmacSecurityScheme) -> Self {
+        SecuritySchemeReference {
+            security_scheme_identifier: value.scheme_identifier(),
+        }
+    }
+}
@@ -142,18 +142,30 @@ pub fn get_response_headers(
     field_values: &[Value],
     record: &TypeRecord,
 ) -> Result<Option<ResolvedResponseHeaders>, String> {
-    match record
-        .fields
-        .iter()
-        .position(|pair| &pair.name == "headers")
-    {
-        None => Ok(None),
-        Some(field_position) => Ok(Some(ResolvedResponseHeaders::from_typed_value(
-            ValueAndType::new(
-                field_values[field_position].clone(),
-                record.fields[field_position].typ.clone(),
-            ),
-        )?)),
+    let field = |name: &str| {
+        record
+            .fields
+            .iter()
+            .position(|pair| pair.name == name)
+            .map(|field_position| {
+                ValueAndType::new(
+                    field_values[field_position].clone(),
+                    record.fields[field_position].typ.clone(),
+                )
+            })
+    };
+
+    let headers = field("headers")
+        .map(ResolvedResponseHeaders::from_typed_value)
+        .transpose()?;
+
+    match field("set-cookies") {
+        None => Ok(headers),
+        Some(set_cookies) => {
+            let mut headers = headers.unwrap_or_default();
+            headers.append_set_cookies(set_cookies)?;
+            Ok(Some(headers))
+        }
     }
 }
 
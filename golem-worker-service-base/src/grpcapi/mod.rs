@@ -14,11 +14,13 @@
 
 use golem_api_grpc::proto::golem::common::{ErrorBody, ErrorsBody};
 use golem_api_grpc::proto::golem::worker::v1::{
-    worker_error, worker_execution_error, WorkerError, WorkerExecutionError,
+    worker_error, worker_execution_error, UnknownError, WorkerError, WorkerExecutionError,
 };
 use golem_common::model::{ComponentFilePath, TargetWorkerId, WorkerId};
 use golem_service_base::model::validate_worker_name;
 use golem_wasm_rpc::json::OptionallyTypeAnnotatedValueJson;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use golem_wasm_rpc::ValueAndType;
 use tonic::Status;
 
 pub fn validated_worker_id(
@@ -201,6 +203,13 @@ pub fn error_to_status(error: WorkerError) -> Status {
                 worker_execution_error::Error::FileSystemError(_) => {
                     "Failed accessing worker filesystem".to_string()
                 }
+                worker_execution_error::Error::WorkerExecutorDraining(_) => {
+                    "Worker Executor Draining".to_string()
+                }
+                worker_execution_error::Error::OplogTruncated(err) => format!(
+                    "Oplog Truncated: Oplog Index = {}, Truncated Until = {}",
+                    err.oplog_index, err.truncated_until
+                ),
             };
             Status::internal(message)
         }
@@ -208,6 +217,22 @@ pub fn error_to_status(error: WorkerError) -> Status {
     }
 }
 
+// Converts the typed result of an invocation, as returned by the worker executor, to a
+// `ValueAndType` keeping the cases of variants and enums, flags, chars and resource handles
+pub fn invoke_result_value_and_type(
+    result: TypeAnnotatedValue,
+) -> Result<golem_wasm_rpc::protobuf::ValueAndType, WorkerError> {
+    let value_and_type = ValueAndType::try_from(result).map_err(|err| WorkerError {
+        error: Some(worker_error::Error::InternalError(WorkerExecutionError {
+            error: Some(worker_execution_error::Error::Unknown(UnknownError {
+                details: format!("Failed to convert response: {err}"),
+            })),
+        })),
+    })?;
+
+    Ok(value_and_type.into())
+}
+
 pub fn parse_json_invoke_parameters(
     parameters: &[String],
 ) -> Result<Vec<OptionallyTypeAnnotatedValueJson>, WorkerError> {
@@ -219,3 +244,57 @@ pub fn parse_json_invoke_parameters(
 
     Ok(optionally_typed_parameters)
 }
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::grpcapi::invoke_result_value_and_type;
+    use golem_wasm_ast::analysis::analysed_type::{
+        case, chr, field, flags, handle, r#enum, record, u32, unit_case, variant,
+    };
+    use golem_wasm_ast::analysis::{AnalysedResourceId, AnalysedResourceMode};
+    use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+    use golem_wasm_rpc::{TypeAnnotatedValueConstructors, Value, ValueAndType};
+
+    #[test]
+    fn invoke_result_value_and_type_round_trip() {
+        let value_and_type = ValueAndType::new(
+            Value::Record(vec![
+                Value::Char('λ'),
+                Value::Flags(vec![true, false, true]),
+                Value::Enum(2),
+                Value::Variant {
+                    case_idx: 1,
+                    case_value: Some(Box::new(Value::U32(7))),
+                },
+                Value::Handle {
+                    uri: "urn:worker:component/worker".to_string(),
+                    resource_id: 42,
+                },
+            ]),
+            record(vec![
+                field("initial", chr()),
+                field("permissions", flags(&["read", "write", "execute"])),
+                field("color", r#enum(&["red", "green", "blue"])),
+                field(
+                    "state",
+                    variant(vec![unit_case("idle"), case("running", u32())]),
+                ),
+                field(
+                    "session",
+                    handle(AnalysedResourceId(3), AnalysedResourceMode::Borrowed),
+                ),
+            ]),
+        );
+
+        // The worker executor returns the results of the invocations as type annotated values
+        let executor_result =
+            TypeAnnotatedValue::create(&value_and_type.value, &value_and_type.typ).unwrap();
+
+        let protobuf = invoke_result_value_and_type(executor_result).unwrap();
+        let round_trip: ValueAndType = protobuf.try_into().unwrap();
+
+        assert_eq!(round_trip, value_and_type);
+    }
+}
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use golem_wasm_ast::analysis::AnalysedType;
+use golem_wasm_ast::analysis::{AnalysedType, TypeList, TypeOption, TypeTuple};
 use golem_wasm_rpc::{Value, ValueAndType};
+use http::header::{HeaderName, HeaderValue};
 use http::HeaderMap;
 use poem::web::headers::ContentType;
 use rib::GetLiteralValue;
@@ -52,8 +53,68 @@ impl ResolvedResponseHeaders {
                 Ok(ResolvedResponseHeaders { headers })
             }
 
+            ValueAndType {
+                value: Value::List(values),
+                typ: AnalysedType::List(TypeList { inner }),
+            } => {
+                let mut headers = HeaderMap::new();
+
+                for value in values {
+                    let (name, value) = match (value, inner.as_ref()) {
+                        (Value::Tuple(items), AnalysedType::Tuple(TypeTuple { items: types }))
+                            if items.len() == 2 && types.len() == 2 =>
+                        {
+                            let mut items = items.into_iter();
+                            let name = literal_string(items.next().unwrap(), types[0].clone());
+                            let value = literal_string(items.next().unwrap(), types[1].clone());
+                            (name, value)
+                        }
+                        _ => Err(
+                            "Header list items must be tuples of header name and value".to_string()
+                        )?,
+                    };
+
+                    let name = HeaderName::from_str(&name)
+                        .map_err(|e| format!("Invalid header name {name}. Error: {e}"))?;
+                    let value = HeaderValue::from_str(&value)
+                        .map_err(|e| format!("Invalid value for header {name}. Error: {e}"))?;
+
+                    headers.append(name, value);
+                }
+
+                Ok(ResolvedResponseHeaders { headers })
+            }
+
+            _ => Err(format!(
+                "Header expression is not a record or a list of tuples. It is resolved to {header_map}",
+            )),
+        }
+    }
+
+    /// Appends a `Set-Cookie` header for each cookie of a `set-cookies` list.
+    ///
+    /// A cookie is either the raw header value as a string, or a record of `name`, `value`
+    /// and the optional attributes `path`, `domain`, `max-age`, `expires`, `secure`,
+    /// `http-only` and `same-site`.
+    pub fn append_set_cookies(&mut self, set_cookies: ValueAndType) -> Result<(), String> {
+        match set_cookies {
+            ValueAndType {
+                value: Value::List(values),
+                typ: AnalysedType::List(TypeList { inner }),
+            } => {
+                for value in values {
+                    let cookie = set_cookie_header(ValueAndType::new(value, (*inner).clone()))?;
+
+                    let header_value = HeaderValue::from_str(&cookie)
+                        .map_err(|e| format!("Invalid cookie {cookie}. Error: {e}"))?;
+
+                    self.headers.append(http::header::SET_COOKIE, header_value);
+                }
+
+                Ok(())
+            }
             _ => Err(format!(
-                "Header expression is not a record. It is resolved to {header_map}",
+                "Set-cookies expression is not a list. It is resolved to {set_cookies}",
             )),
         }
     }
@@ -70,13 +131,87 @@ impl ResolvedResponseHeaders {
     }
 }
 
+fn literal_string(value: Value, typ: AnalysedType) -> String {
+    ValueAndType::new(value, typ)
+        .get_literal()
+        .map(|primitive| primitive.to_string())
+        .unwrap_or_else(|| "unable to infer header".to_string())
+}
+
+fn set_cookie_header(cookie: ValueAndType) -> Result<String, String> {
+    match cookie {
+        ValueAndType {
+            value: Value::Record(field_values),
+            typ: AnalysedType::Record(record),
+        } => {
+            let mut name = None;
+            let mut value = None;
+            let mut attributes = vec![];
+
+            for (field_value, field_def) in field_values.into_iter().zip(record.fields) {
+                let Some((field_value, field_type)) = unwrap_option(field_value, field_def.typ)
+                else {
+                    continue;
+                };
+
+                let field_str = literal_string(field_value, field_type);
+
+                match field_def.name.as_str() {
+                    "name" => name = Some(field_str),
+                    "value" => value = Some(field_str),
+                    "path" => attributes.push(format!("Path={field_str}")),
+                    "domain" => attributes.push(format!("Domain={field_str}")),
+                    "max-age" => attributes.push(format!("Max-Age={field_str}")),
+                    "expires" => attributes.push(format!("Expires={field_str}")),
+                    "same-site" => attributes.push(format!("SameSite={field_str}")),
+                    "secure" if field_str == "true" => attributes.push("Secure".to_string()),
+                    "http-only" if field_str == "true" => attributes.push("HttpOnly".to_string()),
+                    "secure" | "http-only" => {}
+                    other => Err(format!("Unknown cookie attribute {other}"))?,
+                }
+            }
+
+            let name = name.ok_or("Cookie is missing the name field".to_string())?;
+            let value = value.ok_or(format!("Cookie {name} is missing the value field"))?;
+
+            let mut cookie = format!("{name}={value}");
+            for attribute in attributes {
+                cookie.push_str("; ");
+                cookie.push_str(&attribute);
+            }
+
+            Ok(cookie)
+        }
+        ValueAndType {
+            value: Value::String(raw),
+            ..
+        } => Ok(raw),
+        other => Err(format!(
+            "Cookie expression is not a record or a string. It is resolved to {other}",
+        )),
+    }
+}
+
+fn unwrap_option(value: Value, typ: AnalysedType) -> Option<(Value, AnalysedType)> {
+    match (value, typ) {
+        (Value::Option(None), _) => None,
+        (Value::Option(Some(value)), AnalysedType::Option(TypeOption { inner })) => {
+            Some((*value, *inner))
+        }
+        (value, typ) => Some((value, typ)),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::headers::ResolvedResponseHeaders;
+    use golem_wasm_ast::analysis::analysed_type::{
+        bool, field, list, option, record, str, tuple, u32,
+    };
     use golem_wasm_rpc::protobuf::{
         type_annotated_value::TypeAnnotatedValue, NameTypePair, NameValuePair, Type, TypedRecord,
     };
-    use golem_wasm_rpc::ValueAndType;
+    use golem_wasm_rpc::{Value, ValueAndType};
     use http::{HeaderMap, HeaderValue};
     use test_r::test;
 
@@ -130,4 +265,84 @@ mod test {
 
         assert_eq!(resolved_headers, expected)
     }
+
+    #[test]
+    fn test_get_response_headers_from_list_of_tuples() {
+        let header_list = ValueAndType::new(
+            Value::List(vec![
+                Value::Tuple(vec![
+                    Value::String("x-custom".to_string()),
+                    Value::String("a".to_string()),
+                ]),
+                Value::Tuple(vec![
+                    Value::String("x-custom".to_string()),
+                    Value::String("b".to_string()),
+                ]),
+            ]),
+            list(tuple(vec![str(), str()])),
+        );
+
+        let resolved_headers = ResolvedResponseHeaders::from_typed_value(header_list).unwrap();
+
+        let values = resolved_headers
+            .headers
+            .get_all("x-custom")
+            .iter()
+            .map(|v| v.to_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec!["a".to_string(), "b".to_string()])
+    }
+
+    #[test]
+    fn test_append_set_cookies() {
+        let cookie_type = record(vec![
+            field("name", str()),
+            field("value", str()),
+            field("path", option(str())),
+            field("max-age", option(u32())),
+            field("secure", bool()),
+            field("http-only", bool()),
+        ]);
+
+        let set_cookies = ValueAndType::new(
+            Value::List(vec![
+                Value::Record(vec![
+                    Value::String("session".to_string()),
+                    Value::String("abc".to_string()),
+                    Value::Option(Some(Box::new(Value::String("/".to_string())))),
+                    Value::Option(Some(Box::new(Value::U32(3600)))),
+                    Value::Bool(true),
+                    Value::Bool(true),
+                ]),
+                Value::Record(vec![
+                    Value::String("theme".to_string()),
+                    Value::String("dark".to_string()),
+                    Value::Option(None),
+                    Value::Option(None),
+                    Value::Bool(false),
+                    Value::Bool(false),
+                ]),
+            ]),
+            list(cookie_type),
+        );
+
+        let mut resolved_headers = ResolvedResponseHeaders::default();
+        resolved_headers.append_set_cookies(set_cookies).unwrap();
+
+        let values = resolved_headers
+            .headers
+            .get_all(http::header::SET_COOKIE)
+            .iter()
+            .map(|v| v.to_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            values,
+            vec![
+                "session=abc; Path=/; Max-Age=3600; Secure; HttpOnly".to_string(),
+                "theme=dark".to_string()
+            ]
+        )
+    }
 }
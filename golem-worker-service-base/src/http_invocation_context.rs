@@ -12,11 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::gateway_execution::request_id::{request_id, REQUEST_ID_ATTRIBUTE};
 use golem_common::model::invocation_context::{
-    AttributeValue, InvocationContextSpan, InvocationContextStack, TraceId,
+    AttributeValue, InvocationContextSpan, InvocationContextStack, TraceId, DEADLINE_ATTRIBUTE,
 };
+use golem_common::model::Timestamp;
 use golem_service_base::headers::TraceContextHeaders;
+use http::HeaderMap;
 use std::collections::HashMap;
+use std::str::FromStr;
+
+// Headers setting the deadline of the invocations made for a request, either as an ISO 8601
+// timestamp, or as a timeout in milliseconds from the time the request is received
+pub const DEADLINE_HEADER: &str = "golem-deadline";
+pub const TIMEOUT_MS_HEADER: &str = "golem-timeout-ms";
 
 pub fn extract_request_attributes(request: &poem::Request) -> HashMap<String, AttributeValue> {
     let mut result = HashMap::new();
@@ -34,9 +43,40 @@ pub fn extract_request_attributes(request: &poem::Request) -> HashMap<String, At
         AttributeValue::String(request.remote_addr().to_string()),
     );
 
+    if let Some(request_id) = request_id(request) {
+        result.insert(
+            REQUEST_ID_ATTRIBUTE.to_string(),
+            AttributeValue::String(request_id.to_string()),
+        );
+    }
+
+    if let Some(deadline) = request_deadline(request.headers()) {
+        result.insert(
+            DEADLINE_ATTRIBUTE.to_string(),
+            AttributeValue::String(deadline.to_string()),
+        );
+    }
+
     result
 }
 
+// The earliest of the deadlines set by the headers of the request
+fn request_deadline(headers: &HeaderMap) -> Option<Timestamp> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+    let deadline = header(DEADLINE_HEADER).and_then(|value| Timestamp::from_str(value).ok());
+    let timeout_deadline = header(TIMEOUT_MS_HEADER)
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(|timeout_ms| {
+            Timestamp::from(Timestamp::now_utc().to_millis().saturating_add(timeout_ms))
+        });
+
+    match (deadline, timeout_deadline) {
+        (Some(deadline), Some(timeout_deadline)) => Some(deadline.min(timeout_deadline)),
+        (deadline, timeout_deadline) => deadline.or(timeout_deadline),
+    }
+}
+
 pub fn invocation_context_from_request(request: &poem::Request) -> InvocationContextStack {
     let trace_context_headers = TraceContextHeaders::parse(request.headers());
     let request_attributes = extract_request_attributes(request);
@@ -75,6 +115,43 @@ pub fn grpc_invocation_context_from_request(
         parent: None,
         args: Vec::new(),
         env: HashMap::new(),
+        wasi_config_vars: HashMap::new(),
         tracing: Some(grpc_tracing_invocation_context),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn deadline_of_the_request_is_the_earliest_of_its_headers() {
+        let request = poem::Request::builder()
+            .header(DEADLINE_HEADER, "2024-01-01T00:00:00Z")
+            .header(TIMEOUT_MS_HEADER, "30000")
+            .finish();
+        let attributes = extract_request_attributes(&request);
+        assert_eq!(
+            attributes.get(DEADLINE_ATTRIBUTE),
+            Some(&AttributeValue::String(
+                Timestamp::from_str("2024-01-01T00:00:00Z")
+                    .unwrap()
+                    .to_string()
+            ))
+        );
+
+        let before = Timestamp::now_utc().to_millis();
+        let request = poem::Request::builder()
+            .header(TIMEOUT_MS_HEADER, "30000")
+            .finish();
+        let context = invocation_context_from_request(&request);
+        let deadline = context.deadline().unwrap().to_millis();
+        assert!(deadline >= before + 30000 && deadline <= Timestamp::now_utc().to_millis() + 30000);
+
+        let request = poem::Request::builder()
+            .header(TIMEOUT_MS_HEADER, "soon")
+            .finish();
+        assert_eq!(invocation_context_from_request(&request).deadline(), None);
+    }
+}
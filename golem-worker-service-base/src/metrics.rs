@@ -12,12 +12,264 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app_config::MetricsConfig;
 use crate::VERSION;
 use golem_service_base::metrics::VERSION_INFO;
 use prometheus::*;
 
-pub fn register_all() -> Registry {
+pub fn register_all(config: &MetricsConfig) -> Registry {
     VERSION_INFO.with_label_values(&[VERSION]).inc();
+    labels::configure(config);
 
     default_registry().clone()
 }
+
+// Bounds the cardinality of the labels whose values come from the deployments and the
+// components. Once a label has the maximal number of distinct values, the new values are
+// recorded as `other`.
+mod labels {
+    use crate::app_config::MetricsConfig;
+    use std::collections::HashSet;
+    use std::sync::{Mutex, OnceLock};
+
+    const OTHER: &str = "other";
+    const ALL_ROUTES: &str = "all";
+
+    static LABELS: OnceLock<Labels> = OnceLock::new();
+
+    struct Labels {
+        route_labels: bool,
+        sites: LabelValues,
+        routes: LabelValues,
+        components: LabelValues,
+    }
+
+    pub struct LabelValues {
+        max: usize,
+        values: Mutex<HashSet<String>>,
+    }
+
+    impl LabelValues {
+        pub fn new(max: usize) -> Self {
+            Self {
+                max,
+                values: Mutex::new(HashSet::new()),
+            }
+        }
+
+        pub fn get(&self, value: &str) -> String {
+            let mut values = self.values.lock().unwrap();
+            if values.contains(value) {
+                value.to_string()
+            } else if values.len() < self.max {
+                values.insert(value.to_string());
+                value.to_string()
+            } else {
+                OTHER.to_string()
+            }
+        }
+    }
+
+    pub fn configure(config: &MetricsConfig) {
+        let _ = LABELS.set(Labels::new(config));
+    }
+
+    fn labels() -> &'static Labels {
+        LABELS.get_or_init(|| Labels::new(&MetricsConfig::default()))
+    }
+
+    impl Labels {
+        fn new(config: &MetricsConfig) -> Self {
+            Self {
+                route_labels: config.route_labels,
+                sites: LabelValues::new(config.max_label_values),
+                routes: LabelValues::new(config.max_label_values),
+                components: LabelValues::new(config.max_label_values),
+            }
+        }
+    }
+
+    pub fn site(site: &str) -> String {
+        labels().sites.get(site)
+    }
+
+    // Routes are only distinct within their site
+    pub fn route(site: &str, route: &str) -> String {
+        let labels = labels();
+        if !labels.route_labels {
+            ALL_ROUTES.to_string()
+        } else if labels.routes.get(&format!("{site} {route}")) == OTHER {
+            OTHER.to_string()
+        } else {
+            route.to_string()
+        }
+    }
+
+    pub fn component(component_id: &str) -> String {
+        labels().components.get(component_id)
+    }
+}
+
+pub mod gateway {
+    use std::future::Future;
+    use std::time::Duration;
+
+    use golem_common::metrics::{DEFAULT_SIZE_BUCKETS, DEFAULT_TIME_BUCKETS};
+    use lazy_static::lazy_static;
+    use prometheus::*;
+
+    use super::labels;
+
+    tokio::task_local! {
+        static ROUTE: RouteLabels;
+    }
+
+    #[derive(Clone)]
+    struct RouteLabels {
+        site: String,
+        route: String,
+    }
+
+    lazy_static! {
+        static ref GATEWAY_RIB_COMPILE_CACHE_TOTAL: CounterVec = register_counter_vec!(
+            "gateway_rib_compile_cache_total",
+            "Number of lookups of the compiled Rib scripts of the API definitions of a site",
+            &["site", "result"]
+        )
+        .unwrap();
+        static ref GATEWAY_RIB_EVALUATION_SECONDS: HistogramVec = register_histogram_vec!(
+            "gateway_rib_evaluation_seconds",
+            "Time to evaluate the response mapping of a route, including the worker invocations",
+            &["site", "route"],
+            DEFAULT_TIME_BUCKETS.to_vec()
+        )
+        .unwrap();
+        static ref GATEWAY_WORKER_INVOKE_SECONDS: HistogramVec = register_histogram_vec!(
+            "gateway_worker_invoke_seconds",
+            "Duration of the worker invocations made by a route",
+            &["site", "route"],
+            DEFAULT_TIME_BUCKETS.to_vec()
+        )
+        .unwrap();
+        static ref GATEWAY_RESPONSE_SIZE_BYTES: HistogramVec = register_histogram_vec!(
+            "gateway_response_size_bytes",
+            "Size of the response bodies of a route",
+            &["site", "route"],
+            DEFAULT_SIZE_BUCKETS.to_vec()
+        )
+        .unwrap();
+    }
+
+    /// Runs the handling of a request of a route, labelling the metrics recorded meanwhile
+    /// with the route
+    pub async fn in_route<F: Future>(site: &str, route: &str, f: F) -> F::Output {
+        ROUTE
+            .scope(
+                RouteLabels {
+                    site: site.to_string(),
+                    route: route.to_string(),
+                },
+                f,
+            )
+            .await
+    }
+
+    /// Keeps the route of the request being handled for a task spawned while handling it
+    pub fn with_current_route<F: Future>(f: F) -> impl Future<Output = F::Output> {
+        let route = ROUTE.try_with(|route| route.clone()).ok();
+        async move {
+            match route {
+                Some(route) => ROUTE.scope(route, f).await,
+                None => f.await,
+            }
+        }
+    }
+
+    pub fn is_in_route() -> bool {
+        ROUTE.try_with(|_| ()).is_ok()
+    }
+
+    pub fn record_rib_compile_cache(site: &str, hit: bool) {
+        GATEWAY_RIB_COMPILE_CACHE_TOTAL
+            .with_label_values(&[&labels::site(site), if hit { "hit" } else { "miss" }])
+            .inc();
+    }
+
+    pub fn record_rib_evaluation(duration: Duration) {
+        if let Some((site, route)) = current_labels() {
+            GATEWAY_RIB_EVALUATION_SECONDS
+                .with_label_values(&[&site, &route])
+                .observe(duration.as_secs_f64());
+        }
+    }
+
+    pub fn record_worker_invoke(duration: Duration) {
+        if let Some((site, route)) = current_labels() {
+            GATEWAY_WORKER_INVOKE_SECONDS
+                .with_label_values(&[&site, &route])
+                .observe(duration.as_secs_f64());
+        }
+    }
+
+    pub fn record_response_size(site: &str, route: &str, size: usize) {
+        GATEWAY_RESPONSE_SIZE_BYTES
+            .with_label_values(&[&labels::site(site), &labels::route(site, route)])
+            .observe(size as f64);
+    }
+
+    fn current_labels() -> Option<(String, String)> {
+        ROUTE
+            .try_with(|route| {
+                (
+                    labels::site(&route.site),
+                    labels::route(&route.site, &route.route),
+                )
+            })
+            .ok()
+    }
+}
+
+pub mod invocations {
+    use golem_common::model::ComponentId;
+    use lazy_static::lazy_static;
+    use prometheus::*;
+
+    use super::{gateway, labels};
+
+    lazy_static! {
+        static ref WORKER_INVOCATION_TOTAL: CounterVec = register_counter_vec!(
+            "worker_invocation_total",
+            "Number of invocations of the workers of a component, by the gateway or the API",
+            &["component_id", "source"]
+        )
+        .unwrap();
+    }
+
+    pub fn record_invocation(component_id: &ComponentId) {
+        let source = if gateway::is_in_route() {
+            "gateway"
+        } else {
+            "api"
+        };
+
+        WORKER_INVOCATION_TOTAL
+            .with_label_values(&[&labels::component(&component_id.to_string()), source])
+            .inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::labels::LabelValues;
+    use test_r::test;
+
+    #[test]
+    fn label_values_beyond_the_limit_are_recorded_as_other() {
+        let values = LabelValues::new(2);
+
+        assert_eq!(values.get("a"), "a");
+        assert_eq!(values.get("b"), "b");
+        assert_eq!(values.get("c"), "other");
+        assert_eq!(values.get("a"), "a");
+    }
+}
@@ -32,6 +32,15 @@ pub struct ApiDefinitionRecord {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct DeletedApiDefinitionRecord {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+    pub deleted_at: chrono::DateTime<chrono::Utc>,
+    pub deleted_by: String,
+}
+
 impl ApiDefinitionRecord {
     pub fn new<Namespace: Display>(
         definition: CompiledHttpApiDefinition<Namespace>,
@@ -98,6 +107,14 @@ pub trait ApiDefinitionRepo {
 
     async fn update(&self, definition: &ApiDefinitionRecord) -> Result<(), RepoError>;
 
+    /// Updates the definition only if its stored content is still the expected one,
+    /// returns false without changing anything otherwise
+    async fn update_if_unchanged(
+        &self,
+        definition: &ApiDefinitionRecord,
+        expected: &ApiDefinitionRecord,
+    ) -> Result<bool, RepoError>;
+
     async fn set_draft(
         &self,
         namespace: &str,
@@ -120,7 +137,28 @@ pub trait ApiDefinitionRepo {
         version: &str,
     ) -> Result<Option<bool>, RepoError>;
 
-    async fn delete(&self, namespace: &str, id: &str, version: &str) -> Result<bool, RepoError>;
+    /// Moves an API definition to the trash, returns false if it does not exist
+    async fn delete(
+        &self,
+        namespace: &str,
+        id: &str,
+        version: &str,
+        deleted_by: &str,
+    ) -> Result<bool, RepoError>;
+
+    /// Restores an API definition from the trash, returns false if it is not in the trash
+    async fn restore(&self, namespace: &str, id: &str, version: &str) -> Result<bool, RepoError>;
+
+    async fn get_deleted(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<DeletedApiDefinitionRecord>, RepoError>;
+
+    /// Permanently deletes all API definitions moved to the trash before the given time
+    async fn purge_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepoError>;
 
     async fn get_all(&self, namespace: &str) -> Result<Vec<ApiDefinitionRecord>, RepoError>;
 
@@ -176,6 +214,20 @@ impl<Repo: ApiDefinitionRepo + Sync> ApiDefinitionRepo for LoggedApiDefinitionRe
         Self::logged_with_id("update", &definition.namespace, &definition.id, result)
     }
 
+    async fn update_if_unchanged(
+        &self,
+        definition: &ApiDefinitionRecord,
+        expected: &ApiDefinitionRecord,
+    ) -> Result<bool, RepoError> {
+        let result = self.repo.update_if_unchanged(definition, expected).await;
+        Self::logged_with_id(
+            "update_if_unchanged",
+            &definition.namespace,
+            &definition.id,
+            result,
+        )
+    }
+
     async fn set_draft(
         &self,
         namespace: &str,
@@ -207,11 +259,38 @@ impl<Repo: ApiDefinitionRepo + Sync> ApiDefinitionRepo for LoggedApiDefinitionRe
         Self::logged_with_id("get_draft", namespace, id, result)
     }
 
-    async fn delete(&self, namespace: &str, id: &str, version: &str) -> Result<bool, RepoError> {
-        let result = self.repo.delete(namespace, id, version).await;
+    async fn delete(
+        &self,
+        namespace: &str,
+        id: &str,
+        version: &str,
+        deleted_by: &str,
+    ) -> Result<bool, RepoError> {
+        let result = self.repo.delete(namespace, id, version, deleted_by).await;
         Self::logged_with_id("delete", namespace, id, result)
     }
 
+    async fn restore(&self, namespace: &str, id: &str, version: &str) -> Result<bool, RepoError> {
+        let result = self.repo.restore(namespace, id, version).await;
+        Self::logged_with_id("restore", namespace, id, result)
+    }
+
+    async fn get_deleted(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<DeletedApiDefinitionRecord>, RepoError> {
+        let result = self.repo.get_deleted(namespace).await;
+        Self::logged_with_id("get_deleted", namespace, "*", result)
+    }
+
+    async fn purge_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepoError> {
+        let result = self.repo.purge_deleted_before(deleted_before).await;
+        Self::logged_with_id("purge_deleted_before", "*", "*", result)
+    }
+
     async fn get_all(&self, namespace: &str) -> Result<Vec<ApiDefinitionRecord>, RepoError> {
         let result = self.repo.get_all(namespace).await;
         Self::logged_with_id("get_all", namespace, "*", result)
@@ -241,6 +320,21 @@ impl<DB: Database> DbApiDefinitionRepo<DB> {
 #[async_trait]
 impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
     async fn create(&self, definition: &ApiDefinitionRecord) -> Result<(), RepoError> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        // A definition in the trash does not reserve its id and version
+        sqlx::query(
+            r#"
+              DELETE FROM api_definitions
+              WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NOT NULL
+               "#,
+        )
+        .bind(definition.namespace.clone())
+        .bind(definition.id.clone())
+        .bind(definition.version.clone())
+        .execute(&mut *transaction)
+        .await?;
+
         sqlx::query(
             r#"
               INSERT INTO api_definitions
@@ -255,9 +349,11 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         .bind(definition.draft)
         .bind(definition.data.clone())
         .bind(definition.created_at)
-        .execute(self.db_pool.deref())
+        .execute(&mut *transaction)
         .await?;
 
+        transaction.commit().await?;
+
         Ok(())
     }
 
@@ -266,7 +362,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
             r#"
               UPDATE api_definitions
               SET draft = $4, data = $5
-              WHERE namespace = $1 AND id = $2 AND version = $3
+              WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NULL
                "#,
         )
         .bind(definition.namespace.clone())
@@ -281,6 +377,32 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         Ok(())
     }
 
+    async fn update_if_unchanged(
+        &self,
+        definition: &ApiDefinitionRecord,
+        expected: &ApiDefinitionRecord,
+    ) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+              UPDATE api_definitions
+              SET draft = $4, data = $5
+              WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NULL
+                    AND draft = $6 AND data = $7
+               "#,
+        )
+        .bind(definition.namespace.clone())
+        .bind(definition.id.clone())
+        .bind(definition.version.clone())
+        .bind(definition.draft)
+        .bind(definition.data.clone())
+        .bind(expected.draft)
+        .bind(expected.data.clone())
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn set_draft(
         &self,
         namespace: &str,
@@ -292,7 +414,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
             r#"
               UPDATE api_definitions
               SET draft = $4
-              WHERE namespace = $1 AND id = $2 AND version = $3
+              WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NULL
                "#,
         )
         .bind(namespace)
@@ -312,7 +434,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         id: &str,
         version: &str,
     ) -> Result<Option<ApiDefinitionRecord>, RepoError> {
-        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at::timestamptz FROM api_definitions WHERE namespace = $1 AND id = $2 AND version = $3")
+        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at::timestamptz FROM api_definitions WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NULL")
             .bind(namespace)
             .bind(id)
             .bind(version)
@@ -328,7 +450,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         id: &str,
         version: &str,
     ) -> Result<Option<ApiDefinitionRecord>, RepoError> {
-        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at FROM api_definitions WHERE namespace = $1 AND id = $2 AND version = $3")
+        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at FROM api_definitions WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NULL")
             .bind(namespace)
             .bind(id)
             .bind(version)
@@ -344,7 +466,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         version: &str,
     ) -> Result<Option<bool>, RepoError> {
         let result = sqlx::query(
-            "SELECT draft FROM api_definitions WHERE namespace = $1 AND id = $2 AND version = $3",
+            "SELECT draft FROM api_definitions WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NULL",
         )
         .bind(namespace)
         .bind(id)
@@ -356,9 +478,38 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         Ok(draft)
     }
 
-    async fn delete(&self, namespace: &str, id: &str, version: &str) -> Result<bool, RepoError> {
+    async fn delete(
+        &self,
+        namespace: &str,
+        id: &str,
+        version: &str,
+        deleted_by: &str,
+    ) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+              UPDATE api_definitions
+              SET deleted_at = $4, deleted_by = $5
+              WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NULL
+               "#,
+        )
+        .bind(namespace)
+        .bind(id)
+        .bind(version)
+        .bind(chrono::Utc::now())
+        .bind(deleted_by)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn restore(&self, namespace: &str, id: &str, version: &str) -> Result<bool, RepoError> {
         let result = sqlx::query(
-            "DELETE FROM api_definitions WHERE namespace = $1 AND id = $2 AND version = $3",
+            r#"
+              UPDATE api_definitions
+              SET deleted_at = NULL, deleted_by = NULL
+              WHERE namespace = $1 AND id = $2 AND version = $3 AND deleted_at IS NOT NULL
+               "#,
         )
         .bind(namespace)
         .bind(id)
@@ -369,13 +520,55 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         Ok(result.rows_affected() > 0)
     }
 
+    #[when(sqlx::Postgres -> get_deleted)]
+    async fn get_deleted_postgres(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<DeletedApiDefinitionRecord>, RepoError> {
+        sqlx::query_as::<_, DeletedApiDefinitionRecord>(
+            "SELECT namespace, id, version, deleted_at::timestamptz AS deleted_at, deleted_by FROM api_definitions WHERE namespace = $1 AND deleted_at IS NOT NULL ORDER BY deleted_at",
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_deleted)]
+    async fn get_deleted_sqlite(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<DeletedApiDefinitionRecord>, RepoError> {
+        sqlx::query_as::<_, DeletedApiDefinitionRecord>(
+            "SELECT namespace, id, version, deleted_at, deleted_by FROM api_definitions WHERE namespace = $1 AND deleted_at IS NOT NULL ORDER BY deleted_at",
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn purge_deleted_before(
+        &self,
+        deleted_before: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, RepoError> {
+        let result = sqlx::query(
+            "DELETE FROM api_definitions WHERE deleted_at IS NOT NULL AND deleted_at < $1",
+        )
+        .bind(deleted_before)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     #[when(sqlx::Postgres -> get_all)]
     async fn get_all_postgres(
         &self,
         namespace: &str,
     ) -> Result<Vec<ApiDefinitionRecord>, RepoError> {
         sqlx::query_as::<_, ApiDefinitionRecord>(
-            "SELECT namespace, id, version, draft, data, created_at::timestamptz FROM api_definitions WHERE namespace = $1 ORDER BY namespace, id, version",
+            "SELECT namespace, id, version, draft, data, created_at::timestamptz FROM api_definitions WHERE namespace = $1 AND deleted_at IS NULL ORDER BY namespace, id, version",
         )
         .bind(namespace)
         .fetch_all(self.db_pool.deref())
@@ -386,7 +579,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
     #[when(sqlx::Sqlite -> get_all)]
     async fn get_all_sqlite(&self, namespace: &str) -> Result<Vec<ApiDefinitionRecord>, RepoError> {
         sqlx::query_as::<_, ApiDefinitionRecord>(
-            "SELECT namespace, id, version, draft, data, created_at FROM api_definitions WHERE namespace = $1 ORDER BY namespace, id, version",
+            "SELECT namespace, id, version, draft, data, created_at FROM api_definitions WHERE namespace = $1 AND deleted_at IS NULL ORDER BY namespace, id, version",
         )
             .bind(namespace)
             .fetch_all(self.db_pool.deref())
@@ -400,7 +593,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         namespace: &str,
         id: &str,
     ) -> Result<Vec<ApiDefinitionRecord>, RepoError> {
-        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at::timestamptz FROM api_definitions WHERE namespace = $1 AND id = $2 ORDER BY version")
+        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at::timestamptz FROM api_definitions WHERE namespace = $1 AND id = $2 AND deleted_at IS NULL ORDER BY version")
             .bind(namespace)
             .bind(id)
             .fetch_all(self.db_pool.deref())
@@ -414,7 +607,7 @@ impl ApiDefinitionRepo for DbApiDefinitionRepo<sqlx::Postgres> {
         namespace: &str,
         id: &str,
     ) -> Result<Vec<ApiDefinitionRecord>, RepoError> {
-        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at FROM api_definitions WHERE namespace = $1 AND id = $2 ORDER BY version")
+        sqlx::query_as::<_, ApiDefinitionRecord>("SELECT namespace, id, version, draft, data, created_at FROM api_definitions WHERE namespace = $1 AND id = $2 AND deleted_at IS NULL ORDER BY version")
             .bind(namespace)
             .bind(id)
             .fetch_all(self.db_pool.deref())
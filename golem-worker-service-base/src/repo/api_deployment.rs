@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::gateway_api_deployment::ApiSite;
+use crate::gateway_api_deployment::{
+    ApiDeploymentClientCertificates, ApiDeploymentDeadLetterQueue, ApiDeploymentErrorPages,
+    ApiDeploymentRequestFixtures, ApiSite, ApiSiteString,
+};
 use crate::repo::api_definition::ApiDefinitionRecord;
 use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
 use async_trait::async_trait;
@@ -33,6 +36,16 @@ pub struct ApiDeploymentRecord {
     pub definition_id: String,
     pub definition_version: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    // The CORS configuration of the deployment as JSON, the same for every record of the site
+    pub cors: Option<String>,
+    // The traffic split of the deployment as JSON, the same for every record of the site
+    pub traffic_split: Option<String>,
+    // The experiments of the deployment as JSON, the same for every record of the site
+    pub experiments: Option<String>,
+    // The invocation capacity of the deployment as JSON, the same for every record of the site
+    pub capacity: Option<String>,
+    // The response compression of the deployment as JSON, the same for every record of the site
+    pub compression: Option<String>,
 }
 
 impl ApiDeploymentRecord {
@@ -41,6 +54,11 @@ impl ApiDeploymentRecord {
         site: ApiSite,
         definition_id: ApiDefinitionIdWithVersion,
         created_at: chrono::DateTime<chrono::Utc>,
+        cors: Option<String>,
+        traffic_split: Option<String>,
+        experiments: Option<String>,
+        capacity: Option<String>,
+        compression: Option<String>,
     ) -> Self {
         Self {
             namespace: namespace.to_string(),
@@ -50,16 +68,189 @@ impl ApiDeploymentRecord {
             definition_id: definition_id.id.0,
             definition_version: definition_id.version.0,
             created_at,
+            cors,
+            traffic_split,
+            experiments,
+            capacity,
+            compression,
         }
     }
 }
 
+// The columns of a deployment record which make up the state of its site, used to detect
+// concurrent changes of the site
+type DeploymentStateKey = (
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
+
+impl ApiDeploymentRecord {
+    fn state_key(self) -> DeploymentStateKey {
+        (
+            self.namespace,
+            self.definition_id,
+            self.definition_version,
+            self.cors,
+            self.traffic_split,
+            self.experiments,
+            self.capacity,
+            self.compression,
+        )
+    }
+}
+
+// The error pages of a site, stored as JSON
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ApiDeploymentErrorPagesRecord {
+    pub namespace: String,
+    pub site: String,
+    pub error_pages: String,
+}
+
+impl ApiDeploymentErrorPagesRecord {
+    pub fn new<Namespace: Display>(
+        namespace: Namespace,
+        site: &ApiSiteString,
+        error_pages: &ApiDeploymentErrorPages,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            namespace: namespace.to_string(),
+            site: site.to_string(),
+            error_pages: serde_json::to_string(error_pages).map_err(|err| err.to_string())?,
+        })
+    }
+}
+
+impl TryFrom<ApiDeploymentErrorPagesRecord> for ApiDeploymentErrorPages {
+    type Error = String;
+
+    fn try_from(value: ApiDeploymentErrorPagesRecord) -> Result<Self, Self::Error> {
+        serde_json::from_str(&value.error_pages).map_err(|err| err.to_string())
+    }
+}
+
+// The client certificate authentication of a site, stored as JSON
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ApiDeploymentClientCertificatesRecord {
+    pub namespace: String,
+    pub site: String,
+    pub client_certificates: String,
+}
+
+impl ApiDeploymentClientCertificatesRecord {
+    pub fn new<Namespace: Display>(
+        namespace: Namespace,
+        site: &ApiSiteString,
+        client_certificates: &ApiDeploymentClientCertificates,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            namespace: namespace.to_string(),
+            site: site.to_string(),
+            client_certificates: serde_json::to_string(client_certificates)
+                .map_err(|err| err.to_string())?,
+        })
+    }
+}
+
+impl TryFrom<ApiDeploymentClientCertificatesRecord> for ApiDeploymentClientCertificates {
+    type Error = String;
+
+    fn try_from(value: ApiDeploymentClientCertificatesRecord) -> Result<Self, Self::Error> {
+        serde_json::from_str(&value.client_certificates).map_err(|err| err.to_string())
+    }
+}
+
+// The dead-letter queue of a site, stored as JSON
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ApiDeploymentDeadLetterQueueRecord {
+    pub namespace: String,
+    pub site: String,
+    pub dead_letter_queue: String,
+}
+
+impl ApiDeploymentDeadLetterQueueRecord {
+    pub fn new<Namespace: Display>(
+        namespace: Namespace,
+        site: &ApiSiteString,
+        dead_letter_queue: &ApiDeploymentDeadLetterQueue,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            namespace: namespace.to_string(),
+            site: site.to_string(),
+            dead_letter_queue: serde_json::to_string(dead_letter_queue)
+                .map_err(|err| err.to_string())?,
+        })
+    }
+}
+
+impl TryFrom<ApiDeploymentDeadLetterQueueRecord> for ApiDeploymentDeadLetterQueue {
+    type Error = String;
+
+    fn try_from(value: ApiDeploymentDeadLetterQueueRecord) -> Result<Self, Self::Error> {
+        serde_json::from_str(&value.dead_letter_queue).map_err(|err| err.to_string())
+    }
+}
+
+// The request fixture recording of a site, stored as JSON
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ApiDeploymentRequestFixturesRecord {
+    pub namespace: String,
+    pub site: String,
+    pub request_fixtures: String,
+}
+
+impl ApiDeploymentRequestFixturesRecord {
+    pub fn new<Namespace: Display>(
+        namespace: Namespace,
+        site: &ApiSiteString,
+        request_fixtures: &ApiDeploymentRequestFixtures,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            namespace: namespace.to_string(),
+            site: site.to_string(),
+            request_fixtures: serde_json::to_string(request_fixtures)
+                .map_err(|err| err.to_string())?,
+        })
+    }
+}
+
+impl TryFrom<ApiDeploymentRequestFixturesRecord> for ApiDeploymentRequestFixtures {
+    type Error = String;
+
+    fn try_from(value: ApiDeploymentRequestFixturesRecord) -> Result<Self, Self::Error> {
+        serde_json::from_str(&value.request_fixtures).map_err(|err| err.to_string())
+    }
+}
+
 #[async_trait]
 pub trait ApiDeploymentRepo {
     async fn create(&self, deployments: Vec<ApiDeploymentRecord>) -> Result<(), RepoError>;
 
     async fn delete(&self, deployments: Vec<ApiDeploymentRecord>) -> Result<bool, RepoError>;
 
+    /// Deletes and creates deployment records in a single transaction, so that the gateways
+    /// never observe a partially applied change
+    async fn replace(
+        &self,
+        removed: Vec<ApiDeploymentRecord>,
+        added: Vec<ApiDeploymentRecord>,
+    ) -> Result<(), RepoError>;
+
+    /// Creates deployment records only if the records of the site are still the expected ones,
+    /// checked in the same transaction. Returns false, without creating any, if they changed
+    async fn create_if_unchanged(
+        &self,
+        site: &str,
+        expected: Vec<ApiDeploymentRecord>,
+        deployments: Vec<ApiDeploymentRecord>,
+    ) -> Result<bool, RepoError>;
+
     async fn get_all(&self, namespace: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError>;
 
     async fn get_by_id(
@@ -77,6 +268,28 @@ pub trait ApiDeploymentRepo {
 
     async fn get_by_site(&self, site: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError>;
 
+    async fn set_cors(&self, site: &str, cors: Option<String>) -> Result<(), RepoError>;
+
+    async fn set_traffic_split(
+        &self,
+        site: &str,
+        traffic_split: Option<String>,
+    ) -> Result<(), RepoError>;
+
+    async fn set_experiments(
+        &self,
+        site: &str,
+        experiments: Option<String>,
+    ) -> Result<(), RepoError>;
+
+    async fn set_capacity(&self, site: &str, capacity: Option<String>) -> Result<(), RepoError>;
+
+    async fn set_compression(
+        &self,
+        site: &str,
+        compression: Option<String>,
+    ) -> Result<(), RepoError>;
+
     async fn get_definitions_by_site(
         &self,
         namespace: &str,
@@ -87,6 +300,52 @@ pub trait ApiDeploymentRepo {
         &self,
         site: &str,
     ) -> Result<Vec<ApiDefinitionRecord>, RepoError>;
+
+    async fn get_error_pages(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentErrorPagesRecord>, RepoError>;
+
+    async fn set_error_pages(&self, record: ApiDeploymentErrorPagesRecord)
+        -> Result<(), RepoError>;
+
+    async fn delete_error_pages(&self, site: &str) -> Result<bool, RepoError>;
+
+    async fn get_client_certificates(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentClientCertificatesRecord>, RepoError>;
+
+    async fn set_client_certificates(
+        &self,
+        record: ApiDeploymentClientCertificatesRecord,
+    ) -> Result<(), RepoError>;
+
+    async fn delete_client_certificates(&self, site: &str) -> Result<bool, RepoError>;
+
+    async fn get_dead_letter_queue(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentDeadLetterQueueRecord>, RepoError>;
+
+    async fn set_dead_letter_queue(
+        &self,
+        record: ApiDeploymentDeadLetterQueueRecord,
+    ) -> Result<(), RepoError>;
+
+    async fn delete_dead_letter_queue(&self, site: &str) -> Result<bool, RepoError>;
+
+    async fn get_request_fixtures(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentRequestFixturesRecord>, RepoError>;
+
+    async fn set_request_fixtures(
+        &self,
+        record: ApiDeploymentRequestFixturesRecord,
+    ) -> Result<(), RepoError>;
+
+    async fn delete_request_fixtures(&self, site: &str) -> Result<bool, RepoError>;
 }
 
 pub struct LoggedDeploymentRepo<Repo: ApiDeploymentRepo> {
@@ -142,6 +401,28 @@ impl<Repo: ApiDeploymentRepo + Sync> ApiDeploymentRepo for LoggedDeploymentRepo<
         Self::logged("delete", result)
     }
 
+    async fn replace(
+        &self,
+        removed: Vec<ApiDeploymentRecord>,
+        added: Vec<ApiDeploymentRecord>,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.replace(removed, added).await;
+        Self::logged("replace", result)
+    }
+
+    async fn create_if_unchanged(
+        &self,
+        site: &str,
+        expected: Vec<ApiDeploymentRecord>,
+        deployments: Vec<ApiDeploymentRecord>,
+    ) -> Result<bool, RepoError> {
+        let result = self
+            .repo
+            .create_if_unchanged(site, expected, deployments)
+            .await;
+        Self::logged("create_if_unchanged", result)
+    }
+
     async fn get_by_id(
         &self,
         namespace: &str,
@@ -174,6 +455,43 @@ impl<Repo: ApiDeploymentRepo + Sync> ApiDeploymentRepo for LoggedDeploymentRepo<
         Self::logged("get_by_site", result)
     }
 
+    async fn set_cors(&self, site: &str, cors: Option<String>) -> Result<(), RepoError> {
+        let result = self.repo.set_cors(site, cors).await;
+        Self::logged("set_cors", result)
+    }
+
+    async fn set_traffic_split(
+        &self,
+        site: &str,
+        traffic_split: Option<String>,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.set_traffic_split(site, traffic_split).await;
+        Self::logged("set_traffic_split", result)
+    }
+
+    async fn set_experiments(
+        &self,
+        site: &str,
+        experiments: Option<String>,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.set_experiments(site, experiments).await;
+        Self::logged("set_experiments", result)
+    }
+
+    async fn set_capacity(&self, site: &str, capacity: Option<String>) -> Result<(), RepoError> {
+        let result = self.repo.set_capacity(site, capacity).await;
+        Self::logged("set_capacity", result)
+    }
+
+    async fn set_compression(
+        &self,
+        site: &str,
+        compression: Option<String>,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.set_compression(site, compression).await;
+        Self::logged("set_compression", result)
+    }
+
     async fn get_definitions_by_site(
         &self,
         namespace: &str,
@@ -190,6 +508,90 @@ impl<Repo: ApiDeploymentRepo + Sync> ApiDeploymentRepo for LoggedDeploymentRepo<
         let result = self.repo.get_all_definitions_by_site(site).await;
         Self::logged("get_all_definitions_by_site", result)
     }
+
+    async fn get_error_pages(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentErrorPagesRecord>, RepoError> {
+        let result = self.repo.get_error_pages(site).await;
+        Self::logged("get_error_pages", result)
+    }
+
+    async fn set_error_pages(
+        &self,
+        record: ApiDeploymentErrorPagesRecord,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.set_error_pages(record).await;
+        Self::logged("set_error_pages", result)
+    }
+
+    async fn delete_error_pages(&self, site: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete_error_pages(site).await;
+        Self::logged("delete_error_pages", result)
+    }
+
+    async fn get_client_certificates(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentClientCertificatesRecord>, RepoError> {
+        let result = self.repo.get_client_certificates(site).await;
+        Self::logged("get_client_certificates", result)
+    }
+
+    async fn set_client_certificates(
+        &self,
+        record: ApiDeploymentClientCertificatesRecord,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.set_client_certificates(record).await;
+        Self::logged("set_client_certificates", result)
+    }
+
+    async fn delete_client_certificates(&self, site: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete_client_certificates(site).await;
+        Self::logged("delete_client_certificates", result)
+    }
+
+    async fn get_dead_letter_queue(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentDeadLetterQueueRecord>, RepoError> {
+        let result = self.repo.get_dead_letter_queue(site).await;
+        Self::logged("get_dead_letter_queue", result)
+    }
+
+    async fn set_dead_letter_queue(
+        &self,
+        record: ApiDeploymentDeadLetterQueueRecord,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.set_dead_letter_queue(record).await;
+        Self::logged("set_dead_letter_queue", result)
+    }
+
+    async fn delete_dead_letter_queue(&self, site: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete_dead_letter_queue(site).await;
+        Self::logged("delete_dead_letter_queue", result)
+    }
+
+    async fn get_request_fixtures(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentRequestFixturesRecord>, RepoError> {
+        let result = self.repo.get_request_fixtures(site).await;
+        Self::logged("get_request_fixtures", result)
+    }
+
+    async fn set_request_fixtures(
+        &self,
+        record: ApiDeploymentRequestFixturesRecord,
+    ) -> Result<(), RepoError> {
+        let result = self.repo.set_request_fixtures(record).await;
+        Self::logged("set_request_fixtures", result)
+    }
+
+    async fn delete_request_fixtures(&self, site: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete_request_fixtures(site).await;
+        Self::logged("delete_request_fixtures", result)
+    }
 }
 
 pub struct DbApiDeploymentRepo<DB: Database> {
@@ -212,9 +614,9 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
                 sqlx::query(
                     r#"
                       INSERT INTO api_deployments
-                        (namespace, site, host, subdomain, definition_id, definition_version, created_at)
+                        (namespace, site, host, subdomain, definition_id, definition_version, created_at, cors, traffic_split, experiments, capacity, compression)
                       VALUES
-                        ($1, $2, $3, $4, $5, $6, $7)
+                        ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
                        "#,
                 )
                 .bind(deployment.namespace.clone())
@@ -224,6 +626,11 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
                 .bind(deployment.definition_id.clone())
                 .bind(deployment.definition_version.clone())
                 .bind(deployment.created_at)
+                .bind(deployment.cors.clone())
+                .bind(deployment.traffic_split.clone())
+                .bind(deployment.experiments.clone())
+                .bind(deployment.capacity.clone())
+                .bind(deployment.compression.clone())
                 .execute(&mut *transaction)
                 .await?;
             }
@@ -252,6 +659,124 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
             Ok(false)
         }
     }
+
+    async fn replace(
+        &self,
+        removed: Vec<ApiDeploymentRecord>,
+        added: Vec<ApiDeploymentRecord>,
+    ) -> Result<(), RepoError> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        for deployment in removed {
+            sqlx::query(
+                "DELETE FROM api_deployments WHERE namespace = $1 AND site = $2 AND definition_id = $3 AND definition_version = $4",
+            )
+            .bind(deployment.namespace)
+            .bind(deployment.site)
+            .bind(deployment.definition_id)
+            .bind(deployment.definition_version)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        for deployment in added {
+            sqlx::query(
+                r#"
+                  INSERT INTO api_deployments
+                    (namespace, site, host, subdomain, definition_id, definition_version, created_at, cors, traffic_split, experiments, capacity, compression)
+                  VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                   "#,
+            )
+            .bind(deployment.namespace)
+            .bind(deployment.site)
+            .bind(deployment.host)
+            .bind(deployment.subdomain)
+            .bind(deployment.definition_id)
+            .bind(deployment.definition_version)
+            .bind(deployment.created_at)
+            .bind(deployment.cors)
+            .bind(deployment.traffic_split)
+            .bind(deployment.experiments)
+            .bind(deployment.capacity)
+            .bind(deployment.compression)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn create_if_unchanged(
+        &self,
+        site: &str,
+        expected: Vec<ApiDeploymentRecord>,
+        deployments: Vec<ApiDeploymentRecord>,
+    ) -> Result<bool, RepoError> {
+        let mut transaction = self.db_pool.begin().await?;
+
+        // Locks the records of the site until the end of the transaction
+        sqlx::query("UPDATE api_deployments SET site = site WHERE site = $1")
+            .bind(site)
+            .execute(&mut *transaction)
+            .await?;
+
+        let mut current = sqlx::query_as::<_, DeploymentStateKey>(
+            r#"
+              SELECT namespace, definition_id, definition_version, cors, traffic_split, experiments, capacity, compression
+              FROM api_deployments
+              WHERE site = $1
+               "#,
+        )
+        .bind(site)
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        let mut expected = expected
+            .into_iter()
+            .map(ApiDeploymentRecord::state_key)
+            .collect::<Vec<_>>();
+
+        current.sort();
+        expected.sort();
+
+        if current != expected {
+            transaction.rollback().await?;
+            return Ok(false);
+        }
+
+        for deployment in deployments {
+            sqlx::query(
+                r#"
+                  INSERT INTO api_deployments
+                    (namespace, site, host, subdomain, definition_id, definition_version, created_at, cors, traffic_split, experiments, capacity, compression)
+                  VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                   "#,
+            )
+            .bind(deployment.namespace)
+            .bind(deployment.site)
+            .bind(deployment.host)
+            .bind(deployment.subdomain)
+            .bind(deployment.definition_id)
+            .bind(deployment.definition_version)
+            .bind(deployment.created_at)
+            .bind(deployment.cors)
+            .bind(deployment.traffic_split)
+            .bind(deployment.experiments)
+            .bind(deployment.capacity)
+            .bind(deployment.compression)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(true)
+    }
+
     #[when(sqlx::Postgres -> get_all)]
     async fn get_all_postgres(
         &self,
@@ -259,7 +784,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE namespace = $1
                 ORDER BY site, host, subdomain, definition_id, definition_version
@@ -274,7 +799,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     async fn get_all_sqlite(&self, namespace: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE namespace = $1
                 ORDER BY site, host, subdomain, definition_id, definition_version
@@ -294,7 +819,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2
                 ORDER BY site, host, subdomain, definition_version
@@ -315,7 +840,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2
                 ORDER BY site, host, subdomain, definition_version
@@ -337,7 +862,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2 AND definition_version = $3
                 ORDER BY site, host, subdomain
@@ -360,7 +885,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE namespace = $1 AND definition_id = $2 AND definition_version = $3
                 ORDER BY site, host, subdomain
@@ -381,7 +906,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     ) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at::timestamptz, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE site = $1
                 ORDER BY namespace, host, subdomain, definition_id, definition_version
@@ -397,7 +922,7 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
     async fn get_by_site_sqlite(&self, site: &str) -> Result<Vec<ApiDeploymentRecord>, RepoError> {
         sqlx::query_as::<_, ApiDeploymentRecord>(
             r#"
-                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at
+                SELECT namespace, site, host, subdomain, definition_id, definition_version, created_at, cors, traffic_split, experiments, capacity, compression
                 FROM api_deployments
                 WHERE site = $1
                 ORDER BY namespace, host, subdomain, definition_id, definition_version
@@ -492,4 +1017,262 @@ impl ApiDeploymentRepo for DbApiDeploymentRepo<sqlx::Postgres> {
             .await
             .map_err(|e| e.into())
     }
+
+    async fn set_cors(&self, site: &str, cors: Option<String>) -> Result<(), RepoError> {
+        sqlx::query("UPDATE api_deployments SET cors = $1 WHERE site = $2")
+            .bind(cors)
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_traffic_split(
+        &self,
+        site: &str,
+        traffic_split: Option<String>,
+    ) -> Result<(), RepoError> {
+        sqlx::query("UPDATE api_deployments SET traffic_split = $1 WHERE site = $2")
+            .bind(traffic_split)
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_experiments(
+        &self,
+        site: &str,
+        experiments: Option<String>,
+    ) -> Result<(), RepoError> {
+        sqlx::query("UPDATE api_deployments SET experiments = $1 WHERE site = $2")
+            .bind(experiments)
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_capacity(&self, site: &str, capacity: Option<String>) -> Result<(), RepoError> {
+        sqlx::query("UPDATE api_deployments SET capacity = $1 WHERE site = $2")
+            .bind(capacity)
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_compression(
+        &self,
+        site: &str,
+        compression: Option<String>,
+    ) -> Result<(), RepoError> {
+        sqlx::query("UPDATE api_deployments SET compression = $1 WHERE site = $2")
+            .bind(compression)
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_error_pages(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentErrorPagesRecord>, RepoError> {
+        sqlx::query_as::<_, ApiDeploymentErrorPagesRecord>(
+            r#"
+                SELECT namespace, site, error_pages
+                FROM api_deployment_error_pages
+                WHERE site = $1
+                "#,
+        )
+        .bind(site)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn set_error_pages(
+        &self,
+        record: ApiDeploymentErrorPagesRecord,
+    ) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO api_deployment_error_pages
+                (namespace, site, error_pages)
+              VALUES
+                ($1, $2, $3)
+              ON CONFLICT (site) DO UPDATE
+                SET namespace = excluded.namespace, error_pages = excluded.error_pages
+               "#,
+        )
+        .bind(record.namespace)
+        .bind(record.site)
+        .bind(record.error_pages)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_error_pages(&self, site: &str) -> Result<bool, RepoError> {
+        let result = sqlx::query("DELETE FROM api_deployment_error_pages WHERE site = $1")
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_client_certificates(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentClientCertificatesRecord>, RepoError> {
+        sqlx::query_as::<_, ApiDeploymentClientCertificatesRecord>(
+            r#"
+                SELECT namespace, site, client_certificates
+                FROM api_deployment_client_certificates
+                WHERE site = $1
+                "#,
+        )
+        .bind(site)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn set_client_certificates(
+        &self,
+        record: ApiDeploymentClientCertificatesRecord,
+    ) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO api_deployment_client_certificates
+                (namespace, site, client_certificates)
+              VALUES
+                ($1, $2, $3)
+              ON CONFLICT (site) DO UPDATE
+                SET namespace = excluded.namespace, client_certificates = excluded.client_certificates
+               "#,
+        )
+        .bind(record.namespace)
+        .bind(record.site)
+        .bind(record.client_certificates)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_client_certificates(&self, site: &str) -> Result<bool, RepoError> {
+        let result = sqlx::query("DELETE FROM api_deployment_client_certificates WHERE site = $1")
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_dead_letter_queue(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentDeadLetterQueueRecord>, RepoError> {
+        sqlx::query_as::<_, ApiDeploymentDeadLetterQueueRecord>(
+            r#"
+                SELECT namespace, site, dead_letter_queue
+                FROM api_deployment_dead_letter_queues
+                WHERE site = $1
+                "#,
+        )
+        .bind(site)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn set_dead_letter_queue(
+        &self,
+        record: ApiDeploymentDeadLetterQueueRecord,
+    ) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO api_deployment_dead_letter_queues
+                (namespace, site, dead_letter_queue)
+              VALUES
+                ($1, $2, $3)
+              ON CONFLICT (site) DO UPDATE
+                SET namespace = excluded.namespace, dead_letter_queue = excluded.dead_letter_queue
+               "#,
+        )
+        .bind(record.namespace)
+        .bind(record.site)
+        .bind(record.dead_letter_queue)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_dead_letter_queue(&self, site: &str) -> Result<bool, RepoError> {
+        let result = sqlx::query("DELETE FROM api_deployment_dead_letter_queues WHERE site = $1")
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn get_request_fixtures(
+        &self,
+        site: &str,
+    ) -> Result<Option<ApiDeploymentRequestFixturesRecord>, RepoError> {
+        sqlx::query_as::<_, ApiDeploymentRequestFixturesRecord>(
+            r#"
+                SELECT namespace, site, request_fixtures
+                FROM api_deployment_request_fixtures
+                WHERE site = $1
+                "#,
+        )
+        .bind(site)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn set_request_fixtures(
+        &self,
+        record: ApiDeploymentRequestFixturesRecord,
+    ) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO api_deployment_request_fixtures
+                (namespace, site, request_fixtures)
+              VALUES
+                ($1, $2, $3)
+              ON CONFLICT (site) DO UPDATE
+                SET namespace = excluded.namespace, request_fixtures = excluded.request_fixtures
+               "#,
+        )
+        .bind(record.namespace)
+        .bind(record.site)
+        .bind(record.request_fixtures)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_request_fixtures(&self, site: &str) -> Result<bool, RepoError> {
+        let result = sqlx::query("DELETE FROM api_deployment_request_fixtures WHERE site = $1")
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
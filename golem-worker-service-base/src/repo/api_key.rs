@@ -0,0 +1,356 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_security::{ApiKey, HashedApiKeySecret};
+use async_trait::async_trait;
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool};
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+// Only a salted hash of the secret of a key is stored, so a leaked database does not leak the keys
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct ApiKeyRecord {
+    pub namespace: String,
+    pub security_scheme_id: String,
+    pub key_id: String,
+    pub name: String,
+    pub metadata: String,
+    pub salt: Vec<u8>,
+    pub key_hash: Vec<u8>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl ApiKeyRecord {
+    pub fn new(
+        namespace: String,
+        security_scheme_id: String,
+        api_key: &ApiKey,
+        hashed_secret: HashedApiKeySecret,
+    ) -> Result<Self, String> {
+        let metadata = serde_json::to_string(&api_key.metadata).map_err(|err| err.to_string())?;
+
+        Ok(ApiKeyRecord {
+            namespace,
+            security_scheme_id,
+            key_id: api_key.key_id.clone(),
+            name: api_key.name.clone(),
+            metadata,
+            salt: hashed_secret.salt,
+            key_hash: hashed_secret.hash,
+            created_at: api_key.created_at,
+        })
+    }
+
+    pub fn hashed_secret(&self) -> HashedApiKeySecret {
+        HashedApiKeySecret {
+            salt: self.salt.clone(),
+            hash: self.key_hash.clone(),
+        }
+    }
+}
+
+impl TryFrom<ApiKeyRecord> for ApiKey {
+    type Error = String;
+
+    fn try_from(value: ApiKeyRecord) -> Result<Self, Self::Error> {
+        let metadata: BTreeMap<String, String> =
+            serde_json::from_str(&value.metadata).map_err(|err| err.to_string())?;
+
+        Ok(ApiKey {
+            key_id: value.key_id,
+            name: value.name,
+            metadata,
+            created_at: value.created_at,
+        })
+    }
+}
+
+#[async_trait]
+pub trait ApiKeyRepo {
+    async fn create(&self, api_key: &ApiKeyRecord) -> Result<(), RepoError>;
+
+    async fn get(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+    ) -> Result<Option<ApiKeyRecord>, RepoError>;
+
+    async fn get_all(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+    ) -> Result<Vec<ApiKeyRecord>, RepoError>;
+
+    /// Replaces the secret of a key, returns false if it does not exist
+    async fn update_secret(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+        hashed_secret: &HashedApiKeySecret,
+    ) -> Result<bool, RepoError>;
+
+    /// Deletes a key, returns false if it does not exist
+    async fn delete(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+    ) -> Result<bool, RepoError>;
+}
+
+pub struct DbApiKeyRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbApiKeyRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedApiKeyRepo<Repo: ApiKeyRepo> {
+    repo: Repo,
+}
+
+impl<Repo: ApiKeyRepo> LoggedApiKeyRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        security_scheme_id: &str,
+        key_id: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(
+                security_scheme_id = security_scheme_id,
+                key_id = key_id,
+                "{}",
+                message
+            ),
+            Err(error) => error!(
+                security_scheme_id = security_scheme_id,
+                key_id = key_id,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: ApiKeyRepo + Send + Sync> ApiKeyRepo for LoggedApiKeyRepo<Repo> {
+    async fn create(&self, api_key: &ApiKeyRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(api_key).await;
+        Self::logged_with_id(
+            "create",
+            &api_key.security_scheme_id,
+            Some(&api_key.key_id),
+            result,
+        )
+    }
+
+    async fn get(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+    ) -> Result<Option<ApiKeyRecord>, RepoError> {
+        let result = self.repo.get(namespace, security_scheme_id, key_id).await;
+        Self::logged_with_id("get", security_scheme_id, Some(key_id), result)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+    ) -> Result<Vec<ApiKeyRecord>, RepoError> {
+        let result = self.repo.get_all(namespace, security_scheme_id).await;
+        Self::logged_with_id("get_all", security_scheme_id, None, result)
+    }
+
+    async fn update_secret(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+        hashed_secret: &HashedApiKeySecret,
+    ) -> Result<bool, RepoError> {
+        let result = self
+            .repo
+            .update_secret(namespace, security_scheme_id, key_id, hashed_secret)
+            .await;
+        Self::logged_with_id("update_secret", security_scheme_id, Some(key_id), result)
+    }
+
+    async fn delete(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+    ) -> Result<bool, RepoError> {
+        let result = self
+            .repo
+            .delete(namespace, security_scheme_id, key_id)
+            .await;
+        Self::logged_with_id("delete", security_scheme_id, Some(key_id), result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl ApiKeyRepo for DbApiKeyRepo<sqlx::Postgres> {
+    async fn create(&self, api_key: &ApiKeyRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO api_keys
+                (namespace, security_scheme_id, key_id, name, metadata, salt, key_hash, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8)
+               "#,
+        )
+        .bind(api_key.namespace.clone())
+        .bind(api_key.security_scheme_id.clone())
+        .bind(api_key.key_id.clone())
+        .bind(api_key.name.clone())
+        .bind(api_key.metadata.clone())
+        .bind(api_key.salt.clone())
+        .bind(api_key.key_hash.clone())
+        .bind(api_key.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get)]
+    async fn get_postgres(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+    ) -> Result<Option<ApiKeyRecord>, RepoError> {
+        sqlx::query_as::<_, ApiKeyRecord>(
+            "SELECT namespace, security_scheme_id, key_id, name, metadata, salt, key_hash, created_at::timestamptz FROM api_keys WHERE namespace = $1 AND security_scheme_id = $2 AND key_id = $3",
+        )
+        .bind(namespace)
+        .bind(security_scheme_id)
+        .bind(key_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get)]
+    async fn get_sqlite(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+    ) -> Result<Option<ApiKeyRecord>, RepoError> {
+        sqlx::query_as::<_, ApiKeyRecord>(
+            "SELECT namespace, security_scheme_id, key_id, name, metadata, salt, key_hash, created_at FROM api_keys WHERE namespace = $1 AND security_scheme_id = $2 AND key_id = $3",
+        )
+        .bind(namespace)
+        .bind(security_scheme_id)
+        .bind(key_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+    ) -> Result<Vec<ApiKeyRecord>, RepoError> {
+        sqlx::query_as::<_, ApiKeyRecord>(
+            "SELECT namespace, security_scheme_id, key_id, name, metadata, salt, key_hash, created_at::timestamptz FROM api_keys WHERE namespace = $1 AND security_scheme_id = $2 ORDER BY created_at, key_id",
+        )
+        .bind(namespace)
+        .bind(security_scheme_id)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+    ) -> Result<Vec<ApiKeyRecord>, RepoError> {
+        sqlx::query_as::<_, ApiKeyRecord>(
+            "SELECT namespace, security_scheme_id, key_id, name, metadata, salt, key_hash, created_at FROM api_keys WHERE namespace = $1 AND security_scheme_id = $2 ORDER BY created_at, key_id",
+        )
+        .bind(namespace)
+        .bind(security_scheme_id)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn update_secret(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+        hashed_secret: &HashedApiKeySecret,
+    ) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+              UPDATE api_keys
+              SET salt = $4, key_hash = $5
+              WHERE namespace = $1 AND security_scheme_id = $2 AND key_id = $3
+               "#,
+        )
+        .bind(namespace)
+        .bind(security_scheme_id)
+        .bind(key_id)
+        .bind(hashed_secret.salt.clone())
+        .bind(hashed_secret.hash.clone())
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete(
+        &self,
+        namespace: &str,
+        security_scheme_id: &str,
+        key_id: &str,
+    ) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            "DELETE FROM api_keys WHERE namespace = $1 AND security_scheme_id = $2 AND key_id = $3",
+        )
+        .bind(namespace)
+        .bind(security_scheme_id)
+        .bind(key_id)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct DeadLetterRecord {
+    pub namespace: String,
+    pub site: String,
+    pub dead_letter_id: String,
+    pub route: String,
+    pub method: String,
+    pub uri: String,
+    // The JSON array of the stored headers of the request
+    pub headers: String,
+    pub body: Option<Vec<u8>>,
+    pub error: String,
+    pub replay_count: i32,
+    pub last_replayed_at: Option<DateTime<Utc>>,
+    pub last_replay_status: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait DeadLetterRepo {
+    async fn create(&self, dead_letter: &DeadLetterRecord) -> Result<(), RepoError>;
+
+    async fn get(
+        &self,
+        site: &str,
+        dead_letter_id: &str,
+    ) -> Result<Option<DeadLetterRecord>, RepoError>;
+
+    // The dead letters of a site, the oldest first
+    async fn get_all(&self, site: &str) -> Result<Vec<DeadLetterRecord>, RepoError>;
+
+    /// Deletes a dead letter, returns false if it does not exist
+    async fn delete(&self, site: &str, dead_letter_id: &str) -> Result<bool, RepoError>;
+
+    /// Records a failed replay of a dead letter, returns false if it does not exist
+    async fn record_replay(
+        &self,
+        site: &str,
+        dead_letter_id: &str,
+        replayed_at: &DateTime<Utc>,
+        status: i32,
+    ) -> Result<bool, RepoError>;
+}
+
+pub struct DbDeadLetterRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbDeadLetterRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedDeadLetterRepo<Repo: DeadLetterRepo> {
+    repo: Repo,
+}
+
+impl<Repo: DeadLetterRepo> LoggedDeadLetterRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        site: &str,
+        dead_letter_id: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(site = site, dead_letter_id = dead_letter_id, "{}", message),
+            Err(error) => error!(
+                site = site,
+                dead_letter_id = dead_letter_id,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: DeadLetterRepo + Send + Sync> DeadLetterRepo for LoggedDeadLetterRepo<Repo> {
+    async fn create(&self, dead_letter: &DeadLetterRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(dead_letter).await;
+        Self::logged_with_id(
+            "create",
+            &dead_letter.site,
+            Some(&dead_letter.dead_letter_id),
+            result,
+        )
+    }
+
+    async fn get(
+        &self,
+        site: &str,
+        dead_letter_id: &str,
+    ) -> Result<Option<DeadLetterRecord>, RepoError> {
+        let result = self.repo.get(site, dead_letter_id).await;
+        Self::logged_with_id("get", site, Some(dead_letter_id), result)
+    }
+
+    async fn get_all(&self, site: &str) -> Result<Vec<DeadLetterRecord>, RepoError> {
+        let result = self.repo.get_all(site).await;
+        Self::logged_with_id("get_all", site, None, result)
+    }
+
+    async fn delete(&self, site: &str, dead_letter_id: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete(site, dead_letter_id).await;
+        Self::logged_with_id("delete", site, Some(dead_letter_id), result)
+    }
+
+    async fn record_replay(
+        &self,
+        site: &str,
+        dead_letter_id: &str,
+        replayed_at: &DateTime<Utc>,
+        status: i32,
+    ) -> Result<bool, RepoError> {
+        let result = self
+            .repo
+            .record_replay(site, dead_letter_id, replayed_at, status)
+            .await;
+        Self::logged_with_id("record_replay", site, Some(dead_letter_id), result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl DeadLetterRepo for DbDeadLetterRepo<sqlx::Postgres> {
+    async fn create(&self, dead_letter: &DeadLetterRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO gateway_dead_letters
+                (namespace, site, dead_letter_id, route, method, uri, headers, body, error, replay_count, last_replayed_at, last_replay_status, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+               "#,
+        )
+        .bind(dead_letter.namespace.clone())
+        .bind(dead_letter.site.clone())
+        .bind(dead_letter.dead_letter_id.clone())
+        .bind(dead_letter.route.clone())
+        .bind(dead_letter.method.clone())
+        .bind(dead_letter.uri.clone())
+        .bind(dead_letter.headers.clone())
+        .bind(dead_letter.body.clone())
+        .bind(dead_letter.error.clone())
+        .bind(dead_letter.replay_count)
+        .bind(dead_letter.last_replayed_at)
+        .bind(dead_letter.last_replay_status)
+        .bind(dead_letter.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get)]
+    async fn get_postgres(
+        &self,
+        site: &str,
+        dead_letter_id: &str,
+    ) -> Result<Option<DeadLetterRecord>, RepoError> {
+        sqlx::query_as::<_, DeadLetterRecord>(
+            "SELECT namespace, site, dead_letter_id, route, method, uri, headers, body, error, replay_count, last_replayed_at::timestamptz, last_replay_status, created_at::timestamptz FROM gateway_dead_letters WHERE site = $1 AND dead_letter_id = $2",
+        )
+        .bind(site)
+        .bind(dead_letter_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get)]
+    async fn get_sqlite(
+        &self,
+        site: &str,
+        dead_letter_id: &str,
+    ) -> Result<Option<DeadLetterRecord>, RepoError> {
+        sqlx::query_as::<_, DeadLetterRecord>(
+            "SELECT namespace, site, dead_letter_id, route, method, uri, headers, body, error, replay_count, last_replayed_at, last_replay_status, created_at FROM gateway_dead_letters WHERE site = $1 AND dead_letter_id = $2",
+        )
+        .bind(site)
+        .bind(dead_letter_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(&self, site: &str) -> Result<Vec<DeadLetterRecord>, RepoError> {
+        sqlx::query_as::<_, DeadLetterRecord>(
+            "SELECT namespace, site, dead_letter_id, route, method, uri, headers, body, error, replay_count, last_replayed_at::timestamptz, last_replay_status, created_at::timestamptz FROM gateway_dead_letters WHERE site = $1 ORDER BY created_at, dead_letter_id",
+        )
+        .bind(site)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(&self, site: &str) -> Result<Vec<DeadLetterRecord>, RepoError> {
+        sqlx::query_as::<_, DeadLetterRecord>(
+            "SELECT namespace, site, dead_letter_id, route, method, uri, headers, body, error, replay_count, last_replayed_at, last_replay_status, created_at FROM gateway_dead_letters WHERE site = $1 ORDER BY created_at, dead_letter_id",
+        )
+        .bind(site)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn delete(&self, site: &str, dead_letter_id: &str) -> Result<bool, RepoError> {
+        let result =
+            sqlx::query("DELETE FROM gateway_dead_letters WHERE site = $1 AND dead_letter_id = $2")
+                .bind(site)
+                .bind(dead_letter_id)
+                .execute(self.db_pool.deref())
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_replay(
+        &self,
+        site: &str,
+        dead_letter_id: &str,
+        replayed_at: &DateTime<Utc>,
+        status: i32,
+    ) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+              UPDATE gateway_dead_letters
+              SET replay_count = replay_count + 1, last_replayed_at = $3, last_replay_status = $4
+              WHERE site = $1 AND dead_letter_id = $2
+               "#,
+        )
+        .bind(site)
+        .bind(dead_letter_id)
+        .bind(replayed_at)
+        .bind(status)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
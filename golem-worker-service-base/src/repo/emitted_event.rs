@@ -0,0 +1,192 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct EmittedEventRecord {
+    pub namespace: String,
+    pub event_id: String,
+    pub event_name: String,
+    // The JSON of the type annotated payload of the event
+    pub payload: String,
+    pub component_id: String,
+    pub worker_name: Option<String>,
+    pub trace_id: String,
+    pub method: Option<String>,
+    pub uri: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait EmittedEventRepo {
+    async fn create(&self, event: &EmittedEventRecord) -> Result<(), RepoError>;
+
+    // The latest events of a namespace, the newest first
+    async fn get_all(
+        &self,
+        namespace: &str,
+        event_name: Option<&str>,
+        component_id: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<EmittedEventRecord>, RepoError>;
+}
+
+pub struct DbEmittedEventRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbEmittedEventRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedEmittedEventRepo<Repo: EmittedEventRepo> {
+    repo: Repo,
+}
+
+impl<Repo: EmittedEventRepo> LoggedEmittedEventRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        namespace: &str,
+        event_id: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(namespace = namespace, event_id = event_id, "{}", message),
+            Err(error) => error!(
+                namespace = namespace,
+                event_id = event_id,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: EmittedEventRepo + Send + Sync> EmittedEventRepo for LoggedEmittedEventRepo<Repo> {
+    async fn create(&self, event: &EmittedEventRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(event).await;
+        Self::logged_with_id("create", &event.namespace, Some(&event.event_id), result)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &str,
+        event_name: Option<&str>,
+        component_id: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<EmittedEventRecord>, RepoError> {
+        let result = self
+            .repo
+            .get_all(namespace, event_name, component_id, limit)
+            .await;
+        Self::logged_with_id("get_all", namespace, None, result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl EmittedEventRepo for DbEmittedEventRepo<sqlx::Postgres> {
+    async fn create(&self, event: &EmittedEventRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO gateway_emitted_events
+                (namespace, event_id, event_name, payload, component_id, worker_name, trace_id, method, uri, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               "#,
+        )
+        .bind(event.namespace.clone())
+        .bind(event.event_id.clone())
+        .bind(event.event_name.clone())
+        .bind(event.payload.clone())
+        .bind(event.component_id.clone())
+        .bind(event.worker_name.clone())
+        .bind(event.trace_id.clone())
+        .bind(event.method.clone())
+        .bind(event.uri.clone())
+        .bind(event.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(
+        &self,
+        namespace: &str,
+        event_name: Option<&str>,
+        component_id: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<EmittedEventRecord>, RepoError> {
+        sqlx::query_as::<_, EmittedEventRecord>(
+            r#"
+              SELECT namespace, event_id, event_name, payload, component_id, worker_name, trace_id, method, uri, created_at::timestamptz
+              FROM gateway_emitted_events
+              WHERE namespace = $1 AND ($2::text IS NULL OR event_name = $2) AND ($3::text IS NULL OR component_id = $3)
+              ORDER BY created_at DESC, event_id
+              LIMIT $4
+               "#,
+        )
+        .bind(namespace)
+        .bind(event_name)
+        .bind(component_id)
+        .bind(limit as i64)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(
+        &self,
+        namespace: &str,
+        event_name: Option<&str>,
+        component_id: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<EmittedEventRecord>, RepoError> {
+        sqlx::query_as::<_, EmittedEventRecord>(
+            r#"
+              SELECT namespace, event_id, event_name, payload, component_id, worker_name, trace_id, method, uri, created_at
+              FROM gateway_emitted_events
+              WHERE namespace = $1 AND ($2 IS NULL OR event_name = $2) AND ($3 IS NULL OR component_id = $3)
+              ORDER BY created_at DESC, event_id
+              LIMIT $4
+               "#,
+        )
+        .bind(namespace)
+        .bind(event_name)
+        .bind(component_id)
+        .bind(limit as i64)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+}
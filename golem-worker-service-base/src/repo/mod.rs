@@ -14,4 +14,10 @@
 
 pub mod api_definition;
 pub mod api_deployment;
+pub mod api_key;
+pub mod dead_letter;
+pub mod emitted_event;
+pub mod request_fixture;
+pub mod rib_library;
 pub mod security_scheme;
+pub mod worker_schedule;
@@ -0,0 +1,204 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct RequestFixtureRecord {
+    pub namespace: String,
+    pub site: String,
+    pub fixture_id: String,
+    pub route: String,
+    pub method: String,
+    pub uri: String,
+    // The JSON array of the stored headers of the request
+    pub headers: String,
+    pub body: Vec<u8>,
+    pub status: i32,
+    // The JSON schema of the response body, if it was JSON
+    pub response_schema: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait RequestFixtureRepo {
+    async fn create(&self, fixture: &RequestFixtureRecord) -> Result<(), RepoError>;
+
+    // The fixtures of a site, the oldest first
+    async fn get_all(&self, site: &str) -> Result<Vec<RequestFixtureRecord>, RepoError>;
+
+    async fn count(&self, site: &str) -> Result<u64, RepoError>;
+
+    /// Deletes a fixture, returns false if it does not exist
+    async fn delete(&self, site: &str, fixture_id: &str) -> Result<bool, RepoError>;
+
+    /// Deletes all the fixtures of a site, returns their number
+    async fn delete_all(&self, site: &str) -> Result<u64, RepoError>;
+}
+
+pub struct DbRequestFixtureRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbRequestFixtureRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedRequestFixtureRepo<Repo: RequestFixtureRepo> {
+    repo: Repo,
+}
+
+impl<Repo: RequestFixtureRepo> LoggedRequestFixtureRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        site: &str,
+        fixture_id: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(site = site, fixture_id = fixture_id, "{}", message),
+            Err(error) => error!(
+                site = site,
+                fixture_id = fixture_id,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: RequestFixtureRepo + Send + Sync> RequestFixtureRepo for LoggedRequestFixtureRepo<Repo> {
+    async fn create(&self, fixture: &RequestFixtureRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(fixture).await;
+        Self::logged_with_id("create", &fixture.site, Some(&fixture.fixture_id), result)
+    }
+
+    async fn get_all(&self, site: &str) -> Result<Vec<RequestFixtureRecord>, RepoError> {
+        let result = self.repo.get_all(site).await;
+        Self::logged_with_id("get_all", site, None, result)
+    }
+
+    async fn count(&self, site: &str) -> Result<u64, RepoError> {
+        let result = self.repo.count(site).await;
+        Self::logged_with_id("count", site, None, result)
+    }
+
+    async fn delete(&self, site: &str, fixture_id: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete(site, fixture_id).await;
+        Self::logged_with_id("delete", site, Some(fixture_id), result)
+    }
+
+    async fn delete_all(&self, site: &str) -> Result<u64, RepoError> {
+        let result = self.repo.delete_all(site).await;
+        Self::logged_with_id("delete_all", site, None, result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl RequestFixtureRepo for DbRequestFixtureRepo<sqlx::Postgres> {
+    async fn create(&self, fixture: &RequestFixtureRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO gateway_request_fixtures
+                (namespace, site, fixture_id, route, method, uri, headers, body, status, response_schema, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+               "#,
+        )
+        .bind(fixture.namespace.clone())
+        .bind(fixture.site.clone())
+        .bind(fixture.fixture_id.clone())
+        .bind(fixture.route.clone())
+        .bind(fixture.method.clone())
+        .bind(fixture.uri.clone())
+        .bind(fixture.headers.clone())
+        .bind(fixture.body.clone())
+        .bind(fixture.status)
+        .bind(fixture.response_schema.clone())
+        .bind(fixture.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(&self, site: &str) -> Result<Vec<RequestFixtureRecord>, RepoError> {
+        sqlx::query_as::<_, RequestFixtureRecord>(
+            "SELECT namespace, site, fixture_id, route, method, uri, headers, body, status, response_schema, created_at::timestamptz FROM gateway_request_fixtures WHERE site = $1 ORDER BY created_at, fixture_id",
+        )
+        .bind(site)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(&self, site: &str) -> Result<Vec<RequestFixtureRecord>, RepoError> {
+        sqlx::query_as::<_, RequestFixtureRecord>(
+            "SELECT namespace, site, fixture_id, route, method, uri, headers, body, status, response_schema, created_at FROM gateway_request_fixtures WHERE site = $1 ORDER BY created_at, fixture_id",
+        )
+        .bind(site)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn count(&self, site: &str) -> Result<u64, RepoError> {
+        let (count,) = sqlx::query_as::<_, (i64,)>(
+            "SELECT COUNT(*) FROM gateway_request_fixtures WHERE site = $1",
+        )
+        .bind(site)
+        .fetch_one(self.db_pool.deref())
+        .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn delete(&self, site: &str, fixture_id: &str) -> Result<bool, RepoError> {
+        let result =
+            sqlx::query("DELETE FROM gateway_request_fixtures WHERE site = $1 AND fixture_id = $2")
+                .bind(site)
+                .bind(fixture_id)
+                .execute(self.db_pool.deref())
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_all(&self, site: &str) -> Result<u64, RepoError> {
+        let result = sqlx::query("DELETE FROM gateway_request_fixtures WHERE site = $1")
+            .bind(site)
+            .execute(self.db_pool.deref())
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
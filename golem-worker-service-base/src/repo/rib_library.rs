@@ -0,0 +1,261 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub struct RibLibraryRecord {
+    pub namespace: String,
+    pub name: String,
+    pub version: String,
+    pub source: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[async_trait]
+pub trait RibLibraryRepo {
+    async fn create(&self, library: &RibLibraryRecord) -> Result<(), RepoError>;
+
+    async fn get(
+        &self,
+        namespace: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError>;
+
+    // The most recently created version of the library
+    async fn get_latest(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError>;
+
+    async fn get_all(&self, namespace: &str) -> Result<Vec<RibLibraryRecord>, RepoError>;
+
+    /// Deletes a library version, returns false if it does not exist
+    async fn delete(&self, namespace: &str, name: &str, version: &str) -> Result<bool, RepoError>;
+}
+
+pub struct DbRibLibraryRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbRibLibraryRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedRibLibraryRepo<Repo: RibLibraryRepo> {
+    repo: Repo,
+}
+
+impl<Repo: RibLibraryRepo> LoggedRibLibraryRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        name: Option<&str>,
+        version: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(name = name, version = version, "{}", message),
+            Err(error) => error!(
+                name = name,
+                version = version,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: RibLibraryRepo + Send + Sync> RibLibraryRepo for LoggedRibLibraryRepo<Repo> {
+    async fn create(&self, library: &RibLibraryRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(library).await;
+        Self::logged_with_id(
+            "create",
+            Some(&library.name),
+            Some(&library.version),
+            result,
+        )
+    }
+
+    async fn get(
+        &self,
+        namespace: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError> {
+        let result = self.repo.get(namespace, name, version).await;
+        Self::logged_with_id("get", Some(name), Some(version), result)
+    }
+
+    async fn get_latest(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError> {
+        let result = self.repo.get_latest(namespace, name).await;
+        Self::logged_with_id("get_latest", Some(name), None, result)
+    }
+
+    async fn get_all(&self, namespace: &str) -> Result<Vec<RibLibraryRecord>, RepoError> {
+        let result = self.repo.get_all(namespace).await;
+        Self::logged_with_id("get_all", None, None, result)
+    }
+
+    async fn delete(&self, namespace: &str, name: &str, version: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete(namespace, name, version).await;
+        Self::logged_with_id("delete", Some(name), Some(version), result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl RibLibraryRepo for DbRibLibraryRepo<sqlx::Postgres> {
+    async fn create(&self, library: &RibLibraryRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO rib_libraries
+                (namespace, name, version, source, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5)
+               "#,
+        )
+        .bind(library.namespace.clone())
+        .bind(library.name.clone())
+        .bind(library.version.clone())
+        .bind(library.source.clone())
+        .bind(library.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    #[when(sqlx::Postgres -> get)]
+    async fn get_postgres(
+        &self,
+        namespace: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError> {
+        sqlx::query_as::<_, RibLibraryRecord>(
+            "SELECT namespace, name, version, source, created_at::timestamptz FROM rib_libraries WHERE namespace = $1 AND name = $2 AND version = $3",
+        )
+        .bind(namespace)
+        .bind(name)
+        .bind(version)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get)]
+    async fn get_sqlite(
+        &self,
+        namespace: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError> {
+        sqlx::query_as::<_, RibLibraryRecord>(
+            "SELECT namespace, name, version, source, created_at FROM rib_libraries WHERE namespace = $1 AND name = $2 AND version = $3",
+        )
+        .bind(namespace)
+        .bind(name)
+        .bind(version)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Postgres -> get_latest)]
+    async fn get_latest_postgres(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError> {
+        sqlx::query_as::<_, RibLibraryRecord>(
+            "SELECT namespace, name, version, source, created_at::timestamptz FROM rib_libraries WHERE namespace = $1 AND name = $2 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(namespace)
+        .bind(name)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_latest)]
+    async fn get_latest_sqlite(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Option<RibLibraryRecord>, RepoError> {
+        sqlx::query_as::<_, RibLibraryRecord>(
+            "SELECT namespace, name, version, source, created_at FROM rib_libraries WHERE namespace = $1 AND name = $2 ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(namespace)
+        .bind(name)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(&self, namespace: &str) -> Result<Vec<RibLibraryRecord>, RepoError> {
+        sqlx::query_as::<_, RibLibraryRecord>(
+            "SELECT namespace, name, version, source, created_at::timestamptz FROM rib_libraries WHERE namespace = $1 ORDER BY name, created_at",
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(&self, namespace: &str) -> Result<Vec<RibLibraryRecord>, RepoError> {
+        sqlx::query_as::<_, RibLibraryRecord>(
+            "SELECT namespace, name, version, source, created_at FROM rib_libraries WHERE namespace = $1 ORDER BY name, created_at",
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn delete(&self, namespace: &str, name: &str, version: &str) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            "DELETE FROM rib_libraries WHERE namespace = $1 AND name = $2 AND version = $3",
+        )
+        .bind(namespace)
+        .bind(name)
+        .bind(version)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
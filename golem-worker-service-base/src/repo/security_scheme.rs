@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use crate::gateway_security::{
-    GolemIdentityProviderMetadata, Provider, SecurityScheme, SecuritySchemeIdentifier,
+    ApiKeySecurityScheme, GolemIdentityProviderMetadata, HmacSecurityScheme, JwtSecurityScheme,
+    Provider, RegisteredSecurityScheme, SecurityScheme, SecuritySchemeIdentifier,
     SecuritySchemeWithProviderMetadata,
 };
 use async_trait::async_trait;
@@ -28,6 +29,16 @@ use std::str::FromStr;
 use std::sync::Arc;
 use tracing::{debug, error};
 
+// JWT schemes are stored along with the OpenID Connect ones, with their configuration
+// in the metadata, and none of the client details, which they do not have
+const JWT_PROVIDER_TYPE: &str = "jwt";
+
+// Likewise for API key schemes, whose keys are stored separately in the api key repo
+const API_KEY_PROVIDER_TYPE: &str = "api-key";
+
+// HMAC schemes keep their shared secret in the client secret
+const HMAC_PROVIDER_TYPE: &str = "hmac";
+
 #[derive(sqlx::FromRow, Debug, Clone)]
 pub struct SecuritySchemeRecord {
     pub namespace: String,
@@ -78,6 +89,92 @@ impl SecuritySchemeRecord {
     }
 }
 
+impl SecuritySchemeRecord {
+    pub fn from_jwt_security_scheme<Namespace: Display>(
+        namespace: &Namespace,
+        value: &JwtSecurityScheme,
+    ) -> Result<SecuritySchemeRecord, String> {
+        let metadata = jwt_security_scheme_serde::serialize(value)?;
+
+        Ok(SecuritySchemeRecord {
+            namespace: namespace.to_string(),
+            provider_type: JWT_PROVIDER_TYPE.to_string(),
+            security_scheme_id: value.scheme_identifier().to_string(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            redirect_url: String::new(),
+            scopes: String::new(),
+            security_scheme_metadata: metadata.into(),
+        })
+    }
+}
+
+impl SecuritySchemeRecord {
+    pub fn from_api_key_security_scheme<Namespace: Display>(
+        namespace: &Namespace,
+        value: &ApiKeySecurityScheme,
+    ) -> Result<SecuritySchemeRecord, String> {
+        let metadata = api_key_security_scheme_serde::serialize(value)?;
+
+        Ok(SecuritySchemeRecord {
+            namespace: namespace.to_string(),
+            provider_type: API_KEY_PROVIDER_TYPE.to_string(),
+            security_scheme_id: value.scheme_identifier().to_string(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            redirect_url: String::new(),
+            scopes: String::new(),
+            security_scheme_metadata: metadata.into(),
+        })
+    }
+}
+
+impl SecuritySchemeRecord {
+    pub fn from_hmac_security_scheme<Namespace: Display>(
+        namespace: &Namespace,
+        value: &HmacSecurityScheme,
+        secret: &str,
+    ) -> Result<SecuritySchemeRecord, String> {
+        let metadata = hmac_security_scheme_serde::serialize(value)?;
+
+        Ok(SecuritySchemeRecord {
+            namespace: namespace.to_string(),
+            provider_type: HMAC_PROVIDER_TYPE.to_string(),
+            security_scheme_id: value.scheme_identifier().to_string(),
+            client_id: String::new(),
+            client_secret: secret.to_string(),
+            redirect_url: String::new(),
+            scopes: String::new(),
+            security_scheme_metadata: metadata.into(),
+        })
+    }
+
+    // The shared secret of an HMAC scheme
+    pub fn hmac_secret(&self) -> Option<&str> {
+        (self.provider_type == HMAC_PROVIDER_TYPE).then_some(self.client_secret.as_str())
+    }
+}
+
+impl TryFrom<SecuritySchemeRecord> for RegisteredSecurityScheme {
+    type Error = String;
+    fn try_from(value: SecuritySchemeRecord) -> Result<Self, Self::Error> {
+        match value.provider_type.as_str() {
+            JWT_PROVIDER_TYPE => Ok(RegisteredSecurityScheme::Jwt(
+                jwt_security_scheme_serde::deserialize(&value.security_scheme_metadata)?,
+            )),
+            API_KEY_PROVIDER_TYPE => Ok(RegisteredSecurityScheme::ApiKey(
+                api_key_security_scheme_serde::deserialize(&value.security_scheme_metadata)?,
+            )),
+            HMAC_PROVIDER_TYPE => Ok(RegisteredSecurityScheme::Hmac(
+                hmac_security_scheme_serde::deserialize(&value.security_scheme_metadata)?,
+            )),
+            _ => Ok(RegisteredSecurityScheme::OpenId(
+                SecuritySchemeWithProviderMetadata::try_from(value)?,
+            )),
+        }
+    }
+}
+
 impl TryFrom<SecuritySchemeRecord> for SecuritySchemeWithProviderMetadata {
     type Error = String;
     fn try_from(value: SecuritySchemeRecord) -> Result<Self, Self::Error> {
@@ -122,6 +219,7 @@ pub trait SecuritySchemeRepo {
 
     async fn get(
         &self,
+        namespace: &str,
         security_scheme_id: &str,
     ) -> Result<Option<SecuritySchemeRecord>, RepoError>;
 }
@@ -174,9 +272,10 @@ impl<Repo: SecuritySchemeRepo + Send + Sync> SecuritySchemeRepo for LoggedSecuri
 
     async fn get(
         &self,
+        namespace: &str,
         security_scheme_id: &str,
     ) -> Result<Option<SecuritySchemeRecord>, RepoError> {
-        let result = self.repo.get(security_scheme_id).await;
+        let result = self.repo.get(namespace, security_scheme_id).await;
         Self::logged_with_id("get", &security_scheme_id.to_string(), result)
     }
 }
@@ -213,6 +312,7 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<sqlx::Postgres> {
     #[when(sqlx::Postgres -> get)]
     async fn get_postgres(
         &self,
+        namespace: &str,
         security_scheme_id: &str,
     ) -> Result<Option<SecuritySchemeRecord>, RepoError> {
         let security_scheme_record = sqlx::query_as::<_, SecuritySchemeRecord>(
@@ -227,9 +327,10 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<sqlx::Postgres> {
                     scopes,
                     security_scheme_metadata
                 FROM security_schemes
-                WHERE security_scheme_id = $1
+                WHERE namespace = $1 AND security_scheme_id = $2
                 "#,
         )
+        .bind(namespace.to_string())
         .bind(security_scheme_id.to_string())
         .fetch_optional(self.db_pool.deref())
         .await
@@ -241,6 +342,7 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<sqlx::Postgres> {
     #[when(sqlx::Sqlite -> get)]
     async fn get(
         &self,
+        namespace: &str,
         security_scheme_id: &str,
     ) -> Result<Option<SecuritySchemeRecord>, RepoError> {
         let security_scheme_record = sqlx::query_as::<_, SecuritySchemeRecord>(
@@ -255,9 +357,10 @@ impl SecuritySchemeRepo for DbSecuritySchemeRepo<sqlx::Postgres> {
                     scopes,
                     security_scheme_metadata
                 FROM security_schemes
-                WHERE security_scheme_id = $1
+                WHERE namespace = $1 AND security_scheme_id = $2
                "#,
         )
+        .bind(namespace)
         .bind(security_scheme_id)
         .fetch_optional(self.db_pool.deref())
         .await
@@ -303,6 +406,99 @@ pub mod identity_provider_metadata_serde {
     }
 }
 
+pub mod jwt_security_scheme_serde {
+    use bytes::{BufMut, Bytes, BytesMut};
+    use golem_api_grpc::proto::golem::apidefinition::JwtSecurityScheme as JwtSecuritySchemeProto;
+
+    use crate::gateway_security::JwtSecurityScheme;
+    use prost::Message;
+
+    pub const SERIALIZATION_VERSION_V1: u8 = 1u8;
+
+    pub fn serialize(value: &JwtSecurityScheme) -> Result<Bytes, String> {
+        let proto_value: JwtSecuritySchemeProto = JwtSecuritySchemeProto::from(value.clone());
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(SERIALIZATION_VERSION_V1);
+        bytes.extend_from_slice(&proto_value.encode_to_vec());
+        Ok(bytes.freeze())
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Result<JwtSecurityScheme, String> {
+        let (version, data) = bytes.split_at(1);
+
+        match version[0] {
+            SERIALIZATION_VERSION_V1 => {
+                let proto_value: JwtSecuritySchemeProto = Message::decode(data)
+                    .map_err(|e| format!("Failed to deserialize value: {e}"))?;
+                JwtSecurityScheme::try_from(proto_value)
+            }
+            _ => Err("Unsupported serialization version".to_string()),
+        }
+    }
+}
+
+pub mod api_key_security_scheme_serde {
+    use bytes::{BufMut, Bytes, BytesMut};
+    use golem_api_grpc::proto::golem::apidefinition::ApiKeySecurityScheme as ApiKeySecuritySchemeProto;
+
+    use crate::gateway_security::ApiKeySecurityScheme;
+    use prost::Message;
+
+    pub const SERIALIZATION_VERSION_V1: u8 = 1u8;
+
+    pub fn serialize(value: &ApiKeySecurityScheme) -> Result<Bytes, String> {
+        let proto_value: ApiKeySecuritySchemeProto = ApiKeySecuritySchemeProto::from(value.clone());
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(SERIALIZATION_VERSION_V1);
+        bytes.extend_from_slice(&proto_value.encode_to_vec());
+        Ok(bytes.freeze())
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Result<ApiKeySecurityScheme, String> {
+        let (version, data) = bytes.split_at(1);
+
+        match version[0] {
+            SERIALIZATION_VERSION_V1 => {
+                let proto_value: ApiKeySecuritySchemeProto = Message::decode(data)
+                    .map_err(|e| format!("Failed to deserialize value: {e}"))?;
+                ApiKeySecurityScheme::try_from(proto_value)
+            }
+            _ => Err("Unsupported serialization version".to_string()),
+        }
+    }
+}
+
+pub mod hmac_security_scheme_serde {
+    use bytes::{BufMut, Bytes, BytesMut};
+    use golem_api_grpc::proto::golem::apidefinition::HmacSecurityScheme as HmacSecuritySchemeProto;
+
+    use crate::gateway_security::HmacSecurityScheme;
+    use prost::Message;
+
+    pub const SERIALIZATION_VERSION_V1: u8 = 1u8;
+
+    pub fn serialize(value: &HmacSecurityScheme) -> Result<Bytes, String> {
+        let proto_value: HmacSecuritySchemeProto = HmacSecuritySchemeProto::from(value.clone());
+        let mut bytes = BytesMut::new();
+        bytes.put_u8(SERIALIZATION_VERSION_V1);
+        bytes.extend_from_slice(&proto_value.encode_to_vec());
+        Ok(bytes.freeze())
+    }
+
+    pub fn deserialize(bytes: &[u8]) -> Result<HmacSecurityScheme, String> {
+        let (version, data) = bytes.split_at(1);
+
+        match version[0] {
+            SERIALIZATION_VERSION_V1 => {
+                let proto_value: HmacSecuritySchemeProto = Message::decode(data)
+                    .map_err(|e| format!("Failed to deserialize value: {e}"))?;
+                HmacSecurityScheme::try_from(proto_value)
+            }
+            _ => Err("Unsupported serialization version".to_string()),
+        }
+    }
+}
+
 pub mod constraint_serde {
     use bytes::{BufMut, Bytes, BytesMut};
     use golem_api_grpc::proto::golem::component::FunctionConstraintCollection as FunctionConstraintCollectionProto;
@@ -0,0 +1,358 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use conditional_trait_gen::{trait_gen, when};
+use golem_service_base::repo::RepoError;
+use sqlx::{Database, Pool};
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+#[derive(sqlx::FromRow, Debug, Clone, PartialEq)]
+pub struct WorkerScheduleRecord {
+    pub namespace: String,
+    pub schedule_id: String,
+    pub component_id: String,
+    pub worker_name: String,
+    pub function_name: String,
+    // The JSON array of the parameters of the invocations
+    pub function_params: String,
+    pub cron_expression: String,
+    pub timezone: String,
+    pub misfire_policy: String,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait WorkerScheduleRepo {
+    async fn create(&self, schedule: &WorkerScheduleRecord) -> Result<(), RepoError>;
+
+    /// Updates the definition and the next run of a schedule, returns false if it does not exist
+    async fn update(&self, schedule: &WorkerScheduleRecord) -> Result<bool, RepoError>;
+
+    async fn get(
+        &self,
+        namespace: &str,
+        schedule_id: &str,
+    ) -> Result<Option<WorkerScheduleRecord>, RepoError>;
+
+    async fn get_all(&self, namespace: &str) -> Result<Vec<WorkerScheduleRecord>, RepoError>;
+
+    /// Deletes a schedule, returns false if it does not exist
+    async fn delete(&self, namespace: &str, schedule_id: &str) -> Result<bool, RepoError>;
+
+    // The schedules of all namespaces due at the given time, the earliest first
+    async fn get_due(
+        &self,
+        now: &DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WorkerScheduleRecord>, RepoError>;
+
+    /// Moves a schedule to its next run if its next run is still the expected one,
+    /// returns false if it was already moved on (or updated, or deleted) in the meantime
+    async fn advance(
+        &self,
+        namespace: &str,
+        schedule_id: &str,
+        expected_next_run_at: &DateTime<Utc>,
+        next_run_at: &DateTime<Utc>,
+        last_run_at: Option<&DateTime<Utc>>,
+    ) -> Result<bool, RepoError>;
+}
+
+pub struct DbWorkerScheduleRepo<DB: Database> {
+    db_pool: Arc<Pool<DB>>,
+}
+
+impl<DB: Database> DbWorkerScheduleRepo<DB> {
+    pub fn new(db_pool: Arc<Pool<DB>>) -> Self {
+        Self { db_pool }
+    }
+}
+
+pub struct LoggedWorkerScheduleRepo<Repo: WorkerScheduleRepo> {
+    repo: Repo,
+}
+
+impl<Repo: WorkerScheduleRepo> LoggedWorkerScheduleRepo<Repo> {
+    pub fn new(repo: Repo) -> Self {
+        Self { repo }
+    }
+
+    fn logged_with_id<R>(
+        message: &'static str,
+        schedule_id: Option<&str>,
+        result: Result<R, RepoError>,
+    ) -> Result<R, RepoError> {
+        match &result {
+            Ok(_) => debug!(schedule_id = schedule_id, "{}", message),
+            Err(error) => error!(
+                schedule_id = schedule_id,
+                error = error.to_string(),
+                "{message}"
+            ),
+        }
+        result
+    }
+}
+
+#[async_trait]
+impl<Repo: WorkerScheduleRepo + Send + Sync> WorkerScheduleRepo for LoggedWorkerScheduleRepo<Repo> {
+    async fn create(&self, schedule: &WorkerScheduleRecord) -> Result<(), RepoError> {
+        let result = self.repo.create(schedule).await;
+        Self::logged_with_id("create", Some(&schedule.schedule_id), result)
+    }
+
+    async fn update(&self, schedule: &WorkerScheduleRecord) -> Result<bool, RepoError> {
+        let result = self.repo.update(schedule).await;
+        Self::logged_with_id("update", Some(&schedule.schedule_id), result)
+    }
+
+    async fn get(
+        &self,
+        namespace: &str,
+        schedule_id: &str,
+    ) -> Result<Option<WorkerScheduleRecord>, RepoError> {
+        let result = self.repo.get(namespace, schedule_id).await;
+        Self::logged_with_id("get", Some(schedule_id), result)
+    }
+
+    async fn get_all(&self, namespace: &str) -> Result<Vec<WorkerScheduleRecord>, RepoError> {
+        let result = self.repo.get_all(namespace).await;
+        Self::logged_with_id("get_all", None, result)
+    }
+
+    async fn delete(&self, namespace: &str, schedule_id: &str) -> Result<bool, RepoError> {
+        let result = self.repo.delete(namespace, schedule_id).await;
+        Self::logged_with_id("delete", Some(schedule_id), result)
+    }
+
+    async fn get_due(
+        &self,
+        now: &DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WorkerScheduleRecord>, RepoError> {
+        let result = self.repo.get_due(now, limit).await;
+        Self::logged_with_id("get_due", None, result)
+    }
+
+    async fn advance(
+        &self,
+        namespace: &str,
+        schedule_id: &str,
+        expected_next_run_at: &DateTime<Utc>,
+        next_run_at: &DateTime<Utc>,
+        last_run_at: Option<&DateTime<Utc>>,
+    ) -> Result<bool, RepoError> {
+        let result = self
+            .repo
+            .advance(
+                namespace,
+                schedule_id,
+                expected_next_run_at,
+                next_run_at,
+                last_run_at,
+            )
+            .await;
+        Self::logged_with_id("advance", Some(schedule_id), result)
+    }
+}
+
+#[trait_gen(sqlx::Postgres -> sqlx::Postgres, sqlx::Sqlite)]
+#[async_trait]
+impl WorkerScheduleRepo for DbWorkerScheduleRepo<sqlx::Postgres> {
+    async fn create(&self, schedule: &WorkerScheduleRecord) -> Result<(), RepoError> {
+        sqlx::query(
+            r#"
+              INSERT INTO worker_schedules
+                (namespace, schedule_id, component_id, worker_name, function_name, function_params, cron_expression, timezone, misfire_policy, next_run_at, last_run_at, created_at)
+              VALUES
+                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+               "#,
+        )
+        .bind(schedule.namespace.clone())
+        .bind(schedule.schedule_id.clone())
+        .bind(schedule.component_id.clone())
+        .bind(schedule.worker_name.clone())
+        .bind(schedule.function_name.clone())
+        .bind(schedule.function_params.clone())
+        .bind(schedule.cron_expression.clone())
+        .bind(schedule.timezone.clone())
+        .bind(schedule.misfire_policy.clone())
+        .bind(schedule.next_run_at)
+        .bind(schedule.last_run_at)
+        .bind(schedule.created_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update(&self, schedule: &WorkerScheduleRecord) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+              UPDATE worker_schedules
+              SET component_id = $3, worker_name = $4, function_name = $5, function_params = $6, cron_expression = $7, timezone = $8, misfire_policy = $9, next_run_at = $10
+              WHERE namespace = $1 AND schedule_id = $2
+               "#,
+        )
+        .bind(schedule.namespace.clone())
+        .bind(schedule.schedule_id.clone())
+        .bind(schedule.component_id.clone())
+        .bind(schedule.worker_name.clone())
+        .bind(schedule.function_name.clone())
+        .bind(schedule.function_params.clone())
+        .bind(schedule.cron_expression.clone())
+        .bind(schedule.timezone.clone())
+        .bind(schedule.misfire_policy.clone())
+        .bind(schedule.next_run_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[when(sqlx::Postgres -> get)]
+    async fn get_postgres(
+        &self,
+        namespace: &str,
+        schedule_id: &str,
+    ) -> Result<Option<WorkerScheduleRecord>, RepoError> {
+        sqlx::query_as::<_, WorkerScheduleRecord>(
+            "SELECT namespace, schedule_id, component_id, worker_name, function_name, function_params, cron_expression, timezone, misfire_policy, next_run_at::timestamptz, last_run_at::timestamptz, created_at::timestamptz FROM worker_schedules WHERE namespace = $1 AND schedule_id = $2",
+        )
+        .bind(namespace)
+        .bind(schedule_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get)]
+    async fn get_sqlite(
+        &self,
+        namespace: &str,
+        schedule_id: &str,
+    ) -> Result<Option<WorkerScheduleRecord>, RepoError> {
+        sqlx::query_as::<_, WorkerScheduleRecord>(
+            "SELECT namespace, schedule_id, component_id, worker_name, function_name, function_params, cron_expression, timezone, misfire_policy, next_run_at, last_run_at, created_at FROM worker_schedules WHERE namespace = $1 AND schedule_id = $2",
+        )
+        .bind(namespace)
+        .bind(schedule_id)
+        .fetch_optional(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Postgres -> get_all)]
+    async fn get_all_postgres(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<WorkerScheduleRecord>, RepoError> {
+        sqlx::query_as::<_, WorkerScheduleRecord>(
+            "SELECT namespace, schedule_id, component_id, worker_name, function_name, function_params, cron_expression, timezone, misfire_policy, next_run_at::timestamptz, last_run_at::timestamptz, created_at::timestamptz FROM worker_schedules WHERE namespace = $1 ORDER BY schedule_id",
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_all)]
+    async fn get_all_sqlite(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<WorkerScheduleRecord>, RepoError> {
+        sqlx::query_as::<_, WorkerScheduleRecord>(
+            "SELECT namespace, schedule_id, component_id, worker_name, function_name, function_params, cron_expression, timezone, misfire_policy, next_run_at, last_run_at, created_at FROM worker_schedules WHERE namespace = $1 ORDER BY schedule_id",
+        )
+        .bind(namespace)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn delete(&self, namespace: &str, schedule_id: &str) -> Result<bool, RepoError> {
+        let result =
+            sqlx::query("DELETE FROM worker_schedules WHERE namespace = $1 AND schedule_id = $2")
+                .bind(namespace)
+                .bind(schedule_id)
+                .execute(self.db_pool.deref())
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[when(sqlx::Postgres -> get_due)]
+    async fn get_due_postgres(
+        &self,
+        now: &DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WorkerScheduleRecord>, RepoError> {
+        sqlx::query_as::<_, WorkerScheduleRecord>(
+            "SELECT namespace, schedule_id, component_id, worker_name, function_name, function_params, cron_expression, timezone, misfire_policy, next_run_at::timestamptz, last_run_at::timestamptz, created_at::timestamptz FROM worker_schedules WHERE next_run_at <= $1 ORDER BY next_run_at LIMIT $2",
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    #[when(sqlx::Sqlite -> get_due)]
+    async fn get_due_sqlite(
+        &self,
+        now: &DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WorkerScheduleRecord>, RepoError> {
+        sqlx::query_as::<_, WorkerScheduleRecord>(
+            "SELECT namespace, schedule_id, component_id, worker_name, function_name, function_params, cron_expression, timezone, misfire_policy, next_run_at, last_run_at, created_at FROM worker_schedules WHERE next_run_at <= $1 ORDER BY next_run_at LIMIT $2",
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(self.db_pool.deref())
+        .await
+        .map_err(|e| e.into())
+    }
+
+    async fn advance(
+        &self,
+        namespace: &str,
+        schedule_id: &str,
+        expected_next_run_at: &DateTime<Utc>,
+        next_run_at: &DateTime<Utc>,
+        last_run_at: Option<&DateTime<Utc>>,
+    ) -> Result<bool, RepoError> {
+        let result = sqlx::query(
+            r#"
+              UPDATE worker_schedules
+              SET next_run_at = $4, last_run_at = COALESCE($5, last_run_at)
+              WHERE namespace = $1 AND schedule_id = $2 AND next_run_at = $3
+               "#,
+        )
+        .bind(namespace)
+        .bind(schedule_id)
+        .bind(expected_next_run_at)
+        .bind(next_run_at)
+        .bind(last_run_at)
+        .execute(self.db_pool.deref())
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
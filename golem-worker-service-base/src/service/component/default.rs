@@ -18,8 +18,9 @@ use async_trait::async_trait;
 use golem_api_grpc::proto::golem::component::v1::component_service_client::ComponentServiceClient;
 use golem_api_grpc::proto::golem::component::v1::{
     create_component_constraints_response, get_component_metadata_response,
-    CreateComponentConstraintsRequest, CreateComponentConstraintsResponse,
-    GetComponentMetadataResponse, GetLatestComponentRequest, GetVersionedComponentRequest,
+    get_components_response, CreateComponentConstraintsRequest, CreateComponentConstraintsResponse,
+    GetComponentMetadataResponse, GetComponentsRequest, GetComponentsResponse,
+    GetLatestComponentRequest, GetVersionedComponentRequest,
 };
 use golem_api_grpc::proto::golem::component::ComponentConstraints;
 use golem_api_grpc::proto::golem::component::FunctionConstraintCollection as FunctionConstraintCollectionProto;
@@ -56,6 +57,9 @@ pub trait ComponentService<AuthCtx> {
         constraints: FunctionConstraintCollection,
         auth_ctx: &AuthCtx,
     ) -> ComponentResult<FunctionConstraintCollection>;
+
+    /// Gets all versions of all components visible with the given auth context
+    async fn get_all(&self, auth_ctx: &AuthCtx) -> ComponentResult<Vec<Component>>;
 }
 
 #[derive(Clone)]
@@ -114,6 +118,26 @@ impl RemoteComponentService {
         }
     }
 
+    fn process_components_response(
+        response: GetComponentsResponse,
+    ) -> Result<Vec<Component>, ComponentServiceError> {
+        match response.result {
+            None => Err(ComponentServiceError::Internal(
+                "Empty response".to_string(),
+            )),
+            Some(get_components_response::Result::Success(response)) => response
+                .components
+                .into_iter()
+                .map(|component| {
+                    component.try_into().map_err(|err| {
+                        ComponentServiceError::Internal(format!("Response conversion error: {err}"))
+                    })
+                })
+                .collect(),
+            Some(get_components_response::Result::Error(error)) => Err(error.into()),
+        }
+    }
+
     fn process_create_component_metadata_response(
         response: CreateComponentConstraintsResponse,
     ) -> Result<FunctionConstraintCollection, ComponentServiceError> {
@@ -284,4 +308,36 @@ where
 
         Ok(value)
     }
+
+    async fn get_all(&self, metadata: &AuthCtx) -> ComponentResult<Vec<Component>> {
+        let value = with_retries(
+            "component",
+            "get_components",
+            None,
+            &self.retry_config,
+            &(self.client.clone(), metadata.clone()),
+            |(client, metadata)| {
+                Box::pin(async move {
+                    let response = client
+                        .call("get_components", move |client| {
+                            let request = GetComponentsRequest {
+                                project_id: None,
+                                component_name: None,
+                            };
+                            let request = with_metadata(request, metadata.clone());
+
+                            Box::pin(client.get_components(request))
+                        })
+                        .await?
+                        .into_inner();
+
+                    Self::process_components_response(response)
+                })
+            },
+            Self::is_retriable,
+        )
+        .await?;
+
+        Ok(value)
+    }
 }
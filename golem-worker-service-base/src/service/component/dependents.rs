@@ -0,0 +1,202 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_definition::http::HttpApiDefinition;
+use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion, HasGolemBindings};
+use crate::gateway_api_deployment::ApiDeployment;
+use golem_common::model::component_metadata::DynamicLinkedInstance;
+use golem_common::model::WorkerId;
+use golem_service_base::model::{Component, VersionedComponentId};
+use golem_wasm_ast::analysis::AnalysedExport;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Reverse dependencies of a component version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ComponentDependents {
+    pub versioned_component_id: VersionedComponentId,
+    /// API definitions with at least one route bound to this component version
+    pub api_definitions: Vec<ApiDefinitionDependent>,
+    /// Workers running this component version which have not exited or failed
+    pub workers: Vec<WorkerId>,
+    /// True if there are more active workers than listed in `workers`
+    pub workers_truncated: bool,
+    /// Component versions dynamically linking to an interface exported by this component version
+    pub rpc_callers: Vec<VersionedComponentId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct ApiDefinitionDependent {
+    pub id: ApiDefinitionId,
+    pub version: ApiVersion,
+    pub draft: bool,
+    /// Sites the API definition is currently deployed to
+    pub deployed_sites: Vec<String>,
+}
+
+/// Selects the API definitions referencing the given component version, together with the
+/// sites they are deployed to
+pub fn api_definition_dependents<Namespace>(
+    versioned_component_id: &VersionedComponentId,
+    definitions: &[HttpApiDefinition],
+    deployments: &[ApiDeployment<Namespace>],
+) -> Vec<ApiDefinitionDependent> {
+    definitions
+        .iter()
+        .filter(|definition| {
+            definition
+                .get_bindings()
+                .iter()
+                .flat_map(|binding| binding.get_component_ids())
+                .any(|component_id| &component_id == versioned_component_id)
+        })
+        .map(|definition| {
+            let mut deployed_sites = deployments
+                .iter()
+                .filter(|deployment| {
+                    deployment
+                        .api_definition_keys
+                        .iter()
+                        .any(|key| key.id == definition.id && key.version == definition.version)
+                })
+                .map(|deployment| deployment.site.to_string())
+                .collect::<Vec<_>>();
+            deployed_sites.sort();
+            deployed_sites.dedup();
+
+            ApiDefinitionDependent {
+                id: definition.id.clone(),
+                version: definition.version.clone(),
+                draft: definition.draft,
+                deployed_sites,
+            }
+        })
+        .collect()
+}
+
+/// Selects the component versions which dynamically link to any interface exported by `target`
+pub fn rpc_callers(target: &Component, components: &[Component]) -> Vec<VersionedComponentId> {
+    let exported_interfaces = target
+        .metadata
+        .exports
+        .iter()
+        .filter_map(|export| match export {
+            AnalysedExport::Instance(instance) => Some(interface_without_version(&instance.name)),
+            AnalysedExport::Function(_) => None,
+        })
+        .collect::<HashSet<_>>();
+
+    components
+        .iter()
+        .filter(|component| {
+            component.versioned_component_id.component_id
+                != target.versioned_component_id.component_id
+        })
+        .filter(|component| {
+            component
+                .metadata
+                .dynamic_linking
+                .values()
+                .any(|instance| match instance {
+                    DynamicLinkedInstance::WasmRpc(rpc) => {
+                        rpc.target_interface_name.values().any(|interface| {
+                            exported_interfaces.contains(interface_without_version(interface))
+                        })
+                    }
+                })
+        })
+        .map(|component| component.versioned_component_id.clone())
+        .collect()
+}
+
+fn interface_without_version(interface: &str) -> &str {
+    interface
+        .split_once('@')
+        .map(|(name, _)| name)
+        .unwrap_or(interface)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use golem_common::model::component_metadata::{ComponentMetadata, DynamicLinkedWasmRpc};
+    use golem_common::model::ComponentId;
+    use golem_wasm_ast::analysis::AnalysedInstance;
+    use std::collections::HashMap;
+
+    fn component(
+        component_id: &ComponentId,
+        exports: Vec<&str>,
+        links: Vec<(&str, &str)>,
+    ) -> Component {
+        Component {
+            versioned_component_id: VersionedComponentId {
+                component_id: component_id.clone(),
+                version: 0,
+            },
+            component_name: golem_service_base::model::ComponentName("test".to_string()),
+            component_size: 0,
+            metadata: ComponentMetadata {
+                exports: exports
+                    .into_iter()
+                    .map(|name| {
+                        AnalysedExport::Instance(AnalysedInstance {
+                            name: name.to_string(),
+                            functions: vec![],
+                        })
+                    })
+                    .collect(),
+                producers: vec![],
+                memories: vec![],
+                dynamic_linking: HashMap::from_iter([(
+                    "stub".to_string(),
+                    DynamicLinkedInstance::WasmRpc(DynamicLinkedWasmRpc {
+                        target_interface_name: links
+                            .into_iter()
+                            .map(|(resource, interface)| {
+                                (resource.to_string(), interface.to_string())
+                            })
+                            .collect(),
+                    }),
+                )]),
+            },
+            created_at: None,
+            component_type: None,
+            files: vec![],
+            installed_plugins: vec![],
+        }
+    }
+
+    #[test]
+    fn rpc_callers_match_exported_interfaces_ignoring_versions() {
+        let target_id = ComponentId::new_v4();
+        let caller_id = ComponentId::new_v4();
+        let unrelated_id = ComponentId::new_v4();
+
+        let target = component(&target_id, vec!["rpc:counters/api@0.1.0"], vec![]);
+        let caller = component(&caller_id, vec![], vec![("counter", "rpc:counters/api")]);
+        let unrelated = component(&unrelated_id, vec![], vec![("other", "rpc:other/api")]);
+
+        let callers = rpc_callers(&target, &[target.clone(), caller.clone(), unrelated]);
+
+        assert_eq!(callers, vec![caller.versioned_component_id]);
+    }
+}
@@ -16,3 +16,5 @@ pub use default::*;
 pub use error::*;
 mod default;
 mod error;
+
+pub mod dependents;
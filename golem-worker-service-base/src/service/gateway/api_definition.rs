@@ -15,12 +15,15 @@
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::api::DeletedApiDefinition;
 use crate::gateway_api_definition::http::{
     CompiledHttpApiDefinition, ComponentMetadataDictionary, HttpApiDefinition,
     HttpApiDefinitionRequest, RouteCompilationErrors,
 };
 use crate::gateway_api_definition::{ApiDefinitionId, ApiVersion, HasGolemBindings};
+use crate::gateway_rib_compiler::{with_taint_findings, TaintAnalysisPolicy};
 use crate::gateway_security::IdentityProviderError;
 use crate::repo::api_definition::ApiDefinitionRecord;
 use crate::repo::api_definition::ApiDefinitionRepo;
@@ -36,7 +39,7 @@ use golem_common::SafeDisplay;
 use golem_service_base::model::{Component, VersionedComponentId};
 use golem_service_base::repo::RepoError;
 use rib::RibError;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub type ApiResult<T> = Result<T, ApiDefinitionError>;
 
@@ -74,6 +77,8 @@ pub enum ApiDefinitionError {
     InternalRepoError(RepoError),
     #[error("Internal error: {0}")]
     Internal(String),
+    #[error("API definition was modified concurrently, If-Match precondition failed: {0}")]
+    PreconditionFailed(ApiDefinitionId),
 }
 
 impl ApiDefinitionError {}
@@ -99,6 +104,7 @@ impl SafeDisplay for ApiDefinitionError {
             ApiDefinitionError::Internal(_) => self.to_string(),
             ApiDefinitionError::SecuritySchemeError(inner) => inner.to_safe_string(),
             ApiDefinitionError::RibInternal(_) => self.to_string(),
+            ApiDefinitionError::PreconditionFailed(_) => self.to_string(),
         }
     }
 }
@@ -118,6 +124,66 @@ impl From<RouteCompilationErrors> for ApiDefinitionError {
                     e
                 ))
             }
+            RouteCompilationErrors::InvalidGrpcBinding(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid gRPC binding: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidTrafficMirror(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid traffic mirror: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidWebSocketBinding(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid WebSocket binding: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidServerSentEventsBinding(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid server-sent events binding: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidHttpProxyBinding(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid HTTP proxy binding: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidStatusPageBinding(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid status page binding: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidWorkerPerUser(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid worker per user: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidWorkerBootstrap(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid worker bootstrap: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidPagination(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid pagination: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidContentNegotiation(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid content negotiation: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidErrorMapping(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid error mapping: {}", e)],
+                })
+            }
+            RouteCompilationErrors::InvalidStaticWorkerNames(e) => {
+                ApiDefinitionError::ValidationError(ValidationErrors {
+                    errors: vec![format!("Invalid static worker names: {}", e)],
+                })
+            }
         }
     }
 }
@@ -134,6 +200,14 @@ pub trait ApiDefinitionService<AuthCtx, Namespace> {
         auth_ctx: &AuthCtx,
     ) -> ApiResult<CompiledHttpApiDefinition<Namespace>>;
 
+    /// Validates and compiles an API definition against its components without storing it
+    async fn compile(
+        &self,
+        definition: &HttpApiDefinitionRequest,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<CompiledHttpApiDefinition<Namespace>>;
+
     async fn update(
         &self,
         definition: &HttpApiDefinitionRequest,
@@ -141,6 +215,16 @@ pub trait ApiDefinitionService<AuthCtx, Namespace> {
         auth_ctx: &AuthCtx,
     ) -> ApiResult<CompiledHttpApiDefinition<Namespace>>;
 
+    /// Updates the definition only if it is still the expected one, otherwise fails with
+    /// `PreconditionFailed`. The check and the update are done in a single repo write.
+    async fn update_if_unchanged(
+        &self,
+        definition: &HttpApiDefinitionRequest,
+        expected: &CompiledHttpApiDefinition<Namespace>,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<CompiledHttpApiDefinition<Namespace>>;
+
     async fn get(
         &self,
         id: &ApiDefinitionId,
@@ -149,14 +233,34 @@ pub trait ApiDefinitionService<AuthCtx, Namespace> {
         auth_ctx: &AuthCtx,
     ) -> ApiResult<Option<CompiledHttpApiDefinition<Namespace>>>;
 
+    /// Moves an API definition to the trash. Deployed API definitions cannot be deleted.
     async fn delete(
         &self,
         id: &ApiDefinitionId,
         version: &ApiVersion,
         namespace: &Namespace,
+        deleted_by: &str,
         auth_ctx: &AuthCtx,
     ) -> ApiResult<()>;
 
+    async fn restore(
+        &self,
+        id: &ApiDefinitionId,
+        version: &ApiVersion,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<()>;
+
+    async fn get_deleted(
+        &self,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<Vec<DeletedApiDefinition>>;
+
+    /// Permanently deletes the API definitions of all namespaces which have been in the trash
+    /// for longer than `retention`
+    async fn purge_expired(&self, retention: Duration) -> ApiResult<u64>;
+
     async fn get_all(
         &self,
         namespace: &Namespace,
@@ -171,6 +275,27 @@ pub trait ApiDefinitionService<AuthCtx, Namespace> {
     ) -> ApiResult<Vec<CompiledHttpApiDefinition<Namespace>>>;
 }
 
+// Rejects the definition if untrusted values flow into its worker names or response headers and
+// its policy denies them, and warns about them if it is not off
+fn apply_taint_policy(
+    taint_policy: TaintAnalysisPolicy,
+    taint_findings: Vec<String>,
+) -> Result<(), ApiDefinitionError> {
+    match taint_policy {
+        TaintAnalysisPolicy::Off => Ok(()),
+        TaintAnalysisPolicy::Warn => {
+            for finding in taint_findings {
+                warn!("{}", finding);
+            }
+            Ok(())
+        }
+        TaintAnalysisPolicy::Deny if taint_findings.is_empty() => Ok(()),
+        TaintAnalysisPolicy::Deny => Err(ApiDefinitionError::ValidationError(ValidationErrors {
+            errors: taint_findings,
+        })),
+    }
+}
+
 pub struct ApiDefinitionServiceDefault<AuthCtx, Namespace> {
     pub component_service: Arc<dyn ComponentService<AuthCtx> + Send + Sync>,
     pub definition_repo: Arc<dyn ApiDefinitionRepo + Sync + Send>,
@@ -208,7 +333,7 @@ impl<AuthCtx, Namespace> ApiDefinitionServiceDefault<AuthCtx, Namespace> {
             .get_bindings()
             .iter()
             .cloned()
-            .filter_map(|binding| binding.get_component_id())
+            .flat_map(|binding| binding.get_component_ids())
             .map(|id| async move {
                 self.component_service
                     .get_by_version(&id.component_id, id.version, auth_ctx)
@@ -242,133 +367,232 @@ impl<AuthCtx, Namespace> ApiDefinitionServiceDefault<AuthCtx, Namespace> {
 
         Ok(components)
     }
-}
 
-#[async_trait]
-impl<AuthCtx, Namespace> ApiDefinitionService<AuthCtx, Namespace>
-    for ApiDefinitionServiceDefault<AuthCtx, Namespace>
-where
-    AuthCtx: Send + Sync,
-    Namespace: Display + Clone + Send + Sync + TryFrom<String>,
-    <Namespace as TryFrom<String>>::Error: Display,
-{
-    async fn create(
+    async fn compile_at(
         &self,
         definition: &HttpApiDefinitionRequest,
         namespace: &Namespace,
         auth_ctx: &AuthCtx,
-    ) -> ApiResult<CompiledHttpApiDefinition<Namespace>> {
-        info!(namespace = %namespace, "Create API definition");
-        let created_at = Utc::now();
+        created_at: chrono::DateTime<Utc>,
+    ) -> Result<CompiledHttpApiDefinition<Namespace>, ApiDefinitionError>
+    where
+        Namespace: Display + Clone + Send + Sync + TryFrom<String>,
+        <Namespace as TryFrom<String>>::Error: Display,
+    {
+        let taint_policy = definition.taint_policy;
+
+        let (compiled_http_api_definition, taint_findings) = with_taint_findings(async {
+            let definition = HttpApiDefinition::from_http_api_definition_request::<Namespace>(
+                namespace,
+                definition.clone(),
+                created_at,
+                &self.security_scheme_service,
+            )
+            .await?;
 
-        let exists = self
+            let components = self.get_all_components(&definition, auth_ctx).await?;
+
+            self.api_definition_validator
+                .validate_name(&definition.id)?;
+
+            self.api_definition_validator
+                .validate(&definition, components.as_slice())?;
+
+            let component_metadata_dictionary =
+                ComponentMetadataDictionary::from_components(&components);
+
+            CompiledHttpApiDefinition::from_http_api_definition(
+                &definition,
+                &component_metadata_dictionary,
+                namespace,
+            )
+            .map_err(ApiDefinitionError::from)
+        })
+        .await;
+
+        let compiled_http_api_definition = compiled_http_api_definition?;
+        apply_taint_policy(taint_policy, taint_findings)?;
+
+        Ok(compiled_http_api_definition)
+    }
+
+    async fn update_with_precondition(
+        &self,
+        definition: &HttpApiDefinitionRequest,
+        expected: Option<&CompiledHttpApiDefinition<Namespace>>,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<CompiledHttpApiDefinition<Namespace>>
+    where
+        Namespace: Display + Clone + Send + Sync + TryFrom<String>,
+        <Namespace as TryFrom<String>>::Error: Display,
+    {
+        info!(namespace = %namespace, "Update API definition");
+
+        let existing_record = self
             .definition_repo
-            .get_draft(
+            .get(
                 namespace.to_string().as_str(),
                 definition.id.0.as_str(),
                 definition.version.0.as_str(),
             )
             .await?;
 
-        if exists.is_some() {
-            return Err(ApiDefinitionError::ApiDefinitionAlreadyExists(
+        let existing_record = match existing_record {
+            None => Err(ApiDefinitionError::ApiDefinitionNotFound(
                 definition.id.clone(),
-            ));
+            )),
+            Some(record) if !record.draft => Err(ApiDefinitionError::ApiDefinitionNotDraft(
+                definition.id.clone(),
+            )),
+            Some(record) => Ok(record),
+        }?;
+        let created_at = existing_record.created_at;
+
+        if let Some(expected) = expected {
+            let existing: CompiledHttpApiDefinition<Namespace> = existing_record
+                .clone()
+                .try_into()
+                .map_err(ApiDefinitionError::Internal)?;
+            if existing != *expected {
+                return Err(ApiDefinitionError::PreconditionFailed(
+                    definition.id.clone(),
+                ));
+            }
         }
+        let taint_policy = definition.taint_policy;
+
+        let (compiled_http_api_definition, taint_findings) = with_taint_findings(async {
+            let definition = HttpApiDefinition::from_http_api_definition_request(
+                namespace,
+                definition.clone(),
+                created_at,
+                &self.security_scheme_service,
+            )
+            .await?;
 
-        let definition = HttpApiDefinition::from_http_api_definition_request::<Namespace>(
-            namespace,
-            definition.clone(),
-            created_at,
-            &self.security_scheme_service,
-        )
-        .await?;
-
-        let components = self.get_all_components(&definition, auth_ctx).await?;
+            let components = self.get_all_components(&definition, auth_ctx).await?;
 
-        self.api_definition_validator
-            .validate_name(&definition.id)?;
+            self.api_definition_validator
+                .validate(&definition, components.as_slice())?;
 
-        self.api_definition_validator
-            .validate(&definition, components.as_slice())?;
+            let component_metadata_dictionary =
+                ComponentMetadataDictionary::from_components(&components);
 
-        let component_metadata_dictionary =
-            ComponentMetadataDictionary::from_components(&components);
+            CompiledHttpApiDefinition::from_http_api_definition(
+                &definition,
+                &component_metadata_dictionary,
+                namespace,
+            )
+            .map_err(ApiDefinitionError::from)
+        })
+        .await;
 
-        let compiled_http_api_definition = CompiledHttpApiDefinition::from_http_api_definition(
-            &definition,
-            &component_metadata_dictionary,
-            namespace,
-        )?;
+        let compiled_http_api_definition = compiled_http_api_definition?;
+        apply_taint_policy(taint_policy, taint_findings)?;
 
         let record = ApiDefinitionRecord::new(compiled_http_api_definition.clone(), created_at)
             .map_err(|e| {
                 ApiDefinitionError::Internal(format!("Failed to create API definition record: {e}"))
             })?;
 
-        self.definition_repo.create(&record).await?;
+        match expected {
+            None => self.definition_repo.update(&record).await?,
+            // the definition could still have changed since it was read
+            Some(_) => {
+                if !self
+                    .definition_repo
+                    .update_if_unchanged(&record, &existing_record)
+                    .await?
+                {
+                    return Err(ApiDefinitionError::PreconditionFailed(
+                        definition.id.clone(),
+                    ));
+                }
+            }
+        }
 
         Ok(compiled_http_api_definition)
     }
+}
 
-    async fn update(
+#[async_trait]
+impl<AuthCtx, Namespace> ApiDefinitionService<AuthCtx, Namespace>
+    for ApiDefinitionServiceDefault<AuthCtx, Namespace>
+where
+    AuthCtx: Send + Sync,
+    Namespace: Display + Clone + Send + Sync + TryFrom<String>,
+    <Namespace as TryFrom<String>>::Error: Display,
+{
+    async fn create(
         &self,
         definition: &HttpApiDefinitionRequest,
         namespace: &Namespace,
         auth_ctx: &AuthCtx,
     ) -> ApiResult<CompiledHttpApiDefinition<Namespace>> {
-        info!(namespace = %namespace, "Update API definition");
+        info!(namespace = %namespace, "Create API definition");
+        let created_at = Utc::now();
 
-        let existing_record = self
+        let exists = self
             .definition_repo
-            .get(
+            .get_draft(
                 namespace.to_string().as_str(),
                 definition.id.0.as_str(),
                 definition.version.0.as_str(),
             )
             .await?;
 
-        let created_at = match existing_record {
-            None => Err(ApiDefinitionError::ApiDefinitionNotFound(
-                definition.id.clone(),
-            )),
-            Some(record) if !record.draft => Err(ApiDefinitionError::ApiDefinitionNotDraft(
+        if exists.is_some() {
+            return Err(ApiDefinitionError::ApiDefinitionAlreadyExists(
                 definition.id.clone(),
-            )),
-            Some(record) => Ok(record.created_at),
-        }?;
-        let definition = HttpApiDefinition::from_http_api_definition_request(
-            namespace,
-            definition.clone(),
-            created_at,
-            &self.security_scheme_service,
-        )
-        .await?;
-
-        let components = self.get_all_components(&definition, auth_ctx).await?;
-
-        self.api_definition_validator
-            .validate(&definition, components.as_slice())?;
-
-        let component_metadata_dictionary =
-            ComponentMetadataDictionary::from_components(&components);
+            ));
+        }
 
-        let compiled_http_api_definition = CompiledHttpApiDefinition::from_http_api_definition(
-            &definition,
-            &component_metadata_dictionary,
-            namespace,
-        )?;
+        let compiled_http_api_definition = self
+            .compile_at(definition, namespace, auth_ctx, created_at)
+            .await?;
 
         let record = ApiDefinitionRecord::new(compiled_http_api_definition.clone(), created_at)
             .map_err(|e| {
                 ApiDefinitionError::Internal(format!("Failed to create API definition record: {e}"))
             })?;
 
-        self.definition_repo.update(&record).await?;
+        self.definition_repo.create(&record).await?;
 
         Ok(compiled_http_api_definition)
     }
 
+    async fn compile(
+        &self,
+        definition: &HttpApiDefinitionRequest,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<CompiledHttpApiDefinition<Namespace>> {
+        self.compile_at(definition, namespace, auth_ctx, Utc::now())
+            .await
+    }
+
+    async fn update(
+        &self,
+        definition: &HttpApiDefinitionRequest,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<CompiledHttpApiDefinition<Namespace>> {
+        self.update_with_precondition(definition, None, namespace, auth_ctx)
+            .await
+    }
+
+    async fn update_if_unchanged(
+        &self,
+        definition: &HttpApiDefinitionRequest,
+        expected: &CompiledHttpApiDefinition<Namespace>,
+        namespace: &Namespace,
+        auth_ctx: &AuthCtx,
+    ) -> ApiResult<CompiledHttpApiDefinition<Namespace>> {
+        self.update_with_precondition(definition, Some(expected), namespace, auth_ctx)
+            .await
+    }
+
     async fn get(
         &self,
         id: &ApiDefinitionId,
@@ -400,9 +624,10 @@ where
         id: &ApiDefinitionId,
         version: &ApiVersion,
         namespace: &Namespace,
+        deleted_by: &str,
         _auth_ctx: &AuthCtx,
     ) -> ApiResult<()> {
-        info!(namespace = %namespace, "Delete API definition");
+        info!(namespace = %namespace, deleted_by = deleted_by, "Delete API definition");
 
         let deployments = self
             .deployment_repo
@@ -412,7 +637,12 @@ where
         if deployments.is_empty() {
             let deleted = self
                 .definition_repo
-                .delete(&namespace.to_string(), id.0.as_str(), version.0.as_str())
+                .delete(
+                    &namespace.to_string(),
+                    id.0.as_str(),
+                    version.0.as_str(),
+                    deleted_by,
+                )
                 .await?;
 
             if deleted {
@@ -431,6 +661,54 @@ where
         }
     }
 
+    async fn restore(
+        &self,
+        id: &ApiDefinitionId,
+        version: &ApiVersion,
+        namespace: &Namespace,
+        _auth_ctx: &AuthCtx,
+    ) -> ApiResult<()> {
+        info!(namespace = %namespace, "Restore API definition");
+
+        let restored = self
+            .definition_repo
+            .restore(&namespace.to_string(), id.0.as_str(), version.0.as_str())
+            .await?;
+
+        if restored {
+            Ok(())
+        } else {
+            Err(ApiDefinitionError::ApiDefinitionNotFound(id.clone()))
+        }
+    }
+
+    async fn get_deleted(
+        &self,
+        namespace: &Namespace,
+        _auth_ctx: &AuthCtx,
+    ) -> ApiResult<Vec<DeletedApiDefinition>> {
+        info!(namespace = %namespace, "Get deleted API definitions");
+
+        let records = self
+            .definition_repo
+            .get_deleted(&namespace.to_string())
+            .await?;
+
+        Ok(records.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn purge_expired(&self, retention: Duration) -> ApiResult<u64> {
+        let retention = chrono::Duration::from_std(retention)
+            .map_err(|e| ApiDefinitionError::Internal(format!("Invalid retention: {e}")))?;
+
+        let purged = self
+            .definition_repo
+            .purge_deleted_before(Utc::now() - retention)
+            .await?;
+
+        Ok(purged)
+    }
+
     async fn get_all(
         &self,
         namespace: &Namespace,
@@ -20,26 +20,34 @@ use std::collections::{HashMap, HashSet};
 use async_trait::async_trait;
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{error, info};
 
 use crate::gateway_api_definition::http::{
-    AllPathPatterns, CompiledHttpApiDefinition, HttpApiDefinition, Route,
+    AllPathPatterns, CompiledHttpApiDefinition, CompiledRoute, HttpApiDefinition, Route,
 };
 
 use crate::gateway_binding::GatewayBindingCompiled;
 use crate::gateway_execution::router::{Router, RouterPattern};
-use crate::repo::api_definition::ApiDefinitionRepo;
-use crate::repo::api_deployment::ApiDeploymentRecord;
+use crate::gateway_middleware::{HttpCompression, HttpCors};
+use crate::metrics::gateway as gateway_metrics;
+use crate::repo::api_definition::{record_data_serde, ApiDefinitionRecord, ApiDefinitionRepo};
 use crate::repo::api_deployment::ApiDeploymentRepo;
+use crate::repo::api_deployment::{
+    ApiDeploymentClientCertificatesRecord, ApiDeploymentDeadLetterQueueRecord,
+    ApiDeploymentErrorPagesRecord, ApiDeploymentRecord, ApiDeploymentRequestFixturesRecord,
+};
 use crate::service::component::ComponentService;
 use crate::service::gateway::api_definition::ApiDefinitionIdWithVersion;
 use chrono::Utc;
+use golem_common::cache::{BackgroundEvictionMode, Cache, FullCacheEvictionMode, SimpleCache};
 use golem_common::model::component_constraint::FunctionConstraintCollection;
 use golem_common::model::ComponentId;
 use golem_common::SafeDisplay;
 use golem_service_base::repo::RepoError;
 use rib::WorkerFunctionsInRib;
 use std::fmt::{Debug, Display};
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 #[async_trait]
 pub trait ApiDeploymentService<AuthCtx, Namespace> {
@@ -49,11 +57,42 @@ pub trait ApiDeploymentService<AuthCtx, Namespace> {
         auth_ctx: &AuthCtx,
     ) -> Result<(), ApiDeploymentError<Namespace>>;
 
+    /// Deploys only if the current deployment of the site is the expected one, otherwise fails
+    /// with `PreconditionFailed`. Without an expected deployment it is the same as `deploy`
+    async fn deploy_with_precondition(
+        &self,
+        deployment: &ApiDeploymentRequest<Namespace>,
+        expected: Option<&ApiDeployment<Namespace>>,
+        auth_ctx: &AuthCtx,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
     async fn undeploy(
         &self,
         deployment: &ApiDeploymentRequest<Namespace>,
     ) -> Result<(), ApiDeploymentError<Namespace>>;
 
+    // Deploys the given version of an API definition in place of the other versions of the
+    // definition, on all the sites they are deployed to, in a single repo transaction.
+    // Returns the updated sites.
+    async fn rollback(
+        &self,
+        namespace: &Namespace,
+        api_definition_key: &ApiDefinitionIdWithVersion,
+        auth_ctx: &AuthCtx,
+    ) -> Result<Vec<ApiSiteString>, ApiDeploymentError<Namespace>>;
+
+    // Switches a site from the versions of an API definition it serves to the given version,
+    // in a single repo transaction (blue/green deployment). The health check gets the definitions
+    // the site is about to serve, and aborts the swap by failing.
+    async fn swap(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        api_definition_key: &ApiDefinitionIdWithVersion,
+        health_check: Option<&(dyn DeploymentHealthCheck<Namespace> + Sync + Send)>,
+        auth_ctx: &AuthCtx,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
     // Example: A newer version of API definition is in dev site, and older version of the same definition-id is in prod site.
     // Therefore, Vec<ApiDeployment>
     async fn get_by_id(
@@ -67,6 +106,14 @@ pub trait ApiDeploymentService<AuthCtx, Namespace> {
         site: &ApiSiteString,
     ) -> Result<Option<ApiDeployment<Namespace>>, ApiDeploymentError<Namespace>>;
 
+    // The configuration of the deployment of a site applying to all of its requests, looked up by
+    // the gateway on every request. It is cached, and dropped from the cache when the deployment
+    // changes.
+    async fn get_config_by_site(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Arc<ApiDeploymentConfig>, ApiDeploymentError<Namespace>>;
+
     async fn get_definitions_by_site(
         &self,
         namespace: &Namespace,
@@ -83,6 +130,78 @@ pub trait ApiDeploymentService<AuthCtx, Namespace> {
         namespace: &Namespace,
         site: &ApiSiteString,
     ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn get_error_pages(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentErrorPages>, ApiDeploymentError<Namespace>>;
+
+    async fn set_error_pages(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        error_pages: &ApiDeploymentErrorPages,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn delete_error_pages(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn get_client_certificates(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentClientCertificates>, ApiDeploymentError<Namespace>>;
+
+    async fn set_client_certificates(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        client_certificates: &ApiDeploymentClientCertificates,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn delete_client_certificates(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn get_dead_letter_queue(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentDeadLetterQueue>, ApiDeploymentError<Namespace>>;
+
+    async fn set_dead_letter_queue(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_queue: &ApiDeploymentDeadLetterQueue,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn delete_dead_letter_queue(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn get_request_fixtures(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentRequestFixtures>, ApiDeploymentError<Namespace>>;
+
+    async fn set_request_fixtures(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        request_fixtures: &ApiDeploymentRequestFixtures,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
+
+    async fn delete_request_fixtures(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>>;
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -91,6 +210,8 @@ pub enum ApiDeploymentError<Namespace> {
     ApiDefinitionNotFound(Namespace, ApiDefinitionId),
     #[error("API deployment not found: {1}")]
     ApiDeploymentNotFound(Namespace, ApiSiteString),
+    #[error("API definition is not deployed: {1}")]
+    ApiDefinitionNotDeployed(Namespace, ApiDefinitionId),
     #[error("API deployment conflict error: {0}")]
     ApiDeploymentConflict(ApiSiteString),
     #[error("API deployment definitions conflict error: {0}")]
@@ -101,6 +222,28 @@ pub enum ApiDeploymentError<Namespace> {
     InternalConversionError { what: String, error: String },
     #[error("Internal error: failed to create component constraints {0}")]
     ComponentConstraintCreateError(String),
+    #[error("Invalid error pages: {0}")]
+    InvalidErrorPages(String),
+    #[error("Invalid CORS configuration: {0}")]
+    InvalidCors(String),
+    #[error("Invalid client certificate configuration: {0}")]
+    InvalidClientCertificates(String),
+    #[error("Invalid dead-letter queue: {0}")]
+    InvalidDeadLetterQueue(String),
+    #[error("Invalid request fixtures: {0}")]
+    InvalidRequestFixtures(String),
+    #[error("Invalid traffic split: {0}")]
+    InvalidTrafficSplit(String),
+    #[error("Invalid experiments: {0}")]
+    InvalidExperiments(String),
+    #[error("Invalid capacity: {0}")]
+    InvalidCapacity(String),
+    #[error("Invalid compression: {0}")]
+    InvalidCompression(String),
+    #[error("Health check failed, the swap was aborted: {0}")]
+    HealthCheckFailed(String),
+    #[error("API deployment was modified concurrently, If-Match precondition failed: {0}")]
+    PreconditionFailed(ApiSiteString),
 }
 
 impl<T> ApiDeploymentError<T> {
@@ -123,15 +266,101 @@ impl<Namespace: Display> SafeDisplay for ApiDeploymentError<Namespace> {
         match self {
             ApiDeploymentError::ApiDefinitionNotFound(_, _) => self.to_string(),
             ApiDeploymentError::ApiDeploymentNotFound(_, _) => self.to_string(),
+            ApiDeploymentError::ApiDefinitionNotDeployed(_, _) => self.to_string(),
             ApiDeploymentError::ApiDeploymentConflict(_) => self.to_string(),
             ApiDeploymentError::ApiDefinitionsConflict(_) => self.to_string(),
             ApiDeploymentError::InternalRepoError(inner) => inner.to_safe_string(),
             ApiDeploymentError::InternalConversionError { .. } => self.to_string(),
             ApiDeploymentError::ComponentConstraintCreateError(_) => self.to_string(),
+            ApiDeploymentError::InvalidErrorPages(_) => self.to_string(),
+            ApiDeploymentError::InvalidCors(_) => self.to_string(),
+            ApiDeploymentError::InvalidClientCertificates(_) => self.to_string(),
+            ApiDeploymentError::InvalidDeadLetterQueue(_) => self.to_string(),
+            ApiDeploymentError::InvalidRequestFixtures(_) => self.to_string(),
+            ApiDeploymentError::InvalidTrafficSplit(_) => self.to_string(),
+            ApiDeploymentError::InvalidExperiments(_) => self.to_string(),
+            ApiDeploymentError::InvalidCapacity(_) => self.to_string(),
+            ApiDeploymentError::InvalidCompression(_) => self.to_string(),
+            ApiDeploymentError::HealthCheckFailed(_) => self.to_string(),
+            ApiDeploymentError::PreconditionFailed(_) => self.to_string(),
+        }
+    }
+}
+
+// The deployment of a site, built from its deployment records
+fn deployment_from_records<Namespace>(
+    existing_deployment_records: Vec<ApiDeploymentRecord>,
+) -> Result<Option<ApiDeployment<Namespace>>, ApiDeploymentError<Namespace>>
+where
+    Namespace: TryFrom<String>,
+    <Namespace as TryFrom<String>>::Error: Display,
+{
+    let mut api_definition_keys: Vec<ApiDefinitionIdWithVersion> = vec![];
+    let mut namespace: Option<Namespace> = None;
+    let mut site: Option<ApiSite> = None;
+    let mut created_at: Option<chrono::DateTime<Utc>> = None;
+    let mut cors: Option<HttpCors> = None;
+    let mut traffic_split: Option<ApiDeploymentTrafficSplit> = None;
+    let mut experiments: Option<Vec<ApiDeploymentExperiment>> = None;
+    let mut capacity: Option<ApiDeploymentCapacity> = None;
+    let mut compression: Option<HttpCompression> = None;
+
+    for deployment_record in existing_deployment_records {
+        if namespace.is_none() {
+            cors = cors_from_record(&deployment_record.cors)?;
+            traffic_split = traffic_split_from_record(&deployment_record.traffic_split)?;
+            experiments = experiments_from_record(&deployment_record.experiments)?;
+            capacity = capacity_from_record(&deployment_record.capacity)?;
+            compression = compression_from_record(&deployment_record.compression)?;
+            namespace = Some(deployment_record.namespace.try_into().map_err(
+                |e: <Namespace as TryFrom<std::string::String>>::Error| {
+                    ApiDeploymentError::conversion_error("API deployment namespace", e.to_string())
+                },
+            )?);
+        }
+
+        if site.is_none() {
+            site = Some(ApiSite {
+                host: deployment_record.host,
+                subdomain: deployment_record.subdomain,
+            });
         }
+
+        if created_at.is_none() || created_at.is_some_and(|t| t > deployment_record.created_at) {
+            created_at = Some(deployment_record.created_at);
+        }
+
+        api_definition_keys.push(ApiDefinitionIdWithVersion {
+            id: deployment_record.definition_id.into(),
+            version: deployment_record.definition_version.into(),
+        });
+    }
+
+    match (site, namespace, created_at) {
+        (Some(site), Some(namespace), Some(created_at)) => Ok(Some(ApiDeployment {
+            namespace,
+            site,
+            api_definition_keys,
+            created_at,
+            cors,
+            traffic_split,
+            experiments,
+            capacity,
+            compression,
+        })),
+        _ => Ok(None),
     }
 }
 
+// Checks the API definitions a site is about to serve, such as with synthetic requests
+#[async_trait]
+pub trait DeploymentHealthCheck<Namespace> {
+    async fn check(
+        &self,
+        definitions: Vec<CompiledHttpApiDefinition<Namespace>>,
+    ) -> Result<(), String>;
+}
+
 pub trait ConflictChecker {
     type Entity: Display + Send;
     fn find_conflicts(input: &[Self]) -> Vec<Self::Entity>
@@ -172,10 +401,36 @@ impl ConflictChecker for HttpApiDefinition {
     }
 }
 
+// The compiled routes of the deployed definitions, keyed by the id, version and content hash of
+// their records, so an updated draft is compiled again
+type CompiledRoutesCache = Cache<(String, String, u64), (), Arc<Vec<CompiledRoute>>, String>;
+
+// The configuration of the deployments by site, along with the time it was loaded. The deployments
+// may be changed through another instance of the service, so the configurations are loaded again
+// once they are older than `DEPLOYMENT_CONFIG_TTL`.
+type DeploymentConfigCache = Cache<ApiSiteString, (), (Instant, Arc<ApiDeploymentConfig>), String>;
+
+const DEPLOYMENT_CONFIG_TTL: Duration = Duration::from_secs(5);
+
 pub struct ApiDeploymentServiceDefault<AuthCtx> {
     pub deployment_repo: Arc<dyn ApiDeploymentRepo + Sync + Send>,
     pub definition_repo: Arc<dyn ApiDefinitionRepo + Sync + Send>,
     pub component_service: Arc<dyn ComponentService<AuthCtx> + Send + Sync>,
+    compiled_routes: CompiledRoutesCache,
+    deployment_configs: DeploymentConfigCache,
+}
+
+// Drops the cached configuration of a site once the changes to its deployment are done, whether
+// they all succeeded or not
+struct DeploymentConfigInvalidation<'a> {
+    deployment_configs: &'a DeploymentConfigCache,
+    site: ApiSiteString,
+}
+
+impl Drop for DeploymentConfigInvalidation<'_> {
+    fn drop(&mut self) {
+        self.deployment_configs.remove(&self.site);
+    }
 }
 
 impl<AuthCtx> ApiDeploymentServiceDefault<AuthCtx> {
@@ -188,9 +443,148 @@ impl<AuthCtx> ApiDeploymentServiceDefault<AuthCtx> {
             deployment_repo,
             definition_repo,
             component_service,
+            compiled_routes: Cache::new(
+                Some(1024),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::None,
+                "compiled_api_definition",
+            ),
+            deployment_configs: Cache::new(
+                Some(1024),
+                FullCacheEvictionMode::LeastRecentlyUsed(1),
+                BackgroundEvictionMode::None,
+                "api_deployment_config",
+            ),
+        }
+    }
+
+    fn invalidate_config_on_drop(&self, site: ApiSiteString) -> DeploymentConfigInvalidation<'_> {
+        DeploymentConfigInvalidation {
+            deployment_configs: &self.deployment_configs,
+            site,
         }
     }
 
+    async fn load_config<Namespace>(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<ApiDeploymentConfig, ApiDeploymentError<Namespace>> {
+        let site = site.to_string();
+
+        let records = self.deployment_repo.get_by_site(&site).await?;
+
+        // The CORS configuration, traffic split, experiments, capacity and compression are kept
+        // the same on all the records of the site
+        let (cors, traffic_split, experiments, capacity, compression) = match records.first() {
+            Some(record) => (
+                cors_from_record(&record.cors)?,
+                traffic_split_from_record(&record.traffic_split)?,
+                experiments_from_record(&record.experiments)?.unwrap_or_default(),
+                capacity_from_record(&record.capacity)?,
+                compression_from_record(&record.compression)?,
+            ),
+            None => (None, None, vec![], None, None),
+        };
+
+        let error_pages = self
+            .deployment_repo
+            .get_error_pages(&site)
+            .await?
+            .map(ApiDeploymentErrorPages::try_from)
+            .transpose()
+            .map_err(|e| ApiDeploymentError::conversion_error("API deployment error pages", e))?;
+
+        let client_certificates = self
+            .deployment_repo
+            .get_client_certificates(&site)
+            .await?
+            .map(|record| {
+                ApiDeploymentClientCertificates::try_from(record).and_then(|client_certificates| {
+                    ClientCertificateVerifier::try_from(&client_certificates)
+                })
+            })
+            .transpose()
+            .map_err(|e| {
+                ApiDeploymentError::conversion_error("API deployment client certificates", e)
+            })?;
+
+        let dead_letter_queue = self
+            .deployment_repo
+            .get_dead_letter_queue(&site)
+            .await?
+            .map(ApiDeploymentDeadLetterQueue::try_from)
+            .transpose()
+            .map_err(|e| {
+                ApiDeploymentError::conversion_error("API deployment dead-letter queue", e)
+            })?;
+
+        let request_fixtures = self
+            .deployment_repo
+            .get_request_fixtures(&site)
+            .await?
+            .map(ApiDeploymentRequestFixtures::try_from)
+            .transpose()
+            .map_err(|e| {
+                ApiDeploymentError::conversion_error("API deployment request fixtures", e)
+            })?;
+
+        Ok(ApiDeploymentConfig {
+            error_pages,
+            cors,
+            client_certificates,
+            dead_letter_queue,
+            request_fixtures,
+            traffic_split,
+            experiments,
+            capacity,
+            compression,
+        })
+    }
+
+    // The definitions of a site are looked up on every request of the gateway, and decoding
+    // their compiled Rib scripts is the most expensive part of it
+    async fn compiled_definition<Namespace>(
+        &self,
+        site: &ApiSiteString,
+        record: ApiDefinitionRecord,
+    ) -> Result<CompiledHttpApiDefinition<Namespace>, String>
+    where
+        Namespace: TryFrom<String>,
+        <Namespace as TryFrom<String>>::Error: Display,
+    {
+        let mut hasher = DefaultHasher::new();
+        record.data.hash(&mut hasher);
+        let key = (record.id.clone(), record.version.clone(), hasher.finish());
+
+        let routes = match self.compiled_routes.get(&key).await {
+            Some(routes) => {
+                gateway_metrics::record_rib_compile_cache(&site.to_string(), true);
+                routes
+            }
+            None => {
+                gateway_metrics::record_rib_compile_cache(&site.to_string(), false);
+                let data = record.data.clone();
+                self.compiled_routes
+                    .get_or_insert_simple(&key, || {
+                        Box::pin(async move { record_data_serde::deserialize(&data).map(Arc::new) })
+                    })
+                    .await?
+            }
+        };
+
+        let namespace = Namespace::try_from(record.namespace)
+            .map_err(|e| format!("Failed to convert namespace: {e}"))?;
+
+        Ok(CompiledHttpApiDefinition {
+            id: record.id.into(),
+            version: record.version.into(),
+            routes: routes.as_ref().clone(),
+            draft: record.draft,
+            created_at: record.created_at,
+            namespace,
+        })
+    }
+
     async fn set_undeployed_as_draft<Namespace>(
         &self,
         deployments: Vec<ApiDeploymentRecord>,
@@ -220,6 +614,142 @@ impl<AuthCtx> ApiDeploymentServiceDefault<AuthCtx> {
         Ok(())
     }
 
+    // Creates the new records of a site, only if its records are still the expected ones when
+    // there are expected records
+    async fn create_deployment_records<Namespace>(
+        &self,
+        site: &ApiSite,
+        expected_records: Option<Vec<ApiDeploymentRecord>>,
+        new_deployment_records: Vec<ApiDeploymentRecord>,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        match expected_records {
+            Some(expected_records) => {
+                if !self
+                    .deployment_repo
+                    .create_if_unchanged(
+                        &site.to_string(),
+                        expected_records,
+                        new_deployment_records,
+                    )
+                    .await?
+                {
+                    return Err(ApiDeploymentError::PreconditionFailed(site.into()));
+                }
+            }
+            None => {
+                if !new_deployment_records.is_empty() {
+                    self.deployment_repo.create(new_deployment_records).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Updates the CORS configuration of all the records of a site, if it changed
+    async fn update_cors<Namespace>(
+        &self,
+        site: &ApiSite,
+        cors: Option<String>,
+        existing_cors: Option<String>,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        if cors != existing_cors {
+            self.deployment_repo
+                .set_cors(&site.to_string(), cors)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // Updates the traffic split of all the records of a site, if it changed
+    async fn update_traffic_split<Namespace>(
+        &self,
+        site: &ApiSite,
+        traffic_split: Option<String>,
+        existing_traffic_split: Option<String>,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        if traffic_split != existing_traffic_split {
+            self.deployment_repo
+                .set_traffic_split(&site.to_string(), traffic_split)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // Updates the experiments of all the records of a site, if they changed
+    async fn update_experiments<Namespace>(
+        &self,
+        site: &ApiSite,
+        experiments: Option<String>,
+        existing_experiments: Option<String>,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        if experiments != existing_experiments {
+            self.deployment_repo
+                .set_experiments(&site.to_string(), experiments)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // Updates the capacity of all the records of a site, if it changed
+    async fn update_capacity<Namespace>(
+        &self,
+        site: &ApiSite,
+        capacity: Option<String>,
+        existing_capacity: Option<String>,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        if capacity != existing_capacity {
+            self.deployment_repo
+                .set_capacity(&site.to_string(), capacity)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // Updates the response compression of all the records of a site, if it changed
+    async fn update_compression<Namespace>(
+        &self,
+        site: &ApiSite,
+        compression: Option<String>,
+        existing_compression: Option<String>,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        if compression != existing_compression {
+            self.deployment_repo
+                .set_compression(&site.to_string(), compression)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    // Checks the site is deployed, and owned by the namespace
+    async fn check_site_owner<Namespace: Display + Clone>(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        let existing_deployment_records =
+            self.deployment_repo.get_by_site(&site.to_string()).await?;
+
+        if existing_deployment_records.is_empty() {
+            Err(ApiDeploymentError::ApiDeploymentNotFound(
+                namespace.clone(),
+                site.clone(),
+            ))
+        } else if existing_deployment_records
+            .iter()
+            .any(|value| value.namespace != namespace.to_string())
+        {
+            Err(ApiDeploymentError::ApiDeploymentConflict(site.clone()))
+        } else {
+            Ok(())
+        }
+    }
+
     fn get_worker_functions_in_api_definitions<Namespace>(
         definitions: Vec<CompiledHttpApiDefinition<Namespace>>,
     ) -> Result<HashMap<ComponentId, FunctionConstraintCollection>, ApiDeploymentError<Namespace>>
@@ -279,17 +809,132 @@ where
         &self,
         deployment: &ApiDeploymentRequest<Namespace>,
         auth_ctx: &AuthCtx,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        self.deploy_with_precondition(deployment, None, auth_ctx)
+            .await
+    }
+
+    async fn deploy_with_precondition(
+        &self,
+        deployment: &ApiDeploymentRequest<Namespace>,
+        expected: Option<&ApiDeployment<Namespace>>,
+        auth_ctx: &AuthCtx,
     ) -> Result<(), ApiDeploymentError<Namespace>> {
         info!(namespace = %deployment.namespace, "Deploy API definitions");
 
+        let _invalidation = self.invalidate_config_on_drop((&deployment.site).into());
+
         let created_at = Utc::now();
 
+        if let Some(cors) = &deployment.cors {
+            cors.validate().map_err(ApiDeploymentError::InvalidCors)?;
+        }
+
+        if let Some(traffic_split) = &deployment.traffic_split {
+            traffic_split
+                .validate()
+                .map_err(ApiDeploymentError::InvalidTrafficSplit)?;
+        }
+
+        if let Some(experiments) = &deployment.experiments {
+            validate_experiments(experiments).map_err(ApiDeploymentError::InvalidExperiments)?;
+        }
+
+        if let Some(capacity) = &deployment.capacity {
+            capacity
+                .validate()
+                .map_err(ApiDeploymentError::InvalidCapacity)?;
+        }
+
+        if let Some(compression) = &deployment.compression {
+            compression
+                .validate()
+                .map_err(ApiDeploymentError::InvalidCompression)?;
+        }
+
         // Existing deployment
         let existing_deployment_records = self
             .deployment_repo
             .get_by_site(&deployment.site.to_string())
             .await?;
 
+        if let Some(expected) = expected {
+            let current = deployment_from_records(existing_deployment_records.clone())?;
+            if current.as_ref() != Some(expected) {
+                return Err(ApiDeploymentError::PreconditionFailed(
+                    (&deployment.site).into(),
+                ));
+            }
+        }
+
+        // Kept to check that the site did not change concurrently when it is written
+        let expected_records = expected.map(|_| existing_deployment_records.clone());
+
+        // Deploying without a CORS configuration keeps the current one of the site
+        let existing_cors = existing_deployment_records
+            .first()
+            .and_then(|record| record.cors.clone());
+
+        let cors = match &deployment.cors {
+            Some(cors) => Some(
+                serde_json::to_string(cors)
+                    .map_err(|e| ApiDeploymentError::conversion_error("CORS", e.to_string()))?,
+            ),
+            None => existing_cors.clone(),
+        };
+
+        // The same goes for the traffic split
+        let existing_traffic_split = existing_deployment_records
+            .first()
+            .and_then(|record| record.traffic_split.clone());
+
+        let traffic_split = match &deployment.traffic_split {
+            Some(traffic_split) => Some(serde_json::to_string(traffic_split).map_err(|e| {
+                ApiDeploymentError::conversion_error("traffic split", e.to_string())
+            })?),
+            None => existing_traffic_split.clone(),
+        };
+
+        // And for the experiments, which are ended by deploying with none
+        let existing_experiments = existing_deployment_records
+            .first()
+            .and_then(|record| record.experiments.clone());
+
+        let experiments =
+            match &deployment.experiments {
+                Some(experiments) if experiments.is_empty() => None,
+                Some(experiments) => Some(serde_json::to_string(experiments).map_err(|e| {
+                    ApiDeploymentError::conversion_error("experiments", e.to_string())
+                })?),
+                None => existing_experiments.clone(),
+            };
+
+        // And for the capacity
+        let existing_capacity = existing_deployment_records
+            .first()
+            .and_then(|record| record.capacity.clone());
+
+        let capacity = match &deployment.capacity {
+            Some(capacity) => Some(
+                serde_json::to_string(capacity)
+                    .map_err(|e| ApiDeploymentError::conversion_error("capacity", e.to_string()))?,
+            ),
+            None => existing_capacity.clone(),
+        };
+
+        // And for the response compression
+        let existing_compression = existing_deployment_records
+            .first()
+            .and_then(|record| record.compression.clone());
+
+        let compression =
+            match &deployment.compression {
+                Some(compression) => Some(serde_json::to_string(compression).map_err(|e| {
+                    ApiDeploymentError::conversion_error("compression", e.to_string())
+                })?),
+                None => existing_compression.clone(),
+            };
+
         let mut existing_api_definition_keys: HashSet<ApiDefinitionIdWithVersion> = HashSet::new();
 
         for deployment_record in existing_deployment_records {
@@ -355,6 +1000,11 @@ where
                     deployment.site.clone(),
                     api_definition_key,
                     created_at,
+                    cors.clone(),
+                    traffic_split.clone(),
+                    experiments.clone(),
+                    capacity.clone(),
+                    compression.clone(),
                 ));
             }
         }
@@ -365,13 +1015,24 @@ where
 
         new_definitions.extend(existing_definitions);
 
-        let conflicting_definitions = HttpApiDefinition::find_conflicts(
-            new_definitions
-                .clone()
-                .into_iter()
-                .map(|x| x.into())
-                .collect::<Vec<HttpApiDefinition>>()
-                .as_slice(),
+        if let Some(traffic_split) = &deployment.traffic_split {
+            for key in [traffic_split.stable(), traffic_split.canary()] {
+                if !new_definitions
+                    .iter()
+                    .any(|def| def.id == key.id && def.version == key.version)
+                {
+                    return Err(ApiDeploymentError::InvalidTrafficSplit(format!(
+                        "version {} of API definition {} is not deployed to the site",
+                        key.version, key.id
+                    )));
+                }
+            }
+        }
+
+        // The two versions of a split are not served together, so their routes can be the same
+        let conflicting_definitions = find_deployment_conflicts(
+            &new_definitions,
+            traffic_split_from_record(&traffic_split)?.as_ref(),
         );
 
         if !conflicting_definitions.is_empty() {
@@ -417,10 +1078,35 @@ where
                     })?;
             }
 
-            self.deployment_repo.create(new_deployment_records).await?;
-            Ok(())
-        } else {
-            Ok(())
+            self.create_deployment_records(
+                &deployment.site,
+                expected_records,
+                new_deployment_records,
+            )
+            .await?;
+            self.update_cors(&deployment.site, cors, existing_cors)
+                .await?;
+            self.update_traffic_split(&deployment.site, traffic_split, existing_traffic_split)
+                .await?;
+            self.update_experiments(&deployment.site, experiments, existing_experiments)
+                .await?;
+            self.update_capacity(&deployment.site, capacity, existing_capacity)
+                .await?;
+            self.update_compression(&deployment.site, compression, existing_compression)
+                .await
+        } else {
+            self.create_deployment_records(&deployment.site, expected_records, vec![])
+                .await?;
+            self.update_cors(&deployment.site, cors, existing_cors)
+                .await?;
+            self.update_traffic_split(&deployment.site, traffic_split, existing_traffic_split)
+                .await?;
+            self.update_experiments(&deployment.site, experiments, existing_experiments)
+                .await?;
+            self.update_capacity(&deployment.site, capacity, existing_capacity)
+                .await?;
+            self.update_compression(&deployment.site, compression, existing_compression)
+                .await
         }
     }
 
@@ -430,12 +1116,19 @@ where
     ) -> Result<(), ApiDeploymentError<Namespace>> {
         info!(namespace = %deployment.namespace, "Undeploying API definitions");
 
+        let _invalidation = self.invalidate_config_on_drop((&deployment.site).into());
+
         // Existing deployment
         let existing_deployment_records = self
             .deployment_repo
             .get_by_site(&deployment.site.to_string())
             .await?;
 
+        let traffic_split = match existing_deployment_records.first() {
+            Some(record) => traffic_split_from_record(&record.traffic_split)?,
+            None => None,
+        };
+
         let mut remove_deployment_records: Vec<ApiDeploymentRecord> = vec![];
 
         for deployment_record in existing_deployment_records {
@@ -470,6 +1163,20 @@ where
                 .delete(remove_deployment_records.clone())
                 .await?;
 
+            // Undeploying either version of a split ends it
+            if let Some(traffic_split) = traffic_split {
+                if remove_deployment_records.iter().any(|record| {
+                    traffic_split.is_split(&ApiDefinitionIdWithVersion {
+                        id: record.definition_id.clone().into(),
+                        version: record.definition_version.clone().into(),
+                    })
+                }) {
+                    self.deployment_repo
+                        .set_traffic_split(&deployment.site.to_string(), None)
+                        .await?;
+                }
+            }
+
             self.set_undeployed_as_draft(remove_deployment_records)
                 .await?;
         }
@@ -477,6 +1184,289 @@ where
         Ok(())
     }
 
+    async fn rollback(
+        &self,
+        namespace: &Namespace,
+        api_definition_key: &ApiDefinitionIdWithVersion,
+        auth_ctx: &AuthCtx,
+    ) -> Result<Vec<ApiSiteString>, ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Roll back API definition");
+
+        let created_at = Utc::now();
+
+        let record = self
+            .definition_repo
+            .get(
+                namespace.to_string().as_str(),
+                api_definition_key.id.0.as_str(),
+                api_definition_key.version.0.as_str(),
+            )
+            .await?
+            .ok_or(ApiDeploymentError::ApiDefinitionNotFound(
+                namespace.clone(),
+                api_definition_key.id.clone(),
+            ))?;
+
+        let draft = record.draft;
+
+        let definition: CompiledHttpApiDefinition<Namespace> = record
+            .try_into()
+            .map_err(|e| ApiDeploymentError::conversion_error("API definition record", e))?;
+
+        let deployment_records = self
+            .deployment_repo
+            .get_by_id(
+                namespace.to_string().as_str(),
+                api_definition_key.id.0.as_str(),
+            )
+            .await?;
+
+        let mut sites = deployment_records
+            .iter()
+            .map(|record| record.site.clone())
+            .collect::<Vec<_>>();
+        sites.sort();
+        sites.dedup();
+
+        let mut removed_records: Vec<ApiDeploymentRecord> = vec![];
+        let mut added_records: Vec<ApiDeploymentRecord> = vec![];
+        let mut updated_sites: Vec<ApiSiteString> = vec![];
+
+        for site in sites {
+            let site_records = deployment_records
+                .iter()
+                .filter(|record| record.site == site)
+                .collect::<Vec<_>>();
+
+            let replaced_records = site_records
+                .iter()
+                .filter(|record| record.definition_version != api_definition_key.version.0)
+                .map(|record| (*record).clone())
+                .collect::<Vec<_>>();
+
+            let Some(replaced_record) = replaced_records.first().cloned() else {
+                continue;
+            };
+
+            let site = ApiSiteString(site);
+
+            // The definitions of the site once rolled back must not conflict with each other
+            let mut definitions = self
+                .get_definitions_by_site(namespace, &site)
+                .await?
+                .into_iter()
+                .filter(|existing| existing.id != api_definition_key.id)
+                .collect::<Vec<_>>();
+            definitions.push(definition.clone());
+
+            let conflicting_definitions = HttpApiDefinition::find_conflicts(
+                definitions
+                    .into_iter()
+                    .map(|x| x.into())
+                    .collect::<Vec<HttpApiDefinition>>()
+                    .as_slice(),
+            );
+
+            if !conflicting_definitions.is_empty() {
+                return Err(ApiDeploymentError::ApiDefinitionsConflict(
+                    conflicting_definitions
+                        .iter()
+                        .map(|def| format!("{}", def))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ));
+            }
+
+            if site_records.len() == replaced_records.len() {
+                added_records.push(ApiDeploymentRecord {
+                    definition_version: api_definition_key.version.0.clone(),
+                    created_at,
+                    ..replaced_record
+                });
+            }
+
+            removed_records.extend(replaced_records);
+            updated_sites.push(site);
+        }
+
+        if updated_sites.is_empty() {
+            return Err(ApiDeploymentError::ApiDefinitionNotDeployed(
+                namespace.clone(),
+                api_definition_key.id.clone(),
+            ));
+        }
+
+        let constraints = Self::get_worker_functions_in_api_definitions(vec![definition])?;
+
+        for (component_id, constraints) in constraints {
+            self.component_service
+                .create_or_update_constraints(&component_id, constraints, auth_ctx)
+                .await
+                .map_err(|err| {
+                    ApiDeploymentError::ComponentConstraintCreateError(err.to_safe_string())
+                })?;
+        }
+
+        if draft {
+            self.definition_repo
+                .set_draft(
+                    namespace.to_string().as_str(),
+                    api_definition_key.id.0.as_str(),
+                    api_definition_key.version.0.as_str(),
+                    false,
+                )
+                .await?;
+        }
+
+        // The gateways look up the deployments of a site on each request, so replacing the
+        // records at once switches all of them to the rolled back version together
+        self.deployment_repo
+            .replace(removed_records.clone(), added_records)
+            .await?;
+
+        for site in &updated_sites {
+            self.deployment_configs.remove(site);
+        }
+
+        self.set_undeployed_as_draft(removed_records).await?;
+
+        Ok(updated_sites)
+    }
+
+    async fn swap(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        api_definition_key: &ApiDefinitionIdWithVersion,
+        health_check: Option<&(dyn DeploymentHealthCheck<Namespace> + Sync + Send)>,
+        auth_ctx: &AuthCtx,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Swap API definition");
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        let site_records = self.deployment_repo.get_by_site(&site.to_string()).await?;
+
+        let replaced_records = site_records
+            .iter()
+            .filter(|record| {
+                record.definition_id == api_definition_key.id.0
+                    && record.definition_version != api_definition_key.version.0
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let Some(replaced_record) = replaced_records.first().cloned() else {
+            return Err(ApiDeploymentError::ApiDefinitionNotDeployed(
+                namespace.clone(),
+                api_definition_key.id.clone(),
+            ));
+        };
+
+        let record = self
+            .definition_repo
+            .get(
+                namespace.to_string().as_str(),
+                api_definition_key.id.0.as_str(),
+                api_definition_key.version.0.as_str(),
+            )
+            .await?
+            .ok_or(ApiDeploymentError::ApiDefinitionNotFound(
+                namespace.clone(),
+                api_definition_key.id.clone(),
+            ))?;
+
+        let draft = record.draft;
+
+        let definition: CompiledHttpApiDefinition<Namespace> = record
+            .try_into()
+            .map_err(|e| ApiDeploymentError::conversion_error("API definition record", e))?;
+
+        let mut definitions = self
+            .get_definitions_by_site(namespace, site)
+            .await?
+            .into_iter()
+            .filter(|existing| existing.id != api_definition_key.id)
+            .collect::<Vec<_>>();
+        definitions.push(definition.clone());
+
+        let conflicting_definitions = find_deployment_conflicts(&definitions, None);
+
+        if !conflicting_definitions.is_empty() {
+            return Err(ApiDeploymentError::ApiDefinitionsConflict(
+                conflicting_definitions
+                    .iter()
+                    .map(|def| format!("{}", def))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+
+        if let Some(health_check) = health_check {
+            health_check.check(definitions).await.map_err(|err| {
+                info!(namespace = %namespace, "Swap API definition - failed health check: {}", err);
+                ApiDeploymentError::HealthCheckFailed(err)
+            })?;
+        }
+
+        let constraints = Self::get_worker_functions_in_api_definitions(vec![definition])?;
+
+        for (component_id, constraints) in constraints {
+            self.component_service
+                .create_or_update_constraints(&component_id, constraints, auth_ctx)
+                .await
+                .map_err(|err| {
+                    ApiDeploymentError::ComponentConstraintCreateError(err.to_safe_string())
+                })?;
+        }
+
+        if draft {
+            self.definition_repo
+                .set_draft(
+                    namespace.to_string().as_str(),
+                    api_definition_key.id.0.as_str(),
+                    api_definition_key.version.0.as_str(),
+                    false,
+                )
+                .await?;
+        }
+
+        // The site keeps serving the target version if it already did, such as the canary
+        // version of a traffic split
+        let added_records = if !site_records.iter().any(|record| {
+            record.definition_id == api_definition_key.id.0
+                && record.definition_version == api_definition_key.version.0
+        }) {
+            vec![ApiDeploymentRecord {
+                definition_version: api_definition_key.version.0.clone(),
+                created_at: Utc::now(),
+                ..replaced_record
+            }]
+        } else {
+            vec![]
+        };
+
+        // The gateways look up the deployments of a site on each request, so replacing the
+        // records at once switches all of them to the new version together
+        self.deployment_repo
+            .replace(replaced_records.clone(), added_records)
+            .await?;
+
+        if let Some(traffic_split) = traffic_split_from_record(&replaced_record.traffic_split)? {
+            if traffic_split.api_definition_id == api_definition_key.id {
+                self.deployment_repo
+                    .set_traffic_split(&site.to_string(), None)
+                    .await?;
+            }
+        }
+
+        self.set_undeployed_as_draft(replaced_records).await?;
+
+        Ok(())
+    }
+
     async fn get_by_id(
         &self,
         namespace: &Namespace,
@@ -529,6 +1519,11 @@ where
                         namespace,
                         api_definition_keys: vec![api_definition_key],
                         created_at: deployment_record.created_at,
+                        cors: cors_from_record(&deployment_record.cors)?,
+                        traffic_split: traffic_split_from_record(&deployment_record.traffic_split)?,
+                        experiments: experiments_from_record(&deployment_record.experiments)?,
+                        capacity: capacity_from_record(&deployment_record.capacity)?,
+                        compression: compression_from_record(&deployment_record.compression)?,
                     });
                 }
             }
@@ -545,50 +1540,31 @@ where
         let existing_deployment_records =
             self.deployment_repo.get_by_site(&site.to_string()).await?;
 
-        let mut api_definition_keys: Vec<ApiDefinitionIdWithVersion> = vec![];
-        let mut namespace: Option<Namespace> = None;
-        let mut site: Option<ApiSite> = None;
-        let mut created_at: Option<chrono::DateTime<Utc>> = None;
+        deployment_from_records(existing_deployment_records)
+    }
 
-        for deployment_record in existing_deployment_records {
-            if namespace.is_none() {
-                namespace = Some(deployment_record.namespace.try_into().map_err(
-                    |e: <Namespace as TryFrom<std::string::String>>::Error| {
-                        ApiDeploymentError::conversion_error(
-                            "API deployment namespace",
-                            e.to_string(),
-                        )
-                    },
-                )?);
+    async fn get_config_by_site(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Arc<ApiDeploymentConfig>, ApiDeploymentError<Namespace>> {
+        if let Some((loaded_at, config)) = self.deployment_configs.get(site).await {
+            if loaded_at.elapsed() < DEPLOYMENT_CONFIG_TTL {
+                return Ok(config);
             }
+            self.deployment_configs.remove(site);
+        }
 
-            if site.is_none() {
-                site = Some(ApiSite {
-                    host: deployment_record.host,
-                    subdomain: deployment_record.subdomain,
-                });
-            }
+        let config = Arc::new(self.load_config(site).await?);
 
-            if created_at.is_none() || created_at.is_some_and(|t| t > deployment_record.created_at)
-            {
-                created_at = Some(deployment_record.created_at);
-            }
+        let (_, config) = self
+            .deployment_configs
+            .get_or_insert_simple(site, || {
+                Box::pin(async move { Ok((Instant::now(), config)) })
+            })
+            .await
+            .map_err(|e| ApiDeploymentError::conversion_error("API deployment config", e))?;
 
-            api_definition_keys.push(ApiDefinitionIdWithVersion {
-                id: deployment_record.definition_id.into(),
-                version: deployment_record.definition_version.into(),
-            });
-        }
-
-        match (site, namespace, created_at) {
-            (Some(site), Some(namespace), Some(created_at)) => Ok(Some(ApiDeployment {
-                namespace,
-                site,
-                api_definition_keys,
-                created_at,
-            })),
-            _ => Ok(None),
-        }
+        Ok(config)
     }
 
     async fn get_definitions_by_site(
@@ -629,7 +1605,7 @@ where
 
         for record in records {
             values.push(
-                record.try_into().map_err(|e| {
+                self.compiled_definition(site, record).await.map_err(|e| {
                     ApiDeploymentError::conversion_error("API definition record", e)
                 })?,
             );
@@ -662,16 +1638,365 @@ where
 
             Err(ApiDeploymentError::ApiDeploymentConflict(site.clone()))
         } else {
+            let _invalidation = self.invalidate_config_on_drop(site.clone());
+
             self.deployment_repo
                 .delete(existing_deployment_records.clone())
                 .await?;
 
+            self.deployment_repo
+                .delete_error_pages(&site.to_string())
+                .await?;
+
+            self.deployment_repo
+                .delete_client_certificates(&site.to_string())
+                .await?;
+
+            self.deployment_repo
+                .delete_dead_letter_queue(&site.to_string())
+                .await?;
+
+            self.deployment_repo
+                .delete_request_fixtures(&site.to_string())
+                .await?;
+
             self.set_undeployed_as_draft(existing_deployment_records)
                 .await?;
 
             Ok(())
         }
     }
+
+    async fn get_error_pages(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentErrorPages>, ApiDeploymentError<Namespace>> {
+        let record = self
+            .deployment_repo
+            .get_error_pages(&site.to_string())
+            .await?;
+
+        record
+            .map(ApiDeploymentErrorPages::try_from)
+            .transpose()
+            .map_err(|e| ApiDeploymentError::conversion_error("API deployment error pages", e))
+    }
+
+    async fn set_error_pages(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        error_pages: &ApiDeploymentErrorPages,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Set API deployment error pages");
+
+        error_pages
+            .validate()
+            .map_err(ApiDeploymentError::InvalidErrorPages)?;
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        let record = ApiDeploymentErrorPagesRecord::new(namespace, site, error_pages)
+            .map_err(|e| ApiDeploymentError::conversion_error("API deployment error pages", e))?;
+
+        self.deployment_repo.set_error_pages(record).await?;
+
+        Ok(())
+    }
+
+    async fn delete_error_pages(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Delete API deployment error pages");
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        self.deployment_repo
+            .delete_error_pages(&site.to_string())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_client_certificates(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentClientCertificates>, ApiDeploymentError<Namespace>> {
+        let record = self
+            .deployment_repo
+            .get_client_certificates(&site.to_string())
+            .await?;
+
+        record
+            .map(ApiDeploymentClientCertificates::try_from)
+            .transpose()
+            .map_err(|e| {
+                ApiDeploymentError::conversion_error("API deployment client certificates", e)
+            })
+    }
+
+    async fn set_client_certificates(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        client_certificates: &ApiDeploymentClientCertificates,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Set API deployment client certificates");
+
+        client_certificates
+            .validate()
+            .map_err(ApiDeploymentError::InvalidClientCertificates)?;
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        let record =
+            ApiDeploymentClientCertificatesRecord::new(namespace, site, client_certificates)
+                .map_err(|e| {
+                    ApiDeploymentError::conversion_error("API deployment client certificates", e)
+                })?;
+
+        self.deployment_repo.set_client_certificates(record).await?;
+
+        Ok(())
+    }
+
+    async fn delete_client_certificates(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Delete API deployment client certificates");
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        self.deployment_repo
+            .delete_client_certificates(&site.to_string())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_dead_letter_queue(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentDeadLetterQueue>, ApiDeploymentError<Namespace>> {
+        let record = self
+            .deployment_repo
+            .get_dead_letter_queue(&site.to_string())
+            .await?;
+
+        record
+            .map(ApiDeploymentDeadLetterQueue::try_from)
+            .transpose()
+            .map_err(|e| {
+                ApiDeploymentError::conversion_error("API deployment dead-letter queue", e)
+            })
+    }
+
+    async fn set_dead_letter_queue(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_queue: &ApiDeploymentDeadLetterQueue,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Set API deployment dead-letter queue");
+
+        dead_letter_queue
+            .validate()
+            .map_err(ApiDeploymentError::InvalidDeadLetterQueue)?;
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        let record = ApiDeploymentDeadLetterQueueRecord::new(namespace, site, dead_letter_queue)
+            .map_err(|e| {
+                ApiDeploymentError::conversion_error("API deployment dead-letter queue", e)
+            })?;
+
+        self.deployment_repo.set_dead_letter_queue(record).await?;
+
+        Ok(())
+    }
+
+    async fn delete_dead_letter_queue(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Delete API deployment dead-letter queue");
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        self.deployment_repo
+            .delete_dead_letter_queue(&site.to_string())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_request_fixtures(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<Option<ApiDeploymentRequestFixtures>, ApiDeploymentError<Namespace>> {
+        let record = self
+            .deployment_repo
+            .get_request_fixtures(&site.to_string())
+            .await?;
+
+        record
+            .map(ApiDeploymentRequestFixtures::try_from)
+            .transpose()
+            .map_err(|e| ApiDeploymentError::conversion_error("API deployment request fixtures", e))
+    }
+
+    async fn set_request_fixtures(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        request_fixtures: &ApiDeploymentRequestFixtures,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Set API deployment request fixtures");
+
+        request_fixtures
+            .validate()
+            .map_err(ApiDeploymentError::InvalidRequestFixtures)?;
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        let record = ApiDeploymentRequestFixturesRecord::new(namespace, site, request_fixtures)
+            .map_err(|e| {
+                ApiDeploymentError::conversion_error("API deployment request fixtures", e)
+            })?;
+
+        self.deployment_repo.set_request_fixtures(record).await?;
+
+        Ok(())
+    }
+
+    async fn delete_request_fixtures(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<(), ApiDeploymentError<Namespace>> {
+        info!(namespace = %namespace, "Delete API deployment request fixtures");
+
+        self.check_site_owner(namespace, site).await?;
+
+        let _invalidation = self.invalidate_config_on_drop(site.clone());
+
+        self.deployment_repo
+            .delete_request_fixtures(&site.to_string())
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn cors_from_record<Namespace>(
+    cors: &Option<String>,
+) -> Result<Option<HttpCors>, ApiDeploymentError<Namespace>> {
+    cors.as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| ApiDeploymentError::conversion_error("API deployment CORS", e.to_string()))
+}
+
+fn traffic_split_from_record<Namespace>(
+    traffic_split: &Option<String>,
+) -> Result<Option<ApiDeploymentTrafficSplit>, ApiDeploymentError<Namespace>> {
+    traffic_split
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| {
+            ApiDeploymentError::conversion_error("API deployment traffic split", e.to_string())
+        })
+}
+
+fn experiments_from_record<Namespace>(
+    experiments: &Option<String>,
+) -> Result<Option<Vec<ApiDeploymentExperiment>>, ApiDeploymentError<Namespace>> {
+    experiments
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| {
+            ApiDeploymentError::conversion_error("API deployment experiments", e.to_string())
+        })
+}
+
+fn capacity_from_record<Namespace>(
+    capacity: &Option<String>,
+) -> Result<Option<ApiDeploymentCapacity>, ApiDeploymentError<Namespace>> {
+    capacity
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| ApiDeploymentError::conversion_error("API deployment capacity", e.to_string()))
+}
+
+fn compression_from_record<Namespace>(
+    compression: &Option<String>,
+) -> Result<Option<HttpCompression>, ApiDeploymentError<Namespace>> {
+    compression
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| {
+            ApiDeploymentError::conversion_error("API deployment compression", e.to_string())
+        })
+}
+
+// The routes of the definitions deployed to a site that conflict with each other. With a traffic
+// split, a request is served either with the stable or with the canary version of the definition,
+// so each of them is checked against the other definitions only.
+fn find_deployment_conflicts<Namespace: Clone>(
+    definitions: &[CompiledHttpApiDefinition<Namespace>],
+    traffic_split: Option<&ApiDeploymentTrafficSplit>,
+) -> Vec<AllPathPatterns> {
+    let find_conflicts = |left_out: Option<ApiDefinitionIdWithVersion>| {
+        HttpApiDefinition::find_conflicts(
+            definitions
+                .iter()
+                .filter(|def| {
+                    left_out
+                        .as_ref()
+                        .is_none_or(|key| !(def.id == key.id && def.version == key.version))
+                })
+                .cloned()
+                .map(|x| x.into())
+                .collect::<Vec<HttpApiDefinition>>()
+                .as_slice(),
+        )
+    };
+
+    match traffic_split {
+        Some(traffic_split) => {
+            let mut conflicts = find_conflicts(Some(traffic_split.canary()));
+            for conflict in find_conflicts(Some(traffic_split.stable())) {
+                if !conflicts.contains(&conflict) {
+                    conflicts.push(conflict);
+                }
+            }
+            conflicts
+        }
+        None => find_conflicts(None),
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,323 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_security::{
+    ApiKey, ApiKeySecurityScheme, ApiKeyValidationError, ApiKeyValidator, HashedApiKeySecret,
+    PlaintextApiKey, RegisteredSecurityScheme, SecuritySchemeIdentifier,
+};
+use crate::repo::api_key::{ApiKeyRecord, ApiKeyRepo};
+use crate::service::gateway::security_scheme::{SecuritySchemeService, SecuritySchemeServiceError};
+use async_trait::async_trait;
+use golem_common::SafeDisplay;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use tracing::info;
+
+// Manages the keys of API key security schemes.
+// The plaintext key is only ever returned on creation and rotation, as only its hash is stored
+#[async_trait]
+pub trait ApiKeyService<Namespace> {
+    async fn create(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        name: String,
+        metadata: BTreeMap<String, String>,
+    ) -> Result<(ApiKey, PlaintextApiKey), ApiKeyServiceError>;
+
+    // Replaces the secret of the key, invalidating the previous one immediately
+    async fn rotate(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        key_id: &str,
+    ) -> Result<(ApiKey, PlaintextApiKey), ApiKeyServiceError>;
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+    ) -> Result<Vec<ApiKey>, ApiKeyServiceError>;
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        key_id: &str,
+    ) -> Result<(), ApiKeyServiceError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum ApiKeyServiceError {
+    SecuritySchemeError(SecuritySchemeServiceError),
+    NotAnApiKeyScheme(SecuritySchemeIdentifier),
+    KeyNotFound(String),
+    InvalidName(String),
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for ApiKeyServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for ApiKeyServiceError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            ApiKeyServiceError::SecuritySchemeError(err) => err.to_safe_string(),
+            ApiKeyServiceError::NotAnApiKeyScheme(identifier) => {
+                format!("SecurityScheme {} is not an API key scheme", identifier)
+            }
+            ApiKeyServiceError::KeyNotFound(key_id) => format!("API key not found: {}", key_id),
+            ApiKeyServiceError::InvalidName(err) => format!("Invalid API key name: {}", err),
+            ApiKeyServiceError::InternalError(err) => format!("InternalError: {}", err),
+        }
+    }
+}
+
+impl From<SecuritySchemeServiceError> for ApiKeyServiceError {
+    fn from(value: SecuritySchemeServiceError) -> Self {
+        ApiKeyServiceError::SecuritySchemeError(value)
+    }
+}
+
+pub struct DefaultApiKeyService<Namespace> {
+    repo: Arc<dyn ApiKeyRepo + Sync + Send>,
+    security_scheme_service: Arc<dyn SecuritySchemeService<Namespace> + Sync + Send>,
+}
+
+impl<Namespace> DefaultApiKeyService<Namespace> {
+    pub fn new(
+        repo: Arc<dyn ApiKeyRepo + Sync + Send>,
+        security_scheme_service: Arc<dyn SecuritySchemeService<Namespace> + Sync + Send>,
+    ) -> Self {
+        DefaultApiKeyService {
+            repo,
+            security_scheme_service,
+        }
+    }
+}
+
+impl<Namespace: Display + Send + Sync> DefaultApiKeyService<Namespace> {
+    async fn get_api_key_scheme(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+    ) -> Result<ApiKeySecurityScheme, ApiKeyServiceError> {
+        match self
+            .security_scheme_service
+            .get_registered(security_scheme_identifier, namespace)
+            .await?
+        {
+            RegisteredSecurityScheme::ApiKey(scheme) => Ok(scheme),
+            _ => Err(ApiKeyServiceError::NotAnApiKeyScheme(
+                security_scheme_identifier.clone(),
+            )),
+        }
+    }
+
+    async fn get_api_key(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        key_id: &str,
+    ) -> Result<ApiKey, ApiKeyServiceError> {
+        let record = self
+            .repo
+            .get(
+                &namespace.to_string(),
+                &security_scheme_identifier.to_string(),
+                key_id,
+            )
+            .await
+            .map_err(|err| ApiKeyServiceError::InternalError(err.to_safe_string()))?
+            .ok_or(ApiKeyServiceError::KeyNotFound(key_id.to_string()))?;
+
+        ApiKey::try_from(record).map_err(ApiKeyServiceError::InternalError)
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> ApiKeyService<Namespace>
+    for DefaultApiKeyService<Namespace>
+{
+    async fn create(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        name: String,
+        metadata: BTreeMap<String, String>,
+    ) -> Result<(ApiKey, PlaintextApiKey), ApiKeyServiceError> {
+        if name.trim().is_empty() {
+            return Err(ApiKeyServiceError::InvalidName(
+                "name must not be empty".to_string(),
+            ));
+        }
+
+        self.get_api_key_scheme(namespace, security_scheme_identifier)
+            .await?;
+
+        let plaintext = PlaintextApiKey::generate();
+
+        let api_key = ApiKey {
+            key_id: plaintext.key_id.clone(),
+            name,
+            metadata,
+            created_at: chrono::Utc::now(),
+        };
+
+        let record = ApiKeyRecord::new(
+            namespace.to_string(),
+            security_scheme_identifier.to_string(),
+            &api_key,
+            HashedApiKeySecret::new(&plaintext.secret),
+        )
+        .map_err(ApiKeyServiceError::InternalError)?;
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| ApiKeyServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            "API key {} created for security scheme {}",
+            api_key.key_id, security_scheme_identifier
+        );
+
+        Ok((api_key, plaintext))
+    }
+
+    async fn rotate(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        key_id: &str,
+    ) -> Result<(ApiKey, PlaintextApiKey), ApiKeyServiceError> {
+        let api_key = self
+            .get_api_key(namespace, security_scheme_identifier, key_id)
+            .await?;
+
+        let plaintext = PlaintextApiKey::rotate(key_id);
+
+        let updated = self
+            .repo
+            .update_secret(
+                &namespace.to_string(),
+                &security_scheme_identifier.to_string(),
+                key_id,
+                &HashedApiKeySecret::new(&plaintext.secret),
+            )
+            .await
+            .map_err(|err| ApiKeyServiceError::InternalError(err.to_safe_string()))?;
+
+        if !updated {
+            return Err(ApiKeyServiceError::KeyNotFound(key_id.to_string()));
+        }
+
+        info!(
+            "API key {} rotated for security scheme {}",
+            key_id, security_scheme_identifier
+        );
+
+        Ok((api_key, plaintext))
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+    ) -> Result<Vec<ApiKey>, ApiKeyServiceError> {
+        self.get_api_key_scheme(namespace, security_scheme_identifier)
+            .await?;
+
+        let records = self
+            .repo
+            .get_all(
+                &namespace.to_string(),
+                &security_scheme_identifier.to_string(),
+            )
+            .await
+            .map_err(|err| ApiKeyServiceError::InternalError(err.to_safe_string()))?;
+
+        records
+            .into_iter()
+            .map(|record| ApiKey::try_from(record).map_err(ApiKeyServiceError::InternalError))
+            .collect()
+    }
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        key_id: &str,
+    ) -> Result<(), ApiKeyServiceError> {
+        let deleted = self
+            .repo
+            .delete(
+                &namespace.to_string(),
+                &security_scheme_identifier.to_string(),
+                key_id,
+            )
+            .await
+            .map_err(|err| ApiKeyServiceError::InternalError(err.to_safe_string()))?;
+
+        if deleted {
+            info!(
+                "API key {} deleted from security scheme {}",
+                key_id, security_scheme_identifier
+            );
+            Ok(())
+        } else {
+            Err(ApiKeyServiceError::KeyNotFound(key_id.to_string()))
+        }
+    }
+}
+
+// Keys are looked up on every request rather than cached,
+// so that deleting or rotating a key takes effect immediately
+#[async_trait]
+impl<Namespace: Display + Send + Sync> ApiKeyValidator<Namespace>
+    for DefaultApiKeyService<Namespace>
+{
+    async fn validate(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &ApiKeySecurityScheme,
+        presented_key: &str,
+    ) -> Result<ApiKey, ApiKeyValidationError> {
+        let presented_key =
+            PlaintextApiKey::parse(presented_key).ok_or(ApiKeyValidationError::InvalidKey)?;
+
+        let record = self
+            .repo
+            .get(
+                &namespace.to_string(),
+                &security_scheme.scheme_identifier().to_string(),
+                &presented_key.key_id,
+            )
+            .await
+            .map_err(|err| ApiKeyValidationError::InternalError(err.to_safe_string()))?
+            .ok_or(ApiKeyValidationError::InvalidKey)?;
+
+        if !record.hashed_secret().matches(&presented_key.secret) {
+            return Err(ApiKeyValidationError::InvalidKey);
+        }
+
+        ApiKey::try_from(record).map_err(ApiKeyValidationError::InternalError)
+    }
+}
@@ -0,0 +1,520 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_deployment::{ApiDeploymentDeadLetterQueue, ApiSiteString};
+use crate::gateway_execution::gateway_http_input_executor::GatewayHttpInputExecutor;
+use crate::repo::dead_letter::{DeadLetterRecord, DeadLetterRepo};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use golem_common::SafeDisplay;
+use http::{HeaderName, Uri};
+use poem::Body;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+// Marks the replays of dead letters, whose failures are not added to the queue again
+#[derive(Debug, Clone, Copy)]
+pub struct DeadLetterReplay;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct DeadLetterHeader {
+    pub name: String,
+    pub value: String,
+}
+
+// The parts of a failed request kept in the dead-letter queue
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetterRequest {
+    pub method: String,
+    pub uri: String,
+    pub headers: Vec<DeadLetterHeader>,
+    // None if the body was larger than the limit of the queue
+    pub body: Option<Vec<u8>>,
+}
+
+impl DeadLetterRequest {
+    pub async fn capture(
+        dead_letter_queue: &ApiDeploymentDeadLetterQueue,
+        request: &mut poem::Request,
+    ) -> Self {
+        Self::capture_with(
+            request,
+            |name| dead_letter_queue.is_redacted(name),
+            |size| dead_letter_queue.keeps_body_of_size(size),
+        )
+        .await
+    }
+
+    // Buffers the body of the request, which stays readable by the binding
+    pub async fn capture_with(
+        request: &mut poem::Request,
+        is_redacted: impl Fn(&HeaderName) -> bool,
+        keeps_body_of_size: impl Fn(usize) -> bool,
+    ) -> Self {
+        let headers = request
+            .headers()
+            .iter()
+            .filter(|(name, _)| !is_redacted(name))
+            .filter_map(|(name, value)| {
+                value.to_str().ok().map(|value| DeadLetterHeader {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+            })
+            .collect();
+
+        let content_length = request
+            .headers()
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<usize>().ok());
+
+        // Bodies known to be too large are left to be streamed to the binding
+        let body = if content_length.is_some_and(|length| !keeps_body_of_size(length)) {
+            None
+        } else {
+            match request.take_body().into_bytes().await {
+                Ok(bytes) => {
+                    request.set_body(Body::from_bytes(bytes.clone()));
+                    Some(bytes.to_vec()).filter(|body| keeps_body_of_size(body.len()))
+                }
+                // The binding fails on the same error when reading the body
+                Err(_) => None,
+            }
+        };
+
+        Self {
+            method: request.method().to_string(),
+            uri: request.uri().to_string(),
+            headers,
+            body,
+        }
+    }
+}
+
+/// A request kept in the dead-letter queue of a deployment after its worker invocation failed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct DeadLetter {
+    pub dead_letter_id: String,
+    pub site: String,
+    /// The path pattern of the route of the request
+    pub route: String,
+    pub method: String,
+    pub uri: String,
+    /// The headers of the request, except for the redacted ones
+    pub headers: Vec<DeadLetterHeader>,
+    /// The base64 encoded body of the request, missing if it was larger than the limit of the queue
+    pub body: Option<String>,
+    pub error: String,
+    pub replay_count: u32,
+    pub last_replayed_at: Option<DateTime<Utc>>,
+    pub last_replay_status: Option<u16>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    // Replays are served by the current definition of the site
+    pub fn to_replay_request(&self) -> Result<poem::Request, DeadLetterServiceError> {
+        let body = self
+            .body
+            .as_ref()
+            .ok_or_else(|| {
+                DeadLetterServiceError::NotReplayable(format!(
+                    "the body of dead letter {} was not kept",
+                    self.dead_letter_id
+                ))
+            })
+            .and_then(|body| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(body)
+                    .map_err(|err| DeadLetterServiceError::InternalError(err.to_string()))
+            })?;
+
+        let method = http::Method::from_bytes(self.method.as_bytes())
+            .map_err(|err| DeadLetterServiceError::InternalError(err.to_string()))?;
+        let uri = self
+            .uri
+            .parse::<Uri>()
+            .map_err(|err| DeadLetterServiceError::InternalError(err.to_string()))?;
+
+        let mut request = poem::Request::builder().method(method).uri(uri);
+
+        for header in self.headers.iter().filter(|header| {
+            !header
+                .name
+                .eq_ignore_ascii_case(http::header::HOST.as_str())
+        }) {
+            request = request.header(header.name.as_str(), header.value.as_str());
+        }
+
+        let mut request = request
+            .header(http::header::HOST, self.site.as_str())
+            .body(body);
+        request.extensions_mut().insert(DeadLetterReplay);
+
+        Ok(request)
+    }
+}
+
+impl TryFrom<DeadLetterRecord> for DeadLetter {
+    type Error = String;
+
+    fn try_from(value: DeadLetterRecord) -> Result<Self, Self::Error> {
+        Ok(DeadLetter {
+            dead_letter_id: value.dead_letter_id,
+            site: value.site,
+            route: value.route,
+            method: value.method,
+            uri: value.uri,
+            headers: serde_json::from_str(&value.headers).map_err(|err| err.to_string())?,
+            body: value
+                .body
+                .map(|body| base64::engine::general_purpose::STANDARD.encode(body)),
+            error: value.error,
+            replay_count: value.replay_count as u32,
+            last_replayed_at: value.last_replayed_at,
+            last_replay_status: value.last_replay_status.map(|status| status as u16),
+            created_at: value.created_at,
+        })
+    }
+}
+
+/// The outcome of replaying a dead letter. Dead letters replayed successfully are removed
+/// from the queue, the others are kept with the status of their last replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct DeadLetterReplayResult {
+    pub status: u16,
+    pub succeeded: bool,
+}
+
+// Keeps the requests of the sites with a dead-letter queue whose worker invocation failed
+#[async_trait]
+pub trait DeadLetterService<Namespace> {
+    async fn record(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        route: &str,
+        request: DeadLetterRequest,
+        error: String,
+    ) -> Result<DeadLetter, DeadLetterServiceError>;
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<Vec<DeadLetter>, DeadLetterServiceError>;
+
+    async fn get(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_id: &str,
+    ) -> Result<DeadLetter, DeadLetterServiceError>;
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_id: &str,
+    ) -> Result<(), DeadLetterServiceError>;
+
+    // Executes the request of the dead letter again with the given executor
+    async fn replay(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_id: &str,
+        executor: &(dyn GatewayHttpInputExecutor + Sync + Send),
+    ) -> Result<DeadLetterReplayResult, DeadLetterServiceError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum DeadLetterServiceError {
+    NotFound(String),
+    NotReplayable(String),
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for DeadLetterServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for DeadLetterServiceError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            DeadLetterServiceError::NotFound(dead_letter_id) => {
+                format!("Dead letter {} not found", dead_letter_id)
+            }
+            DeadLetterServiceError::NotReplayable(err) => {
+                format!("Dead letter cannot be replayed: {}", err)
+            }
+            DeadLetterServiceError::InternalError(err) => format!("Internal error: {}", err),
+        }
+    }
+}
+
+pub struct DefaultDeadLetterService {
+    repo: Arc<dyn DeadLetterRepo + Sync + Send>,
+}
+
+impl DefaultDeadLetterService {
+    pub fn new(repo: Arc<dyn DeadLetterRepo + Sync + Send>) -> Self {
+        DefaultDeadLetterService { repo }
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> DeadLetterService<Namespace> for DefaultDeadLetterService {
+    async fn record(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        route: &str,
+        request: DeadLetterRequest,
+        error: String,
+    ) -> Result<DeadLetter, DeadLetterServiceError> {
+        let headers = serde_json::to_string(&request.headers)
+            .map_err(|err| DeadLetterServiceError::InternalError(err.to_string()))?;
+
+        let record = DeadLetterRecord {
+            namespace: namespace.to_string(),
+            site: site.to_string(),
+            dead_letter_id: Uuid::new_v4().to_string(),
+            route: route.to_string(),
+            method: request.method,
+            uri: request.uri,
+            headers,
+            body: request.body,
+            error,
+            replay_count: 0,
+            last_replayed_at: None,
+            last_replay_status: None,
+            created_at: Utc::now(),
+        };
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| DeadLetterServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            site = %site,
+            dead_letter_id = %record.dead_letter_id,
+            "Failed request of route {} added to the dead-letter queue",
+            route
+        );
+
+        DeadLetter::try_from(record).map_err(DeadLetterServiceError::InternalError)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<Vec<DeadLetter>, DeadLetterServiceError> {
+        let namespace = namespace.to_string();
+
+        self.repo
+            .get_all(&site.to_string())
+            .await
+            .map_err(|err| DeadLetterServiceError::InternalError(err.to_safe_string()))?
+            .into_iter()
+            .filter(|record| record.namespace == namespace)
+            .map(|record| {
+                DeadLetter::try_from(record).map_err(DeadLetterServiceError::InternalError)
+            })
+            .collect()
+    }
+
+    async fn get(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_id: &str,
+    ) -> Result<DeadLetter, DeadLetterServiceError> {
+        let record = self
+            .repo
+            .get(&site.to_string(), dead_letter_id)
+            .await
+            .map_err(|err| DeadLetterServiceError::InternalError(err.to_safe_string()))?
+            .filter(|record| record.namespace == namespace.to_string())
+            .ok_or(DeadLetterServiceError::NotFound(dead_letter_id.to_string()))?;
+
+        DeadLetter::try_from(record).map_err(DeadLetterServiceError::InternalError)
+    }
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_id: &str,
+    ) -> Result<(), DeadLetterServiceError> {
+        self.get(namespace, site, dead_letter_id).await?;
+
+        self.repo
+            .delete(&site.to_string(), dead_letter_id)
+            .await
+            .map_err(|err| DeadLetterServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            site = %site,
+            dead_letter_id = dead_letter_id,
+            "Dead letter deleted"
+        );
+
+        Ok(())
+    }
+
+    async fn replay(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        dead_letter_id: &str,
+        executor: &(dyn GatewayHttpInputExecutor + Sync + Send),
+    ) -> Result<DeadLetterReplayResult, DeadLetterServiceError> {
+        let dead_letter = self.get(namespace, site, dead_letter_id).await?;
+        let request = dead_letter.to_replay_request()?;
+
+        let status = executor.execute_http_request(request).await.status();
+        let succeeded = status.is_success();
+
+        if succeeded {
+            self.repo
+                .delete(&site.to_string(), dead_letter_id)
+                .await
+                .map_err(|err| DeadLetterServiceError::InternalError(err.to_safe_string()))?;
+        } else {
+            self.repo
+                .record_replay(
+                    &site.to_string(),
+                    dead_letter_id,
+                    &Utc::now(),
+                    status.as_u16() as i32,
+                )
+                .await
+                .map_err(|err| DeadLetterServiceError::InternalError(err.to_safe_string()))?;
+        }
+
+        info!(
+            site = %site,
+            dead_letter_id = dead_letter_id,
+            "Dead letter replayed with status {}",
+            status
+        );
+
+        Ok(DeadLetterReplayResult {
+            status: status.as_u16(),
+            succeeded,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    async fn captured_requests_are_replayed_without_redacted_headers() {
+        let dead_letter_queue = ApiDeploymentDeadLetterQueue {
+            redacted_headers: vec!["authorization".to_string()],
+            max_body_size: None,
+        };
+
+        let mut request = poem::Request::builder()
+            .method(http::Method::POST)
+            .uri(Uri::from_static("/orders?priority=high"))
+            .header(http::header::HOST, "api.example.com")
+            .header(http::header::AUTHORIZATION, "Bearer secret")
+            .header("x-order-source", "web")
+            .body("{\"id\":1}");
+
+        let captured = DeadLetterRequest::capture(&dead_letter_queue, &mut request).await;
+
+        // The body is still available to the binding
+        assert_eq!(
+            request.take_body().into_string().await.unwrap(),
+            "{\"id\":1}"
+        );
+        assert!(captured
+            .headers
+            .iter()
+            .all(|header| header.name != "authorization"));
+
+        let dead_letter = DeadLetter::try_from(DeadLetterRecord {
+            namespace: "namespace".to_string(),
+            site: "api.example.com".to_string(),
+            dead_letter_id: "dead-letter".to_string(),
+            route: "/orders".to_string(),
+            method: captured.method,
+            uri: captured.uri,
+            headers: serde_json::to_string(&captured.headers).unwrap(),
+            body: captured.body,
+            error: "worker failed".to_string(),
+            replay_count: 0,
+            last_replayed_at: None,
+            last_replay_status: None,
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+        let mut replay = dead_letter.to_replay_request().unwrap();
+
+        assert_eq!(replay.method(), http::Method::POST);
+        assert_eq!(replay.uri().to_string(), "/orders?priority=high");
+        assert_eq!(replay.header(http::header::HOST), Some("api.example.com"));
+        assert_eq!(replay.header("x-order-source"), Some("web"));
+        assert_eq!(replay.header(http::header::AUTHORIZATION), None);
+        assert!(replay.extensions().get::<DeadLetterReplay>().is_some());
+        assert_eq!(
+            replay.take_body().into_string().await.unwrap(),
+            "{\"id\":1}"
+        );
+    }
+
+    #[test]
+    async fn requests_with_a_too_large_body_are_not_replayable() {
+        let dead_letter_queue = ApiDeploymentDeadLetterQueue {
+            redacted_headers: vec![],
+            max_body_size: Some(4),
+        };
+
+        let mut request = poem::Request::builder()
+            .method(http::Method::POST)
+            .uri(Uri::from_static("/orders"))
+            .body("{\"id\":1}");
+
+        let captured = DeadLetterRequest::capture(&dead_letter_queue, &mut request).await;
+
+        assert_eq!(captured.body, None);
+        assert_eq!(
+            request.take_body().into_string().await.unwrap(),
+            "{\"id\":1}"
+        );
+    }
+}
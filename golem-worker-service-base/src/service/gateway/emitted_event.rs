@@ -0,0 +1,189 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::repo::emitted_event::{EmittedEventRecord, EmittedEventRepo};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use golem_common::model::ComponentId;
+use golem_common::SafeDisplay;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+// An event published by the `emit` builtin of a Rib script of a gateway binding
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventEmission {
+    pub event_name: String,
+    pub payload: serde_json::Value,
+    pub component_id: ComponentId,
+    pub worker_name: Option<String>,
+    pub trace_id: String,
+    // The method and the uri of the request served by the binding, if known
+    pub method: Option<String>,
+    pub uri: Option<String>,
+}
+
+/// An event emitted by the Rib script of an API binding while serving a request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct EmittedEvent {
+    pub event_id: String,
+    pub event_name: String,
+    /// The JSON representation of the payload of the event
+    pub payload: serde_json::Value,
+    /// The component of the binding that emitted the event
+    pub component_id: ComponentId,
+    pub worker_name: Option<String>,
+    /// The trace id of the request, correlating the event with the invocations it triggered
+    pub trace_id: String,
+    pub method: Option<String>,
+    pub uri: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<EmittedEventRecord> for EmittedEvent {
+    type Error = String;
+
+    fn try_from(value: EmittedEventRecord) -> Result<Self, Self::Error> {
+        Ok(EmittedEvent {
+            event_id: value.event_id,
+            event_name: value.event_name,
+            payload: serde_json::from_str(&value.payload).map_err(|err| err.to_string())?,
+            component_id: ComponentId::try_from(value.component_id.as_str())?,
+            worker_name: value.worker_name,
+            trace_id: value.trace_id,
+            method: value.method,
+            uri: value.uri,
+            created_at: value.created_at,
+        })
+    }
+}
+
+// Keeps the events emitted by the Rib scripts of the gateway bindings, for auditing
+// which requests triggered which side effects
+#[async_trait]
+pub trait EmittedEventService<Namespace> {
+    async fn publish(
+        &self,
+        namespace: &Namespace,
+        event: EventEmission,
+    ) -> Result<EmittedEvent, EmittedEventServiceError>;
+
+    // The latest events, optionally filtered by their name and component, the newest first
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        event_name: Option<String>,
+        component_id: Option<ComponentId>,
+        limit: u32,
+    ) -> Result<Vec<EmittedEvent>, EmittedEventServiceError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum EmittedEventServiceError {
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for EmittedEventServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for EmittedEventServiceError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            EmittedEventServiceError::InternalError(err) => format!("Internal error: {}", err),
+        }
+    }
+}
+
+pub struct DefaultEmittedEventService {
+    repo: Arc<dyn EmittedEventRepo + Sync + Send>,
+}
+
+impl DefaultEmittedEventService {
+    pub fn new(repo: Arc<dyn EmittedEventRepo + Sync + Send>) -> Self {
+        DefaultEmittedEventService { repo }
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> EmittedEventService<Namespace>
+    for DefaultEmittedEventService
+{
+    async fn publish(
+        &self,
+        namespace: &Namespace,
+        event: EventEmission,
+    ) -> Result<EmittedEvent, EmittedEventServiceError> {
+        let record = EmittedEventRecord {
+            namespace: namespace.to_string(),
+            event_id: Uuid::new_v4().to_string(),
+            event_name: event.event_name,
+            payload: event.payload.to_string(),
+            component_id: event.component_id.to_string(),
+            worker_name: event.worker_name,
+            trace_id: event.trace_id,
+            method: event.method,
+            uri: event.uri,
+            created_at: Utc::now(),
+        };
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| EmittedEventServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            event_id = %record.event_id,
+            trace_id = %record.trace_id,
+            "Event {} emitted",
+            record.event_name
+        );
+
+        EmittedEvent::try_from(record).map_err(EmittedEventServiceError::InternalError)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        event_name: Option<String>,
+        component_id: Option<ComponentId>,
+        limit: u32,
+    ) -> Result<Vec<EmittedEvent>, EmittedEventServiceError> {
+        let component_id = component_id.map(|component_id| component_id.to_string());
+
+        self.repo
+            .get_all(
+                &namespace.to_string(),
+                event_name.as_deref(),
+                component_id.as_deref(),
+                limit,
+            )
+            .await
+            .map_err(|err| EmittedEventServiceError::InternalError(err.to_safe_string()))?
+            .into_iter()
+            .map(|record| {
+                EmittedEvent::try_from(record).map_err(EmittedEventServiceError::InternalError)
+            })
+            .collect()
+    }
+}
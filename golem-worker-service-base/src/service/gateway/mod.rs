@@ -15,5 +15,10 @@
 pub mod api_definition;
 pub mod api_definition_validator;
 pub mod api_deployment;
+pub mod api_key;
+pub mod dead_letter;
+pub mod emitted_event;
 pub mod http_api_definition_validator;
+pub mod request_fixture;
+pub mod rib_library;
 pub mod security_scheme;
@@ -0,0 +1,634 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_api_deployment::ApiSiteString;
+use crate::gateway_execution::gateway_http_input_executor::GatewayHttpInputExecutor;
+use crate::repo::request_fixture::{RequestFixtureRecord, RequestFixtureRepo};
+use crate::service::gateway::dead_letter::{DeadLetterHeader, DeadLetterRequest};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use golem_common::SafeDisplay;
+use http::Uri;
+use poem::Body;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Display;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+// Marks the replays of request fixtures, which are not recorded as fixtures again
+#[derive(Debug, Clone, Copy)]
+pub struct RequestFixtureReplay;
+
+/// A sampled request of a deployment, along with the status and the schema of its response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RequestFixture {
+    pub fixture_id: String,
+    pub site: String,
+    /// The path pattern of the route of the request
+    pub route: String,
+    pub method: String,
+    pub uri: String,
+    /// The headers of the request, except for the redacted ones
+    pub headers: Vec<DeadLetterHeader>,
+    /// The base64 encoded body of the request
+    pub body: String,
+    /// The status of the recorded response
+    pub status: u16,
+    /// The schema of the recorded response body, missing if it was not JSON. Objects map their
+    /// fields to their schema, arrays contain the schema of their first item, and other values
+    /// are represented by the name of their type.
+    pub response_schema: Option<Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RequestFixture {
+    // Replays are sent to the given site, which serves them with its own definitions
+    pub fn to_replay_request(
+        &self,
+        site: &ApiSiteString,
+    ) -> Result<poem::Request, RequestFixtureServiceError> {
+        let body = base64::engine::general_purpose::STANDARD
+            .decode(&self.body)
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_string()))?;
+
+        let method = http::Method::from_bytes(self.method.as_bytes())
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_string()))?;
+        let uri = self
+            .uri
+            .parse::<Uri>()
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_string()))?;
+
+        let mut request = poem::Request::builder().method(method).uri(uri);
+
+        for header in self.headers.iter().filter(|header| {
+            !header
+                .name
+                .eq_ignore_ascii_case(http::header::HOST.as_str())
+        }) {
+            request = request.header(header.name.as_str(), header.value.as_str());
+        }
+
+        let mut request = request
+            .header(http::header::HOST, site.0.as_str())
+            .body(body);
+        request.extensions_mut().insert(RequestFixtureReplay);
+
+        Ok(request)
+    }
+}
+
+impl TryFrom<RequestFixtureRecord> for RequestFixture {
+    type Error = String;
+
+    fn try_from(value: RequestFixtureRecord) -> Result<Self, Self::Error> {
+        Ok(RequestFixture {
+            fixture_id: value.fixture_id,
+            site: value.site,
+            route: value.route,
+            method: value.method,
+            uri: value.uri,
+            headers: serde_json::from_str(&value.headers).map_err(|err| err.to_string())?,
+            body: base64::engine::general_purpose::STANDARD.encode(value.body),
+            status: value.status as u16,
+            response_schema: value
+                .response_schema
+                .map(|schema| serde_json::from_str(&schema))
+                .transpose()
+                .map_err(|err| err.to_string())?,
+            created_at: value.created_at,
+        })
+    }
+}
+
+/// The staging deployment to replay the request fixtures of a site against
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RequestFixtureReplayRequest {
+    pub staging_site: String,
+}
+
+/// The outcome of replaying a request fixture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RequestFixtureReplayResult {
+    pub fixture_id: String,
+    pub method: String,
+    pub uri: String,
+    pub expected_status: u16,
+    pub status: u16,
+    /// The differences between the schema of the recorded response and the one of the replay,
+    /// as JSON paths. Fields added by the replay are not reported.
+    pub schema_mismatches: Vec<String>,
+    pub passed: bool,
+}
+
+/// The outcome of replaying the request fixtures of a deployment against a staging deployment.
+/// The staging deployment passes when every replay got the recorded status and a response
+/// with a compatible schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct RequestFixtureReplayReport {
+    pub staging_site: String,
+    pub replayed: u32,
+    pub failed: u32,
+    pub passed: bool,
+    pub results: Vec<RequestFixtureReplayResult>,
+}
+
+// The shape of a JSON value, without the values themselves
+pub fn response_schema(value: &Value) -> Value {
+    match value {
+        Value::Null => Value::String("null".to_string()),
+        Value::Bool(_) => Value::String("boolean".to_string()),
+        Value::Number(_) => Value::String("number".to_string()),
+        Value::String(_) => Value::String("string".to_string()),
+        Value::Array(items) => {
+            Value::Array(items.first().map(response_schema).into_iter().collect())
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), response_schema(value)))
+                .collect(),
+        ),
+    }
+}
+
+// Fields missing from the actual schema, or of a different type, are reported as mismatches.
+// Nulls are compatible with any type, as they are usually optional values, and so are empty
+// arrays with any array.
+pub fn schema_mismatches(expected: &Value, actual: &Value) -> Vec<String> {
+    let mut mismatches = vec![];
+    collect_schema_mismatches("$".to_string(), expected, actual, &mut mismatches);
+    mismatches
+}
+
+fn collect_schema_mismatches(
+    path: String,
+    expected: &Value,
+    actual: &Value,
+    mismatches: &mut Vec<String>,
+) {
+    match (expected, actual) {
+        (Value::String(kind), _) | (_, Value::String(kind)) if kind == "null" => {}
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (name, expected) in expected {
+                let path = format!("{}.{}", path, name);
+                match actual.get(name) {
+                    Some(actual) => collect_schema_mismatches(path, expected, actual, mismatches),
+                    None => mismatches.push(format!("{}: missing", path)),
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            if let (Some(expected), Some(actual)) = (expected.first(), actual.first()) {
+                collect_schema_mismatches(format!("{}[]", path), expected, actual, mismatches);
+            }
+        }
+        (expected, actual) if expected == actual => {}
+        (expected, actual) => mismatches.push(format!(
+            "{}: expected {}, got {}",
+            path,
+            schema_kind(expected),
+            schema_kind(actual)
+        )),
+    }
+}
+
+fn schema_kind(schema: &Value) -> &str {
+    match schema {
+        Value::String(kind) => kind,
+        Value::Array(_) => "array",
+        _ => "object",
+    }
+}
+
+// Buffers the body of a response, which is still sent to the client, and derives its schema
+pub async fn capture_response(response: poem::Response) -> (poem::Response, Option<Value>) {
+    let (parts, body) = response.into_parts();
+
+    match body.into_bytes().await {
+        Ok(bytes) => {
+            let schema = serde_json::from_slice::<Value>(&bytes)
+                .ok()
+                .map(|value| response_schema(&value));
+            (
+                poem::Response::from_parts(parts, Body::from_bytes(bytes)),
+                schema,
+            )
+        }
+        Err(_) => (poem::Response::from_parts(parts, Body::empty()), None),
+    }
+}
+
+// Keeps a sample of the requests of the sites recording fixtures
+#[async_trait]
+pub trait RequestFixtureService<Namespace> {
+    // Returns None without recording the request if the site already has `max_fixtures` fixtures
+    #[allow(clippy::too_many_arguments)]
+    async fn record(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        route: &str,
+        request: DeadLetterRequest,
+        status: u16,
+        response_schema: Option<Value>,
+        max_fixtures: u32,
+    ) -> Result<Option<RequestFixture>, RequestFixtureServiceError>;
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<Vec<RequestFixture>, RequestFixtureServiceError>;
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        fixture_id: &str,
+    ) -> Result<(), RequestFixtureServiceError>;
+
+    // Returns the number of deleted fixtures
+    async fn delete_all(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<u64, RequestFixtureServiceError>;
+
+    // Sends the fixtures of the site to the staging site with the given executor, and compares
+    // the responses with the recorded ones
+    async fn replay(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        staging_site: &ApiSiteString,
+        executor: &(dyn GatewayHttpInputExecutor + Sync + Send),
+    ) -> Result<RequestFixtureReplayReport, RequestFixtureServiceError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum RequestFixtureServiceError {
+    NotFound(String),
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for RequestFixtureServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for RequestFixtureServiceError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            RequestFixtureServiceError::NotFound(fixture_id) => {
+                format!("Request fixture {} not found", fixture_id)
+            }
+            RequestFixtureServiceError::InternalError(err) => format!("Internal error: {}", err),
+        }
+    }
+}
+
+pub struct DefaultRequestFixtureService {
+    repo: Arc<dyn RequestFixtureRepo + Sync + Send>,
+}
+
+impl DefaultRequestFixtureService {
+    pub fn new(repo: Arc<dyn RequestFixtureRepo + Sync + Send>) -> Self {
+        DefaultRequestFixtureService { repo }
+    }
+
+    async fn replay_fixture(
+        fixture: &RequestFixture,
+        staging_site: &ApiSiteString,
+        executor: &(dyn GatewayHttpInputExecutor + Sync + Send),
+    ) -> Result<RequestFixtureReplayResult, RequestFixtureServiceError> {
+        let request = fixture.to_replay_request(staging_site)?;
+
+        let response = executor.execute_http_request(request).await;
+        let status = response.status().as_u16();
+        let (_, schema) = capture_response(response).await;
+
+        let schema_mismatches = match (&fixture.response_schema, &schema) {
+            (Some(expected), Some(actual)) => schema_mismatches(expected, actual),
+            (Some(_), None) => vec!["$: expected a JSON body".to_string()],
+            (None, _) => vec![],
+        };
+
+        Ok(RequestFixtureReplayResult {
+            fixture_id: fixture.fixture_id.clone(),
+            method: fixture.method.clone(),
+            uri: fixture.uri.clone(),
+            expected_status: fixture.status,
+            status,
+            passed: status == fixture.status && schema_mismatches.is_empty(),
+            schema_mismatches,
+        })
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> RequestFixtureService<Namespace>
+    for DefaultRequestFixtureService
+{
+    async fn record(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        route: &str,
+        request: DeadLetterRequest,
+        status: u16,
+        response_schema: Option<Value>,
+        max_fixtures: u32,
+    ) -> Result<Option<RequestFixture>, RequestFixtureServiceError> {
+        // Requests without a body are not replayable, as it was too large to be kept
+        let Some(body) = request.body else {
+            return Ok(None);
+        };
+
+        let count = self
+            .repo
+            .count(&site.to_string())
+            .await
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_safe_string()))?;
+
+        if count >= max_fixtures as u64 {
+            return Ok(None);
+        }
+
+        let headers = serde_json::to_string(&request.headers)
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_string()))?;
+        let response_schema = response_schema
+            .map(|schema| serde_json::to_string(&schema))
+            .transpose()
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_string()))?;
+
+        let record = RequestFixtureRecord {
+            namespace: namespace.to_string(),
+            site: site.to_string(),
+            fixture_id: Uuid::new_v4().to_string(),
+            route: route.to_string(),
+            method: request.method,
+            uri: request.uri,
+            headers,
+            body,
+            status: status as i32,
+            response_schema,
+            created_at: Utc::now(),
+        };
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            site = %site,
+            fixture_id = %record.fixture_id,
+            "Request of route {} recorded as a fixture",
+            route
+        );
+
+        RequestFixture::try_from(record)
+            .map(Some)
+            .map_err(RequestFixtureServiceError::InternalError)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<Vec<RequestFixture>, RequestFixtureServiceError> {
+        let namespace = namespace.to_string();
+
+        self.repo
+            .get_all(&site.to_string())
+            .await
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_safe_string()))?
+            .into_iter()
+            .filter(|record| record.namespace == namespace)
+            .map(|record| {
+                RequestFixture::try_from(record).map_err(RequestFixtureServiceError::InternalError)
+            })
+            .collect()
+    }
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        fixture_id: &str,
+    ) -> Result<(), RequestFixtureServiceError> {
+        let exists = self
+            .get_all(namespace, site)
+            .await?
+            .iter()
+            .any(|fixture| fixture.fixture_id == fixture_id);
+
+        if !exists {
+            return Err(RequestFixtureServiceError::NotFound(fixture_id.to_string()));
+        }
+
+        self.repo
+            .delete(&site.to_string(), fixture_id)
+            .await
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(site = %site, fixture_id = fixture_id, "Request fixture deleted");
+
+        Ok(())
+    }
+
+    async fn delete_all(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+    ) -> Result<u64, RequestFixtureServiceError> {
+        // The fixtures of a site all belong to the namespace owning it
+        if self.get_all(namespace, site).await?.is_empty() {
+            return Ok(0);
+        }
+
+        let deleted = self
+            .repo
+            .delete_all(&site.to_string())
+            .await
+            .map_err(|err| RequestFixtureServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(site = %site, "{} request fixtures deleted", deleted);
+
+        Ok(deleted)
+    }
+
+    async fn replay(
+        &self,
+        namespace: &Namespace,
+        site: &ApiSiteString,
+        staging_site: &ApiSiteString,
+        executor: &(dyn GatewayHttpInputExecutor + Sync + Send),
+    ) -> Result<RequestFixtureReplayReport, RequestFixtureServiceError> {
+        let fixtures = self.get_all(namespace, site).await?;
+
+        let mut results = Vec::with_capacity(fixtures.len());
+        for fixture in &fixtures {
+            results.push(Self::replay_fixture(fixture, staging_site, executor).await?);
+        }
+
+        let failed = results.iter().filter(|result| !result.passed).count() as u32;
+
+        info!(
+            site = %site,
+            staging_site = %staging_site,
+            "{} request fixtures replayed, {} failed",
+            results.len(),
+            failed
+        );
+
+        Ok(RequestFixtureReplayReport {
+            staging_site: staging_site.to_string(),
+            replayed: results.len() as u32,
+            failed,
+            passed: failed == 0,
+            results,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::StatusCode;
+    use serde_json::json;
+    use test_r::test;
+
+    #[test]
+    fn schemas_are_compatible_with_added_fields_and_nulls() {
+        let recorded = response_schema(&json!({
+            "id": 1,
+            "name": "cart",
+            "discount": null,
+            "items": [{"sku": "a", "quantity": 2}]
+        }));
+
+        let compatible = response_schema(&json!({
+            "id": 2,
+            "name": "other",
+            "discount": 10,
+            "items": [],
+            "currency": "EUR"
+        }));
+        assert!(schema_mismatches(&recorded, &compatible).is_empty());
+
+        let incompatible = response_schema(&json!({
+            "id": "2",
+            "items": [{"sku": "a", "quantity": "2"}]
+        }));
+        let mut mismatches = schema_mismatches(&recorded, &incompatible);
+        mismatches.sort();
+        assert_eq!(
+            mismatches,
+            vec![
+                "$.id: expected number, got string".to_string(),
+                "$.items[].quantity: expected number, got string".to_string(),
+                "$.name: missing".to_string(),
+            ]
+        );
+    }
+
+    struct StatusExecutor {
+        status: StatusCode,
+        body: Value,
+    }
+
+    #[async_trait]
+    impl GatewayHttpInputExecutor for StatusExecutor {
+        async fn execute_http_request(&self, request: poem::Request) -> poem::Response {
+            assert_eq!(
+                request.header(http::header::HOST),
+                Some("staging.example.com")
+            );
+            assert!(request.extensions().get::<RequestFixtureReplay>().is_some());
+
+            poem::Response::builder()
+                .status(self.status)
+                .body(self.body.to_string())
+        }
+    }
+
+    #[test]
+    async fn fixtures_are_replayed_against_the_staging_site() {
+        let fixture = RequestFixture::try_from(RequestFixtureRecord {
+            namespace: "namespace".to_string(),
+            site: "api.example.com".to_string(),
+            fixture_id: "fixture".to_string(),
+            route: "/carts/{id}".to_string(),
+            method: "GET".to_string(),
+            uri: "/carts/1".to_string(),
+            headers: serde_json::to_string(&vec![DeadLetterHeader {
+                name: "host".to_string(),
+                value: "api.example.com".to_string(),
+            }])
+            .unwrap(),
+            body: vec![],
+            status: 200,
+            response_schema: Some(response_schema(&json!({"id": 1})).to_string()),
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+        let staging_site = ApiSiteString("staging.example.com".to_string());
+
+        let passed = DefaultRequestFixtureService::replay_fixture(
+            &fixture,
+            &staging_site,
+            &StatusExecutor {
+                status: StatusCode::OK,
+                body: json!({"id": 2}),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(passed.passed);
+
+        let failed = DefaultRequestFixtureService::replay_fixture(
+            &fixture,
+            &staging_site,
+            &StatusExecutor {
+                status: StatusCode::NOT_FOUND,
+                body: json!("Route not found"),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!failed.passed);
+        assert_eq!(failed.status, 404);
+        assert_eq!(
+            failed.schema_mismatches,
+            vec!["$: expected object, got string".to_string()]
+        );
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::gateway_rib_compiler::{DefaultWorkerServiceRibCompiler, WorkerServiceRibCompiler};
+use crate::repo::rib_library::{RibLibraryRecord, RibLibraryRepo};
+use async_trait::async_trait;
+use golem_common::SafeDisplay;
+use rib::{Expr, RibImport, RibLibrary};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use tracing::info;
+
+// A named and versioned Rib library, whose bindings can be imported by the binding scripts
+// of API definitions with `use <name>::<binding>;` (the latest version)
+// or `use <name>@<version>::<binding>;`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RibLibraryDefinition {
+    pub name: String,
+    pub version: String,
+    pub source: String,
+    pub exports: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<RibLibraryRecord> for RibLibraryDefinition {
+    type Error = String;
+
+    fn try_from(value: RibLibraryRecord) -> Result<Self, Self::Error> {
+        let library = RibLibrary::from_text(&value.source)?;
+
+        Ok(RibLibraryDefinition {
+            name: value.name,
+            version: value.version,
+            source: value.source,
+            exports: library.exports(),
+            created_at: value.created_at,
+        })
+    }
+}
+
+// Libraries are immutable once created, and are linked into the scripts importing them when
+// an API definition is created or updated, so new versions do not change existing definitions
+#[async_trait]
+pub trait RibLibraryService<Namespace> {
+    async fn create(
+        &self,
+        namespace: &Namespace,
+        name: String,
+        version: String,
+        source: String,
+    ) -> Result<RibLibraryDefinition, RibLibraryServiceError>;
+
+    async fn get(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+        version: &str,
+    ) -> Result<RibLibraryDefinition, RibLibraryServiceError>;
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Vec<RibLibraryDefinition>, RibLibraryServiceError>;
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+        version: &str,
+    ) -> Result<(), RibLibraryServiceError>;
+
+    // Links the bindings imported by the `use` statements of a script in front of it
+    async fn link(
+        &self,
+        namespace: &Namespace,
+        script: &str,
+    ) -> Result<Expr, RibLibraryServiceError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum RibLibraryServiceError {
+    AlreadyExists(String, String),
+    NotFound(String, Option<String>),
+    InvalidLibrary(String),
+    LinkError(String),
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for RibLibraryServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for RibLibraryServiceError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            RibLibraryServiceError::AlreadyExists(name, version) => {
+                format!("Rib library {} version {} already exists", name, version)
+            }
+            RibLibraryServiceError::NotFound(name, Some(version)) => {
+                format!("Rib library {} version {} not found", name, version)
+            }
+            RibLibraryServiceError::NotFound(name, None) => {
+                format!("Rib library {} not found", name)
+            }
+            RibLibraryServiceError::InvalidLibrary(err) => format!("Invalid Rib library: {}", err),
+            RibLibraryServiceError::LinkError(err) => {
+                format!("Failed to link Rib libraries: {}", err)
+            }
+            RibLibraryServiceError::InternalError(err) => format!("InternalError: {}", err),
+        }
+    }
+}
+
+pub struct DefaultRibLibraryService {
+    repo: Arc<dyn RibLibraryRepo + Sync + Send>,
+}
+
+impl DefaultRibLibraryService {
+    pub fn new(repo: Arc<dyn RibLibraryRepo + Sync + Send>) -> Self {
+        DefaultRibLibraryService { repo }
+    }
+
+    async fn resolve(
+        &self,
+        namespace: &str,
+        import: &RibImport,
+    ) -> Result<RibLibrary, RibLibraryServiceError> {
+        let record = match &import.version {
+            Some(version) => self.repo.get(namespace, &import.library, version).await,
+            None => self.repo.get_latest(namespace, &import.library).await,
+        }
+        .map_err(|err| RibLibraryServiceError::InternalError(err.to_safe_string()))?
+        .ok_or(RibLibraryServiceError::NotFound(
+            import.library.clone(),
+            import.version.clone(),
+        ))?;
+
+        RibLibrary::from_text(&record.source).map_err(RibLibraryServiceError::InternalError)
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> RibLibraryService<Namespace> for DefaultRibLibraryService {
+    async fn create(
+        &self,
+        namespace: &Namespace,
+        name: String,
+        version: String,
+        source: String,
+    ) -> Result<RibLibraryDefinition, RibLibraryServiceError> {
+        let is_valid_name = |value: &str| {
+            !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        };
+
+        if !is_valid_name(&name) || name.contains('.') {
+            return Err(RibLibraryServiceError::InvalidLibrary(format!(
+                "invalid name '{}', expected letters, digits, '-' and '_'",
+                name
+            )));
+        }
+
+        if !is_valid_name(&version) {
+            return Err(RibLibraryServiceError::InvalidLibrary(format!(
+                "invalid version '{}', expected letters, digits, '.', '-' and '_'",
+                version
+            )));
+        }
+
+        let library =
+            RibLibrary::from_text(&source).map_err(RibLibraryServiceError::InvalidLibrary)?;
+
+        // The library is compiled on its own once, so that errors are reported against the library
+        // rather than against each of the definitions importing it
+        DefaultWorkerServiceRibCompiler::compile(&library.to_expr(), &[])
+            .map_err(|err| RibLibraryServiceError::InvalidLibrary(err.to_string()))?;
+
+        let existing = self
+            .repo
+            .get(&namespace.to_string(), &name, &version)
+            .await
+            .map_err(|err| RibLibraryServiceError::InternalError(err.to_safe_string()))?;
+
+        if existing.is_some() {
+            return Err(RibLibraryServiceError::AlreadyExists(name, version));
+        }
+
+        let record = RibLibraryRecord {
+            namespace: namespace.to_string(),
+            name,
+            version,
+            source,
+            created_at: chrono::Utc::now(),
+        };
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| RibLibraryServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            "Rib library {} version {} created",
+            record.name, record.version
+        );
+
+        Ok(RibLibraryDefinition {
+            name: record.name,
+            version: record.version,
+            source: record.source,
+            exports: library.exports(),
+            created_at: record.created_at,
+        })
+    }
+
+    async fn get(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+        version: &str,
+    ) -> Result<RibLibraryDefinition, RibLibraryServiceError> {
+        let record = self
+            .repo
+            .get(&namespace.to_string(), name, version)
+            .await
+            .map_err(|err| RibLibraryServiceError::InternalError(err.to_safe_string()))?
+            .ok_or(RibLibraryServiceError::NotFound(
+                name.to_string(),
+                Some(version.to_string()),
+            ))?;
+
+        RibLibraryDefinition::try_from(record).map_err(RibLibraryServiceError::InternalError)
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Vec<RibLibraryDefinition>, RibLibraryServiceError> {
+        let records = self
+            .repo
+            .get_all(&namespace.to_string())
+            .await
+            .map_err(|err| RibLibraryServiceError::InternalError(err.to_safe_string()))?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                RibLibraryDefinition::try_from(record)
+                    .map_err(RibLibraryServiceError::InternalError)
+            })
+            .collect()
+    }
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+        version: &str,
+    ) -> Result<(), RibLibraryServiceError> {
+        let deleted = self
+            .repo
+            .delete(&namespace.to_string(), name, version)
+            .await
+            .map_err(|err| RibLibraryServiceError::InternalError(err.to_safe_string()))?;
+
+        if deleted {
+            info!("Rib library {} version {} deleted", name, version);
+            Ok(())
+        } else {
+            Err(RibLibraryServiceError::NotFound(
+                name.to_string(),
+                Some(version.to_string()),
+            ))
+        }
+    }
+
+    async fn link(
+        &self,
+        namespace: &Namespace,
+        script: &str,
+    ) -> Result<Expr, RibLibraryServiceError> {
+        let (imports, _) = rib::split_imports(script).map_err(RibLibraryServiceError::LinkError)?;
+
+        let mut libraries = HashMap::new();
+        for import in imports {
+            let key = (import.library.clone(), import.version.clone());
+            if !libraries.contains_key(&key) {
+                let library = self.resolve(&namespace.to_string(), &import).await?;
+                libraries.insert(key, library);
+            }
+        }
+
+        rib::link_imports(script, |import| {
+            libraries
+                .get(&(import.library.clone(), import.version.clone()))
+                .cloned()
+                .ok_or(format!("Rib library {} not found", import.library))
+        })
+        .map_err(RibLibraryServiceError::LinkError)
+    }
+}
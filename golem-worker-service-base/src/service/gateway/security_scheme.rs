@@ -13,8 +13,9 @@
 // limitations under the License.
 
 use crate::gateway_security::{
-    IdentityProvider, IdentityProviderError, SecurityScheme, SecuritySchemeIdentifier,
-    SecuritySchemeWithProviderMetadata,
+    ApiKeySecurityScheme, HmacSecurityScheme, HmacSignatureValidationError, HmacSignatureValidator,
+    IdentityProvider, IdentityProviderError, JwtSecurityScheme, RegisteredSecurityScheme,
+    SecurityScheme, SecuritySchemeIdentifier, SecuritySchemeWithProviderMetadata,
 };
 use crate::repo::security_scheme::{SecuritySchemeRecord, SecuritySchemeRepo};
 use async_trait::async_trait;
@@ -29,17 +30,45 @@ use tracing::info;
 // has create-security role in Namespace, before calling this service
 #[async_trait]
 pub trait SecuritySchemeService<Namespace> {
+    // Gets an OpenID Connect security scheme, failing if the scheme is of another type
     async fn get(
         &self,
         security_scheme_name: &SecuritySchemeIdentifier,
         namespace: &Namespace,
     ) -> Result<SecuritySchemeWithProviderMetadata, SecuritySchemeServiceError>;
 
+    // Gets a security scheme of any type, such as the ones referred to by routes
+    async fn get_registered(
+        &self,
+        security_scheme_name: &SecuritySchemeIdentifier,
+        namespace: &Namespace,
+    ) -> Result<RegisteredSecurityScheme, SecuritySchemeServiceError>;
+
     async fn create(
         &self,
         namespace: &Namespace,
         security_scheme: &SecurityScheme,
     ) -> Result<SecuritySchemeWithProviderMetadata, SecuritySchemeServiceError>;
+
+    async fn create_jwt(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &JwtSecurityScheme,
+    ) -> Result<JwtSecurityScheme, SecuritySchemeServiceError>;
+
+    async fn create_api_key(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &ApiKeySecurityScheme,
+    ) -> Result<ApiKeySecurityScheme, SecuritySchemeServiceError>;
+
+    // The secret is stored with the scheme, and never returned
+    async fn create_hmac(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &HmacSecurityScheme,
+        secret: &str,
+    ) -> Result<HmacSecurityScheme, SecuritySchemeServiceError>;
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +76,8 @@ pub enum SecuritySchemeServiceError {
     IdentityProviderError(IdentityProviderError),
     InternalError(String),
     NotFound(SecuritySchemeIdentifier),
+    UnexpectedType(SecuritySchemeIdentifier),
+    InvalidSecret(String),
 }
 
 // For satisfying thiserror::Error
@@ -69,16 +100,21 @@ impl SafeDisplay for SecuritySchemeServiceError {
             SecuritySchemeServiceError::NotFound(identifier) => {
                 format!("SecurityScheme not found: {}", identifier)
             }
+            SecuritySchemeServiceError::UnexpectedType(identifier) => {
+                format!(
+                    "SecurityScheme {} is not an OpenID Connect scheme",
+                    identifier
+                )
+            }
+            SecuritySchemeServiceError::InvalidSecret(err) => {
+                format!("Invalid secret: {}", err)
+            }
         }
     }
 }
 
-pub type SecuritySchemeCache<N> = Cache<
-    (N, SecuritySchemeIdentifier),
-    (),
-    SecuritySchemeWithProviderMetadata,
-    SecuritySchemeServiceError,
->;
+pub type SecuritySchemeCache<N> =
+    Cache<(N, SecuritySchemeIdentifier), (), RegisteredSecurityScheme, SecuritySchemeServiceError>;
 pub struct DefaultSecuritySchemeService<Namespace> {
     cache: SecuritySchemeCache<Namespace>,
     repo: Arc<dyn SecuritySchemeRepo + Sync + Send>,
@@ -112,22 +148,41 @@ impl<Namespace: Display + Clone + Hash + Eq + PartialEq + Send + Sync + 'static>
         security_scheme_identifier: &SecuritySchemeIdentifier,
         namespace: &Namespace,
     ) -> Result<SecuritySchemeWithProviderMetadata, SecuritySchemeServiceError> {
+        match self
+            .get_registered(security_scheme_identifier, namespace)
+            .await?
+        {
+            RegisteredSecurityScheme::OpenId(security_scheme) => Ok(security_scheme),
+            RegisteredSecurityScheme::Jwt(_)
+            | RegisteredSecurityScheme::ApiKey(_)
+            | RegisteredSecurityScheme::Hmac(_) => Err(SecuritySchemeServiceError::UnexpectedType(
+                security_scheme_identifier.clone(),
+            )),
+        }
+    }
+
+    async fn get_registered(
+        &self,
+        security_scheme_identifier: &SecuritySchemeIdentifier,
+        namespace: &Namespace,
+    ) -> Result<RegisteredSecurityScheme, SecuritySchemeServiceError> {
         self.cache
             .get_or_insert_simple(
                 &(namespace.clone(), security_scheme_identifier.clone()),
                 || {
+                    let namespace = namespace.to_string();
                     let security_scheme_identifier = security_scheme_identifier.clone();
                     let repo = self.repo.clone();
                     Box::pin(async move {
                         let result = repo
-                            .get(&security_scheme_identifier.to_string())
+                            .get(&namespace, &security_scheme_identifier.to_string())
                             .await
                             .map_err(|err| {
                                 SecuritySchemeServiceError::InternalError(err.to_string())
                             })?;
 
                         match result {
-                            Some(v) => SecuritySchemeWithProviderMetadata::try_from(v)
+                            Some(v) => RegisteredSecurityScheme::try_from(v)
                                 .map_err(SecuritySchemeServiceError::InternalError),
                             None => Err(SecuritySchemeServiceError::NotFound(
                                 security_scheme_identifier.clone(),
@@ -180,4 +235,116 @@ impl<Namespace: Display + Clone + Hash + Eq + PartialEq + Send + Sync + 'static>
             Err(err) => Err(SecuritySchemeServiceError::IdentityProviderError(err)),
         }
     }
+
+    async fn create_jwt(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &JwtSecurityScheme,
+    ) -> Result<JwtSecurityScheme, SecuritySchemeServiceError> {
+        let record = SecuritySchemeRecord::from_jwt_security_scheme(namespace, security_scheme)
+            .map_err(SecuritySchemeServiceError::InternalError)?;
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| SecuritySchemeServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            "JWT security scheme created: {}",
+            security_scheme.scheme_identifier()
+        );
+
+        Ok(security_scheme.clone())
+    }
+
+    async fn create_api_key(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &ApiKeySecurityScheme,
+    ) -> Result<ApiKeySecurityScheme, SecuritySchemeServiceError> {
+        let record = SecuritySchemeRecord::from_api_key_security_scheme(namespace, security_scheme)
+            .map_err(SecuritySchemeServiceError::InternalError)?;
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| SecuritySchemeServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            "API key security scheme created: {}",
+            security_scheme.scheme_identifier()
+        );
+
+        Ok(security_scheme.clone())
+    }
+
+    async fn create_hmac(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &HmacSecurityScheme,
+        secret: &str,
+    ) -> Result<HmacSecurityScheme, SecuritySchemeServiceError> {
+        if secret.is_empty() {
+            return Err(SecuritySchemeServiceError::InvalidSecret(
+                "secret must not be empty".to_string(),
+            ));
+        }
+
+        let record =
+            SecuritySchemeRecord::from_hmac_security_scheme(namespace, security_scheme, secret)
+                .map_err(SecuritySchemeServiceError::InternalError)?;
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| SecuritySchemeServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            "HMAC security scheme created: {}",
+            security_scheme.scheme_identifier()
+        );
+
+        Ok(security_scheme.clone())
+    }
+}
+
+// The secret is looked up on every request rather than cached along with the scheme,
+// keeping it out of the memory of the gateway in between requests
+#[async_trait]
+impl<Namespace: Display + Send + Sync> HmacSignatureValidator<Namespace>
+    for DefaultSecuritySchemeService<Namespace>
+{
+    async fn validate(
+        &self,
+        namespace: &Namespace,
+        security_scheme: &HmacSecurityScheme,
+        body: &[u8],
+        presented_signature: &str,
+    ) -> Result<(), HmacSignatureValidationError> {
+        let record = self
+            .repo
+            .get(
+                &namespace.to_string(),
+                &security_scheme.scheme_identifier().to_string(),
+            )
+            .await
+            .map_err(|err| HmacSignatureValidationError::InternalError(err.to_safe_string()))?
+            .ok_or(HmacSignatureValidationError::InternalError(format!(
+                "SecurityScheme not found: {}",
+                security_scheme.scheme_identifier()
+            )))?;
+
+        let secret = record
+            .hmac_secret()
+            .ok_or(HmacSignatureValidationError::InternalError(format!(
+                "SecurityScheme {} is not an HMAC scheme",
+                security_scheme.scheme_identifier()
+            )))?;
+
+        if security_scheme.verify(secret.as_bytes(), body, presented_signature) {
+            Ok(())
+        } else {
+            Err(HmacSignatureValidationError::InvalidSignature)
+        }
+    }
 }
@@ -0,0 +1,730 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app_config::AsyncInvocationConfig;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use fred::types::Expiration;
+use golem_common::model::ComponentId;
+use golem_common::redis::RedisPool;
+use golem_common::retries::get_delay;
+use golem_common::SafeDisplay;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use hmac::{Hmac, Mac};
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, warn, Instrument};
+use url::{Host, Url};
+use uuid::Uuid;
+
+// Invocations kept in memory at most, the ones expiring first are dropped to make room
+const MAX_IN_MEMORY_INVOCATIONS: usize = 10_000;
+
+// Headers of the signed callbacks: the signature is the hex encoded HMAC-SHA256 of
+// `{timestamp}.{body}` with the configured secret, prefixed with `sha256=`
+pub const CALLBACK_TIMESTAMP_HEADER: &str = "x-golem-timestamp";
+pub const CALLBACK_SIGNATURE_HEADER: &str = "x-golem-signature";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+pub enum AsyncInvocationStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+/// An invocation of a worker function running in the background.
+/// Once completed, it has either the result or the error of the invocation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct AsyncInvocation {
+    pub invocation_id: Uuid,
+    pub component_id: ComponentId,
+    pub worker_name: Option<String>,
+    pub function: String,
+    pub status: AsyncInvocationStatus,
+    pub result: Option<TypeAnnotatedValue>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl AsyncInvocation {
+    pub fn pending(
+        component_id: ComponentId,
+        worker_name: Option<String>,
+        function: String,
+    ) -> Self {
+        AsyncInvocation {
+            invocation_id: Uuid::new_v4(),
+            component_id,
+            worker_name,
+            function,
+            status: AsyncInvocationStatus::Pending,
+            result: None,
+            error: None,
+            created_at: Utc::now(),
+            completed_at: None,
+        }
+    }
+
+    pub fn completed(self, result: Result<TypeAnnotatedValue, String>) -> Self {
+        let (status, result, error) = match result {
+            Ok(result) => (AsyncInvocationStatus::Completed, Some(result), None),
+            Err(error) => (AsyncInvocationStatus::Failed, None, Some(error)),
+        };
+
+        AsyncInvocation {
+            status,
+            result,
+            error,
+            completed_at: Some(Utc::now()),
+            ..self
+        }
+    }
+}
+
+/// Returned by async invocations, to poll for their result at `/v1/invocations/{invocationId}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct AsyncInvokeResponse {
+    pub invocation_id: Uuid,
+}
+
+#[async_trait]
+pub trait AsyncInvocationStore {
+    async fn put(
+        &self,
+        invocation: &AsyncInvocation,
+        ttl: Duration,
+    ) -> Result<(), AsyncInvocationStoreError>;
+
+    async fn get(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<AsyncInvocation>, AsyncInvocationStoreError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum AsyncInvocationStoreError {
+    InternalError(String),
+}
+
+impl SafeDisplay for AsyncInvocationStoreError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            AsyncInvocationStoreError::InternalError(e) => format!("Internal error: {}", e),
+        }
+    }
+}
+
+/// Keeps the invocations in the memory of a single worker service instance,
+/// used when there is no Redis shared by the replicas.
+pub struct InMemoryAsyncInvocationStore {
+    capacity: usize,
+    invocations: Mutex<HashMap<Uuid, (AsyncInvocation, Instant)>>,
+}
+
+impl InMemoryAsyncInvocationStore {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            invocations: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for InMemoryAsyncInvocationStore {
+    fn default() -> Self {
+        Self::with_capacity(MAX_IN_MEMORY_INVOCATIONS)
+    }
+}
+
+#[async_trait]
+impl AsyncInvocationStore for InMemoryAsyncInvocationStore {
+    async fn put(
+        &self,
+        invocation: &AsyncInvocation,
+        ttl: Duration,
+    ) -> Result<(), AsyncInvocationStoreError> {
+        let now = Instant::now();
+        let mut invocations = self.invocations.lock().unwrap();
+
+        if invocations.len() >= self.capacity
+            && !invocations.contains_key(&invocation.invocation_id)
+        {
+            invocations.retain(|_, (_, expires_at)| *expires_at > now);
+
+            // Still full: the invocations expiring first are dropped, making some room at once
+            // so that the next invocations don't have to scan all of them again
+            if invocations.len() >= self.capacity {
+                let keep = self.capacity - self.capacity.div_ceil(10).max(1);
+                let mut by_expiry = invocations
+                    .iter()
+                    .map(|(invocation_id, (_, expires_at))| (*expires_at, *invocation_id))
+                    .collect::<Vec<_>>();
+                by_expiry.sort();
+                for (_, invocation_id) in by_expiry.into_iter().take(invocations.len() - keep) {
+                    invocations.remove(&invocation_id);
+                }
+            }
+        }
+
+        invocations.insert(invocation.invocation_id, (invocation.clone(), now + ttl));
+
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<AsyncInvocation>, AsyncInvocationStoreError> {
+        let now = Instant::now();
+        let invocations = self.invocations.lock().unwrap();
+
+        Ok(invocations
+            .get(invocation_id)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(invocation, _)| invocation.clone()))
+    }
+}
+
+/// Keeps the invocations in Redis, so that they can be polled from any replica
+#[derive(Clone)]
+pub struct RedisAsyncInvocationStore {
+    redis: RedisPool,
+}
+
+impl RedisAsyncInvocationStore {
+    pub fn new(redis: RedisPool) -> Self {
+        Self { redis }
+    }
+
+    fn invocation_key(invocation_id: &Uuid) -> String {
+        format!("worker_async_invocation:{}", invocation_id)
+    }
+}
+
+#[async_trait]
+impl AsyncInvocationStore for RedisAsyncInvocationStore {
+    async fn put(
+        &self,
+        invocation: &AsyncInvocation,
+        ttl: Duration,
+    ) -> Result<(), AsyncInvocationStoreError> {
+        let serialized = serde_json::to_vec(invocation)
+            .map_err(|e| AsyncInvocationStoreError::InternalError(e.to_string()))?;
+
+        self.redis
+            .with("worker_async_invocation", "put")
+            .set::<(), _, _>(
+                Self::invocation_key(&invocation.invocation_id),
+                Bytes::from(serialized),
+                Some(Expiration::EX(ttl.as_secs().max(1) as i64)),
+                None,
+                false,
+            )
+            .await
+            .map_err(|e| {
+                error!("Failed to store an async invocation in Redis: {}", e);
+                AsyncInvocationStoreError::InternalError(e.to_string())
+            })
+    }
+
+    async fn get(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<AsyncInvocation>, AsyncInvocationStoreError> {
+        let serialized: Option<Bytes> = self
+            .redis
+            .with("worker_async_invocation", "get")
+            .get(Self::invocation_key(invocation_id))
+            .await
+            .map_err(|e| {
+                error!("Failed to get an async invocation from Redis: {}", e);
+                AsyncInvocationStoreError::InternalError(e.to_string())
+            })?;
+
+        serialized
+            .map(|serialized| {
+                serde_json::from_slice(&serialized)
+                    .map_err(|e| AsyncInvocationStoreError::InternalError(e.to_string()))
+            })
+            .transpose()
+    }
+}
+
+// Runs invocations in the background, keeping them to be polled until their results expire,
+// and posting them to the callback URL of the caller, if any, once they complete
+#[derive(Clone)]
+pub struct AsyncInvocations {
+    config: AsyncInvocationConfig,
+    store: Arc<dyn AsyncInvocationStore + Send + Sync>,
+}
+
+impl AsyncInvocations {
+    pub fn new(
+        config: AsyncInvocationConfig,
+        store: Arc<dyn AsyncInvocationStore + Send + Sync>,
+    ) -> Self {
+        Self { config, store }
+    }
+
+    /// Parses the callback URL of an invocation, accepting it only if its host is allowed and
+    /// resolves to public addresses
+    pub async fn validate_callback_url(&self, callback_url: &str) -> Result<Url, String> {
+        let url = parse_callback_url(callback_url)?;
+        self.resolve_callback_url(&url).await?;
+        Ok(url)
+    }
+
+    // The host is resolved again before each post, as what it resolves to may have changed since
+    // the invocation started, and the request is sent to the checked addresses only
+    async fn resolve_callback_url(&self, url: &Url) -> Result<Vec<SocketAddr>, String> {
+        let host = url
+            .host()
+            .ok_or_else(|| format!("Invalid callback URL '{}': missing host", url))?;
+
+        if !is_allowed_callback_host(&self.config.callback_allowed_hosts, &host) {
+            return Err(format!(
+                "Invalid callback URL '{}': host {} is not allowed",
+                url, host
+            ));
+        }
+
+        let port = url.port_or_known_default().unwrap_or(80);
+        let addresses = match &host {
+            Host::Ipv4(ip) => vec![SocketAddr::new(IpAddr::V4(*ip), port)],
+            Host::Ipv6(ip) => vec![SocketAddr::new(IpAddr::V6(*ip), port)],
+            Host::Domain(domain) => tokio::net::lookup_host((*domain, port))
+                .await
+                .map_err(|err| {
+                    format!(
+                        "Invalid callback URL '{}': failed to resolve {}: {}",
+                        url, domain, err
+                    )
+                })?
+                .collect(),
+        };
+
+        if addresses.is_empty() {
+            return Err(format!(
+                "Invalid callback URL '{}': {} does not resolve to any address",
+                url, host
+            ));
+        }
+
+        if !self.config.allow_private_callback_addresses {
+            if let Some(address) = addresses.iter().find(|a| !is_public_address(&a.ip())) {
+                return Err(format!(
+                    "Invalid callback URL '{}': {} resolves to the non-public address {}",
+                    url,
+                    host,
+                    address.ip()
+                ));
+            }
+        }
+
+        Ok(addresses)
+    }
+
+    pub async fn start<F>(
+        &self,
+        invocation: AsyncInvocation,
+        callback_url: Option<Url>,
+        invoke: F,
+    ) -> Result<Uuid, AsyncInvocationStoreError>
+    where
+        F: Future<Output = Result<TypeAnnotatedValue, String>> + Send + 'static,
+    {
+        let invocation_id = invocation.invocation_id;
+
+        self.store.put(&invocation, self.config.result_ttl).await?;
+
+        let this = self.clone();
+        tokio::spawn(
+            async move {
+                let invocation = invocation.completed(invoke.await);
+
+                if let Err(err) = this.store.put(&invocation, this.config.result_ttl).await {
+                    error!(
+                        "Failed to store the result of async invocation {}: {}",
+                        invocation.invocation_id,
+                        err.to_safe_string()
+                    );
+                }
+
+                if let Some(callback_url) = callback_url {
+                    this.post_to_callback(&callback_url, &invocation).await;
+                }
+            }
+            .in_current_span(),
+        );
+
+        Ok(invocation_id)
+    }
+
+    pub async fn get(
+        &self,
+        invocation_id: &Uuid,
+    ) -> Result<Option<AsyncInvocation>, AsyncInvocationStoreError> {
+        self.store.get(invocation_id).await
+    }
+
+    // Retried as configured until the callback answers with a successful status.
+    // Redirects are not followed, as they could lead to hosts that were not checked.
+    async fn post_to_callback(&self, callback_url: &Url, invocation: &AsyncInvocation) {
+        let body = match serde_json::to_vec(invocation) {
+            Ok(body) => body,
+            Err(err) => {
+                error!(
+                    "Failed to serialize async invocation {} for its callback: {}",
+                    invocation.invocation_id, err
+                );
+                return;
+            }
+        };
+
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            let result = match self.resolve_callback_url(callback_url).await {
+                Ok(addresses) => self
+                    .send_to_callback(callback_url, &addresses, body.clone())
+                    .await
+                    .map_err(|err| err.to_string()),
+                Err(err) => Err(err),
+            };
+
+            let Err(err) = result else {
+                return;
+            };
+
+            match get_delay(&self.config.callback_retries, attempts) {
+                Some(delay) => {
+                    warn!(
+                        "Failed to post async invocation {} to its callback, retrying in {:?}: {}",
+                        invocation.invocation_id, delay, err
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                None => {
+                    error!(
+                        "Failed to post async invocation {} to its callback: {}",
+                        invocation.invocation_id, err
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn send_to_callback(
+        &self,
+        callback_url: &Url,
+        addresses: &[SocketAddr],
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let mut client = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+        if let Some(Host::Domain(domain)) = callback_url.host() {
+            client = client.resolve_to_addrs(domain, addresses);
+        }
+
+        let mut request = client
+            .build()?
+            .post(callback_url.clone())
+            .timeout(self.config.callback_timeout)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        if let Some(secret) = &self.config.callback_signing_secret {
+            let timestamp = Utc::now().timestamp().to_string();
+            request = request
+                .header(
+                    CALLBACK_SIGNATURE_HEADER,
+                    sign_callback(secret, &timestamp, &body),
+                )
+                .header(CALLBACK_TIMESTAMP_HEADER, timestamp);
+        }
+
+        request.body(body).send().await?.error_for_status()
+    }
+}
+
+pub fn sign_callback(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take keys of any size");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+// Without allowed hosts any host is allowed, otherwise the host must be one of them, or a
+// subdomain of the ones starting with a dot
+fn is_allowed_callback_host(allowed_hosts: &[String], host: &Host<&str>) -> bool {
+    if allowed_hosts.is_empty() {
+        return true;
+    }
+
+    let host = host.to_string().to_lowercase();
+    allowed_hosts.iter().any(|allowed| {
+        let allowed = allowed.to_lowercase();
+        match allowed.strip_prefix('.') {
+            Some(domain) => host == domain || host.ends_with(&allowed),
+            None => host == allowed,
+        }
+    })
+}
+
+fn is_public_address(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(&ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: &Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // 0.0.0.0/8 and the shared address space 100.64.0.0/10
+        || first == 0
+        || (first == 100 && (second & 0b1100_0000) == 64))
+}
+
+fn is_public_ipv6(ip: &Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        // unique local fc00::/7 and link-local fe80::/10
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80)
+}
+
+pub fn parse_callback_url(callback_url: &str) -> Result<Url, String> {
+    let url = Url::parse(callback_url)
+        .map_err(|err| format!("Invalid callback URL '{}': {}", callback_url, err))?;
+
+    match url.scheme() {
+        "http" | "https" => Ok(url),
+        scheme => Err(format!(
+            "Invalid callback URL '{}': unsupported scheme {}, expected http or https",
+            callback_url, scheme
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    async fn invocations_can_be_polled_until_their_results_expire() {
+        let config = AsyncInvocationConfig {
+            result_ttl: Duration::from_millis(200),
+            ..AsyncInvocationConfig::default()
+        };
+        let invocations =
+            AsyncInvocations::new(config, Arc::new(InMemoryAsyncInvocationStore::default()));
+
+        let (complete, completed) = tokio::sync::oneshot::channel::<()>();
+
+        let invocation = AsyncInvocation::pending(
+            ComponentId(Uuid::new_v4()),
+            Some("worker-1".to_string()),
+            "golem:it/api.{get-cart-contents}".to_string(),
+        );
+        let invocation_id = invocations
+            .start(invocation, None, async move {
+                completed.await.unwrap();
+                Ok(TypeAnnotatedValue::U32(42))
+            })
+            .await
+            .unwrap();
+
+        let polled = invocations.get(&invocation_id).await.unwrap().unwrap();
+        assert_eq!(polled.status, AsyncInvocationStatus::Pending);
+        assert_eq!(polled.result, None);
+
+        complete.send(()).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let polled = invocations.get(&invocation_id).await.unwrap().unwrap();
+        assert_eq!(polled.status, AsyncInvocationStatus::Completed);
+        assert_eq!(polled.result, Some(TypeAnnotatedValue::U32(42)));
+        assert!(polled.completed_at.is_some());
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(invocations.get(&invocation_id).await.unwrap(), None);
+
+        assert!(parse_callback_url("https://example.com/callback").is_ok());
+        assert!(parse_callback_url("ftp://example.com/callback").is_err());
+        assert!(parse_callback_url("not a url").is_err());
+    }
+
+    #[test]
+    async fn in_memory_store_keeps_at_most_its_capacity() {
+        let store = InMemoryAsyncInvocationStore::with_capacity(10);
+
+        let mut invocation_ids = Vec::new();
+        for i in 0..25 {
+            let invocation = AsyncInvocation::pending(
+                ComponentId(Uuid::new_v4()),
+                None,
+                "golem:it/api.{get-cart-contents}".to_string(),
+            );
+            invocation_ids.push(invocation.invocation_id);
+            store
+                .put(&invocation, Duration::from_secs(60 + i))
+                .await
+                .unwrap();
+        }
+
+        assert!(store.invocations.lock().unwrap().len() <= 10);
+        // The last ones expire last, so they are kept
+        assert!(store.get(&invocation_ids[24]).await.unwrap().is_some());
+        assert!(store.get(&invocation_ids[0]).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn only_public_addresses_are_accepted_for_callbacks() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "100.64.0.1",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public_address(&ip.parse().unwrap()), "{ip}");
+        }
+
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:2800:220:1::1"] {
+            assert!(is_public_address(&ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    async fn callbacks_are_only_accepted_to_allowed_public_hosts() {
+        let invocations = AsyncInvocations::new(
+            AsyncInvocationConfig::default(),
+            Arc::new(InMemoryAsyncInvocationStore::default()),
+        );
+        assert!(invocations
+            .validate_callback_url("http://127.0.0.1:8080/callback")
+            .await
+            .is_err());
+        assert!(invocations
+            .validate_callback_url("http://[::1]/callback")
+            .await
+            .is_err());
+        assert!(invocations
+            .validate_callback_url("http://169.254.169.254/latest/meta-data")
+            .await
+            .is_err());
+        assert!(invocations
+            .validate_callback_url("http://localhost/callback")
+            .await
+            .is_err());
+        assert!(invocations
+            .validate_callback_url("https://93.184.216.34/callback")
+            .await
+            .is_ok());
+
+        let invocations = AsyncInvocations::new(
+            AsyncInvocationConfig {
+                callback_allowed_hosts: vec!["93.184.216.34".to_string()],
+                ..AsyncInvocationConfig::default()
+            },
+            Arc::new(InMemoryAsyncInvocationStore::default()),
+        );
+        assert!(invocations
+            .validate_callback_url("https://93.184.216.34/callback")
+            .await
+            .is_ok());
+        assert!(invocations
+            .validate_callback_url("https://8.8.8.8/callback")
+            .await
+            .is_err());
+
+        let allowed = vec!["hooks.example.com".to_string(), ".example.org".to_string()];
+        let allowed_host = |host: &str| is_allowed_callback_host(&allowed, &Host::Domain(host));
+        assert!(allowed_host("hooks.example.com"));
+        assert!(allowed_host("HOOKS.example.com"));
+        assert!(!allowed_host("other.example.com"));
+        assert!(allowed_host("example.org"));
+        assert!(allowed_host("a.b.example.org"));
+        assert!(!allowed_host("badexample.org"));
+
+        let invocations = AsyncInvocations::new(
+            AsyncInvocationConfig {
+                allow_private_callback_addresses: true,
+                ..AsyncInvocationConfig::default()
+            },
+            Arc::new(InMemoryAsyncInvocationStore::default()),
+        );
+        assert!(invocations
+            .validate_callback_url("http://127.0.0.1:8080/callback")
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn callbacks_are_signed_with_the_timestamp_and_body() {
+        let signature = sign_callback("secret", "1700000000", br#"{"status":"Completed"}"#);
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(br#"1700000000.{"status":"Completed"}"#);
+        assert_eq!(
+            signature,
+            format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+        );
+        assert_ne!(
+            signature,
+            sign_callback("other", "1700000000", br#"{"status":"Completed"}"#)
+        );
+        assert_ne!(
+            signature,
+            sign_callback("secret", "1700000001", br#"{"status":"Completed"}"#)
+        );
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app_config::BatchInvocationConfig;
+use futures::{stream, StreamExt};
+use golem_common::model::{ComponentId, IdempotencyKey};
+use golem_wasm_rpc::json::OptionallyTypeAnnotatedValueJson;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// An invocation of a function of a worker, awaiting its result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BatchInvocation {
+    pub component_id: ComponentId,
+    /// The name of the invoked worker, a new worker with a random generated name is invoked if missing
+    pub worker_name: Option<String>,
+    pub idempotency_key: Option<IdempotencyKey>,
+    pub function: String,
+    pub params: Vec<OptionallyTypeAnnotatedValueJson>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+pub struct BatchInvokeRequest {
+    pub invocations: Vec<BatchInvocation>,
+}
+
+/// The outcome of an invocation of a batch, either its result or the error it failed with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+pub struct BatchInvocationResult {
+    pub result: Option<TypeAnnotatedValue>,
+    pub error: Option<String>,
+}
+
+/// The outcomes of the invocations of a batch, in the order of the invocations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+pub struct BatchInvokeResponse {
+    pub results: Vec<BatchInvocationResult>,
+}
+
+// Dispatches the invocations of a batch concurrently, at most `max_parallelism` of them at the
+// same time, and returns their outcomes in the order of the invocations.
+// A failing invocation does not affect the others, so the whole batch only fails when its size
+// is out of the configured limits.
+pub async fn invoke_batch<I, O, F, Fut>(
+    config: &BatchInvocationConfig,
+    invocations: Vec<I>,
+    invoke: F,
+) -> Result<Vec<O>, String>
+where
+    F: Fn(I) -> Fut,
+    Fut: Future<Output = O>,
+{
+    if invocations.is_empty() {
+        return Err("The batch has no invocations".to_string());
+    }
+
+    if invocations.len() > config.max_batch_size {
+        return Err(format!(
+            "The batch has {} invocations, more than the maximum of {}",
+            invocations.len(),
+            config.max_batch_size
+        ));
+    }
+
+    Ok(stream::iter(invocations)
+        .map(invoke)
+        .buffered(config.max_parallelism.max(1))
+        .collect()
+        .await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use test_r::test;
+
+    #[test]
+    async fn invocations_are_dispatched_with_bounded_parallelism() {
+        let config = BatchInvocationConfig {
+            max_batch_size: 10,
+            max_parallelism: 3,
+        };
+
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        let results = invoke_batch(&config, (0..10).collect(), |index: u64| {
+            let running = &running;
+            let max_running = &max_running;
+            async move {
+                let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now_running, Ordering::SeqCst);
+                // Later invocations finish first, so the results have to be put back in order
+                tokio::time::sleep(Duration::from_millis(20 - index)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+
+                if index % 4 == 0 {
+                    Err(format!("Invocation {} failed", index))
+                } else {
+                    Ok(index * 2)
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 10);
+        assert_eq!(results[0], Err("Invocation 0 failed".to_string()));
+        assert_eq!(results[1], Ok(2));
+        assert_eq!(results[9], Ok(18));
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+
+        assert!(invoke_batch(
+            &config,
+            (0..11).collect(),
+            |index: u64| async move { index }
+        )
+        .await
+        .is_err());
+        assert!(
+            invoke_batch(&config, vec![], |index: u64| async move { index })
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app_config::BatchInvocationConfig;
+use futures::{stream, StreamExt};
+use golem_common::model::{ComponentId, ComponentVersion, ScanCursor, WorkerFilter, WorkerId};
+use golem_service_base::model::WorkerUpdateMode;
+use poem_openapi::{Object, Union};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+const DEFAULT_BULK_PAGE_SIZE: u64 = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BulkInterrupt {
+    #[serde(default)]
+    #[oai(default)]
+    pub recover_immediately: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BulkResume {
+    #[serde(default)]
+    #[oai(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct BulkDelete {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BulkUpdate {
+    pub mode: WorkerUpdateMode,
+    pub target_version: ComponentVersion,
+}
+
+/// The operation applied to every matching worker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(tag = "type")]
+#[oai(discriminator_name = "type", one_of = true)]
+pub enum BulkWorkerOperation {
+    Interrupt(BulkInterrupt),
+    Resume(BulkResume),
+    Delete(BulkDelete),
+    Update(BulkUpdate),
+}
+
+/// Applies an operation to a page of the workers of a component matching a filter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BulkWorkerOperationRequest {
+    pub component_id: ComponentId,
+    pub filter: Option<WorkerFilter>,
+    pub operation: BulkWorkerOperation,
+    /// Only lists the matching workers, without applying the operation to them
+    #[serde(default)]
+    #[oai(default)]
+    pub dry_run: bool,
+    pub cursor: Option<ScanCursor>,
+    pub count: Option<u64>,
+    pub precise: Option<bool>,
+}
+
+/// The outcome of the operation for a worker, the error is missing if it succeeded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BulkWorkerOperationResult {
+    pub worker_id: WorkerId,
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+/// The outcomes of the operation for a page of the matching workers, and the cursor of the next
+/// page if there are more workers to scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct BulkWorkerOperationResponse {
+    pub results: Vec<BulkWorkerOperationResult>,
+    pub cursor: Option<ScanCursor>,
+    pub dry_run: bool,
+}
+
+impl BulkWorkerOperationRequest {
+    // The number of workers scanned by a request is bounded by the size of a batch
+    pub fn page_size(&self, config: &BatchInvocationConfig) -> u64 {
+        self.count
+            .unwrap_or(DEFAULT_BULK_PAGE_SIZE)
+            .clamp(1, config.max_batch_size.max(1) as u64)
+    }
+}
+
+// Applies an operation to the matched workers, at most `max_parallelism` of them at the same
+// time, and returns the outcomes in the order of the workers. In a dry run the operation is
+// not applied, and the outcomes only list the workers.
+pub async fn apply_to_workers<F, Fut>(
+    config: &BatchInvocationConfig,
+    workers: Vec<WorkerId>,
+    dry_run: bool,
+    apply: F,
+) -> Vec<BulkWorkerOperationResult>
+where
+    F: Fn(WorkerId) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    if dry_run {
+        return workers
+            .into_iter()
+            .map(|worker_id| BulkWorkerOperationResult {
+                worker_id,
+                applied: false,
+                error: None,
+            })
+            .collect();
+    }
+
+    stream::iter(workers)
+        .map(|worker_id| {
+            let result = apply(worker_id.clone());
+            async move {
+                match result.await {
+                    Ok(()) => BulkWorkerOperationResult {
+                        worker_id,
+                        applied: true,
+                        error: None,
+                    },
+                    Err(error) => BulkWorkerOperationResult {
+                        worker_id,
+                        applied: false,
+                        error: Some(error),
+                    },
+                }
+            }
+        })
+        .buffered(config.max_parallelism.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    async fn operations_are_applied_unless_dry_run() {
+        let config = BatchInvocationConfig {
+            max_batch_size: 20,
+            max_parallelism: 4,
+        };
+        let component_id = ComponentId(Uuid::new_v4());
+        let workers = (0..5)
+            .map(|index| WorkerId {
+                component_id: component_id.clone(),
+                worker_name: format!("worker-{index}"),
+            })
+            .collect::<Vec<_>>();
+
+        let apply = |worker_id: WorkerId| async move {
+            if worker_id.worker_name == "worker-3" {
+                Err("Worker not found".to_string())
+            } else {
+                Ok(())
+            }
+        };
+
+        let results = apply_to_workers(&config, workers.clone(), false, apply).await;
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].worker_id, workers[0]);
+        assert!(results[0].applied);
+        assert!(!results[3].applied);
+        assert_eq!(results[3].error, Some("Worker not found".to_string()));
+
+        let results = apply_to_workers(&config, workers, true, apply).await;
+        assert!(results
+            .iter()
+            .all(|result| !result.applied && result.error.is_none()));
+
+        let operation: BulkWorkerOperation =
+            serde_json::from_str(r#"{"type":"Update","mode":"Automatic","targetVersion":3}"#)
+                .unwrap();
+        assert_eq!(
+            operation,
+            BulkWorkerOperation::Update(BulkUpdate {
+                mode: WorkerUpdateMode::Automatic,
+                target_version: 3,
+            })
+        );
+
+        let request = BulkWorkerOperationRequest {
+            component_id,
+            filter: None,
+            operation,
+            dry_run: false,
+            cursor: None,
+            count: Some(1000),
+            precise: None,
+        };
+        assert_eq!(request.page_size(&config), 20);
+    }
+}
@@ -17,21 +17,108 @@ use std::{
     time::Duration,
 };
 
-use futures::{Sink, SinkExt, Stream, StreamExt};
+use super::{MultiplexedWorkerEvent, WorkerEventFilter};
+use futures::{future, stream, Sink, SinkExt, Stream, StreamExt};
 use golem_api_grpc::proto::golem::worker::LogEvent;
-use golem_common::model::{WorkerEvent, WorkerId};
+use golem_common::model::{ComponentId, WorkerEvent, WorkerId};
 use poem::web::websocket::Message;
+use serde::Serialize;
 use tonic::Status;
 use tracing::{error, info};
 
 /// Proxies a worker connection, listening for either connection to close. Websocket sink will be closed at the end.
+/// Only the events matching the filter are sent to the client.
 ///
 /// keep_alive_interval: Interval at which Ping messages are sent
 /// max_pong_timeout: Maximum time to wait for a Pong message before considering the connection dead
 #[tracing::instrument(skip_all, fields(worker_id = worker_id.to_string()))]
 pub async fn proxy_worker_connection(
     worker_id: WorkerId,
-    mut worker_stream: impl Stream<Item = Result<LogEvent, Status>> + Unpin,
+    worker_stream: impl Stream<Item = Result<LogEvent, Status>> + Unpin,
+    filter: WorkerEventFilter,
+    websocket_sender: impl Sink<Message, Error = IoError> + Unpin,
+    websocket_receiver: impl Stream<Item = IoResult<Message>> + Unpin,
+    keep_alive_interval: Duration,
+    max_pong_timeout: Duration,
+) -> Result<(), ConnectProxyError> {
+    let events = worker_stream.filter_map(move |message| {
+        future::ready(
+            decode_worker_event(message)
+                .map(|event| filter.matches(&event).then_some(event))
+                .transpose(),
+        )
+    });
+
+    proxy_events(
+        events,
+        websocket_sender,
+        websocket_receiver,
+        keep_alive_interval,
+        max_pong_timeout,
+    )
+    .await
+}
+
+/// Proxies the connections of multiple workers of a component into one websocket, tagging each
+/// event with the worker it comes from. The failure of a worker connection only ends the events of
+/// that worker, and the websocket is closed once the connections of all the workers ended.
+#[tracing::instrument(skip_all, fields(component_id = component_id.to_string()))]
+pub async fn proxy_multiplexed_worker_connection<S>(
+    component_id: ComponentId,
+    worker_streams: Vec<(WorkerId, S)>,
+    filter: WorkerEventFilter,
+    websocket_sender: impl Sink<Message, Error = IoError> + Unpin,
+    websocket_receiver: impl Stream<Item = IoResult<Message>> + Unpin,
+    keep_alive_interval: Duration,
+    max_pong_timeout: Duration,
+) -> Result<(), ConnectProxyError>
+where
+    S: Stream<Item = Result<LogEvent, Status>> + Unpin,
+{
+    let events = stream::select_all(worker_streams.into_iter().map(
+        |(worker_id, worker_stream)| {
+            let filter = filter.clone();
+            let failed_worker_id = worker_id.clone();
+            worker_stream
+                .map(decode_worker_event)
+                .take_while(move |result| {
+                    if let Err(error) = result {
+                        info!(
+                            worker_id = failed_worker_id.to_string(),
+                            error = error.to_string(),
+                            "Worker stream failed"
+                        );
+                    }
+                    future::ready(result.is_ok())
+                })
+                .filter_map(move |result| {
+                    future::ready(
+                        result
+                            .ok()
+                            .filter(|event| filter.matches(event))
+                            .map(|event| {
+                                Ok(MultiplexedWorkerEvent {
+                                    worker_id: worker_id.clone(),
+                                    event,
+                                })
+                            }),
+                    )
+                })
+        },
+    ));
+
+    proxy_events(
+        events,
+        websocket_sender,
+        websocket_receiver,
+        keep_alive_interval,
+        max_pong_timeout,
+    )
+    .await
+}
+
+async fn proxy_events<T: Serialize>(
+    mut events: impl Stream<Item = Result<T, ConnectProxyError>> + Unpin,
     websocket_sender: impl Sink<Message, Error = IoError> + Unpin,
     websocket_receiver: impl Stream<Item = IoResult<Message>> + Unpin,
     keep_alive_interval: Duration,
@@ -74,7 +161,7 @@ pub async fn proxy_worker_connection(
                 }
             },
 
-            worker_message = worker_stream.next() => {
+            worker_message = events.next() => {
                 if let Some(message) = worker_message {
                     if let Err(error) = forward_worker_message(message, &mut websocket).await {
                         info!(error=error.to_string(), "Error forwarding message to WebSocket client");
@@ -102,15 +189,20 @@ pub async fn proxy_worker_connection(
     result
 }
 
-async fn forward_worker_message<E>(
-    message: Result<LogEvent, tonic::Status>,
+fn decode_worker_event(
+    message: Result<LogEvent, Status>,
+) -> Result<WorkerEvent, ConnectProxyError> {
+    message?.try_into().map_err(ConnectProxyError::Proto)
+}
+
+async fn forward_worker_message<T: Serialize, E>(
+    message: Result<T, ConnectProxyError>,
     socket: &mut (impl Sink<Message, Error = E> + Unpin),
 ) -> Result<(), ConnectProxyError>
 where
     ConnectProxyError: From<E>,
 {
-    let message: WorkerEvent = message?.try_into().map_err(ConnectProxyError::Proto)?;
-    let msg_json = serde_json::to_string(&message)?;
+    let msg_json = serde_json::to_string(&message?)?;
     socket.send(Message::Text(msg_json)).await?;
     Ok(())
 }
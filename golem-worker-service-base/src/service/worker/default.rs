@@ -16,8 +16,11 @@ use super::{
     AllExecutors, CallWorkerExecutorError, HasWorkerExecutorClients, RandomExecutor,
     ResponseMapResult, RoutingLogic, WorkerServiceError, WorkerStream,
 };
+use crate::metrics::invocations::record_invocation;
+use crate::service::component::ComponentService;
 use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use futures::stream::TryStreamExt;
 use futures::{Stream, StreamExt};
 use golem_api_grpc::proto::golem::worker::LogEvent;
@@ -27,28 +30,39 @@ use golem_api_grpc::proto::golem::workerexecutor;
 use golem_api_grpc::proto::golem::workerexecutor::v1::worker_executor_client::WorkerExecutorClient;
 use golem_api_grpc::proto::golem::workerexecutor::v1::{
     ActivatePluginRequest, CancelInvocationRequest, CompletePromiseRequest, ConnectWorkerRequest,
-    CreateWorkerRequest, DeactivatePluginRequest, ForkWorkerRequest, InterruptWorkerRequest,
+    CreateWorkerRequest, DeactivatePluginRequest, ForkWorkerRequest, GetInvocationLogsRequest,
+    GetInvocationTreeRequest, GetWorkerStatusSummaryRequest, InterruptWorkerRequest,
     InvokeAndAwaitWorkerJsonRequest, InvokeAndAwaitWorkerRequest, ResumeWorkerRequest,
-    RevertWorkerRequest, SearchOplogResponse, UpdateWorkerRequest,
+    RevertWorkerRequest, SearchOplogResponse, UpdateWorkerLabelsRequest, UpdateWorkerRequest,
 };
 use golem_common::client::MultiTargetGrpcClient;
+use golem_common::model::invocation_tree::InvocationTree;
 use golem_common::model::oplog::OplogIndex;
-use golem_common::model::public_oplog::{OplogCursor, PublicOplogEntry};
+use golem_common::model::public_oplog::{
+    LogParameters, OplogCursor, PublicOplogEntry, PublicOplogEntryFilter,
+};
 use golem_common::model::RetryConfig;
 use golem_common::model::{
     AccountId, ComponentFilePath, ComponentFileSystemNode, ComponentId, ComponentVersion,
     FilterComparator, IdempotencyKey, PluginInstallationId, PromiseId, ScanCursor, TargetWorkerId,
-    WorkerFilter, WorkerId, WorkerStatus,
+    WorkerFilter, WorkerId, WorkerStatus, WorkerStatusSummary,
 };
+use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
 use golem_service_base::model::{
     GetOplogResponse, GolemErrorUnknown, PublicOplogEntryWithIndex, ResourceLimits, WorkerMetadata,
 };
 use golem_service_base::model::{GolemError, RevertWorkerTarget};
+use golem_service_base::service::namespace_quota::{
+    ensure_within_limit, NamespaceQuotaService, QuotaResource,
+};
 use golem_service_base::service::routing_table::{HasRoutingTableService, RoutingTableService};
 use golem_wasm_ast::analysis::AnalysedFunctionResult;
 use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use golem_wasm_rpc::protobuf::Val as ProtoVal;
+use std::collections::HashSet;
 use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
 use std::{collections::HashMap, sync::Arc};
 use tonic::transport::Channel;
 use tonic::Code;
@@ -56,6 +70,10 @@ use tracing::{error, info};
 
 pub type WorkerResult<T> = Result<T, WorkerServiceError>;
 
+// Workers created through other instances of the service, or implicitly by invoking them,
+// are picked up when the workers are counted again after this period
+const WORKER_COUNT_TTL: Duration = Duration::from_secs(30);
+
 #[async_trait]
 pub trait WorkerService {
     async fn create(
@@ -64,6 +82,8 @@ pub trait WorkerService {
         component_version: u64,
         arguments: Vec<String>,
         environment_variables: HashMap<String, String>,
+        wasi_config_vars: HashMap<String, String>,
+        labels: HashMap<String, String>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<WorkerId>;
 
@@ -146,6 +166,7 @@ pub trait WorkerService {
 
     /// Validates the provided list of `TypeAnnotatedValue` parameters, and then enqueues
     /// an invocation for the worker without awaiting its results.
+    #[allow(clippy::too_many_arguments)]
     async fn validate_and_invoke(
         &self,
         worker_id: &TargetWorkerId,
@@ -153,6 +174,7 @@ pub trait WorkerService {
         function_name: String,
         params: Vec<TypeAnnotatedValue>,
         invocation_context: Option<InvocationContext>,
+        invoke_at: Option<DateTime<Utc>>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<()> {
         let params = self.validate_typed_parameters(params)?;
@@ -162,6 +184,7 @@ pub trait WorkerService {
             function_name,
             params,
             invocation_context,
+            invoke_at,
             metadata,
         )
         .await
@@ -169,6 +192,7 @@ pub trait WorkerService {
 
     /// Enqueues an invocation for the worker without awaiting its results, using raw `Val`
     /// parameters.
+    #[allow(clippy::too_many_arguments)]
     async fn invoke(
         &self,
         worker_id: &TargetWorkerId,
@@ -176,12 +200,14 @@ pub trait WorkerService {
         function_name: String,
         params: Vec<ProtoVal>,
         invocation_context: Option<InvocationContext>,
+        invoke_at: Option<DateTime<Utc>>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<()>;
 
     /// Enqueues an invocation for the worker without awaiting its results, using JSON value
     /// encoding represented as raw strings. Without type information these representations cannot
     /// be converted to `Val` so they get forwarded as-is to the executor.
+    #[allow(clippy::too_many_arguments)]
     async fn invoke_json(
         &self,
         worker_id: &TargetWorkerId,
@@ -189,6 +215,7 @@ pub trait WorkerService {
         function_name: String,
         params: Vec<String>,
         invocation_context: Option<InvocationContext>,
+        invoke_at: Option<DateTime<Utc>>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<()>;
 
@@ -223,6 +250,18 @@ pub trait WorkerService {
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<(Option<ScanCursor>, Vec<WorkerMetadata>)>;
 
+    /// Gets the number of workers per status of a component from the status projection
+    /// maintained by the worker executors, without enumerating the workers.
+    ///
+    /// With `backfill` the workers missing from the projection are added to it first, which
+    /// enumerates the workers.
+    async fn get_status_summary(
+        &self,
+        component_id: &ComponentId,
+        backfill: bool,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<WorkerStatusSummary>;
+
     async fn resume(
         &self,
         worker_id: &WorkerId,
@@ -253,6 +292,7 @@ pub trait WorkerService {
         cursor: Option<OplogCursor>,
         count: u64,
         query: String,
+        filter: PublicOplogEntryFilter,
         metadata: WorkerRequestMetadata,
     ) -> Result<GetOplogResponse, WorkerServiceError>;
 
@@ -305,6 +345,28 @@ pub trait WorkerService {
         idempotency_key: &IdempotencyKey,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<bool>;
+
+    /// Gets the most recent stdout, stderr and log entries emitted by an invocation
+    async fn get_invocation_logs(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<Vec<LogParameters>>;
+
+    async fn update_labels(
+        &self,
+        worker_id: &WorkerId,
+        labels: HashMap<String, String>,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<()>;
+
+    /// Gets the invocations of the workers belonging to a trace, as recorded in their oplogs
+    async fn get_invocation_tree(
+        &self,
+        trace_id: &str,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<InvocationTree>;
 }
 
 pub struct TypedResult {
@@ -326,6 +388,17 @@ pub struct WorkerServiceDefault {
     //       rather it emits a warn log and resets the retry state.
     worker_executor_retries: RetryConfig,
     routing_table_service: Arc<dyn RoutingTableService + Send + Sync>,
+    // The quotas are enforced here rather than by the APIs, so that every way of creating and
+    // invoking workers is limited, including the gateway, batch, async and scheduled invocations
+    namespace_quota_service: Arc<dyn NamespaceQuotaService<DefaultNamespace> + Send + Sync>,
+    component_service: Arc<dyn ComponentService<EmptyAuthCtx> + Send + Sync>,
+    worker_count: Arc<Mutex<Option<WorkerCount>>>,
+}
+
+#[derive(Clone, Copy)]
+struct WorkerCount {
+    counted_at: Instant,
+    count: u64,
 }
 
 impl WorkerServiceDefault {
@@ -333,11 +406,16 @@ impl WorkerServiceDefault {
         worker_executor_clients: MultiTargetGrpcClient<WorkerExecutorClient<Channel>>,
         worker_executor_retries: RetryConfig,
         routing_table_service: Arc<dyn RoutingTableService + Send + Sync>,
+        namespace_quota_service: Arc<dyn NamespaceQuotaService<DefaultNamespace> + Send + Sync>,
+        component_service: Arc<dyn ComponentService<EmptyAuthCtx> + Send + Sync>,
     ) -> Self {
         Self {
             worker_executor_clients,
             worker_executor_retries,
             routing_table_service,
+            namespace_quota_service,
+            component_service,
+            worker_count: Arc::new(Mutex::new(None)),
         }
     }
 }
@@ -366,8 +444,12 @@ impl WorkerService for WorkerServiceDefault {
         component_version: u64,
         arguments: Vec<String>,
         environment_variables: HashMap<String, String>,
+        wasi_config_vars: HashMap<String, String>,
+        labels: HashMap<String, String>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<WorkerId> {
+        self.check_worker_quota(&metadata).await?;
+
         let worker_id_clone = worker_id.clone();
         self.call_worker_executor(
             worker_id.clone(),
@@ -382,6 +464,8 @@ impl WorkerService for WorkerServiceDefault {
                     env: environment_variables.clone(),
                     account_id: metadata.account_id.clone().map(|id| id.into()),
                     account_limits: metadata.limits.clone().map(|id| id.into()),
+                    wasi_config_vars: wasi_config_vars.clone(),
+                    labels: labels.clone(),
                 }))
             },
             |response| match response.into_inner() {
@@ -397,6 +481,8 @@ impl WorkerService for WorkerServiceDefault {
         )
         .await?;
 
+        self.update_worker_count(|count| count + 1);
+
         Ok(worker_id.clone())
     }
 
@@ -469,6 +555,8 @@ impl WorkerService for WorkerServiceDefault {
         )
         .await?;
 
+        self.update_worker_count(|count| count.saturating_sub(1));
+
         Ok(())
     }
 
@@ -494,6 +582,9 @@ impl WorkerService for WorkerServiceDefault {
         invocation_context: Option<InvocationContext>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<TypeAnnotatedValue> {
+        self.check_invocation_quota(worker_id, &metadata).await?;
+        record_invocation(&worker_id.component_id);
+
         let worker_id = worker_id.clone();
         let worker_id_clone = worker_id.clone();
         let function_name_clone = function_name.clone();
@@ -557,6 +648,9 @@ impl WorkerService for WorkerServiceDefault {
         invocation_context: Option<InvocationContext>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<InvokeResult> {
+        self.check_invocation_quota(worker_id, &metadata).await?;
+        record_invocation(&worker_id.component_id);
+
         let worker_id = worker_id.clone();
         let worker_id_clone = worker_id.clone();
 
@@ -618,6 +712,9 @@ impl WorkerService for WorkerServiceDefault {
         invocation_context: Option<InvocationContext>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<TypeAnnotatedValue> {
+        self.check_invocation_quota(worker_id, &metadata).await?;
+        record_invocation(&worker_id.component_id);
+
         let worker_id = worker_id.clone();
         let worker_id_clone = worker_id.clone();
         let function_name_clone = function_name.clone();
@@ -679,8 +776,12 @@ impl WorkerService for WorkerServiceDefault {
         function_name: String,
         params: Vec<ProtoVal>,
         invocation_context: Option<InvocationContext>,
+        invoke_at: Option<DateTime<Utc>>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<()> {
+        self.check_invocation_quota(worker_id, &metadata).await?;
+        record_invocation(&worker_id.component_id);
+
         let worker_id = worker_id.clone();
         self.call_worker_executor(
             worker_id.clone(),
@@ -697,6 +798,7 @@ impl WorkerService for WorkerServiceDefault {
                         account_id: metadata.account_id.clone().map(|id| id.into()),
                         account_limits: metadata.limits.clone().map(|id| id.into()),
                         context: invocation_context.clone(),
+                        invoke_at: invoke_at.map(|t| SystemTime::from(t).into()),
                     },
                 ))
             },
@@ -725,8 +827,12 @@ impl WorkerService for WorkerServiceDefault {
         function_name: String,
         params: Vec<String>,
         invocation_context: Option<InvocationContext>,
+        invoke_at: Option<DateTime<Utc>>,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<()> {
+        self.check_invocation_quota(worker_id, &metadata).await?;
+        record_invocation(&worker_id.component_id);
+
         let worker_id = worker_id.clone();
         self.call_worker_executor(
             worker_id.clone(),
@@ -743,6 +849,7 @@ impl WorkerService for WorkerServiceDefault {
                         account_id: metadata.account_id.clone().map(|id| id.into()),
                         account_limits: metadata.limits.clone().map(|id| id.into()),
                         context: invocation_context.clone(),
+                        invoke_at: invoke_at.map(|t| SystemTime::from(t).into()),
                     },
                 ))
             },
@@ -923,6 +1030,61 @@ impl WorkerService for WorkerServiceDefault {
         }
     }
 
+    async fn get_status_summary(
+        &self,
+        component_id: &ComponentId,
+        backfill: bool,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<WorkerStatusSummary> {
+        let component_id = component_id.clone();
+        let summary = self
+            .call_worker_executor(
+                RandomExecutor,
+                "get_worker_status_summary",
+                move |worker_executor_client| {
+                    let component_id: golem_api_grpc::proto::golem::component::ComponentId =
+                        component_id.clone().into();
+                    Box::pin(worker_executor_client.get_worker_status_summary(
+                        GetWorkerStatusSummaryRequest {
+                            component_id: Some(component_id),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                            backfill,
+                        },
+                    ))
+                },
+                |response| match response.into_inner() {
+                    workerexecutor::v1::GetWorkerStatusSummaryResponse {
+                        result:
+                            Some(
+                                workerexecutor::v1::get_worker_status_summary_response::Result::Success(
+                                    summary,
+                                ),
+                            ),
+                    } => WorkerStatusSummary::try_from(summary).map_err(|err| {
+                        GolemError::Unknown(GolemErrorUnknown {
+                            details: format!("Unexpected worker status summary in response: {err}"),
+                        })
+                        .into()
+                    }),
+                    workerexecutor::v1::GetWorkerStatusSummaryResponse {
+                        result:
+                            Some(
+                                workerexecutor::v1::get_worker_status_summary_response::Result::Failure(
+                                    err,
+                                ),
+                            ),
+                    } => Err(err.into()),
+                    workerexecutor::v1::GetWorkerStatusSummaryResponse { .. } => {
+                        Err("Empty response".into())
+                    }
+                },
+                WorkerServiceError::InternalCallError,
+            )
+            .await?;
+
+        Ok(summary)
+    }
+
     async fn resume(
         &self,
         worker_id: &WorkerId,
@@ -1047,6 +1209,7 @@ impl WorkerService for WorkerServiceDefault {
                                     (first_index_in_chunk) + idx as u64,
                                 ),
                                 entry,
+                                decoded: None,
                             })
                             .collect(),
                         next: next.map(|c| c.into()),
@@ -1070,6 +1233,7 @@ impl WorkerService for WorkerServiceDefault {
         cursor: Option<OplogCursor>,
         count: u64,
         query: String,
+        filter: PublicOplogEntryFilter,
         metadata: WorkerRequestMetadata,
     ) -> Result<GetOplogResponse, WorkerServiceError> {
         let worker_id = worker_id.clone();
@@ -1087,6 +1251,7 @@ impl WorkerService for WorkerServiceDefault {
                         cursor: cursor.clone().map(|c| c.into()),
                         count,
                         account_id: metadata.account_id.clone().map(|id| id.into()),
+                        filter: Some(filter.clone().into()),
                     }),
                 )
             },
@@ -1357,6 +1522,8 @@ impl WorkerService for WorkerServiceDefault {
         oplog_index_cut_off: OplogIndex,
         metadata: WorkerRequestMetadata,
     ) -> WorkerResult<()> {
+        self.check_worker_quota(&metadata).await?;
+
         let source_worker_id = source_worker_id.clone();
         let target_worker_id = target_worker_id.clone();
         self.call_worker_executor(
@@ -1384,6 +1551,9 @@ impl WorkerService for WorkerServiceDefault {
             WorkerServiceError::InternalCallError,
         )
         .await?;
+
+        self.update_worker_count(|count| count + 1);
+
         Ok(())
     }
 
@@ -1455,9 +1625,240 @@ impl WorkerService for WorkerServiceDefault {
         .await?;
         Ok(canceled)
     }
+
+    async fn get_invocation_logs(
+        &self,
+        worker_id: &WorkerId,
+        idempotency_key: &IdempotencyKey,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<Vec<LogParameters>> {
+        let worker_id = worker_id.clone();
+        let idempotency_key = idempotency_key.clone();
+        let entries = self.call_worker_executor(
+            worker_id.clone(),
+            "get_invocation_logs",
+            move |worker_executor_client| {
+                let worker_id = worker_id.clone();
+                let idempotency_key = idempotency_key.clone();
+                Box::pin(worker_executor_client.get_invocation_logs(GetInvocationLogsRequest {
+                    worker_id: Some(worker_id.into()),
+                    idempotency_key: Some(idempotency_key.into()),
+                    account_id: metadata.account_id.clone().map(|id| id.into()),
+                }))
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::GetInvocationLogsResponse {
+                    result: Some(workerexecutor::v1::get_invocation_logs_response::Result::Success(success)),
+                } => success
+                    .entries
+                    .into_iter()
+                    .map(LogParameters::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| {
+                        GolemError::Unknown(GolemErrorUnknown {
+                            details: format!("Unexpected invocation log entries in response: {err}"),
+                        })
+                        .into()
+                    }),
+                workerexecutor::v1::GetInvocationLogsResponse {
+                    result: Some(workerexecutor::v1::get_invocation_logs_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::GetInvocationLogsResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+        .await?;
+        Ok(entries)
+    }
+
+    async fn update_labels(
+        &self,
+        worker_id: &WorkerId,
+        labels: HashMap<String, String>,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<()> {
+        let worker_id = worker_id.clone();
+        self.call_worker_executor(
+            worker_id.clone(),
+            "update_worker_labels",
+            move |worker_executor_client| {
+                let worker_id = worker_id.clone();
+                Box::pin(
+                    worker_executor_client.update_worker_labels(UpdateWorkerLabelsRequest {
+                        worker_id: Some(worker_id.into()),
+                        account_id: metadata.account_id.clone().map(|id| id.into()),
+                        labels: labels.clone(),
+                    }),
+                )
+            },
+            |response| match response.into_inner() {
+                workerexecutor::v1::UpdateWorkerLabelsResponse {
+                    result: Some(workerexecutor::v1::update_worker_labels_response::Result::Success(_)),
+                } => Ok(()),
+                workerexecutor::v1::UpdateWorkerLabelsResponse {
+                    result: Some(workerexecutor::v1::update_worker_labels_response::Result::Failure(err)),
+                } => Err(err.into()),
+                workerexecutor::v1::UpdateWorkerLabelsResponse { .. } => Err("Empty response".into()),
+            },
+            WorkerServiceError::InternalCallError,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn get_invocation_tree(
+        &self,
+        trace_id: &str,
+        metadata: WorkerRequestMetadata,
+    ) -> WorkerResult<InvocationTree> {
+        let trace_id = trace_id.to_string();
+        let tree = self
+            .call_worker_executor(
+                RandomExecutor,
+                "get_invocation_tree",
+                move |worker_executor_client| {
+                    Box::pin(worker_executor_client.get_invocation_tree(
+                        GetInvocationTreeRequest {
+                            trace_id: trace_id.clone(),
+                            account_id: metadata.account_id.clone().map(|id| id.into()),
+                        },
+                    ))
+                },
+                |response| match response.into_inner() {
+                    workerexecutor::v1::GetInvocationTreeResponse {
+                        result:
+                            Some(workerexecutor::v1::get_invocation_tree_response::Result::Success(
+                                tree,
+                            )),
+                    } => InvocationTree::try_from(tree).map_err(|err| {
+                        GolemError::Unknown(GolemErrorUnknown {
+                            details: format!("Unexpected invocation tree in response: {err}"),
+                        })
+                        .into()
+                    }),
+                    workerexecutor::v1::GetInvocationTreeResponse {
+                        result:
+                            Some(workerexecutor::v1::get_invocation_tree_response::Result::Failure(
+                                err,
+                            )),
+                    } => Err(err.into()),
+                    workerexecutor::v1::GetInvocationTreeResponse { .. } => {
+                        Err("Empty response".into())
+                    }
+                },
+                WorkerServiceError::InternalCallError,
+            )
+            .await?;
+
+        Ok(tree)
+    }
 }
 
 impl WorkerServiceDefault {
+    // Fails if creating a worker would exceed the worker quota of the namespace
+    async fn check_worker_quota(&self, metadata: &WorkerRequestMetadata) -> WorkerResult<()> {
+        let limit = self
+            .namespace_quota_service
+            .limit(&DefaultNamespace::default(), QuotaResource::Workers)
+            .await?;
+
+        if limit.is_some() {
+            let workers = self.count_workers(metadata).await?;
+            ensure_within_limit(QuotaResource::Workers, limit, workers + 1)?;
+        }
+
+        Ok(())
+    }
+
+    // Counts the invocation against the invocation rate quota of the namespace. Invoking a worker
+    // which does not exist yet creates it, which is refused once the namespace is at its worker
+    // limit.
+    async fn check_invocation_quota(
+        &self,
+        worker_id: &TargetWorkerId,
+        metadata: &WorkerRequestMetadata,
+    ) -> WorkerResult<()> {
+        self.namespace_quota_service
+            .acquire_invocation(&DefaultNamespace::default())
+            .await?;
+
+        let limit = self
+            .namespace_quota_service
+            .limit(&DefaultNamespace::default(), QuotaResource::Workers)
+            .await?;
+
+        if let Some(limit) = limit {
+            let workers = self.count_workers(metadata).await?;
+            if workers >= limit && !self.worker_exists(worker_id, metadata).await? {
+                ensure_within_limit(QuotaResource::Workers, Some(limit), workers + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn worker_exists(
+        &self,
+        worker_id: &TargetWorkerId,
+        metadata: &WorkerRequestMetadata,
+    ) -> WorkerResult<bool> {
+        // Targeting no worker in particular always creates a new one
+        let Some(worker_name) = &worker_id.worker_name else {
+            return Ok(false);
+        };
+
+        let worker_id = WorkerId {
+            component_id: worker_id.component_id.clone(),
+            worker_name: worker_name.clone(),
+        };
+
+        match self.get_metadata(&worker_id, metadata.clone()).await {
+            Ok(_) => Ok(true),
+            Err(WorkerServiceError::Golem(GolemError::WorkerNotFound(_))) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    // The number of workers of all the components, counted again once the previous count is
+    // older than WORKER_COUNT_TTL, instead of on every check
+    async fn count_workers(&self, metadata: &WorkerRequestMetadata) -> WorkerResult<u64> {
+        let cached = *self.worker_count.lock().unwrap();
+        if let Some(worker_count) = cached {
+            if worker_count.counted_at.elapsed() < WORKER_COUNT_TTL {
+                return Ok(worker_count.count);
+            }
+        }
+
+        let component_ids = self
+            .component_service
+            .get_all(&EmptyAuthCtx::default())
+            .await?
+            .into_iter()
+            .map(|component| component.versioned_component_id.component_id)
+            .collect::<HashSet<_>>();
+
+        let mut count = 0;
+        for component_id in component_ids {
+            count += self
+                .get_status_summary(&component_id, false, metadata.clone())
+                .await?
+                .total;
+        }
+
+        *self.worker_count.lock().unwrap() = Some(WorkerCount {
+            counted_at: Instant::now(),
+            count,
+        });
+
+        Ok(count)
+    }
+
+    fn update_worker_count(&self, update: impl FnOnce(u64) -> u64) {
+        if let Some(worker_count) = self.worker_count.lock().unwrap().as_mut() {
+            worker_count.count = update(worker_count.count);
+        }
+    }
+
     async fn find_running_metadata_internal(
         &self,
         component_id: &ComponentId,
@@ -18,6 +18,7 @@ use golem_api_grpc::proto::golem::worker::v1::{
 use golem_common::model::{AccountId, ComponentFilePath, ComponentId, WorkerId};
 use golem_common::SafeDisplay;
 use golem_service_base::model::{GolemError, VersionedComponentId};
+use golem_service_base::service::namespace_quota::{NamespaceQuotaError, QuotaExceeded};
 
 use crate::service::component::ComponentServiceError;
 use crate::service::worker::CallWorkerExecutorError;
@@ -46,6 +47,8 @@ pub enum WorkerServiceError {
     FileNotFound(ComponentFilePath),
     #[error("Bad file type: {0}")]
     BadFileType(ComponentFilePath),
+    #[error("{}", .0.error)]
+    QuotaExceeded(QuotaExceeded),
 }
 
 impl SafeDisplay for WorkerServiceError {
@@ -62,6 +65,7 @@ impl SafeDisplay for WorkerServiceError {
             WorkerServiceError::InternalCallError(inner) => inner.to_safe_string(),
             WorkerServiceError::FileNotFound(_) => self.to_string(),
             WorkerServiceError::BadFileType(_) => self.to_string(),
+            WorkerServiceError::QuotaExceeded(_) => self.to_string(),
         }
     }
 }
@@ -113,6 +117,9 @@ impl From<WorkerServiceError> for worker_error::Error {
             WorkerServiceError::BadFileType(_) => worker_error::Error::BadRequest(ErrorsBody {
                 errors: vec![error.to_safe_string()],
             }),
+            WorkerServiceError::QuotaExceeded(_) => worker_error::Error::LimitExceeded(ErrorBody {
+                error: error.to_safe_string(),
+            }),
         }
     }
 }
@@ -122,3 +129,14 @@ impl From<GolemError> for WorkerServiceError {
         WorkerServiceError::Golem(value)
     }
 }
+
+impl From<NamespaceQuotaError> for WorkerServiceError {
+    fn from(value: NamespaceQuotaError) -> Self {
+        match value {
+            NamespaceQuotaError::Exceeded(exceeded) => WorkerServiceError::QuotaExceeded(exceeded),
+            NamespaceQuotaError::UnsupportedResource(_) | NamespaceQuotaError::InternalError(_) => {
+                WorkerServiceError::Internal(value.to_safe_string())
+            }
+        }
+    }
+}
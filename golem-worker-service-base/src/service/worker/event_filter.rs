@@ -0,0 +1,156 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::model::{LogLevel, Timestamp, WorkerEvent, WorkerId};
+use regex::Regex;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Filters the events streamed from a worker connection before sending them to the client
+///
+/// Stdout counts as `info` and stderr as `error` for the minimal level, and the message regex is
+/// matched against their text. When a level or a message filter is set, only the output and log
+/// events are streamed, without the invocation start and finish events.
+#[derive(Debug, Clone, Default)]
+pub struct WorkerEventFilter {
+    pub min_level: Option<LogLevel>,
+    pub message: Option<Regex>,
+    pub since: Option<Timestamp>,
+}
+
+impl WorkerEventFilter {
+    pub fn new(
+        level: Option<&str>,
+        message: Option<&str>,
+        since: Option<&str>,
+    ) -> Result<Self, String> {
+        let min_level = level
+            .map(|level| match level.to_lowercase().as_str() {
+                "trace" => Ok(LogLevel::Trace),
+                "debug" => Ok(LogLevel::Debug),
+                "info" => Ok(LogLevel::Info),
+                "warn" => Ok(LogLevel::Warn),
+                "error" => Ok(LogLevel::Error),
+                "critical" => Ok(LogLevel::Critical),
+                _ => Err(format!("Invalid log level: {level}")),
+            })
+            .transpose()?;
+        let message = message
+            .map(|message| {
+                Regex::new(message).map_err(|err| format!("Invalid message filter: {err}"))
+            })
+            .transpose()?;
+        let since = since
+            .map(|since| {
+                Timestamp::from_str(since).map_err(|err| format!("Invalid since filter: {err}"))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            min_level,
+            message,
+            since,
+        })
+    }
+
+    pub fn matches(&self, event: &WorkerEvent) -> bool {
+        match event {
+            WorkerEvent::StdOut { timestamp, bytes } => {
+                self.matches_log(timestamp, &LogLevel::Info, &String::from_utf8_lossy(bytes))
+            }
+            WorkerEvent::StdErr { timestamp, bytes } => {
+                self.matches_log(timestamp, &LogLevel::Error, &String::from_utf8_lossy(bytes))
+            }
+            WorkerEvent::Log {
+                timestamp,
+                level,
+                message,
+                ..
+            } => self.matches_log(timestamp, level, message),
+            WorkerEvent::InvocationStart { timestamp, .. }
+            | WorkerEvent::InvocationFinished { timestamp, .. } => {
+                self.min_level.is_none() && self.message.is_none() && self.is_recent(timestamp)
+            }
+            WorkerEvent::Close => true,
+        }
+    }
+
+    fn matches_log(&self, timestamp: &Timestamp, level: &LogLevel, message: &str) -> bool {
+        self.is_recent(timestamp)
+            && self
+                .min_level
+                .as_ref()
+                .is_none_or(|min_level| severity(level) >= severity(min_level))
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(message))
+    }
+
+    fn is_recent(&self, timestamp: &Timestamp) -> bool {
+        self.since.is_none_or(|since| *timestamp >= since)
+    }
+}
+
+fn severity(level: &LogLevel) -> u8 {
+    match level {
+        LogLevel::Trace => 0,
+        LogLevel::Debug => 1,
+        LogLevel::Info => 2,
+        LogLevel::Warn => 3,
+        LogLevel::Error => 4,
+        LogLevel::Critical => 5,
+    }
+}
+
+/// An event of one of the workers streamed through a multiplexed connection
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiplexedWorkerEvent {
+    pub worker_id: WorkerId,
+    pub event: WorkerEvent,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_r::test;
+
+    #[test]
+    fn events_are_filtered_by_level_message_and_time() {
+        let filter = WorkerEventFilter::new(Some("warn"), Some("^order \\d+"), None).unwrap();
+
+        assert!(filter.matches(&WorkerEvent::log(LogLevel::Error, "", "order 12 failed")));
+        assert!(!filter.matches(&WorkerEvent::log(LogLevel::Info, "", "order 12 placed")));
+        assert!(!filter.matches(&WorkerEvent::log(LogLevel::Warn, "", "cart 3 expired")));
+        assert!(filter.matches(&WorkerEvent::stderr(b"order 7 retried".to_vec())));
+        assert!(!filter.matches(&WorkerEvent::stdout(b"order 7 retried".to_vec())));
+        assert!(filter.matches(&WorkerEvent::Close));
+
+        let since = Timestamp::from(2_000);
+        let filter = WorkerEventFilter {
+            since: Some(since),
+            ..WorkerEventFilter::default()
+        };
+        let event_at = |millis: u64| WorkerEvent::StdOut {
+            timestamp: Timestamp::from(millis),
+            bytes: vec![],
+        };
+        assert!(!filter.matches(&event_at(1_999)));
+        assert!(filter.matches(&event_at(2_000)));
+
+        assert!(WorkerEventFilter::new(Some("loud"), None, None).is_err());
+        assert!(WorkerEventFilter::new(None, Some("("), None).is_err());
+    }
+}
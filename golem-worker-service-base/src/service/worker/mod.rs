@@ -12,16 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub use async_invocation::*;
+pub use batch_invocation::*;
+pub use bulk_operation::*;
 pub use connect_proxy::*;
 pub use default::*;
 pub use error::*;
+pub use event_filter::*;
 pub use invocation_parameters::*;
 pub use routing_logic::*;
+pub use schedule::*;
 pub use worker_stream::*;
 
+mod async_invocation;
+mod batch_invocation;
+mod bulk_operation;
 mod connect_proxy;
 mod default;
 mod error;
+mod event_filter;
 mod invocation_parameters;
 mod routing_logic;
+mod schedule;
 mod worker_stream;
@@ -0,0 +1,815 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app_config::SchedulerConfig;
+use crate::empty_worker_metadata;
+use crate::repo::worker_schedule::{WorkerScheduleRecord, WorkerScheduleRepo};
+use crate::service::worker::{InvocationParameters, WorkerService};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use golem_api_grpc::proto::golem::common::{ErrorBody, ErrorsBody};
+use golem_api_grpc::proto::golem::worker::v1::{
+    worker_error, worker_execution_error, UnknownError, WorkerError as GrpcWorkerError,
+    WorkerExecutionError,
+};
+use golem_api_grpc::proto::golem::worker::v1::{
+    MisfirePolicy as GrpcMisfirePolicy, WorkerSchedule as GrpcWorkerSchedule,
+    WorkerScheduleRequest as GrpcWorkerScheduleRequest,
+};
+use golem_common::model::{ComponentId, IdempotencyKey, TargetWorkerId};
+use golem_common::SafeDisplay;
+use golem_service_base::model::validate_worker_name;
+use golem_wasm_rpc::json::OptionallyTypeAnnotatedValueJson;
+use poem_openapi::{Enum, Object};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+// The idempotency keys of the scheduled invocations are derived from the schedule and the time
+// of the run, so a run triggered again after a failure or by another replica is not repeated
+const SCHEDULED_INVOCATION_NS: Uuid = uuid::uuid!("3F4E6D8A-0B2C-4E7F-9A1D-5C6B7E8F9012");
+
+/// What happens to a run which could not be triggered in time, for example while the
+/// worker service was down. `Skip` drops the missed runs, `FireOnce` triggers a single
+/// invocation for all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Enum)]
+pub enum MisfirePolicy {
+    #[default]
+    Skip,
+    FireOnce,
+}
+
+impl Display for MisfirePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MisfirePolicy::Skip => write!(f, "skip"),
+            MisfirePolicy::FireOnce => write!(f, "fire-once"),
+        }
+    }
+}
+
+impl FromStr for MisfirePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(MisfirePolicy::Skip),
+            "fire-once" => Ok(MisfirePolicy::FireOnce),
+            _ => Err(format!("Unknown misfire policy: {}", s)),
+        }
+    }
+}
+
+/// A function of a worker to be invoked on a cron schedule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerScheduleRequest {
+    pub schedule_id: String,
+    pub component_id: ComponentId,
+    pub worker_name: String,
+    pub function: String,
+    pub params: Vec<OptionallyTypeAnnotatedValueJson>,
+    /// A cron expression with 5 fields, or 6 fields starting with the seconds.
+    /// Days of the week are numbered from 1 (Sunday), or given by name, like `MON-FRI`.
+    pub cron: String,
+    /// An IANA time zone the cron expression is evaluated in, UTC if not given
+    pub timezone: Option<String>,
+    pub misfire_policy: Option<MisfirePolicy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Object)]
+#[serde(rename_all = "camelCase")]
+#[oai(rename_all = "camelCase")]
+pub struct WorkerSchedule {
+    pub schedule_id: String,
+    pub component_id: ComponentId,
+    pub worker_name: String,
+    pub function: String,
+    pub params: Vec<OptionallyTypeAnnotatedValueJson>,
+    pub cron: String,
+    pub timezone: String,
+    pub misfire_policy: MisfirePolicy,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<WorkerScheduleRecord> for WorkerSchedule {
+    type Error = String;
+
+    fn try_from(value: WorkerScheduleRecord) -> Result<Self, Self::Error> {
+        Ok(WorkerSchedule {
+            schedule_id: value.schedule_id,
+            component_id: ComponentId(
+                Uuid::parse_str(&value.component_id).map_err(|err| err.to_string())?,
+            ),
+            worker_name: value.worker_name,
+            function: value.function_name,
+            params: serde_json::from_str(&value.function_params).map_err(|err| err.to_string())?,
+            cron: value.cron_expression,
+            timezone: value.timezone,
+            misfire_policy: value.misfire_policy.parse()?,
+            next_run_at: value.next_run_at,
+            last_run_at: value.last_run_at,
+            created_at: value.created_at,
+        })
+    }
+}
+
+impl From<WorkerSchedule> for GrpcWorkerSchedule {
+    fn from(value: WorkerSchedule) -> Self {
+        let timestamp = |value: DateTime<Utc>| prost_types::Timestamp {
+            seconds: value.timestamp(),
+            nanos: value.timestamp_subsec_nanos() as i32,
+        };
+
+        GrpcWorkerSchedule {
+            schedule_id: value.schedule_id,
+            component_id: Some(value.component_id.into()),
+            worker_name: value.worker_name,
+            function: value.function,
+            params: value
+                .params
+                .into_iter()
+                .map(|param| serde_json::to_string(&param).unwrap_or_default())
+                .collect(),
+            cron: value.cron,
+            timezone: value.timezone,
+            misfire_policy: GrpcMisfirePolicy::from(value.misfire_policy) as i32,
+            next_run_at: Some(timestamp(value.next_run_at)),
+            last_run_at: value.last_run_at.map(timestamp),
+            created_at: Some(timestamp(value.created_at)),
+        }
+    }
+}
+
+impl From<MisfirePolicy> for GrpcMisfirePolicy {
+    fn from(value: MisfirePolicy) -> Self {
+        match value {
+            MisfirePolicy::Skip => GrpcMisfirePolicy::MisfireSkip,
+            MisfirePolicy::FireOnce => GrpcMisfirePolicy::MisfireFireOnce,
+        }
+    }
+}
+
+impl TryFrom<GrpcWorkerScheduleRequest> for WorkerScheduleRequest {
+    type Error = String;
+
+    fn try_from(value: GrpcWorkerScheduleRequest) -> Result<Self, Self::Error> {
+        let misfire_policy = value
+            .misfire_policy
+            .map(|policy| match GrpcMisfirePolicy::try_from(policy) {
+                Ok(GrpcMisfirePolicy::MisfireSkip) => Ok(MisfirePolicy::Skip),
+                Ok(GrpcMisfirePolicy::MisfireFireOnce) => Ok(MisfirePolicy::FireOnce),
+                Err(_) => Err(format!("Unknown misfire policy: {}", policy)),
+            })
+            .transpose()?;
+
+        Ok(WorkerScheduleRequest {
+            schedule_id: value.schedule_id,
+            component_id: value
+                .component_id
+                .ok_or("Missing component id")?
+                .try_into()?,
+            worker_name: value.worker_name,
+            function: value.function,
+            params: value
+                .params
+                .iter()
+                .map(|param| serde_json::from_str(param))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| format!("Failed to parse JSON parameters: {}", err))?,
+            cron: value.cron,
+            timezone: value.timezone,
+            misfire_policy,
+        })
+    }
+}
+
+/// A cron expression evaluated in a time zone
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    schedule: cron::Schedule,
+    timezone: Tz,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str, timezone: &str) -> Result<Self, String> {
+        // The standard 5 fields are accepted along with the seconds field of the cron crate
+        let expression = if expression.split_whitespace().count() == 5 {
+            format!("0 {}", expression)
+        } else {
+            expression.to_string()
+        };
+
+        let schedule = cron::Schedule::from_str(&expression)
+            .map_err(|err| format!("Invalid cron expression '{}': {}", expression, err))?;
+        let timezone = timezone
+            .parse::<Tz>()
+            .map_err(|_| format!("Unknown time zone '{}'", timezone))?;
+
+        Ok(CronSchedule { schedule, timezone })
+    }
+
+    // The first run strictly after the given time, if the schedule has any
+    pub fn next_after(&self, time: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.schedule
+            .after(&time.with_timezone(&self.timezone))
+            .next()
+            .map(|next| next.with_timezone(&Utc))
+    }
+}
+
+// The outcome of a due run of a schedule
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledRun {
+    // The time of the run to invoke the function for, if it is not skipped
+    pub fire_at: Option<DateTime<Utc>>,
+    // The next run of the schedule, none if it has no more runs
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub misfired: bool,
+}
+
+impl ScheduledRun {
+    // Runs late by more than the misfire threshold are handled by the misfire policy, and the
+    // schedule continues from the current time, so that the missed runs are not caught up
+    pub fn plan(
+        schedule: &CronSchedule,
+        misfire_policy: MisfirePolicy,
+        due_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+        misfire_threshold: Duration,
+    ) -> ScheduledRun {
+        let late_by = (now - due_at).to_std().unwrap_or_default();
+
+        if late_by <= misfire_threshold {
+            ScheduledRun {
+                fire_at: Some(due_at),
+                next_run_at: schedule.next_after(&due_at),
+                misfired: false,
+            }
+        } else {
+            ScheduledRun {
+                fire_at: match misfire_policy {
+                    MisfirePolicy::Skip => None,
+                    MisfirePolicy::FireOnce => Some(due_at),
+                },
+                next_run_at: schedule.next_after(&now),
+                misfired: true,
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait WorkerScheduleService<Namespace> {
+    async fn create(
+        &self,
+        namespace: &Namespace,
+        request: WorkerScheduleRequest,
+    ) -> Result<WorkerSchedule, WorkerScheduleServiceError>;
+
+    // Replaces the definition of a schedule, its next run is computed from the current time
+    async fn update(
+        &self,
+        namespace: &Namespace,
+        request: WorkerScheduleRequest,
+    ) -> Result<WorkerSchedule, WorkerScheduleServiceError>;
+
+    async fn get(
+        &self,
+        namespace: &Namespace,
+        schedule_id: &str,
+    ) -> Result<WorkerSchedule, WorkerScheduleServiceError>;
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Vec<WorkerSchedule>, WorkerScheduleServiceError>;
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        schedule_id: &str,
+    ) -> Result<(), WorkerScheduleServiceError>;
+
+    // Triggers the due runs of the schedules of all namespaces, returns the number of invocations
+    async fn trigger_due(&self) -> Result<usize, WorkerScheduleServiceError>;
+}
+
+#[derive(Debug, Clone)]
+pub enum WorkerScheduleServiceError {
+    AlreadyExists(String),
+    NotFound(String),
+    InvalidSchedule(Vec<String>),
+    InternalError(String),
+}
+
+// For satisfying thiserror::Error
+// https://github.com/golemcloud/golem/issues/1071
+impl Display for WorkerScheduleServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_safe_string())
+    }
+}
+
+impl SafeDisplay for WorkerScheduleServiceError {
+    fn to_safe_string(&self) -> String {
+        match self {
+            WorkerScheduleServiceError::AlreadyExists(schedule_id) => {
+                format!("Worker schedule {} already exists", schedule_id)
+            }
+            WorkerScheduleServiceError::NotFound(schedule_id) => {
+                format!("Worker schedule {} not found", schedule_id)
+            }
+            WorkerScheduleServiceError::InvalidSchedule(errors) => {
+                format!("Invalid worker schedule: {}", errors.join(", "))
+            }
+            WorkerScheduleServiceError::InternalError(err) => format!("Internal error: {}", err),
+        }
+    }
+}
+
+impl From<WorkerScheduleServiceError> for GrpcWorkerError {
+    fn from(error: WorkerScheduleServiceError) -> Self {
+        let error = match error {
+            WorkerScheduleServiceError::AlreadyExists(_) => {
+                worker_error::Error::AlreadyExists(ErrorBody {
+                    error: error.to_safe_string(),
+                })
+            }
+            WorkerScheduleServiceError::NotFound(_) => worker_error::Error::NotFound(ErrorBody {
+                error: error.to_safe_string(),
+            }),
+            WorkerScheduleServiceError::InvalidSchedule(errors) => {
+                worker_error::Error::BadRequest(ErrorsBody { errors })
+            }
+            WorkerScheduleServiceError::InternalError(_) => {
+                worker_error::Error::InternalError(WorkerExecutionError {
+                    error: Some(worker_execution_error::Error::Unknown(UnknownError {
+                        details: error.to_safe_string(),
+                    })),
+                })
+            }
+        };
+
+        GrpcWorkerError { error: Some(error) }
+    }
+}
+
+pub struct DefaultWorkerScheduleService {
+    repo: Arc<dyn WorkerScheduleRepo + Sync + Send>,
+    worker_service: Arc<dyn WorkerService + Sync + Send>,
+    config: SchedulerConfig,
+}
+
+impl DefaultWorkerScheduleService {
+    pub fn new(
+        repo: Arc<dyn WorkerScheduleRepo + Sync + Send>,
+        worker_service: Arc<dyn WorkerService + Sync + Send>,
+        config: SchedulerConfig,
+    ) -> Self {
+        DefaultWorkerScheduleService {
+            repo,
+            worker_service,
+            config,
+        }
+    }
+
+    fn to_record(
+        namespace: String,
+        request: WorkerScheduleRequest,
+        now: DateTime<Utc>,
+    ) -> Result<WorkerScheduleRecord, WorkerScheduleServiceError> {
+        let mut errors = Vec::new();
+
+        let is_valid_id = !request.schedule_id.is_empty()
+            && request
+                .schedule_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid_id {
+            errors.push(format!(
+                "invalid schedule id '{}', expected letters, digits, '-' and '_'",
+                request.schedule_id
+            ));
+        }
+
+        if let Err(err) = validate_worker_name(&request.worker_name) {
+            errors.push(format!("invalid worker name: {}", err));
+        }
+
+        if let Err(mut params_errors) =
+            InvocationParameters::from_optionally_type_annotated_value_jsons(request.params.clone())
+        {
+            errors.append(&mut params_errors);
+        }
+
+        let timezone = request.timezone.unwrap_or_else(|| "UTC".to_string());
+        let next_run_at = match CronSchedule::parse(&request.cron, &timezone) {
+            Ok(schedule) => match schedule.next_after(&now) {
+                Some(next_run_at) => Some(next_run_at),
+                None => {
+                    errors.push(format!("cron expression '{}' has no runs", request.cron));
+                    None
+                }
+            },
+            Err(err) => {
+                errors.push(err);
+                None
+            }
+        };
+
+        let function_params = serde_json::to_string(&request.params)
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_string()))?;
+
+        match next_run_at {
+            Some(next_run_at) if errors.is_empty() => Ok(WorkerScheduleRecord {
+                namespace,
+                schedule_id: request.schedule_id,
+                component_id: request.component_id.0.to_string(),
+                worker_name: request.worker_name,
+                function_name: request.function,
+                function_params,
+                cron_expression: request.cron,
+                timezone,
+                misfire_policy: request.misfire_policy.unwrap_or_default().to_string(),
+                next_run_at,
+                last_run_at: None,
+                created_at: now,
+            }),
+            _ => Err(WorkerScheduleServiceError::InvalidSchedule(errors)),
+        }
+    }
+
+    async fn invoke(
+        &self,
+        namespace: &str,
+        schedule: &WorkerSchedule,
+        fire_at: &DateTime<Utc>,
+    ) -> Result<(), String> {
+        let idempotency_key = IdempotencyKey::from_uuid(Uuid::new_v5(
+            &SCHEDULED_INVOCATION_NS,
+            format!(
+                "{}/{}/{}/{}",
+                namespace,
+                schedule.schedule_id,
+                schedule.created_at.timestamp_millis(),
+                fire_at.timestamp()
+            )
+            .as_bytes(),
+        ));
+
+        let worker_id = TargetWorkerId {
+            component_id: schedule.component_id.clone(),
+            worker_name: Some(schedule.worker_name.clone()),
+        };
+
+        let params = InvocationParameters::from_optionally_type_annotated_value_jsons(
+            schedule.params.clone(),
+        )
+        .map_err(|errors| errors.join(", "))?;
+
+        match params {
+            InvocationParameters::TypedProtoVals(vals) => {
+                self.worker_service
+                    .validate_and_invoke(
+                        &worker_id,
+                        Some(idempotency_key),
+                        schedule.function.clone(),
+                        vals,
+                        None,
+                        None,
+                        empty_worker_metadata(),
+                    )
+                    .await
+            }
+            InvocationParameters::RawJsonStrings(jsons) => {
+                self.worker_service
+                    .invoke_json(
+                        &worker_id,
+                        Some(idempotency_key),
+                        schedule.function.clone(),
+                        jsons,
+                        None,
+                        None,
+                        empty_worker_metadata(),
+                    )
+                    .await
+            }
+        }
+        .map_err(|err| err.to_safe_string())
+    }
+
+    // The run is invoked before the schedule is moved on, so that it is retried on the next poll
+    // if the worker service stops in between. Failed invocations are retried until the run
+    // misfires.
+    async fn trigger(
+        &self,
+        record: WorkerScheduleRecord,
+        now: DateTime<Utc>,
+    ) -> Result<bool, WorkerScheduleServiceError> {
+        let namespace = record.namespace.clone();
+        let schedule =
+            WorkerSchedule::try_from(record).map_err(WorkerScheduleServiceError::InternalError)?;
+        let cron = CronSchedule::parse(&schedule.cron, &schedule.timezone)
+            .map_err(WorkerScheduleServiceError::InternalError)?;
+
+        let run = ScheduledRun::plan(
+            &cron,
+            schedule.misfire_policy,
+            schedule.next_run_at,
+            now,
+            self.config.misfire_threshold,
+        );
+
+        let fired_at = match &run.fire_at {
+            Some(fire_at) => match self.invoke(&namespace, &schedule, fire_at).await {
+                Ok(()) => Some(*fire_at),
+                Err(err) if !run.misfired => {
+                    warn!(
+                        schedule_id = schedule.schedule_id,
+                        "Failed to invoke a scheduled run, retrying on the next poll: {}", err
+                    );
+                    return Ok(false);
+                }
+                Err(err) => {
+                    error!(
+                        schedule_id = schedule.schedule_id,
+                        "Failed to invoke a misfired scheduled run: {}", err
+                    );
+                    None
+                }
+            },
+            None => {
+                info!(
+                    schedule_id = schedule.schedule_id,
+                    "Skipping a misfired run scheduled at {}", schedule.next_run_at
+                );
+                None
+            }
+        };
+
+        match run.next_run_at {
+            Some(next_run_at) => {
+                self.repo
+                    .advance(
+                        &namespace,
+                        &schedule.schedule_id,
+                        &schedule.next_run_at,
+                        &next_run_at,
+                        fired_at.as_ref(),
+                    )
+                    .await
+                    .map_err(|err| {
+                        WorkerScheduleServiceError::InternalError(err.to_safe_string())
+                    })?;
+            }
+            None => {
+                info!(
+                    schedule_id = schedule.schedule_id,
+                    "Deleting a schedule with no more runs"
+                );
+                self.repo
+                    .delete(&namespace, &schedule.schedule_id)
+                    .await
+                    .map_err(|err| {
+                        WorkerScheduleServiceError::InternalError(err.to_safe_string())
+                    })?;
+            }
+        }
+
+        Ok(fired_at.is_some())
+    }
+}
+
+#[async_trait]
+impl<Namespace: Display + Send + Sync> WorkerScheduleService<Namespace>
+    for DefaultWorkerScheduleService
+{
+    async fn create(
+        &self,
+        namespace: &Namespace,
+        request: WorkerScheduleRequest,
+    ) -> Result<WorkerSchedule, WorkerScheduleServiceError> {
+        let record = Self::to_record(namespace.to_string(), request, Utc::now())?;
+
+        let existing = self
+            .repo
+            .get(&record.namespace, &record.schedule_id)
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?;
+
+        if existing.is_some() {
+            return Err(WorkerScheduleServiceError::AlreadyExists(
+                record.schedule_id,
+            ));
+        }
+
+        self.repo
+            .create(&record)
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?;
+
+        info!(
+            schedule_id = record.schedule_id,
+            "Created worker schedule, next run at {}", record.next_run_at
+        );
+
+        WorkerSchedule::try_from(record).map_err(WorkerScheduleServiceError::InternalError)
+    }
+
+    async fn update(
+        &self,
+        namespace: &Namespace,
+        request: WorkerScheduleRequest,
+    ) -> Result<WorkerSchedule, WorkerScheduleServiceError> {
+        let existing = self
+            .repo
+            .get(&namespace.to_string(), &request.schedule_id)
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?
+            .ok_or(WorkerScheduleServiceError::NotFound(
+                request.schedule_id.clone(),
+            ))?;
+
+        let record = WorkerScheduleRecord {
+            last_run_at: existing.last_run_at,
+            created_at: existing.created_at,
+            ..Self::to_record(namespace.to_string(), request, Utc::now())?
+        };
+
+        let updated = self
+            .repo
+            .update(&record)
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?;
+
+        if !updated {
+            return Err(WorkerScheduleServiceError::NotFound(record.schedule_id));
+        }
+
+        WorkerSchedule::try_from(record).map_err(WorkerScheduleServiceError::InternalError)
+    }
+
+    async fn get(
+        &self,
+        namespace: &Namespace,
+        schedule_id: &str,
+    ) -> Result<WorkerSchedule, WorkerScheduleServiceError> {
+        self.repo
+            .get(&namespace.to_string(), schedule_id)
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?
+            .ok_or(WorkerScheduleServiceError::NotFound(
+                schedule_id.to_string(),
+            ))
+            .and_then(|record| {
+                WorkerSchedule::try_from(record).map_err(WorkerScheduleServiceError::InternalError)
+            })
+    }
+
+    async fn get_all(
+        &self,
+        namespace: &Namespace,
+    ) -> Result<Vec<WorkerSchedule>, WorkerScheduleServiceError> {
+        self.repo
+            .get_all(&namespace.to_string())
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?
+            .into_iter()
+            .map(|record| {
+                WorkerSchedule::try_from(record).map_err(WorkerScheduleServiceError::InternalError)
+            })
+            .collect()
+    }
+
+    async fn delete(
+        &self,
+        namespace: &Namespace,
+        schedule_id: &str,
+    ) -> Result<(), WorkerScheduleServiceError> {
+        let deleted = self
+            .repo
+            .delete(&namespace.to_string(), schedule_id)
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?;
+
+        if deleted {
+            Ok(())
+        } else {
+            Err(WorkerScheduleServiceError::NotFound(
+                schedule_id.to_string(),
+            ))
+        }
+    }
+
+    async fn trigger_due(&self) -> Result<usize, WorkerScheduleServiceError> {
+        let now = Utc::now();
+
+        let due = self
+            .repo
+            .get_due(&now, self.config.max_due_per_poll)
+            .await
+            .map_err(|err| WorkerScheduleServiceError::InternalError(err.to_safe_string()))?;
+
+        let mut triggered = 0;
+        for record in due {
+            let schedule_id = record.schedule_id.clone();
+            match self.trigger(record, now).await {
+                Ok(true) => triggered += 1,
+                Ok(false) => {}
+                Err(err) => error!(
+                    schedule_id = schedule_id,
+                    "Failed to trigger a scheduled run: {}",
+                    err.to_safe_string()
+                ),
+            }
+        }
+
+        Ok(triggered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use test_r::test;
+
+    #[test]
+    fn misfired_runs_are_skipped_or_fired_once() {
+        let cron = CronSchedule::parse("30 9 * * MON-FRI", "Europe/Budapest").unwrap();
+
+        // Friday 2025-01-03 09:30 in Budapest is 08:30 UTC, and the next run is on Monday
+        let due_at = Utc.with_ymd_and_hms(2025, 1, 3, 8, 30, 0).unwrap();
+        let monday = Utc.with_ymd_and_hms(2025, 1, 6, 8, 30, 0).unwrap();
+        assert_eq!(
+            cron.next_after(&Utc.with_ymd_and_hms(2025, 1, 3, 8, 0, 0).unwrap()),
+            Some(due_at)
+        );
+
+        let threshold = Duration::from_secs(60);
+
+        let on_time = ScheduledRun::plan(
+            &cron,
+            MisfirePolicy::Skip,
+            due_at,
+            due_at + chrono::Duration::seconds(5),
+            threshold,
+        );
+        assert_eq!(
+            on_time,
+            ScheduledRun {
+                fire_at: Some(due_at),
+                next_run_at: Some(monday),
+                misfired: false,
+            }
+        );
+
+        // After being down over the weekend, the runs missed until Tuesday are collapsed
+        let tuesday_noon = Utc.with_ymd_and_hms(2025, 1, 7, 12, 0, 0).unwrap();
+        let wednesday = Utc.with_ymd_and_hms(2025, 1, 8, 8, 30, 0).unwrap();
+
+        let skipped =
+            ScheduledRun::plan(&cron, MisfirePolicy::Skip, due_at, tuesday_noon, threshold);
+        assert_eq!(skipped.fire_at, None);
+        assert_eq!(skipped.next_run_at, Some(wednesday));
+        assert!(skipped.misfired);
+
+        let fired_once = ScheduledRun::plan(
+            &cron,
+            MisfirePolicy::FireOnce,
+            due_at,
+            tuesday_noon,
+            threshold,
+        );
+        assert_eq!(fired_once.fire_at, Some(due_at));
+        assert_eq!(fired_once.next_run_at, Some(wednesday));
+
+        assert!(CronSchedule::parse("*/10 * * * * *", "UTC").is_ok());
+        assert!(CronSchedule::parse("not a cron", "UTC").is_err());
+        assert!(CronSchedule::parse("* * * * *", "Mars/Olympus_Mons").is_err());
+        assert_eq!(
+            "fire-once".parse::<MisfirePolicy>(),
+            Ok(MisfirePolicy::FireOnce)
+        );
+    }
+}
@@ -25,20 +25,35 @@ use crate::internal::get_preflight_from_response;
 use crate::security::TestIdentityProvider;
 use chrono::{DateTime, Utc};
 use golem_common::model::IdempotencyKey;
+use golem_worker_service_base::gateway_api_deployment::{
+    ApiDeploymentClientCertificates, ApiDeploymentConfig, ClientCertificateVerifier,
+    CLIENT_CERTIFICATE_HEADER,
+};
 use golem_worker_service_base::gateway_execution::auth_call_back_binding_handler::DefaultAuthCallBack;
+use golem_worker_service_base::gateway_execution::deployment_capacity_limiter::DeploymentCapacityLimiter;
+use golem_worker_service_base::gateway_execution::experiment_assignment::DefaultExperimentAssignmentStore;
 use golem_worker_service_base::gateway_execution::gateway_http_input_executor::{
     DefaultGatewayInputExecutor, GatewayHttpInputExecutor,
 };
 use golem_worker_service_base::gateway_execution::gateway_session::{
     GatewaySession, GatewaySessionStore,
 };
+use golem_worker_service_base::gateway_execution::grpc_transcoding::DefaultGrpcBindingHandler;
+use golem_worker_service_base::gateway_execution::http_proxy_binding_handler::DefaultHttpProxyBindingHandler;
+use golem_worker_service_base::gateway_execution::rate_limiter::{
+    InMemoryRateLimiter, RateLimiterStore,
+};
+use golem_worker_service_base::gateway_execution::response_cache::InMemoryResponseCache;
+use golem_worker_service_base::gateway_execution::route_concurrency_limiter::RouteConcurrencyLimiter;
+use golem_worker_service_base::gateway_execution::route_metrics::RouteMetrics;
+use golem_worker_service_base::gateway_execution::shadow_traffic_diff::DefaultShadowTrafficDiffStore;
 use golem_worker_service_base::gateway_middleware::HttpCors;
 use golem_worker_service_base::gateway_request::http_request::ApiInputPath;
 use golem_worker_service_base::gateway_security::{
-    Provider, SecurityScheme, SecuritySchemeIdentifier,
+    DefaultJwtValidator, Provider, SecurityScheme, SecuritySchemeIdentifier,
 };
 use golem_worker_service_base::{api, gateway_api_definition};
-use http::header::LOCATION;
+use http::header::{ACCESS_CONTROL_ALLOW_ORIGIN, LOCATION, ORIGIN, RETRY_AFTER, VARY};
 use http::{HeaderMap, HeaderValue, Method, StatusCode, Uri};
 use openidconnect::{ClientId, ClientSecret, RedirectUrl, Scope};
 use poem::{Request, Response};
@@ -59,6 +74,57 @@ async fn execute(
     session_store: &GatewaySessionStore,
     test_identity_provider: &TestIdentityProvider,
 ) -> Response {
+    execute_with_config(
+        api_request,
+        api_specification,
+        ApiDeploymentConfig::default(),
+        session_store,
+        test_identity_provider,
+    )
+    .await
+}
+
+// Executes the request against a deployment with the given configuration
+async fn execute_with_config(
+    api_request: Request,
+    api_specification: &HttpApiDefinition,
+    config: ApiDeploymentConfig,
+    session_store: &GatewaySessionStore,
+    test_identity_provider: &TestIdentityProvider,
+) -> Response {
+    test_executor(
+        api_specification,
+        config,
+        session_store,
+        test_identity_provider,
+    )
+    .execute_http_request(api_request)
+    .await
+}
+
+// An executor keeping its state, such as the rate limits, between the requests
+fn test_executor(
+    api_specification: &HttpApiDefinition,
+    config: ApiDeploymentConfig,
+    session_store: &GatewaySessionStore,
+    test_identity_provider: &TestIdentityProvider,
+) -> DefaultGatewayInputExecutor<DefaultNamespace> {
+    test_executor_with_rate_limiter(
+        api_specification,
+        config,
+        session_store,
+        test_identity_provider,
+        Arc::new(InMemoryRateLimiter::default()),
+    )
+}
+
+fn test_executor_with_rate_limiter(
+    api_specification: &HttpApiDefinition,
+    config: ApiDeploymentConfig,
+    session_store: &GatewaySessionStore,
+    test_identity_provider: &TestIdentityProvider,
+    rate_limiter: RateLimiterStore,
+) -> DefaultGatewayInputExecutor<DefaultNamespace> {
     // Compile the API definition
     let compiled = CompiledHttpApiDefinition::from_http_api_definition(
         api_specification,
@@ -67,17 +133,30 @@ async fn execute(
     )
     .expect("Failed to compile API definition");
 
-    let test_executor = DefaultGatewayInputExecutor::new(
+    DefaultGatewayInputExecutor::new(
         internal::get_test_rib_interpreter(),
         internal::get_test_file_server_binding_handler(),
         Arc::new(DefaultAuthCallBack),
         internal::get_test_http_handler_binding_handler(),
-        Arc::new(internal::TestApiDefinitionLookup::new(compiled)),
+        Arc::new(DefaultGrpcBindingHandler::default()),
+        Arc::new(DefaultHttpProxyBindingHandler::default()),
+        Arc::new(DefaultShadowTrafficDiffStore::default()),
+        Arc::new(DefaultExperimentAssignmentStore::default()),
+        Arc::new(internal::TestDeadLetterService),
+        Arc::new(internal::TestRequestFixtureService),
+        internal::get_test_worker_event_subscriber(),
+        rate_limiter,
+        Arc::new(InMemoryResponseCache::default()),
+        Arc::new(RouteConcurrencyLimiter::default()),
+        Arc::new(DeploymentCapacityLimiter::default()),
+        Arc::new(RouteMetrics::default()),
+        Arc::new(internal::TestApiDefinitionLookup::new(compiled, config)),
         Arc::clone(session_store),
         Arc::new(test_identity_provider.clone()),
-    );
-
-    test_executor.execute_http_request(api_request).await
+        Arc::new(DefaultJwtValidator::default()),
+        Arc::new(internal::TestApiKeyValidator),
+        Arc::new(internal::TestHmacSignatureValidator),
+    )
 }
 
 #[test]
@@ -926,6 +1005,394 @@ async fn test_api_def_with_cors_preflight_for_preflight_input_and_simple_input()
     );
 }
 
+#[test]
+async fn test_deployment_cors_preflight_for_route_without_preflight() {
+    let worker_name = r#"
+      let id: u64 = request.path.user-id;
+      "shopping-cart-${id}"
+    "#;
+
+    let response_mapping = r#"
+      let response = golem:it/api.{get-cart-contents}("a", "b");
+      response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_worker_binding("/foo/{user-id}", Some(worker_name), response_mapping)
+            .await;
+
+    let cors = HttpCors::from_parameters(
+        Some("http://example.com, http://example.org".to_string()),
+        Some("GET, OPTIONS".to_string()),
+        Some("Content-Type".to_string()),
+        None,
+        None,
+        Some(600),
+    )
+    .unwrap();
+
+    let config = ApiDeploymentConfig {
+        cors: Some(cors),
+        ..ApiDeploymentConfig::default()
+    };
+
+    let session_store = internal::get_session_store();
+
+    let mut allowed_origin = HeaderMap::new();
+    allowed_origin.insert(ORIGIN, HeaderValue::from_static("http://example.org"));
+
+    let response = execute_with_config(
+        get_preflight_gateway_request("/foo/1", None, &allowed_origin, Value::Null),
+        &api_specification,
+        config.clone(),
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN),
+        Some(&HeaderValue::from_static("http://example.org"))
+    );
+    assert_eq!(
+        response.headers().get(VARY),
+        Some(&HeaderValue::from_static("Origin"))
+    );
+
+    let mut other_origin = HeaderMap::new();
+    other_origin.insert(ORIGIN, HeaderValue::from_static("http://example.net"));
+
+    let response = execute_with_config(
+        get_preflight_gateway_request("/foo/1", None, &other_origin, Value::Null),
+        &api_specification,
+        config,
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    assert_eq!(response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN), None);
+    assert_eq!(
+        response.headers().get(VARY),
+        Some(&HeaderValue::from_static("Origin"))
+    );
+}
+
+#[test]
+async fn test_route_cors_overrides_deployment_cors() {
+    let worker_name = r#"
+      let id: u64 = request.path.user-id;
+      "shopping-cart-${id}"
+    "#;
+
+    let response_mapping = r#"
+      let response = golem:it/api.{get-cart-contents}("a", "b");
+      response
+    "#;
+
+    let route_cors = HttpCors::from_parameters(
+        Some("http://example.com".to_string()),
+        Some("GET, OPTIONS".to_string()),
+        Some("Content-Type".to_string()),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_cors_preflight_for_get_endpoint_resource(
+            "/foo/{user-id}",
+            worker_name,
+            response_mapping,
+            &route_cors,
+        )
+        .await;
+
+    let deployment_cors = HttpCors::from_parameters(
+        Some("http://example.org, http://example.net".to_string()),
+        Some("GET, POST, OPTIONS".to_string()),
+        Some("Content-Type".to_string()),
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+
+    let config = ApiDeploymentConfig {
+        cors: Some(deployment_cors),
+        ..ApiDeploymentConfig::default()
+    };
+
+    let session_store = internal::get_session_store();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(ORIGIN, HeaderValue::from_static("http://example.org"));
+
+    let preflight_response = execute_with_config(
+        get_preflight_gateway_request("/foo/1", None, &headers, Value::Null),
+        &api_specification,
+        config.clone(),
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    assert_eq!(get_preflight_from_response(preflight_response), route_cors);
+
+    let response = execute_with_config(
+        get_gateway_request("/foo/1", None, &headers, Value::Null),
+        &api_specification,
+        config,
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    assert_eq!(
+        response.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN),
+        Some(&HeaderValue::from_static("http://example.com"))
+    );
+    assert_eq!(response.headers().get(VARY), None);
+}
+
+#[test]
+async fn test_api_def_with_rate_limit_rejects_requests_over_the_limit() {
+    let worker_name = r#"
+      let id: u64 = request.path.user-id;
+      "shopping-cart-${id}"
+    "#;
+
+    let response_mapping = r#"
+      let response = golem:it/api.{get-cart-contents}("a", "b");
+      response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_rate_limit("/foo/{user-id}", worker_name, response_mapping).await;
+
+    let session_store = internal::get_session_store();
+
+    let executor = test_executor(
+        &api_specification,
+        ApiDeploymentConfig::default(),
+        &session_store,
+        &TestIdentityProvider::default(),
+    );
+
+    let first_response = executor
+        .execute_http_request(get_gateway_request(
+            "/foo/1",
+            None,
+            &HeaderMap::new(),
+            Value::Null,
+        ))
+        .await;
+
+    let second_response = executor
+        .execute_http_request(get_gateway_request(
+            "/foo/1",
+            None,
+            &HeaderMap::new(),
+            Value::Null,
+        ))
+        .await;
+
+    assert_eq!(first_response.status(), StatusCode::OK);
+    assert_eq!(second_response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        second_response.headers().get(RETRY_AFTER),
+        Some(&HeaderValue::from_static("60"))
+    );
+}
+
+#[test]
+async fn test_api_def_with_rate_limit_rejects_requests_when_the_buckets_are_unavailable() {
+    let worker_name = r#"
+      let id: u64 = request.path.user-id;
+      "shopping-cart-${id}"
+    "#;
+
+    let response_mapping = r#"
+      let response = golem:it/api.{get-cart-contents}("a", "b");
+      response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_rate_limit("/foo/{user-id}", worker_name, response_mapping).await;
+
+    let session_store = internal::get_session_store();
+
+    // A limiter failing closed, such as the Redis one when it cannot reach Redis
+    let executor = test_executor_with_rate_limiter(
+        &api_specification,
+        ApiDeploymentConfig::default(),
+        &session_store,
+        &TestIdentityProvider::default(),
+        Arc::new(internal::UnavailableRateLimiter),
+    );
+
+    let response = executor
+        .execute_http_request(get_gateway_request(
+            "/foo/1",
+            None,
+            &HeaderMap::new(),
+            Value::Null,
+        ))
+        .await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers().get(RETRY_AFTER), None);
+}
+
+#[test]
+async fn test_deployment_client_certificates_are_verified_through_intermediate_cas() {
+    let worker_name = r#"
+      let id: u64 = request.path.user-id;
+      "shopping-cart-${id}"
+    "#;
+
+    let response_mapping = r#"
+      let response = golem:it/api.{get-cart-contents}("a", "b");
+      response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_worker_binding("/foo/{user-id}", Some(worker_name), response_mapping)
+            .await;
+
+    let config = ApiDeploymentConfig {
+        client_certificates: Some(
+            ClientCertificateVerifier::try_from(&ApiDeploymentClientCertificates {
+                required: true,
+                ca_bundle: ROOT_CA.to_string(),
+            })
+            .unwrap(),
+        ),
+        ..ApiDeploymentConfig::default()
+    };
+
+    let session_store = internal::get_session_store();
+
+    let executor = test_executor(
+        &api_specification,
+        config,
+        &session_store,
+        &TestIdentityProvider::default(),
+    );
+
+    let with_client_certificate = |pems: &[&str]| {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CLIENT_CERTIFICATE_HEADER,
+            HeaderValue::from_str(
+                &percent_encoding::utf8_percent_encode(
+                    &pems.concat(),
+                    percent_encoding::NON_ALPHANUMERIC,
+                )
+                .to_string(),
+            )
+            .unwrap(),
+        );
+        get_gateway_request("/foo/1", None, &headers, Value::Null)
+    };
+
+    let response = executor
+        .execute_http_request(with_client_certificate(&[SERVICE, INTERMEDIATE_CA]))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = executor
+        .execute_http_request(with_client_certificate(&[SERVICE]))
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = executor
+        .execute_http_request(with_client_certificate(&[FORGED, NOT_A_CA]))
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = executor
+        .execute_http_request(get_gateway_request(
+            "/foo/1",
+            None,
+            &HeaderMap::new(),
+            Value::Null,
+        ))
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+const ROOT_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBojCCAUegAwIBAgIUPPM6Hb2w3km8H0lZyj9OAmGX1T0wCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSR29sZW0gVGVzdCBSb290IENBMCAXDTI2MTAxNjE2MzY0N1oY
+DzIxMjYwOTIyMTYzNjQ3WjAdMRswGQYDVQQDDBJHb2xlbSBUZXN0IFJvb3QgQ0Ew
+WTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAARW6hhM8ltns8ma+WbP5JH2EKeWkWbx
+NMDG7UGy9odlUHx56XQeLf6FjeV3l9zXjRqGPTESXsuVd/Ntf0+LlFUCo2MwYTAd
+BgNVHQ4EFgQUIb53Vekrf+ey+limMFZn0BEjVsgwHwYDVR0jBBgwFoAUIb53Vekr
+f+ey+limMFZn0BEjVsgwDwYDVR0TAQH/BAUwAwEB/zAOBgNVHQ8BAf8EBAMCAQYw
+CgYIKoZIzj0EAwIDSQAwRgIhAOf7jPlm/dpaXx76AtP/0yGibB6/1t2UQHAhmORp
+U5IRAiEAioEA8OT/5YVj9/2tGQPuQqy5X8Yd+9p54bgcg/nWYmU=
+-----END CERTIFICATE-----
+";
+
+// Issued by ROOT_CA
+const INTERMEDIATE_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBqDCCAU+gAwIBAgIUDIYGaGa5gcq4KWmo84Fo+t//ItUwCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSR29sZW0gVGVzdCBSb290IENBMCAXDTI2MTAxNjE2MzY0N1oY
+DzIxMjYwOTIyMTYzNjQ3WjAlMSMwIQYDVQQDDBpHb2xlbSBUZXN0IEludGVybWVk
+aWF0ZSBDQTBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABODYks7alw0p0OvYsUUj
+p8aOyW7RuuFIN/Vk1p2KdwKmY8mQ97rirNh5HV+PPx/HdtC/tiKhB9KMgVj3oJUU
+VMOjYzBhMA8GA1UdEwEB/wQFMAMBAf8wDgYDVR0PAQH/BAQDAgEGMB0GA1UdDgQW
+BBTwTUwKqT9Xp9wWbeinHlJhe5GbKTAfBgNVHSMEGDAWgBQhvndV6St/57L6WKYw
+VmfQESNWyDAKBggqhkjOPQQDAgNHADBEAiAqH3vr+M20OeKiABL0i0ccabYYVnHA
+8ybttXoP9rA1wgIgM4kkyjpj/SvHJgyzot7yskekrHmjGDJrEMv96iPNKEk=
+-----END CERTIFICATE-----
+";
+
+// Issued by INTERMEDIATE_CA
+const SERVICE: &str = "-----BEGIN CERTIFICATE-----
+MIIBqTCCAU+gAwIBAgIUXaEw9RE1NWNZgxFyz77CqvS/zrIwCgYIKoZIzj0EAwIw
+JTEjMCEGA1UEAwwaR29sZW0gVGVzdCBJbnRlcm1lZGlhdGUgQ0EwIBcNMjYxMDE2
+MTYzNjQ3WhgPMjEyNjA5MjIxNjM2NDdaMDAxHDAaBgNVBAMME3NlcnZpY2UuZXhh
+bXBsZS5jb20xEDAOBgNVBAoMB1BhcnRuZXIwWTATBgcqhkjOPQIBBggqhkjOPQMB
+BwNCAARSAZqbktVYSY9xBnhN1OAcN4jfJ2vcFJD8b2oCecNofml44K4ky2R2dyBK
+nXzi+iv1711K/XpQhAmBDxoexonto1AwTjAMBgNVHRMBAf8EAjAAMB0GA1UdDgQW
+BBTIyw7qj+zrHTUa1JcvgpA9brsomzAfBgNVHSMEGDAWgBTwTUwKqT9Xp9wWbein
+HlJhe5GbKTAKBggqhkjOPQQDAgNIADBFAiA//Kemc/eE3HnBi+QucZrcmRXEZS7l
+ZB6ZARntCrLXmgIhALaqLpH6+BHAnI47zahKID9NEDD96ay2b5LAWryvzQe1
+-----END CERTIFICATE-----
+";
+
+// Issued by ROOT_CA, but not marked as a certificate authority
+const NOT_A_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBhDCCASqgAwIBAgIUDIYGaGa5gcq4KWmo84Fo+t//ItYwCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSR29sZW0gVGVzdCBSb290IENBMCAXDTI2MTAxNjE2MzY0N1oY
+DzIxMjYwOTIyMTYzNjQ3WjATMREwDwYDVQQDDAhOb3QgQSBDQTBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABPQqcLNunwLzfBZZ2iwQ3Juo3Q3PHhMsVhszT141tip6
+FXPHEeRqvZJmJxRnBq4liB2DtDOgTJGJSXPXq4CHYJKjUDBOMAwGA1UdEwEB/wQC
+MAAwHQYDVR0OBBYEFBsdqIT+g9H6pQ7t7AtL1Qq+V2DHMB8GA1UdIwQYMBaAFCG+
+d1XpK3/nsvpYpjBWZ9ARI1bIMAoGCCqGSM49BAMCA0gAMEUCIEoFc/z8Su0UtMUz
+ZGmPAX1YhDXHVkPgEVm9uKqHObZSAiEA2kb877Ka96ah0Fcv96L1yI5kZIXXX5Wr
+IoEHCrFA9GI=
+-----END CERTIFICATE-----
+";
+
+// Issued by NOT_A_CA
+const FORGED: &str = "-----BEGIN CERTIFICATE-----
+MIIBlTCCATygAwIBAgIUXIRYYYhIu+BmtogNTbfdqUnwc+IwCgYIKoZIzj0EAwIw
+EzERMA8GA1UEAwwITm90IEEgQ0EwIBcNMjYxMDE2MTYzNjQ3WhgPMjEyNjA5MjIx
+NjM2NDdaMC8xGzAZBgNVBAMMEmZvcmdlZC5leGFtcGxlLmNvbTEQMA4GA1UECgwH
+UGFydG5lcjBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABBmtyiRMChMDIJfPYZIc
+4duSjgUlhxDiO1dto0yuARo3lqNdG6o+U/SSCMaBJF0AYe71dDqHEgMptz8BimU7
+IeejUDBOMAwGA1UdEwEB/wQCMAAwHQYDVR0OBBYEFNI+JaFbjfUKM6myIGBLKRwi
+LPYGMB8GA1UdIwQYMBaAFBsdqIT+g9H6pQ7t7AtL1Qq+V2DHMAoGCCqGSM49BAMC
+A0cAMEQCIB4FuIX8DLIptpgljj+EwqL2jb6IqaAn+SUF3/CuBh+sAiB8MSmmTa4B
+esgo1G9Wl51w3nALwy7T1qxrIFEhj0usaw==
+-----END CERTIFICATE-----
+";
+
 #[test]
 async fn test_api_def_with_path_and_query_params_lookup_for_valid_input() {
     let empty_headers = HeaderMap::new();
@@ -1243,18 +1710,183 @@ async fn test_api_def_with_request_body_params_lookup_for_valid_input3() {
 }
 
 #[test]
-async fn test_api_def_for_valid_input_with_idempotency_key_in_header() {
-    async fn test_key(header_map: &HeaderMap, idempotency_key: Option<IdempotencyKey>) {
-        let api_request = get_gateway_request("/getcartcontent/1", None, header_map, Value::Null);
+async fn test_api_def_with_request_cookies_lookup_for_valid_input() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        http::header::COOKIE,
+        HeaderValue::from_static("session=abc=1; cart=cart-1"),
+    );
 
-        let expression = r#"
-            let response = golem:it/api.{get-cart-contents}("foo", "bar");
-            response
-            "#;
+    let api_request = get_gateway_request("/foo/2", None, &headers, Value::Null);
 
-        let api_specification: HttpApiDefinition = get_api_def_with_worker_binding(
-            "/getcartcontent/{cart-id}",
-            Some("${let x: u64 = request.path.cart-id; \"shopping-cart-${x}\"}"),
+    let worker_name = r#"
+        let cart: string = request.cookies.cart;
+        "shopping-${cart}"
+    "#;
+
+    let response_mapping = r#"
+        let response = golem:it/api.{get-cart-contents}(request.cookies.session, request.cookies.cart);
+        response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_worker_binding("/foo/{user-id}", Some(worker_name), response_mapping)
+            .await;
+
+    let session_store = internal::get_session_store();
+
+    let response = execute(
+        api_request,
+        &api_specification,
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    let test_response = internal::get_details_from_response(response).await;
+
+    let result = (
+        test_response.worker_name,
+        test_response.function_name,
+        test_response.function_params,
+    );
+
+    let expected = (
+        "shopping-cart-1".to_string(),
+        "golem:it/api.{get-cart-contents}".to_string(),
+        Value::Array(vec![
+            Value::String("abc=1".to_string()),
+            Value::String("cart-1".to_string()),
+        ]),
+    );
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+async fn test_api_def_with_multipart_request_body_lookup_for_valid_input() {
+    let boundary = "golem-boundary";
+
+    let body = format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"description\"\r\n\r\n\
+         holiday photo\r\n\
+         --{boundary}\r\n\
+         Content-Disposition: form-data; name=\"upload\"; filename=\"photo.png\"\r\n\
+         Content-Type: image/png\r\n\r\n\
+         \x01\x02\x03\r\n\
+         --{boundary}--\r\n"
+    );
+
+    let mut api_request = get_gateway_request("/foo/2", None, &HeaderMap::new(), Value::Null);
+    api_request.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_str(&format!("multipart/form-data; boundary={boundary}")).unwrap(),
+    );
+    api_request.set_body(body);
+
+    let worker_name = r#"
+        let userid: u64 = request.path.user-id;
+        "shopping-cart-${userid}"
+    "#;
+
+    let response_mapping = r#"
+        let data: list<u8> = request.body.upload.data;
+        let response = golem:it/api.{get-cart-contents}(request.body.description, "${request.body.upload.file_name}:${request.body.upload.content_type}");
+        response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_worker_binding("/foo/{user-id}", Some(worker_name), response_mapping)
+            .await;
+
+    let session_store = internal::get_session_store();
+
+    let response = execute(
+        api_request,
+        &api_specification,
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    let test_response = internal::get_details_from_response(response).await;
+
+    let result = (
+        test_response.worker_name,
+        test_response.function_name,
+        test_response.function_params,
+    );
+
+    let expected = (
+        "shopping-cart-2".to_string(),
+        "golem:it/api.{get-cart-contents}".to_string(),
+        Value::Array(vec![
+            Value::String("holiday photo".to_string()),
+            Value::String("photo.png:image/png".to_string()),
+        ]),
+    );
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+async fn test_api_def_with_binary_request_and_response_body() {
+    let mut api_request = get_gateway_request("/foo/2", None, &HeaderMap::new(), Value::Null);
+    api_request.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("image/png"),
+    );
+    api_request.set_body(vec![0u8, 1, 2, 255]);
+
+    let worker_name = r#"
+        let userid: u64 = request.path.user-id;
+        "shopping-cart-${userid}"
+    "#;
+
+    let response_mapping = r#"
+        let data: list<u8> = request.body;
+        { body: data }
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_worker_binding("/foo/{user-id}", Some(worker_name), response_mapping)
+            .await;
+
+    let session_store = internal::get_session_store();
+
+    let response = execute(
+        api_request,
+        &api_specification,
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.header(http::header::CONTENT_TYPE),
+        Some("application/octet-stream")
+    );
+    assert_eq!(
+        response.into_body().into_vec().await.unwrap(),
+        vec![0u8, 1, 2, 255]
+    );
+}
+
+#[test]
+async fn test_api_def_for_valid_input_with_idempotency_key_in_header() {
+    async fn test_key(header_map: &HeaderMap, idempotency_key: Option<IdempotencyKey>) {
+        let api_request = get_gateway_request("/getcartcontent/1", None, header_map, Value::Null);
+
+        let expression = r#"
+            let response = golem:it/api.{get-cart-contents}("foo", "bar");
+            response
+            "#;
+
+        let api_specification: HttpApiDefinition = get_api_def_with_worker_binding(
+            "/getcartcontent/{cart-id}",
+            Some("${let x: u64 = request.path.cart-id; \"shopping-cart-${x}\"}"),
             expression,
         )
         .await;
@@ -1283,6 +1915,44 @@ async fn test_api_def_for_valid_input_with_idempotency_key_in_header() {
     test_key(&headers, Some(IdempotencyKey::new("bar".to_string()))).await;
 }
 
+#[test]
+async fn test_first_class_worker_api_def_with_worker_bootstrap() {
+    let api_request =
+        get_gateway_request("/foo/1", None, &HeaderMap::new(), serde_json::Value::Null);
+
+    let response_mapping = r#"
+       let id: u64 = request.path.user-id;
+       let worker-name = "shopping-cart-${id}";
+       let worker-instance = instance(worker-name);
+       let response = worker-instance.get-cart-contents("a", "b");
+      response
+    "#;
+
+    let api_specification: HttpApiDefinition =
+        get_api_def_with_worker_bootstrap("/foo/{user-id}", response_mapping).await;
+
+    let session_store: Arc<dyn GatewaySession + Sync + Send> = internal::get_session_store();
+
+    let response = execute(
+        api_request,
+        &api_specification,
+        &session_store,
+        &TestIdentityProvider::default(),
+    )
+    .await;
+
+    let bytes = response.into_body().into_bytes().await.unwrap();
+    let body_json: Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        body_json.get("wasi_config_vars"),
+        Some(&serde_json::json!({
+            "region": "eu-west",
+            "user": "user-1"
+        }))
+    );
+}
+
 fn get_gateway_request(
     base_path: &str,
     query_path: Option<&str>,
@@ -1425,6 +2095,97 @@ async fn get_api_def_with_worker_binding(
     .unwrap()
 }
 
+async fn get_api_def_with_rate_limit(
+    path_pattern: &str,
+    worker_name: &str,
+    rib_expression: &str,
+) -> HttpApiDefinition {
+    let yaml_string = format!(
+        r#"
+          id: users-api
+          version: 0.0.1
+          createdAt: 2024-08-21T07:42:15.696Z
+          routes:
+          - method: Get
+            path: {}
+            binding:
+              type: wit-worker
+              componentId:
+                componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+                version: 0
+              workerName: '{}'
+              response: '${{{}}}'
+            rate_limit:
+              capacity: 1
+              refillIntervalMs: 60000
+
+        "#,
+        path_pattern, worker_name, rib_expression
+    );
+
+    let http_api_definition_request: api::HttpApiDefinitionRequest =
+        serde_yaml::from_str(yaml_string.as_str()).unwrap();
+
+    let core_request: gateway_api_definition::http::HttpApiDefinitionRequest =
+        http_api_definition_request.try_into().unwrap();
+
+    let create_at: DateTime<Utc> = "2024-08-21T07:42:15.696Z".parse().unwrap();
+
+    HttpApiDefinition::from_http_api_definition_request(
+        &DefaultNamespace(),
+        core_request,
+        create_at,
+        &security::get_test_security_scheme_service(TestIdentityProvider::default()),
+    )
+    .await
+    .unwrap()
+}
+
+async fn get_api_def_with_worker_bootstrap(
+    path_pattern: &str,
+    rib_expression: &str,
+) -> HttpApiDefinition {
+    let yaml_string = format!(
+        r#"
+          id: users-api
+          version: 0.0.1
+          createdAt: 2024-08-21T07:42:15.696Z
+          routes:
+          - method: Get
+            path: {}
+            binding:
+              type: wit-worker
+              componentId:
+                componentId: 0b6d9cd8-f373-4e29-8a5a-548e61b868a5
+                version: 0
+              response: '${{{}}}'
+              bootstrap:
+                config:
+                  region: '"eu-west"'
+                  user: 'let id: u64 = request.path.user-id; "user-${{id}}"'
+
+        "#,
+        path_pattern, rib_expression
+    );
+
+    let http_api_definition_request: api::HttpApiDefinitionRequest =
+        serde_yaml::from_str(yaml_string.as_str()).unwrap();
+
+    let core_request: gateway_api_definition::http::HttpApiDefinitionRequest =
+        http_api_definition_request.try_into().unwrap();
+
+    let create_at: DateTime<Utc> = "2024-08-21T07:42:15.696Z".parse().unwrap();
+
+    HttpApiDefinition::from_http_api_definition_request(
+        &DefaultNamespace(),
+        core_request,
+        create_at,
+        &security::get_test_security_scheme_service(TestIdentityProvider::default()),
+    )
+    .await
+    .unwrap()
+}
+
 // https://swagger.io/docs/specification/v3_0/authentication/openid-connect-discovery/
 async fn get_api_def_with_security(
     path_pattern: &str,
@@ -1697,7 +2458,8 @@ async fn get_api_def_with_with_default_cors_preflight_for_get_endpoint_resource(
 
 mod internal {
     use async_trait::async_trait;
-    use golem_common::model::{ComponentId, IdempotencyKey};
+    use futures::stream::BoxStream;
+    use golem_common::model::{ComponentId, IdempotencyKey, WorkerEvent};
     use golem_common::virtual_exports::http_incoming_handler::IncomingHttpRequest;
     use golem_service_base::auth::DefaultNamespace;
     use golem_service_base::model::VersionedComponentId;
@@ -1711,28 +2473,49 @@ mod internal {
     use golem_worker_service_base::gateway_api_definition::http::{
         CompiledHttpApiDefinition, ComponentMetadataDictionary,
     };
-    use golem_worker_service_base::gateway_api_deployment::ApiSiteString;
+    use golem_worker_service_base::gateway_api_deployment::{ApiDeploymentConfig, ApiSiteString};
     use golem_worker_service_base::gateway_execution::api_definition_lookup::{
         ApiDefinitionLookupError, HttpApiDefinitionsLookup,
     };
     use golem_worker_service_base::gateway_execution::file_server_binding_handler::{
         FileServerBindingHandler, FileServerBindingResult,
     };
+    use golem_worker_service_base::gateway_execution::gateway_http_input_executor::GatewayHttpInputExecutor;
     use golem_worker_service_base::gateway_execution::gateway_session::{
         DataKey, DataValue, GatewaySession, GatewaySessionError, GatewaySessionStore, SessionId,
     };
     use golem_worker_service_base::gateway_execution::http_handler_binding_handler::{
         HttpHandlerBindingHandler, HttpHandlerBindingResult,
     };
+    use golem_worker_service_base::gateway_execution::rate_limiter::{
+        RateLimitDecision, RateLimiter, RateLimiterError,
+    };
+    use golem_worker_service_base::gateway_execution::server_sent_events_bridge::WorkerEventSubscriber;
     use golem_worker_service_base::gateway_execution::WorkerDetail;
     use golem_worker_service_base::gateway_execution::{
         GatewayResolvedWorkerRequest, GatewayWorkerRequestExecutor, WorkerRequestExecutorError,
         WorkerResponse,
     };
-    use golem_worker_service_base::gateway_middleware::HttpCors;
+    use golem_worker_service_base::gateway_middleware::{HttpCors, HttpRateLimit};
     use golem_worker_service_base::gateway_rib_interpreter::{
         DefaultRibInterpreter, EvaluationError, WorkerServiceRibInterpreter,
     };
+    use golem_worker_service_base::gateway_security::{
+        ApiKey, ApiKeySecurityScheme, ApiKeyValidationError, ApiKeyValidator, HmacSecurityScheme,
+        HmacSignatureValidationError, HmacSignatureValidator,
+    };
+    use golem_worker_service_base::service::gateway::dead_letter::{
+        DeadLetter, DeadLetterReplayResult, DeadLetterRequest, DeadLetterService,
+        DeadLetterServiceError,
+    };
+    use golem_worker_service_base::service::gateway::emitted_event::{
+        EmittedEvent, EmittedEventService, EmittedEventServiceError, EventEmission,
+    };
+    use golem_worker_service_base::service::gateway::request_fixture::{
+        RequestFixture, RequestFixtureReplayReport, RequestFixtureService,
+        RequestFixtureServiceError,
+    };
+    use golem_worker_service_base::service::worker::WorkerServiceError;
     use http::header::{
         ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
         ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
@@ -1744,13 +2527,208 @@ mod internal {
     use std::collections::HashMap;
     use std::sync::{Arc, Mutex};
 
+    // None of the tests secure their routes with API keys
+    pub struct TestApiKeyValidator;
+
+    #[async_trait]
+    impl ApiKeyValidator<DefaultNamespace> for TestApiKeyValidator {
+        async fn validate(
+            &self,
+            _namespace: &DefaultNamespace,
+            _security_scheme: &ApiKeySecurityScheme,
+            _presented_key: &str,
+        ) -> Result<ApiKey, ApiKeyValidationError> {
+            Err(ApiKeyValidationError::InvalidKey)
+        }
+    }
+
+    // None of the tests secure their routes with HMAC signatures
+    pub struct TestHmacSignatureValidator;
+
+    #[async_trait]
+    impl HmacSignatureValidator<DefaultNamespace> for TestHmacSignatureValidator {
+        async fn validate(
+            &self,
+            _namespace: &DefaultNamespace,
+            _security_scheme: &HmacSecurityScheme,
+            _body: &[u8],
+            _presented_signature: &str,
+        ) -> Result<(), HmacSignatureValidationError> {
+            Err(HmacSignatureValidationError::InvalidSignature)
+        }
+    }
+
+    // A rate limiter which cannot reach its buckets
+    pub struct UnavailableRateLimiter;
+
+    #[async_trait]
+    impl RateLimiter for UnavailableRateLimiter {
+        async fn try_acquire(
+            &self,
+            _key: &str,
+            _limit: &HttpRateLimit,
+        ) -> Result<RateLimitDecision, RateLimiterError> {
+            Err(RateLimiterError::InternalError(
+                "Rate limit buckets unavailable".to_string(),
+            ))
+        }
+    }
+
+    // None of the tests have a dead-letter queue
+    pub struct TestDeadLetterService;
+
+    #[async_trait]
+    impl DeadLetterService<DefaultNamespace> for TestDeadLetterService {
+        async fn record(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+            _route: &str,
+            _request: DeadLetterRequest,
+            _error: String,
+        ) -> Result<DeadLetter, DeadLetterServiceError> {
+            Err(DeadLetterServiceError::InternalError(
+                "No dead-letter queue".to_string(),
+            ))
+        }
+
+        async fn get_all(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+        ) -> Result<Vec<DeadLetter>, DeadLetterServiceError> {
+            Ok(vec![])
+        }
+
+        async fn get(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+            dead_letter_id: &str,
+        ) -> Result<DeadLetter, DeadLetterServiceError> {
+            Err(DeadLetterServiceError::NotFound(dead_letter_id.to_string()))
+        }
+
+        async fn delete(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+            dead_letter_id: &str,
+        ) -> Result<(), DeadLetterServiceError> {
+            Err(DeadLetterServiceError::NotFound(dead_letter_id.to_string()))
+        }
+
+        async fn replay(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+            dead_letter_id: &str,
+            _executor: &(dyn GatewayHttpInputExecutor + Sync + Send),
+        ) -> Result<DeadLetterReplayResult, DeadLetterServiceError> {
+            Err(DeadLetterServiceError::NotFound(dead_letter_id.to_string()))
+        }
+    }
+
+    // None of the tests record request fixtures
+    pub struct TestRequestFixtureService;
+
+    #[async_trait]
+    impl RequestFixtureService<DefaultNamespace> for TestRequestFixtureService {
+        async fn record(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+            _route: &str,
+            _request: DeadLetterRequest,
+            _status: u16,
+            _response_schema: Option<serde_json::Value>,
+            _max_fixtures: u32,
+        ) -> Result<Option<RequestFixture>, RequestFixtureServiceError> {
+            Ok(None)
+        }
+
+        async fn get_all(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+        ) -> Result<Vec<RequestFixture>, RequestFixtureServiceError> {
+            Ok(vec![])
+        }
+
+        async fn delete(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+            fixture_id: &str,
+        ) -> Result<(), RequestFixtureServiceError> {
+            Err(RequestFixtureServiceError::NotFound(fixture_id.to_string()))
+        }
+
+        async fn delete_all(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+        ) -> Result<u64, RequestFixtureServiceError> {
+            Ok(0)
+        }
+
+        async fn replay(
+            &self,
+            _namespace: &DefaultNamespace,
+            _site: &ApiSiteString,
+            staging_site: &ApiSiteString,
+            _executor: &(dyn GatewayHttpInputExecutor + Sync + Send),
+        ) -> Result<RequestFixtureReplayReport, RequestFixtureServiceError> {
+            Ok(RequestFixtureReplayReport {
+                staging_site: staging_site.to_string(),
+                replayed: 0,
+                failed: 0,
+                passed: true,
+                results: vec![],
+            })
+        }
+    }
+
+    // None of the tests emit events
+    pub struct TestEmittedEventService;
+
+    #[async_trait]
+    impl EmittedEventService<DefaultNamespace> for TestEmittedEventService {
+        async fn publish(
+            &self,
+            _namespace: &DefaultNamespace,
+            _event: EventEmission,
+        ) -> Result<EmittedEvent, EmittedEventServiceError> {
+            Err(EmittedEventServiceError::InternalError(
+                "No emitted events".to_string(),
+            ))
+        }
+
+        async fn get_all(
+            &self,
+            _namespace: &DefaultNamespace,
+            _event_name: Option<String>,
+            _component_id: Option<ComponentId>,
+            _limit: u32,
+        ) -> Result<Vec<EmittedEvent>, EmittedEventServiceError> {
+            Ok(vec![])
+        }
+    }
+
     pub struct TestApiDefinitionLookup {
         pub api_definition: CompiledHttpApiDefinition<DefaultNamespace>,
+        pub config: Arc<ApiDeploymentConfig>,
     }
 
     impl TestApiDefinitionLookup {
-        pub fn new(api_definition: CompiledHttpApiDefinition<DefaultNamespace>) -> Self {
-            Self { api_definition }
+        pub fn new(
+            api_definition: CompiledHttpApiDefinition<DefaultNamespace>,
+            config: ApiDeploymentConfig,
+        ) -> Self {
+            Self {
+                api_definition,
+                config: Arc::new(config),
+            }
         }
     }
 
@@ -1763,6 +2741,13 @@ mod internal {
         {
             Ok(vec![self.api_definition.clone()])
         }
+
+        async fn get_config(
+            &self,
+            _input: &ApiSiteString,
+        ) -> Result<Arc<ApiDeploymentConfig>, ApiDefinitionLookupError> {
+            Ok(self.config.clone())
+        }
     }
 
     pub struct TestApiGatewayWorkerRequestExecutor {}
@@ -1779,6 +2764,19 @@ mod internal {
 
             Ok(WorkerResponse::new(worker_response))
         }
+
+        // Every test worker is labelled with its own name
+        async fn get_worker_labels(
+            &self,
+            _component_id: &ComponentId,
+            worker_name: &str,
+            _namespace: DefaultNamespace,
+        ) -> Result<HashMap<String, String>, WorkerRequestExecutorError> {
+            Ok(HashMap::from([(
+                "name".to_string(),
+                worker_name.to_string(),
+            )]))
+        }
     }
 
     struct TestFileServerBindingHandler {}
@@ -1794,6 +2792,17 @@ mod internal {
         }
     }
 
+    struct TestWorkerEventSubscriber {}
+    #[async_trait]
+    impl WorkerEventSubscriber for TestWorkerEventSubscriber {
+        async fn subscribe(
+            &self,
+            _worker_detail: &WorkerDetail,
+        ) -> Result<BoxStream<'static, Result<WorkerEvent, String>>, WorkerServiceError> {
+            unimplemented!()
+        }
+    }
+
     struct TestHttpHandlerBindingHandler {}
     #[async_trait]
     impl<Namespace> HttpHandlerBindingHandler<Namespace> for TestHttpHandlerBindingHandler {
@@ -1903,6 +2912,20 @@ mod internal {
             ))
         };
 
+        if let Some(bootstrap) = &worker_request.bootstrap {
+            let mut config = bootstrap
+                .config
+                .iter()
+                .map(|(name, value)| (name.clone(), TypeAnnotatedValue::Str(value.clone())))
+                .collect::<Vec<_>>();
+            config.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            record_elems.push((
+                "wasi_config_vars".to_string(),
+                create_record(config).unwrap(),
+            ))
+        };
+
         create_record(record_elems).unwrap()
     }
 
@@ -1953,6 +2976,7 @@ mod internal {
     ) -> Arc<dyn WorkerServiceRibInterpreter<DefaultNamespace> + Sync + Send> {
         Arc::new(DefaultRibInterpreter::from_worker_request_executor(
             Arc::new(TestApiGatewayWorkerRequestExecutor {}),
+            Arc::new(TestEmittedEventService),
         ))
     }
 
@@ -1961,6 +2985,10 @@ mod internal {
         Arc::new(TestFileServerBindingHandler {})
     }
 
+    pub fn get_test_worker_event_subscriber() -> Arc<dyn WorkerEventSubscriber + Sync + Send> {
+        Arc::new(TestWorkerEventSubscriber {})
+    }
+
     pub fn get_test_http_handler_binding_handler<Namespace>(
     ) -> Arc<dyn HttpHandlerBindingHandler<Namespace> + Sync + Send> {
         Arc::new(TestHttpHandlerBindingHandler {})
@@ -2335,6 +3363,7 @@ nUhg4edJVHjqxYyoQT+YSPLlHl6AkLZt9/n1NJ+bft0=
 
         async fn get(
             &self,
+            namespace: &str,
             security_scheme_id: &str,
         ) -> Result<Option<SecuritySchemeRecord>, RepoError> {
             Ok(self
@@ -2342,6 +3371,7 @@ nUhg4edJVHjqxYyoQT+YSPLlHl6AkLZt9/n1NJ+bft0=
                 .lock()
                 .await
                 .get(security_scheme_id)
+                .filter(|record| record.namespace == namespace)
                 .cloned())
         }
     }
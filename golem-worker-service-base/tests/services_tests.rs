@@ -26,9 +26,16 @@ use golem_wasm_ast::analysis::{
     AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
     AnalysedInstance,
 };
+use golem_worker_service_base::gateway_api_definition::http::CompiledHttpApiDefinition;
 use golem_worker_service_base::gateway_api_definition::http::HttpApiDefinition;
 use golem_worker_service_base::gateway_api_definition::http::HttpApiDefinitionRequest;
 use golem_worker_service_base::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use golem_worker_service_base::gateway_binding::{GatewayBinding, GatewayBindingCompiled};
+use golem_worker_service_base::gateway_execution::rate_limiter::{
+    RateLimitDecision, RateLimiter, RedisRateLimiter,
+};
+use golem_worker_service_base::gateway_middleware::{HttpCors, HttpRateLimit};
+use golem_worker_service_base::gateway_rib_compiler::TaintAnalysisPolicy;
 use golem_worker_service_base::repo::{api_definition, api_deployment};
 use golem_worker_service_base::service::component::{ComponentResult, ComponentService};
 use golem_worker_service_base::service::gateway::api_definition::{
@@ -36,7 +43,7 @@ use golem_worker_service_base::service::gateway::api_definition::{
     ApiDefinitionServiceDefault,
 };
 use golem_worker_service_base::service::gateway::api_deployment::{
-    ApiDeploymentError, ApiDeploymentService, ApiDeploymentServiceDefault,
+    ApiDeploymentError, ApiDeploymentService, ApiDeploymentServiceDefault, DeploymentHealthCheck,
 };
 use golem_worker_service_base::service::gateway::http_api_definition_validator::HttpApiDefinitionValidator;
 
@@ -47,17 +54,23 @@ use golem_service_base::storage::sqlite::SqlitePool;
 use golem_wasm_ast::analysis::analysed_type::str;
 use golem_worker_service_base::api;
 use golem_worker_service_base::gateway_api_deployment::{
-    ApiDeploymentRequest, ApiSite, ApiSiteString,
+    ApiDeploymentErrorPages, ApiDeploymentExperiment, ApiDeploymentRequest,
+    ApiDeploymentTrafficSplit, ApiSite, ApiSiteString, ErrorPage, ExperimentVariant,
 };
 use golem_worker_service_base::gateway_execution::gateway_session::{
     DataKey, DataValue, GatewaySession, GatewaySessionError, RedisGatewaySession,
     RedisGatewaySessionExpiration, SessionId, SqliteGatewaySession, SqliteGatewaySessionExpiration,
 };
 use golem_worker_service_base::gateway_security::{
-    AuthorizationUrl, DefaultIdentityProvider, GolemIdentityProviderMetadata, IdentityProvider,
-    IdentityProviderError, OpenIdClient, Provider, SecurityScheme, SecuritySchemeIdentifier,
+    ApiKeyLocation, ApiKeySecurityScheme, ApiKeyValidationError, ApiKeyValidator, AuthorizationUrl,
+    DefaultIdentityProvider, GolemIdentityProviderMetadata, HmacAlgorithm, HmacSecurityScheme,
+    HmacSignatureEncoding, HmacSignatureFormat, HmacSignatureValidationError,
+    HmacSignatureValidator, IdentityProvider, IdentityProviderError, OpenIdClient, Provider,
+    SecurityScheme, SecuritySchemeIdentifier,
 };
+use golem_worker_service_base::repo::api_key::DbApiKeyRepo;
 use golem_worker_service_base::repo::security_scheme::{DbSecuritySchemeRepo, SecuritySchemeRepo};
+use golem_worker_service_base::service::gateway::api_key::{ApiKeyService, DefaultApiKeyService};
 use golem_worker_service_base::service::gateway::security_scheme::{
     DefaultSecuritySchemeService, SecuritySchemeService,
 };
@@ -293,6 +306,39 @@ pub async fn test_gateway_session_redis() {
     ));
 }
 
+#[test]
+pub async fn test_rate_limiter_redis() {
+    let (redis_config, _container) = start_docker_redis().await;
+
+    let redis = RedisPool::configured(&redis_config).await.unwrap();
+    let rate_limiter = RedisRateLimiter::new(redis, false);
+
+    let strict = HttpRateLimit::new(1, 60_000, None).unwrap();
+    let loose = HttpRateLimit::new(100, 1, None).unwrap();
+
+    // The buckets of the routes are kept with the limits of their own routes
+    assert_eq!(
+        rate_limiter
+            .try_acquire("site:strict:", &strict)
+            .await
+            .unwrap(),
+        RateLimitDecision::Allowed
+    );
+    for _ in 0..10 {
+        assert_eq!(
+            rate_limiter
+                .try_acquire("site:loose:", &loose)
+                .await
+                .unwrap(),
+            RateLimitDecision::Allowed
+        );
+    }
+    assert!(matches!(
+        rate_limiter.try_acquire("site:strict:", &strict).await.unwrap(),
+        RateLimitDecision::Limited { retry_after } if retry_after > Duration::from_secs(50)
+    ));
+}
+
 async fn insert_and_get_with_redis(
     session_id: SessionId,
     data_key: DataKey,
@@ -372,6 +418,155 @@ pub async fn test_with_sqlite_db() {
     .await;
 }
 
+// Namespaces telling apart the projects of a multi-tenant deployment,
+// which DefaultNamespace cannot as it is a single one
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct TestNamespace(String);
+
+impl std::fmt::Display for TestNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[test]
+pub async fn test_api_key_namespaces_with_sqlite() {
+    let db = SqliteDb::default();
+    let db_config = DbSqliteConfig {
+        database: db.db_path.clone(),
+        max_connections: 10,
+    };
+
+    db::sqlite_migrate(
+        &db_config,
+        MigrationsDir::new("../golem-worker-service/db/migration".into()).sqlite_migrations(),
+    )
+    .await
+    .unwrap();
+
+    let db_pool = db::create_sqlite_pool(&db_config).await.unwrap();
+
+    let security_scheme_service: Arc<dyn SecuritySchemeService<TestNamespace> + Send + Sync> =
+        Arc::new(DefaultSecuritySchemeService::new(
+            Arc::new(DbSecuritySchemeRepo::new(db_pool.clone().into())),
+            Arc::new(TestIdentityProvider),
+        ));
+
+    let api_key_service = DefaultApiKeyService::new(
+        Arc::new(DbApiKeyRepo::new(db_pool.clone().into())),
+        security_scheme_service.clone(),
+    );
+
+    let namespace_a = TestNamespace("project-a".to_string());
+    let namespace_b = TestNamespace("project-b".to_string());
+
+    // Both namespaces use the same name for their scheme
+    let security_scheme = ApiKeySecurityScheme::new(
+        SecuritySchemeIdentifier::new("api-keys".to_string()),
+        ApiKeyLocation::Header("x-api-key".to_string()),
+    )
+    .unwrap();
+
+    for namespace in [&namespace_a, &namespace_b] {
+        security_scheme_service
+            .create_api_key(namespace, &security_scheme)
+            .await
+            .expect("Failed to create API key security scheme");
+    }
+
+    let (api_key, plaintext_key) = api_key_service
+        .create(
+            &namespace_a,
+            &security_scheme.scheme_identifier(),
+            "ci".to_string(),
+            Default::default(),
+        )
+        .await
+        .expect("Failed to create API key");
+
+    let valid_in_a = api_key_service
+        .validate(&namespace_a, &security_scheme, &plaintext_key.to_string())
+        .await;
+
+    let valid_in_b = api_key_service
+        .validate(&namespace_b, &security_scheme, &plaintext_key.to_string())
+        .await;
+
+    let keys_of_b = api_key_service
+        .get_all(&namespace_b, &security_scheme.scheme_identifier())
+        .await
+        .expect("Failed to get API keys");
+
+    assert_eq!(valid_in_a.map(|key| key.key_id), Ok(api_key.key_id));
+    assert_eq!(valid_in_b, Err(ApiKeyValidationError::InvalidKey));
+    assert!(keys_of_b.is_empty());
+}
+
+#[test]
+pub async fn test_hmac_secret_namespaces_with_sqlite() {
+    let db = SqliteDb::default();
+    let db_config = DbSqliteConfig {
+        database: db.db_path.clone(),
+        max_connections: 10,
+    };
+
+    db::sqlite_migrate(
+        &db_config,
+        MigrationsDir::new("../golem-worker-service/db/migration".into()).sqlite_migrations(),
+    )
+    .await
+    .unwrap();
+
+    let db_pool = db::create_sqlite_pool(&db_config).await.unwrap();
+
+    let security_scheme_service = DefaultSecuritySchemeService::<TestNamespace>::new(
+        Arc::new(DbSecuritySchemeRepo::new(db_pool.clone().into())),
+        Arc::new(TestIdentityProvider),
+    );
+
+    let namespace_a = TestNamespace("project-a".to_string());
+    let namespace_b = TestNamespace("project-b".to_string());
+
+    // Both namespaces use the same name for their scheme, with secrets of their own
+    let security_scheme = HmacSecurityScheme::new(
+        SecuritySchemeIdentifier::new("github".to_string()),
+        "X-Hub-Signature-256".to_string(),
+        HmacAlgorithm::Sha256,
+        HmacSignatureEncoding::Hex,
+        Some("sha256=".to_string()),
+        HmacSignatureFormat::Plain,
+    )
+    .unwrap();
+
+    security_scheme_service
+        .create_hmac(&namespace_a, &security_scheme, "It's a Secret to Everybody")
+        .await
+        .expect("Failed to create HMAC security scheme");
+
+    security_scheme_service
+        .create_hmac(&namespace_b, &security_scheme, "another secret")
+        .await
+        .expect("Failed to create HMAC security scheme");
+
+    // Signed with the secret of namespace A
+    let body = b"Hello, World!";
+    let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+    let valid_in_a = security_scheme_service
+        .validate(&namespace_a, &security_scheme, body, signature)
+        .await;
+
+    let valid_in_b = security_scheme_service
+        .validate(&namespace_b, &security_scheme, body, signature)
+        .await;
+
+    assert_eq!(valid_in_a, Ok(()));
+    assert_eq!(
+        valid_in_b,
+        Err(HmacSignatureValidationError::InvalidSignature)
+    );
+}
+
 struct TestComponentService;
 
 impl TestComponentService {
@@ -450,6 +645,10 @@ impl<AuthCtx> ComponentService<AuthCtx> for TestComponentService {
             function_constraints: vec![],
         })
     }
+
+    async fn get_all(&self, _auth_ctx: &AuthCtx) -> ComponentResult<Vec<Component>> {
+        Ok(vec![Self::test_component()])
+    }
 }
 
 async fn test_services(
@@ -493,6 +692,21 @@ async fn test_services(
     test_delete_non_existing(definition_service.clone()).await;
     test_deployment(definition_service.clone(), deployment_service.clone()).await;
     test_deployment_conflict(definition_service.clone(), deployment_service.clone()).await;
+    test_deployment_error_pages(definition_service.clone(), deployment_service.clone()).await;
+    test_deployment_cors(definition_service.clone(), deployment_service.clone()).await;
+    test_deployment_rollback(definition_service.clone(), deployment_service.clone()).await;
+    test_deployment_traffic_split(definition_service.clone(), deployment_service.clone()).await;
+    test_deployment_experiments(definition_service.clone(), deployment_service.clone()).await;
+    test_deployment_swap(definition_service.clone(), deployment_service.clone()).await;
+    test_definition_update_precondition(definition_service.clone()).await;
+    test_definition_taint_policy(definition_service.clone()).await;
+    test_definition_static_worker_names(definition_service.clone()).await;
+    test_deployment_precondition(
+        definition_service.clone(),
+        deployment_service.clone(),
+        api_deployment_repo.clone(),
+    )
+    .await;
 }
 
 async fn test_deployment(
@@ -746,132 +960,1008 @@ async fn test_deployment(
     assert!(definition2.is_some_and(|x| x.draft));
 }
 
-async fn test_deployment_conflict(
+async fn test_deployment_rollback(
     definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
     deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
 ) {
+    let id = Uuid::new_v4().to_string();
     let def1 = get_api_definition(
-            &Uuid::new_v4().to_string(),
-            "0.0.1",
-            "/api/get1",
-            "${ let worker = instance[golem:it](\"worker1\"); let status: u64 = 200; { headers: { ContentType: \"json\", userid: \"foo\"}, body: worker.get-cart-contents(\"foo\"), status: status }  }",
-            false,
-        );
-    let def2 = get_api_definition(
-        &Uuid::new_v4().to_string(),
+        &id,
         "0.0.1",
-        "/api/get2",
-        "${ let worker = instance[golem:it](\"worker2\"); {body: worker.get-cart-contents(\"foo\")} }",
+        "/api/rollback",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
         true,
     );
-
-    let def3 = get_api_definition(
-        &Uuid::new_v4().to_string(),
-        "0.0.1",
-        "/api/get1",
+    let def2 = get_api_definition(
+        &id,
+        "0.0.2",
+        "/api/rollback",
         "${ let worker = instance[golem:it](\"worker2\"); {body: worker.get-cart-contents(\"foo\")} }",
-        false,
+        true,
     );
 
-    definition_service
-        .create(
-            &def1,
-            &DefaultNamespace::default(),
-            &EmptyAuthCtx::default(),
-        )
+    for def in [&def1, &def2] {
+        definition_service
+            .create(def, &DefaultNamespace::default(), &EmptyAuthCtx::default())
+            .await
+            .unwrap();
+    }
+
+    let deployment = ApiDeploymentRequest {
+        api_definition_keys: vec![ApiDefinitionIdWithVersion {
+            id: def2.id.clone(),
+            version: def2.version.clone(),
+        }],
+        ..get_api_deployment("test-rollback.com", None, vec![])
+    };
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
         .await
         .unwrap();
-    definition_service
-        .create(
-            &def2,
+
+    let rollback_key = ApiDefinitionIdWithVersion {
+        id: def1.id.clone(),
+        version: def1.version.clone(),
+    };
+
+    let sites = deployment_service
+        .rollback(
             &DefaultNamespace::default(),
+            &rollback_key,
             &EmptyAuthCtx::default(),
         )
         .await
         .unwrap();
-    definition_service
-        .create(
-            &def3,
-            &DefaultNamespace::default(),
-            &EmptyAuthCtx::default(),
-        )
+    assert_eq!(sites, vec![ApiSiteString("test-rollback.com".to_string())]);
+
+    let definitions = deployment_service
+        .get_definitions_by_site(&DefaultNamespace::default(), &sites[0])
         .await
         .unwrap();
+    assert_eq!(definitions.len(), 1);
+    assert_eq!(definitions[0].version, def1.version);
+    assert!(!definitions[0].draft);
 
-    let deployment = get_api_deployment("test-conflict.com", None, vec![&def1.id.0, &def2.id.0]);
-    deployment_service
-        .deploy(&deployment, &EmptyAuthCtx::default())
+    let definition2 = definition_service
+        .get(
+            &def2.id,
+            &def2.version,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
         .await
         .unwrap();
+    assert!(definition2.is_some_and(|x| x.draft));
 
-    let deployment = get_api_deployment("test-conflict.com", None, vec![&def3.id.0]);
-    let deployment_result = deployment_service
-        .deploy(&deployment, &EmptyAuthCtx::default())
-        .await;
-    assert!(deployment_result.is_err());
-    assert_eq!(
-        deployment_result.unwrap_err().to_string(),
-        ApiDeploymentError::<DefaultNamespace>::ApiDefinitionsConflict("/api/get1".to_string())
-            .to_string()
-    );
-
-    let delete_result = definition_service
-        .delete(
-            &def1.id,
-            &def1.version,
+    let rollback_result = deployment_service
+        .rollback(
             &DefaultNamespace::default(),
+            &rollback_key,
             &EmptyAuthCtx::default(),
         )
         .await;
-    assert!(delete_result.is_err());
-    assert_eq!(
-        delete_result.unwrap_err().to_string(),
-        ApiDefinitionError::ApiDefinitionDeployed("test-conflict.com".to_string()).to_string()
-    );
+    assert!(rollback_result.is_err());
 }
 
-async fn test_security_crud(
-    security_scheme_service: Arc<dyn SecuritySchemeService<DefaultNamespace> + Sync + Send>,
+async fn test_deployment_traffic_split(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
 ) {
-    let security_identifier = SecuritySchemeIdentifier::new("test".to_string());
+    let id = Uuid::new_v4().to_string();
+    let def1 = get_api_definition(
+        &id,
+        "0.0.1",
+        "/api/canary",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+    let def2 = get_api_definition(
+        &id,
+        "0.0.2",
+        "/api/canary",
+        "${ let worker = instance[golem:it](\"worker2\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
 
-    let security_scheme = get_security(&security_identifier);
+    for def in [&def1, &def2] {
+        definition_service
+            .create(def, &DefaultNamespace::default(), &EmptyAuthCtx::default())
+            .await
+            .unwrap();
+    }
 
-    let insert = security_scheme_service
-        .create(&DefaultNamespace(), &security_scheme)
+    let keys = [&def1, &def2]
+        .iter()
+        .map(|def| ApiDefinitionIdWithVersion {
+            id: def.id.clone(),
+            version: def.version.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let traffic_split = ApiDeploymentTrafficSplit {
+        api_definition_id: def1.id.clone(),
+        stable_version: def1.version.clone(),
+        canary_version: def2.version.clone(),
+        canary_weight: 10,
+        header: None,
+    };
+
+    // Without a split, the routes of the two versions conflict
+    let deployment = ApiDeploymentRequest {
+        api_definition_keys: keys.clone(),
+        ..get_api_deployment("test-canary.com", None, vec![])
+    };
+    assert!(deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
         .await
-        .expect("Failed to create security scheme");
+        .is_err());
+
+    let invalid_deployment = ApiDeploymentRequest {
+        api_definition_keys: keys.clone(),
+        traffic_split: Some(ApiDeploymentTrafficSplit {
+            canary_weight: 101,
+            ..traffic_split.clone()
+        }),
+        ..get_api_deployment("test-canary.com", None, vec![])
+    };
+    assert!(deployment_service
+        .deploy(&invalid_deployment, &EmptyAuthCtx::default())
+        .await
+        .is_err());
 
-    let get = security_scheme_service
-        .get(&security_identifier, &DefaultNamespace())
+    let deployment = ApiDeploymentRequest {
+        api_definition_keys: keys.clone(),
+        traffic_split: Some(traffic_split.clone()),
+        ..get_api_deployment("test-canary.com", None, vec![])
+    };
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
         .await
-        .expect("Failed to get security scheme");
+        .unwrap();
 
-    assert_eq!(insert.security_scheme, security_scheme);
-    assert_eq!(get.security_scheme, security_scheme);
-    assert_eq!(insert.provider_metadata, get_test_provider_metadata());
-    assert_eq!(insert.provider_metadata, get.provider_metadata)
+    let site = ApiSiteString("test-canary.com".to_string());
+    let deployment = deployment_service
+        .get_by_site(&site)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(deployment.api_definition_keys.len(), 2);
+    assert_eq!(deployment.traffic_split, Some(traffic_split));
+
+    // Undeploying the stable version ends the split
+    let undeployment = ApiDeploymentRequest {
+        api_definition_keys: vec![keys[0].clone()],
+        ..get_api_deployment("test-canary.com", None, vec![])
+    };
+    deployment_service.undeploy(&undeployment).await.unwrap();
+
+    let deployment = deployment_service
+        .get_by_site(&site)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(deployment.api_definition_keys, vec![keys[1].clone()]);
+    assert_eq!(deployment.traffic_split, None);
 }
 
-async fn test_definition_crud(
+struct StaticHealthCheck(Result<(), String>);
+
+#[async_trait]
+impl DeploymentHealthCheck<DefaultNamespace> for StaticHealthCheck {
+    async fn check(
+        &self,
+        definitions: Vec<CompiledHttpApiDefinition<DefaultNamespace>>,
+    ) -> Result<(), String> {
+        assert_eq!(definitions.len(), 1);
+        self.0.clone()
+    }
+}
+
+async fn test_deployment_swap(
     definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
 ) {
-    let def1v1 = get_api_definition(
-            &Uuid::new_v4().to_string(),
-            "0.0.1",
-            "/api/get1",
-            "${ let userid: u64 = request.path.user; let res = if userid>100u64 then 0u64 else 1u64; let worker = instance(\"shopping-cart-${res}\"); let not_found: u64 = 401; let success: u64 = 200; let result = worker.get-cart-contents(\"foo\"); let status = if result == \"admin\" then not_found else success; status }",
-            false,
-        );
-    let def1v1_upd = get_api_definition(
-            &def1v1.id.0,
-            "0.0.1",
-            "/api/get1/1",
-            "${ let userid: u64 = request.path.user; let res = if userid>100u64 then 0u64 else 1u64; let worker = instance(\"shopping-cart-${res}\"); let not_found: u64 = 401; let success: u64 = 200; let result = worker.get-cart-contents(\"foo\"); let status = if result == \"admin\" then not_found else success; status }",
-            false,
-        );
-    let def1v2 = get_api_definition(
-            &def1v1.id.0,
+    let id = Uuid::new_v4().to_string();
+    let blue = get_api_definition(
+        &id,
+        "0.0.1",
+        "/api/swap",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+    let green = get_api_definition(
+        &id,
+        "0.0.2",
+        "/api/swap",
+        "${ let worker = instance[golem:it](\"worker2\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+
+    for def in [&blue, &green] {
+        definition_service
+            .create(def, &DefaultNamespace::default(), &EmptyAuthCtx::default())
+            .await
+            .unwrap();
+    }
+
+    let deployment = ApiDeploymentRequest {
+        api_definition_keys: vec![ApiDefinitionIdWithVersion {
+            id: blue.id.clone(),
+            version: blue.version.clone(),
+        }],
+        ..get_api_deployment("test-swap.com", None, vec![])
+    };
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    let site = ApiSiteString("test-swap.com".to_string());
+    let green_key = ApiDefinitionIdWithVersion {
+        id: green.id.clone(),
+        version: green.version.clone(),
+    };
+
+    // A failed health check leaves the site as it was
+    let swap_result = deployment_service
+        .swap(
+            &DefaultNamespace::default(),
+            &site,
+            &green_key,
+            Some(&StaticHealthCheck(Err("unhealthy".to_string()))),
+            &EmptyAuthCtx::default(),
+        )
+        .await;
+    assert!(swap_result.is_err());
+
+    let definitions = deployment_service
+        .get_definitions_by_site(&DefaultNamespace::default(), &site)
+        .await
+        .unwrap();
+    assert_eq!(definitions.len(), 1);
+    assert_eq!(definitions[0].version, blue.version);
+
+    deployment_service
+        .swap(
+            &DefaultNamespace::default(),
+            &site,
+            &green_key,
+            Some(&StaticHealthCheck(Ok(()))),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let definitions = deployment_service
+        .get_definitions_by_site(&DefaultNamespace::default(), &site)
+        .await
+        .unwrap();
+    assert_eq!(definitions.len(), 1);
+    assert_eq!(definitions[0].version, green.version);
+    assert!(!definitions[0].draft);
+
+    let blue_definition = definition_service
+        .get(
+            &blue.id,
+            &blue.version,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+    assert!(blue_definition.is_some_and(|x| x.draft));
+}
+
+async fn test_deployment_experiments(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    let def = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/experiments",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+    definition_service
+        .create(&def, &DefaultNamespace::default(), &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    let keys = vec![ApiDefinitionIdWithVersion {
+        id: def.id.clone(),
+        version: def.version.clone(),
+    }];
+
+    let experiment = ApiDeploymentExperiment {
+        name: "checkout".to_string(),
+        variants: vec![
+            ExperimentVariant {
+                name: "control".to_string(),
+                weight: 50,
+            },
+            ExperimentVariant {
+                name: "one-click".to_string(),
+                weight: 50,
+            },
+        ],
+        bucket_header: Some("x-user-id".to_string()),
+    };
+
+    let invalid_deployment = ApiDeploymentRequest {
+        api_definition_keys: keys.clone(),
+        experiments: Some(vec![experiment.clone(), experiment.clone()]),
+        ..get_api_deployment("test-experiments.com", None, vec![])
+    };
+    assert!(deployment_service
+        .deploy(&invalid_deployment, &EmptyAuthCtx::default())
+        .await
+        .is_err());
+
+    let deployment = ApiDeploymentRequest {
+        api_definition_keys: keys.clone(),
+        experiments: Some(vec![experiment.clone()]),
+        ..get_api_deployment("test-experiments.com", None, vec![])
+    };
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    let site = ApiSiteString("test-experiments.com".to_string());
+    let get_experiments = || async {
+        deployment_service
+            .get_by_site(&site)
+            .await
+            .unwrap()
+            .unwrap()
+            .experiments
+    };
+    assert_eq!(get_experiments().await, Some(vec![experiment.clone()]));
+
+    // Deploying without experiments keeps them, and deploying with none ends them
+    let deployment = ApiDeploymentRequest {
+        api_definition_keys: keys.clone(),
+        ..get_api_deployment("test-experiments.com", None, vec![])
+    };
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+    assert_eq!(get_experiments().await, Some(vec![experiment]));
+
+    let deployment = ApiDeploymentRequest {
+        api_definition_keys: keys,
+        experiments: Some(vec![]),
+        ..get_api_deployment("test-experiments.com", None, vec![])
+    };
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+    assert_eq!(get_experiments().await, None);
+}
+
+async fn test_deployment_conflict(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    let def1 = get_api_definition(
+            &Uuid::new_v4().to_string(),
+            "0.0.1",
+            "/api/get1",
+            "${ let worker = instance[golem:it](\"worker1\"); let status: u64 = 200; { headers: { ContentType: \"json\", userid: \"foo\"}, body: worker.get-cart-contents(\"foo\"), status: status }  }",
+            false,
+        );
+    let def2 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/get2",
+        "${ let worker = instance[golem:it](\"worker2\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+
+    let def3 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/get1",
+        "${ let worker = instance[golem:it](\"worker2\"); {body: worker.get-cart-contents(\"foo\")} }",
+        false,
+    );
+
+    definition_service
+        .create(
+            &def1,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+    definition_service
+        .create(
+            &def2,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+    definition_service
+        .create(
+            &def3,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let deployment = get_api_deployment("test-conflict.com", None, vec![&def1.id.0, &def2.id.0]);
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    let deployment = get_api_deployment("test-conflict.com", None, vec![&def3.id.0]);
+    let deployment_result = deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await;
+    assert!(deployment_result.is_err());
+    assert_eq!(
+        deployment_result.unwrap_err().to_string(),
+        ApiDeploymentError::<DefaultNamespace>::ApiDefinitionsConflict("/api/get1".to_string())
+            .to_string()
+    );
+
+    let delete_result = definition_service
+        .delete(
+            &def1.id,
+            &def1.version,
+            &DefaultNamespace::default(),
+            "test-user",
+            &EmptyAuthCtx::default(),
+        )
+        .await;
+    assert!(delete_result.is_err());
+    assert_eq!(
+        delete_result.unwrap_err().to_string(),
+        ApiDefinitionError::ApiDefinitionDeployed("test-conflict.com".to_string()).to_string()
+    );
+}
+
+async fn test_definition_update_precondition(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    let def = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/precondition",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+    let def_upd1 = get_api_definition(
+        &def.id.0,
+        "0.0.1",
+        "/api/precondition/1",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+    let def_upd2 = get_api_definition(
+        &def.id.0,
+        "0.0.1",
+        "/api/precondition/2",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        true,
+    );
+
+    definition_service
+        .create(&def, &DefaultNamespace::default(), &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    let stale = definition_service
+        .get(
+            &def.id,
+            &def.version,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    definition_service
+        .update(
+            &def_upd1,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let result = definition_service
+        .update_if_unchanged(
+            &def_upd2,
+            &stale,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApiDefinitionError::PreconditionFailed(_))
+    ));
+
+    let current = definition_service
+        .get(
+            &def.id,
+            &def.version,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(current.routes[0].path.to_string(), "/api/precondition/1");
+
+    let updated = definition_service
+        .update_if_unchanged(
+            &def_upd2,
+            &current,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.routes[0].path.to_string(), "/api/precondition/2");
+}
+
+async fn test_definition_taint_policy(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    let rib = "${ let worker = instance[golem:it](\"cart-${request.path.user-id}\"); {body: worker.get-cart-contents(\"foo\")} }";
+
+    let unvalidated = HttpApiDefinitionRequest {
+        taint_policy: TaintAnalysisPolicy::Deny,
+        ..get_api_definition(
+            &Uuid::new_v4().to_string(),
+            "0.0.1",
+            "/api/taint/{user-id}",
+            rib,
+            false,
+        )
+    };
+    let result = definition_service
+        .create(
+            &unvalidated,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApiDefinitionError::ValidationError(_))
+    ));
+
+    // The findings are only reported with the default policy
+    let warned = HttpApiDefinitionRequest {
+        taint_policy: TaintAnalysisPolicy::Warn,
+        ..unvalidated.clone()
+    };
+    definition_service
+        .create(
+            &warned,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    // A path parameter validated by a regex is trusted in the worker name
+    let validated = HttpApiDefinitionRequest {
+        taint_policy: TaintAnalysisPolicy::Deny,
+        ..get_api_definition(
+            &Uuid::new_v4().to_string(),
+            "0.0.1",
+            "/api/taint/{user-id:[0-9]+}",
+            rib,
+            false,
+        )
+    };
+    definition_service
+        .create(
+            &validated,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn test_definition_static_worker_names(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    fn with_static_worker_names(
+        mut definition: HttpApiDefinitionRequest,
+    ) -> HttpApiDefinitionRequest {
+        for route in definition.routes.iter_mut() {
+            if let GatewayBinding::Default(worker_binding) = &mut route.binding {
+                worker_binding.static_worker_names = true;
+            }
+        }
+        definition
+    }
+
+    let dynamic_rib = "${ let worker = instance[golem:it](\"cart-${request.path.user-id}\"); {body: worker.get-cart-contents(\"foo\")} }";
+    let static_rib = "${ let region = \"eu\"; let worker = instance[golem:it](\"cart-${region}\"); {body: worker.get-cart-contents(\"foo\")} }";
+
+    // The worker name computed from the request is only rejected when required to be static
+    let dynamic = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/static/{user-id}",
+        dynamic_rib,
+        false,
+    );
+    definition_service
+        .create(
+            &dynamic,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let result = definition_service
+        .create(
+            &with_static_worker_names(HttpApiDefinitionRequest {
+                id: ApiDefinitionId(Uuid::new_v4().to_string()),
+                ..dynamic
+            }),
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApiDefinitionError::RibCompilationErrors(_))
+    ));
+
+    let static_definition = with_static_worker_names(get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/static/{user-id}",
+        static_rib,
+        false,
+    ));
+    definition_service
+        .create(
+            &static_definition,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    // The flag is stored with the binding
+    let stored = definition_service
+        .get(
+            &static_definition.id,
+            &static_definition.version,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(stored.routes.iter().all(|route| matches!(
+        &route.binding,
+        GatewayBindingCompiled::Worker(worker_binding) if worker_binding.static_worker_names
+    )));
+}
+
+async fn test_deployment_precondition(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    api_deployment_repo: Arc<dyn api_deployment::ApiDeploymentRepo + Sync + Send>,
+) {
+    let def1 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/precondition/get1",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        false,
+    );
+    let def2 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/precondition/get2",
+        "${ let worker = instance[golem:it](\"worker2\"); {body: worker.get-cart-contents(\"foo\")} }",
+        false,
+    );
+    let def3 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/precondition/get3",
+        "${ let worker = instance[golem:it](\"worker3\"); {body: worker.get-cart-contents(\"foo\")} }",
+        false,
+    );
+
+    for def in [&def1, &def2, &def3] {
+        definition_service
+            .create(def, &DefaultNamespace::default(), &EmptyAuthCtx::default())
+            .await
+            .unwrap();
+    }
+
+    let site = ApiSiteString("test-precondition.com".to_string());
+
+    deployment_service
+        .deploy(
+            &get_api_deployment("test-precondition.com", None, vec![&def1.id.0]),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let stale = deployment_service
+        .get_by_site(&site)
+        .await
+        .unwrap()
+        .unwrap();
+    let stale_records = api_deployment_repo
+        .get_by_site(&site.to_string())
+        .await
+        .unwrap();
+
+    deployment_service
+        .deploy(
+            &get_api_deployment("test-precondition.com", None, vec![&def2.id.0]),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let result = deployment_service
+        .deploy_with_precondition(
+            &get_api_deployment("test-precondition.com", None, vec![&def3.id.0]),
+            Some(&stale),
+            &EmptyAuthCtx::default(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ApiDeploymentError::PreconditionFailed(_))
+    ));
+
+    // The site changed after the records were read, so the conditional write must not happen
+    let created = api_deployment_repo
+        .create_if_unchanged(&site.to_string(), stale_records, vec![])
+        .await
+        .unwrap();
+    assert!(!created);
+
+    let current = deployment_service
+        .get_by_site(&site)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(current.api_definition_keys.len(), 2);
+
+    deployment_service
+        .deploy_with_precondition(
+            &get_api_deployment("test-precondition.com", None, vec![&def3.id.0]),
+            Some(&current),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let updated = deployment_service
+        .get_by_site(&site)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.api_definition_keys.len(), 3);
+}
+
+async fn test_deployment_error_pages(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    let def1 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/error-pages",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        false,
+    );
+
+    definition_service
+        .create(
+            &def1,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let site = ApiSiteString("test-error-pages.com".to_string());
+    let error_pages = ApiDeploymentErrorPages {
+        maintenance: true,
+        maintenance_retry_after: Some(120),
+        maintenance_page: vec![ErrorPage {
+            content_type: "text/html".to_string(),
+            body: "<h1>Back soon</h1>".to_string(),
+        }],
+        ..Default::default()
+    };
+
+    let not_deployed_result = deployment_service
+        .set_error_pages(&DefaultNamespace::default(), &site, &error_pages)
+        .await;
+    assert!(not_deployed_result.is_err());
+
+    let deployment = get_api_deployment("test-error-pages.com", None, vec![&def1.id.0]);
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    // Cached by the first lookup, and dropped from the cache by the changes
+    let config = deployment_service.get_config_by_site(&site).await.unwrap();
+    assert_eq!(config.error_pages, None);
+
+    deployment_service
+        .set_error_pages(&DefaultNamespace::default(), &site, &error_pages)
+        .await
+        .unwrap();
+    let stored = deployment_service.get_error_pages(&site).await.unwrap();
+    assert_eq!(stored, Some(error_pages.clone()));
+    let config = deployment_service.get_config_by_site(&site).await.unwrap();
+    assert_eq!(config.error_pages, Some(error_pages.clone()));
+
+    let invalid_result = deployment_service
+        .set_error_pages(
+            &DefaultNamespace::default(),
+            &site,
+            &ApiDeploymentErrorPages {
+                not_found: vec![ErrorPage {
+                    content_type: "html".to_string(),
+                    body: "".to_string(),
+                }],
+                ..error_pages
+            },
+        )
+        .await;
+    assert!(invalid_result.is_err());
+
+    deployment_service
+        .delete(&DefaultNamespace::default(), &site)
+        .await
+        .unwrap();
+    let stored = deployment_service.get_error_pages(&site).await.unwrap();
+    assert_eq!(stored, None);
+    let config = deployment_service.get_config_by_site(&site).await.unwrap();
+    assert_eq!(config.error_pages, None);
+}
+
+async fn test_deployment_cors(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    let def1 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/cors/1",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        false,
+    );
+    let def2 = get_api_definition(
+        &Uuid::new_v4().to_string(),
+        "0.0.1",
+        "/api/cors/2",
+        "${ let worker = instance[golem:it](\"worker1\"); {body: worker.get-cart-contents(\"foo\")} }",
+        false,
+    );
+
+    for def in [&def1, &def2] {
+        definition_service
+            .create(def, &DefaultNamespace::default(), &EmptyAuthCtx::default())
+            .await
+            .unwrap();
+    }
+
+    let cors = HttpCors::new(
+        "https://app.golem.cloud",
+        "GET, POST",
+        "Content-Type",
+        None,
+        Some(true),
+        Some(600),
+    );
+
+    let invalid_deployment = ApiDeploymentRequest {
+        cors: Some(HttpCors::new(
+            "*",
+            "FETCH",
+            "Content-Type",
+            None,
+            None,
+            None,
+        )),
+        ..get_api_deployment("test-cors.com", None, vec![&def1.id.0])
+    };
+    assert!(deployment_service
+        .deploy(&invalid_deployment, &EmptyAuthCtx::default())
+        .await
+        .is_err());
+
+    let deployment = ApiDeploymentRequest {
+        cors: Some(cors.clone()),
+        ..get_api_deployment("test-cors.com", None, vec![&def1.id.0])
+    };
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    // Deploying without CORS keeps the configuration of the site
+    let deployment = get_api_deployment("test-cors.com", None, vec![&def2.id.0]);
+    deployment_service
+        .deploy(&deployment, &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+
+    let deployment = deployment_service
+        .get_by_site(&ApiSiteString("test-cors.com".to_string()))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(deployment.api_definition_keys.len(), 2);
+    assert_eq!(deployment.cors, Some(cors));
+}
+
+async fn test_security_crud(
+    security_scheme_service: Arc<dyn SecuritySchemeService<DefaultNamespace> + Sync + Send>,
+) {
+    let security_identifier = SecuritySchemeIdentifier::new("test".to_string());
+
+    let security_scheme = get_security(&security_identifier);
+
+    let insert = security_scheme_service
+        .create(&DefaultNamespace(), &security_scheme)
+        .await
+        .expect("Failed to create security scheme");
+
+    let get = security_scheme_service
+        .get(&security_identifier, &DefaultNamespace())
+        .await
+        .expect("Failed to get security scheme");
+
+    assert_eq!(insert.security_scheme, security_scheme);
+    assert_eq!(get.security_scheme, security_scheme);
+    assert_eq!(insert.provider_metadata, get_test_provider_metadata());
+    assert_eq!(insert.provider_metadata, get.provider_metadata)
+}
+
+async fn test_definition_crud(
+    definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+) {
+    let def1v1 = get_api_definition(
+            &Uuid::new_v4().to_string(),
+            "0.0.1",
+            "/api/get1",
+            "${ let userid: u64 = request.path.user; let res = if userid>100u64 then 0u64 else 1u64; let worker = instance(\"shopping-cart-${res}\"); let not_found: u64 = 401; let success: u64 = 200; let result = worker.get-cart-contents(\"foo\"); let status = if result == \"admin\" then not_found else success; status }",
+            false,
+        );
+    let def1v1_upd = get_api_definition(
+            &def1v1.id.0,
+            "0.0.1",
+            "/api/get1/1",
+            "${ let userid: u64 = request.path.user; let res = if userid>100u64 then 0u64 else 1u64; let worker = instance(\"shopping-cart-${res}\"); let not_found: u64 = 401; let success: u64 = 200; let result = worker.get-cart-contents(\"foo\"); let status = if result == \"admin\" then not_found else success; status }",
+            false,
+        );
+    let def1v2 = get_api_definition(
+            &def1v1.id.0,
             "0.0.2",
             "/api/get1/2",
             "${ let userid: u64 = request.path.user; let res = if userid>100u64 then 0u64 else 1u64; let worker = instance(\"shopping-cart-${res}\"); let not_found: u64 = 401; let success: u64 = 200; let result = worker.get-cart-contents(\"foo\"); let status = if result == \"admin\" then not_found else success; status }",
@@ -985,6 +2075,7 @@ async fn test_definition_crud(
                 &def1v1.id,
                 &def1v1.version,
                 &DefaultNamespace::default(),
+                "test-user",
                 &EmptyAuthCtx::default(),
             )
             .await
@@ -997,6 +2088,7 @@ async fn test_definition_crud(
                 &def1v2.id,
                 &def1v2.version,
                 &DefaultNamespace::default(),
+                "test-user",
                 &EmptyAuthCtx::default(),
             )
             .await
@@ -1013,6 +2105,33 @@ async fn test_definition_crud(
         .await
         .unwrap();
     assert!(definitions.is_empty());
+
+    let deleted = definition_service
+        .get_deleted(&DefaultNamespace::default(), &EmptyAuthCtx::default())
+        .await
+        .unwrap();
+    assert_eq!(deleted.len(), 2);
+    assert!(deleted.iter().all(|d| d.deleted_by == "test-user"));
+
+    definition_service
+        .restore(
+            &def1v1.id,
+            &def1v1.version,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+
+    let definitions = definition_service
+        .get_all_versions(
+            &def1v1.id,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(definitions.len(), 1);
 }
 
 async fn test_delete_non_existing(
@@ -1023,6 +2142,7 @@ async fn test_delete_non_existing(
             &ApiDefinitionId("non-existing".to_string()),
             &ApiVersion("0.0.1".to_string()),
             &DefaultNamespace::default(),
+            "test-user",
             &EmptyAuthCtx::default(),
         )
         .await;
@@ -1050,6 +2170,11 @@ fn get_api_deployment(
             host: host.to_string(),
             subdomain: subdomain.map(|s| s.to_string()),
         },
+        cors: None,
+        traffic_split: None,
+        experiments: None,
+        capacity: None,
+        compression: None,
     }
 }
 
@@ -0,0 +1,4 @@
+ALTER TABLE api_definitions
+    ADD COLUMN IF NOT EXISTS deleted_at timestamp without time zone;
+ALTER TABLE api_definitions
+    ADD COLUMN IF NOT EXISTS deleted_by text;
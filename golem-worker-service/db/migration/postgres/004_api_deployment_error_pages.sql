@@ -0,0 +1,7 @@
+CREATE TABLE api_deployment_error_pages
+(
+    site        text NOT NULL,
+    namespace   text NOT NULL,
+    error_pages text NOT NULL,
+    PRIMARY KEY (site)
+);
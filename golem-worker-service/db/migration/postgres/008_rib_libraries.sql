@@ -0,0 +1,9 @@
+CREATE TABLE rib_libraries
+(
+    namespace  text      NOT NULL,
+    name       text      NOT NULL,
+    version    text      NOT NULL,
+    source     text      NOT NULL,
+    created_at timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (namespace, name, version)
+);
@@ -0,0 +1,25 @@
+CREATE TABLE api_deployment_request_fixtures
+(
+    site             text NOT NULL,
+    namespace        text NOT NULL,
+    request_fixtures text NOT NULL,
+    PRIMARY KEY (site)
+);
+
+CREATE TABLE gateway_request_fixtures
+(
+    namespace       text      NOT NULL,
+    site            text      NOT NULL,
+    fixture_id      text      NOT NULL,
+    route           text      NOT NULL,
+    method          text      NOT NULL,
+    uri             text      NOT NULL,
+    headers         text      NOT NULL,
+    body            bytea     NOT NULL,
+    status          integer   NOT NULL,
+    response_schema text,
+    created_at      timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (site, fixture_id)
+);
+
+CREATE INDEX gateway_request_fixtures_created_at_idx ON gateway_request_fixtures (site, created_at);
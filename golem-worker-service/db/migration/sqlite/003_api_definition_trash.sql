@@ -0,0 +1,4 @@
+ALTER TABLE api_definitions
+    ADD COLUMN deleted_at timestamp without time zone;
+ALTER TABLE api_definitions
+    ADD COLUMN deleted_by text;
@@ -0,0 +1,14 @@
+CREATE TABLE api_keys
+(
+    namespace          text                        NOT NULL,
+    security_scheme_id text                        NOT NULL,
+    key_id             text                        NOT NULL,
+    name               text                        NOT NULL,
+    metadata           text                        NOT NULL,
+    salt               blob                        NOT NULL,
+    key_hash           blob                        NOT NULL,
+    created_at         timestamp without time zone DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (namespace, security_scheme_id, key_id)
+);
+
+CREATE INDEX api_keys_security_scheme_key_idx ON api_keys (security_scheme_id, key_id);
@@ -0,0 +1,7 @@
+CREATE TABLE api_deployment_client_certificates
+(
+    site                text NOT NULL,
+    namespace           text NOT NULL,
+    client_certificates text NOT NULL,
+    PRIMARY KEY (site)
+);
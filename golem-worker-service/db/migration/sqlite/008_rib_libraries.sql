@@ -0,0 +1,9 @@
+CREATE TABLE rib_libraries
+(
+    namespace  text                        NOT NULL,
+    name       text                        NOT NULL,
+    version    text                        NOT NULL,
+    source     text                        NOT NULL,
+    created_at timestamp without time zone DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (namespace, name, version)
+);
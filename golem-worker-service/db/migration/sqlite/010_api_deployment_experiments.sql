@@ -0,0 +1,2 @@
+ALTER TABLE api_deployments
+    ADD COLUMN experiments text;
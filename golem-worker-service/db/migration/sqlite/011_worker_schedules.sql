@@ -0,0 +1,18 @@
+CREATE TABLE worker_schedules
+(
+    namespace       text                        NOT NULL,
+    schedule_id     text                        NOT NULL,
+    component_id    text                        NOT NULL,
+    worker_name     text                        NOT NULL,
+    function_name   text                        NOT NULL,
+    function_params text                        NOT NULL,
+    cron_expression text                        NOT NULL,
+    timezone        text                        NOT NULL,
+    misfire_policy  text                        NOT NULL,
+    next_run_at     timestamp without time zone NOT NULL,
+    last_run_at     timestamp without time zone,
+    created_at      timestamp without time zone DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (namespace, schedule_id)
+);
+
+CREATE INDEX worker_schedules_next_run_at_idx ON worker_schedules (next_run_at);
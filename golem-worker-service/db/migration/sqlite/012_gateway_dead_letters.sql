@@ -0,0 +1,27 @@
+CREATE TABLE api_deployment_dead_letter_queues
+(
+    site              text NOT NULL,
+    namespace         text NOT NULL,
+    dead_letter_queue text NOT NULL,
+    PRIMARY KEY (site)
+);
+
+CREATE TABLE gateway_dead_letters
+(
+    namespace          text                        NOT NULL,
+    site               text                        NOT NULL,
+    dead_letter_id     text                        NOT NULL,
+    route              text                        NOT NULL,
+    method             text                        NOT NULL,
+    uri                text                        NOT NULL,
+    headers            text                        NOT NULL,
+    body               blob,
+    error              text                        NOT NULL,
+    replay_count       integer                     NOT NULL DEFAULT 0,
+    last_replayed_at   timestamp without time zone,
+    last_replay_status integer,
+    created_at         timestamp without time zone DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (site, dead_letter_id)
+);
+
+CREATE INDEX gateway_dead_letters_created_at_idx ON gateway_dead_letters (site, created_at);
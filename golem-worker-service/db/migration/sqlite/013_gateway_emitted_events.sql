@@ -0,0 +1,16 @@
+CREATE TABLE gateway_emitted_events
+(
+    namespace    text                        NOT NULL,
+    event_id     text                        NOT NULL,
+    event_name   text                        NOT NULL,
+    payload      text                        NOT NULL,
+    component_id text                        NOT NULL,
+    worker_name  text,
+    trace_id     text                        NOT NULL,
+    method       text,
+    uri          text,
+    created_at   timestamp without time zone DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    PRIMARY KEY (event_id)
+);
+
+CREATE INDEX gateway_emitted_events_created_at_idx ON gateway_emitted_events (namespace, created_at);
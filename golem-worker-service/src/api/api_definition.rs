@@ -1,17 +1,26 @@
 use golem_common::json_yaml::JsonOrYaml;
+use golem_common::model::{ComponentId, ComponentVersion};
 use golem_common::{recorded_http_api_request, safe};
 use golem_service_base::api_tags::ApiTags;
 use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
+use golem_service_base::headers::{with_etag, IfMatch};
+use golem_service_base::model::VersionedComponentId;
+use golem_worker_service_base::api::ApiDefinitionDiff;
 use golem_worker_service_base::api::ApiEndpointError;
+use golem_worker_service_base::api::DeletedApiDefinition;
 use golem_worker_service_base::api::HttpApiDefinitionRequest;
 use golem_worker_service_base::api::HttpApiDefinitionResponseData;
 use golem_worker_service_base::gateway_api_definition::http::CompiledHttpApiDefinition;
 use golem_worker_service_base::gateway_api_definition::http::HttpApiDefinitionRequest as CoreHttpApiDefinitionRequest;
 use golem_worker_service_base::gateway_api_definition::http::OpenApiHttpApiDefinitionRequest;
+use golem_worker_service_base::gateway_api_definition::http::RibBindingStubs;
+use golem_worker_service_base::gateway_api_definition::http::RibHttpApiDefinitionRequest;
 use golem_worker_service_base::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use golem_worker_service_base::gateway_rib_compiler::with_taint_findings;
 use golem_worker_service_base::service::gateway::api_definition::ApiDefinitionService;
-use poem_openapi::param::{Path, Query};
-use poem_openapi::payload::Json;
+use golem_worker_service_base::service::gateway::rib_library::RibLibraryService;
+use poem_openapi::param::{Header, Path, Query};
+use poem_openapi::payload::{Json, PlainText, Response};
 use poem_openapi::*;
 use std::result::Result;
 use std::sync::Arc;
@@ -19,6 +28,7 @@ use tracing::{error, Instrument};
 
 pub struct RegisterApiDefinitionApi {
     definition_service: Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    rib_library_service: Arc<dyn RibLibraryService<DefaultNamespace> + Sync + Send>,
 }
 
 #[OpenApi(prefix_path = "/v1/api/definitions", tag = ApiTags::ApiDefinition)]
@@ -27,8 +37,12 @@ impl RegisterApiDefinitionApi {
         definition_service: Arc<
             dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send,
         >,
+        rib_library_service: Arc<dyn RibLibraryService<DefaultNamespace> + Sync + Send>,
     ) -> Self {
-        Self { definition_service }
+        Self {
+            definition_service,
+            rib_library_service,
+        }
     }
 
     /// Upload an OpenAPI definition
@@ -42,25 +56,96 @@ impl RegisterApiDefinitionApi {
     ) -> Result<Json<HttpApiDefinitionResponseData>, ApiEndpointError> {
         let record = recorded_http_api_request!("import_open_api",);
 
-        let response = {
-            let definition = payload.0.to_http_api_definition_request().map_err(|e| {
-                error!("Invalid Spec {}", e);
-                ApiEndpointError::bad_request(safe(e))
-            })?;
+        let response = self
+            .create_parsed(|| {
+                payload.0.to_http_api_definition_request().map_err(|e| {
+                    error!("Invalid Spec {}", e);
+                    ApiEndpointError::bad_request(safe(e))
+                })
+            })
+            .instrument(record.span.clone())
+            .await
+            .map(Json);
 
-            let result = self
-                .create_api(&definition)
-                .instrument(record.span.clone())
-                .await?;
+        record.result(response)
+    }
 
-            let result = HttpApiDefinitionResponseData::try_from(result).map_err(|e| {
-                error!("Failed to convert to response data {}", e);
-                ApiEndpointError::internal(safe(e))
-            });
+    /// Generate an API definition from an OpenAPI definition
+    ///
+    /// Converts an OpenAPI 3.0 or 3.1 document to a Golem API definition without storing it.
+    /// Operations without a Golem binding extension get a templated Rib binding calling the
+    /// function named after the operation id of the given component, with the path, query, header
+    /// and cookie parameters passed as arguments. The result can be adjusted and then created.
+    #[oai(
+        path = "/import/stubs",
+        method = "post",
+        operation_id = "generate_definition_from_open_api"
+    )]
+    async fn generate_from_open_api(
+        &self,
+        payload: JsonOrYaml<OpenApiHttpApiDefinitionRequest>,
+        #[oai(name = "component-id")] component_id: Query<ComponentId>,
+        #[oai(name = "component-version")] component_version: Query<ComponentVersion>,
+        #[oai(name = "worker-name")] worker_name: Query<Option<String>>,
+        interface: Query<Option<String>>,
+    ) -> Result<Json<HttpApiDefinitionRequest>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "generate_definition_from_open_api",
+            component_id = component_id.0.to_string(),
+            component_version = component_version.0.to_string()
+        );
 
-            result.map(Json)
+        let stubs = RibBindingStubs {
+            component_id: VersionedComponentId {
+                component_id: component_id.0,
+                version: component_version.0,
+            },
+            worker_name: worker_name.0,
+            interface: interface.0,
         };
 
+        let response = payload
+            .0
+            .to_http_api_definition_request_with_stubs(&stubs)
+            .and_then(HttpApiDefinitionRequest::try_from)
+            .map(Json)
+            .map_err(|e| {
+                error!("Invalid Spec {}", e);
+                ApiEndpointError::bad_request(safe(e))
+            });
+
+        record.result(response)
+    }
+
+    /// Create an API definition from a Rib document
+    ///
+    /// Creates a new draft API definition from a single document declaring its routes, security
+    /// and bindings, with the binding scripts written inline in Rib. The embedded scripts are
+    /// parsed when the document is read and compiled against the bound components on creation.
+    #[oai(
+        path = "/rib",
+        method = "post",
+        operation_id = "create_definition_from_rib"
+    )]
+    async fn create_from_rib(
+        &self,
+        payload: PlainText<String>,
+    ) -> Result<Json<HttpApiDefinitionResponseData>, ApiEndpointError> {
+        let record = recorded_http_api_request!("create_definition_from_rib",);
+
+        let response = self
+            .create_parsed(|| {
+                RibHttpApiDefinitionRequest(payload.0)
+                    .to_http_api_definition_request()
+                    .map_err(|e| {
+                        error!("Invalid Rib API definition {}", e);
+                        ApiEndpointError::bad_request(safe(e))
+                    })
+            })
+            .instrument(record.span.clone())
+            .await
+            .map(Json);
+
         record.result(response)
     }
 
@@ -68,11 +153,13 @@ impl RegisterApiDefinitionApi {
     ///
     /// Creates a new API definition described by Golem's API definition JSON document.
     /// If an API definition of the same version already exists, its an error.
+    /// The bindings imported from Rib libraries with `use` statements are linked into the
+    /// binding scripts on creation.
     #[oai(path = "/", method = "post", operation_id = "create_definition")]
     async fn create(
         &self,
         payload: JsonOrYaml<HttpApiDefinitionRequest>,
-    ) -> Result<Json<HttpApiDefinitionResponseData>, ApiEndpointError> {
+    ) -> Result<Response<Json<HttpApiDefinitionResponseData>>, ApiEndpointError> {
         let record = recorded_http_api_request!(
             "create_definition",
             api_definition_id = payload.0.id.to_string(),
@@ -81,23 +168,19 @@ impl RegisterApiDefinitionApi {
         );
 
         let response = {
-            let definition: CoreHttpApiDefinitionRequest = payload
-                .0
-                .try_into()
-                .map_err(|err| ApiEndpointError::bad_request(safe(err)))?;
-
-            let compiled_definition = self
-                .create_api(&definition)
+            let mut payload = payload.0;
+            self.link_rib_libraries(&mut payload)
                 .instrument(record.span.clone())
                 .await?;
 
-            let result =
-                HttpApiDefinitionResponseData::try_from(compiled_definition).map_err(|e| {
-                    error!("Failed to convert to response data {}", e);
-                    ApiEndpointError::internal(safe(e))
-                });
-
-            result.map(Json)
+            self.create_parsed(|| {
+                payload
+                    .try_into()
+                    .map_err(|err: String| ApiEndpointError::bad_request(safe(err)))
+            })
+            .instrument(record.span.clone())
+            .await
+            .map(with_definition_etag)
         };
 
         record.result(response)
@@ -106,6 +189,8 @@ impl RegisterApiDefinitionApi {
     /// Update an existing API definition.
     ///
     /// Only draft API definitions can be updated.
+    /// If the `If-Match` header is set, the update is only applied when it matches
+    /// the `etag` of the current definition, otherwise it fails with 412 Precondition Failed.
     #[oai(
         path = "/:id/:version",
         method = "put",
@@ -115,8 +200,9 @@ impl RegisterApiDefinitionApi {
         &self,
         id: Path<ApiDefinitionId>,
         version: Path<ApiVersion>,
+        #[oai(name = "If-Match")] if_match: Header<Option<String>>,
         payload: JsonOrYaml<HttpApiDefinitionRequest>,
-    ) -> Result<Json<HttpApiDefinitionResponseData>, ApiEndpointError> {
+    ) -> Result<Response<Json<HttpApiDefinitionResponseData>>, ApiEndpointError> {
         let record = recorded_http_api_request!(
             "update_definition",
             api_definition_id = id.0.to_string(),
@@ -125,38 +211,91 @@ impl RegisterApiDefinitionApi {
         );
 
         let response = {
-            let definition: CoreHttpApiDefinitionRequest = payload
-                .0
-                .try_into()
-                .map_err(|err| ApiEndpointError::bad_request(safe(err)))?;
-
-            if id.0 != definition.id {
-                Err(ApiEndpointError::bad_request(safe(
-                    "Unmatched url and body ids.".to_string(),
-                )))
-            } else if version.0 != definition.version {
-                Err(ApiEndpointError::bad_request(safe(
-                    "Unmatched url and body versions.".to_string(),
-                )))
-            } else {
-                let compiled_definition = self
-                    .definition_service
-                    .update(
-                        &definition,
-                        &DefaultNamespace::default(),
-                        &EmptyAuthCtx::default(),
-                    )
-                    .instrument(record.span.clone())
-                    .await?;
+            let mut payload = payload.0;
+            self.link_rib_libraries(&mut payload)
+                .instrument(record.span.clone())
+                .await?;
 
-                let result =
-                    HttpApiDefinitionResponseData::try_from(compiled_definition).map_err(|e| {
-                        error!("Failed to convert to response data {}", e);
-                        ApiEndpointError::internal(safe(e))
-                    });
+            // The scripts of the definition are compiled both when it is converted and updated
+            let (result, taint_findings) = with_taint_findings(async {
+                let definition: CoreHttpApiDefinitionRequest = payload
+                    .try_into()
+                    .map_err(|err| ApiEndpointError::bad_request(safe(err)))?;
+
+                if id.0 != definition.id {
+                    Err(ApiEndpointError::bad_request(safe(
+                        "Unmatched url and body ids.".to_string(),
+                    )))
+                } else if version.0 != definition.version {
+                    Err(ApiEndpointError::bad_request(safe(
+                        "Unmatched url and body versions.".to_string(),
+                    )))
+                } else {
+                    let compiled_definition = match if_match.0.as_deref().map(IfMatch::parse) {
+                        Some(if_match) => {
+                            let current = self
+                                .definition_service
+                                .get(
+                                    &definition.id,
+                                    &definition.version,
+                                    &DefaultNamespace::default(),
+                                    &EmptyAuthCtx::default(),
+                                )
+                                .instrument(record.span.clone())
+                                .await?;
+
+                            let current_etag = current
+                                .clone()
+                                .map(HttpApiDefinitionResponseData::try_from)
+                                .transpose()
+                                .map_err(|e| ApiEndpointError::internal(safe(e)))?
+                                .and_then(|current| current.etag);
+
+                            match current {
+                                Some(current) if if_match.matches(current_etag.as_deref()) => {
+                                    // Only written if it is still the definition the precondition matched
+                                    self.definition_service
+                                        .update_if_unchanged(
+                                            &definition,
+                                            &current,
+                                            &DefaultNamespace::default(),
+                                            &EmptyAuthCtx::default(),
+                                        )
+                                        .instrument(record.span.clone())
+                                        .await?
+                                }
+                                _ => Err(ApiEndpointError::precondition_failed(safe(
+                                    "API definition was modified concurrently, If-Match precondition failed"
+                                        .to_string(),
+                                )))?,
+                            }
+                        }
+                        None => {
+                            self.definition_service
+                                .update(
+                                    &definition,
+                                    &DefaultNamespace::default(),
+                                    &EmptyAuthCtx::default(),
+                                )
+                                .instrument(record.span.clone())
+                                .await?
+                        }
+                    };
 
-                result.map(Json)
-            }
+                    Ok((definition.taint_policy, compiled_definition))
+                }
+            })
+            .await;
+
+            let (taint_policy, compiled_definition) = result?;
+
+            HttpApiDefinitionResponseData::try_from(compiled_definition)
+                .map(|data| data.with_taint_findings(taint_policy, taint_findings))
+                .map(with_definition_etag)
+                .map_err(|e| {
+                    error!("Failed to convert to response data {}", e);
+                    ApiEndpointError::internal(safe(e))
+                })
         };
 
         record.result(response)
@@ -174,7 +313,7 @@ impl RegisterApiDefinitionApi {
         &self,
         id: Path<ApiDefinitionId>,
         version: Path<ApiVersion>,
-    ) -> Result<Json<HttpApiDefinitionResponseData>, ApiEndpointError> {
+    ) -> Result<Response<Json<HttpApiDefinitionResponseData>>, ApiEndpointError> {
         let record = recorded_http_api_request!(
             "get_definition",
             api_definition_id = id.0.to_string(),
@@ -207,7 +346,66 @@ impl RegisterApiDefinitionApi {
                     ApiEndpointError::internal(safe(e))
                 });
 
-            result.map(Json)
+            result.map(with_definition_etag)
+        };
+
+        record.result(response)
+    }
+
+    /// Compare two versions of an API definition
+    ///
+    /// Lists the routes added, removed and changed from `version` to `target-version`, matching
+    /// routes by method and path. Changed Rib scripts come with a statement level diff.
+    #[oai(
+        path = "/:id/:version/diff/:target_version",
+        method = "get",
+        operation_id = "diff_definition"
+    )]
+    async fn diff(
+        &self,
+        id: Path<ApiDefinitionId>,
+        version: Path<ApiVersion>,
+        target_version: Path<ApiVersion>,
+    ) -> Result<Json<ApiDefinitionDiff>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "diff_definition",
+            api_definition_id = id.0.to_string(),
+            version = version.0.to_string(),
+            target_version = target_version.0.to_string()
+        );
+
+        let response = {
+            let mut definitions = vec![];
+
+            for version in [&version.0, &target_version.0] {
+                let definition = self
+                    .definition_service
+                    .get(
+                        &id.0,
+                        version,
+                        &DefaultNamespace::default(),
+                        &EmptyAuthCtx::default(),
+                    )
+                    .instrument(record.span.clone())
+                    .await?
+                    .ok_or(ApiEndpointError::not_found(safe(format!(
+                        "Can't find api definition with id {}, and version {version}",
+                        id.0
+                    ))))?;
+
+                let definition =
+                    HttpApiDefinitionResponseData::try_from(definition).map_err(|e| {
+                        error!("Failed to convert to response data {}", e);
+                        ApiEndpointError::internal(safe(e))
+                    })?;
+
+                definitions.push(definition);
+            }
+
+            Ok(Json(ApiDefinitionDiff::between(
+                &definitions[0],
+                &definitions[1],
+            )))
         };
 
         record.result(response)
@@ -215,7 +413,9 @@ impl RegisterApiDefinitionApi {
 
     /// Delete an API definition
     ///
-    /// Deletes an API definition by its API definition ID and version.
+    /// Moves an API definition to the trash by its API definition ID and version. Deleted API
+    /// definitions can be restored until they are permanently purged after the configured
+    /// retention period.
     #[oai(
         path = "/:id/:version",
         method = "delete",
@@ -241,6 +441,7 @@ impl RegisterApiDefinitionApi {
                     &api_definition_id,
                     &api_definition_version,
                     &DefaultNamespace::default(),
+                    &DefaultNamespace::default().to_string(),
                     &EmptyAuthCtx::default(),
                 )
                 .instrument(record.span.clone())
@@ -251,6 +452,63 @@ impl RegisterApiDefinitionApi {
         record.result(response)
     }
 
+    /// Restore a deleted API definition
+    ///
+    /// Restores an API definition from the trash by its API definition ID and version.
+    #[oai(
+        path = "/:id/:version/restore",
+        method = "post",
+        operation_id = "restore_definition"
+    )]
+    async fn restore(
+        &self,
+        id: Path<ApiDefinitionId>,
+        version: Path<ApiVersion>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "restore_definition",
+            api_definition_id = id.0.to_string(),
+            version = version.0.to_string()
+        );
+
+        let response = {
+            self.definition_service
+                .restore(
+                    &id.0,
+                    &version.0,
+                    &DefaultNamespace::default(),
+                    &EmptyAuthCtx::default(),
+                )
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json("API definition restored".to_string()))
+        };
+        record.result(response)
+    }
+
+    /// List deleted API definitions
+    ///
+    /// Lists the API definitions currently in the trash.
+    #[oai(
+        path = "/trash",
+        method = "get",
+        operation_id = "list_deleted_definitions"
+    )]
+    async fn list_deleted(&self) -> Result<Json<Vec<DeletedApiDefinition>>, ApiEndpointError> {
+        let record = recorded_http_api_request!("list_deleted_definitions",);
+
+        let response = self
+            .definition_service
+            .get_deleted(&DefaultNamespace::default(), &EmptyAuthCtx::default())
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|e| e.into());
+
+        record.result(response)
+    }
+
     /// Get or list API definitions
     ///
     /// If `api_definition_id` is specified, returns a single API definition.
@@ -294,6 +552,36 @@ impl RegisterApiDefinitionApi {
 }
 
 impl RegisterApiDefinitionApi {
+    async fn link_rib_libraries(
+        &self,
+        definition: &mut HttpApiDefinitionRequest,
+    ) -> Result<(), ApiEndpointError> {
+        link_rib_libraries(self.rib_library_service.as_ref(), definition).await
+    }
+
+    // Creates the definition returned by `parse`, along with the findings of the taint analysis of
+    // its scripts, which are compiled both when the definition is parsed and created
+    async fn create_parsed(
+        &self,
+        parse: impl FnOnce() -> Result<CoreHttpApiDefinitionRequest, ApiEndpointError> + Send,
+    ) -> Result<HttpApiDefinitionResponseData, ApiEndpointError> {
+        let (result, taint_findings) = with_taint_findings(async {
+            let definition = parse()?;
+            let compiled_definition = self.create_api(&definition).await?;
+            Ok::<_, ApiEndpointError>((definition.taint_policy, compiled_definition))
+        })
+        .await;
+
+        let (taint_policy, compiled_definition) = result?;
+
+        HttpApiDefinitionResponseData::try_from(compiled_definition)
+            .map(|data| data.with_taint_findings(taint_policy, taint_findings))
+            .map_err(|e| {
+                error!("Failed to convert to response data {}", e);
+                ApiEndpointError::internal(safe(e))
+            })
+    }
+
     async fn create_api(
         &self,
         definition: &CoreHttpApiDefinitionRequest,
@@ -318,6 +606,37 @@ impl RegisterApiDefinitionApi {
     }
 }
 
+// The response with the entity tag of the definition in its `ETag` header
+fn with_definition_etag(
+    data: HttpApiDefinitionResponseData,
+) -> Response<Json<HttpApiDefinitionResponseData>> {
+    let etag = data.etag.clone().unwrap_or_default();
+    with_etag(Json(data), &etag)
+}
+
+// Links the Rib libraries imported with `use` statements into the binding scripts
+pub(crate) async fn link_rib_libraries(
+    rib_library_service: &(dyn RibLibraryService<DefaultNamespace> + Sync + Send),
+    definition: &mut HttpApiDefinitionRequest,
+) -> Result<(), ApiEndpointError> {
+    for script in definition.rib_scripts_mut() {
+        let has_imports = rib::split_imports(script)
+            .map(|(imports, _)| !imports.is_empty())
+            .unwrap_or(false);
+
+        if has_imports {
+            let linked = rib_library_service
+                .link(&DefaultNamespace::default(), script)
+                .await?;
+
+            *script = rib::to_string(&linked)
+                .map_err(|e| ApiEndpointError::internal(safe(e.to_string())))?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use golem_service_base::migration::{Migrations, MigrationsDir};
@@ -328,20 +647,22 @@ mod test {
     use async_trait::async_trait;
     use golem_common::config::DbSqliteConfig;
     use golem_common::model::component_constraint::FunctionConstraintCollection;
-    use golem_common::model::ComponentId;
     use golem_service_base::db;
     use golem_service_base::model::Component;
+    use golem_worker_service_base::gateway_rib_compiler::TaintAnalysisPolicy;
     use golem_worker_service_base::gateway_security::DefaultIdentityProvider;
     use golem_worker_service_base::repo::api_definition::{
         ApiDefinitionRepo, DbApiDefinitionRepo, LoggedApiDefinitionRepo,
     };
     use golem_worker_service_base::repo::api_deployment;
+    use golem_worker_service_base::repo::rib_library::{DbRibLibraryRepo, LoggedRibLibraryRepo};
     use golem_worker_service_base::repo::security_scheme::{
         DbSecuritySchemeRepo, LoggedSecuritySchemeRepo, SecuritySchemeRepo,
     };
     use golem_worker_service_base::service::component::ComponentResult;
     use golem_worker_service_base::service::gateway::api_definition::ApiDefinitionServiceDefault;
     use golem_worker_service_base::service::gateway::http_api_definition_validator::HttpApiDefinitionValidator;
+    use golem_worker_service_base::service::gateway::rib_library::DefaultRibLibraryService;
     use golem_worker_service_base::service::gateway::security_scheme::DefaultSecuritySchemeService;
     use http::StatusCode;
     use poem::test::TestClient;
@@ -398,6 +719,10 @@ mod test {
         ) -> ComponentResult<FunctionConstraintCollection> {
             unimplemented!()
         }
+
+        async fn get_all(&self, _auth_ctx: &EmptyAuthCtx) -> ComponentResult<Vec<Component>> {
+            unimplemented!()
+        }
     }
 
     async fn make_route<'c>() -> (poem::Route, SqliteDb<'c>) {
@@ -444,10 +769,21 @@ mod test {
             Arc::new(HttpApiDefinitionValidator {}),
         );
 
-        let endpoint = RegisterApiDefinitionApi::new(Arc::new(definition_service));
+        let rib_library_service = Arc::new(DefaultRibLibraryService::new(Arc::new(
+            LoggedRibLibraryRepo::new(DbRibLibraryRepo::new(db_pool.clone().into())),
+        )));
+
+        let endpoint = RegisterApiDefinitionApi::new(
+            Arc::new(definition_service),
+            rib_library_service.clone(),
+        );
+        let rib_library_endpoint = crate::api::rib_library::RibLibraryApi::new(rib_library_service);
 
         (
-            poem::Route::new().nest("", OpenApiService::new(endpoint, "test", "1.0")),
+            poem::Route::new().nest(
+                "",
+                OpenApiService::new((endpoint, rib_library_endpoint), "test", "1.0"),
+            ),
             db,
         )
     }
@@ -463,6 +799,7 @@ mod test {
             routes: vec![],
             draft: false,
             security: None,
+            taint_policy: TaintAnalysisPolicy::Warn,
         };
 
         let response = client
@@ -493,6 +830,7 @@ mod test {
             routes: vec![],
             draft: false,
             security: None,
+            taint_policy: TaintAnalysisPolicy::Warn,
         };
 
         let response = client
@@ -515,6 +853,7 @@ mod test {
             routes: vec![],
             draft: false,
             security: None,
+            taint_policy: TaintAnalysisPolicy::Warn,
         };
 
         let response = client
@@ -537,6 +876,7 @@ mod test {
             routes: vec![],
             draft: false,
             security: None,
+            taint_policy: TaintAnalysisPolicy::Warn,
         };
 
         let response = client
@@ -551,6 +891,110 @@ mod test {
         response.assert_status(http::StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    async fn update_with_if_match() {
+        let (api, _db) = make_route().await;
+        let client = TestClient::new(api);
+
+        let definition = HttpApiDefinitionRequest {
+            id: ApiDefinitionId("test".to_string()),
+            version: ApiVersion("1.0".to_string()),
+            routes: vec![],
+            draft: true,
+            security: None,
+            taint_policy: TaintAnalysisPolicy::Warn,
+        };
+        let response = client
+            .post("/v1/api/definitions")
+            .body_json(&definition)
+            .send()
+            .await;
+        response.assert_status_is_ok();
+        let etag = response
+            .json()
+            .await
+            .value()
+            .object()
+            .get("etag")
+            .string()
+            .to_string();
+
+        let path = format!(
+            "/v1/api/definitions/{}/{}",
+            definition.id.0, definition.version.0
+        );
+
+        let response = client
+            .put(&path)
+            .header("If-Match", "\"stale\"")
+            .body_json(&definition)
+            .send()
+            .await;
+        response.assert_status(StatusCode::PRECONDITION_FAILED);
+
+        let response = client
+            .put(&path)
+            .header("If-Match", format!("\"{etag}\""))
+            .body_json(&definition)
+            .send()
+            .await;
+        response.assert_status_is_ok();
+        response.assert_header(http::header::ETAG, format!("\"{etag}\""));
+    }
+
+    #[test]
+    async fn create_rib_library() {
+        let (api, _db) = make_route().await;
+        let client = TestClient::new(api);
+
+        let library = golem_worker_service_base::api::RibLibraryCreationData {
+            name: "users".to_string(),
+            version: "1.0".to_string(),
+            source: r#"let prefix = "user"; let worker = "${prefix}-${request.path.user-id}";"#
+                .to_string(),
+        };
+
+        let response = client
+            .post("/v1/api/rib-libraries")
+            .body_json(&library)
+            .send()
+            .await;
+
+        response.assert_status_is_ok();
+
+        let response = client.get("/v1/api/rib-libraries/users/1.0").send().await;
+
+        response.assert_status_is_ok();
+        let library_data = response
+            .json()
+            .await
+            .value()
+            .deserialize::<golem_worker_service_base::api::RibLibraryData>();
+        assert_eq!(library_data.exports, vec!["prefix", "worker"]);
+
+        let response = client
+            .post("/v1/api/rib-libraries")
+            .body_json(&library)
+            .send()
+            .await;
+
+        response.assert_status(StatusCode::CONFLICT);
+
+        let invalid = golem_worker_service_base::api::RibLibraryCreationData {
+            name: "invalid".to_string(),
+            version: "1.0".to_string(),
+            source: r#"let prefix = "user"; prefix"#.to_string(),
+        };
+
+        let response = client
+            .post("/v1/api/rib-libraries")
+            .body_json(&invalid)
+            .send()
+            .await;
+
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     async fn get_all() {
         let (api, _db) = make_route().await;
@@ -562,6 +1006,7 @@ mod test {
             routes: vec![],
             draft: false,
             security: None,
+            taint_policy: TaintAnalysisPolicy::Warn,
         };
         let response = client
             .post("/v1/api/definitions")
@@ -576,6 +1021,7 @@ mod test {
             routes: vec![],
             draft: false,
             security: None,
+            taint_policy: TaintAnalysisPolicy::Warn,
         };
         let response = client
             .post("/v1/api/definitions")
@@ -1,22 +1,47 @@
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::service::watch::ApiDeploymentWatch;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use golem_common::{recorded_http_api_request, safe};
 use golem_service_base::api_tags::ApiTags;
 use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
+use golem_service_base::headers::{with_etag, IfMatch};
+use golem_service_base::service::audit_log::{AuditLogService, AuditOperation, AuditedOperation};
 use golem_worker_service_base::api::ApiEndpointError;
-use golem_worker_service_base::api::{ApiDeployment, ApiDeploymentRequest};
+use golem_worker_service_base::api::{
+    ApiDefinitionInfo, ApiDeployment, ApiDeploymentRequest, ApiDeploymentSwapRequest,
+    ApiDeploymentWatchEvent,
+};
 use golem_worker_service_base::gateway_api_definition::ApiDefinitionId;
 use golem_worker_service_base::gateway_api_deployment;
-use golem_worker_service_base::gateway_api_deployment::ApiSiteString;
+use golem_worker_service_base::gateway_api_deployment::{
+    ApiDeploymentClientCertificates, ApiDeploymentDeadLetterQueue, ApiDeploymentErrorPages,
+    ApiDeploymentRequestFixtures, ApiSiteString,
+};
+use golem_worker_service_base::gateway_execution::deployment_probe::{
+    DeploymentProber, ProbesHealthCheck,
+};
+use golem_worker_service_base::gateway_execution::response_cache::ResponseCacheStore;
 use golem_worker_service_base::service::gateway::api_definition::ApiDefinitionIdWithVersion;
-use golem_worker_service_base::service::gateway::api_deployment::ApiDeploymentService;
-use poem_openapi::param::{Path, Query};
-use poem_openapi::payload::Json;
+use golem_worker_service_base::service::gateway::api_deployment::{
+    ApiDeploymentService, DeploymentHealthCheck,
+};
+use poem_openapi::param::{Header, Path, Query};
+use poem_openapi::payload::{EventStream, Json, Response};
 use poem_openapi::*;
-use tracing::Instrument;
+use tap::TapFallible;
+use tracing::{error, Instrument};
+
+const WATCH_KEEP_ALIVE: Duration = Duration::from_secs(15);
 
 pub struct ApiDeploymentApi {
     deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    response_cache: ResponseCacheStore,
+    deployment_prober: Arc<DeploymentProber<DefaultNamespace>>,
+    audit_log_service: Arc<dyn AuditLogService<DefaultNamespace> + Sync + Send>,
+    deployment_watch: Arc<ApiDeploymentWatch>,
 }
 
 #[OpenApi(prefix_path = "/v1/api/deployments", tag = ApiTags::ApiDeployment)]
@@ -25,21 +50,66 @@ impl ApiDeploymentApi {
         deployment_service: Arc<
             dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send,
         >,
+        response_cache: ResponseCacheStore,
+        deployment_prober: Arc<DeploymentProber<DefaultNamespace>>,
+        audit_log_service: Arc<dyn AuditLogService<DefaultNamespace> + Sync + Send>,
+        deployment_watch: Arc<ApiDeploymentWatch>,
     ) -> Self {
-        Self { deployment_service }
+        Self {
+            deployment_service,
+            response_cache,
+            deployment_prober,
+            audit_log_service,
+            deployment_watch,
+        }
+    }
+
+    async fn audit(&self, operation: AuditedOperation) {
+        self.audit_log_service
+            .record_or_log(
+                &DefaultNamespace::default(),
+                &EmptyAuthCtx::default(),
+                operation,
+            )
+            .await
     }
 
     /// Creates or updates a deployment
     ///
     /// Deploys a set of API definitions to a site (specific host and subdomain).
+    /// If the `If-Match` header is set, the deployment is only updated when it matches
+    /// the `etag` of the current deployment of the site, otherwise it fails with 412 Precondition Failed.
+    /// The `ETag` header of the response is the `etag` of the updated deployment.
     #[oai(path = "/deploy", method = "post", operation_id = "deploy")]
     async fn create_or_update(
         &self,
+        #[oai(name = "If-Match")] if_match: Header<Option<String>>,
         payload: Json<ApiDeploymentRequest>,
-    ) -> Result<Json<ApiDeployment>, ApiEndpointError> {
+    ) -> Result<Response<Json<ApiDeployment>>, ApiEndpointError> {
         let record = recorded_http_api_request!("deploy", site = payload.0.site.to_string());
         let namespace = DefaultNamespace::default();
         let response = {
+            let current_deployment = self
+                .deployment_service
+                .get_by_site(&ApiSiteString::from(&payload.site))
+                .instrument(record.span.clone())
+                .await?;
+            let current = current_deployment.clone().map(ApiDeployment::from);
+
+            // The deployment is only written if it is still the one the precondition matched
+            let mut expected = None;
+            if let Some(if_match) = if_match.0.as_deref().map(IfMatch::parse) {
+                let current_etag = current.as_ref().and_then(|current| current.etag.clone());
+
+                if !if_match.matches(current_etag.as_deref()) {
+                    Err(ApiEndpointError::precondition_failed(safe(
+                        "API deployment was modified concurrently, If-Match precondition failed"
+                            .to_string(),
+                    )))?
+                }
+                expected = current_deployment.as_ref();
+            }
+
             let api_definition_infos = payload
                 .api_definitions
                 .iter()
@@ -53,10 +123,15 @@ impl ApiDeploymentApi {
                 namespace: namespace.clone(),
                 api_definition_keys: api_definition_infos,
                 site: payload.site.clone(),
+                cors: payload.cors.clone(),
+                traffic_split: payload.traffic_split.clone(),
+                experiments: payload.experiments.clone(),
+                capacity: payload.capacity.clone(),
+                compression: payload.compression.clone(),
             };
 
             self.deployment_service
-                .deploy(&api_deployment, &EmptyAuthCtx::default())
+                .deploy_with_precondition(&api_deployment, expected, &EmptyAuthCtx::default())
                 .instrument(record.span.clone())
                 .await?;
 
@@ -66,9 +141,138 @@ impl ApiDeploymentApi {
                 .instrument(record.span.clone())
                 .await?;
 
-            let deployment = data.ok_or(ApiEndpointError::internal(safe(
-                "Failed to verify the deployment".to_string(),
-            )))?;
+            let deployment: ApiDeployment = data
+                .ok_or(ApiEndpointError::internal(safe(
+                    "Failed to verify the deployment".to_string(),
+                )))?
+                .into();
+
+            self.audit(
+                AuditedOperation::new(AuditOperation::ApiDeploymentDeploy, &payload.site)
+                    .before(current.as_ref())
+                    .after(Some(&deployment)),
+            )
+            .instrument(record.span.clone())
+            .await;
+
+            let etag = deployment.etag.clone().unwrap_or_default();
+            Ok(with_etag(Json(deployment), &etag))
+        };
+
+        record.result(response)
+    }
+
+    /// Roll back an API definition
+    ///
+    /// Deploys the given version of an API definition in place of the other versions of the same
+    /// definition, on every site they are deployed to. All the sites are switched in a single
+    /// transaction, so the gateways never serve a mix of the versions. Returns the updated deployments.
+    #[oai(
+        path = "/rollback",
+        method = "post",
+        operation_id = "rollback_deployments"
+    )]
+    async fn rollback(
+        &self,
+        payload: Json<ApiDefinitionInfo>,
+    ) -> Result<Json<Vec<ApiDeployment>>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "rollback_deployments",
+            api_definition_id = payload.0.id.to_string(),
+            version = payload.0.version.to_string()
+        );
+        let response = {
+            let api_definition_key = ApiDefinitionIdWithVersion {
+                id: payload.0.id,
+                version: payload.0.version,
+            };
+
+            let sites = self
+                .deployment_service
+                .rollback(
+                    &DefaultNamespace::default(),
+                    &api_definition_key,
+                    &EmptyAuthCtx::default(),
+                )
+                .instrument(record.span.clone())
+                .await?;
+
+            let mut deployments = vec![];
+            for site in sites {
+                if let Some(deployment) = self
+                    .deployment_service
+                    .get_by_site(&site)
+                    .instrument(record.span.clone())
+                    .await?
+                {
+                    deployments.push(deployment.into());
+                }
+            }
+
+            Ok(Json(deployments))
+        };
+
+        record.result(response)
+    }
+
+    /// Swap the version of an API definition served by a site
+    ///
+    /// Switches a site from the versions of an API definition it serves to the given version,
+    /// in a single transaction, so the gateways never serve a mix of the versions (blue/green
+    /// deployment). The probes are sent to the site as it would be served after the swap, before
+    /// switching it, and the swap is aborted if any of them fails. The probes invoke the workers
+    /// like any other request.
+    #[oai(
+        path = "/:site/swap",
+        method = "post",
+        operation_id = "swap_deployment"
+    )]
+    async fn swap(
+        &self,
+        site: Path<String>,
+        payload: Json<ApiDeploymentSwapRequest>,
+    ) -> Result<Json<ApiDeployment>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "swap_deployment",
+            site = site.0,
+            api_definition_id = payload.0.api_definition.id.to_string(),
+            version = payload.0.api_definition.version.to_string()
+        );
+        let response = {
+            let site = ApiSiteString(site.0);
+            let payload = payload.0;
+
+            let api_definition_key = ApiDefinitionIdWithVersion {
+                id: payload.api_definition.id,
+                version: payload.api_definition.version,
+            };
+
+            let health_check = (!payload.probes.is_empty()).then(|| ProbesHealthCheck {
+                prober: self.deployment_prober.clone(),
+                probes: payload.probes,
+            });
+
+            self.deployment_service
+                .swap(
+                    &DefaultNamespace::default(),
+                    &site,
+                    &api_definition_key,
+                    health_check.as_ref().map(|health_check| {
+                        health_check as &(dyn DeploymentHealthCheck<DefaultNamespace> + Sync + Send)
+                    }),
+                    &EmptyAuthCtx::default(),
+                )
+                .instrument(record.span.clone())
+                .await?;
+
+            let deployment = self
+                .deployment_service
+                .get_by_site(&site)
+                .instrument(record.span.clone())
+                .await?
+                .ok_or(ApiEndpointError::internal(safe(
+                    "Failed to verify the deployment".to_string(),
+                )))?;
 
             Ok(Json(deployment.into()))
         };
@@ -107,11 +311,49 @@ impl ApiDeploymentApi {
         record.result(response)
     }
 
+    /// Watch API deployments
+    ///
+    /// Streams the API deployments, optionally filtered by `api-definition-id`, as server-sent events.
+    /// The current state is sent first as `Added` events, followed by `Added`, `Modified` and `Deleted`
+    /// events whenever the deployments change. Every batch of events ends with a `Bookmark` event
+    /// carrying a cursor; passing it as `cursor` when reconnecting avoids resending unchanged state.
+    #[oai(path = "/watch", method = "get", operation_id = "watch_deployments")]
+    async fn watch(
+        &self,
+        #[oai(name = "api-definition-id")] api_definition_id_query: Query<Option<ApiDefinitionId>>,
+        cursor: Query<Option<String>>,
+    ) -> Result<EventStream<BoxStream<'static, ApiDeploymentWatchEvent>>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "watch_deployments",
+            api_definition_id = api_definition_id_query
+                .0
+                .clone()
+                .unwrap_or(ApiDefinitionId("".to_string()))
+                .to_string(),
+        );
+
+        let events = self
+            .deployment_watch
+            .watch(api_definition_id_query.0, cursor.0)
+            .filter_map(|event| async move {
+                event
+                    .tap_err(|error| error!("Watching API deployments failed: {error}"))
+                    .ok()
+                    .map(ApiDeploymentWatchEvent::from)
+            })
+            .boxed();
+
+        record.result(Ok(EventStream::new(events).keep_alive(WATCH_KEEP_ALIVE)))
+    }
+
     /// Get API deployment by site
     ///
     /// Gets an API deployment by the host name (optionally with a subdomain) it is deployed to.
     #[oai(path = "/:site", method = "get", operation_id = "get_deployment")]
-    async fn get(&self, site: Path<String>) -> Result<Json<ApiDeployment>, ApiEndpointError> {
+    async fn get(
+        &self,
+        site: Path<String>,
+    ) -> Result<Response<Json<ApiDeployment>>, ApiEndpointError> {
         let record = recorded_http_api_request!("get_deployment", site = site.0);
         let response = {
             let site = site.0;
@@ -124,7 +366,9 @@ impl ApiDeploymentApi {
                     "Api deployment not found".to_string(),
                 )))?;
 
-            Ok(Json(value.into()))
+            let deployment: ApiDeployment = value.into();
+            let etag = deployment.etag.clone().unwrap_or_default();
+            Ok(with_etag(Json(deployment), &etag))
         };
 
         record.result(response)
@@ -137,15 +381,421 @@ impl ApiDeploymentApi {
     async fn delete(&self, site: Path<String>) -> Result<Json<String>, ApiEndpointError> {
         let record = recorded_http_api_request!("delete_deployment", site = site.0);
         let response = {
-            let site = site.0;
+            let site = ApiSiteString(site.0);
+
+            let current = self
+                .deployment_service
+                .get_by_site(&site)
+                .await?
+                .map(ApiDeployment::from);
 
             self.deployment_service
-                .delete(&DefaultNamespace::default(), &ApiSiteString(site))
+                .delete(&DefaultNamespace::default(), &site)
                 .await?;
 
+            self.audit(
+                AuditedOperation::new(AuditOperation::ApiDeploymentDelete, &site)
+                    .before(current.as_ref()),
+            )
+            .instrument(record.span.clone())
+            .await;
+
             Ok(Json("API deployment deleted".to_string()))
         };
 
         record.result(response)
     }
+
+    /// Get the error pages of an API deployment
+    ///
+    /// Gets the error pages and the maintenance mode of the API deployment of a site.
+    #[oai(
+        path = "/:site/error-pages",
+        method = "get",
+        operation_id = "get_deployment_error_pages"
+    )]
+    async fn get_error_pages(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<ApiDeploymentErrorPages>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_deployment_error_pages", site = site.0);
+        let response = {
+            let value = self
+                .deployment_service
+                .get_error_pages(&ApiSiteString(site.0))
+                .await?
+                .unwrap_or_default();
+
+            Ok(Json(value))
+        };
+
+        record.result(response)
+    }
+
+    /// Set the error pages of an API deployment
+    ///
+    /// Configures the responses returned instead of the default gateway responses for requests
+    /// not matching any route, for `429` and `5xx` responses, and while the site is in maintenance.
+    /// Every kind of error can have multiple representations, selected based on the `Accept`
+    /// header of the request. Enabling `maintenance` answers every request to the site with
+    /// the maintenance page and a `503` status, without invoking any worker.
+    #[oai(
+        path = "/:site/error-pages",
+        method = "put",
+        operation_id = "set_deployment_error_pages"
+    )]
+    async fn set_error_pages(
+        &self,
+        site: Path<String>,
+        payload: Json<ApiDeploymentErrorPages>,
+    ) -> Result<Json<ApiDeploymentErrorPages>, ApiEndpointError> {
+        let record = recorded_http_api_request!("set_deployment_error_pages", site = site.0);
+        let response = {
+            self.deployment_service
+                .set_error_pages(
+                    &DefaultNamespace::default(),
+                    &ApiSiteString(site.0),
+                    &payload.0,
+                )
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json(payload.0))
+        };
+
+        record.result(response)
+    }
+
+    /// Delete the error pages of an API deployment
+    ///
+    /// Restores the default gateway responses, and disables the maintenance mode.
+    #[oai(
+        path = "/:site/error-pages",
+        method = "delete",
+        operation_id = "delete_deployment_error_pages"
+    )]
+    async fn delete_error_pages(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!("delete_deployment_error_pages", site = site.0);
+        let response = {
+            self.deployment_service
+                .delete_error_pages(&DefaultNamespace::default(), &ApiSiteString(site.0))
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json("API deployment error pages deleted".to_string()))
+        };
+
+        record.result(response)
+    }
+
+    /// Get the client certificate authentication of an API deployment
+    ///
+    /// Gets the certificate authorities the client certificates of the requests to the site
+    /// have to be issued by, and whether a client certificate is required.
+    #[oai(
+        path = "/:site/client-certificates",
+        method = "get",
+        operation_id = "get_deployment_client_certificates"
+    )]
+    async fn get_client_certificates(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<ApiDeploymentClientCertificates>, ApiEndpointError> {
+        let record =
+            recorded_http_api_request!("get_deployment_client_certificates", site = site.0);
+        let response = {
+            let value = self
+                .deployment_service
+                .get_client_certificates(&ApiSiteString(site.0.clone()))
+                .await?
+                .ok_or(ApiEndpointError::not_found(safe(format!(
+                    "Client certificate authentication not found for site {}",
+                    site.0
+                ))))?;
+
+            Ok(Json(value))
+        };
+
+        record.result(response)
+    }
+
+    /// Set the client certificate authentication of an API deployment
+    ///
+    /// Enables mutual TLS for the site. TLS is terminated in front of the gateway, by one of its
+    /// trusted proxies, which has to forward the client certificate as a URL encoded PEM in the
+    /// `X-Client-Cert` header, followed by its intermediate certificate authorities, if any.
+    /// Requests presenting a certificate not issued by one of the certificate authorities of
+    /// `caBundle` are rejected with `403 Forbidden`, as well as requests without a certificate
+    /// if `required` is enabled. The subject, issuer, serial number and fingerprint of the
+    /// certificate are available to bindings as `request.client_certificate`.
+    #[oai(
+        path = "/:site/client-certificates",
+        method = "put",
+        operation_id = "set_deployment_client_certificates"
+    )]
+    async fn set_client_certificates(
+        &self,
+        site: Path<String>,
+        payload: Json<ApiDeploymentClientCertificates>,
+    ) -> Result<Json<ApiDeploymentClientCertificates>, ApiEndpointError> {
+        let record =
+            recorded_http_api_request!("set_deployment_client_certificates", site = site.0);
+        let response = {
+            self.deployment_service
+                .set_client_certificates(
+                    &DefaultNamespace::default(),
+                    &ApiSiteString(site.0),
+                    &payload.0,
+                )
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json(payload.0))
+        };
+
+        record.result(response)
+    }
+
+    /// Delete the client certificate authentication of an API deployment
+    ///
+    /// Disables mutual TLS for the site.
+    #[oai(
+        path = "/:site/client-certificates",
+        method = "delete",
+        operation_id = "delete_deployment_client_certificates"
+    )]
+    async fn delete_client_certificates(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record =
+            recorded_http_api_request!("delete_deployment_client_certificates", site = site.0);
+        let response = {
+            self.deployment_service
+                .delete_client_certificates(&DefaultNamespace::default(), &ApiSiteString(site.0))
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json(
+                "API deployment client certificates deleted".to_string(),
+            ))
+        };
+
+        record.result(response)
+    }
+
+    /// Get the dead-letter queue of an API deployment
+    ///
+    /// Gets the configuration of the dead-letter queue keeping the requests of the site whose
+    /// worker invocation failed.
+    #[oai(
+        path = "/:site/dead-letter-queue",
+        method = "get",
+        operation_id = "get_deployment_dead_letter_queue"
+    )]
+    async fn get_dead_letter_queue(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<ApiDeploymentDeadLetterQueue>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_deployment_dead_letter_queue", site = site.0);
+        let response = {
+            let value = self
+                .deployment_service
+                .get_dead_letter_queue(&ApiSiteString(site.0.clone()))
+                .await?
+                .ok_or(ApiEndpointError::not_found(safe(format!(
+                    "Dead-letter queue not found for site {}",
+                    site.0
+                ))))?;
+
+            Ok(Json(value))
+        };
+
+        record.result(response)
+    }
+
+    /// Set the dead-letter queue of an API deployment
+    ///
+    /// Once enabled, the requests of the site whose worker invocation fails are kept along with
+    /// the error, except for the `redactedHeaders`. They can be listed, inspected and replayed
+    /// against the current definition through the dead-letters API of the site.
+    #[oai(
+        path = "/:site/dead-letter-queue",
+        method = "put",
+        operation_id = "set_deployment_dead_letter_queue"
+    )]
+    async fn set_dead_letter_queue(
+        &self,
+        site: Path<String>,
+        payload: Json<ApiDeploymentDeadLetterQueue>,
+    ) -> Result<Json<ApiDeploymentDeadLetterQueue>, ApiEndpointError> {
+        let record = recorded_http_api_request!("set_deployment_dead_letter_queue", site = site.0);
+        let response = {
+            self.deployment_service
+                .set_dead_letter_queue(
+                    &DefaultNamespace::default(),
+                    &ApiSiteString(site.0),
+                    &payload.0,
+                )
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json(payload.0))
+        };
+
+        record.result(response)
+    }
+
+    /// Delete the dead-letter queue of an API deployment
+    ///
+    /// Failed requests of the site are no longer kept. Entries already in the queue are not
+    /// deleted.
+    #[oai(
+        path = "/:site/dead-letter-queue",
+        method = "delete",
+        operation_id = "delete_deployment_dead_letter_queue"
+    )]
+    async fn delete_dead_letter_queue(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record =
+            recorded_http_api_request!("delete_deployment_dead_letter_queue", site = site.0);
+        let response = {
+            self.deployment_service
+                .delete_dead_letter_queue(&DefaultNamespace::default(), &ApiSiteString(site.0))
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json("API deployment dead-letter queue deleted".to_string()))
+        };
+
+        record.result(response)
+    }
+
+    /// Get the request fixture recording of an API deployment
+    ///
+    /// Gets the configuration of the sampling of the requests of the site recorded as fixtures.
+    #[oai(
+        path = "/:site/request-fixtures",
+        method = "get",
+        operation_id = "get_deployment_request_fixtures"
+    )]
+    async fn get_request_fixtures(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<ApiDeploymentRequestFixtures>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_deployment_request_fixtures", site = site.0);
+        let response = {
+            let value = self
+                .deployment_service
+                .get_request_fixtures(&ApiSiteString(site.0.clone()))
+                .await?
+                .ok_or(ApiEndpointError::not_found(safe(format!(
+                    "Request fixture recording not found for site {}",
+                    site.0
+                ))))?;
+
+            Ok(Json(value))
+        };
+
+        record.result(response)
+    }
+
+    /// Set the request fixture recording of an API deployment
+    ///
+    /// Once enabled, a `sampleRate` share of the requests of the worker bindings of the site is
+    /// recorded along with the status and the schema of the response, except for the
+    /// `redactedHeaders`, up to `maxFixtures`. The fixtures can be replayed against a staging
+    /// deployment through the fixtures API of the site before promoting its definitions.
+    #[oai(
+        path = "/:site/request-fixtures",
+        method = "put",
+        operation_id = "set_deployment_request_fixtures"
+    )]
+    async fn set_request_fixtures(
+        &self,
+        site: Path<String>,
+        payload: Json<ApiDeploymentRequestFixtures>,
+    ) -> Result<Json<ApiDeploymentRequestFixtures>, ApiEndpointError> {
+        let record = recorded_http_api_request!("set_deployment_request_fixtures", site = site.0);
+        let response = {
+            self.deployment_service
+                .set_request_fixtures(
+                    &DefaultNamespace::default(),
+                    &ApiSiteString(site.0),
+                    &payload.0,
+                )
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json(payload.0))
+        };
+
+        record.result(response)
+    }
+
+    /// Delete the request fixture recording of an API deployment
+    ///
+    /// Requests of the site are no longer recorded. Fixtures already recorded are not deleted.
+    #[oai(
+        path = "/:site/request-fixtures",
+        method = "delete",
+        operation_id = "delete_deployment_request_fixtures"
+    )]
+    async fn delete_request_fixtures(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record =
+            recorded_http_api_request!("delete_deployment_request_fixtures", site = site.0);
+        let response = {
+            self.deployment_service
+                .delete_request_fixtures(&DefaultNamespace::default(), &ApiSiteString(site.0))
+                .instrument(record.span.clone())
+                .await?;
+
+            Ok(Json(
+                "API deployment request fixture recording deleted".to_string(),
+            ))
+        };
+
+        record.result(response)
+    }
+
+    /// Invalidate the cached responses of an API deployment
+    ///
+    /// Drops the responses cached by the gateway for the routes of the site, or only for the
+    /// routes with the given `path` pattern (such as `/users/{id}`), so that the next requests
+    /// are served by the workers again.
+    #[oai(
+        path = "/:site/cache",
+        method = "delete",
+        operation_id = "invalidate_deployment_cache"
+    )]
+    async fn invalidate_cache(
+        &self,
+        site: Path<String>,
+        path: Query<Option<String>>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!("invalidate_deployment_cache", site = site.0);
+        let response = {
+            let invalidated_routes = self
+                .response_cache
+                .invalidate(&site.0, path.0.as_deref())
+                .instrument(record.span.clone())
+                .await
+                .map_err(ApiEndpointError::internal)?;
+
+            Ok(Json(format!(
+                "Cached responses of {} routes invalidated",
+                invalidated_routes
+            )))
+        };
+
+        record.result(response)
+    }
 }
@@ -0,0 +1,250 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::service::worker::WorkerService;
+use golem_common::model::{ComponentId, IdempotencyKey, TargetWorkerId};
+use golem_common::recorded_http_api_request;
+use golem_common::SafeDisplay;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::model::{validate_worker_name, ErrorBody, ErrorsBody, InvokeParameters};
+use golem_worker_service_base::api::WorkerApiBaseError;
+use golem_worker_service_base::empty_worker_metadata;
+use golem_worker_service_base::http_invocation_context::grpc_invocation_context_from_request;
+use golem_worker_service_base::service::worker::{
+    AsyncInvocation, AsyncInvocations, AsyncInvokeResponse, InvocationParameters,
+};
+use poem::Request;
+use poem_openapi::param::{Header, Path, Query};
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub struct AsyncInvocationApi {
+    pub worker_service: WorkerService,
+    pub async_invocations: AsyncInvocations,
+}
+
+type Result<T> = std::result::Result<T, WorkerApiBaseError>;
+
+#[OpenApi(prefix_path = "/v1", tag = ApiTags::Worker)]
+impl AsyncInvocationApi {
+    /// Invoke a function in the background
+    ///
+    /// Starts the invocation and returns its id immediately, without awaiting its result.
+    /// The result can be polled at `/v1/invocations/{invocationId}` until it expires.
+    /// If a `callback-url` is given, the invocation is also posted to it as JSON once completed.
+    /// Callback URLs must be of allowed hosts resolving to public addresses, and the posted
+    /// invocations are signed in the `x-golem-signature` header if a signing secret is configured.
+    #[oai(
+        path = "/components/:component_id/workers/:worker_name/invoke-async",
+        method = "post",
+        operation_id = "invoke_function_async"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    async fn invoke_function_async(
+        &self,
+        request: &Request,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        #[oai(name = "Idempotency-Key")] idempotency_key: Header<Option<IdempotencyKey>>,
+        function: Query<String>,
+        #[oai(name = "callback-url")] callback_url: Query<Option<String>>,
+        params: Json<InvokeParameters>,
+    ) -> Result<Json<AsyncInvokeResponse>> {
+        self.invoke_async(
+            request,
+            component_id.0,
+            Some(worker_name.0),
+            idempotency_key.0,
+            function.0,
+            callback_url.0,
+            params.0,
+        )
+        .await
+    }
+
+    /// Invoke a function in the background on a new worker with a random generated name
+    ///
+    /// Same as `invoke-async`, for ephemeral components.
+    #[oai(
+        path = "/components/:component_id/invoke-async",
+        method = "post",
+        operation_id = "invoke_function_without_name_async"
+    )]
+    async fn invoke_function_without_name_async(
+        &self,
+        request: &Request,
+        component_id: Path<ComponentId>,
+        #[oai(name = "Idempotency-Key")] idempotency_key: Header<Option<IdempotencyKey>>,
+        function: Query<String>,
+        #[oai(name = "callback-url")] callback_url: Query<Option<String>>,
+        params: Json<InvokeParameters>,
+    ) -> Result<Json<AsyncInvokeResponse>> {
+        self.invoke_async(
+            request,
+            component_id.0,
+            None,
+            idempotency_key.0,
+            function.0,
+            callback_url.0,
+            params.0,
+        )
+        .await
+    }
+
+    /// Get an async invocation
+    ///
+    /// Returns the status of an invocation started in the background, with its result or error
+    /// once completed. Invocations are kept for a configured time after they are started.
+    #[oai(
+        path = "/invocations/:invocation_id",
+        method = "get",
+        operation_id = "get_async_invocation"
+    )]
+    async fn get_async_invocation(
+        &self,
+        invocation_id: Path<Uuid>,
+    ) -> Result<Json<AsyncInvocation>> {
+        let record = recorded_http_api_request!(
+            "get_async_invocation",
+            invocation_id = invocation_id.0.to_string()
+        );
+
+        let response = self
+            .async_invocations
+            .get(&invocation_id.0)
+            .instrument(record.span.clone())
+            .await
+            .map_err(|error| WorkerApiBaseError::from(error.to_safe_string()))
+            .and_then(|invocation| {
+                invocation.map(Json).ok_or_else(|| {
+                    WorkerApiBaseError::NotFound(Json(ErrorBody {
+                        error: format!("Invocation {} not found", invocation_id.0),
+                    }))
+                })
+            });
+
+        record.result(response)
+    }
+}
+
+impl AsyncInvocationApi {
+    #[allow(clippy::too_many_arguments)]
+    async fn invoke_async(
+        &self,
+        request: &Request,
+        component_id: ComponentId,
+        worker_name: Option<String>,
+        idempotency_key: Option<IdempotencyKey>,
+        function: String,
+        callback_url: Option<String>,
+        params: InvokeParameters,
+    ) -> Result<Json<AsyncInvokeResponse>> {
+        let record = recorded_http_api_request!(
+            "invoke_function_async",
+            component_id = component_id.to_string(),
+            worker_name = worker_name.clone(),
+            idempotency_key = idempotency_key.as_ref().map(|v| v.value.clone()),
+            function = function
+        );
+
+        let response = async {
+            if let Some(worker_name) = &worker_name {
+                validate_worker_name(worker_name)
+                    .map_err(|error| bad_request(format!("Invalid worker name: {error}")))?;
+            }
+
+            let callback_url = match &callback_url {
+                Some(callback_url) => Some(
+                    self.async_invocations
+                        .validate_callback_url(callback_url)
+                        .await
+                        .map_err(bad_request)?,
+                ),
+                None => None,
+            };
+
+            let params =
+                InvocationParameters::from_optionally_type_annotated_value_jsons(params.params)
+                    .map_err(|errors| {
+                        WorkerApiBaseError::BadRequest(Json(ErrorsBody { errors }))
+                    })?;
+
+            let invocation_context = grpc_invocation_context_from_request(request);
+
+            // The idempotency key is fixed before starting, so that the invocation is not
+            // repeated by the retries of the executor
+            let idempotency_key = idempotency_key.unwrap_or_else(IdempotencyKey::fresh);
+
+            let invocation = AsyncInvocation::pending(
+                component_id.clone(),
+                worker_name.clone(),
+                function.clone(),
+            );
+
+            let worker_id = TargetWorkerId {
+                component_id,
+                worker_name,
+            };
+            let worker_service = self.worker_service.clone();
+
+            let invoke = async move {
+                match params {
+                    InvocationParameters::TypedProtoVals(vals) => {
+                        worker_service
+                            .validate_and_invoke_and_await_typed(
+                                &worker_id,
+                                Some(idempotency_key),
+                                function,
+                                vals,
+                                Some(invocation_context),
+                                empty_worker_metadata(),
+                            )
+                            .await
+                    }
+                    InvocationParameters::RawJsonStrings(jsons) => {
+                        worker_service
+                            .invoke_and_await_json(
+                                &worker_id,
+                                Some(idempotency_key),
+                                function,
+                                jsons,
+                                Some(invocation_context),
+                                empty_worker_metadata(),
+                            )
+                            .await
+                    }
+                }
+                .map_err(|error| error.to_safe_string())
+            };
+
+            self.async_invocations
+                .start(invocation, callback_url, invoke)
+                .await
+                .map(|invocation_id| Json(AsyncInvokeResponse { invocation_id }))
+                .map_err(|error| WorkerApiBaseError::from(error.to_safe_string()))
+        }
+        .instrument(record.span.clone())
+        .await;
+
+        record.result(response)
+    }
+}
+
+fn bad_request(error: String) -> WorkerApiBaseError {
+    WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+        errors: vec![error],
+    }))
+}
@@ -0,0 +1,85 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{DateTime, Utc};
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::DefaultNamespace;
+use golem_service_base::service::audit_log::{
+    AuditLogEntry, AuditLogFilter, AuditLogService, AuditOperation,
+};
+use golem_worker_service_base::api::ApiEndpointError;
+use poem_openapi::param::Query;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+const DEFAULT_AUDIT_LOG_LIMIT: u32 = 100;
+
+pub struct AuditLogApi {
+    audit_log_service: Arc<dyn AuditLogService<DefaultNamespace> + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/api/audit-log", tag = ApiTags::AuditLog)]
+impl AuditLogApi {
+    pub fn new(
+        audit_log_service: Arc<dyn AuditLogService<DefaultNamespace> + Sync + Send>,
+    ) -> Self {
+        Self { audit_log_service }
+    }
+
+    /// Get the audit log of API deployments and workers
+    ///
+    /// Lists the recorded API deployments, deployment deletions and worker deletions, the newest
+    /// first, with the digests of the deployments before and after each of them. The entries can
+    /// be filtered by their actor, operation, resource and time range.
+    #[oai(path = "/", method = "get", operation_id = "get_audit_log")]
+    async fn get_audit_log(
+        &self,
+        actor: Query<Option<String>>,
+        operation: Query<Option<AuditOperation>>,
+        /// The site of an API deployment, or the id of a worker
+        #[oai(name = "resource-id")]
+        resource_id: Query<Option<String>>,
+        since: Query<Option<DateTime<Utc>>>,
+        until: Query<Option<DateTime<Utc>>>,
+        /// The maximum number of entries to return, 100 by default
+        limit: Query<Option<u32>>,
+    ) -> Result<Json<Vec<AuditLogEntry>>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_audit_log",);
+
+        let filter = AuditLogFilter {
+            actor: actor.0,
+            operation: operation.0,
+            resource_id: resource_id.0,
+            since: since.0,
+            until: until.0,
+        };
+
+        let response = self
+            .audit_log_service
+            .get_all(
+                &DefaultNamespace::default(),
+                filter,
+                limit.0.unwrap_or(DEFAULT_AUDIT_LOG_LIMIT),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+}
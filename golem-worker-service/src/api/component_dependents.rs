@@ -0,0 +1,157 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::service::component::ComponentService;
+use crate::service::worker::WorkerService;
+use golem_common::model::{
+    ComponentId, ComponentVersion, FilterComparator, ScanCursor, WorkerFilter, WorkerStatus,
+};
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
+use golem_service_base::model::VersionedComponentId;
+use golem_worker_service_base::api::ApiEndpointError;
+use golem_worker_service_base::empty_worker_metadata;
+use golem_worker_service_base::gateway_api_definition::http::HttpApiDefinition;
+use golem_worker_service_base::service::component::dependents::{
+    api_definition_dependents, rpc_callers, ComponentDependents,
+};
+use golem_worker_service_base::service::component::ComponentServiceError;
+use golem_worker_service_base::service::gateway::api_definition::ApiDefinitionService;
+use golem_worker_service_base::service::gateway::api_deployment::ApiDeploymentService;
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+/// Maximum number of active workers listed in a dependents response
+const WORKER_LIMIT: u64 = 100;
+
+pub struct ComponentDependentsApi {
+    pub component_service: ComponentService,
+    pub worker_service: WorkerService,
+    pub definition_service:
+        Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    pub deployment_service:
+        Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/components", tag = ApiTags::Component)]
+impl ComponentDependentsApi {
+    /// Get the dependents of a component version
+    ///
+    /// Returns the API definitions with routes bound to the component version (and the sites they
+    /// are deployed to), the active workers running it, and the component versions calling it
+    /// through worker-to-worker RPC. Use it to assess the impact of deleting or deprecating a
+    /// component version.
+    #[oai(
+        path = "/:component_id/versions/:version/dependents",
+        method = "get",
+        operation_id = "get_component_dependents"
+    )]
+    async fn get_component_dependents(
+        &self,
+        component_id: Path<ComponentId>,
+        version: Path<ComponentVersion>,
+    ) -> Result<Json<ComponentDependents>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "get_component_dependents",
+            component_id = component_id.0.to_string(),
+            version = version.0.to_string()
+        );
+
+        let response = self
+            .get_component_dependents_internal(component_id.0, version.0)
+            .instrument(record.span.clone())
+            .await
+            .map(Json);
+
+        record.result(response)
+    }
+
+    async fn get_component_dependents_internal(
+        &self,
+        component_id: ComponentId,
+        version: ComponentVersion,
+    ) -> Result<ComponentDependents, ApiEndpointError> {
+        let namespace = DefaultNamespace::default();
+        let auth_ctx = EmptyAuthCtx::default();
+
+        let target = self
+            .component_service
+            .get_by_version(&component_id, version, &auth_ctx)
+            .await
+            .map_err(component_error)?;
+
+        let definitions = self
+            .definition_service
+            .get_all(&namespace, &auth_ctx)
+            .await?
+            .into_iter()
+            .map(HttpApiDefinition::from)
+            .collect::<Vec<_>>();
+
+        let deployments = self.deployment_service.get_by_id(&namespace, None).await?;
+
+        let components = self
+            .component_service
+            .get_all(&auth_ctx)
+            .await
+            .map_err(component_error)?;
+
+        let filter = WorkerFilter::new_and(vec![
+            WorkerFilter::new_version(FilterComparator::Equal, version),
+            WorkerFilter::new_status(FilterComparator::NotEqual, WorkerStatus::Exited),
+            WorkerFilter::new_status(FilterComparator::NotEqual, WorkerStatus::Failed),
+        ]);
+
+        let (cursor, workers) = self
+            .worker_service
+            .find_metadata(
+                &component_id,
+                Some(filter),
+                ScanCursor::default(),
+                WORKER_LIMIT,
+                false,
+                empty_worker_metadata(),
+            )
+            .await
+            .map_err(ApiEndpointError::internal)?;
+
+        let versioned_component_id = VersionedComponentId {
+            component_id,
+            version,
+        };
+
+        Ok(ComponentDependents {
+            api_definitions: api_definition_dependents(
+                &versioned_component_id,
+                &definitions,
+                &deployments,
+            ),
+            workers: workers.into_iter().map(|w| w.worker_id).collect(),
+            workers_truncated: cursor.is_some(),
+            rpc_callers: rpc_callers(&target, &components),
+            versioned_component_id,
+        })
+    }
+}
+
+fn component_error(error: ComponentServiceError) -> ApiEndpointError {
+    match error {
+        ComponentServiceError::NotFound(_) => ApiEndpointError::not_found(error),
+        _ => ApiEndpointError::internal(error),
+    }
+}
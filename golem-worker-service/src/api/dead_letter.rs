@@ -0,0 +1,171 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::DefaultNamespace;
+use golem_worker_service_base::api::ApiEndpointError;
+use golem_worker_service_base::gateway_api_deployment::ApiSiteString;
+use golem_worker_service_base::gateway_execution::gateway_http_input_executor::GatewayHttpInputExecutor;
+use golem_worker_service_base::service::gateway::dead_letter::{
+    DeadLetter, DeadLetterReplayResult, DeadLetterService,
+};
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+pub struct DeadLetterApi {
+    dead_letter_service: Arc<dyn DeadLetterService<DefaultNamespace> + Sync + Send>,
+    // Serves the replays with the current definitions of the sites
+    executor: Arc<dyn GatewayHttpInputExecutor + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/api/deployments", tag = ApiTags::ApiDeployment)]
+impl DeadLetterApi {
+    pub fn new(
+        dead_letter_service: Arc<dyn DeadLetterService<DefaultNamespace> + Sync + Send>,
+        executor: Arc<dyn GatewayHttpInputExecutor + Sync + Send>,
+    ) -> Self {
+        Self {
+            dead_letter_service,
+            executor,
+        }
+    }
+
+    /// Get the dead letters of an API deployment
+    ///
+    /// Lists the requests of the site kept in its dead-letter queue after their worker
+    /// invocation failed, the oldest first.
+    #[oai(
+        path = "/:site/dead-letters",
+        method = "get",
+        operation_id = "get_dead_letters"
+    )]
+    async fn get_dead_letters(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<Vec<DeadLetter>>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_dead_letters", site = site.0);
+        let response = self
+            .dead_letter_service
+            .get_all(&DefaultNamespace::default(), &ApiSiteString(site.0))
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Get a dead letter of an API deployment
+    #[oai(
+        path = "/:site/dead-letters/:dead_letter_id",
+        method = "get",
+        operation_id = "get_dead_letter"
+    )]
+    async fn get_dead_letter(
+        &self,
+        site: Path<String>,
+        dead_letter_id: Path<String>,
+    ) -> Result<Json<DeadLetter>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "get_dead_letter",
+            site = site.0,
+            dead_letter_id = dead_letter_id.0
+        );
+        let response = self
+            .dead_letter_service
+            .get(
+                &DefaultNamespace::default(),
+                &ApiSiteString(site.0),
+                &dead_letter_id.0,
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Delete a dead letter of an API deployment
+    #[oai(
+        path = "/:site/dead-letters/:dead_letter_id",
+        method = "delete",
+        operation_id = "delete_dead_letter"
+    )]
+    async fn delete_dead_letter(
+        &self,
+        site: Path<String>,
+        dead_letter_id: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "delete_dead_letter",
+            site = site.0,
+            dead_letter_id = dead_letter_id.0
+        );
+        let response = self
+            .dead_letter_service
+            .delete(
+                &DefaultNamespace::default(),
+                &ApiSiteString(site.0),
+                &dead_letter_id.0,
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(|_| Json("Dead letter deleted".to_string()))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Replay a dead letter of an API deployment
+    ///
+    /// Sends the request of the dead letter to the site again, where it is served by the
+    /// current definition of its route. The dead letter is deleted if the replay succeeds,
+    /// otherwise it is kept with the status of the replay. Dead letters whose body was not
+    /// kept cannot be replayed.
+    #[oai(
+        path = "/:site/dead-letters/:dead_letter_id/replay",
+        method = "post",
+        operation_id = "replay_dead_letter"
+    )]
+    async fn replay_dead_letter(
+        &self,
+        site: Path<String>,
+        dead_letter_id: Path<String>,
+    ) -> Result<Json<DeadLetterReplayResult>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "replay_dead_letter",
+            site = site.0,
+            dead_letter_id = dead_letter_id.0
+        );
+        let response = self
+            .dead_letter_service
+            .replay(
+                &DefaultNamespace::default(),
+                &ApiSiteString(site.0),
+                &dead_letter_id.0,
+                self.executor.as_ref(),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+}
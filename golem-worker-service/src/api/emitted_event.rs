@@ -0,0 +1,78 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::model::ComponentId;
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::DefaultNamespace;
+use golem_worker_service_base::api::ApiEndpointError;
+use golem_worker_service_base::service::gateway::emitted_event::{
+    EmittedEvent, EmittedEventService,
+};
+use poem_openapi::param::Query;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+const DEFAULT_EVENT_LIMIT: u32 = 100;
+
+pub struct EmittedEventApi {
+    emitted_event_service: Arc<dyn EmittedEventService<DefaultNamespace> + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/api/events", tag = ApiTags::ApiDeployment)]
+impl EmittedEventApi {
+    pub fn new(
+        emitted_event_service: Arc<dyn EmittedEventService<DefaultNamespace> + Sync + Send>,
+    ) -> Self {
+        Self {
+            emitted_event_service,
+        }
+    }
+
+    /// Get the events emitted by API bindings
+    ///
+    /// Lists the events published with `emit` by the Rib scripts of the API bindings, the
+    /// newest first. Each event refers to the request that emitted it by its method, uri and
+    /// trace id. The events can be filtered by their name and by the component of the binding.
+    #[oai(path = "/", method = "get", operation_id = "get_emitted_events")]
+    async fn get_emitted_events(
+        &self,
+        #[oai(name = "event-name")] event_name: Query<Option<String>>,
+        #[oai(name = "component-id")] component_id: Query<Option<ComponentId>>,
+        /// The maximum number of events to return, 100 by default
+        limit: Query<Option<u32>>,
+    ) -> Result<Json<Vec<EmittedEvent>>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "get_emitted_events",
+            event_name = event_name.0.clone(),
+            component_id = component_id.0.as_ref().map(|id| id.to_string())
+        );
+        let response = self
+            .emitted_event_service
+            .get_all(
+                &DefaultNamespace::default(),
+                event_name.0,
+                component_id.0,
+                limit.0.unwrap_or(DEFAULT_EVENT_LIMIT),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_worker_service_base::api::ApiEndpointError;
+use golem_worker_service_base::gateway_execution::experiment_assignment::{
+    ExperimentAssignmentCounts, ExperimentAssignmentStore,
+};
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+
+pub struct ExperimentsApi {
+    pub experiment_assignment_store: Arc<dyn ExperimentAssignmentStore + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/api/experiments", tag = ApiTags::ApiDeployment)]
+impl ExperimentsApi {
+    /// Get the assignment counts of the experiments of API deployments
+    ///
+    /// For every experiment of a deployment, returns the number of requests assigned to each of
+    /// its variants. Counts are kept in memory by each worker service instance, since it started
+    /// or since they were last cleared.
+    #[oai(
+        path = "/assignments",
+        method = "get",
+        operation_id = "get_experiment_assignments"
+    )]
+    async fn get_experiment_assignments(
+        &self,
+    ) -> Result<Json<Vec<ExperimentAssignmentCounts>>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_experiment_assignments",);
+
+        let response = Ok(Json(self.experiment_assignment_store.get_counts()));
+
+        record.result(response)
+    }
+
+    /// Clear the assignment counts of the experiments of API deployments
+    #[oai(
+        path = "/assignments",
+        method = "delete",
+        operation_id = "clear_experiment_assignments"
+    )]
+    async fn clear_experiment_assignments(&self) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!("clear_experiment_assignments",);
+
+        self.experiment_assignment_store.clear();
+
+        record.result(Ok(Json("Experiment assignments cleared".to_string())))
+    }
+}
@@ -0,0 +1,840 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// A read-only GraphQL view over the control-plane resources (components, workers,
+// API definitions and deployments). Dashboards can resolve nested shapes such as
+// component -> workers -> recent errors in a single round trip instead of
+// issuing one REST call per level.
+
+use crate::service::Services;
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+use async_trait::async_trait;
+use golem_common::model::public_oplog::{
+    OplogCursor, PublicOplogEntry, PublicOplogEntryFilter, PublicOplogEntryKind,
+};
+use golem_common::model::{ComponentId, ScanCursor, WorkerFilter, WorkerId};
+use golem_common::SafeDisplay;
+use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
+use golem_service_base::model::{Component, GetOplogResponse, WorkerMetadata};
+use golem_worker_service_base::api::HttpApiDefinitionResponseData;
+use golem_worker_service_base::empty_worker_metadata;
+use golem_worker_service_base::gateway_api_definition::http::CompiledHttpApiDefinition;
+use golem_worker_service_base::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+use golem_worker_service_base::gateway_api_deployment::ApiDeployment;
+use golem_worker_service_base::service::component::ComponentServiceError;
+use poem::web::{Data, Html, Json};
+use poem::{handler, IntoResponse};
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub type ManagementSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+const DEFAULT_WORKER_PAGE_SIZE: u64 = 50;
+const DEFAULT_RECENT_ERROR_COUNT: u64 = 10;
+// The executor returns at most this many entries of an oplog search at once
+const OPLOG_SEARCH_PAGE_SIZE: u64 = 100;
+const MAX_QUERY_DEPTH: usize = 10;
+
+pub fn make_schema(services: &Services) -> ManagementSchema {
+    schema(Arc::new(services.clone()))
+}
+
+fn schema(queries: Arc<dyn ManagementQueries + Send + Sync>) -> ManagementSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(queries)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+// The reads the schema is resolved with, with their errors already made safe to display
+#[async_trait]
+trait ManagementQueries {
+    async fn component(
+        &self,
+        component_id: &ComponentId,
+        version: Option<u64>,
+    ) -> Result<Option<Component>, String>;
+
+    async fn worker(&self, worker_id: &WorkerId) -> Result<WorkerMetadata, String>;
+
+    async fn workers(
+        &self,
+        component_id: &ComponentId,
+        filter: Option<WorkerFilter>,
+        cursor: ScanCursor,
+        count: u64,
+        precise: bool,
+    ) -> Result<(Option<ScanCursor>, Vec<WorkerMetadata>), String>;
+
+    async fn search_errors(
+        &self,
+        worker_id: &WorkerId,
+        cursor: Option<OplogCursor>,
+        count: u64,
+    ) -> Result<GetOplogResponse, String>;
+
+    async fn api_definitions(
+        &self,
+        id: Option<ApiDefinitionId>,
+    ) -> Result<Vec<CompiledHttpApiDefinition<DefaultNamespace>>, String>;
+
+    async fn api_definition(
+        &self,
+        id: ApiDefinitionId,
+        version: ApiVersion,
+    ) -> Result<Option<CompiledHttpApiDefinition<DefaultNamespace>>, String>;
+
+    async fn deployments(
+        &self,
+        api_definition_id: Option<ApiDefinitionId>,
+    ) -> Result<Vec<ApiDeployment<DefaultNamespace>>, String>;
+}
+
+#[async_trait]
+impl ManagementQueries for Services {
+    async fn component(
+        &self,
+        component_id: &ComponentId,
+        version: Option<u64>,
+    ) -> Result<Option<Component>, String> {
+        let result = match version {
+            Some(version) => {
+                self.component_service
+                    .get_by_version(component_id, version, &EmptyAuthCtx::default())
+                    .await
+            }
+            None => {
+                self.component_service
+                    .get_latest(component_id, &EmptyAuthCtx::default())
+                    .await
+            }
+        };
+
+        match result {
+            Ok(component) => Ok(Some(component)),
+            Err(ComponentServiceError::NotFound(_)) => Ok(None),
+            Err(error) => Err(error.to_safe_string()),
+        }
+    }
+
+    async fn worker(&self, worker_id: &WorkerId) -> Result<WorkerMetadata, String> {
+        self.worker_service
+            .get_metadata(worker_id, empty_worker_metadata())
+            .await
+            .map_err(|error| error.to_safe_string())
+    }
+
+    async fn workers(
+        &self,
+        component_id: &ComponentId,
+        filter: Option<WorkerFilter>,
+        cursor: ScanCursor,
+        count: u64,
+        precise: bool,
+    ) -> Result<(Option<ScanCursor>, Vec<WorkerMetadata>), String> {
+        self.worker_service
+            .find_metadata(
+                component_id,
+                filter,
+                cursor,
+                count,
+                precise,
+                empty_worker_metadata(),
+            )
+            .await
+            .map_err(|error| error.to_safe_string())
+    }
+
+    async fn search_errors(
+        &self,
+        worker_id: &WorkerId,
+        cursor: Option<OplogCursor>,
+        count: u64,
+    ) -> Result<GetOplogResponse, String> {
+        self.worker_service
+            .search_oplog(
+                worker_id,
+                cursor,
+                count,
+                String::new(),
+                PublicOplogEntryFilter {
+                    kinds: vec![PublicOplogEntryKind::Error],
+                    ..Default::default()
+                },
+                empty_worker_metadata(),
+            )
+            .await
+            .map_err(|error| error.to_safe_string())
+    }
+
+    async fn api_definitions(
+        &self,
+        id: Option<ApiDefinitionId>,
+    ) -> Result<Vec<CompiledHttpApiDefinition<DefaultNamespace>>, String> {
+        let namespace = DefaultNamespace::default();
+        let auth_ctx = EmptyAuthCtx::default();
+
+        match id {
+            Some(id) => {
+                self.definition_service
+                    .get_all_versions(&id, &namespace, &auth_ctx)
+                    .await
+            }
+            None => self.definition_service.get_all(&namespace, &auth_ctx).await,
+        }
+        .map_err(|error| error.to_safe_string())
+    }
+
+    async fn api_definition(
+        &self,
+        id: ApiDefinitionId,
+        version: ApiVersion,
+    ) -> Result<Option<CompiledHttpApiDefinition<DefaultNamespace>>, String> {
+        self.definition_service
+            .get(
+                &id,
+                &version,
+                &DefaultNamespace::default(),
+                &EmptyAuthCtx::default(),
+            )
+            .await
+            .map_err(|error| error.to_safe_string())
+    }
+
+    async fn deployments(
+        &self,
+        api_definition_id: Option<ApiDefinitionId>,
+    ) -> Result<Vec<ApiDeployment<DefaultNamespace>>, String> {
+        self.deployment_service
+            .get_by_id(&DefaultNamespace::default(), api_definition_id)
+            .await
+            .map_err(|error| error.to_safe_string())
+    }
+}
+
+fn queries<'a>(
+    ctx: &Context<'a>,
+) -> async_graphql::Result<&'a Arc<dyn ManagementQueries + Send + Sync>> {
+    ctx.data::<Arc<dyn ManagementQueries + Send + Sync>>()
+}
+
+#[handler]
+pub async fn graphql_handler(
+    schema: Data<&ManagementSchema>,
+    request: Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request.0).await)
+}
+
+#[handler]
+pub async fn graphiql() -> impl IntoResponse {
+    Html(GraphiQLSource::build().endpoint("/v1/graphql").finish())
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Gets a component by id. Returns the latest version unless `version` is given.
+    async fn component(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+        version: Option<u64>,
+    ) -> async_graphql::Result<Option<ComponentNode>> {
+        let component_id = parse_component_id(&component_id)?;
+
+        queries(ctx)?
+            .component(&component_id, version)
+            .await
+            .map(|component| component.map(ComponentNode))
+            .map_err(async_graphql::Error::new)
+    }
+
+    /// Gets a single worker's metadata
+    async fn worker(
+        &self,
+        ctx: &Context<'_>,
+        component_id: String,
+        worker_name: String,
+    ) -> async_graphql::Result<WorkerNode> {
+        let worker_id = WorkerId {
+            component_id: parse_component_id(&component_id)?,
+            worker_name,
+        };
+
+        queries(ctx)?
+            .worker(&worker_id)
+            .await
+            .map(WorkerNode)
+            .map_err(async_graphql::Error::new)
+    }
+
+    /// Lists all API definitions, or all versions of one definition if `id` is given
+    async fn api_definitions(
+        &self,
+        ctx: &Context<'_>,
+        id: Option<String>,
+    ) -> async_graphql::Result<Vec<ApiDefinitionNode>> {
+        queries(ctx)?
+            .api_definitions(id.map(ApiDefinitionId))
+            .await
+            .map_err(async_graphql::Error::new)?
+            .into_iter()
+            .map(ApiDefinitionNode::try_from)
+            .collect()
+    }
+
+    /// Gets a specific version of an API definition
+    async fn api_definition(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        version: String,
+    ) -> async_graphql::Result<Option<ApiDefinitionNode>> {
+        queries(ctx)?
+            .api_definition(ApiDefinitionId(id), ApiVersion(version))
+            .await
+            .map_err(async_graphql::Error::new)?
+            .map(ApiDefinitionNode::try_from)
+            .transpose()
+    }
+
+    /// Lists API deployments, optionally restricted to those deploying the given API definition
+    async fn deployments(
+        &self,
+        ctx: &Context<'_>,
+        api_definition_id: Option<String>,
+    ) -> async_graphql::Result<Vec<DeploymentNode>> {
+        queries(ctx)?
+            .deployments(api_definition_id.map(ApiDefinitionId))
+            .await
+            .map(|deployments| deployments.into_iter().map(DeploymentNode).collect())
+            .map_err(async_graphql::Error::new)
+    }
+}
+
+pub struct ComponentNode(Component);
+
+#[Object]
+impl ComponentNode {
+    async fn component_id(&self) -> String {
+        self.0.versioned_component_id.component_id.to_string()
+    }
+
+    async fn version(&self) -> u64 {
+        self.0.versioned_component_id.version
+    }
+
+    async fn name(&self) -> String {
+        self.0.component_name.to_string()
+    }
+
+    async fn size(&self) -> u64 {
+        self.0.component_size
+    }
+
+    async fn created_at(&self) -> Option<String> {
+        self.0.created_at.map(|created_at| created_at.to_rfc3339())
+    }
+
+    /// Lists the workers of this component. `filter` uses the same syntax as the REST API.
+    async fn workers(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<Vec<String>>,
+        cursor: Option<String>,
+        count: Option<u64>,
+        precise: Option<bool>,
+    ) -> async_graphql::Result<WorkerPage> {
+        let filter = match filter {
+            Some(filters) if !filters.is_empty() => {
+                Some(WorkerFilter::from(filters).map_err(async_graphql::Error::new)?)
+            }
+            _ => None,
+        };
+
+        let cursor = cursor
+            .map(|cursor| ScanCursor::from_str(&cursor))
+            .transpose()
+            .map_err(async_graphql::Error::new)?;
+
+        let (cursor, workers) = queries(ctx)?
+            .workers(
+                &self.0.versioned_component_id.component_id,
+                filter,
+                cursor.unwrap_or_default(),
+                count.unwrap_or(DEFAULT_WORKER_PAGE_SIZE),
+                precise.unwrap_or(false),
+            )
+            .await
+            .map_err(async_graphql::Error::new)?;
+
+        Ok(WorkerPage {
+            cursor: cursor.map(|cursor| cursor.to_string()),
+            workers: workers.into_iter().map(WorkerNode).collect(),
+        })
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct WorkerPage {
+    cursor: Option<String>,
+    workers: Vec<WorkerNode>,
+}
+
+pub struct WorkerNode(WorkerMetadata);
+
+#[Object]
+impl WorkerNode {
+    async fn component_id(&self) -> String {
+        self.0.worker_id.component_id.to_string()
+    }
+
+    async fn worker_name(&self) -> String {
+        self.0.worker_id.worker_name.clone()
+    }
+
+    async fn status(&self) -> String {
+        self.0.status.to_string()
+    }
+
+    async fn component_version(&self) -> u64 {
+        self.0.component_version
+    }
+
+    async fn args(&self) -> Vec<String> {
+        self.0.args.clone()
+    }
+
+    async fn retry_count(&self) -> u64 {
+        self.0.retry_count
+    }
+
+    async fn pending_invocation_count(&self) -> u64 {
+        self.0.pending_invocation_count
+    }
+
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_string()
+    }
+
+    async fn last_error(&self) -> Option<String> {
+        self.0.last_error.clone()
+    }
+
+    /// The most recent error entries of the worker's oplog, the latest first
+    async fn recent_errors(
+        &self,
+        ctx: &Context<'_>,
+        count: Option<u64>,
+    ) -> async_graphql::Result<Vec<WorkerErrorNode>> {
+        let queries = queries(ctx)?;
+        let count = count.unwrap_or(DEFAULT_RECENT_ERROR_COUNT) as usize;
+
+        if count == 0 {
+            return Ok(vec![]);
+        }
+
+        // The search goes forward from the start of the oplog, so it is paged through to its end
+        // keeping only the last errors
+        let mut errors = VecDeque::with_capacity(count);
+        let mut cursor = None;
+
+        loop {
+            let response = queries
+                .search_errors(&self.0.worker_id, cursor, OPLOG_SEARCH_PAGE_SIZE)
+                .await
+                .map_err(async_graphql::Error::new)?;
+
+            if response.entries.is_empty() {
+                break;
+            }
+
+            for entry in response.entries {
+                if let PublicOplogEntry::Error(params) = entry.entry {
+                    if errors.len() == count {
+                        errors.pop_front();
+                    }
+                    errors.push_back(WorkerErrorNode {
+                        oplog_index: entry.oplog_index.into(),
+                        timestamp: params.timestamp.to_string(),
+                        error: params.error,
+                    });
+                }
+            }
+
+            match response.next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(errors.into_iter().rev().collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct WorkerErrorNode {
+    oplog_index: u64,
+    timestamp: String,
+    error: String,
+}
+
+pub struct ApiDefinitionNode(HttpApiDefinitionResponseData);
+
+impl TryFrom<CompiledHttpApiDefinition<DefaultNamespace>> for ApiDefinitionNode {
+    type Error = async_graphql::Error;
+
+    fn try_from(value: CompiledHttpApiDefinition<DefaultNamespace>) -> Result<Self, Self::Error> {
+        HttpApiDefinitionResponseData::try_from(value)
+            .map(ApiDefinitionNode)
+            .map_err(async_graphql::Error::new)
+    }
+}
+
+#[Object]
+impl ApiDefinitionNode {
+    async fn id(&self) -> String {
+        self.0.id.to_string()
+    }
+
+    async fn version(&self) -> String {
+        self.0.version.to_string()
+    }
+
+    async fn draft(&self) -> bool {
+        self.0.draft
+    }
+
+    async fn created_at(&self) -> Option<String> {
+        self.0.created_at.map(|created_at| created_at.to_rfc3339())
+    }
+
+    async fn routes(&self) -> Vec<RouteNode> {
+        self.0
+            .routes
+            .iter()
+            .map(|route| RouteNode {
+                method: http::Method::from(route.method.clone()).to_string(),
+                path: route.path.clone(),
+                security: route.security.clone(),
+                component_id: route
+                    .binding
+                    .component_id
+                    .as_ref()
+                    .map(|c| c.component_id.to_string()),
+                component_version: route.binding.component_id.as_ref().map(|c| c.version),
+            })
+            .collect()
+    }
+
+    /// Deployments that currently serve this version of the API definition
+    async fn deployments(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<DeploymentNode>> {
+        let deployments = queries(ctx)?
+            .deployments(Some(self.0.id.clone()))
+            .await
+            .map_err(async_graphql::Error::new)?;
+
+        Ok(deployments
+            .into_iter()
+            .filter(|deployment| {
+                deployment
+                    .api_definition_keys
+                    .iter()
+                    .any(|key| key.id == self.0.id && key.version == self.0.version)
+            })
+            .map(DeploymentNode)
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct RouteNode {
+    method: String,
+    path: String,
+    security: Option<String>,
+    component_id: Option<String>,
+    component_version: Option<u64>,
+}
+
+#[async_graphql::ComplexObject]
+impl RouteNode {
+    /// The component version the route is bound to
+    async fn component(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<ComponentNode>> {
+        match (&self.component_id, self.component_version) {
+            (Some(component_id), Some(version)) => {
+                QueryRoot
+                    .component(ctx, component_id.clone(), Some(version))
+                    .await
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+pub struct DeploymentNode(ApiDeployment<DefaultNamespace>);
+
+#[Object]
+impl DeploymentNode {
+    async fn site(&self) -> String {
+        self.0.site.to_string()
+    }
+
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_rfc3339()
+    }
+
+    async fn api_definitions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ApiDefinitionNode>> {
+        let mut result = Vec::new();
+
+        for key in &self.0.api_definition_keys {
+            if let Some(definition) = QueryRoot
+                .api_definition(ctx, key.id.0.clone(), key.version.0.clone())
+                .await?
+            {
+                result.push(definition);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+fn parse_component_id(component_id: &str) -> async_graphql::Result<ComponentId> {
+    ComponentId::from_str(component_id)
+        .map_err(|err| async_graphql::Error::new(format!("Invalid component id: {err}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::component_metadata::ComponentMetadata;
+    use golem_common::model::oplog::OplogIndex;
+    use golem_common::model::public_oplog::ErrorParameters;
+    use golem_common::model::{ComponentType, Timestamp, WorkerStatus};
+    use golem_service_base::model::{
+        ComponentName, PublicOplogEntryWithIndex, VersionedComponentId,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+    use test_r::test;
+
+    struct Fixture {
+        component_id: ComponentId,
+        // The oplog indexes of the worker's errors
+        errors: Vec<u64>,
+    }
+
+    #[async_trait]
+    impl ManagementQueries for Fixture {
+        async fn component(
+            &self,
+            component_id: &ComponentId,
+            _version: Option<u64>,
+        ) -> Result<Option<Component>, String> {
+            Ok((*component_id == self.component_id).then(|| Component {
+                versioned_component_id: VersionedComponentId {
+                    component_id: component_id.clone(),
+                    version: 0,
+                },
+                component_name: ComponentName("shopping-cart".to_string()),
+                component_size: 0,
+                metadata: ComponentMetadata {
+                    exports: vec![],
+                    producers: vec![],
+                    memories: vec![],
+                    dynamic_linking: HashMap::new(),
+                },
+                created_at: None,
+                component_type: Some(ComponentType::Durable),
+                files: vec![],
+                installed_plugins: vec![],
+            }))
+        }
+
+        async fn worker(&self, worker_id: &WorkerId) -> Result<WorkerMetadata, String> {
+            Ok(worker_metadata(worker_id.clone()))
+        }
+
+        async fn workers(
+            &self,
+            component_id: &ComponentId,
+            _filter: Option<WorkerFilter>,
+            _cursor: ScanCursor,
+            _count: u64,
+            _precise: bool,
+        ) -> Result<(Option<ScanCursor>, Vec<WorkerMetadata>), String> {
+            Ok((
+                None,
+                vec![worker_metadata(WorkerId {
+                    component_id: component_id.clone(),
+                    worker_name: "cart-1".to_string(),
+                })],
+            ))
+        }
+
+        // Pages through the errors the way the executor does, from the start of the oplog
+        async fn search_errors(
+            &self,
+            _worker_id: &WorkerId,
+            cursor: Option<OplogCursor>,
+            count: u64,
+        ) -> Result<GetOplogResponse, String> {
+            let start = cursor.map(|cursor| cursor.next_oplog_index).unwrap_or(1);
+            let entries = self
+                .errors
+                .iter()
+                .filter(|index| **index >= start)
+                .take(count as usize)
+                .map(|index| PublicOplogEntryWithIndex {
+                    oplog_index: OplogIndex::from_u64(*index),
+                    entry: PublicOplogEntry::Error(ErrorParameters {
+                        timestamp: Timestamp::now_utc(),
+                        error: format!("error at {index}"),
+                    }),
+                    decoded: None,
+                })
+                .collect::<Vec<_>>();
+
+            let next = entries.last().map(|entry| OplogCursor {
+                next_oplog_index: u64::from(entry.oplog_index) + 1,
+                current_component_version: 0,
+            });
+
+            Ok(GetOplogResponse {
+                entries,
+                next,
+                first_index_in_chunk: start,
+                last_index: self.errors.last().copied().unwrap_or(0),
+            })
+        }
+
+        async fn api_definitions(
+            &self,
+            _id: Option<ApiDefinitionId>,
+        ) -> Result<Vec<CompiledHttpApiDefinition<DefaultNamespace>>, String> {
+            Ok(vec![])
+        }
+
+        async fn api_definition(
+            &self,
+            _id: ApiDefinitionId,
+            _version: ApiVersion,
+        ) -> Result<Option<CompiledHttpApiDefinition<DefaultNamespace>>, String> {
+            Ok(None)
+        }
+
+        async fn deployments(
+            &self,
+            _api_definition_id: Option<ApiDefinitionId>,
+        ) -> Result<Vec<ApiDeployment<DefaultNamespace>>, String> {
+            Ok(vec![])
+        }
+    }
+
+    fn worker_metadata(worker_id: WorkerId) -> WorkerMetadata {
+        WorkerMetadata {
+            worker_id,
+            args: vec![],
+            env: HashMap::new(),
+            wasi_config_vars: HashMap::new(),
+            labels: HashMap::new(),
+            status: WorkerStatus::Idle,
+            component_version: 0,
+            retry_count: 0,
+            pending_invocation_count: 0,
+            updates: vec![],
+            created_at: Timestamp::now_utc(),
+            last_error: None,
+            component_size: 0,
+            total_linear_memory_size: 0,
+            owned_resources: HashMap::new(),
+            active_plugins: Default::default(),
+            compaction: Default::default(),
+            fork: None,
+        }
+    }
+
+    #[test]
+    async fn recent_errors_of_the_workers_of_a_component_are_the_last_ones() {
+        let component_id = ComponentId::new_v4();
+        let schema = schema(Arc::new(Fixture {
+            component_id: component_id.clone(),
+            // More errors than the executor returns at once
+            errors: (1..=250).map(|index| index * 2).collect(),
+        }));
+
+        let query = format!(
+            r#"{{
+                component(componentId: "{component_id}") {{
+                    name
+                    workers {{
+                        workers {{
+                            workerName
+                            recentErrors(count: 3) {{ oplogIndex error }}
+                        }}
+                    }}
+                }}
+            }}"#
+        );
+        let response = schema.execute(query).await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({
+                "component": {
+                    "name": "shopping-cart",
+                    "workers": {
+                        "workers": [{
+                            "workerName": "cart-1",
+                            "recentErrors": [
+                                { "oplogIndex": 500, "error": "error at 500" },
+                                { "oplogIndex": 498, "error": "error at 498" },
+                                { "oplogIndex": 496, "error": "error at 496" }
+                            ]
+                        }]
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    async fn unknown_components_resolve_to_null() {
+        let schema = schema(Arc::new(Fixture {
+            component_id: ComponentId::new_v4(),
+            errors: vec![],
+        }));
+
+        let query = format!(
+            r#"{{ component(componentId: "{}") {{ name }} }}"#,
+            ComponentId::new_v4()
+        );
+        let response = schema.execute(query).await;
+
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({ "component": null })
+        );
+    }
+}
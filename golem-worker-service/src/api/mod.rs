@@ -1,23 +1,60 @@
 pub mod api_definition;
 pub mod api_deployment;
+pub mod async_invocation;
+pub mod audit_log;
+pub mod component_dependents;
+pub mod dead_letter;
+pub mod emitted_event;
+pub mod experiments;
+pub mod graphql;
+pub mod namespace_quota;
+pub mod request_fixture;
+pub mod rib_library;
 mod security_scheme;
+pub mod shadow_traffic;
+pub mod trace;
 pub mod worker;
+pub mod worker_batch;
 pub mod worker_connect;
+pub mod worker_schedule;
 
 use crate::api::worker::WorkerApi;
+use crate::local_gateway::LocalHttpApiDefinitionLookup;
 use crate::service::Services;
+use golem_service_base::auth::DefaultNamespace;
 use golem_worker_service_base::api::CustomHttpRequestApi;
 use golem_worker_service_base::api::HealthcheckApi;
+use golem_worker_service_base::gateway_api_definition::http::CompiledHttpApiDefinition;
+use golem_worker_service_base::gateway_execution::api_definition_lookup::HttpApiDefinitionsLookup;
+use golem_worker_service_base::gateway_execution::deployment_probe::{
+    DeploymentProber, StagedHttpApiDefinitionLookup,
+};
+use golem_worker_service_base::gateway_execution::request_id::RequestIdMiddleware;
 use poem::endpoint::PrometheusExporter;
 use poem::{get, EndpointExt, Route};
 use poem_openapi::OpenApiService;
 use prometheus::Registry;
+use std::sync::Arc;
 
 pub type ApiServices = (
     WorkerApi,
+    worker_batch::WorkerBatchApi,
+    async_invocation::AsyncInvocationApi,
+    worker_schedule::WorkerScheduleApi,
     api_definition::RegisterApiDefinitionApi,
     api_deployment::ApiDeploymentApi,
+    (
+        dead_letter::DeadLetterApi,
+        request_fixture::RequestFixtureApi,
+    ),
+    emitted_event::EmittedEventApi,
+    (audit_log::AuditLogApi, trace::TraceApi),
+    namespace_quota::NamespaceQuotaApi,
     security_scheme::SecuritySchemeApi,
+    rib_library::RibLibraryApi,
+    component_dependents::ComponentDependentsApi,
+    shadow_traffic::ShadowTrafficApi,
+    experiments::ExperimentsApi,
     HealthcheckApi,
 );
 
@@ -29,6 +66,7 @@ pub fn combined_routes(prometheus_registry: Registry, services: &Services) -> Ro
     let metrics = PrometheusExporter::new(prometheus_registry.clone());
 
     let connect_services = worker_connect::ConnectService::new(services.worker_service.clone());
+    let graphql_schema = graphql::make_schema(services);
 
     Route::new()
         .nest("/", api_service)
@@ -37,20 +75,83 @@ pub fn combined_routes(prometheus_registry: Registry, services: &Services) -> Ro
         .nest("/metrics", metrics)
         .at(
             "/v1/components/:component_id/workers/:worker_name/connect",
-            get(worker_connect::ws.data(connect_services)),
+            get(worker_connect::ws.data(connect_services.clone())),
+        )
+        .at(
+            "/v1/components/:component_id/connect",
+            get(worker_connect::ws_component.data(connect_services)),
+        )
+        .at(
+            "/v1/graphql",
+            get(graphql::graphiql).post(graphql::graphql_handler.data(graphql_schema)),
         )
 }
 
 pub fn custom_request_route(services: &Services) -> Route {
-    let custom_request_executor = CustomHttpRequestApi::new(
+    let custom_request_executor =
+        custom_request_executor(services, services.http_definition_lookup_service.clone());
+
+    Route::new().nest(
+        "/",
+        custom_request_executor
+            .with(services.forwarded_client_certificate.clone())
+            .with(RequestIdMiddleware),
+    )
+}
+
+// Serves a single API definition which is not deployed, under every host
+pub fn local_request_route(
+    services: &Services,
+    definition: CompiledHttpApiDefinition<DefaultNamespace>,
+) -> Route {
+    let custom_request_executor = custom_request_executor(
+        services,
+        Arc::new(LocalHttpApiDefinitionLookup::new(definition)),
+    );
+
+    Route::new().nest(
+        "/",
+        custom_request_executor
+            .with(services.forwarded_client_certificate.clone())
+            .with(RequestIdMiddleware),
+    )
+}
+
+fn custom_request_executor(
+    services: &Services,
+    http_definition_lookup_service: Arc<
+        dyn HttpApiDefinitionsLookup<DefaultNamespace> + Sync + Send,
+    >,
+) -> CustomHttpRequestApi {
+    CustomHttpRequestApi::new(
         services.worker_to_http_service.clone(),
-        services.http_definition_lookup_service.clone(),
+        http_definition_lookup_service,
         services.fileserver_binding_handler.clone(),
         services.http_handler_binding_handler.clone(),
         services.gateway_session_store.clone(),
-    );
+        services.shadow_traffic_diff_store.clone(),
+        services.experiment_assignment_store.clone(),
+        services.dead_letter_service.clone(),
+        services.request_fixture_service.clone(),
+        services.emitted_event_service.clone(),
+        services.worker_event_subscriber.clone(),
+        services.rate_limiter.clone(),
+        services.response_cache.clone(),
+        services.api_key_validator.clone(),
+        services.hmac_signature_validator.clone(),
+    )
+}
+
+// Serves the probes of deployment swaps with the definitions staged for them, along with the
+// services of the custom requests
+fn deployment_prober(services: &Services) -> Arc<DeploymentProber<DefaultNamespace>> {
+    let staged_definitions = Arc::new(StagedHttpApiDefinitionLookup::default());
+    let executor = custom_request_executor(services, staged_definitions.clone());
 
-    Route::new().nest("/", custom_request_executor)
+    Arc::new(DeploymentProber::new(
+        staged_definitions,
+        executor.gateway_http_input_executor,
+    ))
 }
 
 pub fn make_open_api_service(services: &Services) -> OpenApiService<ApiServices, ()> {
@@ -59,10 +160,73 @@ pub fn make_open_api_service(services: &Services) -> OpenApiService<ApiServices,
             worker::WorkerApi {
                 component_service: services.component_service.clone(),
                 worker_service: services.worker_service.clone(),
+                audit_log_service: services.audit_log_service.clone(),
+                worker_files: services.worker_files.clone(),
+                worker_watch: services.worker_watch.clone(),
+            },
+            worker_batch::WorkerBatchApi {
+                worker_service: services.worker_service.clone(),
+                batch_invocation: services.batch_invocation.clone(),
+            },
+            async_invocation::AsyncInvocationApi {
+                worker_service: services.worker_service.clone(),
+                async_invocations: services.async_invocations.clone(),
+            },
+            worker_schedule::WorkerScheduleApi::new(services.worker_schedule_service.clone()),
+            api_definition::RegisterApiDefinitionApi::new(
+                services.definition_service.clone(),
+                services.rib_library_service.clone(),
+            ),
+            api_deployment::ApiDeploymentApi::new(
+                services.deployment_service.clone(),
+                services.response_cache.clone(),
+                deployment_prober(services),
+                services.audit_log_service.clone(),
+                services.deployment_watch.clone(),
+            ),
+            (
+                dead_letter::DeadLetterApi::new(
+                    services.dead_letter_service.clone(),
+                    custom_request_executor(
+                        services,
+                        services.http_definition_lookup_service.clone(),
+                    )
+                    .gateway_http_input_executor,
+                ),
+                request_fixture::RequestFixtureApi::new(
+                    services.request_fixture_service.clone(),
+                    custom_request_executor(
+                        services,
+                        services.http_definition_lookup_service.clone(),
+                    )
+                    .gateway_http_input_executor,
+                ),
+            ),
+            emitted_event::EmittedEventApi::new(services.emitted_event_service.clone()),
+            (
+                audit_log::AuditLogApi::new(services.audit_log_service.clone()),
+                trace::TraceApi {
+                    worker_service: services.worker_service.clone(),
+                },
+            ),
+            namespace_quota::NamespaceQuotaApi::new(services.namespace_quota_service.clone()),
+            security_scheme::SecuritySchemeApi::new(
+                services.security_scheme_service.clone(),
+                services.api_key_service.clone(),
+            ),
+            rib_library::RibLibraryApi::new(services.rib_library_service.clone()),
+            component_dependents::ComponentDependentsApi {
+                component_service: services.component_service.clone(),
+                worker_service: services.worker_service.clone(),
+                definition_service: services.definition_service.clone(),
+                deployment_service: services.deployment_service.clone(),
+            },
+            shadow_traffic::ShadowTrafficApi {
+                shadow_traffic_diff_store: services.shadow_traffic_diff_store.clone(),
+            },
+            experiments::ExperimentsApi {
+                experiment_assignment_store: services.experiment_assignment_store.clone(),
             },
-            api_definition::RegisterApiDefinitionApi::new(services.definition_service.clone()),
-            api_deployment::ApiDeploymentApi::new(services.deployment_service.clone()),
-            security_scheme::SecuritySchemeApi::new(services.security_scheme_service.clone()),
             HealthcheckApi,
         ),
         "Golem API",
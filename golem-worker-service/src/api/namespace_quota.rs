@@ -0,0 +1,88 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::DefaultNamespace;
+use golem_service_base::service::namespace_quota::{
+    NamespaceQuota, NamespaceQuotaService, NamespaceQuotaUpdate,
+};
+use golem_worker_service_base::api::WorkerApiBaseError;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+pub struct NamespaceQuotaApi {
+    namespace_quota_service: Arc<dyn NamespaceQuotaService<DefaultNamespace> + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/api/quotas", tag = ApiTags::Quota)]
+impl NamespaceQuotaApi {
+    pub fn new(
+        namespace_quota_service: Arc<dyn NamespaceQuotaService<DefaultNamespace> + Sync + Send>,
+    ) -> Self {
+        Self {
+            namespace_quota_service,
+        }
+    }
+
+    /// Get the worker quotas
+    ///
+    /// Lists the limits of the number of workers and of the invocations per minute of the
+    /// project. Resources without a limit are not listed.
+    #[oai(path = "/", method = "get", operation_id = "get_worker_quotas")]
+    async fn get_quotas(&self) -> Result<Json<Vec<NamespaceQuota>>, WorkerApiBaseError> {
+        let record = recorded_http_api_request!("get_worker_quotas",);
+
+        let response = self
+            .namespace_quota_service
+            .get_all(&DefaultNamespace::default())
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Update a worker quota
+    ///
+    /// Sets or removes the limit of the number of workers or of the invocations per minute of
+    /// the project. Launching, forking or invoking workers over the limit fails with `429`,
+    /// whichever API it is done through. The workers are counted again every 30 seconds, so
+    /// workers created through other worker service instances are only taken into account
+    /// after a while. The invocations are counted by each worker service instance on its own,
+    /// so the project can make as many invocations per minute through each of them.
+    #[oai(path = "/", method = "put", operation_id = "update_worker_quota")]
+    async fn update_quota(
+        &self,
+        update: Json<NamespaceQuotaUpdate>,
+    ) -> Result<Json<Vec<NamespaceQuota>>, WorkerApiBaseError> {
+        let record = recorded_http_api_request!(
+            "update_worker_quota",
+            resource = update.0.resource.to_string()
+        );
+
+        let response = self
+            .namespace_quota_service
+            .update(&DefaultNamespace::default(), update.0)
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+}
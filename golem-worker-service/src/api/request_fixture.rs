@@ -0,0 +1,163 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::DefaultNamespace;
+use golem_worker_service_base::api::ApiEndpointError;
+use golem_worker_service_base::gateway_api_deployment::ApiSiteString;
+use golem_worker_service_base::gateway_execution::gateway_http_input_executor::GatewayHttpInputExecutor;
+use golem_worker_service_base::service::gateway::request_fixture::{
+    RequestFixture, RequestFixtureReplayReport, RequestFixtureReplayRequest, RequestFixtureService,
+};
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+pub struct RequestFixtureApi {
+    request_fixture_service: Arc<dyn RequestFixtureService<DefaultNamespace> + Sync + Send>,
+    // Serves the replays with the definitions deployed to the staging sites
+    executor: Arc<dyn GatewayHttpInputExecutor + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/api/deployments", tag = ApiTags::ApiDeployment)]
+impl RequestFixtureApi {
+    pub fn new(
+        request_fixture_service: Arc<dyn RequestFixtureService<DefaultNamespace> + Sync + Send>,
+        executor: Arc<dyn GatewayHttpInputExecutor + Sync + Send>,
+    ) -> Self {
+        Self {
+            request_fixture_service,
+            executor,
+        }
+    }
+
+    /// Get the request fixtures of an API deployment
+    ///
+    /// Lists the sampled requests of the site recorded as fixtures, the oldest first.
+    #[oai(
+        path = "/:site/fixtures",
+        method = "get",
+        operation_id = "get_request_fixtures_of_site"
+    )]
+    async fn get_request_fixtures(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<Vec<RequestFixture>>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_request_fixtures_of_site", site = site.0);
+        let response = self
+            .request_fixture_service
+            .get_all(&DefaultNamespace::default(), &ApiSiteString(site.0))
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Delete the request fixtures of an API deployment
+    ///
+    /// Deletes every recorded fixture of the site, so that new ones can be recorded.
+    #[oai(
+        path = "/:site/fixtures",
+        method = "delete",
+        operation_id = "delete_request_fixtures_of_site"
+    )]
+    async fn delete_request_fixtures(
+        &self,
+        site: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!("delete_request_fixtures_of_site", site = site.0);
+        let response = self
+            .request_fixture_service
+            .delete_all(&DefaultNamespace::default(), &ApiSiteString(site.0))
+            .instrument(record.span.clone())
+            .await
+            .map(|deleted| Json(format!("{} request fixtures deleted", deleted)))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Delete a request fixture of an API deployment
+    #[oai(
+        path = "/:site/fixtures/:fixture_id",
+        method = "delete",
+        operation_id = "delete_request_fixture"
+    )]
+    async fn delete_request_fixture(
+        &self,
+        site: Path<String>,
+        fixture_id: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "delete_request_fixture",
+            site = site.0,
+            fixture_id = fixture_id.0
+        );
+        let response = self
+            .request_fixture_service
+            .delete(
+                &DefaultNamespace::default(),
+                &ApiSiteString(site.0),
+                &fixture_id.0,
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(|_| Json("Request fixture deleted".to_string()))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Replay the request fixtures of an API deployment against a staging deployment
+    ///
+    /// Sends every recorded fixture of the site to the staging site, which serves them with its
+    /// own definitions, and compares the status and the schema of the responses with the
+    /// recorded ones. The report passes when every replay matches, so it can be used to gate
+    /// the promotion of the definitions of the staging site.
+    #[oai(
+        path = "/:site/fixtures/replay",
+        method = "post",
+        operation_id = "replay_request_fixtures"
+    )]
+    async fn replay_request_fixtures(
+        &self,
+        site: Path<String>,
+        request: Json<RequestFixtureReplayRequest>,
+    ) -> Result<Json<RequestFixtureReplayReport>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "replay_request_fixtures",
+            site = site.0,
+            staging_site = request.0.staging_site
+        );
+        let response = self
+            .request_fixture_service
+            .replay(
+                &DefaultNamespace::default(),
+                &ApiSiteString(site.0),
+                &ApiSiteString(request.0.staging_site),
+                self.executor.as_ref(),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+}
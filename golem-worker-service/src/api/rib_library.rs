@@ -0,0 +1,133 @@
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::DefaultNamespace;
+use golem_worker_service_base::api::{ApiEndpointError, RibLibraryCreationData, RibLibraryData};
+use golem_worker_service_base::service::gateway::rib_library::RibLibraryService;
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+use poem_openapi::OpenApi;
+use std::sync::Arc;
+use tracing::Instrument;
+
+pub struct RibLibraryApi {
+    rib_library_service: Arc<dyn RibLibraryService<DefaultNamespace> + Sync + Send>,
+}
+
+impl RibLibraryApi {
+    pub fn new(
+        rib_library_service: Arc<dyn RibLibraryService<DefaultNamespace> + Sync + Send>,
+    ) -> Self {
+        Self {
+            rib_library_service,
+        }
+    }
+}
+
+#[OpenApi(prefix_path = "/v1/api/rib-libraries", tag = ApiTags::ApiDefinition)]
+impl RibLibraryApi {
+    /// Create a Rib library
+    ///
+    /// Creates a new version of a Rib library, made of `let` bindings only. The bindings can be
+    /// imported by the binding scripts of API definitions with `use <name>::<binding>;` for the
+    /// latest version, or `use <name>@<version>::<binding>;` for a given one. The library is
+    /// compiled on creation, and a version of a library cannot be changed once created.
+    #[oai(path = "/", method = "post", operation_id = "create_rib_library")]
+    async fn create(
+        &self,
+        payload: Json<RibLibraryCreationData>,
+    ) -> Result<Json<RibLibraryData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "create_rib_library",
+            name = payload.0.name,
+            version = payload.0.version
+        );
+
+        let response = self
+            .rib_library_service
+            .create(
+                &DefaultNamespace::default(),
+                payload.0.name,
+                payload.0.version,
+                payload.0.source,
+            )
+            .instrument(record.span.clone())
+            .await
+            .map(|library| Json(RibLibraryData::from(library)))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Get all Rib libraries
+    ///
+    /// Lists all the versions of all the Rib libraries
+    #[oai(path = "/", method = "get", operation_id = "list_rib_libraries")]
+    async fn list(&self) -> Result<Json<Vec<RibLibraryData>>, ApiEndpointError> {
+        let record = recorded_http_api_request!("list_rib_libraries",);
+
+        let response = self
+            .rib_library_service
+            .get_all(&DefaultNamespace::default())
+            .instrument(record.span.clone())
+            .await
+            .map(|libraries| Json(libraries.into_iter().map(RibLibraryData::from).collect()))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Get a Rib library
+    ///
+    /// Gets a version of a Rib library, along with the bindings it exports
+    #[oai(
+        path = "/:name/:version",
+        method = "get",
+        operation_id = "get_rib_library"
+    )]
+    async fn get(
+        &self,
+        name: Path<String>,
+        version: Path<String>,
+    ) -> Result<Json<RibLibraryData>, ApiEndpointError> {
+        let record =
+            recorded_http_api_request!("get_rib_library", name = name.0, version = version.0);
+
+        let response = self
+            .rib_library_service
+            .get(&DefaultNamespace::default(), &name.0, &version.0)
+            .instrument(record.span.clone())
+            .await
+            .map(|library| Json(RibLibraryData::from(library)))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Delete a Rib library
+    ///
+    /// Deletes a version of a Rib library. The API definitions that imported it are not affected,
+    /// as the imported bindings are linked into their scripts on creation.
+    #[oai(
+        path = "/:name/:version",
+        method = "delete",
+        operation_id = "delete_rib_library"
+    )]
+    async fn delete(
+        &self,
+        name: Path<String>,
+        version: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record =
+            recorded_http_api_request!("delete_rib_library", name = name.0, version = version.0);
+
+        let response = self
+            .rib_library_service
+            .delete(&DefaultNamespace::default(), &name.0, &version.0)
+            .instrument(record.span.clone())
+            .await
+            .map(|_| Json("Rib library deleted".to_string()))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+}
@@ -1,8 +1,16 @@
 use golem_common::{recorded_http_api_request, safe};
 use golem_service_base::api_tags::ApiTags;
 use golem_service_base::auth::DefaultNamespace;
-use golem_worker_service_base::api::{ApiEndpointError, SecuritySchemeData};
-use golem_worker_service_base::gateway_security::{SecurityScheme, SecuritySchemeIdentifier};
+use golem_worker_service_base::api::{
+    ApiEndpointError, ApiKeyCreationData, ApiKeyData, ApiKeySecuritySchemeData,
+    HmacSecuritySchemeCreationData, HmacSecuritySchemeData, IssuedApiKeyData,
+    JwtSecuritySchemeData, SecuritySchemeData,
+};
+use golem_worker_service_base::gateway_security::{
+    ApiKeySecurityScheme, JwtSecurityScheme, RegisteredSecurityScheme, SecurityScheme,
+    SecuritySchemeIdentifier,
+};
+use golem_worker_service_base::service::gateway::api_key::ApiKeyService;
 use golem_worker_service_base::service::gateway::security_scheme::SecuritySchemeService;
 use poem_openapi::param::Path;
 use poem_openapi::payload::Json;
@@ -13,14 +21,17 @@ use tracing::Instrument;
 
 pub struct SecuritySchemeApi {
     security_scheme_service: Arc<dyn SecuritySchemeService<DefaultNamespace> + Sync + Send>,
+    api_key_service: Arc<dyn ApiKeyService<DefaultNamespace> + Sync + Send>,
 }
 
 impl SecuritySchemeApi {
     pub fn new(
         security_scheme_service: Arc<dyn SecuritySchemeService<DefaultNamespace> + Sync + Send>,
+        api_key_service: Arc<dyn ApiKeyService<DefaultNamespace> + Sync + Send>,
     ) -> Self {
         Self {
             security_scheme_service,
+            api_key_service,
         }
     }
 }
@@ -79,4 +90,331 @@ impl SecuritySchemeApi {
             security_scheme_with_metadata,
         )))
     }
+
+    /// Get a JWT security scheme
+    ///
+    /// Get a JWT security scheme by name
+    #[oai(
+        path = "/jwt/:security_scheme_identifier",
+        method = "get",
+        operation_id = "get_jwt"
+    )]
+    async fn get_jwt(
+        &self,
+        security_scheme_identifier: Path<String>,
+    ) -> Result<Json<JwtSecuritySchemeData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "get_jwt",
+            security_scheme_identifier = security_scheme_identifier.0
+        );
+        let security_scheme = self
+            .security_scheme_service
+            .get_registered(
+                &SecuritySchemeIdentifier::new(security_scheme_identifier.0.clone()),
+                &DefaultNamespace::default(),
+            )
+            .instrument(record.span.clone())
+            .await?;
+
+        match security_scheme {
+            RegisteredSecurityScheme::Jwt(security_scheme) => {
+                Ok(Json(JwtSecuritySchemeData::from(security_scheme)))
+            }
+            RegisteredSecurityScheme::OpenId(_)
+            | RegisteredSecurityScheme::ApiKey(_)
+            | RegisteredSecurityScheme::Hmac(_) => {
+                Err(ApiEndpointError::bad_request(safe(format!(
+                    "Security scheme {} is not a JWT scheme",
+                    security_scheme_identifier.0
+                ))))
+            }
+        }
+    }
+
+    /// Create a JWT security scheme
+    ///
+    /// Create a security scheme validating the bearer JWT of requests with the keys of the issuer,
+    /// without the OpenID Connect login flow
+    #[oai(path = "/jwt", method = "post", operation_id = "create_jwt")]
+    async fn create_jwt(
+        &self,
+        payload: Json<JwtSecuritySchemeData>,
+    ) -> Result<Json<JwtSecuritySchemeData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "create_jwt",
+            security_scheme_identifier = payload.0.scheme_identifier
+        );
+        let security_scheme = JwtSecurityScheme::try_from(payload.0).map_err(|err| {
+            ApiEndpointError::bad_request(safe(format!("Invalid security scheme {}", err)))
+        })?;
+
+        let security_scheme = self
+            .security_scheme_service
+            .create_jwt(&DefaultNamespace::default(), &security_scheme)
+            .instrument(record.span.clone())
+            .await?;
+
+        Ok(Json(JwtSecuritySchemeData::from(security_scheme)))
+    }
+
+    /// Get an API key security scheme
+    ///
+    /// Get an API key security scheme by name
+    #[oai(
+        path = "/api-key/:security_scheme_identifier",
+        method = "get",
+        operation_id = "get_api_key_scheme"
+    )]
+    async fn get_api_key_scheme(
+        &self,
+        security_scheme_identifier: Path<String>,
+    ) -> Result<Json<ApiKeySecuritySchemeData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "get_api_key_scheme",
+            security_scheme_identifier = security_scheme_identifier.0
+        );
+        let security_scheme = self
+            .security_scheme_service
+            .get_registered(
+                &SecuritySchemeIdentifier::new(security_scheme_identifier.0.clone()),
+                &DefaultNamespace::default(),
+            )
+            .instrument(record.span.clone())
+            .await?;
+
+        match security_scheme {
+            RegisteredSecurityScheme::ApiKey(security_scheme) => {
+                Ok(Json(ApiKeySecuritySchemeData::from(security_scheme)))
+            }
+            RegisteredSecurityScheme::OpenId(_)
+            | RegisteredSecurityScheme::Jwt(_)
+            | RegisteredSecurityScheme::Hmac(_) => {
+                Err(ApiEndpointError::bad_request(safe(format!(
+                    "Security scheme {} is not an API key scheme",
+                    security_scheme_identifier.0
+                ))))
+            }
+        }
+    }
+
+    /// Create an API key security scheme
+    ///
+    /// Create a security scheme authenticating requests with API keys issued by Golem,
+    /// taken from the given header or query parameter
+    #[oai(
+        path = "/api-key",
+        method = "post",
+        operation_id = "create_api_key_scheme"
+    )]
+    async fn create_api_key_scheme(
+        &self,
+        payload: Json<ApiKeySecuritySchemeData>,
+    ) -> Result<Json<ApiKeySecuritySchemeData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "create_api_key_scheme",
+            security_scheme_identifier = payload.0.scheme_identifier
+        );
+        let security_scheme = ApiKeySecurityScheme::try_from(payload.0).map_err(|err| {
+            ApiEndpointError::bad_request(safe(format!("Invalid security scheme {}", err)))
+        })?;
+
+        let security_scheme = self
+            .security_scheme_service
+            .create_api_key(&DefaultNamespace::default(), &security_scheme)
+            .instrument(record.span.clone())
+            .await?;
+
+        Ok(Json(ApiKeySecuritySchemeData::from(security_scheme)))
+    }
+
+    /// Get an HMAC security scheme
+    ///
+    /// Get an HMAC security scheme by name, without its secret
+    #[oai(
+        path = "/hmac/:security_scheme_identifier",
+        method = "get",
+        operation_id = "get_hmac_scheme"
+    )]
+    async fn get_hmac_scheme(
+        &self,
+        security_scheme_identifier: Path<String>,
+    ) -> Result<Json<HmacSecuritySchemeData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "get_hmac_scheme",
+            security_scheme_identifier = security_scheme_identifier.0
+        );
+        let security_scheme = self
+            .security_scheme_service
+            .get_registered(
+                &SecuritySchemeIdentifier::new(security_scheme_identifier.0.clone()),
+                &DefaultNamespace::default(),
+            )
+            .instrument(record.span.clone())
+            .await?;
+
+        match security_scheme {
+            RegisteredSecurityScheme::Hmac(security_scheme) => {
+                Ok(Json(HmacSecuritySchemeData::from(security_scheme)))
+            }
+            RegisteredSecurityScheme::OpenId(_)
+            | RegisteredSecurityScheme::Jwt(_)
+            | RegisteredSecurityScheme::ApiKey(_) => {
+                Err(ApiEndpointError::bad_request(safe(format!(
+                    "Security scheme {} is not an HMAC scheme",
+                    security_scheme_identifier.0
+                ))))
+            }
+        }
+    }
+
+    /// Create an HMAC security scheme
+    ///
+    /// Create a security scheme verifying that requests are signed with the given secret,
+    /// such as the webhooks of GitHub or Stripe. The secret is never returned
+    #[oai(path = "/hmac", method = "post", operation_id = "create_hmac_scheme")]
+    async fn create_hmac_scheme(
+        &self,
+        payload: Json<HmacSecuritySchemeCreationData>,
+    ) -> Result<Json<HmacSecuritySchemeData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "create_hmac_scheme",
+            security_scheme_identifier = payload.0.scheme_identifier
+        );
+        let (security_scheme, secret) = payload.0.into_security_scheme().map_err(|err| {
+            ApiEndpointError::bad_request(safe(format!("Invalid security scheme {}", err)))
+        })?;
+
+        let security_scheme = self
+            .security_scheme_service
+            .create_hmac(&DefaultNamespace::default(), &security_scheme, &secret)
+            .instrument(record.span.clone())
+            .await?;
+
+        Ok(Json(HmacSecuritySchemeData::from(security_scheme)))
+    }
+
+    /// Get the API keys of a security scheme
+    ///
+    /// Lists the keys of an API key security scheme, without their secrets
+    #[oai(
+        path = "/api-key/:security_scheme_identifier/keys",
+        method = "get",
+        operation_id = "get_api_keys"
+    )]
+    async fn get_api_keys(
+        &self,
+        security_scheme_identifier: Path<String>,
+    ) -> Result<Json<Vec<ApiKeyData>>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "get_api_keys",
+            security_scheme_identifier = security_scheme_identifier.0
+        );
+        let api_keys = self
+            .api_key_service
+            .get_all(
+                &DefaultNamespace::default(),
+                &SecuritySchemeIdentifier::new(security_scheme_identifier.0),
+            )
+            .instrument(record.span.clone())
+            .await?;
+
+        Ok(Json(api_keys.into_iter().map(ApiKeyData::from).collect()))
+    }
+
+    /// Create an API key
+    ///
+    /// Issues a new key for an API key security scheme. The key is only returned in this response
+    #[oai(
+        path = "/api-key/:security_scheme_identifier/keys",
+        method = "post",
+        operation_id = "create_api_key"
+    )]
+    async fn create_api_key(
+        &self,
+        security_scheme_identifier: Path<String>,
+        payload: Json<ApiKeyCreationData>,
+    ) -> Result<Json<IssuedApiKeyData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "create_api_key",
+            security_scheme_identifier = security_scheme_identifier.0
+        );
+        let (api_key, plaintext) = self
+            .api_key_service
+            .create(
+                &DefaultNamespace::default(),
+                &SecuritySchemeIdentifier::new(security_scheme_identifier.0),
+                payload.0.name,
+                payload.0.metadata,
+            )
+            .instrument(record.span.clone())
+            .await?;
+
+        Ok(Json(IssuedApiKeyData {
+            api_key: ApiKeyData::from(api_key),
+            key: plaintext.to_string(),
+        }))
+    }
+
+    /// Rotate an API key
+    ///
+    /// Replaces the secret of an API key, invalidating the previous one.
+    /// The new key is only returned in this response
+    #[oai(
+        path = "/api-key/:security_scheme_identifier/keys/:key_id/rotate",
+        method = "post",
+        operation_id = "rotate_api_key"
+    )]
+    async fn rotate_api_key(
+        &self,
+        security_scheme_identifier: Path<String>,
+        key_id: Path<String>,
+    ) -> Result<Json<IssuedApiKeyData>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "rotate_api_key",
+            security_scheme_identifier = security_scheme_identifier.0,
+            key_id = key_id.0
+        );
+        let (api_key, plaintext) = self
+            .api_key_service
+            .rotate(
+                &DefaultNamespace::default(),
+                &SecuritySchemeIdentifier::new(security_scheme_identifier.0),
+                &key_id.0,
+            )
+            .instrument(record.span.clone())
+            .await?;
+
+        Ok(Json(IssuedApiKeyData {
+            api_key: ApiKeyData::from(api_key),
+            key: plaintext.to_string(),
+        }))
+    }
+
+    /// Delete an API key
+    #[oai(
+        path = "/api-key/:security_scheme_identifier/keys/:key_id",
+        method = "delete",
+        operation_id = "delete_api_key"
+    )]
+    async fn delete_api_key(
+        &self,
+        security_scheme_identifier: Path<String>,
+        key_id: Path<String>,
+    ) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!(
+            "delete_api_key",
+            security_scheme_identifier = security_scheme_identifier.0,
+            key_id = key_id.0
+        );
+        self.api_key_service
+            .delete(
+                &DefaultNamespace::default(),
+                &SecuritySchemeIdentifier::new(security_scheme_identifier.0),
+                &key_id.0,
+            )
+            .instrument(record.span.clone())
+            .await?;
+
+        Ok(Json("API key deleted".to_string()))
+    }
 }
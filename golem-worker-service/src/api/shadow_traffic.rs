@@ -0,0 +1,65 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_worker_service_base::api::ApiEndpointError;
+use golem_worker_service_base::gateway_execution::shadow_traffic_diff::{
+    ShadowRouteDiffStats, ShadowTrafficDiffStore,
+};
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+
+pub struct ShadowTrafficApi {
+    pub shadow_traffic_diff_store: Arc<dyn ShadowTrafficDiffStore + Sync + Send>,
+}
+
+#[OpenApi(prefix_path = "/v1/api/shadow-traffic", tag = ApiTags::ApiDefinition)]
+impl ShadowTrafficApi {
+    /// Get the response diff statistics of mirrored routes
+    ///
+    /// For every route mirroring traffic to a shadow component, returns the number of compared
+    /// and mismatching responses, with a sample of the most recent differences between the
+    /// primary and the shadow responses. Statistics are kept in memory by each worker service
+    /// instance, since it started or since they were last cleared.
+    #[oai(
+        path = "/diffs",
+        method = "get",
+        operation_id = "get_shadow_traffic_diffs"
+    )]
+    async fn get_shadow_traffic_diffs(
+        &self,
+    ) -> Result<Json<Vec<ShadowRouteDiffStats>>, ApiEndpointError> {
+        let record = recorded_http_api_request!("get_shadow_traffic_diffs",);
+
+        let response = Ok(Json(self.shadow_traffic_diff_store.get_stats()));
+
+        record.result(response)
+    }
+
+    /// Clear the response diff statistics of mirrored routes
+    #[oai(
+        path = "/diffs",
+        method = "delete",
+        operation_id = "clear_shadow_traffic_diffs"
+    )]
+    async fn clear_shadow_traffic_diffs(&self) -> Result<Json<String>, ApiEndpointError> {
+        let record = recorded_http_api_request!("clear_shadow_traffic_diffs",);
+
+        self.shadow_traffic_diff_store.clear();
+
+        record.result(Ok(Json("Shadow traffic diffs cleared".to_string())))
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::service::worker::WorkerService;
+use golem_common::model::invocation_tree::InvocationTree;
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_worker_service_base::api::WorkerApiBaseError;
+use golem_worker_service_base::empty_worker_metadata;
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use tracing::Instrument;
+
+pub struct TraceApi {
+    pub worker_service: WorkerService,
+}
+
+#[OpenApi(prefix_path = "/v1/traces", tag = ApiTags::Worker)]
+impl TraceApi {
+    /// Get the invocation tree of a trace
+    ///
+    /// The trace id of an invocation comes from the `traceparent` header of the request which
+    /// invoked it, through the API gateway or the worker API, and is propagated to the workers it
+    /// invokes through worker-to-worker RPC. Returns the invocations of all the workers belonging
+    /// to the trace in depth-first order, each of them followed by the invocations it made, with
+    /// their outcome as recorded in the oplogs of the workers.
+    #[oai(
+        path = "/:trace_id/invocations",
+        method = "get",
+        operation_id = "get_invocation_tree"
+    )]
+    async fn get_invocation_tree(
+        &self,
+        trace_id: Path<String>,
+    ) -> Result<Json<InvocationTree>, WorkerApiBaseError> {
+        let record = recorded_http_api_request!("get_invocation_tree", trace_id = trace_id.0);
+
+        let response = self
+            .worker_service
+            .get_invocation_tree(&trace_id.0, empty_worker_metadata())
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+}
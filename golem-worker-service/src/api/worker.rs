@@ -1,35 +1,52 @@
+use crate::service::watch::WorkerWatch;
 use crate::service::{component::ComponentService, worker::WorkerService};
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use futures_util::TryStreamExt;
 use golem_common::model::oplog::OplogIndex;
-use golem_common::model::public_oplog::OplogCursor;
+use golem_common::model::public_oplog::{
+    OplogCursor, PublicOplogEntryFilter, PublicOplogEntryKind,
+};
 use golem_common::model::{
-    ComponentFilePath, ComponentId, IdempotencyKey, PluginInstallationId, ScanCursor,
-    TargetWorkerId, WorkerFilter, WorkerId,
+    ComponentFilePath, ComponentFileSystemNode, ComponentFileSystemNodeDetails, ComponentId,
+    IdempotencyKey, PluginInstallationId, ScanCursor, TargetWorkerId, Timestamp, WorkerFilter,
+    WorkerId, WorkerStatusSummary,
 };
 use golem_common::recorded_http_api_request;
 use golem_service_base::api_tags::ApiTags;
-use golem_service_base::auth::EmptyAuthCtx;
+use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
 use golem_service_base::model::*;
+use golem_service_base::poem::CodecPayload;
+use golem_service_base::service::audit_log::{AuditLogService, AuditOperation, AuditedOperation};
 use golem_worker_service_base::api::WorkerApiBaseError;
+use golem_worker_service_base::app_config::WorkerFilesConfig;
 use golem_worker_service_base::empty_worker_metadata;
 use golem_worker_service_base::http_invocation_context::grpc_invocation_context_from_request;
 use golem_worker_service_base::service::worker::InvocationParameters;
 use payload::Binary;
 use poem::{Body, Request};
 use poem_openapi::param::{Header, Path, Query};
-use poem_openapi::payload::Json;
+use poem_openapi::payload::{EventStream, Json};
 use poem_openapi::*;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 use tap::TapFallible;
 use tracing::Instrument;
 
 pub struct WorkerApi {
     pub component_service: ComponentService,
     pub worker_service: WorkerService,
+    pub audit_log_service: Arc<dyn AuditLogService<DefaultNamespace> + Sync + Send>,
+    pub worker_files: WorkerFilesConfig,
+    pub worker_watch: Arc<WorkerWatch>,
 }
 
 type Result<T> = std::result::Result<T, WorkerApiBaseError>;
 
+const WATCH_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
 #[OpenApi(prefix_path = "/v1/components", tag = ApiTags::Worker)]
 impl WorkerApi {
     /// Launch a new worker.
@@ -40,6 +57,8 @@ impl WorkerApi {
     /// - `name` is the name of the created worker. This has to be unique, but only for a given component
     /// - `args` is a list of strings which appear as command line arguments for the worker
     /// - `env` is a list of key-value pairs (represented by arrays) which appear as environment variables for the worker
+    /// - `wasi_config_vars` is an optional list of key-value pairs which are the WASI config variables of the worker
+    /// - `labels` is an optional list of key-value pairs which are the initial labels of the worker
     #[oai(
         path = "/:component_id/workers",
         method = "post",
@@ -73,7 +92,13 @@ impl WorkerApi {
                     }))
                 })?;
 
-            let WorkerCreationRequest { name, args, env } = request.0;
+            let WorkerCreationRequest {
+                name,
+                args,
+                env,
+                wasi_config_vars,
+                labels,
+            } = request.0;
 
             let worker_id = make_worker_id(component_id, name)?;
             let worker_id = self
@@ -83,6 +108,8 @@ impl WorkerApi {
                     latest_component.versioned_component_id.version,
                     args,
                     env,
+                    wasi_config_vars,
+                    labels,
                     empty_worker_metadata(),
                 )
                 .instrument(record.span.clone())
@@ -120,13 +147,25 @@ impl WorkerApi {
             .map_err(|e| e.into())
             .map(|_| Json(DeleteWorkerResponse {}));
 
+        if response.is_ok() {
+            self.audit_log_service
+                .record_or_log(
+                    &DefaultNamespace::default(),
+                    &EmptyAuthCtx::default(),
+                    AuditedOperation::new(AuditOperation::WorkerDelete, &worker_id),
+                )
+                .instrument(record.span.clone())
+                .await;
+        }
+
         record.result(response)
     }
 
     /// Invoke a function and await its resolution on a new worker with a random generated name
     ///
     /// Ideal for invoking ephemeral components, but works with durable ones as well.
-    /// Supply the parameters in the request body as JSON.
+    /// Supply the parameters in the request body as JSON, MessagePack or CBOR.
+    /// The result is returned in the same format as the request.
     #[oai(
         path = "/:component_id/invoke-and-await",
         method = "post",
@@ -138,8 +177,8 @@ impl WorkerApi {
         component_id: Path<ComponentId>,
         #[oai(name = "Idempotency-Key")] idempotency_key: Header<Option<IdempotencyKey>>,
         function: Query<String>,
-        params: Json<InvokeParameters>,
-    ) -> Result<Json<InvokeResult>> {
+        params: CodecPayload<InvokeParameters>,
+    ) -> Result<CodecPayload<InvokeResult>> {
         let worker_id = make_target_worker_id(component_id.0, None)?;
 
         let record = recorded_http_api_request!(
@@ -151,8 +190,9 @@ impl WorkerApi {
 
         let invocation_context = grpc_invocation_context_from_request(request);
 
+        let codec = params.codec;
         let params =
-            InvocationParameters::from_optionally_type_annotated_value_jsons(params.0.params)
+            InvocationParameters::from_optionally_type_annotated_value_jsons(params.value.params)
                 .map_err(|errors| WorkerApiBaseError::BadRequest(Json(ErrorsBody { errors })))?;
 
         let response = match params {
@@ -180,14 +220,15 @@ impl WorkerApi {
         .instrument(record.span.clone())
         .await
         .map_err(|e| e.into())
-        .map(|result| Json(InvokeResult { result }));
+        .map(|result| CodecPayload::new(InvokeResult { result }, codec));
 
         record.result(response)
     }
 
     /// Invoke a function and await its resolution
     ///
-    /// Supply the parameters in the request body as JSON.
+    /// Supply the parameters in the request body as JSON, MessagePack or CBOR.
+    /// The result is returned in the same format as the request.
     #[oai(
         path = "/:component_id/workers/:worker_name/invoke-and-await",
         method = "post",
@@ -200,8 +241,8 @@ impl WorkerApi {
         worker_name: Path<String>,
         #[oai(name = "Idempotency-Key")] idempotency_key: Header<Option<IdempotencyKey>>,
         function: Query<String>,
-        params: Json<InvokeParameters>,
-    ) -> Result<Json<InvokeResult>> {
+        params: CodecPayload<InvokeParameters>,
+    ) -> Result<CodecPayload<InvokeResult>> {
         let worker_id = make_target_worker_id(component_id.0, Some(worker_name.0))?;
 
         let record = recorded_http_api_request!(
@@ -213,8 +254,9 @@ impl WorkerApi {
 
         let invocation_context = grpc_invocation_context_from_request(request);
 
+        let codec = params.codec;
         let params =
-            InvocationParameters::from_optionally_type_annotated_value_jsons(params.0.params)
+            InvocationParameters::from_optionally_type_annotated_value_jsons(params.value.params)
                 .map_err(|errors| WorkerApiBaseError::BadRequest(Json(ErrorsBody { errors })))?;
 
         let response = match params {
@@ -242,7 +284,7 @@ impl WorkerApi {
         .instrument(record.span.clone())
         .await
         .map_err(|e| e.into())
-        .map(|result| Json(InvokeResult { result }));
+        .map(|result| CodecPayload::new(InvokeResult { result }, codec));
         record.result(response)
     }
 
@@ -250,6 +292,8 @@ impl WorkerApi {
     ///
     /// Ideal for invoking ephemeral components, but works with durable ones as well.
     /// Triggers the execution of a function and immediately returns.
+    /// Supply the parameters in the request body as JSON, MessagePack or CBOR.
+    /// Scheduling with `invoke-at` is rejected here, as it requires a worker name.
     #[oai(
         path = "/:component_id/invoke",
         method = "post",
@@ -261,8 +305,17 @@ impl WorkerApi {
         component_id: Path<ComponentId>,
         #[oai(name = "Idempotency-Key")] idempotency_key: Header<Option<IdempotencyKey>>,
         function: Query<String>,
-        params: Json<InvokeParameters>,
-    ) -> Result<Json<InvokeResponse>> {
+        #[oai(name = "invoke-at")] invoke_at: Query<Option<DateTime<Utc>>>,
+        params: CodecPayload<InvokeParameters>,
+    ) -> Result<CodecPayload<InvokeResponse>> {
+        if invoke_at.0.is_some() {
+            return Err(WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                errors: vec![
+                    "Scheduling an invocation with 'invoke-at' requires a worker name".to_string(),
+                ],
+            })));
+        }
+
         let worker_id = make_target_worker_id(component_id.0, None)?;
 
         let record = recorded_http_api_request!(
@@ -274,8 +327,9 @@ impl WorkerApi {
 
         let invocation_context = grpc_invocation_context_from_request(request);
 
+        let codec = params.codec;
         let params =
-            InvocationParameters::from_optionally_type_annotated_value_jsons(params.0.params)
+            InvocationParameters::from_optionally_type_annotated_value_jsons(params.value.params)
                 .map_err(|errors| WorkerApiBaseError::BadRequest(Json(ErrorsBody { errors })))?;
 
         let response = match params {
@@ -285,6 +339,7 @@ impl WorkerApi {
                 function.0,
                 vals,
                 Some(invocation_context),
+                None,
                 empty_worker_metadata(),
             ),
             InvocationParameters::RawJsonStrings(jsons) => self.worker_service.invoke_json(
@@ -293,13 +348,14 @@ impl WorkerApi {
                 function.0,
                 jsons,
                 Some(invocation_context),
+                None,
                 empty_worker_metadata(),
             ),
         }
         .instrument(record.span.clone())
         .await
         .map_err(|e| e.into())
-        .map(|_| Json(InvokeResponse {}));
+        .map(|_| CodecPayload::new(InvokeResponse {}, codec));
 
         record.result(response)
     }
@@ -307,11 +363,15 @@ impl WorkerApi {
     /// Invoke a function
     ///
     /// Triggers the execution of a function and immediately returns.
+    /// Supply the parameters in the request body as JSON, MessagePack or CBOR.
+    /// If `invoke-at` is given, the invocation is durably scheduled by the worker executor and
+    /// only enqueued at that time, which can be used for reminders and timeouts.
     #[oai(
         path = "/:component_id/workers/:worker_name/invoke",
         method = "post",
         operation_id = "invoke_function"
     )]
+    #[allow(clippy::too_many_arguments)]
     async fn invoke_function(
         &self,
         request: &Request,
@@ -319,8 +379,9 @@ impl WorkerApi {
         worker_name: Path<String>,
         #[oai(name = "Idempotency-Key")] idempotency_key: Header<Option<IdempotencyKey>>,
         function: Query<String>,
-        params: Json<InvokeParameters>,
-    ) -> Result<Json<InvokeResponse>> {
+        #[oai(name = "invoke-at")] invoke_at: Query<Option<DateTime<Utc>>>,
+        params: CodecPayload<InvokeParameters>,
+    ) -> Result<CodecPayload<InvokeResponse>> {
         let worker_id = make_target_worker_id(component_id.0, Some(worker_name.0))?;
 
         let record = recorded_http_api_request!(
@@ -330,8 +391,9 @@ impl WorkerApi {
             function = function.0
         );
 
+        let codec = params.codec;
         let params =
-            InvocationParameters::from_optionally_type_annotated_value_jsons(params.0.params)
+            InvocationParameters::from_optionally_type_annotated_value_jsons(params.value.params)
                 .map_err(|errors| WorkerApiBaseError::BadRequest(Json(ErrorsBody { errors })))?;
 
         let invocation_context = grpc_invocation_context_from_request(request);
@@ -343,6 +405,7 @@ impl WorkerApi {
                 function.0,
                 vals,
                 Some(invocation_context),
+                invoke_at.0,
                 empty_worker_metadata(),
             ),
             InvocationParameters::RawJsonStrings(jsons) => self.worker_service.invoke_json(
@@ -351,13 +414,14 @@ impl WorkerApi {
                 function.0,
                 jsons,
                 Some(invocation_context),
+                invoke_at.0,
                 empty_worker_metadata(),
             ),
         }
         .instrument(record.span.clone())
         .await
         .map_err(|e| e.into())
-        .map(|_| Json(InvokeResponse {}));
+        .map(|_| CodecPayload::new(InvokeResponse {}, codec));
 
         record.result(response)
     }
@@ -487,12 +551,18 @@ impl WorkerApi {
     /// | status      | FilterComparator       | Status of worker               | `status = Running`               |
     /// | env.\[key\] | StringFilterComparator | Environment variable of worker | `env.var1 = value`               |
     /// | createdAt   | FilterComparator       | Creation time of worker        | `createdAt > 2024-04-01T12:10:00Z` |
+    /// | lastError   | StringFilterComparator | Last error of worker           | `lastError like out of memory`   |
+    /// | label.\[key\] | StringFilterComparator | Label of worker              | `label.tenant = acme`            |
+    ///
+    /// Statuses and version ranges can also be matched at once, as `status in [Idle, Running]` and
+    /// `version between 1 and 3`. Filters on the name (with `=` or `startswith`) narrow the scan of the
+    /// workers, while filters on the last error require looking it up for every scanned worker.
     ///
     ///
     /// ### Comparators
     ///
-    /// - StringFilterComparator: `eq|equal|=|==`, `ne|notequal|!=`, `like`, `notlike`
-    /// - FilterComparator: `eq|equal|=|==`, `ne|notequal|!=`, `ge|greaterequal|>=`, `gt|greater|>`, `le|lessequal|<=`, `lt|less|<`
+    /// - StringFilterComparator: `eq|equal|=|==`, `ne|notequal|!=`, `like`, `notlike`, `startswith|prefix`
+    /// - FilterComparator: `eq|equal|=|==`, `ne|notequal|!=`, `ge|greaterequal|>=`, `gt|greater|after|>`, `le|lessequal|<=`, `lt|less|before|<`
     ///
     /// Returns metadata about an existing component workers:
     /// - `workers` list of workers metadata
@@ -549,6 +619,56 @@ impl WorkerApi {
         record.result(response)
     }
 
+    /// Watch workers of a component
+    ///
+    /// Streams the metadata of the component's workers, optionally filtered, as server-sent events.
+    /// The filter syntax is the same as for `get_workers_metadata`.
+    /// The current state is sent first as `Added` events, followed by `Added`, `Modified` and `Deleted`
+    /// events whenever the workers change. Every batch of events ends with a `Bookmark` event
+    /// carrying a cursor; passing it as `cursor` when reconnecting avoids resending unchanged state.
+    #[oai(
+        path = "/:component_id/workers/watch",
+        method = "get",
+        operation_id = "watch_workers_metadata"
+    )]
+    async fn watch_workers_metadata(
+        &self,
+        component_id: Path<ComponentId>,
+        filter: Query<Option<Vec<String>>>,
+        cursor: Query<Option<String>>,
+        precise: Query<Option<bool>>,
+    ) -> Result<EventStream<BoxStream<'static, WorkerWatchEvent>>> {
+        let record = recorded_http_api_request!(
+            "watch_workers_metadata",
+            component_id = component_id.0.to_string()
+        );
+        let response = {
+            let filter = match filter.0 {
+                Some(filters) if !filters.is_empty() => {
+                    Some(WorkerFilter::from(filters).map_err(|e| {
+                        WorkerApiBaseError::BadRequest(Json(ErrorsBody { errors: vec![e] }))
+                    })?)
+                }
+                _ => None,
+            };
+
+            let events = self
+                .worker_watch
+                .watch(component_id.0, filter, precise.0.unwrap_or(false), cursor.0)
+                .filter_map(|event| async move {
+                    event
+                        .tap_err(|error| tracing::error!("Watching workers failed: {error}"))
+                        .ok()
+                        .map(WorkerWatchEvent::from)
+                })
+                .boxed();
+
+            Ok(EventStream::new(events).keep_alive(WATCH_KEEP_ALIVE))
+        };
+
+        record.result(response)
+    }
+
     /// Advanced search for workers
     ///
     /// ### Filter types
@@ -559,12 +679,14 @@ impl WorkerApi {
     /// | Status    | FilterComparator       | Status of worker               | `{ "type": "Status", "comparator": "Equal", "value": "Running" }`                             |
     /// | Env       | StringFilterComparator | Environment variable of worker | `{ "type": "Env", "name": "var1", "comparator": "Equal", "value": "value" }`                  |
     /// | CreatedAt | FilterComparator       | Creation time of worker        | `{ "type": "CreatedAt", "comparator": "Greater", "value": "2024-04-01T12:10:00Z" }`           |
+    /// | LastError | StringFilterComparator | Last error of worker           | `{ "type": "LastError", "comparator": "Like", "value": "out of memory" }`                     |
+    /// | Label     | StringFilterComparator | Label of worker                | `{ "type": "Label", "name": "tenant", "comparator": "Equal", "value": "acme" }`               |
     /// | And       |                        | And filter combinator          | `{ "type": "And", "filters": [ ... ] }`                                                       |
     /// | Or        |                        | Or filter combinator           | `{ "type": "Or", "filters": [ ... ] }`                                                        |
     /// | Not       |                        | Negates the specified filter   | `{ "type": "Not", "filter": { "type": "Version", "comparator": "GreaterEqual", "value": 0 } }`|
     ///
     /// ### Comparators
-    /// - StringFilterComparator: `Equal`, `NotEqual`, `Like`, `NotLike`
+    /// - StringFilterComparator: `Equal`, `NotEqual`, `Like`, `NotLike`, `StartsWith`
     /// - FilterComparator: `Equal`, `NotEqual`, `GreaterEqual`, `Greater`, `LessEqual`, `Less`
     ///
     /// Returns metadata about an existing component workers:
@@ -603,6 +725,43 @@ impl WorkerApi {
         record.result(response)
     }
 
+    /// Get the status summary of a component's workers
+    ///
+    /// Returns the number of workers per status, the ratio of failed workers and the latest
+    /// component version a worker was created with or updated to. The summary is maintained by the
+    /// worker executors on every status change, so it is returned without enumerating the workers.
+    /// The workers created before the summary was first requested are added to it once; set
+    /// `backfill` to add the missing workers again.
+    #[oai(
+        path = "/:component_id/workers/summary",
+        method = "get",
+        operation_id = "get_workers_status_summary"
+    )]
+    async fn get_workers_status_summary(
+        &self,
+        component_id: Path<ComponentId>,
+        backfill: Query<Option<bool>>,
+    ) -> Result<Json<WorkerStatusSummary>> {
+        let record = recorded_http_api_request!(
+            "get_workers_status_summary",
+            component_id = component_id.0.to_string()
+        );
+
+        let response = self
+            .worker_service
+            .get_status_summary(
+                &component_id.0,
+                backfill.0.unwrap_or(false),
+                empty_worker_metadata(),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(Json);
+
+        record.result(response)
+    }
+
     /// Resume a worker
     #[oai(
         path = "/:component_id/workers/:worker_name/resume",
@@ -661,11 +820,17 @@ impl WorkerApi {
     }
 
     /// Get or search the oplog of a worker
+    ///
+    /// The entries can be restricted to some kinds (`exported-function`, `host-call` or `error`)
+    /// with `kind`, and to the ones added in a time range with `after` and `before`, in addition
+    /// to (or instead of) a search `query`. With `decode`, the typed payloads of the entries are
+    /// also returned as JSON, so they can be read without decoding the raw entries.
     #[oai(
         path = "/:component_id/workers/:worker_name/oplog",
         method = "get",
         operation_id = "get_oplog"
     )]
+    #[allow(clippy::too_many_arguments)]
     async fn get_oplog(
         &self,
         component_id: Path<ComponentId>,
@@ -674,71 +839,95 @@ impl WorkerApi {
         count: Query<u64>,
         cursor: Query<Option<OplogCursor>>,
         query: Query<Option<String>>,
+        kind: Query<Option<Vec<PublicOplogEntryKind>>>,
+        after: Query<Option<Timestamp>>,
+        before: Query<Option<Timestamp>>,
+        decode: Query<Option<bool>>,
     ) -> Result<Json<GetOplogResponse>> {
         let worker_id = make_worker_id(component_id.0, worker_name.0)?;
         let record = recorded_http_api_request!("get_oplog", worker_id = worker_id.to_string());
 
-        match (from.0, query.0) {
+        let filter = PublicOplogEntryFilter {
+            kinds: kind.0.unwrap_or_default(),
+            after: after.0,
+            before: before.0,
+        };
+
+        let response = match (from.0, query.0) {
             (Some(_), Some(_)) => Err(WorkerApiBaseError::BadRequest(Json(ErrorsBody {
                 errors: vec![
                     "Cannot specify both the 'from' and the 'query' parameters".to_string()
                 ],
             }))),
-            (Some(from), None) => {
-                let response = self
-                    .worker_service
-                    .get_oplog(
-                        &worker_id,
-                        OplogIndex::from_u64(from),
-                        cursor.0,
-                        count.0,
-                        empty_worker_metadata(),
-                    )
-                    .instrument(record.span.clone())
-                    .await
-                    .map_err(|e| e.into())
-                    .map(Json);
-
-                record.result(response)
-            }
-            (None, Some(query)) => {
-                let response = self
-                    .worker_service
-                    .search_oplog(
-                        &worker_id,
-                        cursor.0,
-                        count.0,
-                        query,
-                        empty_worker_metadata(),
-                    )
-                    .instrument(record.span.clone())
-                    .await
-                    .map_err(|e| e.into())
-                    .map(Json);
-
-                record.result(response)
+            (Some(_), None) if !filter.is_empty() => {
+                Err(WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                    errors: vec![
+                        "Cannot specify both the 'from' parameter and the entry filters"
+                            .to_string(),
+                    ],
+                })))
             }
-            (None, None) => {
-                let response = self
-                    .worker_service
-                    .get_oplog(
-                        &worker_id,
-                        OplogIndex::INITIAL,
-                        cursor.0,
-                        count.0,
-                        empty_worker_metadata(),
-                    )
-                    .instrument(record.span.clone())
-                    .await
-                    .map_err(|e| e.into())
-                    .map(Json);
-
-                record.result(response)
+            (Some(from), None) => self
+                .worker_service
+                .get_oplog(
+                    &worker_id,
+                    OplogIndex::from_u64(from),
+                    cursor.0,
+                    count.0,
+                    empty_worker_metadata(),
+                )
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into()),
+            (None, query) if query.is_some() || !filter.is_empty() => self
+                .worker_service
+                .search_oplog(
+                    &worker_id,
+                    cursor.0,
+                    count.0,
+                    query.unwrap_or_default(),
+                    filter,
+                    empty_worker_metadata(),
+                )
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into()),
+            (None, _) => self
+                .worker_service
+                .get_oplog(
+                    &worker_id,
+                    OplogIndex::INITIAL,
+                    cursor.0,
+                    count.0,
+                    empty_worker_metadata(),
+                )
+                .instrument(record.span.clone())
+                .await
+                .map_err(|e| e.into()),
+        };
+
+        let response = response.map(|response| {
+            if decode.0.unwrap_or(false) {
+                Json(GetOplogResponse {
+                    entries: response
+                        .entries
+                        .into_iter()
+                        .map(|entry| entry.with_decoded_payload())
+                        .collect(),
+                    ..response
+                })
+            } else {
+                Json(response)
             }
-        }
+        });
+
+        record.result(response)
     }
 
     /// List files in a worker
+    ///
+    /// Directories with more entries than the configured limit are listed partially, by name,
+    /// and the response is marked as truncated.
     #[oai(
         path = "/:component_id/workers/:worker_name/files/:file_name",
         method = "get",
@@ -755,21 +944,17 @@ impl WorkerApi {
         let record = recorded_http_api_request!("get_file", worker_id = worker_id.to_string());
 
         let response = self
-            .worker_service
-            .list_directory(&worker_id, path, empty_worker_metadata())
+            .list_worker_directory(&worker_id, path)
             .instrument(record.span.clone())
             .await
-            .map(|s| {
-                Json(GetFilesResponse {
-                    nodes: s.into_iter().map(|n| n.into()).collect(),
-                })
-            })
-            .map_err(|e| e.into());
+            .map(Json);
 
         record.result(response)
     }
 
     /// Get contents of a file in a worker
+    ///
+    /// Files larger than the configured download limit are rejected.
     #[oai(
         path = "/:component_id/workers/:worker_name/file-contents/:file_name",
         method = "get",
@@ -786,16 +971,9 @@ impl WorkerApi {
         let record = recorded_http_api_request!("get_files", worker_id = worker_id.to_string());
 
         let response = self
-            .worker_service
-            .get_file_contents(&worker_id, path, empty_worker_metadata())
+            .download_worker_file(&worker_id, path)
             .instrument(record.span.clone())
-            .await
-            .map_err(|e| e.into())
-            .map(|bytes| {
-                Binary(Body::from_bytes_stream(bytes.map_err(|e| {
-                    std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
-                })))
-            });
+            .await;
 
         record.result(response)
     }
@@ -904,6 +1082,47 @@ impl WorkerApi {
         record.result(response)
     }
 
+    /// Fork a worker
+    ///
+    /// Creates a new worker of the same component with a copy of the oplog of the worker up to the given index,
+    /// replaying it to the state the worker had at that point. The metadata of the new worker records the worker
+    /// and the oplog index it was forked from, and the number of entries it has added since then.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/fork",
+        method = "post",
+        operation_id = "fork_worker"
+    )]
+    async fn fork_worker(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        request: Json<ForkWorkerRequest>,
+    ) -> Result<Json<ForkWorkerResponse>> {
+        let source_worker_id = make_worker_id(component_id.0.clone(), worker_name.0)?;
+        let target_worker_id = make_worker_id(component_id.0, request.0.target_worker_name)?;
+
+        let record = recorded_http_api_request!(
+            "fork_worker",
+            worker_id = source_worker_id.to_string(),
+            target_worker_id = target_worker_id.to_string(),
+        );
+
+        let response = self
+            .worker_service
+            .fork_worker(
+                &source_worker_id,
+                &target_worker_id,
+                request.0.oplog_index_cutoff,
+                empty_worker_metadata(),
+            )
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(|_| Json(ForkWorkerResponse {}));
+
+        record.result(response)
+    }
+
     /// Cancels a pending invocation if it has not started yet
     ///
     /// The invocation to be cancelled is identified by the idempotency key passed to the invoke API.
@@ -936,6 +1155,72 @@ impl WorkerApi {
 
         record.result(response)
     }
+
+    /// Get the logs of an invocation
+    ///
+    /// Returns the most recent stdout, stderr and log entries emitted by the invocation identified
+    /// by the idempotency key passed to the invoke API. Only a limited number of entries is kept
+    /// for each invocation, and for the recent invocations of the worker.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/invocations/:idempotency_key/logs",
+        method = "get",
+        operation_id = "get_invocation_logs"
+    )]
+    async fn get_invocation_logs(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        idempotency_key: Path<IdempotencyKey>,
+    ) -> Result<Json<GetInvocationLogsResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let record = recorded_http_api_request!(
+            "get_invocation_logs",
+            worker_id = worker_id.to_string(),
+            idempotency_key = idempotency_key.0.to_string(),
+        );
+
+        let response = self
+            .worker_service
+            .get_invocation_logs(&worker_id, &idempotency_key.0, empty_worker_metadata())
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(|entries| Json(GetInvocationLogsResponse { entries }));
+
+        record.result(response)
+    }
+
+    /// Update the labels of a worker
+    ///
+    /// Replaces all the labels of the worker with the given ones. Labels can be used to filter
+    /// the workers (as `label.<key>`) and are available to the API gateway as `worker.labels`.
+    #[oai(
+        path = "/:component_id/workers/:worker_name/labels",
+        method = "put",
+        operation_id = "update_worker_labels"
+    )]
+    async fn update_worker_labels(
+        &self,
+        component_id: Path<ComponentId>,
+        worker_name: Path<String>,
+        request: Json<UpdateWorkerLabelsRequest>,
+    ) -> Result<Json<UpdateWorkerLabelsResponse>> {
+        let worker_id = make_worker_id(component_id.0, worker_name.0)?;
+
+        let record =
+            recorded_http_api_request!("update_worker_labels", worker_id = worker_id.to_string());
+
+        let response = self
+            .worker_service
+            .update_labels(&worker_id, request.0.labels, empty_worker_metadata())
+            .instrument(record.span.clone())
+            .await
+            .map_err(|e| e.into())
+            .map(|_| Json(UpdateWorkerLabelsResponse {}));
+
+        record.result(response)
+    }
 }
 
 fn make_worker_id(
@@ -971,6 +1256,116 @@ fn make_target_worker_id(
     })
 }
 
+impl WorkerApi {
+    async fn list_worker_directory(
+        &self,
+        worker_id: &TargetWorkerId,
+        path: ComponentFilePath,
+    ) -> Result<GetFilesResponse> {
+        let nodes = self
+            .worker_service
+            .list_directory(worker_id, path, empty_worker_metadata())
+            .await?;
+
+        Ok(limit_directory_entries(
+            nodes,
+            self.worker_files.max_directory_entries,
+        ))
+    }
+
+    // The size of the file is looked up in its directory before downloading it, and the download
+    // is also cut short if the file grows over the limit while being streamed
+    async fn download_worker_file(
+        &self,
+        worker_id: &TargetWorkerId,
+        path: ComponentFilePath,
+    ) -> Result<Binary<Body>> {
+        let max_download_size = self.worker_files.max_download_size;
+
+        if let (Some(directory), Some(file_name)) =
+            (path.as_path().parent(), path.as_path().file_name())
+        {
+            let directory =
+                ComponentFilePath::from_abs_str(directory.as_str()).map_err(|error| {
+                    WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                        errors: vec![format!("Invalid file name: {error}")],
+                    }))
+                })?;
+            let nodes = self
+                .worker_service
+                .list_directory(worker_id, directory, empty_worker_metadata())
+                .await?;
+
+            if let Some(size) =
+                file_size(&nodes, file_name).filter(|size| *size > max_download_size)
+            {
+                return Err(WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                    errors: vec![format!(
+                        "File {path} has {size} bytes, more than the download limit of {max_download_size} bytes"
+                    )],
+                })));
+            }
+        }
+
+        let bytes = self
+            .worker_service
+            .get_file_contents(worker_id, path, empty_worker_metadata())
+            .await?;
+
+        Ok(Binary(Body::from_bytes_stream(limit_download_size(
+            bytes,
+            max_download_size,
+        ))))
+    }
+}
+
+// Lists up to the given number of entries of a directory, by name
+fn limit_directory_entries(
+    mut nodes: Vec<ComponentFileSystemNode>,
+    max_entries: usize,
+) -> GetFilesResponse {
+    nodes.sort_by(|left, right| left.name.cmp(&right.name));
+    let truncated = nodes.len() > max_entries;
+    nodes.truncate(max_entries);
+
+    GetFilesResponse {
+        nodes: nodes.into_iter().map(|node| node.into()).collect(),
+        truncated,
+    }
+}
+
+fn file_size(nodes: &[ComponentFileSystemNode], file_name: &str) -> Option<u64> {
+    nodes
+        .iter()
+        .find(|node| node.name == file_name)
+        .and_then(|node| match node.details {
+            ComponentFileSystemNodeDetails::File { size, .. } => Some(size),
+            ComponentFileSystemNodeDetails::Directory => None,
+        })
+}
+
+// Cuts the download short if the file grows over the limit while being streamed
+fn limit_download_size<E: std::fmt::Display>(
+    contents: impl futures::Stream<Item = std::result::Result<bytes::Bytes, E>>,
+    max_download_size: u64,
+) -> impl futures::Stream<Item = std::io::Result<bytes::Bytes>> {
+    let mut downloaded = 0u64;
+    contents
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+        .and_then(move |chunk| {
+            downloaded += chunk.len() as u64;
+            let result = if downloaded > max_download_size {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "File is larger than the download limit",
+                ))
+            } else {
+                Ok(chunk)
+            };
+            futures::future::ready(result)
+        })
+}
+
 fn make_component_file_path(
     name: String,
 ) -> std::result::Result<ComponentFilePath, WorkerApiBaseError> {
@@ -980,3 +1375,85 @@ fn make_component_file_path(
         }))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use golem_common::model::ComponentFilePermissions;
+    use std::time::SystemTime;
+    use test_r::test;
+
+    fn file(name: &str, size: u64) -> ComponentFileSystemNode {
+        ComponentFileSystemNode {
+            name: name.to_string(),
+            last_modified: SystemTime::UNIX_EPOCH,
+            details: ComponentFileSystemNodeDetails::File {
+                permissions: ComponentFilePermissions::ReadOnly,
+                size,
+            },
+        }
+    }
+
+    fn directory(name: &str) -> ComponentFileSystemNode {
+        ComponentFileSystemNode {
+            name: name.to_string(),
+            last_modified: SystemTime::UNIX_EPOCH,
+            details: ComponentFileSystemNodeDetails::Directory,
+        }
+    }
+
+    #[test]
+    fn directories_are_listed_up_to_the_limit_by_name() {
+        let nodes = vec![file("c.txt", 1), directory("a"), file("b.txt", 2)];
+
+        let response = limit_directory_entries(nodes.clone(), 2);
+        let names = response
+            .nodes
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "b.txt"]);
+        assert!(response.truncated);
+
+        let response = limit_directory_entries(nodes, 3);
+        assert_eq!(response.nodes.len(), 3);
+        assert!(!response.truncated);
+    }
+
+    #[test]
+    fn sizes_are_only_known_for_files() {
+        let nodes = vec![file("data.bin", 42), directory("logs")];
+
+        assert_eq!(file_size(&nodes, "data.bin"), Some(42));
+        assert_eq!(file_size(&nodes, "logs"), None);
+        assert_eq!(file_size(&nodes, "missing"), None);
+    }
+
+    #[test]
+    async fn downloads_are_cut_short_over_the_limit() {
+        let chunks = || {
+            futures::stream::iter(vec![
+                Ok::<_, String>(bytes::Bytes::from_static(b"0123")),
+                Ok(bytes::Bytes::from_static(b"4567")),
+            ])
+        };
+
+        let downloaded = limit_download_size(chunks(), 8).collect::<Vec<_>>().await;
+        assert_eq!(downloaded.len(), 2);
+        assert!(downloaded.iter().all(|chunk| chunk.is_ok()));
+
+        let downloaded = limit_download_size(chunks(), 6).collect::<Vec<_>>().await;
+        assert!(downloaded[0].is_ok());
+        assert!(downloaded[1].is_err());
+    }
+
+    #[test]
+    fn file_paths_are_relative_to_the_root_of_the_worker() {
+        assert_eq!(
+            make_component_file_path("dir/file.txt".to_string())
+                .unwrap()
+                .to_string(),
+            "/dir/file.txt"
+        );
+    }
+}
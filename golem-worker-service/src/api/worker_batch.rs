@@ -0,0 +1,257 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::service::worker::WorkerService;
+use golem_api_grpc::proto::golem::worker::InvocationContext;
+use golem_common::model::{TargetWorkerId, WorkerId};
+use golem_common::recorded_http_api_request;
+use golem_common::SafeDisplay;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::model::{validate_worker_name, ErrorsBody};
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
+use golem_worker_service_base::api::WorkerApiBaseError;
+use golem_worker_service_base::app_config::BatchInvocationConfig;
+use golem_worker_service_base::empty_worker_metadata;
+use golem_worker_service_base::http_invocation_context::grpc_invocation_context_from_request;
+use golem_worker_service_base::service::worker::{
+    apply_to_workers, invoke_batch, BatchInvocation, BatchInvocationResult, BatchInvokeRequest,
+    BatchInvokeResponse, BulkWorkerOperation, BulkWorkerOperationRequest,
+    BulkWorkerOperationResponse, InvocationParameters,
+};
+use poem::Request;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use tracing::Instrument;
+
+pub struct WorkerBatchApi {
+    pub worker_service: WorkerService,
+    pub batch_invocation: BatchInvocationConfig,
+}
+
+#[OpenApi(prefix_path = "/v1/workers", tag = ApiTags::Worker)]
+impl WorkerBatchApi {
+    /// Invoke functions of many workers and await their results
+    ///
+    /// Dispatches a batch of invocations, potentially of many workers of different components,
+    /// concurrently, with a limited number of them running at the same time. Each invocation
+    /// supplies its parameters the same way as the body of `invoke-and-await`.
+    /// Returns the result or the error of each invocation, in the order of the invocations;
+    /// a failing invocation does not fail the others.
+    #[oai(
+        path = "/batch-invoke",
+        method = "post",
+        operation_id = "batch_invoke_and_await"
+    )]
+    async fn batch_invoke_and_await(
+        &self,
+        request: &Request,
+        body: Json<BatchInvokeRequest>,
+    ) -> Result<Json<BatchInvokeResponse>, WorkerApiBaseError> {
+        let record = recorded_http_api_request!(
+            "batch_invoke_and_await",
+            invocations = body.0.invocations.len()
+        );
+
+        let invocation_context = grpc_invocation_context_from_request(request);
+
+        let response = invoke_batch(&self.batch_invocation, body.0.invocations, |invocation| {
+            self.invoke_and_await(invocation, invocation_context.clone())
+        })
+        .instrument(record.span.clone())
+        .await
+        .map(|results| Json(BatchInvokeResponse { results }))
+        .map_err(|error| {
+            WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                errors: vec![error],
+            }))
+        });
+
+        record.result(response)
+    }
+
+    /// Interrupt, resume, delete or update the workers of a component matching a filter
+    ///
+    /// Scans a page of `count` workers of the component matching the filter, with the same filter
+    /// types as `find_workers_metadata`, and applies the operation to each of them concurrently.
+    /// The number of workers scanned by a request is bounded by the maximum batch size.
+    /// With `dryRun`, the matching workers are only listed, and the operation is not applied.
+    ///
+    /// Returns the outcome of the operation for each worker of the page, a failing worker does not
+    /// fail the others. If `cursor` is returned, there are more workers to scan, and passing it to
+    /// the next request continues the operation with the next page.
+    #[oai(
+        path = "/bulk",
+        method = "post",
+        operation_id = "bulk_worker_operation"
+    )]
+    async fn bulk_worker_operation(
+        &self,
+        body: Json<BulkWorkerOperationRequest>,
+    ) -> Result<Json<BulkWorkerOperationResponse>, WorkerApiBaseError> {
+        let record = recorded_http_api_request!(
+            "bulk_worker_operation",
+            component_id = body.0.component_id.to_string(),
+            dry_run = body.0.dry_run.to_string()
+        );
+
+        let response = self
+            .bulk_worker_operation_internal(body.0)
+            .instrument(record.span.clone())
+            .await;
+
+        record.result(response)
+    }
+}
+
+impl WorkerBatchApi {
+    async fn bulk_worker_operation_internal(
+        &self,
+        request: BulkWorkerOperationRequest,
+    ) -> Result<Json<BulkWorkerOperationResponse>, WorkerApiBaseError> {
+        let (cursor, workers) = self
+            .worker_service
+            .find_metadata(
+                &request.component_id,
+                request.filter.clone(),
+                request.cursor.clone().unwrap_or_default(),
+                request.page_size(&self.batch_invocation),
+                request.precise.unwrap_or(false),
+                empty_worker_metadata(),
+            )
+            .await?;
+
+        let workers = workers.into_iter().map(|worker| worker.worker_id).collect();
+
+        let results = apply_to_workers(
+            &self.batch_invocation,
+            workers,
+            request.dry_run,
+            |worker_id| self.apply_bulk_operation(worker_id, &request.operation),
+        )
+        .await;
+
+        Ok(Json(BulkWorkerOperationResponse {
+            results,
+            cursor,
+            dry_run: request.dry_run,
+        }))
+    }
+
+    async fn apply_bulk_operation(
+        &self,
+        worker_id: WorkerId,
+        operation: &BulkWorkerOperation,
+    ) -> Result<(), String> {
+        match operation {
+            BulkWorkerOperation::Interrupt(interrupt) => {
+                self.worker_service
+                    .interrupt(
+                        &worker_id,
+                        interrupt.recover_immediately,
+                        empty_worker_metadata(),
+                    )
+                    .await
+            }
+            BulkWorkerOperation::Resume(resume) => {
+                self.worker_service
+                    .resume(&worker_id, empty_worker_metadata(), resume.force)
+                    .await
+            }
+            BulkWorkerOperation::Delete(_) => {
+                self.worker_service
+                    .delete(&worker_id, empty_worker_metadata())
+                    .await
+            }
+            BulkWorkerOperation::Update(update) => {
+                self.worker_service
+                    .update(
+                        &worker_id,
+                        update.mode.clone().into(),
+                        update.target_version,
+                        empty_worker_metadata(),
+                    )
+                    .await
+            }
+        }
+        .map_err(|error| error.to_safe_string())
+    }
+
+    async fn invoke_and_await(
+        &self,
+        invocation: BatchInvocation,
+        invocation_context: InvocationContext,
+    ) -> BatchInvocationResult {
+        match self
+            .invoke_and_await_internal(invocation, invocation_context)
+            .await
+        {
+            Ok(result) => BatchInvocationResult {
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => BatchInvocationResult {
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+
+    async fn invoke_and_await_internal(
+        &self,
+        invocation: BatchInvocation,
+        invocation_context: InvocationContext,
+    ) -> Result<TypeAnnotatedValue, String> {
+        if let Some(worker_name) = &invocation.worker_name {
+            validate_worker_name(worker_name)
+                .map_err(|error| format!("Invalid worker name: {error}"))?;
+        }
+
+        let worker_id = TargetWorkerId {
+            component_id: invocation.component_id,
+            worker_name: invocation.worker_name,
+        };
+
+        let params =
+            InvocationParameters::from_optionally_type_annotated_value_jsons(invocation.params)
+                .map_err(|errors| errors.join(", "))?;
+
+        match params {
+            InvocationParameters::TypedProtoVals(vals) => {
+                self.worker_service
+                    .validate_and_invoke_and_await_typed(
+                        &worker_id,
+                        invocation.idempotency_key,
+                        invocation.function,
+                        vals,
+                        Some(invocation_context),
+                        empty_worker_metadata(),
+                    )
+                    .await
+            }
+            InvocationParameters::RawJsonStrings(jsons) => {
+                self.worker_service
+                    .invoke_and_await_json(
+                        &worker_id,
+                        invocation.idempotency_key,
+                        invocation.function,
+                        jsons,
+                        Some(invocation_context),
+                        empty_worker_metadata(),
+                    )
+                    .await
+            }
+        }
+        .map_err(|error| error.to_safe_string())
+    }
+}
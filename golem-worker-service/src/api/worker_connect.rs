@@ -17,17 +17,22 @@ use std::time::Duration;
 use crate::service::worker::WorkerService;
 use futures::StreamExt;
 use golem_api_grpc::proto::golem::worker::LogEvent;
-use golem_common::model::{ComponentId, WorkerId};
+use golem_common::model::{
+    ComponentId, FilterComparator, ScanCursor, WorkerFilter, WorkerId, WorkerStatus,
+};
 use golem_common::recorded_http_api_request;
 use golem_service_base::model::{validate_worker_name, ErrorsBody};
 use golem_worker_service_base::api::WorkerApiBaseError;
 use golem_worker_service_base::empty_worker_metadata;
-use golem_worker_service_base::service::worker::{proxy_worker_connection, WorkerStream};
+use golem_worker_service_base::service::worker::{
+    proxy_multiplexed_worker_connection, proxy_worker_connection, WorkerEventFilter, WorkerStream,
+};
 use poem::web::websocket::WebSocket;
-use poem::web::{Data, Path};
+use poem::web::{Data, Path, Query};
 use poem::*;
 use poem_openapi::payload::Json;
-use tracing::Instrument;
+use serde::Deserialize;
+use tracing::{warn, Instrument};
 
 #[derive(Clone)]
 pub struct ConnectService {
@@ -40,12 +45,39 @@ impl ConnectService {
     }
 }
 
+/// Server-side filters of the streamed events, and for the component connection the comma
+/// separated names of the workers to connect to
+#[derive(Debug, Default, Deserialize)]
+pub struct ConnectQuery {
+    level: Option<String>,
+    message: Option<String>,
+    since: Option<String>,
+    workers: Option<String>,
+}
+
+impl ConnectQuery {
+    fn filter(&self) -> Result<WorkerEventFilter, Response> {
+        WorkerEventFilter::new(
+            self.level.as_deref(),
+            self.message.as_deref(),
+            self.since.as_deref(),
+        )
+        .map_err(bad_request)
+    }
+}
+
 #[handler]
 pub async fn ws(
     Path((component_id, worker_name)): Path<(ComponentId, String)>,
+    Query(query): Query<ConnectQuery>,
     websocket: WebSocket,
     Data(service): Data<&ConnectService>,
 ) -> Response {
+    let filter = match query.filter() {
+        Ok(filter) => filter,
+        Err(response) => return response,
+    };
+
     connect_to_worker(service, component_id, worker_name)
         .await
         .map(|(worker_id, worker_stream)| {
@@ -56,6 +88,45 @@ pub async fn ws(
                         let _ = proxy_worker_connection(
                             worker_id,
                             worker_stream,
+                            filter,
+                            sink,
+                            stream,
+                            PING_INTERVAL,
+                            PING_TIMEOUT,
+                        )
+                        .await;
+                    })
+                })
+                .into_response()
+        })
+        .unwrap_or_else(|err| err)
+}
+
+/// Streams the events of multiple workers of a component, the listed ones or otherwise the running
+/// and idle ones
+#[handler]
+pub async fn ws_component(
+    Path(component_id): Path<ComponentId>,
+    Query(query): Query<ConnectQuery>,
+    websocket: WebSocket,
+    Data(service): Data<&ConnectService>,
+) -> Response {
+    let filter = match query.filter() {
+        Ok(filter) => filter,
+        Err(response) => return response,
+    };
+
+    connect_to_workers(service, &component_id, query.workers.as_deref())
+        .await
+        .map(|worker_streams| {
+            websocket
+                .on_upgrade(move |socket| {
+                    tokio::spawn(async move {
+                        let (sink, stream) = socket.split();
+                        let _ = proxy_multiplexed_worker_connection(
+                            component_id,
+                            worker_streams,
+                            filter,
                             sink,
                             stream,
                             PING_INTERVAL,
@@ -72,17 +143,91 @@ pub async fn ws(
 const PING_INTERVAL: Duration = Duration::from_secs(30);
 const PING_TIMEOUT: Duration = Duration::from_secs(15);
 
+// The maximal number of workers multiplexed into one connection
+const MAX_CONNECTED_WORKERS: usize = 100;
+
+fn bad_request(error: String) -> Response {
+    WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+        errors: vec![error],
+    }))
+    .into_response()
+}
+
+async fn connect_to_workers(
+    service: &ConnectService,
+    component_id: &ComponentId,
+    workers: Option<&str>,
+) -> Result<Vec<(WorkerId, WorkerStream<LogEvent>)>, Response> {
+    match workers {
+        Some(workers) => {
+            let worker_names = workers
+                .split(',')
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>();
+            if worker_names.len() > MAX_CONNECTED_WORKERS {
+                return Err(bad_request(format!(
+                    "At most {MAX_CONNECTED_WORKERS} workers can be connected at once"
+                )));
+            }
+
+            let mut worker_streams = Vec::with_capacity(worker_names.len());
+            for worker_name in worker_names {
+                worker_streams.push(
+                    connect_to_worker(service, component_id.clone(), worker_name.to_string())
+                        .await?,
+                );
+            }
+            Ok(worker_streams)
+        }
+        None => {
+            let filter =
+                WorkerFilter::new_status(FilterComparator::Equal, WorkerStatus::Running).or(
+                    WorkerFilter::new_status(FilterComparator::Equal, WorkerStatus::Idle),
+                );
+            let (_, workers) = service
+                .worker_service
+                .find_metadata(
+                    component_id,
+                    Some(filter),
+                    ScanCursor::default(),
+                    MAX_CONNECTED_WORKERS as u64,
+                    false,
+                    empty_worker_metadata(),
+                )
+                .await
+                .map_err(|error| WorkerApiBaseError::from(error).into_response())?;
+
+            // The workers may have stopped since they were listed, so they are skipped if they
+            // can no longer be connected to
+            let mut worker_streams = Vec::with_capacity(workers.len());
+            for worker in workers {
+                match connect_to_worker(
+                    service,
+                    component_id.clone(),
+                    worker.worker_id.worker_name.clone(),
+                )
+                .await
+                {
+                    Ok(worker_stream) => worker_streams.push(worker_stream),
+                    Err(_) => warn!(
+                        worker_id = worker.worker_id.to_string(),
+                        "Skipping worker which could not be connected to"
+                    ),
+                }
+            }
+            Ok(worker_streams)
+        }
+    }
+}
+
 async fn connect_to_worker(
     service: &ConnectService,
     component_id: ComponentId,
     worker_name: String,
 ) -> Result<(WorkerId, WorkerStream<LogEvent>), Response> {
-    validate_worker_name(&worker_name).map_err(|e| {
-        let error = WorkerApiBaseError::BadRequest(Json(ErrorsBody {
-            errors: vec![format!("Invalid worker name: {e}")],
-        }));
-        error.into_response()
-    })?;
+    validate_worker_name(&worker_name)
+        .map_err(|e| bad_request(format!("Invalid worker name: {e}")))?;
     let worker_id = WorkerId {
         component_id: component_id.clone(),
         worker_name: worker_name.clone(),
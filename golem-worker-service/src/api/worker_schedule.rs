@@ -0,0 +1,165 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_common::recorded_http_api_request;
+use golem_service_base::api_tags::ApiTags;
+use golem_service_base::auth::DefaultNamespace;
+use golem_service_base::model::ErrorsBody;
+use golem_worker_service_base::api::WorkerApiBaseError;
+use golem_worker_service_base::service::worker::{
+    WorkerSchedule, WorkerScheduleRequest, WorkerScheduleService,
+};
+use poem_openapi::param::Path;
+use poem_openapi::payload::Json;
+use poem_openapi::*;
+use std::sync::Arc;
+use tracing::Instrument;
+
+pub struct WorkerScheduleApi {
+    worker_schedule_service: Arc<dyn WorkerScheduleService<DefaultNamespace> + Sync + Send>,
+}
+
+impl WorkerScheduleApi {
+    pub fn new(
+        worker_schedule_service: Arc<dyn WorkerScheduleService<DefaultNamespace> + Sync + Send>,
+    ) -> Self {
+        Self {
+            worker_schedule_service,
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, WorkerApiBaseError>;
+
+#[OpenApi(prefix_path = "/v1/schedules", tag = ApiTags::Worker)]
+impl WorkerScheduleApi {
+    /// Create a worker schedule
+    ///
+    /// Schedules the invocation of a worker function on a cron expression, evaluated in the
+    /// given time zone. Runs missed by more than the misfire threshold of the worker service,
+    /// for example while it was down, are either skipped or triggered once, depending on the
+    /// misfire policy of the schedule.
+    #[oai(path = "/", method = "post", operation_id = "create_worker_schedule")]
+    async fn create(&self, payload: Json<WorkerScheduleRequest>) -> Result<Json<WorkerSchedule>> {
+        let record = recorded_http_api_request!(
+            "create_worker_schedule",
+            schedule_id = payload.0.schedule_id,
+            component_id = payload.0.component_id.to_string(),
+            worker_name = payload.0.worker_name
+        );
+
+        let response = self
+            .worker_schedule_service
+            .create(&DefaultNamespace::default(), payload.0)
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Get all worker schedules
+    #[oai(path = "/", method = "get", operation_id = "list_worker_schedules")]
+    async fn list(&self) -> Result<Json<Vec<WorkerSchedule>>> {
+        let record = recorded_http_api_request!("list_worker_schedules",);
+
+        let response = self
+            .worker_schedule_service
+            .get_all(&DefaultNamespace::default())
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Get a worker schedule
+    ///
+    /// Gets a schedule, along with the times of its next and last runs
+    #[oai(
+        path = "/:schedule_id",
+        method = "get",
+        operation_id = "get_worker_schedule"
+    )]
+    async fn get(&self, schedule_id: Path<String>) -> Result<Json<WorkerSchedule>> {
+        let record = recorded_http_api_request!("get_worker_schedule", schedule_id = schedule_id.0);
+
+        let response = self
+            .worker_schedule_service
+            .get(&DefaultNamespace::default(), &schedule_id.0)
+            .instrument(record.span.clone())
+            .await
+            .map(Json)
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+
+    /// Update a worker schedule
+    ///
+    /// Replaces the definition of a schedule. Its next run is computed from the time of the update.
+    #[oai(
+        path = "/:schedule_id",
+        method = "put",
+        operation_id = "update_worker_schedule"
+    )]
+    async fn update(
+        &self,
+        schedule_id: Path<String>,
+        payload: Json<WorkerScheduleRequest>,
+    ) -> Result<Json<WorkerSchedule>> {
+        let record =
+            recorded_http_api_request!("update_worker_schedule", schedule_id = schedule_id.0);
+
+        let response = if payload.0.schedule_id != schedule_id.0 {
+            Err(WorkerApiBaseError::BadRequest(Json(ErrorsBody {
+                errors: vec!["Unmatched url and body schedule ids.".to_string()],
+            })))
+        } else {
+            self.worker_schedule_service
+                .update(&DefaultNamespace::default(), payload.0)
+                .instrument(record.span.clone())
+                .await
+                .map(Json)
+                .map_err(|err| err.into())
+        };
+
+        record.result(response)
+    }
+
+    /// Delete a worker schedule
+    ///
+    /// Deletes a schedule, its runs which were already triggered are not affected
+    #[oai(
+        path = "/:schedule_id",
+        method = "delete",
+        operation_id = "delete_worker_schedule"
+    )]
+    async fn delete(&self, schedule_id: Path<String>) -> Result<Json<String>> {
+        let record =
+            recorded_http_api_request!("delete_worker_schedule", schedule_id = schedule_id.0);
+
+        let response = self
+            .worker_schedule_service
+            .delete(&DefaultNamespace::default(), &schedule_id.0)
+            .instrument(record.span.clone())
+            .await
+            .map(|_| Json("Worker schedule deleted".to_string()))
+            .map_err(|err| err.into());
+
+        record.result(response)
+    }
+}
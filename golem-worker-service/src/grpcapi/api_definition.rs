@@ -422,6 +422,7 @@ impl GrpcApiDefinitionService {
                 &api_definition_id,
                 &version,
                 &DefaultNamespace::default(),
+                &DefaultNamespace::default().to_string(),
                 &EmptyAuthCtx::default(),
             )
             .await?;
@@ -14,10 +14,12 @@
 
 use crate::grpcapi::api_definition::GrpcApiDefinitionService;
 use crate::grpcapi::worker::WorkerGrpcApi;
+use crate::grpcapi::worker_schedule::WorkerScheduleGrpcApi;
 use crate::service::Services;
 use futures_util::TryFutureExt;
 use golem_api_grpc::proto;
 use golem_api_grpc::proto::golem::apidefinition::v1::api_definition_service_server::ApiDefinitionServiceServer;
+use golem_api_grpc::proto::golem::worker::v1::worker_schedule_service_server::WorkerScheduleServiceServer;
 use golem_api_grpc::proto::golem::worker::v1::worker_service_server::WorkerServiceServer;
 use std::net::SocketAddr;
 use tokio::net::TcpListener;
@@ -29,6 +31,7 @@ use tracing::Instrument;
 
 mod api_definition;
 mod worker;
+mod worker_schedule;
 
 pub async fn start_grpc_server(
     addr: SocketAddr,
@@ -48,6 +51,10 @@ pub async fn start_grpc_server(
         .set_serving::<ApiDefinitionServiceServer<GrpcApiDefinitionService>>()
         .await;
 
+    health_reporter
+        .set_serving::<WorkerScheduleServiceServer<WorkerScheduleGrpcApi>>()
+        .await;
+
     let reflection_service = tonic_reflection::server::Builder::configure()
         .register_encoded_file_descriptor_set(proto::FILE_DESCRIPTOR_SET)
         .build_v1()
@@ -62,6 +69,8 @@ pub async fn start_grpc_server(
                     WorkerServiceServer::new(WorkerGrpcApi::new(
                         services.component_service.clone(),
                         services.worker_service.clone(),
+                        services.batch_invocation.clone(),
+                        services.worker_watch.clone(),
                     ))
                     .accept_compressed(CompressionEncoding::Gzip)
                     .send_compressed(CompressionEncoding::Gzip),
@@ -73,6 +82,13 @@ pub async fn start_grpc_server(
                     .accept_compressed(CompressionEncoding::Gzip)
                     .send_compressed(CompressionEncoding::Gzip),
                 )
+                .add_service(
+                    WorkerScheduleServiceServer::new(WorkerScheduleGrpcApi::new(
+                        services.worker_schedule_service.clone(),
+                    ))
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .send_compressed(CompressionEncoding::Gzip),
+                )
                 .serve_with_incoming(TcpListenerStream::new(listener))
                 .map_err(anyhow::Error::from)
                 .await
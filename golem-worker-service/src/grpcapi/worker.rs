@@ -13,37 +13,42 @@
 // limitations under the License.
 
 use crate::service::component::ComponentService;
+use crate::service::watch::WorkerWatch;
 use crate::service::worker::WorkerService;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::Stream;
 use futures::StreamExt;
 use golem_api_grpc::proto::golem::common::{Empty, ErrorBody};
 use golem_api_grpc::proto::golem::worker::v1::worker_service_server::WorkerService as GrpcWorkerService;
 use golem_api_grpc::proto::golem::worker::v1::{
-    activate_plugin_response, cancel_invocation_response, complete_promise_response,
-    deactivate_plugin_response, delete_worker_response, fork_worker_response, get_oplog_response,
-    get_worker_metadata_response, get_workers_metadata_response, interrupt_worker_response,
-    invoke_and_await_json_response, invoke_and_await_response, invoke_and_await_typed_response,
-    invoke_response, launch_new_worker_response, resume_worker_response, revert_worker_response,
-    search_oplog_response, update_worker_response, worker_error, worker_execution_error,
-    ActivatePluginRequest, ActivatePluginResponse, CancelInvocationRequest,
-    CancelInvocationResponse, CompletePromiseRequest, CompletePromiseResponse,
-    ConnectWorkerRequest, DeactivatePluginRequest, DeactivatePluginResponse, DeleteWorkerRequest,
-    DeleteWorkerResponse, ForkWorkerRequest, ForkWorkerResponse, GetOplogRequest, GetOplogResponse,
-    GetOplogSuccessResponse, GetWorkerMetadataRequest, GetWorkerMetadataResponse,
-    GetWorkersMetadataRequest, GetWorkersMetadataResponse, GetWorkersMetadataSuccessResponse,
-    InterruptWorkerRequest, InterruptWorkerResponse, InvokeAndAwaitJsonRequest,
-    InvokeAndAwaitJsonResponse, InvokeAndAwaitRequest, InvokeAndAwaitResponse,
+    activate_plugin_response, batch_invoke_and_await_response, cancel_invocation_response,
+    complete_promise_response, deactivate_plugin_response, delete_worker_response,
+    fork_worker_response, get_oplog_response, get_worker_metadata_response,
+    get_workers_metadata_response, interrupt_worker_response, invoke_and_await_json_response,
+    invoke_and_await_json_typed_response, invoke_and_await_response,
+    invoke_and_await_typed_response, invoke_response, launch_new_worker_response,
+    resume_worker_response, revert_worker_response, search_oplog_response, update_worker_response,
+    watch_workers_metadata_response, worker_error, worker_execution_error, ActivatePluginRequest,
+    ActivatePluginResponse, BatchInvokeAndAwaitRequest, BatchInvokeAndAwaitResponse,
+    BatchInvokeAndAwaitSuccessResponse, CancelInvocationRequest, CancelInvocationResponse,
+    CompletePromiseRequest, CompletePromiseResponse, ConnectWorkerRequest, DeactivatePluginRequest,
+    DeactivatePluginResponse, DeleteWorkerRequest, DeleteWorkerResponse, ForkWorkerRequest,
+    ForkWorkerResponse, GetOplogRequest, GetOplogResponse, GetOplogSuccessResponse,
+    GetWorkerMetadataRequest, GetWorkerMetadataResponse, GetWorkersMetadataRequest,
+    GetWorkersMetadataResponse, GetWorkersMetadataSuccessResponse, InterruptWorkerRequest,
+    InterruptWorkerResponse, InvokeAndAwaitJsonRequest, InvokeAndAwaitJsonResponse,
+    InvokeAndAwaitJsonTypedResponse, InvokeAndAwaitRequest, InvokeAndAwaitResponse,
     InvokeAndAwaitTypedResponse, InvokeJsonRequest, InvokeRequest, InvokeResponse,
     LaunchNewWorkerRequest, LaunchNewWorkerResponse, LaunchNewWorkerSuccessResponse,
     ResumeWorkerRequest, ResumeWorkerResponse, RevertWorkerRequest, RevertWorkerResponse,
     SearchOplogRequest, SearchOplogResponse, SearchOplogSuccessResponse, UnknownError,
-    UpdateWorkerRequest, UpdateWorkerResponse, WorkerError as GrpcWorkerError,
-    WorkerExecutionError,
+    UpdateWorkerRequest, UpdateWorkerResponse, WatchWorkersMetadataRequest,
+    WatchWorkersMetadataResponse, WorkerError as GrpcWorkerError, WorkerExecutionError,
 };
 use golem_api_grpc::proto::golem::worker::v1::{list_directory_response, GetFileContentsResponse};
 use golem_api_grpc::proto::golem::worker::{
-    InvokeResult, InvokeResultTyped, LogEvent, WorkerMetadata,
+    InvokeResult, InvokeResultTyped, InvokeResultValueAndType, LogEvent, WorkerMetadata,
 };
 use golem_common::grpc::{
     proto_component_id_string, proto_idempotency_key_string,
@@ -54,15 +59,22 @@ use golem_common::model::oplog::OplogIndex;
 use golem_common::model::{ComponentVersion, ScanCursor, WorkerFilter, WorkerId};
 use golem_common::recorded_grpc_api_request;
 use golem_service_base::auth::EmptyAuthCtx;
+use golem_wasm_rpc::protobuf::type_annotated_value::TypeAnnotatedValue;
 use golem_worker_service_base::api::WorkerTraceErrorKind;
+use golem_worker_service_base::app_config::BatchInvocationConfig;
 use golem_worker_service_base::empty_worker_metadata;
 use golem_worker_service_base::grpcapi::{
-    bad_request_error, bad_request_errors, error_to_status, parse_json_invoke_parameters,
-    validate_component_file_path, validate_protobuf_plugin_installation_id,
-    validate_protobuf_target_worker_id, validate_protobuf_worker_id, validated_worker_id,
+    bad_request_error, bad_request_errors, error_to_status, invoke_result_value_and_type,
+    parse_json_invoke_parameters, validate_component_file_path,
+    validate_protobuf_plugin_installation_id, validate_protobuf_target_worker_id,
+    validate_protobuf_worker_id, validated_worker_id,
+};
+use golem_worker_service_base::service::worker::{
+    invoke_batch, InvocationParameters, WorkerStream,
 };
-use golem_worker_service_base::service::worker::{InvocationParameters, WorkerStream};
 use std::pin::Pin;
+use std::sync::Arc;
+use std::time::SystemTime;
 use tap::TapFallible;
 use tonic::{Request, Response, Status};
 use tracing::Instrument;
@@ -70,13 +82,22 @@ use tracing::Instrument;
 pub struct WorkerGrpcApi {
     component_service: ComponentService,
     worker_service: WorkerService,
+    batch_invocation: BatchInvocationConfig,
+    worker_watch: Arc<WorkerWatch>,
 }
 
 impl WorkerGrpcApi {
-    pub fn new(component_service: ComponentService, worker_service: WorkerService) -> Self {
+    pub fn new(
+        component_service: ComponentService,
+        worker_service: WorkerService,
+        batch_invocation: BatchInvocationConfig,
+        worker_watch: Arc<WorkerWatch>,
+    ) -> Self {
         Self {
             component_service,
             worker_service,
+            batch_invocation,
+            worker_watch,
         }
     }
 }
@@ -287,6 +308,66 @@ impl GrpcWorkerService for WorkerGrpcApi {
         }))
     }
 
+    async fn invoke_and_await_json_typed(
+        &self,
+        request: Request<InvokeAndAwaitJsonRequest>,
+    ) -> Result<Response<InvokeAndAwaitJsonTypedResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "invoke_and_await_json_typed",
+            worker_id = proto_target_worker_id_string(&request.worker_id),
+            idempotency_key = proto_idempotency_key_string(&request.idempotency_key),
+            function = request.function,
+            context_parent_worker_id =
+                proto_invocation_context_parent_worker_id_string(&request.context)
+        );
+
+        let response = match self
+            .invoke_and_await_json_typed(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(result) => record.succeed(invoke_and_await_json_typed_response::Result::Success(
+                result,
+            )),
+            Err(error) => record.fail(
+                invoke_and_await_json_typed_response::Result::Error(error.clone()),
+                &WorkerTraceErrorKind(&error),
+            ),
+        };
+
+        Ok(Response::new(InvokeAndAwaitJsonTypedResponse {
+            result: Some(response),
+        }))
+    }
+
+    async fn batch_invoke_and_await(
+        &self,
+        request: Request<BatchInvokeAndAwaitRequest>,
+    ) -> Result<Response<BatchInvokeAndAwaitResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "batch_invoke_and_await",
+            invocations = request.invocations.len()
+        );
+
+        let response = match self
+            .batch_invoke_and_await(request)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(result) => record.succeed(batch_invoke_and_await_response::Result::Success(result)),
+            Err(error) => record.fail(
+                batch_invoke_and_await_response::Result::Error(error.clone()),
+                &WorkerTraceErrorKind(&error),
+            ),
+        };
+
+        Ok(Response::new(BatchInvokeAndAwaitResponse {
+            result: Some(response),
+        }))
+    }
+
     async fn invoke_and_await_typed(
         &self,
         request: Request<InvokeAndAwaitRequest>,
@@ -457,6 +538,35 @@ impl GrpcWorkerService for WorkerGrpcApi {
         }))
     }
 
+    type WatchWorkersMetadataStream =
+        Pin<Box<dyn Stream<Item = Result<WatchWorkersMetadataResponse, Status>> + Send + 'static>>;
+
+    async fn watch_workers_metadata(
+        &self,
+        request: Request<WatchWorkersMetadataRequest>,
+    ) -> Result<Response<Self::WatchWorkersMetadataStream>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "watch_workers_metadata",
+            component_id = proto_component_id_string(&request.component_id),
+        );
+
+        let stream = match self.watch_workers_metadata(request) {
+            Ok(stream) => record.succeed(stream),
+            Err(error) => {
+                let res = WatchWorkersMetadataResponse {
+                    result: Some(watch_workers_metadata_response::Result::Error(
+                        error.clone(),
+                    )),
+                };
+                let err_stream: Self::WatchWorkersMetadataStream =
+                    Box::pin(tokio_stream::iter(vec![Ok(res)]));
+                record.fail(err_stream, &WorkerTraceErrorKind(&error))
+            }
+        };
+        Ok(Response::new(stream))
+    }
+
     async fn update_worker(
         &self,
         request: Request<UpdateWorkerRequest>,
@@ -774,6 +884,8 @@ impl WorkerGrpcApi {
                 latest_component.versioned_component_id.version,
                 request.args,
                 request.env,
+                request.wasi_config_vars,
+                request.labels,
                 empty_worker_metadata(),
             )
             .await?;
@@ -912,6 +1024,46 @@ impl WorkerGrpcApi {
         Ok((new_cursor, result))
     }
 
+    fn watch_workers_metadata(
+        &self,
+        request: WatchWorkersMetadataRequest,
+    ) -> Result<<Self as GrpcWorkerService>::WatchWorkersMetadataStream, GrpcWorkerError> {
+        let component_id: golem_common::model::ComponentId = request
+            .component_id
+            .ok_or_else(|| bad_request_error("Missing component id"))?
+            .try_into()
+            .map_err(|_| bad_request_error("Invalid component id"))?;
+
+        let filter: Option<WorkerFilter> =
+            match request.filter {
+                Some(f) => Some(f.try_into().map_err(|error| {
+                    bad_request_error(format!("Invalid worker filter: {error}"))
+                })?),
+                _ => None,
+            };
+
+        let stream = self
+            .worker_watch
+            .watch(component_id, filter, request.precise, request.cursor)
+            .map(|event| {
+                let result = match event {
+                    Ok(event) => watch_workers_metadata_response::Result::Success(event.into()),
+                    Err(error) => watch_workers_metadata_response::Result::Error(GrpcWorkerError {
+                        error: Some(worker_error::Error::InternalError(WorkerExecutionError {
+                            error: Some(worker_execution_error::Error::Unknown(UnknownError {
+                                details: format!("Failed to watch workers: {error}"),
+                            })),
+                        })),
+                    }),
+                };
+                Ok(WatchWorkersMetadataResponse {
+                    result: Some(result),
+                })
+            });
+
+        Ok(Box::pin(stream))
+    }
+
     async fn interrupt_worker(
         &self,
         request: InterruptWorkerRequest,
@@ -936,6 +1088,13 @@ impl WorkerGrpcApi {
             .invoke_parameters
             .ok_or_else(|| bad_request_error("Missing invoke parameters"))?;
 
+        let invoke_at = request
+            .invoke_at
+            .map(SystemTime::try_from)
+            .transpose()
+            .map_err(|err| bad_request_error(format!("Invalid invoke_at: {err}")))?
+            .map(DateTime::<Utc>::from);
+
         self.worker_service
             .invoke(
                 &worker_id,
@@ -943,6 +1102,7 @@ impl WorkerGrpcApi {
                 request.function,
                 params.params,
                 request.context,
+                invoke_at,
                 empty_worker_metadata(),
             )
             .await?;
@@ -962,6 +1122,13 @@ impl WorkerGrpcApi {
             .ok_or_else(|| bad_request_error("Missing idempotency key"))?
             .into();
 
+        let invoke_at = request
+            .invoke_at
+            .map(SystemTime::try_from)
+            .transpose()
+            .map_err(|err| bad_request_error(format!("Invalid invoke_at: {err}")))?
+            .map(DateTime::<Utc>::from);
+
         match params {
             InvocationParameters::TypedProtoVals(params) => {
                 self.worker_service
@@ -971,6 +1138,7 @@ impl WorkerGrpcApi {
                         request.function,
                         params,
                         request.context,
+                        invoke_at,
                         empty_worker_metadata(),
                     )
                     .await?
@@ -983,6 +1151,7 @@ impl WorkerGrpcApi {
                         request.function,
                         jsons,
                         request.context,
+                        invoke_at,
                         empty_worker_metadata(),
                     )
                     .await?
@@ -1017,10 +1186,10 @@ impl WorkerGrpcApi {
         Ok(result)
     }
 
-    async fn invoke_and_await_json(
+    async fn invoke_and_await_json_annotated(
         &self,
         request: InvokeAndAwaitJsonRequest,
-    ) -> Result<String, GrpcWorkerError> {
+    ) -> Result<TypeAnnotatedValue, GrpcWorkerError> {
         let worker_id = validate_protobuf_target_worker_id(request.worker_id)?;
 
         let params = parse_json_invoke_parameters(&request.invoke_parameters)?;
@@ -1059,6 +1228,15 @@ impl WorkerGrpcApi {
             }
         };
 
+        Ok(result)
+    }
+
+    async fn invoke_and_await_json(
+        &self,
+        request: InvokeAndAwaitJsonRequest,
+    ) -> Result<String, GrpcWorkerError> {
+        let result = self.invoke_and_await_json_annotated(request).await?;
+
         Ok(serde_json::to_value(result)
             .map_err(|err| GrpcWorkerError {
                 error: Some(worker_error::Error::InternalError(WorkerExecutionError {
@@ -1070,6 +1248,41 @@ impl WorkerGrpcApi {
             .to_string())
     }
 
+    async fn invoke_and_await_json_typed(
+        &self,
+        request: InvokeAndAwaitJsonRequest,
+    ) -> Result<InvokeResultValueAndType, GrpcWorkerError> {
+        let result = self.invoke_and_await_json_annotated(request).await?;
+
+        Ok(InvokeResultValueAndType {
+            result: Some(invoke_result_value_and_type(result)?),
+        })
+    }
+
+    async fn batch_invoke_and_await(
+        &self,
+        request: BatchInvokeAndAwaitRequest,
+    ) -> Result<BatchInvokeAndAwaitSuccessResponse, GrpcWorkerError> {
+        let results = invoke_batch(
+            &self.batch_invocation,
+            request.invocations,
+            |invocation| async move {
+                let result = match self.invoke_and_await_json_typed(invocation).await {
+                    Ok(result) => invoke_and_await_json_typed_response::Result::Success(result),
+                    Err(error) => invoke_and_await_json_typed_response::Result::Error(error),
+                };
+
+                InvokeAndAwaitJsonTypedResponse {
+                    result: Some(result),
+                }
+            },
+        )
+        .await
+        .map_err(bad_request_error)?;
+
+        Ok(BatchInvokeAndAwaitSuccessResponse { results })
+    }
+
     async fn invoke_and_await_typed(
         &self,
         request: InvokeAndAwaitRequest,
@@ -1097,6 +1310,7 @@ impl WorkerGrpcApi {
             .await?;
 
         Ok(InvokeResultTyped {
+            value_and_type: Some(invoke_result_value_and_type(result.clone())?),
             result: Some(golem_wasm_rpc::protobuf::TypeAnnotatedValue {
                 type_annotated_value: Some(result),
             }),
@@ -1200,6 +1414,10 @@ impl WorkerGrpcApi {
                 request.cursor.map(|cursor| cursor.into()),
                 request.count,
                 request.query,
+                request
+                    .filter
+                    .map(|filter| filter.into())
+                    .unwrap_or_default(),
                 empty_worker_metadata(),
             )
             .await?;
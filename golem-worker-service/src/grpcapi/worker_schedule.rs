@@ -0,0 +1,236 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+use golem_api_grpc::proto::golem::common::Empty;
+use golem_api_grpc::proto::golem::worker::v1::worker_schedule_service_server::WorkerScheduleService as GrpcWorkerScheduleService;
+use golem_api_grpc::proto::golem::worker::v1::{
+    delete_worker_schedule_response, get_worker_schedules_response, worker_schedule_response,
+    CreateWorkerScheduleRequest, DeleteWorkerScheduleRequest, DeleteWorkerScheduleResponse,
+    GetWorkerScheduleRequest, GetWorkerSchedulesRequest, GetWorkerSchedulesResponse,
+    UpdateWorkerScheduleRequest, WorkerError as GrpcWorkerError, WorkerSchedule,
+    WorkerScheduleList, WorkerScheduleRequest, WorkerScheduleResponse,
+};
+use golem_common::recorded_grpc_api_request;
+use golem_service_base::auth::DefaultNamespace;
+use golem_worker_service_base::api::WorkerTraceErrorKind;
+use golem_worker_service_base::grpcapi::bad_request_error;
+use golem_worker_service_base::service::worker::{
+    WorkerScheduleRequest as ScheduleRequest, WorkerScheduleService,
+};
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::Instrument;
+
+pub struct WorkerScheduleGrpcApi {
+    worker_schedule_service: Arc<dyn WorkerScheduleService<DefaultNamespace> + Sync + Send>,
+}
+
+impl WorkerScheduleGrpcApi {
+    pub fn new(
+        worker_schedule_service: Arc<dyn WorkerScheduleService<DefaultNamespace> + Sync + Send>,
+    ) -> Self {
+        Self {
+            worker_schedule_service,
+        }
+    }
+}
+
+#[async_trait]
+impl GrpcWorkerScheduleService for WorkerScheduleGrpcApi {
+    async fn create_worker_schedule(
+        &self,
+        request: Request<CreateWorkerScheduleRequest>,
+    ) -> Result<Response<WorkerScheduleResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "create_worker_schedule",
+            schedule_id = request.schedule.as_ref().map(|s| s.schedule_id.clone()),
+        );
+
+        let result = match self
+            .create_worker_schedule(request.schedule)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(schedule) => record.succeed(worker_schedule_response::Result::Success(schedule)),
+            Err(error) => record.fail(
+                worker_schedule_response::Result::Error(error.clone()),
+                &WorkerTraceErrorKind(&error),
+            ),
+        };
+
+        Ok(Response::new(WorkerScheduleResponse {
+            result: Some(result),
+        }))
+    }
+
+    async fn update_worker_schedule(
+        &self,
+        request: Request<UpdateWorkerScheduleRequest>,
+    ) -> Result<Response<WorkerScheduleResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "update_worker_schedule",
+            schedule_id = request.schedule.as_ref().map(|s| s.schedule_id.clone()),
+        );
+
+        let result = match self
+            .update_worker_schedule(request.schedule)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(schedule) => record.succeed(worker_schedule_response::Result::Success(schedule)),
+            Err(error) => record.fail(
+                worker_schedule_response::Result::Error(error.clone()),
+                &WorkerTraceErrorKind(&error),
+            ),
+        };
+
+        Ok(Response::new(WorkerScheduleResponse {
+            result: Some(result),
+        }))
+    }
+
+    async fn get_worker_schedule(
+        &self,
+        request: Request<GetWorkerScheduleRequest>,
+    ) -> Result<Response<WorkerScheduleResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "get_worker_schedule",
+            schedule_id = request.schedule_id.clone(),
+        );
+
+        let result = match self
+            .worker_schedule_service
+            .get(&DefaultNamespace::default(), &request.schedule_id)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(schedule) => {
+                record.succeed(worker_schedule_response::Result::Success(schedule.into()))
+            }
+            Err(error) => {
+                let error = GrpcWorkerError::from(error);
+                record.fail(
+                    worker_schedule_response::Result::Error(error.clone()),
+                    &WorkerTraceErrorKind(&error),
+                )
+            }
+        };
+
+        Ok(Response::new(WorkerScheduleResponse {
+            result: Some(result),
+        }))
+    }
+
+    async fn get_worker_schedules(
+        &self,
+        _request: Request<GetWorkerSchedulesRequest>,
+    ) -> Result<Response<GetWorkerSchedulesResponse>, Status> {
+        let record = recorded_grpc_api_request!("get_worker_schedules",);
+
+        let result = match self
+            .worker_schedule_service
+            .get_all(&DefaultNamespace::default())
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(schedules) => record.succeed(get_worker_schedules_response::Result::Success(
+                WorkerScheduleList {
+                    schedules: schedules.into_iter().map(WorkerSchedule::from).collect(),
+                },
+            )),
+            Err(error) => {
+                let error = GrpcWorkerError::from(error);
+                record.fail(
+                    get_worker_schedules_response::Result::Error(error.clone()),
+                    &WorkerTraceErrorKind(&error),
+                )
+            }
+        };
+
+        Ok(Response::new(GetWorkerSchedulesResponse {
+            result: Some(result),
+        }))
+    }
+
+    async fn delete_worker_schedule(
+        &self,
+        request: Request<DeleteWorkerScheduleRequest>,
+    ) -> Result<Response<DeleteWorkerScheduleResponse>, Status> {
+        let request = request.into_inner();
+        let record = recorded_grpc_api_request!(
+            "delete_worker_schedule",
+            schedule_id = request.schedule_id.clone(),
+        );
+
+        let result = match self
+            .worker_schedule_service
+            .delete(&DefaultNamespace::default(), &request.schedule_id)
+            .instrument(record.span.clone())
+            .await
+        {
+            Ok(()) => record.succeed(delete_worker_schedule_response::Result::Success(Empty {})),
+            Err(error) => {
+                let error = GrpcWorkerError::from(error);
+                record.fail(
+                    delete_worker_schedule_response::Result::Error(error.clone()),
+                    &WorkerTraceErrorKind(&error),
+                )
+            }
+        };
+
+        Ok(Response::new(DeleteWorkerScheduleResponse {
+            result: Some(result),
+        }))
+    }
+}
+
+impl WorkerScheduleGrpcApi {
+    async fn create_worker_schedule(
+        &self,
+        request: Option<WorkerScheduleRequest>,
+    ) -> Result<WorkerSchedule, GrpcWorkerError> {
+        let request: ScheduleRequest = request
+            .ok_or_else(|| bad_request_error("Missing schedule"))?
+            .try_into()
+            .map_err(bad_request_error)?;
+
+        let schedule = self
+            .worker_schedule_service
+            .create(&DefaultNamespace::default(), request)
+            .await?;
+
+        Ok(schedule.into())
+    }
+
+    async fn update_worker_schedule(
+        &self,
+        request: Option<WorkerScheduleRequest>,
+    ) -> Result<WorkerSchedule, GrpcWorkerError> {
+        let request: ScheduleRequest = request
+            .ok_or_else(|| bad_request_error("Missing schedule"))?
+            .try_into()
+            .map_err(bad_request_error)?;
+
+        let schedule = self
+            .worker_schedule_service
+            .update(&DefaultNamespace::default(), request)
+            .await?;
+
+        Ok(schedule.into())
+    }
+}
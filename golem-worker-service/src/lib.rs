@@ -28,11 +28,12 @@ use poem_openapi::OpenApiService;
 use prometheus::Registry;
 use std::net::{Ipv4Addr, SocketAddrV4};
 use tokio::task::JoinSet;
-use tracing::Instrument;
+use tracing::{debug, error, info, Instrument};
 
 pub mod api;
 pub mod config;
 pub mod grpcapi;
+pub mod local_gateway;
 pub mod service;
 
 #[cfg(test)]
@@ -96,6 +97,8 @@ impl WorkerService {
         let grpc_port = self.start_grpc_server(join_set).await?;
         let http_port = self.start_http_server(join_set).await?;
         let custom_request_port = self.start_api_gateway_server(join_set).await?;
+        self.start_api_definition_trash_purge(join_set);
+        self.start_worker_scheduler(join_set);
 
         Ok(RunDetails {
             http_port,
@@ -111,6 +114,8 @@ impl WorkerService {
     ) -> Result<TrafficReadyEndpoints, anyhow::Error> {
         let grpc_port = self.start_grpc_server(join_set).await?;
         let custom_request_port = self.start_api_gateway_server(join_set).await?;
+        self.start_api_definition_trash_purge(join_set);
+        self.start_worker_scheduler(join_set);
         let api_endpoint = api::make_open_api_service(&self.services).boxed();
         Ok(TrafficReadyEndpoints {
             grpc_port,
@@ -140,6 +145,54 @@ impl WorkerService {
         .map_err(|err| anyhow!(err).context("gRPC server failed"))
     }
 
+    /// Periodically purges the API definitions which have been in the trash longer than the retention period
+    fn start_api_definition_trash_purge(&self, join_set: &mut JoinSet<anyhow::Result<()>>) {
+        let definition_service = self.services.definition_service.clone();
+        let trash = self.config.api_definition_trash.clone();
+
+        join_set.spawn(
+            async move {
+                let mut interval = tokio::time::interval(trash.purge_interval);
+                loop {
+                    interval.tick().await;
+                    match definition_service.purge_expired(trash.retention).await {
+                        Ok(0) => {}
+                        Ok(purged) => info!("Purged {purged} API definitions from the trash"),
+                        Err(err) => {
+                            error!("Failed to purge API definitions from the trash: {err}")
+                        }
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Periodically triggers the due runs of the worker schedules
+    fn start_worker_scheduler(&self, join_set: &mut JoinSet<anyhow::Result<()>>) {
+        if !self.config.scheduler.enabled {
+            return;
+        }
+
+        let worker_schedule_service = self.services.worker_schedule_service.clone();
+        let poll_interval = self.config.scheduler.poll_interval;
+
+        join_set.spawn(
+            async move {
+                let mut interval = tokio::time::interval(poll_interval);
+                loop {
+                    interval.tick().await;
+                    match worker_schedule_service.trigger_due().await {
+                        Ok(0) => {}
+                        Ok(triggered) => debug!("Triggered {triggered} scheduled invocations"),
+                        Err(err) => error!("Failed to trigger the due worker schedules: {err}"),
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
     async fn start_http_server(
         &self,
         join_set: &mut JoinSet<anyhow::Result<()>>,
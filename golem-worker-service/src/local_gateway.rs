@@ -0,0 +1,271 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::api;
+use crate::api::api_definition::link_rib_libraries;
+use crate::service::Services;
+use crate::WorkerService;
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use golem_common::config::{DbConfig, DbSqliteConfig};
+use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
+use golem_service_base::db;
+use golem_service_base::migration::{IncludedMigrationsDir, Migrations};
+use golem_worker_service_base::api::HttpApiDefinitionRequest;
+use golem_worker_service_base::app_config::{GatewaySessionStorageConfig, WorkerServiceBaseConfig};
+use golem_worker_service_base::gateway_api_definition::http::{
+    CompiledHttpApiDefinition, HttpApiDefinitionRequest as CoreHttpApiDefinitionRequest,
+};
+use golem_worker_service_base::gateway_api_deployment::ApiSiteString;
+use golem_worker_service_base::gateway_execution::api_definition_lookup::{
+    ApiDefinitionLookupError, HttpApiDefinitionsLookup,
+};
+use poem::listener::{Acceptor, Listener};
+use poem::middleware::{OpenTelemetryMetrics, Tracing};
+use poem::EndpointExt;
+use std::path::{Path, PathBuf};
+use tokio::task::JoinSet;
+use tracing::{info, Instrument};
+
+// The gateway serving the API definition of a local file on localhost, instead of the whole
+// worker service. It keeps its own state (sessions, dead letters, request fixtures, API keys...)
+// in SQLite databases of its `state_dir`, so neither the database of the worker service
+// nor Redis has to be reachable, and none of the APIs, the purge of the trash or the scheduler
+// are started. The bindings invoke the workers of the executors found through the configured
+// routing table, of either a local or a remote cluster.
+pub struct LocalGateway {
+    config: WorkerServiceBaseConfig,
+    services: Services,
+}
+
+impl LocalGateway {
+    pub async fn new(config: WorkerServiceBaseConfig) -> Result<Self, anyhow::Error> {
+        let state_dir = config
+            .local_gateway
+            .state_dir
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("golem-local-gateway"));
+
+        tokio::fs::create_dir_all(&state_dir)
+            .await
+            .with_context(|| format!("Failed to create {}", state_dir.display()))?;
+
+        let sqlite_config = |file: &str| DbSqliteConfig {
+            database: state_dir.join(file).to_string_lossy().to_string(),
+            max_connections: 10,
+        };
+
+        let db_config = sqlite_config("local-gateway.db");
+        db::sqlite_migrate(
+            &db_config,
+            IncludedMigrationsDir::new(WorkerService::db_migrations()).sqlite_migrations(),
+        )
+        .await
+        .context("Local gateway DB migration")?;
+
+        let config = WorkerServiceBaseConfig {
+            db: DbConfig::Sqlite(db_config),
+            gateway_session_storage: GatewaySessionStorageConfig::Sqlite(sqlite_config(
+                "local-gateway-sessions.db",
+            )),
+            ..config
+        };
+
+        let services = Services::new(&config)
+            .await
+            .map_err(|err| anyhow!(err).context("Local gateway initialization"))?;
+
+        Ok(Self { config, services })
+    }
+
+    /// Compiles the API definition of a local file and serves it on localhost, on the custom
+    /// request port, without deploying it.
+    pub async fn start(
+        &self,
+        definition_file: &Path,
+        join_set: &mut JoinSet<anyhow::Result<()>>,
+    ) -> Result<u16, anyhow::Error> {
+        let definition = compile_definition_file(&self.services, definition_file).await?;
+
+        info!(
+            "Serving API definition {} {} from {}",
+            definition.id,
+            definition.version,
+            definition_file.display()
+        );
+
+        let route = api::local_request_route(&self.services, definition)
+            .with(OpenTelemetryMetrics::new())
+            .with(Tracing);
+
+        let poem_listener = poem::listener::TcpListener::bind(format!(
+            "127.0.0.1:{}",
+            self.config.custom_request_port
+        ));
+        let acceptor = poem_listener.into_acceptor().await?;
+        let port = acceptor.local_addr()[0]
+            .as_socket_addr()
+            .expect("socket address")
+            .port();
+
+        join_set.spawn(
+            async move {
+                poem::Server::new_with_acceptor(acceptor)
+                    .run(route)
+                    .await
+                    .map_err(|err| anyhow!(err).context("Local API Gateway server failed"))
+            }
+            .in_current_span(),
+        );
+
+        Ok(port)
+    }
+}
+
+// Serves a single API definition under every host, so it can be tried out on localhost
+// without deploying it to a site
+pub struct LocalHttpApiDefinitionLookup {
+    definition: CompiledHttpApiDefinition<DefaultNamespace>,
+}
+
+impl LocalHttpApiDefinitionLookup {
+    pub fn new(definition: CompiledHttpApiDefinition<DefaultNamespace>) -> Self {
+        Self { definition }
+    }
+}
+
+#[async_trait]
+impl HttpApiDefinitionsLookup<DefaultNamespace> for LocalHttpApiDefinitionLookup {
+    async fn get(
+        &self,
+        _host: &ApiSiteString,
+    ) -> Result<Vec<CompiledHttpApiDefinition<DefaultNamespace>>, ApiDefinitionLookupError> {
+        Ok(vec![self.definition.clone()])
+    }
+}
+
+/// Parses the `api-definition serve --local <file>` command, which serves the API definition of
+/// the file on localhost instead of running the service. Returns None for the other arguments.
+pub fn parse_serve_local_command(args: &[String]) -> Option<Result<PathBuf, String>> {
+    match args {
+        [command, subcommand, rest @ ..]
+            if command == "api-definition" && subcommand == "serve" =>
+        {
+            let definition_file = match rest {
+                [flag, file] if flag == "--local" => Some(file.as_str()),
+                [flag] => flag.strip_prefix("--local="),
+                _ => None,
+            };
+            Some(
+                definition_file
+                    .filter(|file| !file.is_empty())
+                    .map(PathBuf::from)
+                    .ok_or_else(|| {
+                        "Usage: golem-worker-service api-definition serve --local <file>"
+                            .to_string()
+                    }),
+            )
+        }
+        _ => None,
+    }
+}
+
+// Reads a JSON or YAML API definition from a file and compiles its Rib bindings against the
+// components, the same way as on creation, but without storing it
+pub async fn compile_definition_file(
+    services: &Services,
+    path: &Path,
+) -> anyhow::Result<CompiledHttpApiDefinition<DefaultNamespace>> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read API definition {}", path.display()))?;
+
+    let mut definition: HttpApiDefinitionRequest = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse API definition {}", path.display()))?;
+
+    link_rib_libraries(services.rib_library_service.as_ref(), &mut definition)
+        .await
+        .map_err(|err| anyhow!("Failed to link the Rib libraries: {err:?}"))?;
+
+    let definition: CoreHttpApiDefinitionRequest = definition
+        .try_into()
+        .map_err(|err: String| anyhow!("Invalid API definition: {err}"))?;
+
+    services
+        .definition_service
+        .compile(
+            &definition,
+            &DefaultNamespace::default(),
+            &EmptyAuthCtx::default(),
+        )
+        .await
+        .map_err(|err| anyhow!("Failed to compile API definition: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use super::*;
+    use golem_worker_service_base::gateway_api_definition::{ApiDefinitionId, ApiVersion};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn serve_local_command_is_parsed() {
+        assert_eq!(
+            parse_serve_local_command(&args(&["api-definition", "serve", "--local", "api.yaml"])),
+            Some(Ok(PathBuf::from("api.yaml")))
+        );
+        assert_eq!(
+            parse_serve_local_command(&args(&["api-definition", "serve", "--local=api.json"])),
+            Some(Ok(PathBuf::from("api.json")))
+        );
+        assert!(
+            parse_serve_local_command(&args(&["api-definition", "serve"]))
+                .is_some_and(|result| result.is_err())
+        );
+        assert!(
+            parse_serve_local_command(&args(&["api-definition", "serve", "--local="]))
+                .is_some_and(|result| result.is_err())
+        );
+        assert_eq!(parse_serve_local_command(&args(&[])), None);
+        assert_eq!(
+            parse_serve_local_command(&args(&["--dump-config-default-toml"])),
+            None
+        );
+    }
+
+    #[test]
+    async fn local_lookup_serves_the_definition_under_any_host() {
+        let definition = CompiledHttpApiDefinition {
+            id: ApiDefinitionId("local-api".to_string()),
+            version: ApiVersion("0.0.1".to_string()),
+            routes: vec![],
+            draft: true,
+            created_at: chrono::Utc::now(),
+            namespace: DefaultNamespace::default(),
+        };
+
+        let lookup = LocalHttpApiDefinitionLookup::new(definition);
+
+        for host in ["localhost:9006", "127.0.0.1:9006"] {
+            let definitions = lookup.get(&ApiSiteString(host.to_string())).await.unwrap();
+            assert_eq!(definitions.len(), 1);
+            assert_eq!(definitions[0].id, ApiDefinitionId("local-api".to_string()));
+        }
+    }
+}
@@ -15,23 +15,37 @@
 use golem_common::tracing::init_tracing_with_default_env_filter;
 use golem_service_base::migration::MigrationsDir;
 use golem_worker_service::config::make_config_loader;
+use golem_worker_service::local_gateway::{parse_serve_local_command, LocalGateway};
 use golem_worker_service::WorkerService;
 use golem_worker_service_base::app_config::WorkerServiceBaseConfig;
 use golem_worker_service_base::metrics;
 use opentelemetry::global;
 use prometheus::Registry;
 use tokio::task::JoinSet;
+use tracing::info;
 
 fn main() -> Result<(), anyhow::Error> {
-    if std::env::args().any(|arg| arg == "--dump-openapi-yaml") {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // `api-definition serve --local <file>` serves the API definition of the file instead of
+    // running the service, the same as setting `local_gateway.definition_file`
+    let local_definition = parse_serve_local_command(&args)
+        .transpose()
+        .map_err(anyhow::Error::msg)?;
+
+    if args.iter().any(|arg| arg == "--dump-openapi-yaml") {
         tokio::runtime::Builder::new_multi_thread()
             .enable_all()
             .build()?
             .block_on(dump_openapi_yaml())
-    } else if let Some(config) = make_config_loader().load_or_dump_config() {
+    } else if let Some(mut config) = make_config_loader().load_or_dump_config() {
+        if local_definition.is_some() {
+            config.local_gateway.definition_file = local_definition;
+        }
+
         init_tracing_with_default_env_filter(&config.tracing);
 
-        let prometheus = metrics::register_all();
+        let prometheus = metrics::register_all(&config.metrics);
 
         let exporter = opentelemetry_prometheus::exporter()
             .with_registry(prometheus.clone())
@@ -56,16 +70,28 @@ async fn async_main(
     config: WorkerServiceBaseConfig,
     prometheus: Registry,
 ) -> Result<(), anyhow::Error> {
-    let server = WorkerService::new(
-        config,
-        prometheus,
-        MigrationsDir::new("./db/migration".into()),
-    )
-    .await?;
-
     let mut join_set = JoinSet::new();
 
-    server.run(&mut join_set).await?;
+    match config.local_gateway.definition_file.clone() {
+        Some(definition_file) => {
+            // Only the gateway is started, without the database of the service
+            let gateway = LocalGateway::new(config).await?;
+            let port = gateway.start(&definition_file, &mut join_set).await?;
+            info!(
+                "Serving {} on http://localhost:{port}",
+                definition_file.display()
+            );
+        }
+        None => {
+            let server = WorkerService::new(
+                config,
+                prometheus,
+                MigrationsDir::new("./db/migration".into()),
+            )
+            .await?;
+            server.run(&mut join_set).await?;
+        }
+    }
 
     while let Some(res) = join_set.join_next().await {
         res??;
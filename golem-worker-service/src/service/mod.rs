@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod component;
+pub mod watch;
 pub mod worker;
 pub mod worker_request_executor;
 
@@ -20,17 +21,40 @@ use golem_service_base::config::BlobStorageConfig;
 use golem_service_base::service::initial_component_files::InitialComponentFilesService;
 use golem_service_base::storage::blob::BlobStorage;
 use golem_service_base::storage::sqlite::SqlitePool;
+use golem_worker_service_base::gateway_execution::experiment_assignment::{
+    DefaultExperimentAssignmentStore, ExperimentAssignmentStore,
+};
 use golem_worker_service_base::gateway_execution::file_server_binding_handler::DefaultFileServerBindingHandler;
 use golem_worker_service_base::gateway_execution::file_server_binding_handler::FileServerBindingHandler;
+use golem_worker_service_base::gateway_execution::forwarded_client_certificate::ForwardedClientCertificateMiddleware;
 use golem_worker_service_base::gateway_execution::http_handler_binding_handler::{
     DefaultHttpHandlerBindingHandler, HttpHandlerBindingHandler,
 };
+use golem_worker_service_base::gateway_execution::rate_limiter::{
+    InMemoryRateLimiter, RateLimiterStore, RedisRateLimiter,
+};
+use golem_worker_service_base::gateway_execution::response_cache::{
+    InMemoryResponseCache, RedisResponseCache, ResponseCacheStore,
+};
+use golem_worker_service_base::gateway_execution::server_sent_events_bridge::{
+    DefaultWorkerEventSubscriber, WorkerEventSubscriber,
+};
+use golem_worker_service_base::gateway_execution::shadow_traffic_diff::{
+    DefaultShadowTrafficDiffStore, ShadowTrafficDiffStore,
+};
+use golem_worker_service_base::service::worker::{
+    AsyncInvocationStore, AsyncInvocations, DefaultWorkerScheduleService,
+    InMemoryAsyncInvocationStore, RedisAsyncInvocationStore, WorkerScheduleService,
+};
+use watch::{ApiDeploymentWatch, WorkerWatch};
 use worker_request_executor::UnauthorisedWorkerRequestExecutor;
 
 use golem_worker_service_base::gateway_api_definition::http::HttpApiDefinition;
 
 use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
-use golem_worker_service_base::app_config::{GatewaySessionStorageConfig, WorkerServiceBaseConfig};
+use golem_worker_service_base::app_config::{
+    BatchInvocationConfig, GatewaySessionStorageConfig, WorkerFilesConfig, WorkerServiceBaseConfig,
+};
 
 use golem_worker_service_base::gateway_execution::api_definition_lookup::{
     DefaultHttpApiDefinitionLookup, HttpApiDefinitionsLookup,
@@ -53,15 +77,54 @@ use golem_common::model::RetryConfig;
 use golem_common::config::DbConfig;
 use golem_common::redis::RedisPool;
 use golem_service_base::db;
+use golem_service_base::repo::audit_log::{AuditLogRepo, DbAuditLogRepo, LoggedAuditLogRepo};
+use golem_service_base::repo::namespace_quota::{
+    DbNamespaceQuotaRepo, LoggedNamespaceQuotaRepo, NamespaceQuotaRepo,
+};
+use golem_service_base::service::audit_log::{AuditLogService, DefaultAuditLogService};
+use golem_service_base::service::namespace_quota::{
+    DefaultNamespaceQuotaService, NamespaceQuotaService, QuotaResource,
+};
 use golem_worker_service_base::gateway_execution::gateway_session::{
     GatewaySession, RedisGatewaySession, RedisGatewaySessionExpiration, SqliteGatewaySession,
     SqliteGatewaySessionExpiration,
 };
-use golem_worker_service_base::gateway_security::DefaultIdentityProvider;
+use golem_worker_service_base::gateway_security::{
+    ApiKeyValidator, DefaultIdentityProvider, HmacSignatureValidator,
+};
+use golem_worker_service_base::repo::api_key::{ApiKeyRepo, DbApiKeyRepo, LoggedApiKeyRepo};
+use golem_worker_service_base::repo::dead_letter::{
+    DbDeadLetterRepo, DeadLetterRepo, LoggedDeadLetterRepo,
+};
+use golem_worker_service_base::repo::emitted_event::{
+    DbEmittedEventRepo, EmittedEventRepo, LoggedEmittedEventRepo,
+};
+use golem_worker_service_base::repo::request_fixture::{
+    DbRequestFixtureRepo, LoggedRequestFixtureRepo, RequestFixtureRepo,
+};
+use golem_worker_service_base::repo::rib_library::{
+    DbRibLibraryRepo, LoggedRibLibraryRepo, RibLibraryRepo,
+};
 use golem_worker_service_base::repo::security_scheme::{DbSecuritySchemeRepo, SecuritySchemeRepo};
+use golem_worker_service_base::repo::worker_schedule::{
+    DbWorkerScheduleRepo, LoggedWorkerScheduleRepo, WorkerScheduleRepo,
+};
 use golem_worker_service_base::service::gateway::api_deployment::{
     ApiDeploymentService, ApiDeploymentServiceDefault,
 };
+use golem_worker_service_base::service::gateway::api_key::{ApiKeyService, DefaultApiKeyService};
+use golem_worker_service_base::service::gateway::dead_letter::{
+    DeadLetterService, DefaultDeadLetterService,
+};
+use golem_worker_service_base::service::gateway::emitted_event::{
+    DefaultEmittedEventService, EmittedEventService,
+};
+use golem_worker_service_base::service::gateway::request_fixture::{
+    DefaultRequestFixtureService, RequestFixtureService,
+};
+use golem_worker_service_base::service::gateway::rib_library::{
+    DefaultRibLibraryService, RibLibraryService,
+};
 use golem_worker_service_base::service::gateway::security_scheme::{
     DefaultSecuritySchemeService, SecuritySchemeService,
 };
@@ -74,6 +137,10 @@ pub struct Services {
     pub worker_service: worker::WorkerService,
     pub component_service: component::ComponentService,
     pub security_scheme_service: Arc<dyn SecuritySchemeService<DefaultNamespace> + Sync + Send>,
+    pub api_key_service: Arc<dyn ApiKeyService<DefaultNamespace> + Sync + Send>,
+    pub api_key_validator: Arc<dyn ApiKeyValidator<DefaultNamespace> + Sync + Send>,
+    pub rib_library_service: Arc<dyn RibLibraryService<DefaultNamespace> + Sync + Send>,
+    pub hmac_signature_validator: Arc<dyn HmacSignatureValidator<DefaultNamespace> + Sync + Send>,
     pub definition_service:
         Arc<dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
     pub deployment_service:
@@ -89,10 +156,30 @@ pub struct Services {
         Arc<dyn FileServerBindingHandler<DefaultNamespace> + Sync + Send>,
     pub http_handler_binding_handler:
         Arc<dyn HttpHandlerBindingHandler<DefaultNamespace> + Sync + Send>,
+    pub shadow_traffic_diff_store: Arc<dyn ShadowTrafficDiffStore + Sync + Send>,
+    pub experiment_assignment_store: Arc<dyn ExperimentAssignmentStore + Sync + Send>,
+    pub dead_letter_service: Arc<dyn DeadLetterService<DefaultNamespace> + Sync + Send>,
+    pub request_fixture_service: Arc<dyn RequestFixtureService<DefaultNamespace> + Sync + Send>,
+    pub emitted_event_service: Arc<dyn EmittedEventService<DefaultNamespace> + Sync + Send>,
+    pub audit_log_service: Arc<dyn AuditLogService<DefaultNamespace> + Sync + Send>,
+    pub namespace_quota_service: Arc<dyn NamespaceQuotaService<DefaultNamespace> + Sync + Send>,
+    pub worker_event_subscriber: Arc<dyn WorkerEventSubscriber + Sync + Send>,
+    pub rate_limiter: RateLimiterStore,
+    pub response_cache: ResponseCacheStore,
+    pub batch_invocation: BatchInvocationConfig,
+    pub worker_files: WorkerFilesConfig,
+    pub async_invocations: AsyncInvocations,
+    pub worker_schedule_service: Arc<dyn WorkerScheduleService<DefaultNamespace> + Sync + Send>,
+    pub worker_watch: Arc<WorkerWatch>,
+    pub deployment_watch: Arc<ApiDeploymentWatch>,
+    pub forwarded_client_certificate: ForwardedClientCertificateMiddleware,
 }
 
 impl Services {
     pub async fn new(config: &WorkerServiceBaseConfig) -> Result<Services, String> {
+        let forwarded_client_certificate =
+            ForwardedClientCertificateMiddleware::new(&config.client_certificates.trusted_proxies)?;
+
         let routing_table_service: Arc<
             dyn golem_service_base::service::routing_table::RoutingTableService + Send + Sync,
         > = Arc::new(
@@ -128,47 +215,67 @@ impl Services {
             Arc::new(RemoteComponentService::new(uri, retry_config))
         };
 
-        let worker_service: worker::WorkerService = Arc::new(WorkerServiceDefault::new(
-            worker_executor_grpc_clients.clone(),
-            config.worker_executor_retries.clone(),
-            routing_table_service.clone(),
-        ));
+        // Rate limits, cached responses and the results of async invocations are shared
+        // by the replicas through the Redis of the gateway sessions, if any
+        let (gateway_session_store, rate_limiter, response_cache, async_invocation_store): (
+            Arc<dyn GatewaySession + Sync + Send>,
+            RateLimiterStore,
+            ResponseCacheStore,
+            Arc<dyn AsyncInvocationStore + Sync + Send>,
+        ) = match &config.gateway_session_storage {
+            GatewaySessionStorageConfig::Redis(redis_config) => {
+                let redis = RedisPool::configured(redis_config)
+                    .await
+                    .map_err(|e| e.to_string())?;
 
-        let worker_to_http_service: Arc<
-            dyn GatewayWorkerRequestExecutor<DefaultNamespace> + Sync + Send,
-        > = Arc::new(UnauthorisedWorkerRequestExecutor::new(
-            worker_service.clone(),
-        ));
+                let gateway_session_with_redis = RedisGatewaySession::new(
+                    redis.clone(),
+                    RedisGatewaySessionExpiration::default(),
+                );
+
+                (
+                    Arc::new(gateway_session_with_redis),
+                    Arc::new(RedisRateLimiter::new(
+                        redis.clone(),
+                        config.rate_limit.fail_open,
+                    )),
+                    Arc::new(RedisResponseCache::new(redis.clone())),
+                    Arc::new(RedisAsyncInvocationStore::new(redis)),
+                )
+            }
+            GatewaySessionStorageConfig::Sqlite(sqlite_config) => {
+                let pool = SqlitePool::configured(sqlite_config)
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+                let gateway_session_with_sqlite =
+                    SqliteGatewaySession::new(pool, SqliteGatewaySessionExpiration::default())
+                        .await?;
+
+                (
+                    Arc::new(gateway_session_with_sqlite),
+                    Arc::new(InMemoryRateLimiter::new(
+                        config.rate_limit.max_in_memory_buckets,
+                    )),
+                    Arc::new(InMemoryResponseCache::default()),
+                    Arc::new(InMemoryAsyncInvocationStore::default()),
+                )
+            }
+        };
 
-        let gateway_session_store: Arc<dyn GatewaySession + Sync + Send> =
-            match &config.gateway_session_storage {
-                GatewaySessionStorageConfig::Redis(redis_config) => {
-                    let redis = RedisPool::configured(redis_config)
-                        .await
-                        .map_err(|e| e.to_string())?;
-
-                    let gateway_session_with_redis =
-                        RedisGatewaySession::new(redis, RedisGatewaySessionExpiration::default());
-
-                    Arc::new(gateway_session_with_redis)
-                }
-                GatewaySessionStorageConfig::Sqlite(sqlite_config) => {
-                    let pool = SqlitePool::configured(sqlite_config)
-                        .await
-                        .map_err(|e| e.to_string())?;
-
-                    let gateway_session_with_sqlite =
-                        SqliteGatewaySession::new(pool, SqliteGatewaySessionExpiration::default())
-                            .await?;
-
-                    Arc::new(gateway_session_with_sqlite)
-                }
-            };
-
-        let (api_definition_repo, api_deployment_repo, security_scheme_repo) = match config
-            .db
-            .clone()
-        {
+        let (
+            api_definition_repo,
+            api_deployment_repo,
+            security_scheme_repo,
+            api_key_repo,
+            rib_library_repo,
+            worker_schedule_repo,
+            dead_letter_repo,
+            request_fixture_repo,
+            emitted_event_repo,
+            audit_log_repo,
+            namespace_quota_repo,
+        ) = match config.db.clone() {
             DbConfig::Postgres(c) => {
                 let db_pool = db::create_postgres_pool(&c)
                     .await
@@ -185,10 +292,53 @@ impl Services {
                 let security_scheme_repo: Arc<dyn SecuritySchemeRepo + Sync + Send> =
                     Arc::new(DbSecuritySchemeRepo::new(db_pool.clone().into()));
 
+                let api_key_repo: Arc<dyn ApiKeyRepo + Sync + Send> = Arc::new(
+                    LoggedApiKeyRepo::new(DbApiKeyRepo::new(db_pool.clone().into())),
+                );
+
+                let rib_library_repo: Arc<dyn RibLibraryRepo + Sync + Send> = Arc::new(
+                    LoggedRibLibraryRepo::new(DbRibLibraryRepo::new(db_pool.clone().into())),
+                );
+
+                let worker_schedule_repo: Arc<dyn WorkerScheduleRepo + Sync + Send> =
+                    Arc::new(LoggedWorkerScheduleRepo::new(DbWorkerScheduleRepo::new(
+                        db_pool.clone().into(),
+                    )));
+
+                let dead_letter_repo: Arc<dyn DeadLetterRepo + Sync + Send> = Arc::new(
+                    LoggedDeadLetterRepo::new(DbDeadLetterRepo::new(db_pool.clone().into())),
+                );
+
+                let request_fixture_repo: Arc<dyn RequestFixtureRepo + Sync + Send> =
+                    Arc::new(LoggedRequestFixtureRepo::new(DbRequestFixtureRepo::new(
+                        db_pool.clone().into(),
+                    )));
+
+                let emitted_event_repo: Arc<dyn EmittedEventRepo + Sync + Send> = Arc::new(
+                    LoggedEmittedEventRepo::new(DbEmittedEventRepo::new(db_pool.clone().into())),
+                );
+
+                let audit_log_repo: Arc<dyn AuditLogRepo + Sync + Send> = Arc::new(
+                    LoggedAuditLogRepo::new(DbAuditLogRepo::new(db_pool.clone().into())),
+                );
+
+                let namespace_quota_repo: Arc<dyn NamespaceQuotaRepo + Sync + Send> =
+                    Arc::new(LoggedNamespaceQuotaRepo::new(DbNamespaceQuotaRepo::new(
+                        db_pool.clone().into(),
+                    )));
+
                 (
                     api_definition_repo,
                     api_deployment_repo,
                     security_scheme_repo,
+                    api_key_repo,
+                    rib_library_repo,
+                    worker_schedule_repo,
+                    dead_letter_repo,
+                    request_fixture_repo,
+                    emitted_event_repo,
+                    audit_log_repo,
+                    namespace_quota_repo,
                 )
             }
             DbConfig::Sqlite(c) => {
@@ -207,18 +357,85 @@ impl Services {
                 let security_scheme_repo: Arc<dyn SecuritySchemeRepo + Sync + Send> =
                     Arc::new(DbSecuritySchemeRepo::new(db_pool.clone().into()));
 
+                let api_key_repo: Arc<dyn ApiKeyRepo + Sync + Send> = Arc::new(
+                    LoggedApiKeyRepo::new(DbApiKeyRepo::new(db_pool.clone().into())),
+                );
+
+                let rib_library_repo: Arc<dyn RibLibraryRepo + Sync + Send> = Arc::new(
+                    LoggedRibLibraryRepo::new(DbRibLibraryRepo::new(db_pool.clone().into())),
+                );
+
+                let worker_schedule_repo: Arc<dyn WorkerScheduleRepo + Sync + Send> =
+                    Arc::new(LoggedWorkerScheduleRepo::new(DbWorkerScheduleRepo::new(
+                        db_pool.clone().into(),
+                    )));
+
+                let dead_letter_repo: Arc<dyn DeadLetterRepo + Sync + Send> = Arc::new(
+                    LoggedDeadLetterRepo::new(DbDeadLetterRepo::new(db_pool.clone().into())),
+                );
+
+                let request_fixture_repo: Arc<dyn RequestFixtureRepo + Sync + Send> =
+                    Arc::new(LoggedRequestFixtureRepo::new(DbRequestFixtureRepo::new(
+                        db_pool.clone().into(),
+                    )));
+
+                let emitted_event_repo: Arc<dyn EmittedEventRepo + Sync + Send> = Arc::new(
+                    LoggedEmittedEventRepo::new(DbEmittedEventRepo::new(db_pool.clone().into())),
+                );
+
+                let audit_log_repo: Arc<dyn AuditLogRepo + Sync + Send> = Arc::new(
+                    LoggedAuditLogRepo::new(DbAuditLogRepo::new(db_pool.clone().into())),
+                );
+
+                let namespace_quota_repo: Arc<dyn NamespaceQuotaRepo + Sync + Send> =
+                    Arc::new(LoggedNamespaceQuotaRepo::new(DbNamespaceQuotaRepo::new(
+                        db_pool.clone().into(),
+                    )));
+
                 (
                     api_definition_repo,
                     api_deployment_repo,
                     security_scheme_repo,
+                    api_key_repo,
+                    rib_library_repo,
+                    worker_schedule_repo,
+                    dead_letter_repo,
+                    request_fixture_repo,
+                    emitted_event_repo,
+                    audit_log_repo,
+                    namespace_quota_repo,
                 )
             }
         };
 
+        let namespace_quota_service: Arc<
+            dyn NamespaceQuotaService<DefaultNamespace> + Sync + Send,
+        > = Arc::new(DefaultNamespaceQuotaService::new(
+            namespace_quota_repo,
+            vec![QuotaResource::Workers, QuotaResource::InvocationsPerMinute],
+        ));
+
+        let worker_service: worker::WorkerService = Arc::new(WorkerServiceDefault::new(
+            worker_executor_grpc_clients.clone(),
+            config.worker_executor_retries.clone(),
+            routing_table_service.clone(),
+            namespace_quota_service.clone(),
+            component_service.clone(),
+        ));
+
+        let worker_to_http_service: Arc<
+            dyn GatewayWorkerRequestExecutor<DefaultNamespace> + Sync + Send,
+        > = Arc::new(UnauthorisedWorkerRequestExecutor::new(
+            worker_service.clone(),
+        ));
+
         let blob_storage: Arc<dyn BlobStorage + Sync + Send> = match &config.blob_storage {
             BlobStorageConfig::S3(config) => Arc::new(
                 golem_service_base::storage::blob::s3::S3BlobStorage::new(config.clone()).await,
             ),
+            BlobStorageConfig::AzureBlob(config) => Arc::new(
+                golem_service_base::storage::blob::azure::AzureBlobStorage::new(config.clone())?,
+            ),
             BlobStorageConfig::LocalFileSystem(config) => Arc::new(
                 golem_service_base::storage::blob::fs::FileSystemBlobStorage::new(&config.root)
                     .await?,
@@ -257,6 +474,15 @@ impl Services {
             worker_to_http_service.clone(),
         ));
 
+        let shadow_traffic_diff_store: Arc<dyn ShadowTrafficDiffStore + Sync + Send> =
+            Arc::new(DefaultShadowTrafficDiffStore::default());
+
+        let experiment_assignment_store: Arc<dyn ExperimentAssignmentStore + Sync + Send> =
+            Arc::new(DefaultExperimentAssignmentStore::default());
+
+        let worker_event_subscriber: Arc<dyn WorkerEventSubscriber + Sync + Send> =
+            Arc::new(DefaultWorkerEventSubscriber::new(worker_service.clone()));
+
         let api_definition_validator_service = Arc::new(HttpApiDefinitionValidator {});
 
         let identity_provider = Arc::new(DefaultIdentityProvider);
@@ -266,6 +492,32 @@ impl Services {
             identity_provider,
         ));
 
+        let api_key_service = Arc::new(DefaultApiKeyService::<DefaultNamespace>::new(
+            api_key_repo,
+            security_scheme_service.clone(),
+        ));
+
+        let rib_library_service = Arc::new(DefaultRibLibraryService::new(rib_library_repo));
+
+        let worker_schedule_service = Arc::new(DefaultWorkerScheduleService::new(
+            worker_schedule_repo,
+            worker_service.clone(),
+            config.scheduler.clone(),
+        ));
+
+        let dead_letter_service: Arc<dyn DeadLetterService<DefaultNamespace> + Sync + Send> =
+            Arc::new(DefaultDeadLetterService::new(dead_letter_repo));
+
+        let request_fixture_service: Arc<
+            dyn RequestFixtureService<DefaultNamespace> + Sync + Send,
+        > = Arc::new(DefaultRequestFixtureService::new(request_fixture_repo));
+
+        let emitted_event_service: Arc<dyn EmittedEventService<DefaultNamespace> + Sync + Send> =
+            Arc::new(DefaultEmittedEventService::new(emitted_event_repo));
+
+        let audit_log_service: Arc<dyn AuditLogService<DefaultNamespace> + Sync + Send> =
+            Arc::new(DefaultAuditLogService::new(audit_log_repo));
+
         let definition_service: Arc<
             dyn ApiDefinitionService<EmptyAuthCtx, DefaultNamespace> + Sync + Send,
         > = Arc::new(ApiDefinitionServiceDefault::new(
@@ -288,10 +540,23 @@ impl Services {
             deployment_service.clone(),
         ));
 
+        let worker_watch = Arc::new(WorkerWatch::new(
+            worker_service.clone(),
+            config.watch.clone(),
+        ));
+        let deployment_watch = Arc::new(ApiDeploymentWatch::new(
+            deployment_service.clone(),
+            config.watch.clone(),
+        ));
+
         Ok(Services {
             worker_service,
             definition_service,
-            security_scheme_service,
+            security_scheme_service: security_scheme_service.clone(),
+            hmac_signature_validator: security_scheme_service,
+            api_key_service: api_key_service.clone(),
+            api_key_validator: api_key_service,
+            rib_library_service,
             deployment_service,
             http_definition_lookup_service,
             worker_to_http_service,
@@ -300,6 +565,26 @@ impl Services {
             fileserver_binding_handler,
             gateway_session_store,
             http_handler_binding_handler,
+            shadow_traffic_diff_store,
+            experiment_assignment_store,
+            dead_letter_service,
+            request_fixture_service,
+            emitted_event_service,
+            audit_log_service,
+            namespace_quota_service,
+            worker_event_subscriber,
+            rate_limiter,
+            response_cache,
+            batch_invocation: config.batch_invocation.clone(),
+            worker_files: config.worker_files.clone(),
+            async_invocations: AsyncInvocations::new(
+                config.async_invocation.clone(),
+                async_invocation_store,
+            ),
+            worker_schedule_service,
+            worker_watch,
+            deployment_watch,
+            forwarded_client_certificate,
         })
     }
 }
@@ -0,0 +1,141 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::service::worker::WorkerService;
+use futures::Stream;
+use golem_common::model::{ComponentId, ScanCursor, WorkerFilter};
+use golem_service_base::auth::{DefaultNamespace, EmptyAuthCtx};
+use golem_service_base::config::WatchConfig;
+use golem_service_base::model::WorkerMetadata;
+use golem_service_base::watch::{WatchEvent, WatchHub};
+use golem_worker_service_base::api::ApiDeployment;
+use golem_worker_service_base::empty_worker_metadata;
+use golem_worker_service_base::gateway_api_definition::ApiDefinitionId;
+use golem_worker_service_base::service::gateway::api_deployment::{
+    ApiDeploymentError, ApiDeploymentService,
+};
+use golem_worker_service_base::service::worker::WorkerServiceError;
+use std::sync::Arc;
+
+const WATCH_PAGE_SIZE: u64 = 100;
+
+/// Watch streams of the workers of components, shared by the HTTP and gRPC APIs so that all the
+/// watchers of the same workers are served by a single poller
+pub struct WorkerWatch {
+    worker_service: WorkerService,
+    hub: WatchHub<WorkerMetadata>,
+}
+
+impl WorkerWatch {
+    pub fn new(worker_service: WorkerService, config: WatchConfig) -> Self {
+        Self {
+            worker_service,
+            hub: WatchHub::new(config),
+        }
+    }
+
+    /// Watches the workers of the component matching the filter
+    pub fn watch(
+        &self,
+        component_id: ComponentId,
+        filter: Option<WorkerFilter>,
+        precise: bool,
+        resume_from: Option<String>,
+    ) -> impl Stream<Item = Result<WatchEvent<WorkerMetadata>, String>> + Send + 'static {
+        let key = format!("{component_id}/{filter:?}/{precise}");
+        let worker_service = self.worker_service.clone();
+
+        let list = move || {
+            let worker_service = worker_service.clone();
+            let component_id = component_id.clone();
+            let filter = filter.clone();
+            async move {
+                let mut workers = Vec::new();
+                let mut cursor = Some(ScanCursor::default());
+                while let Some(current) = cursor {
+                    let (next, page) = worker_service
+                        .find_metadata(
+                            &component_id,
+                            filter.clone(),
+                            current,
+                            WATCH_PAGE_SIZE,
+                            precise,
+                            empty_worker_metadata(),
+                        )
+                        .await?;
+                    workers.extend(
+                        page.into_iter()
+                            .map(|worker| (worker.worker_id.to_string(), worker)),
+                    );
+                    cursor = next;
+                }
+                Ok::<_, WorkerServiceError>(workers)
+            }
+        };
+
+        self.hub.watch(key, list, resume_from)
+    }
+}
+
+/// Watch streams of the API deployments, shared by all their watchers
+pub struct ApiDeploymentWatch {
+    deployment_service: Arc<dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send>,
+    hub: WatchHub<ApiDeployment>,
+}
+
+impl ApiDeploymentWatch {
+    pub fn new(
+        deployment_service: Arc<
+            dyn ApiDeploymentService<EmptyAuthCtx, DefaultNamespace> + Sync + Send,
+        >,
+        config: WatchConfig,
+    ) -> Self {
+        Self {
+            deployment_service,
+            hub: WatchHub::new(config),
+        }
+    }
+
+    /// Watches all the API deployments, or the ones of an API definition
+    pub fn watch(
+        &self,
+        api_definition_id: Option<ApiDefinitionId>,
+        resume_from: Option<String>,
+    ) -> impl Stream<Item = Result<WatchEvent<ApiDeployment>, String>> + Send + 'static {
+        let key = format!("{api_definition_id:?}");
+        let deployment_service = self.deployment_service.clone();
+
+        let list = move || {
+            let deployment_service = deployment_service.clone();
+            let api_definition_id = api_definition_id.clone();
+            async move {
+                let values = deployment_service
+                    .get_by_id(&DefaultNamespace::default(), api_definition_id)
+                    .await?;
+
+                Ok::<_, ApiDeploymentError<DefaultNamespace>>(
+                    values
+                        .into_iter()
+                        .map(|v| {
+                            let deployment: ApiDeployment = v.into();
+                            (deployment.site.to_string(), deployment)
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        self.hub.watch(key, list, resume_from)
+    }
+}
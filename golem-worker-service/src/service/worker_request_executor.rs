@@ -12,19 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use golem_common::model::TargetWorkerId;
+use golem_api_grpc::proto::golem::worker::InvocationContext;
+use golem_common::model::{ComponentId, TargetWorkerId, WorkerId};
 use golem_service_base::auth::DefaultNamespace;
-use golem_service_base::model::validate_worker_name;
+use golem_service_base::model::{validate_worker_name, GolemError};
 use golem_worker_service_base::empty_worker_metadata;
 use golem_worker_service_base::gateway_execution::{
     GatewayResolvedWorkerRequest, GatewayWorkerRequestExecutor, WorkerRequestExecutorError,
     WorkerResponse,
 };
-use golem_worker_service_base::service::worker::WorkerService;
-use tracing::{debug, info};
+use golem_worker_service_base::service::worker::{WorkerService, WorkerServiceError};
+use tracing::{debug, info, info_span, Instrument};
 
 // The open source deviates from the proprietary codebase here, only in terms of authorisation
 pub struct UnauthorisedWorkerRequestExecutor {
@@ -91,6 +93,23 @@ impl GatewayWorkerRequestExecutor<DefaultNamespace> for UnauthorisedWorkerReques
             "Invocation parameters"
         );
 
+        // The worker executor uses the args, env and config vars of the invocation context
+        // only when the invocation is the one creating the worker
+        let bootstrap = worker_request_params.bootstrap.unwrap_or_default();
+        let invocation_context = InvocationContext {
+            parent: None,
+            args: bootstrap.args,
+            env: bootstrap.env,
+            wasi_config_vars: bootstrap.config,
+            tracing: Some(worker_request_params.invocation_context.into()),
+        };
+
+        let span = info_span!(
+            "worker_invocation",
+            worker_id = %worker_id,
+            function = worker_request_params.function_name
+        );
+
         let type_annotated_value = self
             .worker_service
             .validate_and_invoke_and_await_typed(
@@ -98,9 +117,10 @@ impl GatewayWorkerRequestExecutor<DefaultNamespace> for UnauthorisedWorkerReques
                 worker_request_params.idempotency_key,
                 worker_request_params.function_name,
                 invoke_parameters,
-                None,
+                Some(invocation_context),
                 empty_worker_metadata(),
             )
+            .instrument(span)
             .await
             .map_err(|e| e.to_string())?;
 
@@ -108,4 +128,29 @@ impl GatewayWorkerRequestExecutor<DefaultNamespace> for UnauthorisedWorkerReques
             result: type_annotated_value,
         })
     }
+
+    async fn get_worker_labels(
+        &self,
+        component_id: &ComponentId,
+        worker_name: &str,
+        _namespace: DefaultNamespace,
+    ) -> Result<HashMap<String, String>, WorkerRequestExecutorError> {
+        validate_worker_name(worker_name)?;
+
+        let worker_id = WorkerId {
+            component_id: component_id.clone(),
+            worker_name: worker_name.to_string(),
+        };
+
+        match self
+            .worker_service
+            .get_metadata(&worker_id, empty_worker_metadata())
+            .await
+        {
+            Ok(metadata) => Ok(metadata.labels),
+            Err(WorkerServiceError::WorkerNotFound(_))
+            | Err(WorkerServiceError::Golem(GolemError::WorkerNotFound(_))) => Ok(HashMap::new()),
+            Err(error) => Err(error.to_string().into()),
+        }
+    }
 }
@@ -57,6 +57,15 @@ async fn create_and_get_api_security_scheme(deps: &EnvBasedTestDependencies) {
                         binding_type: Some(GatewayBindingType::Default as i32),
                         static_binding: None,
                         invocation_context: None,
+                        grpc_binding: None,
+                        traffic_mirror: None,
+                        http_proxy_binding: None,
+                        worker_per_user: None,
+                        bootstrap: None,
+                        pagination: None,
+                        content_negotiation: None,
+                        error_mapping: None,
+                        static_worker_names: None,
                     }),
                     middleware: None,
                 }],
@@ -137,6 +146,15 @@ async fn get_api_definition_versions(deps: &EnvBasedTestDependencies) {
                         binding_type: Some(GatewayBindingType::Default as i32),
                         static_binding: None,
                         invocation_context: None,
+                        grpc_binding: None,
+                        traffic_mirror: None,
+                        http_proxy_binding: None,
+                        worker_per_user: None,
+                        bootstrap: None,
+                        pagination: None,
+                        content_negotiation: None,
+                        error_mapping: None,
+                        static_worker_names: None,
                     }),
                     middleware: None,
                 }],
@@ -176,6 +194,15 @@ async fn get_api_definition_versions(deps: &EnvBasedTestDependencies) {
                             binding_type: Some(GatewayBindingType::Default as i32),
                             static_binding: None,
                             invocation_context: None,
+                            grpc_binding: None,
+                            traffic_mirror: None,
+                            http_proxy_binding: None,
+                            worker_per_user: None,
+                            bootstrap: None,
+                            pagination: None,
+                            content_negotiation: None,
+                            error_mapping: None,
+                            static_worker_names: None,
                         }),
                         middleware: None,
                     },
@@ -204,6 +231,15 @@ async fn get_api_definition_versions(deps: &EnvBasedTestDependencies) {
                             binding_type: Some(GatewayBindingType::Default as i32),
                             static_binding: None,
                             invocation_context: None,
+                            grpc_binding: None,
+                            traffic_mirror: None,
+                            http_proxy_binding: None,
+                            worker_per_user: None,
+                            bootstrap: None,
+                            pagination: None,
+                            content_negotiation: None,
+                            error_mapping: None,
+                            static_worker_names: None,
                         }),
                         middleware: None,
                     },
@@ -340,6 +376,15 @@ async fn get_api_definition_all_versions(deps: &EnvBasedTestDependencies) {
                         binding_type: Some(GatewayBindingType::Default as i32),
                         static_binding: None,
                         invocation_context: None,
+                        grpc_binding: None,
+                        traffic_mirror: None,
+                        http_proxy_binding: None,
+                        worker_per_user: None,
+                        bootstrap: None,
+                        pagination: None,
+                        content_negotiation: None,
+                        error_mapping: None,
+                        static_worker_names: None,
                     }),
                     middleware: None,
                 }],
@@ -385,6 +430,15 @@ async fn get_api_definition_all_versions(deps: &EnvBasedTestDependencies) {
                         binding_type: Some(GatewayBindingType::Default as i32),
                         static_binding: None,
                         invocation_context: None,
+                        grpc_binding: None,
+                        traffic_mirror: None,
+                        http_proxy_binding: None,
+                        worker_per_user: None,
+                        bootstrap: None,
+                        pagination: None,
+                        content_negotiation: None,
+                        error_mapping: None,
+                        static_worker_names: None,
                     }),
                     middleware: None,
                 }],
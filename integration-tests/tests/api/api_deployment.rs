@@ -307,6 +307,15 @@ async fn create_api_definition(
                                     binding_type: Some(GatewayBindingType::Default as i32),
                                     static_binding: None,
                                     invocation_context: None,
+                                    grpc_binding: None,
+                                    traffic_mirror: None,
+                                    http_proxy_binding: None,
+                                    worker_per_user: None,
+                                    bootstrap: None,
+                                    pagination: None,
+                                    content_negotiation: None,
+                                    error_mapping: None,
+                                    static_worker_names: None,
                                 }),
                                 middleware: None,
                             }],
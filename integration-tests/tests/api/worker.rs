@@ -5,10 +5,11 @@ use golem_api_grpc::proto::golem::worker::v1::{
     LaunchNewWorkerRequest, LaunchNewWorkerResponse, LaunchNewWorkerSuccessResponse,
 };
 use golem_api_grpc::proto::golem::worker::{InvokeResult, TargetWorkerId};
+use golem_common::model::WorkerId;
 use golem_test_framework::config::{EnvBasedTestDependencies, TestDependencies};
 use golem_test_framework::dsl::TestDslUnsafe;
 use golem_wasm_rpc::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use test_r::{inherit_test_dep, test};
 use uuid::Uuid;
 
@@ -38,6 +39,8 @@ async fn add_and_invoke_worker_with_args_and_env(deps: &EnvBasedTestDependencies
                 ("TEST_ENV_VAR_1".to_string(), "value_1".to_string()),
                 ("TEST_ENV_VAR_2".to_string(), "value_2".to_string()),
             ]),
+            wasi_config_vars: HashMap::new(),
+            labels: HashMap::new(),
         })
         .await
         .unwrap()
@@ -115,6 +118,43 @@ async fn add_and_invoke_worker_with_args_and_env(deps: &EnvBasedTestDependencies
     check!(env_vars.get("TEST_ENV_VAR_2") == Some(&"value_2".to_string()));
 }
 
+#[test]
+#[tracing::instrument]
+async fn add_worker_with_wasi_config_vars(deps: &EnvBasedTestDependencies) {
+    let component_id = deps.component("environment-service").unique().store().await;
+
+    let create_result = deps
+        .worker_service()
+        .create_worker(LaunchNewWorkerRequest {
+            component_id: Some(component_id.clone().into()),
+            name: format!("worker-{}", Uuid::new_v4()),
+            args: vec![],
+            env: HashMap::from([("TEST_ENV_VAR_1".to_string(), "value_1".to_string())]),
+            wasi_config_vars: HashMap::from([
+                ("region".to_string(), "eu-west".to_string()),
+                ("tier".to_string(), "gold".to_string()),
+            ]),
+            labels: HashMap::new(),
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    let worker_id: WorkerId = create_result.worker_id.unwrap().try_into().unwrap();
+    let (metadata, _) = deps.get_worker_metadata(&worker_id).await.unwrap();
+
+    check!(
+        metadata.wasi_config_vars
+            == BTreeMap::from([
+                ("region".to_string(), "eu-west".to_string()),
+                ("tier".to_string(), "gold".to_string()),
+            ])
+    );
+    check!(metadata
+        .env
+        .contains(&("TEST_ENV_VAR_1".to_string(), "value_1".to_string())));
+}
+
 trait Unwrap {
     type Inner;
 
@@ -368,6 +368,19 @@ async fn fork_idle_worker(deps: &EnvBasedTestDependencies, _tracing: &Tracing) {
 
     assert_eq!(result1.len(), 4); //  two invocations for G1002 and two log messages preceded
     assert_eq!(result2.len(), 2); //  two invocations for G1001 which was in the original source oplog
+
+    let (target_metadata, _) = deps
+        .get_worker_metadata(&target_worker_id)
+        .await
+        .expect("Expect the forked worker to exist");
+    let fork = target_metadata
+        .last_known_status
+        .fork()
+        .expect("Expect the forked worker to record its origin")
+        .clone();
+
+    assert_eq!(fork.source_worker_id, source_worker_id);
+    assert_eq!(fork.forked_at, oplog_index_of_function_completed_g1001);
 }
 
 #[test]
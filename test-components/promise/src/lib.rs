@@ -1,7 +1,23 @@
 mod bindings;
 
-use crate::bindings::exports::golem::it::api::{Guest, PromiseId};
+use crate::bindings::exports::golem::it::api::{Awaited, Guest, PromiseId};
 use crate::bindings::golem::api::host::*;
+use std::cell::RefCell;
+
+struct State {
+    awaited_history: Vec<Awaited>,
+}
+
+thread_local! {
+    static STATE: RefCell<State> = RefCell::new(State {
+        awaited_history: Vec::new(),
+    });
+}
+
+fn record(awaited: Awaited) -> Awaited {
+    STATE.with_borrow_mut(|state| state.awaited_history.push(awaited.clone()));
+    awaited
+}
 
 struct Component;
 
@@ -17,6 +33,18 @@ impl Guest for Component {
     fn poll(id: PromiseId) -> Option<Vec<u8>> {
         poll_promise(&id)
     }
+
+    fn await_any(ids: Vec<PromiseId>, timeout_nanos: Option<u64>) -> Awaited {
+        record(await_any(&ids, &[], timeout_nanos))
+    }
+
+    fn await_all(ids: Vec<PromiseId>, timeout_nanos: Option<u64>) -> Awaited {
+        record(await_all(&ids, &[], timeout_nanos))
+    }
+
+    fn awaited_history() -> Vec<Awaited> {
+        STATE.with_borrow(|state| state.awaited_history.clone())
+    }
 }
 
 bindings::export!(Component with_types_in bindings);
@@ -42,6 +42,10 @@ pub mod json;
 #[cfg(feature = "poem_openapi")]
 pub mod poem;
 
+/// Type-annotated, indented printing of values for CLI output and REPL echo
+#[cfg(feature = "text")]
+mod pretty;
+
 /// Protobuf-defined value types and conversion to them
 #[cfg(feature = "protobuf")]
 pub mod protobuf;
@@ -177,6 +181,11 @@ pub use text::{parse_type_annotated_value, print_type_annotated_value};
 #[cfg(feature = "text")]
 pub use text::{parse_value_and_type, print_value_and_type};
 
+#[cfg(feature = "text")]
+pub use pretty::{
+    pretty_print_type_name, pretty_print_value_and_type, PrettyPrintFormat, PrettyPrintOptions,
+};
+
 #[cfg(feature = "typeinfo")]
 pub use value_and_type::*;
 
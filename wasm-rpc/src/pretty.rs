@@ -0,0 +1,425 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{Value, ValueAndType};
+use golem_wasm_ast::analysis::AnalysedType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrettyPrintFormat {
+    // WAVE, with the type of the values at the top level, in record fields and in variant
+    // payloads, where it is not implied by an enclosing type (Example: `age: u32 42`).
+    // Lists of bytes are printed in hex (Example: `0x0a0b`).
+    Annotated,
+    // WAVE, which can be parsed back unless truncated
+    Wave,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyPrintOptions {
+    pub format: PrettyPrintFormat,
+    // Spaces per nesting level. With 0, compound values are printed on a single line.
+    pub indent: usize,
+    // The limits above which lists, lists of bytes and strings are truncated,
+    // leaving a `... (N more items)` marker
+    pub max_list_items: Option<usize>,
+    pub max_bytes: Option<usize>,
+    pub max_string_chars: Option<usize>,
+}
+
+impl PrettyPrintOptions {
+    // Printing whole values as WAVE, on a single line
+    pub fn wave() -> Self {
+        PrettyPrintOptions {
+            format: PrettyPrintFormat::Wave,
+            indent: 0,
+            max_list_items: None,
+            max_bytes: None,
+            max_string_chars: None,
+        }
+    }
+}
+
+// Tuned for CLI output and REPL echo
+impl Default for PrettyPrintOptions {
+    fn default() -> Self {
+        PrettyPrintOptions {
+            format: PrettyPrintFormat::Annotated,
+            indent: 2,
+            max_list_items: Some(100),
+            max_bytes: Some(64),
+            max_string_chars: Some(1000),
+        }
+    }
+}
+
+pub fn pretty_print_value_and_type(value: &ValueAndType, options: &PrettyPrintOptions) -> String {
+    let printer = Printer { options };
+    printer.annotated(&value.value, &value.typ, 0)
+}
+
+// The name of a type as printed in the annotations. Records, variants, enums and flags are
+// anonymous, and only named by their kind.
+pub fn pretty_print_type_name(typ: &AnalysedType) -> String {
+    match typ {
+        AnalysedType::Bool(_) => "bool".to_string(),
+        AnalysedType::U8(_) => "u8".to_string(),
+        AnalysedType::U16(_) => "u16".to_string(),
+        AnalysedType::U32(_) => "u32".to_string(),
+        AnalysedType::U64(_) => "u64".to_string(),
+        AnalysedType::S8(_) => "s8".to_string(),
+        AnalysedType::S16(_) => "s16".to_string(),
+        AnalysedType::S32(_) => "s32".to_string(),
+        AnalysedType::S64(_) => "s64".to_string(),
+        AnalysedType::F32(_) => "f32".to_string(),
+        AnalysedType::F64(_) => "f64".to_string(),
+        AnalysedType::Chr(_) => "char".to_string(),
+        AnalysedType::Str(_) => "string".to_string(),
+        AnalysedType::List(list) => format!("list<{}>", pretty_print_type_name(&list.inner)),
+        AnalysedType::Option(option) => {
+            format!("option<{}>", pretty_print_type_name(&option.inner))
+        }
+        AnalysedType::Tuple(tuple) => format!(
+            "tuple<{}>",
+            tuple
+                .items
+                .iter()
+                .map(pretty_print_type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        AnalysedType::Result(result) => match (&result.ok, &result.err) {
+            (None, None) => "result".to_string(),
+            (Some(ok), None) => format!("result<{}>", pretty_print_type_name(ok)),
+            (None, Some(err)) => format!("result<_, {}>", pretty_print_type_name(err)),
+            (Some(ok), Some(err)) => format!(
+                "result<{}, {}>",
+                pretty_print_type_name(ok),
+                pretty_print_type_name(err)
+            ),
+        },
+        AnalysedType::Record(_) => "record".to_string(),
+        AnalysedType::Variant(_) => "variant".to_string(),
+        AnalysedType::Enum(_) => "enum".to_string(),
+        AnalysedType::Flags(_) => "flags".to_string(),
+        AnalysedType::Handle(_) => "handle".to_string(),
+    }
+}
+
+struct Printer<'a> {
+    options: &'a PrettyPrintOptions,
+}
+
+impl Printer<'_> {
+    fn annotated(&self, value: &Value, typ: &AnalysedType, level: usize) -> String {
+        match self.options.format {
+            PrettyPrintFormat::Annotated => {
+                format!(
+                    "{} {}",
+                    pretty_print_type_name(typ),
+                    self.value(value, typ, level)
+                )
+            }
+            PrettyPrintFormat::Wave => self.value(value, typ, level),
+        }
+    }
+
+    fn value(&self, value: &Value, typ: &AnalysedType, level: usize) -> String {
+        match (value, typ) {
+            (Value::Bool(value), AnalysedType::Bool(_)) => value.to_string(),
+            (Value::U8(value), AnalysedType::U8(_)) => value.to_string(),
+            (Value::U16(value), AnalysedType::U16(_)) => value.to_string(),
+            (Value::U32(value), AnalysedType::U32(_)) => value.to_string(),
+            (Value::U64(value), AnalysedType::U64(_)) => value.to_string(),
+            (Value::S8(value), AnalysedType::S8(_)) => value.to_string(),
+            (Value::S16(value), AnalysedType::S16(_)) => value.to_string(),
+            (Value::S32(value), AnalysedType::S32(_)) => value.to_string(),
+            (Value::S64(value), AnalysedType::S64(_)) => value.to_string(),
+            (Value::F32(value), AnalysedType::F32(_)) => float(*value as f64, value.to_string()),
+            (Value::F64(value), AnalysedType::F64(_)) => float(*value, value.to_string()),
+            (Value::Char(value), AnalysedType::Chr(_)) => {
+                format!("'{}'", escape(&value.to_string(), '\''))
+            }
+            (Value::String(value), AnalysedType::Str(_)) => self.string(value),
+            (Value::List(items), AnalysedType::List(list)) => {
+                if matches!(*list.inner, AnalysedType::U8(_))
+                    && self.options.format == PrettyPrintFormat::Annotated
+                {
+                    self.bytes(items)
+                } else {
+                    let limit = match *list.inner {
+                        AnalysedType::U8(_) => self.options.max_bytes,
+                        _ => self.options.max_list_items,
+                    };
+                    let printed = items
+                        .iter()
+                        .take(limit.unwrap_or(usize::MAX))
+                        .map(|item| self.value(item, &list.inner, level + 1))
+                        .collect::<Vec<_>>();
+                    let more = items.len() - printed.len();
+                    self.compound("[", "]", printed, more, level)
+                }
+            }
+            (Value::Tuple(items), AnalysedType::Tuple(tuple))
+                if items.len() == tuple.items.len() =>
+            {
+                let printed = items
+                    .iter()
+                    .zip(&tuple.items)
+                    .map(|(item, typ)| self.value(item, typ, level + 1))
+                    .collect();
+                self.compound("(", ")", printed, 0, level)
+            }
+            (Value::Record(fields), AnalysedType::Record(record))
+                if fields.len() == record.fields.len() =>
+            {
+                let printed = fields
+                    .iter()
+                    .zip(&record.fields)
+                    .map(|(field, typ)| {
+                        format!(
+                            "{}: {}",
+                            label(&typ.name),
+                            self.annotated(field, &typ.typ, level + 1)
+                        )
+                    })
+                    .collect();
+                self.compound("{", "}", printed, 0, level)
+            }
+            (
+                Value::Variant {
+                    case_idx,
+                    case_value,
+                },
+                AnalysedType::Variant(variant),
+            ) if (*case_idx as usize) < variant.cases.len() => {
+                let case = &variant.cases[*case_idx as usize];
+                match (case_value, &case.typ) {
+                    (Some(case_value), Some(typ)) => format!(
+                        "{}({})",
+                        label(&case.name),
+                        self.annotated(case_value, typ, level)
+                    ),
+                    _ => label(&case.name),
+                }
+            }
+            (Value::Enum(case_idx), AnalysedType::Enum(r#enum))
+                if (*case_idx as usize) < r#enum.cases.len() =>
+            {
+                label(&r#enum.cases[*case_idx as usize])
+            }
+            (Value::Flags(values), AnalysedType::Flags(flags)) => {
+                let names = values
+                    .iter()
+                    .zip(&flags.names)
+                    .filter(|(value, _)| **value)
+                    .map(|(_, name)| label(name))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", names.join(", "))
+            }
+            (Value::Option(value), AnalysedType::Option(option)) => match value {
+                Some(value) => format!("some({})", self.value(value, &option.inner, level)),
+                None => "none".to_string(),
+            },
+            (Value::Result(value), AnalysedType::Result(result)) => {
+                let (case, value, typ) = match value {
+                    Ok(value) => ("ok", value, &result.ok),
+                    Err(value) => ("err", value, &result.err),
+                };
+                match (value, typ) {
+                    (Some(value), Some(typ)) => {
+                        format!("{}({})", case, self.value(value, typ, level))
+                    }
+                    _ => case.to_string(),
+                }
+            }
+            (Value::Handle { uri, resource_id }, AnalysedType::Handle(_)) => {
+                format!("{}/{}", uri, resource_id)
+            }
+            // Values not matching their type are printed as they are
+            (value, _) => format!("{:?}", value),
+        }
+    }
+
+    fn string(&self, value: &str) -> String {
+        let chars = value.chars().count();
+        match self.options.max_string_chars {
+            Some(max) if chars > max => {
+                let truncated = value.chars().take(max).collect::<String>();
+                format!(
+                    "\"{}\"... ({} more chars)",
+                    escape(&truncated, '"'),
+                    chars - max
+                )
+            }
+            _ => format!("\"{}\"", escape(value, '"')),
+        }
+    }
+
+    fn bytes(&self, items: &[Value]) -> String {
+        let limit = self.options.max_bytes.unwrap_or(usize::MAX);
+        let hex = items
+            .iter()
+            .take(limit)
+            .map(|item| match item {
+                Value::U8(byte) => format!("{:02x}", byte),
+                _ => "??".to_string(),
+            })
+            .collect::<String>();
+
+        if items.len() > limit {
+            format!("0x{}... ({} more bytes)", hex, items.len() - limit)
+        } else {
+            format!("0x{}", hex)
+        }
+    }
+
+    // Items of lists, tuples and records, one per line when indenting
+    fn compound(
+        &self,
+        open: &str,
+        close: &str,
+        mut items: Vec<String>,
+        more: usize,
+        level: usize,
+    ) -> String {
+        if more > 0 {
+            items.push(format!("... ({} more items)", more));
+        }
+
+        if items.is_empty() {
+            format!("{}{}", open, close)
+        } else if self.options.indent == 0 {
+            format!("{}{}{}", open, items.join(", "), close)
+        } else {
+            let indent = " ".repeat(self.options.indent * (level + 1));
+            let items = items
+                .iter()
+                .map(|item| format!("{}{}", indent, item))
+                .collect::<Vec<_>>()
+                .join(",\n");
+            format!(
+                "{}\n{}\n{}{}",
+                open,
+                items,
+                " ".repeat(self.options.indent * level),
+                close
+            )
+        }
+    }
+}
+
+fn float(value: f64, printed: String) -> String {
+    if value.is_nan() {
+        "nan".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "inf" } else { "-inf" }.to_string()
+    } else {
+        printed
+    }
+}
+
+// Labels colliding with the keywords of WAVE are prefixed with `%`
+fn label(name: &str) -> String {
+    match name {
+        "true" | "false" | "some" | "none" | "ok" | "err" | "inf" | "nan" => format!("%{}", name),
+        _ => name.to_string(),
+    }
+}
+
+fn escape(value: &str, quote: char) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch == quote => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            ch if ch.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use test_r::test;
+
+    use crate::pretty::{pretty_print_value_and_type, PrettyPrintOptions};
+    use crate::{parse_value_and_type, Value, ValueAndType};
+    use golem_wasm_ast::analysis::analysed_type::{
+        case, field, list, option, record, str, u32, u8, unit_case, variant,
+    };
+
+    fn order() -> ValueAndType {
+        ValueAndType::new(
+            Value::Record(vec![
+                Value::String("apple \"fuji\"".to_string()),
+                Value::List(vec![Value::U32(1), Value::U32(2), Value::U32(3)]),
+                Value::List((0..10).map(Value::U8).collect()),
+                Value::Option(Some(Box::new(Value::Variant {
+                    case_idx: 1,
+                    case_value: Some(Box::new(Value::String("boom".to_string()))),
+                }))),
+            ]),
+            record(vec![
+                field("name", str()),
+                field("quantities", list(u32())),
+                field("payload", list(u8())),
+                field(
+                    "status",
+                    option(variant(vec![unit_case("ok"), case("failed", str())])),
+                ),
+            ]),
+        )
+    }
+
+    #[test]
+    fn values_are_printed_with_type_annotations_and_truncated() {
+        let options = PrettyPrintOptions {
+            max_list_items: Some(2),
+            max_bytes: Some(4),
+            ..PrettyPrintOptions::default()
+        };
+
+        assert_eq!(
+            pretty_print_value_and_type(&order(), &options),
+            r#"record {
+  name: string "apple \"fuji\"",
+  quantities: list<u32> [
+    1,
+    2,
+    ... (1 more items)
+  ],
+  payload: list<u8> 0x00010203... (6 more bytes),
+  status: option<variant> some(failed(string "boom"))
+}"#
+        );
+    }
+
+    #[test]
+    fn values_are_printed_as_wave() {
+        let printed = pretty_print_value_and_type(&order(), &PrettyPrintOptions::wave());
+
+        assert_eq!(
+            printed,
+            r#"{name: "apple \"fuji\"", quantities: [1, 2, 3], payload: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9], status: some(failed("boom"))}"#
+        );
+        assert_eq!(parse_value_and_type(&order().typ, &printed), Ok(order()));
+    }
+}
@@ -72,6 +72,28 @@ impl TryFrom<ValueAndType> for crate::protobuf::TypeAnnotatedValue {
     }
 }
 
+impl From<ValueAndType> for crate::protobuf::ValueAndType {
+    fn from(value_and_type: ValueAndType) -> Self {
+        crate::protobuf::ValueAndType {
+            value: Some(value_and_type.value.into()),
+            typ: Some((&value_and_type.typ).into()),
+        }
+    }
+}
+
+impl TryFrom<crate::protobuf::ValueAndType> for ValueAndType {
+    type Error = String;
+
+    fn try_from(value_and_type: crate::protobuf::ValueAndType) -> Result<Self, Self::Error> {
+        let value: Value = value_and_type
+            .value
+            .ok_or("Missing value field")?
+            .try_into()?;
+        let typ: AnalysedType = (&value_and_type.typ.ok_or("Missing typ field")?).try_into()?;
+        Ok(Self::new(value, typ))
+    }
+}
+
 pub trait TypeAnnotatedValueConstructors: Sized {
     fn create<T: Into<Type>>(value: &Value, typ: T) -> Result<Self, Vec<String>>;
 }
@@ -409,9 +431,12 @@ mod tests {
     use test_r::test;
 
     use crate::protobuf::type_annotated_value::TypeAnnotatedValue;
-    use crate::{TypeAnnotatedValueConstructors, Value};
-    use golem_wasm_ast::analysis::analysed_type::u32;
+    use crate::{TypeAnnotatedValueConstructors, Value, ValueAndType};
+    use golem_wasm_ast::analysis::analysed_type::{
+        case, chr, field, flags, handle, record, u32, unit_case, variant,
+    };
     use golem_wasm_ast::analysis::protobuf::{r#type, PrimitiveType, TypePrimitive};
+    use golem_wasm_ast::analysis::{AnalysedResourceId, AnalysedResourceMode};
 
     #[test]
     fn test_type_annotated_value_from_analysed_type() {
@@ -438,4 +463,39 @@ mod tests {
 
         assert_eq!(result, Ok(expected));
     }
+
+    #[test]
+    fn test_value_and_type_protobuf_round_trip() {
+        let value_and_type = ValueAndType::new(
+            Value::Record(vec![
+                Value::Char('λ'),
+                Value::Flags(vec![true, false, true]),
+                Value::Variant {
+                    case_idx: 1,
+                    case_value: Some(Box::new(Value::U32(7))),
+                },
+                Value::Handle {
+                    uri: "urn:worker:component/worker".to_string(),
+                    resource_id: 42,
+                },
+            ]),
+            record(vec![
+                field("initial", chr()),
+                field("permissions", flags(&["read", "write", "execute"])),
+                field(
+                    "state",
+                    variant(vec![unit_case("idle"), case("running", u32())]),
+                ),
+                field(
+                    "session",
+                    handle(AnalysedResourceId(3), AnalysedResourceMode::Owned),
+                ),
+            ]),
+        );
+
+        let protobuf: crate::protobuf::ValueAndType = value_and_type.clone().into();
+        let round_trip: ValueAndType = protobuf.try_into().unwrap();
+
+        assert_eq!(round_trip, value_and_type);
+    }
 }